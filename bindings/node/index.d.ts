@@ -23,6 +23,23 @@ export interface Lite3Frame {
   flags: number
   payload: Buffer
 }
+export interface AppendOptions {
+  durability?: Durability
+  timestampNs?: bigint
+  idempotencyKey?: string
+  tags?: Array<string>
+}
+export interface AbiInfo {
+  /** ABI the addon was built against, as "major.minor". */
+  addonAbiVersion: string
+  /** ABI of the loaded libplasmite; null when it predates `plsm_abi_version`. */
+  libraryAbiVersion?: string
+  libraryVersion?: string
+  /** Same major and a library minor at least the addon's. */
+  compatible: boolean
+}
+/** ABI handshake between this addon and the libplasmite it loaded; call before anything else. */
+export declare function abiInfo(): AbiInfo
 export declare class Client {
   constructor(poolDir: string)
   createPool(poolRef: string, sizeBytes: number | bigint): Pool
@@ -31,9 +48,13 @@ export declare class Client {
 }
 export declare class Pool {
   appendJson(payload: Buffer, tags: Array<string>, durability: Durability): Buffer
+  appendJsonWithOptions(payload: Buffer, options: AppendOptions): Buffer
   appendLite3(payload: Buffer, durability: Durability): bigint
   getJson(seq: number | bigint): Buffer
   getLite3(seq: number | bigint): Lite3Frame
+  infoJson(): Buffer
+  saveCursor(name: string, seq: number | bigint): void
+  loadCursor(name: string): bigint | null
   openStream(sinceSeq?: number | bigint | undefined | null, maxMessages?: number | bigint | undefined | null, timeoutMs?: number | bigint | undefined | null): Stream
   openLite3Stream(sinceSeq?: number | bigint | undefined | null, maxMessages?: number | bigint | undefined | null, timeoutMs?: number | bigint | undefined | null): Lite3Stream
   close(): void
@@ -1,9 +1,12 @@
 /*
 Purpose: JavaScript entry point for the Plasmite Node binding.
-Key Exports: Client, Pool, Message, Stream, Durability, ErrorKind, replay.
+Key Exports: Client, Pool, Message, Stream, Durability, ErrorKind, replay, nativeInfo.
 Role: Thin wrapper around the native N-API addon.
 Invariants: Exports align with native symbols and v0 API semantics.
+Invariants: The addon is used only after its ABI handshake with libplasmite passes.
 Notes: Requires libplasmite to be discoverable at runtime.
+Notes: Addon lookup: PLASMITE_NODE_ADDON, then the prebuilt native/<platform>/index.node, then a
+local `npm run build` output (./index.node).
 */
 
 const { RemoteClient, RemoteError, RemotePool } = require("./remote");
@@ -11,6 +14,7 @@ const { Message, parseMessage } = require("./message");
 const { ERROR_KIND_VALUES, mapErrorKind } = require("./mappings");
 
 const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+const fs = require("node:fs");
 const path = require("node:path");
 const os = require("node:os");
 
@@ -29,24 +33,53 @@ function resolvePlatformDir() {
 }
 
 function resolveNativeAddonPath() {
+  if (process.env.PLASMITE_NODE_ADDON) {
+    return path.resolve(process.env.PLASMITE_NODE_ADDON);
+  }
   const platformDir = resolvePlatformDir();
-  if (!platformDir) {
-    return null;
+  const prebuilt = platformDir ? path.join(__dirname, "native", platformDir, "index.node") : null;
+  if (prebuilt && fs.existsSync(prebuilt)) {
+    return prebuilt;
+  }
+  const localBuild = path.join(__dirname, "index.node");
+  if (fs.existsSync(localBuild)) {
+    return localBuild;
+  }
+  return prebuilt;
+}
+
+// Refuse an addon whose libplasmite speaks a different ABI, before any call can crash on it.
+function checkNativeAbi(addon) {
+  if (typeof addon.abiInfo !== "function") {
+    throw new Error("native addon predates the ABI handshake; rebuild it with `npm run build`");
+  }
+  const info = addon.abiInfo();
+  if (!info.compatible) {
+    const library = info.libraryAbiVersion
+      ? `libplasmite ${info.libraryVersion ?? "(unknown version)"} has ABI ${info.libraryAbiVersion}`
+      : "libplasmite predates plsm_abi_version";
+    throw new Error(
+      `${library}, but this addon needs ABI ${info.addonAbiVersion} (same major, equal or newer minor); ` +
+        "install matching plasmite and libplasmite versions",
+    );
   }
-  return path.join(__dirname, "native", platformDir, "index.node");
+  return info;
 }
 
 const DEFAULT_POOL_DIR = path.join(os.homedir(), ".plasmite", "pools");
 const DEFAULT_POOL_SIZE = 1024 * 1024;
 const DEFAULT_POOL_SIZE_BYTES = DEFAULT_POOL_SIZE;
 let native = null;
+let nativeAbi = null;
 let nativeLoadError = null;
 let nativeAddonPath = null;
 
 try {
   nativeAddonPath = resolveNativeAddonPath();
   if (nativeAddonPath) {
-    native = require(nativeAddonPath);
+    const addon = require(nativeAddonPath);
+    nativeAbi = checkNativeAbi(addon);
+    native = addon;
   } else {
     nativeLoadError = new Error(`unsupported platform: ${process.platform}-${process.arch}`);
   }
@@ -129,6 +162,24 @@ function isNativeNotFoundError(err) {
   return err.kind === ErrorKind.NotFound;
 }
 
+function isAppendOptions(value) {
+  return value !== null && typeof value === "object" && !Array.isArray(value);
+}
+
+function toNativeAppendOptions(options) {
+  const native = {
+    durability: options.durability ?? Durability.Fast,
+    tags: options.tags ?? [],
+  };
+  if (options.timestampNs !== undefined && options.timestampNs !== null) {
+    native.timestampNs = BigInt(options.timestampNs);
+  }
+  if (options.idempotencyKey !== undefined && options.idempotencyKey !== null) {
+    native.idempotencyKey = String(options.idempotencyKey);
+  }
+  return native;
+}
+
 class Client {
   /**
    * Create a local client bound to a pool directory.
@@ -213,17 +264,23 @@ class Pool {
 
   /**
    * Append JSON bytes and return raw message bytes.
+   *
+   * Accepts either positional `(payload, tags, durability)` or
+   * `(payload, { tags, durability, timestampNs, idempotencyKey })`.
    * @param {unknown} payload
-   * @param {string[]} [tags]
+   * @param {string[]|import("./types").AppendOptions} [tagsOrOptions]
    * @param {number} [durability]
    * @returns {Buffer}
    */
-  appendJson(payload, tags, durability) {
+  appendJson(payload, tagsOrOptions, durability) {
     const input = Buffer.isBuffer(payload)
       ? payload
       : Buffer.from(JSON.stringify(payload));
     try {
-      return this._inner.appendJson(input, tags ?? [], durability ?? Durability.Fast);
+      if (isAppendOptions(tagsOrOptions)) {
+        return this._inner.appendJsonWithOptions(input, toNativeAppendOptions(tagsOrOptions));
+      }
+      return this._inner.appendJson(input, tagsOrOptions ?? [], durability ?? Durability.Fast);
     } catch (err) {
       throw wrapNativeError(err);
     }
@@ -232,12 +289,12 @@ class Pool {
   /**
    * Append payload and return parsed Message.
    * @param {unknown} payload
-   * @param {string[]} [tags]
+   * @param {string[]|import("./types").AppendOptions} [tagsOrOptions]
    * @param {number} [durability]
    * @returns {Message}
    */
-  append(payload, tags, durability) {
-    return parseMessage(this.appendJson(payload, tags, durability));
+  append(payload, tagsOrOptions, durability) {
+    return parseMessage(this.appendJson(payload, tagsOrOptions, durability));
   }
 
   /**
@@ -289,6 +346,56 @@ class Pool {
     }
   }
 
+  /**
+   * Get raw pool-info JSON bytes.
+   * @returns {Buffer}
+   */
+  infoJson() {
+    try {
+      return this._inner.infoJson();
+    } catch (err) {
+      throw wrapNativeError(err);
+    }
+  }
+
+  /**
+   * Get pool metadata: sizes, index stats, bounds, and metrics.
+   * @returns {import("./types").PoolInfo}
+   */
+  info() {
+    return JSON.parse(this.infoJson().toString("utf8"));
+  }
+
+  /**
+   * Save a named consumer cursor: the last seq that consumer finished.
+   * Stored beside the pool file, so it survives restarts and is shared by
+   * every process that opens the pool.
+   * @param {string} name
+   * @param {number|bigint} seq
+   * @returns {void}
+   */
+  saveCursor(name, seq) {
+    try {
+      this._inner.saveCursor(name, seq);
+    } catch (err) {
+      throw wrapNativeError(err);
+    }
+  }
+
+  /**
+   * Load a named consumer cursor; `null` when it was never saved.
+   * Resume with `tail({ sinceSeq: seq + 1n })`.
+   * @param {string} name
+   * @returns {bigint|null}
+   */
+  loadCursor(name) {
+    try {
+      return this._inner.loadCursor(name);
+    } catch (err) {
+      throw wrapNativeError(err);
+    }
+  }
+
   /**
    * Open a raw JSON stream.
    * @param {number|bigint|null} sinceSeq
@@ -633,6 +740,22 @@ async function* replay(pool, options = {}) {
   yield* pool.replay(options);
 }
 
+/**
+ * Versions of the loaded native addon and libplasmite; throws when the addon is unavailable
+ * or failed its ABI handshake.
+ */
+function nativeInfo() {
+  if (!native) {
+    throw makeNativeUnavailableError();
+  }
+  return {
+    addonPath: nativeAddonPath,
+    addonAbiVersion: nativeAbi.addonAbiVersion,
+    libraryAbiVersion: nativeAbi.libraryAbiVersion,
+    libraryVersion: nativeAbi.libraryVersion,
+  };
+}
+
 module.exports = {
   Client,
   Pool,
@@ -648,6 +771,7 @@ module.exports = {
   RemoteClient,
   RemoteError,
   RemotePool,
+  nativeInfo,
   parseMessage,
   replay,
 };
@@ -1,6 +1,6 @@
 /*
 Purpose: Provide a Node N-API binding over the libplasmite C ABI.
-Key Exports: Client, Pool, Stream, Durability, ErrorKind.
+Key Exports: Client, Pool, Stream, Durability, ErrorKind, abiInfo.
 Role: Official Node/TypeScript binding that mirrors the v0 API contract.
 Invariants: Calls into C ABI only; JSON bytes in/out; explicit Close methods.
 Invariants: Errors include stable kinds and context in message text.
@@ -40,6 +40,14 @@ struct plsm_buf_t {
     len: usize,
 }
 
+#[repr(C)]
+struct plsm_append_options_t {
+    durability: u32,
+    has_timestamp: u32,
+    timestamp_ns: u64,
+    idempotency_key: *const c_char,
+}
+
 #[repr(C)]
 struct plsm_lite3_frame_t {
     seq: u64,
@@ -57,6 +65,7 @@ struct plsm_error_t {
     offset: u64,
     has_seq: u8,
     has_offset: u8,
+    json: *mut c_char,
 }
 
 unsafe extern "C" {
@@ -93,6 +102,17 @@ unsafe extern "C" {
         out_err: *mut *mut plsm_error_t,
     ) -> c_int;
 
+    fn plsm_pool_append_json_ex(
+        pool: *mut plsm_pool_t,
+        json_bytes: *const u8,
+        json_len: usize,
+        tags: *const *const c_char,
+        tags_len: usize,
+        options: *const plsm_append_options_t,
+        out_message: *mut plsm_buf_t,
+        out_err: *mut *mut plsm_error_t,
+    ) -> c_int;
+
     fn plsm_pool_append_lite3(
         pool: *mut plsm_pool_t,
         payload: *const u8,
@@ -109,6 +129,12 @@ unsafe extern "C" {
         out_err: *mut *mut plsm_error_t,
     ) -> c_int;
 
+    fn plsm_pool_info(
+        pool: *mut plsm_pool_t,
+        out_json: *mut plsm_buf_t,
+        out_err: *mut *mut plsm_error_t,
+    ) -> c_int;
+
     fn plsm_pool_get_lite3(
         pool: *mut plsm_pool_t,
         seq: u64,
@@ -116,6 +142,21 @@ unsafe extern "C" {
         out_err: *mut *mut plsm_error_t,
     ) -> c_int;
 
+    fn plsm_cursor_save(
+        pool: *mut plsm_pool_t,
+        name: *const c_char,
+        seq: u64,
+        out_err: *mut *mut plsm_error_t,
+    ) -> c_int;
+
+    fn plsm_cursor_load(
+        pool: *mut plsm_pool_t,
+        name: *const c_char,
+        out_seq: *mut u64,
+        out_found: *mut u32,
+        out_err: *mut *mut plsm_error_t,
+    ) -> c_int;
+
     fn plsm_stream_open(
         pool: *mut plsm_pool_t,
         since_seq: u64,
@@ -160,6 +201,96 @@ unsafe extern "C" {
     fn plsm_error_free(err: *mut plsm_error_t);
 }
 
+/// ABI this addon was built against; must match `PLSM_ABI_VERSION_*` in include/plasmite.h.
+const ABI_VERSION_MAJOR: u32 = 0;
+const ABI_VERSION_MINOR: u32 = 1;
+
+// Windows import libraries resolve every symbol at load time, so a direct call is safe there.
+#[cfg(not(unix))]
+unsafe extern "C" {
+    fn plsm_abi_version() -> u32;
+    fn plsm_version() -> *const c_char;
+}
+
+/// Look up `name` in the loaded libplasmite without binding to it, so a library too old to
+/// export it yields null here instead of aborting the process at the first call.
+#[cfg(unix)]
+fn library_symbol(name: &CStr) -> *mut libc::c_void {
+    unsafe {
+        let mut info: libc::Dl_info = std::mem::zeroed();
+        if libc::dladdr(plsm_client_new as *const libc::c_void, &mut info) == 0 || info.dli_fname.is_null() {
+            return ptr::null_mut();
+        }
+        let handle = libc::dlopen(info.dli_fname, libc::RTLD_LAZY | libc::RTLD_NOLOAD);
+        if handle.is_null() {
+            return ptr::null_mut();
+        }
+        // The addon itself keeps the library loaded, so the symbol outlives this handle.
+        let symbol = libc::dlsym(handle, name.as_ptr());
+        libc::dlclose(handle);
+        symbol
+    }
+}
+
+#[cfg(unix)]
+fn library_abi_version() -> Option<u32> {
+    let symbol = library_symbol(c"plsm_abi_version");
+    if symbol.is_null() {
+        return None;
+    }
+    let abi_version: unsafe extern "C" fn() -> u32 = unsafe { std::mem::transmute(symbol) };
+    Some(unsafe { abi_version() })
+}
+
+#[cfg(not(unix))]
+fn library_abi_version() -> Option<u32> {
+    Some(unsafe { plsm_abi_version() })
+}
+
+#[cfg(unix)]
+fn library_version() -> Option<String> {
+    let symbol = library_symbol(c"plsm_version");
+    if symbol.is_null() {
+        return None;
+    }
+    let version: unsafe extern "C" fn() -> *const c_char = unsafe { std::mem::transmute(symbol) };
+    Some(unsafe { CStr::from_ptr(version()) }.to_string_lossy().into_owned())
+}
+
+#[cfg(not(unix))]
+fn library_version() -> Option<String> {
+    Some(unsafe { CStr::from_ptr(plsm_version()) }.to_string_lossy().into_owned())
+}
+
+#[napi(object)]
+pub struct AbiInfo {
+    /// ABI the addon was built against, as "major.minor".
+    #[napi(js_name = "addonAbiVersion")]
+    pub addon_abi_version: String,
+    /// ABI of the loaded libplasmite; null when it predates `plsm_abi_version`.
+    #[napi(js_name = "libraryAbiVersion")]
+    pub library_abi_version: Option<String>,
+    #[napi(js_name = "libraryVersion")]
+    pub library_version: Option<String>,
+    /// Same major and a library minor at least the addon's.
+    pub compatible: bool,
+}
+
+/// ABI handshake between this addon and the libplasmite it loaded; call before anything else.
+#[napi]
+pub fn abi_info() -> AbiInfo {
+    let library = library_abi_version();
+    let compatible = library.is_some_and(|version| {
+        version >> 16 == ABI_VERSION_MAJOR && version & 0xffff >= ABI_VERSION_MINOR
+    });
+    AbiInfo {
+        addon_abi_version: format!("{ABI_VERSION_MAJOR}.{ABI_VERSION_MINOR}"),
+        library_abi_version: library.map(|version| format!("{}.{}", version >> 16, version & 0xffff)),
+        library_version: library_version(),
+        compatible,
+    }
+}
+
 #[napi]
 #[derive(Debug, PartialEq, Eq)]
 pub enum Durability {
@@ -253,6 +384,16 @@ pub struct Lite3Frame {
     pub payload: Buffer,
 }
 
+#[napi(object)]
+pub struct AppendOptions {
+    pub durability: Option<Durability>,
+    #[napi(js_name = "timestampNs")]
+    pub timestamp_ns: Option<BigInt>,
+    #[napi(js_name = "idempotencyKey")]
+    pub idempotency_key: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
 #[napi]
 impl Pool {
     #[napi]
@@ -278,6 +419,47 @@ impl Pool {
         Ok(copy_and_free_buf(out))
     }
 
+    #[napi]
+    pub fn append_json_with_options(&self, payload: Buffer, options: AppendOptions) -> Result<Buffer> {
+        let tags = options.tags.unwrap_or_default();
+        let c_tags = CStringArray::new(&tags)?;
+        let timestamp_ns = options
+            .timestamp_ns
+            .map(|value| to_u64(Either::B(value), "timestampNs"))
+            .transpose()?;
+        let idempotency_key = options
+            .idempotency_key
+            .map(|key| {
+                CString::new(key)
+                    .map_err(|_| Error::new(Status::InvalidArg, "idempotencyKey contains NUL"))
+            })
+            .transpose()?;
+        let c_options = plsm_append_options_t {
+            durability: options.durability.unwrap_or(Durability::Fast) as u32,
+            has_timestamp: timestamp_ns.is_some() as u32,
+            timestamp_ns: timestamp_ns.unwrap_or(0),
+            idempotency_key: idempotency_key.as_ref().map_or(ptr::null(), |key| key.as_ptr()),
+        };
+        let mut out = plsm_buf_t { data: ptr::null_mut(), len: 0 };
+        let mut err = ptr::null_mut();
+        let rc = unsafe {
+            plsm_pool_append_json_ex(
+                self.ptr,
+                payload.as_ptr(),
+                payload.len(),
+                c_tags.as_ptr(),
+                tags.len(),
+                &c_options,
+                &mut out,
+                &mut err,
+            )
+        };
+        if rc != 0 {
+            return Err(take_error(err));
+        }
+        Ok(copy_and_free_buf(out))
+    }
+
     #[napi]
     pub fn append_lite3(&self, payload: Buffer, durability: Durability) -> Result<BigInt> {
         let mut seq = 0u64;
@@ -310,6 +492,17 @@ impl Pool {
         Ok(copy_and_free_buf(out))
     }
 
+    #[napi]
+    pub fn info_json(&self) -> Result<Buffer> {
+        let mut out = plsm_buf_t { data: ptr::null_mut(), len: 0 };
+        let mut err = ptr::null_mut();
+        let rc = unsafe { plsm_pool_info(self.ptr, &mut out, &mut err) };
+        if rc != 0 {
+            return Err(take_error(err));
+        }
+        Ok(copy_and_free_buf(out))
+    }
+
     #[napi]
     pub fn get_lite3(&self, seq: Either<u32, BigInt>) -> Result<Lite3Frame> {
         let seq = to_u64(seq, "seq")?;
@@ -327,6 +520,31 @@ impl Pool {
         Ok(copy_and_free_lite3_frame(out))
     }
 
+    #[napi]
+    pub fn save_cursor(&self, name: String, seq: Either<u32, BigInt>) -> Result<()> {
+        let name = CString::new(name).map_err(|_| Error::new(Status::InvalidArg, "name contains NUL"))?;
+        let seq = to_u64(seq, "seq")?;
+        let mut err = ptr::null_mut();
+        let rc = unsafe { plsm_cursor_save(self.ptr, name.as_ptr(), seq, &mut err) };
+        if rc != 0 {
+            return Err(take_error(err));
+        }
+        Ok(())
+    }
+
+    #[napi]
+    pub fn load_cursor(&self, name: String) -> Result<Option<BigInt>> {
+        let name = CString::new(name).map_err(|_| Error::new(Status::InvalidArg, "name contains NUL"))?;
+        let mut seq = 0u64;
+        let mut found = 0u32;
+        let mut err = ptr::null_mut();
+        let rc = unsafe { plsm_cursor_load(self.ptr, name.as_ptr(), &mut seq, &mut found, &mut err) };
+        if rc != 0 {
+            return Err(take_error(err));
+        }
+        Ok((found != 0).then(|| BigInt::from(seq)))
+    }
+
     #[napi]
     pub fn open_stream(
         &self,
@@ -210,7 +210,8 @@ class RemotePool {
 
   /**
    * Tail remote messages as an async iterable.
-   * @param {{sinceSeq?: number|bigint, maxMessages?: number|bigint, timeoutMs?: number, tags?: string[]}} [options]
+   * `minSeq` (an appended message's seq) holds the stream until the pool contains it.
+   * @param {{sinceSeq?: number|bigint, minSeq?: number|bigint, maxMessages?: number|bigint, timeoutMs?: number, tags?: string[]}} [options]
    * @returns {AsyncGenerator<import("./message").Message, void, unknown>}
    */
   async *tail(options = {}) {
@@ -218,6 +219,9 @@ class RemotePool {
     if (options.sinceSeq !== undefined) {
       url.searchParams.set("since_seq", String(options.sinceSeq));
     }
+    if (options.minSeq !== undefined) {
+      url.searchParams.set("min_seq", String(options.minSeq));
+    }
     if (options.maxMessages !== undefined) {
       url.searchParams.set("max", String(options.maxMessages));
     }
@@ -244,7 +248,12 @@ class RemotePool {
           continue;
         }
         const raw = Buffer.from(line, "utf8");
-        yield messageFromEnvelope(JSON.parse(line), raw);
+        const envelope = JSON.parse(line);
+        if (envelope && envelope.error) {
+          // Terminal error line, e.g. a minSeq that did not arrive before the timeout.
+          throw new RemoteError(envelope, response.status);
+        }
+        yield messageFromEnvelope(envelope, raw);
       }
     } finally {
       controller.abort();
@@ -19,6 +19,7 @@ const {
   Durability,
   ErrorKind,
   Pool,
+  nativeInfo,
   parseMessage,
   PlasmiteNativeError,
   RemoteClient,
@@ -119,6 +120,68 @@ test("append/get aliases and parseMessage helper round-trip JSON", () => {
   });
 });
 
+test("append options set timestamp and dedupe by idempotency key", () => {
+  withPool("append-options", ({ pool }) => {
+    const timestampNs = 1_700_000_000_000_000_000n;
+    const first = pool.append(
+      { kind: "import" },
+      { tags: ["alpha"], durability: Durability.Flush, timestampNs, idempotencyKey: "row-1" },
+    );
+    assert.equal(first.time.toISOString(), "2023-11-14T22:13:20.000Z");
+    assert.deepEqual(first.meta.tags, ["alpha"]);
+
+    const retry = pool.append({ kind: "import" }, { idempotencyKey: "row-1" });
+    assert.equal(retry.seq, first.seq);
+
+    const other = pool.append({ kind: "import" }, { idempotencyKey: "row-2" });
+    assert.equal(other.seq, first.seq + 1n);
+
+    const positional = pool.append({ kind: "legacy" }, ["beta"], Durability.Fast);
+    assert.deepEqual(positional.meta.tags, ["beta"]);
+  });
+});
+
+test("pool info reports bounds and message count", () => {
+  withPool("info", ({ pool }) => {
+    const empty = pool.info();
+    assert.equal(empty.name, "info");
+    assert.deepEqual(empty.bounds, {});
+    assert.equal(empty.metrics.message_count, 0);
+
+    pool.append({ n: 1 });
+    pool.append({ n: 2 });
+    const info = pool.info();
+    assert.equal(info.bounds.oldest, 1);
+    assert.equal(info.bounds.newest, 2);
+    assert.equal(info.metrics.message_count, 2);
+    assert.equal(info.file_size, TEST_POOL_SIZE_BYTES);
+    assert.ok(Buffer.isBuffer(pool.infoJson()));
+  });
+});
+
+test("named cursors persist beside the pool across handles", () => {
+  withPool("cursors", ({ client, pool, poolDir }) => {
+    assert.equal(pool.loadCursor("indexer"), null);
+    pool.append({ n: 1 });
+    const second = pool.append({ n: 2 });
+    pool.saveCursor("indexer", second.seq);
+
+    const reopened = client.openPool("cursors");
+    try {
+      assert.equal(reopened.loadCursor("indexer"), second.seq);
+    } finally {
+      reopened.close();
+    }
+    const stored = path.join(poolDir, "cursors.plasmite.cursors", "indexer.json");
+    assert.deepEqual(JSON.parse(fs.readFileSync(stored, "utf8")), { seq: Number(second.seq) });
+
+    assert.throws(
+      () => pool.saveCursor("../escape", 1),
+      (err) => err instanceof PlasmiteNativeError && err.kind === ErrorKind.Usage,
+    );
+  });
+});
+
 test("client.pool creates missing pool and reopens existing pool", () => {
   withClient(({ client }) => {
     const first = client.pool("work", TEST_POOL_SIZE_BYTES);
@@ -154,6 +217,31 @@ test("default client creates pool dir in fresh HOME", () => {
   assert.equal(fs.existsSync(expectedPoolPath), true);
 });
 
+test("native ABI handshake reports matching addon and library versions", () => {
+  const info = nativeInfo();
+  assert.equal(info.libraryAbiVersion, info.addonAbiVersion);
+  assert.equal(info.libraryVersion, require("../package.json").version);
+  assert.ok(fs.existsSync(info.addonPath));
+
+  const script = `
+    const { nativeInfo } = require("./index.js");
+    try {
+      nativeInfo();
+      process.exit(2);
+    } catch (err) {
+      process.stdout.write(err.message);
+    }
+  `;
+  const output = childProcess.spawnSync(process.execPath, ["-e", script], {
+    cwd: path.join(__dirname, ".."),
+    env: { ...process.env, PLASMITE_NODE_ADDON: path.join(makeTempDir(), "missing.node") },
+    encoding: "utf8",
+  });
+  assert.equal(output.status, 0, output.stderr || output.stdout);
+  assert.match(output.stdout, /native addon is unavailable/);
+  assert.match(output.stdout, /missing\.node/);
+});
+
 test("tail timeout returns no message and close is safe", () => {
   withPool("tail", ({ pool }) => {
     const stream = pool.openStream(BigInt(9999), BigInt(1), BigInt(10));
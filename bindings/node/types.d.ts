@@ -76,11 +76,17 @@ export class Client {
 
 export class Pool {
   append(payload: unknown, tags?: string[], durability?: Durability): Message
+  append(payload: unknown, options: AppendOptions): Message
   appendJson(payload: unknown, tags?: string[], durability?: Durability): Buffer
+  appendJson(payload: unknown, options: AppendOptions): Buffer
   appendLite3(payload: Buffer, durability?: Durability): bigint
   get(seq: number | bigint): Message
   getJson(seq: number | bigint): Buffer
   getLite3(seq: number | bigint): Lite3Frame
+  info(): PoolInfo
+  infoJson(): Buffer
+  saveCursor(name: string, seq: number | bigint): void
+  loadCursor(name: string): bigint | null
   tail(options?: LocalTailOptions): AsyncGenerator<Message, void, unknown>
   replay(options?: ReplayOptions): AsyncGenerator<Message, void, unknown>
   openStream(
@@ -97,6 +103,49 @@ export class Pool {
   [Symbol.dispose](): void
 }
 
+export interface AppendOptions {
+  tags?: string[]
+  durability?: Durability
+  /** Explicit message timestamp (ns since epoch); defaults to now. */
+  timestampNs?: number | bigint
+  /** Retries with the same key within the last 248 keyed appends return the original message. */
+  idempotencyKey?: string
+}
+
+export interface PoolInfo {
+  name: string
+  path: string
+  file_size: number
+  index_offset: number
+  index_capacity: number
+  index_size_bytes: number
+  ring_offset: number
+  ring_size: number
+  allocated_bytes?: number
+  max_messages?: number
+  bounds: {
+    oldest?: number
+    newest?: number
+  }
+  metrics?: {
+    message_count: number
+    seq_span: number
+    utilization: {
+      used_bytes: number
+      free_bytes: number
+      used_percent: number
+    }
+    age: {
+      oldest_time: string | null
+      newest_time: string | null
+      oldest_age_ms: number | null
+      newest_age_ms: number | null
+    }
+  }
+  hybrid_timestamps?: boolean
+  codec?: string
+}
+
 export class Stream {
   [Symbol.iterator](): Iterator<Buffer>
   nextJson(): Buffer | null
@@ -133,6 +182,19 @@ export function replay(
   options?: ReplayOptions,
 ): AsyncGenerator<Message, void, unknown>
 
+export interface NativeInfo {
+  /** Addon file in use (prebuilt, local build, or PLASMITE_NODE_ADDON). */
+  addonPath: string
+  /** ABI the addon was built against, as "major.minor". */
+  addonAbiVersion: string
+  /** ABI of the loaded libplasmite, as "major.minor". */
+  libraryAbiVersion: string
+  /** libplasmite release version, e.g. "0.6.1". */
+  libraryVersion: string
+}
+
+export function nativeInfo(): NativeInfo
+
 export interface RemoteClientOptions {
   token?: string
 }
@@ -152,6 +214,7 @@ export interface RemotePoolInfo {
 
 export interface RemoteTailOptions {
   sinceSeq?: number | bigint
+  minSeq?: number | bigint
   maxMessages?: number | bigint
   timeoutMs?: number
 }
@@ -0,0 +1,351 @@
+/*
+Purpose: Implement the Plasmite v0 remote protocol in portable TypeScript.
+Key Exports: RemoteClient, RemotePool, RemoteError, Message, ErrorKind.
+Role: Remote-only client for runtimes that cannot load the N-API addon (Deno, Bun, browsers, edge).
+Invariants: Uses only web-standard APIs (fetch, URL, ReadableStream, TextDecoder, AbortController).
+Invariants: Request/response shapes follow spec/remote/v0/SPEC.md; tail is JSONL.
+Invariants: Resumed tails reconnect with `since_seq = last + 1` and drop any seq already yielded.
+Notes: Hand-written against the spec; update alongside spec/remote/v0 when the protocol changes.
+*/
+
+export type ErrorKind =
+  | "Internal"
+  | "Usage"
+  | "NotFound"
+  | "AlreadyExists"
+  | "Busy"
+  | "Permission"
+  | "Corrupt"
+  | "Io";
+
+const ERROR_KINDS: readonly ErrorKind[] = [
+  "Internal",
+  "Usage",
+  "NotFound",
+  "AlreadyExists",
+  "Busy",
+  "Permission",
+  "Corrupt",
+  "Io",
+];
+
+export type Durability = "fast" | "flush";
+
+export interface MessageMeta {
+  readonly tags: readonly string[];
+}
+
+export interface MessageEnvelope {
+  seq: number | string;
+  time: string;
+  data: unknown;
+  meta?: { tags?: unknown[] };
+}
+
+export interface PoolInfo {
+  [key: string]: unknown;
+}
+
+export class Message {
+  readonly seq: bigint;
+  readonly time: Date;
+  readonly timeRfc3339: string;
+  readonly data: unknown;
+  readonly meta: MessageMeta;
+
+  constructor(envelope: MessageEnvelope) {
+    if (!envelope || typeof envelope !== "object") {
+      throw new TypeError("message envelope must be an object");
+    }
+    this.seq = BigInt(envelope.seq);
+    this.timeRfc3339 = String(envelope.time);
+    this.time = new Date(this.timeRfc3339);
+    if (!Number.isFinite(this.time.getTime())) {
+      throw new TypeError("message time must be RFC3339");
+    }
+    const tags = Array.isArray(envelope.meta?.tags) ? envelope.meta.tags.map(String) : [];
+    this.data = envelope.data;
+    this.meta = Object.freeze({ tags: Object.freeze(tags) });
+  }
+
+  get tags(): readonly string[] {
+    return this.meta.tags;
+  }
+}
+
+export class RemoteError extends Error {
+  readonly kind: ErrorKind;
+  readonly status: number;
+  readonly hint?: string;
+  readonly path?: string;
+  readonly seq?: number;
+  readonly offset?: number;
+
+  constructor(payload: unknown, status: number) {
+    const body = (payload ?? {}) as Record<string, unknown>;
+    const error = (body.error ?? body) as Record<string, unknown>;
+    super(typeof error.message === "string" ? error.message : `Remote error ${status}`);
+    this.name = "RemoteError";
+    this.status = status;
+    this.kind = ERROR_KINDS.includes(error.kind as ErrorKind) ? (error.kind as ErrorKind) : "Io";
+    this.hint = typeof error.hint === "string" ? error.hint : undefined;
+    this.path = typeof error.path === "string" ? error.path : undefined;
+    this.seq = typeof error.seq === "number" ? error.seq : undefined;
+    this.offset = typeof error.offset === "number" ? error.offset : undefined;
+  }
+}
+
+export interface RemoteClientOptions {
+  token?: string;
+  /** Override `fetch` (for tests or custom transports). */
+  fetch?: typeof fetch;
+}
+
+export interface TailOptions {
+  sinceSeq?: number | bigint;
+  maxMessages?: number;
+  timeoutMs?: number;
+  tags?: string[];
+  /** Reconnect after transport failures, resuming after the last yielded seq. */
+  resume?: boolean;
+  /** Delay between reconnect attempts (default 500ms). */
+  retryDelayMs?: number;
+  /** Give up after this many consecutive failed reconnects (default unlimited). */
+  maxRetries?: number;
+  signal?: AbortSignal;
+}
+
+export class RemoteClient {
+  readonly baseUrl: URL;
+  token: string | null;
+  private readonly fetchImpl: typeof fetch;
+
+  constructor(baseUrl: string, options: RemoteClientOptions = {}) {
+    this.baseUrl = normalizeBaseUrl(baseUrl);
+    this.token = options.token ?? null;
+    this.fetchImpl = options.fetch ?? ((input, init) => fetch(input, init));
+  }
+
+  withToken(token: string): this {
+    this.token = token;
+    return this;
+  }
+
+  async createPool(pool: string, sizeBytes: number): Promise<PoolInfo> {
+    const data = await this.requestJson("POST", this.url(["v0", "pools"]), {
+      pool,
+      size_bytes: sizeBytes,
+    });
+    return data.pool as PoolInfo;
+  }
+
+  async openPool(pool: string): Promise<RemotePool> {
+    await this.requestJson("POST", this.url(["v0", "pools", "open"]), { pool });
+    return new RemotePool(this, pool);
+  }
+
+  async poolInfo(pool: string): Promise<PoolInfo> {
+    const data = await this.requestJson("GET", this.url(["v0", "pools", pool, "info"]));
+    return data.pool as PoolInfo;
+  }
+
+  async listPools(): Promise<PoolInfo[]> {
+    const data = await this.requestJson("GET", this.url(["v0", "pools"]));
+    return data.pools as PoolInfo[];
+  }
+
+  async deletePool(pool: string): Promise<void> {
+    await this.requestJson("DELETE", this.url(["v0", "pools", pool]));
+  }
+
+  /** @internal */
+  url(segments: string[]): URL {
+    const url = new URL(this.baseUrl.toString());
+    url.pathname = `/${segments.map(encodeURIComponent).join("/")}`;
+    return url;
+  }
+
+  /** @internal */
+  async requestJson(
+    method: string,
+    url: URL,
+    body?: unknown,
+  ): Promise<Record<string, unknown>> {
+    const headers: Record<string, string> = { Accept: "application/json" };
+    if (this.token) {
+      headers.Authorization = `Bearer ${this.token}`;
+    }
+    let payload: string | undefined;
+    if (body !== undefined) {
+      headers["Content-Type"] = "application/json";
+      payload = JSON.stringify(body);
+    }
+    const response = await this.fetchImpl(url.toString(), { method, headers, body: payload });
+    if (!response.ok) {
+      throw await parseRemoteError(response);
+    }
+    if (response.status === 204) {
+      return {};
+    }
+    return (await response.json()) as Record<string, unknown>;
+  }
+
+  /** @internal */
+  async requestStream(url: URL, signal: AbortSignal): Promise<Response> {
+    const headers: Record<string, string> = { Accept: "application/jsonl" };
+    if (this.token) {
+      headers.Authorization = `Bearer ${this.token}`;
+    }
+    const response = await this.fetchImpl(url.toString(), { method: "GET", headers, signal });
+    if (!response.ok) {
+      throw await parseRemoteError(response);
+    }
+    return response;
+  }
+}
+
+export class RemotePool {
+  constructor(readonly client: RemoteClient, readonly pool: string) {}
+
+  poolRef(): string {
+    return this.pool;
+  }
+
+  async append(data: unknown, tags: string[] = [], durability: Durability = "fast"): Promise<Message> {
+    const url = this.client.url(["v0", "pools", this.pool, "append"]);
+    const response = await this.client.requestJson("POST", url, { data, tags, durability });
+    return new Message(response.message as MessageEnvelope);
+  }
+
+  async get(seq: number | bigint): Promise<Message> {
+    const url = this.client.url(["v0", "pools", this.pool, "messages", String(seq)]);
+    const response = await this.client.requestJson("GET", url);
+    return new Message(response.message as MessageEnvelope);
+  }
+
+  /** Stream messages in seq order; with `resume`, survive dropped connections without duplicates. */
+  async *tail(options: TailOptions = {}): AsyncGenerator<Message, void, unknown> {
+    let lastSeq: bigint | null = options.sinceSeq === undefined ? null : BigInt(options.sinceSeq) - 1n;
+    let remaining = options.maxMessages;
+    let failures = 0;
+    while (true) {
+      const url = this.client.url(["v0", "pools", this.pool, "tail"]);
+      if (lastSeq !== null) {
+        url.searchParams.set("since_seq", String(lastSeq + 1n));
+      }
+      if (remaining !== undefined) {
+        url.searchParams.set("max", String(remaining));
+      }
+      if (options.timeoutMs !== undefined) {
+        url.searchParams.set("timeout_ms", String(options.timeoutMs));
+      }
+      for (const tag of options.tags ?? []) {
+        url.searchParams.append("tag", tag);
+      }
+
+      const controller = new AbortController();
+      const abort = () => controller.abort();
+      options.signal?.addEventListener("abort", abort);
+      try {
+        const response = await this.client.requestStream(url, controller.signal);
+        failures = 0;
+        for await (const line of readLines(response)) {
+          const parsed = JSON.parse(line) as Record<string, unknown>;
+          if (parsed.error) {
+            throw new RemoteError(parsed, 500);
+          }
+          const message = new Message(parsed as unknown as MessageEnvelope);
+          if (lastSeq !== null && message.seq <= lastSeq) {
+            continue;
+          }
+          lastSeq = message.seq;
+          yield message;
+          if (remaining !== undefined && --remaining <= 0) {
+            return;
+          }
+        }
+        return;
+      } catch (err) {
+        if (!options.resume || options.signal?.aborted || !isTransportError(err)) {
+          throw err;
+        }
+        failures += 1;
+        if (options.maxRetries !== undefined && failures > options.maxRetries) {
+          throw err;
+        }
+        await sleep(options.retryDelayMs ?? 500);
+      } finally {
+        options.signal?.removeEventListener("abort", abort);
+        controller.abort();
+      }
+    }
+  }
+}
+
+async function* readLines(response: Response): AsyncGenerator<string, void, unknown> {
+  if (!response.body) {
+    throw new RemoteError({ kind: "Io", message: "remote tail response has no body" }, response.status);
+  }
+  const reader = response.body.getReader();
+  const decoder = new TextDecoder();
+  let pending = "";
+  try {
+    while (true) {
+      const { done, value } = await reader.read();
+      pending += done ? decoder.decode() : decoder.decode(value, { stream: true });
+      let newline: number;
+      while ((newline = pending.indexOf("\n")) >= 0) {
+        const line = pending.slice(0, newline).trim();
+        pending = pending.slice(newline + 1);
+        if (line) {
+          yield line;
+        }
+      }
+      if (done) {
+        break;
+      }
+    }
+    if (pending.trim()) {
+      yield pending.trim();
+    }
+  } finally {
+    reader.releaseLock();
+  }
+}
+
+/** Transport failures (network drops, resets) are retryable; server error envelopes are not. */
+function isTransportError(err: unknown): boolean {
+  if (err instanceof RemoteError) {
+    return err.kind === "Busy" || err.status >= 502;
+  }
+  // fetch and stream reads reject with TypeError on network failure in every web runtime.
+  return err instanceof TypeError;
+}
+
+function sleep(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+async function parseRemoteError(response: Response): Promise<RemoteError> {
+  let payload: unknown = null;
+  try {
+    payload = await response.json();
+  } catch {
+    payload = null;
+  }
+  return new RemoteError(payload, response.status);
+}
+
+function normalizeBaseUrl(raw: string): URL {
+  const url = new URL(raw);
+  if (url.protocol !== "http:" && url.protocol !== "https:") {
+    throw new TypeError("remote base URL must use http or https");
+  }
+  if (url.pathname && url.pathname !== "/") {
+    throw new TypeError("remote base URL must not include a path");
+  }
+  url.pathname = "/";
+  url.search = "";
+  url.hash = "";
+  return url;
+}
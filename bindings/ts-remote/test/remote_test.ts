@@ -0,0 +1,95 @@
+/*
+Purpose: Exercise the ts-remote client against a scripted fetch.
+Key Exports: None (Deno test module).
+Role: Protocol-shape regression tests that run without a live server.
+Invariants: Tests never touch the network; fetch is injected per client.
+*/
+
+import { assertEquals, assertRejects } from "jsr:@std/assert@1";
+import { RemoteClient, RemoteError } from "../mod.ts";
+
+type Handler = (url: URL, init: RequestInit) => Response | Promise<Response>;
+
+function client(handler: Handler): RemoteClient {
+  const fetchImpl = ((input: string | URL | Request, init?: RequestInit) =>
+    Promise.resolve(handler(new URL(String(input)), init ?? {}))) as typeof fetch;
+  return new RemoteClient("http://127.0.0.1:9700", { token: "t", fetch: fetchImpl });
+}
+
+function envelope(seq: number, data: unknown): string {
+  return JSON.stringify({ seq, time: "2026-01-01T00:00:00Z", data, meta: { tags: ["a"] } });
+}
+
+function jsonl(lines: string[], failAfter = false): Response {
+  const encoder = new TextEncoder();
+  const body = new ReadableStream<Uint8Array>({
+    start(controller) {
+      for (const line of lines) {
+        controller.enqueue(encoder.encode(`${line}\n`));
+      }
+      if (failAfter) {
+        controller.error(new TypeError("connection reset"));
+      } else {
+        controller.close();
+      }
+    },
+  });
+  return new Response(body, { status: 200 });
+}
+
+Deno.test("append posts data, tags, durability, and bearer token", async () => {
+  let seen: { path: string; auth: string | null; body: unknown } | null = null;
+  const remote = client((url, init) => {
+    if (url.pathname === "/v0/pools/open") {
+      return Response.json({ pool: {} });
+    }
+    seen = {
+      path: url.pathname,
+      auth: new Headers(init.headers).get("Authorization"),
+      body: JSON.parse(String(init.body)),
+    };
+    return Response.json({ message: JSON.parse(envelope(7, { ok: true })) });
+  });
+  const pool = await remote.openPool("events");
+  const message = await pool.append({ ok: true }, ["a"], "flush");
+  assertEquals(message.seq, 7n);
+  assertEquals(message.tags, ["a"]);
+  assertEquals(seen, {
+    path: "/v0/pools/events/append",
+    auth: "Bearer t",
+    body: { data: { ok: true }, tags: ["a"], durability: "flush" },
+  });
+});
+
+Deno.test("error envelopes surface as RemoteError with kind and status", async () => {
+  const remote = client(() =>
+    Response.json({ error: { kind: "NotFound", message: "pool not found", hint: "create it" } }, {
+      status: 404,
+    })
+  );
+  const err = await assertRejects(() => remote.poolInfo("missing"), RemoteError);
+  assertEquals(err.kind, "NotFound");
+  assertEquals(err.status, 404);
+  assertEquals(err.hint, "create it");
+});
+
+Deno.test("resumed tail reconnects after last seq without duplicates", async () => {
+  const tails: string[] = [];
+  const remote = client((url) => {
+    if (url.pathname === "/v0/pools/open") {
+      return Response.json({ pool: {} });
+    }
+    tails.push(url.search);
+    if (tails.length === 1) {
+      return jsonl([envelope(1, 1), envelope(2, 2)], true);
+    }
+    return jsonl([envelope(2, 2), envelope(3, 3)]);
+  });
+  const pool = await remote.openPool("events");
+  const seqs: bigint[] = [];
+  for await (const message of pool.tail({ resume: true, retryDelayMs: 0, maxMessages: 3 })) {
+    seqs.push(message.seq);
+  }
+  assertEquals(seqs, [1n, 2n, 3n]);
+  assertEquals(tails, ["?max=3", "?since_seq=3&max=1"]);
+});
@@ -0,0 +1,351 @@
+/*
+Purpose: Provide a browser (wasm-bindgen) client for pools served by `plasmite serve`.
+Key Exports: RemoteClient, RemotePool, RemoteTail.
+Role: Lets browser dashboards append/get/tail over fetch without a hand-written JS HTTP layer.
+Role: Also runs under Node 18+ (`wasm-pack --target nodejs`) via the global `fetch`.
+Invariants: Speaks the same v0 HTTP/JSON protocol as the Rust and Node remote clients.
+Invariants: Base URL must be http(s) without a path; requests carry an optional bearer token.
+Invariants: Tail streams are JSONL read incrementally from the fetch body; `cancel` aborts the request.
+Notes: Messages are returned as plain JS objects (`{seq, time, meta, data}`), not Lite3 bytes.
+*/
+
+use js_sys::{Array, Function, JSON, Object, Promise, Reflect, Uint8Array};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AbortController, Headers, ReadableStreamDefaultReader, Request, RequestInit, Response, Url,
+    Window, WorkerGlobalScope,
+};
+
+/// HTTP client bound to one `plasmite serve` base URL.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct RemoteClient {
+    base_url: String,
+    token: Option<String>,
+}
+
+#[wasm_bindgen]
+impl RemoteClient {
+    #[wasm_bindgen(constructor)]
+    pub fn new(base_url: &str) -> Result<RemoteClient, JsValue> {
+        let url =
+            Url::new(base_url).map_err(|_| usage_error(format!("invalid base URL: {base_url}")))?;
+        if url.protocol() != "http:" && url.protocol() != "https:" {
+            return Err(usage_error("base URL must use http or https".to_string()));
+        }
+        if !url.pathname().is_empty() && url.pathname() != "/" {
+            return Err(usage_error("base URL must not include a path".to_string()));
+        }
+        Ok(Self {
+            base_url: url.origin(),
+            token: None,
+        })
+    }
+
+    /// Set the bearer token sent with every request.
+    #[wasm_bindgen(js_name = withToken)]
+    pub fn with_token(mut self, token: String) -> RemoteClient {
+        self.token = Some(token);
+        self
+    }
+
+    #[wasm_bindgen(js_name = listPools)]
+    pub async fn list_pools(&self) -> Result<JsValue, JsValue> {
+        let body = self
+            .request_json("GET", &self.url(&["v0", "pools"]), None)
+            .await?;
+        field(&body, "pools")
+    }
+
+    #[wasm_bindgen(js_name = poolInfo)]
+    pub async fn pool_info(&self, pool: String) -> Result<JsValue, JsValue> {
+        let url = self.url(&["v0", "pools", &pool, "info"]);
+        let body = self.request_json("GET", &url, None).await?;
+        field(&body, "pool")
+    }
+
+    /// Open a pool handle; fails with a `NotFound` error if the pool does not exist.
+    #[wasm_bindgen(js_name = openPool)]
+    pub async fn open_pool(&self, pool: String) -> Result<RemotePool, JsValue> {
+        let payload = Object::new();
+        set(&payload, "pool", &JsValue::from_str(&pool))?;
+        self.request_json("POST", &self.url(&["v0", "pools", "open"]), Some(&payload))
+            .await?;
+        Ok(RemotePool {
+            client: self.clone(),
+            pool,
+        })
+    }
+}
+
+impl RemoteClient {
+    fn url(&self, segments: &[&str]) -> String {
+        let path: Vec<String> = segments
+            .iter()
+            .map(|segment| String::from(js_sys::encode_uri_component(segment)))
+            .collect();
+        format!("{}/{}", self.base_url, path.join("/"))
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<&Object>,
+        controller: Option<&AbortController>,
+    ) -> Result<Request, JsValue> {
+        let headers = Headers::new()?;
+        headers.set("Accept", "application/json")?;
+        if let Some(token) = &self.token {
+            headers.set("Authorization", &format!("Bearer {token}"))?;
+        }
+        let init = RequestInit::new();
+        init.set_method(method);
+        if let Some(body) = body {
+            headers.set("Content-Type", "application/json")?;
+            init.set_body(&JSON::stringify(body)?);
+        }
+        if let Some(controller) = controller {
+            init.set_signal(Some(&controller.signal()));
+        }
+        init.set_headers(&headers);
+        Request::new_with_str_and_init(url, &init)
+    }
+
+    async fn send(&self, request: &Request) -> Result<Response, JsValue> {
+        let global = js_sys::global();
+        let promise = if let Some(window) = global.dyn_ref::<Window>() {
+            window.fetch_with_request(request)
+        } else if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() {
+            worker.fetch_with_request(request)
+        } else {
+            global_fetch(&global, request)?
+        };
+        let response: Response = JsFuture::from(promise)
+            .await
+            .map_err(|_| io_error("failed to reach plasmite server"))?
+            .dyn_into()?;
+        if !response.ok() {
+            return Err(remote_error(&response).await);
+        }
+        Ok(response)
+    }
+
+    async fn request_json(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<&Object>,
+    ) -> Result<JsValue, JsValue> {
+        let response = self.send(&self.request(method, url, body, None)?).await?;
+        if response.status() == 204 {
+            return Ok(JsValue::NULL);
+        }
+        JsFuture::from(response.json()?).await
+    }
+}
+
+/// Handle to one remote pool.
+#[wasm_bindgen]
+pub struct RemotePool {
+    client: RemoteClient,
+    pool: String,
+}
+
+#[wasm_bindgen]
+impl RemotePool {
+    #[wasm_bindgen(js_name = poolRef)]
+    pub fn pool_ref(&self) -> String {
+        self.pool.clone()
+    }
+
+    /// Append `data` (any JSON value) and return the committed message.
+    pub async fn append(
+        &self,
+        data: JsValue,
+        tags: Option<Vec<String>>,
+        durability: Option<String>,
+    ) -> Result<JsValue, JsValue> {
+        let payload = Object::new();
+        set(&payload, "data", &data)?;
+        let tags: Array = tags
+            .unwrap_or_default()
+            .into_iter()
+            .map(JsValue::from)
+            .collect();
+        set(&payload, "tags", &tags)?;
+        let durability = durability.unwrap_or_else(|| "fast".to_string());
+        if durability != "fast" && durability != "flush" {
+            return Err(usage_error(format!("invalid durability: {durability}")));
+        }
+        set(&payload, "durability", &JsValue::from_str(&durability))?;
+        let url = self.client.url(&["v0", "pools", &self.pool, "append"]);
+        let body = self
+            .client
+            .request_json("POST", &url, Some(&payload))
+            .await?;
+        field(&body, "message")
+    }
+
+    /// Fetch one message by sequence number.
+    pub async fn get(&self, seq: f64) -> Result<JsValue, JsValue> {
+        let seq = format!("{}", seq as u64);
+        let url = self
+            .client
+            .url(&["v0", "pools", &self.pool, "messages", &seq]);
+        let body = self.client.request_json("GET", &url, None).await?;
+        field(&body, "message")
+    }
+
+    /// Open a tail stream; call `next()` until it resolves to `undefined`.
+    pub async fn tail(
+        &self,
+        since_seq: Option<f64>,
+        max_messages: Option<f64>,
+        timeout_ms: Option<u32>,
+        tags: Option<Vec<String>>,
+    ) -> Result<RemoteTail, JsValue> {
+        let url = Url::new(&self.client.url(&["v0", "pools", &self.pool, "tail"]))?;
+        let params = url.search_params();
+        if let Some(since_seq) = since_seq {
+            params.set("since_seq", &format!("{}", since_seq as u64));
+        }
+        if let Some(max) = max_messages {
+            params.set("max", &format!("{}", max as u64));
+        }
+        if let Some(timeout_ms) = timeout_ms {
+            params.set("timeout_ms", &timeout_ms.to_string());
+        }
+        for tag in tags.unwrap_or_default() {
+            params.append("tag", &tag);
+        }
+        let controller = AbortController::new()?;
+        let request = self
+            .client
+            .request("GET", &url.href(), None, Some(&controller))?;
+        let response = self.client.send(&request).await?;
+        let body = response
+            .body()
+            .ok_or_else(|| io_error("remote tail response has no body"))?;
+        let reader: ReadableStreamDefaultReader = body.get_reader().dyn_into()?;
+        Ok(RemoteTail {
+            reader: Some(reader),
+            controller,
+            pending: Vec::new(),
+        })
+    }
+}
+
+/// Incremental JSONL reader over a tail response body.
+#[wasm_bindgen]
+pub struct RemoteTail {
+    reader: Option<ReadableStreamDefaultReader>,
+    controller: AbortController,
+    pending: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl RemoteTail {
+    /// Next message, or `undefined` once the stream ends (max reached, timeout, or cancel).
+    pub async fn next(&mut self) -> Result<JsValue, JsValue> {
+        loop {
+            if let Some(newline) = self.pending.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = self.pending.drain(..=newline).collect();
+                if let Some(message) = parse_line(&line)? {
+                    return Ok(message);
+                }
+                continue;
+            }
+            let Some(reader) = &self.reader else {
+                let rest = std::mem::take(&mut self.pending);
+                return Ok(parse_line(&rest)?.unwrap_or(JsValue::UNDEFINED));
+            };
+            let chunk = JsFuture::from(reader.read()).await?;
+            if Reflect::get(&chunk, &JsValue::from_str("done"))?.is_truthy() {
+                self.reader = None;
+                continue;
+            }
+            let value = Reflect::get(&chunk, &JsValue::from_str("value"))?;
+            self.pending
+                .extend_from_slice(&value.dyn_into::<Uint8Array>()?.to_vec());
+        }
+    }
+
+    /// Abort the underlying request; subsequent `next()` calls drain buffered lines then end.
+    pub fn cancel(&mut self) {
+        self.controller.abort();
+        self.reader = None;
+    }
+}
+
+/// `globalThis.fetch(request)` for runtimes without `Window`/`WorkerGlobalScope` (Node 18+).
+fn global_fetch(global: &Object, request: &Request) -> Result<Promise, JsValue> {
+    let fetch: Function = Reflect::get(global, &JsValue::from_str("fetch"))?
+        .dyn_into()
+        .map_err(|_| io_error("fetch is not available in this environment"))?;
+    fetch.call1(global, request)?.dyn_into()
+}
+
+fn parse_line(line: &[u8]) -> Result<Option<JsValue>, JsValue> {
+    let text = std::str::from_utf8(line).map_err(|_| io_error("tail line is not valid UTF-8"))?;
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    JSON::parse(text).map(Some)
+}
+
+fn field(body: &JsValue, key: &str) -> Result<JsValue, JsValue> {
+    Reflect::get(body, &JsValue::from_str(key))
+}
+
+fn set(target: &Object, key: &str, value: &JsValue) -> Result<(), JsValue> {
+    Reflect::set(target, &JsValue::from_str(key), value).map(|_| ())
+}
+
+/// Structured error mirroring the Node binding's `RemoteError` (`kind`, `status`, `hint`).
+fn error(kind: &str, message: &str, status: Option<u16>, hint: Option<JsValue>) -> JsValue {
+    let err = js_sys::Error::new(message);
+    err.set_name("RemoteError");
+    let _ = Reflect::set(&err, &JsValue::from_str("kind"), &JsValue::from_str(kind));
+    if let Some(status) = status {
+        let _ = Reflect::set(&err, &JsValue::from_str("status"), &JsValue::from(status));
+    }
+    if let Some(hint) = hint.filter(|hint| !hint.is_undefined() && !hint.is_null()) {
+        let _ = Reflect::set(&err, &JsValue::from_str("hint"), &hint);
+    }
+    err.into()
+}
+
+fn usage_error(message: String) -> JsValue {
+    error("Usage", &message, None, None)
+}
+
+fn io_error(message: &str) -> JsValue {
+    error("Io", message, None, None)
+}
+
+async fn remote_error(response: &Response) -> JsValue {
+    let status = response.status();
+    let fallback = format!("Remote error {status}");
+    let payload = match response.json() {
+        Ok(promise) => JsFuture::from(promise).await.ok(),
+        Err(_) => None,
+    };
+    let Some(envelope) = payload.and_then(|payload| field(&payload, "error").ok()) else {
+        return error("Io", &fallback, Some(status), None);
+    };
+    let text = |key: &str| {
+        field(&envelope, key)
+            .ok()
+            .and_then(|value| value.as_string())
+    };
+    error(
+        &text("kind").unwrap_or_else(|| "Io".to_string()),
+        &text("message").unwrap_or(fallback),
+        Some(status),
+        field(&envelope, "hint").ok(),
+    )
+}
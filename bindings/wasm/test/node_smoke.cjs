@@ -0,0 +1,44 @@
+/*
+Purpose: Exercise the wasm RemoteClient end to end under Node against a live `plasmite serve`.
+Key Exports: None (script entry point run by scripts/wasm_node_smoke.sh).
+Role: Catches wasm-bindgen binding regressions without a browser.
+Invariants: PLASMITE_WASM_PKG points at a `wasm-pack build --target nodejs` output directory.
+Invariants: PLASMITE_URL serves an existing, empty pool named `smoke`.
+*/
+
+const assert = require("node:assert/strict");
+const path = require("node:path");
+
+const { RemoteClient } = require(path.resolve(process.env.PLASMITE_WASM_PKG));
+
+async function main() {
+  assert.throws(() => new RemoteClient("ftp://127.0.0.1"), (err) => err.kind === "Usage");
+
+  const client = new RemoteClient(process.env.PLASMITE_URL);
+  const pools = await client.listPools();
+  assert.ok(pools.some((pool) => pool.name === "smoke"), "smoke pool is listed");
+
+  const pool = await client.openPool("smoke");
+  const first = await pool.append({ kind: "deploy", ok: true }, ["ops"], "fast");
+  assert.equal(first.seq, 1);
+  await pool.append({ kind: "noise" }, [], "fast");
+  assert.deepEqual((await pool.get(1)).data, { kind: "deploy", ok: true });
+
+  const tail = await pool.tail(1, 2, 2000, ["ops"]);
+  const seen = [];
+  for (let msg = await tail.next(); msg !== undefined; msg = await tail.next()) {
+    seen.push(msg.seq);
+  }
+  assert.deepEqual(seen, [1]);
+
+  await assert.rejects(client.openPool("missing"), (err) => err.kind === "NotFound");
+  await assert.rejects(pool.get(99), (err) => err.status === 404);
+}
+
+main().then(
+  () => console.log("[smoke] wasm binding under node ok"),
+  (err) => {
+    console.error(err);
+    process.exit(1);
+  },
+);
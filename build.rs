@@ -3,6 +3,7 @@
 //! Invariants: `cargo:rerun-if-changed` covers C sources plus embedded UI assets used by the server.
 //! Invariants: Produces a `lite3` object library linked into the Rust crate.
 //! Invariants: Requests C23-compatible mode for vendored Lite3 sources that declare variables after labels.
+//! Invariants: The `pure-lite3` feature skips the C build entirely (pure-Rust `core::lite3::sys`).
 //! Invariants: Uses only Cargo-provided env vars (e.g. `CARGO_MANIFEST_DIR`).
 use std::env;
 use std::fs;
@@ -29,6 +30,10 @@ fn main() {
     println!("cargo:rerun-if-changed=vendor/lite3/lib/nibble_base64/base64.c");
     println!("cargo:rerun-if-changed=ui/index.html");
 
+    if env::var_os("CARGO_FEATURE_PURE_LITE3").is_some() {
+        return;
+    }
+
     ensure_c23_label_decl_support(&target, &out_dir);
 
     let mut build = cc::Build::new();
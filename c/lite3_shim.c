@@ -94,6 +94,42 @@ int plasmite_lite3_get_i64(
         return lite3_get_i64(buf, buf_len, ofs, key, out);
 }
 
+int plasmite_lite3_get_f64(
+        const unsigned char *buf,
+        size_t buf_len,
+        size_t ofs,
+        const char *key,
+        double *out)
+{
+        return lite3_get_f64(buf, buf_len, ofs, key, out);
+}
+
+int plasmite_lite3_get_str(
+        const unsigned char *buf,
+        size_t buf_len,
+        size_t ofs,
+        const char *key,
+        const char **out_ptr,
+        size_t *out_len)
+{
+        lite3_str value = {0};
+        int ret = lite3_get_str(buf, buf_len, ofs, key, &value);
+        if (ret < 0) {
+                return ret;
+        }
+        const char *ptr = LITE3_STR(buf, value);
+        if (!ptr) {
+                return -1;
+        }
+        if (out_ptr) {
+                *out_ptr = ptr;
+        }
+        if (out_len) {
+                *out_len = (size_t)value.len;
+        }
+        return 0;
+}
+
 int plasmite_lite3_count(
         const unsigned char *buf,
         size_t buf_len,
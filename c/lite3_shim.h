@@ -66,6 +66,21 @@ int plasmite_lite3_get_i64(
         const char *key,
         int64_t *out);
 
+int plasmite_lite3_get_f64(
+        const unsigned char *buf,
+        size_t buf_len,
+        size_t ofs,
+        const char *key,
+        double *out);
+
+int plasmite_lite3_get_str(
+        const unsigned char *buf,
+        size_t buf_len,
+        size_t ofs,
+        const char *key,
+        const char **out_ptr,
+        size_t *out_len);
+
 int plasmite_lite3_count(
         const unsigned char *buf,
         size_t buf_len,
@@ -1,12 +1,15 @@
 /*
 Purpose: C ABI for Plasmite bindings using libplasmite.
-Key Exports: Client/Pool/Stream handles, JSON + Lite3 append/get/tail functions, buffers, errors.
+Key Exports: Client/Pool/Stream handles, JSON + Lite3 append/get/tail functions, buffers, errors,
+  ABI version handshake (plsm_abi_version).
 Role: Stable boundary for official bindings (Go/Python/Node) in v0.
 
 ABI stability:
   - Within a major version, this header is additive-only: no field removals,
     no reordering of struct members, no changes to enum discriminant values.
-  - New functions and struct fields are appended at the end.
+  - New functions and struct fields are appended at the end, and each such
+    release bumps PLSM_ABI_VERSION_MINOR. Bindings call plsm_abi_version()
+    at load time to refuse an older or incompatible library.
   - If a breaking change is unavoidable, it bumps the major version and the
     old symbols remain available via versioned symbol names (when supported).
 
@@ -62,6 +65,13 @@ typedef struct plsm_buf {
     size_t len;
 } plsm_buf_t;
 
+typedef struct plsm_append_options {
+    uint32_t durability;        /* 0 = fast, 1 = flush */
+    uint32_t has_timestamp;     /* non-zero: use timestamp_ns instead of now */
+    uint64_t timestamp_ns;
+    const char *idempotency_key; /* NULL for none; see plsm_pool_append_json_ex */
+} plsm_append_options_t;
+
 typedef struct plsm_lite3_frame {
     uint64_t seq;
     uint64_t timestamp_ns;
@@ -77,6 +87,7 @@ typedef struct plsm_error {
     uint64_t offset;
     uint8_t has_seq;
     uint8_t has_offset;
+    char *json; /* structured JSON envelope; read via plsm_error_to_json */
 } plsm_error_t;
 
 int plsm_client_new(const char *pool_dir, plsm_client_t **out_client, plsm_error_t **out_err);
@@ -86,6 +97,17 @@ int plsm_pool_create(plsm_client_t *client, const char *pool_ref, uint64_t size_
 int plsm_pool_open(plsm_client_t *client, const char *pool_ref, plsm_pool_t **out_pool, plsm_error_t **out_err);
 void plsm_pool_free(plsm_pool_t *pool);
 
+/* Run the `plasmite doctor` checks on a pool and write one report object
+   (same shape as an entry of `doctor --json` "reports") to out_report_json.
+   deep != 0 also decodes every retained payload. A corrupt pool is a report
+   with "status":"corrupt", not an error. */
+int plsm_pool_validate(
+    plsm_client_t *client,
+    const char *pool_ref,
+    uint32_t deep,
+    plsm_buf_t *out_report_json,
+    plsm_error_t **out_err);
+
 int plsm_pool_append_json(
     plsm_pool_t *pool,
     const uint8_t *json_bytes,
@@ -96,6 +118,35 @@ int plsm_pool_append_json(
     plsm_buf_t *out_message,
     plsm_error_t **out_err);
 
+/* Append with explicit options. When idempotency_key is set and a recent keyed
+   append used the same key, the original envelope is returned and nothing is
+   appended. Keys are kept as hashes in the pool header (not in tags); the last
+   248 keyed appends are remembered, or the dedupe window when one is set. */
+int plsm_pool_append_json_ex(
+    plsm_pool_t *pool,
+    const uint8_t *json_bytes,
+    size_t json_len,
+    const char **tags,
+    size_t tags_len,
+    const plsm_append_options_t *options,
+    plsm_buf_t *out_message,
+    plsm_error_t **out_err);
+
+/* Append a JSON message plus raw attachment bytes stored beside it. The JSON
+   must reference the attachment with a {"$attachment": {"bytes": N}} object;
+   read the bytes back with plsm_pool_get_attachment. */
+int plsm_pool_append_json_attachment(
+    plsm_pool_t *pool,
+    const uint8_t *json_bytes,
+    size_t json_len,
+    const char **tags,
+    size_t tags_len,
+    const uint8_t *attachment,
+    size_t attachment_len,
+    uint32_t durability,
+    plsm_buf_t *out_message,
+    plsm_error_t **out_err);
+
 int plsm_pool_append_lite3(
     plsm_pool_t *pool,
     const uint8_t *payload,
@@ -110,12 +161,55 @@ int plsm_pool_get_json(
     plsm_buf_t *out_message,
     plsm_error_t **out_err);
 
+/* Write the raw attachment bytes of the message at seq to out_attachment
+   (free with plsm_buf_free); PLSM_ERROR_NOT_FOUND when it has none. */
+int plsm_pool_get_attachment(
+    plsm_pool_t *pool,
+    uint64_t seq,
+    plsm_buf_t *out_attachment,
+    plsm_error_t **out_err);
+
+/* Write {"messages": [...]} for seqs start_seq..=end_seq (inclusive), clamped
+   to the retained range; the start is located via the inline index. */
+int plsm_pool_get_range_json(
+    plsm_pool_t *pool,
+    uint64_t start_seq,
+    uint64_t end_seq,
+    plsm_buf_t *out_messages,
+    plsm_error_t **out_err);
+
+/* Write the pool-info JSON object (name, path, sizes, index stats, bounds,
+   metrics) to out_json; same shape as `plasmite pool info --json`. */
+int plsm_pool_info(
+    plsm_pool_t *pool,
+    plsm_buf_t *out_json,
+    plsm_error_t **out_err);
+
 int plsm_pool_get_lite3(
     plsm_pool_t *pool,
     uint64_t seq,
     plsm_lite3_frame_t *out_frame,
     plsm_error_t **out_err);
 
+/* Save consumer name's cursor (the last seq it finished) beside the pool file,
+   at <pool>.cursors/<name>.json; the CLI's cursor files use the same
+   {"seq": N} shape. Names are 1-128 of [A-Za-z0-9_.-], not starting with '.'. */
+int plsm_cursor_save(
+    plsm_pool_t *pool,
+    const char *name,
+    uint64_t seq,
+    plsm_error_t **out_err);
+
+/* Load consumer name's saved cursor into out_seq and set out_found to 1; when
+   it never saved one, out_found is 0 and out_seq is left untouched. Resume
+   with plsm_stream_open(pool, seq + 1, 1, ...). */
+int plsm_cursor_load(
+    plsm_pool_t *pool,
+    const char *name,
+    uint64_t *out_seq,
+    uint32_t *out_found,
+    plsm_error_t **out_err);
+
 int plsm_stream_open(
     plsm_pool_t *pool,
     uint64_t since_seq,
@@ -155,6 +249,27 @@ void plsm_buf_free(plsm_buf_t *buf);
 void plsm_lite3_frame_free(plsm_lite3_frame_t *frame);
 void plsm_error_free(plsm_error_t *err);
 
+/* Copy the structured error envelope printed by the CLI:
+   {"error":{"kind","message","hint"?,"path"?,"seq"?,"offset"?,"causes"?}}.
+   Returns 0 on success, -1 if err/out_buf is NULL. Free out_buf with plsm_buf_free. */
+int plsm_error_to_json(const plsm_error_t *err, plsm_buf_t *out_buf);
+
+/* ABI version of this header: major in the high 16 bits, minor in the low 16.
+   The minor bumps whenever functions are appended; the major bumps on a
+   breaking change. A binding built against (major, minor) works with any
+   library of the same major and an equal or higher minor. */
+#define PLSM_ABI_VERSION_MAJOR 0
+#define PLSM_ABI_VERSION_MINOR 1
+#define PLSM_ABI_VERSION ((PLSM_ABI_VERSION_MAJOR << 16) | PLSM_ABI_VERSION_MINOR)
+
+/* ABI version of the loaded library, encoded as PLSM_ABI_VERSION. Bindings
+   should compare it with the header they were built against before calling
+   anything else; a library older than this function does not export it. */
+uint32_t plsm_abi_version(void);
+
+/* libplasmite release version ("0.6.1"). Static; do not free. */
+const char *plsm_version(void);
+
 #ifdef __cplusplus
 } // extern "C"
 #endif
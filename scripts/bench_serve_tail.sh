@@ -0,0 +1,92 @@
+#!/usr/bin/env bash
+# Purpose: Measure `plasmite serve` tail throughput for a range of `max_batch` values.
+# Key exports: None; invoke as `scripts/bench_serve_tail.sh [output_md]`.
+# Role: Produce the numbers behind the tail batching guidance in docs/record/serving.md.
+# Invariants: Drains the same pre-filled pool once per `max_batch` with a release binary on loopback.
+# Invariants: Output records the environment, message size, and msgs/s per setting.
+# Notes: Writes temp pools under .scratch/; the server is stopped on exit.
+
+set -euo pipefail
+
+SCRIPT_DIR="$(cd -- "$(dirname -- "${BASH_SOURCE[0]}")" && pwd)"
+REPO_ROOT="$(cd -- "${SCRIPT_DIR}/.." && pwd)"
+cd "${REPO_ROOT}"
+
+OUTPUT_PATH="${1:-tmp/bench-serve-tail.md}"
+MESSAGES="${MESSAGES:-100000}"
+PAYLOAD_BYTES="${PAYLOAD_BYTES:-256}"
+POOL_SIZE="${POOL_SIZE:-256M}"
+BATCHES="${BATCHES:-1 16 256 4096}"
+BIND="${BIND:-127.0.0.1:9787}"
+
+BIN="target/release/plasmite"
+POOL_DIR=".scratch/bench-serve-tail"
+SERVER_PID=""
+
+cleanup() {
+  if [[ -n "${SERVER_PID}" ]]; then
+    kill "${SERVER_PID}" 2>/dev/null || true
+    wait "${SERVER_PID}" 2>/dev/null || true
+  fi
+}
+trap cleanup EXIT
+
+cargo build --release --bin plasmite
+
+rm -rf "${POOL_DIR}"
+mkdir -p "${POOL_DIR}" "$(dirname -- "${OUTPUT_PATH}")"
+"${BIN}" --dir "${POOL_DIR}" pool create bench --size "${POOL_SIZE}" >/dev/null
+
+# Each line is one JSON object padded so the whole line is PAYLOAD_BYTES long.
+awk -v n="${MESSAGES}" -v size="${PAYLOAD_BYTES}" 'BEGIN {
+  for (i = 1; i <= n; i++) {
+    head = sprintf("{\"i\":%d,\"pad\":\"", i)
+    pad = size - length(head) - 2
+    if (pad < 0) pad = 0
+    line = head
+    for (j = 0; j < pad; j++) line = line "x"
+    print line "\"}"
+  }
+}' | "${BIN}" --dir "${POOL_DIR}" feed bench >/dev/null
+
+"${BIN}" --dir "${POOL_DIR}" serve --bind "${BIND}" >"${POOL_DIR}/serve.log" 2>&1 &
+SERVER_PID=$!
+for _ in $(seq 1 50); do
+  if curl -sf "http://${BIND}/healthz" >/dev/null; then
+    break
+  fi
+  sleep 0.1
+done
+if ! kill -0 "${SERVER_PID}" 2>/dev/null; then
+  echo "bench_serve_tail: server exited; see ${POOL_DIR}/serve.log" >&2
+  exit 1
+fi
+
+cpu="$(grep -m1 'model name' /proc/cpuinfo 2>/dev/null | cut -d: -f2- | sed 's/^ *//' || true)"
+if [[ -z "${cpu}" ]]; then
+  cpu="$(sysctl -n machdep.cpu.brand_string 2>/dev/null || echo unknown)"
+fi
+
+{
+  printf '# serve tail throughput\n\n'
+  printf -- '- plasmite: %s\n' "$("${BIN}" --version)"
+  printf -- '- host: %s, %s\n' "$(uname -srm)" "${cpu}"
+  printf -- '- transport: HTTP/1.1 over loopback (%s), curl %s\n' \
+    "${BIND}" "$(curl --version | head -n1 | cut -d' ' -f2)"
+  printf -- '- messages: %s of %s bytes each (JSON line length)\n\n' "${MESSAGES}" "${PAYLOAD_BYTES}"
+  printf '| max_batch | seconds | msgs/s | MB/s |\n'
+  printf '|---:|---:|---:|---:|\n'
+} >"${OUTPUT_PATH}"
+
+for batch in ${BATCHES}; do
+  url="http://${BIND}/v0/pools/bench/tail?since_seq=1&max=${MESSAGES}&max_batch=${batch}"
+  # Warm the page cache and connection path before the timed run.
+  curl -sN "${url}" >/dev/null
+  read -r bytes seconds < <(curl -sN -o /dev/null -w '%{size_download} %{time_total}\n' "${url}")
+  awk -v b="${batch}" -v n="${MESSAGES}" -v bytes="${bytes}" -v s="${seconds}" 'BEGIN {
+    printf "| %d | %.3f | %.0f | %.1f |\n", b, s, n / s, bytes / s / 1e6
+  }' >>"${OUTPUT_PATH}"
+done
+
+cat "${OUTPUT_PATH}"
+printf 'Wrote benchmark artifact: %s\n' "${OUTPUT_PATH}"
@@ -0,0 +1,58 @@
+#!/usr/bin/env bash
+# Purpose: Smoke-test the wasm-bindgen remote client under Node against a live `plasmite serve`.
+# Exports: N/A (script entry point).
+# Role: Keeps bindings/wasm building and speaking the v0 protocol without a browser.
+# Invariants: Builds with `wasm-pack --target nodejs` into a throwaway dir; never touches pkg/.
+# Invariants: The server binds loopback only and is stopped on exit.
+# Notes: Needs wasm-pack, the wasm32-unknown-unknown target, Node 18+, curl, and python3.
+
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+PLASMITE_BIN="${PLASMITE_BIN:-$ROOT/target/debug/plasmite}"
+mkdir -p "$ROOT/.scratch"
+WORKDIR="$(mktemp -d "$ROOT/.scratch/wasm-node-smoke.XXXXXX")"
+SERVE_PID=""
+
+cleanup() {
+  if [[ -n "$SERVE_PID" ]]; then
+    kill "$SERVE_PID" 2>/dev/null || true
+  fi
+  rm -rf "$WORKDIR"
+}
+trap cleanup EXIT
+
+(
+  cd "$ROOT/bindings/wasm"
+  wasm-pack build --target nodejs --dev --out-dir "$WORKDIR/pkg" >/dev/null
+)
+
+port="$(python3 - <<'PY'
+import socket
+
+with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as sock:
+    sock.bind(("127.0.0.1", 0))
+    print(sock.getsockname()[1])
+PY
+)"
+url="http://127.0.0.1:${port}"
+
+"$PLASMITE_BIN" --dir "$WORKDIR/pools" pool create smoke >/dev/null
+"$PLASMITE_BIN" --dir "$WORKDIR/pools" serve --bind "127.0.0.1:${port}" >"$WORKDIR/serve.log" 2>&1 &
+SERVE_PID=$!
+
+ready=false
+for _ in $(seq 1 100); do
+  if curl -fsS "$url/healthz" >/dev/null 2>&1; then
+    ready=true
+    break
+  fi
+  sleep 0.05
+done
+if [[ "$ready" != true ]]; then
+  echo "plasmite serve was not ready; log follows" >&2
+  cat "$WORKDIR/serve.log" >&2
+  exit 1
+fi
+
+PLASMITE_WASM_PKG="$WORKDIR/pkg" PLASMITE_URL="$url" node "$ROOT/bindings/wasm/test/node_smoke.cjs"
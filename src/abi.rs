@@ -11,9 +11,14 @@
 //! Notes: Remote pool refs are not supported in v0.
 #![allow(clippy::result_large_err)]
 
-use crate::api::{LocalClient, PoolApiExt, PoolOptions, PoolRef};
+use crate::api::{
+    LocalClient, PoolApiExt, PoolOptions, PoolRef, ValidationIssue, ValidationReport, load_cursor,
+    save_cursor,
+};
 use crate::core::error::{Error, ErrorKind};
 use crate::core::pool::Pool;
+use crate::pool_info_json::pool_info_json;
+use crate::pool_paths::is_path_ref;
 use serde_json::Value;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
@@ -21,6 +26,12 @@ use std::path::PathBuf;
 use std::ptr;
 use std::time::{Duration, Instant};
 
+/// Bumped on a breaking ABI change; see `PLSM_ABI_VERSION_MAJOR` in `include/plasmite.h`.
+pub const PLSM_ABI_VERSION_MAJOR: u32 = 0;
+/// Bumped whenever functions are appended to the ABI.
+pub const PLSM_ABI_VERSION_MINOR: u32 = 1;
+const PLSM_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
 #[repr(C)]
 pub struct plsm_client {
     client: LocalClient,
@@ -29,6 +40,7 @@ pub struct plsm_client {
 #[repr(C)]
 pub struct plsm_pool {
     pool: Pool,
+    name: String,
 }
 
 #[repr(C)]
@@ -59,6 +71,14 @@ pub struct plsm_buf {
     len: usize,
 }
 
+#[repr(C)]
+pub struct plsm_append_options {
+    durability: u32,
+    has_timestamp: u32,
+    timestamp_ns: u64,
+    idempotency_key: *const c_char,
+}
+
 #[repr(C)]
 pub struct plsm_lite3_frame {
     seq: u64,
@@ -76,6 +96,19 @@ pub struct plsm_error {
     offset: u64,
     has_seq: u8,
     has_offset: u8,
+    json: *mut c_char,
+}
+
+/// ABI version of this library: major in the high 16 bits, minor in the low 16.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_abi_version() -> u32 {
+    (PLSM_ABI_VERSION_MAJOR << 16) | PLSM_ABI_VERSION_MINOR
+}
+
+/// libplasmite release version as a static NUL-terminated string; never freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_version() -> *const c_char {
+    PLSM_VERSION.as_ptr().cast()
 }
 
 #[unsafe(no_mangle)]
@@ -153,7 +186,10 @@ pub extern "C" fn plsm_pool_create(
             Error::new(ErrorKind::Usage).with_message("out_pool is null"),
         );
     }
-    let handle = Box::new(plsm_pool { pool });
+    let handle = Box::new(plsm_pool {
+        pool,
+        name: pool_ref_label(&pool_ref),
+    });
     unsafe {
         *out_pool = Box::into_raw(handle);
     }
@@ -185,13 +221,74 @@ pub extern "C" fn plsm_pool_open(
             Error::new(ErrorKind::Usage).with_message("out_pool is null"),
         );
     }
-    let handle = Box::new(plsm_pool { pool });
+    let handle = Box::new(plsm_pool {
+        pool,
+        name: pool_ref_label(&pool_ref),
+    });
     unsafe {
         *out_pool = Box::into_raw(handle);
     }
     0
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_pool_validate(
+    client: *mut plsm_client,
+    pool_ref: *const c_char,
+    deep: u32,
+    out_report_json: *mut plsm_buf,
+    out_err: *mut *mut plsm_error,
+) -> i32 {
+    let client = match borrow_client(client, out_err) {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
+    let pool_ref = match parse_pool_ref(pool_ref, out_err) {
+        Ok(pool_ref) => pool_ref,
+        Err(code) => return code,
+    };
+    if out_report_json.is_null() {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::Usage).with_message("out_report_json is null"),
+        );
+    }
+    let result = if deep != 0 {
+        client.client.validate_pool_deep(&pool_ref)
+    } else {
+        client.client.validate_pool(&pool_ref)
+    };
+    let report = match result {
+        Ok(report) => report,
+        // Match `plasmite doctor`: unreadable pools are reported, not raised.
+        Err(err) if err.kind() == ErrorKind::Corrupt => ValidationReport::corrupt(
+            err.path().map(PathBuf::from).unwrap_or_default(),
+            ValidationIssue {
+                code: "corrupt".to_string(),
+                message: err.message().unwrap_or("corrupt").to_string(),
+                seq: err.seq(),
+                offset: err.offset(),
+            },
+            None,
+        )
+        .with_pool_ref(pool_ref_label(&pool_ref)),
+        Err(err) => return fail(out_err, err),
+    };
+    let bytes = match serde_json::to_vec(&report.to_json()) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            return fail(
+                out_err,
+                Error::new(ErrorKind::Internal)
+                    .with_message("failed to encode validation report")
+                    .with_source(err),
+            );
+        }
+    };
+    fill_buf(unsafe { &mut *out_report_json }, bytes);
+    0
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn plsm_pool_free(pool: *mut plsm_pool) {
     if pool.is_null() {
@@ -202,6 +299,41 @@ pub extern "C" fn plsm_pool_free(pool: *mut plsm_pool) {
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_pool_info(
+    pool: *mut plsm_pool,
+    out_json: *mut plsm_buf,
+    out_err: *mut *mut plsm_error,
+) -> i32 {
+    let pool = match borrow_pool(pool, out_err) {
+        Ok(pool) => pool,
+        Err(code) => return code,
+    };
+    let info = match pool.pool.info() {
+        Ok(info) => info,
+        Err(err) => return fail(out_err, err),
+    };
+    if out_json.is_null() {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::Usage).with_message("out_json is null"),
+        );
+    }
+    let bytes = match serde_json::to_vec(&pool_info_json(&pool.name, &info)) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            return fail(
+                out_err,
+                Error::new(ErrorKind::Internal)
+                    .with_message("failed to encode pool info")
+                    .with_source(err),
+            );
+        }
+    };
+    fill_buf(unsafe { &mut *out_json }, bytes);
+    0
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn plsm_pool_append_json(
     pool: *mut plsm_pool,
@@ -212,6 +344,117 @@ pub extern "C" fn plsm_pool_append_json(
     durability: u32,
     out_message: *mut plsm_buf,
     out_err: *mut *mut plsm_error,
+) -> i32 {
+    let options = plsm_append_options {
+        durability,
+        has_timestamp: 0,
+        timestamp_ns: 0,
+        idempotency_key: ptr::null(),
+    };
+    plsm_pool_append_json_ex(
+        pool,
+        json_bytes,
+        json_len,
+        tags,
+        tags_len,
+        &options,
+        out_message,
+        out_err,
+    )
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_pool_append_json_ex(
+    pool: *mut plsm_pool,
+    json_bytes: *const u8,
+    json_len: usize,
+    tags: *const *const c_char,
+    tags_len: usize,
+    options: *const plsm_append_options,
+    out_message: *mut plsm_buf,
+    out_err: *mut *mut plsm_error,
+) -> i32 {
+    let pool = match borrow_pool(pool, out_err) {
+        Ok(pool) => pool,
+        Err(code) => return code,
+    };
+    let data = match parse_json_bytes(json_bytes, json_len) {
+        Ok(value) => value,
+        Err(err) => return fail(out_err, err),
+    };
+    let tags = match parse_tags(tags, tags_len) {
+        Ok(tags) => tags,
+        Err(err) => return fail(out_err, err),
+    };
+    if options.is_null() {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::Usage).with_message("options is null"),
+        );
+    }
+    let options = unsafe { &*options };
+    let durability = match options.durability {
+        0 => crate::api::Durability::Fast,
+        1 => crate::api::Durability::Flush,
+        _ => {
+            return fail(
+                out_err,
+                Error::new(ErrorKind::Usage).with_message("invalid durability"),
+            );
+        }
+    };
+    let timestamp_ns = if options.has_timestamp != 0 {
+        options.timestamp_ns
+    } else {
+        match now_ns() {
+            Ok(now) => now,
+            Err(err) => return fail(out_err, err),
+        }
+    };
+    let idempotency_key = if options.idempotency_key.is_null() {
+        None
+    } else {
+        match unsafe { CStr::from_ptr(options.idempotency_key) }.to_str() {
+            Ok(key) if !key.is_empty() => Some(key),
+            _ => {
+                return fail(
+                    out_err,
+                    Error::new(ErrorKind::Usage)
+                        .with_message("idempotency_key must be non-empty UTF-8"),
+                );
+            }
+        }
+    };
+
+    let options = crate::api::AppendOptions::new(timestamp_ns, durability);
+    let message = match idempotency_key {
+        Some(key) => pool.pool.append_json_idempotent(key, &data, &tags, options),
+        None => pool.pool.append_json(&data, &tags, options),
+    };
+    let message = match message {
+        Ok(message) => message,
+        Err(err) => return fail(out_err, err),
+    };
+    if let Err(err) = write_message_buf(out_message, message) {
+        return fail(out_err, err);
+    }
+    0
+}
+
+/// Append a JSON message plus `attachment_len` raw bytes stored beside it. The JSON must
+/// reference the attachment with a `{"$attachment": {"bytes": N}}` object.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_pool_append_json_attachment(
+    pool: *mut plsm_pool,
+    json_bytes: *const u8,
+    json_len: usize,
+    tags: *const *const c_char,
+    tags_len: usize,
+    attachment: *const u8,
+    attachment_len: usize,
+    durability: u32,
+    out_message: *mut plsm_buf,
+    out_err: *mut *mut plsm_error,
 ) -> i32 {
     let pool = match borrow_pool(pool, out_err) {
         Ok(pool) => pool,
@@ -225,6 +468,17 @@ pub extern "C" fn plsm_pool_append_json(
         Ok(tags) => tags,
         Err(err) => return fail(out_err, err),
     };
+    if attachment.is_null() && attachment_len > 0 {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::Usage).with_message("attachment is null"),
+        );
+    }
+    let attachment = if attachment_len == 0 {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(attachment, attachment_len) }
+    };
     let durability = match durability {
         0 => crate::api::Durability::Fast,
         1 => crate::api::Durability::Flush,
@@ -235,7 +489,12 @@ pub extern "C" fn plsm_pool_append_json(
             );
         }
     };
-    let message = match pool.pool.append_json_now(&data, &tags, durability) {
+    let message = now_ns().and_then(|timestamp_ns| {
+        let options = crate::api::AppendOptions::new(timestamp_ns, durability);
+        pool.pool
+            .append_json_with_attachment(&data, &tags, attachment, options)
+    });
+    let message = match message {
         Ok(message) => message,
         Err(err) => return fail(out_err, err),
     };
@@ -312,6 +571,89 @@ pub extern "C" fn plsm_pool_get_json(
     0
 }
 
+/// Write the raw attachment bytes of the message at `seq`; NotFound when it has none.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_pool_get_attachment(
+    pool: *mut plsm_pool,
+    seq: u64,
+    out_attachment: *mut plsm_buf,
+    out_err: *mut *mut plsm_error,
+) -> i32 {
+    let pool = match borrow_pool(pool, out_err) {
+        Ok(pool) => pool,
+        Err(code) => return code,
+    };
+    if out_attachment.is_null() {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::Usage).with_message("out_attachment is null"),
+        );
+    }
+    let attachment = match pool.pool.get_message(seq) {
+        Ok(message) => message.attachment,
+        Err(err) => return fail(out_err, err),
+    };
+    let Some(attachment) = attachment else {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::NotFound)
+                .with_message("message has no attachment")
+                .with_seq(seq),
+        );
+    };
+    unsafe { fill_buf(&mut *out_attachment, attachment) };
+    0
+}
+
+/// Write `{"messages": [...]}` for seqs in `start_seq..=end_seq`, clamped to the retained range.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_pool_get_range_json(
+    pool: *mut plsm_pool,
+    start_seq: u64,
+    end_seq: u64,
+    out_messages: *mut plsm_buf,
+    out_err: *mut *mut plsm_error,
+) -> i32 {
+    let pool = match borrow_pool(pool, out_err) {
+        Ok(pool) => pool,
+        Err(code) => return code,
+    };
+    if out_messages.is_null() {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::Usage).with_message("out_messages is null"),
+        );
+    }
+    let messages = match pool.pool.get_messages(start_seq, end_seq) {
+        Ok(messages) => messages,
+        Err(err) => return fail(out_err, err),
+    };
+    let messages = messages
+        .into_iter()
+        .map(|message| {
+            serde_json::json!({
+                "seq": message.seq,
+                "time": message.time,
+                "meta": message.meta.to_json(),
+                "data": message.data,
+            })
+        })
+        .collect::<Vec<_>>();
+    let json_bytes = match serde_json::to_vec(&serde_json::json!({ "messages": messages })) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            return fail(
+                out_err,
+                Error::new(ErrorKind::Internal)
+                    .with_message("failed to serialize messages")
+                    .with_source(err),
+            );
+        }
+    };
+    unsafe { fill_buf(&mut *out_messages, json_bytes) };
+    0
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn plsm_pool_get_lite3(
     pool: *mut plsm_pool,
@@ -333,6 +675,62 @@ pub extern "C" fn plsm_pool_get_lite3(
     0
 }
 
+/// Save consumer `name`'s cursor (the last seq it finished) beside the pool file.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_cursor_save(
+    pool: *mut plsm_pool,
+    name: *const c_char,
+    seq: u64,
+    out_err: *mut *mut plsm_error,
+) -> i32 {
+    let pool = match borrow_pool(pool, out_err) {
+        Ok(pool) => pool,
+        Err(code) => return code,
+    };
+    let name = match parse_cursor_name(name) {
+        Ok(name) => name,
+        Err(err) => return fail(out_err, err),
+    };
+    if let Err(err) = save_cursor(pool.pool.path(), name, seq) {
+        return fail(out_err, err);
+    }
+    0
+}
+
+/// Load consumer `name`'s saved cursor; `*out_found` is 0 (and `*out_seq` untouched) when none.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_cursor_load(
+    pool: *mut plsm_pool,
+    name: *const c_char,
+    out_seq: *mut u64,
+    out_found: *mut u32,
+    out_err: *mut *mut plsm_error,
+) -> i32 {
+    let pool = match borrow_pool(pool, out_err) {
+        Ok(pool) => pool,
+        Err(code) => return code,
+    };
+    if out_seq.is_null() || out_found.is_null() {
+        return fail(
+            out_err,
+            Error::new(ErrorKind::Usage).with_message("out_seq or out_found is null"),
+        );
+    }
+    let name = match parse_cursor_name(name) {
+        Ok(name) => name,
+        Err(err) => return fail(out_err, err),
+    };
+    match load_cursor(pool.pool.path(), name) {
+        Ok(Some(seq)) => unsafe {
+            *out_seq = seq;
+            *out_found = 1;
+        },
+        Ok(None) => unsafe { *out_found = 0 },
+        Err(err) => return fail(out_err, err),
+    }
+    0
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn plsm_stream_open(
     pool: *mut plsm_pool,
@@ -424,7 +822,12 @@ pub extern "C" fn plsm_stream_next(
                         continue;
                     }
                 }
-                let message = match message_from_frame(&frame) {
+                let payload = match stream.pool.join_chunks(&frame) {
+                    Ok(Some(payload)) => payload,
+                    Ok(None) => continue,
+                    Err(err) => return fail(out_err, err),
+                };
+                let message = match message_from_frame(&frame, &payload) {
                     Ok(message) => message,
                     Err(err) => return fail(out_err, err),
                 };
@@ -534,6 +937,31 @@ pub extern "C" fn plsm_lite3_stream_next(
                         continue;
                     }
                 }
+                match crate::api::chunk_position(frame.flags, frame.payload) {
+                    Ok(None) => {}
+                    Ok(Some(position)) if !position.is_last() => continue,
+                    Ok(Some(_)) => return fail(
+                        out_err,
+                        Error::new(ErrorKind::Usage)
+                            .with_message(
+                                "message is stored in chunks; raw Lite3 frame reads are unavailable",
+                            )
+                            .with_seq(frame.seq)
+                            .with_hint("Use plsm_stream_open, which joins chunked messages."),
+                    ),
+                    Err(err) => return fail(out_err, err),
+                }
+                if frame.flags & crate::api::FRAME_FLAG_ATTACHMENT != 0 {
+                    return fail(
+                        out_err,
+                        Error::new(ErrorKind::Usage)
+                            .with_message(
+                                "message carries an attachment; raw Lite3 frame reads are unavailable",
+                            )
+                            .with_seq(frame.seq)
+                            .with_hint("Use plsm_stream_open and plsm_pool_get_attachment."),
+                    );
+                }
                 stream.seen += 1;
                 if let Err(err) = write_lite3_frame(out_frame, frame) {
                     return fail(out_err, err);
@@ -611,9 +1039,27 @@ pub extern "C" fn plsm_error_free(err: *mut plsm_error) {
         if !err.path.is_null() {
             drop(CString::from_raw(err.path));
         }
+        if !err.json.is_null() {
+            drop(CString::from_raw(err.json));
+        }
     }
 }
 
+/// Copy the error's structured JSON envelope (same shape the CLI prints) into `out_buf`.
+#[unsafe(no_mangle)]
+pub extern "C" fn plsm_error_to_json(err: *const plsm_error, out_buf: *mut plsm_buf) -> i32 {
+    if err.is_null() || out_buf.is_null() {
+        return -1;
+    }
+    let err = unsafe { &*err };
+    if err.json.is_null() {
+        return -1;
+    }
+    let json = unsafe { CStr::from_ptr(err.json) };
+    unsafe { fill_buf(&mut *out_buf, json.to_bytes().to_vec()) };
+    0
+}
+
 fn borrow_client<'a>(
     client: *mut plsm_client,
     out_err: *mut *mut plsm_error,
@@ -683,13 +1129,40 @@ fn parse_pool_ref(input: *const c_char, out_err: *mut *mut plsm_error) -> Result
             Error::new(ErrorKind::Usage).with_message("remote pool refs are not supported in v0"),
         ));
     }
-    if raw.contains('/') {
+    if is_path_ref(raw) {
         Ok(PoolRef::path(raw))
     } else {
         Ok(PoolRef::name(raw))
     }
 }
 
+fn parse_cursor_name<'a>(name: *const c_char) -> Result<&'a str, Error> {
+    if name.is_null() {
+        return Err(Error::new(ErrorKind::Usage).with_message("cursor name is null"));
+    }
+    unsafe { CStr::from_ptr(name) }
+        .to_str()
+        .map_err(|_| Error::new(ErrorKind::Usage).with_message("cursor name is not valid UTF-8"))
+}
+
+fn now_ns() -> Result<u64, Error> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("time went backwards")
+                .with_source(err)
+        })
+}
+
+fn pool_ref_label(pool_ref: &PoolRef) -> String {
+    match pool_ref {
+        PoolRef::Name(name) | PoolRef::Uri(name) => name.clone(),
+        PoolRef::Path(path) => path.display().to_string(),
+    }
+}
+
 fn parse_json_bytes(bytes: *const u8, len: usize) -> Result<Value, Error> {
     if bytes.is_null() {
         return Err(Error::new(ErrorKind::Usage).with_message("json_bytes is null"));
@@ -726,8 +1199,12 @@ fn parse_tags(tags: *const *const c_char, len: usize) -> Result<Vec<String>, Err
     Ok(out)
 }
 
-fn message_from_frame(frame: &crate::api::FrameRef<'_>) -> Result<crate::api::Message, Error> {
-    let doc = crate::api::Lite3DocRef::new(frame.payload);
+fn message_from_frame(
+    frame: &crate::api::FrameRef<'_>,
+    payload: &[u8],
+) -> Result<crate::api::Message, Error> {
+    let (envelope, attachment) = crate::core::attachment::unpack(frame.flags, payload)?;
+    let doc = crate::api::Lite3DocRef::new(envelope);
     let meta_type = doc
         .type_at_key(0, "meta")
         .map_err(|err| err.with_message("missing meta"))?;
@@ -773,8 +1250,9 @@ fn message_from_frame(frame: &crate::api::FrameRef<'_>) -> Result<crate::api::Me
     Ok(crate::api::Message {
         seq: frame.seq,
         time: format_ts(frame.timestamp_ns)?,
-        meta: crate::api::Meta { tags },
+        meta: crate::api::Meta::new(tags),
         data,
+        attachment: attachment.map(<[u8]>::to_vec),
     })
 }
 
@@ -796,16 +1274,17 @@ fn write_message_buf(
             .with_message("failed to serialize message")
             .with_source(err)
     })?;
-    unsafe {
-        let buf = &mut *out_message;
-        let mut data = json_bytes.into_boxed_slice();
-        buf.len = data.len();
-        buf.data = data.as_mut_ptr();
-        std::mem::forget(data);
-    }
+    unsafe { fill_buf(&mut *out_message, json_bytes) };
     Ok(())
 }
 
+fn fill_buf(buf: &mut plsm_buf, bytes: Vec<u8>) {
+    let mut data = bytes.into_boxed_slice();
+    buf.len = data.len();
+    buf.data = data.as_mut_ptr();
+    std::mem::forget(data);
+}
+
 fn write_lite3_frame(
     out_frame: *mut plsm_lite3_frame,
     frame: crate::api::FrameRef<'_>,
@@ -841,6 +1320,7 @@ fn fail(out_err: *mut *mut plsm_error, err: Error) -> i32 {
         offset: err.offset().unwrap_or(0),
         has_seq: if err.seq().is_some() { 1 } else { 0 },
         has_offset: if err.offset().is_some() { 1 } else { 0 },
+        json: to_c_string(&err.to_json().to_string()),
     });
     unsafe {
         *out_err = Box::into_raw(error);
@@ -980,6 +1460,18 @@ mod tests {
         plsm_buf_free(&mut out);
         assert_eq!(message.get("seq").and_then(|v| v.as_u64()), Some(1));
 
+        let mut out = plsm_buf {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+        let rc = plsm_pool_get_range_json(pool, 0, 10, &mut out, &mut err);
+        assert_eq!(rc, 0, "get_range failed");
+        let range = parse_buf(&out);
+        plsm_buf_free(&mut out);
+        let messages = range["messages"].as_array().expect("messages");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["data"]["x"], 1);
+
         plsm_pool_free(pool);
         plsm_client_free(client);
         if !err.is_null() {
@@ -1101,6 +1593,264 @@ mod tests {
         plsm_client_free(client);
     }
 
+    #[test]
+    fn abi_append_json_ex_honors_timestamp_and_idempotency_key() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pool_dir_c = CString::new(temp.path().to_string_lossy().as_ref()).expect("cstr");
+        let mut client: *mut plsm_client = std::ptr::null_mut();
+        let mut err: *mut plsm_error = std::ptr::null_mut();
+        let rc = plsm_client_new(pool_dir_c.as_ptr(), &mut client, &mut err);
+        assert_eq!(rc, 0, "client_new failed");
+
+        let pool_name = CString::new("idem").expect("cstr");
+        let mut pool: *mut plsm_pool = std::ptr::null_mut();
+        let rc = plsm_pool_create(client, pool_name.as_ptr(), 1024 * 1024, &mut pool, &mut err);
+        assert_eq!(rc, 0, "pool_create failed");
+
+        let key = CString::new("batch-7").expect("cstr");
+        let options = plsm_append_options {
+            durability: 0,
+            has_timestamp: 1,
+            timestamp_ns: 1_000_000_000,
+            idempotency_key: key.as_ptr(),
+        };
+        let mut append = |payload: &[u8]| {
+            let mut out = plsm_buf {
+                data: std::ptr::null_mut(),
+                len: 0,
+            };
+            let rc = plsm_pool_append_json_ex(
+                pool,
+                payload.as_ptr(),
+                payload.len(),
+                std::ptr::null(),
+                0,
+                &options,
+                &mut out,
+                &mut err,
+            );
+            assert_eq!(rc, 0, "append_json_ex failed");
+            let message = parse_buf(&out);
+            plsm_buf_free(&mut out);
+            message
+        };
+
+        let first = append(br#"{"n":1}"#);
+        assert_eq!(first["seq"], 1);
+        assert_eq!(first["time"], "1970-01-01T00:00:01Z");
+        assert_eq!(first["meta"]["tags"], serde_json::json!([]));
+        let retry = append(br#"{"n":2}"#);
+        assert_eq!(retry["seq"], 1);
+        assert_eq!(retry["data"]["n"], 1);
+        assert_eq!(retry["meta"]["tags"], serde_json::json!([]));
+
+        plsm_pool_free(pool);
+        plsm_client_free(client);
+    }
+
+    #[test]
+    fn abi_attachment_bytes_round_trip() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pool_dir_c = CString::new(temp.path().to_string_lossy().as_ref()).expect("cstr");
+        let mut client: *mut plsm_client = std::ptr::null_mut();
+        let mut err: *mut plsm_error = std::ptr::null_mut();
+        let rc = plsm_client_new(pool_dir_c.as_ptr(), &mut client, &mut err);
+        assert_eq!(rc, 0, "client_new failed");
+
+        let pool_name = CString::new("blobs").expect("cstr");
+        let mut pool: *mut plsm_pool = std::ptr::null_mut();
+        let rc = plsm_pool_create(client, pool_name.as_ptr(), 1024 * 1024, &mut pool, &mut err);
+        assert_eq!(rc, 0, "pool_create failed");
+
+        let payload = br#"{"image":{"$attachment":{"bytes":3}}}"#;
+        let blob = [0u8, 7, 255];
+        let mut out = plsm_buf {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+        let rc = plsm_pool_append_json_attachment(
+            pool,
+            payload.as_ptr(),
+            payload.len(),
+            std::ptr::null(),
+            0,
+            blob.as_ptr(),
+            blob.len(),
+            0,
+            &mut out,
+            &mut err,
+        );
+        assert_eq!(rc, 0, "append_json_attachment failed");
+        let message = parse_buf(&out);
+        plsm_buf_free(&mut out);
+        assert_eq!(message["data"]["image"]["$attachment"]["bytes"], 3);
+        assert!(message.get("attachment").is_none());
+
+        let rc = plsm_pool_get_attachment(pool, 1, &mut out, &mut err);
+        assert_eq!(rc, 0, "get_attachment failed");
+        let bytes = unsafe { std::slice::from_raw_parts(out.data, out.len) }.to_vec();
+        plsm_buf_free(&mut out);
+        assert_eq!(bytes, blob);
+
+        plsm_pool_free(pool);
+        plsm_client_free(client);
+    }
+
+    #[test]
+    fn abi_version_matches_header_and_package() {
+        let header = include_str!("../include/plasmite.h");
+        for (name, value) in [
+            ("PLSM_ABI_VERSION_MAJOR", PLSM_ABI_VERSION_MAJOR),
+            ("PLSM_ABI_VERSION_MINOR", PLSM_ABI_VERSION_MINOR),
+        ] {
+            assert!(
+                header.contains(&format!("#define {name} {value}\n")),
+                "{name} in include/plasmite.h must be {value}"
+            );
+        }
+        assert_eq!(plsm_abi_version() >> 16, PLSM_ABI_VERSION_MAJOR);
+        assert_eq!(plsm_abi_version() & 0xffff, PLSM_ABI_VERSION_MINOR);
+        let version = unsafe { CStr::from_ptr(plsm_version()) };
+        assert_eq!(version.to_str().expect("utf8"), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn abi_cursor_save_and_load_round_trip() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pool_dir_c = CString::new(temp.path().to_string_lossy().as_ref()).expect("cstr");
+        let mut client: *mut plsm_client = std::ptr::null_mut();
+        let mut err: *mut plsm_error = std::ptr::null_mut();
+        let rc = plsm_client_new(pool_dir_c.as_ptr(), &mut client, &mut err);
+        assert_eq!(rc, 0, "client_new failed");
+
+        let pool_name = CString::new("jobs").expect("cstr");
+        let mut pool: *mut plsm_pool = std::ptr::null_mut();
+        let rc = plsm_pool_create(client, pool_name.as_ptr(), 1024 * 1024, &mut pool, &mut err);
+        assert_eq!(rc, 0, "pool_create failed");
+
+        let name = CString::new("indexer").expect("cstr");
+        let mut seq = 0u64;
+        let mut found = 1u32;
+        let rc = plsm_cursor_load(pool, name.as_ptr(), &mut seq, &mut found, &mut err);
+        assert_eq!(rc, 0, "cursor_load failed");
+        assert_eq!(found, 0);
+
+        let rc = plsm_cursor_save(pool, name.as_ptr(), 17, &mut err);
+        assert_eq!(rc, 0, "cursor_save failed");
+        let rc = plsm_cursor_load(pool, name.as_ptr(), &mut seq, &mut found, &mut err);
+        assert_eq!(rc, 0, "cursor_load failed");
+        assert_eq!((seq, found), (17, 1));
+
+        let bad = CString::new("../escape").expect("cstr");
+        let rc = plsm_cursor_save(pool, bad.as_ptr(), 1, &mut err);
+        assert_eq!(rc, -1);
+        let (kind, message, _path, _seq, _offset) = take_error(err);
+        assert_eq!(kind, error_kind_code(ErrorKind::Usage));
+        assert!(message.contains("invalid cursor name"), "{message}");
+
+        plsm_pool_free(pool);
+        plsm_client_free(client);
+    }
+
+    #[test]
+    fn abi_pool_info_reports_bounds_and_metrics() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pool_dir_c = CString::new(temp.path().to_string_lossy().as_ref()).expect("cstr");
+        let mut client: *mut plsm_client = std::ptr::null_mut();
+        let mut err: *mut plsm_error = std::ptr::null_mut();
+        let rc = plsm_client_new(pool_dir_c.as_ptr(), &mut client, &mut err);
+        assert_eq!(rc, 0, "client_new failed");
+
+        let pool_name = CString::new("info-pool").expect("cstr");
+        let mut pool: *mut plsm_pool = std::ptr::null_mut();
+        let rc = plsm_pool_create(client, pool_name.as_ptr(), 1024 * 1024, &mut pool, &mut err);
+        assert_eq!(rc, 0, "pool_create failed");
+
+        let payload = br#"{"n":1}"#;
+        for _ in 0..3 {
+            let mut out = plsm_buf {
+                data: std::ptr::null_mut(),
+                len: 0,
+            };
+            let rc = plsm_pool_append_json(
+                pool,
+                payload.as_ptr(),
+                payload.len(),
+                std::ptr::null(),
+                0,
+                0,
+                &mut out,
+                &mut err,
+            );
+            assert_eq!(rc, 0, "append failed");
+            plsm_buf_free(&mut out);
+        }
+
+        let mut out = plsm_buf {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+        let rc = plsm_pool_info(pool, &mut out, &mut err);
+        assert_eq!(rc, 0, "pool_info failed");
+        let info = parse_buf(&out);
+        plsm_buf_free(&mut out);
+        assert_eq!(info["name"], "info-pool");
+        assert_eq!(info["bounds"]["oldest"], 1);
+        assert_eq!(info["bounds"]["newest"], 3);
+        assert_eq!(info["metrics"]["message_count"], 3);
+        assert!(info["index_capacity"].as_u64().is_some());
+
+        let rc = plsm_pool_info(pool, std::ptr::null_mut(), &mut err);
+        assert_eq!(rc, -1);
+        let (kind, _message, _path, _seq, _offset) = take_error(err);
+        assert_eq!(kind, error_kind_code(ErrorKind::Usage));
+
+        plsm_pool_free(pool);
+        plsm_client_free(client);
+    }
+
+    #[test]
+    fn abi_pool_validate_reports_doctor_shape() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pool_dir_c = CString::new(temp.path().to_string_lossy().as_ref()).expect("cstr");
+        let mut client: *mut plsm_client = std::ptr::null_mut();
+        let mut err: *mut plsm_error = std::ptr::null_mut();
+        let rc = plsm_client_new(pool_dir_c.as_ptr(), &mut client, &mut err);
+        assert_eq!(rc, 0, "client_new failed");
+
+        let pool_name = CString::new("checked").expect("cstr");
+        let mut pool: *mut plsm_pool = std::ptr::null_mut();
+        let rc = plsm_pool_create(client, pool_name.as_ptr(), 1024 * 1024, &mut pool, &mut err);
+        assert_eq!(rc, 0, "pool_create failed");
+        plsm_pool_free(pool);
+
+        for deep in [0, 1] {
+            let mut out = plsm_buf {
+                data: std::ptr::null_mut(),
+                len: 0,
+            };
+            let rc = plsm_pool_validate(client, pool_name.as_ptr(), deep, &mut out, &mut err);
+            assert_eq!(rc, 0, "validate failed");
+            let report = parse_buf(&out);
+            plsm_buf_free(&mut out);
+            assert_eq!(report["status"], "ok");
+            assert_eq!(report["issue_count"], 0);
+            assert!(report["issues"].as_array().is_some_and(Vec::is_empty));
+        }
+
+        let missing = CString::new("missing").expect("cstr");
+        let mut out = plsm_buf {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+        let rc = plsm_pool_validate(client, missing.as_ptr(), 0, &mut out, &mut err);
+        assert_eq!(rc, -1);
+        let (kind, _message, _path, _seq, _offset) = take_error(err);
+        assert_eq!(kind, error_kind_code(ErrorKind::NotFound));
+
+        plsm_client_free(client);
+    }
+
     #[test]
     fn abi_errors_include_path_for_missing_pool() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -1125,6 +1875,40 @@ mod tests {
         plsm_client_free(client);
     }
 
+    #[test]
+    fn abi_error_to_json_matches_cli_envelope() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pool_dir_c = CString::new(temp.path().to_string_lossy().as_ref()).expect("cstr");
+        let mut client: *mut plsm_client = std::ptr::null_mut();
+        let mut err: *mut plsm_error = std::ptr::null_mut();
+        let rc = plsm_client_new(pool_dir_c.as_ptr(), &mut client, &mut err);
+        assert_eq!(rc, 0, "client_new failed");
+
+        let pool_name = CString::new("missing").expect("cstr");
+        let mut pool: *mut plsm_pool = std::ptr::null_mut();
+        let rc = plsm_pool_open(client, pool_name.as_ptr(), &mut pool, &mut err);
+        assert_eq!(rc, -1);
+
+        let mut out = plsm_buf {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+        assert_eq!(plsm_error_to_json(err, &mut out), 0);
+        let value = parse_buf(&out);
+        plsm_buf_free(&mut out);
+        assert_eq!(value["error"]["kind"], "NotFound");
+        assert!(value["error"]["message"].is_string());
+        assert!(
+            value["error"]["path"]
+                .as_str()
+                .is_some_and(|path| path.ends_with("missing.plasmite"))
+        );
+        assert_eq!(plsm_error_to_json(std::ptr::null(), &mut out), -1);
+
+        plsm_error_free(err);
+        plsm_client_free(client);
+    }
+
     #[test]
     fn abi_errors_report_seq_on_missing_message() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -3,14 +3,28 @@
 //! Role: Stable boundary for bindings; mirrors CLI resolution rules.
 //! Invariants: Pool resolution matches `spec/v0/SPEC.md` and is additive-only in v0.
 //! Invariants: Remote pool refs are accepted but rejected at runtime in v0.
+//! Invariants: Names under a hidden directory (`with_hidden_dirs`) resolve as not found and
+//! `list_pools` skips those directories, so `serve` root routes never reach namespaced pools.
 #![allow(clippy::result_large_err)]
 
-use super::validation::validate_pool_state_report;
-use super::{ValidationIssue, ValidationReport};
+use super::control::CONTROL_DELETED;
+use super::cursor_store::cursors_sidecar_dir;
+use super::deadline::Deadline;
+use super::message::PoolApiExt;
+use super::pool_cache::{PoolCache, PoolCacheStats};
+use super::validation::{apply_intent_journal, deep_payload_issues, validate_pool_state_report};
+use super::{ValidationIssue, ValidationReport, ValidationStatus};
 use crate::core::error::{Error, ErrorKind};
-use crate::core::pool::{Pool, PoolInfo, PoolOptions};
-use crate::pool_paths::{PoolNameResolveError, default_pool_dir, resolve_named_pool_path};
+use crate::core::inuse::{
+    PoolUser, Registration, pool_users, registrations, registry_dir, writer_lock_held,
+};
+use crate::core::pool::{Durability, Pool, PoolInfo, PoolOptions};
+use crate::pool_paths::{
+    PoolNameResolveError, default_pool_dir, find_pool_files, resolve_named_pool_path,
+};
+use serde_json::Map;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 pub type ApiResult<T> = Result<T, Error>;
 
@@ -52,15 +66,66 @@ impl PoolRef {
     }
 }
 
+/// Directory (beside the pool file) that `delete --trash` moves pools into.
+pub const TRASH_DIR: &str = ".trash";
+
+/// How `LocalClient::delete_pool_with` treats busy pools and where the file goes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DeleteOptions {
+    /// Delete even when other processes have the pool open or hold its append lock.
+    pub force: bool,
+    /// Move the pool into `TRASH_DIR` instead of removing it.
+    pub trash: bool,
+}
+
+/// What `LocalClient::delete_pool_with` would remove, from `LocalClient::plan_delete_pool`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeletePlan {
+    pub path: PathBuf,
+    /// Where `trash` would move the pool; `None` when it would be removed.
+    pub trash_path: Option<PathBuf>,
+    /// The pool file, then each sidecar that exists, with its size (directories summed).
+    pub files: Vec<(PathBuf, u64)>,
+    /// Oldest and newest seq the pool holds; `None` when it is empty or cannot be opened.
+    pub seqs: Option<(u64, u64)>,
+}
+
+impl DeletePlan {
+    pub fn bytes(&self) -> u64 {
+        self.files.iter().map(|(_, bytes)| bytes).sum()
+    }
+}
+
+/// Snapshot returned by `LocalClient::pool_presence`.
+#[derive(Clone, Debug)]
+pub struct PoolPresence {
+    pub path: PathBuf,
+    /// Handles registered in the `.who` sidecar (readers carry their cursor).
+    pub registrations: Vec<Registration>,
+    /// Processes holding the file open (Linux only; empty elsewhere).
+    pub users: Vec<PoolUser>,
+    pub append_lock_held: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct LocalClient {
     pool_dir: PathBuf,
+    read_only: bool,
+    /// Shared by clones, so a server's handlers all draw on one set of open pools.
+    pool_cache: Option<Arc<Mutex<PoolCache>>>,
+    deadline: Deadline,
+    /// Top-level subdirectories of `pool_dir` this client treats as absent.
+    hidden_dirs: Arc<[String]>,
 }
 
 impl LocalClient {
     pub fn new() -> Self {
         Self {
             pool_dir: default_pool_dir(),
+            read_only: false,
+            pool_cache: None,
+            deadline: Deadline::default(),
+            hidden_dirs: Arc::new([]),
         }
     }
 
@@ -69,48 +134,178 @@ impl LocalClient {
         self
     }
 
+    /// Open every pool with `Pool::open_read_only` and refuse create/delete/restore.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Keep up to `capacity` opened pools (mmap and lock state) for `with_pool` and
+    /// `pool_info`, evicting the least recently used. `0` keeps none but still counts stats.
+    pub fn with_pool_cache(mut self, capacity: usize) -> Self {
+        self.pool_cache = Some(Arc::new(Mutex::new(PoolCache::new(capacity))));
+        self
+    }
+
+    /// Stop opens, validation, and pool scans with a `Busy` error once `deadline` expires or
+    /// is cancelled. Clones made afterwards share it; set a fresh one per request.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Treat these top-level subdirectories of the pool dir as absent: names under them are
+    /// not found and `list_pools` skips them. `serve` hides namespace directories from root.
+    pub fn with_hidden_dirs<I, S>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.hidden_dirs = dirs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether `name` is a hierarchical pool name under a hidden directory.
+    pub fn hides_name(&self, name: &str) -> bool {
+        name.split_once('/')
+            .is_some_and(|(first, _)| self.hidden_dirs.iter().any(|dir| dir == first))
+    }
+
+    fn hides_path(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.pool_dir) else {
+            return false;
+        };
+        let mut components = relative.components();
+        match (components.next(), components.next()) {
+            (Some(std::path::Component::Normal(first)), Some(_)) => self
+                .hidden_dirs
+                .iter()
+                .any(|dir| first == std::ffi::OsStr::new(dir)),
+            _ => false,
+        }
+    }
+
+    fn resolve(&self, pool_ref: &PoolRef) -> ApiResult<PathBuf> {
+        if matches!(pool_ref, PoolRef::Name(name) if self.hides_name(name)) {
+            return Err(Error::new(ErrorKind::NotFound)
+                .with_message("pool not found")
+                .with_hint("Namespaced pools are only reachable under their namespace."));
+        }
+        pool_ref.resolve_local_path(&self.pool_dir)
+    }
+
+    pub fn deadline(&self) -> &Deadline {
+        &self.deadline
+    }
+
     pub fn pool_dir(&self) -> &Path {
         &self.pool_dir
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn open_path(&self, path: &Path) -> ApiResult<Pool> {
+        self.deadline.check()?;
+        if self.read_only {
+            Pool::open_read_only(path)
+        } else {
+            Pool::open(path)
+        }
+    }
+
+    fn ensure_writable(&self, path: &Path) -> ApiResult<()> {
+        if !self.read_only {
+            return Ok(());
+        }
+        Err(Error::new(ErrorKind::Permission)
+            .with_message("pool directory is opened read-only")
+            .with_path(path)
+            .with_hint("Use a client without read-only mode to create or delete pools."))
+    }
+
     pub fn create_pool(&self, pool_ref: &PoolRef, options: PoolOptions) -> ApiResult<PoolInfo> {
-        let path = pool_ref.resolve_local_path(&self.pool_dir)?;
+        let path = self.resolve(pool_ref)?;
+        self.ensure_writable(&path)?;
+        // Hierarchical names (`team/events`) live in subdirectories created on demand.
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| parent.starts_with(&self.pool_dir))
+        {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                Error::new(map_io_error_kind(&err))
+                    .with_message("failed to create pool directory")
+                    .with_path(parent)
+                    .with_source(err)
+            })?;
+        }
         let pool = Pool::create(&path, options)?;
+        self.invalidate_path(&path);
         pool.info()
     }
 
     pub fn open_pool(&self, pool_ref: &PoolRef) -> ApiResult<Pool> {
-        let path = pool_ref.resolve_local_path(&self.pool_dir)?;
-        Pool::open(&path)
+        let path = self.resolve(pool_ref)?;
+        self.open_path(&path)
+    }
+
+    /// Run `f` on the pool, reusing a cached handle when the client has a pool cache and
+    /// opening a fresh one otherwise. Calls on the same cached pool are serialized.
+    pub fn with_pool<T>(
+        &self,
+        pool_ref: &PoolRef,
+        f: impl FnOnce(&mut Pool) -> ApiResult<T>,
+    ) -> ApiResult<T> {
+        let path = self.resolve(pool_ref)?;
+        let Some(cache) = &self.pool_cache else {
+            return f(&mut self.open_path(&path)?);
+        };
+        let pool =
+            lock_cache(cache).get_or_open(&path, self.read_only, |path| self.open_path(path))?;
+        let mut pool = pool.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut pool)
+    }
+
+    /// Drop any cached handle for the pool so the next access reopens it, e.g. after
+    /// another process rotated or replaced the file. No-op without a pool cache.
+    pub fn invalidate_pool(&self, pool_ref: &PoolRef) -> ApiResult<()> {
+        let path = self.resolve(pool_ref)?;
+        self.invalidate_path(&path);
+        Ok(())
+    }
+
+    /// Hit, miss, and eviction counters of the pool cache; `None` without one.
+    pub fn pool_cache_stats(&self) -> Option<PoolCacheStats> {
+        self.pool_cache
+            .as_ref()
+            .map(|cache| lock_cache(cache).stats())
+    }
+
+    fn invalidate_path(&self, path: &Path) {
+        if let Some(cache) = &self.pool_cache {
+            lock_cache(cache).invalidate(path);
+        }
     }
 
     pub fn pool_info(&self, pool_ref: &PoolRef) -> ApiResult<PoolInfo> {
-        let path = pool_ref.resolve_local_path(&self.pool_dir)?;
-        let pool = Pool::open(&path)?;
-        pool.info()
+        self.with_pool(pool_ref, |pool| pool.info())
     }
 
     pub fn list_pools(&self) -> ApiResult<Vec<PoolInfo>> {
         let mut pools = Vec::new();
-        let entries = std::fs::read_dir(&self.pool_dir).map_err(|err| {
+        let paths = find_pool_files(&self.pool_dir).map_err(|err| {
             Error::new(map_io_error_kind(&err))
                 .with_message("failed to read pool directory")
                 .with_path(&self.pool_dir)
                 .with_source(err)
         })?;
 
-        for entry in entries {
-            let entry = entry.map_err(|err| {
-                Error::new(ErrorKind::Io)
-                    .with_message("failed to read pool directory entry")
-                    .with_path(&self.pool_dir)
-                    .with_source(err)
-            })?;
-            let path = entry.path();
-            if path.extension().and_then(|ext| ext.to_str()) != Some("plasmite") {
+        for path in paths {
+            if self.hides_path(&path) {
                 continue;
             }
-            let pool = Pool::open(&path)?;
+            let pool = self.open_path(&path)?;
             pools.push(pool.info()?);
         }
 
@@ -118,18 +313,224 @@ impl LocalClient {
     }
 
     pub fn delete_pool(&self, pool_ref: &PoolRef) -> ApiResult<()> {
-        let path = pool_ref.resolve_local_path(&self.pool_dir)?;
+        let path = self.resolve(pool_ref)?;
+        self.ensure_writable(&path)?;
+        self.invalidate_path(&path);
         std::fs::remove_file(&path).map_err(|err| {
             Error::new(map_io_error_kind(&err))
                 .with_message("failed to delete pool")
                 .with_path(&path)
                 .with_source(err)
+        })?;
+        // Best effort: sidecars only exist for journaled, templated, pinned, or cursored pools.
+        for sidecar in pool_sidecar_paths(&path) {
+            let _ = if sidecar.is_dir() {
+                std::fs::remove_dir_all(&sidecar)
+            } else {
+                std::fs::remove_file(&sidecar)
+            };
+        }
+        Ok(())
+    }
+
+    /// Run the checks `delete_pool_with` runs and report what it would remove, without
+    /// touching the pool.
+    pub fn plan_delete_pool(
+        &self,
+        pool_ref: &PoolRef,
+        options: DeleteOptions,
+    ) -> ApiResult<DeletePlan> {
+        let path = self.resolve(pool_ref)?;
+        self.ensure_writable(&path)?;
+        if !path.exists() {
+            return Err(Error::new(ErrorKind::NotFound)
+                .with_message("failed to delete pool")
+                .with_path(&path));
+        }
+        if !options.force {
+            ensure_pool_idle(&path)?;
+        }
+        let files = std::iter::once(path.clone())
+            .chain(pool_sidecar_paths(&path))
+            .filter(|file| file.exists())
+            .map(|file| {
+                let bytes = path_bytes(&file);
+                (file, bytes)
+            })
+            .collect();
+        let seqs = Pool::open_read_only(&path)
+            .and_then(|pool| pool.bounds())
+            .ok()
+            .and_then(|bounds| Some((bounds.oldest_seq?, bounds.newest_seq?)));
+        Ok(DeletePlan {
+            trash_path: options.trash.then(|| trash_path_for(&path)),
+            path,
+            files,
+            seqs,
+        })
+    }
+
+    /// Delete a pool, refusing (`Busy`) while other processes use it unless `force` is set.
+    /// Returns the trash path when `trash` is set.
+    pub fn delete_pool_with(
+        &self,
+        pool_ref: &PoolRef,
+        options: DeleteOptions,
+    ) -> ApiResult<Option<PathBuf>> {
+        let path = self.resolve(pool_ref)?;
+        self.ensure_writable(&path)?;
+        if !path.exists() {
+            return Err(Error::new(ErrorKind::NotFound)
+                .with_message("failed to delete pool")
+                .with_path(&path));
+        }
+        if !options.force {
+            ensure_pool_idle(&path)?;
+        }
+        signal_deleted(&path);
+        self.invalidate_path(&path);
+        if !options.trash {
+            self.delete_pool(&PoolRef::path(path))?;
+            return Ok(None);
+        }
+        let trash_path = trash_path_for(&path);
+        if let Some(trash_dir) = trash_path.parent() {
+            std::fs::create_dir_all(trash_dir).map_err(|err| {
+                Error::new(map_io_error_kind(&err))
+                    .with_message("failed to create trash directory")
+                    .with_path(trash_dir)
+                    .with_source(err)
+            })?;
+        }
+        move_pool_files(&path, &trash_path).map_err(|err| {
+            err.with_message("failed to move pool to trash")
+                .with_path(&path)
+        })?;
+        Ok(Some(trash_path))
+    }
+
+    /// Move the most recently trashed copy back into place; fails if a pool already exists at
+    /// the target.
+    pub fn restore_pool(&self, pool_ref: &PoolRef) -> ApiResult<PathBuf> {
+        let path = self.resolve(pool_ref)?;
+        self.ensure_writable(&path)?;
+        let Some(trash_path) = latest_trash_path(&path) else {
+            return Err(Error::new(ErrorKind::NotFound)
+                .with_message("pool not found in trash")
+                .with_path(trash_slot(&path, 0))
+                .with_hint("Only pools deleted with --trash can be restored."));
+        };
+        if path.exists() {
+            return Err(Error::new(ErrorKind::AlreadyExists)
+                .with_message("a pool already exists at the restore target")
+                .with_path(&path)
+                .with_hint("Delete or rename the existing pool first."));
+        }
+        move_pool_files(&trash_path, &path).map_err(|err| {
+            err.with_message("failed to restore pool")
+                .with_path(&trash_path)
+        })?;
+        self.invalidate_path(&path);
+        Ok(path)
+    }
+
+    /// Copy a live pool to `out` under its append lock, then validate the copy the way
+    /// `doctor` does. The copy is written beside `out` and renamed into place only once it
+    /// validates, so `out` never holds a torn or corrupt file. Fails if `out` exists.
+    pub fn snapshot_pool(&self, pool_ref: &PoolRef, out: &Path) -> ApiResult<ValidationReport> {
+        let path = self.resolve(pool_ref)?;
+        if out.exists() {
+            return Err(Error::new(ErrorKind::AlreadyExists)
+                .with_message("snapshot target already exists")
+                .with_path(out)
+                .with_hint("Choose a different --out path or remove the existing file."));
+        }
+        let pool = self.open_path(&path)?;
+        let mut partial = out.as_os_str().to_owned();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+        let copied = pool.snapshot_to(&partial);
+        drop(pool);
+        if let Err(err) = copied {
+            let _ = std::fs::remove_file(&partial);
+            return Err(err);
+        }
+
+        let report = match self.validate_pool(&PoolRef::path(&partial)) {
+            Ok(report) if report.status == ValidationStatus::Ok => report,
+            Ok(report) => {
+                let _ = std::fs::remove_file(&partial);
+                let detail = report
+                    .issues
+                    .first()
+                    .map(|issue| issue.message.clone())
+                    .unwrap_or_else(|| "unknown issue".to_string());
+                return Err(Error::new(ErrorKind::Corrupt)
+                    .with_message(format!("snapshot failed validation: {detail}"))
+                    .with_path(&path)
+                    .with_hint("Run `plasmite doctor` on the source pool."));
+            }
+            Err(err) => {
+                let _ = std::fs::remove_file(&partial);
+                return Err(err);
+            }
+        };
+        std::fs::rename(&partial, out).map_err(|err| {
+            let _ = std::fs::remove_file(&partial);
+            Error::new(map_io_error_kind(&err))
+                .with_message("failed to move snapshot into place")
+                .with_path(out)
+                .with_source(err)
+        })?;
+        Ok(ValidationReport {
+            path: out.to_path_buf(),
+            ..report
+        }
+        .with_pool_ref(pool_ref.describe())
+        .with_snapshot(out))
+    }
+
+    /// Set or clear the pool's frozen flag; returns whether it was frozen before.
+    /// Frozen pools refuse appends from every process until unfrozen.
+    pub fn set_pool_frozen(&self, pool_ref: &PoolRef, frozen: bool) -> ApiResult<bool> {
+        let path = self.resolve(pool_ref)?;
+        self.ensure_writable(&path)?;
+        self.with_pool(pool_ref, |pool| pool.set_frozen(frozen))
+    }
+
+    /// Roll back an append whose writer died mid-write, using the pool's intent journal.
+    /// Returns the discarded seq, or `None` when there was nothing to repair.
+    pub fn repair_pool(&self, pool_ref: &PoolRef) -> ApiResult<Option<u64>> {
+        let path = self.resolve(pool_ref)?;
+        self.ensure_writable(&path)?;
+        self.with_pool(pool_ref, |pool| pool.repair_interrupted_append())
+    }
+
+    /// Other processes that currently have the pool file open (Linux only; empty elsewhere).
+    pub fn pool_users(&self, pool_ref: &PoolRef) -> ApiResult<Vec<PoolUser>> {
+        let path = self.resolve(pool_ref)?;
+        Ok(pool_users(&path))
+    }
+
+    /// Who is using the pool: registered handles, open-file holders, and append-lock state.
+    pub fn pool_presence(&self, pool_ref: &PoolRef) -> ApiResult<PoolPresence> {
+        let path = self.resolve(pool_ref)?;
+        if !path.exists() {
+            return Err(Error::new(ErrorKind::NotFound)
+                .with_message("pool not found")
+                .with_path(&path));
+        }
+        Ok(PoolPresence {
+            registrations: registrations(&path),
+            users: pool_users(&path),
+            append_lock_held: writer_lock_held(&path),
+            path,
         })
     }
 
     pub fn validate_pool(&self, pool_ref: &PoolRef) -> ApiResult<ValidationReport> {
-        let path = pool_ref.resolve_local_path(&self.pool_dir)?;
-        let pool = match Pool::open(&path) {
+        let path = self.resolve(pool_ref)?;
+        let pool = match self.open_path(&path) {
             Ok(pool) => pool,
             Err(err) if err.kind() == ErrorKind::Usage => {
                 let message = err
@@ -154,8 +555,38 @@ impl LocalClient {
             Err(err) => return Err(err),
         };
         let header = pool.header_from_mmap()?;
-        let report = validate_pool_state_report(header, pool.mmap(), &path)
-            .with_pool_ref(pool_ref.describe());
+        let report = validate_pool_state_report(header, pool.mmap(), &path);
+        self.deadline.check()?;
+        let report = apply_intent_journal(report, &pool).with_pool_ref(pool_ref.describe());
+        Ok(report)
+    }
+
+    /// Like `validate_pool`, then decode every retained payload with the pool codec.
+    pub fn validate_pool_deep(&self, pool_ref: &PoolRef) -> ApiResult<ValidationReport> {
+        self.validate_pool_deep_bounded(pool_ref, None)
+    }
+
+    /// Like `validate_pool_deep`, decoding at most the newest `max_frames` payloads.
+    pub fn validate_pool_deep_bounded(
+        &self,
+        pool_ref: &PoolRef,
+        max_frames: Option<u64>,
+    ) -> ApiResult<ValidationReport> {
+        let report = self.validate_pool(pool_ref)?;
+        if report.status == ValidationStatus::Corrupt {
+            return Ok(report);
+        }
+        let path = self.resolve(pool_ref)?;
+        let pool = self.open_path(&path)?;
+        let issues = deep_payload_issues(&pool, max_frames, &self.deadline)?;
+        if issues.is_empty() {
+            return Ok(report);
+        }
+        let mut report = report.set_issues(issues);
+        report.remediation_hints.push(
+            "Some payloads cannot be decoded; export readable messages and recreate the pool."
+                .to_string(),
+        );
         Ok(report)
     }
 }
@@ -166,10 +597,158 @@ impl Default for LocalClient {
     }
 }
 
+fn lock_cache(cache: &Mutex<PoolCache>) -> std::sync::MutexGuard<'_, PoolCache> {
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 fn resolve_name(name: &str, pool_dir: &Path) -> ApiResult<PathBuf> {
     resolve_named_pool_path(name, pool_dir).map_err(map_pool_name_resolve_error)
 }
 
+/// Best effort: a `deleted` control message lets live readers (follow, forward) stop cleanly.
+/// Skipped while another writer holds the append lock, so a forced delete never blocks on it.
+fn signal_deleted(path: &Path) {
+    if writer_lock_held(path) {
+        return;
+    }
+    if let Ok(mut pool) = Pool::open(path) {
+        let _ = pool.append_control(CONTROL_DELETED, Map::new(), Durability::Fast);
+    }
+}
+
+fn ensure_pool_idle(path: &Path) -> ApiResult<()> {
+    let mut users = pool_users(path);
+    // Registrations cover platforms without process discovery.
+    let own_pid = std::process::id();
+    for record in registrations(path) {
+        if record.pid != own_pid && users.iter().all(|user| user.pid != record.pid) {
+            users.push(PoolUser {
+                pid: record.pid,
+                command: record.command,
+            });
+        }
+    }
+    if users.is_empty() && !writer_lock_held(path) {
+        return Ok(());
+    }
+    let holders = if users.is_empty() {
+        "another process holds the append lock".to_string()
+    } else {
+        let list = users
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("open by {list}")
+    };
+    Err(Error::new(ErrorKind::Busy)
+        .with_message(format!("pool is in use: {holders}"))
+        .with_path(path)
+        .with_hint("Stop those processes first, or pass --force to delete anyway."))
+}
+
+/// Sidecars `delete_pool` removes alongside the pool file, whether or not they exist.
+fn pool_sidecar_paths(path: &Path) -> [PathBuf; 5] {
+    [
+        crate::core::journal::journal_path(path),
+        template_sidecar_path(path),
+        pins_sidecar_path(path),
+        cursors_sidecar_dir(path),
+        registry_dir(path),
+    ]
+}
+
+/// File size, or the summed size of every file under a directory.
+fn path_bytes(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| path_bytes(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// `TRASH_DIR/<name>`, then `<name>.1`, `<name>.2`, …: the `n`th trashed copy of `path`.
+fn trash_slot(path: &Path, n: u32) -> PathBuf {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    if n > 0 {
+        file_name.push(format!(".{n}"));
+    }
+    parent.join(TRASH_DIR).join(file_name)
+}
+
+/// The first free trash slot for `path`, so repeated deletes under one name never replace an
+/// earlier trashed copy.
+fn trash_path_for(path: &Path) -> PathBuf {
+    (0..)
+        .map(|n| trash_slot(path, n))
+        .find(|slot| !slot.exists())
+        .unwrap_or_else(|| trash_slot(path, 0))
+}
+
+/// The most recently trashed copy of `path`, which `restore_pool` moves back first.
+fn latest_trash_path(path: &Path) -> Option<PathBuf> {
+    (0..)
+        .map(|n| trash_slot(path, n))
+        .take_while(|slot| slot.exists())
+        .last()
+}
+
+/// Sidecar path for `pool_path` (`<pool file>.template.json`) holding the defaults recorded by
+/// `pool create --template` (template name, default tags, required fields).
+pub fn template_sidecar_path(pool_path: &Path) -> PathBuf {
+    let mut name = pool_path.as_os_str().to_os_string();
+    name.push(".template.json");
+    PathBuf::from(name)
+}
+
+/// `<pool>.pins.json`: copies of pinned messages kept outside the ring.
+pub fn pins_sidecar_path(pool_path: &Path) -> PathBuf {
+    let mut name = pool_path.as_os_str().to_os_string();
+    name.push(".pins.json");
+    PathBuf::from(name)
+}
+
+/// Rename the pool file and, when present, its intent-journal, template, pins, and cursor
+/// sidecars.
+fn move_pool_files(from: &Path, to: &Path) -> ApiResult<()> {
+    std::fs::rename(from, to).map_err(|err| {
+        Error::new(map_io_error_kind(&err))
+            .with_path(from)
+            .with_source(err)
+    })?;
+    let journal_from = crate::core::journal::journal_path(from);
+    if journal_from.exists() {
+        let _ = std::fs::rename(&journal_from, crate::core::journal::journal_path(to));
+    }
+    let template_from = template_sidecar_path(from);
+    if template_from.exists() {
+        let _ = std::fs::rename(&template_from, template_sidecar_path(to));
+    }
+    let pins_from = pins_sidecar_path(from);
+    if pins_from.exists() {
+        let _ = std::fs::rename(&pins_from, pins_sidecar_path(to));
+    }
+    let cursors_from = cursors_sidecar_dir(from);
+    if cursors_from.exists() {
+        let _ = std::fs::rename(&cursors_from, cursors_sidecar_dir(to));
+    }
+    // Registrations describe live handles on the old path; they never travel with the file.
+    let _ = std::fs::remove_dir_all(registry_dir(from));
+    Ok(())
+}
+
 fn map_io_error_kind(err: &std::io::Error) -> ErrorKind {
     match err.kind() {
         std::io::ErrorKind::NotFound => ErrorKind::NotFound,
@@ -179,16 +758,17 @@ fn map_io_error_kind(err: &std::io::Error) -> ErrorKind {
 }
 
 fn map_pool_name_resolve_error(err: PoolNameResolveError) -> Error {
-    match err {
-        PoolNameResolveError::ContainsPathSeparator => {
-            Error::new(ErrorKind::Usage).with_message("pool name must not contain path separators")
-        }
-    }
+    let message = match err {
+        PoolNameResolveError::EmptySegment => "pool name has an empty segment",
+        PoolNameResolveError::DotSegment => "pool name segments must not start with '.'",
+        PoolNameResolveError::InvalidCharacter => "pool name must not contain '\\' or NUL",
+    };
+    Error::new(ErrorKind::Usage).with_message(message)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{LocalClient, PoolRef, resolve_name};
+    use super::{LocalClient, Pool, PoolOptions, PoolRef, resolve_name};
     use std::path::PathBuf;
 
     #[test]
@@ -206,9 +786,11 @@ mod tests {
     }
 
     #[test]
-    fn poolref_name_rejects_slash() {
+    fn poolref_name_maps_slashes_to_subdirectories() {
         let pool_dir = PathBuf::from(".scratch/pools");
-        let err = resolve_name("foo/bar", &pool_dir).expect_err("err");
+        let path = resolve_name("team/foo/bar", &pool_dir).expect("path");
+        assert_eq!(path, PathBuf::from(".scratch/pools/team/foo/bar.plasmite"));
+        let err = resolve_name("foo/../bar", &pool_dir).expect_err("err");
         assert_eq!(err.kind(), super::ErrorKind::Usage);
     }
 
@@ -226,6 +808,84 @@ mod tests {
         assert!(client.pool_dir().to_string_lossy().contains(".plasmite"));
     }
 
+    #[test]
+    fn delete_plans_list_files_and_seqs_without_deleting() {
+        use super::{DeleteOptions, PoolApiExt, pins_sidecar_path};
+        let temp = tempfile::tempdir().expect("tempdir");
+        let client = LocalClient::new().with_pool_dir(temp.path());
+        let path = temp.path().join("events.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        for n in 0..3 {
+            pool.append_json_now(
+                &serde_json::json!({ "n": n }),
+                &[],
+                crate::core::pool::Durability::Fast,
+            )
+            .expect("append");
+        }
+        drop(pool);
+        std::fs::write(pins_sidecar_path(&path), b"[]").expect("pins");
+
+        let options = DeleteOptions {
+            trash: true,
+            ..DeleteOptions::default()
+        };
+        let plan = client
+            .plan_delete_pool(&PoolRef::name("events"), options)
+            .expect("plan");
+        assert_eq!(plan.seqs, Some((1, 3)));
+        assert_eq!(
+            plan.files,
+            vec![(path.clone(), 1024 * 1024), (pins_sidecar_path(&path), 2)]
+        );
+        assert_eq!(plan.bytes(), 1024 * 1024 + 2);
+        assert_eq!(
+            plan.trash_path,
+            Some(temp.path().join(".trash/events.plasmite"))
+        );
+        assert!(path.exists() && pins_sidecar_path(&path).exists());
+
+        let missing = client
+            .plan_delete_pool(&PoolRef::name("missing"), DeleteOptions::default())
+            .expect_err("missing");
+        assert_eq!(missing.kind(), super::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn pool_cache_reuses_handles_and_reopens_replaced_files() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let client = LocalClient::new()
+            .with_pool_dir(temp.path())
+            .with_pool_cache(1);
+        let (alpha, beta) = (PoolRef::name("alpha"), PoolRef::name("beta"));
+        for pool_ref in [&alpha, &beta] {
+            client
+                .create_pool(pool_ref, PoolOptions::new(1024 * 1024))
+                .expect("create");
+        }
+
+        client.pool_info(&alpha).expect("info");
+        client.clone().pool_info(&alpha).expect("info");
+        client.pool_info(&beta).expect("info");
+        let stats = client.pool_cache_stats().expect("stats");
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 2, 1));
+        assert_eq!((stats.capacity, stats.len), (1, 1));
+
+        // Replaced behind the client's back: the stale mapping is dropped, not reused.
+        std::fs::remove_file(temp.path().join("beta.plasmite")).expect("remove");
+        Pool::create(
+            temp.path().join("beta.plasmite"),
+            PoolOptions::new(2 * 1024 * 1024),
+        )
+        .expect("recreate");
+        let info = client.pool_info(&beta).expect("info");
+        assert_eq!(info.file_size, 2 * 1024 * 1024);
+        client.delete_pool(&beta).expect("delete");
+        let stats = client.pool_cache_stats().expect("stats");
+        assert_eq!((stats.invalidations, stats.len), (2, 0));
+        assert!(LocalClient::new().pool_cache_stats().is_none());
+    }
+
     #[test]
     fn poolref_uri_is_usage_error() {
         let client = LocalClient::new();
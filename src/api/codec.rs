@@ -0,0 +1,169 @@
+//! Purpose: Pluggable payload codecs mapping `(tags, data)` to frame bytes and back.
+//! Exports: `Codec`, `Lite3Codec`, `JsonCodec`, `codec_for`, `payload_codec`.
+//! Role: Lets API users pick a per-pool payload encoding without touching core storage.
+//! Invariants: The codec is fixed at pool create time and recorded in the header flags.
+//! Invariants: `JsonCodec` never calls into the Lite3 FFI; payloads always start with `{`.
+//! Invariants: Both codecs store the same `{meta:{tags}, data}` envelope shape.
+//! Invariants: The codec set is closed: it mirrors `PoolCodec`, which the header records as a
+//! flag, so adding a codec is a format change. No runtime registry exists, and `codec_for` and
+//! `payload_codec` are the only places that map ids or bytes to a codec.
+#![allow(clippy::result_large_err)]
+
+use super::Meta;
+use super::message::decode_payload;
+use crate::core::error::{Error, ErrorKind};
+use crate::core::lite3::encode_message;
+use crate::core::pool::PoolCodec;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Encode/decode the message envelope stored in a frame payload.
+pub trait Codec: Send + Sync {
+    /// Header-recorded identity of this codec.
+    fn id(&self) -> PoolCodec;
+
+    /// Encode tags + data into payload bytes. `data` must be a JSON object.
+    fn encode(&self, tags: &[String], data: &Value) -> Result<Vec<u8>, Error>;
+
+    /// Decode payload bytes into message metadata and data.
+    fn decode(&self, payload: &[u8]) -> Result<(Meta, Value), Error>;
+}
+
+/// Canonical Lite3 codec (default for new pools).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Lite3Codec;
+
+/// Raw JSON codec for embedders that want to avoid Lite3 on the append/read path.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonCodec;
+
+/// Codec implementation for a pool's recorded codec id.
+pub fn codec_for(codec: PoolCodec) -> &'static dyn Codec {
+    match codec {
+        PoolCodec::Lite3 => &Lite3Codec,
+        PoolCodec::Json => &JsonCodec,
+    }
+}
+
+/// Codec that wrote `payload`, for readers without the pool header at hand.
+///
+/// Sound only because the codec set is closed: JSON envelopes start with `{`, while Lite3
+/// documents start with a type byte. Prefer `Pool::codec` when the pool is available.
+pub fn payload_codec(payload: &[u8]) -> PoolCodec {
+    if payload.first() == Some(&b'{') {
+        PoolCodec::Json
+    } else {
+        PoolCodec::Lite3
+    }
+}
+
+impl Codec for Lite3Codec {
+    fn id(&self) -> PoolCodec {
+        PoolCodec::Lite3
+    }
+
+    fn encode(&self, tags: &[String], data: &Value) -> Result<Vec<u8>, Error> {
+        Ok(encode_message(tags, data)?.as_slice().to_vec())
+    }
+
+    fn decode(&self, payload: &[u8]) -> Result<(Meta, Value), Error> {
+        decode_payload(payload)
+    }
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a> {
+    meta: MetaRef<'a>,
+    data: &'a Value,
+}
+
+#[derive(Serialize)]
+struct MetaRef<'a> {
+    tags: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    meta: EnvelopeMeta,
+    data: Value,
+}
+
+#[derive(Deserialize)]
+struct EnvelopeMeta {
+    tags: Vec<String>,
+}
+
+impl Codec for JsonCodec {
+    fn id(&self) -> PoolCodec {
+        PoolCodec::Json
+    }
+
+    fn encode(&self, tags: &[String], data: &Value) -> Result<Vec<u8>, Error> {
+        if !matches!(data, Value::Object(_)) {
+            return Err(Error::new(ErrorKind::Usage).with_message("data must be object"));
+        }
+        serde_json::to_vec(&EnvelopeRef {
+            meta: MetaRef { tags },
+            data,
+        })
+        .map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message("failed to serialize json")
+                .with_source(err)
+        })
+    }
+
+    fn decode(&self, payload: &[u8]) -> Result<(Meta, Value), Error> {
+        let envelope: Envelope = serde_json::from_slice(payload).map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("invalid json payload envelope")
+                .with_source(err)
+        })?;
+        if !envelope.data.is_object() {
+            return Err(Error::new(ErrorKind::Corrupt).with_message("data is not object"));
+        }
+        Ok((Meta::new(envelope.meta.tags), envelope.data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Codec, JsonCodec, Lite3Codec, codec_for, payload_codec};
+    use crate::core::pool::PoolCodec;
+    use serde_json::json;
+
+    #[test]
+    fn codecs_round_trip_the_same_envelope() {
+        let tags = vec!["a".to_string(), "b".to_string()];
+        let data = json!({"x": 1, "nested": {"y": [true, null]}});
+        for codec in [&Lite3Codec as &dyn Codec, &JsonCodec] {
+            let payload = codec.encode(&tags, &data).expect("encode");
+            let (meta, out) = codec.decode(&payload).expect("decode");
+            assert_eq!(meta.tags, tags);
+            assert_eq!(out, data);
+        }
+    }
+
+    #[test]
+    fn json_codec_payload_is_plain_json() {
+        let payload = JsonCodec.encode(&[], &json!({"x": 1})).expect("encode");
+        assert_eq!(payload, br#"{"meta":{"tags":[]},"data":{"x":1}}"#);
+        assert!(JsonCodec.encode(&[], &json!([1])).is_err());
+        assert!(JsonCodec.decode(b"{\"data\":{}}").is_err());
+    }
+
+    #[test]
+    fn codec_for_matches_recorded_id() {
+        assert_eq!(codec_for(PoolCodec::Lite3).id(), PoolCodec::Lite3);
+        assert_eq!(codec_for(PoolCodec::Json).id(), PoolCodec::Json);
+    }
+
+    #[test]
+    fn payload_codec_identifies_the_writer() {
+        let data = json!({"x": 1});
+        for codec in [&Lite3Codec as &dyn Codec, &JsonCodec] {
+            let payload = codec.encode(&[], &data).expect("encode");
+            assert_eq!(payload_codec(&payload), codec.id());
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Purpose: Reserved control messages for in-band pool signaling.
+//! Exports: `CONTROL_ROTATED`, `CONTROL_DELETED`, `control_kind`, `control_payload`,
+//! `check_control_payload`.
+//! Role: Shared vocabulary between writers that signal lifecycle events and readers that react.
+//! Invariants: A control message is a frame with `FRAME_FLAG_CONTROL` whose data is an object
+//! with a string `control` field naming the kind; other fields are kind-specific details.
+//! Invariants: Unknown kinds are legal; readers that do not understand a kind skip it.
+
+use crate::core::error::{Error, ErrorKind};
+use crate::core::frame::FRAME_FLAG_CONTROL;
+use serde_json::{Map, Value};
+
+/// The pool file is about to be replaced; readers reopen the path once it changes and start
+/// over at the oldest retained message of the new file.
+pub const CONTROL_ROTATED: &str = "rotated";
+/// The pool is being deleted; live readers stop.
+pub const CONTROL_DELETED: &str = "deleted";
+
+/// The control kind of a message, or `None` for ordinary messages.
+pub fn control_kind(flags: u32, data: &Value) -> Option<&str> {
+    if flags & FRAME_FLAG_CONTROL == 0 {
+        return None;
+    }
+    data.get("control").and_then(Value::as_str)
+}
+
+/// Build control message data: `details` plus `"control": kind`.
+pub fn control_payload(kind: &str, mut details: Map<String, Value>) -> Value {
+    details.insert("control".to_string(), Value::String(kind.to_string()));
+    Value::Object(details)
+}
+
+/// Control-flagged appends must carry a non-empty `control` kind so readers can dispatch.
+#[allow(clippy::result_large_err)]
+pub fn check_control_payload(flags: u32, data: &Value) -> Result<(), Error> {
+    if flags & FRAME_FLAG_CONTROL == 0 {
+        return Ok(());
+    }
+    match control_kind(flags, data) {
+        Some(kind) if !kind.is_empty() => Ok(()),
+        _ => Err(Error::new(ErrorKind::Usage)
+            .with_message("control messages need a string `control` field in data")
+            .with_hint("Example: '{\"control\": \"rotated\"}'.")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CONTROL_ROTATED, check_control_payload, control_kind, control_payload};
+    use crate::core::error::ErrorKind;
+    use crate::core::frame::FRAME_FLAG_CONTROL;
+    use serde_json::{Map, json};
+
+    #[test]
+    fn control_kind_requires_flag_and_field() {
+        let data = control_payload(CONTROL_ROTATED, Map::new());
+        assert_eq!(control_kind(FRAME_FLAG_CONTROL, &data), Some("rotated"));
+        assert_eq!(control_kind(0, &data), None);
+        assert_eq!(control_kind(FRAME_FLAG_CONTROL, &json!({"op": 1})), None);
+
+        assert!(check_control_payload(0, &json!({"op": 1})).is_ok());
+        assert!(check_control_payload(FRAME_FLAG_CONTROL, &data).is_ok());
+        let err = check_control_payload(FRAME_FLAG_CONTROL, &json!({"control": ""}))
+            .expect_err("empty kind");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+}
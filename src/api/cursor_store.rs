@@ -0,0 +1,117 @@
+//! Purpose: Named consumer cursors persisted beside a pool, for resumable reads from bindings.
+//! Exports: `cursors_sidecar_dir`, `save_cursor`, `load_cursor`.
+//! Role: Backing store for `plsm_cursor_save` / `plsm_cursor_load` (and the bindings built on
+//! them); a cursor records the last seq a named consumer finished, so it resumes at seq + 1.
+//! Invariants: Cursor `<name>` lives at `<pool>.cursors/<name>.json` as `{"seq": N}`, the same
+//! shape as the CLI's forward and hook cursor files; one file per name, so consumers saving
+//! different names never contend.
+//! Invariants: Saves are atomic (temp file + rename); a crash leaves the previous value.
+//! Invariants: Names are 1-128 characters of `[A-Za-z0-9_.-]` and never start with `.`, so
+//! they cannot escape the sidecar directory or collide with temp files.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+use crate::core::error::{Error, ErrorKind};
+
+const MAX_CURSOR_NAME_LEN: usize = 128;
+
+/// `<pool>.cursors/`: one `<name>.json` file per saved cursor.
+pub fn cursors_sidecar_dir(pool_path: &Path) -> PathBuf {
+    let mut name = pool_path.as_os_str().to_os_string();
+    name.push(".cursors");
+    PathBuf::from(name)
+}
+
+/// Record that consumer `name` has finished `seq`.
+#[allow(clippy::result_large_err)]
+pub fn save_cursor(pool_path: &Path, name: &str, seq: u64) -> Result<(), Error> {
+    let path = cursor_path(pool_path, name)?;
+    let io_error = |err: std::io::Error| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to save cursor")
+            .with_path(&path)
+            .with_source(err)
+    };
+    fs::create_dir_all(cursors_sidecar_dir(pool_path)).map_err(io_error)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, format!("{}\n", json!({ "seq": seq }))).map_err(io_error)?;
+    fs::rename(&tmp, &path).map_err(|err| {
+        let _ = fs::remove_file(&tmp);
+        io_error(err)
+    })
+}
+
+/// Last seq consumer `name` saved for the pool; `None` when it never saved one.
+#[allow(clippy::result_large_err)]
+pub fn load_cursor(pool_path: &Path, name: &str) -> Result<Option<u64>, Error> {
+    let path = cursor_path(pool_path, name)?;
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(Error::new(ErrorKind::Io)
+                .with_message("failed to read cursor")
+                .with_path(&path)
+                .with_source(err));
+        }
+    };
+    let corrupt = || {
+        Error::new(ErrorKind::Corrupt)
+            .with_message("invalid cursor file")
+            .with_path(&path)
+            .with_hint("Delete the cursor file to start the consumer over.")
+    };
+    let value: Value = serde_json::from_str(&raw).map_err(|err| corrupt().with_source(err))?;
+    value
+        .get("seq")
+        .and_then(Value::as_u64)
+        .map(Some)
+        .ok_or_else(corrupt)
+}
+
+#[allow(clippy::result_large_err)]
+fn cursor_path(pool_path: &Path, name: &str) -> Result<PathBuf, Error> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_CURSOR_NAME_LEN
+        && !name.starts_with('.')
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.' | b'-'));
+    if !valid {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!("invalid cursor name: {name:?}"))
+            .with_hint("Use 1-128 letters, digits, '_', '.', or '-', not starting with '.'."));
+    }
+    Ok(cursors_sidecar_dir(pool_path).join(format!("{name}.json")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cursors_sidecar_dir, load_cursor, save_cursor};
+    use crate::core::error::ErrorKind;
+
+    #[test]
+    fn named_cursors_round_trip_independently() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let pool = temp.path().join("events.plasmite");
+        assert_eq!(load_cursor(&pool, "indexer").expect("load"), None);
+
+        save_cursor(&pool, "indexer", 41).expect("save");
+        save_cursor(&pool, "indexer", 42).expect("save");
+        save_cursor(&pool, "mailer.v2", 7).expect("save");
+        assert_eq!(load_cursor(&pool, "indexer").expect("load"), Some(42));
+        assert_eq!(load_cursor(&pool, "mailer.v2").expect("load"), Some(7));
+        assert!(cursors_sidecar_dir(&pool).join("indexer.json").exists());
+
+        for name in ["", ".hidden", "../escape", "a/b", &"x".repeat(129)] {
+            let err = save_cursor(&pool, name, 1).expect_err(name);
+            assert_eq!(err.kind(), ErrorKind::Usage, "{name}");
+        }
+
+        std::fs::write(cursors_sidecar_dir(&pool).join("broken.json"), "{}").expect("write");
+        let err = load_cursor(&pool, "broken").expect_err("corrupt");
+        assert_eq!(err.kind(), ErrorKind::Corrupt);
+    }
+}
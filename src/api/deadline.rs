@@ -0,0 +1,98 @@
+//! Purpose: Bound long local operations by a deadline and let another thread cancel them.
+//! Exports: `Deadline`, `CancelOnDrop`.
+//! Role: `LocalClient::with_deadline` carries one into opens, validation, and pool scans; the
+//! CLI builds it from `--op-timeout`, and `serve` builds one per request.
+//! Invariants: Operations check between units of work (a pool, a frame), so an expired or
+//! cancelled operation stops at the next check with a `Busy` error and changes nothing.
+//! Invariants: Clones share the cancellation flag; cancelling any clone stops them all.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::core::error::{Error, ErrorKind};
+
+/// When a long operation must stop; the default never expires and is never cancelled.
+#[derive(Clone, Debug, Default)]
+pub struct Deadline {
+    expires: Option<(Instant, Duration)>,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Cancels its deadline when dropped; see `Deadline::cancel_on_drop`.
+#[derive(Debug)]
+pub struct CancelOnDrop(Deadline);
+
+impl Deadline {
+    /// Expires `timeout` from now.
+    pub fn after(timeout: Duration) -> Self {
+        Self {
+            expires: Some((Instant::now() + timeout, timeout)),
+            cancelled: Arc::default(),
+        }
+    }
+
+    /// Stop every operation checking this deadline (or a clone) at its next check.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// A guard that cancels this deadline when dropped, e.g. with the request future that
+    /// is waiting on a blocking task, so a disconnected caller stops the work it started.
+    pub fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop(self.clone())
+    }
+
+    /// `Busy` once the deadline has passed or it was cancelled.
+    #[allow(clippy::result_large_err)]
+    pub fn check(&self) -> Result<(), Error> {
+        if self.is_cancelled() {
+            return Err(Error::new(ErrorKind::Busy)
+                .with_message("operation cancelled")
+                .with_hint(
+                    "The caller stopped waiting; retry the operation if it is still needed.",
+                ));
+        }
+        match self.expires {
+            Some((at, timeout)) if Instant::now() >= at => Err(Error::new(ErrorKind::Busy)
+                .with_message(format!("operation timed out after {timeout:?}"))
+                .with_hint(
+                    "Raise --op-timeout, or narrow the operation (e.g. doctor --max-frames N).",
+                )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deadline;
+    use crate::core::error::ErrorKind;
+    use std::time::Duration;
+
+    #[test]
+    fn deadlines_expire_and_cancel_across_clones() {
+        assert!(Deadline::default().check().is_ok());
+        let expired = Deadline::after(Duration::ZERO);
+        let err = expired.check().expect_err("expired");
+        assert_eq!(err.kind(), ErrorKind::Busy);
+        assert!(err.message().unwrap_or_default().contains("timed out"));
+
+        let deadline = Deadline::after(Duration::from_secs(60));
+        let shared = deadline.clone();
+        assert!(shared.check().is_ok());
+        drop(deadline.cancel_on_drop());
+        let err = shared.check().expect_err("cancelled");
+        assert_eq!(err.message(), Some("operation cancelled"));
+    }
+}
@@ -0,0 +1,452 @@
+//! Purpose: Dump the raw bytes and surrounding ring/index state of one frame location.
+//! Exports: `ExplainTarget`, `FrameExplain`, `HeaderField`, `IndexProbe`, `explain_frame`.
+//! Role: Forensics model behind `plasmite explain`; complements `doctor` issue offsets.
+//! Invariants: Offsets are ring-relative (as in `doctor` issues); `file_offset` adds the ring base.
+//! Invariants: Undecodable headers are annotated, never returned as errors; the pool is not mutated.
+//! Invariants: Neighbor boundaries come from walking committed frames forward from the tail.
+
+use crate::core::cursor::{ReadResult, read_frame_at};
+use crate::core::error::{Error, ErrorKind};
+use crate::core::frame::{self, FRAME_HEADER_LEN, FRAME_MAGIC, FrameHeader, FrameState};
+use crate::core::hex::hex_encode_spaced;
+use crate::core::journal;
+use crate::core::pool::{Pool, PoolHeader};
+use crate::core::view::RingView;
+use serde_json::{Value, json};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExplainTarget {
+    Seq(u64),
+    /// Ring-relative byte offset.
+    Offset(u64),
+}
+
+/// One annotated header field: byte range within the header, decoded value, and a finding.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeaderField {
+    pub start: usize,
+    pub end: usize,
+    pub name: &'static str,
+    pub value: String,
+    pub note: Option<String>,
+}
+
+/// Index slot contents for a seq near the explained frame.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexProbe {
+    pub seq: u64,
+    pub slot: u64,
+    pub stored_seq: u64,
+    pub stored_offset: u64,
+    /// `ok`, `stale` (points elsewhere than the frame walk found), `reused` (slot holds
+    /// another seq), `empty`, `out_of_range`, or `unverified` (seq not reached by the walk).
+    pub status: &'static str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameExplain {
+    pub ring: PoolHeader,
+    pub offset: u64,
+    pub file_offset: u64,
+    /// Whether `offset` is a frame boundary reached by walking from the tail.
+    pub on_boundary: bool,
+    pub header_bytes: Vec<u8>,
+    pub fields: Vec<HeaderField>,
+    pub decode_error: Option<String>,
+    pub seq: Option<u64>,
+    pub payload_len: Option<u32>,
+    pub stored_crc32c: Option<u32>,
+    pub computed_crc32c: Option<u32>,
+    pub commit_marker_ok: Option<bool>,
+    pub prev_offset: Option<u64>,
+    pub next_offset: Option<u64>,
+    pub index: Vec<IndexProbe>,
+}
+
+impl FrameExplain {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "offset": self.offset,
+            "file_offset": self.file_offset,
+            "on_boundary": self.on_boundary,
+            "seq": self.seq,
+            "header_hex": hex_encode_spaced(&self.header_bytes),
+            "fields": self.fields.iter().map(|field| json!({
+                "range": [field.start, field.end],
+                "name": field.name,
+                "hex": hex_encode_spaced(&self.header_bytes[field.start..field.end]),
+                "value": field.value,
+                "note": field.note,
+            })).collect::<Vec<_>>(),
+            "decode_error": self.decode_error,
+            "checksums": {
+                "payload_len": self.payload_len,
+                "stored_crc32c": self.stored_crc32c,
+                "computed_crc32c": self.computed_crc32c,
+                "commit_marker_ok": self.commit_marker_ok,
+            },
+            "neighbors": {
+                "prev_offset": self.prev_offset,
+                "next_offset": self.next_offset,
+            },
+            "index": self.index.iter().map(|probe| json!({
+                "seq": probe.seq,
+                "slot": probe.slot,
+                "stored_seq": probe.stored_seq,
+                "stored_offset": probe.stored_offset,
+                "status": probe.status,
+            })).collect::<Vec<_>>(),
+            "ring": {
+                "ring_offset": self.ring.ring_offset,
+                "ring_size": self.ring.ring_size,
+                "tail_off": self.ring.tail_off,
+                "head_off": self.ring.head_off,
+                "oldest_seq": self.ring.oldest_seq,
+                "newest_seq": self.ring.newest_seq,
+                "index_capacity": self.ring.index_capacity,
+            },
+        })
+    }
+}
+
+/// Lowercase hex pairs separated by spaces.
+/// Explain the frame holding `target`. Seqs must still be retained; offsets may point anywhere
+/// inside the ring, including mid-frame or at garbage.
+#[allow(clippy::result_large_err)]
+pub fn explain_frame(pool: &Pool, target: ExplainTarget) -> Result<FrameExplain, Error> {
+    let ring = pool.ring_view()?;
+    let header = ring.header();
+    let ring_size = ring.ring_size();
+    let walk = walk_frames(&ring);
+
+    let offset = match target {
+        ExplainTarget::Offset(offset) => {
+            if offset >= header.ring_size {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message(format!(
+                        "offset {offset} is outside the ring (size {})",
+                        header.ring_size
+                    ))
+                    .with_hint("Offsets are ring-relative, as reported by `plasmite doctor`."));
+            }
+            offset as usize
+        }
+        ExplainTarget::Seq(seq) => walk
+            .iter()
+            .find(|step| step.seq == seq)
+            .map(|step| step.offset)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::NotFound)
+                    .with_message("message not found")
+                    .with_seq(seq)
+                    .with_hint("Use --offset to inspect frames the tail walk cannot reach.")
+            })?,
+    };
+
+    let header_bytes = ring
+        .bytes(offset, FRAME_HEADER_LEN.min(ring_size - offset))?
+        .to_vec();
+    let fields = annotate_header(&header_bytes, ring_size);
+
+    let mut explain = FrameExplain {
+        ring: header,
+        offset: offset as u64,
+        file_offset: header.ring_offset + offset as u64,
+        on_boundary: walk.iter().any(|step| step.offset == offset),
+        header_bytes,
+        fields,
+        decode_error: None,
+        seq: None,
+        payload_len: None,
+        stored_crc32c: None,
+        computed_crc32c: None,
+        commit_marker_ok: None,
+        prev_offset: walk
+            .iter()
+            .map(|step| step.offset)
+            .filter(|&walked| walked < offset)
+            .max()
+            .map(|walked| walked as u64),
+        next_offset: walk
+            .iter()
+            .map(|step| step.offset)
+            .filter(|&walked| walked > offset)
+            .min()
+            .map(|walked| walked as u64),
+        index: Vec::new(),
+    };
+
+    match FrameHeader::decode(&explain.header_bytes) {
+        Ok(frame_header) => {
+            explain.seq = Some(frame_header.seq);
+            explain.payload_len = Some(frame_header.payload_len);
+            explain.stored_crc32c = Some(frame_header.crc32c);
+            if let Err(err) = frame_header.validate(ring_size) {
+                explain.decode_error = Some(err.to_string());
+            } else if frame_header.state != FrameState::Wrap {
+                let total =
+                    frame::frame_total_len(FRAME_HEADER_LEN, frame_header.payload_len as usize);
+                if let Some(total) = total.filter(|total| offset + total <= ring_size) {
+                    let payload_start = offset + FRAME_HEADER_LEN;
+                    let payload = ring.bytes(payload_start, frame_header.payload_len as usize)?;
+                    explain.computed_crc32c = Some(journal::crc32c(payload));
+                    explain.commit_marker_ok =
+                        Some(ring.has_commit_marker(payload_start + payload.len()));
+                    if explain.on_boundary || explain.next_offset.is_none() {
+                        let next = offset + total;
+                        explain.next_offset = Some(if next == ring_size { 0 } else { next as u64 });
+                    }
+                } else {
+                    explain.decode_error = Some("frame extends past the ring end".to_string());
+                }
+            }
+        }
+        Err(err) => explain.decode_error = Some(err.to_string()),
+    }
+
+    let center = explain.seq.or(match target {
+        ExplainTarget::Seq(seq) => Some(seq),
+        ExplainTarget::Offset(_) => None,
+    });
+    if let Some(center) = center.filter(|_| header.index_capacity > 0) {
+        explain.index = [center.checked_sub(1), Some(center), center.checked_add(1)]
+            .into_iter()
+            .flatten()
+            .filter(|&seq| seq != 0)
+            .map(|seq| probe_index(&ring, &walk, seq))
+            .collect();
+    }
+    Ok(explain)
+}
+
+struct WalkStep {
+    offset: usize,
+    seq: u64,
+}
+
+/// Committed frames from the tail up to `newest_seq`, stopping at the first unreadable one.
+fn walk_frames(ring: &RingView<'_>) -> Vec<WalkStep> {
+    let header = ring.header();
+    let mut steps = Vec::new();
+    if header.oldest_seq == 0 {
+        return steps;
+    }
+    let max_steps = header.newest_seq - header.oldest_seq + 2;
+    let mut offset = header.tail_off as usize;
+    for _ in 0..max_steps {
+        match read_frame_at(ring, offset) {
+            Ok(ReadResult::Message { frame, next_off }) => {
+                steps.push(WalkStep {
+                    offset,
+                    seq: frame.seq,
+                });
+                if frame.seq >= header.newest_seq {
+                    break;
+                }
+                offset = next_off;
+            }
+            Ok(ReadResult::Wrap) if offset != 0 => offset = 0,
+            _ => break,
+        }
+    }
+    steps
+}
+
+fn probe_index(ring: &RingView<'_>, walk: &[WalkStep], seq: u64) -> IndexProbe {
+    let header = ring.header();
+    let slot = seq % u64::from(header.index_capacity);
+    let (stored_seq, stored_offset) = ring.index_entry(seq).unwrap_or((0, 0));
+    let walked = walk
+        .iter()
+        .find(|step| step.seq == seq)
+        .map(|step| step.offset as u64);
+    let status = if stored_seq == 0 && stored_offset == 0 {
+        "empty"
+    } else if stored_seq != seq {
+        "reused"
+    } else if stored_offset >= header.ring_size {
+        "out_of_range"
+    } else {
+        match walked {
+            Some(walked) if walked == stored_offset => "ok",
+            Some(_) => "stale",
+            None => "unverified",
+        }
+    };
+    IndexProbe {
+        seq,
+        slot,
+        stored_seq,
+        stored_offset,
+        status,
+    }
+}
+
+fn annotate_header(bytes: &[u8], ring_size: usize) -> Vec<HeaderField> {
+    let mut fields = Vec::new();
+    let mut field = |start: usize,
+                     end: usize,
+                     name: &'static str,
+                     render: &dyn Fn(&[u8]) -> (String, Option<String>)| {
+        if end <= bytes.len() {
+            let (value, note) = render(&bytes[start..end]);
+            fields.push(HeaderField {
+                start,
+                end,
+                name,
+                value,
+                note,
+            });
+        }
+    };
+    field(0, 4, "magic", &|raw| {
+        let ok = raw == FRAME_MAGIC;
+        (
+            String::from_utf8_lossy(raw).escape_debug().to_string(),
+            (!ok).then(|| "expected FRM1".to_string()),
+        )
+    });
+    field(4, 8, "state", &|raw| {
+        let value = u32_le(raw);
+        let name = match value {
+            0 => "empty",
+            1 => "writing",
+            2 => "committed",
+            3 => "wrap",
+            _ => "invalid",
+        };
+        (
+            format!("{value} ({name})"),
+            (value > 3).then(|| "unknown state".to_string()),
+        )
+    });
+    field(8, 12, "flags", &|raw| {
+        let value = u32_le(raw);
+        let names = frame::frame_flag_names(value);
+        let reserved = value & !frame::FRAME_FLAGS_SETTABLE;
+        (
+            if names.is_empty() {
+                format!("{value:#x}")
+            } else {
+                format!("{value:#x} ({})", names.join(", "))
+            },
+            (reserved != 0).then(|| format!("reserved bits set: {reserved:#x}")),
+        )
+    });
+    field(12, 16, "header_len", &|raw| {
+        let value = u32_le(raw);
+        (
+            value.to_string(),
+            (value as usize != FRAME_HEADER_LEN).then(|| format!("expected {FRAME_HEADER_LEN}")),
+        )
+    });
+    field(16, 24, "seq", &|raw| (u64_le(raw).to_string(), None));
+    field(24, 32, "timestamp_ns", &|raw| {
+        (u64_le(raw).to_string(), None)
+    });
+    field(32, 36, "payload_len", &|raw| {
+        let value = u32_le(raw);
+        let max = frame::max_payload(ring_size, FRAME_HEADER_LEN);
+        (
+            value.to_string(),
+            (value as usize > max).then(|| format!("exceeds max payload {max}")),
+        )
+    });
+    let payload_len = (bytes.len() >= 36).then(|| u32_le(&bytes[32..36]));
+    field(36, 40, "payload_len_xor", &|raw| {
+        let value = u32_le(raw);
+        let ok = payload_len.is_some_and(|len| len ^ value == 0xFFFF_FFFF);
+        (
+            format!("{value:#010x}"),
+            (!ok).then(|| "does not complement payload_len".to_string()),
+        )
+    });
+    field(40, 44, "crc32c", &|raw| {
+        let value = u32_le(raw);
+        (
+            format!("{value:#010x}"),
+            (value == 0).then(|| "unset".to_string()),
+        )
+    });
+    field(44, 48, "reserved", &|raw| {
+        let zero = raw.iter().all(|byte| *byte == 0);
+        (
+            String::new(),
+            (!zero).then(|| "non-zero reserved bytes".to_string()),
+        )
+    });
+    field(48, FRAME_HEADER_LEN, "bloom", &|raw| {
+        let mut out = [0u8; 16];
+        out.copy_from_slice(raw);
+        let value = u128::from_le_bytes(out);
+        if value == 0 {
+            ("none".to_string(), None)
+        } else {
+            (format!("{value:#034x}"), None)
+        }
+    });
+    fields
+}
+
+fn u32_le(raw: &[u8]) -> u32 {
+    let mut out = [0u8; 4];
+    out.copy_from_slice(raw);
+    u32::from_le_bytes(out)
+}
+
+fn u64_le(raw: &[u8]) -> u64 {
+    let mut out = [0u8; 8];
+    out.copy_from_slice(raw);
+    u64::from_le_bytes(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExplainTarget, explain_frame};
+    use crate::core::error::ErrorKind;
+    use crate::core::lite3;
+    use crate::core::pool::{Pool, PoolOptions};
+    use serde_json::json;
+
+    #[test]
+    fn explains_frame_by_seq_and_by_mid_frame_offset() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        for n in 0..3 {
+            let payload = lite3::encode_message(&[], &json!({ "n": n })).expect("payload");
+            pool.append(payload.as_slice()).expect("append");
+        }
+
+        let middle = explain_frame(&pool, ExplainTarget::Seq(2)).expect("explain");
+        assert_eq!(middle.seq, Some(2));
+        assert!(middle.on_boundary);
+        assert!(middle.decode_error.is_none());
+        assert_eq!(middle.commit_marker_ok, Some(true));
+        assert!(
+            middle
+                .fields
+                .iter()
+                .all(|field| field.note.is_none() || field.name == "crc32c")
+        );
+        assert_eq!(middle.prev_offset, Some(0));
+        let statuses = middle
+            .index
+            .iter()
+            .map(|probe| (probe.seq, probe.status))
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, [(1, "ok"), (2, "ok"), (3, "ok")]);
+
+        let inside =
+            explain_frame(&pool, ExplainTarget::Offset(middle.offset + 8)).expect("explain");
+        assert!(!inside.on_boundary);
+        assert!(inside.decode_error.is_some());
+        assert_eq!(inside.prev_offset, Some(middle.offset));
+        assert_eq!(inside.next_offset, middle.next_offset);
+
+        let missing = explain_frame(&pool, ExplainTarget::Seq(9)).expect_err("missing");
+        assert_eq!(missing.kind(), ErrorKind::NotFound);
+        let outside = explain_frame(&pool, ExplainTarget::Offset(u64::MAX)).expect_err("outside");
+        assert_eq!(outside.kind(), ErrorKind::Usage);
+    }
+}
@@ -1,22 +1,71 @@
 //! Purpose: Define public message types and append/get/tail/replay helpers for the API.
-//! Exports: `Message`, `Meta`, `TailOptions`, `Tail`, `Lite3Tail`, `ReplayOptions`, `Replay`.
+//! Exports: `Message`, `Meta`, `TailOptions`, `Tail`, `Lite3Tail`, `ReplayOptions`, `Replay`,
+//! `validate_message_frame`.
 //! Role: Stable message envelope aligned with the CLI contract.
 //! Invariants: Message fields mirror CLI JSON; time is RFC3339 UTC.
 //! Invariants: Tail streams preserve ordering and avoid unbounded buffering.
 //! Invariants: Replay is bounded; all messages are collected up front.
+//! Invariants: Chunked messages are joined and read at their last chunk's seq; continuation
+//! chunks are skipped, and raw Lite3 frame reads reject chunked messages.
+//! Invariants: Attachments stay bytes (`Message::attachment`); `to_json` is the only place they
+//! become base64. Raw Lite3 frame reads reject messages that carry one.
+//! Invariants: `strict` tails run `validate_message_frame` before decoding; others decode
+//! only the fields they read.
+//! Invariants: A tail with `min_seq` yields nothing until the pool holds that seq; reaching
+//! its timeout first is a `Busy` error, not an empty end of stream.
+//! Notes: `try_next_*` variants return `None` instead of waiting, for batching readers.
 #![allow(clippy::result_large_err)]
 
+use super::codec::{Codec, codec_for};
+use super::control::{check_control_payload, control_kind, control_payload};
+use crate::core::attachment::{self, references_attachment};
+use crate::core::base64::base64_encode;
+use crate::core::chunk::chunk_position;
 use crate::core::cursor::{Cursor, CursorResult, FrameRef};
 use crate::core::error::{Error, ErrorKind};
+use crate::core::frame::{
+    FRAME_FLAG_ATTACHMENT, FRAME_FLAG_CHUNK, FRAME_FLAG_CONTROL, frame_flag_names,
+};
 use crate::core::lite3::{Lite3DocRef, sys, validate_bytes};
 use crate::core::notify::{NotifyError, PoolSemaphore, WaitOutcome, open_for_path};
-use crate::core::pool::{AppendOptions, Durability, Pool};
-use serde_json::Value;
+use crate::core::pool::{AppendOptions, Durability, Pool, PoolCodec};
+use serde_json::{Map, Value};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Meta {
     pub tags: Vec<String>,
+    /// Frame flag bits; `0` for ordinary messages.
+    pub flags: u32,
+    /// Control kind (`data.control`) when `FRAME_FLAG_CONTROL` is set.
+    pub control: Option<String>,
+}
+
+impl Meta {
+    pub fn new(tags: Vec<String>) -> Self {
+        Self {
+            tags,
+            flags: 0,
+            control: None,
+        }
+    }
+
+    /// Symbolic flag names (`compressed`, `control`, `user0`, ...).
+    pub fn flag_names(&self) -> Vec<String> {
+        frame_flag_names(self.flags)
+    }
+
+    /// The envelope `meta` object; `flags` and `control` appear only when set.
+    pub fn to_json(&self) -> Value {
+        let mut meta = serde_json::json!({ "tags": self.tags });
+        if self.flags != 0 {
+            meta["flags"] = serde_json::json!(self.flag_names());
+        }
+        if let Some(control) = &self.control {
+            meta["control"] = serde_json::json!(control);
+        }
+        meta
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -25,6 +74,24 @@ pub struct Message {
     pub time: String,
     pub meta: Meta,
     pub data: Value,
+    /// Binary attachment stored beside the envelope; `.data` references it.
+    pub attachment: Option<Vec<u8>>,
+}
+
+impl Message {
+    /// The CLI/HTTP message envelope; an attachment appears as base64 under `attachment`.
+    pub fn to_json(&self) -> Value {
+        let mut message = serde_json::json!({
+            "seq": self.seq,
+            "time": self.time,
+            "meta": self.meta.to_json(),
+            "data": self.data,
+        });
+        if let Some(attachment) = &self.attachment {
+            message["attachment"] = Value::String(base64_encode(attachment));
+        }
+        message
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -35,6 +102,12 @@ pub struct TailOptions {
     pub poll_interval: Duration,
     pub timeout: Option<Duration>,
     pub notify: bool,
+    /// Validate each message's envelope in full before yielding it (`validate_message_frame`)
+    /// and fail on the first violation instead of decoding it best effort.
+    pub strict: bool,
+    /// Read-your-writes token: wait until the pool has committed this seq (for example one
+    /// returned by an append through another connection) before yielding anything.
+    pub min_seq: Option<u64>,
 }
 
 impl TailOptions {
@@ -46,6 +119,8 @@ impl TailOptions {
             poll_interval: Duration::from_millis(50),
             timeout: None,
             notify: true,
+            strict: false,
+            min_seq: None,
         }
     }
 }
@@ -63,6 +138,8 @@ pub struct Tail<'a> {
     seen: usize,
     deadline: Option<Instant>,
     notify: Option<PoolSemaphore>,
+    /// `options.min_seq` until the pool is seen to hold it.
+    pending_min_seq: Option<u64>,
 }
 
 pub struct Lite3Tail<'a> {
@@ -72,6 +149,8 @@ pub struct Lite3Tail<'a> {
     seen: usize,
     deadline: Option<Instant>,
     notify: Option<PoolSemaphore>,
+    /// `options.min_seq` until the pool is seen to hold it.
+    pending_min_seq: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
@@ -102,6 +181,7 @@ impl Replay {
     fn new(pool: &Pool, options: ReplayOptions) -> Result<Self, Error> {
         let mut cursor = Cursor::new();
         let mut entries: Vec<(u64, Message)> = Vec::new();
+        let codec = codec_for(pool.codec());
 
         loop {
             match cursor.next(pool)? {
@@ -112,8 +192,9 @@ impl Replay {
                         }
                     }
                     let ts = frame.timestamp_ns;
-                    let msg = message_from_frame(&frame)?;
-                    entries.push((ts, msg));
+                    if let Some(msg) = joined_message(pool, codec, &frame)? {
+                        entries.push((ts, msg));
+                    }
                 }
                 CursorResult::WouldBlock => break,
                 CursorResult::FellBehind => continue,
@@ -171,6 +252,7 @@ impl<'a> Tail<'a> {
         Self {
             pool,
             cursor: Cursor::new(),
+            pending_min_seq: options.min_seq,
             options,
             seen: 0,
             deadline,
@@ -179,6 +261,15 @@ impl<'a> Tail<'a> {
     }
 
     pub fn next_message(&mut self) -> Result<Option<Message>, Error> {
+        self.advance(true)
+    }
+
+    /// Next message if one is already committed; never waits (for batching readers).
+    pub fn try_next_message(&mut self) -> Result<Option<Message>, Error> {
+        self.advance(false)
+    }
+
+    fn advance(&mut self, block: bool) -> Result<Option<Message>, Error> {
         if let Some(max) = self.options.max_messages {
             if self.seen >= max {
                 return Ok(None);
@@ -186,6 +277,19 @@ impl<'a> Tail<'a> {
         }
 
         loop {
+            if !min_seq_reached(self.pool, &mut self.pending_min_seq)? {
+                if !block {
+                    return Ok(None);
+                }
+                if self
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+                {
+                    return Err(min_seq_timeout_error(self.pool, self.options.min_seq));
+                }
+                wait_for_commit(&mut self.notify, self.deadline, self.options.poll_interval);
+                continue;
+            }
             if let Some(deadline) = self.deadline {
                 if Instant::now() >= deadline {
                     return Ok(None);
@@ -199,30 +303,22 @@ impl<'a> Tail<'a> {
                             continue;
                         }
                     }
-                    let message = message_from_frame(&frame)?;
+                    if self.options.strict {
+                        validate_message_frame(self.pool, &frame)?;
+                    }
+                    let codec = codec_for(self.pool.codec());
+                    let Some(message) = joined_message(self.pool, codec, &frame)? else {
+                        continue;
+                    };
                     if !has_required_tags(&message.meta.tags, self.options.tags.as_slice()) {
                         continue;
                     }
                     self.seen += 1;
                     return Ok(Some(message));
                 }
+                CursorResult::WouldBlock if !block => return Ok(None),
                 CursorResult::WouldBlock => {
-                    let wait_for = wait_interval(self.deadline, self.options.poll_interval);
-                    if let Some(notify) = &mut self.notify {
-                        match notify.wait(wait_for) {
-                            Ok(WaitOutcome::Signaled) | Ok(WaitOutcome::TimedOut) => {}
-                            Err(NotifyError::Unavailable) => {
-                                self.notify = None;
-                                std::thread::sleep(wait_for);
-                            }
-                            Err(NotifyError::Io(err)) => {
-                                let _ = err.kind();
-                                std::thread::sleep(wait_for);
-                            }
-                        }
-                    } else {
-                        std::thread::sleep(wait_for);
-                    }
+                    wait_for_commit(&mut self.notify, self.deadline, self.options.poll_interval);
                 }
                 CursorResult::FellBehind => {
                     continue;
@@ -243,6 +339,7 @@ impl<'a> Lite3Tail<'a> {
         Self {
             pool,
             cursor: Cursor::new(),
+            pending_min_seq: options.min_seq,
             options,
             seen: 0,
             deadline,
@@ -251,6 +348,16 @@ impl<'a> Lite3Tail<'a> {
     }
 
     pub fn next_frame(&mut self) -> Result<Option<FrameRef<'a>>, Error> {
+        self.advance(true)
+    }
+
+    /// Next frame if one is already committed; never waits (for batching readers).
+    pub fn try_next_frame(&mut self) -> Result<Option<FrameRef<'a>>, Error> {
+        self.advance(false)
+    }
+
+    fn advance(&mut self, block: bool) -> Result<Option<FrameRef<'a>>, Error> {
+        require_lite3_codec(self.pool)?;
         if let Some(max) = self.options.max_messages {
             if self.seen >= max {
                 return Ok(None);
@@ -258,6 +365,19 @@ impl<'a> Lite3Tail<'a> {
         }
 
         loop {
+            if !min_seq_reached(self.pool, &mut self.pending_min_seq)? {
+                if !block {
+                    return Ok(None);
+                }
+                if self
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+                {
+                    return Err(min_seq_timeout_error(self.pool, self.options.min_seq));
+                }
+                wait_for_commit(&mut self.notify, self.deadline, self.options.poll_interval);
+                continue;
+            }
             if let Some(deadline) = self.deadline {
                 if Instant::now() >= deadline {
                     return Ok(None);
@@ -271,6 +391,19 @@ impl<'a> Lite3Tail<'a> {
                             continue;
                         }
                     }
+                    match chunk_position(frame.flags, frame.payload)? {
+                        Some(position) if position.is_last() => {
+                            return Err(chunked_lite3_error(frame.seq));
+                        }
+                        Some(_) => continue,
+                        None => {}
+                    }
+                    if frame.flags & FRAME_FLAG_ATTACHMENT != 0 {
+                        return Err(attachment_lite3_error(frame.seq));
+                    }
+                    if self.options.strict {
+                        validate_message_frame(self.pool, &frame)?;
+                    }
                     let (meta, _) = decode_payload(frame.payload)?;
                     if !has_required_tags(&meta.tags, self.options.tags.as_slice()) {
                         continue;
@@ -278,23 +411,9 @@ impl<'a> Lite3Tail<'a> {
                     self.seen += 1;
                     return Ok(Some(frame));
                 }
+                CursorResult::WouldBlock if !block => return Ok(None),
                 CursorResult::WouldBlock => {
-                    let wait_for = wait_interval(self.deadline, self.options.poll_interval);
-                    if let Some(notify) = &mut self.notify {
-                        match notify.wait(wait_for) {
-                            Ok(WaitOutcome::Signaled) | Ok(WaitOutcome::TimedOut) => {}
-                            Err(NotifyError::Unavailable) => {
-                                self.notify = None;
-                                std::thread::sleep(wait_for);
-                            }
-                            Err(NotifyError::Io(err)) => {
-                                let _ = err.kind();
-                                std::thread::sleep(wait_for);
-                            }
-                        }
-                    } else {
-                        std::thread::sleep(wait_for);
-                    }
+                    wait_for_commit(&mut self.notify, self.deadline, self.options.poll_interval);
                 }
                 CursorResult::FellBehind => {
                     continue;
@@ -304,6 +423,69 @@ impl<'a> Lite3Tail<'a> {
     }
 }
 
+/// Sleep until a writer posts or the poll interval (capped by `deadline`) elapses.
+fn wait_for_commit(
+    notify: &mut Option<PoolSemaphore>,
+    deadline: Option<Instant>,
+    poll_interval: Duration,
+) {
+    let wait_for = wait_interval(deadline, poll_interval);
+    if let Some(semaphore) = notify {
+        match semaphore.wait(wait_for) {
+            Ok(WaitOutcome::Signaled) | Ok(WaitOutcome::TimedOut) => {}
+            Err(NotifyError::Unavailable) => {
+                *notify = None;
+                std::thread::sleep(wait_for);
+            }
+            Err(NotifyError::Io(err)) => {
+                let _ = err.kind();
+                std::thread::sleep(wait_for);
+            }
+        }
+    } else {
+        std::thread::sleep(wait_for);
+    }
+}
+
+/// Whether `pool` has committed the pending `min_seq`; clears it once it has.
+fn min_seq_reached(pool: &Pool, pending: &mut Option<u64>) -> Result<bool, Error> {
+    let Some(min_seq) = *pending else {
+        return Ok(true);
+    };
+    if pool
+        .bounds()?
+        .newest_seq
+        .is_some_and(|newest| newest >= min_seq)
+    {
+        *pending = None;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+fn min_seq_timeout_error(pool: &Pool, min_seq: Option<u64>) -> Error {
+    Error::new(ErrorKind::Busy)
+        .with_message(format!(
+            "pool has not reached seq {} before the tail timeout",
+            min_seq.unwrap_or_default()
+        ))
+        .with_path(pool.path())
+        .with_hint("The write has not arrived here yet; retry, or allow a longer timeout.")
+}
+
+fn require_lite3_codec(pool: &Pool) -> Result<(), Error> {
+    if pool.codec() == PoolCodec::Lite3 {
+        return Ok(());
+    }
+    Err(Error::new(ErrorKind::Usage)
+        .with_message(format!(
+            "pool uses the {} payload codec; Lite3 fast paths are unavailable",
+            pool.codec().as_str()
+        ))
+        .with_path(pool.path())
+        .with_hint("Use append_json/get_message/tail for this pool."))
+}
+
 fn has_required_tags(message_tags: &[String], required_tags: &[String]) -> bool {
     required_tags
         .iter()
@@ -342,6 +524,35 @@ pub trait PoolApiExt {
         durability: Durability,
     ) -> Result<Message, Error>;
 
+    /// Append `data` unless a recent append used the same idempotency `key`, in which case the
+    /// original message is returned. The key is kept in the pool header, not in `tags`; see
+    /// `Pool::append_idempotent` for how many keys are remembered.
+    fn append_json_idempotent(
+        &mut self,
+        key: &str,
+        data: &Value,
+        tags: &[String],
+        options: AppendOptions,
+    ) -> Result<Message, Error>;
+
+    /// Append `data` with a binary attachment stored beside it; `data` must reference the
+    /// attachment with an `attachment_ref` object.
+    fn append_json_with_attachment(
+        &mut self,
+        data: &Value,
+        tags: &[String],
+        attachment: &[u8],
+        options: AppendOptions,
+    ) -> Result<Message, Error>;
+
+    /// Append a control message of `kind` (see `api::control`) with a generated timestamp.
+    fn append_control(
+        &mut self,
+        kind: &str,
+        details: Map<String, Value>,
+        durability: Durability,
+    ) -> Result<Message, Error>;
+
     /// Append a pre-encoded Lite3 payload without JSON encoding/decoding.
     fn append_lite3(&mut self, payload: &[u8], options: AppendOptions) -> Result<u64, Error>;
 
@@ -350,6 +561,9 @@ pub trait PoolApiExt {
 
     fn get_message(&self, seq: u64) -> Result<Message, Error>;
 
+    /// Decode messages with `start_seq <= seq <= end_seq`, clamped to the retained range.
+    fn get_messages(&self, start_seq: u64, end_seq: u64) -> Result<Vec<Message>, Error>;
+
     /// Fetch the raw Lite3 payload for a sequence number.
     fn get_lite3(&self, seq: u64) -> Result<FrameRef<'_>, Error>;
 
@@ -368,15 +582,54 @@ impl PoolApiExt for Pool {
         tags: &[String],
         options: AppendOptions,
     ) -> Result<Message, Error> {
-        let payload = crate::core::lite3::encode_message(tags, data)?;
+        check_control_payload(options.flags, data)?;
+        let payload = codec_for(self.codec()).encode(tags, data)?;
         let seq = self.append_with_options(payload.as_slice(), options)?;
         Ok(Message {
             seq,
             time: format_ts(options.timestamp_ns)?,
             meta: Meta {
                 tags: tags.to_vec(),
+                flags: options.flags,
+                control: control_kind(options.flags, data).map(str::to_string),
+            },
+            data: data.clone(),
+            attachment: None,
+        })
+    }
+
+    fn append_json_with_attachment(
+        &mut self,
+        data: &Value,
+        tags: &[String],
+        attachment: &[u8],
+        options: AppendOptions,
+    ) -> Result<Message, Error> {
+        if options.flags & FRAME_FLAG_CONTROL != 0 {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("control messages cannot carry attachments"));
+        }
+        if !references_attachment(data) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("data does not reference its attachment")
+                .with_hint(
+                    "Add an attachment reference, e.g. {\"file\": {\"$attachment\": {\"bytes\": N}}}.",
+                ));
+        }
+        let envelope = codec_for(self.codec()).encode(tags, data)?;
+        let payload = attachment::pack(&envelope, attachment)?;
+        let stored = options.with_flags(options.flags | FRAME_FLAG_ATTACHMENT);
+        let seq = self.append_with_options(payload.as_slice(), stored)?;
+        Ok(Message {
+            seq,
+            time: format_ts(options.timestamp_ns)?,
+            meta: Meta {
+                tags: tags.to_vec(),
+                flags: options.flags,
+                control: None,
             },
             data: data.clone(),
+            attachment: Some(attachment.to_vec()),
         })
     }
 
@@ -391,7 +644,45 @@ impl PoolApiExt for Pool {
         self.append_json(data, tags, options)
     }
 
+    fn append_json_idempotent(
+        &mut self,
+        key: &str,
+        data: &Value,
+        tags: &[String],
+        options: AppendOptions,
+    ) -> Result<Message, Error> {
+        check_control_payload(options.flags, data)?;
+        let payload = codec_for(self.codec()).encode(tags, data)?;
+        let (seq, appended) =
+            self.append_idempotent(key.as_bytes(), payload.as_slice(), options)?;
+        if !appended {
+            return self.get_message(seq);
+        }
+        Ok(Message {
+            seq,
+            time: format_ts(options.timestamp_ns)?,
+            meta: Meta {
+                tags: tags.to_vec(),
+                flags: options.flags,
+                control: control_kind(options.flags, data).map(str::to_string),
+            },
+            data: data.clone(),
+            attachment: None,
+        })
+    }
+
+    fn append_control(
+        &mut self,
+        kind: &str,
+        details: Map<String, Value>,
+        durability: Durability,
+    ) -> Result<Message, Error> {
+        let options = AppendOptions::new(now_ns()?, durability).with_flags(FRAME_FLAG_CONTROL);
+        self.append_json(&control_payload(kind, details), &[], options)
+    }
+
     fn append_lite3(&mut self, payload: &[u8], options: AppendOptions) -> Result<u64, Error> {
+        require_lite3_codec(self)?;
         validate_bytes(payload)?;
         self.append_with_options(payload, options)
     }
@@ -404,11 +695,30 @@ impl PoolApiExt for Pool {
 
     fn get_message(&self, seq: u64) -> Result<Message, Error> {
         let frame = self.get(seq)?;
-        message_from_frame(&frame)
+        if let Some(message) = joined_message(self, codec_for(self.codec()), &frame)? {
+            return Ok(message);
+        }
+        Err(missing_chunks_error(&frame))
+    }
+
+    fn get_messages(&self, start_seq: u64, end_seq: u64) -> Result<Vec<Message>, Error> {
+        let codec = codec_for(self.codec());
+        self.get_range(start_seq, end_seq)?
+            .iter()
+            .filter_map(|frame| joined_message(self, codec, frame).transpose())
+            .collect()
     }
 
     fn get_lite3(&self, seq: u64) -> Result<FrameRef<'_>, Error> {
-        self.get(seq)
+        require_lite3_codec(self)?;
+        let frame = self.get(seq)?;
+        if frame.flags & FRAME_FLAG_CHUNK != 0 {
+            return Err(chunked_lite3_error(seq));
+        }
+        if frame.flags & FRAME_FLAG_ATTACHMENT != 0 {
+            return Err(attachment_lite3_error(seq));
+        }
+        Ok(frame)
     }
 
     fn tail(&self, options: TailOptions) -> Tail<'_> {
@@ -424,17 +734,98 @@ impl PoolApiExt for Pool {
     }
 }
 
-fn message_from_frame(frame: &FrameRef<'_>) -> Result<Message, Error> {
-    let (meta, data) = decode_payload(frame.payload)?;
+/// Decode the message at `frame`, joining chunks; `None` for continuation chunks and for
+/// chunked messages whose earlier chunks were overwritten.
+fn joined_message(
+    pool: &Pool,
+    codec: &dyn Codec,
+    frame: &FrameRef<'_>,
+) -> Result<Option<Message>, Error> {
+    let Some(payload) = pool.join_chunks(frame)? else {
+        return Ok(None);
+    };
+    message_from_payload(codec, frame, &payload).map(Some)
+}
+
+/// Check the envelope of the message read at `frame` in full before it is decoded, failing
+/// with its seq (and ring offset, when indexed) on the first violation. Lite3 envelopes go
+/// through `lite3::validate_bytes`; JSON envelopes already decode strictly. Continuation chunks
+/// pass; their message is checked at its last chunk.
+pub fn validate_message_frame(pool: &Pool, frame: &FrameRef<'_>) -> Result<(), Error> {
+    if pool.codec() != PoolCodec::Lite3 {
+        return Ok(());
+    }
+    let Some(payload) = pool.join_chunks(frame)? else {
+        return Ok(());
+    };
+    let (envelope, _) = attachment::unpack(frame.flags, &payload)?;
+    validate_bytes(envelope).map_err(|err| {
+        let detail = err
+            .message()
+            .unwrap_or("invalid lite3 document")
+            .to_string();
+        let err = err
+            .with_message(format!("message failed strict validation: {detail}"))
+            .with_seq(frame.seq)
+            .with_hint("Run `plasmite doctor --deep` on the pool, or read without --strict.");
+        match pool.locate(frame.seq).ok().flatten() {
+            Some(offset) => err.with_offset(offset as u64),
+            None => err,
+        }
+    })
+}
+
+/// Why `get_message(seq)` found a frame but no message there.
+fn missing_chunks_error(frame: &FrameRef<'_>) -> Error {
+    let position = chunk_position(frame.flags, frame.payload).ok().flatten();
+    match position {
+        Some(position) if !position.is_last() => Error::new(ErrorKind::NotFound)
+            .with_message("seq holds a continuation chunk of a larger message")
+            .with_seq(frame.seq)
+            .with_hint(format!(
+                "Read the whole message at seq {}.",
+                position.message_seq(frame.seq)
+            )),
+        _ => Error::new(ErrorKind::NotFound)
+            .with_message("chunked message is incomplete; its first chunks were overwritten")
+            .with_seq(frame.seq),
+    }
+}
+
+fn chunked_lite3_error(seq: u64) -> Error {
+    Error::new(ErrorKind::Usage)
+        .with_message("message is stored in chunks; raw Lite3 frame reads are unavailable")
+        .with_seq(seq)
+        .with_hint("Use get_message/tail, which join chunked messages.")
+}
+
+fn attachment_lite3_error(seq: u64) -> Error {
+    Error::new(ErrorKind::Usage)
+        .with_message("message carries an attachment; raw Lite3 frame reads are unavailable")
+        .with_seq(seq)
+        .with_hint("Use get_message/tail, which return the attachment bytes.")
+}
+
+fn message_from_payload(
+    codec: &dyn Codec,
+    frame: &FrameRef<'_>,
+    payload: &[u8],
+) -> Result<Message, Error> {
+    let (envelope, attachment) = attachment::unpack(frame.flags, payload)?;
+    let (mut meta, data) = codec.decode(envelope)?;
+    // Chunking and attachment layout are storage details; readers see the flags the writer set.
+    meta.flags = frame.flags & !(FRAME_FLAG_CHUNK | FRAME_FLAG_ATTACHMENT);
+    meta.control = control_kind(frame.flags, &data).map(str::to_string);
     Ok(Message {
         seq: frame.seq,
         time: format_ts(frame.timestamp_ns)?,
         meta,
         data,
+        attachment: attachment.map(<[u8]>::to_vec),
     })
 }
 
-fn decode_payload(payload: &[u8]) -> Result<(Meta, Value), Error> {
+pub(super) fn decode_payload(payload: &[u8]) -> Result<(Meta, Value), Error> {
     let doc = Lite3DocRef::new(payload);
     let meta_type = doc
         .type_at_key(0, "meta")
@@ -478,7 +869,7 @@ fn decode_payload(payload: &[u8]) -> Result<(Meta, Value), Error> {
             .with_source(err)
     })?;
 
-    Ok((Meta { tags }, data))
+    Ok((Meta::new(tags), data))
 }
 
 fn now_ns() -> Result<u64, Error> {
@@ -510,8 +901,10 @@ fn format_ts(timestamp_ns: u64) -> Result<String, Error> {
 #[cfg(test)]
 mod tests {
     use super::{Meta, PoolApiExt, ReplayOptions, TailOptions, decode_payload};
-    use crate::core::lite3::{encode_message, json_counter_snapshot, reset_json_counters};
-    use crate::core::pool::{Pool, PoolOptions};
+    use crate::core::lite3::{
+        Lite3Buf, encode_message, json_counter_snapshot, reset_json_counters,
+    };
+    use crate::core::pool::{AppendOptions, Durability, Pool, PoolOptions};
     use serde_json::json;
     use tempfile::tempdir;
 
@@ -520,13 +913,80 @@ mod tests {
         let data = json!({"x": 1});
         let payload = encode_message(&["tag".to_string()], &data).expect("encode");
         let (meta, out) = decode_payload(payload.as_slice()).expect("decode");
+        assert_eq!(meta, Meta::new(vec!["tag".to_string()]));
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn strict_tails_fail_on_the_first_malformed_payload() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        pool.append_json_now(&json!({"x": 1}), &[], Durability::Fast)
+            .expect("append");
+        // Raw appends skip the shape checks `append_lite3` applies.
+        let bad = Lite3Buf::from_json_str(r#"{"meta":{"tags":[]},"data":[1]}"#).expect("lite3");
+        pool.append_with_options(bad.as_slice(), AppendOptions::new(2, Durability::Fast))
+            .expect("raw append");
+
+        let options = TailOptions {
+            strict: true,
+            timeout: Some(std::time::Duration::from_millis(10)),
+            ..TailOptions::default()
+        };
+        let mut tail = pool.tail(options);
+        assert_eq!(tail.next_message().expect("first").expect("message").seq, 1);
+        let err = tail.next_message().expect_err("strict rejects seq 2");
+        assert_eq!(err.kind(), crate::core::error::ErrorKind::Corrupt);
+        assert_eq!(err.seq(), Some(2));
+        assert!(err.offset().is_some());
+        assert!(
+            err.message()
+                .unwrap_or_default()
+                .contains("data is not object")
+        );
+    }
+
+    #[test]
+    fn min_seq_tails_wait_for_the_token_seq_then_read_from_since_seq() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        pool.append_json_now(&json!({"x": 1}), &[], Durability::Fast)
+            .expect("append");
+
+        let options = |timeout_ms| TailOptions {
+            since_seq: Some(1),
+            min_seq: Some(2),
+            timeout: Some(std::time::Duration::from_millis(timeout_ms)),
+            ..TailOptions::default()
+        };
+        let err = pool
+            .tail(options(20))
+            .next_message()
+            .expect_err("seq 2 never arrives");
+        assert_eq!(err.kind(), crate::core::error::ErrorKind::Busy);
+        assert!(
+            pool.tail(options(20))
+                .try_next_message()
+                .expect("try")
+                .is_none()
+        );
+
+        let writer_path = path.clone();
+        let writer = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            let mut pool = Pool::open(&writer_path).expect("open");
+            pool.append_json_now(&json!({"x": 2}), &[], Durability::Fast)
+                .expect("append");
+        });
+        let mut tail = pool.tail(options(5_000));
+        assert_eq!(tail.next_message().expect("first").expect("message").seq, 1);
         assert_eq!(
-            meta,
-            Meta {
-                tags: vec!["tag".to_string()]
-            }
+            tail.next_message().expect("second").expect("message").seq,
+            2
         );
-        assert_eq!(out, data);
+        writer.join().expect("writer");
     }
 
     #[test]
@@ -540,6 +1000,63 @@ mod tests {
         assert_eq!(partial, 1);
     }
 
+    #[test]
+    fn append_json_flags_round_trip_through_meta() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        let flags = crate::core::frame::FRAME_FLAG_CONTROL | 1 << 20;
+        let options = crate::core::pool::AppendOptions::new(1, crate::core::pool::Durability::Fast)
+            .with_flags(flags);
+        let appended = pool
+            .append_json(&json!({"x": 1}), &[], options)
+            .expect("append");
+        assert_eq!(appended.meta.flags, flags);
+
+        let message = pool.get_message(appended.seq).expect("get");
+        assert_eq!(message.meta.flags, flags);
+        assert_eq!(message.meta.flag_names(), vec!["control", "user4"]);
+        assert_eq!(message.meta.to_json()["flags"], json!(["control", "user4"]));
+        assert!(Meta::new(Vec::new()).to_json().get("flags").is_none());
+
+        let reserved = crate::core::pool::AppendOptions::default().with_flags(1 << 8);
+        assert!(pool.append_json(&json!({"x": 2}), &[], reserved).is_err());
+    }
+
+    #[test]
+    fn attachments_round_trip_as_bytes_and_render_as_base64() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        let blob = [0u8, 1, 254, 255];
+        let data =
+            json!({ "name": "icon.png", "image": crate::core::attachment::attachment_ref(4) });
+        let appended = pool
+            .append_json_with_attachment(
+                &data,
+                &["img".to_string()],
+                &blob,
+                crate::core::pool::AppendOptions::default(),
+            )
+            .expect("append");
+        assert_eq!(appended.meta.flags, 0);
+
+        let message = pool.get_message(appended.seq).expect("get");
+        assert_eq!(message.data, data);
+        assert_eq!(message.attachment.as_deref(), Some(&blob[..]));
+        assert!(message.meta.flag_names().is_empty());
+        assert_eq!(message.to_json()["attachment"], json!("AAH+/w=="));
+        assert!(pool.get_lite3(appended.seq).is_err());
+
+        let unreferenced = pool.append_json_with_attachment(
+            &json!({ "name": "icon.png" }),
+            &[],
+            &blob,
+            crate::core::pool::AppendOptions::default(),
+        );
+        assert!(unreferenced.is_err());
+    }
+
     #[test]
     fn append_get_tail_lite3() {
         let dir = tempdir().expect("tempdir");
@@ -610,6 +1127,28 @@ mod tests {
         assert_eq!(message.data, json!({"n": 2}));
     }
 
+    #[test]
+    fn try_next_message_drains_without_waiting() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        for n in 0..3 {
+            pool.append_json_now(&json!({ "n": n }), &[], crate::core::pool::Durability::Fast)
+                .expect("append");
+        }
+
+        let mut options = TailOptions::new();
+        options.timeout = Some(std::time::Duration::from_secs(30));
+        let mut tail = pool.tail(options);
+        let started = std::time::Instant::now();
+        let mut seen = Vec::new();
+        while let Some(message) = tail.try_next_message().expect("try_next") {
+            seen.push(message.data["n"].clone());
+        }
+        assert_eq!(seen, vec![json!(0), json!(1), json!(2)]);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
     #[test]
     fn replay_returns_messages_in_order() {
         let dir = tempdir().expect("tempdir");
@@ -638,4 +1177,42 @@ mod tests {
         assert_eq!(collected[1], values[1]);
         assert_eq!(collected[2], values[2]);
     }
+
+    #[test]
+    fn json_codec_pool_round_trips_and_rejects_lite3_paths() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(
+            &path,
+            PoolOptions::new(1024 * 1024).with_codec(crate::core::pool::PoolCodec::Json),
+        )
+        .expect("create");
+
+        let message = pool
+            .append_json_now(
+                &json!({"n": 1}),
+                &["tag".to_string()],
+                crate::core::pool::Durability::Fast,
+            )
+            .expect("append");
+        let frame = pool.get(message.seq).expect("frame");
+        assert_eq!(
+            frame.payload,
+            br#"{"meta":{"tags":["tag"]},"data":{"n":1}}"#
+        );
+
+        let fetched = pool.get_message(message.seq).expect("get");
+        assert_eq!(fetched.meta.tags, vec!["tag".to_string()]);
+        assert_eq!(fetched.data, json!({"n": 1}));
+
+        let payload = encode_message(&[], &json!({"x": 1})).expect("payload");
+        let err = pool
+            .append_lite3(
+                payload.as_slice(),
+                crate::core::pool::AppendOptions::default(),
+            )
+            .expect_err("lite3 append rejected");
+        assert_eq!(err.kind(), crate::core::error::ErrorKind::Usage);
+        assert!(pool.get_lite3(message.seq).is_err());
+    }
 }
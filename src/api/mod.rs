@@ -5,21 +5,59 @@
 //! Invariants: Internal modules remain private and are not directly exposed.
 
 mod client;
+mod codec;
+pub mod control;
+mod cursor_store;
+mod deadline;
+mod explain;
 mod message;
 pub mod notify;
+mod pool_cache;
 mod remote;
 mod validation;
 
+pub use crate::core::activity::{ActivityHistogram, MAX_ACTIVITY_BUCKETS, bucket_activity};
+pub use crate::core::attachment::{
+    ATTACHMENT_REF_KEY, attachment_ref, pack as pack_attachment, references_attachment,
+    unpack as unpack_attachment,
+};
+pub use crate::core::base64::{base64_decode, base64_encode};
+pub use crate::core::bloom::{BLOOM_NONE, might_contain as bloom_might_contain};
+pub use crate::core::chaos::{self, ChaosConfig};
+pub use crate::core::chunk::{ChunkPosition, chunk_position};
 pub use crate::core::cursor::{Cursor, CursorResult, FrameRef};
 #[doc(hidden)]
 pub use crate::core::error::to_exit_code;
 pub use crate::core::error::{Error, ErrorKind};
-pub use crate::core::lite3::{self, Lite3DocRef};
+pub use crate::core::frame::{
+    FRAME_FLAG_ATTACHMENT, FRAME_FLAG_CHUNK, FRAME_FLAG_COMPRESSED, FRAME_FLAG_CONTROL,
+    FRAME_FLAG_ENCRYPTED, FRAME_FLAGS_USER, frame_flag_names, parse_frame_flags,
+};
+pub use crate::core::hash::fnv1a64;
+pub use crate::core::hex::{hex_decode, hex_encode, hex_encode_spaced};
+pub use crate::core::inuse::{
+    PoolRole, PoolUser, Registration, RegistrationGuard, register as register_pool_user,
+};
+pub use crate::core::lite3::{self, Lite3DocRef, Lite3Scalar};
 pub use crate::core::pool::{
-    AppendOptions, Bounds, Durability, Pool, PoolAgeMetrics, PoolInfo, PoolMetrics, PoolOptions,
-    PoolUtilization, SeqOffsetCache,
+    AppendOptions, Bounds, Durability, Pool, PoolAgeMetrics, PoolCodec, PoolInfo, PoolMetrics,
+    PoolOptions, PoolUtilization, SeqOffsetCache, TimeNearest,
+};
+pub use client::{
+    DeleteOptions, DeletePlan, LocalClient, PoolPresence, PoolRef, TRASH_DIR, pins_sidecar_path,
+    template_sidecar_path,
+};
+pub use codec::{Codec, JsonCodec, Lite3Codec, codec_for, payload_codec};
+pub use cursor_store::{cursors_sidecar_dir, load_cursor, save_cursor};
+pub use deadline::{CancelOnDrop, Deadline};
+pub use explain::{ExplainTarget, FrameExplain, HeaderField, IndexProbe, explain_frame};
+pub use message::{
+    Lite3Tail, Message, Meta, PoolApiExt, Replay, ReplayOptions, Tail, TailOptions,
+    validate_message_frame,
+};
+pub use pool_cache::PoolCacheStats;
+pub use remote::{
+    RemoteAppendReceipt, RemoteAppendReceipts, RemoteClient, RemoteLite3Frame, RemoteLite3Tail,
+    RemotePool, RemoteTail,
 };
-pub use client::{LocalClient, PoolRef};
-pub use message::{Lite3Tail, Message, Meta, PoolApiExt, Replay, ReplayOptions, Tail, TailOptions};
-pub use remote::{RemoteClient, RemoteLite3Frame, RemoteLite3Tail, RemotePool, RemoteTail};
 pub use validation::{ValidationIssue, ValidationReport, ValidationStatus};
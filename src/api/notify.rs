@@ -1,5 +1,5 @@
 //! Purpose: Expose best-effort notification helpers for external callers.
-//! Exports: `NotifyWait`, `wait_for_path`.
+//! Exports: `NotifyWait`, `NotifyCounters`, `counters`, `wait_for_path`.
 //! Role: Thin wrapper over core notify for CLI/bindings use.
 //! Invariants: Wait results are best-effort; callers must fall back to polling.
 //! Invariants: Unavailable notify never blocks progress.
@@ -8,6 +8,26 @@ use crate::core::notify::{WaitOutcome, open_for_path as core_open_for_path};
 use std::path::Path;
 use std::time::Duration;
 
+/// Process-wide notify counters; compare snapshots to measure wakeup coalescing.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NotifyCounters {
+    /// Semaphore posts actually issued by appends in this process.
+    pub posts: u64,
+    /// Append posts skipped because every blocked follower already had a wakeup pending.
+    pub coalesced: u64,
+    /// Follower waits in this process that were woken by a post.
+    pub wakeups: u64,
+}
+
+pub fn counters() -> NotifyCounters {
+    let counters = crate::core::notify::counters();
+    NotifyCounters {
+        posts: counters.posts,
+        coalesced: counters.coalesced,
+        wakeups: counters.wakeups,
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum NotifyWait {
     Signaled,
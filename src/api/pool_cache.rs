@@ -0,0 +1,176 @@
+//! Purpose: Keep recently used `Pool` handles open so repeated requests skip open + mmap.
+//! Exports: `PoolCache`, `PoolCacheStats`.
+//! Role: Shared by clones of a `LocalClient` built with `with_pool_cache`; serve and MCP route
+//! their per-request pool access through it.
+//! Invariants: Entries are keyed by canonical path and read-only mode, and evicted least
+//! recently used first once `capacity` is reached.
+//! Invariants: An entry is reused only while the file at its path is still the file it mapped
+//! (same device, inode, and length); a replaced, rotated, or resized pool is reopened.
+
+use crate::core::error::Error;
+use crate::core::pool::Pool;
+use std::collections::{HashMap, VecDeque};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+type CacheKey = (PathBuf, bool);
+
+/// Counters reported by `LocalClient::pool_cache_stats`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PoolCacheStats {
+    pub capacity: usize,
+    pub len: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// Entries dropped by `invalidate` or because the file behind them changed.
+    pub invalidations: u64,
+}
+
+pub struct PoolCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, CachedPool>,
+    order: VecDeque<CacheKey>,
+    stats: PoolCacheStats,
+}
+
+struct CachedPool {
+    pool: Arc<Mutex<Pool>>,
+    identity: FileIdentity,
+}
+
+impl PoolCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            stats: PoolCacheStats {
+                capacity,
+                ..PoolCacheStats::default()
+            },
+        }
+    }
+
+    pub fn stats(&self) -> PoolCacheStats {
+        PoolCacheStats {
+            len: self.entries.len(),
+            ..self.stats
+        }
+    }
+
+    /// The cached handle for `path`, opening it with `open` on a miss or when stale.
+    #[allow(clippy::result_large_err)]
+    pub fn get_or_open(
+        &mut self,
+        path: &Path,
+        read_only: bool,
+        open: impl FnOnce(&Path) -> Result<Pool, Error>,
+    ) -> Result<Arc<Mutex<Pool>>, Error> {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let key = (canonical, read_only);
+        let identity = std::fs::metadata(path)
+            .ok()
+            .map(|meta| FileIdentity::of(&meta));
+        if let Some(entry) = self.entries.get(&key) {
+            if identity == Some(entry.identity) {
+                let pool = entry.pool.clone();
+                self.stats.hits += 1;
+                self.touch(&key);
+                return Ok(pool);
+            }
+            self.stats.invalidations += 1;
+            self.remove(&key);
+        }
+        self.stats.misses += 1;
+        let pool = open(path)?;
+        let Some(identity) = identity else {
+            // The file vanished between stat and open; serve this request uncached.
+            return Ok(Arc::new(Mutex::new(pool)));
+        };
+        let pool = Arc::new(Mutex::new(pool));
+        if self.capacity == 0 {
+            return Ok(pool);
+        }
+        if self.entries.len() == self.capacity {
+            if let Some(evict) = self.order.pop_back() {
+                self.entries.remove(&evict);
+                self.stats.evictions += 1;
+            }
+        }
+        self.entries.insert(
+            key.clone(),
+            CachedPool {
+                pool: pool.clone(),
+                identity,
+            },
+        );
+        self.order.push_front(key);
+        Ok(pool)
+    }
+
+    /// Drop every entry for `path` (both access modes); the next access reopens it.
+    pub fn invalidate(&mut self, path: &Path) {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        for read_only in [false, true] {
+            let key = (canonical.clone(), read_only);
+            if self.entries.contains_key(&key) {
+                self.stats.invalidations += 1;
+                self.remove(&key);
+            }
+        }
+    }
+
+    fn touch(&mut self, key: &CacheKey) {
+        if let Some(index) = self.order.iter().position(|candidate| candidate == key) {
+            self.order.remove(index);
+        }
+        self.order.push_front(key.clone());
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        self.entries.remove(key);
+        if let Some(index) = self.order.iter().position(|candidate| candidate == key) {
+            self.order.remove(index);
+        }
+    }
+}
+
+impl std::fmt::Debug for PoolCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolCache")
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+/// What makes an open mapping still valid for the file at a path.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct FileIdentity {
+    dev: u64,
+    ino: u64,
+    len: u64,
+}
+
+impl FileIdentity {
+    #[cfg(unix)]
+    fn of(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+        Self {
+            dev: meta.dev(),
+            ino: meta.ino(),
+            len: meta.len(),
+        }
+    }
+
+    // Without inode numbers only a resize is detectable; rely on explicit invalidation.
+    #[cfg(not(unix))]
+    fn of(meta: &Metadata) -> Self {
+        Self {
+            dev: 0,
+            ino: 0,
+            len: meta.len(),
+        }
+    }
+}
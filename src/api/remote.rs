@@ -6,11 +6,13 @@
 //! Invariants: Tail streams are JSONL (messages) or framed Lite3 bytes (fast path).
 #![allow(clippy::result_large_err)]
 
-use super::{Message, Meta, PoolRef, TailOptions};
+use super::{Message, Meta, PoolRef, TailOptions, ValidationReport};
+use crate::core::base64::{base64_decode, base64_encode};
 use crate::core::error::{Error, ErrorKind};
+use crate::core::frame::{frame_flag_names, parse_frame_flags};
 use crate::core::pool::{
-    AppendOptions, Bounds, Durability, PoolAgeMetrics, PoolInfo, PoolMetrics, PoolOptions,
-    PoolUtilization,
+    AppendOptions, Bounds, Durability, PoolAgeMetrics, PoolCodec, PoolInfo, PoolMetrics,
+    PoolOptions, PoolUtilization,
 };
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -22,7 +24,7 @@ use ureq::rustls::client::danger::{
     HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
 };
 use ureq::rustls::pki_types::pem::PemObject;
-use ureq::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use ureq::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
 use ureq::rustls::{DigitallySignedStruct, Error as TlsError, SignatureScheme};
 use url::Url;
 
@@ -37,6 +39,20 @@ struct RemoteClientInner {
     base_url: Url,
     token: Option<String>,
     agent: ureq::Agent,
+    tls: TlsSettings,
+}
+
+/// TLS choices accumulated by the `with_tls_*` builders; the agent is rebuilt from them.
+#[derive(Clone, Default)]
+struct TlsSettings {
+    roots: Option<Arc<ureq::rustls::RootCertStore>>,
+    skip_verify: bool,
+    client_auth: Option<Arc<TlsClientAuth>>,
+}
+
+struct TlsClientAuth {
+    certs: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
 }
 
 #[derive(Debug)]
@@ -79,6 +95,34 @@ impl ServerCertVerifier for AcceptAllServerCertVerifier {
     }
 }
 
+fn build_tls_agent(tls: &TlsSettings) -> ApiResult<ureq::Agent> {
+    let _ = ureq::rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let builder = ureq::rustls::ClientConfig::builder();
+    let builder = if tls.skip_verify {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAllServerCertVerifier))
+    } else {
+        let roots = tls.roots.clone().unwrap_or_else(|| {
+            Arc::new(ureq::rustls::RootCertStore::from_iter(
+                webpki_roots::TLS_SERVER_ROOTS.iter().cloned(),
+            ))
+        });
+        builder.with_root_certificates(roots)
+    };
+    let tls_config = match &tls.client_auth {
+        Some(auth) => builder
+            .with_client_auth_cert(auth.certs.clone(), auth.key.clone_key())
+            .map_err(|err| {
+                Error::new(ErrorKind::Usage)
+                    .with_message("TLS client certificate does not match its key")
+                    .with_source(err)
+            })?,
+        None => builder.with_no_client_auth(),
+    };
+    Ok(ureq::builder().tls_config(Arc::new(tls_config)).build())
+}
+
 #[derive(Clone)]
 pub struct RemotePool {
     client: RemoteClient,
@@ -89,6 +133,21 @@ pub struct RemotePool {
 
 pub struct RemoteTail {
     reader: Option<BufReader<Box<dyn std::io::Read + Send + Sync>>>,
+    line: Vec<u8>,
+    request_id: Option<String>,
+}
+
+/// Receipts for one `append_batch` request, read as the server commits each record.
+pub struct RemoteAppendReceipts {
+    reader: Option<BufReader<Box<dyn std::io::Read + Send + Sync>>>,
+    line: Vec<u8>,
+    request_id: Option<String>,
+}
+
+/// Outcome for the record at `index` (0-based within its batch).
+pub struct RemoteAppendReceipt {
+    pub index: usize,
+    pub result: ApiResult<Message>,
 }
 
 pub struct RemoteLite3Tail {
@@ -108,6 +167,11 @@ struct ResolvedPool {
     pool: String,
 }
 
+#[derive(Deserialize)]
+struct ValidateEnvelope {
+    report: Value,
+}
+
 #[derive(Deserialize)]
 struct PoolEnvelope {
     pool: RemotePoolInfo,
@@ -139,11 +203,18 @@ struct RemoteMessage {
     time: String,
     meta: RemoteMeta,
     data: Value,
+    /// Base64 attachment bytes, when the message carries one.
+    #[serde(default)]
+    attachment: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct RemoteMeta {
     tags: Vec<String>,
+    #[serde(default)]
+    flags: Vec<String>,
+    #[serde(default)]
+    control: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -163,6 +234,22 @@ struct RemotePoolInfo {
     bounds: RemoteBounds,
     #[serde(default)]
     metrics: Option<RemotePoolMetrics>,
+    #[serde(default)]
+    hybrid_timestamps: bool,
+    #[serde(default)]
+    codec: Option<String>,
+    #[serde(default)]
+    allocated_bytes: Option<u64>,
+    #[serde(default)]
+    max_messages: Option<u64>,
+    #[serde(default)]
+    max_frame_bytes: Option<u32>,
+    #[serde(default)]
+    max_message_bytes: Option<u32>,
+    #[serde(default)]
+    bloom_filter: bool,
+    #[serde(default)]
+    frozen: bool,
 }
 
 #[derive(Deserialize, Default)]
@@ -199,6 +286,21 @@ struct ErrorEnvelope {
     error: RemoteError,
 }
 
+#[derive(Deserialize)]
+struct RemoteReceiptLine {
+    index: usize,
+    message: Option<RemoteMessage>,
+    error: Option<RemoteError>,
+}
+
+/// One JSONL tail line: a message, or the terminal error the server emits mid-stream.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RemoteTailLine {
+    Error(ErrorEnvelope),
+    Message(RemoteMessage),
+}
+
 #[derive(Deserialize)]
 struct RemoteError {
     kind: String,
@@ -207,6 +309,29 @@ struct RemoteError {
     path: Option<String>,
     seq: Option<u64>,
     offset: Option<u64>,
+    #[serde(default)]
+    causes: Vec<String>,
+}
+
+/// A server-side cause carried as text, chained so `Error::causes` matches the local error.
+#[derive(Debug)]
+struct RemoteCause {
+    message: String,
+    source: Option<Box<RemoteCause>>,
+}
+
+impl std::fmt::Display for RemoteCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RemoteCause {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
 }
 
 #[derive(Serialize)]
@@ -225,6 +350,16 @@ struct AppendRequest<'a> {
     data: &'a Value,
     tags: &'a [String],
     durability: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    flags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachment: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AppendBatchRecord<'a> {
+    data: &'a Value,
+    tags: &'a [String],
 }
 
 impl RemoteClient {
@@ -236,6 +371,7 @@ impl RemoteClient {
                 base_url,
                 token: None,
                 agent,
+                tls: TlsSettings::default(),
             }),
         })
     }
@@ -248,12 +384,13 @@ impl RemoteClient {
                 base_url: self.inner.base_url.clone(),
                 token: Some(token.into()),
                 agent: self.inner.agent.clone(),
+                tls: self.inner.tls.clone(),
             });
         }
         self
     }
 
-    pub fn with_tls_ca_file(mut self, path: impl AsRef<Path>) -> ApiResult<Self> {
+    pub fn with_tls_ca_file(self, path: impl AsRef<Path>) -> ApiResult<Self> {
         let path = path.as_ref();
         let cert_bytes = std::fs::read(path).map_err(|err| {
             Error::new(ErrorKind::Usage)
@@ -275,7 +412,6 @@ impl RemoteClient {
                 .with_path(path));
         }
 
-        let _ = ureq::rustls::crypto::aws_lc_rs::default_provider().install_default();
         let mut root_store = ureq::rustls::RootCertStore::empty();
         let (added, _) = root_store.add_parsable_certificates(certs);
         if added == 0 {
@@ -284,23 +420,61 @@ impl RemoteClient {
                 .with_path(path));
         }
 
-        let tls_config = ureq::rustls::ClientConfig::builder()
-            .with_root_certificates(root_store)
-            .with_no_client_auth();
-        let agent = ureq::builder().tls_config(Arc::new(tls_config)).build();
-        self = self.with_agent(agent);
-        Ok(self)
+        let mut tls = self.inner.tls.clone();
+        tls.roots = Some(Arc::new(root_store));
+        self.with_tls(tls)
     }
 
-    pub fn with_tls_skip_verify(mut self) -> Self {
-        let _ = ureq::rustls::crypto::aws_lc_rs::default_provider().install_default();
-        let tls_config = ureq::rustls::ClientConfig::builder()
-            .dangerous()
-            .with_custom_certificate_verifier(Arc::new(AcceptAllServerCertVerifier))
-            .with_no_client_auth();
-        let agent = ureq::builder().tls_config(Arc::new(tls_config)).build();
-        self = self.with_agent(agent);
-        self
+    pub fn with_tls_skip_verify(self) -> Self {
+        let mut tls = self.inner.tls.clone();
+        tls.skip_verify = true;
+        let fallback = self.clone();
+        // Client cert/key were already validated when set, so rebuilding cannot fail here.
+        self.with_tls(tls).unwrap_or(fallback)
+    }
+
+    /// Present a client certificate (mTLS) from PEM cert chain and private key files.
+    pub fn with_tls_client_cert(
+        self,
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+    ) -> ApiResult<Self> {
+        let (cert_path, key_path) = (cert_path.as_ref(), key_path.as_ref());
+        let cert_bytes = std::fs::read(cert_path).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message("failed to read TLS client certificate")
+                .with_path(cert_path)
+                .with_source(err)
+        })?;
+        let certs = CertificateDer::pem_slice_iter(&cert_bytes)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                Error::new(ErrorKind::Usage)
+                    .with_message("failed to parse TLS client certificate")
+                    .with_path(cert_path)
+                    .with_source(err)
+            })?;
+        if certs.is_empty() {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("TLS client certificate file contains no certificates")
+                .with_path(cert_path));
+        }
+        let key_bytes = std::fs::read(key_path).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message("failed to read TLS client key")
+                .with_path(key_path)
+                .with_source(err)
+        })?;
+        let key = PrivateKeyDer::from_pem_slice(&key_bytes).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message("failed to parse TLS client key")
+                .with_path(key_path)
+                .with_source(err)
+        })?;
+
+        let mut tls = self.inner.tls.clone();
+        tls.client_auth = Some(Arc::new(TlsClientAuth { certs, key }));
+        self.with_tls(tls)
     }
 
     pub fn base_url(&self) -> &Url {
@@ -451,17 +625,20 @@ impl RemoteClient {
         }
     }
 
-    fn with_agent(mut self, agent: ureq::Agent) -> Self {
+    fn with_tls(mut self, tls: TlsSettings) -> ApiResult<Self> {
+        let agent = build_tls_agent(&tls)?;
         if let Some(inner) = Arc::get_mut(&mut self.inner) {
             inner.agent = agent;
+            inner.tls = tls;
         } else {
             self.inner = Arc::new(RemoteClientInner {
                 base_url: self.inner.base_url.clone(),
                 token: self.inner.token.clone(),
                 agent,
+                tls,
             });
         }
-        self
+        Ok(self)
     }
 }
 
@@ -479,6 +656,28 @@ impl RemotePool {
         Ok(pool_info_from_remote(&self.pool, envelope.pool))
     }
 
+    /// Run doctor checks server-side; `deep` also decodes up to `max_frames` newest payloads
+    /// (the server applies its own cap).
+    pub fn validate(&self, deep: bool, max_frames: Option<u64>) -> ApiResult<ValidationReport> {
+        let mut url = build_url(&self.base_url, &["v0", "pools", &self.pool, "validate"])?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("depth", if deep { "deep" } else { "quick" });
+            if let Some(max_frames) = max_frames {
+                pairs.append_pair("max_frames", &max_frames.to_string());
+            }
+        }
+        let envelope: ValidateEnvelope = self
+            .client
+            .request_json::<(), _>("GET", &url, &())
+            .map_err(|err| err.with_path(self.pool.clone()))?;
+        ValidationReport::from_json(&envelope.report).ok_or_else(|| {
+            Error::new(ErrorKind::Io)
+                .with_message("invalid validate response")
+                .with_path(self.pool.clone())
+        })
+    }
+
     pub fn append_json(
         &self,
         data: &Value,
@@ -494,28 +693,97 @@ impl RemotePool {
             data,
             tags,
             durability: durability_to_str(options.durability),
+            flags: frame_flag_names(options.flags),
+            attachment: None,
         };
         let envelope: MessageEnvelope = self
             .client
             .request_json("POST", &url, &payload)
             .map_err(|err| err.with_path(self.pool.clone()))?;
-        Ok(message_from_remote(envelope.message))
+        message_from_remote(envelope.message)
     }
 
-    pub fn append_json_now(
+    /// Append `data` with a binary attachment; the bytes travel as base64 in the request.
+    pub fn append_json_with_attachment(
         &self,
         data: &Value,
         tags: &[String],
-        durability: Durability,
+        attachment: &[u8],
+        options: AppendOptions,
     ) -> ApiResult<Message> {
-        self.append_json(
+        if options.timestamp_ns != 0 {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("remote append does not support explicit timestamps"));
+        }
+        let url = build_url(&self.base_url, &["v0", "pools", &self.pool, "append"])?;
+        let payload = AppendRequest {
             data,
             tags,
-            AppendOptions {
-                timestamp_ns: 0,
-                durability,
-            },
-        )
+            durability: durability_to_str(options.durability),
+            flags: frame_flag_names(options.flags),
+            attachment: Some(base64_encode(attachment)),
+        };
+        let envelope: MessageEnvelope = self
+            .client
+            .request_json("POST", &url, &payload)
+            .map_err(|err| err.with_path(self.pool.clone()))?;
+        message_from_remote(envelope.message)
+    }
+
+    pub fn append_json_now(
+        &self,
+        data: &Value,
+        tags: &[String],
+        durability: Durability,
+    ) -> ApiResult<Message> {
+        self.append_json(data, tags, AppendOptions::new(0, durability))
+    }
+
+    /// Append `records` in one request. Receipts stream back per record as the server
+    /// commits them, so callers can report progress and attribute individual failures.
+    pub fn append_batch(
+        &self,
+        records: &[Value],
+        tags: &[String],
+        durability: Durability,
+    ) -> ApiResult<RemoteAppendReceipts> {
+        let mut url = build_url(&self.base_url, &["v0", "pools", &self.pool, "append_batch"])?;
+        if durability == Durability::Flush {
+            url.query_pairs_mut()
+                .append_pair("durability", durability_to_str(durability));
+        }
+        let mut body = Vec::new();
+        for data in records {
+            serde_json::to_writer(&mut body, &AppendBatchRecord { data, tags }).map_err(|err| {
+                Error::new(ErrorKind::Internal)
+                    .with_message("failed to encode request json")
+                    .with_source(err)
+            })?;
+            body.push(b'\n');
+        }
+        let response = self
+            .client
+            .request("POST", &url)
+            .set("Accept", "application/jsonl")
+            .set("Content-Type", "application/jsonl")
+            .send_bytes(&body);
+        let response = match response {
+            Ok(resp) => resp,
+            Err(ureq::Error::Status(code, resp)) => {
+                return Err(parse_error_response(code, resp).with_path(self.pool.clone()));
+            }
+            Err(ureq::Error::Transport(err)) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("request failed")
+                    .with_source(err));
+            }
+        };
+        let request_id = response_request_id(&response);
+        Ok(RemoteAppendReceipts {
+            reader: Some(BufReader::new(response.into_reader())),
+            line: Vec::new(),
+            request_id,
+        })
     }
 
     pub fn append_lite3(&self, payload: &[u8], options: AppendOptions) -> ApiResult<u64> {
@@ -548,13 +816,7 @@ impl RemotePool {
     }
 
     pub fn append_lite3_now(&self, payload: &[u8], durability: Durability) -> ApiResult<u64> {
-        self.append_lite3(
-            payload,
-            AppendOptions {
-                timestamp_ns: 0,
-                durability,
-            },
-        )
+        self.append_lite3(payload, AppendOptions::new(0, durability))
     }
 
     pub fn get_message(&self, seq: u64) -> ApiResult<Message> {
@@ -566,7 +828,7 @@ impl RemotePool {
             .client
             .request_json::<(), _>("GET", &url, &())
             .map_err(|err| err.with_path(self.pool.clone()).with_seq(seq))?;
-        Ok(message_from_remote(envelope.message))
+        message_from_remote(envelope.message)
     }
 
     pub fn get_lite3(&self, seq: u64) -> ApiResult<Vec<u8>> {
@@ -611,6 +873,9 @@ impl RemotePool {
             if let Some(since) = options.since_seq {
                 pairs.append_pair("since_seq", &since.to_string());
             }
+            if let Some(min_seq) = options.min_seq {
+                pairs.append_pair("min_seq", &min_seq.to_string());
+            }
             if let Some(max) = options.max_messages {
                 pairs.append_pair("max", &max.to_string());
             }
@@ -626,8 +891,11 @@ impl RemotePool {
             .client
             .request_stream(&url)
             .map_err(|err| err.with_path(self.pool.clone()))?;
+        let request_id = response_request_id(&response);
         Ok(RemoteTail {
             reader: Some(BufReader::new(response.into_reader())),
+            line: Vec::new(),
+            request_id,
         })
     }
 
@@ -638,6 +906,9 @@ impl RemotePool {
             if let Some(since) = options.since_seq {
                 pairs.append_pair("since_seq", &since.to_string());
             }
+            if let Some(min_seq) = options.min_seq {
+                pairs.append_pair("min_seq", &min_seq.to_string());
+            }
             if let Some(max) = options.max_messages {
                 pairs.append_pair("max", &max.to_string());
             }
@@ -659,14 +930,61 @@ impl RemotePool {
     }
 }
 
+impl RemoteAppendReceipts {
+    /// Next receipt, or `None` once the server has answered every record it accepted.
+    pub fn next_receipt(&mut self) -> ApiResult<Option<RemoteAppendReceipt>> {
+        let Some(reader) = self.reader.as_mut() else {
+            return Ok(None);
+        };
+        loop {
+            self.line.clear();
+            let bytes = reader.read_until(b'\n', &mut self.line).map_err(|err| {
+                Error::new(ErrorKind::Io)
+                    .with_message("failed to read append receipts")
+                    .with_source(err)
+            })?;
+            if bytes == 0 {
+                self.reader = None;
+                return Ok(None);
+            }
+            if self.line.trim_ascii().is_empty() {
+                continue;
+            }
+            let line: RemoteReceiptLine = serde_json::from_slice(&self.line).map_err(|err| {
+                Error::new(ErrorKind::Internal)
+                    .with_message("invalid append receipt json")
+                    .with_source(err)
+            })?;
+            let result = match (line.message, line.error) {
+                (Some(message), _) => message_from_remote(message),
+                (None, Some(error)) => {
+                    let err = error_from_remote(error);
+                    Err(match &self.request_id {
+                        Some(request_id) => err.with_request_id(request_id.clone()),
+                        None => err,
+                    })
+                }
+                (None, None) => {
+                    return Err(Error::new(ErrorKind::Internal)
+                        .with_message("append receipt has neither message nor error"));
+                }
+            };
+            return Ok(Some(RemoteAppendReceipt {
+                index: line.index,
+                result,
+            }));
+        }
+    }
+}
+
 impl RemoteTail {
     pub fn next_message(&mut self) -> ApiResult<Option<Message>> {
         let Some(reader) = self.reader.as_mut() else {
             return Ok(None);
         };
         loop {
-            let mut line = String::new();
-            let bytes = reader.read_line(&mut line).map_err(|err| {
+            self.line.clear();
+            let bytes = reader.read_until(b'\n', &mut self.line).map_err(|err| {
                 Error::new(ErrorKind::Io)
                     .with_message("failed to read tail stream")
                     .with_source(err)
@@ -674,15 +992,26 @@ impl RemoteTail {
             if bytes == 0 {
                 return Ok(None);
             }
-            if line.trim().is_empty() {
+            if self.line.trim_ascii().is_empty() {
                 continue;
             }
-            let message: RemoteMessage = serde_json::from_str(&line).map_err(|err| {
+            let line: RemoteTailLine = serde_json::from_slice(&self.line).map_err(|err| {
                 Error::new(ErrorKind::Internal)
                     .with_message("invalid tail message json")
                     .with_source(err)
             })?;
-            return Ok(Some(message_from_remote(message)));
+            return match line {
+                RemoteTailLine::Message(message) => message_from_remote(message).map(Some),
+                RemoteTailLine::Error(envelope) => {
+                    // Terminal error line; the server ends the stream after it.
+                    self.reader = None;
+                    let err = error_from_remote(envelope.error);
+                    Err(match &self.request_id {
+                        Some(request_id) => err.with_request_id(request_id.clone()),
+                        None => err,
+                    })
+                }
+            };
         }
     }
 
@@ -823,13 +1152,33 @@ where
     })
 }
 
+/// Response header carrying the server's per-request correlation ID.
+const REQUEST_ID_HEADER: &str = "x-plasmite-request-id";
+
+fn response_request_id(response: &ureq::Response) -> Option<String> {
+    response.header(REQUEST_ID_HEADER).map(str::to_string)
+}
+
 fn parse_error_response(status: u16, response: ureq::Response) -> Error {
+    let request_id = response_request_id(&response);
     let body = response.into_string().unwrap_or_default();
-    if let Ok(envelope) = serde_json::from_str::<ErrorEnvelope>(&body) {
-        return error_from_remote(envelope.error);
+    let err = match serde_json::from_str::<ErrorEnvelope>(&body) {
+        Ok(envelope) => error_from_remote(envelope.error),
+        // Non-envelope bodies come from proxies or older servers; keep their text when short.
+        Err(_) => {
+            let text = body.trim();
+            let message = if text.is_empty() || text.len() > 512 {
+                format!("remote error status {status}")
+            } else {
+                format!("remote error status {status}: {text}")
+            };
+            Error::new(error_kind_from_status(status)).with_message(message)
+        }
+    };
+    match request_id {
+        Some(request_id) => err.with_request_id(request_id),
+        None => err,
     }
-    let kind = error_kind_from_status(status);
-    Error::new(kind).with_message(format!("remote error status {status}"))
 }
 
 fn read_exact_or_eof(reader: &mut dyn Read, buf: &mut [u8]) -> ApiResult<bool> {
@@ -875,6 +1224,19 @@ fn error_from_remote(remote: RemoteError) -> Error {
     if let Some(offset) = remote.offset {
         err = err.with_offset(offset);
     }
+    let cause = remote
+        .causes
+        .into_iter()
+        .rev()
+        .fold(None, |source, message| {
+            Some(RemoteCause {
+                message,
+                source: source.map(Box::new),
+            })
+        });
+    if let Some(cause) = cause {
+        err = err.with_source(cause);
+    }
     err
 }
 
@@ -894,11 +1256,11 @@ fn parse_error_kind(kind: &str) -> ErrorKind {
 
 fn error_kind_from_status(status: u16) -> ErrorKind {
     match status {
-        400 | 413 => ErrorKind::Usage,
+        400 | 405 | 413 | 415 | 422 => ErrorKind::Usage,
         401 | 403 => ErrorKind::Permission,
         404 => ErrorKind::NotFound,
         409 => ErrorKind::AlreadyExists,
-        423 => ErrorKind::Busy,
+        423 | 429 | 503 => ErrorKind::Busy,
         500..=599 => ErrorKind::Internal,
         _ => ErrorKind::Io,
     }
@@ -923,6 +1285,17 @@ fn pool_info_from_remote(pool_ref: &str, pool: RemotePoolInfo) -> PoolInfo {
             newest_seq: pool.bounds.newest,
         },
         metrics: pool.metrics.map(pool_metrics_from_remote),
+        hybrid_timestamps: pool.hybrid_timestamps,
+        codec: match pool.codec.as_deref() {
+            Some("json") => PoolCodec::Json,
+            _ => PoolCodec::Lite3,
+        },
+        allocated_bytes: pool.allocated_bytes,
+        max_messages: pool.max_messages,
+        max_frame_bytes: pool.max_frame_bytes,
+        max_message_bytes: pool.max_message_bytes,
+        bloom_filter: pool.bloom_filter,
+        frozen: pool.frozen,
     }
 }
 
@@ -950,15 +1323,28 @@ fn pool_metrics_from_remote(metrics: RemotePoolMetrics) -> PoolMetrics {
     }
 }
 
-fn message_from_remote(remote: RemoteMessage) -> Message {
-    Message {
+fn message_from_remote(remote: RemoteMessage) -> ApiResult<Message> {
+    let attachment = remote
+        .attachment
+        .as_deref()
+        .map(base64_decode)
+        .transpose()
+        .map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("server sent an invalid attachment")
+                .with_source(err)
+        })?;
+    Ok(Message {
         seq: remote.seq,
         time: remote.time,
         meta: Meta {
             tags: remote.meta.tags,
+            flags: parse_frame_flags(&remote.meta.flags).unwrap_or(0),
+            control: remote.meta.control,
         },
         data: remote.data,
-    }
+        attachment,
+    })
 }
 
 fn durability_to_str(durability: Durability) -> &'static str {
@@ -971,11 +1357,13 @@ fn durability_to_str(durability: Durability) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::{
-        RemoteClient, extract_pool_from_url, normalize_base_url, parse_error_kind, parse_pool_uri,
+        ErrorEnvelope, RemoteClient, RemoteTail, error_from_remote, extract_pool_from_url,
+        normalize_base_url, parse_error_kind, parse_pool_uri,
     };
     use crate::api::PoolRef;
     use crate::core::error::ErrorKind;
     use crate::core::pool::PoolOptions;
+    use std::io::BufReader;
 
     #[test]
     fn normalize_base_url_strips_path() {
@@ -1021,4 +1409,47 @@ mod tests {
             .expect_err("err");
         assert_eq!(err.kind(), ErrorKind::Usage);
     }
+
+    #[test]
+    fn remote_tail_parses_chunked_lines_and_terminal_error() {
+        let body = concat!(
+            "{\"seq\":1,\"time\":\"t\",\"meta\":{\"tags\":[\"a\"]},\"data\":{\"x\":1}}\n",
+            "\n",
+            "{\"seq\":2,\"time\":\"t\",\"meta\":{\"tags\":[]},\"data\":2}\n",
+            "{\"error\":{\"kind\":\"Corrupt\",\"message\":\"bad frame\"}}\n",
+        );
+        let reader: Box<dyn std::io::Read + Send + Sync> = Box::new(std::io::Cursor::new(body));
+        let mut tail = RemoteTail {
+            reader: Some(BufReader::with_capacity(7, reader)),
+            line: Vec::new(),
+            request_id: Some("abc123".to_string()),
+        };
+        let first = tail.next_message().expect("first").expect("message");
+        assert_eq!(first.seq, 1);
+        assert_eq!(first.meta.tags, vec!["a".to_string()]);
+        assert_eq!(
+            tail.next_message().expect("second").expect("message").seq,
+            2
+        );
+        let err = tail.next_message().expect_err("terminal error");
+        assert_eq!(err.kind(), ErrorKind::Corrupt);
+        assert_eq!(err.request_id(), Some("abc123"));
+        assert!(tail.next_message().expect("ended").is_none());
+    }
+
+    #[test]
+    fn error_envelope_round_trips_into_rich_error() {
+        let envelope: ErrorEnvelope = serde_json::from_str(
+            r#"{"error":{"kind":"Corrupt","message":"bad frame","hint":"Run doctor.",
+                "path":"events","seq":3,"offset":64,"causes":["crc mismatch","short read"]}}"#,
+        )
+        .expect("envelope");
+        let err = error_from_remote(envelope.error);
+        assert_eq!(err.kind(), ErrorKind::Corrupt);
+        assert_eq!(err.message(), Some("bad frame"));
+        assert_eq!(err.hint(), Some("Run doctor."));
+        assert_eq!(err.seq(), Some(3));
+        assert_eq!(err.offset(), Some(64));
+        assert_eq!(err.causes(), vec!["crc mismatch", "short read"]);
+    }
 }
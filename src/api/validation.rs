@@ -3,11 +3,26 @@
 //! Role: Shared contract for CLI diagnostics, API users, and future servers.
 //! Invariants: Reports are additive-only in v0; no heavy payloads are embedded.
 //! Invariants: Snapshot paths are optional and only provided on request.
-
-use crate::core::frame::{self, FRAME_HEADER_LEN, FrameHeader, FrameState};
-use crate::core::pool::PoolHeader;
+//! Invariants: `to_json` is the `doctor --json` report shape shared with the C ABI.
+//! Invariants: Deep scans decode every retained payload and cap reported issues; they stop
+//! with an error when the caller's `Deadline` expires.
+//! Invariants: Intent-journal findings only refine reports; pools without a journal are unaffected.
+
+use super::codec::codec_for;
+use super::deadline::Deadline;
+use crate::core::attachment::unpack;
+use crate::core::cursor::{Cursor, CursorResult};
+use crate::core::error::Error;
+use crate::core::frame::{self, FRAME_HEADER_LEN, FrameState};
+use crate::core::journal::{self, IntentState};
+use crate::core::pool::{Pool, PoolHeader};
+use crate::core::view::RingView;
+use serde_json::{Value, json};
 use std::path::{Path, PathBuf};
 
+/// Upper bound on payload issues collected by a deep scan.
+const MAX_DEEP_ISSUES: usize = 32;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ValidationStatus {
     Ok,
@@ -89,6 +104,233 @@ impl ValidationReport {
         self.last_good_seq = seq;
         self
     }
+
+    /// Parse the `to_json` shape back, e.g. from a remote `validate` response.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let status = match value.get("status")?.as_str()? {
+            "ok" => ValidationStatus::Ok,
+            "corrupt" => ValidationStatus::Corrupt,
+            _ => return None,
+        };
+        let issues = value
+            .get("issues")?
+            .as_array()?
+            .iter()
+            .map(|issue| {
+                Some(ValidationIssue {
+                    code: issue.get("code")?.as_str()?.to_string(),
+                    message: issue.get("message")?.as_str()?.to_string(),
+                    seq: issue.get("seq").and_then(Value::as_u64),
+                    offset: issue.get("offset").and_then(Value::as_u64),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let strings = |key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Some(Self {
+            pool_ref: value
+                .get("pool_ref")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            path: PathBuf::from(value.get("path")?.as_str()?),
+            status,
+            last_good_seq: value.get("last_good_seq").and_then(Value::as_u64),
+            issue_count: value
+                .get("issue_count")
+                .and_then(Value::as_u64)
+                .map_or(issues.len(), |count| count as usize),
+            issues,
+            remediation_hints: strings("remediation_hints"),
+            snapshot_path: value
+                .get("snapshot_path")
+                .and_then(Value::as_str)
+                .map(PathBuf::from),
+        })
+    }
+
+    /// JSON object matching one entry of `plasmite doctor --json` `reports`.
+    pub fn to_json(&self) -> Value {
+        let issues = self
+            .issues
+            .iter()
+            .map(|issue| {
+                json!({
+                    "code": issue.code,
+                    "message": issue.message,
+                    "seq": issue.seq,
+                    "offset": issue.offset,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "pool_ref": self.pool_ref,
+            "path": self.path.to_string_lossy(),
+            "status": match self.status {
+                ValidationStatus::Ok => "ok",
+                ValidationStatus::Corrupt => "corrupt",
+            },
+            "last_good_seq": self.last_good_seq,
+            "issue_count": self.issue_count,
+            "issues": issues,
+            "remediation_hints": self.remediation_hints,
+            "snapshot_path": self.snapshot_path.as_ref().map(|path| path.to_string_lossy()),
+        })
+    }
+}
+
+/// Decode every retained payload with the pool's codec and report undecodable frames.
+///
+/// Structural validation only checks frame headers; this catches payload bytes that
+/// were damaged in place while the header chain stayed intact. Fails once `deadline` does.
+#[allow(clippy::result_large_err)]
+pub(crate) fn deep_payload_issues(
+    pool: &Pool,
+    max_frames: Option<u64>,
+    deadline: &Deadline,
+) -> Result<Vec<ValidationIssue>, Error> {
+    let Ok(header) = pool.header_from_mmap() else {
+        return Ok(Vec::new());
+    };
+    // Bounded scans decode only the newest `max_frames` payloads; older frames are skipped.
+    let first_decoded = max_frames.map_or(0, |max| {
+        header
+            .newest_seq
+            .saturating_sub(max.saturating_sub(1))
+            .max(header.oldest_seq)
+    });
+    let codec = codec_for(pool.codec());
+    let mut cursor = Cursor::new();
+    cursor.seek_to(header.tail_off as usize);
+    let mut issues = Vec::new();
+    while issues.len() < MAX_DEEP_ISSUES {
+        deadline.check()?;
+        let frame = match cursor.next(pool) {
+            Ok(CursorResult::Message(frame)) => frame,
+            Ok(CursorResult::WouldBlock | CursorResult::FellBehind) | Err(_) => break,
+        };
+        if frame.seq > header.newest_seq {
+            break;
+        }
+        if frame.seq < first_decoded {
+            continue;
+        }
+        // Chunked messages decode once, joined at their last chunk; continuations are skipped.
+        let decoded = match pool.join_chunks(&frame) {
+            Ok(Some(payload)) => unpack(frame.flags, &payload)
+                .and_then(|(envelope, _)| codec.decode(envelope))
+                .map(|_| ()),
+            Ok(None) => Ok(()),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = decoded {
+            issues.push(issue(
+                "payload",
+                &format!(
+                    "payload decode failed: {}",
+                    err.message().unwrap_or("invalid payload")
+                ),
+                Some(frame.seq),
+                None,
+            ));
+        }
+    }
+    Ok(issues)
+}
+
+/// Refine `report` with the pool's intent journal, when it has one.
+///
+/// Damage inside a frame whose append never reached `Committed` means the writer died
+/// mid-append; a committed payload that no longer matches its journaled checksum means the
+/// bytes changed afterwards (media errors or external writes).
+pub(crate) fn apply_intent_journal(mut report: ValidationReport, pool: &Pool) -> ValidationReport {
+    let Ok(Some(entries)) = journal::read_entries(pool.path()) else {
+        return report;
+    };
+    let Ok(header) = pool.header_from_mmap() else {
+        return report;
+    };
+    let pending = entries
+        .iter()
+        .filter(|entry| entry.state == IntentState::Intent && entry.seq > header.newest_seq)
+        .collect::<Vec<_>>();
+
+    if report.status == ValidationStatus::Corrupt {
+        let mut interrupted = None;
+        for issue in &mut report.issues {
+            let Some(offset) = issue.offset else {
+                continue;
+            };
+            if let Some(entry) = pending.iter().find(|entry| entry.covers(offset)) {
+                issue.code = "interrupted_append".to_string();
+                issue.message = format!(
+                    "{} (writer died mid-append of seq {} at offset {}, {} bytes)",
+                    issue.message, entry.seq, entry.offset, entry.frame_len
+                );
+                interrupted = Some(entry.seq);
+            }
+        }
+        report.remediation_hints = match interrupted {
+            Some(seq) => vec![format!(
+                "Seq {seq} was never acknowledged to its writer; run `plasmite doctor <pool> \
+                 --repair` to discard it and drop the older messages it overwrote."
+            )],
+            None => {
+                let mut hints = report.remediation_hints;
+                hints.push(
+                    "Intent journal shows no in-flight append at the damaged offset; suspect \
+                     media errors or external writes to the pool file."
+                        .to_string(),
+                );
+                hints
+            }
+        };
+        return report;
+    }
+
+    let mut issues = Vec::new();
+    for entry in entries
+        .iter()
+        .filter(|entry| entry.state == IntentState::Committed)
+    {
+        let Ok(frame) = pool.get(entry.seq) else {
+            continue;
+        };
+        if journal::crc32c(frame.payload) != entry.crc {
+            issues.push(issue(
+                "checksum",
+                "payload differs from its intent-journal checksum (changed after commit)",
+                Some(entry.seq),
+                Some(entry.offset),
+            ));
+        }
+    }
+    for entry in pending {
+        report.remediation_hints.push(format!(
+            "warning: append of seq {} was interrupted before commit and discarded; \
+             committed messages are unaffected",
+            entry.seq
+        ));
+    }
+    if issues.is_empty() {
+        return report;
+    }
+    let mut report = report.set_issues(issues);
+    report.remediation_hints.push(
+        "Committed payload bytes changed on disk; check the storage device and restore affected \
+         messages from a backup."
+            .to_string(),
+    );
+    report
 }
 
 pub(crate) fn validate_pool_state_report(
@@ -96,23 +338,22 @@ pub(crate) fn validate_pool_state_report(
     mmap: &[u8],
     path: &Path,
 ) -> ValidationReport {
-    let ring_offset = header.ring_offset as usize;
-    let ring_size = header.ring_size as usize;
-
-    if ring_size == 0 {
-        return ValidationReport::corrupt(
-            path.to_path_buf(),
-            issue("corrupt", "ring size is zero", None, None),
-            None,
-        );
-    }
-    if ring_offset + ring_size > mmap.len() {
-        return ValidationReport::corrupt(
-            path.to_path_buf(),
-            issue("corrupt", "ring exceeds mmap bounds", None, None),
-            None,
-        );
-    }
+    let ring = match RingView::new(mmap, header) {
+        Ok(ring) => ring,
+        Err(err) => {
+            return ValidationReport::corrupt(
+                path.to_path_buf(),
+                issue(
+                    "corrupt",
+                    err.message().unwrap_or("ring exceeds mmap bounds"),
+                    None,
+                    None,
+                ),
+                None,
+            );
+        }
+    };
+    let ring_size = ring.ring_size();
     let head = header.head_off as usize;
     let tail = header.tail_off as usize;
     if head >= ring_size || tail >= ring_size {
@@ -145,6 +386,7 @@ pub(crate) fn validate_pool_state_report(
     let max_frames = ring_size / FRAME_HEADER_LEN + 1;
     let mut steps = 0usize;
     let mut last_good_seq = None;
+    let mut timestamps = TimestampOrder::default();
 
     loop {
         if steps > max_frames {
@@ -164,7 +406,7 @@ pub(crate) fn validate_pool_state_report(
             steps += 1;
             continue;
         }
-        let frame = match read_frame_header(mmap, ring_offset, offset) {
+        let frame = match ring.frame_header(offset) {
             Ok(frame) => frame,
             Err(err) => {
                 return ValidationReport::corrupt(
@@ -258,6 +500,7 @@ pub(crate) fn validate_pool_state_report(
         }
 
         last_good_seq = Some(frame.seq);
+        timestamps.observe(frame.seq, frame.timestamp_ns);
 
         if expected_seq == header.newest_seq {
             if next_off != head {
@@ -281,12 +524,50 @@ pub(crate) fn validate_pool_state_report(
     }
 
     let mut report = ValidationReport::ok(path.to_path_buf()).set_last_good(last_good_seq);
-    for warning in spot_check_index_warnings(header, mmap) {
+    for warning in spot_check_index_warnings(&ring) {
+        report.remediation_hints.push(format!("warning: {warning}"));
+    }
+    if let Some(warning) = timestamps.warning() {
         report.remediation_hints.push(format!("warning: {warning}"));
     }
     report
 }
 
+/// Tracks frame timestamps that go backwards in seq order (clock jumps, sleep/wake).
+///
+/// Regressions are reported as warnings, not corruption: seq order is still authoritative,
+/// but `--since` and timed replay will behave unexpectedly around the jump.
+#[derive(Default)]
+struct TimestampOrder {
+    previous: Option<u64>,
+    regressions: u64,
+    first_seq: Option<u64>,
+    max_backstep_ns: u64,
+}
+
+impl TimestampOrder {
+    fn observe(&mut self, seq: u64, timestamp_ns: u64) {
+        if let Some(previous) = self.previous {
+            if timestamp_ns < previous {
+                self.regressions += 1;
+                self.first_seq.get_or_insert(seq);
+                self.max_backstep_ns = self.max_backstep_ns.max(previous - timestamp_ns);
+            }
+        }
+        self.previous = Some(timestamp_ns);
+    }
+
+    fn warning(&self) -> Option<String> {
+        let first_seq = self.first_seq?;
+        Some(format!(
+            "{} non-monotonic timestamp(s), first at seq {first_seq} (largest backstep {}ms); \
+             create pools with --hybrid-timestamps to keep timestamps ordered",
+            self.regressions,
+            self.max_backstep_ns / 1_000_000
+        ))
+    }
+}
+
 fn issue(code: &str, message: &str, seq: Option<u64>, offset: Option<u64>) -> ValidationIssue {
     ValidationIssue {
         code: code.to_string(),
@@ -296,23 +577,17 @@ fn issue(code: &str, message: &str, seq: Option<u64>, offset: Option<u64>) -> Va
     }
 }
 
-fn read_frame_header(mmap: &[u8], ring_offset: usize, head: usize) -> Result<FrameHeader, String> {
-    let start = ring_offset + head;
-    let end = start + FRAME_HEADER_LEN;
-    FrameHeader::decode(&mmap[start..end]).map_err(|err| err.to_string())
-}
-
-fn spot_check_index_warnings(header: PoolHeader, mmap: &[u8]) -> Vec<String> {
+fn spot_check_index_warnings(ring: &RingView<'_>) -> Vec<String> {
+    let header = ring.header();
     if header.index_capacity == 0 {
         return Vec::new();
     }
 
-    let ring_offset = header.ring_offset as usize;
-    let ring_size = header.ring_size as usize;
-    let index_offset = header.index_offset as usize;
     let index_slots = header.index_capacity as usize;
-    let index_bytes = index_slots.saturating_mul(16);
-    if index_offset + index_bytes > ring_offset {
+    let index_end = (header.index_capacity as u64)
+        .checked_mul(16)
+        .and_then(|bytes| bytes.checked_add(header.index_offset));
+    if index_end.is_none_or(|end| end > header.ring_offset) {
         return vec!["index bounds overlap ring".to_string()];
     }
 
@@ -326,24 +601,20 @@ fn spot_check_index_warnings(header: PoolHeader, mmap: &[u8]) -> Vec<String> {
 
     let mut warnings = Vec::new();
     for slot in sample_slots {
-        let entry_off = index_offset + slot * 16;
-        let seq = u64::from_le_bytes(mmap[entry_off..entry_off + 8].try_into().unwrap_or([0; 8]));
-        let offset = u64::from_le_bytes(
-            mmap[entry_off + 8..entry_off + 16]
-                .try_into()
-                .unwrap_or([0; 8]),
-        );
+        // Slot `n` is the one seq `n` maps to, so probing by seq reads exactly that slot.
+        let Some((seq, offset)) = ring.index_entry(slot as u64) else {
+            continue;
+        };
         if seq == 0 {
             continue;
         }
-        if offset as usize >= ring_size {
+        if offset >= header.ring_size {
             warnings.push(format!(
                 "index slot {slot} seq {seq} points outside ring at offset {offset}"
             ));
             continue;
         }
-        let frame = read_frame_header(mmap, ring_offset, offset as usize);
-        match frame {
+        match ring.frame_header(offset as usize) {
             Ok(frame) if frame.state == FrameState::Committed && frame.seq == seq => {}
             _ => warnings.push(format!("index slot {slot} seq {seq} is stale or invalid")),
         }
@@ -354,8 +625,12 @@ fn spot_check_index_warnings(header: PoolHeader, mmap: &[u8]) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ValidationStatus, validate_pool_state_report};
-    use crate::core::pool::{Pool, PoolOptions};
+    use super::{
+        ValidationStatus, apply_intent_journal, deep_payload_issues, validate_pool_state_report,
+    };
+    use crate::api::{Deadline, PoolApiExt};
+    use crate::core::pool::{AppendOptions, Durability, Pool, PoolOptions};
+    use serde_json::json;
 
     #[test]
     fn validation_report_ok_for_empty_pool() {
@@ -387,4 +662,142 @@ mod tests {
         assert_eq!(report.last_good_seq, None);
         assert_eq!(report.path, path);
     }
+
+    #[test]
+    fn validation_report_warns_on_timestamp_regression() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("skew.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        for timestamp_ns in [3_000_000_000, 1_000_000_000, 2_000_000_000] {
+            pool.append_with_options(b"{}", AppendOptions::new(timestamp_ns, Durability::Fast))
+                .expect("append");
+        }
+        let header = pool.header_from_mmap().expect("header");
+
+        let report = validate_pool_state_report(header, pool.mmap(), &path);
+        assert_eq!(report.status, ValidationStatus::Ok);
+        assert_eq!(report.issue_count, 0);
+        assert_eq!(report.remediation_hints.len(), 1);
+        assert!(
+            report.remediation_hints[0].contains("1 non-monotonic timestamp(s), first at seq 2")
+        );
+        assert!(report.remediation_hints[0].contains("largest backstep 2000ms"));
+    }
+
+    #[test]
+    fn deep_scan_flags_undecodable_payloads() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("deep.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        pool.append_json(&json!({"ok": true}), &[], AppendOptions::default())
+            .expect("append");
+        pool.append_with_options(b"not lite3", AppendOptions::new(1, Durability::Fast))
+            .expect("append raw");
+        assert!(
+            validate_pool_state_report(
+                pool.header_from_mmap().expect("header"),
+                pool.mmap(),
+                &path
+            )
+            .issues
+            .is_empty()
+        );
+
+        let none = Deadline::default();
+        let issues = deep_payload_issues(&pool, None, &none).expect("deep scan");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "payload");
+        assert_eq!(issues[0].seq, Some(2));
+
+        pool.append_json(&json!({"ok": true}), &[], AppendOptions::default())
+            .expect("append");
+        let bounded = |max| deep_payload_issues(&pool, Some(max), &none).expect("deep scan");
+        assert!(bounded(1).is_empty());
+        assert_eq!(bounded(2).len(), 1);
+        let expired = Deadline::after(std::time::Duration::ZERO);
+        let err = deep_payload_issues(&pool, None, &expired).expect_err("expired deadline");
+        assert_eq!(err.kind(), crate::core::error::ErrorKind::Busy);
+    }
+
+    #[test]
+    fn report_json_matches_doctor_shape() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("shape.plasmite");
+        let report = super::ValidationReport::ok(path.clone()).with_pool_ref("shape");
+        let value = report.to_json();
+        assert_eq!(value["pool_ref"], "shape");
+        assert_eq!(value["status"], "ok");
+        assert_eq!(value["issue_count"], 0);
+        assert_eq!(value["path"], path.to_string_lossy().as_ref());
+        assert!(value["snapshot_path"].is_null());
+        assert_eq!(super::ValidationReport::from_json(&value), Some(report));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn intent_journal_separates_interrupted_appends_from_media_damage() {
+        use crate::core::frame::FRAME_HEADER_LEN;
+        use crate::core::journal::{self, IntentJournal};
+        use std::os::unix::fs::FileExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("journaled.plasmite");
+        let options = PoolOptions::new(1024 * 1024).with_intent_journal(true);
+        let mut pool = Pool::create(&path, options).expect("create");
+        for n in 0..3 {
+            pool.append_json(&json!({ "n": n }), &[], AppendOptions::default())
+                .expect("append");
+        }
+        let entries = journal::read_entries(&path)
+            .expect("read")
+            .expect("journal");
+        assert_eq!(entries.len(), 3);
+        let check = |pool: &Pool| {
+            let header = pool.header_from_mmap().expect("header");
+            apply_intent_journal(validate_pool_state_report(header, pool.mmap(), &path), pool)
+        };
+        assert_eq!(check(&pool).status, ValidationStatus::Ok);
+
+        // Flip one payload byte of committed seq 2: the header chain is intact, the checksum is not.
+        let ring_offset = pool.header_from_mmap().expect("header").ring_offset;
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .expect("open");
+        let payload_at = ring_offset + entries[1].offset + FRAME_HEADER_LEN as u64;
+        let mut byte = [0u8; 1];
+        file.read_exact_at(&mut byte, payload_at).expect("read");
+        file.write_all_at(&[byte[0] ^ 0xFF], payload_at)
+            .expect("write");
+        let report = check(&pool);
+        assert_eq!(report.status, ValidationStatus::Corrupt);
+        assert_eq!(report.issues[0].code, "checksum");
+        assert_eq!(report.issues[0].seq, Some(2));
+        file.write_all_at(&byte, payload_at).expect("restore");
+
+        // A writer that died while overwriting the oldest frame leaves an uncommitted intent there.
+        let tail_off = pool.header_from_mmap().expect("header").tail_off;
+        let rollback = journal::Rollback {
+            tail_off: entries[1].offset,
+            tail_next_off: entries[2].offset,
+            oldest_seq: 2,
+        };
+        IntentJournal::open_or_create(&path)
+            .expect("journal")
+            .record_intent(4, tail_off, 4096, 0, rollback, true);
+        file.write_all_at(&[0xAA; 8], ring_offset + tail_off)
+            .expect("clobber");
+        let report = check(&pool);
+        assert_eq!(report.status, ValidationStatus::Corrupt);
+        assert_eq!(report.issues[0].code, "interrupted_append");
+        assert!(report.remediation_hints[0].contains("Seq 4"));
+
+        // Repair drops the clobbered seq 1 and forgets the intent.
+        assert_eq!(pool.repair_interrupted_append().expect("repair"), Some(4));
+        assert_eq!(check(&pool).status, ValidationStatus::Ok);
+        let bounds = pool.bounds().expect("bounds");
+        assert_eq!((bounds.oldest_seq, bounds.newest_seq), (Some(2), Some(3)));
+        assert_eq!(pool.repair_interrupted_append().expect("repair"), None);
+    }
 }
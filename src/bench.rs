@@ -17,6 +17,7 @@ use serde_json::{Value, json};
 use plasmite::api::{
     AppendOptions, Cursor, CursorResult, Durability, Error, ErrorKind, Pool, PoolOptions, lite3,
 };
+use plasmite::message_jsonl::FrameJsonlEncoder;
 
 #[derive(Clone, Debug)]
 pub struct BenchArgs {
@@ -133,6 +134,15 @@ pub fn run_bench(args: BenchArgs, program_version: &str) -> Result<(), Error> {
                 )?;
                 results.push(follow_local);
 
+                let follow_emit = bench_follow_emit(
+                    &pool_path,
+                    *pool_size,
+                    *payload_bytes,
+                    args.messages,
+                    *durability,
+                )?;
+                results.extend(follow_emit);
+
                 let follow = bench_follow(
                     &work_dir,
                     &pool_path,
@@ -439,6 +449,13 @@ impl BenchRow {
                 }
             }
             "multi_writer" => "multi_writer".to_string(),
+            "follow_emit" => {
+                if self.notes.starts_with("batched") {
+                    "emit:batched".to_string()
+                } else {
+                    "emit:value".to_string()
+                }
+            }
             "follow" => {
                 if self.notes.contains("single-process") {
                     "follow:local".to_string()
@@ -676,6 +693,110 @@ fn bench_follow_local(
     Ok(Value::Object(entry.into_iter().collect()))
 }
 
+/// Compare per-message `Value` + `String` JSONL output with the batched encoder used by
+/// `plasmite follow` (both write to a sink, so only encode/allocation cost is measured).
+fn bench_follow_emit(
+    pool_path: &Path,
+    pool_size: u64,
+    payload_bytes: usize,
+    messages: u64,
+    durability: Durability,
+) -> Result<Vec<Value>, Error> {
+    let _ = std::fs::remove_file(pool_path);
+    let mut pool = Pool::create(pool_path, PoolOptions::new(pool_size))?;
+    let payload_once = payload_for_bytes(payload_bytes, None, false)?;
+    for _ in 0..messages {
+        append_with_durability(&mut pool, payload_once.as_slice(), durability)?;
+    }
+    let header = pool.header_from_mmap()?;
+    let io_err = |err: io::Error| Error::new(ErrorKind::Io).with_source(err);
+
+    let mut sink = io::sink();
+    let mut cursor = Cursor::new();
+    cursor.seek_to(header.tail_off as usize);
+    let mut value_count = 0u64;
+    let start = Instant::now();
+    while let CursorResult::Message(frame) = cursor.next(&pool)? {
+        let doc = lite3::Lite3DocRef::new(frame.payload);
+        let data_json = doc.to_json_at(doc.key_offset("data")?, false)?;
+        let data: Value = serde_json::from_str(&data_json)
+            .map_err(|err| Error::new(ErrorKind::Corrupt).with_source(err))?;
+        let ts = time::OffsetDateTime::from_unix_timestamp_nanos(frame.timestamp_ns as i128)
+            .map_err(|err| Error::new(ErrorKind::Internal).with_source(err))?
+            .format(&time::format_description::well_known::Rfc3339)
+            .map_err(|err| Error::new(ErrorKind::Internal).with_source(err))?;
+        let message = json!({
+            "seq": frame.seq,
+            "time": ts,
+            "meta": {"tags": Vec::<String>::new()},
+            "data": data,
+        });
+        let line = serde_json::to_string(&message)
+            .map_err(|err| Error::new(ErrorKind::Internal).with_source(err))?;
+        writeln!(sink, "{line}").map_err(io_err)?;
+        value_count += 1;
+    }
+    let value_dur = start.elapsed();
+
+    let mut encoder = FrameJsonlEncoder::new(pool.codec());
+    let mut cursor = Cursor::new();
+    cursor.seek_to(header.tail_off as usize);
+    let mut batched_count = 0u64;
+    let start = Instant::now();
+    while let CursorResult::Message(frame) = cursor.next(&pool)? {
+        encoder.encode(&frame, false)?;
+        if encoder.buffered().len() >= 64 * 1024 {
+            encoder.write_to(&mut sink).map_err(io_err)?;
+        }
+        batched_count += 1;
+    }
+    encoder.write_to(&mut sink).map_err(io_err)?;
+    let batched_dur = start.elapsed();
+
+    let speedup = value_dur.as_secs_f64() / batched_dur.as_secs_f64().max(1e-9);
+    let value_entry = result_entry(
+        "follow_emit",
+        pool_size,
+        payload_bytes,
+        value_count,
+        1,
+        value_dur,
+        durability,
+        Some("value: Value + String per message"),
+    );
+    let mut batched_entry = result_entry(
+        "follow_emit",
+        pool_size,
+        payload_bytes,
+        batched_count,
+        1,
+        batched_dur,
+        durability,
+        Some(&format!("batched: reused buffer ({speedup:.2}x)")),
+    );
+    if let Some(map) = batched_entry.as_object_mut() {
+        map.insert("speedup_vs_value".to_string(), json!(speedup));
+    }
+    Ok(vec![
+        with_runtime_metadata(
+            value_entry,
+            "follow_emit_value",
+            "single_process",
+            "none",
+            "lite3_to_json_value",
+            "follow_emit_value",
+        ),
+        with_runtime_metadata(
+            batched_entry,
+            "follow_emit_batched",
+            "single_process",
+            "none",
+            "lite3_to_json_value",
+            "follow_emit_batched",
+        ),
+    ])
+}
+
 fn bench_follow(
     work_dir: &Path,
     pool_path: &Path,
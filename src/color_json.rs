@@ -1,90 +1,169 @@
-//! Purpose: Render pretty JSON with optional ANSI colorization for CLI output.
-//! Exports: colorize_json.
-//! Role: Small, pure formatter used by CLI emission paths.
+//! Purpose: Render pretty JSON and CLI labels with optional, themed ANSI styling.
+//! Exports: colorize_json, paint, Theme, Style, color_env_override.
+//! Role: Single home for CLI style decisions (palettes, NO_COLOR/CLICOLOR_FORCE).
 //! Invariants: When color is disabled, output equals serde_json::to_string_pretty.
 //! Invariants: ANSI escapes appear only when explicitly enabled.
+//! Invariants: `--color always|never` beats the environment; env only steers `auto`.
 use serde_json::Value;
 
 const INDENT: &str = "  ";
 
-// Conservative 8/16-color palette for broad terminal compatibility.
-// Avoid bright variants that can lose contrast on themes like Solarized.
-const COLOR_KEY: &str = "36";
-const COLOR_STRING: &str = "32";
-const COLOR_NUMBER: &str = "33";
-const COLOR_BOOL: &str = "35";
-const COLOR_NULL: &str = "39";
-const COLOR_PUNCT: &str = "39";
+/// Palette family for styled output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    /// Conservative 8/16-color palette tuned for dark backgrounds.
+    #[default]
+    Dark,
+    /// Avoids yellow/cyan, which wash out on light backgrounds.
+    Light,
+    /// No hues; bold keys and labels only.
+    Mono,
+}
+
+/// Semantic roles that callers style; the theme maps each role to SGR codes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Style {
+    Key,
+    String,
+    Number,
+    Bool,
+    Null,
+    Punct,
+    Error,
+    Warning,
+}
+
+impl Theme {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "dark" => Some(Theme::Dark),
+            "light" => Some(Theme::Light),
+            "mono" => Some(Theme::Mono),
+            _ => None,
+        }
+    }
+
+    /// SGR parameters for `style`, or `None` when the theme leaves it unstyled.
+    fn sgr(self, style: Style) -> Option<&'static str> {
+        // Avoid bright variants that can lose contrast on themes like Solarized.
+        match self {
+            Theme::Dark => Some(match style {
+                Style::Key => "36",
+                Style::String => "32",
+                Style::Number => "33",
+                Style::Bool => "35",
+                Style::Null | Style::Punct => "39",
+                Style::Error => "31",
+                Style::Warning => "33",
+            }),
+            Theme::Light => Some(match style {
+                Style::Key => "34",
+                Style::String => "32",
+                Style::Number => "35",
+                Style::Bool => "31",
+                Style::Null | Style::Punct => "39",
+                Style::Error => "31",
+                Style::Warning => "35",
+            }),
+            Theme::Mono => match style {
+                Style::Key | Style::Error | Style::Warning => Some("1"),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Environment color preference for `--color auto`, per no-color.org and CLICOLOR_FORCE.
+///
+/// A non-empty `NO_COLOR` disables color and wins over `CLICOLOR_FORCE`;
+/// a non-empty `CLICOLOR_FORCE` other than `0` enables color even off a TTY.
+pub fn color_env_override(no_color: Option<&str>, clicolor_force: Option<&str>) -> Option<bool> {
+    if no_color.is_some_and(|value| !value.is_empty()) {
+        return Some(false);
+    }
+    if clicolor_force.is_some_and(|value| !value.is_empty() && value != "0") {
+        return Some(true);
+    }
+    None
+}
 
-pub fn colorize_json(value: &Value, use_color: bool) -> String {
+/// Pretty-print `value`, styled with `theme` when color is enabled (`Some`).
+pub fn colorize_json(value: &Value, theme: Option<Theme>) -> String {
     let mut out = String::new();
-    write_value(value, 0, use_color, &mut out);
+    write_value(value, 0, theme, &mut out);
     out
 }
 
-fn write_value(value: &Value, indent: usize, use_color: bool, out: &mut String) {
+/// Style a short label (for example `error:`) with the theme's color for `style`.
+pub fn paint(text: &str, style: Style, theme: Option<Theme>) -> String {
+    let mut out = String::new();
+    push_colored(text, style, theme, &mut out);
+    out
+}
+
+fn write_value(value: &Value, indent: usize, theme: Option<Theme>, out: &mut String) {
     match value {
-        Value::Null => push_colored("null", COLOR_NULL, use_color, out),
+        Value::Null => push_colored("null", Style::Null, theme, out),
         Value::Bool(val) => {
             let text = if *val { "true" } else { "false" };
-            push_colored(text, COLOR_BOOL, use_color, out);
+            push_colored(text, Style::Bool, theme, out);
         }
-        Value::Number(num) => push_colored(&num.to_string(), COLOR_NUMBER, use_color, out),
+        Value::Number(num) => push_colored(&num.to_string(), Style::Number, theme, out),
         Value::String(text) => {
             let encoded = serde_json::to_string(text).unwrap_or_else(|_| "\"\"".to_string());
-            push_colored(&encoded, COLOR_STRING, use_color, out);
+            push_colored(&encoded, Style::String, theme, out);
         }
-        Value::Array(items) => write_array(items, indent, use_color, out),
-        Value::Object(map) => write_object(map, indent, use_color, out),
+        Value::Array(items) => write_array(items, indent, theme, out),
+        Value::Object(map) => write_object(map, indent, theme, out),
     }
 }
 
-fn write_array(items: &[Value], indent: usize, use_color: bool, out: &mut String) {
+fn write_array(items: &[Value], indent: usize, theme: Option<Theme>, out: &mut String) {
     if items.is_empty() {
-        push_colored("[]", COLOR_PUNCT, use_color, out);
+        push_colored("[]", Style::Punct, theme, out);
         return;
     }
-    push_colored("[", COLOR_PUNCT, use_color, out);
+    push_colored("[", Style::Punct, theme, out);
     out.push('\n');
     for (idx, item) in items.iter().enumerate() {
         push_indent(indent + 1, out);
-        write_value(item, indent + 1, use_color, out);
+        write_value(item, indent + 1, theme, out);
         if idx + 1 < items.len() {
-            push_colored(",", COLOR_PUNCT, use_color, out);
+            push_colored(",", Style::Punct, theme, out);
         }
         out.push('\n');
     }
     push_indent(indent, out);
-    push_colored("]", COLOR_PUNCT, use_color, out);
+    push_colored("]", Style::Punct, theme, out);
 }
 
 fn write_object(
     map: &serde_json::Map<String, Value>,
     indent: usize,
-    use_color: bool,
+    theme: Option<Theme>,
     out: &mut String,
 ) {
     if map.is_empty() {
-        push_colored("{}", COLOR_PUNCT, use_color, out);
+        push_colored("{}", Style::Punct, theme, out);
         return;
     }
-    push_colored("{", COLOR_PUNCT, use_color, out);
+    push_colored("{", Style::Punct, theme, out);
     out.push('\n');
     let len = map.len();
     for (idx, (key, value)) in map.iter().enumerate() {
         push_indent(indent + 1, out);
         let encoded = serde_json::to_string(key).unwrap_or_else(|_| "\"\"".to_string());
-        push_colored(&encoded, COLOR_KEY, use_color, out);
-        push_colored(":", COLOR_PUNCT, use_color, out);
+        push_colored(&encoded, Style::Key, theme, out);
+        push_colored(":", Style::Punct, theme, out);
         out.push(' ');
-        write_value(value, indent + 1, use_color, out);
+        write_value(value, indent + 1, theme, out);
         if idx + 1 < len {
-            push_colored(",", COLOR_PUNCT, use_color, out);
+            push_colored(",", Style::Punct, theme, out);
         }
         out.push('\n');
     }
     push_indent(indent, out);
-    push_colored("}", COLOR_PUNCT, use_color, out);
+    push_colored("}", Style::Punct, theme, out);
 }
 
 fn push_indent(level: usize, out: &mut String) {
@@ -93,11 +172,11 @@ fn push_indent(level: usize, out: &mut String) {
     }
 }
 
-fn push_colored(text: &str, color: &str, use_color: bool, out: &mut String) {
-    if !use_color {
+fn push_colored(text: &str, style: Style, theme: Option<Theme>, out: &mut String) {
+    let Some(color) = theme.and_then(|theme| theme.sgr(style)) else {
         out.push_str(text);
         return;
-    }
+    };
     out.push_str("\u{1b}[");
     out.push_str(color);
     out.push('m');
@@ -107,7 +186,7 @@ fn push_colored(text: &str, color: &str, use_color: bool, out: &mut String) {
 
 #[cfg(test)]
 mod tests {
-    use super::colorize_json;
+    use super::{Style, Theme, color_env_override, colorize_json, paint};
     use serde_json::json;
 
     #[test]
@@ -116,7 +195,7 @@ mod tests {
             "arr": [1, true, null],
             "nested": { "x": "y" }
         });
-        let plain = colorize_json(&value, false);
+        let plain = colorize_json(&value, None);
         let pretty = serde_json::to_string_pretty(&value).expect("pretty");
         assert_eq!(plain, pretty);
     }
@@ -124,7 +203,7 @@ mod tests {
     #[test]
     fn colorize_json_emits_ansi_when_enabled() {
         let value = json!({"k":"v","n":1,"b":true,"z":null});
-        let colored = colorize_json(&value, true);
+        let colored = colorize_json(&value, Some(Theme::Dark));
         assert!(colored.contains("\u{1b}["));
         assert!(colored.contains("\u{1b}[36m\"k\"\u{1b}[0m"));
         assert!(colored.contains("\u{1b}[32m\"v\"\u{1b}[0m"));
@@ -132,4 +211,28 @@ mod tests {
         assert!(colored.contains("\u{1b}[35mtrue\u{1b}[0m"));
         assert!(colored.contains("\u{1b}[39mnull\u{1b}[0m"));
     }
+
+    #[test]
+    fn light_and_mono_themes_avoid_dark_palette() {
+        let value = json!({"k":"v","n":1});
+        let light = colorize_json(&value, Some(Theme::Light));
+        assert!(light.contains("\u{1b}[34m\"k\"\u{1b}[0m"));
+        assert!(light.contains("\u{1b}[35m1\u{1b}[0m"));
+        assert!(!light.contains("\u{1b}[33m"));
+
+        let mono = colorize_json(&value, Some(Theme::Mono));
+        assert!(mono.contains("\u{1b}[1m\"k\"\u{1b}[0m"));
+        assert!(mono.contains(": \"v\""));
+        assert_eq!(paint("hint:", Style::Warning, None), "hint:");
+    }
+
+    #[test]
+    fn color_env_override_follows_no_color_then_clicolor_force() {
+        assert_eq!(color_env_override(None, None), None);
+        assert_eq!(color_env_override(Some("1"), Some("1")), Some(false));
+        assert_eq!(color_env_override(Some(""), Some("1")), Some(true));
+        assert_eq!(color_env_override(None, Some("0")), None);
+        assert_eq!(Theme::parse("Light"), Some(Theme::Light));
+        assert_eq!(Theme::parse("neon"), None);
+    }
 }
@@ -17,11 +17,23 @@ pub(super) fn dispatch_command(
             clap_complete::aot::generate(shell, &mut cmd, "plasmite", &mut io::stdout());
             Ok(RunOutcome::ok())
         }
+        Command::Ui { pool } => ui::run_ui(&pool_dir, pool.as_deref()),
         Command::Version => {
             emit_version_output(color_mode);
             Ok(RunOutcome::ok())
         }
-        Command::Doctor { pool, all, json } => {
+        Command::Doctor {
+            pool,
+            all,
+            deep,
+            max_frames,
+            repair,
+            json,
+            token_file,
+            tls_ca,
+            tls_skip_verify,
+        } => {
+            let json = wants_json(json);
             if all && pool.is_some() {
                 return Err(Error::new(ErrorKind::Usage)
                     .with_message("--all cannot be combined with a pool name")
@@ -32,23 +44,86 @@ pub(super) fn dispatch_command(
                     .with_message("doctor requires a pool name or --all")
                     .with_hint("Use `plasmite doctor <pool>` or `plasmite doctor --all`."));
             }
-            let client = LocalClient::new().with_pool_dir(&pool_dir);
-            let reports = if let Some(pool) = pool {
-                let path = resolve_poolref(&pool, &pool_dir)?;
-                let pool_ref = PoolRef::path(path.clone());
-                vec![doctor_report(&client, pool_ref, pool, path)?]
-            } else {
-                let mut reports = Vec::new();
-                for path in list_pool_paths(&pool_dir)? {
-                    let label = path.to_string_lossy().to_string();
+            let client = LocalClient::new()
+                .with_pool_dir(&pool_dir)
+                .with_deadline(op_deadline());
+            let target = pool
+                .as_deref()
+                .map(|pool| resolve_pool_target(pool, &pool_dir))
+                .transpose()?;
+            let reports = match (pool, target) {
+                (
+                    Some(pool),
+                    Some(PoolTarget::Remote {
+                        base_url,
+                        pool: name,
+                    }),
+                ) => {
+                    if repair {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("--repair is only supported for local pools")
+                            .with_hint(
+                                "Run `plasmite doctor <pool> --repair` on the server host.",
+                            ));
+                    }
+                    let token_value = resolve_token_value(None, token_file)?;
+                    let mut client = RemoteClient::new(base_url)?;
+                    if let Some(token_value) = token_value {
+                        client = client.with_token(token_value);
+                    }
+                    if let Some(path) = tls_ca {
+                        client = client.with_tls_ca_file(path)?;
+                    }
+                    if tls_skip_verify {
+                        eprintln!(
+                            "warning: --tls-skip-verify disables TLS certificate verification (unsafe)"
+                        );
+                        client = client.with_tls_skip_verify();
+                    }
+                    let remote_pool = client
+                        .open_pool(&PoolRef::name(name.clone()))
+                        .map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+                    let report = match remote_pool.validate(deep, max_frames) {
+                        Ok(report) => report,
+                        Err(err) if err.kind() == ErrorKind::Corrupt => {
+                            ValidationReport::corrupt(PathBuf::from(&name), error_issue(&err), None)
+                        }
+                        Err(err) => return Err(err),
+                    };
+                    vec![report.with_pool_ref(pool)]
+                }
+                (Some(pool), Some(PoolTarget::LocalPath(path))) => {
+                    reject_remote_only_flags_for_local_target(
+                        "doctor",
+                        None,
+                        token_file.as_deref(),
+                        tls_ca.as_deref(),
+                        tls_skip_verify,
+                        None,
+                    )?;
                     let pool_ref = PoolRef::path(path.clone());
-                    reports.push(doctor_report(&client, pool_ref, label, path)?);
+                    vec![doctor_report(
+                        &client, pool_ref, pool, path, deep, max_frames, repair,
+                    )?]
+                }
+                _ => {
+                    let mut reports = Vec::new();
+                    for path in list_pool_paths(&pool_dir)? {
+                        let label = path.to_string_lossy().to_string();
+                        let pool_ref = PoolRef::path(path.clone());
+                        reports.push(doctor_report(
+                            &client, pool_ref, label, path, deep, max_frames, repair,
+                        )?);
+                    }
+                    reports
                 }
-                reports
             };
 
             if json {
-                let values = reports.iter().map(report_json).collect::<Vec<_>>();
+                let values = reports
+                    .iter()
+                    .map(ValidationReport::to_json)
+                    .collect::<Vec<_>>();
                 emit_json(json!({ "reports": values }), color_mode);
             } else if all {
                 emit_doctor_human_summary(&reports);
@@ -68,7 +143,440 @@ pub(super) fn dispatch_command(
             };
             Ok(RunOutcome::with_code(exit_code))
         }
-        Command::Serve { subcommand, run } => match subcommand {
+        Command::Diff { a, b, key, json } => {
+            let json = wants_json(json);
+            let key_filter = key.as_deref().map(JqFilter::compile).transpose()?;
+            let a_entries = diff_source_entries(&a, &pool_dir, key_filter.as_ref())?;
+            let b_entries = diff_source_entries(&b, &pool_dir, key_filter.as_ref())?;
+            let report = pool_diff::diff(&a_entries, &b_entries);
+            if json {
+                let mut value = report.to_json();
+                if let Some(object) = value.as_object_mut() {
+                    object.insert("a".to_string(), json!(a));
+                    object.insert("b".to_string(), json!(b));
+                    // `key` is null when messages were matched by content.
+                    object.insert("key".to_string(), json!(key));
+                }
+                emit_json(value, color_mode);
+            } else {
+                emit_diff_human(&a, &b, &report);
+            }
+            Ok(RunOutcome::with_code(if report.is_identical() {
+                0
+            } else {
+                1
+            }))
+        }
+        Command::Backup {
+            pool,
+            to,
+            incremental,
+            json,
+        } => {
+            let json = wants_json(json);
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("backup accepts local pool refs only"));
+            }
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let outcome = pool_backup::backup(&path, &to, incremental)
+                .map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            let value = match &outcome {
+                pool_backup::BackupOutcome::Base { path, newest } => json!({
+                    "kind": "base",
+                    "path": path.display().to_string(),
+                    "newest": newest,
+                }),
+                pool_backup::BackupOutcome::Delta {
+                    path,
+                    first,
+                    last,
+                    missed,
+                } => json!({
+                    "kind": "delta",
+                    "path": path.display().to_string(),
+                    "first": first,
+                    "last": last,
+                    "missed": missed,
+                }),
+                pool_backup::BackupOutcome::UpToDate { newest } => json!({
+                    "kind": "up_to_date",
+                    "newest": newest,
+                }),
+            };
+            if json {
+                emit_json(json!({ "pool": pool, "backup": value }), color_mode);
+            } else {
+                match outcome {
+                    pool_backup::BackupOutcome::Base { path, .. } => {
+                        println!("Wrote full backup of {pool} to {}", path.display());
+                    }
+                    pool_backup::BackupOutcome::Delta {
+                        path,
+                        first,
+                        last,
+                        missed,
+                    } => {
+                        println!("Wrote seq {first}..{last} of {pool} to {}", path.display());
+                        if missed > 0 {
+                            eprintln!(
+                                "warning: {missed} message(s) were overwritten before this backup; back up more often"
+                            );
+                        }
+                    }
+                    pool_backup::BackupOutcome::UpToDate { newest } => {
+                        println!("Backup of {pool} is up to date (seq {newest})");
+                    }
+                }
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Restore {
+            from,
+            to,
+            dry_run,
+            json,
+        } => {
+            let json = wants_json(json);
+            if to.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("restore accepts local pool refs only"));
+            }
+            let path = resolve_poolref(&to, &pool_dir)?;
+            let outcome = if dry_run {
+                pool_backup::plan_restore(&from, &path)?
+            } else {
+                if let Some(parent) = path.parent() {
+                    ensure_pool_dir(parent)?;
+                }
+                pool_backup::restore(&from, &path)?
+            };
+            if json {
+                emit_json(
+                    json!({
+                        "dry_run": dry_run,
+                        "pool": to,
+                        "path": path.display().to_string(),
+                        "created": outcome.created,
+                        "deltas": outcome.deltas,
+                        "messages": outcome.messages,
+                        "through": outcome.through,
+                    }),
+                    color_mode,
+                );
+            } else {
+                println!(
+                    "{} {to} through seq {} ({} message(s) from {} delta(s))",
+                    match (dry_run, outcome.created) {
+                        (false, true) => "Restored",
+                        (false, false) => "Caught up",
+                        (true, true) => "Would restore",
+                        (true, false) => "Would catch up",
+                    },
+                    outcome.through,
+                    outcome.messages,
+                    outcome.deltas
+                );
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Schema {
+            pool,
+            sample,
+            all,
+            json,
+        } => {
+            let json = wants_json(json);
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("schema accepts local pool refs only")
+                    .with_hint(
+                        "Use a local pool name/path (for example `plasmite schema events`).",
+                    ));
+            }
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let pool_handle =
+                Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            let bounds = pool_handle.bounds()?;
+            let mut inference = schema_infer::SchemaInference::new();
+            if let (Some(oldest), Some(newest)) = (bounds.oldest_seq, bounds.newest_seq) {
+                let start = if all || sample == 0 {
+                    oldest
+                } else {
+                    newest.saturating_sub(sample - 1).max(oldest)
+                };
+                for frame in pool_handle.get_range(start, newest)? {
+                    let Some(message) = pool_message(&pool_handle, &frame)? else {
+                        continue;
+                    };
+                    inference.observe(message.get("data").unwrap_or(&Value::Null));
+                }
+            }
+            if json {
+                let mut value = inference.to_json();
+                if let Some(object) = value.as_object_mut() {
+                    object.insert("pool".to_string(), json!(pool));
+                    object.insert("path".to_string(), json!(path.display().to_string()));
+                }
+                emit_json(value, color_mode);
+            } else {
+                emit_schema_human(&pool, &inference);
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Sql {
+            query,
+            pools,
+            since,
+            until,
+            json,
+        } => {
+            let json = wants_json(json);
+            sql_query::ensure_available()?;
+            let now = now_ns()?;
+            let since_ns = since
+                .as_deref()
+                .map(|value| parse_since(value, now))
+                .transpose()?;
+            let until_ns = until
+                .as_deref()
+                .map(|value| parse_since(value, now))
+                .transpose()?;
+            let mut tables: Vec<sql_query::SqlTable> = Vec::new();
+            for pool in &pools {
+                let name = sql_query::table_name(pool);
+                if tables.iter().any(|table| table.name == name) {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message(format!("two pools map to table '{name}'"))
+                        .with_hint("Pass each pool once; pools with the same file stem collide."));
+                }
+                let path = resolve_poolref(pool, &pool_dir)?;
+                let pool_handle = Pool::open_read_only(&path)
+                    .map_err(|err| add_missing_pool_hint(err, pool, pool))?;
+                let bounds = pool_handle.bounds()?;
+                let mut rows = Vec::new();
+                if let (Some(oldest), Some(newest)) = (bounds.oldest_seq, bounds.newest_seq) {
+                    for frame in pool_handle.get_range(oldest, newest)? {
+                        if since_ns.is_some_and(|since| frame.timestamp_ns < since)
+                            || until_ns.is_some_and(|until| frame.timestamp_ns > until)
+                        {
+                            continue;
+                        }
+                        let Some(message) = pool_message(&pool_handle, &frame)? else {
+                            continue;
+                        };
+                        rows.push(pool_export::ExportRow {
+                            seq: frame.seq,
+                            timestamp_ns: frame.timestamp_ns,
+                            message,
+                        });
+                    }
+                }
+                tables.push(sql_query::SqlTable { name, rows });
+            }
+            let result = sql_query::run_query(&tables, &query)?;
+            if json {
+                emit_json(
+                    json!({ "columns": result.columns, "rows": result.rows }),
+                    color_mode,
+                );
+            } else {
+                let headers = result
+                    .columns
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                let rows = result
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|value| match value {
+                                Value::Null => String::new(),
+                                Value::String(text) => text.clone(),
+                                other => other.to_string(),
+                            })
+                            .collect()
+                    })
+                    .collect::<Vec<_>>();
+                emit_table(&headers, &rows);
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Explain {
+            pool,
+            seq,
+            offset,
+            json,
+        } => {
+            let json = wants_json(json);
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("explain accepts local pool refs only")
+                    .with_hint("Run explain on the host that owns the pool file."));
+            }
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let pool_handle = Pool::open_read_only(&path)
+                .map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            let target = match (seq, offset) {
+                (Some(seq), _) => ExplainTarget::Seq(seq),
+                (None, Some(offset)) => ExplainTarget::Offset(offset),
+                (None, None) => {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("explain needs --seq or --offset"));
+                }
+            };
+            let explain =
+                explain_frame(&pool_handle, target).map_err(|err| err.with_path(&path))?;
+            if json {
+                let mut value = explain.to_json();
+                value["pool"] = json!(pool);
+                value["path"] = json!(path.display().to_string());
+                emit_json(value, color_mode);
+            } else {
+                emit_explain_human(&pool, &explain);
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::ReplayTranscript { file, speed, jsonl } => {
+            if speed < 0.0 || !speed.is_finite() {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--speed must be a non-negative finite number")
+                    .with_hint(
+                        "Use --speed 1 for realtime, --speed 2 for 2x, --speed 0 for no delay.",
+                    ));
+            }
+            let jsonl = jsonl || output_mode() == OutputMode::Jsonl;
+            replay_transcript(&file, speed, jsonl, color_mode)
+        }
+        Command::AuditGaps { pool, since, json } => {
+            let json = wants_json(json);
+            let since_ns = since
+                .as_deref()
+                .map(|value| parse_since(value, now_ns()?))
+                .transpose()?;
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let pool_handle =
+                Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            let header = pool_handle.header_from_mmap()?;
+            let mut audit = seq_audit::SeqAudit::new();
+            let mut window_start = if since_ns.is_some() { None } else { Some(1) };
+            let mut cursor = Cursor::new();
+            cursor.seek_to(header.tail_off as usize);
+            loop {
+                match cursor.next(&pool_handle)? {
+                    CursorResult::Message(frame) => {
+                        if frame.seq > header.newest_seq {
+                            break;
+                        }
+                        if window_start.is_none() {
+                            if since_ns.is_some_and(|since_ns| frame.timestamp_ns < since_ns) {
+                                continue;
+                            }
+                            window_start = Some(frame.seq);
+                        }
+                        audit.observe(frame.seq);
+                    }
+                    CursorResult::WouldBlock => break,
+                    CursorResult::FellBehind => {
+                        return Err(Error::new(ErrorKind::Busy)
+                            .with_message("pool wrapped while auditing")
+                            .with_path(&path)
+                            .with_hint("Writers overwrote frames mid-scan; re-run when writes are quieter."));
+                    }
+                }
+            }
+            let window_start = window_start.unwrap_or_else(|| header.newest_seq.saturating_add(1));
+            let report = audit.finish(window_start, header.oldest_seq, header.newest_seq);
+
+            if json {
+                let mut value = report.to_json();
+                if let Some(object) = value.as_object_mut() {
+                    object.insert("pool".to_string(), json!(pool));
+                    object.insert("path".to_string(), json!(path.display().to_string()));
+                    object.insert(
+                        "oldest_seq".to_string(),
+                        json!((header.oldest_seq != 0).then_some(header.oldest_seq)),
+                    );
+                    object.insert(
+                        "newest_seq".to_string(),
+                        json!((header.oldest_seq != 0).then_some(header.newest_seq)),
+                    );
+                }
+                emit_json(value, color_mode);
+            } else {
+                emit_audit_gaps_human(&pool, &report);
+            }
+            let exit_code = if report.has_corruption() {
+                to_exit_code(ErrorKind::Corrupt)
+            } else {
+                0
+            };
+            Ok(RunOutcome::with_code(exit_code))
+        }
+        Command::Activity {
+            pool,
+            bucket,
+            since,
+            json,
+        } => {
+            let json = wants_json(json);
+            let bucket_ns = u64::try_from(parse_duration(&bucket)?.as_nanos())
+                .ok()
+                .filter(|bucket_ns| *bucket_ns > 0)
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::Usage)
+                        .with_message(format!("invalid --bucket '{bucket}'"))
+                        .with_hint("Use a positive duration like 10s, 5m, or 1h.")
+                })?;
+            let now = now_ns()?;
+            let since_ns = since
+                .as_deref()
+                .map(|value| parse_since(value, now))
+                .transpose()?;
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let pool_handle =
+                Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            let histogram = bucket_activity(&pool_handle, since_ns, now, bucket_ns)?;
+            if json {
+                let buckets = histogram
+                    .counts
+                    .iter()
+                    .enumerate()
+                    .map(|(index, count)| {
+                        let start_ns = histogram.bucket_start_ns(index);
+                        Ok(json!({
+                            "start": format_ts(start_ns)?,
+                            "start_ns": start_ns,
+                            "count": count,
+                        }))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                emit_json(
+                    json!({
+                        "pool": pool,
+                        "path": path.display().to_string(),
+                        "bucket_ns": histogram.bucket_ns,
+                        "start_ns": histogram.start_ns,
+                        "end_ns": now,
+                        "total": histogram.total(),
+                        "peak": histogram.peak(),
+                        "first_seq": histogram.first_seq,
+                        "last_seq": histogram.last_seq,
+                        "skipped": histogram.skipped,
+                        "buckets": buckets,
+                    }),
+                    color_mode,
+                );
+            } else {
+                emit_activity_human(&pool, &bucket, &histogram)?;
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Serve {
+            subcommand,
+            mut run,
+        } => match subcommand {
             Some(ServeSubcommand::Init(args)) => {
                 let bind: SocketAddr = args.bind.parse().map_err(|_| {
                     Error::new(ErrorKind::Usage)
@@ -84,7 +592,7 @@ pub(super) fn dispatch_command(
                     force: args.force,
                 };
                 let result = serve_init::init(config)?;
-                if io::stdout().is_terminal() {
+                if stdout_is_terminal() {
                     emit_serve_init_human(&result);
                 } else {
                     emit_json(
@@ -106,13 +614,24 @@ pub(super) fn dispatch_command(
                 }
                 Ok(RunOutcome::ok())
             }
-            Some(ServeSubcommand::Check { json }) => {
+            Some(ServeSubcommand::Check {
+                json,
+                from_env,
+                samples,
+            }) => {
+                if run.from_env || from_env {
+                    apply_serve_env(&mut run, |name| std::env::var(name).ok())?;
+                }
                 let mut config = serve_config_from_run_args(run, &pool_dir)?;
                 config.cors_allowed_origins = serve::preflight_config(&config)?;
-                emit_serve_check_report(&config, color_mode, json);
+                let samples = serve_check_samples(&config, &samples)?;
+                emit_serve_check_report(&config, &samples, color_mode, wants_json(json));
                 Ok(RunOutcome::ok())
             }
             None => {
+                if run.from_env {
+                    apply_serve_env(&mut run, |name| std::env::var(name).ok())?;
+                }
                 let config = serve_config_from_run_args(run, &pool_dir)?;
                 emit_serve_startup_guidance(&config);
                 let runtime = tokio::runtime::Builder::new_multi_thread()
@@ -137,14 +656,46 @@ pub(super) fn dispatch_command(
                 names,
                 size,
                 index_capacity,
+                hybrid_timestamps,
+                preallocate,
+                intent_journal,
+                dedupe_window,
+                max_messages,
+                max_frame,
+                max_message,
+                bloom_filter,
+                template,
                 json,
             } => {
+                let json = wants_json(json);
                 let client = LocalClient::new().with_pool_dir(&pool_dir);
+                let template = template
+                    .as_deref()
+                    .map(pool_template::resolve_template)
+                    .transpose()?
+                    .unwrap_or_default();
                 let size = size
                     .as_deref()
                     .map(parse_size)
                     .transpose()?
+                    .or(template.size)
                     .unwrap_or(DEFAULT_POOL_SIZE);
+                let index_capacity = index_capacity.or(template.index_capacity);
+                let max_messages = max_messages.or(template.max_messages);
+                let max_frame = max_frame
+                    .as_deref()
+                    .map(|value| parse_frame_limit("--max-frame", value))
+                    .transpose()?;
+                let max_message = max_message
+                    .as_deref()
+                    .map(|value| parse_frame_limit("--max-message", value))
+                    .transpose()?;
+                let hybrid_timestamps = hybrid_timestamps || template.hybrid_timestamps;
+                let dedupe_window = if dedupe_window == 0 {
+                    template.dedupe_window.unwrap_or(0)
+                } else {
+                    dedupe_window
+                };
                 ensure_pool_dir(&pool_dir)?;
                 let mut results = Vec::new();
                 for name in names {
@@ -157,7 +708,22 @@ pub(super) fn dispatch_command(
                                 "Choose a different name or remove the existing pool file.",
                             ));
                     }
-                    let mut options = PoolOptions::new(size);
+                    ensure_pool_parent(&pool_dir, &path)?;
+                    let mut options = PoolOptions::new(size)
+                        .with_hybrid_timestamps(hybrid_timestamps)
+                        .with_preallocate(preallocate)
+                        .with_intent_journal(intent_journal)
+                        .with_dedupe_window(dedupe_window)
+                        .with_bloom_filter(bloom_filter);
+                    if let Some(max_messages) = max_messages {
+                        options = options.with_max_messages(max_messages);
+                    }
+                    if let Some(max_frame) = max_frame {
+                        options = options.with_max_frame_bytes(max_frame);
+                    }
+                    if let Some(max_message) = max_message {
+                        options = options.with_max_message_bytes(max_message);
+                    }
                     if let Some(index_capacity) = index_capacity {
                         let index_size_bytes = index_capacity as u64 * 16;
                         if index_size_bytes > size / 2 {
@@ -171,7 +737,12 @@ pub(super) fn dispatch_command(
                     }
                     let pool_ref = PoolRef::path(path.clone());
                     let info = client.create_pool(&pool_ref, options)?;
-                    results.push(pool_info_json(&name, &info));
+                    let mut result = pool_info_json(&name, &info);
+                    if !template.name.is_empty() {
+                        template.defaults().store(&path)?;
+                        result["template"] = json!(template.name);
+                    }
+                    results.push(result);
                 }
                 if json {
                     emit_json(json!({ "created": results }), color_mode);
@@ -180,10 +751,113 @@ pub(super) fn dispatch_command(
                 }
                 Ok(RunOutcome::ok())
             }
+            PoolCommand::Describe { name, format } => {
+                let client = LocalClient::new().with_pool_dir(&pool_dir);
+                let path = resolve_poolref(&name, &pool_dir)?;
+                let info = client
+                    .pool_info(&PoolRef::path(path.clone()))
+                    .map_err(|err| add_missing_pool_hint(err, &name, &name))?;
+                let defaults = TemplateDefaults::load(&path)?;
+                let mut manifest = pool_manifest::PoolManifest::default();
+                manifest.pools.insert(
+                    name,
+                    pool_manifest::PoolSpec::describe(&info, defaults.as_ref()),
+                );
+                match format {
+                    pool_manifest::DescribeFormat::Toml => print!("{}", manifest.render()?),
+                    pool_manifest::DescribeFormat::Json => {
+                        let value = serde_json::to_value(&manifest).map_err(|err| {
+                            Error::new(ErrorKind::Internal)
+                                .with_message("failed to encode pool manifest")
+                                .with_source(err)
+                        })?;
+                        emit_json(value, color_mode);
+                    }
+                }
+                Ok(RunOutcome::ok())
+            }
+            PoolCommand::Apply {
+                file,
+                dry_run,
+                json,
+            } => {
+                let json = wants_json(json);
+                let manifest = pool_manifest::PoolManifest::load(&file)?;
+                let client = LocalClient::new().with_pool_dir(&pool_dir);
+                // Validate every table before touching any pool.
+                let mut targets = Vec::new();
+                for (name, spec) in &manifest.pools {
+                    let options = spec.options(DEFAULT_POOL_SIZE).map_err(|err| {
+                        let message = err.message().unwrap_or("invalid pool spec").to_string();
+                        err.with_message(format!("pools.{name}: {message}"))
+                            .with_path(&file)
+                    })?;
+                    targets.push((name, spec, resolve_poolref(name, &pool_dir)?, options));
+                }
+                let mut results = Vec::new();
+                let mut drifted = false;
+                for (name, spec, path, options) in targets {
+                    if !path.exists() {
+                        if !dry_run {
+                            ensure_pool_dir(&pool_dir)?;
+                            ensure_pool_parent(&pool_dir, &path)?;
+                            client.create_pool(&PoolRef::path(path.clone()), options)?;
+                            if let Some(defaults) = spec.new_defaults(name) {
+                                defaults.store(&path)?;
+                            }
+                        }
+                        results.push(json!({
+                            "name": name,
+                            "action": "created",
+                            "changes": [],
+                            "drift": [],
+                        }));
+                        continue;
+                    }
+                    let info = client.pool_info(&PoolRef::path(path.clone()))?;
+                    let defaults = TemplateDefaults::load(&path)?;
+                    let plan = spec.plan(name, &info, defaults.as_ref())?;
+                    if !dry_run {
+                        if let Some(defaults) = &plan.defaults {
+                            defaults.store(&path)?;
+                        }
+                    }
+                    drifted |= !plan.drift.is_empty();
+                    let action = if plan.changes.is_empty() {
+                        "unchanged"
+                    } else {
+                        "updated"
+                    };
+                    let drift = plan
+                        .drift
+                        .iter()
+                        .map(|drift| {
+                            json!({
+                                "field": drift.field,
+                                "desired": drift.desired,
+                                "actual": drift.actual,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    results.push(json!({
+                        "name": name,
+                        "action": action,
+                        "changes": plan.changes,
+                        "drift": drift,
+                    }));
+                }
+                if json {
+                    emit_json(json!({ "dry_run": dry_run, "pools": results }), color_mode);
+                } else {
+                    emit_pool_apply_table(&results, dry_run);
+                }
+                Ok(RunOutcome::with_code(if drifted { 1 } else { 0 }))
+            }
             PoolCommand::Info { name, json } => {
+                let json = wants_json(json);
                 let client = LocalClient::new().with_pool_dir(&pool_dir);
                 let path = resolve_poolref(&name, &pool_dir)?;
-                let pool_ref = PoolRef::path(path);
+                let pool_ref = PoolRef::path(path.clone());
                 let info = client.pool_info(&pool_ref).map_err(|err| {
                     if err.kind() == ErrorKind::NotFound {
                         let base = Error::new(ErrorKind::NotFound).with_message("not found");
@@ -192,14 +866,48 @@ pub(super) fn dispatch_command(
                         err
                     }
                 })?;
+                let pins = pool_pins::PinSet::load(&path)?;
                 if json {
-                    emit_json(pool_info_json(&name, &info), color_mode);
+                    let mut value = pool_info_json(&name, &info);
+                    if !pins.pins().is_empty() {
+                        value["pins"] = pins
+                            .pins()
+                            .iter()
+                            .map(|pin| {
+                                json!({
+                                    "seq": pin.seq,
+                                    "pinned_at": pin.pinned_at,
+                                    "note": pin.note,
+                                })
+                            })
+                            .collect();
+                    }
+                    emit_json(value, color_mode);
                 } else {
                     emit_pool_info_pretty(&name, &info);
+                    if !pins.pins().is_empty() {
+                        let seqs = pins
+                            .pins()
+                            .iter()
+                            .map(|pin| pin.seq.to_string())
+                            .collect::<Vec<_>>();
+                        println!("Pins: {} (seqs {})", seqs.len(), seqs.join(", "));
+                    }
                 }
                 Ok(RunOutcome::ok())
             }
-            PoolCommand::Delete { names, json } => {
+            PoolCommand::Delete {
+                names,
+                force,
+                trash,
+                dry_run,
+                json,
+            } => {
+                if dry_run {
+                    let options = DeleteOptions { force, trash };
+                    return preview_pool_delete(&pool_dir, &names, options, json, color_mode);
+                }
+                let json = wants_json(json);
                 let client = LocalClient::new().with_pool_dir(&pool_dir);
                 let mut deleted = Vec::new();
                 let mut failed = Vec::new();
@@ -219,33 +927,39 @@ pub(super) fn dispatch_command(
                     } else {
                         resolve_poolref(&name, &pool_dir).and_then(|path| {
                             let pool_ref = PoolRef::path(path.clone());
-                            client.delete_pool(&pool_ref).map_err(|err| {
-                                if err.kind() == ErrorKind::NotFound {
-                                    Error::new(ErrorKind::NotFound)
-                                        .with_message("pool not found")
-                                        .with_path(&path)
-                                        .with_hint("Create the pool first or check --dir.")
-                                } else if err.kind() == ErrorKind::Permission {
-                                    // Keep historical CLI delete semantics: permission failures
-                                    // are surfaced as I/O for stable exit-code behavior.
-                                    Error::new(ErrorKind::Io)
-                                        .with_message("failed to delete pool")
-                                        .with_path(&path)
-                                } else {
-                                    err
-                                }
-                            })?;
-                            Ok(path)
+                            let options = DeleteOptions { force, trash };
+                            let trash_path =
+                                client.delete_pool_with(&pool_ref, options).map_err(|err| {
+                                    if err.kind() == ErrorKind::NotFound {
+                                        Error::new(ErrorKind::NotFound)
+                                            .with_message("pool not found")
+                                            .with_path(&path)
+                                            .with_hint("Create the pool first or check --dir.")
+                                    } else if err.kind() == ErrorKind::Permission {
+                                        // Keep historical CLI delete semantics: permission failures
+                                        // are surfaced as I/O for stable exit-code behavior.
+                                        Error::new(ErrorKind::Io)
+                                            .with_message("failed to delete pool")
+                                            .with_path(&path)
+                                    } else {
+                                        err
+                                    }
+                                })?;
+                            Ok((path, trash_path))
                         })
                     };
 
                     match result {
-                        Ok(path) => {
+                        Ok((path, trash_path)) => {
                             let display_path = short_display_path(path.as_path(), Some(&pool_dir));
-                            deleted.push(json!({
+                            let mut entry = json!({
                                 "pool": name,
                                 "path": path.display().to_string(),
-                            }));
+                            });
+                            if let Some(trash_path) = trash_path {
+                                entry["trash"] = json!(trash_path.display().to_string());
+                            }
+                            deleted.push(entry);
                             table_rows.push(vec![
                                 name.clone(),
                                 "OK".to_string(),
@@ -265,7 +979,7 @@ pub(super) fn dispatch_command(
                             let detail = err.message().unwrap_or("error").to_string();
                             failed.push(json!({
                                 "pool": name.clone(),
-                                "error": error_json(&err)["error"].clone(),
+                                "error": err.to_json()["error"].clone(),
                             }));
                             table_rows.push(vec![
                                 name.clone(),
@@ -292,40 +1006,48 @@ pub(super) fn dispatch_command(
                         }),
                         color_mode,
                     );
-                } else if io::stdout().is_terminal() {
+                } else if stdout_is_terminal() {
                     let total = human_rows.len();
                     let deleted_count = deleted.len();
                     if total == 1 {
                         if let Some((name, status)) = human_rows.first() {
                             match status {
+                                HumanDeleteStatus::Ok if trash => {
+                                    println!("{}", tr(Msg::TrashedPool, &[("name", name)]));
+                                }
                                 HumanDeleteStatus::Ok => {
-                                    println!("Deleted pool \"{name}\".");
+                                    println!("{}", tr(Msg::DeletedPool, &[("name", name)]));
                                 }
                                 HumanDeleteStatus::Err {
                                     kind: ErrorKind::NotFound,
                                     ..
                                 } => {
-                                    println!("Pool \"{name}\" not found. Nothing to delete.");
+                                    println!(
+                                        "{}",
+                                        tr(Msg::PoolNotFoundNothingToDelete, &[("name", name)])
+                                    );
                                     println!();
                                     println!(
-                                        "  Pool directory: {}",
+                                        "  {} {}",
+                                        tr(Msg::PoolDirectoryLabel, &[]),
                                         display_pool_dir_for_humans(&pool_dir)
                                     );
-                                    println!("  List pools:     pls pool list");
+                                    println!("  {} pls pool list", tr(Msg::ListPoolsLabel, &[]));
                                 }
                                 HumanDeleteStatus::Err { detail, .. } => {
-                                    println!("Failed to delete pool \"{name}\".");
+                                    println!("{}", tr(Msg::FailedToDeletePool, &[("name", name)]));
                                     println!();
-                                    println!("  Reason:         {detail}");
+                                    println!("  {} {detail}", tr(Msg::ReasonLabel, &[]));
                                     println!(
-                                        "  Pool directory: {}",
+                                        "  {} {}",
+                                        tr(Msg::PoolDirectoryLabel, &[]),
                                         display_pool_dir_for_humans(&pool_dir)
                                     );
                                 }
                             }
                         }
                     } else if failed.is_empty() {
-                        println!("Deleted {deleted_count} pools.");
+                        println!("{}", tr(Msg::DeletedPools, &[("count", &deleted_count)]));
                         println!();
                         for (name, status) in &human_rows {
                             if matches!(status, HumanDeleteStatus::Ok) {
@@ -334,11 +1056,18 @@ pub(super) fn dispatch_command(
                         }
                         println!();
                         println!(
-                            "  Pool directory: {}",
+                            "  {} {}",
+                            tr(Msg::PoolDirectoryLabel, &[]),
                             display_pool_dir_for_humans(&pool_dir)
                         );
                     } else {
-                        println!("Deleted {deleted_count} of {total} pools.");
+                        println!(
+                            "{}",
+                            tr(
+                                Msg::DeletedSomePools,
+                                &[("count", &deleted_count), ("total", &total)]
+                            )
+                        );
                         println!();
                         for (name, status) in &human_rows {
                             match status {
@@ -350,7 +1079,8 @@ pub(super) fn dispatch_command(
                         }
                         println!();
                         println!(
-                            "  Pool directory: {}",
+                            "  {} {}",
+                            tr(Msg::PoolDirectoryLabel, &[]),
                             display_pool_dir_for_humans(&pool_dir)
                         );
                     }
@@ -363,13 +1093,637 @@ pub(super) fn dispatch_command(
                     Ok(RunOutcome::ok())
                 }
             }
-            PoolCommand::List { json } => {
+            PoolCommand::Restore { name, json } => {
+                let json = wants_json(json);
+                if name.contains("://") {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("pool restore accepts local pool names or paths only"));
+                }
+                let path = resolve_poolref(&name, &pool_dir)?;
                 let client = LocalClient::new().with_pool_dir(&pool_dir);
-                let pools = list_pools(&pool_dir, &client);
+                let restored = client.restore_pool(&PoolRef::path(path))?;
                 if json {
-                    emit_json(json!({ "pools": pools }), color_mode);
-                } else {
-                    emit_pool_list_table(&pools, &pool_dir);
+                    emit_json(
+                        json!({
+                            "restored": {
+                                "pool": name,
+                                "path": restored.display().to_string(),
+                            },
+                        }),
+                        color_mode,
+                    );
+                } else {
+                    println!("{}", tr(Msg::RestoredPool, &[("name", &name)]));
+                }
+                Ok(RunOutcome::ok())
+            }
+            PoolCommand::Gc {
+                older_than,
+                empty_only,
+                dry_run,
+                yes,
+                json,
+            } => {
+                let json = wants_json(json);
+                let now = now_ns()?;
+                let cutoff_ns = parse_relative_since(&older_than)
+                    .map(|age| now.saturating_sub(age))
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::Usage)
+                            .with_message("invalid --older-than value")
+                            .with_hint("Use a duration like 30d, 12h, or 90m.")
+                    })?;
+                let paths = match list_pool_paths(&pool_dir) {
+                    Ok(paths) => paths,
+                    Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+                    Err(err) => return Err(err),
+                };
+                let scan = pool_gc::scan_pools(&pool_dir, paths, cutoff_ns, empty_only);
+                let interactive = !json && stdout_is_terminal() && io::stdin().is_terminal();
+                if !dry_run && !yes && !interactive && !scan.candidates.is_empty() {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("pool gc needs confirmation to delete pools")
+                        .with_hint(
+                            "Pass --yes to delete without prompting, or --dry-run to preview.",
+                        ));
+                }
+
+                let last_activity = |candidate: &pool_gc::GcCandidate| {
+                    candidate
+                        .last_activity_ns
+                        .map(|ts| format_ts(ts).unwrap_or_else(|_| ts.to_string()))
+                };
+                if !json {
+                    if scan.candidates.is_empty() {
+                        println!("{}", tr(Msg::GcNothingToCollect, &[]));
+                    } else {
+                        let rows = scan
+                            .candidates
+                            .iter()
+                            .map(|candidate| {
+                                let age_ms = candidate
+                                    .last_activity_ns
+                                    .map(|ts| now.saturating_sub(ts) / 1_000_000);
+                                vec![
+                                    candidate.name.clone(),
+                                    candidate.reason.as_str().to_string(),
+                                    format_relative_time(age_ms),
+                                    format_bytes(candidate.file_size),
+                                    candidate.seqs.map_or_else(
+                                        || "-".to_string(),
+                                        |(oldest, newest)| format!("{oldest}-{newest}"),
+                                    ),
+                                    short_display_path(&candidate.path, Some(&pool_dir)),
+                                ]
+                            })
+                            .collect::<Vec<_>>();
+                        emit_table(
+                            &["NAME", "REASON", "LAST ACTIVITY", "SIZE", "SEQS", "PATH"],
+                            &rows,
+                        );
+                    }
+                    for skipped in &scan.skipped {
+                        eprintln!(
+                            "  ! {} — {}",
+                            skipped.name,
+                            skipped.error.message().unwrap_or("error")
+                        );
+                    }
+                }
+
+                let confirmed = if dry_run || scan.candidates.is_empty() {
+                    false
+                } else if yes {
+                    true
+                } else {
+                    use std::io::Write as _;
+                    println!();
+                    print!(
+                        "{}",
+                        tr(Msg::GcConfirm, &[("count", &scan.candidates.len())])
+                    );
+                    io::stdout().flush().ok();
+                    let mut answer = String::new();
+                    io::stdin().read_line(&mut answer).map_err(|err| {
+                        Error::new(ErrorKind::Io)
+                            .with_message("failed to read confirmation")
+                            .with_source(err)
+                    })?;
+                    matches!(
+                        answer.trim().to_lowercase().as_str(),
+                        "y" | "yes" | "s" | "si" | "sí"
+                    )
+                };
+
+                let client = LocalClient::new().with_pool_dir(&pool_dir);
+                let mut deleted = Vec::new();
+                let mut failed = Vec::new();
+                let mut first_error_kind = None;
+                if confirmed {
+                    for candidate in &scan.candidates {
+                        let options = DeleteOptions {
+                            force: false,
+                            trash: false,
+                        };
+                        match client
+                            .delete_pool_with(&PoolRef::path(candidate.path.clone()), options)
+                        {
+                            Ok(_) => deleted.push(json!({
+                                "pool": candidate.name,
+                                "path": candidate.path.display().to_string(),
+                            })),
+                            Err(err) => {
+                                first_error_kind.get_or_insert(err.kind());
+                                if !json {
+                                    println!(
+                                        "  ✗ {} — {}",
+                                        candidate.name,
+                                        err.message().unwrap_or("error")
+                                    );
+                                }
+                                failed.push(json!({
+                                    "pool": candidate.name,
+                                    "error": err.to_json()["error"].clone(),
+                                }));
+                            }
+                        }
+                    }
+                }
+
+                if json {
+                    emit_json(
+                        json!({
+                            "pool_dir": pool_dir.display().to_string(),
+                            "older_than": older_than,
+                            "empty_only": empty_only,
+                            "dry_run": dry_run,
+                            "candidates": scan
+                                .candidates
+                                .iter()
+                                .map(|candidate| candidate.to_json(last_activity(candidate)))
+                                .collect::<Vec<_>>(),
+                            "deleted": deleted,
+                            "failed": failed,
+                            "skipped": scan
+                                .skipped
+                                .iter()
+                                .map(|skipped| json!({
+                                    "pool": skipped.name,
+                                    "path": skipped.path.display().to_string(),
+                                    "error": skipped.error.to_json()["error"].clone(),
+                                }))
+                                .collect::<Vec<_>>(),
+                        }),
+                        color_mode,
+                    );
+                } else if dry_run && !scan.candidates.is_empty() {
+                    println!();
+                    println!(
+                        "{}",
+                        tr(Msg::GcDryRun, &[("count", &scan.candidates.len())])
+                    );
+                } else if confirmed {
+                    println!();
+                    println!(
+                        "{}",
+                        tr(
+                            Msg::GcCollected,
+                            &[("count", &deleted.len()), ("total", &scan.candidates.len())]
+                        )
+                    );
+                }
+                if let Some(kind) = first_error_kind {
+                    Ok(RunOutcome::with_code(to_exit_code(kind)))
+                } else {
+                    Ok(RunOutcome::ok())
+                }
+            }
+            PoolCommand::Snapshot { name, out, json } => {
+                let json = wants_json(json);
+                if name.contains("://") {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("pool snapshot accepts local pool names or paths only"));
+                }
+                let path = resolve_poolref(&name, &pool_dir)?;
+                let client = LocalClient::new().with_pool_dir(&pool_dir);
+                client
+                    .snapshot_pool(&PoolRef::path(path), &out)
+                    .map_err(|err| add_missing_pool_hint(err, &name, &name))?;
+                let bounds = Pool::open_read_only(&out)?.bounds()?;
+                if json {
+                    emit_json(
+                        json!({
+                            "snapshot": {
+                                "pool": name,
+                                "path": out.display().to_string(),
+                                "bounds": bounds_json(bounds),
+                            },
+                        }),
+                        color_mode,
+                    );
+                } else {
+                    println!(
+                        "{}",
+                        tr(
+                            Msg::SnapshotPool,
+                            &[("name", &name), ("path", &out.display())]
+                        )
+                    );
+                }
+                Ok(RunOutcome::ok())
+            }
+            PoolCommand::Freeze { name, json } => {
+                set_pool_frozen(&pool_dir, &name, true, json, color_mode)
+            }
+            PoolCommand::Unfreeze { name, json } => {
+                set_pool_frozen(&pool_dir, &name, false, json, color_mode)
+            }
+            PoolCommand::Seal { name, out, json } => {
+                let json = wants_json(json);
+                let (path, pool) = open_local_pool_read_only("seal", &name, &pool_dir)?;
+                let manifest = pool_seal::seal(&pool, &name, format_ts(now_ns()?)?)
+                    .map_err(|err| err.with_path(&path))?;
+                let Some(out) = out else {
+                    emit_json(json!(manifest), color_mode);
+                    return Ok(RunOutcome::ok());
+                };
+                let text = serde_json::to_string_pretty(&manifest).map_err(|err| {
+                    Error::new(ErrorKind::Internal)
+                        .with_message("failed to encode seal manifest")
+                        .with_source(err)
+                })?;
+                std::fs::write(&out, text + "\n").map_err(|err| {
+                    Error::new(ErrorKind::Io)
+                        .with_message("failed to write seal manifest")
+                        .with_path(&out)
+                        .with_source(err)
+                })?;
+                if json {
+                    emit_json(
+                        json!({
+                            "seal": {
+                                "pool": name,
+                                "path": path.display().to_string(),
+                                "manifest": out.display().to_string(),
+                                "oldest_seq": manifest.oldest_seq,
+                                "newest_seq": manifest.newest_seq,
+                                "root": manifest.root,
+                            },
+                        }),
+                        color_mode,
+                    );
+                } else {
+                    let seqs = format!("{}-{}", manifest.oldest_seq, manifest.newest_seq);
+                    println!(
+                        "{}",
+                        tr(
+                            Msg::SealedPool,
+                            &[("name", &name), ("seqs", &seqs), ("path", &out.display())]
+                        )
+                    );
+                }
+                Ok(RunOutcome::ok())
+            }
+            PoolCommand::Verify {
+                name,
+                manifest,
+                json,
+            } => {
+                let json = wants_json(json);
+                let text = std::fs::read_to_string(&manifest).map_err(|err| {
+                    let kind = if err.kind() == io::ErrorKind::NotFound {
+                        ErrorKind::NotFound
+                    } else {
+                        ErrorKind::Io
+                    };
+                    Error::new(kind)
+                        .with_message("failed to read seal manifest")
+                        .with_path(&manifest)
+                        .with_source(err)
+                })?;
+                let sealed: pool_seal::SealManifest =
+                    serde_json::from_str(&text).map_err(|err| {
+                        Error::new(ErrorKind::Usage)
+                            .with_message(format!("invalid seal manifest: {err}"))
+                            .with_path(&manifest)
+                            .with_hint("Pass a manifest written by `plasmite pool seal`.")
+                    })?;
+                let (_, pool) = open_local_pool_read_only("verify", &name, &pool_dir)?;
+                let verification =
+                    pool_seal::verify(&pool, &sealed).map_err(|err| err.with_path(&manifest))?;
+                if json {
+                    let mut report = verification.to_json();
+                    report["pool"] = json!(name);
+                    report["manifest"] = json!(manifest.display().to_string());
+                    report["oldest_seq"] = json!(sealed.oldest_seq);
+                    report["newest_seq"] = json!(sealed.newest_seq);
+                    emit_json(json!({ "verify": report }), color_mode);
+                } else if verification.intact() {
+                    println!(
+                        "{}",
+                        tr(
+                            Msg::VerifyIntact,
+                            &[
+                                ("name", &name),
+                                ("checked", &verification.checked),
+                                ("evicted", &verification.evicted),
+                            ]
+                        )
+                    );
+                } else {
+                    println!("{}", tr(Msg::VerifyTampered, &[("name", &name)]));
+                    println!();
+                    if !verification.root_ok {
+                        println!("  manifest:   leaves no longer match root {}", sealed.root);
+                    }
+                    if verification.mismatched > 0 {
+                        let seqs = verification
+                            .mismatched_seqs
+                            .iter()
+                            .map(u64::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        println!(
+                            "  changed:    {} frames (seq {seqs})",
+                            verification.mismatched
+                        );
+                    }
+                    if verification.missing > 0 {
+                        println!("  missing:    {} sealed seqs", verification.missing);
+                    }
+                }
+                Ok(if verification.intact() {
+                    RunOutcome::ok()
+                } else {
+                    RunOutcome::with_code(to_exit_code(ErrorKind::Corrupt))
+                })
+            }
+            PoolCommand::Who { name, json } => {
+                let json = wants_json(json);
+                if name.contains("://") {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("pool who accepts local pool names or paths only"));
+                }
+                let path = resolve_poolref(&name, &pool_dir)?;
+                let client = LocalClient::new().with_pool_dir(&pool_dir);
+                let presence = client
+                    .pool_presence(&PoolRef::path(path))
+                    .map_err(|err| add_missing_pool_hint(err, &name, &name))?;
+                let registered = |pid: u32| presence.registrations.iter().any(|r| r.pid == pid);
+                if json {
+                    let handles = presence
+                        .registrations
+                        .iter()
+                        .map(|record| {
+                            let mut entry = json!({
+                                "pid": record.pid,
+                                "role": record.role,
+                                "command": record.command,
+                                "started": format_ts(record.started_ns)?,
+                                "cursor": record.cursor,
+                            });
+                            if let Some(name) = &record.name {
+                                entry["name"] = json!(name);
+                            }
+                            Ok(entry)
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?;
+                    let processes = presence
+                        .users
+                        .iter()
+                        .map(|user| {
+                            json!({
+                                "pid": user.pid,
+                                "command": user.command,
+                                "registered": registered(user.pid),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    emit_json(
+                        json!({
+                            "pool": name,
+                            "path": presence.path.display().to_string(),
+                            "append_lock_held": presence.append_lock_held,
+                            "handles": handles,
+                            "processes": processes,
+                        }),
+                        color_mode,
+                    );
+                } else {
+                    let mut rows = Vec::new();
+                    for record in &presence.registrations {
+                        let role = match record.role {
+                            PoolRole::Reader => "reader",
+                            PoolRole::Writer => "writer",
+                        };
+                        rows.push(vec![
+                            record.pid.to_string(),
+                            role.to_string(),
+                            record.command.clone(),
+                            format_ts(record.started_ns)?,
+                            record
+                                .cursor
+                                .map(|seq| seq.to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                        ]);
+                    }
+                    for user in presence.users.iter().filter(|u| !registered(u.pid)) {
+                        rows.push(vec![
+                            user.pid.to_string(),
+                            "open".to_string(),
+                            user.command.clone(),
+                            "-".to_string(),
+                            "-".to_string(),
+                        ]);
+                    }
+                    if rows.is_empty() {
+                        println!("{}", tr(Msg::NoActivePoolUsers, &[("name", &name)]));
+                    } else {
+                        emit_table(&["PID", "ROLE", "COMMAND", "STARTED", "CURSOR"], &rows);
+                    }
+                    if presence.append_lock_held {
+                        println!("append lock: held");
+                    }
+                }
+                Ok(RunOutcome::ok())
+            }
+            PoolCommand::List { json } => {
+                let json = wants_json(json);
+                let client = LocalClient::new()
+                    .with_pool_dir(&pool_dir)
+                    .with_deadline(op_deadline());
+                let pools = list_pools(&pool_dir, &client)?;
+                if json {
+                    emit_json(json!({ "pools": pools }), color_mode);
+                } else {
+                    emit_pool_list_table(&pools, &pool_dir);
+                }
+                Ok(RunOutcome::ok())
+            }
+            PoolCommand::Export {
+                name,
+                format,
+                out,
+                columns,
+            } => {
+                let columns = columns
+                    .as_deref()
+                    .map(pool_export::parse_columns)
+                    .transpose()?
+                    .unwrap_or_default();
+                if !columns.is_empty() && format != pool_export::ExportFormat::Parquet {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("--columns requires --format parquet")
+                        .with_hint("JSONL exports keep the full envelope; drop --columns."));
+                }
+                let path = resolve_poolref(&name, &pool_dir)?;
+                let deadline = op_deadline();
+                deadline.check()?;
+                let pool_handle = Pool::open_read_only(&path)
+                    .map_err(|err| add_missing_pool_hint(err, &name, &name))?;
+                let bounds = pool_handle.bounds()?;
+                let mut rows = Vec::new();
+                if let (Some(oldest), Some(newest)) = (bounds.oldest_seq, bounds.newest_seq) {
+                    for frame in pool_handle.get_range(oldest, newest)? {
+                        deadline.check()?;
+                        let Some(message) = pool_message(&pool_handle, &frame)? else {
+                            continue;
+                        };
+                        rows.push(pool_export::ExportRow {
+                            seq: frame.seq,
+                            timestamp_ns: frame.timestamp_ns,
+                            message,
+                        });
+                    }
+                }
+                match (format, out) {
+                    (pool_export::ExportFormat::Parquet, Some(out)) => {
+                        pool_export::write_parquet(&rows, &columns, &out)?;
+                    }
+                    (pool_export::ExportFormat::Parquet, None) => {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("--format parquet requires --out")
+                            .with_hint("Add --out <file.parquet>."));
+                    }
+                    (pool_export::ExportFormat::Sqlite, Some(out)) => {
+                        pool_export::write_sqlite(&rows, &out)?;
+                    }
+                    (pool_export::ExportFormat::Sqlite, None) => {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("--format sqlite requires --out")
+                            .with_hint("Add --out <file.db>."));
+                    }
+                    (pool_export::ExportFormat::Jsonl, Some(out)) => {
+                        let file = std::fs::File::create(&out).map_err(|err| {
+                            Error::new(ErrorKind::Io)
+                                .with_message("failed to create output file")
+                                .with_path(&out)
+                                .with_source(err)
+                        })?;
+                        pool_export::write_jsonl(&rows, &mut io::BufWriter::new(file)).map_err(
+                            |err| {
+                                Error::new(ErrorKind::Io)
+                                    .with_message("failed to write export")
+                                    .with_path(&out)
+                                    .with_source(err)
+                            },
+                        )?;
+                    }
+                    (pool_export::ExportFormat::Jsonl, None) => {
+                        let stdout = io::stdout();
+                        let _ = pool_export::write_jsonl(&rows, &mut stdout.lock());
+                    }
+                }
+                Ok(RunOutcome::ok())
+            }
+            PoolCommand::Import {
+                name,
+                from,
+                dry_run,
+                json,
+            } => {
+                let json = wants_json(json);
+                if name.contains("://") {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("pool import accepts local pool names or paths only"));
+                }
+                let path = resolve_poolref(&name, &pool_dir)?;
+                let span = |seqs: Option<(u64, u64)>, seq: u64| {
+                    Some(seqs.map_or((seq, seq), |(first, _)| (first, seq)))
+                };
+                if dry_run {
+                    Pool::open_read_only(&path)
+                        .map_err(|err| add_missing_pool_hint(err, &name, &name))?;
+                    let (mut rows, mut seqs) = (0u64, None);
+                    pool_export::read_sqlite(&from, |row| {
+                        rows += 1;
+                        seqs = span(seqs, row.seq);
+                        Ok(())
+                    })?;
+                    if json {
+                        emit_json(
+                            json!({
+                                "dry_run": true,
+                                "import": {
+                                    "pool": name,
+                                    "from": from.display().to_string(),
+                                    "rows": rows,
+                                    "oldest_seq": seqs.map(|(first, _)| first),
+                                    "newest_seq": seqs.map(|(_, last)| last),
+                                },
+                            }),
+                            color_mode,
+                        );
+                    } else {
+                        let seqs = seqs.map_or_else(
+                            || "-".to_string(),
+                            |(first, last)| format!("{first}-{last}"),
+                        );
+                        println!(
+                            "{}",
+                            tr(
+                                Msg::ImportDryRun,
+                                &[("count", &rows), ("name", &name), ("seqs", &seqs)]
+                            )
+                        );
+                    }
+                    return Ok(RunOutcome::ok());
+                }
+                let mut pool =
+                    Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &name, &name))?;
+                let (mut imported, mut seqs) = (0u64, None);
+                pool_export::read_sqlite(&from, |row| {
+                    let options = AppendOptions::new(row.timestamp_ns, Durability::Fast);
+                    let seq = pool.append_json(&row.data, &row.tags, options)?.seq;
+                    imported += 1;
+                    seqs = span(seqs, seq);
+                    Ok(())
+                })?;
+                if json {
+                    emit_json(
+                        json!({
+                            "import": {
+                                "pool": name,
+                                "from": from.display().to_string(),
+                                "imported": imported,
+                                "first_seq": seqs.map(|(first, _)| first),
+                                "last_seq": seqs.map(|(_, last)| last),
+                            },
+                        }),
+                        color_mode,
+                    );
+                } else {
+                    let seqs = seqs.map_or_else(
+                        || "-".to_string(),
+                        |(first, last)| format!("{first}-{last}"),
+                    );
+                    println!(
+                        "{}",
+                        tr(
+                            Msg::ImportedMessages,
+                            &[("count", &imported), ("name", &name), ("seqs", &seqs)]
+                        )
+                    );
                 }
                 Ok(RunOutcome::ok())
             }
@@ -379,17 +1733,27 @@ pub(super) fn dispatch_command(
             tag,
             data,
             file,
+            edit,
+            from_clipboard,
+            set,
+            set_time,
+            flag,
+            attach,
             durability,
             create,
             create_size,
             retry,
             retry_delay,
             input,
+            csv_header,
             errors,
+            batch,
             token,
             token_file,
             tls_ca,
             tls_skip_verify,
+            tls_client_cert,
+            tls_client_key,
         } => {
             let target = resolve_pool_target(&pool, &pool_dir)?;
             let data_arg = data;
@@ -404,8 +1768,34 @@ pub(super) fn dispatch_command(
                     .with_message("--retry-delay requires --retry")
                     .with_hint("Add --retry or remove --retry-delay."));
             }
+            if batch == Some(0) {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--batch must be greater than zero")
+                    .with_hint("Use --batch 1 to send one record per request."));
+            }
+            if csv_header.is_some() && input != InputMode::Csv {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--csv-header requires --in csv")
+                    .with_hint("Add --in csv or remove --csv-header."));
+            }
+            let field_sets = FieldSets::parse(&set, &set_time)?;
+            let flags = parse_frame_flags(&flag)?;
+            if attach.is_some() && flags & FRAME_FLAG_CONTROL != 0 {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("control messages cannot carry attachments")
+                    .with_hint("Drop --flag control or --attach."));
+            }
+            let ingest_mode = input_mode_to_ingest(input, csv_header.unwrap_or(CsvHeaderCli::Auto));
             let durability = parse_durability(&durability)?;
             let retry_config = parse_retry_config(retry, retry_delay.as_deref())?;
+            // Interactive sources resolve last so usage errors surface before the editor opens.
+            let data_arg = if edit {
+                Some(feed_source::edit_message(&pool)?)
+            } else if from_clipboard {
+                Some(feed_source::read_clipboard()?)
+            } else {
+                data_arg
+            };
             if data_arg.is_some() && file_arg.is_some() {
                 return Err(Error::new(ErrorKind::Usage)
                     .with_message("multiple data inputs provided")
@@ -437,11 +1827,28 @@ pub(super) fn dispatch_command(
                         token_file.as_deref(),
                         tls_ca.as_deref(),
                         tls_skip_verify,
+                        tls_client_cert.as_deref(),
                     )?;
+                    if batch.is_some() {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("--batch requires a remote http(s) pool ref")
+                            .with_hint("Local feed appends each record directly; drop --batch."));
+                    }
+                    // Pools created from a template carry default tags and required fields.
+                    let (tag, field_sets) = match TemplateDefaults::load(&path)? {
+                        Some(defaults) => {
+                            let mut tags = defaults.tags.clone();
+                            tags.extend(tag.into_iter().filter(|t| !defaults.tags.contains(t)));
+                            let field_sets =
+                                field_sets.with_required(&defaults.template, &defaults.required);
+                            (tags, field_sets)
+                        }
+                        None => (tag, field_sets),
+                    };
                     let mut pool_handle = match Pool::open(&path) {
                         Ok(pool) => pool,
                         Err(err) if create && err.kind() == ErrorKind::NotFound => {
-                            ensure_pool_dir(&pool_dir)?;
+                            ensure_pool_parent(&pool_dir, &path)?;
                             let size = create_size
                                 .as_deref()
                                 .map(parse_size)
@@ -460,18 +1867,45 @@ pub(super) fn dispatch_command(
                         }
                     };
                     if let Some(data) = data_arg.as_deref() {
-                        let data = parse_inline_json(data)?;
-                        let payload = lite3::encode_message(&tag, &data)?;
+                        let data = field_sets.apply(parse_inline_json(data)?)?;
+                        check_control_payload(flags, &data)?;
+                        let (data, attachment) = match attach.as_deref() {
+                            Some(path) => {
+                                let (data, bytes) = attach_file(data, path)?;
+                                (data, Some(bytes))
+                            }
+                            None => (data, None),
+                        };
+                        let envelope = lite3::encode_message(&tag, &data)?;
+                        let (payload, stored_flags) = match &attachment {
+                            Some(bytes) => (
+                                pack_attachment(envelope.as_slice(), bytes)?,
+                                flags | FRAME_FLAG_ATTACHMENT,
+                            ),
+                            None => (envelope.as_slice().to_vec(), flags),
+                        };
                         let (seq, timestamp_ns) = retry_with_config(retry_config, || {
                             let timestamp_ns = now_ns()?;
-                            let options = AppendOptions::new(timestamp_ns, durability);
+                            let options = AppendOptions::new(timestamp_ns, durability)
+                                .with_flags(stored_flags);
                             let seq =
                                 pool_handle.append_with_options(payload.as_slice(), options)?;
                             Ok((seq, timestamp_ns))
                         })?;
-                        emit_feed_receipt(feed_receipt_json(seq, timestamp_ns, &tag)?, color_mode);
+                        emit_feed_receipt(
+                            feed_receipt_json(
+                                seq,
+                                timestamp_ns,
+                                &tag,
+                                flags,
+                                control_kind(flags, &data),
+                            )?,
+                            color_mode,
+                        );
                     } else {
                         let pool_path_label = path.display().to_string();
+                        let _presence =
+                            register_presence(&pool, &pool_dir, PoolRole::Writer, "feed");
                         let outcome = if let Some(file) = file {
                             let reader = open_feed_reader(file)?;
                             ingest_from_stdin(
@@ -480,12 +1914,15 @@ pub(super) fn dispatch_command(
                                     pool_ref: &pool,
                                     pool_path_label: &pool_path_label,
                                     tags: &tag,
+                                    sets: &field_sets,
+                                    flags,
                                     durability,
                                     retry_config,
                                     pool_handle: &mut pool_handle,
                                     color_mode,
-                                    input,
+                                    input: ingest_mode,
                                     errors,
+                                    record: None,
                                 },
                                 true,
                             )?
@@ -496,12 +1933,15 @@ pub(super) fn dispatch_command(
                                     pool_ref: &pool,
                                     pool_path_label: &pool_path_label,
                                     tags: &tag,
+                                    sets: &field_sets,
+                                    flags,
                                     durability,
                                     retry_config,
                                     pool_handle: &mut pool_handle,
                                     color_mode,
-                                    input,
+                                    input: ingest_mode,
                                     errors,
+                                    record: None,
                                 },
                                 true,
                             )?
@@ -539,17 +1979,42 @@ pub(super) fn dispatch_command(
                         );
                         client = client.with_tls_skip_verify();
                     }
+                    if let (Some(cert), Some(key)) = (&tls_client_cert, &tls_client_key) {
+                        client = client.with_tls_client_cert(cert, key)?;
+                    }
                     let remote_pool = client
                         .open_pool(&PoolRef::name(name.clone()))
                         .map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
                     if let Some(data) = data_arg.as_deref() {
-                        let data = parse_inline_json(data)?;
-                        let message = retry_with_config(retry_config, || {
-                            remote_pool.append_json_now(&data, &tag, durability)
-                        })?;
+                        let data = field_sets.apply(parse_inline_json(data)?)?;
+                        let options = AppendOptions::new(0, durability).with_flags(flags);
+                        let message = match attach.as_deref() {
+                            Some(path) => {
+                                let (data, bytes) = attach_file(data, path)?;
+                                retry_with_config(retry_config, || {
+                                    remote_pool
+                                        .append_json_with_attachment(&data, &tag, &bytes, options)
+                                })?
+                            }
+                            None => retry_with_config(retry_config, || {
+                                remote_pool.append_json(&data, &tag, options)
+                            })?,
+                        };
                         emit_feed_receipt(feed_receipt_from_message(&message), color_mode);
                     } else {
                         let pool_path_label = format!("{}/{}", client.base_url(), name);
+                        let batch_size = batch.unwrap_or(if file.is_some() && flags == 0 {
+                            DEFAULT_REMOTE_FEED_BATCH
+                        } else {
+                            1
+                        });
+                        if batch_size > 1 && flags != 0 {
+                            return Err(Error::new(ErrorKind::Usage)
+                                .with_message("--flag is not supported with remote --batch")
+                                .with_hint(
+                                    "Use --batch 1 so each record is appended with its flags.",
+                                ));
+                        }
                         let outcome = if let Some(file) = file {
                             let reader = open_feed_reader(file)?;
                             ingest_from_stdin_remote(
@@ -558,12 +2023,16 @@ pub(super) fn dispatch_command(
                                     pool_ref: &pool,
                                     pool_path_label: &pool_path_label,
                                     tags: &tag,
+                                    sets: &field_sets,
+                                    flags,
                                     durability,
                                     retry_config,
                                     remote_pool: &remote_pool,
                                     color_mode,
-                                    input,
+                                    input: ingest_mode,
                                     errors,
+                                    batch_size,
+                                    record: None,
                                 },
                                 true,
                             )?
@@ -574,12 +2043,16 @@ pub(super) fn dispatch_command(
                                     pool_ref: &pool,
                                     pool_path_label: &pool_path_label,
                                     tags: &tag,
+                                    sets: &field_sets,
+                                    flags,
                                     durability,
                                     retry_config,
                                     remote_pool: &remote_pool,
                                     color_mode,
-                                    input,
+                                    input: ingest_mode,
                                     errors,
+                                    batch_size,
+                                    record: None,
                                 },
                                 true,
                             )?
@@ -597,228 +2070,733 @@ pub(super) fn dispatch_command(
             };
             Ok(RunOutcome::ok())
         }
-        Command::Fetch { pool, seq } => {
+        Command::Fetch {
+            pool,
+            seq,
+            at,
+            nearest,
+            fields,
+            output,
+            jq,
+            attachment_out,
+            strict,
+        } => {
+            let projection = fields
+                .as_deref()
+                .map(|spec| Projection::parse(spec, output))
+                .transpose()?;
+            let program = jq.as_deref().map(JqFilter::compile_program).transpose()?;
             let path = resolve_poolref(&pool, &pool_dir)?;
             let pool_handle =
                 Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
-            let frame = pool_handle
-                .get(seq)
-                .map_err(|err| add_missing_seq_hint(err, &pool))?;
-            emit_json(message_from_frame(&frame)?, color_mode);
+            let frame = match (seq, at) {
+                (Some(seq), _) => match pool_handle.get(seq) {
+                    Ok(frame) => frame,
+                    Err(err) if err.kind() == ErrorKind::NotFound => {
+                        // Pinned messages outlive their frames; serve the pinned copy.
+                        let pins = pool_pins::PinSet::load(&path)?;
+                        let Some(pin) = pins.get(seq) else {
+                            return Err(add_missing_seq_hint(err, &pool));
+                        };
+                        let mut message = pin.message.clone();
+                        if let Some(path) = attachment_out.as_deref() {
+                            save_attachment(&mut message, path)?;
+                        }
+                        emit_fetched_message(message, projection, program, output, color_mode);
+                        return Ok(RunOutcome::ok());
+                    }
+                    Err(err) => return Err(add_missing_seq_hint(err, &pool)),
+                },
+                (None, Some(at)) => {
+                    let timestamp_ns = parse_since(&at, now_ns()?)?;
+                    let nearest = match nearest {
+                        NearestCli::Before => TimeNearest::Before,
+                        NearestCli::After => TimeNearest::After,
+                    };
+                    pool_handle
+                        .get_at_time(timestamp_ns, nearest)
+                        .map_err(|err| {
+                            if err.kind() != ErrorKind::NotFound || err.hint().is_some() {
+                                return err;
+                            }
+                            err.with_hint(format!(
+                                "Check the pool's time range with `plasmite pool info {pool}`, or try the other --nearest side."
+                            ))
+                        })?
+                }
+                (None, None) => {
+                    return Err(
+                        Error::new(ErrorKind::Usage).with_message("fetch requires a seq or --at")
+                    );
+                }
+            };
+            if strict {
+                validate_message_frame(&pool_handle, &frame)?;
+            }
+            let mut message = fetched_message(&pool_handle, &frame)?;
+            if let Some(path) = attachment_out.as_deref() {
+                save_attachment(&mut message, path)?;
+            }
+            emit_fetched_message(message, projection, program, output, color_mode);
             Ok(RunOutcome::ok())
         }
-        Command::Tap {
+        Command::Annotate {
             pool,
-            create,
-            create_size,
+            seq,
+            data,
             tag,
-            quiet,
-            durability,
-            command,
         } => {
-            if create_size.is_some() && !create {
-                return Err(Error::new(ErrorKind::Usage)
-                    .with_message("--create-size requires --create")
-                    .with_hint("Add --create or remove --create-size."));
-            }
             if pool.contains("://") {
                 return Err(Error::new(ErrorKind::Usage)
-                    .with_message("tap accepts local pool refs only")
-                    .with_hint(
-                        "Use a local pool name/path (for example `plasmite tap build -- ...`).",
-                    ));
+                    .with_message("annotate accepts local pool refs only")
+                    .with_hint("Use a local pool name/path (for example `plasmite annotate chat 12 '{...}'`)."));
             }
-            if command.is_empty() {
-                return Err(Error::new(ErrorKind::Usage)
-                    .with_message("tap requires a wrapped command after `--`")
-                    .with_hint("Use `plasmite tap <pool> -- <command...>`."));
+            let data: Value = serde_json::from_str(&data).map_err(|err| {
+                Error::new(ErrorKind::Usage)
+                    .with_message("annotation is not valid JSON")
+                    .with_hint("Pass a JSON object, for example '{\"emoji\":\"+1\"}'.")
+                    .with_source(err)
+            })?;
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let mut pool_handle =
+                Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            // Refuse dangling refs: the annotated message must still be readable.
+            pool_handle
+                .get(seq)
+                .map_err(|err| add_missing_seq_hint(err, &pool))?;
+            let refs = [seq];
+            let payload = lite3::encode_message_with_refs(&tag, &refs, &data)?;
+            let timestamp_ns = now_ns()?;
+            let options = AppendOptions::new(timestamp_ns, Durability::Fast);
+            let annotation_seq = pool_handle.append_with_options(payload.as_slice(), options)?;
+            emit_feed_receipt(
+                annotate_receipt_json(annotation_seq, timestamp_ns, &tag, &refs)?,
+                color_mode,
+            );
+            Ok(RunOutcome::ok())
+        }
+        Command::Pin {
+            pool,
+            seqs,
+            note,
+            remove,
+            json,
+        } => {
+            let json = wants_json(json);
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("pin accepts local pool refs only")
+                    .with_hint(
+                        "Use a local pool name/path (for example `plasmite pin incidents 42`).",
+                    ));
             }
-            let durability = parse_durability(&durability)?;
             let path = resolve_poolref(&pool, &pool_dir)?;
-            let mut pool_handle = match Pool::open(&path) {
-                Ok(pool_handle) => pool_handle,
-                Err(err) if create && err.kind() == ErrorKind::NotFound => {
-                    ensure_pool_dir(&pool_dir)?;
-                    let size = create_size
-                        .as_deref()
-                        .map(parse_size)
-                        .transpose()?
-                        .unwrap_or(DEFAULT_POOL_SIZE);
-                    Pool::create(&path, PoolOptions::new(size))?
-                }
-                Err(err) => {
-                    return Err(add_missing_pool_create_hint(err, "tap", &pool, &pool, None));
+            let pool_handle =
+                Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            if seqs.is_empty() {
+                let pins = pool_pins::PinSet::load(&path)?;
+                let rows = pins
+                    .pins()
+                    .iter()
+                    .map(|pin| (pin, pool_handle.get(pin.seq).is_ok()))
+                    .collect::<Vec<_>>();
+                if json {
+                    let pins = rows
+                        .iter()
+                        .map(|(pin, retained)| {
+                            json!({
+                                "seq": pin.seq,
+                                "pinned_at": pin.pinned_at,
+                                "note": pin.note,
+                                "retained": retained,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    emit_json(json!({ "pool": pool, "pins": pins }), color_mode);
+                } else if rows.is_empty() {
+                    println!("no pins in {pool}");
+                } else {
+                    let table = rows
+                        .iter()
+                        .map(|(pin, retained)| {
+                            vec![
+                                pin.seq.to_string(),
+                                pin.pinned_at.clone(),
+                                if *retained { "yes" } else { "copy only" }.to_string(),
+                                pin.note.clone().unwrap_or_default(),
+                            ]
+                        })
+                        .collect::<Vec<_>>();
+                    emit_table(&["SEQ", "PINNED AT", "IN RING", "NOTE"], &table);
                 }
-            };
-
-            let mut child = std::process::Command::new(&command[0])
-                .args(&command[1..])
-                .stdin(std::process::Stdio::inherit())
-                .stdout(std::process::Stdio::piped())
-                .stderr(std::process::Stdio::piped())
-                .spawn()
-                .map_err(|err| tap_spawn_error(&command, err))?;
-            // Install a dedicated relay that forwards SIGINT/SIGTERM received by tap
-            // to the wrapped child process PID.
-            tap_spawn_signal_forwarder(child.id() as i32);
-            let status_on_tty_stderr = io::stderr().is_terminal();
-            if status_on_tty_stderr {
-                eprintln!(
-                    "tapping {} <- {}",
-                    pool,
-                    render_shell_agnostic_command(&command)
-                );
+                return Ok(RunOutcome::ok());
             }
-
-            let child_stdout = child.stdout.take().ok_or_else(|| {
-                Error::new(ErrorKind::Internal).with_message("tap child stdout pipe unavailable")
-            })?;
-            let child_stderr = child.stderr.take().ok_or_else(|| {
-                Error::new(ErrorKind::Internal).with_message("tap child stderr pipe unavailable")
-            })?;
-
-            let lifecycle_tags = vec!["lifecycle".to_string()];
-            if let Err(err) = tap_append_message(
-                &mut pool_handle,
-                durability,
-                &lifecycle_tags,
-                &json!({
-                    "kind": "start",
-                    "cmd": command,
-                }),
-            ) {
-                tap_terminate_child(&mut child);
-                return Err(err);
-            }
-
-            let start_time = Instant::now();
-            let (event_tx, event_rx) = mpsc::channel();
-            let stdout_reader =
-                tap_spawn_reader(child_stdout, TapStream::Stdout, !quiet, event_tx.clone());
-            let stderr_reader = tap_spawn_reader(child_stderr, TapStream::Stderr, !quiet, event_tx);
-
-            let mut reader_error: Option<Error> = None;
-            let mut child_status = None;
-            let mut line_count: u64 = 0;
-
-            while child_status.is_none() {
-                match event_rx.recv_timeout(Duration::from_millis(25)) {
-                    Ok(TapEvent::Line { stream, raw_line }) => {
-                        line_count = line_count.saturating_add(1);
-                        if let Err(err) = tap_append_message(
-                            &mut pool_handle,
-                            durability,
-                            &tag,
-                            &json!({
-                                "kind": "line",
-                                "stream": stream.as_str(),
-                                "line": trim_tap_line_endings(&raw_line),
-                            }),
-                        ) {
-                            tap_terminate_child(&mut child);
-                            return Err(err);
+            pool_pins::PinSet::update(&pool_handle, &path, |pins| {
+                if remove {
+                    for &seq in &seqs {
+                        if !pins.unpin(seq) {
+                            return Err(Error::new(ErrorKind::NotFound)
+                                .with_message(format!("seq {seq} is not pinned"))
+                                .with_path(&path)
+                                .with_hint(format!("List pins with `plasmite pin {pool}`.")));
                         }
                     }
-                    Ok(TapEvent::ReaderError(err)) => {
-                        if reader_error.is_none() {
-                            reader_error = Some(err);
-                        }
+                } else {
+                    let pinned_at = format_ts(now_ns()?)?;
+                    for &seq in &seqs {
+                        let frame = pool_handle
+                            .get(seq)
+                            .map_err(|err| add_missing_seq_hint(err, &pool))?;
+                        pins.pin(pool_pins::Pin {
+                            seq,
+                            pinned_at: pinned_at.clone(),
+                            note: note.clone(),
+                            message: fetched_message(&pool_handle, &frame)?,
+                        });
                     }
-                    Err(mpsc::RecvTimeoutError::Timeout) => {}
-                    Err(mpsc::RecvTimeoutError::Disconnected) => {}
                 }
-                child_status = child.try_wait().map_err(|err| {
-                    Error::new(ErrorKind::Io)
-                        .with_message("failed waiting for wrapped command")
-                        .with_source(err)
-                })?;
-            }
-
-            let child_status = child_status.expect("status set once loop exits");
-            if stdout_reader.join().is_err() && reader_error.is_none() {
-                reader_error = Some(
-                    Error::new(ErrorKind::Internal).with_message("tap stdout reader panicked"),
-                );
+                Ok(())
+            })?;
+            let action = if remove { "unpinned" } else { "pinned" };
+            if json {
+                let mut receipt = json!({ "pool": pool });
+                receipt[action] = json!(seqs);
+                emit_json(receipt, color_mode);
+            } else {
+                let list = seqs
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("{action} {list} in {pool}");
             }
-            if stderr_reader.join().is_err() && reader_error.is_none() {
-                reader_error = Some(
-                    Error::new(ErrorKind::Internal).with_message("tap stderr reader panicked"),
-                );
+            Ok(RunOutcome::ok())
+        }
+        Command::Tap {
+            pool,
+            create,
+            create_size,
+            tag,
+            quiet,
+            durability,
+            restart,
+            max_restarts,
+            backoff,
+            command,
+        } => {
+            if create_size.is_some() && !create {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--create-size requires --create")
+                    .with_hint("Add --create or remove --create-size."));
             }
-
-            while let Ok(event) = event_rx.try_recv() {
-                match event {
-                    TapEvent::Line { stream, raw_line } => {
-                        line_count = line_count.saturating_add(1);
-                        tap_append_message(
-                            &mut pool_handle,
-                            durability,
-                            &tag,
-                            &json!({
-                                "kind": "line",
-                                "stream": stream.as_str(),
-                                "line": trim_tap_line_endings(&raw_line),
-                            }),
-                        )?;
-                    }
-                    TapEvent::ReaderError(err) => {
-                        if reader_error.is_none() {
-                            reader_error = Some(err);
-                        }
-                    }
-                }
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("tap accepts local pool refs only")
+                    .with_hint(
+                        "Use a local pool name/path (for example `plasmite tap build -- ...`).",
+                    ));
             }
-
-            if let Some(err) = reader_error {
-                return Err(err);
+            if command.is_empty() {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("tap requires a wrapped command after `--`")
+                    .with_hint("Use `plasmite tap <pool> -- <command...>`."));
             }
+            let durability = parse_durability(&durability)?;
+            let mut pool_handle =
+                open_capture_pool("tap", &pool, &pool_dir, create, create_size.as_deref())?;
+            let _presence = register_presence(&pool, &pool_dir, PoolRole::Writer, "tap");
 
-            let elapsed_ms = start_time.elapsed().as_millis().min(u64::MAX as u128) as u64;
-            let exit_code = if let Some(signal) = tap_exit_signal(&child_status) {
-                let signal_name = tap_signal_name(signal);
-                tap_append_message(
+            let plan = supervise::RestartPlan {
+                policy: restart,
+                max_restarts,
+                backoff: parse_duration(&backoff)?,
+            };
+            let supervised = restart != supervise::RestartPolicy::Never;
+            // One relay forwards SIGINT/SIGTERM received by tap to whichever attempt is
+            // running; an interrupt also ends the restart loop.
+            let child_pid = Arc::new(AtomicI32::new(0));
+            let interrupted = Arc::new(AtomicBool::new(false));
+            tap_spawn_signal_forwarder(child_pid.clone(), interrupted.clone());
+            let lifecycle_tags = vec!["lifecycle".to_string()];
+            let mut attempt = 1u32;
+            let mut restarts = 0u32;
+            loop {
+                let started = Instant::now();
+                let outcome = tap_run_once(
                     &mut pool_handle,
-                    durability,
-                    &lifecycle_tags,
-                    &json!({
-                        "kind": "exit",
-                        "signal": signal_name,
-                        "elapsed_ms": elapsed_ms,
-                    }),
+                    &TapRun {
+                        pool: &pool,
+                        command: &command,
+                        tag: &tag,
+                        quiet,
+                        durability,
+                        attempt: supervised.then_some(attempt),
+                        child_pid: &child_pid,
+                    },
                 )?;
-                if status_on_tty_stderr {
-                    eprintln!(
-                        "tapped {line_count} lines ({}) -> {} signal {}",
-                        format_tap_elapsed(elapsed_ms),
-                        pool,
-                        signal_name
-                    );
+                if !supervised
+                    || interrupted.load(Ordering::SeqCst)
+                    || !plan.policy.wants_restart(outcome.success)
+                {
+                    return Ok(RunOutcome::with_code(outcome.exit_code));
                 }
-                128 + signal
-            } else {
-                let code = child_status.code().unwrap_or(1);
+                if started.elapsed() >= supervise::STABLE_AFTER {
+                    restarts = 0;
+                }
+                let Some(delay) = plan.next_delay(restarts) else {
+                    tap_append_message(
+                        &mut pool_handle,
+                        durability,
+                        &lifecycle_tags,
+                        &json!({
+                            "kind": "give_up",
+                            "attempt": attempt,
+                            "restarts": restarts,
+                        }),
+                    )?;
+                    eprintln!("tap: {pool} gave up after {restarts} restarts");
+                    return Ok(RunOutcome::with_code(outcome.exit_code));
+                };
+                restarts += 1;
+                attempt += 1;
                 tap_append_message(
                     &mut pool_handle,
                     durability,
                     &lifecycle_tags,
                     &json!({
-                        "kind": "exit",
-                        "code": code,
-                        "elapsed_ms": elapsed_ms,
+                        "kind": "restart",
+                        "attempt": attempt,
+                        "delay_ms": delay.as_millis() as u64,
                     }),
                 )?;
-                if status_on_tty_stderr {
-                    eprintln!(
-                        "tapped {line_count} lines ({}) -> {} exit {}",
-                        format_tap_elapsed(elapsed_ms),
-                        pool,
-                        code
+                let resume_at = Instant::now() + delay;
+                while Instant::now() < resume_at {
+                    if interrupted.load(Ordering::SeqCst) {
+                        return Ok(RunOutcome::with_code(outcome.exit_code));
+                    }
+                    std::thread::sleep(Duration::from_millis(20).min(delay));
+                }
+            }
+        }
+        Command::Run { config } => {
+            let config = supervise::load_config(&config)?;
+            let mut control =
+                open_capture_pool("run", &config.control_pool, &pool_dir, true, None)?;
+            for spec in &config.pools {
+                open_capture_pool("run", &spec.name, &pool_dir, true, spec.size.as_deref())?;
+            }
+            let processes = config.processes(&pool_dir)?;
+            if processes.is_empty() {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("run config declares no processes")
+                    .with_hint("Add a [[tap]] entry or a [serve] section."));
+            }
+            let exe = std::env::current_exe().map_err(|err| {
+                Error::new(ErrorKind::Io)
+                    .with_message("failed to locate plasmite executable")
+                    .with_source(err)
+            })?;
+            run_supervisor(&exe, processes, &mut control)
+        }
+        Command::Syslog {
+            listen,
+            pool,
+            create,
+            create_size,
+            tag,
+            durability,
+        } => {
+            if create_size.is_some() && !create {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--create-size requires --create")
+                    .with_hint("Add --create or remove --create-size."));
+            }
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("syslog accepts local pool refs only")
+                    .with_hint(
+                        "Use a local pool name/path (for example `plasmite syslog --listen udp:5514 --pool syslog`).",
+                    ));
+            }
+            let listen = syslog::parse_listen(&listen)?;
+            let durability = parse_durability(&durability)?;
+            let mut pool_handle = open_capture_pool(
+                "syslog --pool",
+                &pool,
+                &pool_dir,
+                create,
+                create_size.as_deref(),
+            )?;
+            let _presence = register_presence(&pool, &pool_dir, PoolRole::Writer, "syslog");
+
+            let socket = UdpSocket::bind(listen.addr).map_err(|err| {
+                let kind = if err.kind() == io::ErrorKind::PermissionDenied {
+                    ErrorKind::Permission
+                } else {
+                    ErrorKind::Io
+                };
+                Error::new(kind)
+                    .with_message(format!("failed to bind syslog listener on {}", listen.addr))
+                    .with_hint("Ports below 1024 need elevated privileges; try udp:5514.")
+                    .with_source(err)
+            })?;
+            if io::stderr().is_terminal() {
+                eprintln!("listening on udp {} -> {}", listen.addr, pool);
+            }
+
+            let mut buf = vec![0u8; syslog::MAX_DATAGRAM_BYTES];
+            loop {
+                let (len, peer) = match socket.recv_from(&mut buf) {
+                    Ok(received) => received,
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => {
+                        return Err(Error::new(ErrorKind::Io)
+                            .with_message("failed to receive syslog datagram")
+                            .with_source(err));
+                    }
+                };
+                let data = syslog::parse_datagram(&buf[..len], Some(peer));
+                tap_append_message(&mut pool_handle, durability, &tag, &data)?;
+            }
+        }
+        Command::Journal {
+            pool,
+            follow,
+            unit,
+            since,
+            create,
+            create_size,
+            tag,
+            durability,
+            journalctl,
+        } => {
+            if create_size.is_some() && !create {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--create-size requires --create")
+                    .with_hint("Add --create or remove --create-size."));
+            }
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("journal accepts local pool refs only")
+                    .with_hint(
+                        "Use a local pool name/path (for example `plasmite journal --pool svc-logs`).",
+                    ));
+            }
+            let durability = parse_durability(&durability)?;
+            let mut pool_handle = open_capture_pool(
+                "journal --pool",
+                &pool,
+                &pool_dir,
+                create,
+                create_size.as_deref(),
+            )?;
+            let _presence = register_presence(&pool, &pool_dir, PoolRole::Writer, "journal");
+
+            let args = journal::journalctl_args(follow, &unit, since.as_deref());
+            let mut child = std::process::Command::new(&journalctl)
+                .args(&args)
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::inherit())
+                .spawn()
+                .map_err(|err| {
+                    if err.kind() == io::ErrorKind::NotFound {
+                        return Error::new(ErrorKind::Usage)
+                            .with_message(format!("journalctl not found: {journalctl}"))
+                            .with_hint("Install systemd's journalctl or pass --journalctl <path>.")
+                            .with_source(err);
+                    }
+                    Error::new(ErrorKind::Io)
+                        .with_message("failed to spawn journalctl")
+                        .with_source(err)
+                })?;
+            let stdout = child.stdout.take().ok_or_else(|| {
+                Error::new(ErrorKind::Internal).with_message("journalctl stdout pipe unavailable")
+            })?;
+            let mut reader = io::BufReader::new(stdout);
+
+            let entry_count =
+                match journal_copy_entries(&mut reader, &mut pool_handle, &tag, durability) {
+                    Ok(entry_count) => entry_count,
+                    Err(err) => {
+                        tap_terminate_child(&mut child);
+                        return Err(err);
+                    }
+                };
+
+            let status = child.wait().map_err(|err| {
+                Error::new(ErrorKind::Io)
+                    .with_message("failed waiting for journalctl")
+                    .with_source(err)
+            })?;
+            if !status.success() {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message(format!("journalctl exited with status {status}"))
+                    .with_hint("Check the journalctl error above (permissions, unit names, --since syntax)."));
+            }
+            if io::stderr().is_terminal() {
+                eprintln!("copied {entry_count} journal entries -> {pool}");
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Forward {
+            pool,
+            to,
+            batch,
+            where_expr,
+            where_jsonpath,
+            tags,
+            retry,
+            retry_delay,
+            header,
+            cursor_file,
+            drain,
+        } => {
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("forward accepts local pool refs only")
+                    .with_hint(
+                        "Use a local pool name/path (for example `plasmite forward events --to URL`).",
+                    ));
+            }
+            if batch == 0 {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--batch must be greater than zero")
+                    .with_hint("Use --batch 1 to send one message per request."));
+            }
+            let headers = header
+                .iter()
+                .map(|value| forward::parse_header(value))
+                .collect::<Result<Vec<_>, _>>()?;
+            let forwarder =
+                forward::Forwarder::new(&to, headers, retry, parse_duration(&retry_delay)?)?;
+            let cfg = ForwardConfig {
+                batch,
+                required_tags: tags,
+                where_predicates: compile_where(&where_expr, &where_jsonpath)?,
+                drain,
+                color_mode,
+            };
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let pool_handle = Pool::open(&path)
+                .map_err(|err| add_missing_pool_create_hint(err, "forward", &pool, &pool, None))?;
+            let cursor = match cursor_file {
+                Some(cursor_file) => forward::ForwardCursor::at(cursor_file),
+                None => forward::ForwardCursor::for_target(&pool_dir, &pool, &to),
+            };
+            let delivered = forward_pool(&pool_handle, &pool, &path, &forwarder, &cursor, &cfg)?;
+            if io::stderr().is_terminal() {
+                eprintln!("forwarded {delivered} messages -> {to}");
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Hook { command } => match command {
+            HookCommand::Add {
+                pool,
+                exec,
+                post,
+                where_expr,
+                json,
+            } => {
+                let json = wants_json(json);
+                if pool.contains("://") {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message("hook add accepts local pool refs only")
+                        .with_hint(
+                            "Use a local pool name/path (for example `plasmite hook add events --exec 'cat'`).",
+                        ));
+                }
+                let action = match (exec, post) {
+                    (Some(command), None) => hooks::HookAction::Exec(command),
+                    (None, Some(url)) => {
+                        let scheme = Url::parse(&url).map(|parsed| parsed.scheme().to_string());
+                        if !matches!(scheme.as_deref(), Ok("http" | "https")) {
+                            return Err(Error::new(ErrorKind::Usage)
+                                .with_message(format!("invalid --post URL: {url}"))
+                                .with_hint(
+                                    "Use an absolute http(s) URL, e.g. https://example.com/hook.",
+                                ));
+                        }
+                        hooks::HookAction::Post(url)
+                    }
+                    _ => {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("hook add needs exactly one of --exec or --post"));
+                    }
+                };
+                compile_filters(&where_expr)?;
+                let path = resolve_poolref(&pool, &pool_dir)?;
+                let bounds = Pool::open_read_only(&path)
+                    .map_err(|err| add_missing_pool_hint(err, &pool, &pool))?
+                    .bounds()?;
+                // hookd may run from another directory, so path refs are stored absolute.
+                let pool_ref = if is_path_ref(&pool) {
+                    std::fs::canonicalize(&path)
+                        .unwrap_or(path)
+                        .display()
+                        .to_string()
+                } else {
+                    pool
+                };
+                let mut registry = hooks::HookRegistry::load(&pool_dir)?;
+                let hook = registry
+                    .add(pool_ref, action, where_expr, format_ts(now_ns()?)?)
+                    .clone();
+                // Start after the current newest message: hooks fire for new appends only.
+                hooks::hook_cursor(&pool_dir, &hook.id).store(bounds.newest_seq.unwrap_or(0))?;
+                registry.save(&pool_dir)?;
+                if json {
+                    emit_json(json!({ "hook": hook_json(&hook) }), color_mode);
+                } else {
+                    println!(
+                        "added hook {} on {} ({} {})",
+                        hook.id,
+                        hook.pool,
+                        hook.action.kind(),
+                        hook.action.target()
                     );
                 }
-                code
+                Ok(RunOutcome::ok())
+            }
+            HookCommand::List { json } => {
+                let json = wants_json(json);
+                let registry = hooks::HookRegistry::load(&pool_dir)?;
+                let rows = registry
+                    .hooks()
+                    .iter()
+                    .map(|hook| {
+                        let seq = hooks::hook_cursor(&pool_dir, &hook.id).load()?;
+                        Ok((hook, seq))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                if json {
+                    let hooks = rows
+                        .iter()
+                        .map(|(hook, seq)| {
+                            let mut value = hook_json(hook);
+                            value["cursor_seq"] = json!(seq);
+                            value
+                        })
+                        .collect::<Vec<_>>();
+                    emit_json(json!({ "hooks": hooks }), color_mode);
+                } else if rows.is_empty() {
+                    println!("no hooks in {}", display_pool_dir_for_humans(&pool_dir));
+                } else {
+                    let table = rows
+                        .iter()
+                        .map(|(hook, seq)| {
+                            vec![
+                                hook.id.clone(),
+                                hook.pool.clone(),
+                                format!("{} {}", hook.action.kind(), hook.action.target()),
+                                hook.where_exprs.join(" and "),
+                                seq.map_or_else(|| "-".to_string(), |seq| seq.to_string()),
+                            ]
+                        })
+                        .collect::<Vec<_>>();
+                    emit_table(&["ID", "POOL", "ACTION", "WHERE", "CURSOR"], &table);
+                }
+                Ok(RunOutcome::ok())
+            }
+            HookCommand::Remove { id, json } => {
+                let json = wants_json(json);
+                let mut registry = hooks::HookRegistry::load(&pool_dir)?;
+                let Some(hook) = registry.remove(&id) else {
+                    return Err(Error::new(ErrorKind::NotFound)
+                        .with_message(format!("no hook with id {id}"))
+                        .with_hint("List hooks with `plasmite hook list`."));
+                };
+                registry.save(&pool_dir)?;
+                let cursor = hooks::hook_cursor(&pool_dir, &hook.id);
+                match std::fs::remove_file(cursor.path()) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                        return Err(Error::new(ErrorKind::Io)
+                            .with_message("failed to remove hook cursor")
+                            .with_path(cursor.path())
+                            .with_source(err));
+                    }
+                    _ => {}
+                }
+                if json {
+                    emit_json(json!({ "removed": hook_json(&hook) }), color_mode);
+                } else {
+                    println!("removed hook {} on {}", hook.id, hook.pool);
+                }
+                Ok(RunOutcome::ok())
+            }
+        },
+        Command::Hookd { interval, once } => {
+            run_hookd(&pool_dir, parse_duration(&interval)?, once, color_mode)
+        }
+        Command::Statsd { interval, once } => {
+            run_statsd(&pool_dir, parse_duration(&interval)?, once, color_mode)
+        }
+        Command::Ctl { socket, command } => {
+            let (request, json) = match command {
+                CtlCommand::Stats { json } => (follow_control::ControlRequest::Stats, json),
+                CtlCommand::Pause { json } => (follow_control::ControlRequest::Pause, json),
+                CtlCommand::Resume { json } => (follow_control::ControlRequest::Resume, json),
+                CtlCommand::Where { exprs, clear, json } => {
+                    let exprs = if clear { Vec::new() } else { exprs };
+                    // Catch typos here; the follower validates again before swapping.
+                    compile_filters(&exprs)?;
+                    (follow_control::ControlRequest::Where(exprs), json)
+                }
             };
-
-            Ok(RunOutcome::with_code(exit_code))
+            let reply = follow_control::send_request(&socket, &request)?;
+            if wants_json(json) {
+                emit_json(reply, color_mode);
+            } else {
+                let state = if reply["paused"].as_bool() == Some(true) {
+                    "paused"
+                } else {
+                    "running"
+                };
+                let filters = reply["where"]
+                    .as_array()
+                    .map(|exprs| {
+                        exprs
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .collect::<Vec<_>>()
+                            .join(" and ")
+                    })
+                    .filter(|filters| !filters.is_empty())
+                    .unwrap_or_else(|| "-".to_string());
+                let last_seq = reply["last_seq"]
+                    .as_u64()
+                    .map_or_else(|| "-".to_string(), |seq| seq.to_string());
+                println!(
+                    "{} {state}: emitted {}, filtered {}, last seq {last_seq}",
+                    reply["pool"].as_str().unwrap_or("follow"),
+                    reply["emitted"],
+                    reply["filtered"]
+                );
+                println!("where: {filters}");
+            }
+            Ok(RunOutcome::ok())
+        }
+        Command::Bridge {
+            pool,
+            fifo,
+            recreate,
+        } => {
+            if pool.contains("://") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("bridge accepts local pool refs only")
+                    .with_hint(
+                        "Use a local pool name/path (for example `plasmite bridge events --fifo /tmp/events.pipe`).",
+                    ));
+            }
+            let path = resolve_poolref(&pool, &pool_dir)?;
+            let pool_handle =
+                Pool::open(&path).map_err(|err| add_missing_pool_hint(err, &pool, &pool))?;
+            let bridge = fifo_bridge::FifoBridge::prepare(&fifo, recreate)?;
+            let streamed = bridge_pool(&pool_handle, &pool, &path, &bridge, color_mode)?;
+            if io::stderr().is_terminal() {
+                eprintln!("bridged {streamed} messages -> {}", fifo.display());
+            }
+            Ok(RunOutcome::ok())
         }
         Command::Duplex {
             pool,
@@ -830,6 +2808,7 @@ pub(super) fn dispatch_command(
             format,
             since,
             echo_self,
+            record,
         } => {
             if jsonl && format.is_some() {
                 return Err(Error::new(ErrorKind::Usage)
@@ -843,7 +2822,7 @@ pub(super) fn dispatch_command(
                     .with_hint("Provide --me NAME to send TTY line-mode messages."));
             }
             let format_flag = format;
-            let format = format.unwrap_or(if jsonl {
+            let format = format.unwrap_or(if jsonl || output_mode() == OutputMode::Jsonl {
                 FollowFormat::Jsonl
             } else {
                 FollowFormat::Pretty
@@ -871,6 +2850,12 @@ pub(super) fn dispatch_command(
                 false,
                 None,
             );
+            let record = record
+                .map(|path| {
+                    let started = notice_time_now().unwrap_or_default();
+                    TranscriptWriter::create(&path, &pool, me.as_deref(), &started).map(Arc::new)
+                })
+                .transpose()?;
             let stop = Arc::new(AtomicBool::new(false));
             let cfg = FollowConfig {
                 tail,
@@ -881,12 +2866,26 @@ pub(super) fn dispatch_command(
                 since_ns,
                 required_tags: Vec::new(),
                 where_predicates: compile_filters(&[])?,
+                where_strings: Vec::new(),
+                where_fields: Vec::new(),
                 quiet_drops: false,
                 notify: true,
                 color_mode,
                 replay_speed: None,
+                replay_control: ReplayControl::default(),
                 suppress_sender: if echo_self { None } else { me.clone() },
                 stop: Some(stop.clone()),
+                exec: None,
+                thread: None,
+                projection: None,
+                jq: None,
+                stats_interval: None,
+                poll: FollowPoll::default(),
+                on_sigpipe: OnSigpipe::Exit,
+                show_control: false,
+                strict: false,
+                record: record.clone(),
+                control: None,
             };
 
             #[derive(Clone, Copy)]
@@ -903,7 +2902,7 @@ pub(super) fn dispatch_command(
                     let follow_pool_handle = match Pool::open(&path) {
                         Ok(pool_handle) => pool_handle,
                         Err(err) if create && err.kind() == ErrorKind::NotFound => {
-                            ensure_pool_dir(&pool_dir)?;
+                            ensure_pool_parent(&pool_dir, &path)?;
                             Pool::create(&path, PoolOptions::new(DEFAULT_POOL_SIZE))?
                         }
                         Err(err) => {
@@ -943,6 +2942,7 @@ pub(super) fn dispatch_command(
                     let send_tx = event_tx;
                     let stop_for_send = stop.clone();
                     let me_for_send = me.clone();
+                    let record_for_send = record.clone();
                     let stdin_mode_terminal = stdin_is_terminal;
                     let _ = std::thread::spawn(move || {
                         if stdin_mode_terminal {
@@ -983,14 +2983,22 @@ pub(super) fn dispatch_command(
                                     return;
                                 }
                                 let payload = payload.expect("payload");
-                                if let Err(err) = retry_with_config(None, || {
+                                let appended = retry_with_config(None, || {
                                     let timestamp_ns = now_ns()?;
                                     let options =
                                         AppendOptions::new(timestamp_ns, Durability::Fast);
-                                    send_pool
-                                        .append_with_options(payload.as_slice(), options)
-                                        .map(|_| ())
-                                }) {
+                                    send_pool.append_with_options(payload.as_slice(), options)
+                                })
+                                .and_then(|seq| {
+                                    match &record_for_send {
+                                        Some(record) => record.record(
+                                            Direction::Sent,
+                                            &json!({ "seq": seq, "data": value }),
+                                        ),
+                                        None => Ok(()),
+                                    }
+                                });
+                                if let Err(err) = appended {
                                     let _ = send_tx.send((DuplexSide::Send, Err(err)));
                                     return;
                                 }
@@ -1009,12 +3017,15 @@ pub(super) fn dispatch_command(
                                     pool_ref: &pool_ref,
                                     pool_path_label: &pool_path_label,
                                     tags: &[],
+                                    sets: &FieldSets::default(),
+                                    flags: 0,
                                     durability: Durability::Fast,
                                     retry_config: None,
                                     pool_handle: &mut send_pool,
                                     color_mode,
-                                    input: InputMode::Auto,
+                                    input: IngestMode::Auto,
                                     errors: ErrorPolicyCli::Stop,
+                                    record: record_for_send.as_deref(),
                                 },
                                 false,
                             );
@@ -1058,7 +3069,11 @@ pub(super) fn dispatch_command(
                     let follow_client = client.clone();
                     let pool_name = name.clone();
                     let _ = std::thread::spawn(move || {
-                        let outcome = follow_remote(&follow_client, &pool_name, &follow_cfg);
+                        let target = RemoteFollowTarget {
+                            client: follow_client,
+                            pool: pool_name,
+                        };
+                        let outcome = follow_remote(std::slice::from_ref(&target), &follow_cfg);
                         if outcome.is_err() {
                             stop_for_follow.store(true, Ordering::Release);
                         }
@@ -1068,6 +3083,7 @@ pub(super) fn dispatch_command(
                     let send_tx = event_tx;
                     let stop_for_send = stop.clone();
                     let me_for_send = me.clone();
+                    let record_for_send = record.clone();
                     let stdin_mode_terminal = stdin_is_terminal;
                     let _ = std::thread::spawn(move || {
                         if stdin_mode_terminal {
@@ -1102,9 +3118,16 @@ pub(super) fn dispatch_command(
                                     continue;
                                 };
                                 have_input = true;
-                                if let Err(err) =
-                                    remote_pool.append_json_now(&value, &[], Durability::Fast)
-                                {
+                                let appended = remote_pool
+                                    .append_json_now(&value, &[], Durability::Fast)
+                                    .and_then(|message| match &record_for_send {
+                                        Some(record) => record.record(
+                                            Direction::Sent,
+                                            &json!({ "seq": message.seq, "data": value }),
+                                        ),
+                                        None => Ok(()),
+                                    });
+                                if let Err(err) = appended {
                                     let _ = send_tx.send((DuplexSide::Send, Err(err)));
                                     return;
                                 }
@@ -1121,12 +3144,16 @@ pub(super) fn dispatch_command(
                                     pool_ref: &name,
                                     pool_path_label: &pool_path_label,
                                     tags: &[],
+                                    sets: &FieldSets::default(),
+                                    flags: 0,
                                     durability: Durability::Fast,
                                     retry_config: None,
                                     remote_pool: &remote_pool,
                                     color_mode,
-                                    input: InputMode::Auto,
+                                    input: IngestMode::Auto,
                                     errors: ErrorPolicyCli::Stop,
+                                    batch_size: 1,
+                                    record: record_for_send.as_deref(),
                                 },
                                 false,
                             );
@@ -1158,6 +3185,7 @@ pub(super) fn dispatch_command(
         }
         Command::Follow {
             pool,
+            remote,
             create,
             jsonl,
             tail,
@@ -1166,15 +3194,36 @@ pub(super) fn dispatch_command(
             data_only,
             quiet_drops,
             no_notify,
+            poll_min,
+            poll_max,
+            poll_strategy,
+            show_control,
+            strict,
+            stats_interval,
+            control_socket,
+            on_sigpipe,
             format,
             since,
             where_expr,
+            where_jsonpath,
+            thread,
+            fields,
+            output,
+            jq,
             tags,
             replay,
+            until,
+            speed_ramp,
+            pace_by,
+            exec,
+            exec_parallel,
+            exec_errors,
             token,
             token_file,
             tls_ca,
             tls_skip_verify,
+            tls_client_cert,
+            tls_client_key,
         } => {
             if jsonl && format.is_some() {
                 return Err(Error::new(ErrorKind::Usage)
@@ -1182,7 +3231,7 @@ pub(super) fn dispatch_command(
                     .with_hint("Use --format jsonl (or --jsonl), but not both."));
             }
             let format_flag = format;
-            let format = format.unwrap_or(if jsonl {
+            let format = format.unwrap_or(if jsonl || output_mode() == OutputMode::Jsonl {
                 FollowFormat::Jsonl
             } else {
                 FollowFormat::Pretty
@@ -1193,8 +3242,36 @@ pub(super) fn dispatch_command(
                 .as_deref()
                 .map(|value| parse_since(value, now))
                 .transpose()?;
+            let replay_control = ReplayControl {
+                until: until
+                    .as_deref()
+                    .map(|value| parse_replay_until(value, now))
+                    .transpose()?,
+                ramp: speed_ramp.as_deref().map(SpeedRamp::parse).transpose()?,
+                pace_by: pace_by.as_deref().map(PaceBy::parse).transpose()?,
+            };
             let timeout_input = timeout.as_deref();
             let timeout = timeout_input.map(parse_duration).transpose()?;
+            let stats_interval = stats_interval.as_deref().map(parse_duration).transpose()?;
+            if stats_interval.is_some_and(|interval| interval.is_zero()) {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--stats-interval must be greater than zero")
+                    .with_hint("Use a duration like 10s or 1m."));
+            }
+            let poll_tuned = poll_min.is_some() || poll_max.is_some() || poll_strategy.is_some();
+            let poll = follow_poll(poll_min.as_deref(), poll_max.as_deref(), poll_strategy)?;
+            // Without a positional ref the first --remote is the primary.
+            let (pool, failover) = match pool {
+                Some(pool) => (pool, remote),
+                None => {
+                    let mut remote = remote.into_iter();
+                    let primary = remote.next().ok_or_else(|| {
+                        Error::new(ErrorKind::Usage)
+                            .with_message("follow requires a pool ref or --remote")
+                    })?;
+                    (primary, remote.collect())
+                }
+            };
             let exact_follow_create_hint = follow_exact_create_command_hint(
                 &pool,
                 tail,
@@ -1210,6 +3287,35 @@ pub(super) fn dispatch_command(
                 no_notify,
                 replay,
             );
+            if exec.is_some() && exec_parallel == 0 {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("--exec-parallel must be greater than zero")
+                    .with_hint("Use --exec-parallel 1 to run hooks one at a time."));
+            }
+            let exec_hook = exec.map(|command| {
+                let policy = match exec_errors {
+                    ExecErrorsCli::Continue => ExecErrorPolicy::Continue,
+                    ExecErrorsCli::Stop => ExecErrorPolicy::Stop,
+                };
+                Arc::new(Mutex::new(ExecHook::new(command, exec_parallel, policy)))
+            });
+            let thread_view = thread
+                .as_deref()
+                .map(JqFilter::compile)
+                .transpose()?
+                .map(|key| ThreadView::start(key, DEFAULT_THREAD_WINDOW, pretty));
+            let projection = fields
+                .as_deref()
+                .map(|spec| Projection::parse(spec, output))
+                .transpose()?
+                .map(Arc::new);
+            if let Some(header) = projection
+                .as_ref()
+                .and_then(|projection| projection.header())
+            {
+                println!("{header}");
+            }
+            let where_predicates = compile_where(&where_expr, &where_jsonpath)?;
             let cfg = FollowConfig {
                 tail,
                 pretty,
@@ -1218,13 +3324,30 @@ pub(super) fn dispatch_command(
                 data_only,
                 since_ns,
                 required_tags: tags,
-                where_predicates: compile_filters(&where_expr)?,
+                where_strings: required_strings(&where_predicates),
+                where_fields: field_equalities(&where_predicates)
+                    .into_iter()
+                    .filter(|(path, _)| path.starts_with(".data."))
+                    .collect(),
+                where_predicates,
                 quiet_drops,
                 notify: !no_notify,
                 color_mode,
                 replay_speed: replay,
+                replay_control,
                 suppress_sender: None,
                 stop: None,
+                exec: exec_hook.clone(),
+                thread: thread_view.clone(),
+                projection,
+                jq: jq.as_deref().map(JqFilter::compile_program).transpose()?,
+                stats_interval,
+                poll,
+                on_sigpipe,
+                show_control,
+                strict,
+                record: None,
+                control: None,
             };
             let target = resolve_pool_target(&pool, &pool_dir)?;
             match target {
@@ -1235,7 +3358,15 @@ pub(super) fn dispatch_command(
                         token_file.as_deref(),
                         tls_ca.as_deref(),
                         tls_skip_verify,
+                        tls_client_cert.as_deref(),
                     )?;
+                    if !failover.is_empty() {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("--remote requires a remote pool ref")
+                            .with_hint(
+                                "Pass http(s)://host:port/<pool> refs only when using --remote failover.",
+                            ));
+                    }
                     let exact_create_hint = Some(exact_follow_create_hint.clone());
                     if let Some(speed) = replay {
                         if speed < 0.0 {
@@ -1259,7 +3390,7 @@ pub(super) fn dispatch_command(
                     let pool_handle = match Pool::open(&path) {
                         Ok(pool_handle) => pool_handle,
                         Err(err) if create && err.kind() == ErrorKind::NotFound => {
-                            ensure_pool_dir(&pool_dir)?;
+                            ensure_pool_parent(&pool_dir, &path)?;
                             Pool::create(&path, PoolOptions::new(DEFAULT_POOL_SIZE))?
                         }
                         Err(err) => {
@@ -1277,7 +3408,25 @@ pub(super) fn dispatch_command(
                             return Ok(RunOutcome::ok());
                         }
                     }
-                    let outcome = follow_pool(&pool_handle, &pool, &path, cfg)?;
+                    let control = control_socket
+                        .as_deref()
+                        .map(|socket| FollowControl::bind(socket, &pool, where_expr.clone()))
+                        .transpose()?;
+                    let cfg = FollowConfig {
+                        control: control.clone(),
+                        ..cfg
+                    };
+                    let outcome = finish_follow_exec(
+                        exec_hook.as_ref(),
+                        follow_pool(&pool_handle, &pool, &path, cfg),
+                        &pool,
+                        color_mode,
+                    );
+                    if let Some(control) = &control {
+                        control.close();
+                    }
+                    finish_follow_thread(thread_view.as_ref());
+                    let outcome = outcome?;
                     if outcome.exit_code == 124 {
                         if let Some(timeout_input) = timeout_input {
                             emit_follow_timeout_human(timeout_input);
@@ -1286,6 +3435,26 @@ pub(super) fn dispatch_command(
                     Ok(outcome)
                 }
                 PoolTarget::Remote { base_url, pool } => {
+                    if cfg.stats_interval.is_some() {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("remote follow does not support --stats-interval")
+                            .with_hint("Run follow against the local pool to report stats."));
+                    }
+                    if poll_tuned {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("remote follow does not support --poll-min/--poll-max/--poll-strategy")
+                            .with_hint("Remote follow streams from the server; tune polling on a local follow."));
+                    }
+                    if strict {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("remote follow does not support --strict")
+                            .with_hint("Validate on the server with `plasmite doctor --deep`, or follow the local pool."));
+                    }
+                    if control_socket.is_some() {
+                        return Err(Error::new(ErrorKind::Usage)
+                            .with_message("remote follow does not support --control-socket")
+                            .with_hint("Run follow against the local pool to control it with `plasmite ctl`."));
+                    }
                     if create {
                         return Err(Error::new(ErrorKind::Usage)
                             .with_message("remote follow does not support --create")
@@ -1294,20 +3463,51 @@ pub(super) fn dispatch_command(
                             ));
                     }
                     let token_value = resolve_token_value(token, token_file)?;
-                    let mut client = RemoteClient::new(base_url)?;
-                    if let Some(token_value) = token_value {
-                        client = client.with_token(token_value);
-                    }
-                    if let Some(path) = tls_ca {
-                        client = client.with_tls_ca_file(path)?;
-                    }
                     if tls_skip_verify {
                         eprintln!(
                             "warning: --tls-skip-verify disables TLS certificate verification (unsafe)"
                         );
-                        client = client.with_tls_skip_verify();
                     }
-                    let outcome = follow_remote(&client, &pool, &cfg)?;
+                    let connect =
+                        |base_url: String, pool: String| -> Result<RemoteFollowTarget, Error> {
+                            let mut client = RemoteClient::new(base_url)?;
+                            if let Some(token_value) = &token_value {
+                                client = client.with_token(token_value.clone());
+                            }
+                            if let Some(path) = &tls_ca {
+                                client = client.with_tls_ca_file(path)?;
+                            }
+                            if tls_skip_verify {
+                                client = client.with_tls_skip_verify();
+                            }
+                            if let (Some(cert), Some(key)) = (&tls_client_cert, &tls_client_key) {
+                                client = client.with_tls_client_cert(cert, key)?;
+                            }
+                            Ok(RemoteFollowTarget { client, pool })
+                        };
+                    let mut targets = vec![connect(base_url, pool.clone())?];
+                    for input in &failover {
+                        match resolve_pool_target(input, &pool_dir)? {
+                            PoolTarget::Remote { base_url, pool } => {
+                                targets.push(connect(base_url, pool)?);
+                            }
+                            PoolTarget::LocalPath(_) => {
+                                return Err(Error::new(ErrorKind::Usage)
+                                    .with_message(format!(
+                                        "--remote expects a remote pool ref, got '{input}'"
+                                    ))
+                                    .with_hint("Use http(s)://host:port/<pool>."));
+                            }
+                        }
+                    }
+                    let outcome = finish_follow_exec(
+                        exec_hook.as_ref(),
+                        follow_remote(&targets, &cfg),
+                        &pool,
+                        color_mode,
+                    );
+                    finish_follow_thread(thread_view.as_ref());
+                    let outcome = outcome?;
                     if outcome.exit_code == 124 {
                         if let Some(timeout_input) = timeout_input {
                             emit_follow_timeout_human(timeout_input);
@@ -1353,6 +3553,636 @@ fn tap_append_message(
     Ok(())
 }
 
+/// Open a local pool for capture commands (`tap`, `syslog`, `journal`), creating it on request.
+/// `hint_command` is the argv prefix echoed before the pool ref in the missing-pool hint.
+/// Advisory registration for `pool who`; unwritable pool dirs simply go unregistered.
+fn register_presence(
+    pool: &str,
+    pool_dir: &Path,
+    role: PoolRole,
+    command: &str,
+) -> Option<RegistrationGuard> {
+    let path = resolve_poolref(pool, pool_dir).ok()?;
+    register_pool_user(&path, role, command, None).ok()
+}
+
+fn open_capture_pool(
+    hint_command: &str,
+    pool: &str,
+    pool_dir: &Path,
+    create: bool,
+    create_size: Option<&str>,
+) -> Result<Pool, Error> {
+    let path = resolve_poolref(pool, pool_dir)?;
+    match Pool::open(&path) {
+        Ok(pool_handle) => Ok(pool_handle),
+        Err(err) if create && err.kind() == ErrorKind::NotFound => {
+            ensure_pool_parent(pool_dir, &path)?;
+            let size = create_size
+                .map(parse_size)
+                .transpose()?
+                .unwrap_or(DEFAULT_POOL_SIZE);
+            Pool::create(&path, PoolOptions::new(size))
+        }
+        Err(err) => Err(add_missing_pool_create_hint(
+            err,
+            hint_command,
+            pool,
+            pool,
+            None,
+        )),
+    }
+}
+
+fn journal_copy_entries<R: io::BufRead>(
+    reader: &mut R,
+    pool: &mut Pool,
+    tags: &[String],
+    durability: Durability,
+) -> Result<u64, Error> {
+    let mut entry_count: u64 = 0;
+    let max_field_len = pool.max_payload_len();
+    while let Some(entry) = journal::read_entry(reader, max_field_len)? {
+        let message = journal::entry_message(&entry, tags);
+        let payload = lite3::encode_message(&message.tags, &message.data)?;
+        let timestamp_ns = match message.timestamp_ns {
+            Some(timestamp_ns) => timestamp_ns,
+            None => now_ns()?,
+        };
+        let options = AppendOptions::new(timestamp_ns, durability);
+        pool.append_with_options(payload.as_slice(), options)?;
+        entry_count = entry_count.saturating_add(1);
+    }
+    Ok(entry_count)
+}
+
+/// Idle polling schedule from `follow --poll-min/--poll-max/--poll-strategy`.
+fn follow_poll(
+    min: Option<&str>,
+    max: Option<&str>,
+    strategy: Option<PollStrategy>,
+) -> Result<FollowPoll, Error> {
+    let defaults = FollowPoll::default();
+    let min = min.map(parse_duration).transpose()?.unwrap_or(defaults.min);
+    let max = max.map(parse_duration).transpose()?.unwrap_or(defaults.max);
+    if min.is_zero() {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("--poll-min must be greater than zero")
+            .with_hint("Use a duration like 1ms."));
+    }
+    if min > max {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("--poll-min must not exceed --poll-max")
+            .with_hint("The default --poll-max is 50ms; raise it along with --poll-min."));
+    }
+    Ok(FollowPoll {
+        min,
+        max,
+        strategy: strategy.unwrap_or(defaults.strategy),
+    })
+}
+
+/// `--where` and `--where-jsonpath` predicates, ANDed together.
+fn compile_where(where_expr: &[String], where_jsonpath: &[String]) -> Result<Vec<JqFilter>, Error> {
+    let mut predicates = compile_filters(where_expr)?;
+    predicates.extend(compile_jsonpath_filters(where_jsonpath)?);
+    Ok(predicates)
+}
+
+/// Print whatever `--thread` is still holding back once follow stops.
+fn finish_follow_thread(thread_view: Option<&Arc<Mutex<ThreadView>>>) {
+    if let Some(view) = thread_view {
+        view.lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .finish();
+    }
+}
+
+/// Wait for in-flight `--exec` hooks and report failures counted under `--exec-errors continue`.
+fn finish_follow_exec(
+    exec_hook: Option<&Arc<Mutex<ExecHook>>>,
+    result: Result<RunOutcome, Error>,
+    pool_ref: &str,
+    color_mode: ColorMode,
+) -> Result<RunOutcome, Error> {
+    let Some(exec_hook) = exec_hook else {
+        return result;
+    };
+    let mut hook = exec_hook
+        .lock()
+        .unwrap_or_else(|poison| poison.into_inner());
+    let finished = hook.finish();
+    let outcome = result?;
+    finished?;
+    let failures = hook.failures();
+    if failures > 0 {
+        if let Some(time) = notice_time_now() {
+            let mut details = Map::new();
+            details.insert("failed_count".to_string(), json!(failures));
+            let notice = Notice {
+                kind: "exec_failures".to_string(),
+                time,
+                cmd: "follow".to_string(),
+                pool: pool_ref.to_string(),
+                message: format!("{failures} --exec commands exited non-zero"),
+                details,
+            };
+            emit_notice(&notice, color_mode);
+        }
+    }
+    Ok(outcome)
+}
+
+fn hook_json(hook: &hooks::Hook) -> Value {
+    json!({
+        "id": hook.id,
+        "pool": hook.pool,
+        "action": hook.action.kind(),
+        "target": hook.action.target(),
+        "where": hook.where_exprs,
+        "created_at": hook.created_at,
+    })
+}
+
+/// Most messages one hook handles per poll, so a backlog on one pool cannot starve the rest.
+const HOOKD_MAX_PER_POLL: u64 = 256;
+
+/// `hookd` loop: reload the registry, give every ready hook one pass, sleep, repeat.
+fn run_hookd(
+    pool_dir: &Path,
+    interval: Duration,
+    once: bool,
+    color_mode: ColorMode,
+) -> Result<RunOutcome, Error> {
+    let mut backoffs = std::collections::HashMap::<String, hooks::HookBackoff>::new();
+    let mut failed = false;
+    loop {
+        let registry = hooks::HookRegistry::load(pool_dir)?;
+        backoffs.retain(|id, _| registry.hooks().iter().any(|hook| &hook.id == id));
+        for hook in registry.hooks() {
+            let backoff = backoffs.entry(hook.id.clone()).or_default();
+            if !backoff.ready(Instant::now()) {
+                continue;
+            }
+            let result = run_hook_pass(pool_dir, hook, color_mode);
+            match result {
+                Ok(_) => backoff.succeeded(),
+                Err(err) => {
+                    failed = true;
+                    let retry_in = backoff.failed(Instant::now());
+                    if let Some(time) = notice_time_now() {
+                        let mut details = Map::new();
+                        details.insert("hook".to_string(), json!(hook.id));
+                        details.insert("failures".to_string(), json!(backoff.failures()));
+                        details.insert(
+                            "retry_in_ms".to_string(),
+                            json!(retry_in.as_millis() as u64),
+                        );
+                        let notice = Notice {
+                            kind: "hook_failed".to_string(),
+                            time,
+                            cmd: "hookd".to_string(),
+                            pool: hook.pool.clone(),
+                            message: err.message().unwrap_or("hook failed").to_string(),
+                            details,
+                        };
+                        emit_notice(&notice, color_mode);
+                    }
+                }
+            }
+        }
+        if once {
+            return Ok(if failed {
+                RunOutcome::with_code(1)
+            } else {
+                RunOutcome::ok()
+            });
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Deliver up to `HOOKD_MAX_PER_POLL` messages past the hook's cursor; returns how many fired.
+fn run_statsd(
+    pool_dir: &Path,
+    interval: Duration,
+    once: bool,
+    color_mode: ColorMode,
+) -> Result<RunOutcome, Error> {
+    let mut recorder = stats_history::StatsRecorder::new(pool_dir);
+    loop {
+        match recorder.tick() {
+            Ok(_) => {}
+            Err(err) if once => return Err(err),
+            Err(err) => {
+                if let Some(time) = notice_time_now() {
+                    let notice = Notice {
+                        kind: "stats_failed".to_string(),
+                        time,
+                        cmd: "statsd".to_string(),
+                        pool: stats_history::STATS_POOL.to_string(),
+                        message: err.message().unwrap_or("stats snapshot failed").to_string(),
+                        details: Map::new(),
+                    };
+                    emit_notice(&notice, color_mode);
+                }
+            }
+        }
+        if once {
+            return Ok(RunOutcome::ok());
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+fn run_hook_pass(pool_dir: &Path, hook: &hooks::Hook, color_mode: ColorMode) -> Result<u64, Error> {
+    let path = resolve_poolref(&hook.pool, pool_dir)?;
+    let pool = Pool::open_read_only(&path)?;
+    let predicates = compile_filters(&hook.where_exprs)?;
+    let cursor = hooks::hook_cursor(pool_dir, &hook.id);
+    let bounds = pool.bounds()?;
+    let (Some(oldest), Some(newest)) = (bounds.oldest_seq, bounds.newest_seq) else {
+        return Ok(0);
+    };
+    // A cursor past the newest seq means the pool was recreated; seqs restarted.
+    let handled = cursor.load()?.filter(|seq| *seq <= newest).unwrap_or(0);
+    let mut next = handled + 1;
+    if next < oldest {
+        if let Some(time) = notice_time_now() {
+            let mut details = Map::new();
+            details.insert("hook".to_string(), json!(hook.id));
+            details.insert("skipped_from".to_string(), json!(next));
+            details.insert("skipped_to".to_string(), json!(oldest - 1));
+            let notice = Notice {
+                kind: "hook_dropped".to_string(),
+                time,
+                cmd: "hookd".to_string(),
+                pool: hook.pool.clone(),
+                message: "messages were overwritten before the hook ran".to_string(),
+                details,
+            };
+            emit_notice(&notice, color_mode);
+        }
+        next = oldest;
+    }
+    let mut fired = 0;
+    for seq in next..=newest.min(next + HOOKD_MAX_PER_POLL - 1) {
+        let frame = match pool.get(seq) {
+            Ok(frame) => frame,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                cursor.store(seq)?;
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        if frame_control_kind(&frame)?.is_none() {
+            if let Some(message) = pool_message(pool, &frame)? {
+                if matches_all(&predicates, &message)? {
+                    hooks::deliver(&hook.action, &message)?;
+                    fired += 1;
+                }
+            }
+        }
+        cursor.store(seq)?;
+    }
+    Ok(fired)
+}
+
+struct ForwardConfig {
+    batch: usize,
+    required_tags: Vec<String>,
+    where_predicates: Vec<JqFilter>,
+    drain: bool,
+    color_mode: ColorMode,
+}
+
+/// Follow `pool` and deliver matches; returns the number of messages delivered.
+fn forward_pool(
+    pool: &Pool,
+    pool_ref: &str,
+    pool_path: &Path,
+    forwarder: &forward::Forwarder,
+    cursor_store: &forward::ForwardCursor,
+    cfg: &ForwardConfig,
+) -> Result<u64, Error> {
+    let mut header = pool.header_from_mmap()?;
+    let mut cursor = Cursor::new();
+    let mut delivered_seq = match cursor_store.load()? {
+        Some(seq) => {
+            cursor.seek_to(header.tail_off as usize);
+            seq
+        }
+        None => {
+            // Pin the starting point right away so a restart before the first
+            // delivery does not skip messages appended in between.
+            cursor_store.store(header.newest_seq)?;
+            cursor.seek_to(header.head_off as usize);
+            header.newest_seq
+        }
+    };
+    let mut presence = register_pool_user(
+        pool_path,
+        PoolRole::Reader,
+        "forward",
+        Some(cursor_store.path().display().to_string()),
+    )
+    .ok();
+    let mut last_seen_seq = delivered_seq;
+    let mut batch: Vec<Value> = Vec::new();
+    let mut delivered = 0u64;
+    let mut backoff = Duration::from_millis(1);
+    let max_backoff = Duration::from_millis(50);
+    let mut notify_handle = notify::open_for_path(pool_path);
+    let mut rotation = RotationWatch::new(pool_path);
+    let mut reopened: Option<Pool> = None;
+
+    loop {
+        let pool = reopened.as_ref().unwrap_or(pool);
+        match cursor.next(pool)? {
+            CursorResult::Message(frame) => {
+                if frame.seq <= last_seen_seq {
+                    continue;
+                }
+                if frame.seq > last_seen_seq + 1 {
+                    emit_forward_drop_notice(cfg, pool_ref, last_seen_seq, frame.seq);
+                }
+                // Control messages are never delivered; they only steer the reader.
+                if let Some(kind) = frame_control_kind(&frame)? {
+                    last_seen_seq = frame.seq;
+                    if react_to_control(
+                        "forward",
+                        pool_ref,
+                        &kind,
+                        frame.seq,
+                        &mut rotation,
+                        cfg.color_mode,
+                    )
+                    .is_some()
+                    {
+                        delivered += forward_flush(forwarder, cfg, &mut batch)?;
+                        forward_store_cursor(cursor_store, delivered_seq, last_seen_seq)?;
+                        return Ok(delivered);
+                    }
+                    continue;
+                }
+                if let Some(message) = pool_message(pool, &frame)? {
+                    if matches_required_tags(cfg.required_tags.as_slice(), &message)
+                        && matches_all(cfg.where_predicates.as_slice(), &message)?
+                    {
+                        batch.push(message);
+                    }
+                }
+                last_seen_seq = frame.seq;
+                if batch.len() >= cfg.batch {
+                    delivered += forward_flush(forwarder, cfg, &mut batch)?;
+                    delivered_seq =
+                        forward_store_cursor(cursor_store, delivered_seq, last_seen_seq)?;
+                }
+                backoff = Duration::from_millis(1);
+            }
+            CursorResult::WouldBlock => {
+                delivered += forward_flush(forwarder, cfg, &mut batch)?;
+                delivered_seq = forward_store_cursor(cursor_store, delivered_seq, last_seen_seq)?;
+                if let Some(presence) = presence.as_mut() {
+                    presence.update_cursor(delivered_seq);
+                }
+                if let Some(next) = rotation.poll(pool_path)? {
+                    // Seqs restart with the new file, so the persisted cursor restarts too.
+                    header = next.header_from_mmap()?;
+                    cursor = Cursor::new();
+                    cursor.seek_to(header.tail_off as usize);
+                    cursor_store.store(0)?;
+                    delivered_seq = 0;
+                    last_seen_seq = 0;
+                    notify_handle = notify::open_for_path(pool_path);
+                    reopened = Some(next);
+                    emit_control_notice(
+                        "forward",
+                        pool_ref,
+                        CONTROL_ROTATED,
+                        None,
+                        "reopened rotated pool",
+                        cfg.color_mode,
+                    );
+                    backoff = Duration::from_millis(1);
+                    continue;
+                }
+                if cfg.drain {
+                    return Ok(delivered);
+                }
+                match notify_handle
+                    .as_mut()
+                    .map(|handle| handle.wait(backoff))
+                    .unwrap_or(NotifyWait::Unavailable)
+                {
+                    NotifyWait::Signaled | NotifyWait::TimedOut => {}
+                    NotifyWait::Unavailable => {
+                        notify_handle = None;
+                        std::thread::sleep(backoff);
+                    }
+                }
+                backoff = std::cmp::min(backoff * 2, max_backoff);
+            }
+            CursorResult::FellBehind => {
+                header = pool.header_from_mmap()?;
+                cursor.seek_to(header.tail_off as usize);
+            }
+        }
+    }
+}
+
+fn forward_flush(
+    forwarder: &forward::Forwarder,
+    cfg: &ForwardConfig,
+    batch: &mut Vec<Value>,
+) -> Result<u64, Error> {
+    if batch.is_empty() {
+        return Ok(0);
+    }
+    forwarder.deliver(&forward::batch_body(batch, cfg.batch))?;
+    let count = batch.len() as u64;
+    batch.clear();
+    Ok(count)
+}
+
+/// Persist `last_seen_seq` once everything up to it has been delivered.
+fn forward_store_cursor(
+    cursor_store: &forward::ForwardCursor,
+    delivered_seq: u64,
+    last_seen_seq: u64,
+) -> Result<u64, Error> {
+    if last_seen_seq != delivered_seq {
+        cursor_store.store(last_seen_seq)?;
+    }
+    Ok(last_seen_seq)
+}
+
+fn emit_forward_drop_notice(
+    cfg: &ForwardConfig,
+    pool_ref: &str,
+    last_seen_seq: u64,
+    next_seen_seq: u64,
+) {
+    let Some(time) = notice_time_now() else {
+        return;
+    };
+    let dropped_count = next_seen_seq - last_seen_seq - 1;
+    let mut details = Map::new();
+    details.insert("last_seen_seq".to_string(), json!(last_seen_seq));
+    details.insert("next_seen_seq".to_string(), json!(next_seen_seq));
+    details.insert("dropped_count".to_string(), json!(dropped_count));
+    let notice = Notice {
+        kind: "drop".to_string(),
+        time,
+        cmd: "forward".to_string(),
+        pool: pool_ref.to_string(),
+        message: format!("dropped {dropped_count} messages before delivery"),
+        details,
+    };
+    emit_notice(&notice, cfg.color_mode);
+}
+
+/// Stream new messages into the FIFO whenever a reader is attached, until SIGINT/SIGTERM.
+/// Returns the number of messages written.
+fn bridge_pool(
+    pool: &Pool,
+    pool_ref: &str,
+    pool_path: &Path,
+    bridge: &fifo_bridge::FifoBridge,
+    color_mode: ColorMode,
+) -> Result<u64, Error> {
+    let stop = Arc::new(AtomicBool::new(false));
+    for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+        let _ = signal_hook::flag::register(signal, stop.clone());
+    }
+    let idle = Duration::from_millis(50);
+    let mut notify_handle = notify::open_for_path(pool_path);
+    let mut writer: Option<std::fs::File> = None;
+    let mut cursor = Cursor::new();
+    let mut last_seen_seq = 0;
+    let mut streamed = 0u64;
+    while !stop.load(Ordering::Relaxed) {
+        let Some(file) = writer.as_mut() else {
+            match bridge.try_connect()? {
+                Some(file) => {
+                    // New readers start at the live edge.
+                    let header = pool.header_from_mmap()?;
+                    cursor.seek_to(header.head_off as usize);
+                    last_seen_seq = header.newest_seq;
+                    emit_bridge_notice(
+                        bridge,
+                        pool_ref,
+                        "reader_connected",
+                        last_seen_seq,
+                        color_mode,
+                    );
+                    writer = Some(file);
+                }
+                None => std::thread::sleep(idle),
+            }
+            continue;
+        };
+        match cursor.next(pool)? {
+            CursorResult::Message(frame) => {
+                if frame.seq <= last_seen_seq {
+                    continue;
+                }
+                last_seen_seq = frame.seq;
+                let Some(message) = pool_message(pool, &frame)? else {
+                    continue;
+                };
+                if message_control_kind(&message).is_some() {
+                    continue;
+                }
+                let mut line = serde_json::to_vec(&message).map_err(|err| {
+                    Error::new(ErrorKind::Internal)
+                        .with_message("failed to encode message")
+                        .with_source(err)
+                })?;
+                line.push(b'\n');
+                match io::Write::write_all(file, &line) {
+                    Ok(()) => streamed += 1,
+                    Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {
+                        writer = None;
+                        emit_bridge_notice(
+                            bridge,
+                            pool_ref,
+                            "reader_disconnected",
+                            last_seen_seq,
+                            color_mode,
+                        );
+                    }
+                    Err(err) => {
+                        return Err(Error::new(ErrorKind::Io)
+                            .with_message("failed to write to FIFO")
+                            .with_path(bridge.path())
+                            .with_source(err));
+                    }
+                }
+            }
+            CursorResult::WouldBlock => {
+                if fifo_bridge::FifoBridge::reader_gone(file) {
+                    writer = None;
+                    emit_bridge_notice(
+                        bridge,
+                        pool_ref,
+                        "reader_disconnected",
+                        last_seen_seq,
+                        color_mode,
+                    );
+                    continue;
+                }
+                match notify_handle
+                    .as_mut()
+                    .map(|handle| handle.wait(idle))
+                    .unwrap_or(NotifyWait::Unavailable)
+                {
+                    NotifyWait::Signaled | NotifyWait::TimedOut => {}
+                    NotifyWait::Unavailable => {
+                        notify_handle = None;
+                        std::thread::sleep(idle);
+                    }
+                }
+            }
+            CursorResult::FellBehind => {
+                let header = pool.header_from_mmap()?;
+                cursor.seek_to(header.tail_off as usize);
+            }
+        }
+    }
+    Ok(streamed)
+}
+
+fn emit_bridge_notice(
+    bridge: &fifo_bridge::FifoBridge,
+    pool_ref: &str,
+    kind: &str,
+    seq: u64,
+    color_mode: ColorMode,
+) {
+    let Some(time) = notice_time_now() else {
+        return;
+    };
+    let mut details = Map::new();
+    details.insert(
+        "fifo".to_string(),
+        json!(bridge.path().display().to_string()),
+    );
+    details.insert("last_seen_seq".to_string(), json!(seq));
+    let notice = Notice {
+        kind: kind.to_string(),
+        time,
+        cmd: "bridge".to_string(),
+        pool: pool_ref.to_string(),
+        message: match kind {
+            "reader_connected" => "FIFO reader connected".to_string(),
+            _ => "FIFO reader disconnected; waiting for the next reader".to_string(),
+        },
+        details,
+    };
+    emit_notice(&notice, color_mode);
+}
+
 fn trim_tap_line_endings(raw_line: &str) -> String {
     raw_line.trim_end_matches(['\r', '\n']).to_string()
 }
@@ -1420,6 +4250,235 @@ where
     })
 }
 
+/// One wrapped-command run for `tap`; `--restart` calls it once per attempt.
+struct TapRun<'a> {
+    pool: &'a str,
+    command: &'a [String],
+    tag: &'a [String],
+    quiet: bool,
+    durability: Durability,
+    /// 1-based attempt number recorded on lifecycle messages in `--restart` mode.
+    attempt: Option<u32>,
+    /// PID the signal relay forwards to; `0` while no child is running.
+    child_pid: &'a AtomicI32,
+}
+
+struct TapAttempt {
+    exit_code: i32,
+    success: bool,
+}
+
+fn tap_run_once(pool_handle: &mut Pool, run: &TapRun<'_>) -> Result<TapAttempt, Error> {
+    let mut child = std::process::Command::new(&run.command[0])
+        .args(&run.command[1..])
+        .stdin(std::process::Stdio::inherit())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|err| tap_spawn_error(run.command, err))?;
+    // The relay installed by the caller forwards SIGINT/SIGTERM to this PID.
+    run.child_pid.store(child.id() as i32, Ordering::SeqCst);
+    let status_on_tty_stderr = io::stderr().is_terminal();
+    if status_on_tty_stderr {
+        eprintln!(
+            "tapping {} <- {}",
+            run.pool,
+            render_shell_agnostic_command(run.command)
+        );
+    }
+
+    let child_stdout = child.stdout.take().ok_or_else(|| {
+        Error::new(ErrorKind::Internal).with_message("tap child stdout pipe unavailable")
+    })?;
+    let child_stderr = child.stderr.take().ok_or_else(|| {
+        Error::new(ErrorKind::Internal).with_message("tap child stderr pipe unavailable")
+    })?;
+
+    let lifecycle_tags = vec!["lifecycle".to_string()];
+    let mut start = json!({
+        "kind": "start",
+        "cmd": run.command,
+    });
+    if let Some(attempt) = run.attempt {
+        start["attempt"] = json!(attempt);
+    }
+    if let Err(err) = tap_append_message(pool_handle, run.durability, &lifecycle_tags, &start) {
+        tap_terminate_child(&mut child);
+        return Err(err);
+    }
+
+    let start_time = Instant::now();
+    let (event_tx, event_rx) = mpsc::channel();
+    let stdout_reader = tap_spawn_reader(
+        child_stdout,
+        TapStream::Stdout,
+        !run.quiet,
+        event_tx.clone(),
+    );
+    let stderr_reader = tap_spawn_reader(child_stderr, TapStream::Stderr, !run.quiet, event_tx);
+
+    let mut reader_error: Option<Error> = None;
+    let mut child_status = None;
+    let mut child_rusage = None;
+    let mut line_count: u64 = 0;
+
+    while child_status.is_none() {
+        match event_rx.recv_timeout(Duration::from_millis(25)) {
+            Ok(TapEvent::Line { stream, raw_line }) => {
+                line_count = line_count.saturating_add(1);
+                if let Err(err) = tap_append_message(
+                    pool_handle,
+                    run.durability,
+                    run.tag,
+                    &json!({
+                        "kind": "line",
+                        "stream": stream.as_str(),
+                        "line": trim_tap_line_endings(&raw_line),
+                    }),
+                ) {
+                    tap_terminate_child(&mut child);
+                    return Err(err);
+                }
+            }
+            Ok(TapEvent::ReaderError(err)) => {
+                if reader_error.is_none() {
+                    reader_error = Some(err);
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {}
+        }
+        if let Some((status, rusage)) = tap_try_wait(&mut child).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed waiting for wrapped command")
+                .with_source(err)
+        })? {
+            child_status = Some(status);
+            child_rusage = rusage;
+        }
+    }
+
+    let child_status = child_status.expect("status set once loop exits");
+    run.child_pid.store(0, Ordering::SeqCst);
+    if stdout_reader.join().is_err() && reader_error.is_none() {
+        reader_error =
+            Some(Error::new(ErrorKind::Internal).with_message("tap stdout reader panicked"));
+    }
+    if stderr_reader.join().is_err() && reader_error.is_none() {
+        reader_error =
+            Some(Error::new(ErrorKind::Internal).with_message("tap stderr reader panicked"));
+    }
+
+    while let Ok(event) = event_rx.try_recv() {
+        match event {
+            TapEvent::Line { stream, raw_line } => {
+                line_count = line_count.saturating_add(1);
+                tap_append_message(
+                    pool_handle,
+                    run.durability,
+                    run.tag,
+                    &json!({
+                        "kind": "line",
+                        "stream": stream.as_str(),
+                        "line": trim_tap_line_endings(&raw_line),
+                    }),
+                )?;
+            }
+            TapEvent::ReaderError(err) => {
+                if reader_error.is_none() {
+                    reader_error = Some(err);
+                }
+            }
+        }
+    }
+
+    if let Some(err) = reader_error {
+        return Err(err);
+    }
+
+    let elapsed_ms = start_time.elapsed().as_millis().min(u64::MAX as u128) as u64;
+    let exit_code = if let Some(signal) = tap_exit_signal(&child_status) {
+        let signal_name = tap_signal_name(signal);
+        let core_dumped = tap_core_dumped(&child_status);
+        let mut exit = json!({
+            "kind": "exit",
+            "signal": signal_name,
+            "core_dumped": core_dumped,
+            "elapsed_ms": elapsed_ms,
+        });
+        if let Some(rusage) = &child_rusage {
+            exit["rusage"] = rusage.to_json();
+        }
+        if let Some(attempt) = run.attempt {
+            exit["attempt"] = json!(attempt);
+        }
+        tap_append_message(pool_handle, run.durability, &lifecycle_tags, &exit)?;
+        if status_on_tty_stderr {
+            eprintln!(
+                "tapped {line_count} lines ({}) -> {} signal {}{}",
+                format_tap_elapsed(elapsed_ms),
+                run.pool,
+                signal_name,
+                if core_dumped { " (core dumped)" } else { "" }
+            );
+        }
+        128 + signal
+    } else {
+        let code = child_status.code().unwrap_or(1);
+        let mut exit = json!({
+            "kind": "exit",
+            "code": code,
+            "elapsed_ms": elapsed_ms,
+        });
+        if let Some(rusage) = &child_rusage {
+            exit["rusage"] = rusage.to_json();
+        }
+        if let Some(attempt) = run.attempt {
+            exit["attempt"] = json!(attempt);
+        }
+        tap_append_message(pool_handle, run.durability, &lifecycle_tags, &exit)?;
+        if status_on_tty_stderr {
+            eprintln!(
+                "tapped {line_count} lines ({}) -> {} exit {}",
+                format_tap_elapsed(elapsed_ms),
+                run.pool,
+                code
+            );
+        }
+        code
+    };
+
+    Ok(TapAttempt {
+        exit_code,
+        success: child_status.success(),
+    })
+}
+
+fn emit_fetched_message(
+    message: Value,
+    projection: Option<Projection>,
+    program: Option<JqFilter>,
+    output: ProjectionFormat,
+    color_mode: ColorMode,
+) {
+    if let Some(program) = program {
+        for value in program.outputs(&message) {
+            emit_json(value, color_mode);
+        }
+        return;
+    }
+    match projection {
+        Some(projection) if output != ProjectionFormat::Json => {
+            if let Some(header) = projection.header() {
+                println!("{header}");
+            }
+            println!("{}", projection.render(&message));
+        }
+        Some(projection) => emit_json(projection.project(&message), color_mode),
+        None => emit_json(message, color_mode),
+    }
+}
+
 fn tap_spawn_error(command: &[String], err: std::io::Error) -> Error {
     if err.kind() == std::io::ErrorKind::NotFound {
         let hint_cmd = command
@@ -1443,6 +4502,79 @@ fn tap_terminate_child(child: &mut std::process::Child) {
     let _ = child.wait();
 }
 
+/// Resource usage of the reaped wrapped command, recorded on the `exit` lifecycle message.
+struct TapRusage {
+    max_rss_kb: u64,
+    user_cpu_ms: u64,
+    sys_cpu_ms: u64,
+}
+
+impl TapRusage {
+    fn to_json(&self) -> Value {
+        json!({
+            "max_rss_kb": self.max_rss_kb,
+            "user_cpu_ms": self.user_cpu_ms,
+            "sys_cpu_ms": self.sys_cpu_ms,
+        })
+    }
+}
+
+/// Non-blocking reap via `wait4`, which also reports the child's rusage.
+#[cfg(unix)]
+fn tap_try_wait(
+    child: &mut std::process::Child,
+) -> std::io::Result<Option<(std::process::ExitStatus, Option<TapRusage>)>> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut status = 0;
+    // SAFETY: `rusage` is plain data that wait4 fills in; zeroed is a valid initial value.
+    let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+    // SAFETY: both out-pointers are valid for the call; the pid is our unreaped child.
+    let reaped = unsafe {
+        libc::wait4(
+            child.id() as libc::pid_t,
+            &mut status,
+            libc::WNOHANG,
+            &mut rusage,
+        )
+    };
+    match reaped {
+        0 => Ok(None),
+        -1 => {
+            let err = std::io::Error::last_os_error();
+            if err.kind() == std::io::ErrorKind::Interrupted {
+                Ok(None)
+            } else {
+                Err(err)
+            }
+        }
+        _ => {
+            let cpu_ms = |tv: libc::timeval| tv.tv_sec as u64 * 1000 + tv.tv_usec as u64 / 1000;
+            // Linux reports ru_maxrss in KiB, macOS in bytes.
+            let max_rss_kb = if cfg!(target_os = "macos") {
+                rusage.ru_maxrss as u64 / 1024
+            } else {
+                rusage.ru_maxrss as u64
+            };
+            Ok(Some((
+                std::process::ExitStatus::from_raw(status),
+                Some(TapRusage {
+                    max_rss_kb,
+                    user_cpu_ms: cpu_ms(rusage.ru_utime),
+                    sys_cpu_ms: cpu_ms(rusage.ru_stime),
+                }),
+            )))
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn tap_try_wait(
+    child: &mut std::process::Child,
+) -> std::io::Result<Option<(std::process::ExitStatus, Option<TapRusage>)>> {
+    Ok(child.try_wait()?.map(|status| (status, None)))
+}
+
 #[cfg(unix)]
 fn tap_exit_signal(status: &std::process::ExitStatus) -> Option<i32> {
     use std::os::unix::process::ExitStatusExt;
@@ -1454,14 +4586,49 @@ fn tap_exit_signal(_: &std::process::ExitStatus) -> Option<i32> {
     None
 }
 
+#[cfg(unix)]
+fn tap_core_dumped(status: &std::process::ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.core_dumped()
+}
+
+#[cfg(not(unix))]
+fn tap_core_dumped(_: &std::process::ExitStatus) -> bool {
+    false
+}
+
+#[cfg(unix)]
 fn tap_signal_name(signal: i32) -> String {
-    match signal {
-        2 => "SIGINT".to_string(),
-        9 => "SIGKILL".to_string(),
-        11 => "SIGSEGV".to_string(),
-        15 => "SIGTERM".to_string(),
-        _ => format!("SIG{signal}"),
-    }
+    const NAMES: &[(i32, &str)] = &[
+        (libc::SIGHUP, "SIGHUP"),
+        (libc::SIGINT, "SIGINT"),
+        (libc::SIGQUIT, "SIGQUIT"),
+        (libc::SIGILL, "SIGILL"),
+        (libc::SIGTRAP, "SIGTRAP"),
+        (libc::SIGABRT, "SIGABRT"),
+        (libc::SIGBUS, "SIGBUS"),
+        (libc::SIGFPE, "SIGFPE"),
+        (libc::SIGKILL, "SIGKILL"),
+        (libc::SIGUSR1, "SIGUSR1"),
+        (libc::SIGSEGV, "SIGSEGV"),
+        (libc::SIGUSR2, "SIGUSR2"),
+        (libc::SIGPIPE, "SIGPIPE"),
+        (libc::SIGALRM, "SIGALRM"),
+        (libc::SIGTERM, "SIGTERM"),
+        (libc::SIGXCPU, "SIGXCPU"),
+        (libc::SIGXFSZ, "SIGXFSZ"),
+        (libc::SIGSYS, "SIGSYS"),
+    ];
+    NAMES
+        .iter()
+        .find(|(number, _)| *number == signal)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| format!("SIG{signal}"))
+}
+
+#[cfg(not(unix))]
+fn tap_signal_name(signal: i32) -> String {
+    format!("SIG{signal}")
 }
 
 fn format_tap_elapsed(elapsed_ms: u64) -> String {
@@ -1469,23 +4636,200 @@ fn format_tap_elapsed(elapsed_ms: u64) -> String {
 }
 
 #[cfg(unix)]
-fn tap_spawn_signal_forwarder(child_pid: i32) {
+fn tap_spawn_signal_forwarder(child_pid: Arc<AtomicI32>, interrupted: Arc<AtomicBool>) {
     let mut signals = match signal_hook::iterator::Signals::new([libc::SIGINT, libc::SIGTERM]) {
         Ok(signals) => signals,
         Err(_) => return,
     };
     std::thread::spawn(move || {
         for signal in signals.forever() {
-            tap_forward_signal(child_pid, signal);
+            interrupted.store(true, Ordering::SeqCst);
+            tap_forward_signal(child_pid.load(Ordering::SeqCst), signal);
         }
     });
 }
 
 #[cfg(not(unix))]
-fn tap_spawn_signal_forwarder(_child_pid: i32) {}
+fn tap_spawn_signal_forwarder(_child_pid: Arc<AtomicI32>, _interrupted: Arc<AtomicBool>) {}
 
 #[cfg(unix)]
 fn tap_forward_signal(child_pid: i32, signal: i32) {
+    // Between attempts there is no child; never signal pid 0 (our own process group).
+    if child_pid <= 0 {
+        return;
+    }
     // If the child already exited, `kill` may return ESRCH; ignore and continue.
     let _ = unsafe { libc::kill(child_pid, signal) };
 }
+
+/// One supervised child slot for `plasmite run`.
+struct RunChild {
+    spec: supervise::ProcessSpec,
+    child: Option<std::process::Child>,
+    started: Instant,
+    restarts: u32,
+    restart_at: Option<Instant>,
+    failed: bool,
+}
+
+fn run_supervisor(
+    exe: &Path,
+    processes: Vec<supervise::ProcessSpec>,
+    control: &mut Pool,
+) -> Result<RunOutcome, Error> {
+    let stop = Arc::new(AtomicBool::new(false));
+    for signal in [signal_hook::consts::SIGINT, signal_hook::consts::SIGTERM] {
+        let _ = signal_hook::flag::register(signal, stop.clone());
+    }
+    let mut children: Vec<RunChild> = processes
+        .into_iter()
+        .map(|spec| RunChild {
+            spec,
+            child: None,
+            started: Instant::now(),
+            restarts: 0,
+            restart_at: Some(Instant::now()),
+            failed: false,
+        })
+        .collect();
+
+    while !stop.load(Ordering::Relaxed) {
+        let now = Instant::now();
+        for slot in &mut children {
+            if slot.restart_at.is_some_and(|at| at <= now) {
+                slot.restart_at = None;
+                run_start_child(exe, slot, control)?;
+            }
+            let Some(child) = slot.child.as_mut() else {
+                continue;
+            };
+            let Some(status) = child.try_wait().map_err(|err| {
+                Error::new(ErrorKind::Io)
+                    .with_message(format!("failed to poll {}", slot.spec.name))
+                    .with_source(err)
+            })?
+            else {
+                continue;
+            };
+            let pid = child.id();
+            slot.child = None;
+            if slot.started.elapsed() >= supervise::STABLE_AFTER {
+                slot.restarts = 0;
+            }
+            run_event(
+                control,
+                &slot.spec.name,
+                json!({
+                    "kind": "exit",
+                    "pid": pid,
+                    "code": status.code(),
+                    "signal": tap_exit_signal(&status).map(tap_signal_name),
+                    "uptime_ms": slot.started.elapsed().as_millis() as u64,
+                }),
+            )?;
+            let plan = slot.spec.restart;
+            if !plan.policy.wants_restart(status.success()) {
+                slot.failed = !status.success();
+                continue;
+            }
+            match plan.next_delay(slot.restarts) {
+                Some(delay) => {
+                    slot.restarts += 1;
+                    slot.restart_at = Some(Instant::now() + delay);
+                    run_event(
+                        control,
+                        &slot.spec.name,
+                        json!({
+                            "kind": "restart",
+                            "restarts": slot.restarts,
+                            "delay_ms": delay.as_millis() as u64,
+                        }),
+                    )?;
+                }
+                None => {
+                    slot.failed = !status.success();
+                    eprintln!(
+                        "run: {} gave up after {} restarts",
+                        slot.spec.name, slot.restarts
+                    );
+                    run_event(
+                        control,
+                        &slot.spec.name,
+                        json!({ "kind": "give_up", "restarts": slot.restarts }),
+                    )?;
+                }
+            }
+        }
+        if children
+            .iter()
+            .all(|slot| slot.child.is_none() && slot.restart_at.is_none())
+        {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    for slot in &mut children {
+        if let Some(mut child) = slot.child.take() {
+            run_stop_child(&mut child);
+            run_event(
+                control,
+                &slot.spec.name,
+                json!({ "kind": "stop", "pid": child.id() }),
+            )?;
+        }
+    }
+    let code = if children.iter().any(|slot| slot.failed) {
+        1
+    } else {
+        0
+    };
+    Ok(RunOutcome::with_code(code))
+}
+
+fn run_start_child(exe: &Path, slot: &mut RunChild, control: &mut Pool) -> Result<(), Error> {
+    let child = std::process::Command::new(exe)
+        .args(&slot.spec.argv)
+        .stdin(std::process::Stdio::null())
+        .spawn()
+        .map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message(format!("failed to start {}", slot.spec.name))
+                .with_source(err)
+        })?;
+    slot.started = Instant::now();
+    run_event(
+        control,
+        &slot.spec.name,
+        json!({
+            "kind": "start",
+            "pid": child.id(),
+            "argv": slot.spec.argv,
+            "restarts": slot.restarts,
+        }),
+    )?;
+    slot.child = Some(child);
+    Ok(())
+}
+
+fn run_event(control: &mut Pool, name: &str, mut data: Value) -> Result<(), Error> {
+    data["name"] = json!(name);
+    let tags = vec!["run".to_string(), format!("run:{name}")];
+    tap_append_message(control, Durability::Fast, &tags, &data)
+}
+
+/// Ask a child to exit (SIGTERM on unix), escalating to kill after a grace period.
+fn run_stop_child(child: &mut std::process::Child) {
+    #[cfg(unix)]
+    {
+        tap_forward_signal(child.id() as i32, libc::SIGTERM);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = child.try_wait() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+    tap_terminate_child(child);
+}
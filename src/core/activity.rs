@@ -0,0 +1,139 @@
+//! Purpose: Count messages per fixed time bucket for `plasmite activity`.
+//! Exports: `ActivityHistogram`, `bucket_activity`, `MAX_ACTIVITY_BUCKETS`.
+//! Role: Header-only scan (timestamps, never payloads) over the retained range.
+//! Invariants: Buckets are aligned to multiples of `bucket_ns` since the Unix epoch and dense
+//! (empty buckets are zero), so gaps show up as runs of zeros.
+//! Invariants: The scan starts at the first frame at or after `since_ns` (binary search), so
+//! its cost scales with the window, not the pool; frames outside the window are counted as skipped.
+use crate::core::error::{Error, ErrorKind};
+use crate::core::pool::{Pool, TimeNearest};
+
+/// Upper bound on buckets per histogram; keeps `--bucket 1s --since 30d` from allocating wildly.
+pub const MAX_ACTIVITY_BUCKETS: u64 = 100_000;
+
+/// Frames read per `get_range` batch while scanning.
+const SCAN_BATCH: u64 = 4096;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActivityHistogram {
+    pub bucket_ns: u64,
+    /// Start of the first bucket (aligned down to `bucket_ns`).
+    pub start_ns: u64,
+    pub counts: Vec<u64>,
+    pub first_seq: Option<u64>,
+    pub last_seq: Option<u64>,
+    /// Frames in the scanned seq range whose timestamp fell outside the window.
+    pub skipped: u64,
+}
+
+impl ActivityHistogram {
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    pub fn peak(&self) -> u64 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn bucket_start_ns(&self, index: usize) -> u64 {
+        self.start_ns + index as u64 * self.bucket_ns
+    }
+}
+
+/// Bucket message timestamps in `[since_ns, until_ns]`.
+///
+/// `since_ns = None` starts at the oldest retained frame. Assumes timestamps mostly move
+/// forward (hybrid-timestamp pools guarantee it); out-of-order frames are still counted when
+/// they land inside the window.
+pub fn bucket_activity(
+    pool: &Pool,
+    since_ns: Option<u64>,
+    until_ns: u64,
+    bucket_ns: u64,
+) -> Result<ActivityHistogram, Error> {
+    if bucket_ns == 0 {
+        return Err(Error::new(ErrorKind::Usage).with_message("bucket width must be positive"));
+    }
+    let bounds = pool.bounds()?;
+    let since_ns = match (since_ns, bounds.oldest_seq) {
+        (Some(since_ns), _) => since_ns,
+        (None, Some(oldest)) => pool.get(oldest)?.timestamp_ns,
+        (None, None) => until_ns,
+    };
+    let start_ns = since_ns - since_ns % bucket_ns;
+    let bucket_count = until_ns.saturating_sub(start_ns) / bucket_ns + 1;
+    if bucket_count > MAX_ACTIVITY_BUCKETS {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!(
+                "window needs {bucket_count} buckets (max {MAX_ACTIVITY_BUCKETS})"
+            ))
+            .with_hint("Use a wider --bucket or a shorter --since."));
+    }
+    let mut histogram = ActivityHistogram {
+        bucket_ns,
+        start_ns,
+        counts: vec![0; bucket_count as usize],
+        first_seq: None,
+        last_seq: None,
+        skipped: 0,
+    };
+    let Some(newest) = bounds.newest_seq else {
+        return Ok(histogram);
+    };
+    let mut next = match pool.get_at_time(since_ns, TimeNearest::After) {
+        Ok(frame) => frame.seq,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(histogram),
+        Err(err) => return Err(err),
+    };
+    while next <= newest {
+        let frames = pool.get_range(next, next.saturating_add(SCAN_BATCH - 1).min(newest))?;
+        let Some(last) = frames.last() else {
+            break;
+        };
+        next = last.seq + 1;
+        for frame in frames {
+            if frame.timestamp_ns < since_ns || frame.timestamp_ns > until_ns {
+                histogram.skipped += 1;
+                continue;
+            }
+            let index = ((frame.timestamp_ns - start_ns) / bucket_ns) as usize;
+            histogram.counts[index] += 1;
+            histogram.first_seq.get_or_insert(frame.seq);
+            histogram.last_seq = Some(frame.seq);
+        }
+    }
+    Ok(histogram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bucket_activity;
+    use crate::core::error::ErrorKind;
+    use crate::core::pool::{Pool, PoolOptions};
+
+    #[test]
+    fn buckets_are_aligned_dense_and_windowed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        for timestamp_ns in [105, 110, 150, 310, 320, 330] {
+            pool.append_with_timestamp(b"x", timestamp_ns)
+                .expect("append");
+        }
+
+        let all = bucket_activity(&pool, None, 399, 100).expect("all");
+        assert_eq!(all.start_ns, 100);
+        assert_eq!(all.counts, vec![3, 0, 3]);
+        assert_eq!((all.first_seq, all.last_seq), (Some(1), Some(6)));
+        assert_eq!((all.total(), all.peak()), (6, 3));
+
+        let windowed = bucket_activity(&pool, Some(140), 315, 50).expect("window");
+        assert_eq!(windowed.start_ns, 100);
+        assert_eq!(windowed.counts, vec![0, 1, 0, 0, 1]);
+        assert_eq!(windowed.first_seq, Some(3));
+        assert_eq!(windowed.skipped, 2);
+
+        let err = bucket_activity(&pool, Some(0), u64::MAX / 2, 1).expect_err("too many");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+}
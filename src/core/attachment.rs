@@ -0,0 +1,81 @@
+//! Purpose: Pack a message envelope and one binary attachment into a frame payload.
+//! Exports: `ATTACHMENT_REF_KEY`, `pack`, `unpack`, `attachment_ref`, `references_attachment`.
+//! Role: Pure helpers; the API appends packed payloads with `FRAME_FLAG_ATTACHMENT`, and
+//! readers split them before decoding the envelope with the pool's codec.
+//! Invariants: A flagged payload is `[envelope_len u32 LE][envelope][attachment bytes]`; the
+//! envelope is an ordinary codec payload, so chunking and dedupe see one opaque byte string.
+//! Invariants: `.data` references the attachment with a `{"$attachment": {"bytes": N}}` object;
+//! the bytes themselves never pass through JSON except as base64 at output boundaries.
+use serde_json::{Value, json};
+
+use crate::core::error::{Error, ErrorKind};
+use crate::core::frame::FRAME_FLAG_ATTACHMENT;
+
+pub const ATTACHMENT_REF_KEY: &str = "$attachment";
+const ENVELOPE_LEN_BYTES: usize = 4;
+
+/// Payload bytes for `envelope` followed by `attachment`.
+pub fn pack(envelope: &[u8], attachment: &[u8]) -> Result<Vec<u8>, Error> {
+    let envelope_len = u32::try_from(envelope.len())
+        .map_err(|_| Error::new(ErrorKind::Usage).with_message("message envelope is too large"))?;
+    let mut payload = Vec::with_capacity(ENVELOPE_LEN_BYTES + envelope.len() + attachment.len());
+    payload.extend_from_slice(&envelope_len.to_le_bytes());
+    payload.extend_from_slice(envelope);
+    payload.extend_from_slice(attachment);
+    Ok(payload)
+}
+
+/// Split a frame payload into its envelope and attachment; unflagged payloads are all envelope.
+pub fn unpack(flags: u32, payload: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
+    if flags & FRAME_FLAG_ATTACHMENT == 0 {
+        return Ok((payload, None));
+    }
+    let corrupt = || Error::new(ErrorKind::Corrupt).with_message("invalid attachment payload");
+    let len_bytes = payload.get(..ENVELOPE_LEN_BYTES).ok_or_else(corrupt)?;
+    let envelope_len = u32::from_le_bytes(len_bytes.try_into().expect("4 bytes")) as usize;
+    let rest = &payload[ENVELOPE_LEN_BYTES..];
+    if envelope_len > rest.len() {
+        return Err(corrupt());
+    }
+    let (envelope, attachment) = rest.split_at(envelope_len);
+    Ok((envelope, Some(attachment)))
+}
+
+/// The `.data` value that stands in for an attachment of `len` bytes.
+pub fn attachment_ref(len: usize) -> Value {
+    json!({ ATTACHMENT_REF_KEY: { "bytes": len } })
+}
+
+/// Whether `data` holds an attachment reference anywhere.
+pub fn references_attachment(data: &Value) -> bool {
+    match data {
+        Value::Object(map) => {
+            map.get(ATTACHMENT_REF_KEY).is_some_and(Value::is_object)
+                || map.values().any(references_attachment)
+        }
+        Value::Array(items) => items.iter().any(references_attachment),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attachment_ref, pack, references_attachment, unpack};
+    use crate::core::frame::FRAME_FLAG_ATTACHMENT;
+    use serde_json::json;
+
+    #[test]
+    fn packed_payloads_split_back_into_envelope_and_attachment() {
+        let payload = pack(b"{\"meta\":{}}", &[0, 159, 255]).expect("pack");
+        let (envelope, attachment) = unpack(FRAME_FLAG_ATTACHMENT, &payload).expect("unpack");
+        assert_eq!(envelope, b"{\"meta\":{}}");
+        assert_eq!(attachment, Some(&[0u8, 159, 255][..]));
+        assert_eq!(unpack(0, b"{}").expect("plain"), (&b"{}"[..], None));
+        assert!(unpack(FRAME_FLAG_ATTACHMENT, &[9, 0, 0, 0, 1]).is_err());
+
+        assert!(references_attachment(
+            &json!({ "file": "a.png", "blob": attachment_ref(3) })
+        ));
+        assert!(!references_attachment(&json!({ "$attachment": 3 })));
+    }
+}
@@ -0,0 +1,77 @@
+//! Purpose: Standard padded base64 shared by attachments and the Lite3 JSON encoder.
+//! Exports: `base64_encode`, `base64_decode`.
+//! Role: Pure helpers; the one place the crate turns bytes into base64 text and back.
+//! Invariants: RFC 4648 `+/` alphabet with `=` padding; decoding rejects unpadded input,
+//! padding before the last quad, and any character outside the alphabet.
+use crate::core::error::{Error, ErrorKind};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard padded base64 (RFC 4648 `+/` alphabet).
+pub fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (u32::from(chunk[0]) << 16)
+            | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8)
+            | u32::from(*chunk.get(2).unwrap_or(&0));
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> shift) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Inverse of `base64_encode`; surrounding whitespace is ignored.
+pub fn base64_decode(text: &str) -> Result<Vec<u8>, Error> {
+    let invalid = || {
+        Error::new(ErrorKind::Usage)
+            .with_message("invalid base64")
+            .with_hint("Encode bytes as standard padded base64 (`+/` alphabet, `=` padding).")
+    };
+    let text = text.trim().as_bytes();
+    if text.len() % 4 != 0 {
+        return Err(invalid());
+    }
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    for (index, quad) in text.chunks(4).enumerate() {
+        let last = index + 1 == text.len() / 4;
+        let padding = quad.iter().rev().take_while(|byte| **byte == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return Err(invalid());
+        }
+        let mut n = 0u32;
+        for byte in &quad[..4 - padding] {
+            let value = ALPHABET
+                .iter()
+                .position(|candidate| candidate == byte)
+                .ok_or_else(invalid)?;
+            n = (n << 6) | value as u32;
+        }
+        n <<= 6 * padding as u32;
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        out.extend_from_slice(&bytes[..3 - padding]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_decode, base64_encode};
+
+    #[test]
+    fn base64_round_trips_every_padding_length() {
+        for len in 0..8u8 {
+            let bytes = (0..len).map(|b| b.wrapping_mul(37)).collect::<Vec<_>>();
+            let encoded = base64_encode(&bytes);
+            assert_eq!(base64_decode(&encoded).expect("decode"), bytes);
+        }
+        assert_eq!(base64_encode(b"hi!"), "aGkh");
+        assert!(base64_decode("aGk").is_err());
+        assert!(base64_decode("a=kh").is_err());
+        assert!(base64_decode("aG*h").is_err());
+    }
+}
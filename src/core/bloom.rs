@@ -0,0 +1,109 @@
+//! Purpose: Per-frame bloom filters over a message's string values, for skipping frames in
+//! filtered scans without decoding them.
+//! Exports: `BLOOM_NONE`, `envelope_bloom`, `strings_bloom`, `might_contain`.
+//! Role: Pools created with `with_bloom_filter` stamp each message's frame header with the
+//! filter of its string values; readers test the string literals a `--where` predicate
+//! requires before running the predicate itself.
+//! Invariants: A filter is 128 bits with three probes per string. `BLOOM_NONE` (0) means the
+//! frame has no filter (older frames, pools without the flag, continuation chunks, messages
+//! with no or too many strings) and never rules a frame out.
+//! Invariants: No false negatives: `might_contain` is false only when the exact string value
+//! is absent from both `meta` and `data`.
+use serde_json::Value;
+
+use crate::core::hash::fnv1a64;
+use crate::core::lite3::Lite3DocRef;
+
+pub const BLOOM_NONE: u128 = 0;
+const BLOOM_PROBES: u64 = 3;
+/// Past this many distinct-ish strings a 128-bit filter is mostly ones; store none instead.
+const MAX_BLOOM_STRINGS: usize = 48;
+
+/// Filter for an encoded message envelope (`json` selects the JSON codec over Lite3).
+/// Undecodable envelopes get `BLOOM_NONE` rather than an error; the filter is only a hint.
+pub fn envelope_bloom(envelope: &[u8], json: bool) -> u128 {
+    let value = if json {
+        serde_json::from_slice::<Value>(envelope).ok()
+    } else {
+        Lite3DocRef::new(envelope)
+            .to_json(false)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+    };
+    let Some(value) = value else {
+        return BLOOM_NONE;
+    };
+    let mut strings = Vec::new();
+    if !collect_strings(&value, &mut strings) {
+        return BLOOM_NONE;
+    }
+    strings_bloom(strings)
+}
+
+/// Filter holding exactly `strings`.
+pub fn strings_bloom<'a>(strings: impl IntoIterator<Item = &'a str>) -> u128 {
+    strings
+        .into_iter()
+        .fold(BLOOM_NONE, |bloom, value| bloom | probe_bits(value))
+}
+
+/// Whether a frame stamped with `bloom` can hold the string value `value`.
+pub fn might_contain(bloom: u128, value: &str) -> bool {
+    let bits = probe_bits(value);
+    bloom == BLOOM_NONE || bloom & bits == bits
+}
+
+/// Push every string value under `value`; false once there are too many to filter usefully.
+fn collect_strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) -> bool {
+    match value {
+        Value::String(text) => {
+            out.push(text);
+            out.len() <= MAX_BLOOM_STRINGS
+        }
+        Value::Array(items) => items.iter().all(|item| collect_strings(item, out)),
+        Value::Object(map) => map.values().all(|item| collect_strings(item, out)),
+        _ => true,
+    }
+}
+
+fn probe_bits(value: &str) -> u128 {
+    // FNV-1a, then double hashing to derive the probes.
+    let hash = fnv1a64(value.as_bytes());
+    let (h1, h2) = (hash, hash.rotate_left(32) | 1);
+    (0..BLOOM_PROBES).fold(0u128, |bits, probe| {
+        bits | 1u128 << (h1.wrapping_add(probe.wrapping_mul(h2)) % 128)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BLOOM_NONE, envelope_bloom, might_contain, strings_bloom};
+    use serde_json::json;
+
+    #[test]
+    fn filters_have_no_false_negatives_and_reject_most_absent_strings() {
+        let envelope = json!({
+            "meta": { "tags": ["deploy"] },
+            "data": { "level": "error", "items": [{ "host": "web-1" }], "count": 3 }
+        })
+        .to_string();
+        let bloom = envelope_bloom(envelope.as_bytes(), true);
+        assert_ne!(bloom, BLOOM_NONE);
+        for present in ["deploy", "error", "web-1"] {
+            assert!(might_contain(bloom, present), "{present}");
+        }
+        let absent = (0..100)
+            .filter(|index| !might_contain(bloom, &format!("absent-{index}")))
+            .count();
+        assert!(absent > 90, "only {absent} of 100 absent strings ruled out");
+
+        assert!(might_contain(BLOOM_NONE, "anything"));
+        assert_eq!(envelope_bloom(b"not json", true), BLOOM_NONE);
+        let crowded = (0..100).map(|index| index.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            envelope_bloom(json!({ "data": crowded }).to_string().as_bytes(), true),
+            BLOOM_NONE
+        );
+        assert_eq!(strings_bloom(["a"]), strings_bloom(["a", "a"]));
+    }
+}
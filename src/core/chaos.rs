@@ -0,0 +1,158 @@
+//! Purpose: Developer-only fault injection for resilience tests of retries, mirrors, and buffering.
+//! Exports: `ChaosConfig`, `install`, `delay`, `before_append`.
+//! Role: The hidden `--chaos-*` CLI flags install one process-wide config; `Pool` appends call
+//! `before_append` before taking the append lock, and `serve` delays read requests by `delay`.
+//! Invariants: Without the `chaos` cargo feature every hook is a no-op and `install` fails.
+//! Invariants: Drop decisions come from a seeded splitmix64 stream indexed by append count, so
+//! with the same seed the Nth append of a process fails the same way on every run.
+//! Invariants: Injected failures are `Busy` and happen before the pool is touched.
+use std::path::Path;
+use std::time::Duration;
+
+use crate::core::error::{Error, ErrorKind};
+
+/// Faults to inject; the default injects nothing.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChaosConfig {
+    /// Fraction of appends (0.0-1.0) that fail with an injected `Busy` error.
+    pub drop_appends: f64,
+    /// Latency added before every append (and every `serve` read request).
+    pub delay: Duration,
+    /// Seed for the drop decisions.
+    pub seed: u64,
+}
+
+impl ChaosConfig {
+    /// Whether the append with zero-based index `draw` is dropped.
+    fn drops_append(&self, draw: u64) -> bool {
+        if self.drop_appends <= 0.0 {
+            return false;
+        }
+        let bits = splitmix64(self.seed.wrapping_add(draw.wrapping_mul(GOLDEN_GAMMA)));
+        ((bits >> 11) as f64 / (1u64 << 53) as f64) < self.drop_appends
+    }
+}
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(GOLDEN_GAMMA);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Install `config` for the rest of the process. Fails on builds without the `chaos` feature,
+/// on a drop rate outside 0.0-1.0, and when a config is already installed.
+pub fn install(config: ChaosConfig) -> Result<(), Error> {
+    if !(0.0..=1.0).contains(&config.drop_appends) {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!(
+                "invalid --chaos-drop-appends value: {}",
+                config.drop_appends
+            ))
+            .with_hint("Use a fraction between 0 and 1, e.g. --chaos-drop-appends 0.01."));
+    }
+    enabled::install(config)
+}
+
+/// Latency the installed config adds (zero when none is installed).
+pub fn delay() -> Duration {
+    enabled::config()
+        .map(|config| config.delay)
+        .unwrap_or_default()
+}
+
+/// Sleep for the configured delay, then fail this append if the seeded stream says so.
+pub(crate) fn before_append(path: &Path) -> Result<(), Error> {
+    let Some(config) = enabled::config() else {
+        return Ok(());
+    };
+    if !config.delay.is_zero() {
+        std::thread::sleep(config.delay);
+    }
+    if config.drops_append(enabled::next_draw()) {
+        return Err(Error::new(ErrorKind::Busy)
+            .with_message("chaos: injected append failure")
+            .with_path(path)
+            .with_hint("This process runs with --chaos-drop-appends; retry the append."));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "chaos")]
+mod enabled {
+    use std::sync::OnceLock;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::ChaosConfig;
+    use crate::core::error::{Error, ErrorKind};
+
+    static CONFIG: OnceLock<ChaosConfig> = OnceLock::new();
+    static DRAWS: AtomicU64 = AtomicU64::new(0);
+
+    pub(super) fn install(config: ChaosConfig) -> Result<(), Error> {
+        CONFIG.set(config).map_err(|_| {
+            Error::new(ErrorKind::Internal).with_message("chaos config is already installed")
+        })
+    }
+
+    pub(super) fn config() -> Option<&'static ChaosConfig> {
+        CONFIG.get()
+    }
+
+    pub(super) fn next_draw() -> u64 {
+        DRAWS.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+#[cfg(not(feature = "chaos"))]
+mod enabled {
+    use super::ChaosConfig;
+    use crate::core::error::{Error, ErrorKind};
+
+    pub(super) fn install(_config: ChaosConfig) -> Result<(), Error> {
+        Err(Error::new(ErrorKind::Usage)
+            .with_message("chaos flags are not available in this build")
+            .with_hint("Rebuild with `--features chaos`; fault injection is for test builds only."))
+    }
+
+    pub(super) fn config() -> Option<&'static ChaosConfig> {
+        None
+    }
+
+    pub(super) fn next_draw() -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChaosConfig;
+
+    #[test]
+    fn drop_decisions_are_seeded_and_track_the_rate() {
+        let config = ChaosConfig {
+            drop_appends: 0.25,
+            seed: 7,
+            ..ChaosConfig::default()
+        };
+        let pattern = |config: ChaosConfig| {
+            (0..10_000)
+                .map(|draw| config.drops_append(draw))
+                .collect::<Vec<_>>()
+        };
+        let first = pattern(config);
+        assert_eq!(first, pattern(config), "same seed, same failures");
+        assert_ne!(first, pattern(ChaosConfig { seed: 8, ..config }));
+        let dropped = first.iter().filter(|dropped| **dropped).count();
+        assert!((2_300..2_700).contains(&dropped), "{dropped}");
+
+        let never = ChaosConfig::default();
+        let always = ChaosConfig {
+            drop_appends: 1.0,
+            ..config
+        };
+        assert!((0..1_000).all(|draw| !never.drops_append(draw) && always.drops_append(draw)));
+    }
+}
@@ -0,0 +1,106 @@
+//! Purpose: Encode oversized payloads as linked chunk frames and decode chunk headers.
+//! Exports: `CHUNK_HEADER_LEN`, `MIN_MAX_FRAME`, `ChunkPosition`, `chunk_position`,
+//! `chunk_body`, `split`.
+//! Role: Pure helpers; `Pool` decides when to split (its frame size limit), writes the chunks
+//! under one append lock, and reads sibling frames back in `Pool::join_chunks`.
+//! Invariants: A chunked message is `count` consecutive seqs, each flagged `FRAME_FLAG_CHUNK`
+//! and prefixed by `[index u32 LE][count u32 LE]`; bodies concatenate to the original payload.
+//! Invariants: The message is addressed by its last chunk's seq, so a reader that reaches the
+//! last chunk knows every earlier one is committed. Earlier chunks are continuations.
+use crate::core::error::{Error, ErrorKind};
+use crate::core::frame::FRAME_FLAG_CHUNK;
+
+pub const CHUNK_HEADER_LEN: usize = 8;
+/// Smallest frame size limit a pool accepts, so each chunk carries a useful body.
+pub const MIN_MAX_FRAME: usize = 64;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChunkPosition {
+    pub index: u32,
+    pub count: u32,
+}
+
+impl ChunkPosition {
+    pub fn is_last(self) -> bool {
+        self.index + 1 == self.count
+    }
+
+    /// Seq of the first chunk, given this chunk's seq.
+    pub fn first_seq(self, seq: u64) -> u64 {
+        seq.saturating_sub(u64::from(self.index))
+    }
+
+    /// Seq the whole message is read at (its last chunk), given this chunk's seq.
+    pub fn message_seq(self, seq: u64) -> u64 {
+        seq + u64::from(self.count - 1 - self.index)
+    }
+}
+
+/// Where a frame sits in a chunk run; `None` for ordinary frames.
+pub fn chunk_position(flags: u32, payload: &[u8]) -> Result<Option<ChunkPosition>, Error> {
+    if flags & FRAME_FLAG_CHUNK == 0 {
+        return Ok(None);
+    }
+    let corrupt = || Error::new(ErrorKind::Corrupt).with_message("invalid chunk frame header");
+    let header = payload.get(..CHUNK_HEADER_LEN).ok_or_else(corrupt)?;
+    let index = u32::from_le_bytes(header[..4].try_into().expect("4 bytes"));
+    let count = u32::from_le_bytes(header[4..].try_into().expect("4 bytes"));
+    if count < 2 || index >= count {
+        return Err(corrupt());
+    }
+    Ok(Some(ChunkPosition { index, count }))
+}
+
+/// Message bytes carried by one chunk frame's payload.
+pub fn chunk_body(payload: &[u8]) -> &[u8] {
+    payload.get(CHUNK_HEADER_LEN..).unwrap_or_default()
+}
+
+/// Chunk frame payloads (header + body, each at most `max_frame` bytes) for `payload`.
+pub fn split(payload: &[u8], max_frame: usize) -> Result<Vec<Vec<u8>>, Error> {
+    let body_len = max_frame.saturating_sub(CHUNK_HEADER_LEN).max(1);
+    let count = u32::try_from(payload.len().div_ceil(body_len))
+        .map_err(|_| Error::new(ErrorKind::Usage).with_message("message has too many chunks"))?;
+    Ok(payload
+        .chunks(body_len)
+        .enumerate()
+        .map(|(index, body)| {
+            let mut chunk = Vec::with_capacity(CHUNK_HEADER_LEN + body.len());
+            chunk.extend_from_slice(&(index as u32).to_le_bytes());
+            chunk.extend_from_slice(&count.to_le_bytes());
+            chunk.extend_from_slice(body);
+            chunk
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkPosition, chunk_body, chunk_position, split};
+    use crate::core::frame::FRAME_FLAG_CHUNK;
+
+    #[test]
+    fn split_chunks_round_trip_through_their_headers() {
+        let payload = (0..200u8).collect::<Vec<_>>();
+        let chunks = split(&payload, 72).expect("split");
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 72));
+        let positions = chunks
+            .iter()
+            .map(|chunk| chunk_position(FRAME_FLAG_CHUNK, chunk).expect("header"))
+            .collect::<Vec<_>>();
+        assert_eq!(positions[3], Some(ChunkPosition { index: 3, count: 4 }));
+        assert!(positions[3].expect("last").is_last());
+        assert_eq!(positions[1].expect("middle").message_seq(11), 13);
+        assert_eq!(positions[3].expect("last").first_seq(13), 10);
+        let joined = chunks
+            .iter()
+            .flat_map(|chunk| chunk_body(chunk).to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(joined, payload);
+
+        assert_eq!(chunk_position(0, b"{}").expect("plain"), None);
+        assert!(chunk_position(FRAME_FLAG_CHUNK, &[0; 4]).is_err());
+        assert!(chunk_position(FRAME_FLAG_CHUNK, &[2, 0, 0, 0, 2, 0, 0, 0]).is_err());
+    }
+}
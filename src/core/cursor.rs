@@ -3,9 +3,12 @@
 //! Role: Read-side API used by CLI commands (fetch/follow) without exposing raw offsets.
 //! Invariants: Never returns `Writing` or invalid frames; treats them as non-visible.
 //! Invariants: Detects overwrite (fell-behind) and resynchronizes to the current tail.
-use crate::core::error::{Error, ErrorKind};
-use crate::core::frame::{self, FRAME_HEADER_LEN, FrameHeader, FrameState};
+//! Invariants: Frame bytes are read through `RingView`, so torn or hostile offsets fall behind
+//! instead of indexing past the ring.
+use crate::core::error::Error;
+use crate::core::frame::{self, FRAME_HEADER_LEN, FrameState};
 use crate::core::pool::Pool;
+use crate::core::view::RingView;
 
 #[derive(Debug, PartialEq)]
 pub enum CursorResult<'a> {
@@ -19,6 +22,8 @@ pub struct FrameRef<'a> {
     pub seq: u64,
     pub timestamp_ns: u64,
     pub flags: u32,
+    /// Bloom filter over the message's string values (`core::bloom`); 0 when absent.
+    pub bloom: u128,
     pub payload: &'a [u8],
 }
 
@@ -51,10 +56,8 @@ impl Cursor {
             return Ok(CursorResult::WouldBlock);
         }
 
-        let ring_size = header.ring_size as usize;
-        if ring_size == 0 {
-            return Err(Error::new(ErrorKind::Corrupt).with_message("ring size is zero"));
-        }
+        let ring = RingView::new(pool.mmap(), header)?;
+        let ring_size = ring.ring_size();
 
         let tail = header.tail_off as usize;
         let head = header.head_off as usize;
@@ -72,13 +75,14 @@ impl Cursor {
         }
 
         loop {
-            let read = read_frame_at(
-                pool.mmap(),
-                header.ring_offset as usize,
-                ring_size,
-                self.next_off,
-            )?;
+            let read = read_frame_at(&ring, self.next_off)?;
             match read {
+                // A wrap marker at offset 0 would send the scan back to itself forever.
+                ReadResult::Wrap if self.next_off == 0 => {
+                    self.next_off = tail;
+                    self.last_seq = 0;
+                    return Ok(CursorResult::FellBehind);
+                }
                 ReadResult::Wrap => {
                     self.next_off = 0;
                     continue;
@@ -116,20 +120,11 @@ pub(crate) enum ReadResult<'a> {
 }
 
 pub(crate) fn read_frame_at<'a>(
-    mmap: &'a [u8],
-    ring_offset: usize,
-    ring_size: usize,
+    ring: &RingView<'a>,
     offset: usize,
 ) -> Result<ReadResult<'a>, Error> {
-    let start = ring_offset + offset;
-    let end = start + FRAME_HEADER_LEN;
-    if end > ring_offset + ring_size {
+    let Ok(h1) = ring.frame_header(offset) else {
         return Ok(ReadResult::FellBehind);
-    }
-
-    let h1 = match FrameHeader::decode(&mmap[start..end]) {
-        Ok(header) => header,
-        Err(_) => return Ok(ReadResult::FellBehind),
     };
 
     match h1.state {
@@ -138,6 +133,7 @@ pub(crate) fn read_frame_at<'a>(
         _ => return Ok(ReadResult::WouldBlock),
     }
 
+    let ring_size = ring.ring_size();
     if h1.validate(ring_size).is_err() {
         return Ok(ReadResult::FellBehind);
     }
@@ -146,17 +142,13 @@ pub(crate) fn read_frame_at<'a>(
         Some(len) => len,
         None => return Ok(ReadResult::FellBehind),
     };
-    if offset + frame_len > ring_size {
+    if ring.bytes(offset, frame_len).is_err() {
         return Ok(ReadResult::FellBehind);
     }
 
-    let payload_start = start + FRAME_HEADER_LEN;
-    let payload_end = payload_start + h1.payload_len as usize;
-    let payload = &mmap[payload_start..payload_end];
-
-    let marker_start = payload_end;
-    let marker_end = marker_start + frame::FRAME_COMMIT_MARKER_LEN;
-    if &mmap[marker_start..marker_end] != frame::FRAME_COMMIT_MARKER.as_slice() {
+    let payload_start = offset + FRAME_HEADER_LEN;
+    let payload = ring.bytes(payload_start, h1.payload_len as usize)?;
+    if !ring.has_commit_marker(payload_start + payload.len()) {
         return Ok(ReadResult::FellBehind);
     }
 
@@ -170,6 +162,7 @@ pub(crate) fn read_frame_at<'a>(
             seq: h1.seq,
             timestamp_ns: h1.timestamp_ns,
             flags: h1.flags,
+            bloom: h1.bloom,
             payload,
         },
         next_off,
@@ -207,6 +200,7 @@ mod tests {
     use crate::core::frame::{self, FRAME_HEADER_LEN, FrameHeader, FrameState};
     use crate::core::lite3;
     use crate::core::pool::{Pool, PoolOptions};
+    use crate::core::view::RingView;
     use serde_json::json;
 
     #[test]
@@ -220,7 +214,7 @@ mod tests {
         buf[FRAME_HEADER_LEN..FRAME_HEADER_LEN + payload.len()].copy_from_slice(payload.as_slice());
         // Intentionally omit the commit marker.
 
-        let result = read_frame_at(&buf, 0, ring_size, 0).expect("read");
+        let result = read_frame_at(&RingView::ring_only(&buf), 0).expect("read");
         assert!(matches!(result, ReadResult::FellBehind));
     }
 
@@ -231,7 +225,7 @@ mod tests {
         let header = FrameHeader::new(FrameState::Wrap, 0, 0, 0, 0, 0);
         buf[0..FRAME_HEADER_LEN].copy_from_slice(&header.encode());
 
-        let result = read_frame_at(&buf, 0, ring_size, 0).expect("read");
+        let result = read_frame_at(&RingView::ring_only(&buf), 0).expect("read");
         assert!(matches!(result, ReadResult::Wrap));
     }
 
@@ -241,7 +235,7 @@ mod tests {
         let mut buf = vec![0u8; ring_size];
         buf[0..4].copy_from_slice(b"NOPE");
 
-        let result = read_frame_at(&buf, 0, ring_size, 0).expect("read");
+        let result = read_frame_at(&RingView::ring_only(&buf), 0).expect("read");
         assert!(matches!(result, ReadResult::FellBehind));
     }
 
@@ -254,7 +248,7 @@ mod tests {
         buf[0..FRAME_HEADER_LEN].copy_from_slice(&header.encode());
         buf[FRAME_HEADER_LEN..FRAME_HEADER_LEN + payload.len()].copy_from_slice(payload.as_slice());
 
-        let result = read_frame_at(&buf, 0, ring_size, 0).expect("read");
+        let result = read_frame_at(&RingView::ring_only(&buf), 0).expect("read");
         assert!(matches!(result, ReadResult::WouldBlock));
     }
 
@@ -268,7 +262,7 @@ mod tests {
         buf[0..FRAME_HEADER_LEN].copy_from_slice(&header.encode());
         write_payload_and_marker(&mut buf, payload.as_slice());
 
-        let result = read_frame_at(&buf, 0, ring_size, 0).expect("read");
+        let result = read_frame_at(&RingView::ring_only(&buf), 0).expect("read");
         assert!(matches!(result, ReadResult::FellBehind));
     }
 
@@ -282,7 +276,7 @@ mod tests {
         buf[0..FRAME_HEADER_LEN].copy_from_slice(&header.encode());
         write_payload_and_marker(&mut buf, payload.as_slice());
 
-        let result = read_frame_at(&buf, 0, ring_size, 0).expect("read");
+        let result = read_frame_at(&RingView::ring_only(&buf), 0).expect("read");
         assert!(matches!(result, ReadResult::FellBehind));
     }
 
@@ -2,11 +2,14 @@
 //! Exports: `ErrorKind`, `Error`, `to_exit_code`.
 //! Role: Central error vocabulary for JSON stderr surfaces and exit code mapping.
 //! Invariants: `ErrorKind` names are part of the v0.0.1 contract; change with care.
-//! Invariants: `Error` may carry optional context (path/seq/offset) + a source error.
+//! Invariants: `Error` may carry optional context (path/seq/offset/request_id) + a source error.
+//! Invariants: `Error::to_json` is the one envelope shape shared by CLI stderr and the C ABI.
 use std::error::Error as StdError;
 use std::fmt;
 use std::path::PathBuf;
 
+use serde_json::{Map, Value, json};
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ErrorKind {
     Internal,
@@ -27,6 +30,7 @@ pub struct Error {
     path: Option<PathBuf>,
     seq: Option<u64>,
     offset: Option<u64>,
+    request_id: Option<String>,
     source: Option<Box<dyn StdError + Send + Sync>>,
 }
 
@@ -39,6 +43,7 @@ impl Error {
             path: None,
             seq: None,
             offset: None,
+            request_id: None,
             source: None,
         }
     }
@@ -67,6 +72,11 @@ impl Error {
         self.offset
     }
 
+    /// Server-assigned request ID for errors returned by a remote call.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
     pub fn with_message(mut self, message: impl Into<String>) -> Self {
         self.message = Some(message.into());
         self
@@ -92,10 +102,74 @@ impl Error {
         self
     }
 
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
     pub fn with_source(mut self, source: impl StdError + Send + Sync + 'static) -> Self {
         self.source = Some(Box::new(source));
         self
     }
+
+    /// Message text, falling back to a generic phrase for the kind.
+    pub fn display_message(&self) -> String {
+        if let Some(message) = &self.message {
+            return message.clone();
+        }
+        match self.kind {
+            ErrorKind::Internal => "internal error",
+            ErrorKind::Usage => "usage error",
+            ErrorKind::NotFound => "not found",
+            ErrorKind::AlreadyExists => "already exists",
+            ErrorKind::Busy => "resource is busy",
+            ErrorKind::Permission => "permission denied",
+            ErrorKind::Corrupt => "corrupt data",
+            ErrorKind::Io => "i/o error",
+        }
+        .to_string()
+    }
+
+    /// Source-chain messages, outermost first.
+    pub fn causes(&self) -> Vec<String> {
+        let mut causes = Vec::new();
+        let mut cur = StdError::source(self);
+        while let Some(source) = cur {
+            causes.push(source.to_string());
+            cur = source.source();
+        }
+        causes
+    }
+
+    /// Structured `{"error": {...}}` envelope (kind, message, hint, path, seq, offset, request_id, causes).
+    pub fn to_json(&self) -> Value {
+        let mut inner = Map::new();
+        inner.insert("kind".to_string(), json!(format!("{:?}", self.kind)));
+        inner.insert("message".to_string(), json!(self.display_message()));
+        if let Some(hint) = &self.hint {
+            inner.insert("hint".to_string(), json!(hint));
+        }
+        if let Some(path) = &self.path {
+            inner.insert("path".to_string(), json!(path.display().to_string()));
+        }
+        if let Some(seq) = self.seq {
+            inner.insert("seq".to_string(), json!(seq));
+        }
+        if let Some(offset) = self.offset {
+            inner.insert("offset".to_string(), json!(offset));
+        }
+        if let Some(request_id) = &self.request_id {
+            inner.insert("request_id".to_string(), json!(request_id));
+        }
+        let causes = self.causes();
+        if !causes.is_empty() {
+            inner.insert("causes".to_string(), json!(causes));
+        }
+
+        let mut outer = Map::new();
+        outer.insert("error".to_string(), Value::Object(inner));
+        Value::Object(outer)
+    }
 }
 
 impl fmt::Display for Error {
@@ -113,6 +187,9 @@ impl fmt::Display for Error {
         if let Some(offset) = self.offset {
             write!(f, " (offset: {offset})")?;
         }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request_id: {request_id})")?;
+        }
         Ok(())
     }
 }
@@ -140,7 +217,8 @@ pub fn to_exit_code(kind: ErrorKind) -> i32 {
 
 #[cfg(test)]
 mod tests {
-    use super::{ErrorKind, to_exit_code};
+    use super::{Error, ErrorKind, to_exit_code};
+    use serde_json::json;
 
     #[test]
     fn exit_code_mapping_is_stable() {
@@ -159,4 +237,24 @@ mod tests {
             assert_eq!(to_exit_code(kind), code);
         }
     }
+
+    #[test]
+    fn to_json_includes_context_and_causes() {
+        let err = Error::new(ErrorKind::Corrupt)
+            .with_hint("Run plasmite doctor.")
+            .with_path("/tmp/p.plasmite")
+            .with_seq(7)
+            .with_source(std::io::Error::other("short read"));
+        assert_eq!(
+            err.to_json(),
+            json!({"error": {
+                "kind": "Corrupt",
+                "message": "corrupt data",
+                "hint": "Run plasmite doctor.",
+                "path": "/tmp/p.plasmite",
+                "seq": 7,
+                "causes": ["short read"],
+            }})
+        );
+    }
 }
@@ -1,13 +1,18 @@
 //! Purpose: Centralize pool format versioning and migration guidance.
-//! Exports: `POOL_FORMAT_VERSION`, `SUPPORTED_POOL_FORMAT_VERSIONS`, `pool_version_error`.
+//! Exports: `POOL_FORMAT_VERSION`, `POOL_FORMAT_VERSION_BASE`, `SUPPORTED_POOL_FORMAT_VERSIONS`,
+//! `pool_version_error`, `pool_feature_error`.
 //! Role: Shared policy for gating on-disk compatibility across open/validation paths.
 //! Invariants: Version list is additive; bump only for incompatible on-disk changes.
 //! Invariants: Migration guidance stays actionable and stable for users.
+//! Invariants: Version 4 marks pools whose header sets incompatible feature flags; pools without
+//! them are still written as version 3 so binaries that predate feature flags can open them.
 
 use crate::core::error::{Error, ErrorKind};
 
-pub const POOL_FORMAT_VERSION: u32 = 3;
-pub const SUPPORTED_POOL_FORMAT_VERSIONS: &[u32] = &[POOL_FORMAT_VERSION];
+pub const POOL_FORMAT_VERSION: u32 = 4;
+/// Layout without feature flags; written for pools that set none.
+pub const POOL_FORMAT_VERSION_BASE: u32 = 3;
+pub const SUPPORTED_POOL_FORMAT_VERSIONS: &[u32] = &[POOL_FORMAT_VERSION_BASE, POOL_FORMAT_VERSION];
 
 pub fn pool_version_error(detected: u32) -> Error {
     let supported = SUPPORTED_POOL_FORMAT_VERSIONS
@@ -23,3 +28,13 @@ pub fn pool_version_error(detected: u32) -> Error {
             "Upgrade plasmite or migrate the pool (export/import). Run `plasmite doctor <pool>` for guidance.",
         )
 }
+
+pub fn pool_feature_error(unknown_flags: u64) -> Error {
+    Error::new(ErrorKind::Usage)
+        .with_message(format!(
+            "pool uses unsupported incompatible features (flags {unknown_flags:#x})"
+        ))
+        .with_hint(
+            "Upgrade plasmite to a version that supports this pool, or export it with the binary that created it.",
+        )
+}
@@ -1,9 +1,13 @@
 //! Purpose: Define frame header layout plus helpers for sizing/alignment and validation.
-//! Exports: `FrameHeader`, `FrameState`, `FRAME_HEADER_LEN`, `FRAME_COMMIT_MARKER`, `frame_total_len`.
+//! Exports: `FrameHeader`, `FrameState`, `FRAME_HEADER_LEN`, `FRAME_COMMIT_MARKER`, `frame_total_len`,
+//! frame flag bits plus `frame_flag_names` / `parse_frame_flags`.
 //! Role: Shared encoding/validation primitives used by planner, pool, cursor, and validator.
 //! Invariants: Frame headers are fixed-size (64 bytes) and encoded little-endian.
+//! Invariants: Header bytes 48..64 hold an optional bloom filter (`core::bloom`); 0 means none.
 //! Invariants: Payload validation enforces canonical Lite3 encoding when required.
 //! Invariants: Committed frames include an 8-byte commit marker written after the payload.
+//! Invariants: Frame flags are opaque to storage: low bits are named markers, bits 16..31 are
+//! free-form user flags, and the remaining reserved bits are rejected on append.
 use crate::core::error::{Error, ErrorKind};
 #[cfg(test)]
 use crate::core::lite3;
@@ -14,6 +18,88 @@ pub const FRAME_COMMIT_MARKER: [u8; 8] = *b"PLSMCMIT";
 pub const FRAME_COMMIT_MARKER_LEN: usize = FRAME_COMMIT_MARKER.len();
 pub const MAX_PAYLOAD_ABS: usize = 256 * 1024 * 1024;
 
+/// Payload bytes are compressed by the producer.
+pub const FRAME_FLAG_COMPRESSED: u32 = 1 << 0;
+/// Payload bytes are encrypted by the producer.
+pub const FRAME_FLAG_ENCRYPTED: u32 = 1 << 1;
+/// Message is a control/out-of-band record rather than application data.
+pub const FRAME_FLAG_CONTROL: u32 = 1 << 2;
+/// Frame holds one piece of a message split across frames (see `core::chunk`). Set by the
+/// pool itself, never by callers.
+pub const FRAME_FLAG_CHUNK: u32 = 1 << 3;
+/// Payload carries a binary attachment after the message envelope (see `core::attachment`).
+/// Set by attachment appends, never through `--flag`.
+pub const FRAME_FLAG_ATTACHMENT: u32 = 1 << 4;
+/// Bits 16..31, named `user0`..`user15`, are left to applications.
+pub const FRAME_FLAGS_USER: u32 = 0xFFFF_0000;
+/// Every bit an append may set; the rest are reserved for future named flags.
+pub const FRAME_FLAGS_SETTABLE: u32 =
+    FRAME_FLAG_COMPRESSED | FRAME_FLAG_ENCRYPTED | FRAME_FLAG_CONTROL | FRAME_FLAGS_USER;
+
+const NAMED_FRAME_FLAGS: [(u32, &str); 5] = [
+    (FRAME_FLAG_COMPRESSED, "compressed"),
+    (FRAME_FLAG_ENCRYPTED, "encrypted"),
+    (FRAME_FLAG_CONTROL, "control"),
+    (FRAME_FLAG_CHUNK, "chunk"),
+    (FRAME_FLAG_ATTACHMENT, "attachment"),
+];
+
+/// Symbolic names for `flags`, lowest bit first; unnamed reserved bits render as `bitN`.
+pub fn frame_flag_names(flags: u32) -> Vec<String> {
+    (0..32)
+        .map(|bit| 1u32 << bit)
+        .filter(|mask| flags & mask != 0)
+        .map(|mask| {
+            let bit = mask.trailing_zeros();
+            match NAMED_FRAME_FLAGS.iter().find(|(named, _)| *named == mask) {
+                Some((_, name)) => (*name).to_string(),
+                None if mask & FRAME_FLAGS_USER != 0 => format!("user{}", bit - 16),
+                None => format!("bit{bit}"),
+            }
+        })
+        .collect()
+}
+
+/// Inverse of `frame_flag_names` (`compressed`, `control`, `user3`, `bit5`, ...).
+/// Reserved `bitN` names parse, but appends still reject them.
+pub fn parse_frame_flags<S: AsRef<str>>(names: &[S]) -> Result<u32, Error> {
+    let mut flags = 0;
+    for name in names {
+        let name = name.as_ref().trim();
+        let named = NAMED_FRAME_FLAGS
+            .iter()
+            .find(|(_, known)| *known == name)
+            .map(|(mask, _)| *mask);
+        let user = name
+            .strip_prefix("user")
+            .and_then(|index| index.parse::<u32>().ok())
+            .filter(|index| *index < 16)
+            .map(|index| 1u32 << (16 + index));
+        let raw = name
+            .strip_prefix("bit")
+            .and_then(|bit| bit.parse::<u32>().ok())
+            .filter(|bit| *bit < 32)
+            .map(|bit| 1u32 << bit);
+        flags |= named.or(user).or(raw).ok_or_else(|| {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("unknown frame flag '{name}'"))
+                .with_hint("Use compressed, encrypted, control, or user0..user15.")
+        })?;
+    }
+    Ok(flags)
+}
+
+/// Reject reserved bits before they reach disk.
+pub fn check_settable_flags(flags: u32) -> Result<(), Error> {
+    let reserved = flags & !FRAME_FLAGS_SETTABLE;
+    if reserved != 0 {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!("reserved frame flag bits set: {reserved:#x}"))
+            .with_hint("Use compressed, encrypted, control, or the user bits 16..31."));
+    }
+    Ok(())
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum FrameState {
     Empty = 0,
@@ -44,6 +130,8 @@ pub struct FrameHeader {
     pub payload_len: u32,
     pub payload_len_xor: u32,
     pub crc32c: u32,
+    /// String-value bloom filter (`core::bloom`); 0 when the frame carries none.
+    pub bloom: u128,
 }
 
 impl FrameHeader {
@@ -64,6 +152,7 @@ impl FrameHeader {
             payload_len,
             payload_len_xor: payload_len ^ 0xFFFF_FFFF,
             crc32c,
+            bloom: 0,
         }
     }
 
@@ -78,6 +167,7 @@ impl FrameHeader {
         write_u32(&mut buf, 32, self.payload_len);
         write_u32(&mut buf, 36, self.payload_len_xor);
         write_u32(&mut buf, 40, self.crc32c);
+        buf[48..64].copy_from_slice(&self.bloom.to_le_bytes());
         buf
     }
 
@@ -96,6 +186,8 @@ impl FrameHeader {
         let payload_len = read_u32(buf, 32);
         let payload_len_xor = read_u32(buf, 36);
         let crc32c = read_u32(buf, 40);
+        let mut bloom = [0u8; 16];
+        bloom.copy_from_slice(&buf[48..64]);
 
         Ok(Self {
             state,
@@ -106,6 +198,7 @@ impl FrameHeader {
             payload_len,
             payload_len_xor,
             crc32c,
+            bloom: u128::from_le_bytes(bloom),
         })
     }
 
@@ -252,6 +345,26 @@ mod tests {
         headers
     }
 
+    #[test]
+    fn frame_flag_names_round_trip() {
+        use super::{
+            FRAME_FLAG_CONTROL, check_settable_flags, frame_flag_names, parse_frame_flags,
+        };
+
+        let flags = parse_frame_flags(&["control", "user0", "user15"]).expect("parse");
+        assert_eq!(flags, FRAME_FLAG_CONTROL | 1 << 16 | 1 << 31);
+        assert_eq!(frame_flag_names(flags), vec!["control", "user0", "user15"]);
+        assert_eq!(frame_flag_names(1 << 5), vec!["bit5"]);
+        assert_eq!(parse_frame_flags(&["bit5"]).expect("raw"), 1 << 5);
+        assert!(frame_flag_names(0).is_empty());
+        assert!(parse_frame_flags(&["user16"]).is_err());
+        assert!(check_settable_flags(flags).is_ok());
+        assert_eq!(
+            check_settable_flags(1 << 5).expect_err("reserved").kind(),
+            ErrorKind::Usage
+        );
+    }
+
     #[test]
     fn alignment_is_8_bytes() {
         assert_eq!(align8(0), Some(0));
@@ -0,0 +1,27 @@
+//! Purpose: 64-bit FNV-1a shared by dedupe, idempotency keys, bloom probes, and diff keys.
+//! Exports: `fnv1a64`.
+//! Role: Pure helper; the one place the crate computes a non-cryptographic content hash.
+//! Invariants: Output is stable across versions and platforms; dedupe hashes and bloom bits
+//! are persisted in pool files, so the constants must never change.
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a over `bytes`; `b""` → `0xcbf29ce484222325`.
+pub fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fnv1a64;
+
+    #[test]
+    fn matches_the_published_fnv1a_vectors() {
+        assert_eq!(fnv1a64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a64(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -0,0 +1,55 @@
+//! Purpose: Lowercase hex encoding shared by checksums, ids, tokens, and byte dumps.
+//! Exports: `hex_encode`, `hex_encode_spaced`, `hex_decode`.
+//! Role: Pure helpers; the one place the crate turns bytes into hex text and back.
+//! Invariants: Encoding is lowercase with two digits per byte; decoding accepts either case
+//! and rejects any length other than exactly two digits per output byte.
+use std::fmt::Write;
+
+/// `[0x00, 0xff]` → `"00ff"`.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
+/// `[0x00, 0xff]` → `"00 ff"`, for human-readable byte dumps.
+pub fn hex_encode_spaced(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 3);
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
+/// Exactly `N` bytes from `2 * N` hex digits; `None` for any other length or a non-hex digit.
+pub fn hex_decode<const N: usize>(text: &str) -> Option<[u8; N]> {
+    if text.len() != N * 2 {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (index, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(text.get(index * 2..index * 2 + 2)?, 16).ok()?;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hex_decode, hex_encode, hex_encode_spaced};
+
+    #[test]
+    fn encodes_and_decodes_round_trip() {
+        assert_eq!(hex_encode(&[0, 15, 255]), "000fff");
+        assert_eq!(hex_encode_spaced(&[0, 15, 255]), "00 0f ff");
+        assert_eq!(hex_encode_spaced(&[]), "");
+        assert_eq!(hex_decode::<3>("000fFF"), Some([0, 15, 255]));
+        assert_eq!(hex_decode::<2>("000f0f"), None);
+        assert_eq!(hex_decode::<1>("0g"), None);
+        assert_eq!(hex_decode::<1>("é"), None);
+    }
+}
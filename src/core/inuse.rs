@@ -0,0 +1,309 @@
+//! Purpose: Detect whether a pool file is in use and who is using it.
+//! Exports: `PoolUser`, `pool_users`, `writer_lock_held`, `PoolRole`, `Registration`,
+//! `RegistrationGuard`, `register`, `registrations`, `registry_dir`.
+//! Role: Best-effort guard for delete and data source for `pool who`; never blocks.
+//! Invariants: Process discovery is Linux-only (`/proc/<pid>/fd`); other platforms report none.
+//! Invariants: The calling process is never reported as a user of the pool.
+//! Invariants: Registrations live in `<pool>.who/`, one JSON file per handle, removed on drop;
+//! files left by dead processes are ignored and pruned when listed.
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{Error, ErrorKind};
+
+/// Minimum spacing between cursor rewrites for one registration.
+const CURSOR_WRITE_INTERVAL: Duration = Duration::from_secs(1);
+
+static NEXT_REGISTRATION: AtomicU64 = AtomicU64::new(0);
+
+/// A process that currently has the pool file open.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolUser {
+    pub pid: u32,
+    pub command: String,
+}
+
+impl std::fmt::Display for PoolUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.command.is_empty() {
+            write!(f, "pid {}", self.pid)
+        } else {
+            write!(f, "pid {} ({})", self.pid, self.command)
+        }
+    }
+}
+
+/// Processes (other than this one) holding an open descriptor to `path`, sorted by pid.
+#[cfg(target_os = "linux")]
+pub fn pool_users(path: &Path) -> Vec<PoolUser> {
+    let Ok(target) = std::fs::canonicalize(path) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let own_pid = std::process::id();
+    let mut users = Vec::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if pid == own_pid {
+            continue;
+        }
+        // Other users' processes are unreadable without privileges; skip them quietly.
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let holds = fds
+            .flatten()
+            .any(|fd| std::fs::read_link(fd.path()).is_ok_and(|link| link == target));
+        if holds {
+            let command = std::fs::read_to_string(entry.path().join("comm"))
+                .map(|comm| comm.trim().to_string())
+                .unwrap_or_default();
+            users.push(PoolUser { pid, command });
+        }
+    }
+    users.sort_by_key(|user| user.pid);
+    users
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pool_users(_path: &Path) -> Vec<PoolUser> {
+    Vec::new()
+}
+
+/// True when another handle holds the append lock right now.
+pub fn writer_lock_held(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    match file.try_lock_exclusive() {
+        Ok(()) => {
+            let _ = FileExt::unlock(&file);
+            false
+        }
+        Err(err) => err.raw_os_error() == fs2::lock_contended_error().raw_os_error(),
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PoolRole {
+    Reader,
+    Writer,
+}
+
+/// One registered reader or writer, as stored in the sidecar.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Registration {
+    pub pid: u32,
+    pub role: PoolRole,
+    /// The registering command, e.g. `follow` or `tap`.
+    pub command: String,
+    pub started_ns: u64,
+    /// Last seq this handle has read or committed to, when it tracks one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<u64>,
+    /// Durable cursor identity (for example a forward cursor file), when named.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Keeps a registration file alive; dropping it removes the file.
+pub struct RegistrationGuard {
+    path: PathBuf,
+    record: Registration,
+    last_write: Instant,
+}
+
+impl RegistrationGuard {
+    /// Record the handle's position; rewrites are throttled to `CURSOR_WRITE_INTERVAL`.
+    pub fn update_cursor(&mut self, seq: u64) {
+        if self.record.cursor == Some(seq) || self.last_write.elapsed() < CURSOR_WRITE_INTERVAL {
+            return;
+        }
+        self.record.cursor = Some(seq);
+        // Presence is advisory; a failed rewrite leaves the previous position visible.
+        let _ = write_registration(&self.path, &self.record);
+        self.last_write = Instant::now();
+    }
+}
+
+impl Drop for RegistrationGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+        if let Some(dir) = self.path.parent() {
+            // Only succeeds once the last registration is gone.
+            let _ = std::fs::remove_dir(dir);
+        }
+    }
+}
+
+/// Sidecar directory holding registrations for the pool at `pool_path`.
+pub fn registry_dir(pool_path: &Path) -> PathBuf {
+    let mut name = pool_path.as_os_str().to_os_string();
+    name.push(".who");
+    PathBuf::from(name)
+}
+
+pub fn register(
+    pool_path: &Path,
+    role: PoolRole,
+    command: &str,
+    name: Option<String>,
+) -> Result<RegistrationGuard, Error> {
+    let dir = registry_dir(pool_path);
+    std::fs::create_dir_all(&dir).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to create pool registry directory")
+            .with_path(&dir)
+            .with_source(err)
+    })?;
+    let pid = std::process::id();
+    let id = NEXT_REGISTRATION.fetch_add(1, Ordering::Relaxed);
+    let record = Registration {
+        pid,
+        role,
+        command: command.to_string(),
+        started_ns: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(0),
+        cursor: None,
+        name,
+    };
+    let path = dir.join(format!("{pid}-{id}.json"));
+    write_registration(&path, &record)?;
+    Ok(RegistrationGuard {
+        path,
+        record,
+        last_write: Instant::now(),
+    })
+}
+
+/// Live registrations for the pool, ordered by start time; stale files are pruned.
+pub fn registrations(pool_path: &Path) -> Vec<Registration> {
+    let Ok(entries) = std::fs::read_dir(registry_dir(pool_path)) else {
+        return Vec::new();
+    };
+    let mut records = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(record) = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Registration>(&bytes).ok())
+        else {
+            continue;
+        };
+        if !process_alive(record.pid) {
+            let _ = std::fs::remove_file(&path);
+            continue;
+        }
+        records.push(record);
+    }
+    records.sort_by_key(|record| (record.started_ns, record.pid));
+    records
+}
+
+fn write_registration(path: &Path, record: &Registration) -> Result<(), Error> {
+    let bytes = serde_json::to_vec(record).map_err(|err| {
+        Error::new(ErrorKind::Internal)
+            .with_message("failed to encode pool registration")
+            .with_source(err)
+    })?;
+    // Write-then-rename so listers never observe a torn record.
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, bytes)
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to write pool registration")
+                .with_path(path)
+                .with_source(err)
+        })
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 probes existence; EPERM still means the process exists.
+    let rc = unsafe { libc::kill(pid, 0) };
+    rc == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PoolRole, register, registrations, registry_dir};
+
+    #[test]
+    fn registrations_track_cursor_and_disappear_on_drop() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pool = dir.path().join("who.plasmite");
+        let mut guard =
+            register(&pool, PoolRole::Reader, "follow", Some("c1".into())).expect("register");
+        let listed = registrations(&pool);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].pid, std::process::id());
+        assert_eq!(listed[0].role, PoolRole::Reader);
+        assert_eq!(listed[0].cursor, None);
+
+        guard.last_write -= super::CURSOR_WRITE_INTERVAL;
+        guard.update_cursor(42);
+        assert_eq!(registrations(&pool)[0].cursor, Some(42));
+
+        drop(guard);
+        assert!(registrations(&pool).is_empty());
+        assert!(!registry_dir(&pool).exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn pool_users_reports_other_processes_but_not_self() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("busy.plasmite");
+        std::fs::write(&path, b"pool").expect("write");
+        let _own = std::fs::File::open(&path).expect("open");
+        assert!(super::pool_users(&path).is_empty());
+        assert!(!super::writer_lock_held(&path));
+
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg("exec 3<\"$0\"; exec sleep 5")
+            .arg(&path)
+            .spawn()
+            .expect("spawn");
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+        let mut users = super::pool_users(&path);
+        while users.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            users = super::pool_users(&path);
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].pid, child.id());
+    }
+}
@@ -0,0 +1,341 @@
+//! Purpose: Optional rolling sidecar journal of recent append intents for crash diagnosis/repair.
+//! Exports: `IntentJournal`, `IntentEntry`, `IntentState`, `Rollback`, `journal_path`,
+//! `read_entries`, `crc32c`.
+//! Role: Lets validation tell "writer died mid-append" apart from damage to committed bytes, and
+//! lets `Pool::repair_interrupted_append` roll such an append back.
+//! Invariants: Fixed 64-byte entries live in slot `seq % capacity`; writes are positional.
+//! Invariants: An entry is marked `Intent` before frame bytes are written and `Committed` after.
+//! Invariants: The journal is advisory; a missing or unreadable sidecar never blocks pool IO.
+//! Invariants: Entries survive a writer crash (they sit in the page cache like the frame bytes),
+//! but only `Flush` appends sync the intent before writing the frame, so after power loss a
+//! `Fast` append's intent may be missing or older than the frame bytes it describes.
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::core::error::{Error, ErrorKind};
+
+const JOURNAL_MAGIC: [u8; 8] = *b"PLSMINT2";
+const JOURNAL_HEADER_LEN: u64 = 16;
+const ENTRY_LEN: u64 = 64;
+/// Slots kept in the rolling journal; only the most recent appends matter for postmortems.
+pub const DEFAULT_JOURNAL_CAPACITY: u32 = 64;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntentState {
+    /// Frame bytes may be partially written.
+    Intent,
+    /// Frame and header were fully written.
+    Committed,
+}
+
+/// Header tail state an append leaves behind once it has dropped the oldest frames it needs,
+/// not counting its own frame. An empty pool is `oldest_seq == 0` with both offsets at the head.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Rollback {
+    pub tail_off: u64,
+    pub tail_next_off: u64,
+    pub oldest_seq: u64,
+}
+
+/// One journaled append: where the frame was planned and what its payload should hash to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IntentEntry {
+    pub seq: u64,
+    /// Ring-relative frame offset.
+    pub offset: u64,
+    /// Total frame length in bytes (header, payload, commit marker, padding).
+    pub frame_len: u32,
+    /// CRC-32C of the payload bytes.
+    pub crc: u32,
+    pub state: IntentState,
+    /// Where the header's tail goes if this append is rolled back.
+    pub rollback: Rollback,
+}
+
+impl IntentEntry {
+    /// Whether ring offset `offset` falls inside this entry's frame.
+    pub fn covers(&self, offset: u64) -> bool {
+        offset >= self.offset && offset < self.offset + self.frame_len as u64
+    }
+}
+
+/// Sidecar path for `pool_path` (`<pool file>.intent`).
+pub fn journal_path(pool_path: &Path) -> PathBuf {
+    let mut name = pool_path.as_os_str().to_os_string();
+    name.push(".intent");
+    PathBuf::from(name)
+}
+
+pub struct IntentJournal {
+    file: File,
+    capacity: u32,
+}
+
+impl IntentJournal {
+    /// Create (or reset) the sidecar next to `pool_path`.
+    pub fn create(pool_path: &Path, capacity: u32) -> Result<Self, Error> {
+        let path = journal_path(pool_path);
+        let capacity = capacity.max(1);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map_err(|err| io_error("failed to create intent journal", &path, err))?;
+        file.set_len(JOURNAL_HEADER_LEN + capacity as u64 * ENTRY_LEN)
+            .map_err(|err| io_error("failed to size intent journal", &path, err))?;
+        let mut header = [0u8; JOURNAL_HEADER_LEN as usize];
+        header[0..8].copy_from_slice(&JOURNAL_MAGIC);
+        header[8..12].copy_from_slice(&capacity.to_le_bytes());
+        write_at(&file, &header, 0)
+            .map_err(|err| io_error("failed to write intent journal header", &path, err))?;
+        Ok(Self { file, capacity })
+    }
+
+    /// Open an existing sidecar, creating a fresh one when it is missing or unreadable.
+    pub fn open_or_create(pool_path: &Path) -> Result<Self, Error> {
+        match Self::open(pool_path) {
+            Ok(journal) => Ok(journal),
+            Err(_) => Self::create(pool_path, DEFAULT_JOURNAL_CAPACITY),
+        }
+    }
+
+    fn open(pool_path: &Path) -> Result<Self, Error> {
+        let path = journal_path(pool_path);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map_err(|err| io_error("failed to open intent journal", &path, err))?;
+        let capacity = read_header(&file, &path)?;
+        Ok(Self { file, capacity })
+    }
+
+    /// Journal an append before its frame bytes are written; pass the result to `record_commit`.
+    /// With `sync`, the entry reaches the disk before this returns.
+    pub fn record_intent(
+        &self,
+        seq: u64,
+        offset: u64,
+        frame_len: u32,
+        crc: u32,
+        rollback: Rollback,
+        sync: bool,
+    ) -> IntentEntry {
+        let entry = IntentEntry {
+            seq,
+            offset,
+            frame_len,
+            crc,
+            state: IntentState::Intent,
+            rollback,
+        };
+        self.write_entry(&entry);
+        if sync {
+            let _ = self.file.sync_data();
+        }
+        entry
+    }
+
+    pub fn record_commit(&self, entry: IntentEntry) {
+        self.write_entry(&IntentEntry {
+            state: IntentState::Committed,
+            ..entry
+        });
+    }
+
+    /// Forget the entry for `seq` once its interrupted append has been rolled back.
+    pub fn clear(&self, seq: u64) -> Result<(), Error> {
+        let slot = seq % self.capacity as u64;
+        write_at(
+            &self.file,
+            &[0u8; ENTRY_LEN as usize],
+            JOURNAL_HEADER_LEN + slot * ENTRY_LEN,
+        )
+        .and_then(|()| self.file.sync_data())
+        .map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to clear intent journal entry")
+                .with_source(err)
+        })
+    }
+
+    fn write_entry(&self, entry: &IntentEntry) {
+        let slot = entry.seq % self.capacity as u64;
+        let mut buf = [0u8; ENTRY_LEN as usize];
+        buf[0..8].copy_from_slice(&entry.seq.to_le_bytes());
+        buf[8..16].copy_from_slice(&entry.offset.to_le_bytes());
+        buf[16..20].copy_from_slice(&entry.frame_len.to_le_bytes());
+        buf[20..24].copy_from_slice(&entry.crc.to_le_bytes());
+        let state: u32 = match entry.state {
+            IntentState::Intent => 1,
+            IntentState::Committed => 2,
+        };
+        buf[24..28].copy_from_slice(&state.to_le_bytes());
+        buf[32..40].copy_from_slice(&entry.rollback.tail_off.to_le_bytes());
+        buf[40..48].copy_from_slice(&entry.rollback.tail_next_off.to_le_bytes());
+        buf[48..56].copy_from_slice(&entry.rollback.oldest_seq.to_le_bytes());
+        // Advisory: a failed journal write must not fail the append it describes.
+        let _ = write_at(&self.file, &buf, JOURNAL_HEADER_LEN + slot * ENTRY_LEN);
+    }
+}
+
+/// Read all populated entries for `pool_path`, oldest seq first.
+/// Returns `Ok(None)` when the pool has no sidecar.
+pub fn read_entries(pool_path: &Path) -> Result<Option<Vec<IntentEntry>>, Error> {
+    let path = journal_path(pool_path);
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(io_error("failed to open intent journal", &path, err)),
+    };
+    let capacity = read_header(&file, &path)?;
+    let mut entries = Vec::new();
+    let mut buf = [0u8; ENTRY_LEN as usize];
+    for slot in 0..capacity as u64 {
+        read_at(&file, &mut buf, JOURNAL_HEADER_LEN + slot * ENTRY_LEN)
+            .map_err(|err| io_error("failed to read intent journal", &path, err))?;
+        let state = match u32::from_le_bytes(buf[24..28].try_into().expect("state")) {
+            1 => IntentState::Intent,
+            2 => IntentState::Committed,
+            _ => continue,
+        };
+        entries.push(IntentEntry {
+            seq: u64::from_le_bytes(buf[0..8].try_into().expect("seq")),
+            offset: u64::from_le_bytes(buf[8..16].try_into().expect("offset")),
+            frame_len: u32::from_le_bytes(buf[16..20].try_into().expect("frame_len")),
+            crc: u32::from_le_bytes(buf[20..24].try_into().expect("crc")),
+            state,
+            rollback: Rollback {
+                tail_off: u64::from_le_bytes(buf[32..40].try_into().expect("tail_off")),
+                tail_next_off: u64::from_le_bytes(buf[40..48].try_into().expect("tail_next_off")),
+                oldest_seq: u64::from_le_bytes(buf[48..56].try_into().expect("oldest_seq")),
+            },
+        });
+    }
+    entries.sort_by_key(|entry| entry.seq);
+    Ok(Some(entries))
+}
+
+fn read_header(file: &File, path: &Path) -> Result<u32, Error> {
+    let mut header = [0u8; JOURNAL_HEADER_LEN as usize];
+    read_at(file, &mut header, 0)
+        .map_err(|err| io_error("failed to read intent journal header", path, err))?;
+    if header[0..8] != JOURNAL_MAGIC {
+        return Err(Error::new(ErrorKind::Corrupt)
+            .with_message("intent journal magic mismatch")
+            .with_path(path));
+    }
+    let capacity = u32::from_le_bytes(header[8..12].try_into().expect("capacity"));
+    if capacity == 0 {
+        return Err(Error::new(ErrorKind::Corrupt)
+            .with_message("intent journal capacity is zero")
+            .with_path(path));
+    }
+    Ok(capacity)
+}
+
+fn write_at(mut file: &File, buf: &[u8], offset: u64) -> std::io::Result<()> {
+    file.seek(SeekFrom::Start(offset))?;
+    file.write_all(buf)
+}
+
+fn read_at(mut file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)
+}
+
+fn io_error(message: &str, path: &Path, err: std::io::Error) -> Error {
+    let kind = match err.kind() {
+        std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+        std::io::ErrorKind::PermissionDenied => ErrorKind::Permission,
+        _ => ErrorKind::Io,
+    };
+    Error::new(kind)
+        .with_message(message)
+        .with_path(path)
+        .with_source(err)
+}
+
+const CRC32C_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
+/// CRC-32C (Castagnoli) of `bytes`.
+pub fn crc32c(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc = CRC32C_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IntentJournal, IntentState, Rollback, crc32c, journal_path, read_entries};
+
+    #[test]
+    fn crc32c_matches_reference_vector() {
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+        assert_eq!(crc32c(b""), 0);
+    }
+
+    #[test]
+    fn entries_roll_over_by_seq_and_track_state() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pool_path = dir.path().join("pool.plasmite");
+        assert!(read_entries(&pool_path).expect("read").is_none());
+
+        let journal = IntentJournal::create(&pool_path, 2).expect("create");
+        assert!(journal_path(&pool_path).exists());
+        for seq in 1..=3 {
+            let rollback = Rollback {
+                tail_off: 64,
+                tail_next_off: 128,
+                oldest_seq: seq - 1,
+            };
+            journal.record_intent(seq, seq * 128, 128, seq as u32, rollback, seq == 3);
+        }
+        let entries = read_entries(&pool_path).expect("read").expect("entries");
+        assert_eq!(
+            entries.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        journal.record_commit(entries[1]);
+
+        let reopened = IntentJournal::open_or_create(&pool_path).expect("reopen");
+        drop(reopened);
+        let entries = read_entries(&pool_path).expect("read").expect("entries");
+        assert_eq!(entries[0].state, IntentState::Intent);
+        assert_eq!(entries[1].state, IntentState::Committed);
+        assert!(entries[1].covers(3 * 128 + 10));
+        assert!(!entries[1].covers(4 * 128));
+        assert_eq!(entries[1].rollback.oldest_seq, 2);
+        assert_eq!(entries[1].rollback.tail_next_off, 128);
+
+        journal.clear(3).expect("clear");
+        let entries = read_entries(&pool_path).expect("read").expect("entries");
+        assert_eq!(
+            entries.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
+}
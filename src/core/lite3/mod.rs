@@ -1,8 +1,9 @@
 //! Purpose: Safe wrappers around Lite3 encoding/decoding and canonical message validation.
-//! Exports: `Lite3Buf`, `Lite3DocRef`, `encode_message`, `validate_bytes`.
+//! Exports: `Lite3Buf`, `Lite3DocRef`, `Lite3Scalar`, `encode_message`, `validate_bytes`.
 //! Role: Canonical JSON <-> Lite3 boundary for payloads stored in pool frames.
 //! Invariants: Buffer growth is capped (`MAX_LITE3_BUF`) to avoid unbounded allocation.
 //! Invariants: All FFI interaction is confined to this module + `sys`.
+//! Invariants: With the `pure-lite3` feature, `sys` is the pure-Rust port in `pure.rs` (no C build).
 #[cfg(test)]
 use std::cell::Cell;
 use std::ffi::CString;
@@ -13,6 +14,12 @@ use serde_json::Value;
 
 use crate::core::error::{Error, ErrorKind};
 
+#[cfg(all(test, not(feature = "pure-lite3")))]
+mod pure;
+#[cfg(feature = "pure-lite3")]
+#[path = "pure.rs"]
+pub mod sys;
+#[cfg(not(feature = "pure-lite3"))]
 pub mod sys;
 
 const MAX_LITE3_BUF: usize = 256 * 1024 * 1024;
@@ -90,6 +97,16 @@ impl Lite3Buf {
     }
 }
 
+/// A scalar read in place by `Lite3DocRef::get_path`; strings borrow from the document.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lite3Scalar<'a> {
+    Null,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    Str(&'a str),
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Lite3DocRef<'a> {
     bytes: &'a [u8],
@@ -227,6 +244,87 @@ impl<'a> Lite3DocRef<'a> {
         Ok(out)
     }
 
+    pub fn f64_at_key(&self, ofs: usize, key: &str) -> Result<f64, Error> {
+        let mut out: f64 = 0.0;
+        let ret = unsafe {
+            sys::plasmite_lite3_get_f64(
+                self.bytes.as_ptr(),
+                self.bytes.len(),
+                ofs,
+                c_key(key).as_ptr(),
+                &mut out as *mut f64,
+            )
+        };
+        if ret < 0 {
+            return Err(Error::new(ErrorKind::Corrupt).with_message("missing or invalid key"));
+        }
+        Ok(out)
+    }
+
+    pub fn str_at_key(&self, ofs: usize, key: &str) -> Result<&'a str, Error> {
+        let mut out_ptr: *const std::os::raw::c_char = std::ptr::null();
+        let mut out_len: usize = 0;
+        let ret = unsafe {
+            sys::plasmite_lite3_get_str(
+                self.bytes.as_ptr(),
+                self.bytes.len(),
+                ofs,
+                c_key(key).as_ptr(),
+                &mut out_ptr as *mut *const std::os::raw::c_char,
+                &mut out_len as *mut usize,
+            )
+        };
+        if ret < 0 || out_ptr.is_null() {
+            return Err(Error::new(ErrorKind::Corrupt).with_message("missing or invalid key"));
+        }
+        // The shim returns a pointer into `self.bytes`, so the slice lives as long as the doc.
+        let bytes = unsafe { std::slice::from_raw_parts(out_ptr.cast::<u8>(), out_len) };
+        std::str::from_utf8(bytes).map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("invalid utf-8")
+                .with_source(err)
+        })
+    }
+
+    /// Scalar at a jq-style field path such as `.data.user.id`, read in place without encoding
+    /// the document to JSON. `Ok(None)` when a field along the path is missing or null (jq
+    /// reads both as `null`). Array indexes, and paths that reach an object, array, or bytes
+    /// value or cross a non-object, are `Usage` errors; decode the document for those.
+    pub fn get_path(&self, path: &str) -> Result<Option<Lite3Scalar<'a>>, Error> {
+        let unsupported = |message: &str| {
+            Error::new(ErrorKind::Usage)
+                .with_message(message.to_string())
+                .with_hint("Use a field path like .data.user.id.")
+        };
+        let Some(fields) = path.strip_prefix('.') else {
+            return Err(unsupported("path must start with '.'"));
+        };
+        let fields = fields.split('.').collect::<Vec<_>>();
+        if fields.iter().any(|field| {
+            field.is_empty() || !field.chars().all(|c| c.is_alphanumeric() || c == '_')
+        }) {
+            return Err(unsupported("path must be dot-separated field names"));
+        }
+        let (last, parents) = fields.split_last().expect("split yields one field");
+        let mut ofs = 0;
+        for field in parents {
+            match self.type_at_key(ofs, field) {
+                Err(_) | Ok(sys::LITE3_TYPE_NULL) => return Ok(None),
+                Ok(sys::LITE3_TYPE_OBJECT) => ofs = self.key_offset_at(ofs, field)?,
+                Ok(_) => return Err(unsupported("path crosses a non-object value")),
+            }
+        }
+        let value = match self.type_at_key(ofs, last) {
+            Err(_) | Ok(sys::LITE3_TYPE_NULL) => return Ok(None),
+            Ok(sys::LITE3_TYPE_BOOL) => Lite3Scalar::Bool(self.bool_at_key(ofs, last)?),
+            Ok(sys::LITE3_TYPE_I64) => Lite3Scalar::I64(self.i64_at_key(ofs, last)?),
+            Ok(sys::LITE3_TYPE_F64) => Lite3Scalar::F64(self.f64_at_key(ofs, last)?),
+            Ok(sys::LITE3_TYPE_STRING) => Lite3Scalar::Str(self.str_at_key(ofs, last)?),
+            Ok(_) => return Err(unsupported("path selects an object, array, or bytes value")),
+        };
+        Ok(Some(value))
+    }
+
     pub fn type_at_key(&self, ofs: usize, key: &str) -> Result<u8, Error> {
         let value = unsafe {
             sys::plasmite_lite3_get_type(
@@ -308,6 +406,16 @@ impl<'a> Lite3DocRef<'a> {
 }
 
 pub fn encode_message(meta_tags: &[String], data: &Value) -> Result<Lite3Buf, Error> {
+    encode_message_with_refs(meta_tags, &[], data)
+}
+
+/// Like `encode_message`, also recording `meta.refs` (seqs this message annotates).
+/// Empty `refs` are omitted, so plain messages encode byte-for-byte as before.
+pub fn encode_message_with_refs(
+    meta_tags: &[String],
+    refs: &[u64],
+    data: &Value,
+) -> Result<Lite3Buf, Error> {
     if !matches!(data, Value::Object(_)) {
         return Err(Error::new(ErrorKind::Usage).with_message("data must be object"));
     }
@@ -315,6 +423,8 @@ pub fn encode_message(meta_tags: &[String], data: &Value) -> Result<Lite3Buf, Er
     #[derive(Serialize)]
     struct MetaEnvelope<'a> {
         tags: &'a [String],
+        #[serde(skip_serializing_if = "<[u64]>::is_empty")]
+        refs: &'a [u64],
     }
 
     #[derive(Serialize)]
@@ -324,7 +434,10 @@ pub fn encode_message(meta_tags: &[String], data: &Value) -> Result<Lite3Buf, Er
     }
 
     let json = MessageEnvelope {
-        meta: MetaEnvelope { tags: meta_tags },
+        meta: MetaEnvelope {
+            tags: meta_tags,
+            refs,
+        },
         data,
     };
     let json_str = serde_json::to_string(&json).map_err(|err| {
@@ -415,7 +528,10 @@ pub(crate) fn json_counter_snapshot() -> (usize, usize) {
 
 #[cfg(test)]
 mod tests {
-    use super::{Lite3Buf, encode_message, validate_bytes};
+    use super::{
+        Lite3Buf, Lite3Scalar, encode_message, encode_message_with_refs, json_counter_snapshot,
+        reset_json_counters, validate_bytes,
+    };
     use serde_json::json;
 
     #[test]
@@ -429,6 +545,20 @@ mod tests {
         assert_eq!(value["meta"]["tags"][0], "event");
     }
 
+    #[test]
+    fn refs_are_recorded_only_when_present() {
+        let data = json!({"emoji": "+1"});
+        let plain = encode_message(&[], &data).expect("encode");
+        let empty = encode_message_with_refs(&[], &[], &data).expect("encode");
+        assert_eq!(plain.as_slice(), empty.as_slice());
+
+        let buf = encode_message_with_refs(&[], &[7], &data).expect("encode");
+        validate_bytes(buf.as_slice()).expect("valid");
+        let value: serde_json::Value =
+            serde_json::from_str(&buf.as_doc().to_json(false).expect("json")).expect("parse");
+        assert_eq!(value["meta"]["refs"], json!([7]));
+    }
+
     #[test]
     fn invalid_bytes_are_rejected() {
         let buf = [0u8; 8];
@@ -444,6 +574,51 @@ mod tests {
         assert_eq!(err.kind(), crate::core::error::ErrorKind::Corrupt);
     }
 
+    #[test]
+    fn get_path_reads_scalars_without_json() {
+        let data = json!({
+            "user": {"id": 42, "name": "ada", "score": 1.5, "admin": false, "team": null},
+            "items": [1]
+        });
+        let buf = encode_message(&[], &data).expect("encode");
+        let doc = buf.as_doc();
+        reset_json_counters();
+        assert_eq!(
+            doc.get_path(".data.user.id").expect("id"),
+            Some(Lite3Scalar::I64(42))
+        );
+        assert_eq!(
+            doc.get_path(".data.user.name").expect("name"),
+            Some(Lite3Scalar::Str("ada"))
+        );
+        assert_eq!(
+            doc.get_path(".data.user.score").expect("score"),
+            Some(Lite3Scalar::F64(1.5))
+        );
+        assert_eq!(
+            doc.get_path(".data.user.admin").expect("admin"),
+            Some(Lite3Scalar::Bool(false))
+        );
+        assert_eq!(doc.get_path(".data.user.team").expect("team"), None);
+        assert_eq!(doc.get_path(".data.user.missing").expect("missing"), None);
+        assert_eq!(
+            doc.get_path(".data.user.team.lead").expect("through null"),
+            None
+        );
+        assert_eq!(json_counter_snapshot(), (0, 0));
+
+        for path in [
+            ".data.user",
+            ".data.items",
+            ".data.user.id.x",
+            ".data.items[0]",
+            "data",
+        ] {
+            let err = doc.get_path(path).expect_err(path);
+            assert_eq!(err.kind(), crate::core::error::ErrorKind::Usage, "{path}");
+        }
+    }
+
     #[test]
     fn typed_key_getters_work() {
         let data = json!({"done": true, "sent_ns": 42});
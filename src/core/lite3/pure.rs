@@ -0,0 +1,1355 @@
+//! Purpose: Pure-Rust implementation of the Lite3 shim, selected by the `pure-lite3` feature.
+//! Exports: `plasmite_lite3_*` functions and Lite3 type constants (same surface as `sys`).
+//! Role: Drop-in replacement for the C shim on targets without a usable C toolchain (wasm32, MSVC).
+//! Invariants: Buffer layout matches vendored Lite3 byte-for-byte (96-byte nodes, DJB2 key hashes,
+//! Invariants: quadratic probing, zeroed padding) so pools written by either backend interoperate.
+//! Invariants: JSON decode inserts in document order; JSON encode walks the B-tree in key order.
+//! Invariants: Float text may differ from the C encoder (shortest round-trip either way).
+#![cfg_attr(not(feature = "pure-lite3"), allow(dead_code))]
+
+use std::cell::Cell;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::{c_char, c_int, c_uchar, c_void};
+
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+
+use crate::core::base64::base64_encode;
+
+pub const LITE3_TYPE_NULL: u8 = 0;
+pub const LITE3_TYPE_BOOL: u8 = 1;
+pub const LITE3_TYPE_I64: u8 = 2;
+pub const LITE3_TYPE_F64: u8 = 3;
+pub const LITE3_TYPE_BYTES: u8 = 4;
+pub const LITE3_TYPE_STRING: u8 = 5;
+pub const LITE3_TYPE_OBJECT: u8 = 6;
+pub const LITE3_TYPE_ARRAY: u8 = 7;
+pub const LITE3_TYPE_INVALID: u8 = 8;
+
+const NODE_SIZE: usize = 96;
+const NODE_ALIGNMENT_MASK: usize = 3;
+const HASHES_OFS: usize = 4;
+const SIZE_KC_OFS: usize = 32;
+const KV_OFS: usize = 36;
+const CHILD_OFS: usize = 64;
+const KEY_COUNT_MASK: u32 = 7;
+const KEY_COUNT_MAX: usize = 7;
+const KEY_COUNT_MIN: usize = 3;
+const SIZE_SHIFT: u32 = 6;
+const GEN_SHIFT: u32 = 8;
+const TYPE_MASK: u32 = 0xff;
+const TREE_HEIGHT_MAX: usize = 9;
+const HASH_PROBE_MAX: u32 = 128;
+const VAL_SIZE: usize = 1;
+const BUF_SIZE_MAX: usize = u32::MAX as usize;
+const JSON_NESTING_DEPTH_MAX: usize = 32;
+
+/// Payload size per type, excluding the type byte (mirrors `lite3_type_sizes`).
+const TYPE_SIZES: [usize; 9] = [0, 1, 8, 8, 4, 4, NODE_SIZE - 1, NODE_SIZE - 1, 0];
+
+thread_local! {
+    static LAST_ERRNO: Cell<c_int> = const { Cell::new(0) };
+}
+
+type Lite3Result<T> = Result<T, c_int>;
+
+fn set_errno(errno: c_int) {
+    LAST_ERRNO.with(|cell| cell.set(errno));
+}
+
+fn errno() -> c_int {
+    LAST_ERRNO.with(Cell::get)
+}
+
+fn status(result: Lite3Result<()>) -> c_int {
+    match result {
+        Ok(()) => 0,
+        Err(errno) => {
+            set_errno(errno);
+            -1
+        }
+    }
+}
+
+fn read_u32(buf: &[u8], ofs: usize) -> u32 {
+    u32::from_le_bytes(buf[ofs..ofs + 4].try_into().expect("4-byte slice"))
+}
+
+fn write_u32(buf: &mut [u8], ofs: usize, value: u32) {
+    buf[ofs..ofs + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn key_count(buf: &[u8], node: usize) -> usize {
+    (read_u32(buf, node + SIZE_KC_OFS) & KEY_COUNT_MASK) as usize
+}
+
+fn hash_at(buf: &[u8], node: usize, i: usize) -> u32 {
+    read_u32(buf, node + HASHES_OFS + 4 * i)
+}
+
+fn kv_at(buf: &[u8], node: usize, i: usize) -> usize {
+    read_u32(buf, node + KV_OFS + 4 * i) as usize
+}
+
+fn child_at(buf: &[u8], node: usize, i: usize) -> usize {
+    read_u32(buf, node + CHILD_OFS + 4 * i) as usize
+}
+
+fn check_node(buf: &[u8], node: usize) -> Lite3Result<()> {
+    if node & NODE_ALIGNMENT_MASK != 0 {
+        return Err(libc::EBADMSG);
+    }
+    if NODE_SIZE > buf.len() || node > buf.len() - NODE_SIZE {
+        return Err(libc::EFAULT);
+    }
+    Ok(())
+}
+
+/// DJB2 hash and stored size (including the NUL terminator) of a key.
+fn key_data(key: &[u8]) -> (u32, u32) {
+    let hash = key.iter().fold(5381u32, |hash, byte| {
+        hash.wrapping_mul(33).wrapping_add(u32::from(*byte))
+    });
+    (hash, key.len() as u32 + 1)
+}
+
+fn key_tag_size(key_size: u32) -> usize {
+    (((key_size >> 14) != 0) as usize) * 2
+        + ((key_size >> 6) != 0) as usize
+        + (key_size != 0) as usize
+}
+
+enum KeyCheck {
+    Match,
+    Collision,
+}
+
+/// Walk past a stored key at `*ofs`, comparing it to `key` (bytes including NUL) when given.
+fn verify_key(
+    buf: &[u8],
+    key: Option<&[u8]>,
+    expected_tag_size: usize,
+    ofs: &mut usize,
+) -> Lite3Result<KeyCheck> {
+    if 4 > buf.len() || *ofs > buf.len() - 4 {
+        return Err(libc::EFAULT);
+    }
+    let tag_size = (buf[*ofs] & 0x3) as usize + 1;
+    if expected_tag_size != 0 && expected_tag_size != tag_size {
+        return Err(libc::EINVAL);
+    }
+    let mut raw = [0u8; 4];
+    raw[..tag_size].copy_from_slice(&buf[*ofs..*ofs + tag_size]);
+    let stored_size = (u32::from_le_bytes(raw) >> 2) as usize;
+    *ofs += tag_size;
+    if stored_size > buf.len() || *ofs > buf.len() - stored_size {
+        return Err(libc::EFAULT);
+    }
+    if let Some(key) = key {
+        let len = key.len().min(stored_size);
+        if key[..len] != buf[*ofs..*ofs + len] {
+            return Ok(KeyCheck::Collision);
+        }
+    }
+    *ofs += stored_size;
+    Ok(KeyCheck::Match)
+}
+
+/// Walk past a stored value at `*ofs`, checking its type and bounds.
+fn verify_val(buf: &[u8], ofs: &mut usize) -> Lite3Result<()> {
+    if VAL_SIZE > buf.len() || *ofs > buf.len() - VAL_SIZE {
+        return Err(libc::EFAULT);
+    }
+    let kind = buf[*ofs];
+    if kind >= LITE3_TYPE_INVALID {
+        return Err(libc::EINVAL);
+    }
+    let mut size = VAL_SIZE + TYPE_SIZES[kind as usize];
+    if size > buf.len() || *ofs > buf.len() - size {
+        return Err(libc::EFAULT);
+    }
+    if kind == LITE3_TYPE_STRING || kind == LITE3_TYPE_BYTES {
+        size += read_u32(buf, *ofs + VAL_SIZE) as usize;
+        if size > buf.len() || *ofs > buf.len() - size {
+            return Err(libc::EFAULT);
+        }
+    }
+    *ofs += size;
+    Ok(())
+}
+
+/// Locate the value stored under `key` (object) or array index `hash` (when `key` is `None`).
+fn get_impl(
+    buf: &[u8],
+    ofs: usize,
+    key: Option<&[u8]>,
+    hash: u32,
+    key_size: u32,
+) -> Lite3Result<usize> {
+    let tag_size = key_tag_size(key_size);
+    let attempts = if key.is_some() { HASH_PROBE_MAX } else { 1 };
+    'probe: for attempt in 0..attempts {
+        let attempt_hash = hash.wrapping_add(attempt.wrapping_mul(attempt));
+        let mut node = ofs;
+        check_node(buf, node)?;
+        let mut walks = 0;
+        loop {
+            let count = key_count(buf, node);
+            let mut i = 0;
+            while i < count && hash_at(buf, node, i) < attempt_hash {
+                i += 1;
+            }
+            if i < count && hash_at(buf, node, i) == attempt_hash {
+                let mut target = kv_at(buf, node, i);
+                if key.is_some()
+                    && matches!(
+                        verify_key(buf, key, tag_size, &mut target)?,
+                        KeyCheck::Collision
+                    )
+                {
+                    continue 'probe;
+                }
+                let val = target;
+                verify_val(buf, &mut target)?;
+                return Ok(val);
+            }
+            if child_at(buf, node, 0) == 0 {
+                return Err(libc::ENOENT);
+            }
+            node = child_at(buf, node, i);
+            check_node(buf, node)?;
+            walks += 1;
+            if walks > TREE_HEIGHT_MAX {
+                return Err(libc::EBADMSG);
+            }
+        }
+    }
+    Err(libc::ENOENT)
+}
+
+fn verify_get(buf: &[u8], ofs: usize) -> Lite3Result<()> {
+    if buf.len() > BUF_SIZE_MAX || NODE_SIZE > buf.len() || ofs > buf.len() - NODE_SIZE {
+        return Err(libc::EINVAL);
+    }
+    Ok(())
+}
+
+fn get_obj_val(buf: &[u8], ofs: usize, key: &[u8]) -> Lite3Result<usize> {
+    verify_get(buf, ofs)?;
+    if buf[ofs] != LITE3_TYPE_OBJECT {
+        return Err(libc::EINVAL);
+    }
+    get_lookup(buf, ofs, key)
+}
+
+fn get_lookup(buf: &[u8], ofs: usize, key: &[u8]) -> Lite3Result<usize> {
+    let (hash, size) = key_data(key);
+    let mut with_nul = key.to_vec();
+    with_nul.push(0);
+    get_impl(buf, ofs, Some(&with_nul), hash, size)
+}
+
+fn get_by_index(buf: &[u8], ofs: usize, index: u32) -> Lite3Result<usize> {
+    verify_get(buf, ofs)?;
+    if buf[ofs] != LITE3_TYPE_ARRAY {
+        return Err(libc::EINVAL);
+    }
+    if index >= read_u32(buf, ofs + SIZE_KC_OFS) >> SIZE_SHIFT {
+        return Err(libc::EINVAL);
+    }
+    get_impl(buf, ofs, None, index, 0)
+}
+
+/// Append-only Lite3 writer over a caller-provided, zero-initialised buffer.
+struct Writer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Writer<'_> {
+    fn zero(&mut self, start: usize, len: usize) {
+        self.buf[start..start + len].fill(0);
+    }
+
+    fn init_node(&mut self, ofs: usize, kind: u8) {
+        write_u32(self.buf, ofs, u32::from(kind));
+        write_u32(self.buf, ofs + SIZE_KC_OFS, 0);
+        self.zero(ofs + HASHES_OFS, 28);
+        self.zero(ofs + KV_OFS, 28);
+        self.zero(ofs + CHILD_OFS, 32);
+    }
+
+    fn set_kc(&mut self, node: usize, count: usize) {
+        let size_kc = read_u32(self.buf, node + SIZE_KC_OFS);
+        write_u32(
+            self.buf,
+            node + SIZE_KC_OFS,
+            (size_kc & !KEY_COUNT_MASK) | (count as u32 & KEY_COUNT_MASK),
+        );
+    }
+
+    fn set_hash(&mut self, node: usize, i: usize, value: u32) {
+        write_u32(self.buf, node + HASHES_OFS + 4 * i, value);
+    }
+
+    fn set_kv(&mut self, node: usize, i: usize, value: usize) {
+        write_u32(self.buf, node + KV_OFS + 4 * i, value as u32);
+    }
+
+    fn set_child(&mut self, node: usize, i: usize, value: usize) {
+        write_u32(self.buf, node + CHILD_OFS + 4 * i, value as u32);
+    }
+
+    /// Port of `lite3_set_impl`: reserve space for a value and return its offset.
+    fn set_impl(
+        &mut self,
+        ofs: usize,
+        key: Option<&[u8]>,
+        hash: u32,
+        key_size: u32,
+        val_len: usize,
+    ) -> Lite3Result<usize> {
+        let bufsz = self.buf.len();
+        let tag_size = key_tag_size(key_size);
+        let base_entry_size = tag_size + key_size as usize + VAL_SIZE + val_len;
+        if ofs & NODE_ALIGNMENT_MASK != 0 {
+            return Err(libc::EBADMSG);
+        }
+
+        let gen_type = read_u32(self.buf, ofs);
+        let generation = (gen_type >> GEN_SHIFT).wrapping_add(1);
+        write_u32(
+            self.buf,
+            ofs,
+            (gen_type & TYPE_MASK) | (generation << GEN_SHIFT),
+        );
+
+        let attempts = if key.is_some() { HASH_PROBE_MAX } else { 1 };
+        'probe: for attempt in 0..attempts {
+            let attempt_hash = hash.wrapping_add(attempt.wrapping_mul(attempt));
+            let mut entry_size = base_entry_size;
+            let mut parent: Option<usize> = None;
+            let mut node = ofs;
+            let mut count = 0usize;
+            let mut i = 0usize;
+            let mut walks = 0usize;
+
+            loop {
+                let mut matched = false;
+                if key_count(self.buf, node) == KEY_COUNT_MAX {
+                    let aligned = (self.len + NODE_ALIGNMENT_MASK) & !NODE_ALIGNMENT_MASK;
+                    let new_node_size = if parent.is_some() {
+                        NODE_SIZE
+                    } else {
+                        2 * NODE_SIZE
+                    };
+                    if new_node_size > bufsz || aligned > bufsz - new_node_size {
+                        return Err(libc::ENOBUFS);
+                    }
+                    self.len = aligned;
+                    let parent_node = match parent {
+                        Some(parent_node) => parent_node,
+                        None => {
+                            // Root split: move the root's contents down and grow a new root in place.
+                            self.buf.copy_within(node..node + NODE_SIZE, self.len);
+                            node = self.len;
+                            self.zero(ofs + HASHES_OFS, 28);
+                            self.zero(ofs + KV_OFS, 28);
+                            self.zero(ofs + CHILD_OFS, 32);
+                            self.set_kc(ofs, 0);
+                            self.set_child(ofs, 0, self.len);
+                            self.len += NODE_SIZE;
+                            count = 0;
+                            i = 0;
+                            parent = Some(ofs);
+                            ofs
+                        }
+                    };
+                    let mut j = count;
+                    while j > i {
+                        let shifted_hash = hash_at(self.buf, parent_node, j - 1);
+                        let shifted_kv = kv_at(self.buf, parent_node, j - 1);
+                        let shifted_child = child_at(self.buf, parent_node, j);
+                        self.set_hash(parent_node, j, shifted_hash);
+                        self.set_kv(parent_node, j, shifted_kv);
+                        self.set_child(parent_node, j + 1, shifted_child);
+                        j -= 1;
+                    }
+                    let separator_hash = hash_at(self.buf, node, KEY_COUNT_MIN);
+                    let separator_kv = kv_at(self.buf, node, KEY_COUNT_MIN);
+                    self.set_hash(parent_node, i, separator_hash);
+                    self.set_kv(parent_node, i, separator_kv);
+                    self.set_child(parent_node, i + 1, self.len);
+                    let parent_count = key_count(self.buf, parent_node);
+                    self.set_kc(parent_node, parent_count + 1);
+                    self.set_hash(node, KEY_COUNT_MIN, 0);
+                    self.set_kv(node, KEY_COUNT_MIN, 0);
+
+                    let sibling = self.len;
+                    self.zero(sibling + HASHES_OFS, 28);
+                    self.zero(sibling + KV_OFS, 28);
+                    let root_type = read_u32(self.buf, ofs) & TYPE_MASK;
+                    write_u32(self.buf, sibling, root_type);
+                    write_u32(self.buf, sibling + SIZE_KC_OFS, KEY_COUNT_MIN as u32);
+                    write_u32(self.buf, node + SIZE_KC_OFS, KEY_COUNT_MIN as u32);
+                    self.zero(sibling + CHILD_OFS, 32);
+                    let moved_child = child_at(self.buf, node, KEY_COUNT_MIN + 1);
+                    self.set_child(sibling, 0, moved_child);
+                    self.set_child(node, KEY_COUNT_MIN + 1, 0);
+                    for j in 0..KEY_COUNT_MIN {
+                        let moved_hash = hash_at(self.buf, node, j + KEY_COUNT_MIN + 1);
+                        let moved_kv = kv_at(self.buf, node, j + KEY_COUNT_MIN + 1);
+                        let moved_child = child_at(self.buf, node, j + KEY_COUNT_MIN + 2);
+                        self.set_hash(sibling, j, moved_hash);
+                        self.set_kv(sibling, j, moved_kv);
+                        self.set_child(sibling, j + 1, moved_child);
+                        self.set_hash(node, j + KEY_COUNT_MIN + 1, 0);
+                        self.set_kv(node, j + KEY_COUNT_MIN + 1, 0);
+                        self.set_child(node, j + KEY_COUNT_MIN + 2, 0);
+                    }
+                    self.len += NODE_SIZE;
+                    let separator = hash_at(self.buf, parent_node, i);
+                    if attempt_hash > separator {
+                        node = sibling;
+                    } else if attempt_hash == separator {
+                        node = parent_node;
+                        matched = true;
+                    }
+                }
+
+                if !matched {
+                    count = key_count(self.buf, node);
+                    i = 0;
+                    while i < count && hash_at(self.buf, node, i) < attempt_hash {
+                        i += 1;
+                    }
+                    matched = i < count && hash_at(self.buf, node, i) == attempt_hash;
+                }
+
+                if matched {
+                    let key_start = kv_at(self.buf, node, i);
+                    let mut target = key_start;
+                    let used = &self.buf[..self.len];
+                    if key.is_some()
+                        && matches!(
+                            verify_key(used, key, tag_size, &mut target)?,
+                            KeyCheck::Collision
+                        )
+                    {
+                        continue 'probe;
+                    }
+                    let val_start = target;
+                    verify_val(used, &mut target)?;
+                    if val_len >= target - val_start {
+                        let padding = self.alignment_padding(tag_size, key_size, val_len);
+                        entry_size += padding;
+                        if entry_size > bufsz || self.len > bufsz - entry_size {
+                            return Err(libc::ENOBUFS);
+                        }
+                        self.zero(key_start, target - key_start);
+                        self.zero(self.len, padding);
+                        self.len += padding;
+                        self.set_kv(node, i, self.len);
+                        return Ok(self.append_entry(key, key_size, tag_size, val_len));
+                    }
+                    self.zero(val_start, target - val_start);
+                    return Ok(val_start);
+                }
+
+                if child_at(self.buf, node, 0) != 0 {
+                    let next = child_at(self.buf, node, i);
+                    parent = Some(node);
+                    node = next;
+                    if node & NODE_ALIGNMENT_MASK != 0 {
+                        return Err(libc::EBADMSG);
+                    }
+                    if next > self.len - NODE_SIZE {
+                        return Err(libc::EFAULT);
+                    }
+                    walks += 1;
+                    if walks > TREE_HEIGHT_MAX {
+                        return Err(libc::EBADMSG);
+                    }
+                    continue;
+                }
+
+                let padding = self.alignment_padding(tag_size, key_size, val_len);
+                entry_size += padding;
+                if entry_size > bufsz || self.len > bufsz - entry_size {
+                    return Err(libc::ENOBUFS);
+                }
+                let mut j = count;
+                while j > i {
+                    let shifted_hash = hash_at(self.buf, node, j - 1);
+                    let shifted_kv = kv_at(self.buf, node, j - 1);
+                    self.set_hash(node, j, shifted_hash);
+                    self.set_kv(node, j, shifted_kv);
+                    j -= 1;
+                }
+                self.set_hash(node, i, attempt_hash);
+                let node_count = key_count(self.buf, node);
+                self.set_kc(node, node_count + 1);
+                self.zero(self.len, padding);
+                self.len += padding;
+                self.set_kv(node, i, self.len);
+                let size_kc = read_u32(self.buf, ofs + SIZE_KC_OFS);
+                let size = (size_kc >> SIZE_SHIFT).wrapping_add(1);
+                write_u32(
+                    self.buf,
+                    ofs + SIZE_KC_OFS,
+                    (size_kc & ((1 << SIZE_SHIFT) - 1)) | (size << SIZE_SHIFT),
+                );
+                return Ok(self.append_entry(key, key_size, tag_size, val_len));
+            }
+        }
+        Err(libc::EINVAL)
+    }
+
+    fn alignment_padding(&self, tag_size: usize, key_size: u32, val_len: usize) -> usize {
+        let mask = if val_len == TYPE_SIZES[LITE3_TYPE_OBJECT as usize] {
+            NODE_ALIGNMENT_MASK
+        } else {
+            0
+        };
+        let unaligned = self.len + tag_size + key_size as usize;
+        ((unaligned + mask) & !mask) - unaligned
+    }
+
+    fn append_entry(
+        &mut self,
+        key: Option<&[u8]>,
+        key_size: u32,
+        tag_size: usize,
+        val_len: usize,
+    ) -> usize {
+        if let Some(key) = key {
+            let tag = (key_size << 2) | (tag_size as u32 - 1);
+            self.buf[self.len..self.len + tag_size].copy_from_slice(&tag.to_le_bytes()[..tag_size]);
+            self.len += tag_size;
+            self.buf[self.len..self.len + key_size as usize]
+                .copy_from_slice(&key[..key_size as usize]);
+            self.len += key_size as usize;
+        }
+        let val = self.len;
+        self.len += VAL_SIZE + val_len;
+        val
+    }
+
+    /// Insert a value under `slot` and write its type byte; returns the payload offset.
+    fn insert(&mut self, slot: &Slot, kind: u8, payload_len: usize) -> Lite3Result<usize> {
+        let val = match slot {
+            Slot::Root => unreachable!("root is initialised directly"),
+            Slot::Key(parent, key) => {
+                if self.len > self.buf.len()
+                    || NODE_SIZE > self.len
+                    || *parent > self.len - NODE_SIZE
+                {
+                    return Err(libc::EINVAL);
+                }
+                let (hash, size) = key_data(&key[..key.len() - 1]);
+                self.set_impl(*parent, Some(key), hash, size, payload_len)?
+            }
+            Slot::Index(parent) => {
+                if self.len > self.buf.len()
+                    || NODE_SIZE > self.len
+                    || *parent > self.len - NODE_SIZE
+                {
+                    return Err(libc::EINVAL);
+                }
+                let index = read_u32(self.buf, parent + SIZE_KC_OFS) >> SIZE_SHIFT;
+                self.set_impl(*parent, None, index, 0, payload_len)?
+            }
+        };
+        self.buf[val] = kind;
+        Ok(val + VAL_SIZE)
+    }
+
+    fn insert_container(&mut self, slot: &Slot, kind: u8) -> Lite3Result<usize> {
+        let node = match slot {
+            Slot::Root => {
+                if self.buf.len() < NODE_SIZE {
+                    return Err(libc::EINVAL);
+                }
+                self.len = NODE_SIZE;
+                0
+            }
+            _ => self.insert(slot, kind, TYPE_SIZES[kind as usize])? - VAL_SIZE,
+        };
+        self.init_node(node, kind);
+        Ok(node)
+    }
+
+    fn insert_str(&mut self, slot: &Slot, value: &str) -> Lite3Result<()> {
+        let size = value.len() + 1;
+        let payload = self.insert(
+            slot,
+            LITE3_TYPE_STRING,
+            TYPE_SIZES[LITE3_TYPE_STRING as usize] + size,
+        )?;
+        write_u32(self.buf, payload, size as u32);
+        self.buf[payload + 4..payload + 4 + value.len()].copy_from_slice(value.as_bytes());
+        self.buf[payload + 4 + value.len()] = 0;
+        Ok(())
+    }
+
+    fn insert_scalar(&mut self, slot: &Slot, kind: u8, bytes: &[u8]) -> Lite3Result<()> {
+        let payload = self.insert(slot, kind, bytes.len())?;
+        self.buf[payload..payload + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Where a decoded JSON value lands in the Lite3 buffer.
+enum Slot {
+    Root,
+    /// Object offset plus key bytes (NUL-terminated, truncated at the first NUL like C strings).
+    Key(usize, Vec<u8>),
+    Index(usize),
+}
+
+struct DecodeSeed<'w, 'b> {
+    writer: &'w mut Writer<'b>,
+    slot: Slot,
+    depth: usize,
+}
+
+impl DecodeSeed<'_, '_> {
+    fn fail<E: de::Error>(errno: c_int) -> E {
+        set_errno(errno);
+        E::custom("lite3 encode failed")
+    }
+
+    fn scalar<E: de::Error>(self, kind: u8, bytes: &[u8]) -> Result<(), E> {
+        if matches!(self.slot, Slot::Root) {
+            return Err(Self::fail(libc::EINVAL));
+        }
+        self.writer
+            .insert_scalar(&self.slot, kind, bytes)
+            .map_err(Self::fail)
+    }
+
+    fn container<E: de::Error>(&mut self, kind: u8) -> Result<usize, E> {
+        if self.depth + 1 > JSON_NESTING_DEPTH_MAX {
+            return Err(Self::fail(libc::EINVAL));
+        }
+        self.writer
+            .insert_container(&self.slot, kind)
+            .map_err(Self::fail)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for DecodeSeed<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DecodeSeed<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.scalar(LITE3_TYPE_NULL, &[])
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<(), E> {
+        self.scalar(LITE3_TYPE_BOOL, &[u8::from(value)])
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<(), E> {
+        self.scalar(LITE3_TYPE_I64, &value.to_le_bytes())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<(), E> {
+        match i64::try_from(value) {
+            Ok(value) => self.visit_i64(value),
+            Err(_) => self.visit_f64(value as f64),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<(), E> {
+        self.scalar(LITE3_TYPE_F64, &value.to_le_bytes())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<(), E> {
+        if matches!(self.slot, Slot::Root) {
+            return Err(Self::fail(libc::EINVAL));
+        }
+        self.writer
+            .insert_str(&self.slot, value)
+            .map_err(Self::fail)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        let node = self.container(LITE3_TYPE_OBJECT)?;
+        while let Some(key) = map.next_key::<String>()? {
+            let mut key = key.into_bytes();
+            if let Some(nul) = key.iter().position(|byte| *byte == 0) {
+                key.truncate(nul);
+            }
+            key.push(0);
+            map.next_value_seed(DecodeSeed {
+                writer: &mut *self.writer,
+                slot: Slot::Key(node, key),
+                depth: self.depth + 1,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        let node = self.container(LITE3_TYPE_ARRAY)?;
+        while seq
+            .next_element_seed(DecodeSeed {
+                writer: &mut *self.writer,
+                slot: Slot::Index(node),
+                depth: self.depth + 1,
+            })?
+            .is_some()
+        {}
+        Ok(())
+    }
+}
+
+fn json_dec(json: &[u8], buf: &mut [u8]) -> Lite3Result<usize> {
+    if buf.len() > BUF_SIZE_MAX {
+        return Err(libc::EINVAL);
+    }
+    set_errno(0);
+    let mut writer = Writer { buf, len: 0 };
+    let mut deserializer = serde_json::Deserializer::from_slice(json);
+    let parsed = DecodeSeed {
+        writer: &mut writer,
+        slot: Slot::Root,
+        depth: 0,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|()| deserializer.end());
+    match parsed {
+        Ok(()) => Ok(writer.len),
+        Err(_) => Err(match errno() {
+            0 => libc::EINVAL,
+            errno => errno,
+        }),
+    }
+}
+
+/// In-order entry offsets of the object/array node at `ofs` (port of `lite3_iter_next`).
+fn entries(buf: &[u8], ofs: usize) -> Lite3Result<Vec<usize>> {
+    fn walk(buf: &[u8], node: usize, depth: usize, out: &mut Vec<usize>) -> Lite3Result<()> {
+        if depth > TREE_HEIGHT_MAX {
+            return Err(libc::EBADMSG);
+        }
+        check_node(buf, node)?;
+        let count = key_count(buf, node);
+        if child_at(buf, node, 0) != 0 {
+            walk(buf, child_at(buf, node, 0), depth + 1, out)?;
+        }
+        for i in 0..count {
+            out.push(kv_at(buf, node, i));
+            let child = child_at(buf, node, i + 1);
+            if child != 0 {
+                walk(buf, child, depth + 1, out)?;
+            }
+        }
+        Ok(())
+    }
+    let mut out = Vec::new();
+    walk(buf, ofs, 0, &mut out)?;
+    Ok(out)
+}
+
+struct JsonOut {
+    text: String,
+    pretty: bool,
+}
+
+impl JsonOut {
+    fn newline(&mut self, depth: usize) {
+        if self.pretty {
+            self.text.push('\n');
+            for _ in 0..depth {
+                self.text.push_str("    ");
+            }
+        }
+    }
+
+    fn string(&mut self, value: &str) -> Lite3Result<()> {
+        let escaped = serde_json::to_string(value).map_err(|_| libc::EINVAL)?;
+        self.text.push_str(&escaped);
+        Ok(())
+    }
+
+    fn container(&mut self, buf: &[u8], ofs: usize, depth: usize) -> Lite3Result<()> {
+        if depth + 1 > JSON_NESTING_DEPTH_MAX {
+            return Err(libc::EINVAL);
+        }
+        let kind = buf[ofs];
+        if kind != LITE3_TYPE_OBJECT && kind != LITE3_TYPE_ARRAY {
+            return Err(libc::EINVAL);
+        }
+        let (open, close) = if kind == LITE3_TYPE_OBJECT {
+            ('{', '}')
+        } else {
+            ('[', ']')
+        };
+        let items = entries(buf, ofs)?;
+        self.text.push(open);
+        for (index, entry) in items.iter().enumerate() {
+            if index > 0 {
+                self.text.push(',');
+            }
+            self.newline(depth + 1);
+            let mut target = *entry;
+            if kind == LITE3_TYPE_OBJECT {
+                verify_key(buf, None, 0, &mut target)?;
+                let key_start = *entry + (buf[*entry] & 0x3) as usize + 1;
+                let key = CStr::from_bytes_until_nul(&buf[key_start..target])
+                    .map_err(|_| libc::EINVAL)?
+                    .to_str()
+                    .map_err(|_| libc::EINVAL)?;
+                self.string(key)?;
+                self.text.push(':');
+                if self.pretty {
+                    self.text.push(' ');
+                }
+            }
+            let val = target;
+            verify_val(buf, &mut target)?;
+            self.value(buf, val, depth + 1)?;
+        }
+        if !items.is_empty() {
+            self.newline(depth);
+        }
+        self.text.push(close);
+        Ok(())
+    }
+
+    fn value(&mut self, buf: &[u8], val: usize, depth: usize) -> Lite3Result<()> {
+        let payload = val + VAL_SIZE;
+        match buf[val] {
+            LITE3_TYPE_NULL => self.text.push_str("null"),
+            LITE3_TYPE_BOOL => self
+                .text
+                .push_str(if buf[payload] != 0 { "true" } else { "false" }),
+            LITE3_TYPE_I64 => {
+                let value =
+                    i64::from_le_bytes(buf[payload..payload + 8].try_into().expect("8 bytes"));
+                self.text.push_str(&value.to_string());
+            }
+            LITE3_TYPE_F64 => {
+                let value =
+                    f64::from_le_bytes(buf[payload..payload + 8].try_into().expect("8 bytes"));
+                if !value.is_finite() {
+                    return Err(libc::EINVAL);
+                }
+                let text = serde_json::to_string(&value).map_err(|_| libc::EINVAL)?;
+                self.text.push_str(&text);
+            }
+            LITE3_TYPE_BYTES => {
+                let len = read_u32(buf, payload) as usize;
+                let encoded = base64_encode(&buf[payload + 4..payload + 4 + len]);
+                self.string(&encoded)?;
+            }
+            LITE3_TYPE_STRING => {
+                let size = read_u32(buf, payload) as usize;
+                let bytes = &buf[payload + 4..payload + 4 + size.saturating_sub(1)];
+                let text = std::str::from_utf8(bytes).map_err(|_| libc::EINVAL)?;
+                self.string(text)?;
+            }
+            LITE3_TYPE_OBJECT | LITE3_TYPE_ARRAY => self.container(buf, val, depth)?,
+            _ => return Err(libc::EINVAL),
+        }
+        Ok(())
+    }
+}
+
+fn json_enc(buf: &[u8], ofs: usize, pretty: bool) -> Lite3Result<String> {
+    verify_get(buf, ofs)?;
+    let mut out = JsonOut {
+        text: String::new(),
+        pretty,
+    };
+    out.container(buf, ofs, 0)?;
+    Ok(out.text)
+}
+
+/// Borrow the caller's buffer; a null pointer is only valid for an empty buffer.
+///
+/// # Safety
+/// `buf` must be valid for reads of `len` bytes for the returned lifetime.
+unsafe fn input<'a>(buf: *const c_uchar, len: usize) -> &'a [u8] {
+    if buf.is_null() || len == 0 {
+        return &[];
+    }
+    // SAFETY: upheld by the caller per this function's contract.
+    unsafe { std::slice::from_raw_parts(buf, len) }
+}
+
+/// # Safety
+/// `key` must be null or point to a NUL-terminated string.
+unsafe fn key_bytes<'a>(key: *const c_char) -> Option<&'a [u8]> {
+    if key.is_null() {
+        return None;
+    }
+    // SAFETY: upheld by the caller per this function's contract.
+    Some(unsafe { CStr::from_ptr(key) }.to_bytes())
+}
+
+fn enc_result(result: Lite3Result<String>, out_len: *mut usize) -> *mut c_char {
+    match result.and_then(|text| CString::new(text).map_err(|_| libc::EINVAL)) {
+        Ok(text) => {
+            if !out_len.is_null() {
+                // SAFETY: caller passes a valid `out_len` pointer or null.
+                unsafe { *out_len = text.as_bytes().len() };
+            }
+            text.into_raw()
+        }
+        Err(errno) => {
+            set_errno(errno);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// Same contract as the C shim: valid pointers/lengths for the duration of the call.
+pub unsafe fn plasmite_lite3_json_dec(
+    json_str: *const c_char,
+    json_len: usize,
+    buf: *mut c_uchar,
+    out_len: *mut usize,
+    buf_sz: usize,
+) -> c_int {
+    if json_str.is_null() || buf.is_null() || out_len.is_null() {
+        set_errno(libc::EINVAL);
+        return -1;
+    }
+    // SAFETY: caller guarantees `json_str` is readable for `json_len` bytes and `buf`
+    // is writable for `buf_sz` bytes; the two regions do not overlap.
+    let (json, buf) = unsafe {
+        (
+            input(json_str.cast(), json_len),
+            std::slice::from_raw_parts_mut(buf, buf_sz),
+        )
+    };
+    status(json_dec(json, buf).map(|len| {
+        // SAFETY: checked non-null above.
+        unsafe { *out_len = len };
+    }))
+}
+
+/// # Safety
+/// Same contract as the C shim; free the result with `plasmite_lite3_free`.
+pub unsafe fn plasmite_lite3_json_enc(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    out_len: *mut usize,
+) -> *mut c_char {
+    // SAFETY: caller guarantees `buf` is readable for `buf_len` bytes.
+    enc_result(
+        json_enc(unsafe { input(buf, buf_len) }, ofs, false),
+        out_len,
+    )
+}
+
+/// # Safety
+/// Same contract as the C shim; free the result with `plasmite_lite3_free`.
+pub unsafe fn plasmite_lite3_json_enc_pretty(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    out_len: *mut usize,
+) -> *mut c_char {
+    // SAFETY: caller guarantees `buf` is readable for `buf_len` bytes.
+    enc_result(json_enc(unsafe { input(buf, buf_len) }, ofs, true), out_len)
+}
+
+/// # Safety
+/// `buf` must be readable for `buf_len` bytes.
+pub unsafe fn plasmite_lite3_get_root_type(buf: *const c_uchar, buf_len: usize) -> c_uchar {
+    // SAFETY: upheld by the caller.
+    let buf = unsafe { input(buf, buf_len) };
+    if buf.len() < NODE_SIZE {
+        return LITE3_TYPE_INVALID;
+    }
+    buf[0]
+}
+
+/// # Safety
+/// `buf` must be readable for `buf_len` bytes and `key` must be a NUL-terminated string.
+pub unsafe fn plasmite_lite3_get_type(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    key: *const c_char,
+) -> c_uchar {
+    // SAFETY: upheld by the caller.
+    let (buf, key) = unsafe { (input(buf, buf_len), key_bytes(key)) };
+    let Some(key) = key else {
+        set_errno(libc::EINVAL);
+        return LITE3_TYPE_INVALID;
+    };
+    match get_obj_val(buf, ofs, key) {
+        Ok(val) => buf[val],
+        Err(errno) => {
+            set_errno(errno);
+            LITE3_TYPE_INVALID
+        }
+    }
+}
+
+/// # Safety
+/// Pointers must be valid as for the C shim; `out_ofs` must be writable.
+pub unsafe fn plasmite_lite3_get_val_ofs(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    key: *const c_char,
+    out_ofs: *mut usize,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let (buf, key) = unsafe { (input(buf, buf_len), key_bytes(key)) };
+    let Some(key) = key else {
+        set_errno(libc::EINVAL);
+        return -1;
+    };
+    status(get_lookup(buf, ofs, key).map(|val| {
+        // SAFETY: caller passes a writable `out_ofs`.
+        unsafe { *out_ofs = val };
+    }))
+}
+
+/// # Safety
+/// Pointers must be valid as for the C shim; `out` must be writable.
+pub unsafe fn plasmite_lite3_get_bool(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    key: *const c_char,
+    out: *mut bool,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let (buf, key) = unsafe { (input(buf, buf_len), key_bytes(key)) };
+    let result = key
+        .ok_or(libc::EINVAL)
+        .and_then(|key| get_obj_val(buf, ofs, key));
+    status(result.and_then(|val| {
+        if buf[val] != LITE3_TYPE_BOOL {
+            return Err(libc::EINVAL);
+        }
+        // SAFETY: caller passes a writable `out`.
+        unsafe { *out = buf[val + VAL_SIZE] != 0 };
+        Ok(())
+    }))
+}
+
+/// # Safety
+/// Pointers must be valid as for the C shim; `out` must be writable.
+pub unsafe fn plasmite_lite3_get_i64(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    key: *const c_char,
+    out: *mut i64,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let (buf, key) = unsafe { (input(buf, buf_len), key_bytes(key)) };
+    let result = key
+        .ok_or(libc::EINVAL)
+        .and_then(|key| get_obj_val(buf, ofs, key));
+    status(result.and_then(|val| {
+        if buf[val] != LITE3_TYPE_I64 {
+            return Err(libc::EINVAL);
+        }
+        let payload = val + VAL_SIZE;
+        let value = i64::from_le_bytes(buf[payload..payload + 8].try_into().expect("8 bytes"));
+        // SAFETY: caller passes a writable `out`.
+        unsafe { *out = value };
+        Ok(())
+    }))
+}
+
+/// # Safety
+/// Pointers must be valid as for the C shim; `out` must be writable.
+pub unsafe fn plasmite_lite3_get_f64(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    key: *const c_char,
+    out: *mut f64,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let (buf, key) = unsafe { (input(buf, buf_len), key_bytes(key)) };
+    let result = key
+        .ok_or(libc::EINVAL)
+        .and_then(|key| get_obj_val(buf, ofs, key));
+    status(result.and_then(|val| {
+        if buf[val] != LITE3_TYPE_F64 {
+            return Err(libc::EINVAL);
+        }
+        let payload = val + VAL_SIZE;
+        let value = f64::from_le_bytes(buf[payload..payload + 8].try_into().expect("8 bytes"));
+        // SAFETY: caller passes a writable `out`.
+        unsafe { *out = value };
+        Ok(())
+    }))
+}
+
+/// # Safety
+/// Pointers must be valid as for the C shim; the returned pointer borrows from `buf`.
+pub unsafe fn plasmite_lite3_get_str(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    key: *const c_char,
+    out_ptr: *mut *const c_char,
+    out_len: *mut usize,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let (bytes, key) = unsafe { (input(buf, buf_len), key_bytes(key)) };
+    let result = key
+        .ok_or(libc::EINVAL)
+        .and_then(|key| get_obj_val(bytes, ofs, key));
+    status(result.and_then(|val| {
+        if bytes[val] != LITE3_TYPE_STRING {
+            return Err(libc::EINVAL);
+        }
+        let payload = val + VAL_SIZE;
+        let size = read_u32(bytes, payload) as usize;
+        // SAFETY: `get_obj_val` bounds-checked the string within `buf`; out pointers are writable.
+        unsafe {
+            *out_ptr = buf.add(payload + 4).cast();
+            *out_len = size.saturating_sub(1);
+        }
+        Ok(())
+    }))
+}
+
+/// # Safety
+/// `buf` must be readable for `buf_len` bytes; `out` must be writable.
+pub unsafe fn plasmite_lite3_count(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    out: *mut u32,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let buf = unsafe { input(buf, buf_len) };
+    status(verify_get(buf, ofs).and_then(|()| {
+        if buf[ofs] != LITE3_TYPE_OBJECT && buf[ofs] != LITE3_TYPE_ARRAY {
+            return Err(libc::EINVAL);
+        }
+        // SAFETY: caller passes a writable `out`.
+        unsafe { *out = read_u32(buf, ofs + SIZE_KC_OFS) >> SIZE_SHIFT };
+        Ok(())
+    }))
+}
+
+/// # Safety
+/// `buf` must be readable for `buf_len` bytes; `out_type` must be writable.
+pub unsafe fn plasmite_lite3_arr_get_type(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    index: u32,
+    out_type: *mut c_uchar,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let buf = unsafe { input(buf, buf_len) };
+    status(get_by_index(buf, ofs, index).map(|val| {
+        // SAFETY: caller passes a writable `out_type`.
+        unsafe { *out_type = buf[val] };
+    }))
+}
+
+/// # Safety
+/// `buf` must be readable for `buf_len` bytes; the returned pointer borrows from `buf`.
+pub unsafe fn plasmite_lite3_arr_get_str(
+    buf: *const c_uchar,
+    buf_len: usize,
+    ofs: usize,
+    index: u32,
+    out_ptr: *mut *const c_char,
+    out_len: *mut usize,
+) -> c_int {
+    // SAFETY: upheld by the caller.
+    let bytes = unsafe { input(buf, buf_len) };
+    status(get_by_index(bytes, ofs, index).and_then(|val| {
+        if bytes[val] != LITE3_TYPE_STRING {
+            return Err(libc::EINVAL);
+        }
+        let payload = val + VAL_SIZE;
+        let size = read_u32(bytes, payload) as usize;
+        // SAFETY: `get_by_index` bounds-checked the string within `buf`; out pointers are writable.
+        unsafe {
+            *out_ptr = buf.add(payload + 4).cast();
+            *out_len = size.saturating_sub(1);
+        }
+        Ok(())
+    }))
+}
+
+/// # Safety
+/// Always safe to call; mirrors the C shim's errno accessor.
+pub unsafe fn plasmite_lite3_last_errno() -> c_int {
+    errno()
+}
+
+/// # Safety
+/// `ptr` must be null or a pointer returned by `plasmite_lite3_json_enc*` not yet freed.
+pub unsafe fn plasmite_lite3_free(ptr: *mut c_void) {
+    if !ptr.is_null() {
+        // SAFETY: `ptr` came from `CString::into_raw` in `enc_result`.
+        drop(unsafe { CString::from_raw(ptr.cast()) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LITE3_TYPE_ARRAY, LITE3_TYPE_STRING, json_dec, json_enc, plasmite_lite3_get_f64,
+        plasmite_lite3_get_str,
+    };
+
+    fn encode(json: &str) -> Vec<u8> {
+        let mut buf = vec![0u8; 64 * 1024];
+        let len = json_dec(json.as_bytes(), &mut buf).expect("decode");
+        buf.truncate(len);
+        buf
+    }
+
+    #[cfg(not(feature = "pure-lite3"))]
+    fn encode_c(json: &str) -> Vec<u8> {
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut len = 0usize;
+        let ret = unsafe {
+            crate::core::lite3::sys::plasmite_lite3_json_dec(
+                json.as_ptr().cast(),
+                json.len(),
+                buf.as_mut_ptr(),
+                &mut len,
+                buf.len(),
+            )
+        };
+        assert_eq!(ret, 0);
+        buf.truncate(len);
+        buf
+    }
+
+    fn wide_object(keys: usize) -> String {
+        let fields: Vec<String> = (0..keys).map(|i| format!("\"k{i}\":{i}")).collect();
+        format!("{{{}}}", fields.join(","))
+    }
+
+    #[test]
+    fn round_trips_nested_documents() {
+        let json = r#"{"meta":{"tags":["a","b"]},"data":{"n":-3,"f":1.5,"ok":true,"none":null,"s":"hi \"x\""}}"#;
+        let buf = encode(json);
+        let text = json_enc(&buf, 0, false).expect("encode");
+        let expected: serde_json::Value = serde_json::from_str(json).expect("json");
+        let actual: serde_json::Value = serde_json::from_str(&text).expect("json");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn scalar_getters_read_floats_and_borrowed_strings() {
+        let buf = encode(r#"{"f":-2.25,"s":"héllo","n":3}"#);
+        let mut value = 0.0f64;
+        let mut ptr = std::ptr::null();
+        let mut len = 0usize;
+        unsafe {
+            assert_eq!(
+                plasmite_lite3_get_f64(buf.as_ptr(), buf.len(), 0, c"f".as_ptr(), &mut value),
+                0
+            );
+            assert_eq!(
+                plasmite_lite3_get_str(
+                    buf.as_ptr(),
+                    buf.len(),
+                    0,
+                    c"s".as_ptr(),
+                    &mut ptr,
+                    &mut len
+                ),
+                0
+            );
+            assert_eq!(
+                std::slice::from_raw_parts(ptr.cast::<u8>(), len),
+                "héllo".as_bytes()
+            );
+            assert!(
+                plasmite_lite3_get_f64(buf.as_ptr(), buf.len(), 0, c"n".as_ptr(), &mut value) < 0
+            );
+            assert!(
+                plasmite_lite3_get_str(
+                    buf.as_ptr(),
+                    buf.len(),
+                    0,
+                    c"x".as_ptr(),
+                    &mut ptr,
+                    &mut len
+                ) < 0
+            );
+        }
+        assert_eq!(value, -2.25);
+    }
+
+    #[test]
+    fn splits_nodes_beyond_seven_keys() {
+        let json = wide_object(200);
+        let buf = encode(&json);
+        let text = json_enc(&buf, 0, false).expect("encode");
+        let actual: serde_json::Value = serde_json::from_str(&text).expect("json");
+        assert_eq!(actual.as_object().map(|obj| obj.len()), Some(200));
+        assert_eq!(actual["k137"], 137);
+    }
+
+    #[test]
+    fn array_strings_are_indexed_in_order() {
+        let buf = encode(r#"["zero","one","two","three","four","five","six","seven","eight"]"#);
+        assert_eq!(buf[0], LITE3_TYPE_ARRAY);
+        let val = super::get_by_index(&buf, 0, 8).expect("index");
+        assert_eq!(buf[val], LITE3_TYPE_STRING);
+        assert!(super::get_by_index(&buf, 0, 9).is_err());
+    }
+
+    #[test]
+    fn rejects_scalar_roots_and_deep_nesting() {
+        let mut buf = vec![0u8; 4096];
+        assert_eq!(json_dec(b"42", &mut buf), Err(libc::EINVAL));
+        let deep = format!("{}{}", "[".repeat(33), "]".repeat(33));
+        assert_eq!(json_dec(deep.as_bytes(), &mut buf), Err(libc::EINVAL));
+        let mut tiny = vec![0u8; 100];
+        assert_eq!(
+            json_dec(wide_object(20).as_bytes(), &mut tiny),
+            Err(libc::ENOBUFS)
+        );
+    }
+
+    #[test]
+    fn pretty_output_uses_four_space_indent() {
+        let buf = encode(r#"{"a":[1],"b":{}}"#);
+        let text = json_enc(&buf, 0, true).expect("encode");
+        assert_eq!(text, "{\n    \"a\": [\n        1\n    ],\n    \"b\": {}\n}");
+    }
+
+    #[cfg(not(feature = "pure-lite3"))]
+    #[test]
+    fn matches_c_encoder_byte_for_byte() {
+        for json in [
+            r#"{"meta":{"tags":["x","y"]},"data":{"msg":"hello","n":7}}"#.to_string(),
+            wide_object(300),
+            r#"{"a":[1,2.5,-9223372036854775808,18446744073709551615,null,true,{"k":"v"}],"b":"é"}"#
+                .to_string(),
+        ] {
+            assert_eq!(encode(&json), encode_c(&json), "{json}");
+        }
+    }
+}
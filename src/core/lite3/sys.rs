@@ -71,6 +71,23 @@ unsafe extern "C" {
         out: *mut i64,
     ) -> c_int;
 
+    pub fn plasmite_lite3_get_f64(
+        buf: *const c_uchar,
+        buf_len: usize,
+        ofs: usize,
+        key: *const c_char,
+        out: *mut f64,
+    ) -> c_int;
+
+    pub fn plasmite_lite3_get_str(
+        buf: *const c_uchar,
+        buf_len: usize,
+        ofs: usize,
+        key: *const c_char,
+        out_ptr: *mut *const c_char,
+        out_len: *mut usize,
+    ) -> c_int;
+
     pub fn plasmite_lite3_count(
         buf: *const c_uchar,
         buf_len: usize,
@@ -1,15 +1,27 @@
 //! Purpose: Core storage, encoding, planning, validation, and error modeling.
-//! Exports: `pool`, `cursor`, `plan`, `frame`, `validate`, `error`, `lite3`, `format`, `notify`.
+//! Exports: `pool`, `activity`, `attachment`, `bloom`, `chaos`, `chunk`, `cursor`, `plan`, `frame`, `validate`, `error`, `lite3`,
+//! `format`, `hex`, `notify`, `journal`, `inuse`, `view`.
 //! Role: Internal core layer shared by CLI and tests; does not perform CLI I/O.
 //! Invariants: Public functions take explicit inputs and return explicit results/errors.
 //! Invariants: Full scans/expensive validation are opt-in and not on hot paths.
 #![allow(clippy::result_large_err)]
+pub mod activity;
+pub mod attachment;
+pub mod base64;
+pub mod bloom;
+pub mod chaos;
+pub mod chunk;
 pub mod cursor;
 pub mod error;
 pub mod format;
 pub mod frame;
+pub mod hash;
+pub mod hex;
+pub mod inuse;
+pub mod journal;
 pub mod lite3;
 pub mod notify;
 pub mod plan;
 pub mod pool;
 pub mod validate;
+pub mod view;
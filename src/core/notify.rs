@@ -1,14 +1,20 @@
 //! Purpose: Provide best-effort per-pool notifications via named semaphores.
-//! Exports: `PoolSemaphore`, `NotifyError`, `WaitOutcome`, `pool_semaphore_name`, `post_for_path`.
+//! Exports: `PoolSemaphore`, `NotifyError`, `WaitOutcome`, `NotifyCounters`, `counters`,
+//! `pool_semaphore_name`, `post_for_path`.
 //! Role: Optimization for tail-style consumers; correctness must not depend on notify.
 //! Invariants: Name derivation is deterministic; failures never panic or block progress.
 //! Invariants: Unsupported semaphore operations surface as `NotifyError::Unavailable`.
+//! Invariants: Waiters register on a companion `<name>-w` semaphore while blocked; writers
+//! only top pending posts up to the registered waiter count (at least one), and each wakeup
+//! consumes exactly one post, so a burst wakes every follower once and no follower twice.
 
+use crate::core::hex::hex_encode;
 use sha2::{Digest, Sha256};
 use std::io;
 use std::path::Path;
 #[cfg(test)]
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 #[cfg(unix)]
@@ -37,33 +43,103 @@ pub(crate) trait SemaphoreBackend: Clone {
     fn open(&self, name: &str) -> Result<Self::Handle, NotifyError>;
     fn post(&self, handle: &Self::Handle) -> Result<(), NotifyError>;
     fn wait(&self, handle: &Self::Handle, timeout: Duration) -> Result<WaitOutcome, NotifyError>;
+    /// Consume one pending post without blocking; `Ok(false)` when none is pending.
+    fn try_wait(&self, handle: &Self::Handle) -> Result<bool, NotifyError>;
+    /// Pending post count when the platform can report it.
+    fn pending(&self, handle: &Self::Handle) -> Option<u64>;
     fn close(&self, handle: &Self::Handle);
 }
 
+static POSTS: AtomicU64 = AtomicU64::new(0);
+static COALESCED: AtomicU64 = AtomicU64::new(0);
+static WAKEUPS: AtomicU64 = AtomicU64::new(0);
+
+/// Process-wide notify counters (monotonic since process start).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct NotifyCounters {
+    /// Semaphore posts actually issued by writers.
+    pub(crate) posts: u64,
+    /// Writer posts skipped because every registered waiter already had a wakeup pending.
+    pub(crate) coalesced: u64,
+    /// Waits that returned `Signaled`.
+    pub(crate) wakeups: u64,
+}
+
+pub(crate) fn counters() -> NotifyCounters {
+    NotifyCounters {
+        posts: POSTS.load(Ordering::Relaxed),
+        coalesced: COALESCED.load(Ordering::Relaxed),
+        wakeups: WAKEUPS.load(Ordering::Relaxed),
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct Semaphore<B: SemaphoreBackend> {
     handle: B::Handle,
+    /// Counts followers currently blocked in `wait` on `handle`.
+    waiters: B::Handle,
     backend: B,
 }
 
 impl<B: SemaphoreBackend> Semaphore<B> {
     fn open_with_backend(name: String, backend: B) -> Result<Self, NotifyError> {
         let handle = backend.open(&name)?;
-        Ok(Self { handle, backend })
+        let waiters = match backend.open(&format!("{name}-w")) {
+            Ok(waiters) => waiters,
+            Err(err) => {
+                backend.close(&handle);
+                return Err(err);
+            }
+        };
+        Ok(Self {
+            handle,
+            waiters,
+            backend,
+        })
     }
 
     pub(crate) fn post(&self) -> Result<(), NotifyError> {
         self.backend.post(&self.handle)
     }
 
+    /// Top pending posts up to one per registered waiter; returns whether any post was issued.
+    ///
+    /// Without `sem_getvalue` (non-Linux) this always posts once.
+    pub(crate) fn post_coalesced(&self) -> Result<bool, NotifyError> {
+        let Some(pending) = self.backend.pending(&self.handle) else {
+            self.backend.post(&self.handle)?;
+            POSTS.fetch_add(1, Ordering::Relaxed);
+            return Ok(true);
+        };
+        let wanted = self.backend.pending(&self.waiters).unwrap_or(0).max(1);
+        if pending >= wanted {
+            COALESCED.fetch_add(1, Ordering::Relaxed);
+            return Ok(false);
+        }
+        for _ in pending..wanted {
+            self.backend.post(&self.handle)?;
+            POSTS.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(true)
+    }
+
+    /// Wait for a post and consume exactly one, leaving the rest for other followers.
     pub(crate) fn wait(&self, timeout: Duration) -> Result<WaitOutcome, NotifyError> {
-        self.backend.wait(&self.handle, timeout)
+        self.backend.post(&self.waiters)?;
+        let outcome = self.backend.wait(&self.handle, timeout);
+        let _ = self.backend.try_wait(&self.waiters);
+        let outcome = outcome?;
+        if outcome == WaitOutcome::Signaled {
+            WAKEUPS.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(outcome)
     }
 }
 
 impl<B: SemaphoreBackend> Drop for Semaphore<B> {
     fn drop(&mut self) {
         self.backend.close(&self.handle);
+        self.backend.close(&self.waiters);
     }
 }
 
@@ -118,6 +194,33 @@ impl SemaphoreBackend for OsSemaphoreBackend {
         }
     }
 
+    fn try_wait(&self, handle: &Self::Handle) -> Result<bool, NotifyError> {
+        loop {
+            if unsafe { libc::sem_trywait(*handle) } == 0 {
+                return Ok(true);
+            }
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(code) if code == libc::EAGAIN => return Ok(false),
+                Some(code) if code == libc::EINTR => continue,
+                _ => return Err(map_sem_error_with(err)),
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn pending(&self, handle: &Self::Handle) -> Option<u64> {
+        let mut value: libc::c_int = 0;
+        let rc = unsafe { libc::sem_getvalue(*handle, &mut value) };
+        (rc == 0).then(|| value.max(0) as u64)
+    }
+
+    // macOS (and other unixes) lack a working `sem_getvalue`; always post there.
+    #[cfg(not(target_os = "linux"))]
+    fn pending(&self, _handle: &Self::Handle) -> Option<u64> {
+        None
+    }
+
     fn close(&self, handle: &Self::Handle) {
         unsafe {
             libc::sem_close(*handle);
@@ -141,6 +244,14 @@ impl SemaphoreBackend for OsSemaphoreBackend {
         Err(NotifyError::Unavailable)
     }
 
+    fn try_wait(&self, _handle: &Self::Handle) -> Result<bool, NotifyError> {
+        Err(NotifyError::Unavailable)
+    }
+
+    fn pending(&self, _handle: &Self::Handle) -> Option<u64> {
+        None
+    }
+
     fn close(&self, _handle: &Self::Handle) {}
 }
 
@@ -149,12 +260,7 @@ pub(crate) type PoolSemaphore = Semaphore<OsSemaphoreBackend>;
 pub(crate) fn pool_semaphore_name(path: &Path) -> String {
     let bytes = canonical_path_bytes(path);
     let digest = Sha256::digest(&bytes);
-    let mut hex = String::with_capacity(digest.len() * 2);
-    for byte in digest {
-        use std::fmt::Write;
-        let _ = write!(hex, "{byte:02x}");
-    }
-    format!("plsm-{hex}")
+    format!("plsm-{}", hex_encode(&digest))
 }
 
 pub(crate) fn open_for_path(path: &Path) -> Result<PoolSemaphore, NotifyError> {
@@ -172,7 +278,7 @@ pub(crate) fn post_for_path(path: &Path) -> Result<(), NotifyError> {
         return Err(NotifyError::Unavailable);
     }
     let semaphore = open_for_path(path)?;
-    semaphore.post()
+    semaphore.post_coalesced().map(|_| ())
 }
 
 #[cfg(test)]
@@ -275,6 +381,19 @@ mod tests {
             Ok(WaitOutcome::TimedOut)
         }
 
+        fn try_wait(&self, handle: &Self::Handle) -> Result<bool, NotifyError> {
+            let mut count = handle.count.lock().expect("lock");
+            if *count == 0 {
+                return Ok(false);
+            }
+            *count -= 1;
+            Ok(true)
+        }
+
+        fn pending(&self, handle: &Self::Handle) -> Option<u64> {
+            Some(*handle.count.lock().expect("lock"))
+        }
+
         fn close(&self, _handle: &Self::Handle) {}
     }
 
@@ -312,4 +431,83 @@ mod tests {
             WaitOutcome::Signaled
         );
     }
+
+    #[test]
+    fn burst_posts_coalesce_into_one_wakeup() {
+        let backend = TestBackend::default();
+        let name = pool_semaphore_name(Path::new("burst.plasmite"));
+        let writer = Semaphore::open_with_backend(name.clone(), backend.clone()).expect("open");
+        let follower = Semaphore::open_with_backend(name, backend).expect("open");
+        let before = counters();
+
+        let issued = (0..10)
+            .map(|_| writer.post_coalesced().expect("post"))
+            .filter(|posted| *posted)
+            .count();
+        assert_eq!(issued, 1);
+        assert_eq!(
+            follower.wait(Duration::from_millis(50)).expect("wait"),
+            WaitOutcome::Signaled
+        );
+        assert_eq!(
+            follower.wait(Duration::from_millis(5)).expect("wait"),
+            WaitOutcome::TimedOut
+        );
+
+        let after = counters();
+        assert!(after.coalesced >= before.coalesced + 9);
+        assert!(after.wakeups > before.wakeups);
+    }
+
+    #[test]
+    fn wait_consumes_one_post_per_wakeup() {
+        let backend = TestBackend::default();
+        let name = pool_semaphore_name(Path::new("single.plasmite"));
+        let writer = Semaphore::open_with_backend(name.clone(), backend.clone()).expect("open");
+        let follower = Semaphore::open_with_backend(name, backend).expect("open");
+        for _ in 0..3 {
+            writer.post().expect("post");
+        }
+        assert_eq!(
+            follower.wait(Duration::from_millis(50)).expect("wait"),
+            WaitOutcome::Signaled
+        );
+        assert_eq!(follower.backend.pending(&follower.handle), Some(2));
+        assert_eq!(follower.backend.pending(&follower.waiters), Some(0));
+    }
+
+    #[test]
+    fn every_concurrent_follower_wakes_on_one_append() {
+        let backend = TestBackend::default();
+        let name = pool_semaphore_name(Path::new("fanout.plasmite"));
+        let writer = Semaphore::open_with_backend(name.clone(), backend.clone()).expect("open");
+        let followers: Vec<_> = (0..2)
+            .map(|_| {
+                let follower =
+                    Semaphore::open_with_backend(name.clone(), backend.clone()).expect("open");
+                std::thread::spawn(move || {
+                    let start = std::time::Instant::now();
+                    let outcome = follower.wait(Duration::from_secs(5)).expect("wait");
+                    (outcome, start.elapsed())
+                })
+            })
+            .collect();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while writer.backend.pending(&writer.waiters) != Some(2) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "followers never blocked"
+            );
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(writer.post_coalesced().expect("post"));
+
+        for follower in followers {
+            let (outcome, elapsed) = follower.join().expect("join");
+            assert_eq!(outcome, WaitOutcome::Signaled);
+            assert!(elapsed < Duration::from_secs(1));
+        }
+        assert_eq!(writer.backend.pending(&writer.handle), Some(0));
+    }
 }
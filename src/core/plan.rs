@@ -1,11 +1,14 @@
 //! Purpose: Plan append/drop transitions for the ring without performing any I/O.
-//! Exports: `plan_append`, `AppendPlan`, `DropStep`, `DropKind`.
+//! Exports: `plan_append`, `plan_append_capped`, `AppendPlan`, `DropStep`, `DropKind`.
 //! Role: Pure planning layer used by `pool` to apply deterministic writes to storage.
 //! Invariants: No side effects; output depends only on `header`, `storage`, `payload_len`.
 //! Invariants: Reads storage only to validate/inspect existing frames when freeing space.
+//! Invariants: A message cap drops oldest frames before space is reclaimed, so the byte
+//! budget never sees frames the cap already retired.
 use crate::core::error::{Error, ErrorKind};
 use crate::core::frame::{self, FRAME_HEADER_LEN, FrameHeader, FrameState};
 use crate::core::pool::PoolHeader;
+use crate::core::view::ByteView;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DropKind {
@@ -35,6 +38,17 @@ pub fn plan_append(
     header: PoolHeader,
     storage: &[u8],
     payload_len: usize,
+) -> Result<AppendPlan, Error> {
+    plan_append_capped(header, storage, payload_len, 0)
+}
+
+/// Like `plan_append`, but also drops the oldest frames so that at most `max_messages`
+/// remain after the append (`0` means no count cap).
+pub fn plan_append_capped(
+    header: PoolHeader,
+    storage: &[u8],
+    payload_len: usize,
+    max_messages: u64,
 ) -> Result<AppendPlan, Error> {
     if payload_len > u32::MAX as usize {
         return Err(Error::new(ErrorKind::Usage).with_message("payload too large"));
@@ -77,6 +91,26 @@ pub fn plan_append(
     }
 
     let mut drops = Vec::new();
+    while max_messages > 0 && oldest_seq != 0 && newest_seq - oldest_seq + 1 >= max_messages {
+        let outcome = if let Some(fast) =
+            plan_drop_step_fast(ring_size, head, tail, tail_next_off, oldest_seq)
+        {
+            Some(fast)
+        } else {
+            plan_drop_step(storage, ring_offset, ring_size, head, tail, oldest_seq)?
+        };
+        let Some((step, new_tail, new_oldest)) = outcome else {
+            break;
+        };
+        drops.push(step);
+        tail = new_tail;
+        oldest_seq = new_oldest;
+        tail_next_off = 0;
+        if tail == head {
+            oldest_seq = 0;
+            tail_next_off = tail;
+        }
+    }
     let mut required = if oldest_seq == 0 {
         frame_len
     } else {
@@ -292,9 +326,9 @@ fn read_frame_header(
     ring_offset: usize,
     head: usize,
 ) -> Result<FrameHeader, Error> {
-    let start = ring_offset + head;
-    let end = start + FRAME_HEADER_LEN;
-    FrameHeader::decode(&storage[start..end])
+    let ring =
+        ByteView::new(storage).view(ring_offset, storage.len().saturating_sub(ring_offset))?;
+    FrameHeader::decode(ring.slice(head, FRAME_HEADER_LEN)?)
 }
 
 fn free_space(head: usize, tail: usize, ring_size: usize, oldest_seq: u64) -> usize {
@@ -326,7 +360,7 @@ fn required_space(head: usize, frame_len: usize, ring_size: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::{DropKind, plan_append};
+    use super::{DropKind, plan_append, plan_append_capped};
     use crate::core::frame::{self, FRAME_HEADER_LEN, FrameHeader, FrameState};
     use crate::core::pool::PoolHeader;
     use crate::core::validate;
@@ -475,6 +509,36 @@ mod tests {
         assert_eq!(plan.next_header.newest_seq, 3);
     }
 
+    #[test]
+    fn plan_append_capped_drops_oldest_with_free_space() {
+        let payload_len = 16usize;
+        let frame_len = frame::frame_total_len(FRAME_HEADER_LEN, payload_len).expect("frame len");
+        let ring_size = frame_len * 4;
+        let mut storage = vec![0u8; RING_OFFSET + ring_size];
+
+        let first = FrameHeader::new(FrameState::Committed, 0, 1, 0, payload_len as u32, 0);
+        write_frame(&mut storage, 0, &first, payload_len);
+        let second = FrameHeader::new(FrameState::Committed, 0, 2, 0, payload_len as u32, 0);
+        write_frame(&mut storage, frame_len, &second, payload_len);
+
+        let header = header_for(ring_size, frame_len * 2, 0, frame_len, 1, 2);
+        let uncapped = plan_append(header, &storage, payload_len).expect("plan");
+        assert!(uncapped.drops.is_empty());
+
+        let plan = plan_append_capped(header, &storage, payload_len, 2).expect("plan");
+        assert_eq!(plan.drops.len(), 1);
+        assert_eq!(plan.drops[0].kind, DropKind::Frame { seq: 1 });
+        assert_eq!(plan.frame_offset, frame_len * 2);
+        assert_eq!(plan.next_header.tail_off as usize, frame_len);
+        assert_eq!(plan.next_header.oldest_seq, 2);
+        assert_eq!(plan.next_header.newest_seq, 3);
+
+        let single = plan_append_capped(header, &storage, payload_len, 1).expect("plan");
+        assert_eq!(single.drops.len(), 2);
+        assert_eq!(single.next_header.oldest_seq, 3);
+        assert_eq!(single.next_header.newest_seq, 3);
+    }
+
     #[test]
     fn prop_plan_append_invariants() {
         let seeds = [1u64, 7, 42, 99];
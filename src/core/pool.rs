@@ -1,10 +1,24 @@
 //! Purpose: Manage pool files (create/open), mmap access, locking, and append application.
-//! Exports: `Pool`, `PoolOptions`, `AppendOptions`, `Durability`, `PoolHeader`, `Bounds`,
-//! `PoolInfo`, `SeqOffsetCache`.
+//! Exports: `Pool`, `PoolOptions`, `PoolCodec`, `AppendOptions`, `Durability`, `PoolHeader`,
+//! `Bounds`, `PoolInfo`, `SeqOffsetCache`, `TimeNearest`.
 //! Role: IO boundary for the core: owns file handles/mmap and delegates planning to `plan`.
 //! Invariants: All mutations hold an exclusive append lock across processes.
+//! Invariants: Frozen pools (`POOL_FLAG_FROZEN`) refuse appends; the flag is checked under the
+//! append lock, so no append that started after `set_frozen` returned can land.
+//! Invariants: Read-only handles (`open_read_only`) never write the file or take a lock,
+//! except `snapshot_to`, which briefly holds the append lock to copy a consistent image.
 //! Invariants: Append writes mark frames `Writing` -> payload -> `Committed`; header persists last.
 //! Invariants: Header size is fixed (4096) and validated strictly on open.
+//! Invariants: Header flags in `POOL_FLAGS_INCOMPATIBLE` must all be understood to open a pool;
+//! setting any of them writes format version 4 so older binaries refuse the pool.
+//! Invariants: Read paths reach frame and index bytes only through `RingView`, which re-checks
+//! each header snapshot against the mapping.
+//! Invariants: Bytes past the header fields hold the optional dedupe ring, message cap, and
+//! chunking limits and are never touched by header rewrites. Idempotency keys are recorded as
+//! salted hashes in the same ring, so they never appear in message payloads.
+//! Invariants: With a frame size limit, larger payloads are stored as consecutive chunk frames
+//! written under one append lock (see `core::chunk`); `join_chunks` reassembles them.
+use std::borrow::Cow;
 use std::collections::{HashMap, VecDeque};
 use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Seek, SeekFrom, Write};
@@ -13,14 +27,21 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use fs2::FileExt;
 use libc::{EACCES, EPERM};
-use memmap2::MmapMut;
+use memmap2::{MmapMut, MmapOptions};
 
+use crate::core::attachment;
+use crate::core::bloom;
+use crate::core::chaos;
+use crate::core::chunk;
 use crate::core::error::{Error, ErrorKind};
 use crate::core::format;
 use crate::core::frame::{self, FRAME_HEADER_LEN, FrameHeader, FrameState};
+use crate::core::hash::fnv1a64;
+use crate::core::journal::{self, DEFAULT_JOURNAL_CAPACITY, IntentJournal};
 use crate::core::notify;
 use crate::core::plan;
 use crate::core::validate;
+use crate::core::view::{ByteView, RingView};
 
 const MAGIC: [u8; 4] = *b"PLSM";
 const ENDIANNESS_LE: u8 = 1;
@@ -29,6 +50,87 @@ const INDEX_SLOT_BYTES: u64 = 16;
 const MAX_AUTO_INDEX_CAPACITY: u64 = 65_536;
 const MIN_RING_SIZE_FOR_INDEX: u64 = 1024;
 
+/// Header flag: stamp frames with hybrid (wall clock + monotonic) timestamps.
+pub const POOL_FLAG_HYBRID_TIMESTAMPS: u64 = 1;
+/// Header flag: frame payloads are raw JSON envelopes instead of Lite3 documents.
+pub const POOL_FLAG_JSON_CODEC: u64 = 2;
+/// Header flag: appends are recorded in the `<pool>.intent` sidecar journal.
+pub const POOL_FLAG_INTENT_JOURNAL: u64 = 4;
+/// Header flag: identical payloads within the dedupe window are not re-stored.
+pub const POOL_FLAG_DEDUPE: u64 = 8;
+/// Header flag: appends drop the oldest messages beyond a fixed message count.
+pub const POOL_FLAG_MAX_MESSAGES: u64 = 16;
+/// Header flag: payloads above a frame size limit are split into chunk frames.
+pub const POOL_FLAG_CHUNKED: u64 = 32;
+/// Header flag: each message's frame header carries a bloom filter of its string values.
+pub const POOL_FLAG_BLOOM: u64 = 64;
+/// Header flag: appends are refused (`Busy`) until the flag is cleared; set by `pool freeze`.
+pub const POOL_FLAG_FROZEN: u64 = 128;
+/// Low half of the flag word: a reader that does not know a set bit here must refuse the pool.
+/// The high half is reserved for flags older readers may safely ignore.
+pub const POOL_FLAGS_INCOMPATIBLE: u64 = 0xffff_ffff;
+/// Incompatible flags this build understands.
+const KNOWN_INCOMPATIBLE_FLAGS: u64 = POOL_FLAG_HYBRID_TIMESTAMPS
+    | POOL_FLAG_JSON_CODEC
+    | POOL_FLAG_INTENT_JOURNAL
+    | POOL_FLAG_DEDUPE
+    | POOL_FLAG_MAX_MESSAGES
+    | POOL_FLAG_CHUNKED
+    | POOL_FLAG_BLOOM
+    | POOL_FLAG_FROZEN;
+
+/// Dedupe window size (u32) and next ring slot (u32) live in the header's spare bytes.
+const DEDUPE_WINDOW_OFFSET: usize = 104;
+const DEDUPE_CURSOR_OFFSET: usize = 108;
+/// Message cap (u64) for `POOL_FLAG_MAX_MESSAGES` pools.
+const MAX_MESSAGES_OFFSET: usize = 112;
+/// Frame payload limit (u32) and optional whole-message cap (u32, 0 = none) for
+/// `POOL_FLAG_CHUNKED` pools.
+const MAX_FRAME_OFFSET: usize = 120;
+const MAX_MESSAGE_OFFSET: usize = 124;
+/// Ring of `(payload hash, seq)` pairs filling the rest of the header page.
+const DEDUPE_RING_OFFSET: usize = 128;
+const DEDUPE_SLOT_BYTES: usize = 16;
+/// Largest `--dedupe-window` that fits in the header page.
+pub const MAX_DEDUPE_WINDOW: u32 = ((HEADER_SIZE - DEDUPE_RING_OFFSET) / DEDUPE_SLOT_BYTES) as u32;
+
+/// Payload encoding recorded in the pool header at create time.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PoolCodec {
+    /// Canonical Lite3 `{meta, data}` documents.
+    #[default]
+    Lite3,
+    /// UTF-8 JSON `{"meta":{"tags":[...]},"data":{...}}` envelopes.
+    Json,
+}
+
+impl PoolCodec {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PoolCodec::Lite3 => "lite3",
+            PoolCodec::Json => "json",
+        }
+    }
+
+    fn from_flags(flags: u64) -> Self {
+        if flags & POOL_FLAG_JSON_CODEC != 0 {
+            PoolCodec::Json
+        } else {
+            PoolCodec::Lite3
+        }
+    }
+}
+
+/// Which frame `Pool::get_at_time` resolves to when no frame has the exact timestamp.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TimeNearest {
+    /// Newest frame stamped at or before the requested time.
+    #[default]
+    Before,
+    /// Oldest frame stamped at or after the requested time.
+    After,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct PoolHeader {
     pub file_size: u64,
@@ -76,7 +178,7 @@ impl PoolHeader {
     fn encode(&self) -> [u8; HEADER_SIZE] {
         let mut buf = [0u8; HEADER_SIZE];
         buf[0..4].copy_from_slice(&MAGIC);
-        buf[4..8].copy_from_slice(&format::POOL_FORMAT_VERSION.to_le_bytes());
+        buf[4..8].copy_from_slice(&format_version(self.flags).to_le_bytes());
         buf[8] = ENDIANNESS_LE;
 
         write_u64(&mut buf, 16, self.file_size);
@@ -102,7 +204,7 @@ impl PoolHeader {
             return Err(Error::new(ErrorKind::Corrupt).with_message("bad magic"));
         }
         let version = u32::from_le_bytes(read_4(buf, 4));
-        if version != format::POOL_FORMAT_VERSION {
+        if !format::SUPPORTED_POOL_FORMAT_VERSIONS.contains(&version) {
             return Err(format::pool_version_error(version));
         }
         if buf[8] != ENDIANNESS_LE {
@@ -115,6 +217,10 @@ impl PoolHeader {
         let ring_offset = read_u64(buf, 40);
         let ring_size = read_u64(buf, 48);
         let flags = read_u64(buf, 56);
+        let unknown_flags = flags & POOL_FLAGS_INCOMPATIBLE & !KNOWN_INCOMPATIBLE_FLAGS;
+        if unknown_flags != 0 {
+            return Err(format::pool_feature_error(unknown_flags));
+        }
         let head_off = read_u64(buf, 64);
         let tail_off = read_u64(buf, 72);
         let tail_next_off = read_u64(buf, 80);
@@ -191,6 +297,11 @@ impl PoolHeader {
     }
 }
 
+/// Ring hash for an idempotency key; salted so it cannot alias a payload hash.
+fn idempotency_hash(key: &[u8]) -> u64 {
+    fnv1a64(&[b"idempotency\0".as_slice(), key].concat())
+}
+
 fn read_4(buf: &[u8], offset: usize) -> [u8; 4] {
     let mut out = [0u8; 4];
     out.copy_from_slice(&buf[offset..offset + 4]);
@@ -225,6 +336,15 @@ fn write_u32(buf: &mut [u8], offset: usize, value: u32) {
 pub struct PoolOptions {
     pub file_size: u64,
     pub index_capacity: Option<u32>,
+    pub hybrid_timestamps: bool,
+    pub codec: PoolCodec,
+    pub preallocate: bool,
+    pub intent_journal: bool,
+    pub dedupe_window: u32,
+    pub max_messages: Option<u64>,
+    pub max_frame_bytes: Option<u32>,
+    pub max_message_bytes: Option<u32>,
+    pub bloom_filter: bool,
 }
 
 impl PoolOptions {
@@ -232,6 +352,15 @@ impl PoolOptions {
         Self {
             file_size,
             index_capacity: None,
+            hybrid_timestamps: false,
+            codec: PoolCodec::Lite3,
+            preallocate: false,
+            intent_journal: false,
+            dedupe_window: 0,
+            max_messages: None,
+            max_frame_bytes: None,
+            max_message_bytes: None,
+            bloom_filter: false,
         }
     }
 
@@ -240,6 +369,69 @@ impl PoolOptions {
         self
     }
 
+    /// Stamp each frame with `max(wall clock, previous timestamp + 1ns)` so frame
+    /// timestamps never go backwards when the system clock jumps (sleep/wake, NTP).
+    pub fn with_hybrid_timestamps(mut self, enabled: bool) -> Self {
+        self.hybrid_timestamps = enabled;
+        self
+    }
+
+    pub fn with_codec(mut self, codec: PoolCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Reserve every block of the file at create time instead of leaving it sparse.
+    /// Appends write through the mmap, so on a full disk a sparse pool does not see ENOSPC:
+    /// the process gets SIGBUS when it touches an unallocated page. Enable this (CLI:
+    /// `--preallocate`) in production so running out of space fails at create time.
+    pub fn with_preallocate(mut self, enabled: bool) -> Self {
+        self.preallocate = enabled;
+        self
+    }
+
+    /// Record each append's seq/offset/length/checksum in a small rolling sidecar so
+    /// `doctor` can tell an interrupted append from corruption of committed frames.
+    pub fn with_intent_journal(mut self, enabled: bool) -> Self {
+        self.intent_journal = enabled;
+        self
+    }
+
+    /// Remember the hashes of the last `window` appended payloads; an append whose payload
+    /// matches one still in the pool returns that message's seq instead of storing a copy.
+    /// `0` disables dedupe; at most `MAX_DEDUPE_WINDOW`.
+    pub fn with_dedupe_window(mut self, window: u32) -> Self {
+        self.dedupe_window = window;
+        self
+    }
+
+    /// Keep at most `max_messages` messages: each append drops the oldest frames beyond the
+    /// cap even when the ring still has room, so retention is by count as well as by bytes.
+    pub fn with_max_messages(mut self, max_messages: u64) -> Self {
+        self.max_messages = Some(max_messages);
+        self
+    }
+
+    /// Store payloads larger than `bytes` as several chunk frames instead of rejecting the ones
+    /// that exceed the ring's frame limit; readers get the whole message back.
+    pub fn with_max_frame_bytes(mut self, bytes: u32) -> Self {
+        self.max_frame_bytes = Some(bytes);
+        self
+    }
+
+    /// Hard cap on a chunked message's total payload size; needs `with_max_frame_bytes`.
+    pub fn with_max_message_bytes(mut self, bytes: u32) -> Self {
+        self.max_message_bytes = Some(bytes);
+        self
+    }
+
+    /// Stamp each message's frame header with a bloom filter of its string values, so
+    /// filtered scans can skip frames that cannot match without decoding them.
+    pub fn with_bloom_filter(mut self, enabled: bool) -> Self {
+        self.bloom_filter = enabled;
+        self
+    }
+
     fn resolved_index_capacity(&self) -> u32 {
         if let Some(explicit) = self.index_capacity {
             return explicit;
@@ -266,6 +458,8 @@ pub enum Durability {
 pub struct AppendOptions {
     pub timestamp_ns: u64,
     pub durability: Durability,
+    /// Frame flag bits stored in the frame header (see `frame_flag_names`).
+    pub flags: u32,
 }
 
 impl AppendOptions {
@@ -273,8 +467,14 @@ impl AppendOptions {
         Self {
             timestamp_ns,
             durability,
+            flags: 0,
         }
     }
+
+    pub fn with_flags(mut self, flags: u32) -> Self {
+        self.flags = flags;
+        self
+    }
 }
 
 impl Default for AppendOptions {
@@ -282,6 +482,7 @@ impl Default for AppendOptions {
         Self {
             timestamp_ns: 0,
             durability: Durability::Fast,
+            flags: 0,
         }
     }
 }
@@ -364,6 +565,12 @@ impl SeqOffsetCache {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+struct ChunkLimits {
+    max_frame: u32,
+    max_message: Option<u32>,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Bounds {
     pub oldest_seq: Option<u64>,
@@ -381,6 +588,21 @@ pub struct PoolInfo {
     pub ring_size: u64,
     pub bounds: Bounds,
     pub metrics: Option<PoolMetrics>,
+    pub hybrid_timestamps: bool,
+    pub codec: PoolCodec,
+    /// Bytes the filesystem has actually allocated; below `file_size` while the file is sparse.
+    /// `None` when the platform (or a remote server) does not report it.
+    pub allocated_bytes: Option<u64>,
+    /// Message-count cap set at create time (`None` when retention is by bytes only).
+    pub max_messages: Option<u64>,
+    /// Frame payload limit above which messages are chunked (`None` when chunking is off).
+    pub max_frame_bytes: Option<u32>,
+    /// Cap on a chunked message's payload size (`None` when only the ring bounds it).
+    pub max_message_bytes: Option<u32>,
+    /// Whether appends stamp frames with string-value bloom filters.
+    pub bloom_filter: bool,
+    /// Whether `pool freeze` has set the pool to refuse appends.
+    pub frozen: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -411,11 +633,23 @@ pub struct Pool {
     file: File,
     mmap: MmapMut,
     header: PoolHeader,
+    journal: Option<IntentJournal>,
+    read_only: bool,
 }
 
 impl Pool {
     pub fn create(path: impl AsRef<Path>, options: PoolOptions) -> Result<Self, Error> {
         let path = path.as_ref().to_path_buf();
+        if options.dedupe_window > MAX_DEDUPE_WINDOW {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("dedupe window must be at most {MAX_DEDUPE_WINDOW}"))
+                .with_path(&path));
+        }
+        if options.max_messages == Some(0) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("max messages must be at least 1")
+                .with_path(&path));
+        }
 
         // Creating a pool is a mutating operation; ensure the parent directory exists so
         // API/binding users don't need to `mkdir -p` for common first-run flows.
@@ -462,12 +696,62 @@ impl Pool {
                 .with_path(&path)
                 .with_source(err)
         })?;
+        if options.preallocate {
+            file.allocate(options.file_size).map_err(|err| {
+                let kind = map_io_error_kind(&err);
+                Error::new(kind)
+                    .with_message("failed to preallocate pool file")
+                    .with_path(&path)
+                    .with_hint("Free disk space or create the pool without --preallocate.")
+                    .with_source(err)
+            })?;
+        }
 
         let index_capacity = options.resolved_index_capacity();
-        let header = PoolHeader::new(options.file_size, index_capacity)?;
+        let mut header = PoolHeader::new(options.file_size, index_capacity)?;
+        if options.hybrid_timestamps {
+            header.flags |= POOL_FLAG_HYBRID_TIMESTAMPS;
+        }
+        if options.codec == PoolCodec::Json {
+            header.flags |= POOL_FLAG_JSON_CODEC;
+        }
+        let journal = if options.intent_journal {
+            header.flags |= POOL_FLAG_INTENT_JOURNAL;
+            Some(IntentJournal::create(&path, DEFAULT_JOURNAL_CAPACITY)?)
+        } else {
+            None
+        };
+        if options.dedupe_window > 0 {
+            header.flags |= POOL_FLAG_DEDUPE;
+        }
+        if options.max_messages.is_some() {
+            header.flags |= POOL_FLAG_MAX_MESSAGES;
+        }
+        if options.bloom_filter {
+            header.flags |= POOL_FLAG_BLOOM;
+        }
+        if let Some(max_frame) = options.max_frame_bytes {
+            let ring_limit = frame::max_payload(header.ring_size as usize, FRAME_HEADER_LEN);
+            if (max_frame as usize) < chunk::MIN_MAX_FRAME || max_frame as usize > ring_limit {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message(format!(
+                        "max frame size must be between {} and {ring_limit} bytes for this pool",
+                        chunk::MIN_MAX_FRAME
+                    ))
+                    .with_path(&path));
+            }
+            header.flags |= POOL_FLAG_CHUNKED;
+        } else if options.max_message_bytes.is_some() {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("a max message size needs a max frame size")
+                .with_path(&path)
+                .with_hint(
+                    "Messages are only chunked (and capped) when the pool has --max-frame.",
+                ));
+        }
         write_header(&mut file, &header, &path)?;
 
-        let mmap = unsafe {
+        let mut mmap = unsafe {
             MmapMut::map_mut(&file).map_err(|err| {
                 let kind = map_io_error_kind(&err);
                 Error::new(kind)
@@ -477,16 +761,30 @@ impl Pool {
             })?
         };
 
-        let mut pool = Self {
+        // The file was truncated before `set_len`, so the index and ring already read as zero;
+        // leaving them untouched keeps unwritten pages sparse.
+        if options.dedupe_window > 0 {
+            write_u32(&mut mmap, DEDUPE_WINDOW_OFFSET, options.dedupe_window);
+        }
+        if let Some(max_messages) = options.max_messages {
+            write_u64(&mut mmap, MAX_MESSAGES_OFFSET, max_messages);
+        }
+        if let Some(max_frame) = options.max_frame_bytes {
+            write_u32(&mut mmap, MAX_FRAME_OFFSET, max_frame);
+            write_u32(
+                &mut mmap,
+                MAX_MESSAGE_OFFSET,
+                options.max_message_bytes.unwrap_or(0),
+            );
+        }
+        Ok(Self {
             path,
             file,
             mmap,
             header,
-        };
-        let index_start = pool.header.index_offset as usize;
-        let index_end = pool.header.ring_offset as usize;
-        pool.mmap[index_start..index_end].fill(0);
-        Ok(pool)
+            journal,
+            read_only: false,
+        })
     }
 
     pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
@@ -518,21 +816,79 @@ impl Pool {
             MmapMut::map_mut(&file)
                 .map_err(|err| Error::new(ErrorKind::Io).with_path(&path).with_source(err))?
         };
+        // The journal is advisory: pools stay usable even if the sidecar cannot be opened.
+        let journal = if header.flags & POOL_FLAG_INTENT_JOURNAL != 0 {
+            IntentJournal::open_or_create(&path).ok()
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            file,
+            mmap,
+            header,
+            journal,
+            read_only: false,
+        })
+    }
+
+    /// Open without write access: the file descriptor is read-only, the mapping is private,
+    /// the intent journal is skipped, and every append fails with `Permission`.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new().read(true).open(&path).map_err(|err| {
+            let err_kind = err.kind();
+            let mut error = Error::new(map_io_error_kind(&err))
+                .with_path(&path)
+                .with_source(err);
+            if err_kind == io::ErrorKind::NotFound {
+                error = error.with_message("not found");
+            }
+            error
+        })?;
+
+        let actual_size = file
+            .metadata()
+            .map(|meta| meta.len())
+            .map_err(|err| Error::new(ErrorKind::Io).with_path(&path).with_source(err))?;
+
+        let header = read_header(&mut file, &path)?;
+        header.validate(actual_size)?;
+
+        // A private (copy-on-write) mapping keeps the `MmapMut` type readers share with
+        // writable handles, but nothing written through it could ever reach the file.
+        let mmap = unsafe {
+            MmapOptions::new()
+                .map_copy(&file)
+                .map_err(|err| Error::new(ErrorKind::Io).with_path(&path).with_source(err))?
+        };
 
         Ok(Self {
             path,
             file,
             mmap,
             header,
+            journal: None,
+            read_only: true,
         })
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     pub fn header(&self) -> PoolHeader {
         self.header
     }
 
     pub fn header_from_mmap(&self) -> Result<PoolHeader, Error> {
-        PoolHeader::decode(&self.mmap[0..HEADER_SIZE])
+        PoolHeader::decode(ByteView::new(&self.mmap).slice(0, HEADER_SIZE)?)
+    }
+
+    /// Bounds-checked view over the ring and index of the current header snapshot.
+    pub(crate) fn ring_view(&self) -> Result<RingView<'_>, Error> {
+        RingView::new(&self.mmap, self.header_from_mmap()?)
     }
 
     pub(crate) fn path(&self) -> &Path {
@@ -547,6 +903,154 @@ impl Pool {
         &self.mmap
     }
 
+    /// Payload codec recorded in the header when the pool was created.
+    pub fn codec(&self) -> PoolCodec {
+        PoolCodec::from_flags(self.header.flags)
+    }
+
+    /// Configured dedupe window (0 when the pool was created without one).
+    pub fn dedupe_window(&self) -> u32 {
+        if self.header.flags & POOL_FLAG_DEDUPE == 0 {
+            return 0;
+        }
+        read_u32(&self.mmap, DEDUPE_WINDOW_OFFSET).min(MAX_DEDUPE_WINDOW)
+    }
+
+    /// Largest payload one frame of this pool can hold; nothing longer can be appended
+    /// unsplit, so readers can reject larger claimed lengths before allocating.
+    pub fn max_payload_len(&self) -> usize {
+        frame::max_payload(self.header.ring_size as usize, FRAME_HEADER_LEN)
+    }
+
+    /// Message-count cap (`None` when the pool was created without one).
+    pub fn max_messages(&self) -> Option<u64> {
+        if self.header.flags & POOL_FLAG_MAX_MESSAGES == 0 {
+            return None;
+        }
+        Some(read_u64(&self.mmap, MAX_MESSAGES_OFFSET)).filter(|max| *max > 0)
+    }
+
+    /// Whether appends over the pool's frame size limit are split into chunk frames.
+    pub fn is_chunked(&self) -> bool {
+        self.header.flags & POOL_FLAG_CHUNKED != 0
+    }
+
+    /// Whether appends stamp frames with string-value bloom filters.
+    pub fn has_bloom_filter(&self) -> bool {
+        self.header.flags & POOL_FLAG_BLOOM != 0
+    }
+
+    /// Whether appends are refused until `set_frozen(false)`.
+    pub fn is_frozen(&self) -> bool {
+        self.header.flags & POOL_FLAG_FROZEN != 0
+    }
+
+    /// Set or clear the frozen flag under the append lock and flush the header page.
+    /// Returns whether the pool was frozen before.
+    pub fn set_frozen(&mut self, frozen: bool) -> Result<bool, Error> {
+        let _lock = self.append_lock()?;
+        self.header = self.header_from_mmap()?;
+        let was_frozen = self.is_frozen();
+        if was_frozen != frozen {
+            self.header.flags ^= POOL_FLAG_FROZEN;
+            write_u64(&mut self.mmap, 56, self.header.flags);
+            self.mmap[4..8].copy_from_slice(&format_version(self.header.flags).to_le_bytes());
+            flush_mmap_range(
+                &self.mmap,
+                0,
+                HEADER_SIZE,
+                &self.path,
+                "failed to flush header",
+            )?;
+        }
+        Ok(was_frozen)
+    }
+
+    /// Chunking limits (`None` when the pool was created without a frame size limit).
+    fn chunk_limits(&self) -> Option<ChunkLimits> {
+        if !self.is_chunked() {
+            return None;
+        }
+        let max_frame = read_u32(&self.mmap, MAX_FRAME_OFFSET).max(chunk::MIN_MAX_FRAME as u32);
+        let max_message = Some(read_u32(&self.mmap, MAX_MESSAGE_OFFSET)).filter(|max| *max > 0);
+        Some(ChunkLimits {
+            max_frame,
+            max_message,
+        })
+    }
+
+    /// Payload of the message `frame` belongs to, joining chunk frames into one buffer.
+    /// `None` for a continuation chunk (readers skip it; the message is read at its last
+    /// chunk) and for a last chunk whose earlier chunks were already overwritten.
+    pub fn join_chunks<'a>(
+        &'a self,
+        frame: &crate::core::cursor::FrameRef<'a>,
+    ) -> Result<Option<Cow<'a, [u8]>>, Error> {
+        let Some(position) = chunk::chunk_position(frame.flags, frame.payload)? else {
+            return Ok(Some(Cow::Borrowed(frame.payload)));
+        };
+        if !position.is_last() {
+            return Ok(None);
+        }
+        let first_seq = position.first_seq(frame.seq);
+        let mut joined = Vec::new();
+        for seq in first_seq..frame.seq {
+            let part = match self.get(seq) {
+                Ok(part) => part,
+                Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err),
+            };
+            match chunk::chunk_position(part.flags, part.payload)? {
+                Some(part_position)
+                    if part_position.count == position.count
+                        && u64::from(part_position.index) == seq - first_seq =>
+                {
+                    joined.extend_from_slice(chunk::chunk_body(part.payload));
+                }
+                _ => {
+                    return Err(Error::new(ErrorKind::Corrupt)
+                        .with_message("chunked message has a foreign frame in its chunk run")
+                        .with_path(&self.path)
+                        .with_seq(seq));
+                }
+            }
+        }
+        joined.extend_from_slice(chunk::chunk_body(frame.payload));
+        Ok(Some(Cow::Owned(joined)))
+    }
+
+    /// Seq of a message still in the pool whose payload equals `payload`, if its hash is
+    /// in the dedupe ring. Payload bytes are compared so hash collisions never alias.
+    fn dedupe_lookup(&self, window: u32, hash: u64, payload: &[u8]) -> Option<u64> {
+        (0..window as usize).find_map(|slot| {
+            let offset = DEDUPE_RING_OFFSET + slot * DEDUPE_SLOT_BYTES;
+            let seq = read_u64(&self.mmap, offset + 8);
+            if seq == 0 || read_u64(&self.mmap, offset) != hash {
+                return None;
+            }
+            let frame = self.get(seq).ok()?;
+            (frame.payload == payload).then_some(seq)
+        })
+    }
+
+    /// Seq recorded for an idempotency key hash, if that message is still in the pool.
+    fn idempotency_lookup(&self, window: u32, hash: u64) -> Option<u64> {
+        (0..window as usize).find_map(|slot| {
+            let offset = DEDUPE_RING_OFFSET + slot * DEDUPE_SLOT_BYTES;
+            let seq = read_u64(&self.mmap, offset + 8);
+            (seq != 0 && read_u64(&self.mmap, offset) == hash && self.get(seq).is_ok())
+                .then_some(seq)
+        })
+    }
+
+    fn dedupe_record(&mut self, window: u32, hash: u64, seq: u64) {
+        let slot = read_u32(&self.mmap, DEDUPE_CURSOR_OFFSET) % window;
+        let offset = DEDUPE_RING_OFFSET + slot as usize * DEDUPE_SLOT_BYTES;
+        write_u64(&mut self.mmap, offset, hash);
+        write_u64(&mut self.mmap, offset + 8, seq);
+        write_u32(&mut self.mmap, DEDUPE_CURSOR_OFFSET, (slot + 1) % window);
+    }
+
     pub fn bounds(&self) -> Result<Bounds, Error> {
         let header = self.header_from_mmap()?;
         Ok(bounds_from_header(header))
@@ -565,11 +1069,32 @@ impl Pool {
             ring_size: header.ring_size,
             bounds,
             metrics: Some(self.metrics_from_header(header, bounds)),
+            hybrid_timestamps: header.flags & POOL_FLAG_HYBRID_TIMESTAMPS != 0,
+            codec: PoolCodec::from_flags(header.flags),
+            allocated_bytes: self.allocated_bytes(),
+            max_messages: self.max_messages(),
+            max_frame_bytes: self.chunk_limits().map(|limits| limits.max_frame),
+            max_message_bytes: self.chunk_limits().and_then(|limits| limits.max_message),
+            bloom_filter: header.flags & POOL_FLAG_BLOOM != 0,
+            frozen: header.flags & POOL_FLAG_FROZEN != 0,
         })
     }
 
+    #[cfg(unix)]
+    fn allocated_bytes(&self) -> Option<u64> {
+        use std::os::unix::fs::MetadataExt;
+        // `st_blocks` is always in 512-byte units, independent of the filesystem block size.
+        self.file.metadata().ok().map(|meta| meta.blocks() * 512)
+    }
+
+    #[cfg(not(unix))]
+    fn allocated_bytes(&self) -> Option<u64> {
+        None
+    }
+
     pub fn get(&self, seq: u64) -> Result<crate::core::cursor::FrameRef<'_>, Error> {
-        let mut header = self.header_from_mmap()?;
+        let ring = self.ring_view()?;
+        let mut header = ring.header();
         let bounds = bounds_from_header(header);
         let (oldest, newest) = match (bounds.oldest_seq, bounds.newest_seq) {
             (Some(oldest), Some(newest)) => (oldest, newest),
@@ -586,7 +1111,7 @@ impl Pool {
                 .with_seq(seq));
         }
 
-        if let Some(frame) = self.get_via_index(header, seq) {
+        if let Some((_, frame)) = get_via_index(&ring, seq) {
             return Ok(frame);
         }
 
@@ -623,45 +1148,113 @@ impl Pool {
         }
     }
 
-    fn get_via_index(
+    /// Frame closest to `timestamp_ns` on the `nearest` side, found by binary search over the
+    /// retained seqs (each probe is an indexed `get`). Assumes frame timestamps do not go
+    /// backwards, which hybrid-timestamp pools guarantee.
+    pub fn get_at_time(
         &self,
-        header: PoolHeader,
-        seq: u64,
-    ) -> Option<crate::core::cursor::FrameRef<'_>> {
-        let index_capacity = header.index_capacity as u64;
-        if index_capacity == 0 {
-            return None;
+        timestamp_ns: u64,
+        nearest: TimeNearest,
+    ) -> Result<crate::core::cursor::FrameRef<'_>, Error> {
+        let not_found = || {
+            Error::new(ErrorKind::NotFound).with_message(match nearest {
+                TimeNearest::Before => "no message at or before that time",
+                TimeNearest::After => "no message at or after that time",
+            })
+        };
+        let bounds = self.bounds()?;
+        let (Some(oldest), Some(newest)) = (bounds.oldest_seq, bounds.newest_seq) else {
+            return Err(not_found());
+        };
+        // First seq in [oldest, newest + 1) whose frame is past the target on the chosen side.
+        let past = |seq: u64| -> Result<bool, Error> {
+            let ts = self.get(seq)?.timestamp_ns;
+            Ok(match nearest {
+                TimeNearest::Before => ts > timestamp_ns,
+                TimeNearest::After => ts >= timestamp_ns,
+            })
+        };
+        let (mut lo, mut hi) = (oldest, newest + 1);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if past(mid)? {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
         }
+        let seq = match nearest {
+            TimeNearest::Before if lo > oldest => lo - 1,
+            TimeNearest::After if lo <= newest => lo,
+            _ => return Err(not_found()),
+        };
+        self.get(seq)
+    }
 
-        let slot = (seq % index_capacity) as usize;
-        let start = header.index_offset as usize + slot * INDEX_SLOT_BYTES as usize;
-        let end = start + INDEX_SLOT_BYTES as usize;
-        if end > self.mmap.len() {
-            return None;
-        }
-        let stored_seq = read_u64(self.mmap(), start);
-        let stored_offset = read_u64(self.mmap(), start + 8);
-        if stored_seq != seq {
-            return None;
-        }
-        let ring_size = header.ring_size as usize;
-        if stored_offset as usize >= ring_size {
-            return None;
-        }
-        let ring_offset = header.ring_offset as usize;
-        match crate::core::cursor::read_frame_at(
-            self.mmap(),
-            ring_offset,
-            ring_size,
-            stored_offset as usize,
-        ) {
-            Ok(crate::core::cursor::ReadResult::Message { frame, .. }) if frame.seq == seq => {
-                Some(frame)
+    /// Frames with `start_seq <= seq <= end_seq`, oldest first, clamped to the retained range.
+    /// The start frame is located through the inline index when its slot is intact, so the
+    /// cost scales with the range length rather than with the pool size.
+    pub fn get_range(
+        &self,
+        start_seq: u64,
+        end_seq: u64,
+    ) -> Result<Vec<crate::core::cursor::FrameRef<'_>>, Error> {
+        let mut frames: Vec<crate::core::cursor::FrameRef<'_>> = Vec::new();
+        'restart: loop {
+            let ring = self.ring_view()?;
+            let header = ring.header();
+            let bounds = bounds_from_header(header);
+            let (Some(oldest), Some(newest)) = (bounds.oldest_seq, bounds.newest_seq) else {
+                return Ok(frames);
+            };
+            let resume = frames.last().map_or(0, |frame| frame.seq + 1);
+            let start = start_seq.max(oldest).max(resume);
+            let end = end_seq.min(newest);
+            if start > end {
+                return Ok(frames);
+            }
+
+            let mut offset = self
+                .index_slot_offset(&ring, start)
+                .unwrap_or(header.tail_off as usize);
+            loop {
+                match crate::core::cursor::read_frame_at(&ring, offset)? {
+                    crate::core::cursor::ReadResult::Message { frame, next_off } => {
+                        if frame.seq > end {
+                            return Ok(frames);
+                        }
+                        let last = frame.seq == end;
+                        if frame.seq >= start {
+                            frames.push(frame);
+                        }
+                        if last {
+                            return Ok(frames);
+                        }
+                        offset = next_off;
+                    }
+                    crate::core::cursor::ReadResult::Wrap => {
+                        offset = 0;
+                    }
+                    crate::core::cursor::ReadResult::WouldBlock => return Ok(frames),
+                    // A writer lapped the scan; re-read bounds and resume after the last frame.
+                    crate::core::cursor::ReadResult::FellBehind => continue 'restart,
+                }
             }
-            _ => None,
         }
     }
 
+    /// Ring offset of the frame holding `seq`, resolved through the inline index.
+    /// Returns `None` when indexing is disabled or the slot now holds another seq; callers
+    /// then scan from `tail_off`. The offset is suitable for `Cursor::seek_to`.
+    pub fn locate(&self, seq: u64) -> Result<Option<usize>, Error> {
+        let ring = self.ring_view()?;
+        Ok(self.index_slot_offset(&ring, seq))
+    }
+
+    fn index_slot_offset(&self, ring: &RingView<'_>, seq: u64) -> Option<usize> {
+        get_via_index(ring, seq).map(|(offset, _)| offset)
+    }
+
     /// Fetch a frame using a caller-managed seq->offset cache for faster repeats.
     /// The cache is an optional optimization and must be passed explicitly.
     pub fn get_with_cache(
@@ -686,12 +1279,10 @@ impl Pool {
                 .with_seq(seq));
         }
 
-        let ring_offset = header.ring_offset as usize;
-        let ring_size = header.ring_size as usize;
+        let mut ring = RingView::new(self.mmap(), header)?;
 
         if let Some(offset) = cache.get(seq) {
-            let cached =
-                crate::core::cursor::read_frame_at(self.mmap(), ring_offset, ring_size, offset);
+            let cached = crate::core::cursor::read_frame_at(&ring, offset);
             if let Ok(crate::core::cursor::ReadResult::Message { frame, .. }) = cached {
                 if frame.seq == seq {
                     return Ok(frame);
@@ -702,7 +1293,7 @@ impl Pool {
 
         let mut offset = header.tail_off as usize;
         loop {
-            match crate::core::cursor::read_frame_at(self.mmap(), ring_offset, ring_size, offset)? {
+            match crate::core::cursor::read_frame_at(&ring, offset)? {
                 crate::core::cursor::ReadResult::Message { frame, next_off } => {
                     cache.insert(frame.seq, offset);
                     if frame.seq == seq {
@@ -724,7 +1315,8 @@ impl Pool {
                         .with_seq(seq));
                 }
                 crate::core::cursor::ReadResult::FellBehind => {
-                    header = self.header_from_mmap()?;
+                    ring = self.ring_view()?;
+                    header = ring.header();
                     if header.oldest_seq != 0 && seq < header.oldest_seq {
                         return Err(Error::new(ErrorKind::NotFound)
                             .with_message("message not found")
@@ -737,6 +1329,16 @@ impl Pool {
     }
 
     pub fn append_lock(&self) -> Result<AppendLock, Error> {
+        if self.read_only {
+            return Err(Error::new(ErrorKind::Permission)
+                .with_message("pool is opened read-only")
+                .with_path(&self.path)
+                .with_hint("Reopen the pool without read-only mode to append."));
+        }
+        self.exclusive_lock()
+    }
+
+    fn exclusive_lock(&self) -> Result<AppendLock, Error> {
         let file = self.file.try_clone().map_err(|err| {
             Error::new(ErrorKind::Io)
                 .with_path(&self.path)
@@ -750,6 +1352,99 @@ impl Pool {
         Ok(AppendLock { file })
     }
 
+    /// Roll back an append whose writer died before committing it, using the intent journal.
+    /// The header drops the oldest frames that append had already overwritten (moving to the
+    /// tail its plan recorded), and the unacknowledged message itself is discarded. Returns the
+    /// discarded seq, or `None` when the pool has no journal or no interrupted append.
+    pub fn repair_interrupted_append(&mut self) -> Result<Option<u64>, Error> {
+        let _lock = self.append_lock()?;
+        let header = self.header_from_mmap()?;
+        let Some(journal) = &self.journal else {
+            return Ok(None);
+        };
+        let Some(entries) = journal::read_entries(&self.path)? else {
+            return Ok(None);
+        };
+        let Some(entry) = entries.into_iter().find(|entry| {
+            entry.state == journal::IntentState::Intent && entry.seq == header.newest_seq + 1
+        }) else {
+            return Ok(None);
+        };
+        let rollback = entry.rollback;
+        let repaired = PoolHeader {
+            head_off: if rollback.oldest_seq == 0 {
+                rollback.tail_off
+            } else {
+                header.head_off
+            },
+            tail_off: rollback.tail_off,
+            tail_next_off: rollback.tail_next_off,
+            oldest_seq: rollback.oldest_seq,
+            ..header
+        };
+        repaired.validate(self.mmap.len() as u64)?;
+        // Header first: if we die before the entry is cleared, rerunning repeats the same rollback.
+        write_pool_header(&mut self.mmap, &repaired);
+        flush_mmap_range(
+            &self.mmap,
+            0,
+            HEADER_SIZE,
+            &self.path,
+            "failed to flush header",
+        )?;
+        self.header = repaired;
+        journal.clear(entry.seq)?;
+        Ok(Some(entry.seq))
+    }
+
+    /// Copy the whole pool file to `out` while holding the append lock, so the copy sees
+    /// no half-written frame or header. Writers block only for the copy itself; all-zero
+    /// blocks are skipped so sparse pools stay sparse. Returns the header that was copied.
+    /// Works on read-only handles too: the lock is advisory and needs no write access.
+    pub fn snapshot_to(&self, out: impl AsRef<Path>) -> Result<PoolHeader, Error> {
+        const CHUNK: usize = 64 * 1024;
+        let out = out.as_ref();
+        let io_error = |message: &'static str| {
+            move |err: io::Error| {
+                Error::new(map_io_error_kind(&err))
+                    .with_message(message)
+                    .with_path(out)
+                    .with_source(err)
+            }
+        };
+        let _lock = self.exclusive_lock()?;
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(out)
+            .map_err(|err| {
+                if err.kind() == io::ErrorKind::AlreadyExists {
+                    return Error::new(ErrorKind::AlreadyExists)
+                        .with_message("snapshot target already exists")
+                        .with_path(out)
+                        .with_source(err);
+                }
+                io_error("failed to create snapshot file")(err)
+            })?;
+        let header = self.header_from_mmap()?;
+        let len = self.mmap.len();
+        file.set_len(len as u64)
+            .map_err(io_error("failed to size snapshot file"))?;
+        let mut offset = 0;
+        while offset < len {
+            let chunk = &self.mmap[offset..(offset + CHUNK).min(len)];
+            if chunk.iter().any(|byte| *byte != 0) {
+                file.seek(SeekFrom::Start(offset as u64))
+                    .and_then(|_| file.write_all(chunk))
+                    .map_err(io_error("failed to write snapshot"))?;
+            }
+            offset += chunk.len();
+        }
+        file.sync_all()
+            .map_err(io_error("failed to sync snapshot"))?;
+        Ok(header)
+    }
+
     pub fn append(&mut self, payload: &[u8]) -> Result<u64, Error> {
         self.append_with_options(payload, AppendOptions::default())
     }
@@ -767,23 +1462,195 @@ impl Pool {
         payload: &[u8],
         options: AppendOptions,
     ) -> Result<u64, Error> {
+        chaos::before_append(&self.path)?;
         let _lock = self.append_lock()?;
         // Refresh header after acquiring the lock to avoid stale state across processes.
         self.header = self.header_from_mmap()?;
         self.append_locked(payload, options)
     }
 
+    /// Append `payload` unless a message appended under idempotency `key` is still in the pool.
+    /// Returns the seq and whether this call wrote it. Lookup and append share one hold of the
+    /// append lock, so concurrent retries cannot both land. Keys live in the header ring: the
+    /// dedupe window's slots when dedupe is on, else all `MAX_DEDUPE_WINDOW` slots, so a key is
+    /// remembered for that many keyed appends.
+    pub fn append_idempotent(
+        &mut self,
+        key: &[u8],
+        payload: &[u8],
+        options: AppendOptions,
+    ) -> Result<(u64, bool), Error> {
+        chaos::before_append(&self.path)?;
+        let _lock = self.append_lock()?;
+        self.header = self.header_from_mmap()?;
+        let window = match self.dedupe_window() {
+            0 => MAX_DEDUPE_WINDOW,
+            window => window,
+        };
+        let hash = idempotency_hash(key);
+        if let Some(seq) = self.idempotency_lookup(window, hash) {
+            return Ok((seq, false));
+        }
+        let seq = self.append_locked(payload, options)?;
+        self.dedupe_record(window, hash, seq);
+        Ok((seq, true))
+    }
+
     fn append_locked(&mut self, payload: &[u8], options: AppendOptions) -> Result<u64, Error> {
+        if self.is_frozen() {
+            return Err(Error::new(ErrorKind::Busy)
+                .with_message("pool is frozen")
+                .with_path(&self.path)
+                .with_hint("Writes resume after `plasmite pool unfreeze`."));
+        }
+        let (envelope, _) = attachment::unpack(options.flags, payload)?;
+        if self.codec() == PoolCodec::Json && envelope.first() != Some(&b'{') {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("pool uses the json payload codec")
+                .with_path(&self.path)
+                .with_hint(
+                    "Append through the API codec for this pool (Lite3 payloads are rejected).",
+                ));
+        }
+        frame::check_settable_flags(options.flags & !frame::FRAME_FLAG_ATTACHMENT)?;
+        let bloom = if self.has_bloom_filter() {
+            bloom::envelope_bloom(envelope, self.codec() == PoolCodec::Json)
+        } else {
+            bloom::BLOOM_NONE
+        };
+        if let Some(limits) = self.chunk_limits() {
+            if payload.len() > limits.max_frame as usize {
+                return self.append_chunks_locked(payload, options, limits, bloom);
+            }
+        }
+        let dedupe_window = self.dedupe_window();
+        // FNV-1a over the payload bytes; only used to pick dedupe candidates.
+        let dedupe_hash = (dedupe_window > 0).then(|| fnv1a64(payload));
+        if let Some(hash) = dedupe_hash {
+            if let Some(seq) = self.dedupe_lookup(dedupe_window, hash, payload) {
+                return Ok(seq);
+            }
+        }
+        let seq = self.write_frame_locked(payload, options, bloom)?;
+        if let Some(hash) = dedupe_hash {
+            self.dedupe_record(dedupe_window, hash, seq);
+        }
+        Ok(seq)
+    }
+
+    /// Store an oversized payload as consecutive chunk frames under the held append lock and
+    /// return the message's seq (its last chunk). Chunked payloads skip dedupe, and only the
+    /// last chunk carries the message's bloom filter.
+    fn append_chunks_locked(
+        &mut self,
+        payload: &[u8],
+        options: AppendOptions,
+        limits: ChunkLimits,
+        bloom: u128,
+    ) -> Result<u64, Error> {
+        if options.flags & frame::FRAME_FLAG_CONTROL != 0 {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("control messages cannot be chunked")
+                .with_path(&self.path));
+        }
+        if limits
+            .max_message
+            .is_some_and(|max_message| payload.len() > max_message as usize)
+        {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!(
+                    "message is {} bytes; this pool stores at most {} bytes per message",
+                    payload.len(),
+                    limits.max_message.unwrap_or_default()
+                ))
+                .with_path(&self.path)
+                .with_hint(
+                    "Shrink the message, or recreate the pool with a larger --max-message.",
+                ));
+        }
+        let chunks = chunk::split(payload, limits.max_frame as usize)?;
+        let ring_bytes = chunks.iter().try_fold(0usize, |total, chunk| {
+            frame::frame_total_len(FRAME_HEADER_LEN, chunk.len())
+                .and_then(|len| total.checked_add(len))
+        });
+        let too_large = || {
+            Error::new(ErrorKind::Usage)
+                .with_message("message does not fit in the pool even when chunked")
+                .with_path(&self.path)
+                .with_hint("Use a larger pool, or raise --max-messages so every chunk is kept.")
+        };
+        if ring_bytes.is_none_or(|bytes| bytes > self.header.ring_size as usize) {
+            return Err(too_large());
+        }
+        if self
+            .max_messages()
+            .is_some_and(|max| (chunks.len() as u64) > max)
+        {
+            return Err(too_large());
+        }
+        let chunk_options = options.with_flags(options.flags | frame::FRAME_FLAG_CHUNK);
+        let mut seq = 0;
+        for (index, chunk) in chunks.iter().enumerate() {
+            let chunk_bloom = if index + 1 == chunks.len() {
+                bloom
+            } else {
+                bloom::BLOOM_NONE
+            };
+            seq = self.write_frame_locked(chunk, chunk_options, chunk_bloom)?;
+        }
+        Ok(seq)
+    }
+
+    /// Plan and write one frame; callers hold the append lock and have validated `payload`.
+    fn write_frame_locked(
+        &mut self,
+        payload: &[u8],
+        options: AppendOptions,
+        bloom: u128,
+    ) -> Result<u64, Error> {
         let ring_offset = self.header.ring_offset as usize;
         let ring_size = self.header.ring_size as usize;
-        let plan = plan::plan_append(self.header, &self.mmap, payload.len())?;
+        let timestamp_ns = self.resolve_append_timestamp(options.timestamp_ns);
+        let plan = plan::plan_append_capped(
+            self.header,
+            &self.mmap,
+            payload.len(),
+            self.max_messages().unwrap_or(0),
+        )?;
 
+        let intent = self.journal.as_ref().map(|journal| {
+            let next = plan.next_header;
+            // Appending into an empty ring leaves nothing to keep if the append is rolled back.
+            let rollback = if next.oldest_seq == plan.seq {
+                journal::Rollback {
+                    tail_off: self.header.head_off,
+                    tail_next_off: self.header.head_off,
+                    oldest_seq: 0,
+                }
+            } else {
+                journal::Rollback {
+                    tail_off: next.tail_off,
+                    tail_next_off: next.tail_next_off,
+                    oldest_seq: next.oldest_seq,
+                }
+            };
+            journal.record_intent(
+                plan.seq,
+                plan.frame_offset as u64,
+                plan.frame_len as u32,
+                journal::crc32c(payload),
+                rollback,
+                options.durability == Durability::Flush,
+            )
+        });
         apply_append(
             &mut self.mmap,
             ring_offset,
             &plan,
             payload,
-            options.timestamp_ns,
+            timestamp_ns,
+            options.flags,
+            bloom,
         )?;
 
         self.header = plan.next_header;
@@ -833,6 +1700,10 @@ impl Pool {
             )?;
         }
 
+        if let (Some(journal), Some(intent)) = (&self.journal, intent) {
+            journal.record_commit(intent);
+        }
+
         validate::debug_assert_tail_committed(
             &self.mmap,
             ring_offset,
@@ -901,6 +1772,17 @@ impl Pool {
     fn frame_timestamp_ns_for_seq(&self, seq: u64) -> Option<u64> {
         self.get(seq).ok().map(|frame| frame.timestamp_ns)
     }
+
+    /// Apply the hybrid timestamp policy; must be called with the append lock held.
+    fn resolve_append_timestamp(&self, wall_ns: u64) -> u64 {
+        if self.header.flags & POOL_FLAG_HYBRID_TIMESTAMPS == 0 || self.header.oldest_seq == 0 {
+            return wall_ns;
+        }
+        match self.frame_timestamp_ns_for_seq(self.header.newest_seq) {
+            Some(previous) if previous >= wall_ns => previous.saturating_add(1),
+            _ => wall_ns,
+        }
+    }
 }
 
 pub struct AppendLock {
@@ -959,9 +1841,19 @@ fn write_header(file: &mut File, header: &PoolHeader, path: &Path) -> Result<(),
     Ok(())
 }
 
+/// Version 4 only when an incompatible flag is set, so flag-free pools stay readable by
+/// binaries that only know version 3.
+fn format_version(flags: u64) -> u32 {
+    if flags & POOL_FLAGS_INCOMPATIBLE != 0 {
+        format::POOL_FORMAT_VERSION
+    } else {
+        format::POOL_FORMAT_VERSION_BASE
+    }
+}
+
 fn write_pool_header(mmap: &mut MmapMut, header: &PoolHeader) {
     mmap[0..4].copy_from_slice(&MAGIC);
-    mmap[4..8].copy_from_slice(&format::POOL_FORMAT_VERSION.to_le_bytes());
+    mmap[4..8].copy_from_slice(&format_version(header.flags).to_le_bytes());
     mmap[8] = ENDIANNESS_LE;
     write_u64(mmap, 16, header.file_size);
     write_u64(mmap, 24, header.index_offset);
@@ -1037,15 +1929,36 @@ fn format_timestamp_ns(timestamp_ns: u64) -> Option<String> {
         .ok()
 }
 
+/// Frame for `seq` via its inline index slot, with its ring offset; `None` when indexing is
+/// disabled or the slot is stale, so callers fall back to a scan.
+fn get_via_index<'a>(
+    ring: &RingView<'a>,
+    seq: u64,
+) -> Option<(usize, crate::core::cursor::FrameRef<'a>)> {
+    let (stored_seq, stored_offset) = ring.index_entry(seq)?;
+    if stored_seq != seq {
+        return None;
+    }
+    let offset = usize::try_from(stored_offset)
+        .ok()
+        .filter(|offset| *offset < ring.ring_size())?;
+    match crate::core::cursor::read_frame_at(ring, offset) {
+        Ok(crate::core::cursor::ReadResult::Message { frame, .. }) if frame.seq == seq => {
+            Some((offset, frame))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 fn read_frame_header(
     mmap: &MmapMut,
     ring_offset: usize,
     head: usize,
 ) -> Result<FrameHeader, Error> {
-    let start = ring_offset + head;
-    let end = start + FRAME_HEADER_LEN;
-    FrameHeader::decode(&mmap[start..end])
+    ByteView::new(mmap)
+        .slice(ring_offset + head, FRAME_HEADER_LEN)
+        .and_then(FrameHeader::decode)
 }
 
 fn write_frame_header(
@@ -1088,6 +2001,8 @@ fn apply_append(
     plan: &plan::AppendPlan,
     payload: &[u8],
     timestamp_ns: u64,
+    flags: u32,
+    bloom: u128,
 ) -> Result<(), Error> {
     let expected_len = frame::frame_total_len(FRAME_HEADER_LEN, payload.len())
         .ok_or_else(|| Error::new(ErrorKind::Corrupt).with_message("frame length overflow"))?;
@@ -1099,14 +2014,15 @@ fn apply_append(
         write_wrap(mmap, ring_offset, wrap_offset)?;
     }
 
-    let header = FrameHeader::new(
+    let mut header = FrameHeader::new(
         FrameState::Writing,
-        0,
+        flags,
         plan.seq,
         timestamp_ns,
         payload.len() as u32,
         0,
     );
+    header.bloom = bloom;
     write_frame(mmap, ring_offset, plan.frame_offset, &header, payload)?;
 
     let mut committed = header;
@@ -1162,7 +2078,11 @@ fn index_slot_range(index_offset: u64, index_capacity: u32, seq: u64) -> Option<
 
 #[cfg(test)]
 mod tests {
-    use super::{HEADER_SIZE, Pool, PoolHeader, PoolOptions, SeqOffsetCache, apply_append};
+    use super::{
+        AppendOptions, HEADER_SIZE, MAX_DEDUPE_WINDOW, POOL_FLAG_HYBRID_TIMESTAMPS, Pool,
+        PoolCodec, PoolHeader, PoolOptions, SeqOffsetCache, TimeNearest, apply_append, bloom,
+        format, read_4, read_u64, write_u64,
+    };
     use crate::core::error::{Error, ErrorKind};
     use crate::core::frame::{self, FRAME_HEADER_LEN, FrameHeader, FrameState};
     use crate::core::lite3;
@@ -1187,6 +2107,49 @@ mod tests {
         assert_eq!(reopened.header().file_size, 1024 * 1024);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn read_only_open_reads_without_write_access() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut writer = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        let payload = lite3::encode_message(&[], &serde_json::json!({"x": 1})).expect("payload");
+        writer.append(&payload).expect("append");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o444)).expect("chmod");
+
+        let mut reader = Pool::open_read_only(&path).expect("open read-only");
+        assert!(reader.is_read_only());
+        assert_eq!(reader.bounds().expect("bounds").newest_seq, Some(1));
+        assert_eq!(reader.get(1).expect("get").payload, payload.as_slice());
+        let err = reader.append(&payload).expect_err("append must fail");
+        assert_eq!(err.kind(), ErrorKind::Permission);
+
+        // The writer keeps its handle; the read-only mapping sees its later appends.
+        writer.append(&payload).expect("append");
+        assert_eq!(reader.bounds().expect("bounds").newest_seq, Some(2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_is_sparse_unless_preallocated() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let size = 64 * 1024 * 1024;
+        let sparse = Pool::create(dir.path().join("sparse.plasmite"), PoolOptions::new(size))
+            .expect("create sparse");
+        let info = sparse.info().expect("info");
+        assert!(info.allocated_bytes.expect("allocated") < size / 2);
+
+        let full = Pool::create(
+            dir.path().join("full.plasmite"),
+            PoolOptions::new(size).with_preallocate(true),
+        )
+        .expect("create preallocated");
+        let info = full.info().expect("info");
+        assert!(info.allocated_bytes.expect("allocated") >= size);
+    }
+
     #[test]
     fn create_auto_creates_parent_dirs() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -1276,6 +2239,39 @@ mod tests {
         assert!(message.contains("3"));
     }
 
+    #[test]
+    fn feature_flags_bump_format_version_and_unknown_bits_are_refused() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let plain = dir.path().join("plain.plasmite");
+        Pool::create(&plain, PoolOptions::new(1024 * 1024)).expect("create");
+        let bytes = std::fs::read(&plain).expect("read");
+        assert_eq!(
+            read_4(&bytes, 4),
+            format::POOL_FORMAT_VERSION_BASE.to_le_bytes()
+        );
+
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        pool.set_frozen(true).expect("freeze");
+        drop(pool);
+        let mut bytes = std::fs::read(&path).expect("read");
+        assert_eq!(read_4(&bytes, 4), format::POOL_FORMAT_VERSION.to_le_bytes());
+
+        let flags = read_u64(&bytes, 56) | 1 << 20;
+        write_u64(&mut bytes, 56, flags);
+        std::fs::write(&path, &bytes).expect("write");
+        let err = match Pool::open(&path) {
+            Ok(_) => panic!("expected unknown feature error"),
+            Err(err) => err,
+        };
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        assert!(err.message().unwrap_or("").contains("0x100000"));
+
+        write_u64(&mut bytes, 56, (flags & !(1 << 20)) | 1 << 40);
+        std::fs::write(&path, &bytes).expect("write");
+        Pool::open(&path).expect("compatible high bits are ignored");
+    }
+
     #[test]
     fn validator_accepts_wrap_and_seq_range() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -1622,6 +2618,359 @@ mod tests {
         assert_eq!(pool.header().newest_seq, 3);
     }
 
+    #[test]
+    fn hybrid_timestamps_stay_monotonic_across_clock_jumps() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(
+            &path,
+            PoolOptions::new(1024 * 1024).with_hybrid_timestamps(true),
+        )
+        .expect("create");
+        assert!(pool.info().expect("info").hybrid_timestamps);
+
+        for timestamp_ns in [5_000, 1_000, 5_000, 9_000] {
+            pool.append_with_timestamp(b"x", timestamp_ns)
+                .expect("append");
+        }
+        let stamps = (1..=4)
+            .map(|seq| pool.get(seq).expect("get").timestamp_ns)
+            .collect::<Vec<_>>();
+        assert_eq!(stamps, vec![5_000, 5_001, 5_002, 9_000]);
+
+        let reopened = Pool::open(&path).expect("open");
+        assert_eq!(
+            reopened.header().flags & POOL_FLAG_HYBRID_TIMESTAMPS,
+            POOL_FLAG_HYBRID_TIMESTAMPS
+        );
+    }
+
+    #[test]
+    fn dedupe_window_returns_original_seq_for_recent_duplicates() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024).with_dedupe_window(2))
+            .expect("create");
+        assert_eq!(pool.append(b"a").expect("append"), 1);
+        assert_eq!(pool.append(b"a").expect("dup"), 1);
+        assert_eq!(pool.append(b"b").expect("append"), 2);
+        assert_eq!(pool.append(b"c").expect("append"), 3);
+        // `a` has left the two-entry window, so it is stored again.
+        assert_eq!(pool.append(b"a").expect("append"), 4);
+
+        let mut reopened = Pool::open(&path).expect("open");
+        assert_eq!(reopened.dedupe_window(), 2);
+        assert_eq!(reopened.append(b"c").expect("dup"), 3);
+        assert_eq!(reopened.bounds().expect("bounds").newest_seq, Some(4));
+
+        let err = Pool::create(
+            dir.path().join("too-big.plasmite"),
+            PoolOptions::new(1024 * 1024).with_dedupe_window(MAX_DEDUPE_WINDOW + 1),
+        )
+        .err()
+        .expect("window too large");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+
+    #[test]
+    fn append_idempotent_returns_original_seq_without_storing_the_key() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        let options = AppendOptions::default();
+        assert_eq!(
+            pool.append_idempotent(b"k1", b"a", options)
+                .expect("append"),
+            (1, true)
+        );
+        assert_eq!(
+            pool.append_idempotent(b"k1", b"b", options).expect("retry"),
+            (1, false)
+        );
+        assert_eq!(
+            pool.append_idempotent(b"k2", b"a", options)
+                .expect("append"),
+            (2, true)
+        );
+        assert_eq!(pool.get(1).expect("get").payload, b"a");
+        // Unkeyed appends of the same payload are unaffected.
+        assert_eq!(pool.append(b"a").expect("append"), 3);
+
+        let mut reopened = Pool::open(&path).expect("open");
+        assert_eq!(
+            reopened
+                .append_idempotent(b"k2", b"c", options)
+                .expect("retry after reopen"),
+            (2, false)
+        );
+    }
+
+    #[test]
+    fn repair_rolls_back_an_append_that_died_before_its_header_write() {
+        use crate::core::journal::{self, IntentJournal, IntentState};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let options = PoolOptions::new(4096 + 2048)
+            .with_index_capacity(0)
+            .with_intent_journal(true);
+        let mut pool = Pool::create(&path, options).expect("create");
+        let payload = [7u8; 400];
+        for _ in 0..4 {
+            pool.append(&payload).expect("append");
+        }
+        assert_eq!(pool.repair_interrupted_append().expect("repair"), None);
+
+        // Replay a crash: the fifth append overwrites seq 1 but its header never lands.
+        let before = pool.mmap[0..HEADER_SIZE].to_vec();
+        let seq = pool.append(&payload).expect("append");
+        assert_eq!(pool.bounds().expect("bounds").oldest_seq, Some(2));
+        pool.mmap[0..HEADER_SIZE].copy_from_slice(&before);
+        let entry = journal::read_entries(&path)
+            .expect("read")
+            .expect("journal")
+            .into_iter()
+            .find(|entry| entry.seq == seq)
+            .expect("entry");
+        IntentJournal::open_or_create(&path)
+            .expect("journal")
+            .record_intent(
+                entry.seq,
+                entry.offset,
+                entry.frame_len,
+                entry.crc,
+                entry.rollback,
+                true,
+            );
+        assert_eq!(pool.bounds().expect("bounds").oldest_seq, Some(1));
+
+        assert_eq!(pool.repair_interrupted_append().expect("repair"), Some(seq));
+        let bounds = pool.bounds().expect("bounds");
+        assert_eq!((bounds.oldest_seq, bounds.newest_seq), (Some(2), Some(4)));
+        assert!(
+            journal::read_entries(&path)
+                .expect("read")
+                .expect("journal")
+                .iter()
+                .all(|entry| entry.seq != seq || entry.state != IntentState::Intent)
+        );
+        assert_eq!(pool.append(&payload).expect("append after repair"), seq);
+    }
+
+    #[test]
+    fn max_messages_caps_retained_count() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024).with_max_messages(3))
+            .expect("create");
+        for _ in 0..5 {
+            pool.append(b"payload").expect("append");
+        }
+        let bounds = pool.bounds().expect("bounds");
+        assert_eq!(bounds.oldest_seq, Some(3));
+        assert_eq!(bounds.newest_seq, Some(5));
+        assert!(pool.get(2).is_err());
+
+        let mut reopened = Pool::open(&path).expect("open");
+        let info = reopened.info().expect("info");
+        assert_eq!(info.max_messages, Some(3));
+        assert_eq!(info.metrics.expect("metrics").message_count, 3);
+        assert_eq!(reopened.append(b"payload").expect("append"), 6);
+        assert_eq!(reopened.bounds().expect("bounds").oldest_seq, Some(4));
+
+        let err = Pool::create(
+            dir.path().join("zero.plasmite"),
+            PoolOptions::new(1024 * 1024).with_max_messages(0),
+        )
+        .err()
+        .expect("zero cap");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let uncapped = Pool::create(
+            dir.path().join("plain.plasmite"),
+            PoolOptions::new(1024 * 1024),
+        )
+        .expect("create");
+        assert_eq!(uncapped.info().expect("info").max_messages, None);
+    }
+
+    #[test]
+    fn payloads_over_the_frame_limit_are_chunked_and_joined() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let options = PoolOptions::new(1024 * 1024)
+            .with_max_frame_bytes(128)
+            .with_max_message_bytes(1000);
+        let mut pool = Pool::create(&path, options).expect("create");
+        assert_eq!(pool.append(b"small").expect("small"), 1);
+        let large = (0..600u32).map(|n| n as u8).collect::<Vec<_>>();
+        let seq = pool.append(&large).expect("large");
+        assert_eq!(seq, 6, "600 bytes at 120 bytes per chunk is five chunks");
+
+        let last = pool.get(seq).expect("last chunk");
+        assert_eq!(
+            pool.join_chunks(&last).expect("join").as_deref(),
+            Some(large.as_slice())
+        );
+        let continuation = pool.get(3).expect("continuation");
+        assert_eq!(pool.join_chunks(&continuation).expect("join"), None);
+        let small = pool.get(1).expect("small");
+        assert_eq!(
+            pool.join_chunks(&small).expect("join").as_deref(),
+            Some(&b"small"[..])
+        );
+
+        let err = pool.append(&[0; 1001]).expect_err("over the cap");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let info = Pool::open(&path).expect("open").info().expect("info");
+        assert_eq!(info.max_frame_bytes, Some(128));
+        assert_eq!(info.max_message_bytes, Some(1000));
+
+        let err = Pool::create(
+            dir.path().join("uncapped.plasmite"),
+            PoolOptions::new(1024 * 1024).with_max_message_bytes(1000),
+        )
+        .err()
+        .expect("cap without frame limit");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+
+    #[test]
+    fn bloom_pools_stamp_frames_with_their_string_values() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let payload = lite3::encode_message(
+            &["ops".to_string()],
+            &serde_json::json!({ "level": "error", "code": 7 }),
+        )
+        .expect("payload");
+
+        let mut plain = Pool::create(
+            dir.path().join("plain.plasmite"),
+            PoolOptions::new(1024 * 1024),
+        )
+        .expect("create");
+        let seq = plain.append(payload.as_slice()).expect("append");
+        assert_eq!(plain.get(seq).expect("get").bloom, bloom::BLOOM_NONE);
+
+        let path = dir.path().join("bloom.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024).with_bloom_filter(true))
+            .expect("create");
+        let seq = pool.append(payload.as_slice()).expect("append");
+        let frame = pool.get(seq).expect("get");
+        assert!(bloom::might_contain(frame.bloom, "error"));
+        assert!(bloom::might_contain(frame.bloom, "ops"));
+        assert_ne!(frame.bloom, bloom::BLOOM_NONE);
+        assert!(
+            Pool::open(&path)
+                .expect("open")
+                .info()
+                .expect("info")
+                .bloom_filter
+        );
+    }
+
+    #[test]
+    fn frozen_pools_refuse_appends_from_every_handle() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut writer = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        let mut operator = Pool::open(&path).expect("open");
+        writer.append(b"before").expect("append");
+
+        assert!(!operator.set_frozen(true).expect("freeze"));
+        assert!(operator.set_frozen(true).expect("freeze again"));
+        let err = writer.append(b"during").expect_err("frozen");
+        assert_eq!(err.kind(), ErrorKind::Busy);
+        assert!(writer.info().expect("info").frozen);
+        assert_eq!(writer.bounds().expect("bounds").newest_seq, Some(1));
+
+        assert!(operator.set_frozen(false).expect("unfreeze"));
+        assert_eq!(writer.append(b"after").expect("append"), 2);
+        assert!(
+            !Pool::open(&path)
+                .expect("open")
+                .info()
+                .expect("info")
+                .frozen
+        );
+    }
+
+    #[test]
+    fn snapshot_copies_consistent_image() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        for _ in 0..3 {
+            pool.append(b"payload").expect("append");
+        }
+        let out = dir.path().join("snap.plasmite");
+        let header = pool.snapshot_to(&out).expect("snapshot");
+        assert_eq!(header.newest_seq, 3);
+
+        let snapshot = Pool::open_read_only(&out).expect("open snapshot");
+        assert_eq!(
+            snapshot.bounds().expect("bounds"),
+            pool.bounds().expect("bounds")
+        );
+        assert_eq!(snapshot.get(2).expect("get").payload, b"payload");
+        assert!(
+            snapshot
+                .info()
+                .expect("info")
+                .allocated_bytes
+                .expect("allocated")
+                < 1024 * 1024 / 2
+        );
+
+        let err = pool.snapshot_to(&out).err().expect("existing target");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn get_at_time_resolves_nearest_frame_on_either_side() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1024 * 1024)).expect("create");
+        for timestamp_ns in [100, 200, 200, 300] {
+            pool.append_with_timestamp(b"x", timestamp_ns)
+                .expect("append");
+        }
+        let at = |ts, nearest| pool.get_at_time(ts, nearest).map(|frame| frame.seq);
+        assert_eq!(at(250, TimeNearest::Before).expect("before"), 3);
+        assert_eq!(at(250, TimeNearest::After).expect("after"), 4);
+        assert_eq!(at(200, TimeNearest::Before).expect("exact"), 3);
+        assert_eq!(at(200, TimeNearest::After).expect("exact"), 2);
+        assert_eq!(at(500, TimeNearest::Before).expect("late"), 4);
+        assert_eq!(
+            at(50, TimeNearest::Before).expect_err("early").kind(),
+            ErrorKind::NotFound
+        );
+        assert_eq!(
+            at(301, TimeNearest::After).expect_err("late").kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn json_codec_pool_rejects_non_json_payloads() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let mut pool = Pool::create(
+            &path,
+            PoolOptions::new(1024 * 1024).with_codec(PoolCodec::Json),
+        )
+        .expect("create");
+        assert_eq!(pool.info().expect("info").codec, PoolCodec::Json);
+
+        let payload = lite3::encode_message(&[], &serde_json::json!({"x": 1})).expect("payload");
+        let err = pool.append(payload.as_slice()).expect_err("lite3 rejected");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        pool.append(br#"{"meta":{"tags":[]},"data":{}}"#)
+            .expect("json append");
+
+        let reopened = Pool::open(&path).expect("open");
+        assert_eq!(reopened.codec(), PoolCodec::Json);
+    }
+
     #[test]
     fn append_succeeds_when_notify_unavailable() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -1665,6 +3014,8 @@ mod tests {
             &plan,
             payload_b.as_slice(),
             0,
+            0,
+            0,
         )
         .expect("apply");
 
@@ -1707,6 +3058,8 @@ mod tests {
             &plan,
             payload_b.as_slice(),
             0,
+            0,
+            0,
         )
         .expect("apply");
 
@@ -1790,6 +3143,106 @@ mod tests {
         assert_eq!(frame.seq, 2);
     }
 
+    #[test]
+    fn mutated_pool_bytes_never_read_out_of_bounds() {
+        const MUTATION_SEED: u64 = 0x5EED_F00D_CAFE_B0B5;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("fuzz.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(16 * 1024).with_index_capacity(8))
+            .expect("create");
+        for value in 0..64 {
+            let payload =
+                lite3::encode_message(&[], &serde_json::json!({"x": value})).expect("payload");
+            pool.append(payload.as_slice()).expect("append");
+        }
+        let pristine = pool.mmap.to_vec();
+        let header = pool.header_from_mmap().expect("header");
+        let index_bytes = u64::from(header.index_capacity) * INDEX_SLOT_BYTES;
+
+        let mut seed = MUTATION_SEED;
+        let mut next = || {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            seed >> 11
+        };
+        for _ in 0..256 {
+            pool.mmap.copy_from_slice(&pristine);
+            for _ in 0..1 + next() % 4 {
+                // Bias toward header fields and index slots, where one word redirects every read.
+                let target = match next() % 3 {
+                    0 => (next() % 104) as usize,
+                    1 => (header.index_offset + next() % index_bytes) as usize,
+                    _ => next() as usize % pristine.len(),
+                } & !7;
+                let value = match next() % 3 {
+                    0 => next(),
+                    1 => u64::MAX - next() % 4096,
+                    _ => next() % (pristine.len() as u64 * 2),
+                };
+                let end = (target + 8).min(pristine.len());
+                pool.mmap[target..end].copy_from_slice(&value.to_le_bytes()[..end - target]);
+            }
+
+            let Ok(ring) = pool.ring_view() else {
+                continue;
+            };
+            for offset in (0..ring.ring_size()).step_by(8) {
+                let _ = crate::core::cursor::read_frame_at(&ring, offset);
+            }
+            for seq in 0..80 {
+                let _ = super::get_via_index(&ring, seq);
+            }
+            let _ = crate::core::validate::validate_pool_state(ring.header(), &pool.mmap);
+            let mut cursor = crate::core::cursor::Cursor::new();
+            cursor.seek_to(ring.header().tail_off as usize);
+            for _ in 0..80 {
+                if !matches!(
+                    cursor.next(&pool),
+                    Ok(crate::core::cursor::CursorResult::Message(_))
+                ) {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_range_clamps_and_uses_index_or_scan() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for index_capacity in [0, 2, 16] {
+            let path = dir.path().join(format!("range-{index_capacity}.plasmite"));
+            let mut pool = Pool::create(
+                &path,
+                PoolOptions::new(1024 * 1024).with_index_capacity(index_capacity),
+            )
+            .expect("create");
+            assert!(pool.get_range(1, 10).expect("empty").is_empty());
+            for value in 1..=6 {
+                let payload =
+                    lite3::encode_message(&[], &serde_json::json!({"x": value})).expect("payload");
+                pool.append(payload.as_slice()).expect("append");
+            }
+
+            let seqs = |start, end| {
+                pool.get_range(start, end)
+                    .expect("range")
+                    .iter()
+                    .map(|frame| frame.seq)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(seqs(3, 5), vec![3, 4, 5]);
+            assert_eq!(seqs(0, 2), vec![1, 2]);
+            assert_eq!(seqs(5, 100), vec![5, 6]);
+            assert!(seqs(7, 9).is_empty());
+            assert!(seqs(4, 3).is_empty());
+
+            let located = pool.locate(6).expect("locate");
+            assert_eq!(located.is_some(), index_capacity > 0);
+            if index_capacity == 2 {
+                assert_eq!(pool.locate(1).expect("locate"), None);
+            }
+        }
+    }
+
     #[test]
     fn write_pool_header_partial_updates() {
         let dir = tempfile::tempdir().expect("tempdir");
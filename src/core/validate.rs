@@ -4,14 +4,15 @@
 //! Role: Slow-path safety checks used at boundaries and in debug builds.
 //! Invariants: Full scans are explicit; hot paths use tail-only checks.
 //! Invariants: Snapshot output is opt-in and written under `.scratch/` only.
-use crate::core::error::Error;
-#[cfg(test)]
-use crate::core::error::ErrorKind;
+use crate::core::error::{Error, ErrorKind};
 #[cfg(test)]
 use crate::core::frame;
 use crate::core::frame::{FRAME_HEADER_LEN, FrameHeader, FrameState};
 #[cfg(test)]
 use crate::core::pool::PoolHeader;
+use crate::core::view::ByteView;
+#[cfg(test)]
+use crate::core::view::RingView;
 #[cfg(test)]
 use std::fs::{self, File};
 #[cfg(test)]
@@ -39,14 +40,8 @@ pub fn validate_frame_header(header: &FrameHeader, ring_size: usize) -> Result<(
 
 #[cfg(test)]
 pub fn validate_pool_state(header: PoolHeader, mmap: &[u8]) -> Result<(), Error> {
-    if header.ring_size == 0 {
-        return Err(Error::new(ErrorKind::Corrupt).with_message("ring size is zero"));
-    }
-    let ring_offset = header.ring_offset as usize;
-    let ring_size = header.ring_size as usize;
-    if ring_offset + ring_size > mmap.len() {
-        return Err(Error::new(ErrorKind::Corrupt).with_message("ring exceeds mmap bounds"));
-    }
+    let ring = RingView::new(mmap, header)?;
+    let ring_size = ring.ring_size();
     let head = header.head_off as usize;
     let tail = header.tail_off as usize;
     let tail_next = header.tail_next_off as usize;
@@ -71,7 +66,7 @@ pub fn validate_pool_state(header: PoolHeader, mmap: &[u8]) -> Result<(), Error>
     let expected_tail_next = if ring_size - tail < FRAME_HEADER_LEN {
         0usize
     } else {
-        let tail_frame = read_frame_header(mmap, ring_offset, tail)?;
+        let tail_frame = ring.frame_header(tail)?;
         validate_frame_header(&tail_frame, ring_size)?;
         match tail_frame.state {
             FrameState::Wrap => 0usize,
@@ -110,7 +105,7 @@ pub fn validate_pool_state(header: PoolHeader, mmap: &[u8]) -> Result<(), Error>
             steps += 1;
             continue;
         }
-        let frame = read_frame_header(mmap, ring_offset, offset)?;
+        let frame = ring.frame_header(offset)?;
         validate_frame_header(&frame, ring_size)?;
         match frame.state {
             FrameState::Wrap => {
@@ -134,11 +129,8 @@ pub fn validate_pool_state(header: PoolHeader, mmap: &[u8]) -> Result<(), Error>
             return Err(Error::new(ErrorKind::Corrupt).with_message("frame exceeds ring"));
         }
 
-        let payload_start = ring_offset + offset + FRAME_HEADER_LEN;
-        let payload_end = payload_start + frame.payload_len as usize;
-        let marker_start = payload_end;
-        let marker_end = marker_start + frame::FRAME_COMMIT_MARKER_LEN;
-        if &mmap[marker_start..marker_end] != frame::FRAME_COMMIT_MARKER.as_slice() {
+        let marker_start = offset + FRAME_HEADER_LEN + frame.payload_len as usize;
+        if !ring.has_commit_marker(marker_start) {
             return Err(Error::new(ErrorKind::Corrupt).with_message("missing frame commit marker"));
         }
 
@@ -207,9 +199,7 @@ pub fn debug_assert_tail_committed(
         panic!("tail offset out of bounds");
     }
     let header = read_frame_header(mmap, ring_offset, tail).unwrap_or_else(|err| {
-        let start = ring_offset + tail;
-        let end = start + 4;
-        let magic = &mmap[start..end];
+        let magic = ByteView::new(mmap).slice(ring_offset + tail, 4).ok();
         panic!("tail frame header decode failed: {err}; magic={magic:?}");
     });
     validate_frame_header(&header, ring_size).expect("tail frame header validation failed");
@@ -232,9 +222,10 @@ pub fn debug_assert_tail_committed(
 }
 
 fn read_frame_header(mmap: &[u8], ring_offset: usize, head: usize) -> Result<FrameHeader, Error> {
-    let start = ring_offset + head;
-    let end = start + FRAME_HEADER_LEN;
-    FrameHeader::decode(&mmap[start..end])
+    let start = ring_offset
+        .checked_add(head)
+        .ok_or_else(|| Error::new(ErrorKind::Corrupt).with_message("frame offset overflow"))?;
+    FrameHeader::decode(ByteView::new(mmap).slice(start, FRAME_HEADER_LEN)?)
 }
 
 #[cfg(test)]
@@ -293,19 +284,17 @@ fn write_frame_snapshot(
     ring_size: usize,
     offset: usize,
 ) -> std::io::Result<()> {
-    if ring_offset + ring_size > mmap.len() {
+    let Ok(ring) = ByteView::new(mmap).view(ring_offset, ring_size) else {
         return writeln!(file, "{label}: ring out of bounds");
-    }
+    };
     if offset >= ring_size {
         return writeln!(file, "{label}: offset out of range ({offset})");
     }
-    let start = ring_offset + offset;
-    let end = start + FRAME_HEADER_LEN;
-    if end > ring_offset + ring_size {
+    let Ok(bytes) = ring.slice(offset, FRAME_HEADER_LEN) else {
         return writeln!(file, "{label}: header exceeds ring (offset={offset})");
-    }
-    let magic = &mmap[start..start + 4];
-    match FrameHeader::decode(&mmap[start..end]) {
+    };
+    let magic = &bytes[..4];
+    match FrameHeader::decode(bytes) {
         Ok(header) => {
             let frame_len = frame::frame_total_len(FRAME_HEADER_LEN, header.payload_len as usize);
             writeln!(
@@ -0,0 +1,211 @@
+//! Purpose: Bounds-checked typed reads over the pool mapping.
+//! Exports: `ByteView`, `RingView`.
+//! Role: The one place read paths turn file offsets into byte slices; cursor, validate, plan,
+//! pool, and API diagnostics go through it instead of indexing the mmap directly.
+//! Invariants: Every accessor checks `offset + len` (with overflow checks) against the view and
+//! returns `Corrupt` with the offending offset instead of panicking.
+//! Invariants: A `RingView` is built from one decoded, version-checked header snapshot; its ring
+//! and index regions are checked against the mapping once, at construction.
+use crate::core::error::{Error, ErrorKind};
+use crate::core::frame::{FRAME_COMMIT_MARKER, FRAME_HEADER_LEN, FrameHeader};
+use crate::core::pool::PoolHeader;
+
+const INDEX_SLOT_BYTES: usize = 16;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ByteView<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteView<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// `len` bytes at `offset`, or `Corrupt` when the range leaves the view.
+    pub fn slice(&self, offset: usize, len: usize) -> Result<&'a [u8], Error> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Corrupt)
+                    .with_message(format!(
+                        "read of {len} bytes at offset {offset} exceeds {} byte region",
+                        self.bytes.len()
+                    ))
+                    .with_offset(offset as u64)
+            })
+    }
+
+    /// Narrowed view over `len` bytes at `offset`; offsets into it restart at zero.
+    pub fn view(&self, offset: usize, len: usize) -> Result<ByteView<'a>, Error> {
+        self.slice(offset, len).map(ByteView::new)
+    }
+
+    pub fn u32_at(&self, offset: usize) -> Result<u32, Error> {
+        let mut out = [0u8; 4];
+        out.copy_from_slice(self.slice(offset, 4)?);
+        Ok(u32::from_le_bytes(out))
+    }
+
+    pub fn u64_at(&self, offset: usize) -> Result<u64, Error> {
+        let mut out = [0u8; 8];
+        out.copy_from_slice(self.slice(offset, 8)?);
+        Ok(u64::from_le_bytes(out))
+    }
+}
+
+/// Ring and index regions of one header snapshot, with ring-relative accessors.
+#[derive(Clone, Copy, Debug)]
+pub struct RingView<'a> {
+    header: PoolHeader,
+    ring: ByteView<'a>,
+    index: ByteView<'a>,
+}
+
+impl<'a> RingView<'a> {
+    pub fn new(mmap: &'a [u8], header: PoolHeader) -> Result<Self, Error> {
+        let file = ByteView::new(mmap);
+        let region = |offset: u64, len: u64, what: &str| {
+            usize::try_from(offset)
+                .ok()
+                .zip(usize::try_from(len).ok())
+                .and_then(|(offset, len)| file.view(offset, len).ok())
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::Corrupt)
+                        .with_message(format!("{what} exceeds mapped file"))
+                        .with_offset(offset)
+                })
+        };
+        if header.ring_size == 0 {
+            return Err(Error::new(ErrorKind::Corrupt).with_message("ring size is zero"));
+        }
+        let ring = region(header.ring_offset, header.ring_size, "ring")?;
+        let index = if header.index_capacity == 0 {
+            ByteView::new(&[])
+        } else {
+            let index_bytes = u64::from(header.index_capacity) * INDEX_SLOT_BYTES as u64;
+            region(header.index_offset, index_bytes, "index")?
+        };
+        Ok(Self {
+            header,
+            ring,
+            index,
+        })
+    }
+
+    /// Bare ring (no file header, no index) for unit tests over hand-built buffers.
+    #[cfg(test)]
+    pub fn ring_only(ring: &'a [u8]) -> Self {
+        Self {
+            header: PoolHeader {
+                file_size: ring.len() as u64,
+                index_offset: 0,
+                index_capacity: 0,
+                ring_offset: 0,
+                ring_size: ring.len() as u64,
+                flags: 0,
+                head_off: 0,
+                tail_off: 0,
+                tail_next_off: 0,
+                oldest_seq: 0,
+                newest_seq: 0,
+            },
+            ring: ByteView::new(ring),
+            index: ByteView::new(&[]),
+        }
+    }
+
+    /// Header snapshot this view was built from.
+    pub fn header(&self) -> PoolHeader {
+        self.header
+    }
+
+    pub fn ring_size(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Ring-relative bytes.
+    pub fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8], Error> {
+        self.ring.slice(offset, len)
+    }
+
+    pub fn frame_header(&self, offset: usize) -> Result<FrameHeader, Error> {
+        FrameHeader::decode(self.bytes(offset, FRAME_HEADER_LEN)?)
+    }
+
+    /// Whether the commit marker sits at ring-relative `offset` (false when out of range).
+    pub fn has_commit_marker(&self, offset: usize) -> bool {
+        self.bytes(offset, FRAME_COMMIT_MARKER.len())
+            .is_ok_and(|marker| marker == FRAME_COMMIT_MARKER)
+    }
+
+    /// `(stored_seq, stored_offset)` from the index slot `seq` maps to; `None` without an index.
+    pub fn index_entry(&self, seq: u64) -> Option<(u64, u64)> {
+        let capacity = u64::from(self.header.index_capacity);
+        if capacity == 0 {
+            return None;
+        }
+        let slot = usize::try_from(seq % capacity).ok()?;
+        let start = slot.checked_mul(INDEX_SLOT_BYTES)?;
+        Some((
+            self.index.u64_at(start).ok()?,
+            self.index.u64_at(start + 8).ok()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ByteView, RingView};
+    use crate::core::error::ErrorKind;
+    use crate::core::pool::{Pool, PoolOptions};
+
+    #[test]
+    fn byte_view_rejects_out_of_bounds_and_overflowing_reads() {
+        let bytes = [1u8, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0];
+        let view = ByteView::new(&bytes);
+        assert_eq!(view.u32_at(0).expect("u32"), 1);
+        assert_eq!(view.u64_at(4).expect("u64"), 2);
+        assert_eq!(
+            view.u64_at(5).expect_err("past end").kind(),
+            ErrorKind::Corrupt
+        );
+        assert_eq!(
+            view.slice(usize::MAX, 2).expect_err("overflow").offset(),
+            Some(u64::MAX)
+        );
+        let inner = view.view(4, 8).expect("view");
+        assert_eq!(inner.u32_at(0).expect("u32"), 2);
+        assert!(inner.u32_at(6).is_err());
+    }
+
+    #[test]
+    fn ring_view_rejects_header_pointing_past_the_mapping() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pool.plasmite");
+        let pool = Pool::create(&path, PoolOptions::new(64 * 1024)).expect("create");
+        let mut header = pool.header_from_mmap().expect("header");
+        assert!(RingView::new(pool.mmap(), header).is_ok());
+
+        header.ring_size += 8;
+        assert_eq!(
+            RingView::new(pool.mmap(), header).expect_err("ring").kind(),
+            ErrorKind::Corrupt
+        );
+        header.ring_size -= 8;
+        header.index_capacity = u32::MAX;
+        assert!(RingView::new(pool.mmap(), header).is_err());
+        header.index_capacity = 0;
+        header.ring_offset = u64::MAX;
+        assert!(RingView::new(pool.mmap(), header).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! Purpose: Detect a closed stdout pipe (EPIPE) so streaming commands can stop cleanly.
+//! Exports: `OnSigpipe`, `write_line`, `note_write_error`, `stdout_closed`, `probe_stdout_closed`.
+//! Role: Replaces panicking `println!` on follow/fetch output paths; `follow | head -5` exits 0.
+//! Invariants: Rust ignores SIGPIPE, so a closed reader surfaces as `BrokenPipe` on write.
+//! Invariants: Once closed is observed it stays set for the process; later writes are skipped.
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use clap::ValueEnum;
+
+static STDOUT_CLOSED: AtomicBool = AtomicBool::new(false);
+
+/// What a streaming command does once its stdout reader goes away.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnSigpipe {
+    /// Stop, emit a `downstream_closed` notice, and exit 0.
+    #[default]
+    Exit,
+    /// Keep running with output discarded (useful alongside `--exec` side effects).
+    Ignore,
+}
+
+pub fn stdout_closed() -> bool {
+    STDOUT_CLOSED.load(Ordering::Acquire)
+}
+
+/// Records `BrokenPipe` as a closed downstream; returns true when the error was swallowed.
+pub fn note_write_error(err: &io::Error) -> bool {
+    if err.kind() == io::ErrorKind::BrokenPipe {
+        STDOUT_CLOSED.store(true, Ordering::Release);
+        return true;
+    }
+    false
+}
+
+/// Writes one line to stdout; a closed pipe is recorded rather than reported.
+pub fn write_line(line: &str) -> io::Result<()> {
+    if stdout_closed() {
+        return Ok(());
+    }
+    let mut out = io::stdout().lock();
+    match out
+        .write_all(line.as_bytes())
+        .and_then(|()| out.write_all(b"\n"))
+        .and_then(|()| out.flush())
+    {
+        Err(err) if !note_write_error(&err) => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Checks, without writing, whether stdout is a pipe whose reader has gone away.
+/// Lets idle `follow` notice `| head` exiting before the next message arrives.
+#[cfg(unix)]
+pub fn probe_stdout_closed() -> bool {
+    if stdout_closed() {
+        return true;
+    }
+    let mut fds = libc::pollfd {
+        fd: libc::STDOUT_FILENO,
+        events: 0,
+        revents: 0,
+    };
+    // SAFETY: one valid pollfd, zero timeout; poll does not retain the pointer.
+    let ready = unsafe { libc::poll(&mut fds, 1, 0) };
+    if ready > 0 && fds.revents & (libc::POLLERR | libc::POLLHUP) != 0 {
+        STDOUT_CLOSED.store(true, Ordering::Release);
+        return true;
+    }
+    false
+}
+
+#[cfg(not(unix))]
+pub fn probe_stdout_closed() -> bool {
+    stdout_closed()
+}
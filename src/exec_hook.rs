@@ -0,0 +1,171 @@
+//! Purpose: Run a shell command per matching message for `follow --exec`.
+//! Exports: `ExecHook`, `ExecErrorPolicy`, `render_command`.
+//! Role: Bounded-concurrency process runner used by follow emission paths.
+//! Invariants: Each message's JSON is written to the hook's stdin (one line) and substituted for `{}`.
+//! Invariants: At most `parallel` hooks run at once; spawning blocks until a slot frees up.
+//! Invariants: `finish` waits for every in-flight hook before follow returns.
+use std::io::Write;
+use std::process::{Child, Command, ExitStatus, Stdio};
+
+use plasmite::api::{Error, ErrorKind};
+use serde_json::Value;
+
+/// What to do when a hook exits non-zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExecErrorPolicy {
+    /// Keep following; failures are counted and reported at exit.
+    Continue,
+    /// Stop following and fail with the hook's status.
+    Stop,
+}
+
+pub struct ExecHook {
+    command: String,
+    parallel: usize,
+    policy: ExecErrorPolicy,
+    running: Vec<Child>,
+    failures: u64,
+}
+
+impl ExecHook {
+    pub fn new(command: String, parallel: usize, policy: ExecErrorPolicy) -> Self {
+        Self {
+            command,
+            parallel: parallel.max(1),
+            policy,
+            running: Vec::new(),
+            failures: 0,
+        }
+    }
+
+    /// Number of hooks that exited unsuccessfully under `ExecErrorPolicy::Continue`.
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+
+    pub fn run(&mut self, message: &Value) -> Result<(), Error> {
+        self.reap(false)?;
+        while self.running.len() >= self.parallel {
+            let child = self.running.remove(0);
+            self.wait_child(child)?;
+        }
+
+        let json = serde_json::to_string(message).map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to encode message for --exec")
+                .with_source(err)
+        })?;
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(render_command(&self.command, &json))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|err| {
+                Error::new(ErrorKind::Io)
+                    .with_message("failed to spawn --exec command")
+                    .with_hint("Check that `sh` is available on PATH.")
+                    .with_source(err)
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // Write from a helper thread so a hook that ignores stdin cannot stall follow.
+            std::thread::spawn(move || {
+                let _ = stdin.write_all(json.as_bytes());
+                let _ = stdin.write_all(b"\n");
+            });
+        }
+        self.running.push(child);
+        Ok(())
+    }
+
+    /// Wait for all in-flight hooks.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        self.reap(true)
+    }
+
+    fn reap(&mut self, block: bool) -> Result<(), Error> {
+        let mut index = 0;
+        while index < self.running.len() {
+            let done = block
+                || self.running[index]
+                    .try_wait()
+                    .map_err(wait_error)?
+                    .is_some();
+            if done {
+                let child = self.running.remove(index);
+                self.wait_child(child)?;
+            } else {
+                index += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn wait_child(&mut self, mut child: Child) -> Result<(), Error> {
+        let status = child.wait().map_err(wait_error)?;
+        if status.success() {
+            return Ok(());
+        }
+        match self.policy {
+            ExecErrorPolicy::Continue => {
+                self.failures += 1;
+                Ok(())
+            }
+            ExecErrorPolicy::Stop => Err(hook_failed(&self.command, status)),
+        }
+    }
+}
+
+/// Substitute `{}` with the shell-quoted message JSON.
+pub fn render_command(template: &str, json: &str) -> String {
+    template.replace("{}", &shell_quote(json))
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn wait_error(err: std::io::Error) -> Error {
+    Error::new(ErrorKind::Io)
+        .with_message("failed waiting for --exec command")
+        .with_source(err)
+}
+
+fn hook_failed(command: &str, status: ExitStatus) -> Error {
+    Error::new(ErrorKind::Io)
+        .with_message(format!("--exec command failed ({status}): {command}"))
+        .with_hint("Use --exec-errors continue to keep following after hook failures.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecErrorPolicy, ExecHook, render_command};
+    use serde_json::json;
+
+    #[test]
+    fn render_command_quotes_json_placeholder() {
+        assert_eq!(
+            render_command("notify-send {}", r#"{"msg":"it's"}"#),
+            r#"notify-send '{"msg":"it'\''s"}'"#
+        );
+        assert_eq!(render_command("cat", "{}"), "cat");
+    }
+
+    #[test]
+    fn stop_policy_surfaces_hook_failure() {
+        let mut hook = ExecHook::new("exit 3".to_string(), 1, ExecErrorPolicy::Stop);
+        hook.run(&json!({"seq": 1})).expect("spawn");
+        assert!(hook.finish().is_err());
+    }
+
+    #[test]
+    fn continue_policy_counts_failures() {
+        let mut hook = ExecHook::new("exit 1".to_string(), 2, ExecErrorPolicy::Continue);
+        for seq in 0..3 {
+            hook.run(&json!({"seq": seq})).expect("spawn");
+        }
+        hook.finish().expect("finish");
+        assert_eq!(hook.failures(), 3);
+    }
+}
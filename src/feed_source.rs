@@ -0,0 +1,143 @@
+//! Purpose: Interactive input sources for `feed`: `--edit` ($EDITOR) and `--from-clipboard`.
+//! Exports: `edit_message`, `read_clipboard`, `EDIT_TEMPLATE`.
+//! Role: Produces one JSON text that `feed` then treats exactly like inline DATA.
+//! Invariants: Editor buffers drop `//` comment lines; an empty buffer aborts without appending.
+//! Invariants: Clipboard reads shell out to platform tools; `PLASMITE_CLIPBOARD_CMD` overrides them.
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use plasmite::api::{Error, ErrorKind};
+
+/// Initial `--edit` buffer; comment lines are stripped before parsing.
+pub const EDIT_TEMPLATE: &str = "\
+// Compose one JSON value to feed. Lines starting with // are ignored.
+// Save and quit to append; leave the buffer empty to abort.
+{
+  \"msg\": \"\"
+}
+";
+
+/// Opens `$VISUAL`/`$EDITOR` (falling back to `vi`) on a JSON template and returns the saved text.
+pub fn edit_message(pool: &str) -> Result<String, Error> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let path = edit_buffer_path();
+    std::fs::write(&path, EDIT_TEMPLATE).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to write editor buffer")
+            .with_path(&path)
+            .with_source(err)
+    })?;
+    // Run through the shell so EDITOR values with arguments (e.g. `code --wait`) work.
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(&path)
+        .status();
+    let text = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    let status = status.map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message(format!("failed to launch editor `{editor}`"))
+            .with_hint("Set $EDITOR (or $VISUAL) to an installed editor.")
+            .with_source(err)
+    })?;
+    if !status.success() {
+        return Err(Error::new(ErrorKind::Usage).with_message(format!(
+            "editor exited with {status}; nothing fed to {pool}"
+        )));
+    }
+    let text = text.map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to read editor buffer")
+            .with_path(&path)
+            .with_source(err)
+    })?;
+    let body = strip_comment_lines(&text);
+    if body.trim().is_empty() {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!("empty message; nothing fed to {pool}")));
+    }
+    Ok(body)
+}
+
+/// Reads the system clipboard as text.
+pub fn read_clipboard() -> Result<String, Error> {
+    if let Ok(command) = std::env::var("PLASMITE_CLIPBOARD_CMD") {
+        return run_clipboard_command("sh", &["-c", command.as_str()]).ok_or_else(|| {
+            Error::new(ErrorKind::Io)
+                .with_message("PLASMITE_CLIPBOARD_CMD failed")
+                .with_hint("The command must exit 0 and print the clipboard text on stdout.")
+        });
+    }
+    CLIPBOARD_COMMANDS
+        .iter()
+        .find_map(|(program, args)| run_clipboard_command(program, args))
+        .ok_or_else(|| {
+            Error::new(ErrorKind::NotFound)
+                .with_message("no clipboard tool available")
+                .with_hint(
+                    "Install wl-paste, xclip, or xsel, or set PLASMITE_CLIPBOARD_CMD to a command that prints the clipboard.",
+                )
+        })
+}
+
+/// Platform clipboard readers, tried in order until one succeeds.
+#[cfg(target_os = "macos")]
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[("pbpaste", &[])];
+#[cfg(windows)]
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[(
+    "powershell",
+    &["-NoProfile", "-Command", "Get-Clipboard -Raw"],
+)];
+#[cfg(not(any(target_os = "macos", windows)))]
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-o"]),
+    ("xsel", &["--clipboard", "--output"]),
+];
+
+fn run_clipboard_command(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+fn strip_comment_lines(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn edit_buffer_path() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!("plasmite-feed-{}-{nanos}.json", std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EDIT_TEMPLATE, strip_comment_lines};
+
+    #[test]
+    fn template_without_comments_is_valid_json() {
+        let body = strip_comment_lines(EDIT_TEMPLATE);
+        let value: serde_json::Value = serde_json::from_str(&body).expect("json");
+        assert_eq!(value, serde_json::json!({"msg": ""}));
+    }
+}
@@ -0,0 +1,178 @@
+//! Purpose: Apply `feed --set` / `--set-time` field assignments to each ingested record.
+//! Exports: `FieldSets`.
+//! Role: Enriches records with static context (host, version, ingest time) before encoding.
+//! Invariants: Paths are dotted and rooted at `.data` (e.g. `.data.host`); missing parents are
+//! created as objects, and assigning into a non-object record is a per-record error.
+//! Invariants: `env.NAME` / `$ENV.NAME` resolve once at startup (unset → null); `--set-time`
+//! stamps each record with the wall-clock time it was ingested (RFC 3339).
+//! Invariants: Required fields (from a pool template) are checked after all assignments.
+use serde_json::{Map, Value};
+
+use plasmite::api::{Error, ErrorKind};
+
+enum SetValue {
+    Literal(Value),
+    IngestTime,
+}
+
+struct Assignment {
+    expr: String,
+    path: Vec<String>,
+    value: SetValue,
+}
+
+#[derive(Default)]
+pub struct FieldSets {
+    assignments: Vec<Assignment>,
+    required: Vec<String>,
+    template: String,
+}
+
+impl FieldSets {
+    /// Parse `--set 'PATH = VALUE'` entries and `--set-time PATH` entries, applied in that order.
+    pub fn parse(sets: &[String], set_times: &[String]) -> Result<Self, Error> {
+        let mut assignments = Vec::with_capacity(sets.len() + set_times.len());
+        for expr in sets {
+            let (path, value) = expr.split_once('=').ok_or_else(|| set_error(expr))?;
+            if value.starts_with('=') {
+                return Err(set_error(expr));
+            }
+            assignments.push(Assignment {
+                expr: expr.clone(),
+                path: parse_path(path.trim()).ok_or_else(|| set_error(expr))?,
+                value: SetValue::Literal(parse_value(value.trim()).ok_or_else(|| set_error(expr))?),
+            });
+        }
+        for path in set_times {
+            assignments.push(Assignment {
+                expr: format!("{path} = <ingest time>"),
+                path: parse_path(path.trim()).ok_or_else(|| {
+                    Error::new(ErrorKind::Usage)
+                        .with_message(format!("invalid --set-time path '{path}'"))
+                        .with_hint("Use a path under .data, e.g. --set-time .data.received_at")
+                })?,
+                value: SetValue::IngestTime,
+            });
+        }
+        Ok(Self {
+            assignments,
+            ..Self::default()
+        })
+    }
+
+    /// Reject records lacking any of `required` (the `.data` keys pool template `template` demands).
+    pub fn with_required(mut self, template: &str, required: &[String]) -> Self {
+        self.template = template.to_string();
+        self.required = required.to_vec();
+        self
+    }
+
+    /// Apply every assignment to one record (the message `.data`).
+    pub fn apply(&self, mut data: Value) -> Result<Value, Error> {
+        for assignment in &self.assignments {
+            let value = match &assignment.value {
+                SetValue::Literal(value) => value.clone(),
+                SetValue::IngestTime => Value::String(ingest_time()?),
+            };
+            assign(&mut data, &assignment.path, value).ok_or_else(|| {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("cannot apply --set `{}`", assignment.expr))
+                    .with_hint("Assignments need the record (and each parent on the path) to be a JSON object.")
+            })?;
+        }
+        if let Some(key) = self
+            .required
+            .iter()
+            .find(|key| data.get(key.as_str()).is_none())
+        {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("record is missing required field `{key}`"))
+                .with_hint(format!(
+                    "Pool template `{}` requires: {}.",
+                    self.template,
+                    self.required.join(", ")
+                )));
+        }
+        Ok(data)
+    }
+}
+
+/// `.data.a.b` → `["a", "b"]`; the `.data` root itself cannot be replaced.
+fn parse_path(path: &str) -> Option<Vec<String>> {
+    let rest = path.strip_prefix(".data.")?;
+    let segments = rest.split('.').map(str::to_string).collect::<Vec<_>>();
+    if segments.iter().any(String::is_empty) {
+        return None;
+    }
+    Some(segments)
+}
+
+fn parse_value(value: &str) -> Option<Value> {
+    let env_name = value
+        .strip_prefix("env.")
+        .or_else(|| value.strip_prefix("$ENV."));
+    if let Some(name) = env_name {
+        return Some(std::env::var(name).map_or(Value::Null, Value::String));
+    }
+    serde_json::from_str(value).ok()
+}
+
+fn assign(data: &mut Value, path: &[String], value: Value) -> Option<()> {
+    let (last, parents) = path.split_last()?;
+    let mut target = data.as_object_mut()?;
+    for key in parents {
+        target = target
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()?;
+    }
+    target.insert(last.clone(), value);
+    Some(())
+}
+
+fn ingest_time() -> Result<String, Error> {
+    time::OffsetDateTime::now_utc()
+        .format(&time::format_description::well_known::Rfc3339)
+        .map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to format ingest time")
+                .with_source(err)
+        })
+}
+
+fn set_error(expr: &str) -> Error {
+    Error::new(ErrorKind::Usage)
+        .with_message(format!("invalid --set expression '{expr}'"))
+        .with_hint("Use PATH = VALUE with a path under .data and a JSON or env.NAME value, e.g. --set '.data.host = env.HOSTNAME'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FieldSets;
+    use serde_json::json;
+
+    #[test]
+    fn applies_literals_env_and_ingest_time() {
+        let sets = FieldSets::parse(
+            &[
+                ".data.version = \"1.2\"".to_string(),
+                ".data.build.env = env.PLASMITE_FIELD_SET_UNSET_VAR".to_string(),
+            ],
+            &[".data.received_at".to_string()],
+        )
+        .expect("parse");
+        let data = sets.apply(json!({"msg": "hi"})).expect("apply");
+        assert_eq!(data["msg"], json!("hi"));
+        assert_eq!(data["version"], json!("1.2"));
+        assert_eq!(data["build"], json!({"env": null}));
+        assert!(data["received_at"].as_str().is_some());
+
+        assert!(sets.apply(json!([1, 2])).is_err());
+        assert!(FieldSets::parse(&[".data.x == 1".to_string()], &[]).is_err());
+        assert!(FieldSets::parse(&[".meta.x = 1".to_string()], &[]).is_err());
+
+        let required = FieldSets::default().with_required("chat", &["from".to_string()]);
+        assert!(required.apply(json!({"msg": "hi"})).is_err());
+        assert!(required.apply(json!({"from": "a"})).is_ok());
+    }
+}
@@ -0,0 +1,173 @@
+//! Purpose: Maintain a named pipe (FIFO) for `plasmite bridge --fifo`.
+//! Exports: `FifoBridge`.
+//! Role: FIFO lifecycle and reader detection; the follow loop stays in dispatch.
+//! Invariants: Opening for write never blocks: with no reader attached, `try_connect` returns
+//! `None` and the caller keeps polling, so readers may come and go at any time.
+//! Invariants: A FIFO the bridge created is removed when the bridge exits; a pre-existing FIFO
+//! is reused and left in place unless `--recreate` replaced it.
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use plasmite::api::{Error, ErrorKind};
+
+pub struct FifoBridge {
+    path: PathBuf,
+    created: bool,
+}
+
+impl FifoBridge {
+    /// Ensure `path` is a FIFO. Existing non-FIFO files are an error unless `recreate`,
+    /// which also replaces an existing FIFO (detaching any stale readers).
+    #[cfg(unix)]
+    pub fn prepare(path: &Path, recreate: bool) -> Result<Self, Error> {
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::fs::FileTypeExt;
+
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if meta.file_type().is_fifo() && !recreate => {
+                return Ok(Self {
+                    path: path.to_path_buf(),
+                    created: false,
+                });
+            }
+            Ok(_) if !recreate => {
+                return Err(Error::new(ErrorKind::AlreadyExists)
+                    .with_message("bridge target exists and is not a FIFO")
+                    .with_path(path)
+                    .with_hint("Pick another path, or pass --recreate to replace it."));
+            }
+            Ok(_) => std::fs::remove_file(path).map_err(|err| {
+                Error::new(ErrorKind::Io)
+                    .with_message("failed to remove existing bridge target")
+                    .with_path(path)
+                    .with_source(err)
+            })?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to inspect bridge target")
+                    .with_path(path)
+                    .with_source(err));
+            }
+        }
+        let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+            Error::new(ErrorKind::Usage)
+                .with_message("FIFO path contains a NUL byte")
+                .with_path(path)
+        })?;
+        // SAFETY: `c_path` is a valid NUL-terminated string for the duration of the call.
+        if unsafe { libc::mkfifo(c_path.as_ptr(), 0o644) } != 0 {
+            return Err(Error::new(ErrorKind::Io)
+                .with_message("failed to create FIFO")
+                .with_path(path)
+                .with_source(std::io::Error::last_os_error()));
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            created: true,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn prepare(path: &Path, _recreate: bool) -> Result<Self, Error> {
+        Err(Error::new(ErrorKind::Usage)
+            .with_message("bridge --fifo requires a Unix platform")
+            .with_path(path)
+            .with_hint("Use `plasmite follow` with a pipe instead."))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Open the write end if a reader is attached; `None` while nobody is reading.
+    #[cfg(unix)]
+    pub fn try_connect(&self) -> Result<Option<File>, Error> {
+        use std::os::unix::fs::OpenOptionsExt;
+        use std::os::unix::io::AsRawFd;
+
+        let file = match std::fs::OpenOptions::new()
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(&self.path)
+        {
+            Ok(file) => file,
+            Err(err) if err.raw_os_error() == Some(libc::ENXIO) => return Ok(None),
+            Err(err) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to open FIFO for writing")
+                    .with_path(&self.path)
+                    .with_source(err));
+            }
+        };
+        // Back to blocking writes so a slow reader applies backpressure instead of EAGAIN.
+        let fd = file.as_raw_fd();
+        // SAFETY: `fd` is owned by `file`, which outlives both calls.
+        unsafe {
+            let flags = libc::fcntl(fd, libc::F_GETFL);
+            libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK);
+        }
+        Ok(Some(file))
+    }
+
+    #[cfg(not(unix))]
+    pub fn try_connect(&self) -> Result<Option<File>, Error> {
+        Ok(None)
+    }
+
+    /// True once every reader has closed its end (the next write would fail with EPIPE).
+    #[cfg(unix)]
+    pub fn reader_gone(writer: &File) -> bool {
+        use std::os::unix::io::AsRawFd;
+
+        let mut fds = libc::pollfd {
+            fd: writer.as_raw_fd(),
+            events: 0,
+            revents: 0,
+        };
+        // SAFETY: one valid pollfd, zero timeout; poll does not retain the pointer.
+        let ready = unsafe { libc::poll(&mut fds, 1, 0) };
+        ready > 0 && fds.revents & libc::POLLERR != 0
+    }
+
+    #[cfg(not(unix))]
+    pub fn reader_gone(_writer: &File) -> bool {
+        false
+    }
+}
+
+impl Drop for FifoBridge {
+    fn drop(&mut self) {
+        if self.created {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::FifoBridge;
+    use plasmite::api::ErrorKind;
+
+    #[test]
+    fn prepare_reuses_fifos_and_guards_regular_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("events.pipe");
+        {
+            let bridge = FifoBridge::prepare(&path, false).expect("create");
+            assert!(bridge.try_connect().expect("connect").is_none());
+            let reused = FifoBridge::prepare(&path, false).expect("reuse");
+            drop(reused);
+            assert!(path.exists());
+        }
+        assert!(!path.exists());
+
+        std::fs::write(&path, "x").expect("write");
+        let err = FifoBridge::prepare(&path, false)
+            .err()
+            .expect("regular file");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        let _bridge = FifoBridge::prepare(&path, true).expect("recreate");
+        assert!(path.exists());
+    }
+}
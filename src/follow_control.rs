@@ -0,0 +1,384 @@
+//! Purpose: Runtime control channel for `follow --control-socket` and the `plasmite ctl` client.
+//! Exports: `FollowControl`, `ControlRequest`, `send_request`.
+//! Role: A listener thread accepts one JSON request line per connection and updates shared
+//! state (paused flag, `--where` override, counters) that the follow loop polls between frames.
+//! Invariants: Requests are validated before they take effect; a bad `where` expression leaves
+//! the running filters untouched and is answered with `{"ok":false,"error":{...}}`.
+//! Invariants: Pausing only stops reading; the cursor stays put, so resume continues with the
+//! next unread message (subject to pool overwrite, reported as usual drop notices).
+//! Invariants: A stale socket file (nothing listening) is replaced on bind; a live one is a
+//! usage error. The socket file is removed when follow exits normally.
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use plasmite::api::{Error, ErrorKind};
+use serde_json::{Value, json};
+
+use crate::jq_filter::{JqFilter, compile_filters, matches_all};
+
+/// Cap on one request line; anything longer is not a ctl request.
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ControlRequest {
+    Stats,
+    Pause,
+    Resume,
+    /// Replace the follower's `--where` filters; an empty list matches everything.
+    Where(Vec<String>),
+}
+
+impl ControlRequest {
+    pub fn to_json(&self) -> Value {
+        match self {
+            ControlRequest::Stats => json!({ "cmd": "stats" }),
+            ControlRequest::Pause => json!({ "cmd": "pause" }),
+            ControlRequest::Resume => json!({ "cmd": "resume" }),
+            ControlRequest::Where(exprs) => json!({ "cmd": "where", "exprs": exprs }),
+        }
+    }
+
+    fn parse(line: &str) -> Result<Self, Error> {
+        let invalid = |message: &str| {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid control request: {message}"))
+                .with_hint("Send one JSON line like {\"cmd\":\"stats\"}, or use `plasmite ctl`.")
+        };
+        let value: Value = serde_json::from_str(line).map_err(|err| invalid(&err.to_string()))?;
+        match value.get("cmd").and_then(Value::as_str) {
+            Some("stats") => Ok(ControlRequest::Stats),
+            Some("pause") => Ok(ControlRequest::Pause),
+            Some("resume") => Ok(ControlRequest::Resume),
+            Some("where") => {
+                let exprs = value
+                    .get("exprs")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| invalid("\"where\" needs an \"exprs\" array"))?
+                    .iter()
+                    .map(|expr| expr.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| invalid("\"exprs\" must be strings"))?;
+                Ok(ControlRequest::Where(exprs))
+            }
+            Some(other) => Err(invalid(&format!("unknown cmd \"{other}\""))),
+            None => Err(invalid("missing \"cmd\"")),
+        }
+    }
+}
+
+struct WhereState {
+    exprs: Vec<String>,
+    /// `None` until the first `where` request; the follower's own predicates apply until then.
+    filters: Option<Vec<JqFilter>>,
+}
+
+/// State shared between one follower and its control listener.
+pub struct FollowControl {
+    pool: String,
+    socket_path: PathBuf,
+    started: Instant,
+    paused: AtomicBool,
+    where_state: Mutex<WhereState>,
+    emitted: AtomicU64,
+    filtered: AtomicU64,
+    /// Last emitted seq plus one; zero means nothing emitted yet.
+    next_seq: AtomicU64,
+}
+
+impl FollowControl {
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    /// Evaluate the active `--where` set: the last `ctl where` override, else `base`.
+    pub fn matches(&self, base: &[JqFilter], message: &Value) -> Result<bool, Error> {
+        let state = self
+            .where_state
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        let matched = matches_all(state.filters.as_deref().unwrap_or(base), message)?;
+        if !matched {
+            self.filtered.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(matched)
+    }
+
+    pub fn record_emitted(&self, seq: Option<u64>) {
+        self.emitted.fetch_add(1, Ordering::Relaxed);
+        if let Some(seq) = seq {
+            self.next_seq
+                .store(seq.saturating_add(1), Ordering::Relaxed);
+        }
+    }
+
+    /// Remove the socket file; call once follow returns.
+    pub fn close(&self) {
+        let _ = std::fs::remove_file(&self.socket_path);
+    }
+
+    fn apply(&self, request: ControlRequest) -> Result<Value, Error> {
+        match request {
+            ControlRequest::Stats => {}
+            ControlRequest::Pause => self.paused.store(true, Ordering::Release),
+            ControlRequest::Resume => self.paused.store(false, Ordering::Release),
+            ControlRequest::Where(exprs) => {
+                let filters = compile_filters(&exprs)?;
+                let mut state = self
+                    .where_state
+                    .lock()
+                    .unwrap_or_else(|poison| poison.into_inner());
+                *state = WhereState {
+                    exprs,
+                    filters: Some(filters),
+                };
+            }
+        }
+        Ok(self.stats_json())
+    }
+
+    fn stats_json(&self) -> Value {
+        let exprs = self
+            .where_state
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .exprs
+            .clone();
+        let next_seq = self.next_seq.load(Ordering::Relaxed);
+        json!({
+            "ok": true,
+            "pool": self.pool,
+            "paused": self.is_paused(),
+            "where": exprs,
+            "emitted": self.emitted.load(Ordering::Relaxed),
+            "filtered": self.filtered.load(Ordering::Relaxed),
+            "last_seq": next_seq.checked_sub(1),
+            "uptime_ms": self.started.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn respond(&self, line: &str) -> Value {
+        match ControlRequest::parse(line.trim()).and_then(|request| self.apply(request)) {
+            Ok(value) => value,
+            Err(err) => {
+                let mut value = err.to_json();
+                value["ok"] = json!(false);
+                value
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl FollowControl {
+    /// Bind `socket_path` and start the listener thread.
+    pub fn bind(
+        socket_path: &Path,
+        pool: &str,
+        where_exprs: Vec<String>,
+    ) -> Result<std::sync::Arc<Self>, Error> {
+        use std::os::unix::fs::FileTypeExt;
+        use std::os::unix::net::{UnixListener, UnixStream};
+
+        let bind_error = |err: std::io::Error| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to bind control socket")
+                .with_path(socket_path)
+                .with_source(err)
+        };
+        if socket_path.exists() {
+            if UnixStream::connect(socket_path).is_ok() {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("control socket is already in use")
+                    .with_path(socket_path)
+                    .with_hint("Another follower is listening there; pick a different path."));
+            }
+            let is_socket = std::fs::symlink_metadata(socket_path)
+                .is_ok_and(|meta| meta.file_type().is_socket());
+            if !is_socket {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("control socket path exists and is not a socket")
+                    .with_path(socket_path)
+                    .with_hint("Pick a path that does not exist yet."));
+            }
+            std::fs::remove_file(socket_path).map_err(bind_error)?;
+        }
+        let listener = UnixListener::bind(socket_path).map_err(bind_error)?;
+        let control = std::sync::Arc::new(Self {
+            pool: pool.to_string(),
+            socket_path: socket_path.to_path_buf(),
+            started: Instant::now(),
+            paused: AtomicBool::new(false),
+            where_state: Mutex::new(WhereState {
+                exprs: where_exprs,
+                filters: None,
+            }),
+            emitted: AtomicU64::new(0),
+            filtered: AtomicU64::new(0),
+            next_seq: AtomicU64::new(0),
+        });
+        let shared = control.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                // A misbehaving client only loses its own connection.
+                let Ok(stream) = stream else { continue };
+                let _ = serve_connection(&shared, stream);
+            }
+        });
+        Ok(control)
+    }
+}
+
+#[cfg(unix)]
+fn serve_connection(
+    control: &FollowControl,
+    stream: std::os::unix::net::UnixStream,
+) -> std::io::Result<()> {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new((&stream).take(MAX_REQUEST_BYTES)).read_line(&mut line)?;
+    let mut reply = control.respond(&line).to_string();
+    reply.push('\n');
+    (&stream).write_all(reply.as_bytes())
+}
+
+/// Send one request to a follower's control socket and return its JSON reply.
+#[cfg(unix)]
+pub fn send_request(socket_path: &Path, request: &ControlRequest) -> Result<Value, Error> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let io_error = |message: &'static str| {
+        move |err: std::io::Error| {
+            Error::new(ErrorKind::Io)
+                .with_message(message)
+                .with_path(socket_path)
+                .with_source(err)
+        }
+    };
+    let mut stream = UnixStream::connect(socket_path).map_err(|err| {
+        let kind = match err.kind() {
+            std::io::ErrorKind::NotFound | std::io::ErrorKind::ConnectionRefused => {
+                ErrorKind::NotFound
+            }
+            _ => ErrorKind::Io,
+        };
+        Error::new(kind)
+            .with_message("no follower is listening on the control socket")
+            .with_path(socket_path)
+            .with_hint("Start one with `plasmite follow POOL --control-socket PATH`.")
+            .with_source(err)
+    })?;
+    stream
+        .set_read_timeout(Some(CLIENT_TIMEOUT))
+        .map_err(io_error("failed to configure control socket"))?;
+    let mut line = request.to_json().to_string();
+    line.push('\n');
+    stream
+        .write_all(line.as_bytes())
+        .map_err(io_error("failed to send control request"))?;
+    let mut reply = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut reply)
+        .map_err(io_error("failed to read control reply"))?;
+    let value: Value = serde_json::from_str(reply.trim()).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("invalid control reply")
+            .with_path(socket_path)
+            .with_source(err)
+    })?;
+    if value.get("ok").and_then(Value::as_bool) == Some(true) {
+        return Ok(value);
+    }
+    let error = value.get("error").cloned().unwrap_or(Value::Null);
+    let kind = match error.get("kind").and_then(Value::as_str) {
+        Some("Usage") => ErrorKind::Usage,
+        _ => ErrorKind::Internal,
+    };
+    let mut err = Error::new(kind).with_message(
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("control request failed")
+            .to_string(),
+    );
+    if let Some(hint) = error.get("hint").and_then(Value::as_str) {
+        err = err.with_hint(hint.to_string());
+    }
+    Err(err)
+}
+
+#[cfg(not(unix))]
+impl FollowControl {
+    pub fn bind(
+        _socket_path: &Path,
+        _pool: &str,
+        _where_exprs: Vec<String>,
+    ) -> Result<std::sync::Arc<Self>, Error> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(not(unix))]
+pub fn send_request(_socket_path: &Path, _request: &ControlRequest) -> Result<Value, Error> {
+    Err(unsupported())
+}
+
+#[cfg(not(unix))]
+fn unsupported() -> Error {
+    Error::new(ErrorKind::Usage)
+        .with_message("control sockets need a Unix platform")
+        .with_hint("Restart the follower with new flags instead.")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{ControlRequest, FollowControl, send_request};
+    use crate::jq_filter::compile_filters;
+    use serde_json::json;
+
+    #[test]
+    fn requests_pause_and_swap_filters_in_place() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket = dir.path().join("f.sock");
+        let base = compile_filters(&[".data.level == \"info\"".to_string()]).expect("base");
+        let control =
+            FollowControl::bind(&socket, "events", vec![".data.level == \"info\"".into()])
+                .expect("bind");
+        let error = json!({ "data": { "level": "error" } });
+        assert!(!control.matches(&base, &error).expect("base filters"));
+
+        let reply = send_request(&socket, &ControlRequest::Pause).expect("pause");
+        assert_eq!(reply["paused"], true);
+        assert!(control.is_paused());
+        send_request(&socket, &ControlRequest::Resume).expect("resume");
+        assert!(!control.is_paused());
+
+        let bad = send_request(&socket, &ControlRequest::Where(vec!["(".to_string()]));
+        assert!(bad.is_err());
+        let reply = send_request(
+            &socket,
+            &ControlRequest::Where(vec![".data.level == \"error\"".to_string()]),
+        )
+        .expect("where");
+        assert_eq!(reply["where"], json!([".data.level == \"error\""]));
+        assert_eq!(reply["filtered"], 1);
+        assert!(control.matches(&base, &error).expect("override"));
+
+        control.record_emitted(Some(9));
+        let stats = send_request(&socket, &ControlRequest::Stats).expect("stats");
+        assert_eq!(
+            (stats["emitted"].clone(), stats["last_seq"].clone()),
+            (json!(1), json!(9))
+        );
+
+        assert!(FollowControl::bind(&socket, "events", Vec::new()).is_err());
+        control.close();
+        assert!(!socket.exists());
+    }
+}
@@ -0,0 +1,220 @@
+//! Purpose: Deliver pool messages to an HTTP endpoint for `plasmite forward`.
+//! Exports: `ForwardCursor`, `Forwarder`, `parse_header`, `batch_body`.
+//! Role: Cursor persistence and HTTP POST delivery; the follow loop stays in dispatch.
+//! `ForwardCursor` is also the persisted cursor behind `backup`/`restore`.
+//! Invariants: The cursor only advances after the endpoint acknowledges a batch (2xx).
+//! Invariants: Cursor writes are atomic (temp file + rename) so restarts never see partial state.
+//! Invariants: 4xx responses other than 408/429 are permanent failures and are not retried.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use plasmite::api::{Error, ErrorKind};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Last delivered sequence for one (pool, endpoint) pair.
+#[derive(Debug, Clone)]
+pub struct ForwardCursor {
+    path: PathBuf,
+}
+
+impl ForwardCursor {
+    /// Default cursor location: `<pool_dir>/.forward/<pool>-<url digest>.json`.
+    pub fn for_target(pool_dir: &Path, pool: &str, url: &str) -> Self {
+        let digest = Sha256::digest(url.as_bytes());
+        let suffix: String = digest[..6].iter().map(|b| format!("{b:02x}")).collect();
+        let stem = Path::new(pool)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(pool);
+        Self::at(
+            pool_dir
+                .join(".forward")
+                .join(format!("{stem}-{suffix}.json")),
+        )
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> Result<Option<u64>, Error> {
+        let raw = match fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to read cursor file")
+                    .with_path(&self.path)
+                    .with_source(err));
+            }
+        };
+        let value: Value = serde_json::from_str(&raw).map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("invalid cursor file")
+                .with_path(&self.path)
+                .with_hint("Delete the cursor file to start over from new messages.")
+                .with_source(err)
+        })?;
+        Ok(value.get("seq").and_then(Value::as_u64))
+    }
+
+    pub fn store(&self, seq: u64) -> Result<(), Error> {
+        let io_error = |err: std::io::Error| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to write cursor file")
+                .with_path(&self.path)
+                .with_source(err)
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, format!("{}\n", json!({ "seq": seq }))).map_err(io_error)?;
+        fs::rename(&tmp, &self.path).map_err(io_error)
+    }
+}
+
+/// Parse a `Name: value` header argument.
+pub fn parse_header(input: &str) -> Result<(String, String), Error> {
+    match input.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(Error::new(ErrorKind::Usage)
+            .with_message(format!("invalid header: {input}"))
+            .with_hint("Use `Name: value`, e.g. --header 'Authorization: Bearer abc'.")),
+    }
+}
+
+/// Request body for one batch: the message itself for `--batch 1`, otherwise an array.
+pub fn batch_body(batch: &[Value], batch_size: usize) -> Value {
+    if batch_size == 1 && batch.len() == 1 {
+        return batch[0].clone();
+    }
+    Value::Array(batch.to_vec())
+}
+
+pub struct Forwarder {
+    agent: ureq::Agent,
+    url: String,
+    headers: Vec<(String, String)>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl Forwarder {
+    pub fn new(
+        url: &str,
+        headers: Vec<(String, String)>,
+        retries: u32,
+        retry_delay: Duration,
+    ) -> Result<Self, Error> {
+        let parsed = url::Url::parse(url).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid --to URL: {url}"))
+                .with_hint("Use an absolute http(s) URL, e.g. https://example.com/hook.")
+                .with_source(err)
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("unsupported --to scheme: {}", parsed.scheme()))
+                .with_hint("Use an http:// or https:// URL."));
+        }
+        let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+        Ok(Self {
+            agent,
+            url: url.to_string(),
+            headers,
+            retries,
+            retry_delay,
+        })
+    }
+
+    /// POST one batch, retrying transient failures with exponential backoff.
+    pub fn deliver(&self, body: &Value) -> Result<(), Error> {
+        let payload = body.to_string();
+        let mut delay = self.retry_delay;
+        let mut attempts = 0u32;
+        loop {
+            attempts += 1;
+            let mut request = self
+                .agent
+                .post(&self.url)
+                .set("Content-Type", "application/json")
+                .set(
+                    "User-Agent",
+                    concat!("plasmite/", env!("CARGO_PKG_VERSION")),
+                );
+            for (name, value) in &self.headers {
+                request = request.set(name, value);
+            }
+            let (err, retryable) = match request.send_string(&payload) {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(code, _)) => (
+                    Error::new(ErrorKind::Io)
+                        .with_message(format!("endpoint returned HTTP {code}")),
+                    code >= 500 || code == 408 || code == 429,
+                ),
+                Err(ureq::Error::Transport(err)) => (
+                    Error::new(ErrorKind::Io)
+                        .with_message("failed to reach endpoint")
+                        .with_source(err),
+                    true,
+                ),
+            };
+            if !retryable || attempts > self.retries {
+                return Err(err.with_hint(format!(
+                    "Delivery to {} failed after {attempts} attempt(s); rerun to resume from the saved cursor.",
+                    self.url
+                )));
+            }
+            std::thread::sleep(delay);
+            delay = std::cmp::min(delay * 2, MAX_BACKOFF);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ForwardCursor, batch_body, parse_header};
+    use serde_json::json;
+
+    #[test]
+    fn cursor_round_trips_and_is_scoped_by_url() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let a = ForwardCursor::for_target(temp.path(), "events", "https://a.example/hook");
+        let b = ForwardCursor::for_target(temp.path(), "events", "https://b.example/hook");
+        assert_ne!(a.path(), b.path());
+        assert_eq!(a.load().expect("load"), None);
+
+        a.store(42).expect("store");
+        assert_eq!(a.load().expect("load"), Some(42));
+        assert_eq!(b.load().expect("load"), None);
+    }
+
+    #[test]
+    fn parse_header_requires_name() {
+        assert_eq!(
+            parse_header("Authorization: Bearer abc").expect("header"),
+            ("Authorization".to_string(), "Bearer abc".to_string())
+        );
+        assert!(parse_header("no-colon").is_err());
+        assert!(parse_header(": value").is_err());
+    }
+
+    #[test]
+    fn batch_body_unwraps_single_message_batches() {
+        let one = vec![json!({"seq": 1})];
+        assert_eq!(batch_body(&one, 1), json!({"seq": 1}));
+        assert_eq!(batch_body(&one, 20), json!([{"seq": 1}]));
+    }
+}
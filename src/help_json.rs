@@ -0,0 +1,206 @@
+//! Purpose: Render the clap command tree as JSON for `--help-json`.
+//! Exports: `help_json_request`, `command_schema`.
+//! Role: Let GUI wrappers, the MCP server, and docs generators introspect the CLI.
+//! Invariants: Output is derived from the live clap definition; nothing is hand-maintained.
+//! Invariants: Hidden commands/args are omitted, matching `--help`.
+//! Invariants: Arg `type` is one of bool|count|enum|path|string.
+use std::ffi::OsString;
+
+use clap::{ArgAction, Command, ValueHint};
+use serde_json::{Map, Value, json};
+
+const HELP_JSON_FLAG: &str = "--help-json";
+
+/// If `args` request `--help-json`, return the subcommand path named before it.
+///
+/// `plasmite pool create --help-json` yields `["pool", "create"]`; arguments after `--` are ignored.
+pub(crate) fn help_json_request(args: &[OsString]) -> Option<Vec<String>> {
+    let args = args
+        .iter()
+        .skip(1)
+        .take_while(|arg| arg.to_str() != Some("--"))
+        .collect::<Vec<_>>();
+    if !args.iter().any(|arg| arg.to_str() == Some(HELP_JSON_FLAG)) {
+        return None;
+    }
+    Some(
+        args.iter()
+            .filter_map(|arg| arg.to_str())
+            .filter(|arg| !arg.starts_with('-'))
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Schema for `root`, narrowed to the subcommands named in `path` (other segments, such as
+/// option values, are skipped).
+pub(crate) fn command_schema(root: &Command, path: &[String]) -> Value {
+    let mut command = root;
+    let mut names = vec![root.get_name().to_string()];
+    for segment in path {
+        let Some(next) = command.get_subcommands().find(|sub| {
+            sub.get_name() == segment.as_str()
+                || sub.get_all_aliases().any(|alias| alias == segment.as_str())
+        }) else {
+            continue;
+        };
+        names.push(next.get_name().to_string());
+        command = next;
+    }
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "path": names,
+        "command": command_json(command),
+    })
+}
+
+fn command_json(command: &Command) -> Value {
+    let mut out = Map::new();
+    out.insert("name".to_string(), json!(command.get_name()));
+    if let Some(about) = command.get_about() {
+        out.insert("about".to_string(), json!(about.to_string()));
+    }
+    let aliases = command.get_visible_aliases().collect::<Vec<_>>();
+    if !aliases.is_empty() {
+        out.insert("aliases".to_string(), json!(aliases));
+    }
+    let args = command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+        .map(arg_json)
+        .collect::<Vec<_>>();
+    out.insert("args".to_string(), Value::Array(args));
+    let subcommands = command
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set() && sub.get_name() != "help")
+        .map(command_json)
+        .collect::<Vec<_>>();
+    out.insert("subcommands".to_string(), Value::Array(subcommands));
+    Value::Object(out)
+}
+
+fn arg_json(arg: &clap::Arg) -> Value {
+    let action = arg.get_action();
+    let possible_values = arg
+        .get_possible_values()
+        .into_iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_string())
+        .collect::<Vec<_>>();
+    let kind = match action {
+        ArgAction::SetTrue | ArgAction::SetFalse => "bool",
+        ArgAction::Count => "count",
+        _ if !possible_values.is_empty() => "enum",
+        _ if matches!(
+            arg.get_value_hint(),
+            ValueHint::AnyPath | ValueHint::FilePath | ValueHint::DirPath
+        ) =>
+        {
+            "path"
+        }
+        _ => "string",
+    };
+    let multiple = matches!(action, ArgAction::Append)
+        || arg
+            .get_num_args()
+            .is_some_and(|range| range.max_values() > 1);
+
+    let mut out = Map::new();
+    out.insert("id".to_string(), json!(arg.get_id().as_str()));
+    out.insert("positional".to_string(), json!(arg.is_positional()));
+    if let Some(long) = arg.get_long() {
+        out.insert("long".to_string(), json!(format!("--{long}")));
+    }
+    if let Some(short) = arg.get_short() {
+        out.insert("short".to_string(), json!(format!("-{short}")));
+    }
+    out.insert("type".to_string(), json!(kind));
+    out.insert("required".to_string(), json!(arg.is_required_set()));
+    out.insert("multiple".to_string(), json!(multiple));
+    out.insert("global".to_string(), json!(arg.is_global_set()));
+    if let Some(names) = arg.get_value_names() {
+        let names = names
+            .iter()
+            .map(|name| name.as_str().to_string())
+            .collect::<Vec<_>>();
+        out.insert("value_names".to_string(), json!(names));
+    }
+    let defaults = arg
+        .get_default_values()
+        .iter()
+        .map(|value| value.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if !defaults.is_empty() && kind != "bool" && kind != "count" {
+        out.insert("default".to_string(), json!(defaults));
+    }
+    if !possible_values.is_empty() {
+        out.insert("possible_values".to_string(), json!(possible_values));
+    }
+    if let Some(help) = arg.get_help() {
+        out.insert("help".to_string(), json!(help.to_string()));
+    }
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{command_schema, help_json_request};
+    use clap::{Arg, ArgAction, Command};
+    use std::ffi::OsString;
+
+    fn args(values: &[&str]) -> Vec<OsString> {
+        values.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn help_json_request_collects_subcommand_path() {
+        assert_eq!(
+            help_json_request(&args(&["plasmite", "pool", "list"])),
+            None
+        );
+        assert_eq!(
+            help_json_request(&args(&["plasmite", "pool", "create", "--help-json"])),
+            Some(vec!["pool".to_string(), "create".to_string()])
+        );
+        assert_eq!(
+            help_json_request(&args(&["plasmite", "feed", "--", "--help-json"])),
+            None
+        );
+    }
+
+    #[test]
+    fn command_schema_describes_args_and_subcommands() {
+        let root = Command::new("tool").subcommand(
+            Command::new("run")
+                .about("Run things")
+                .arg(
+                    Arg::new("verbose")
+                        .long("verbose")
+                        .short('v')
+                        .action(ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("mode")
+                        .long("mode")
+                        .value_parser(["fast", "slow"])
+                        .default_value("fast"),
+                )
+                .arg(Arg::new("target").required(true)),
+        );
+        let schema = command_schema(&root, &["run".to_string()]);
+        assert_eq!(schema["path"], serde_json::json!(["tool", "run"]));
+        let command = &schema["command"];
+        assert_eq!(command["about"], "Run things");
+        let args = command["args"].as_array().expect("args");
+        assert_eq!(args[0]["type"], "bool");
+        assert_eq!(args[0]["short"], "-v");
+        assert_eq!(args[1]["type"], "enum");
+        assert_eq!(args[1]["default"], serde_json::json!(["fast"]));
+        assert_eq!(args[2]["positional"], true);
+        assert_eq!(args[2]["required"], true);
+
+        let full = command_schema(&root, &[]);
+        assert_eq!(full["command"]["subcommands"][0]["name"], "run");
+    }
+}
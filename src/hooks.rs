@@ -0,0 +1,293 @@
+//! Purpose: Registry, delivery, and backoff for append hooks (`plasmite hook`, `plasmite hookd`).
+//! Exports: `Hook`, `HookAction`, `HookRegistry`, `HookBackoff`, `hook_cursor`, `deliver`.
+//! Role: `hook add/list/remove` edit the registry; `hookd` reloads it every poll and runs each
+//! hook over the messages appended since its cursor. The follow loop itself stays in dispatch.
+//! Invariants: The registry is `<pool_dir>/.hooks/hooks.json`, rewritten atomically (temp file +
+//! rename); each hook's cursor is `<pool_dir>/.hooks/<id>.cursor.json` and only advances past a
+//! message once its delivery succeeded, so a failing hook retries the same message.
+//! Invariants: Failures back off per hook (1s doubling to 5m); other hooks keep running.
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use plasmite::api::{Error, ErrorKind};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::exec_hook::render_command;
+use crate::forward::{ForwardCursor, Forwarder};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookAction {
+    /// Shell command; the message JSON is written to stdin and substituted for `{}`.
+    Exec(String),
+    /// URL the message JSON is POSTed to.
+    Post(String),
+}
+
+impl HookAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HookAction::Exec(_) => "exec",
+            HookAction::Post(_) => "post",
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        match self {
+            HookAction::Exec(command) => command,
+            HookAction::Post(url) => url,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Hook {
+    pub id: String,
+    pub pool: String,
+    pub action: HookAction,
+    /// `--where` predicates, ANDed together.
+    #[serde(default, rename = "where", skip_serializing_if = "Vec::is_empty")]
+    pub where_exprs: Vec<String>,
+    /// RFC 3339 time the hook was added.
+    pub created_at: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HookRegistry {
+    hooks: Vec<Hook>,
+}
+
+impl HookRegistry {
+    pub fn load(pool_dir: &Path) -> Result<Self, Error> {
+        let path = registry_path(pool_dir);
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to read hook registry")
+                    .with_path(&path)
+                    .with_source(err));
+            }
+        };
+        serde_json::from_str(&raw).map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("invalid hook registry")
+                .with_path(&path)
+                .with_hint("Fix the file by hand, or delete it and add the hooks again.")
+                .with_source(err)
+        })
+    }
+
+    pub fn save(&self, pool_dir: &Path) -> Result<(), Error> {
+        let path = registry_path(pool_dir);
+        let io_error = |err: std::io::Error| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to write hook registry")
+                .with_path(&path)
+                .with_source(err)
+        };
+        fs::create_dir_all(hooks_dir(pool_dir)).map_err(io_error)?;
+        let body = serde_json::to_string_pretty(self).map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to encode hook registry")
+                .with_source(err)
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, format!("{body}\n")).map_err(io_error)?;
+        fs::rename(&tmp, &path).map_err(io_error)
+    }
+
+    /// Register a hook under the next free id (`h1`, `h2`, ...).
+    pub fn add(
+        &mut self,
+        pool: String,
+        action: HookAction,
+        where_exprs: Vec<String>,
+        created_at: String,
+    ) -> &Hook {
+        let next = self
+            .hooks
+            .iter()
+            .filter_map(|hook| hook.id.strip_prefix('h')?.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.hooks.push(Hook {
+            id: format!("h{next}"),
+            pool,
+            action,
+            where_exprs,
+            created_at,
+        });
+        self.hooks.last().expect("just pushed")
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Hook> {
+        let index = self.hooks.iter().position(|hook| hook.id == id)?;
+        Some(self.hooks.remove(index))
+    }
+
+    pub fn hooks(&self) -> &[Hook] {
+        &self.hooks
+    }
+}
+
+fn hooks_dir(pool_dir: &Path) -> PathBuf {
+    pool_dir.join(".hooks")
+}
+
+fn registry_path(pool_dir: &Path) -> PathBuf {
+    hooks_dir(pool_dir).join("hooks.json")
+}
+
+/// Last seq hook `id` has handled (delivered or filtered out).
+pub fn hook_cursor(pool_dir: &Path, id: &str) -> ForwardCursor {
+    ForwardCursor::at(hooks_dir(pool_dir).join(format!("{id}.cursor.json")))
+}
+
+/// Run one hook action for one message; `Err` when the command exits non-zero or the POST fails.
+pub fn deliver(action: &HookAction, message: &Value) -> Result<(), Error> {
+    match action {
+        HookAction::Exec(command) => run_command(command, message),
+        HookAction::Post(url) => {
+            Forwarder::new(url, Vec::new(), 0, Duration::ZERO)?.deliver(message)
+        }
+    }
+}
+
+fn run_command(command: &str, message: &Value) -> Result<(), Error> {
+    let json = message.to_string();
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(render_command(command, &json))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to spawn hook command")
+                .with_hint("Check that `sh` is available on PATH.")
+                .with_source(err)
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that never reads stdin closes the pipe; that is not a hook failure.
+        let _ = stdin.write_all(json.as_bytes());
+        let _ = stdin.write_all(b"\n");
+    }
+    let status = child.wait().map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed waiting for hook command")
+            .with_source(err)
+    })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::Io).with_message(format!("hook command failed ({status})")))
+    }
+}
+
+/// Per-hook retry schedule kept by `hookd` (not persisted; a restart retries right away).
+#[derive(Clone, Debug, Default)]
+pub struct HookBackoff {
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl HookBackoff {
+    pub fn ready(&self, now: Instant) -> bool {
+        self.retry_at.is_none_or(|retry_at| now >= retry_at)
+    }
+
+    /// Record a failure and return how long to wait before the next attempt.
+    pub fn failed(&mut self, now: Instant) -> Duration {
+        let delay = INITIAL_BACKOFF
+            .saturating_mul(1 << self.failures.min(16))
+            .min(MAX_BACKOFF);
+        self.failures = self.failures.saturating_add(1);
+        self.retry_at = Some(now + delay);
+        delay
+    }
+
+    pub fn succeeded(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HookAction, HookBackoff, HookRegistry};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn registry_round_trips_and_never_reuses_a_live_id() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut registry = HookRegistry::load(dir.path()).expect("empty");
+        let created = "2026-01-01T00:00:00Z".to_string();
+        let first = registry
+            .add(
+                "events".to_string(),
+                HookAction::Exec("cat".to_string()),
+                vec![".data.level == \"error\"".to_string()],
+                created.clone(),
+            )
+            .id
+            .clone();
+        let second = registry
+            .add(
+                "events".to_string(),
+                HookAction::Post("http://127.0.0.1:1/hook".to_string()),
+                Vec::new(),
+                created.clone(),
+            )
+            .id
+            .clone();
+        assert_eq!((first.as_str(), second.as_str()), ("h1", "h2"));
+        registry.save(dir.path()).expect("save");
+
+        let mut loaded = HookRegistry::load(dir.path()).expect("load");
+        assert_eq!(loaded, registry);
+        assert_eq!(loaded.remove("h1").expect("removed").action.kind(), "exec");
+        assert!(loaded.remove("h1").is_none());
+        let third = loaded
+            .add(
+                "events".to_string(),
+                HookAction::Exec("true".to_string()),
+                Vec::new(),
+                created,
+            )
+            .id
+            .clone();
+        assert_eq!(third, "h3");
+    }
+
+    #[test]
+    fn backoff_doubles_to_a_cap_and_resets_on_success() {
+        let now = Instant::now();
+        let mut backoff = HookBackoff::default();
+        assert!(backoff.ready(now));
+        assert_eq!(backoff.failed(now), Duration::from_secs(1));
+        assert!(!backoff.ready(now));
+        assert!(backoff.ready(now + Duration::from_secs(1)));
+        assert_eq!(backoff.failed(now), Duration::from_secs(2));
+        for _ in 0..20 {
+            backoff.failed(now);
+        }
+        assert_eq!(backoff.failed(now), Duration::from_secs(300));
+        backoff.succeeded();
+        assert_eq!(backoff.failures(), 0);
+        assert!(backoff.ready(now));
+    }
+}
@@ -0,0 +1,273 @@
+//! Purpose: Message catalog for human-facing CLI text with locale selection.
+//! Exports: `Locale`, `Msg`, `resolve_locale`, `set_locale`, `tr`.
+//! Role: Keep translatable banners, summaries, and labels out of command logic.
+//! Invariants: Only TTY/human text goes through the catalog; JSON output never does.
+//! Invariants: Every `Msg` has an English entry; other locales fall back to English.
+//! Invariants: Placeholders are `{name}` and are substituted verbatim.
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+impl Locale {
+    /// Parse `es`, `es_MX.UTF-8`, `es-ES`, ...; `C`/`POSIX` and unknown languages yield `None`.
+    fn parse(value: &str) -> Option<Self> {
+        let language = value
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Msg {
+    ErrorLabel,
+    HintLabel,
+    CausedByLabel,
+    NoPoolsFound,
+    NoPoolsFoundIn,
+    CreateOne,
+    AllPoolsHealthy,
+    PoolsUnhealthy,
+    NoIssues,
+    IssuesRunDoctor,
+    CreatedPool,
+    CreatedPools,
+    DeletedPool,
+    PoolNotFoundNothingToDelete,
+    FailedToDeletePool,
+    DeletedPools,
+    DeletedSomePools,
+    TrashedPool,
+    DeleteDryRun,
+    TrashDryRun,
+    RestoredPool,
+    SnapshotPool,
+    FrozenPool,
+    UnfrozenPool,
+    SealedPool,
+    VerifyIntact,
+    VerifyTampered,
+    ImportedMessages,
+    ImportDryRun,
+    NoActivePoolUsers,
+    PoolDirectoryLabel,
+    ListPoolsLabel,
+    ReasonLabel,
+    FollowTimedOut,
+    GcNothingToCollect,
+    GcConfirm,
+    GcDryRun,
+    GcCollected,
+}
+
+fn english(msg: Msg) -> &'static str {
+    match msg {
+        Msg::ErrorLabel => "error:",
+        Msg::HintLabel => "hint:",
+        Msg::CausedByLabel => "caused by:",
+        Msg::NoPoolsFound => "No pools found.",
+        Msg::NoPoolsFoundIn => "No pools found in {dir}",
+        Msg::CreateOne => "Create one:",
+        Msg::AllPoolsHealthy => "All {total} pools healthy.",
+        Msg::PoolsUnhealthy => "{corrupt} of {total} pools unhealthy.",
+        Msg::NoIssues => "0 issues",
+        Msg::IssuesRunDoctor => "{count} issues (run `pls doctor {label}` for detail)",
+        Msg::CreatedPool => "Created {name} ({size}, {index} index slots)",
+        Msg::CreatedPools => "Created {count} pools ({size} each)",
+        Msg::DeletedPool => "Deleted pool \"{name}\".",
+        Msg::PoolNotFoundNothingToDelete => "Pool \"{name}\" not found. Nothing to delete.",
+        Msg::FailedToDeletePool => "Failed to delete pool \"{name}\".",
+        Msg::DeletedPools => "Deleted {count} pools.",
+        Msg::DeletedSomePools => "Deleted {count} of {total} pools.",
+        Msg::TrashedPool => "Moved pool \"{name}\" to trash (undo: pls pool restore {name}).",
+        Msg::DeleteDryRun => "Would delete {count} pools (dry run).",
+        Msg::TrashDryRun => "Would move {count} pools to trash (dry run).",
+        Msg::RestoredPool => "Restored pool \"{name}\".",
+        Msg::SnapshotPool => "Wrote snapshot of \"{name}\" to {path}.",
+        Msg::FrozenPool => "Froze pool \"{name}\"; appends are refused until unfrozen.",
+        Msg::UnfrozenPool => "Unfroze pool \"{name}\"; appends are accepted.",
+        Msg::SealedPool => "Sealed seqs {seqs} of \"{name}\" into {path}.",
+        Msg::VerifyIntact => {
+            "Pool \"{name}\" matches its seal ({checked} frames checked, {evicted} evicted)."
+        }
+        Msg::VerifyTampered => "Pool \"{name}\" does NOT match its seal.",
+        Msg::ImportedMessages => "Imported {count} messages into \"{name}\" (seqs {seqs}).",
+        Msg::ImportDryRun => {
+            "Would import {count} messages (exported seqs {seqs}) into \"{name}\" (dry run)."
+        }
+        Msg::NoActivePoolUsers => "No active readers or writers on \"{name}\".",
+        Msg::PoolDirectoryLabel => "Pool directory:",
+        Msg::ListPoolsLabel => "List pools:    ",
+        Msg::ReasonLabel => "Reason:        ",
+        Msg::FollowTimedOut => "No messages received (timed out after {timeout}).",
+        Msg::GcNothingToCollect => "No empty or idle pools to collect.",
+        Msg::GcConfirm => "Delete {count} pools? [y/N] ",
+        Msg::GcDryRun => "Would delete {count} pools (dry run).",
+        Msg::GcCollected => "Deleted {count} of {total} pools.",
+    }
+}
+
+fn spanish(msg: Msg) -> Option<&'static str> {
+    Some(match msg {
+        Msg::ErrorLabel => "error:",
+        Msg::HintLabel => "sugerencia:",
+        Msg::CausedByLabel => "causado por:",
+        Msg::NoPoolsFound => "No se encontraron pools.",
+        Msg::NoPoolsFoundIn => "No se encontraron pools en {dir}",
+        Msg::CreateOne => "Crea uno:",
+        Msg::AllPoolsHealthy => "Los {total} pools están sanos.",
+        Msg::PoolsUnhealthy => "{corrupt} de {total} pools con problemas.",
+        Msg::NoIssues => "0 problemas",
+        Msg::IssuesRunDoctor => {
+            "{count} problemas (ejecuta `pls doctor {label}` para ver detalles)"
+        }
+        Msg::CreatedPool => "Creado {name} ({size}, {index} entradas de índice)",
+        Msg::CreatedPools => "Creados {count} pools ({size} cada uno)",
+        Msg::DeletedPool => "Pool \"{name}\" eliminado.",
+        Msg::PoolNotFoundNothingToDelete => "No se encontró el pool \"{name}\". Nada que eliminar.",
+        Msg::FailedToDeletePool => "No se pudo eliminar el pool \"{name}\".",
+        Msg::DeletedPools => "Eliminados {count} pools.",
+        Msg::DeletedSomePools => "Eliminados {count} de {total} pools.",
+        Msg::TrashedPool => {
+            "Pool \"{name}\" movido a la papelera (deshacer: pls pool restore {name})."
+        }
+        Msg::DeleteDryRun => "Se eliminarían {count} pools (simulación).",
+        Msg::TrashDryRun => "Se moverían {count} pools a la papelera (simulación).",
+        Msg::RestoredPool => "Pool \"{name}\" restaurado.",
+        Msg::SnapshotPool => "Instantánea de \"{name}\" escrita en {path}.",
+        Msg::FrozenPool => "Pool \"{name}\" congelado; se rechazan escrituras hasta descongelarlo.",
+        Msg::UnfrozenPool => "Pool \"{name}\" descongelado; se aceptan escrituras.",
+        Msg::SealedPool => "Seqs {seqs} de \"{name}\" sellados en {path}.",
+        Msg::VerifyIntact => {
+            "El pool \"{name}\" coincide con su sello ({checked} frames comprobados, {evicted} desalojados)."
+        }
+        Msg::VerifyTampered => "El pool \"{name}\" NO coincide con su sello.",
+        Msg::ImportedMessages => "Importados {count} mensajes en \"{name}\" (seqs {seqs}).",
+        Msg::ImportDryRun => {
+            "Se importarían {count} mensajes (seqs exportados {seqs}) en \"{name}\" (simulación)."
+        }
+        Msg::NoActivePoolUsers => "No hay lectores ni escritores activos en \"{name}\".",
+        Msg::PoolDirectoryLabel => "Directorio:    ",
+        Msg::ListPoolsLabel => "Listar pools:  ",
+        Msg::ReasonLabel => "Motivo:        ",
+        Msg::FollowTimedOut => "No se recibieron mensajes (tiempo agotado tras {timeout}).",
+        Msg::GcNothingToCollect => "No hay pools vacíos ni inactivos que eliminar.",
+        Msg::GcConfirm => "¿Eliminar {count} pools? [s/N] ",
+        Msg::GcDryRun => "Se eliminarían {count} pools (simulación).",
+        Msg::GcCollected => "Eliminados {count} de {total} pools.",
+    })
+}
+
+fn template(locale: Locale, msg: Msg) -> &'static str {
+    match locale {
+        Locale::En => english(msg),
+        Locale::Es => spanish(msg).unwrap_or_else(|| english(msg)),
+    }
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+pub(crate) fn set_locale(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+/// Pick the locale from `--lang`, then `LC_ALL`, `LC_MESSAGES`, `LANG` (first set wins).
+pub(crate) fn resolve_locale(flag: Option<&str>, env: impl Fn(&str) -> Option<String>) -> Locale {
+    if let Some(flag) = flag {
+        return Locale::parse(flag).unwrap_or_default();
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|key| env(key).filter(|value| !value.is_empty()))
+        .map(|value| Locale::parse(&value).unwrap_or_default())
+        .next()
+        .unwrap_or_default()
+}
+
+/// Render `msg` in the active locale, substituting `{name}` placeholders from `args`.
+pub(crate) fn tr(msg: Msg, args: &[(&str, &dyn Display)]) -> String {
+    render(template(locale(), msg), args)
+}
+
+fn render(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = template.to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Locale, Msg, english, render, resolve_locale, spanish, template};
+
+    #[test]
+    fn resolve_locale_prefers_flag_then_env_order() {
+        let env = |key: &str| match key {
+            "LC_MESSAGES" => Some("es_MX.UTF-8".to_string()),
+            "LANG" => Some("en_US.UTF-8".to_string()),
+            _ => None,
+        };
+        assert_eq!(resolve_locale(None, env), Locale::Es);
+        assert_eq!(resolve_locale(Some("en"), env), Locale::En);
+        assert_eq!(resolve_locale(Some("fr"), env), Locale::En);
+        assert_eq!(resolve_locale(None, |_| Some("C".to_string())), Locale::En);
+        assert_eq!(resolve_locale(None, |_| None), Locale::En);
+    }
+
+    #[test]
+    fn templates_render_placeholders_in_each_locale() {
+        let text = render(
+            template(Locale::Es, Msg::PoolsUnhealthy),
+            &[("corrupt", &1), ("total", &3)],
+        );
+        assert_eq!(text, "1 de 3 pools con problemas.");
+        let text = render(template(Locale::En, Msg::AllPoolsHealthy), &[("total", &2)]);
+        assert_eq!(text, "All 2 pools healthy.");
+    }
+
+    #[test]
+    fn spanish_catalog_keeps_english_placeholders() {
+        for msg in [
+            Msg::NoPoolsFoundIn,
+            Msg::IssuesRunDoctor,
+            Msg::CreatedPool,
+            Msg::DeletedSomePools,
+            Msg::SnapshotPool,
+            Msg::VerifyIntact,
+            Msg::FollowTimedOut,
+        ] {
+            let placeholders = |text: &str| {
+                let mut names = text
+                    .split('{')
+                    .skip(1)
+                    .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+                    .collect::<Vec<_>>();
+                names.sort();
+                names
+            };
+            let translated = spanish(msg).expect("spanish entry");
+            assert_eq!(
+                placeholders(english(msg)),
+                placeholders(translated),
+                "{msg:?}"
+            );
+        }
+    }
+}
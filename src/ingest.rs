@@ -1,9 +1,11 @@
 //! Purpose: Parse stdin streams into JSON values for `feed` with explicit, testable modes.
-//! Exports: `IngestMode`, `ErrorPolicy`, `IngestConfig`, `IngestOutcome`, `IngestFailure`, `ingest`.
+//! Exports: `IngestMode`, `CsvHeader`, `ErrorPolicy`, `IngestConfig`, `IngestOutcome`, `IngestFailure`, `ingest`.
 //! Role: Input ingestion engine used by the CLI; isolates streaming heuristics from main.
 //! Invariants: Auto detection is deterministic, bounded, and documented by config limits.
 //! Invariants: Skip mode only continues at well-defined record boundaries.
 //! Invariants: No unbounded buffering; per-record buffering is capped.
+//! Invariants: CSV cells become JSON numbers/booleans only when unquoted and spelled exactly
+//! as JSON would spell them, so zip codes like `007` stay strings.
 use std::io::{self, BufRead, BufReader, Read};
 
 use bstr::ByteSlice;
@@ -24,6 +26,17 @@ pub enum IngestMode {
     Seq,
     Jq,
     Event,
+    Csv(CsvHeader),
+}
+
+/// Whether the first CSV row names the columns.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CsvHeader {
+    /// Treat the first row as a header when every cell is distinct, non-empty text.
+    Auto,
+    Present,
+    /// Columns are named `col1`, `col2`, ...
+    Absent,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -146,6 +159,13 @@ where
         IngestMode::Event => {
             ingest_event_stream(reader, config, &mut accept_value, &mut handle_failure)
         }
+        IngestMode::Csv(header) => ingest_csv(
+            reader,
+            config,
+            header,
+            &mut accept_value,
+            &mut handle_failure,
+        ),
     }?;
 
     outcome.ok = ok;
@@ -698,6 +718,230 @@ where
     Ok(())
 }
 
+fn ingest_csv<R, F, N>(
+    reader: R,
+    config: IngestConfig,
+    header: CsvHeader,
+    on_value: &mut F,
+    on_failure: &mut N,
+) -> Result<(), Error>
+where
+    R: Read,
+    F: FnMut(Value, u64) -> Result<(), Error>,
+    N: FnMut(u64, IngestMode, Option<u64>, &str, &str, Option<String>) -> Result<(), Error>,
+{
+    let mode = IngestMode::Csv(header);
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+    let mut record = String::new();
+    let mut record_line = 0u64;
+    let mut line_no = 0u64;
+    let mut index = 0u64;
+    let mut columns: Option<Vec<String>> = None;
+    loop {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|err| io_error(err, "failed to read stdin"))?;
+        if read == 0 && record.is_empty() {
+            break;
+        }
+        if read > 0 {
+            line_no += 1;
+            if record.is_empty() {
+                record_line = line_no;
+            }
+            let text = if line_no == 1 {
+                line.trim_start_matches('\u{feff}')
+            } else {
+                line.as_str()
+            };
+            record.push_str(text);
+            // An odd quote count means a quoted cell continues onto the next line.
+            let open = record.bytes().filter(|byte| *byte == b'"').count() % 2 == 1;
+            if open && record.len() <= config.max_record_bytes {
+                continue;
+            }
+        }
+        let text = record.trim_end_matches(['\n', '\r']).to_string();
+        record.clear();
+        if text.trim().is_empty() {
+            continue;
+        }
+        index += 1;
+        if text.len() > config.max_record_bytes {
+            on_failure(
+                index,
+                mode,
+                Some(record_line),
+                "record exceeds size limit",
+                "Oversize",
+                Some(truncate_snippet(&text, config.max_snippet_bytes)),
+            )?;
+            continue;
+        }
+        let cells = match split_csv_record(&text) {
+            Ok(cells) => cells,
+            Err(message) => {
+                on_failure(
+                    index,
+                    mode,
+                    Some(record_line),
+                    message,
+                    "Parse",
+                    Some(truncate_snippet(&text, config.max_snippet_bytes)),
+                )?;
+                continue;
+            }
+        };
+        if columns.is_none() {
+            let is_header = match header {
+                CsvHeader::Present => true,
+                CsvHeader::Absent => false,
+                CsvHeader::Auto => looks_like_csv_header(&cells),
+            };
+            if is_header {
+                columns = Some(csv_column_names(&cells));
+                index -= 1;
+                continue;
+            }
+            columns = Some((1..=cells.len()).map(|n| format!("col{n}")).collect());
+        }
+        let names = columns.as_ref().expect("columns set above");
+        if cells.len() != names.len() {
+            let message = format!(
+                "csv row has {} fields; header has {}",
+                cells.len(),
+                names.len()
+            );
+            on_failure(
+                index,
+                mode,
+                Some(record_line),
+                &message,
+                "Parse",
+                Some(truncate_snippet(&text, config.max_snippet_bytes)),
+            )?;
+            continue;
+        }
+        let object = names
+            .iter()
+            .cloned()
+            .zip(cells.into_iter().map(sniff_csv_cell))
+            .collect::<serde_json::Map<_, _>>();
+        apply_value(
+            Value::Object(object),
+            index,
+            mode,
+            Some(record_line),
+            config.errors,
+            on_value,
+            on_failure,
+        )?;
+    }
+    Ok(())
+}
+
+/// One CSV cell; quoted cells are never type-sniffed.
+#[derive(Debug, PartialEq)]
+struct CsvCell {
+    text: String,
+    quoted: bool,
+}
+
+/// Split one complete RFC 4180 record. Stray quotes inside unquoted cells are kept literally.
+fn split_csv_record(record: &str) -> Result<Vec<CsvCell>, &'static str> {
+    let mut cells = Vec::new();
+    let mut chars = record.chars().peekable();
+    loop {
+        let mut text = String::new();
+        let quoted = chars.peek() == Some(&'"');
+        if quoted {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        text.push('"');
+                    }
+                    Some('"') => break,
+                    Some(ch) => text.push(ch),
+                    None => return Err("unterminated quoted csv field"),
+                }
+            }
+            match chars.next() {
+                None => {
+                    cells.push(CsvCell { text, quoted });
+                    return Ok(cells);
+                }
+                Some(',') => {}
+                Some(_) => return Err("unexpected character after closing quote in csv field"),
+            }
+        } else {
+            loop {
+                match chars.next() {
+                    Some(',') => break,
+                    Some(ch) => text.push(ch),
+                    None => {
+                        cells.push(CsvCell { text, quoted });
+                        return Ok(cells);
+                    }
+                }
+            }
+        }
+        cells.push(CsvCell { text, quoted });
+    }
+}
+
+fn looks_like_csv_header(cells: &[CsvCell]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    cells.iter().all(|cell| {
+        !cell.text.trim().is_empty()
+            && seen.insert(cell.text.as_str())
+            && matches!(
+                sniff_csv_cell(CsvCell {
+                    text: cell.text.clone(),
+                    quoted: false,
+                }),
+                Value::String(_)
+            )
+    })
+}
+
+/// Header cells as object keys; blank names become `colN` and repeats get a `_N` suffix.
+fn csv_column_names(cells: &[CsvCell]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(cells.len());
+    for (position, cell) in cells.iter().enumerate() {
+        let base = match cell.text.trim() {
+            "" => format!("col{}", position + 1),
+            name => name.to_string(),
+        };
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while names.contains(&name) {
+            name = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        names.push(name);
+    }
+    names
+}
+
+fn sniff_csv_cell(cell: CsvCell) -> Value {
+    if cell.quoted {
+        return Value::String(cell.text);
+    }
+    match cell.text.as_str() {
+        "" => Value::Null,
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        text if text.trim() == text => serde_json::from_str::<serde_json::Number>(text)
+            .map(Value::Number)
+            .unwrap_or(Value::String(cell.text)),
+        _ => Value::String(cell.text),
+    }
+}
+
 fn apply_value<F, N>(
     value: Value,
     index: u64,
@@ -808,7 +1052,9 @@ impl<R: Read> Read for PrefixReader<R> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ErrorPolicy, IngestConfig, IngestFailure, IngestMode, ingest, truncate_snippet};
+    use super::{
+        CsvHeader, ErrorPolicy, IngestConfig, IngestFailure, IngestMode, ingest, truncate_snippet,
+    };
     use plasmite::api::{Error, ErrorKind};
 
     fn config(mode: IngestMode, errors: ErrorPolicy) -> IngestConfig {
@@ -993,6 +1239,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn csv_converts_rows_with_header_and_type_sniffing() {
+        let input = b"\xef\xbb\xbfid,name,zip,active,score\n1,\"Smith, J\",007,true,1.5\n2,\"multi\nline\",,false,\"3\"\n";
+        let mut values = Vec::new();
+        let outcome = ingest(
+            &input[..],
+            config(IngestMode::Csv(CsvHeader::Auto), ErrorPolicy::Stop),
+            |value| {
+                values.push(value);
+                Ok(())
+            },
+            |_| {},
+        )
+        .expect("ingest");
+
+        assert_eq!(outcome.ok, 2);
+        assert_eq!(
+            values[0],
+            serde_json::json!({"id": 1, "name": "Smith, J", "zip": "007", "active": true, "score": 1.5})
+        );
+        assert_eq!(
+            values[1],
+            serde_json::json!({"id": 2, "name": "multi\nline", "zip": null, "active": false, "score": "3"})
+        );
+    }
+
+    #[test]
+    fn csv_auto_header_falls_back_to_generated_names() {
+        let input = b"1,a\n2,b,extra\n3,c\n";
+        let mut values = Vec::new();
+        let mut failures = Vec::new();
+        let outcome = ingest(
+            &input[..],
+            config(IngestMode::Csv(CsvHeader::Auto), ErrorPolicy::Skip),
+            |value| {
+                values.push(value);
+                Ok(())
+            },
+            |failure: IngestFailure| failures.push(failure),
+        )
+        .expect("ingest");
+
+        assert_eq!(outcome.ok, 2);
+        assert_eq!(values[0], serde_json::json!({"col1": 1, "col2": "a"}));
+        assert_eq!(failures[0].line, Some(2));
+        assert!(failures[0].message.contains("3 fields"));
+    }
+
     #[test]
     fn jsonl_skip_continues_on_parse_error() {
         let input = b"{\"a\":1}\nnot-json\n{\"b\":2}\n";
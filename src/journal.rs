@@ -0,0 +1,273 @@
+//! Purpose: Decode systemd journal export streams (`journalctl -o export`) into messages.
+//! Exports: `JournalEntry`, `JournalMessage`, `read_entry`, `entry_message`, `journalctl_args`.
+//! Role: Pure parsing helpers for `plasmite journal`; process spawning stays in dispatch.
+//! Invariants: Every field is preserved under `.data.fields`; nothing is dropped.
+//! Invariants: Binary fields (length-prefixed) decode lossily to UTF-8 text; a length over the
+//! target pool's largest payload is `Corrupt` and is rejected before anything is allocated.
+//! Invariants: Entry timestamps come from `__REALTIME_TIMESTAMP` when present.
+use std::io::{BufRead, Read};
+
+use plasmite::api::{Error, ErrorKind};
+use serde_json::{Map, Value, json};
+
+const PRIORITY_NAMES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// One journal entry, in export order.
+pub type JournalEntry = Vec<(String, Vec<u8>)>;
+
+/// Decoded entry ready for append.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalMessage {
+    pub data: Value,
+    pub tags: Vec<String>,
+    pub timestamp_ns: Option<u64>,
+}
+
+/// Build the `journalctl` argv for the requested selection.
+pub fn journalctl_args(follow: bool, units: &[String], since: Option<&str>) -> Vec<String> {
+    let mut args = vec!["--output=export".to_string(), "--no-pager".to_string()];
+    if follow {
+        args.push("--follow".to_string());
+    }
+    for unit in units {
+        args.push(format!("--unit={unit}"));
+    }
+    if let Some(since) = since {
+        args.push(format!("--since={since}"));
+    }
+    args
+}
+
+/// Read the next entry; returns `Ok(None)` at end of stream. Binary fields longer than
+/// `max_field_len` (the pool's `Pool::max_payload_len`) are rejected as corrupt.
+pub fn read_entry<R: BufRead>(
+    reader: &mut R,
+    max_field_len: usize,
+) -> Result<Option<JournalEntry>, Error> {
+    let mut entry = Vec::new();
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line).map_err(read_error)?;
+        if read == 0 {
+            return Ok((!entry.is_empty()).then_some(entry));
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        if line.is_empty() {
+            if entry.is_empty() {
+                continue;
+            }
+            return Ok(Some(entry));
+        }
+        if let Some(eq) = line.iter().position(|b| *b == b'=') {
+            let name = String::from_utf8_lossy(&line[..eq]).into_owned();
+            entry.push((name, line[eq + 1..].to_vec()));
+            continue;
+        }
+        // Binary-safe field: NAME\n<u64 LE length><bytes>\n
+        let name = String::from_utf8_lossy(&line).into_owned();
+        let mut len_bytes = [0u8; 8];
+        reader.read_exact(&mut len_bytes).map_err(read_error)?;
+        let len = u64::from_le_bytes(len_bytes);
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= max_field_len)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Corrupt)
+                    .with_message(format!(
+                        "journal binary field {name} claims {len} bytes, over the {max_field_len}-byte pool limit"
+                    ))
+                    .with_hint("The export stream is truncated or not `journalctl -o export` output.")
+            })?;
+        let mut value = vec![0u8; len];
+        reader.read_exact(&mut value).map_err(read_error)?;
+        let mut newline = [0u8; 1];
+        reader.read_exact(&mut newline).map_err(read_error)?;
+        if newline[0] != b'\n' {
+            return Err(Error::new(ErrorKind::Corrupt).with_message(format!(
+                "journal binary field {name} is not newline-terminated"
+            )));
+        }
+        entry.push((name, value));
+    }
+}
+
+fn read_error(err: std::io::Error) -> Error {
+    Error::new(ErrorKind::Io)
+        .with_message("failed to read journal export stream")
+        .with_source(err)
+}
+
+/// Map an entry into the JSON body, unit/priority tags, and realtime timestamp.
+pub fn entry_message(entry: &JournalEntry, extra_tags: &[String]) -> JournalMessage {
+    let mut fields = Map::new();
+    for (name, value) in entry {
+        let value = json!(String::from_utf8_lossy(value));
+        match fields.get_mut(name) {
+            // Journal fields may repeat; keep every value in order.
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                fields.insert(name.clone(), value);
+            }
+        }
+    }
+    let text = |name: &str| fields.get(name).and_then(Value::as_str).map(str::to_string);
+
+    let priority = text("PRIORITY")
+        .and_then(|value| value.parse::<usize>().ok())
+        .and_then(|value| PRIORITY_NAMES.get(value).copied());
+    let unit = text("_SYSTEMD_UNIT").or_else(|| text("UNIT"));
+    let timestamp_ns = text("__REALTIME_TIMESTAMP")
+        .and_then(|value| value.parse::<u64>().ok())
+        .and_then(|micros| micros.checked_mul(1_000));
+
+    let mut tags = extra_tags.to_vec();
+    if let Some(unit) = &unit {
+        tags.push(format!("unit:{unit}"));
+    }
+    if let Some(priority) = priority {
+        tags.push(format!("priority:{priority}"));
+    }
+
+    let host = text("_HOSTNAME");
+    let app = text("SYSLOG_IDENTIFIER").or_else(|| text("_COMM"));
+    let pid = text("_PID");
+    let msg = text("MESSAGE");
+    let cursor = text("__CURSOR");
+    let data = json!({
+        "unit": unit,
+        "priority": priority,
+        "host": host,
+        "app": app,
+        "pid": pid,
+        "msg": msg,
+        "cursor": cursor,
+        "fields": Value::Object(fields),
+    });
+    JournalMessage {
+        data,
+        tags,
+        timestamp_ns,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_message, journalctl_args, read_entry};
+    use plasmite::api::ErrorKind;
+    use serde_json::{Value, json};
+    use std::io::Cursor;
+
+    const LIMIT: usize = 1024 * 1024;
+
+    fn export_fixture() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"__CURSOR=s=abc;i=1\n");
+        bytes.extend_from_slice(b"__REALTIME_TIMESTAMP=1700000000000001\n");
+        bytes.extend_from_slice(b"_HOSTNAME=lab1\n");
+        bytes.extend_from_slice(b"_SYSTEMD_UNIT=myservice.service\n");
+        bytes.extend_from_slice(b"SYSLOG_IDENTIFIER=myservice\n");
+        bytes.extend_from_slice(b"_PID=42\n");
+        bytes.extend_from_slice(b"PRIORITY=3\n");
+        bytes.extend_from_slice(b"MESSAGE\n");
+        let message = b"line one\nline two";
+        bytes.extend_from_slice(&(message.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(message);
+        bytes.extend_from_slice(b"\n\n");
+        bytes.extend_from_slice(b"MESSAGE=second\n");
+        bytes
+    }
+
+    #[test]
+    fn read_entry_splits_entries_and_decodes_binary_fields() {
+        let mut reader = Cursor::new(export_fixture());
+        let first = read_entry(&mut reader, LIMIT)
+            .expect("read")
+            .expect("entry");
+        assert_eq!(first.len(), 8);
+        assert_eq!(first[7].0, "MESSAGE");
+        assert_eq!(first[7].1, b"line one\nline two".to_vec());
+
+        let second = read_entry(&mut reader, LIMIT)
+            .expect("read")
+            .expect("entry");
+        assert_eq!(second, vec![("MESSAGE".to_string(), b"second".to_vec())]);
+        assert!(read_entry(&mut reader, LIMIT).expect("read").is_none());
+    }
+
+    #[test]
+    fn read_entry_rejects_binary_lengths_over_the_pool_limit() {
+        let mut bytes = b"MESSAGE\n".to_vec();
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        bytes.extend_from_slice(b"short\n\n");
+        let err = read_entry(&mut Cursor::new(bytes), LIMIT).expect_err("oversized");
+        assert_eq!(err.kind(), ErrorKind::Corrupt);
+
+        let mut bytes = b"MESSAGE\n".to_vec();
+        bytes.extend_from_slice(&6u64.to_le_bytes());
+        bytes.extend_from_slice(b"sixsix\n\n");
+        let err = read_entry(&mut Cursor::new(bytes), 5).expect_err("over the limit");
+        assert!(err.message().unwrap_or_default().contains("5-byte"));
+    }
+
+    #[test]
+    fn entry_message_maps_fields_and_tags() {
+        let mut reader = Cursor::new(export_fixture());
+        let entry = read_entry(&mut reader, LIMIT)
+            .expect("read")
+            .expect("entry");
+        let message = entry_message(&entry, &["prod".to_string()]);
+        assert_eq!(message.timestamp_ns, Some(1_700_000_000_000_001_000));
+        assert_eq!(
+            message.tags,
+            vec![
+                "prod".to_string(),
+                "unit:myservice.service".to_string(),
+                "priority:err".to_string()
+            ]
+        );
+        assert_eq!(message.data["unit"], "myservice.service");
+        assert_eq!(message.data["priority"], "err");
+        assert_eq!(message.data["host"], "lab1");
+        assert_eq!(message.data["app"], "myservice");
+        assert_eq!(message.data["pid"], "42");
+        assert_eq!(message.data["msg"], "line one\nline two");
+        assert_eq!(message.data["fields"]["PRIORITY"], "3");
+    }
+
+    #[test]
+    fn entry_message_keeps_repeated_fields() {
+        let entry = vec![
+            ("TAG".to_string(), b"a".to_vec()),
+            ("TAG".to_string(), b"b".to_vec()),
+        ];
+        let message = entry_message(&entry, &[]);
+        assert_eq!(message.data["fields"]["TAG"], json!(["a", "b"]));
+        assert_eq!(message.data["unit"], Value::Null);
+        assert!(message.tags.is_empty());
+        assert_eq!(message.timestamp_ns, None);
+    }
+
+    #[test]
+    fn journalctl_args_include_selection() {
+        let args = journalctl_args(true, &["a.service".to_string()], Some("-5m"));
+        assert_eq!(
+            args,
+            vec![
+                "--output=export",
+                "--no-pager",
+                "--follow",
+                "--unit=a.service",
+                "--since=-5m"
+            ]
+        );
+    }
+}
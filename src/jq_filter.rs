@@ -1,8 +1,20 @@
-//! Purpose: Compile and evaluate jq-style expressions against JSON values.
-//! Exports: `JqFilter`, `compile_filters`, `matches_all`.
-//! Role: Adapter around `jaq-core` for boolean filtering in the CLI.
+//! Purpose: Compile and evaluate jq-style (or JSONPath) expressions against JSON values.
+//! Exports: `JqFilter`, `compile_filters`, `compile_jsonpath_filters`, `matches_all`,
+//! `required_strings`, `field_equalities`.
+//! Role: Adapter around `jaq-core` for boolean filtering, key extraction, and `--jq` output
+//! programs in the CLI.
 //! Invariants: Parse/compile failures are usage errors; runtime eval errors count as "no match".
 //! Invariants: Each filter must yield only booleans (otherwise: usage error).
+//! Invariants: Programs (`JqFilter::outputs`) may yield any number of values; a runtime error
+//! ends that input's outputs. Missing fields are errors here, as for filters.
+//! Invariants: Key extraction (`JqFilter::key`) takes the first output; null or errors mean no key.
+//! Invariants: JSONPath predicates (feature `jsonpath`) match when the path selects any node
+//! other than a lone `false`/`null`; without the feature they are usage errors.
+//! Invariants: `required_strings` is conservative: it only reports literals from expressions
+//! that are a plain `and` of `PATH == "literal"` comparisons, so a bloom miss on any of them
+//! proves the predicate false.
+//! Invariants: `field_equalities` is conservative the same way: it reports `(path, scalar)`
+//! pairs only when the whole expression is an `and` of `.field.path == scalar` comparisons.
 
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
@@ -18,7 +30,14 @@ use plasmite::api::{Error, ErrorKind};
 #[derive(Clone)]
 pub struct JqFilter {
     expr: String,
-    filter: jaq_core::Filter<Native<JaqValue>>,
+    program: Program,
+}
+
+#[derive(Clone)]
+enum Program {
+    Jq(jaq_core::Filter<Native<JaqValue>>),
+    #[cfg(feature = "jsonpath")]
+    JsonPath(serde_json_path::JsonPath),
 }
 
 impl fmt::Debug for JqFilter {
@@ -31,6 +50,15 @@ impl fmt::Debug for JqFilter {
 
 impl JqFilter {
     pub fn compile(expr: &str) -> Result<Self, Error> {
+        Self::compile_with_example(expr, "--where '.data.kind == \"ping\"'")
+    }
+
+    /// Compile a value-producing program for `--jq`; only the error hint differs from `compile`.
+    pub fn compile_program(expr: &str) -> Result<Self, Error> {
+        Self::compile_with_example(expr, "--jq '{seq, msg: .data.msg}'")
+    }
+
+    fn compile_with_example(expr: &str, example: &str) -> Result<Self, Error> {
         let arena = Arena::default();
         let loader = Loader::new(std::iter::empty());
 
@@ -40,23 +68,60 @@ impl JqFilter {
         };
         let modules = loader
             .load(&arena, program)
-            .map_err(|errs| filter_compile_error(expr, errs))?;
+            .map_err(|errs| filter_compile_error(expr, example, errs))?;
 
         let filter = Compiler::default()
             .with_funs(jaq_std::base_funs::<JaqValue>().chain(custom_funs()))
             .compile(modules)
-            .map_err(|errs| filter_compile_error(expr, errs))?;
+            .map_err(|errs| filter_compile_error(expr, example, errs))?;
+
+        Ok(Self {
+            expr: expr.to_string(),
+            program: Program::Jq(filter),
+        })
+    }
 
+    /// Compile a JSONPath (RFC 9535) predicate such as `$.data.items[?(@.price > 10)]`.
+    #[cfg(feature = "jsonpath")]
+    pub fn compile_jsonpath(expr: &str) -> Result<Self, Error> {
+        let path = serde_json_path::JsonPath::parse(expr).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message("invalid JSONPath expression")
+                .with_hint(format!(
+                    "Failed to parse `{expr}`: {err}\nExample: --where-jsonpath '$.data[?(@.kind == \"ping\")]'"
+                ))
+        })?;
         Ok(Self {
             expr: expr.to_string(),
-            filter,
+            program: Program::JsonPath(path),
         })
     }
 
+    #[cfg(not(feature = "jsonpath"))]
+    pub fn compile_jsonpath(expr: &str) -> Result<Self, Error> {
+        Err(Error::new(ErrorKind::Usage)
+            .with_message("JSONPath predicates are not available in this build")
+            .with_hint(format!(
+                "Rebuild with `--features jsonpath`, or express `{expr}` as a jq-style --where."
+            )))
+    }
+
     pub fn matches(&self, input: &Value) -> Result<bool, Error> {
+        let filter = match &self.program {
+            Program::Jq(filter) => filter,
+            #[cfg(feature = "jsonpath")]
+            Program::JsonPath(path) => {
+                let nodes = path.query(input).all();
+                return Ok(match nodes.as_slice() {
+                    [] => false,
+                    [only] => !matches!(only, Value::Bool(false) | Value::Null),
+                    _ => true,
+                });
+            }
+        };
         let input = JaqValue::from_json(input);
         let inputs = RcIter::new(core::iter::empty::<Result<JaqValue, String>>());
-        let out = self.filter.run((Ctx::new([], &inputs), input));
+        let out = filter.run((Ctx::new([], &inputs), input));
 
         let mut any_true = false;
         for item in out {
@@ -80,12 +145,106 @@ impl JqFilter {
 
         Ok(any_true)
     }
+
+    /// String values a message must contain for this predicate to match: the literals of an
+    /// expression made only of `PATH == "literal"` comparisons joined by `and`. Anything else
+    /// (including JSONPath predicates) yields none, and the caller scans as usual.
+    pub fn required_strings(&self) -> Vec<String> {
+        if !matches!(self.program, Program::Jq(_)) {
+            return Vec::new();
+        }
+        split_outside_strings(&self.expr, "and", true)
+            .into_iter()
+            .map(string_equality)
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+
+    /// `(path, literal)` pairs that must all hold for this predicate to match: the comparisons
+    /// of an expression made only of `.field.path == scalar` comparisons joined by `and`, with
+    /// string, number, boolean, or null literals. Anything else yields none.
+    pub fn field_equalities(&self) -> Vec<(String, Value)> {
+        if !matches!(self.program, Program::Jq(_)) {
+            return Vec::new();
+        }
+        split_outside_strings(&self.expr, "and", true)
+            .into_iter()
+            .map(field_equality)
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default()
+    }
+
+    /// Every value the program yields for `input`, in order. A runtime error keeps the values
+    /// yielded before it and drops the rest, so one odd message never stops a stream.
+    pub fn outputs(&self, input: &Value) -> Vec<Value> {
+        let filter = match &self.program {
+            Program::Jq(filter) => filter,
+            #[cfg(feature = "jsonpath")]
+            Program::JsonPath(path) => {
+                return path.query(input).all().into_iter().cloned().collect();
+            }
+        };
+        let input = JaqValue::from_json(input);
+        let inputs = RcIter::new(core::iter::empty::<Result<JaqValue, String>>());
+        filter
+            .run((Ctx::new([], &inputs), input))
+            .map_while(Result::ok)
+            .map(|value| value.to_json())
+            .collect()
+    }
+
+    /// First value the expression yields, as a grouping key. Strings are used verbatim;
+    /// other values use their compact JSON text. `null` and runtime errors yield `None`.
+    pub fn key(&self, input: &Value) -> Option<String> {
+        let filter = match &self.program {
+            Program::Jq(filter) => filter,
+            #[cfg(feature = "jsonpath")]
+            Program::JsonPath(path) => {
+                return match path.query(input).first()? {
+                    Value::Null => None,
+                    Value::String(value) => Some(value.clone()),
+                    other => Some(other.to_string()),
+                };
+            }
+        };
+        let input = JaqValue::from_json(input);
+        let inputs = RcIter::new(core::iter::empty::<Result<JaqValue, String>>());
+        let mut out = filter.run((Ctx::new([], &inputs), input));
+        match out.next()? {
+            Ok(JaqValue::Null) | Err(_) => None,
+            Ok(JaqValue::Str(value)) => Some(value),
+            Ok(other) => Some(other.to_string()),
+        }
+    }
 }
 
 pub fn compile_filters(exprs: &[String]) -> Result<Vec<JqFilter>, Error> {
     exprs.iter().map(|expr| JqFilter::compile(expr)).collect()
 }
 
+pub fn compile_jsonpath_filters(exprs: &[String]) -> Result<Vec<JqFilter>, Error> {
+    exprs
+        .iter()
+        .map(|expr| JqFilter::compile_jsonpath(expr))
+        .collect()
+}
+
+/// String values a message must hold for every filter to match (see `JqFilter::required_strings`).
+pub fn required_strings(filters: &[JqFilter]) -> Vec<String> {
+    filters
+        .iter()
+        .flat_map(JqFilter::required_strings)
+        .collect()
+}
+
+/// Field equalities every filter requires (see `JqFilter::field_equalities`).
+pub fn field_equalities(filters: &[JqFilter]) -> Vec<(String, Value)> {
+    filters
+        .iter()
+        .flat_map(JqFilter::field_equalities)
+        .collect()
+}
+
 pub fn matches_all(filters: &[JqFilter], input: &Value) -> Result<bool, Error> {
     for filter in filters.iter() {
         if !filter.matches(input)? {
@@ -95,11 +254,120 @@ pub fn matches_all(filters: &[JqFilter], input: &Value) -> Result<bool, Error> {
     Ok(true)
 }
 
-fn filter_compile_error<E: fmt::Debug>(expr: &str, err: E) -> Error {
+/// Split `expr` at each `token` outside string literals; a `word` token must also have
+/// whitespace on both sides (so `and` never splits `.data.band`).
+fn split_outside_strings<'a>(expr: &'a str, token: &str, word: bool) -> Vec<&'a str> {
+    let bytes = expr.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_string = false;
+    let mut index = 0;
+    while index < bytes.len() {
+        if in_string {
+            match bytes[index] {
+                b'\\' => index += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+        } else if bytes[index] == b'"' {
+            in_string = true;
+        } else if bytes[index..].starts_with(token.as_bytes())
+            && (!word
+                || (index > 0
+                    && bytes[index - 1].is_ascii_whitespace()
+                    && bytes
+                        .get(index + token.len())
+                        .is_some_and(u8::is_ascii_whitespace)))
+        {
+            parts.push(&expr[start..index]);
+            index += token.len();
+            start = index;
+            continue;
+        }
+        index += 1;
+    }
+    parts.push(&expr[start..]);
+    parts
+}
+
+/// The literal of a `PATH == "literal"` (or `"literal" == PATH`) comparison.
+fn string_equality(comparison: &str) -> Option<String> {
+    let [left, right] = split_outside_strings(comparison, "==", false)[..] else {
+        return None;
+    };
+    let (left, right) = (left.trim(), right.trim());
+    let literal = if is_simple_path(left) {
+        right
+    } else if is_simple_path(right) {
+        left
+    } else {
+        return None;
+    };
+    // `\(` starts jq string interpolation, which JSON would misread as an escape error anyway.
+    if !literal.starts_with('"') || literal.contains("\\(") {
+        return None;
+    }
+    serde_json::from_str::<String>(literal).ok()
+}
+
+/// The field path and scalar literal of a `.a.b == literal` (or `literal == .a.b`) comparison.
+fn field_equality(comparison: &str) -> Option<(String, Value)> {
+    let [left, right] = split_outside_strings(comparison, "==", false)[..] else {
+        return None;
+    };
+    let (left, right) = (left.trim(), right.trim());
+    let is_field_path = |text: &str| is_simple_path(text) && !text.contains('[');
+    let (path, literal) = if is_field_path(left) {
+        (left, right)
+    } else if is_field_path(right) {
+        (right, left)
+    } else {
+        return None;
+    };
+    if literal.contains("\\(") {
+        return None;
+    }
+    match serde_json::from_str::<Value>(literal).ok()? {
+        Value::Array(_) | Value::Object(_) => None,
+        value => Some((path.to_string(), value)),
+    }
+}
+
+/// `.a.b[0].c`-style paths: field names and numeric indexes only.
+fn is_simple_path(text: &str) -> bool {
+    let mut rest = text;
+    if rest.is_empty() {
+        return false;
+    }
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            if len == 0 {
+                return false;
+            }
+            rest = &after[len..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let Some((index, tail)) = after.split_once(']') else {
+                return false;
+            };
+            if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+                return false;
+            }
+            rest = tail;
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+fn filter_compile_error<E: fmt::Debug>(expr: &str, example: &str, err: E) -> Error {
     Error::new(ErrorKind::Usage)
         .with_message("invalid filter expression")
         .with_hint(format!(
-            "Failed to parse/compile `{expr}`.\nDetails: {err:?}\nExample: --where '.data.kind == \"ping\"'"
+            "Failed to parse/compile `{expr}`.\nDetails: {err:?}\nExample: {example}"
         ))
 }
 
@@ -134,6 +402,9 @@ fn false_run<'a>(
     box_once(Ok(JaqValue::Bool(false)))
 }
 
+/// Largest integer an f64 holds exactly (2^53).
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
 #[derive(Clone, Debug)]
 pub enum JaqValue {
     Null,
@@ -170,6 +441,26 @@ impl JaqValue {
         }
     }
 
+    /// JSON for a program output; whole numbers print without a fraction, as jq does, and
+    /// non-finite numbers become null.
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Num(n) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+                Value::from(*n as i64)
+            }
+            Self::Num(n) => serde_json::Number::from_f64(*n)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+            Self::Str(s) => Value::String(s.clone()),
+            Self::Arr(a) => Value::Array(a.iter().map(Self::to_json).collect()),
+            Self::Obj(o) => {
+                Value::Object(o.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+            }
+        }
+    }
+
     fn from_json(value: &Value) -> Self {
         match value {
             Value::Null => Self::Null,
@@ -582,7 +873,8 @@ impl jaq_std::ValT for JaqValue {
 
 #[cfg(test)]
 mod tests {
-    use super::{JqFilter, compile_filters, matches_all};
+    use super::{JqFilter, compile_filters, matches_all, required_strings};
+    use serde_json::Value;
     use serde_json::json;
 
     #[test]
@@ -592,6 +884,63 @@ mod tests {
         assert!(matches_all(&preds, &msg).unwrap());
     }
 
+    #[test]
+    fn programs_yield_every_output_as_json() {
+        let msg = json!({"seq": 3, "data": {"items": [1, 2.5], "msg": "hi"}});
+        let program = JqFilter::compile_program("{seq, msg: .data.msg}").unwrap();
+        assert_eq!(program.outputs(&msg), vec![json!({"seq": 3, "msg": "hi"})]);
+
+        let each = JqFilter::compile_program(".data.items[] * 2").unwrap();
+        assert_eq!(each.outputs(&msg), vec![json!(2), json!(5.0)]);
+        assert_eq!(
+            JqFilter::compile_program("empty").unwrap().outputs(&msg),
+            Vec::<Value>::new()
+        );
+        let partial = JqFilter::compile_program(".data.msg, .data.missing, .seq").unwrap();
+        assert_eq!(
+            partial.outputs(&msg),
+            vec![json!("hi")],
+            "an error ends the outputs"
+        );
+
+        let err = JqFilter::compile_program("{seq").unwrap_err();
+        assert!(err.hint().unwrap_or_default().contains("--jq"));
+    }
+
+    #[test]
+    fn key_takes_first_output_and_skips_null() {
+        let filter = JqFilter::compile(".data.request_id").unwrap();
+        assert_eq!(
+            filter.key(&json!({"data":{"request_id":"r1"}})).as_deref(),
+            Some("r1")
+        );
+        assert_eq!(
+            filter.key(&json!({"data":{"request_id":7}})).as_deref(),
+            Some("7")
+        );
+        assert_eq!(filter.key(&json!({"data":{}})), None);
+    }
+
+    #[cfg(feature = "jsonpath")]
+    #[test]
+    fn jsonpath_matches_when_nodes_are_selected() {
+        let preds = super::compile_jsonpath_filters(&["$.data.items[?(@.price > 10)]".to_string()])
+            .unwrap();
+        let cheap = json!({"data":{"items":[{"price":5}]}});
+        let pricey = json!({"data":{"items":[{"price":5},{"price":12}]}});
+        assert!(!matches_all(&preds, &cheap).unwrap());
+        assert!(matches_all(&preds, &pricey).unwrap());
+    }
+
+    #[cfg(not(feature = "jsonpath"))]
+    #[test]
+    fn jsonpath_without_feature_is_usage_error() {
+        let err = JqFilter::compile_jsonpath("$.data")
+            .err()
+            .expect("unsupported");
+        assert_eq!(err.kind(), plasmite::api::ErrorKind::Usage);
+    }
+
     #[test]
     fn filter_runtime_error_is_false() {
         let preds = compile_filters(&[r#".data.missing == 1"#.to_string()]).unwrap();
@@ -646,4 +995,51 @@ mod tests {
         });
         assert!(!matches_all(&preds, &msg).unwrap());
     }
+
+    #[test]
+    fn required_strings_only_come_from_pure_string_equalities() {
+        let required = |expr: &str| JqFilter::compile(expr).unwrap().required_strings();
+        assert_eq!(required(r#".data.level == "error""#), vec!["error"]);
+        assert_eq!(
+            required(r#""api" == .data.svc and .data.items[0].host == "a and b""#),
+            vec!["api", "a and b"]
+        );
+        assert!(required(r#".data.level == "error" or .data.level == "warn""#).is_empty());
+        assert!(required(r#".data.level == "error" and .data.n > 3"#).is_empty());
+        assert!(required(r#".data.level != "error""#).is_empty());
+        assert!(required(r#".data.band == 1"#).is_empty());
+        assert!(required(r#"(.data.level == "error")"#).is_empty());
+        assert!(required(r#".data.msg == "id \(.x)""#).is_empty());
+
+        let preds = compile_filters(&[
+            r#".data.a == "x""#.to_string(),
+            r#".data.b | length > 0"#.to_string(),
+            r#".data.c == "y""#.to_string(),
+        ])
+        .unwrap();
+        assert_eq!(required_strings(&preds), vec!["x", "y"]);
+    }
+
+    #[test]
+    fn field_equalities_only_come_from_pure_scalar_equalities() {
+        let fields = |expr: &str| JqFilter::compile(expr).unwrap().field_equalities();
+        assert_eq!(
+            fields(r#".data.user.id == 7 and "a" == .data.kind and .data.ok == true"#),
+            vec![
+                (".data.user.id".to_string(), json!(7)),
+                (".data.kind".to_string(), json!("a")),
+                (".data.ok".to_string(), json!(true)),
+            ]
+        );
+        assert_eq!(
+            fields(".data.gone == null"),
+            vec![(".data.gone".to_string(), Value::Null)]
+        );
+        assert!(fields(".data.n == 1 or .data.n == 2").is_empty());
+        assert!(fields(".data.n == 1 and .data.m > 2").is_empty());
+        assert!(fields(".data.items[0] == 1").is_empty());
+        assert!(fields(".data.n == .data.m").is_empty());
+        assert!(fields(r#".data.tags == ["a"]"#).is_empty());
+        assert!(fields(r#".data.msg == "id \(.x)""#).is_empty());
+    }
 }
@@ -1,5 +1,6 @@
 //! Purpose: Shared library crate used by the `plasmite` CLI and bindings.
-//! Exports: `api` (stable public surface), `notice` (structured stderr notices).
+//! Exports: `api` (stable public surface), `notice` (structured stderr notices),
+//! `message_jsonl` (batched follow JSONL encoder), `pool_info_json` (shared pool-info JSON envelope).
 //! Role: Public API boundary with private internal storage modules.
 //! Invariants: Additive-only changes to `api`; internal modules remain private.
 //! Invariants: Core modules prefer explicit inputs/outputs over hidden state.
@@ -7,5 +8,7 @@ mod abi;
 pub mod api;
 mod core;
 pub mod mcp;
+pub mod message_jsonl;
 pub mod notice;
+pub mod pool_info_json;
 mod pool_paths;
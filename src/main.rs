@@ -9,9 +9,9 @@ use std::ffi::OsString;
 use std::io::{self, IsTerminal, Read};
 use std::net::{IpAddr, SocketAddr, UdpSocket};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::mpsc;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use clap::{
     Args, CommandFactory, Parser, Subcommand, ValueEnum, ValueHint,
@@ -26,27 +26,88 @@ use url::Url;
 
 mod color_json;
 mod command_dispatch;
+mod downstream;
+mod exec_hook;
+mod feed_source;
+mod field_set;
+mod fifo_bridge;
+mod follow_control;
+mod forward;
+mod help_json;
+mod hooks;
+mod i18n;
 mod ingest;
+mod journal;
 mod jq_filter;
 mod mcp_stdio;
-mod pool_info_json;
+mod otlp;
+mod pool_backup;
+mod pool_diff;
+mod pool_export;
+mod pool_gc;
+mod pool_manifest;
 mod pool_paths;
+mod pool_pins;
+mod pool_seal;
+mod pool_template;
+mod projection;
+mod replay_control;
+mod schema_infer;
+mod seq_audit;
 mod serve;
 mod serve_init;
-
-use color_json::colorize_json;
-use ingest::{ErrorPolicy, IngestConfig, IngestFailure, IngestMode, IngestOutcome, ingest};
-use jq_filter::{JqFilter, compile_filters, matches_all};
+mod serve_policy;
+mod serve_quota;
+mod serve_routes;
+mod serve_trace;
+mod serve_transform;
+mod sql_query;
+mod stats_history;
+mod supervise;
+mod syslog;
+mod thread_view;
+mod transcript;
+mod ui;
+
+use color_json::{Style, Theme, color_env_override, colorize_json, paint};
+use downstream::OnSigpipe;
+use exec_hook::{ExecErrorPolicy, ExecHook};
+use field_set::FieldSets;
+use follow_control::FollowControl;
+use i18n::{Msg, tr};
+use ingest::{
+    CsvHeader, ErrorPolicy, IngestConfig, IngestFailure, IngestMode, IngestOutcome, ingest,
+};
+use jq_filter::{
+    JqFilter, compile_filters, compile_jsonpath_filters, field_equalities, matches_all,
+    required_strings,
+};
 use plasmite::api::{
-    AppendOptions, Cursor, CursorResult, Durability, Error, ErrorKind, FrameRef, Lite3DocRef,
-    LocalClient, Pool, PoolOptions, PoolRef, RemoteClient, RemotePool, TailOptions,
-    ValidationIssue, ValidationReport, ValidationStatus, lite3,
+    ATTACHMENT_REF_KEY, ActivityHistogram, AppendOptions, ChaosConfig, Codec, Cursor, CursorResult,
+    Deadline, DeleteOptions, Durability, Error, ErrorKind, ExplainTarget, FRAME_FLAG_ATTACHMENT,
+    FRAME_FLAG_CHUNK, FRAME_FLAG_CONTROL, FrameExplain, FrameRef, JsonCodec, Lite3DocRef,
+    Lite3Scalar, LocalClient, Pool, PoolCodec, PoolOptions, PoolRef, PoolRole, RegistrationGuard,
+    RemoteClient, RemotePool, RemoteTail, TailOptions, TimeNearest, ValidationIssue,
+    ValidationReport, ValidationStatus, attachment_ref, base64_decode, base64_encode,
+    bloom_might_contain, bucket_activity, chunk_position,
+    control::{CONTROL_DELETED, CONTROL_ROTATED, check_control_payload, control_kind},
+    explain_frame, frame_flag_names, hex_encode_spaced, lite3,
     notify::{self, NotifyWait},
-    to_exit_code,
+    pack_attachment, parse_frame_flags, payload_codec, register_pool_user, to_exit_code,
+    unpack_attachment, validate_message_frame,
 };
+use plasmite::message_jsonl::FrameJsonlEncoder;
 use plasmite::notice::{Notice, notice_json};
-use pool_info_json::{bounds_json, pool_info_json};
-use pool_paths::{PoolNameResolveError, default_pool_dir, resolve_named_pool_path};
+use plasmite::pool_info_json::{bounds_json, pool_info_json};
+use pool_paths::{
+    PoolNameResolveError, default_pool_dir, find_pool_files, is_path_ref, pool_name_for_path,
+    resolve_named_pool_path,
+};
+use pool_template::TemplateDefaults;
+use projection::{Projection, ProjectionFormat};
+use replay_control::{PaceBy, ReplayControl, ReplayPacer, ReplayUntil, SpeedRamp};
+use thread_view::{DEFAULT_THREAD_WINDOW, ThreadView};
+use transcript::{Direction, TranscriptWriter, parse_transcript_line};
 
 #[derive(Copy, Clone, Debug)]
 struct RunOutcome {
@@ -81,7 +142,21 @@ fn main() {
 }
 
 fn run() -> Result<RunOutcome, (Error, ColorMode)> {
-    let cli = match Cli::try_parse_from(normalize_args(std::env::args_os())) {
+    let args = normalize_args(std::env::args_os());
+    if let Some(path) = help_json::help_json_request(&args) {
+        let schema = help_json::command_schema(&Cli::command(), &path);
+        let json = serde_json::to_string_pretty(&schema).map_err(|err| {
+            (
+                Error::new(ErrorKind::Internal)
+                    .with_message("failed to encode command schema")
+                    .with_source(err),
+                ColorMode::Auto,
+            )
+        })?;
+        println!("{json}");
+        return Ok(RunOutcome::ok());
+    }
+    let cli = match Cli::try_parse_from(args) {
         Ok(cli) => cli,
         Err(err) => match err.kind() {
             ClapErrorKind::DisplayHelp
@@ -120,6 +195,41 @@ fn run() -> Result<RunOutcome, (Error, ColorMode)> {
 
     let pool_dir = cli.dir.unwrap_or_else(default_pool_dir);
     let color_mode = cli.color;
+    let _ = OUTPUT_MODE.set(cli.output);
+    let theme = cli.theme.map(Theme::from).or_else(|| {
+        std::env::var("PLASMITE_THEME")
+            .ok()
+            .and_then(|value| Theme::parse(&value))
+    });
+    let _ = THEME.set(theme.unwrap_or_default());
+    i18n::set_locale(i18n::resolve_locale(cli.lang.as_deref(), |key| {
+        std::env::var(key).ok()
+    }));
+
+    if let Some(value) = cli.op_timeout.as_deref() {
+        let timeout = parse_duration(value).map_err(|err| (err, color_mode))?;
+        if timeout.is_zero() {
+            return Err((
+                Error::new(ErrorKind::Usage)
+                    .with_message("--op-timeout must be greater than zero")
+                    .with_hint(
+                        "Use a duration like 30s or 5m, or omit the flag to wait indefinitely.",
+                    ),
+                color_mode,
+            ));
+        }
+        let _ = OP_TIMEOUT.set((timeout, Deadline::after(timeout)));
+    }
+
+    if cli.chaos_drop_appends.is_some() || cli.chaos_delay_ms.is_some() || cli.chaos_seed.is_some()
+    {
+        plasmite::api::chaos::install(ChaosConfig {
+            drop_appends: cli.chaos_drop_appends.unwrap_or(0.0),
+            delay: Duration::from_millis(cli.chaos_delay_ms.unwrap_or(0)),
+            seed: cli.chaos_seed.unwrap_or(0),
+        })
+        .map_err(|err| (err, color_mode))?;
+    }
 
     let result = command_dispatch::dispatch_command(cli.command, pool_dir, color_mode);
 
@@ -185,6 +295,7 @@ LEARN MORE
     plasmite pool delete <name>...
 
   $ plasmite <command> --help
+  $ plasmite [<command>] --help-json   # command/flag tree as JSON
   https://github.com/sandover/plasmite"#,
     arg_required_else_help = true,
     disable_help_subcommand = false
@@ -203,6 +314,59 @@ struct Cli {
         help = "Colorize stderr diagnostics and pretty JSON output: auto|always|never"
     )]
     color: ColorMode,
+    #[arg(
+        long,
+        global = true,
+        default_value = "auto",
+        value_enum,
+        help = "Stdout format, overriding TTY detection: auto|json|jsonl|pretty"
+    )]
+    output: OutputMode,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        help = "Color palette for pretty JSON and labels: dark|light|mono (default: $PLASMITE_THEME or dark)"
+    )]
+    theme: Option<ThemeCli>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "LOCALE",
+        help = "Language for human-readable text, e.g. en or es (default: $LC_ALL, $LC_MESSAGES, $LANG)"
+    )]
+    lang: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        hide = true,
+        value_name = "RATE",
+        help = "Fail this fraction of appends with an injected busy error (builds with `--features chaos`)"
+    )]
+    chaos_drop_appends: Option<f64>,
+    #[arg(
+        long,
+        global = true,
+        hide = true,
+        value_name = "MS",
+        help = "Delay every append, and every serve read request, by MS milliseconds (builds with `--features chaos`)"
+    )]
+    chaos_delay_ms: Option<u64>,
+    #[arg(
+        long,
+        global = true,
+        hide = true,
+        value_name = "SEED",
+        help = "Seed for --chaos-drop-appends decisions (default: 0)"
+    )]
+    chaos_seed: Option<u64>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "DURATION",
+        help = "Abort pool opens, doctor scans, pool list, and pool export after DURATION (e.g. 30s); serve applies it per request"
+    )]
+    op_timeout: Option<String>,
 
     #[command(subcommand)]
     command: Command,
@@ -215,12 +379,95 @@ enum ColorMode {
     Never,
 }
 
+/// Global `--output` override for stdout formatting.
+///
+/// `json`/`jsonl` force machine envelopes (as if `--json` were given) even on a TTY;
+/// `pretty` forces the human/pretty forms even when stdout is piped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputMode {
+    Auto,
+    Json,
+    Jsonl,
+    Pretty,
+}
+
+static OUTPUT_MODE: OnceLock<OutputMode> = OnceLock::new();
+/// Global `--op-timeout` and the deadline it sets for this command, counted from startup.
+static OP_TIMEOUT: OnceLock<(Duration, Deadline)> = OnceLock::new();
+
+fn op_timeout() -> Option<Duration> {
+    OP_TIMEOUT.get().map(|(timeout, _)| *timeout)
+}
+
+/// Deadline for long local operations; never expires without `--op-timeout`.
+fn op_deadline() -> Deadline {
+    OP_TIMEOUT
+        .get()
+        .map(|(_, deadline)| deadline.clone())
+        .unwrap_or_default()
+}
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum ThemeCli {
+    Dark,
+    Light,
+    Mono,
+}
+
+impl From<ThemeCli> for Theme {
+    fn from(value: ThemeCli) -> Self {
+        match value {
+            ThemeCli::Dark => Theme::Dark,
+            ThemeCli::Light => Theme::Light,
+            ThemeCli::Mono => Theme::Mono,
+        }
+    }
+}
+
+/// Palette to style with when color is enabled; `None` means plain text.
+fn active_theme(use_color: bool) -> Option<Theme> {
+    use_color.then(|| THEME.get().copied().unwrap_or_default())
+}
+
+fn output_mode() -> OutputMode {
+    OUTPUT_MODE.get().copied().unwrap_or(OutputMode::Auto)
+}
+
+/// Whether stdout should get interactive (human) output, honoring `--output`.
+fn stdout_is_terminal() -> bool {
+    match output_mode() {
+        OutputMode::Auto => io::stdout().is_terminal(),
+        OutputMode::Json | OutputMode::Jsonl => false,
+        OutputMode::Pretty => true,
+    }
+}
+
+/// Resolve a command's `--json` flag against the global `--output` mode.
+fn wants_json(flag: bool) -> bool {
+    flag || matches!(output_mode(), OutputMode::Json | OutputMode::Jsonl)
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum NearestCli {
+    Before,
+    After,
+}
+
 #[derive(Copy, Clone, Debug, ValueEnum)]
 enum FollowFormat {
     Pretty,
     Jsonl,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum PollStrategy {
+    /// Start at --poll-min after each message and double up to --poll-max while idle.
+    Exponential,
+    /// Always wait --poll-max between idle polls.
+    Fixed,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 enum InputMode {
     Auto,
@@ -228,6 +475,14 @@ enum InputMode {
     Json,
     Seq,
     Jq,
+    Csv,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum CsvHeaderCli {
+    Auto,
+    Yes,
+    No,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
@@ -236,10 +491,20 @@ enum ErrorPolicyCli {
     Skip,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
+enum ExecErrorsCli {
+    Continue,
+    Stop,
+}
+
 impl ColorMode {
     fn use_color(self, is_tty: bool) -> bool {
         match self {
-            ColorMode::Auto => is_tty,
+            ColorMode::Auto => color_env_override(
+                std::env::var("NO_COLOR").ok().as_deref(),
+                std::env::var("CLICOLOR_FORCE").ok().as_deref(),
+            )
+            .unwrap_or(is_tty),
             ColorMode::Always => true,
             ColorMode::Never => false,
         }
@@ -299,16 +564,29 @@ inline JSON, file input (-f/--file), or streams via stdin (auto-detected)."#,
   # Remote shorthand ref (serve must already expose the pool)
   $ plasmite feed http://127.0.0.1:9700/demo --tag remote '{"msg":"hello"}'
 
+  # Pour a CSV export into a pool (header row detected automatically)
+  $ plasmite feed orders --in csv -f orders.csv
+
   # Auto-create pool on first feed
   $ plasmite feed bar --create '{"first": "message"}'
 
+  # Mark a record with frame flags (shown as meta.flags)
+  $ plasmite feed foo --flag control '{"op": "rotate"}'
+
+  # Store a file's bytes beside the message (no base64 inside the payload)
+  $ plasmite feed shots --attach screen.png '{"page": "/checkout"}'
+
 NOTES
   - Remote refs must be shorthand: http(s)://host:port/<pool> (no trailing slash)
   - API-shaped URLs (e.g. /v0/pools/<pool>/append) are rejected as POOL refs
   - `--create` is local-only; remote feed never creates remote pools
   - `--in auto` detects JSONL, JSON-seq (0x1e), event streams (data: prefix)
+  - `--in csv` converts rows to objects; unquoted numbers and true/false become JSON values
   - `--errors skip` continues past bad records; `--durability flush` syncs to disk
-  - `--retry N` retries on transient failures (lock contention, etc.)"#
+  - `--retry N` retries on transient failures (lock contention, etc.)
+  - Remote `--batch N` sends N records per request with per-record streamed receipts
+  - `--flag` names: compressed, encrypted, control, user0..user15 (remote refs send one record per request)
+  - `--attach FILE` sets .data.attachment to {"$attachment": {"bytes": N, "name": FILE}}; readers print the bytes as base64 under `attachment`"#
     )]
     Feed {
         #[arg(help = "Pool ref: local name/path or shorthand URL http(s)://host:port/<pool>")]
@@ -325,6 +603,44 @@ NOTES
             value_hint = ValueHint::FilePath
         )]
         file: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["data", "file", "from_clipboard"],
+            help = "Compose the message in $EDITOR from a JSON template"
+        )]
+        edit: bool,
+        #[arg(
+            long = "from-clipboard",
+            conflicts_with_all = ["data", "file"],
+            help = "Read the message JSON from the system clipboard"
+        )]
+        from_clipboard: bool,
+        #[arg(
+            long = "set",
+            value_name = "ASSIGN",
+            help = "Set a field on every record before appending (repeatable), e.g. '.data.host = env.HOSTNAME'"
+        )]
+        set: Vec<String>,
+        #[arg(
+            long = "set-time",
+            value_name = "PATH",
+            help = "Stamp every record with its ingest time (RFC 3339) at PATH, e.g. .data.received_at"
+        )]
+        set_time: Vec<String>,
+        #[arg(
+            long = "flag",
+            value_name = "NAME",
+            help = "Set a frame flag on every record (repeatable): compressed, encrypted, control, user0..user15"
+        )]
+        flag: Vec<String>,
+        #[arg(
+            long,
+            value_name = "FILE",
+            requires = "data",
+            value_hint = ValueHint::FilePath,
+            help = "Store FILE's bytes as a binary attachment, referenced at .data.attachment"
+        )]
+        attach: Option<PathBuf>,
         #[arg(long, default_value = "fast", help = "Durability mode: fast|flush")]
         durability: String,
         #[arg(long, help = "Create the pool if it is missing")]
@@ -350,9 +666,17 @@ NOTES
   jsonl  One JSON object per line
   json   Single JSON value (object or array)
   seq    RFC 7464 JSON Text Sequences (0x1e-delimited)
-  jq     jq --raw-output / --stream output"#
+  jq     jq --raw-output / --stream output
+  csv    One message per CSV row, keyed by header names (see --csv-header)"#
         )]
         input: InputMode,
+        #[arg(
+            long = "csv-header",
+            value_enum,
+            value_name = "MODE",
+            help = "With --in csv: first row names the columns: auto|yes|no (no → col1, col2, ...)"
+        )]
+        csv_header: Option<CsvHeaderCli>,
         #[arg(
             short = 'e',
             long = "errors",
@@ -361,6 +685,17 @@ NOTES
             help = "Stream error policy: stop|skip"
         )]
         errors: ErrorPolicyCli,
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Remote only: records per append request (default 256 with --file, else 1)",
+            long_help = r#"Remote only: records per append request
+
+Each request streams back one receipt per record as the server commits it, so large
+imports report progress and failures name the offending record. Defaults to 256 for
+--file input and 1 for stdin (records are sent as soon as they arrive)."#
+        )]
+        batch: Option<usize>,
         #[arg(
             long,
             help = "Bearer token for remote refs (dev-only; prefer --token-file)",
@@ -389,6 +724,24 @@ NOTES
             help_heading = "Remote auth/TLS"
         )]
         tls_skip_verify: bool,
+        #[arg(
+            long = "tls-client-cert",
+            value_name = "PATH",
+            help = "Present this PEM client certificate to remote servers (mTLS)",
+            value_hint = ValueHint::FilePath,
+            requires = "tls_client_key",
+            help_heading = "Remote auth/TLS"
+        )]
+        tls_client_cert: Option<PathBuf>,
+        #[arg(
+            long = "tls-client-key",
+            value_name = "PATH",
+            help = "Private key (PEM) for --tls-client-cert",
+            value_hint = ValueHint::FilePath,
+            requires = "tls_client_cert",
+            help_heading = "Remote auth/TLS"
+        )]
+        tls_client_key: Option<PathBuf>,
     },
     #[command(
         about = "Serve pools over HTTP (loopback default in v0)",
@@ -415,10 +768,17 @@ NOTES
   - Use Authorization: Bearer <token> when --token or --token-file is set
   - Prefer --token-file for non-loopback deployments; --token is dev-only
   - Use --access to restrict read/write operations
+  - --readonly-snapshot opens pools without write access or locks (live dirs, backups)
   - Non-loopback writes require TLS + --token-file (or --insecure-no-tls for demos)
+  - --tls-client-ca requires client certificates (mTLS); a verified cert replaces the bearer token
   - --tls-self-signed is for demos; clients must trust the generated cert
   - Use repeatable --cors-origin to allow browser clients from specific origins
-  - Safety limits: --max-body-bytes, --max-tail-timeout-ms, --max-tail-concurrency"#
+  - Safety limits: --max-body-bytes, --max-tail-timeout-ms, --max-tail-concurrency
+  - --from-env reads PLASMITE_SERVE_BIND, _ACCESS, _CORS_ORIGINS, _TOKEN, _TOKEN_FILE,
+    _TLS_CERT, _TLS_KEY, _TLS_CLIENT_CA, _TLS_SELF_SIGNED, _ALLOW_NON_LOOPBACK,
+    _INSECURE_NO_TLS, _READONLY_SNAPSHOT, _MAX_BODY_BYTES, _MAX_TAIL_TIMEOUT_MS,
+    _MAX_TAIL_CONCURRENCY, _OTLP_BIND
+  - For container probes, run `plasmite serve check --from-env` at startup and probe GET /healthz"#
     )]
     Serve {
         #[command(subcommand)]
@@ -446,17 +806,137 @@ The process exits when stdin closes."#,
     },
     #[command(
         arg_required_else_help = true,
-        about = "Fetch one message by sequence number",
-        long_about = r#"Fetch a specific message by its seq number and print as JSON."#,
+        about = "Fetch one message by sequence number or time",
+        long_about = r#"Fetch a specific message by its seq number (or the message closest to a time) and print as JSON."#,
         after_help = r#"EXAMPLES
   $ plasmite fetch foo 1
-  $ plasmite fetch foo 42 | jq '.data'"#
+  $ plasmite fetch foo 42 | jq '.data'
+  $ plasmite fetch foo --at 2026-02-02T10:00:00Z
+  $ plasmite fetch foo --at 10m --nearest after
+
+NOTES
+  - --at takes RFC 3339 or a relative time (e.g. 10m = ten minutes ago)
+  - --nearest before (default) picks the last message at or before the time; after picks the first at or after
+  - --fields seq,.data.msg prints only those fields; add --output csv|tsv for a header plus one row
+  - --jq PROGRAM prints every value a jq program yields for the message envelope (e.g. --jq '.data.items[]')
+  - --attachment-out FILE writes the message's attachment bytes to FILE and leaves them out of the JSON
+  - --strict validates the stored Lite3 payload in full first and fails with its seq and offset if it is malformed"#
     )]
     Fetch {
         #[arg(help = "Pool name or path")]
         pool: String,
-        #[arg(help = "Sequence number")]
+        #[arg(
+            help = "Sequence number",
+            required_unless_present = "at",
+            conflicts_with = "at"
+        )]
+        seq: Option<u64>,
+        #[arg(
+            long,
+            value_name = "TIME",
+            help = "Fetch the message closest to this time (RFC 3339 or relative like 5m)"
+        )]
+        at: Option<String>,
+        #[arg(
+            long,
+            value_enum,
+            default_value = "before",
+            requires = "at",
+            help = "Side of --at to resolve to when no message has that exact time"
+        )]
+        nearest: NearestCli,
+        #[arg(
+            long,
+            value_name = "LIST",
+            help = "Print only these fields (e.g. seq,time,.data.msg,.meta.tags)"
+        )]
+        fields: Option<String>,
+        #[arg(
+            long,
+            value_enum,
+            default_value = "json",
+            requires = "fields",
+            help = "Projected output format: json|csv|tsv"
+        )]
+        output: ProjectionFormat,
+        #[arg(
+            long,
+            value_name = "PROGRAM",
+            conflicts_with = "fields",
+            help = "Transform the message with a jq program and print what it yields"
+        )]
+        jq: Option<String>,
+        #[arg(
+            long = "attachment-out",
+            value_name = "FILE",
+            value_hint = ValueHint::FilePath,
+            help = "Write the message's binary attachment to FILE"
+        )]
+        attachment_out: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Validate the message's payload in full and fail instead of decoding it best effort"
+        )]
+        strict: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Annotate a message with a linked reply or reaction",
+        long_about = r#"Append an annotation message that references an existing message.
+
+The annotation is an ordinary message whose meta.refs lists the annotated seq.
+Pretty `follow` output renders annotations inline beneath the message they reference."#,
+        after_help = r#"EXAMPLES
+  $ plasmite annotate chat 12 '{"emoji":"+1"}'
+  $ plasmite annotate chat 12 --tag reply '{"from":"bob","msg":"on it"}'
+
+NOTES
+  - Local pools only; the referenced seq must still be in the pool"#
+    )]
+    Annotate {
+        #[arg(help = "Pool name or path")]
+        pool: String,
+        #[arg(help = "Sequence number of the message being annotated")]
         seq: u64,
+        #[arg(help = "Annotation JSON object")]
+        data: String,
+        #[arg(long = "tag", help = "Tag the annotation (repeatable)")]
+        tag: Vec<String>,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Pin messages so ring churn never loses them",
+        long_about = r#"Pin messages by seq, or list a pool's pins when no seq is given.
+
+Pinning copies the message into a `<pool>.pins.json` sidecar, so it survives after the
+ring overwrites its frame (or `max_messages` retention drops it): `fetch` falls back to
+the pinned copy, and `pool info` lists the pins."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pin incidents 42 --note "root cause decision"
+  $ plasmite pin incidents                 # list pins
+  $ plasmite pin incidents 42 --remove
+
+NOTES
+  - Local pools only; a seq must still be in the pool when it is pinned
+  - Re-pinning a seq refreshes its note and copy
+  - Pins travel with `pool delete --trash` / `pool restore` and are removed with the pool"#
+    )]
+    Pin {
+        #[arg(help = "Pool name or path")]
+        pool: String,
+        #[arg(help = "Sequence numbers to pin (omit to list pins)")]
+        seqs: Vec<u64>,
+        #[arg(
+            long,
+            value_name = "TEXT",
+            conflicts_with = "remove",
+            help = "Note stored with the pin"
+        )]
+        note: Option<String>,
+        #[arg(long, requires = "seqs", help = "Unpin the given seqs instead")]
+        remove: bool,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
     },
     #[command(
         arg_required_else_help = true,
@@ -487,6 +967,12 @@ Use `--replay N` with `--tail` or `--since` to replay with timing."#,
   # Replay at original timing (or 2x, 0.5x, 0 = instant)
   $ plasmite follow foo --tail 100 --replay 1
 
+  # Replay an hour of history up to seq 5000, speeding up from 1x to 8x
+  $ plasmite follow foo --since 1h --replay 1 --until 5000 --speed-ramp 1..8
+
+  # Replay paced by the events' own timestamps rather than append times
+  $ plasmite follow foo --tail 500 --replay 1 --pace-by .data.ts
+
   # Filter by exact tag (repeat for AND)
   $ plasmite follow foo --tag ping --one
 
@@ -496,21 +982,68 @@ Use `--replay N` with `--tail` or `--since` to replay with timing."#,
   # Wait up to 5 seconds for a message
   $ plasmite follow foo --timeout 5s
 
+  # Report throughput, lag, and drops on stderr every 10 seconds
+  $ plasmite follow events --format jsonl --stats-interval 10s > events.jsonl
+
+  # Poll lazily on battery, or tighten the ceiling for low latency
+  $ plasmite follow sensors --poll-strategy fixed --poll-max 1s
+  $ plasmite follow orders --poll-max 5ms
+
+  # Adjust a running follower in place
+  $ plasmite follow events --jsonl --control-socket /tmp/f.sock > events.jsonl
+  $ plasmite ctl /tmp/f.sock where '.data.level == "error"'
+
   # Remote shorthand ref (serve must already expose the pool)
   $ plasmite follow http://127.0.0.1:9700/demo --tail 20 --format jsonl
 
+  # Fail over between mirrored servers
+  $ plasmite follow --remote https://a:9700/chat --remote https://b:9700/chat
+
+  # Group interleaved agent traffic by request id (history + live)
+  $ plasmite follow agents --tail 200 --thread '.data.request_id'
+
+  # Run a command per matching message (JSON on stdin and in {})
+  $ plasmite follow alerts --where '.data.alert == true' --exec 'notify-send {}'
+  $ plasmite follow jobs --exec './handle.sh' --exec-parallel 4 --exec-errors stop
+
 NOTES
   - Use `--format jsonl` for scripts (one JSON object per line)
   - `--tag` matches exact tags; `--where` uses jq-style expressions; repeat either for AND
+  - `--where-jsonpath '$.data.items[?(@.price > 10)]'` is the JSONPath alternative (ANDed with --where)
   - `--since 5m` and `--since 2026-01-15T10:00:00Z` both work
   - Remote refs must be shorthand: http(s)://host:port/<pool> (no trailing slash)
-  - Remote `follow` supports `--tail`, `--tag`, `--where`, `--one`, `--timeout`, `--data-only`, and `--format`
+  - Remote `follow` supports `--tail`, `--tag`, `--where`, `--one`, `--timeout`, `--data-only`, `--jq`, and `--format`
   - `--create` is local-only; remote follow never creates remote pools
-  - `--replay N` exits when all selected messages are emitted (no live follow); `--replay 0` emits instantly"#
+  - Repeated `--remote` refs fail over in order after persistent errors, de-duplicating by seq
+  - `--replay N` exits when all selected messages are emitted (no live follow); `--replay 0` emits instantly
+  - `--until 1200` or `--until 2026-01-15T11:00:00Z` ends replay at that seq or frame time; `--speed-ramp 1..8` speeds up from 1x to 8x over the replay; `--pace-by .data.ts` sleeps by gaps in that field instead of frame timestamps
+  - `--exec` replaces stdout output; hook stdout/stderr pass through, and `--data-only` narrows the hook input
+  - `--thread EXPR` holds output ~500ms and prints it grouped by EXPR; pretty output indents each thread under its key
+  - `--fields seq,time,.data.msg` prints compact objects with only those fields; `--output csv|tsv` prints a header row then one row per message
+  - `--jq '{seq, msg: .data.msg}'` runs a jq program on each message envelope and prints every value it yields (none, one, or many); a runtime error such as a missing field ends that message's outputs, so write `.data.x?` or `(.data.x? // null)` for optional fields
+  - `--stats-interval` notices (kind `stats`) carry cumulative `emitted`, `bytes` (payload bytes), `dropped`, `wakeups` (idle waits, `notified_wakeups` of them ended by an append signal), current `lag` behind the newest seq, and the current `poll_ms`; local pools only
+  - When idle, follow waits --poll-min (default 1ms) doubling to --poll-max (default 50ms) between checks, or always --poll-max with `--poll-strategy fixed`; append signals still wake it early unless --no-notify; local pools only
+  - `--strict` validates each emitted message's Lite3 payload in full and exits with its seq and offset on the first malformed one; local pools only
+  - `--control-socket PATH` lets `plasmite ctl` pause/resume, replace `--where`, and read counters; local pools only
+  - Control messages (`meta.control`) are hidden unless `--show-control`; live `rotated` reopens the pool once its file is replaced, `deleted` exits 0 (both with a `control` notice)"#
     )]
     Follow {
-        #[arg(help = "Pool ref: local name/path or shorthand URL http(s)://host:port/<pool>")]
-        pool: String,
+        #[arg(
+            help = "Pool ref: local name/path or shorthand URL http(s)://host:port/<pool>",
+            required_unless_present = "remote"
+        )]
+        pool: Option<String>,
+        #[arg(
+            long,
+            value_name = "URL",
+            help = "Failover remote ref (repeatable, priority order after POOL)",
+            long_help = r#"Failover remote ref: http(s)://host:port/<pool> (repeatable)
+
+Refs are tried in order: POOL (if given) first, then each --remote. After repeated
+errors on the active remote, follow resumes from the next one at the last seen seq,
+dropping any already-emitted seqs. Intended for mirrored pools that share seqs."#
+        )]
+        remote: Vec<String>,
         #[arg(long, help = "Create local pool if missing before following")]
         create: bool,
         #[arg(
@@ -549,6 +1082,41 @@ NOTES
             help = "Filter messages by boolean expression (repeatable; AND across repeats)"
         )]
         where_expr: Vec<String>,
+        #[arg(
+            long = "where-jsonpath",
+            value_name = "PATH",
+            help = "Filter by JSONPath: match when PATH selects anything (repeatable; needs the `jsonpath` build feature)"
+        )]
+        where_jsonpath: Vec<String>,
+        #[arg(
+            long,
+            value_name = "EXPR",
+            conflicts_with_all = ["one", "exec"],
+            help = "Group output by a correlation key expression (e.g. '.data.request_id')"
+        )]
+        thread: Option<String>,
+        #[arg(
+            long,
+            value_name = "LIST",
+            conflicts_with_all = ["data_only", "exec", "thread"],
+            help = "Print only these fields (e.g. seq,time,.data.msg,.meta.tags)"
+        )]
+        fields: Option<String>,
+        #[arg(
+            long,
+            value_enum,
+            default_value = "json",
+            requires = "fields",
+            help = "Projected output format: json (compact, one per line)|csv|tsv"
+        )]
+        output: ProjectionFormat,
+        #[arg(
+            long,
+            value_name = "PROGRAM",
+            conflicts_with_all = ["data_only", "fields", "thread"],
+            help = "Transform each message with a jq program and print what it yields (e.g. '{seq, msg: .data.msg}')"
+        )]
+        jq: Option<String>,
         #[arg(
             long = "tag",
             value_name = "TAG",
@@ -559,12 +1127,109 @@ NOTES
         quiet_drops: bool,
         #[arg(long = "no-notify", help = "Disable semaphore wakeups (poll only)")]
         no_notify: bool,
+        #[arg(
+            long = "poll-min",
+            value_name = "DURATION",
+            help = "Shortest wait between idle polls (default 1ms)"
+        )]
+        poll_min: Option<String>,
+        #[arg(
+            long = "poll-max",
+            value_name = "DURATION",
+            help = "Longest wait between idle polls (default 50ms)"
+        )]
+        poll_max: Option<String>,
+        #[arg(
+            long = "poll-strategy",
+            value_enum,
+            value_name = "STRATEGY",
+            help = "Idle polling: exponential (poll-min doubling to poll-max) or fixed (always poll-max)"
+        )]
+        poll_strategy: Option<PollStrategy>,
+        #[arg(
+            long = "show-control",
+            help = "Also print control messages (meta.control), which are hidden by default"
+        )]
+        show_control: bool,
+        #[arg(
+            long,
+            help = "Validate each message's payload in full and exit on the first malformed one"
+        )]
+        strict: bool,
+        #[arg(
+            long = "stats-interval",
+            value_name = "DURATION",
+            conflicts_with = "replay",
+            help = "Emit a stats notice (emitted, bytes, lag, drops) on stderr every DURATION (e.g. 10s)"
+        )]
+        stats_interval: Option<String>,
+        #[arg(
+            long = "control-socket",
+            value_name = "PATH",
+            conflicts_with = "replay",
+            value_hint = ValueHint::FilePath,
+            help = "Accept `plasmite ctl` commands (pause, resume, where, stats) on this Unix socket"
+        )]
+        control_socket: Option<PathBuf>,
+        #[arg(
+            long = "on-sigpipe",
+            value_enum,
+            default_value = "exit",
+            help = "When stdout's reader goes away (e.g. `| head`): exit 0 with a notice, or ignore and keep running"
+        )]
+        on_sigpipe: OnSigpipe,
         #[arg(
             long = "replay",
             value_name = "SPEED",
             help = "Replay with timing (1 = realtime, 2 = 2x, 0.5 = half; 0 = no delay). Requires --tail or --since"
         )]
         replay: Option<f64>,
+        #[arg(
+            long,
+            value_name = "TIME|SEQ",
+            requires = "replay",
+            help = "Stop replay after this seq, or after this time (RFC 3339 or relative like 5m); inclusive"
+        )]
+        until: Option<String>,
+        #[arg(
+            long = "speed-ramp",
+            value_name = "FROM..TO",
+            requires = "replay",
+            help = "Accelerate replay from speed FROM to TO across the replayed span (overrides the --replay speed)"
+        )]
+        speed_ramp: Option<String>,
+        #[arg(
+            long = "pace-by",
+            value_name = "PATH",
+            requires = "replay",
+            help = "Pace replay by an event time in each message (e.g. .data.ts; RFC 3339 or Unix seconds)"
+        )]
+        pace_by: Option<String>,
+        #[arg(
+            long,
+            value_name = "CMD",
+            help = "Run CMD (via sh -c) per matching message instead of printing; JSON on stdin and in {}",
+            help_heading = "Exec hook"
+        )]
+        exec: Option<String>,
+        #[arg(
+            long = "exec-parallel",
+            value_name = "N",
+            default_value_t = 1,
+            requires = "exec",
+            help = "Max concurrently running --exec commands",
+            help_heading = "Exec hook"
+        )]
+        exec_parallel: usize,
+        #[arg(
+            long = "exec-errors",
+            value_enum,
+            default_value = "continue",
+            requires = "exec",
+            help = "On non-zero hook exit: continue (report count at exit) or stop",
+            help_heading = "Exec hook"
+        )]
+        exec_errors: ExecErrorsCli,
         #[arg(
             long,
             help = "Bearer token for remote refs (dev-only; prefer --token-file)",
@@ -593,23 +1258,44 @@ NOTES
             help_heading = "Remote auth/TLS"
         )]
         tls_skip_verify: bool,
-    },
-    #[command(
-        arg_required_else_help = true,
-        about = "Capture command output into a local pool",
-        long_about = r#"Run a command, capture stdout/stderr as line messages, and append them to a local pool.
-
-Use `--` to separate tap flags from the wrapped command argv."#,
-        after_help = r#"EXAMPLES
-  $ plasmite tap build --create -- cargo build
+        #[arg(
+            long = "tls-client-cert",
+            value_name = "PATH",
+            help = "Present this PEM client certificate to remote servers (mTLS)",
+            value_hint = ValueHint::FilePath,
+            requires = "tls_client_key",
+            help_heading = "Remote auth/TLS"
+        )]
+        tls_client_cert: Option<PathBuf>,
+        #[arg(
+            long = "tls-client-key",
+            value_name = "PATH",
+            help = "Private key (PEM) for --tls-client-cert",
+            value_hint = ValueHint::FilePath,
+            requires = "tls_client_cert",
+            help_heading = "Remote auth/TLS"
+        )]
+        tls_client_key: Option<PathBuf>,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Capture command output into a local pool",
+        long_about = r#"Run a command, capture stdout/stderr as line messages, and append them to a local pool.
+
+Use `--` to separate tap flags from the wrapped command argv."#,
+        after_help = r#"EXAMPLES
+  $ plasmite tap build --create -- cargo build
   $ plasmite follow build
   $ plasmite follow build --where '.data.stream == "stderr"'
   $ plasmite tap deploy --tag prod -- ./deploy.sh
   $ plasmite tap api --create --create-size 64M -- ./server
+  $ plasmite tap worker --restart on-failure --max-restarts 5 -- ./worker
 
 NOTES
   - `--` is required before wrapped command args
+  - With `--restart`, lifecycle `start`/`exit` messages carry an `attempt` number, and `restart` / `give_up` messages record the policy decisions
   - Use --create-size for long-running/high-volume captures
+  - The `exit` lifecycle message carries `code` or `signal` + `core_dumped`, plus `rusage` (`max_rss_kb`, `user_cpu_ms`, `sys_cpu_ms`) on Unix
   - `tap` accepts local pool refs only in v0"#
     )]
     Tap {
@@ -628,6 +1314,27 @@ NOTES
         quiet: bool,
         #[arg(long, default_value = "fast", help = "Durability mode: fast|flush")]
         durability: String,
+        #[arg(
+            long,
+            value_enum,
+            default_value = "never",
+            help = "Restart the wrapped command when it exits: never|on-failure|always"
+        )]
+        restart: supervise::RestartPolicy,
+        #[arg(
+            long = "max-restarts",
+            value_name = "N",
+            default_value_t = 5,
+            help = "Give up after N consecutive restarts (reset after 60s of uptime)"
+        )]
+        max_restarts: u32,
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "1s",
+            help = "Initial restart delay; doubles per consecutive restart up to 30s"
+        )]
+        backoff: String,
         #[arg(
             last = true,
             allow_hyphen_values = true,
@@ -636,6 +1343,366 @@ NOTES
         )]
         command: Vec<String>,
     },
+    #[command(
+        arg_required_else_help = true,
+        about = "Supervise taps and serve from a stack config",
+        long_about = r#"Read a TOML stack config, ensure its pools exist, and supervise the declared
+`tap` commands and optional `serve` process until interrupted.
+
+Each child is restarted according to its policy, and every start, exit,
+restart, and give-up is appended to the control pool (default `run`) tagged
+`run` and `run:<name>`."#,
+        after_help = r#"EXAMPLES
+  $ plasmite run stack.toml
+  $ plasmite follow run --tag run:api
+
+CONFIG
+  control_pool = "run"             # optional
+
+  [[pools]]
+  name = "events"
+  size = "64M"                     # optional
+
+  [[tap]]
+  name = "api"
+  pool = "api-logs"
+  command = ["./server", "--port", "8080"]
+  tags = ["api"]                   # optional
+  restart = "on-failure"           # never | on-failure | always
+  max_restarts = 5                 # consecutive; resets after 60s up
+  backoff = "1s"                   # doubles per restart, capped at 30s
+
+  [serve]
+  args = ["--bind", "127.0.0.1:9701"]
+
+NOTES
+  - Children run this executable with the same --dir
+  - SIGINT/SIGTERM stop all children (SIGTERM first, then kill after 5s)
+  - Exits 1 if any child gave up after failing"#
+    )]
+    Run {
+        #[arg(
+            help = "Stack config file (TOML)",
+            value_hint = ValueHint::FilePath
+        )]
+        config: PathBuf,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Receive syslog datagrams into a local pool",
+        long_about = r#"Listen for syslog datagrams and append each one to a local pool as a JSON message.
+
+RFC 5424 and RFC 3164 (BSD) formats are parsed into `.data` fields
+(facility, severity, time, host, app, pid, msgid, sd, msg, peer)."#,
+        after_help = r#"EXAMPLES
+  $ plasmite syslog --listen udp:514 --pool syslog --create
+  $ plasmite syslog --listen udp:127.0.0.1:5514 --pool lab --create --create-size 64M
+  $ plasmite follow syslog --where '.data.severity == "err"'
+
+NOTES
+  - Only UDP is supported in v0; `udp:<port>` binds all interfaces
+  - Ports below 1024 usually need elevated privileges
+  - Unparseable datagrams are kept with the raw text in `.data.msg`
+  - `syslog` accepts local pool refs only in v0"#
+    )]
+    Syslog {
+        #[arg(
+            long,
+            value_name = "ADDR",
+            help = "Listen address: udp:<port> or udp:<host>:<port>"
+        )]
+        listen: String,
+        #[arg(long, help = "Pool ref: local name/path")]
+        pool: String,
+        #[arg(long, help = "Create local pool if missing before listening")]
+        create: bool,
+        #[arg(
+            long = "create-size",
+            help = "Pool size when creating (bytes or K/M/G)"
+        )]
+        create_size: Option<String>,
+        #[arg(long, help = "Repeatable tag for received messages")]
+        tag: Vec<String>,
+        #[arg(long, default_value = "fast", help = "Durability mode: fast|flush")]
+        durability: String,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Copy systemd journal entries into a local pool",
+        long_about = r#"Read systemd journal entries via `journalctl -o export` and append each one to a local pool.
+
+Every journal field is kept under `.data.fields`; common fields are lifted to
+`.data` (unit, priority, host, app, pid, msg, cursor). Messages are tagged
+`unit:<unit>` and `priority:<name>` and keep the journal's realtime timestamp."#,
+        after_help = r#"EXAMPLES
+  $ plasmite journal --follow --unit myservice --pool svc-logs --create
+  $ plasmite journal --since -1h --pool recent --create
+  $ plasmite follow svc-logs --tag priority:err
+
+NOTES
+  - Requires `journalctl` on PATH (override with --journalctl)
+  - Without --follow, exits after the current journal contents are copied
+  - `journal` accepts local pool refs only in v0"#
+    )]
+    Journal {
+        #[arg(long, help = "Pool ref: local name/path")]
+        pool: String,
+        #[arg(long, help = "Keep reading new entries as they are written")]
+        follow: bool,
+        #[arg(
+            long,
+            value_name = "UNIT",
+            help = "Only read entries for this systemd unit (repeatable)"
+        )]
+        unit: Vec<String>,
+        #[arg(
+            long,
+            value_name = "TIME",
+            help = "Only read entries at or after this time (journalctl --since syntax)"
+        )]
+        since: Option<String>,
+        #[arg(long, help = "Create local pool if missing before reading")]
+        create: bool,
+        #[arg(
+            long = "create-size",
+            help = "Pool size when creating (bytes or K/M/G)"
+        )]
+        create_size: Option<String>,
+        #[arg(long, help = "Repeatable tag for appended messages")]
+        tag: Vec<String>,
+        #[arg(long, default_value = "fast", help = "Durability mode: fast|flush")]
+        durability: String,
+        #[arg(
+            long,
+            value_name = "PATH",
+            default_value = "journalctl",
+            help = "journalctl executable to run",
+            value_hint = ValueHint::FilePath
+        )]
+        journalctl: String,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Follow a pool and POST messages to an HTTP endpoint",
+        long_about = r#"Follow a local pool and deliver matching messages to a webhook with HTTP POST.
+
+Each message is sent as the same JSON object `follow --jsonl` prints. With `--batch N`
+(N > 1), up to N messages are sent as one JSON array; partial batches are flushed as
+soon as the pool is caught up.
+
+Delivery progress is saved in a cursor file after every acknowledged (2xx) request,
+so a restarted forwarder resumes after the last delivered message without duplicates
+or gaps. Without a saved cursor, delivery starts with new messages."#,
+        after_help = r#"EXAMPLES
+  $ plasmite forward events --to https://example.com/hook
+  $ plasmite forward events --to https://example.com/hook --batch 20 --where '.data.level == "error"'
+  $ plasmite forward events --to http://127.0.0.1:8080/ingest --header 'Authorization: Bearer abc'
+  $ plasmite forward events --to https://example.com/hook --drain      # deliver backlog, then exit
+
+NOTES
+  - Transport errors, 5xx, 408, and 429 are retried with exponential backoff (--retry, --retry-delay)
+  - Other 4xx responses stop the forwarder; the cursor keeps the last delivered seq
+  - Default cursor: <pool dir>/.forward/<pool>-<url digest>.json (override with --cursor-file)
+  - Messages overwritten before delivery are reported as a drop notice on stderr
+  - `forward` accepts local pool refs only in v0"#
+    )]
+    Forward {
+        #[arg(help = "Pool ref: local name/path")]
+        pool: String,
+        #[arg(long, value_name = "URL", help = "Endpoint to POST messages to")]
+        to: String,
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 1,
+            help = "Max messages per request (N > 1 sends a JSON array)"
+        )]
+        batch: usize,
+        #[arg(
+            long = "where",
+            value_name = "EXPR",
+            help = "Only forward messages matching this boolean expression (repeatable; AND across repeats)"
+        )]
+        where_expr: Vec<String>,
+        #[arg(
+            long = "where-jsonpath",
+            value_name = "PATH",
+            help = "Only forward messages this JSONPath selects anything from (repeatable; needs the `jsonpath` build feature)"
+        )]
+        where_jsonpath: Vec<String>,
+        #[arg(
+            long = "tag",
+            value_name = "TAG",
+            help = "Only forward messages that have this tag (repeatable; AND across repeats)"
+        )]
+        tags: Vec<String>,
+        #[arg(
+            long,
+            default_value_t = 5,
+            help = "Retry count for transient delivery failures"
+        )]
+        retry: u32,
+        #[arg(
+            long = "retry-delay",
+            value_name = "DURATION",
+            default_value = "500ms",
+            help = "Initial delay between retries; doubles per attempt (max 30s)"
+        )]
+        retry_delay: String,
+        #[arg(
+            long = "header",
+            value_name = "NAME: VALUE",
+            help = "Extra request header (repeatable)"
+        )]
+        header: Vec<String>,
+        #[arg(
+            long = "cursor-file",
+            value_name = "PATH",
+            help = "Where to persist the last delivered seq",
+            value_hint = ValueHint::FilePath
+        )]
+        cursor_file: Option<PathBuf>,
+        #[arg(long, help = "Deliver pending messages and exit instead of following")]
+        drain: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Manage append hooks run by `plasmite hookd`",
+        long_about = r#"Register a shell command or HTTP callback to run for every new message in a pool.
+
+Hooks are stored under the pool directory and run by `plasmite hookd`, one long-lived
+follower for every hook, so automations need no hand-written follow loops."#,
+        after_help = r#"EXAMPLES
+  $ plasmite hook add deploys --exec 'notify-send "deploy" {}'
+  $ plasmite hook add errors --post https://example.com/hook --where '.data.level == "error"'
+  $ plasmite hook list
+  $ plasmite hook remove h2
+  $ plasmite hookd
+
+NOTES
+  - Hooks fire for messages appended after `hook add`; earlier messages are not replayed
+  - --exec commands get the message JSON on stdin, and in place of `{}` (shell-quoted)"#
+    )]
+    Hook {
+        #[command(subcommand)]
+        command: HookCommand,
+    },
+    #[command(
+        about = "Run registered append hooks",
+        long_about = r#"Follow every pool that has hooks and run each hook for new matching messages.
+
+The registry is re-read on every poll, so `hook add` and `hook remove` take effect
+without a restart. A hook only moves past a message once it was delivered: a command
+exiting non-zero or a failed POST is retried with backoff (1s doubling to 5m), and
+other hooks keep running meanwhile. Failures are reported as notices on stderr."#,
+        after_help = r#"EXAMPLES
+  $ plasmite hookd
+  $ plasmite hookd --interval 2s
+  $ plasmite hookd --once
+
+NOTES
+  - --once handles pending messages (one attempt per hook) and exits; it exits 1 when a hook failed
+  - Run one hookd per pool directory"#
+    )]
+    Hookd {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "500ms",
+            help = "How often to check pools and the hook registry"
+        )]
+        interval: String,
+        #[arg(long, help = "Handle pending messages once and exit")]
+        once: bool,
+    },
+    #[command(
+        about = "Record pool metrics into the _stats pool on an interval",
+        long_about = r#"Snapshot every pool's metrics into the built-in `_stats` pool, so growth
+trends can be graphed from plasmite itself.
+
+Each interval appends one message per pool, tagged `stats`, with pool, count, used_bytes,
+free_bytes, used_percent, newest_seq, and append_rate (messages per second since the
+previous snapshot; null on the first). `_stats` is created on first use."#,
+        after_help = r#"EXAMPLES
+  $ plasmite statsd
+  $ plasmite statsd --interval 10s
+  $ plasmite statsd --once
+  $ plasmite follow _stats --where '.data.pool == "events"' --fields .data.count
+
+NOTES
+  - `serve --stats-pool` runs the same recorder inside the server
+  - Failed snapshots are reported as `stats_failed` notices and retried next interval
+  - _stats is a normal 8M pool; older snapshots roll off as it fills"#
+    )]
+    Statsd {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "1m",
+            help = "How often to record a snapshot"
+        )]
+        interval: String,
+        #[arg(long, help = "Record one snapshot and exit")]
+        once: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Send a command to a follower started with --control-socket",
+        long_about = r#"Adjust a running `follow --control-socket PATH` without restarting it.
+
+`pause` stops reading (the follower keeps its place), `resume` continues from there,
+`where` replaces the follower's --where filters, and `stats` reports what it has
+emitted and filtered so far. Every command prints the follower's state afterwards."#,
+        after_help = r#"EXAMPLES
+  $ plasmite ctl /tmp/f.sock stats
+  $ plasmite ctl /tmp/f.sock pause
+  $ plasmite ctl /tmp/f.sock resume
+  $ plasmite ctl /tmp/f.sock where '.data.level == "error"' '.data.svc == "api"'
+  $ plasmite ctl /tmp/f.sock where --clear
+
+NOTES
+  - An invalid --where expression is rejected and the running filters stay as they were
+  - `where` replaces --where-jsonpath predicates too"#
+    )]
+    Ctl {
+        #[arg(help = "Control socket path given to `follow --control-socket`", value_hint = ValueHint::FilePath)]
+        socket: PathBuf,
+        #[command(subcommand)]
+        command: CtlCommand,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Stream a pool into a named pipe (FIFO)",
+        long_about = r#"Create (or reuse) a FIFO and stream a local pool into it as JSON Lines, for
+legacy programs that can only read files or pipes.
+
+Whoever opens the FIFO receives messages appended from that moment on, in the same
+shape `follow --jsonl` prints. When the reader goes away the bridge keeps running and
+waits for the next one; messages appended while nobody is reading are skipped."#,
+        after_help = r#"EXAMPLES
+  $ plasmite bridge events --fifo /tmp/events.pipe
+  $ cat /tmp/events.pipe | legacy-consumer
+  $ plasmite bridge events --fifo /tmp/events.pipe --recreate
+
+NOTES
+  - Unix only; the FIFO is created with mode 0644
+  - A FIFO created by the bridge is removed on exit (Ctrl-C / SIGTERM)
+  - --recreate replaces an existing file or FIFO at the path
+  - Reader connects/disconnects are reported as notices on stderr"#
+    )]
+    Bridge {
+        #[arg(help = "Pool ref: local name/path")]
+        pool: String,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "FIFO to stream into (created if missing)",
+            value_hint = ValueHint::FilePath
+        )]
+        fifo: PathBuf,
+        #[arg(long, help = "Replace an existing file or FIFO at --fifo")]
+        recreate: bool,
+    },
     #[command(
         arg_required_else_help = true,
         about = "Send and follow from one command",
@@ -649,7 +1716,9 @@ NOTES
   Duplex exits when stdin ends (EOF) or when the receive side ends (e.g. timeout/error).
 
 Notes:
-- Remote refs do not support `--create` or `--since` (use `--tail` for remote)."#
+- Remote refs do not support `--create` or `--since` (use `--tail` for remote).
+- `--record FILE` writes a JSONL transcript with a `sent` or `recv` line per message;
+  play it back with `plasmite replay-transcript FILE`."#
     )]
     Duplex {
         #[arg(help = "Pool ref: local name/path or shorthand URL http(s)://host:port/<pool>")]
@@ -689,6 +1758,47 @@ Notes:
         since: Option<String>,
         #[arg(long, help = "Also emit your own messages in the receive stream")]
         echo_self: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Record sent and received messages to a JSONL transcript (see replay-transcript)",
+            value_hint = ValueHint::FilePath
+        )]
+        record: Option<PathBuf>,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Play back a recorded duplex session",
+        long_about = r#"Render a transcript written by `plasmite duplex --record` with its original timing.
+
+Sent messages are marked `>` and received messages `<`, each prefixed with its offset from
+the start of the session."#,
+        after_help = r#"EXAMPLES
+  $ plasmite duplex chat --me alice --record session.jsonl
+  $ plasmite replay-transcript session.jsonl
+  $ plasmite replay-transcript session.jsonl --speed 2
+  $ plasmite replay-transcript session.jsonl --speed 0 --jsonl | jq '.message.data'
+
+NOTES
+  - `--speed 1` is realtime, `2` is twice as fast, `0` prints without delay
+  - `--jsonl` emits the transcript records unchanged, still paced by --speed
+  - Record types other than `sent`/`recv` (including the `start` line) are skipped"#
+    )]
+    ReplayTranscript {
+        #[arg(
+            help = "Transcript file written by duplex --record",
+            value_hint = ValueHint::FilePath
+        )]
+        file: PathBuf,
+        #[arg(
+            long,
+            value_name = "SPEED",
+            default_value_t = 1.0,
+            help = "Playback speed (1 = realtime, 2 = 2x, 0.5 = half; 0 = no delay)"
+        )]
+        speed: f64,
+        #[arg(long, help = "Emit transcript records as JSON Lines")]
+        jsonl: bool,
     },
     #[command(
         arg_required_else_help = true,
@@ -698,33 +1808,366 @@ Notes:
   $ plasmite doctor foo
   $ plasmite doctor --all
   $ plasmite doctor --all --json
+  $ plasmite doctor http://host:9700/foo --deep
+  $ plasmite doctor foo --repair
 
 NOTES
   - Human-readable output is the default.
   - Use --json for machine-readable output.
+  - Remote refs run the checks server-side (GET /v0/pools/<pool>/validate).
+  - --repair needs a pool created with --intent-journal; it rolls back an append whose
+    writer died mid-write. After power loss only --durability flush appends are covered.
   - Exits nonzero when corruption is detected."#
     )]
     Doctor {
-        #[arg(help = "Pool name or path", required = false)]
+        #[arg(
+            help = "Pool name, path, or remote URL (http(s)://host:port/<pool>)",
+            required = false
+        )]
         pool: Option<String>,
         #[arg(long, help = "Validate all pools in the pool directory")]
         all: bool,
+        #[arg(long, help = "Also decode retained payloads with the pool codec")]
+        deep: bool,
+        #[arg(
+            long = "max-frames",
+            value_name = "N",
+            requires = "deep",
+            help = "With --deep, decode only the newest N payloads (remote servers cap this too)"
+        )]
+        max_frames: Option<u64>,
+        #[arg(
+            long,
+            help = "Roll back an interrupted append recorded in the intent journal (local pools)"
+        )]
+        repair: bool,
         #[arg(long, help = "Emit JSON instead of human-readable output")]
         json: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Read bearer token from file for remote refs",
+            value_hint = ValueHint::FilePath,
+            help_heading = "Remote auth/TLS"
+        )]
+        token_file: Option<PathBuf>,
+        #[arg(
+            long = "tls-ca",
+            value_name = "PATH",
+            help = "Trust this PEM CA/certificate for remote TLS",
+            value_hint = ValueHint::FilePath,
+            help_heading = "Remote auth/TLS"
+        )]
+        tls_ca: Option<PathBuf>,
+        #[arg(
+            long = "tls-skip-verify",
+            help = "Disable remote TLS certificate verification (unsafe; dev-only)",
+            help_heading = "Remote auth/TLS"
+        )]
+        tls_skip_verify: bool,
     },
     #[command(
-        about = "Print version info as JSON",
-        long_about = r#"Emit version info as JSON (stable, machine-readable)."#,
+        arg_required_else_help = true,
+        about = "Report sequence gaps in a pool",
+        long_about = r#"Scan a local pool's frames and report every non-contiguous seq range.
+
+Gaps are classified as:
+- overwritten: seqs evicted by normal ring wraparound (below the oldest retained seq)
+- missing: seqs inside the retained range (or claimed by the header) that cannot be read,
+  which indicates corruption"#,
         after_help = r#"EXAMPLES
-  $ plasmite version"#
+  $ plasmite audit-gaps foo
+  $ plasmite audit-gaps foo --since 1h
+  $ plasmite audit-gaps foo --json | jq '.gaps'
+
+NOTES
+  - Exits nonzero when missing (corrupt) spans or out-of-order seqs are found
+  - With --since, the audit window starts at the first message at or after that time
+  - Run while writers are idle; heavy concurrent writes can force a Busy retry"#
     )]
-    Version,
+    AuditGaps {
+        #[arg(help = "Pool name or path")]
+        pool: String,
+        #[arg(
+            long,
+            help = "Only audit messages at or after this time (RFC 3339 or relative like 5m)"
+        )]
+        since: Option<String>,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
     #[command(
         arg_required_else_help = true,
-        about = "Generate shell completions",
-        long_about = r#"Generate shell completion scripts.
+        about = "Dump raw frame bytes around a problem location",
+        long_about = r#"Show the raw 64-byte frame header at a seq or ring offset in hex and annotated form,
+with computed checksums, neighboring frame boundaries, and the index slots around it.
 
-Prints a completion script for the given shell to stdout.
+Offsets are ring-relative, matching the `offset` that `plasmite doctor` reports. An offset
+may point anywhere in the ring; undecodable bytes are annotated rather than rejected."#,
+        after_help = r#"EXAMPLES
+  $ plasmite explain foo --seq 42
+  $ plasmite explain foo --offset 8192
+  $ plasmite explain foo --seq 42 --json | jq '.index'
+
+NOTES
+  - Local pools only; the pool is opened read-only
+  - Neighbor boundaries come from walking committed frames forward from the tail
+  - Index status: ok, stale, reused, empty, out_of_range, or unverified"#
+    )]
+    Explain {
+        #[arg(help = "Pool name or path")]
+        pool: String,
+        #[arg(
+            long,
+            conflicts_with = "offset",
+            required_unless_present = "offset",
+            help = "Explain the frame holding this seq"
+        )]
+        seq: Option<u64>,
+        #[arg(
+            long,
+            value_name = "BYTES",
+            help = "Explain the bytes at this ring-relative offset"
+        )]
+        offset: Option<u64>,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Show message counts per time bucket",
+        long_about = r#"Count a local pool's messages per fixed time bucket and print a sparkline plus
+an ASCII histogram, to spot bursts and quiet gaps at a glance.
+
+Only frame timestamps are read (payloads are never decoded), and the scan starts at the
+first message inside the window."#,
+        after_help = r#"EXAMPLES
+  $ plasmite activity foo
+  $ plasmite activity foo --bucket 1m --since 1h
+  $ plasmite activity foo --since 7d --bucket 1h --json | jq '.buckets'
+
+NOTES
+  - Buckets align to multiples of --bucket since the Unix epoch; empty buckets count 0
+  - Without --since, the window starts at the oldest retained message
+  - The window always ends now"#
+    )]
+    Activity {
+        #[arg(help = "Pool name or path")]
+        pool: String,
+        #[arg(long, default_value = "5m", help = "Bucket width (e.g. 10s, 5m, 1h)")]
+        bucket: String,
+        #[arg(long, help = "Window start (RFC 3339 or relative like 24h)")]
+        since: Option<String>,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Infer the shape of a pool's message data",
+        long_about = r#"Scan the newest messages of a local pool and report every field seen in `.data`:
+its types, how often it is present or missing, whether it is ever null, and its most
+frequent scalar values. `--json` adds an inferred JSON Schema (draft 2020-12)."#,
+        after_help = r#"EXAMPLES
+  $ plasmite schema events
+  $ plasmite schema events --sample 10000
+  $ plasmite schema events --all --json | jq '.schema'
+
+NOTES
+  - Paths are jq-style: `.user.id`, `.items[]` (all array elements share one path)
+  - Missing counts messages whose enclosing object lacked the field
+  - Top values cover strings, numbers, and booleans; very high-cardinality fields are capped"#
+    )]
+    Schema {
+        #[arg(help = "Pool name or path")]
+        pool: String,
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = DEFAULT_SCHEMA_SAMPLE,
+            conflicts_with = "all",
+            help = "Number of newest messages to sample (0 scans all)"
+        )]
+        sample: u64,
+        #[arg(long, help = "Scan every retained message instead of sampling")]
+        all: bool,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Run SQL over pool messages with embedded DuckDB",
+        long_about = r#"Load local pools into an in-memory DuckDB database and run one SQL query.
+
+Each --pool becomes a table named after the pool (non-alphanumerics become `_`) with
+columns seq, time (UTC timestamp), tags (VARCHAR[]), and data (JSON). --since/--until
+load only a time slice. Pool files are read once and never modified."#,
+        after_help = r#"EXAMPLES
+  $ plasmite sql "SELECT count(*) FROM events" --pool events
+  $ plasmite sql "SELECT data->>'level' AS level, count(*) FROM events GROUP BY 1" --pool events --since 1h
+  $ plasmite sql "SELECT * FROM a JOIN b USING (seq)" --pool a --pool b --json
+
+NOTES
+  - Requires a build with `--features sql`
+  - Use DuckDB JSON operators on data: data->>'field', json_extract(data, '$.a.b')
+  - --json prints {"columns": [...], "rows": [[...], ...]}"#
+    )]
+    Sql {
+        #[arg(help = "SQL query to run")]
+        query: String,
+        #[arg(
+            long = "pool",
+            value_name = "POOL",
+            required = true,
+            help = "Pool to load as a table (repeatable)"
+        )]
+        pools: Vec<String>,
+        #[arg(
+            long,
+            help = "Only load messages at or after this time (RFC 3339 or relative like 5m)"
+        )]
+        since: Option<String>,
+        #[arg(
+            long,
+            help = "Only load messages at or before this time (RFC 3339 or relative like 5m)"
+        )]
+        until: Option<String>,
+        #[arg(long, help = "Emit JSON instead of a table")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Compare the messages of two pools or a pool and a dump",
+        long_about = r#"Report messages present in one source but not the other.
+
+Each side may be a local pool, a remote shorthand ref (http(s)://host:port/<pool>), or a
+JSONL dump file (one message envelope or bare data object per line, e.g. `follow --jsonl`
+output). Messages match by `--key` when given, otherwise by a hash of their tags and data,
+so mirrors that assign different seqs still compare equal."#,
+        after_help = r#"EXAMPLES
+  $ plasmite diff orders http://mirror:9700/orders
+  $ plasmite diff orders backup.jsonl --key '.data.id'
+  $ plasmite diff orders replica --json | jq '.only_in_a'
+
+NOTES
+  - Exits 0 when both sides hold the same messages, 1 when they differ
+  - Duplicates count: two copies on one side and one on the other leave one unmatched
+  - Messages where --key yields null share the key `null`"#
+    )]
+    Diff {
+        #[arg(help = "First source: pool ref or JSONL dump path")]
+        a: String,
+        #[arg(help = "Second source: pool ref or JSONL dump path")]
+        b: String,
+        #[arg(
+            long,
+            value_name = "EXPR",
+            help = "Match messages by this key expression instead of content (e.g. '.data.id')"
+        )]
+        key: Option<String>,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Back up a pool: a full copy first, then incremental deltas",
+        long_about = r#"Back up a local pool into a directory.
+
+The first run writes a validated full copy (`base.plasmite`). Later runs with
+--incremental append only the messages written since the previous backup as a
+`delta-<first>-<last>.plsd` file, tracked by a `cursor.json` in the directory."#,
+        after_help = r#"EXAMPLES
+  $ plasmite backup events --to /backups/events
+  $ plasmite backup events --to /backups/events --incremental   # e.g. hourly from cron
+  $ plasmite restore /backups/events --to events-restored
+
+NOTES
+  - Back up more often than the pool turns over: messages the ring overwrites between
+    runs cannot be recovered and are reported as missed
+  - Without --incremental, a directory that already holds a backup is refused"#
+    )]
+    Backup {
+        #[arg(help = "Pool name or path")]
+        pool: String,
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Backup directory",
+            value_hint = ValueHint::DirPath
+        )]
+        to: PathBuf,
+        #[arg(
+            long,
+            help = "Append a delta of messages newer than the previous backup"
+        )]
+        incremental: bool,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Rebuild a pool from a backup directory",
+        long_about = r#"Restore a pool from `plasmite backup` output: copy the base, then apply deltas in order.
+
+Restoring into a pool that an earlier restore created catches it up, applying only
+deltas newer than its `<pool>.restore.json` cursor."#,
+        after_help = r#"EXAMPLES
+  $ plasmite restore /backups/events --to events-restored --dry-run
+  $ plasmite restore /backups/events --to events-restored
+  $ plasmite restore /backups/events --to events-restored --json   # catch up later
+
+NOTES
+  - Messages keep their original timestamps and payload bytes
+  - Refuses to write into an existing pool that was not created by restore
+  - --dry-run runs the same checks and reads every delta, then reports what would be applied"#
+    )]
+    Restore {
+        #[arg(help = "Backup directory", value_hint = ValueHint::DirPath)]
+        from: PathBuf,
+        #[arg(long, value_name = "POOL", help = "Pool name or path to restore into")]
+        to: String,
+        #[arg(
+            long,
+            help = "Show the deltas, messages, and seq a restore would reach, without writing"
+        )]
+        dry_run: bool,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        about = "Interactive terminal dashboard for local pools",
+        long_about = r#"Open a full-screen dashboard: pools with message counts and live rates on the left,
+a scrollable tail of the selected pool on the right."#,
+        after_help = r#"EXAMPLES
+  $ plasmite ui
+  $ plasmite ui chat              # start with chat selected
+
+KEYS
+  ↑/↓ (j/k)     select pool
+  PgUp/PgDn     scroll the tail pane; End resumes following
+  /             filter tail lines by substring (Enter apply, Esc cancel)
+  q, Esc        quit
+
+NOTES
+  - Local pools only (reads --dir); requires an interactive terminal
+  - Read-only: the dashboard never writes to pools"#
+    )]
+    Ui {
+        #[arg(help = "Pool to select on startup")]
+        pool: Option<String>,
+    },
+    #[command(
+        about = "Print version info as JSON",
+        long_about = r#"Emit version info as JSON (stable, machine-readable)."#,
+        after_help = r#"EXAMPLES
+  $ plasmite version"#
+    )]
+    Version,
+    #[command(
+        arg_required_else_help = true,
+        about = "Generate shell completions",
+        long_about = r#"Generate shell completion scripts.
+
+Prints a completion script for the given shell to stdout.
 Install the generated file in your shell's completion directory (or source it)
 to enable tab completion."#,
         after_help = r#"EXAMPLES
@@ -770,9 +2213,29 @@ Pools include an inline sequence index by default for fast `get(seq)` lookups."#
   $ plasmite pool create --size 8M bar baz quux
   $ plasmite pool create --size 8M --index-capacity 4096 indexed
   $ plasmite pool create --json foo
+  $ plasmite pool create --hybrid-timestamps laptop-events
+  $ plasmite pool create --size 1G --preallocate ticks
+  $ plasmite pool create --dedupe-window 64 webhooks
+  $ plasmite pool create --size 64M --max-messages 10000 recent-events
+  $ plasmite pool create --size 1G --bloom-filter audit
 
 NOTES
-  - Sizes: 64K, 1M, 8M, 1G (K/M/G are 1024-based)"#
+  - Sizes: 64K, 1M, 8M, 1G (K/M/G are 1024-based)
+  - Pool files are sparse: disk blocks are allocated as the ring is written. On a full
+    disk, writing an unallocated page of the mmap raises SIGBUS and kills the writer.
+    Use --preallocate in production to reserve every block at create time.
+  - --intent-journal keeps a tiny `<pool>.intent` sidecar of recent appends so `plasmite
+    doctor` can tell a writer that died mid-append from media corruption, and `plasmite
+    doctor --repair` can roll that append back.
+  - --hybrid-timestamps keeps frame timestamps strictly increasing even if the wall clock
+    jumps backwards (sleep/wake, NTP); `plasmite doctor` warns about regressions otherwise.
+  - --dedupe-window N acknowledges a payload identical to one of the last N appends (tags
+    included) with the original seq instead of storing it again.
+  - --max-messages N keeps only the newest N messages, whatever their sizes; the pool size
+    still bounds retention too, so whichever limit is hit first drops the oldest.
+  - --bloom-filter stamps each frame with a small filter of the message's string values;
+    `follow --where '.data.level == "error"'` then skips frames that cannot hold "error"
+    without decoding them. Predicates without `==` string comparisons scan as usual."#
     )]
     Create {
         #[arg(required = true, help = "Pool name(s) to create")]
@@ -780,64 +2243,549 @@ NOTES
         #[arg(long, help = "Pool size (bytes or K/M/G)")]
         size: Option<String>,
         #[arg(
-            long = "index-capacity",
-            help = "Inline index slot count (default: auto-size; 0 disables index)"
+            long = "index-capacity",
+            help = "Inline index slot count (default: auto-size; 0 disables index)"
+        )]
+        index_capacity: Option<u32>,
+        #[arg(
+            long = "hybrid-timestamps",
+            help = "Stamp frames with max(wall clock, previous + 1ns) so timestamps never go backwards"
+        )]
+        hybrid_timestamps: bool,
+        #[arg(
+            long,
+            help = "Allocate the whole file up front instead of creating it sparse"
+        )]
+        preallocate: bool,
+        #[arg(
+            long = "intent-journal",
+            help = "Record recent append intents in a sidecar so doctor can diagnose crashes"
+        )]
+        intent_journal: bool,
+        #[arg(
+            long = "dedupe-window",
+            value_name = "N",
+            default_value_t = 0,
+            help = "Skip re-storing payloads identical to one of the last N appends (max 248)"
+        )]
+        dedupe_window: u32,
+        #[arg(
+            long = "max-messages",
+            value_name = "N",
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Keep at most the newest N messages, independent of their sizes"
+        )]
+        max_messages: Option<u64>,
+        #[arg(
+            long = "max-frame",
+            value_name = "SIZE",
+            help = "Split messages larger than SIZE bytes (or K/M) into chunk frames read back whole"
+        )]
+        max_frame: Option<String>,
+        #[arg(
+            long = "max-message",
+            value_name = "SIZE",
+            requires = "max_frame",
+            help = "Reject chunked messages larger than SIZE bytes (or K/M); needs --max-frame"
+        )]
+        max_message: Option<String>,
+        #[arg(
+            long = "bloom-filter",
+            help = "Stamp frames with string-value bloom filters so --where scans skip non-matches"
+        )]
+        bloom_filter: bool,
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Start from a template (chat, metrics, worklog, or ~/.plasmite/templates.toml); flags override it"
+        )]
+        template: Option<String>,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Show pool metadata and bounds",
+        long_about = r#"Show pool size, bounds, and metrics in human-readable format by default."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool info foo
+  $ plasmite pool info foo --json"#
+    )]
+    Info {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Print a pool's configuration as a `pool apply` manifest",
+        long_about = r#"Print the settings `pool apply` manages for a pool: size, index capacity,
+retention, chunking, timestamp and bloom-filter flags, plus the default tags and required
+fields `feed` enforces. Redirect the TOML into a checked-in pools.toml."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool describe events > pools.toml
+  $ plasmite pool describe events --format json
+
+NOTES
+  - Sizes print in --size syntax (64M) when they are whole K/M/G, otherwise in bytes.
+  - tags and required appear only for pools that have them (templated or applied pools)."#
+    )]
+    Describe {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(long, value_enum, default_value = "toml", help = "Output format")]
+        format: pool_manifest::DescribeFormat,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Create or update pools from a TOML manifest and report drift",
+        long_about = r#"Make the pools described in a manifest exist with the declared settings.
+
+Missing pools are created. Existing pools get their tags and required fields updated.
+Size, index capacity, retention, chunking, and flags are fixed when a pool is created, so
+differences there are reported as drift rather than changed."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool apply pools.toml
+  $ plasmite pool apply pools.toml --dry-run --json
+
+MANIFEST
+  [pools.events]
+  size = "64M"
+  max_messages = 100000
+  bloom_filter = true
+  tags = ["events"]
+  required = ["level", "msg"]
+
+NOTES
+  - Keys: size, index_capacity, max_messages, max_frame, max_message, hybrid_timestamps,
+    bloom_filter, tags, required. Keys left out are not checked or changed.
+  - Pools not named in the manifest are left alone.
+  - Exits 1 when any pool has drift, so CI can gate on it."#
+    )]
+    Apply {
+        #[arg(help = "Manifest file", value_hint = ValueHint::FilePath)]
+        file: PathBuf,
+        #[arg(
+            long,
+            help = "Report what would change without creating or updating pools"
+        )]
+        dry_run: bool,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Delete one or more pool files",
+        long_about = r#"Delete one or more pool files (destructive unless --trash is used).
+
+Pools that other processes have open are refused with the holders listed; --force deletes
+anyway. --trash moves pools into a .trash directory next to them for `pool restore`."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool delete foo
+  $ plasmite pool delete foo bar baz
+  $ plasmite pool delete --json foo bar
+  $ plasmite pool delete --trash foo && plasmite pool restore foo
+  $ plasmite pool delete --dry-run foo bar
+
+NOTES
+  - Human-readable output is the default.
+  - Use --json for machine-readable output.
+  - Best effort: attempts all deletes and reports per-pool failures.
+  - Busy pools fail with exit code 5 unless --force is given.
+  - In-use detection lists holding processes on Linux; elsewhere only the append lock is checked.
+  - Exits non-zero if any requested pool failed to delete.
+  - --dry-run runs the same checks and lists each pool's files, bytes, and seq range without deleting; it exits as the real delete would."#
+    )]
+    Delete {
+        #[arg(required = true, help = "Pool name(s) or path(s)")]
+        names: Vec<String>,
+        #[arg(long, help = "Delete even if other processes have the pool open")]
+        force: bool,
+        #[arg(
+            long,
+            help = "Move pools to a .trash directory instead of deleting them"
+        )]
+        trash: bool,
+        #[arg(
+            long,
+            help = "Show the files, bytes, and seq ranges that would be deleted, without deleting"
+        )]
+        dry_run: bool,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Restore pools deleted with --trash",
+        after_help = r#"EXAMPLES
+  $ plasmite pool restore foo
+  $ plasmite pool restore --json foo
+
+NOTES
+  - Fails if a pool already exists under the same name.
+  - Repeated `--trash` deletes of one name keep every copy; restore takes the newest first."#
+    )]
+    Restore {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        about = "Delete empty or long-idle pools",
+        long_about = r#"Find pools in the pool directory that were never written, or whose last activity
+(newest message or file modification) is older than --older-than, and delete them.
+
+Interactive runs show the candidates and ask before deleting; scripts pass --yes.
+Pools that other processes have open are skipped, as are pools that fail to open."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool gc --dry-run
+  $ plasmite pool gc --older-than 7d
+  $ plasmite pool gc --empty-only --yes
+  $ plasmite pool gc --older-than 90d --yes --json | jq '.deleted'
+
+NOTES
+  - Default --older-than is 30d (units: s, m, h, d)
+  - Subdirectories are scanned; dot-directories such as .trash are not
+  - Exits non-zero if any selected pool failed to delete"#
+    )]
+    Gc {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            default_value = "30d",
+            help = "Collect pools idle for at least this long (e.g. 12h, 30d)"
+        )]
+        older_than: String,
+        #[arg(long, help = "Only collect pools that were never written")]
+        empty_only: bool,
+        #[arg(long, help = "Report what would be deleted without deleting")]
+        dry_run: bool,
+        #[arg(long, short = 'y', help = "Delete without asking for confirmation")]
+        yes: bool,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Copy a live pool to a consistent snapshot file",
+        long_about = r#"Copy a pool file while briefly holding its append lock, then validate the copy.
+
+Unlike `cp` during writes, the snapshot never contains a half-written frame or header,
+so it can be opened, tailed, or attached to a bug report as-is."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool snapshot events --out events-backup.plasmite
+  $ plasmite pool snapshot events --out /tmp/repro.plasmite --json
+
+NOTES
+  - Writers wait for the copy (roughly the time to read the pool file once).
+  - The copy is validated like `plasmite doctor` before it appears at --out.
+  - Fails if --out already exists; the intent-journal sidecar is not copied."#
+    )]
+    Snapshot {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Snapshot file to write",
+            value_hint = ValueHint::FilePath
+        )]
+        out: PathBuf,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Refuse appends to a pool until it is unfrozen",
+        long_about = r#"Set the pool's frozen flag so every append fails with a busy error.
+
+Use it to quiesce writers during a migration, snapshot, or incident. The flag lives in the
+pool header, so it applies to every writer: local commands, bindings, and `plasmite serve`
+(which answers 423). Reads, follows, and exports keep working."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool freeze events
+  $ plasmite pool freeze events && plasmite pool snapshot events --out events.bak
+  $ plasmite pool freeze events --json
+
+NOTES
+  - Appends already holding the append lock finish; later ones exit with code 5.
+  - Freezing a frozen pool is a no-op. Snapshots of a frozen pool are frozen too."#
+    )]
+    Freeze {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Accept appends to a frozen pool again",
+        after_help = r#"EXAMPLES
+  $ plasmite pool unfreeze events
+  $ plasmite pool unfreeze events --json
+
+NOTES
+  - Unfreezing a pool that is not frozen is a no-op."#
+    )]
+    Unfreeze {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Write a hash manifest of a pool's frames for later tamper checks",
+        long_about = r#"Hash every retained frame (seq, timestamp, flags, payload) into a Merkle-style manifest.
+
+Keep the manifest somewhere the pool's writers cannot reach. `plasmite pool verify` later
+proves the sealed frames were not changed, for pools that serve as lightweight ledgers."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool seal ledger --out ledger-seal.json
+  $ plasmite pool seal ledger > ledger-seal.json
+
+NOTES
+  - Without --out, the manifest is printed to stdout.
+  - The manifest holds one SHA-256 leaf per frame plus their Merkle root.
+  - Fails with exit 2 on a pool with no messages."#
+    )]
+    Seal {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Write the manifest to PATH instead of stdout",
+            value_hint = ValueHint::FilePath
+        )]
+        out: Option<PathBuf>,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Check a pool against a manifest from `pool seal`",
+        long_about = r#"Re-hash the frames a manifest sealed and compare them with its leaves.
+
+Frames appended after the seal are not checked. Sealed frames the ring has since
+overwritten are reported as evicted, which is not tampering."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool verify ledger --manifest ledger-seal.json
+  $ plasmite pool verify ledger --manifest ledger-seal.json --json
+
+NOTES
+  - Exits 7 when a sealed frame changed, a sealed seq is missing, or the manifest was edited.
+  - Freeze the pool or verify a snapshot if writers may wrap the ring during the check."#
+    )]
+    Verify {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Manifest written by `pool seal`",
+            value_hint = ValueHint::FilePath
+        )]
+        manifest: PathBuf,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Show processes reading or writing a pool",
+        long_about = r#"Show who is using a pool.
+
+Lists readers and writers that registered themselves (follow, forward, feed, tap, syslog,
+journal) with pid, start time, and cursor position, plus any other processes holding the
+pool file open and whether the append lock is currently held."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool who foo
+  $ plasmite pool who foo --json
+
+NOTES
+  - Registrations live in a `<pool>.who/` sidecar and vanish when the process exits.
+  - Reader cursors are refreshed at most once per second.
+  - Unregistered open-file holders are detected on Linux only."#
+    )]
+    Who {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        about = "List pools in the pool directory",
+        long_about = r#"List pools in the pool directory.
+
+Prints a human-readable table by default. Use --json for machine-readable output."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool list
+  $ plasmite pool list --json
+
+NOTES
+  - Human-readable output is the default.
+  - Use --json for machine-readable output.
+  - Non-.plasmite files are ignored.
+  - Pools that cannot be read include an error field."#
+    )]
+    List {
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Export a pool's messages to JSONL, Parquet, or SQLite",
+        long_about = r#"Export every retained message, oldest first.
+
+JSONL writes one envelope per line (stdout unless --out is given). Parquet writes
+seq, time (UTC timestamp, ns), tags, and data as a JSON string; --columns replaces
+data with one typed column per .data path, for loading straight into DuckDB or pandas.
+SQLite writes a messages(seq, time, tags, data_json) table indexed by time; time is UTC
+nanoseconds and tags a JSON array. `plasmite pool import` reads it back."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool export events > events.jsonl
+  $ plasmite pool export events --format parquet --out events.parquet
+  $ plasmite pool export events --format parquet --out events.parquet --columns level,user.id
+  $ plasmite pool export events --format sqlite --out events.db
+
+NOTES
+  - Parquet output requires a build with `--features parquet`; SQLite needs `--features sqlite`.
+  - Column types are inferred (boolean, int64, double); mixed or nested values become text.
+  - SQLite export refuses to overwrite an existing --out file."#
+    )]
+    Export {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(long, value_enum, default_value = "jsonl", help = "Output format")]
+        format: pool_export::ExportFormat,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Output file (required for parquet and sqlite; JSONL defaults to stdout)",
+            value_hint = ValueHint::FilePath
+        )]
+        out: Option<PathBuf>,
+        #[arg(
+            long,
+            value_name = "PATHS",
+            help = "Comma-separated .data paths to write as Parquet columns instead of data"
+        )]
+        columns: Option<String>,
+    },
+    #[command(
+        arg_required_else_help = true,
+        about = "Append the messages of a SQLite export to a pool",
+        long_about = r#"Read a messages table written by `pool export --format sqlite` and append each row.
+
+Rows are appended in seq order with their original timestamps and tags; the pool assigns
+new seqs. The pool must already exist, so its size and settings stay your choice."#,
+        after_help = r#"EXAMPLES
+  $ plasmite pool create restored --size 64M
+  $ plasmite pool import restored --from events.db --dry-run
+  $ plasmite pool import restored --from events.db
+  $ plasmite pool import restored --from events.db --json
+
+NOTES
+  - Requires a build with `--features sqlite`.
+  - Importing into a pool smaller than the export overwrites its oldest messages;
+    `--dry-run` reads and checks every row and reports what would be appended.
+  - Rows are streamed; a malformed row stops the import, and rows before it stay appended."#
+    )]
+    Import {
+        #[arg(help = "Pool name or path")]
+        name: String,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "SQLite file written by `pool export --format sqlite`",
+            value_hint = ValueHint::FilePath
+        )]
+        from: PathBuf,
+        #[arg(
+            long,
+            help = "Show the row count and exported seq range that would be appended, without appending"
         )]
-        index_capacity: Option<u32>,
+        dry_run: bool,
         #[arg(long, help = "Emit JSON instead of human-readable output")]
         json: bool,
     },
+}
+
+#[derive(Subcommand)]
+enum HookCommand {
     #[command(
         arg_required_else_help = true,
-        about = "Show pool metadata and bounds",
-        long_about = r#"Show pool size, bounds, and metrics in human-readable format by default."#,
+        about = "Register a hook on a pool",
         after_help = r#"EXAMPLES
-  $ plasmite pool info foo
-  $ plasmite pool info foo --json"#
+  $ plasmite hook add deploys --exec 'logger -t deploy'
+  $ plasmite hook add errors --post https://example.com/hook --where '.data.level == "error"'"#
     )]
-    Info {
-        #[arg(help = "Pool name or path")]
-        name: String,
+    Add {
+        #[arg(help = "Pool ref: local name/path")]
+        pool: String,
+        #[arg(
+            long,
+            value_name = "CMD",
+            conflicts_with = "post",
+            required_unless_present = "post",
+            help = "Shell command to run per message (message JSON on stdin and in `{}`)"
+        )]
+        exec: Option<String>,
+        #[arg(long, value_name = "URL", help = "Endpoint to POST each message to")]
+        post: Option<String>,
+        #[arg(
+            long = "where",
+            value_name = "EXPR",
+            help = "Only fire for messages matching this boolean expression (repeatable; AND across repeats)"
+        )]
+        where_expr: Vec<String>,
         #[arg(long, help = "Emit JSON instead of human-readable output")]
         json: bool,
     },
-    #[command(
-        arg_required_else_help = true,
-        about = "Delete one or more pool files",
-        long_about = r#"Delete one or more pool files (destructive, cannot be undone)."#,
-        after_help = r#"EXAMPLES
-  $ plasmite pool delete foo
-  $ plasmite pool delete foo bar baz
-  $ plasmite pool delete --json foo bar
-
-NOTES
-  - Human-readable output is the default.
-  - Use --json for machine-readable output.
-  - Best effort: attempts all deletes and reports per-pool failures.
-  - Exits non-zero if any requested pool failed to delete."#
-    )]
-    Delete {
-        #[arg(required = true, help = "Pool name(s) or path(s)")]
-        names: Vec<String>,
+    #[command(about = "List registered hooks")]
+    List {
         #[arg(long, help = "Emit JSON instead of human-readable output")]
         json: bool,
     },
-    #[command(
-        about = "List pools in the pool directory",
-        long_about = r#"List pools in the pool directory.
-
-Prints a human-readable table by default. Use --json for machine-readable output."#,
-        after_help = r#"EXAMPLES
-  $ plasmite pool list
-  $ plasmite pool list --json
+    #[command(arg_required_else_help = true, about = "Remove a hook")]
+    Remove {
+        #[arg(help = "Hook id (from `hook list`)")]
+        id: String,
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+}
 
-NOTES
-  - Human-readable output is the default.
-  - Use --json for machine-readable output.
-  - Non-.plasmite files are ignored.
-  - Pools that cannot be read include an error field."#
-    )]
-    List {
+#[derive(Subcommand)]
+enum CtlCommand {
+    #[command(about = "Print the follower's counters and filters")]
+    Stats {
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(about = "Stop reading until `resume`")]
+    Pause {
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(about = "Continue reading after `pause`")]
+    Resume {
+        #[arg(long, help = "Emit JSON instead of human-readable output")]
+        json: bool,
+    },
+    #[command(about = "Replace the follower's --where filters")]
+    Where {
+        #[arg(
+            value_name = "EXPR",
+            required_unless_present = "clear",
+            conflicts_with = "clear",
+            help = "Boolean expressions, ANDed together"
+        )]
+        exprs: Vec<String>,
+        #[arg(long, help = "Drop all --where filters")]
+        clear: bool,
         #[arg(long, help = "Emit JSON instead of human-readable output")]
         json: bool,
     },
@@ -865,6 +2813,7 @@ NOTES
   $ plasmite serve check
   $ plasmite serve --bind 0.0.0.0:9700 --allow-non-loopback --access read-only check
   $ plasmite serve --token-file ~/.plasmite/token --tls-self-signed check
+  $ PLASMITE_SERVE_BIND=0.0.0.0:9700 plasmite serve check --from-env
 
 NOTES
   - Exits non-zero when config is invalid
@@ -874,6 +2823,17 @@ NOTES
     Check {
         #[arg(long, help = "Emit JSON instead of human-readable output")]
         json: bool,
+        #[arg(
+            long = "from-env",
+            help = "Read settings from PLASMITE_SERVE_* environment variables"
+        )]
+        from_env: bool,
+        #[arg(
+            long = "sample",
+            value_name = "POOL=JSON",
+            help = "Dry-run the --transforms chain for POOL on this .data value (repeatable)"
+        )]
+        samples: Vec<String>,
     },
 }
 
@@ -953,6 +2913,36 @@ struct ServeRunArgs {
     token: Option<String>,
     #[arg(long, value_name = "PATH", help = "Read bearer token from file", value_hint = ValueHint::FilePath, help_heading = "Authentication")]
     token_file: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Per-pool access rules (`PRINCIPAL POOL ACCESS` per line); callers only see granted pools",
+        value_hint = ValueHint::FilePath,
+        help_heading = "Authentication"
+    )]
+    pool_policy: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Per-pool ingest transforms (TOML: rename, drop, server_time) applied to remote appends",
+        value_hint = ValueHint::FilePath,
+        help_heading = "Ingest"
+    )]
+    transforms: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Record pool metrics into the _stats pool every --stats-interval",
+        help_heading = "Stats"
+    )]
+    stats_pool: bool,
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "1m",
+        help = "How often --stats-pool records a snapshot",
+        help_heading = "Stats"
+    )]
+    stats_interval: String,
     #[arg(long, value_name = "PATH", help = "TLS certificate path (PEM)", value_hint = ValueHint::FilePath, help_heading = "TLS")]
     tls_cert: Option<PathBuf>,
     #[arg(long, value_name = "PATH", help = "TLS key path (PEM)", value_hint = ValueHint::FilePath, help_heading = "TLS")]
@@ -963,6 +2953,14 @@ struct ServeRunArgs {
         help_heading = "TLS"
     )]
     tls_self_signed: bool,
+    #[arg(
+        long = "tls-client-ca",
+        value_name = "PATH",
+        help = "Require client certificates signed by this PEM CA (mTLS)",
+        value_hint = ValueHint::FilePath,
+        help_heading = "TLS"
+    )]
+    tls_client_ca: Option<PathBuf>,
     #[arg(
         long,
         help = "Allow non-loopback binds (unsafe without TLS + token)",
@@ -975,6 +2973,12 @@ struct ServeRunArgs {
         help_heading = "Safety"
     )]
     insecure_no_tls: bool,
+    #[arg(
+        long,
+        help = "Open pools read-only (no writes, no locks); implies --access read-only",
+        help_heading = "Safety"
+    )]
+    readonly_snapshot: bool,
     #[arg(
         long,
         default_value_t = DEFAULT_MAX_BODY_BYTES,
@@ -996,21 +3000,171 @@ struct ServeRunArgs {
         help_heading = "Safety"
     )]
     max_tail_concurrency: usize,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Max pools per namespace (namespaces may override)",
+        help_heading = "Quotas"
+    )]
+    max_pools: Option<u64>,
+    #[arg(
+        long,
+        value_name = "SIZE",
+        help = "Max total pool bytes per namespace, e.g. 10G (namespaces may override)",
+        help_heading = "Quotas"
+    )]
+    max_total_bytes: Option<String>,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Max appended messages per second per namespace (namespaces may override)",
+        help_heading = "Quotas"
+    )]
+    max_append_rate: Option<u32>,
+    #[arg(
+        long,
+        value_name = "ADDR",
+        help = "Also accept OTLP/HTTP JSON logs and traces on this address (e.g. 127.0.0.1:4318); exporters need OTEL_EXPORTER_OTLP_PROTOCOL=http/json",
+        help_heading = "OTLP"
+    )]
+    otlp_bind: Option<String>,
+    #[arg(
+        long = "namespace",
+        value_name = "SPEC",
+        help = "Serve an isolated pool namespace at /v0/ns/NAME (repeatable)",
+        long_help = r#"Serve an isolated pool namespace (repeatable)
+
+SPEC is NAME[,token-file=PATH][,access=read-only|write-only|read-write]
+        [,identity=ID]...[,max-pools=N][,max-bytes=SIZE][,max-append-rate=N].
+Pools live in <dir>/NAME/ and are reached at /v0/ns/NAME/pools/... . A namespace
+with token-file accepts only those tokens; without one it reuses --token/--token-file.
+With --tls-client-ca, only certificates whose identity is listed via identity=ID
+(repeatable) skip the token; other certificates need a namespace token.
+access defaults to --access; unset quotas default to --max-pools, --max-total-bytes,
+and --max-append-rate."#,
+        help_heading = "Connection"
+    )]
+    namespace: Vec<String>,
+    #[arg(
+        long = "from-env",
+        help = "Read settings from PLASMITE_SERVE_* environment variables (override flags)",
+        help_heading = "Connection"
+    )]
+    from_env: bool,
+}
+
+/// Overlay `PLASMITE_SERVE_*` values from `lookup` onto parsed serve flags.
+/// Unset or empty variables leave the flag value in place.
+fn apply_serve_env<F>(run: &mut ServeRunArgs, lookup: F) -> Result<(), Error>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let get = |name: &str| lookup(name).filter(|value| !value.trim().is_empty());
+    let invalid = |name: &str, hint: &str| {
+        Error::new(ErrorKind::Usage)
+            .with_message(format!("invalid {name}"))
+            .with_hint(hint.to_string())
+    };
+    let parse_flag = |name: &str, current: bool| -> Result<bool, Error> {
+        match get(name).as_deref().map(str::trim) {
+            None => Ok(current),
+            Some("1" | "true" | "yes" | "on") => Ok(true),
+            Some("0" | "false" | "no" | "off") => Ok(false),
+            Some(_) => Err(invalid(name, "Use true|false (or 1|0).")),
+        }
+    };
+    let parse_number = |name: &str| -> Result<Option<u64>, Error> {
+        get(name)
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| invalid(name, "Use a non-negative integer."))
+            })
+            .transpose()
+    };
+
+    if let Some(bind) = get("PLASMITE_SERVE_BIND") {
+        run.bind = bind;
+    }
+    if let Some(access) = get("PLASMITE_SERVE_ACCESS") {
+        run.access = AccessModeCli::from_str(access.trim(), true).map_err(|_| {
+            invalid(
+                "PLASMITE_SERVE_ACCESS",
+                "Use read-only, write-only, or read-write.",
+            )
+        })?;
+    }
+    if let Some(origins) = get("PLASMITE_SERVE_CORS_ORIGINS") {
+        run.cors_origin = origins
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    if let Some(token) = get("PLASMITE_SERVE_TOKEN") {
+        run.token = Some(token);
+    }
+    if let Some(path) = get("PLASMITE_SERVE_TOKEN_FILE") {
+        run.token_file = Some(PathBuf::from(path));
+    }
+    if let Some(path) = get("PLASMITE_SERVE_POOL_POLICY") {
+        run.pool_policy = Some(PathBuf::from(path));
+    }
+    if let Some(path) = get("PLASMITE_SERVE_TRANSFORMS") {
+        run.transforms = Some(PathBuf::from(path));
+    }
+    run.stats_pool = parse_flag("PLASMITE_SERVE_STATS_POOL", run.stats_pool)?;
+    if let Some(interval) = get("PLASMITE_SERVE_STATS_INTERVAL") {
+        run.stats_interval = interval;
+    }
+    if let Some(path) = get("PLASMITE_SERVE_TLS_CERT") {
+        run.tls_cert = Some(PathBuf::from(path));
+    }
+    if let Some(path) = get("PLASMITE_SERVE_TLS_KEY") {
+        run.tls_key = Some(PathBuf::from(path));
+    }
+    if let Some(path) = get("PLASMITE_SERVE_TLS_CLIENT_CA") {
+        run.tls_client_ca = Some(PathBuf::from(path));
+    }
+    run.tls_self_signed = parse_flag("PLASMITE_SERVE_TLS_SELF_SIGNED", run.tls_self_signed)?;
+    run.allow_non_loopback =
+        parse_flag("PLASMITE_SERVE_ALLOW_NON_LOOPBACK", run.allow_non_loopback)?;
+    run.insecure_no_tls = parse_flag("PLASMITE_SERVE_INSECURE_NO_TLS", run.insecure_no_tls)?;
+    run.readonly_snapshot = parse_flag("PLASMITE_SERVE_READONLY_SNAPSHOT", run.readonly_snapshot)?;
+    if let Some(value) = parse_number("PLASMITE_SERVE_MAX_BODY_BYTES")? {
+        run.max_body_bytes = value;
+    }
+    if let Some(value) = parse_number("PLASMITE_SERVE_MAX_TAIL_TIMEOUT_MS")? {
+        run.max_tail_timeout_ms = value;
+    }
+    if let Some(value) = parse_number("PLASMITE_SERVE_MAX_TAIL_CONCURRENCY")? {
+        run.max_tail_concurrency = value as usize;
+    }
+    if let Some(bind) = get("PLASMITE_SERVE_OTLP_BIND") {
+        run.otlp_bind = Some(bind);
+    }
+    Ok(())
 }
 
 fn resolve_poolref(input: &str, pool_dir: &Path) -> Result<PathBuf, Error> {
-    if input.chars().any(std::path::is_separator) {
+    if is_path_ref(input) {
         return Ok(PathBuf::from(input));
     }
     resolve_named_pool_path(input, pool_dir).map_err(map_pool_name_resolve_error)
 }
 
 fn map_pool_name_resolve_error(err: PoolNameResolveError) -> Error {
-    match err {
-        PoolNameResolveError::ContainsPathSeparator => {
-            Error::new(ErrorKind::Usage).with_message("pool name must not contain path separators")
-        }
-    }
+    let message = match err {
+        PoolNameResolveError::EmptySegment => "pool name has an empty segment",
+        PoolNameResolveError::DotSegment => "pool name segments must not start with '.'",
+        PoolNameResolveError::InvalidCharacter => "pool name must not contain '\\' or NUL",
+    };
+    Error::new(ErrorKind::Usage).with_message(message).with_hint(
+        "Use names like chat or team/service/events; pass ./path or an absolute path for files.",
+    )
 }
 
 fn resolve_pool_target(input: &str, pool_dir: &Path) -> Result<PoolTarget, Error> {
@@ -1105,7 +3259,7 @@ fn add_missing_pool_hint(err: Error, pool_ref: &str, input: &str) -> Error {
     if err.kind() != ErrorKind::NotFound || err.hint().is_some() {
         return err;
     }
-    if input.chars().any(std::path::is_separator) {
+    if is_path_ref(input) {
         return err.with_hint(
             "Pool path not found. Check the path or pass --dir for a different pool directory.",
         );
@@ -1128,7 +3282,7 @@ fn add_missing_pool_create_hint(
     if input.contains("://") {
         return err.with_hint("Remote pool not found. Create it with server-side tooling first.");
     }
-    if input.chars().any(std::path::is_separator) {
+    if is_path_ref(input) {
         return err.with_hint(
             "Pool path not found. Check the path or pass --dir for a different pool directory.",
         );
@@ -1223,6 +3377,7 @@ fn feed_exact_create_command_hint(pool: &str, options: FeedExactCreateHint<'_>)
                 InputMode::Json => "json",
                 InputMode::Seq => "seq",
                 InputMode::Jq => "jq",
+                InputMode::Csv => "csv",
             }
             .to_string(),
         );
@@ -1340,17 +3495,362 @@ fn add_corrupt_hint(err: Error) -> Error {
     err.with_hint("Pool appears corrupt. Recreate it or investigate with validation tooling.")
 }
 
-fn add_internal_hint(err: Error) -> Error {
-    if err.kind() != ErrorKind::Internal || err.hint().is_some() {
-        return err;
+fn add_internal_hint(err: Error) -> Error {
+    if err.kind() != ErrorKind::Internal || err.hint().is_some() {
+        return err;
+    }
+    err.with_hint(
+        "Unexpected internal failure. Retry with RUST_BACKTRACE=1 and share command/context if it persists.",
+    )
+}
+
+/// Newest messages `plasmite schema` reads when `--sample` is not given.
+const DEFAULT_SCHEMA_SAMPLE: u64 = 1000;
+
+/// How long a remote diff source may stay silent before its history is considered read.
+const DIFF_REMOTE_IDLE: Duration = Duration::from_secs(1);
+
+/// Read every message of a diff source as `(seq, key)` entries.
+fn diff_source_entries(
+    input: &str,
+    pool_dir: &Path,
+    key: Option<&JqFilter>,
+) -> Result<Vec<pool_diff::DiffEntry>, Error> {
+    let entry = |seq: u64, message: &Value| pool_diff::DiffEntry {
+        seq,
+        key: match key {
+            Some(filter) => filter.key(message).unwrap_or_else(|| "null".to_string()),
+            None => pool_diff::content_key(message),
+        },
+    };
+    let path = Path::new(input);
+    if path.is_file() && !is_pool_file(path) {
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to read dump")
+                .with_path(path)
+                .with_source(err)
+        })?;
+        let mut entries = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_no = index as u64 + 1;
+            let value: Value = serde_json::from_str(line).map_err(|err| {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("dump line {line_no} is not valid JSON"))
+                    .with_path(path)
+                    .with_source(err)
+            })?;
+            // Bare data objects (e.g. `follow --data-only`) become untagged messages.
+            let message = if value.get("data").is_some() {
+                value
+            } else {
+                json!({"meta": {"tags": []}, "data": value})
+            };
+            let seq = message
+                .get("seq")
+                .and_then(Value::as_u64)
+                .unwrap_or(line_no);
+            entries.push(entry(seq, &message));
+        }
+        return Ok(entries);
+    }
+
+    match resolve_pool_target(input, pool_dir)? {
+        PoolTarget::Remote { base_url, pool } => {
+            let remote_pool = RemoteClient::new(base_url)?.open_pool(&PoolRef::name(pool))?;
+            let info = remote_pool.info()?;
+            let (Some(oldest), Some(newest)) = (info.bounds.oldest_seq, info.bounds.newest_seq)
+            else {
+                return Ok(Vec::new());
+            };
+            let mut options = TailOptions::new();
+            options.since_seq = Some(oldest);
+            options.max_messages = Some((newest - oldest + 1) as usize);
+            options.timeout = Some(DIFF_REMOTE_IDLE);
+            let mut tail = remote_pool.tail(options)?;
+            let mut entries = Vec::new();
+            while let Some(message) = tail.next_message()? {
+                if message.seq > newest {
+                    break;
+                }
+                entries.push(entry(message.seq, &message_to_json(&message)));
+            }
+            Ok(entries)
+        }
+        PoolTarget::LocalPath(path) => {
+            let pool_handle =
+                Pool::open(&path).map_err(|err| add_missing_pool_hint(err, input, input))?;
+            let header = pool_handle.header_from_mmap()?;
+            let mut cursor = Cursor::new();
+            cursor.seek_to(header.tail_off as usize);
+            let mut entries = Vec::new();
+            loop {
+                match cursor.next(&pool_handle)? {
+                    CursorResult::Message(frame) => {
+                        if frame.seq > header.newest_seq {
+                            break;
+                        }
+                        if let Some(message) = pool_message(&pool_handle, &frame)? {
+                            entries.push(entry(frame.seq, &message));
+                        }
+                    }
+                    CursorResult::WouldBlock => break,
+                    CursorResult::FellBehind => {
+                        return Err(Error::new(ErrorKind::Busy)
+                            .with_message("pool wrapped while reading for diff")
+                            .with_path(&path)
+                            .with_hint("Writers overwrote frames mid-scan; re-run when writes are quieter."));
+                    }
+                }
+            }
+            Ok(entries)
+        }
+    }
+}
+
+fn is_pool_file(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|()| &magic == b"PLSM")
+}
+
+fn emit_diff_human(a: &str, b: &str, report: &pool_diff::PoolDiffReport) {
+    println!("A:       {a} ({} messages)", report.a_count);
+    println!("B:       {b} ({} messages)", report.b_count);
+    println!("Matched: {}", report.matched);
+    if report.is_identical() {
+        println!("No differences.");
+        return;
+    }
+    println!();
+    let rows = report
+        .only_in_a
+        .iter()
+        .map(|entry| ("a", entry))
+        .chain(report.only_in_b.iter().map(|entry| ("b", entry)))
+        .map(|(side, entry)| vec![side.to_string(), entry.seq.to_string(), entry.key.clone()])
+        .collect::<Vec<_>>();
+    emit_table(&["ONLY IN", "SEQ", "KEY"], &rows);
+}
+
+fn emit_schema_human(pool_ref: &str, inference: &schema_infer::SchemaInference) {
+    println!("Pool:    {pool_ref}");
+    println!("Sampled: {} messages", inference.sampled());
+    let fields = inference.fields();
+    if fields.is_empty() {
+        return;
+    }
+    println!();
+    let rows = fields
+        .iter()
+        .map(|field| {
+            let types = field
+                .types
+                .iter()
+                .map(|(name, count)| format!("{name}:{count}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let top = field
+                .top_values
+                .iter()
+                .map(|(value, count)| format!("{value} ({count})"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            vec![
+                field.path.clone(),
+                types,
+                field.present.to_string(),
+                field.missing.to_string(),
+                if field.nullable { "yes" } else { "no" }.to_string(),
+                top,
+            ]
+        })
+        .collect::<Vec<_>>();
+    emit_table(
+        &[
+            "PATH",
+            "TYPES",
+            "PRESENT",
+            "MISSING",
+            "NULLABLE",
+            "TOP VALUES",
+        ],
+        &rows,
+    );
+}
+
+fn emit_audit_gaps_human(pool_ref: &str, report: &seq_audit::SeqAuditReport) {
+    let range = match (report.first_seq, report.last_seq) {
+        (Some(first), Some(last)) => format!("seq {first}..{last}"),
+        _ => "empty".to_string(),
+    };
+    let overwritten = report.count(seq_audit::GapKind::Overwritten);
+    let missing = report.count(seq_audit::GapKind::Missing);
+    println!("Pool:        {pool_ref}");
+    println!("Scanned:     {} messages ({range})", report.scanned);
+    println!("Overwritten: {overwritten}");
+    println!("Missing:     {missing}");
+    if report.out_of_order > 0 {
+        println!("Out of order: {}", report.out_of_order);
+    }
+    if report.gaps.is_empty() {
+        return;
+    }
+    println!();
+    let rows = report
+        .gaps
+        .iter()
+        .map(|gap| {
+            vec![
+                gap.kind.as_str().to_string(),
+                gap.start.to_string(),
+                gap.end.to_string(),
+                gap.count().to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    emit_table(&["KIND", "START", "END", "COUNT"], &rows);
+}
+
+fn emit_explain_human(pool_ref: &str, explain: &FrameExplain) {
+    let seq = explain
+        .seq
+        .map(|seq| seq.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    println!("Pool:        {pool_ref}");
+    println!(
+        "Offset:      {} (file offset {}{})",
+        explain.offset,
+        explain.file_offset,
+        if explain.on_boundary {
+            ""
+        } else {
+            ", not a walked frame boundary"
+        }
+    );
+    println!("Seq:         {seq}");
+    if let Some(err) = &explain.decode_error {
+        println!("Decode:      {err}");
+    }
+    println!();
+    for (line, chunk) in explain.header_bytes.chunks(16).enumerate() {
+        println!(
+            "{:08x}  {}",
+            explain.file_offset + (line * 16) as u64,
+            hex_encode_spaced(chunk)
+        );
+    }
+    println!();
+    let rows = explain
+        .fields
+        .iter()
+        .map(|field| {
+            vec![
+                format!("{}..{}", field.start, field.end),
+                field.name.to_string(),
+                field.value.clone(),
+                field.note.clone().unwrap_or_default(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    emit_table(&["BYTES", "FIELD", "VALUE", "NOTE"], &rows);
+    println!();
+    let crc =
+        |value: Option<u32>| value.map_or_else(|| "-".to_string(), |crc| format!("{crc:#010x}"));
+    println!(
+        "CRC32C:      stored {} computed {}",
+        crc(explain.stored_crc32c),
+        crc(explain.computed_crc32c)
+    );
+    if let Some(ok) = explain.commit_marker_ok {
+        println!(
+            "Commit:      {}",
+            if ok { "marker ok" } else { "marker missing" }
+        );
+    }
+    let boundary =
+        |value: Option<u64>| value.map_or_else(|| "-".to_string(), |off| off.to_string());
+    println!(
+        "Neighbors:   prev {} next {}",
+        boundary(explain.prev_offset),
+        boundary(explain.next_offset)
+    );
+    if explain.index.is_empty() {
+        return;
+    }
+    println!();
+    let rows = explain
+        .index
+        .iter()
+        .map(|probe| {
+            vec![
+                probe.seq.to_string(),
+                probe.slot.to_string(),
+                probe.stored_seq.to_string(),
+                probe.stored_offset.to_string(),
+                probe.status.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    emit_table(
+        &["SEQ", "SLOT", "STORED SEQ", "STORED OFFSET", "STATUS"],
+        &rows,
+    );
+}
+
+/// ASCII ramp for activity sparklines, lowest to highest.
+const SPARK_LEVELS: &[u8] = b" .:-=+*#%@";
+const ACTIVITY_BAR_WIDTH: u64 = 40;
+
+fn activity_sparkline(counts: &[u64], peak: u64) -> String {
+    counts
+        .iter()
+        .map(|&count| {
+            let top = SPARK_LEVELS.len() as u64 - 1;
+            let level = match count {
+                0 => 0,
+                _ => (count * top).div_ceil(peak.max(1)).clamp(1, top),
+            };
+            SPARK_LEVELS[level as usize] as char
+        })
+        .collect()
+}
+
+fn emit_activity_human(
+    pool_ref: &str,
+    bucket: &str,
+    histogram: &ActivityHistogram,
+) -> Result<(), Error> {
+    let peak = histogram.peak();
+    println!("Pool:    {pool_ref}");
+    println!(
+        "Window:  {} .. now ({} buckets of {})",
+        format_ts(histogram.start_ns)?,
+        histogram.counts.len(),
+        bucket
+    );
+    println!("Total:   {} (peak {peak} per bucket)", histogram.total());
+    println!("Spark:   |{}|", activity_sparkline(&histogram.counts, peak));
+    println!();
+    let mut rows = Vec::with_capacity(histogram.counts.len());
+    for (index, &count) in histogram.counts.iter().enumerate() {
+        let width = (count * ACTIVITY_BAR_WIDTH).div_ceil(peak.max(1));
+        rows.push(vec![
+            format_timestamp_human(&format_ts(histogram.bucket_start_ns(index))?),
+            count.to_string(),
+            "#".repeat(width as usize),
+        ]);
     }
-    err.with_hint(
-        "Unexpected internal failure. Retry with RUST_BACKTRACE=1 and share command/context if it persists.",
-    )
+    emit_table(&["BUCKET", "COUNT", ""], &rows);
+    Ok(())
 }
 
 fn emit_doctor_human(report: &ValidationReport) {
-    if !io::stdout().is_terminal() {
+    if !stdout_is_terminal() {
         let label = report
             .pool_ref
             .clone()
@@ -1358,6 +3858,9 @@ fn emit_doctor_human(report: &ValidationReport) {
         match report.status {
             ValidationStatus::Ok => {
                 println!("OK: {label}");
+                for warning in doctor_warnings(report) {
+                    println!("WARN: {label} {warning}");
+                }
             }
             ValidationStatus::Corrupt => {
                 let last_good = report
@@ -1381,6 +3884,9 @@ fn emit_doctor_human(report: &ValidationReport) {
             println!("{label}: healthy");
             println!("  messages:  {}", doctor_messages_summary(report));
             println!("  checked:   header, index, ring — 0 issues");
+            for warning in doctor_warnings(report) {
+                println!("  warning:   {warning}");
+            }
         }
         ValidationStatus::Corrupt => {
             let issue = report
@@ -1399,12 +3905,19 @@ fn emit_doctor_human(report: &ValidationReport) {
     }
 }
 
+fn doctor_warnings(report: &ValidationReport) -> impl Iterator<Item = &str> {
+    report
+        .remediation_hints
+        .iter()
+        .filter_map(|hint| hint.strip_prefix("warning: "))
+}
+
 fn emit_doctor_human_summary(reports: &[ValidationReport]) {
     if reports.is_empty() {
-        println!("No pools found.");
+        println!("{}", tr(Msg::NoPoolsFound, &[]));
         return;
     }
-    if !io::stdout().is_terminal() {
+    if !stdout_is_terminal() {
         for report in reports {
             emit_doctor_human(report);
         }
@@ -1427,30 +3940,42 @@ fn emit_doctor_human_summary(reports: &[ValidationReport]) {
         .max()
         .unwrap_or(0);
     if corrupt == 0 {
-        println!("All {} pools healthy.", reports.len());
+        println!("{}", tr(Msg::AllPoolsHealthy, &[("total", &reports.len())]));
         println!();
+        let no_issues = tr(Msg::NoIssues, &[]);
         for idx in 0..reports.len() {
             println!(
-                "  {:<label_width$}   {:<message_width$}   0 issues",
+                "  {:<label_width$}   {:<message_width$}   {no_issues}",
                 labels[idx], message_labels[idx]
             );
         }
     } else {
-        println!("{corrupt} of {} pools unhealthy.", reports.len());
+        println!(
+            "{}",
+            tr(
+                Msg::PoolsUnhealthy,
+                &[("corrupt", &corrupt), ("total", &reports.len())]
+            )
+        );
         println!();
         for (idx, report) in reports.iter().enumerate() {
             let label = &labels[idx];
             let messages = &message_labels[idx];
             if report.status == ValidationStatus::Corrupt {
                 println!(
-                    "  ✗ {:<label_width$}   {:<message_width$}   {} issues (run `pls doctor {}` for detail)",
+                    "  ✗ {:<label_width$}   {:<message_width$}   {}",
                     label,
                     messages,
-                    report.issues.len(),
-                    label
+                    tr(
+                        Msg::IssuesRunDoctor,
+                        &[("count", &report.issues.len()), ("label", label)]
+                    )
                 );
             } else {
-                println!("  ✓ {label:<label_width$}   {messages:<message_width$}   0 issues");
+                println!(
+                    "  ✓ {label:<label_width$}   {messages:<message_width$}   {}",
+                    tr(Msg::NoIssues, &[])
+                );
             }
         }
     }
@@ -1458,8 +3983,7 @@ fn emit_doctor_human_summary(reports: &[ValidationReport]) {
 
 fn doctor_display_label(report: &ValidationReport) -> String {
     if let Some(pool_ref) = report.pool_ref.as_deref() {
-        let looks_like_path = pool_ref.contains('/') || pool_ref.contains('\\');
-        if !looks_like_path {
+        if !is_path_ref(pool_ref) {
             return pool_ref.to_string();
         }
     }
@@ -1521,42 +4045,38 @@ fn doctor_message_stats(report: &ValidationReport) -> Option<DoctorMessageStats>
     })
 }
 
-fn report_json(report: &ValidationReport) -> Value {
-    let issues = report
-        .issues
-        .iter()
-        .map(|issue| {
-            json!({
-                "code": issue.code,
-                "message": issue.message,
-                "seq": issue.seq,
-                "offset": issue.offset,
-            })
-        })
-        .collect::<Vec<_>>();
-    json!({
-        "pool_ref": report.pool_ref,
-        "path": report.path.to_string_lossy(),
-        "status": match report.status {
-            ValidationStatus::Ok => "ok",
-            ValidationStatus::Corrupt => "corrupt",
-        },
-        "last_good_seq": report.last_good_seq,
-        "issue_count": report.issue_count,
-        "issues": issues,
-        "remediation_hints": report.remediation_hints,
-        "snapshot_path": report.snapshot_path.as_ref().map(|path| path.to_string_lossy()),
-    })
-}
-
 fn doctor_report(
     client: &LocalClient,
     pool_ref: PoolRef,
     label: String,
     path: PathBuf,
+    deep: bool,
+    max_frames: Option<u64>,
+    repair: bool,
 ) -> Result<ValidationReport, Error> {
-    match client.validate_pool(&pool_ref) {
-        Ok(report) => Ok(report.with_pool_ref(label)),
+    let repaired = match repair.then(|| client.repair_pool(&pool_ref)) {
+        Some(Ok(seq)) => seq,
+        Some(Err(err)) if err.kind() != ErrorKind::Corrupt => return Err(err),
+        _ => None,
+    };
+    let report = if deep {
+        client.validate_pool_deep_bounded(&pool_ref, max_frames)
+    } else {
+        client.validate_pool(&pool_ref)
+    };
+    match report {
+        Ok(mut report) => {
+            if let Some(seq) = repaired {
+                report.remediation_hints.insert(
+                    0,
+                    format!(
+                        "warning: rolled back interrupted append of seq {seq}; older messages \
+                         it overwrote were dropped"
+                    ),
+                );
+            }
+            Ok(report.with_pool_ref(label))
+        }
         Err(err) if err.kind() == ErrorKind::Corrupt => {
             Ok(ValidationReport::corrupt(path, error_issue(&err), None).with_pool_ref(label))
         }
@@ -1574,7 +4094,7 @@ fn error_issue(err: &Error) -> ValidationIssue {
 }
 
 fn list_pool_paths(pool_dir: &Path) -> Result<Vec<PathBuf>, Error> {
-    let entries = std::fs::read_dir(pool_dir).map_err(|err| {
+    find_pool_files(pool_dir).map_err(|err| {
         let kind = match err.kind() {
             std::io::ErrorKind::NotFound => ErrorKind::NotFound,
             std::io::ErrorKind::PermissionDenied => ErrorKind::Permission,
@@ -1584,29 +4104,15 @@ fn list_pool_paths(pool_dir: &Path) -> Result<Vec<PathBuf>, Error> {
             .with_message("failed to read pool directory")
             .with_path(pool_dir)
             .with_source(err)
-    })?;
-
-    let mut pools = Vec::new();
-    for entry in entries {
-        let entry = entry.map_err(|err| {
-            Error::new(ErrorKind::Io)
-                .with_message("failed to read pool directory entry")
-                .with_path(pool_dir)
-                .with_source(err)
-        })?;
-        let path = entry.path();
-        if path.extension().and_then(|ext| ext.to_str()) == Some("plasmite") {
-            pools.push(path);
-        }
-    }
-    Ok(pools)
+    })
 }
 
-fn list_pools(pool_dir: &Path, client: &LocalClient) -> Vec<Value> {
+/// One row per pool; a missing directory lists nothing. Stops when the client's deadline does.
+fn list_pools(pool_dir: &Path, client: &LocalClient) -> Result<Vec<Value>, Error> {
     let mut pools = Vec::new();
-    let entries = match std::fs::read_dir(pool_dir) {
-        Ok(entries) => entries,
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return pools,
+    let paths = match find_pool_files(pool_dir) {
+        Ok(paths) => paths,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(pools),
         Err(err) => {
             pools.push(pool_list_error(
                 "pools",
@@ -1616,20 +4122,13 @@ fn list_pools(pool_dir: &Path, client: &LocalClient) -> Vec<Value> {
                     .with_path(pool_dir)
                     .with_source(err),
             ));
-            return pools;
+            return Ok(pools);
         }
     };
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().and_then(|ext| ext.to_str()) != Some("plasmite") {
-            continue;
-        }
-        let name = path
-            .file_stem()
-            .and_then(|stem| stem.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+    for path in paths {
+        client.deadline().check()?;
+        let name = pool_name_for_path(pool_dir, &path).unwrap_or_else(|| "unknown".to_string());
         let meta = match std::fs::metadata(&path) {
             Ok(meta) => meta,
             Err(err) => {
@@ -1672,18 +4171,16 @@ fn list_pools(pool_dir: &Path, client: &LocalClient) -> Vec<Value> {
     }
 
     pools.sort_by_key(pool_list_name);
-    pools
+    Ok(pools)
 }
 
 fn emit_pool_list_table(pools: &[Value], pool_dir: &Path) {
-    let interactive = io::stdout().is_terminal();
+    let interactive = stdout_is_terminal();
     if interactive && pools.is_empty() {
-        println!(
-            "No pools found in {}",
-            display_pool_dir_for_humans(pool_dir)
-        );
+        let dir = display_pool_dir_for_humans(pool_dir);
+        println!("{}", tr(Msg::NoPoolsFoundIn, &[("dir", &dir)]));
         println!();
-        println!("  Create one: plasmite pool create <name>");
+        println!("  {} plasmite pool create <name>", tr(Msg::CreateOne, &[]));
         return;
     }
 
@@ -1792,12 +4289,246 @@ fn emit_pool_list_table(pools: &[Value], pool_dir: &Path) {
             }
         })
         .collect::<Vec<_>>();
+    let rows = if interactive {
+        pool_tree_rows(rows)
+    } else {
+        rows
+    };
 
     emit_table(&headers, &rows);
 }
 
+/// Interactive `pool list` nests hierarchical names: `team/` gets its own row and its pools
+/// are indented beneath it. Rows must already be sorted by full name.
+fn pool_tree_rows(rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    let mut out = Vec::with_capacity(rows.len());
+    let mut open_dirs: Vec<String> = Vec::new();
+    for mut row in rows {
+        let name = std::mem::take(&mut row[0]);
+        let (dirs, leaf) = match name.rsplit_once('/') {
+            Some((dirs, leaf)) => (dirs.split('/').collect::<Vec<_>>(), leaf),
+            None => (Vec::new(), name.as_str()),
+        };
+        let shared = open_dirs
+            .iter()
+            .zip(&dirs)
+            .take_while(|(open, dir)| open == *dir)
+            .count();
+        open_dirs.truncate(shared);
+        for (depth, dir) in dirs.iter().enumerate().skip(shared) {
+            let mut dir_row = vec![String::new(); row.len()];
+            dir_row[0] = format!("{}{dir}/", "  ".repeat(depth));
+            out.push(dir_row);
+            open_dirs.push(dir.to_string());
+        }
+        row[0] = format!("{}{leaf}", "  ".repeat(dirs.len()));
+        out.push(row);
+    }
+    out
+}
+
+/// `pool freeze` / `pool unfreeze`: flip the header flag and report the new state.
+fn set_pool_frozen(
+    pool_dir: &Path,
+    name: &str,
+    frozen: bool,
+    json: bool,
+    color_mode: ColorMode,
+) -> Result<RunOutcome, Error> {
+    let json = wants_json(json);
+    if name.contains("://") {
+        let command = if frozen { "freeze" } else { "unfreeze" };
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!(
+                "pool {command} accepts local pool names or paths only"
+            ))
+            .with_hint("Run the command on the host that owns the pool."));
+    }
+    let path = resolve_poolref(name, pool_dir)?;
+    let client = LocalClient::new().with_pool_dir(pool_dir);
+    let was_frozen = client
+        .set_pool_frozen(&PoolRef::path(path.clone()), frozen)
+        .map_err(|err| add_missing_pool_hint(err, name, name))?;
+    if json {
+        emit_json(
+            json!({
+                "pool": name,
+                "path": path.display().to_string(),
+                "frozen": frozen,
+                "changed": was_frozen != frozen,
+            }),
+            color_mode,
+        );
+    } else {
+        let msg = if frozen {
+            Msg::FrozenPool
+        } else {
+            Msg::UnfrozenPool
+        };
+        println!("{}", tr(msg, &[("name", &name)]));
+    }
+    Ok(RunOutcome::ok())
+}
+
+/// Resolve a local pool for `pool <command>` and open it read-only.
+fn open_local_pool_read_only(
+    command: &str,
+    name: &str,
+    pool_dir: &Path,
+) -> Result<(PathBuf, Pool), Error> {
+    if name.contains("://") {
+        return Err(Error::new(ErrorKind::Usage).with_message(format!(
+            "pool {command} accepts local pool names or paths only"
+        )));
+    }
+    let path = resolve_poolref(name, pool_dir)?;
+    let pool = Pool::open_read_only(&path).map_err(|err| add_missing_pool_hint(err, name, name))?;
+    Ok((path, pool))
+}
+
+/// `pool delete --dry-run`: the checks and file list of a real delete, changing nothing.
+fn preview_pool_delete(
+    pool_dir: &Path,
+    names: &[String],
+    options: DeleteOptions,
+    json: bool,
+    color_mode: ColorMode,
+) -> Result<RunOutcome, Error> {
+    let json = wants_json(json);
+    let client = LocalClient::new().with_pool_dir(pool_dir);
+    let mut plans = Vec::new();
+    let mut failed = Vec::new();
+    let mut first_error_kind = None;
+    for name in names {
+        let result = if name.contains("://") {
+            Err(Error::new(ErrorKind::Usage)
+                .with_message("pool delete accepts local pool names or paths only"))
+        } else {
+            resolve_poolref(name, pool_dir).and_then(|path| {
+                client
+                    .plan_delete_pool(&PoolRef::path(path.clone()), options)
+                    .map_err(|err| match err.kind() {
+                        ErrorKind::NotFound => Error::new(ErrorKind::NotFound)
+                            .with_message("pool not found")
+                            .with_path(&path)
+                            .with_hint("Create the pool first or check --dir."),
+                        ErrorKind::Permission => Error::new(ErrorKind::Io)
+                            .with_message("failed to delete pool")
+                            .with_path(&path),
+                        _ => err,
+                    })
+            })
+        };
+        match result {
+            Ok(plan) => plans.push((name, plan)),
+            Err(err) => {
+                first_error_kind.get_or_insert(err.kind());
+                failed.push((name, err));
+            }
+        }
+    }
+
+    if json {
+        let pools = plans
+            .iter()
+            .map(|(name, plan)| {
+                let mut entry = json!({
+                    "pool": name,
+                    "path": plan.path.display().to_string(),
+                    "files": plan
+                        .files
+                        .iter()
+                        .map(|(path, bytes)| json!({
+                            "path": path.display().to_string(),
+                            "bytes": bytes,
+                        }))
+                        .collect::<Vec<_>>(),
+                    "bytes": plan.bytes(),
+                    "oldest_seq": plan.seqs.map(|(oldest, _)| oldest),
+                    "newest_seq": plan.seqs.map(|(_, newest)| newest),
+                });
+                if let Some(trash_path) = &plan.trash_path {
+                    entry["trash"] = json!(trash_path.display().to_string());
+                }
+                entry
+            })
+            .collect::<Vec<_>>();
+        let failed = failed
+            .iter()
+            .map(|(name, err)| json!({ "pool": name, "error": err.to_json()["error"].clone() }))
+            .collect::<Vec<_>>();
+        emit_json(
+            json!({ "dry_run": true, "pools": pools, "failed": failed }),
+            color_mode,
+        );
+    } else {
+        let msg = if options.trash {
+            Msg::TrashDryRun
+        } else {
+            Msg::DeleteDryRun
+        };
+        println!("{}", tr(msg, &[("count", &plans.len())]));
+        println!();
+        for (name, plan) in &plans {
+            let seqs = match plan.seqs {
+                Some((oldest, newest)) => format!("seqs {oldest}-{newest}"),
+                None => "no messages".to_string(),
+            };
+            println!(
+                "  {name}  {} files, {}, {seqs}",
+                plan.files.len(),
+                format_bytes(plan.bytes())
+            );
+            for (path, bytes) in &plan.files {
+                println!(
+                    "    {}  {}",
+                    short_display_path(path, Some(pool_dir)),
+                    format_bytes(*bytes)
+                );
+            }
+        }
+        for (name, err) in &failed {
+            println!("  ✗ {name} — {}", err.message().unwrap_or("error"));
+        }
+    }
+    Ok(match first_error_kind {
+        Some(kind) => RunOutcome::with_code(to_exit_code(kind)),
+        None => RunOutcome::ok(),
+    })
+}
+
+/// One row per manifest pool: what `pool apply` did (or would do) and any drift.
+fn emit_pool_apply_table(results: &[Value], dry_run: bool) {
+    let rows = results
+        .iter()
+        .map(|result| {
+            let name = result["name"].as_str().unwrap_or("-").to_string();
+            let action = match (dry_run, result["action"].as_str().unwrap_or("-")) {
+                (true, "created") => "would create".to_string(),
+                (true, "updated") => "would update".to_string(),
+                (_, action) => action.to_string(),
+            };
+            let mut details = Vec::new();
+            if let Some(changes) = result["changes"].as_array().filter(|c| !c.is_empty()) {
+                let fields = changes.iter().filter_map(Value::as_str).collect::<Vec<_>>();
+                details.push(format!("set {}", fields.join(", ")));
+            }
+            for drift in result["drift"].as_array().into_iter().flatten() {
+                details.push(format!(
+                    "drift {}: manifest {}, pool {}",
+                    drift["field"].as_str().unwrap_or("-"),
+                    drift["desired"],
+                    drift["actual"]
+                ));
+            }
+            vec![name, action, details.join("; ")]
+        })
+        .collect::<Vec<_>>();
+    emit_table(&["NAME", "ACTION", "DETAIL"], &rows);
+}
+
 fn emit_pool_create_table(created: &[Value], pool_dir: &Path) {
-    if io::stdout().is_terminal() {
+    if stdout_is_terminal() {
         if created.len() == 1 {
             if let Some(pool) = created.first() {
                 let name = pool
@@ -1819,7 +4550,13 @@ fn emit_pool_create_table(created: &[Value], pool_dir: &Path) {
                     .and_then(|value| value.as_str())
                     .map(|value| short_display_path(Path::new(value), Some(pool_dir)))
                     .unwrap_or_else(|| "-".to_string());
-                println!("Created {name} ({size}, {index} index slots)");
+                println!(
+                    "{}",
+                    tr(
+                        Msg::CreatedPool,
+                        &[("name", &name), ("size", &size), ("index", &index)]
+                    )
+                );
                 println!("  path: {path}");
             }
             return;
@@ -1831,7 +4568,13 @@ fn emit_pool_create_table(created: &[Value], pool_dir: &Path) {
             .and_then(|value| value.as_u64())
             .map(format_bytes)
             .unwrap_or_else(|| "-".to_string());
-        println!("Created {} pools ({} each)", created.len(), size);
+        println!(
+            "{}",
+            tr(
+                Msg::CreatedPools,
+                &[("count", &created.len()), ("size", &size)]
+            )
+        );
         for pool in created {
             let name = pool
                 .get("name")
@@ -1881,7 +4624,7 @@ fn pool_list_error(name: &str, path: &Path, err: Error) -> Value {
     let mut map = Map::new();
     map.insert("name".to_string(), json!(name));
     map.insert("path".to_string(), json!(path.display().to_string()));
-    map.insert("error".to_string(), error_json(&err));
+    map.insert("error".to_string(), err.to_json());
     Value::Object(map)
 }
 
@@ -1996,20 +4739,18 @@ fn ensure_pool_dir(dir: &Path) -> Result<(), Error> {
         .map_err(|err| Error::new(ErrorKind::Io).with_path(dir).with_source(err))
 }
 
-fn read_token_file(path: &Path) -> Result<String, Error> {
-    let raw = std::fs::read_to_string(path).map_err(|err| {
-        Error::new(ErrorKind::Usage)
-            .with_message("failed to read token file")
-            .with_path(path)
-            .with_source(err)
-    })?;
-    let token = raw.trim().to_string();
-    if token.is_empty() {
-        return Err(Error::new(ErrorKind::Usage)
-            .with_message("token file is empty")
-            .with_path(path));
+/// Create the pool dir, plus the subdirectories a hierarchical name (`team/events`) maps to.
+fn ensure_pool_parent(pool_dir: &Path, path: &Path) -> Result<(), Error> {
+    match path.parent() {
+        Some(parent) if parent.starts_with(pool_dir) => ensure_pool_dir(parent),
+        _ => ensure_pool_dir(pool_dir),
     }
-    Ok(token)
+}
+
+/// Read the token clients should send: the first entry of a (possibly multi-token) file.
+fn read_token_file(path: &Path) -> Result<String, Error> {
+    let mut tokens = serve::read_token_list(path)?;
+    Ok(tokens.swap_remove(0))
 }
 
 fn resolve_token_value(
@@ -2033,15 +4774,21 @@ fn reject_remote_only_flags_for_local_target(
     token_file: Option<&Path>,
     tls_ca: Option<&Path>,
     tls_skip_verify: bool,
+    tls_client_cert: Option<&Path>,
 ) -> Result<(), Error> {
-    if token.is_none() && token_file.is_none() && tls_ca.is_none() && !tls_skip_verify {
+    if token.is_none()
+        && token_file.is_none()
+        && tls_ca.is_none()
+        && !tls_skip_verify
+        && tls_client_cert.is_none()
+    {
         return Ok(());
     }
     Err(Error::new(ErrorKind::Usage)
         .with_message(format!(
             "{command} remote auth/TLS flags require a remote http(s) pool ref"
         ))
-        .with_hint("Use --token/--token-file/--tls-ca/--tls-skip-verify only with http(s)://host:port/<pool> refs."))
+        .with_hint("Use --token/--token-file/--tls-ca/--tls-skip-verify/--tls-client-cert only with http(s)://host:port/<pool> refs."))
 }
 
 fn emit_serve_init_human(result: &serve_init::ServeInitResult) {
@@ -2257,7 +5004,9 @@ fn build_serve_startup_lines(config: &serve::ServeConfig) -> Vec<String> {
     let append_url = format!("{base_url}/v0/pools/demo/append");
     let curl_tls_flag = if config.tls_self_signed { " -k" } else { "" };
     let scope = serve_scope(config.bind.ip());
-    let auth = if config.token.is_some() {
+    let auth = if config.tls_client_ca.is_some() {
+        "mtls"
+    } else if config.token.is_some() {
         "bearer"
     } else {
         "none"
@@ -2357,9 +5106,47 @@ fn build_serve_startup_lines(config: &serve::ServeConfig) -> Vec<String> {
     lines
 }
 
-fn emit_serve_check_report(config: &serve::ServeConfig, color_mode: ColorMode, json: bool) {
+/// Run `serve check --sample POOL=JSON` values through the configured transforms.
+fn serve_check_samples(
+    config: &serve::ServeConfig,
+    samples: &[String],
+) -> Result<Vec<Value>, Error> {
+    samples
+        .iter()
+        .map(|sample| {
+            let (pool, data) = sample.split_once('=').ok_or_else(|| {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid --sample value: {sample}"))
+                    .with_hint("Use POOL=JSON, e.g. --sample 'events={\"msg\":\"hi\"}'.")
+            })?;
+            let chain = config
+                .transforms
+                .as_ref()
+                .and_then(|transforms| transforms.chain(pool))
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::Usage)
+                        .with_message(format!("no transforms configured for pool '{pool}'"))
+                        .with_hint("Pass --transforms FILE with a [[pools.<name>]] table for this pool (NS/POOL inside a namespace).")
+                })?;
+            let input: Value = serde_json::from_str(data).map_err(|err| {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid --sample JSON for pool '{pool}'"))
+                    .with_source(err)
+            })?;
+            let output = chain.apply(input.clone(), now_ns()?)?;
+            Ok(json!({ "pool": pool, "input": input, "output": output }))
+        })
+        .collect()
+}
+
+fn emit_serve_check_report(
+    config: &serve::ServeConfig,
+    samples: &[Value],
+    color_mode: ColorMode,
+    json: bool,
+) {
     if !json {
-        for line in build_serve_check_lines(config) {
+        for line in build_serve_check_lines(config, samples) {
             println!("{line}");
         }
         return;
@@ -2372,7 +5159,9 @@ fn emit_serve_check_report(config: &serve::ServeConfig, color_mode: ColorMode, j
         display_host(config.bind.ip()),
         config.bind.port()
     );
-    let auth_mode = if config.token.is_some() {
+    let auth_mode = if config.tls_client_ca.is_some() {
+        "client certificate (--tls-client-ca)"
+    } else if config.token.is_some() {
         if config.token_file_used {
             "bearer token (--token-file)"
         } else {
@@ -2409,10 +5198,24 @@ fn emit_serve_check_report(config: &serve::ServeConfig, color_mode: ColorMode, j
                 "tls_fingerprint": config.tls_fingerprint,
                 "access": access_mode,
                 "cors_allowed_origins": cors_origins,
+                "otlp": config.otlp_bind.map(|addr| format!("http://{addr}/v1")),
+                "quota": config.quota.to_json(),
+                "namespaces": config
+                    .namespaces
+                    .iter()
+                    .map(|namespace| format!("{base_url}/v0/ns/{}", namespace.name))
+                    .collect::<Vec<_>>(),
                 "limits": {
                     "max_body_bytes": config.max_body_bytes,
                     "max_tail_timeout_ms": config.max_tail_timeout_ms,
                     "max_tail_concurrency": config.max_concurrent_tails
+                },
+                "transforms": {
+                    "pools": config
+                        .transforms
+                        .as_ref()
+                        .map_or_else(|| json!({}), |transforms| transforms.to_json()),
+                    "samples": samples,
                 }
             }
         }),
@@ -2420,7 +5223,7 @@ fn emit_serve_check_report(config: &serve::ServeConfig, color_mode: ColorMode, j
     );
 }
 
-fn build_serve_check_lines(config: &serve::ServeConfig) -> Vec<String> {
+fn build_serve_check_lines(config: &serve::ServeConfig, samples: &[Value]) -> Vec<String> {
     let tls_enabled = serve_tls_enabled(config);
     let base_url = format!(
         "{}://{}:{}",
@@ -2428,7 +5231,9 @@ fn build_serve_check_lines(config: &serve::ServeConfig) -> Vec<String> {
         display_host(config.bind.ip()),
         config.bind.port()
     );
-    let auth = if config.token.is_some() {
+    let auth = if config.tls_client_ca.is_some() {
+        "client certificate"
+    } else if config.token.is_some() {
         "bearer token"
     } else {
         "none"
@@ -2445,7 +5250,11 @@ fn build_serve_check_lines(config: &serve::ServeConfig) -> Vec<String> {
         serve::AccessMode::WriteOnly => "access: write-only",
         serve::AccessMode::ReadWrite => "access: read-write",
     };
-    let access = access.strip_prefix("access: ").unwrap_or(access);
+    let access = if config.readonly_snapshot {
+        "read-only snapshot"
+    } else {
+        access.strip_prefix("access: ").unwrap_or(access)
+    };
     let cors = if config.cors_allowed_origins.is_empty() {
         "same-origin"
     } else {
@@ -2471,6 +5280,27 @@ fn build_serve_check_lines(config: &serve::ServeConfig) -> Vec<String> {
     if let Some(fingerprint) = config.tls_fingerprint.as_deref() {
         lines.push(format!("  Fingerprint: {fingerprint}"));
     }
+    if let Some(otlp_bind) = config.otlp_bind {
+        lines.push(format!("  OTLP:   http://{otlp_bind}/v1/logs, /v1/traces"));
+    }
+    if let Some(transforms) = config.transforms.as_ref().filter(|t| !t.is_empty()) {
+        let pools: Vec<String> = transforms
+            .iter()
+            .map(|(pool, chain)| {
+                let count = chain.step_count();
+                format!("{pool} ({count} step{})", if count == 1 { "" } else { "s" })
+            })
+            .collect();
+        lines.push(format!("  Transforms: {}", pools.join(", ")));
+    }
+    for sample in samples {
+        lines.push(format!(
+            "  Sample {}: {} -> {}",
+            sample["pool"].as_str().unwrap_or_default(),
+            sample["input"],
+            sample["output"]
+        ));
+    }
     lines.push(String::new());
     lines.push("Start with: pls serve".to_string());
 
@@ -2513,11 +5343,34 @@ fn serve_config_from_run_args(
             .with_message("--token cannot be combined with --token-file")
             .with_hint("Use --token for dev, or run `plasmite serve init` and use the generated --token-file for safer deployments."));
     }
-    let (token, token_file_used) = if let Some(path) = run.token_file {
-        (Some(read_token_file(&path)?), true)
+    let (token, token_file_used) = if let Some(path) = run.token_file.as_ref() {
+        (Some(read_token_file(path)?), true)
     } else {
         (run.token, false)
     };
+    if run.stats_pool && run.readonly_snapshot {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("--stats-pool cannot be combined with --readonly-snapshot")
+            .with_hint(
+                "Run `plasmite statsd` against a writable copy of the pool directory instead.",
+            ));
+    }
+    let stats_interval = if run.stats_pool {
+        Some(parse_duration(&run.stats_interval)?)
+    } else {
+        None
+    };
+    let otlp_bind = run
+        .otlp_bind
+        .as_deref()
+        .map(|value| {
+            value.parse::<SocketAddr>().map_err(|_| {
+                Error::new(ErrorKind::Usage)
+                    .with_message("invalid --otlp-bind address")
+                    .with_hint("Use a host:port value like 127.0.0.1:4318.")
+            })
+        })
+        .transpose()?;
     let tls_self_signed_material = if run.tls_self_signed {
         Some(serve::prepare_self_signed_tls(bind.ip())?)
     } else {
@@ -2530,23 +5383,55 @@ fn serve_config_from_run_args(
     } else {
         None
     };
+    let quota = serve_quota::Quota {
+        max_pools: run.max_pools,
+        max_bytes: run.max_total_bytes.as_deref().map(parse_size).transpose()?,
+        max_append_rate: run.max_append_rate,
+    };
+    let namespaces = run
+        .namespace
+        .iter()
+        .map(|spec| serve::ServeNamespace::parse(spec))
+        .collect::<Result<Vec<_>, _>>()?;
     Ok(serve::ServeConfig {
         bind,
         pool_dir: pool_dir.to_path_buf(),
         token,
         cors_allowed_origins: run.cors_origin,
-        access_mode: run.access.into(),
+        access_mode: if run.readonly_snapshot {
+            serve::AccessMode::ReadOnly
+        } else {
+            run.access.into()
+        },
         allow_non_loopback: run.allow_non_loopback,
         insecure_no_tls: run.insecure_no_tls,
         token_file_used,
+        token_file: run.token_file,
         tls_cert: run.tls_cert,
         tls_key: run.tls_key,
         tls_self_signed: run.tls_self_signed,
+        tls_client_ca: run.tls_client_ca,
         tls_self_signed_material,
         tls_fingerprint,
         max_body_bytes: run.max_body_bytes,
         max_tail_timeout_ms: run.max_tail_timeout_ms,
         max_concurrent_tails: run.max_tail_concurrency,
+        otlp_bind,
+        namespaces,
+        quota,
+        readonly_snapshot: run.readonly_snapshot,
+        pool_policy: run
+            .pool_policy
+            .as_deref()
+            .map(serve_policy::PoolPolicy::load)
+            .transpose()?,
+        transforms: run
+            .transforms
+            .as_deref()
+            .map(serve_transform::IngestTransforms::load)
+            .transpose()?,
+        stats_interval,
+        op_timeout: op_timeout(),
     })
 }
 
@@ -2613,6 +5498,15 @@ fn parse_size(input: &str) -> Result<u64, Error> {
     })
 }
 
+/// Parse a `pool create` frame/message size limit, which the pool header stores as a u32.
+fn parse_frame_limit(flag: &str, input: &str) -> Result<u32, Error> {
+    u32::try_from(parse_size(input)?).map_err(|_| {
+        Error::new(ErrorKind::Usage)
+            .with_message(format!("{flag} is too large"))
+            .with_hint("Use a size below 4G.")
+    })
+}
+
 fn parse_since(input: &str, now_ns: u64) -> Result<u64, Error> {
     if let Some(duration_ns) = parse_relative_since(input) {
         return Ok(now_ns.saturating_sub(duration_ns));
@@ -2628,6 +5522,25 @@ fn parse_since(input: &str, now_ns: u64) -> Result<u64, Error> {
     Ok(ts.unix_timestamp_nanos() as u64)
 }
 
+/// `--until`: a bare integer is a seq; anything else is a time in `--since` syntax.
+fn parse_replay_until(input: &str, now_ns: u64) -> Result<ReplayUntil, Error> {
+    let trimmed = input.trim();
+    if !trimmed.is_empty() && trimmed.chars().all(|ch| ch.is_ascii_digit()) {
+        return trimmed.parse().map(ReplayUntil::Seq).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message("invalid --until seq")
+                .with_source(err)
+        });
+    }
+    parse_since(trimmed, now_ns)
+        .map(ReplayUntil::TimeNs)
+        .map_err(|err| {
+            err.with_message("invalid --until value").with_hint(
+                "Use a seq (1200), RFC 3339 (2026-02-02T23:45:00Z), or relative like 5m.",
+            )
+        })
+}
+
 fn parse_relative_since(input: &str) -> Option<u64> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
@@ -2773,7 +5686,7 @@ fn parse_durability(input: &str) -> Result<Durability, Error> {
 }
 
 fn emit_pool_info_pretty(pool_ref: &str, info: &plasmite::api::PoolInfo) {
-    if !io::stdout().is_terminal() {
+    if !stdout_is_terminal() {
         println!("Pool: {pool_ref}");
         println!("Path: {}", info.path.display());
         println!(
@@ -2785,6 +5698,24 @@ fn emit_pool_info_pretty(pool_ref: &str, info: &plasmite::api::PoolInfo) {
             info.ring_offset,
             info.ring_size
         );
+        if let Some(allocated_bytes) = info.allocated_bytes {
+            println!("Allocated: {allocated_bytes} bytes");
+        }
+        if let Some(max_messages) = info.max_messages {
+            println!("Max messages: {max_messages}");
+        }
+        if let Some(max_frame_bytes) = info.max_frame_bytes {
+            println!("Max frame: {max_frame_bytes} bytes (larger messages are chunked)");
+        }
+        if let Some(max_message_bytes) = info.max_message_bytes {
+            println!("Max message: {max_message_bytes} bytes");
+        }
+        if info.bloom_filter {
+            println!("Bloom filter: on");
+        }
+        if info.frozen {
+            println!("Frozen: yes (appends are refused)");
+        }
 
         let oldest = info
             .bounds
@@ -2870,6 +5801,30 @@ fn emit_pool_info_pretty(pool_ref: &str, info: &plasmite::api::PoolInfo) {
         format_bytes(info.index_size_bytes)
     );
     println!("  ring:      {}", format_bytes(info.ring_size));
+    if let Some(allocated_bytes) = info.allocated_bytes {
+        println!("  on disk:   {}", format_bytes(allocated_bytes));
+    }
+    if let Some(max_messages) = info.max_messages {
+        println!("  max:       {max_messages} messages");
+    }
+    if let Some(max_frame_bytes) = info.max_frame_bytes {
+        println!(
+            "  frame:     {} (chunked above)",
+            format_bytes(max_frame_bytes.into())
+        );
+    }
+    if let Some(max_message_bytes) = info.max_message_bytes {
+        println!(
+            "  message:   {} max",
+            format_bytes(max_message_bytes.into())
+        );
+    }
+    if info.bloom_filter {
+        println!("  bloom:     on");
+    }
+    if info.frozen {
+        println!("  frozen:    yes (appends are refused)");
+    }
 }
 
 fn message_count_from_info(info: &plasmite::api::PoolInfo) -> u64 {
@@ -2945,7 +5900,7 @@ fn emit_feed_receipt_human(receipt: &Value) {
 }
 
 fn emit_feed_receipt(value: Value, color_mode: ColorMode) {
-    if io::stdout().is_terminal() {
+    if stdout_is_terminal() {
         emit_feed_receipt_human(&value);
     } else {
         emit_json(value, color_mode);
@@ -2953,7 +5908,7 @@ fn emit_feed_receipt(value: Value, color_mode: ColorMode) {
 }
 
 fn emit_version_output(color_mode: ColorMode) {
-    if io::stdout().is_terminal() {
+    if stdout_is_terminal() {
         println!("plasmite {}", env!("CARGO_PKG_VERSION"));
     } else {
         emit_json(
@@ -3045,12 +6000,16 @@ fn human_age(age_ms: Option<u64>) -> String {
 }
 
 fn emit_json(value: serde_json::Value, color_mode: ColorMode) {
-    let is_tty = io::stdout().is_terminal();
+    let is_tty = stdout_is_terminal();
     let use_color = color_mode.use_color(is_tty);
-    let pretty = is_tty || use_color;
+    let pretty = match output_mode() {
+        OutputMode::Auto => is_tty || use_color,
+        OutputMode::Json | OutputMode::Pretty => true,
+        OutputMode::Jsonl => false,
+    };
     let json = if pretty {
         if use_color {
-            colorize_json(&value, true)
+            colorize_json(&value, active_theme(true))
         } else {
             serde_json::to_string_pretty(&value)
                 .unwrap_or_else(|_| "{\"error\":\"json encode failed\"}".to_string())
@@ -3059,32 +6018,17 @@ fn emit_json(value: serde_json::Value, color_mode: ColorMode) {
         serde_json::to_string(&value)
             .unwrap_or_else(|_| "{\"error\":\"json encode failed\"}".to_string())
     };
-    println!("{json}");
-}
-
-#[derive(Copy, Clone, Debug)]
-enum AnsiColor {
-    Red,
-    Yellow,
-}
-
-fn colorize_label(label: &str, enabled: bool, color: AnsiColor) -> String {
-    if !enabled {
-        return label.to_string();
+    if let Err(err) = downstream::write_line(&json) {
+        panic!("failed printing to stdout: {err}");
     }
-    let code = match color {
-        AnsiColor::Red => "31",
-        AnsiColor::Yellow => "33",
-    };
-    format!("\u{1b}[{code}m{label}\u{1b}[0m")
 }
 
 fn emit_message(value: serde_json::Value, pretty: bool, color_mode: ColorMode) {
-    let is_tty = io::stdout().is_terminal();
+    let is_tty = stdout_is_terminal();
     let use_color = color_mode.use_color(is_tty);
     let json = if pretty {
         if use_color {
-            colorize_json(&value, true)
+            colorize_json(&value, active_theme(true))
         } else {
             serde_json::to_string_pretty(&value)
                 .unwrap_or_else(|_| "{\"error\":\"json encode failed\"}".to_string())
@@ -3103,7 +6047,7 @@ fn emit_error(err: &Error, color_mode: ColorMode) {
         return;
     }
 
-    let value = error_json(err);
+    let value = err.to_json();
     let json = serde_json::to_string(&value).unwrap_or_else(|_| {
         "{\"error\":{\"kind\":\"Internal\",\"message\":\"json encode failed\"}}".to_string()
     });
@@ -3120,7 +6064,11 @@ fn notice_time_now() -> Option<String> {
 fn emit_notice(notice: &Notice, color_mode: ColorMode) {
     let is_tty = io::stderr().is_terminal();
     if is_tty {
-        let label = colorize_label("notice:", color_mode.use_color(is_tty), AnsiColor::Yellow);
+        let label = paint(
+            "notice:",
+            Style::Warning,
+            active_theme(color_mode.use_color(is_tty)),
+        );
         if notice.cmd == "feed" {
             eprintln!("{label} {}", notice.message);
         } else {
@@ -3136,97 +6084,63 @@ fn emit_notice(notice: &Notice, color_mode: ColorMode) {
     eprintln!("{json}");
 }
 
-fn error_message(err: &Error) -> String {
-    if let Some(message) = err.message() {
-        return message.to_string();
-    }
-    match err.kind() {
-        ErrorKind::Internal => "internal error".to_string(),
-        ErrorKind::Usage => "usage error".to_string(),
-        ErrorKind::NotFound => "not found".to_string(),
-        ErrorKind::AlreadyExists => "already exists".to_string(),
-        ErrorKind::Busy => "resource is busy".to_string(),
-        ErrorKind::Permission => "permission denied".to_string(),
-        ErrorKind::Corrupt => "corrupt data".to_string(),
-        ErrorKind::Io => "i/o error".to_string(),
-    }
-}
-
-fn error_causes(err: &Error) -> Vec<String> {
-    let mut causes = Vec::new();
-    let mut cur = err.source();
-    while let Some(source) = cur {
-        causes.push(source.to_string());
-        cur = source.source();
-    }
-    causes
-}
-
-fn error_json(err: &Error) -> Value {
-    let mut inner = Map::new();
-    inner.insert("kind".to_string(), json!(format!("{:?}", err.kind())));
-    inner.insert("message".to_string(), json!(error_message(err)));
-    if let Some(hint) = err.hint() {
-        inner.insert("hint".to_string(), json!(hint));
-    }
-    if let Some(path) = err.path() {
-        inner.insert("path".to_string(), json!(path.display().to_string()));
-    }
-    if let Some(seq) = err.seq() {
-        inner.insert("seq".to_string(), json!(seq));
-    }
-    if let Some(offset) = err.offset() {
-        inner.insert("offset".to_string(), json!(offset));
-    }
-    let causes = error_causes(err);
-    if !causes.is_empty() {
-        inner.insert("causes".to_string(), json!(causes));
-    }
-
-    let mut outer = Map::new();
-    outer.insert("error".to_string(), Value::Object(inner));
-    Value::Object(outer)
-}
-
 fn error_text(err: &Error, use_color: bool) -> String {
     let mut lines = Vec::new();
     lines.push(format!(
         "{} {}",
-        colorize_label("error:", use_color, AnsiColor::Red),
-        error_message(err)
+        paint(
+            &tr(Msg::ErrorLabel, &[]),
+            Style::Error,
+            active_theme(use_color)
+        ),
+        err.display_message()
     ));
 
     if let Some(hint) = err.hint() {
         lines.push(format!(
             "{} {hint}",
-            colorize_label("hint:", use_color, AnsiColor::Yellow)
+            paint(
+                &tr(Msg::HintLabel, &[]),
+                Style::Warning,
+                active_theme(use_color)
+            )
         ));
     }
     if let Some(path) = err.path() {
         lines.push(format!(
             "{} {}",
-            colorize_label("path:", use_color, AnsiColor::Yellow),
+            paint("path:", Style::Warning, active_theme(use_color)),
             display_handoff_path_from_path(path)
         ));
     }
     if let Some(seq) = err.seq() {
         lines.push(format!(
             "{} {seq}",
-            colorize_label("seq:", use_color, AnsiColor::Yellow)
+            paint("seq:", Style::Warning, active_theme(use_color))
         ));
     }
     if let Some(offset) = err.offset() {
         lines.push(format!(
             "{} {offset}",
-            colorize_label("offset:", use_color, AnsiColor::Yellow)
+            paint("offset:", Style::Warning, active_theme(use_color))
+        ));
+    }
+    if let Some(request_id) = err.request_id() {
+        lines.push(format!(
+            "{} {request_id}",
+            paint("request_id:", Style::Warning, active_theme(use_color))
         ));
     }
 
-    let causes = error_causes(err);
+    let causes = err.causes();
     if let Some(cause) = causes.first() {
         lines.push(format!(
             "{} {cause}",
-            colorize_label("caused by:", use_color, AnsiColor::Yellow)
+            paint(
+                &tr(Msg::CausedByLabel, &[]),
+                Style::Warning,
+                active_theme(use_color)
+            )
         ));
     }
 
@@ -3235,7 +6149,10 @@ fn error_text(err: &Error, use_color: bool) -> String {
 
 fn emit_follow_timeout_human(timeout_label: &str) {
     if io::stderr().is_terminal() {
-        eprintln!("No messages received (timed out after {timeout_label}).");
+        eprintln!(
+            "{}",
+            tr(Msg::FollowTimedOut, &[("timeout", &timeout_label)])
+        );
     }
 }
 
@@ -3329,13 +6246,18 @@ fn open_feed_reader(path: &str) -> Result<Box<dyn Read>, Error> {
     Ok(Box::new(reader))
 }
 
-fn input_mode_to_ingest(mode: InputMode) -> IngestMode {
+fn input_mode_to_ingest(mode: InputMode, csv_header: CsvHeaderCli) -> IngestMode {
     match mode {
         InputMode::Auto => IngestMode::Auto,
         InputMode::Jsonl => IngestMode::Jsonl,
         InputMode::Json => IngestMode::Json,
         InputMode::Seq => IngestMode::Seq,
         InputMode::Jq => IngestMode::Jq,
+        InputMode::Csv => IngestMode::Csv(match csv_header {
+            CsvHeaderCli::Auto => CsvHeader::Auto,
+            CsvHeaderCli::Yes => CsvHeader::Present,
+            CsvHeaderCli::No => CsvHeader::Absent,
+        }),
     }
 }
 
@@ -3416,6 +6338,7 @@ fn mode_label(mode: IngestMode) -> &'static str {
         IngestMode::Seq => "seq",
         IngestMode::Jq => "jq",
         IngestMode::Event => "event",
+        IngestMode::Csv(_) => "csv",
     }
 }
 
@@ -3423,24 +6346,36 @@ struct FeedIngestContext<'a> {
     pool_ref: &'a str,
     pool_path_label: &'a str,
     tags: &'a [String],
+    sets: &'a FieldSets,
+    /// Frame flag bits set on every appended record.
+    flags: u32,
     durability: Durability,
     retry_config: Option<RetryConfig>,
     pool_handle: &'a mut Pool,
     color_mode: ColorMode,
-    input: InputMode,
+    input: IngestMode,
     errors: ErrorPolicyCli,
+    /// `duplex --record`: each appended record is also written as a `sent` transcript line.
+    record: Option<&'a TranscriptWriter>,
 }
 
 struct RemoteFeedIngestContext<'a> {
     pool_ref: &'a str,
     pool_path_label: &'a str,
     tags: &'a [String],
+    sets: &'a FieldSets,
+    /// Frame flag bits set on every appended record.
+    flags: u32,
     durability: Durability,
     retry_config: Option<RetryConfig>,
     remote_pool: &'a RemotePool,
     color_mode: ColorMode,
-    input: InputMode,
+    input: IngestMode,
     errors: ErrorPolicyCli,
+    /// Records per append request; 1 uses the single-record append endpoint.
+    batch_size: usize,
+    /// `duplex --record` (single-record appends only): appended records become `sent` lines.
+    record: Option<&'a TranscriptWriter>,
 }
 
 fn ingest_from_stdin<R: Read>(
@@ -3449,7 +6384,7 @@ fn ingest_from_stdin<R: Read>(
     emit_receipt: bool,
 ) -> Result<IngestOutcome, Error> {
     let ingest_config = IngestConfig {
-        mode: input_mode_to_ingest(ctx.input),
+        mode: ctx.input,
         errors: error_policy_to_ingest(ctx.errors),
         sniff_bytes: DEFAULT_SNIFF_BYTES,
         sniff_lines: DEFAULT_SNIFF_LINES,
@@ -3461,18 +6396,30 @@ fn ingest_from_stdin<R: Read>(
         reader,
         ingest_config,
         |data| {
+            let data = ctx.sets.apply(data)?;
+            check_control_payload(ctx.flags, &data)?;
             let payload = lite3::encode_message(ctx.tags, &data)?;
             let (seq, timestamp_ns) = retry_with_config(ctx.retry_config, || {
                 let timestamp_ns = now_ns()?;
-                let options = AppendOptions::new(timestamp_ns, ctx.durability);
+                let options =
+                    AppendOptions::new(timestamp_ns, ctx.durability).with_flags(ctx.flags);
                 let seq = ctx
                     .pool_handle
                     .append_with_options(payload.as_slice(), options)?;
                 Ok((seq, timestamp_ns))
             })?;
+            if let Some(record) = ctx.record {
+                record.record(Direction::Sent, &json!({ "seq": seq, "data": data }))?;
+            }
             if emit_receipt {
                 emit_feed_receipt(
-                    feed_receipt_json(seq, timestamp_ns, ctx.tags)?,
+                    feed_receipt_json(
+                        seq,
+                        timestamp_ns,
+                        ctx.tags,
+                        ctx.flags,
+                        control_kind(ctx.flags, &data),
+                    )?,
                     ctx.color_mode,
                 );
             }
@@ -3496,7 +6443,7 @@ fn ingest_from_stdin_remote<R: Read>(
     emit_receipt: bool,
 ) -> Result<IngestOutcome, Error> {
     let ingest_config = IngestConfig {
-        mode: input_mode_to_ingest(ctx.input),
+        mode: ctx.input,
         errors: error_policy_to_ingest(ctx.errors),
         sniff_bytes: DEFAULT_SNIFF_BYTES,
         sniff_lines: DEFAULT_SNIFF_LINES,
@@ -3504,14 +6451,28 @@ fn ingest_from_stdin_remote<R: Read>(
         max_snippet_bytes: DEFAULT_MAX_SNIPPET_BYTES,
     };
 
+    let mut batch = RemoteFeedBatch::default();
     let outcome = ingest(
         reader,
         ingest_config,
         |data| {
+            let data = ctx.sets.apply(data)?;
+            if ctx.batch_size > 1 {
+                return batch.push(data, &ctx, emit_receipt);
+            }
             let message = retry_with_config(ctx.retry_config, || {
-                ctx.remote_pool
-                    .append_json_now(&data, ctx.tags, ctx.durability)
+                ctx.remote_pool.append_json(
+                    &data,
+                    ctx.tags,
+                    AppendOptions::new(0, ctx.durability).with_flags(ctx.flags),
+                )
             })?;
+            if let Some(record) = ctx.record {
+                record.record(
+                    Direction::Sent,
+                    &json!({ "seq": message.seq, "data": data }),
+                )?;
+            }
             if emit_receipt {
                 emit_feed_receipt(feed_receipt_from_message(&message), ctx.color_mode);
             }
@@ -3520,7 +6481,13 @@ fn ingest_from_stdin_remote<R: Read>(
         |failure| {
             ingest_failure_notice(&failure, ctx.pool_ref, ctx.pool_path_label, ctx.color_mode)
         },
-    )?;
+    );
+    // Records accepted before a stop-policy failure are still sent, matching local feed.
+    let flushed = batch.flush(&ctx, emit_receipt);
+    let mut outcome = outcome?;
+    flushed?;
+    outcome.ok -= batch.rejected;
+    outcome.failed += batch.rejected;
 
     if ctx.errors == ErrorPolicyCli::Skip && outcome.failed > 0 {
         ingest_summary_notice(&outcome, ctx.pool_ref, ctx.pool_path_label, ctx.color_mode);
@@ -3529,6 +6496,103 @@ fn ingest_from_stdin_remote<R: Read>(
     Ok(outcome)
 }
 
+/// Records per remote `append_batch` request when feeding from `--file`.
+const DEFAULT_REMOTE_FEED_BATCH: usize = 256;
+/// Flush a remote feed batch once its encoded records reach this size, keeping requests
+/// under the default serve body limit.
+const REMOTE_FEED_BATCH_BYTES: usize = 512 * 1024;
+
+/// Records buffered for one `append_batch` request, plus per-record accounting across batches.
+#[derive(Default)]
+struct RemoteFeedBatch {
+    records: Vec<Value>,
+    bytes: usize,
+    sent: u64,
+    rejected: u64,
+}
+
+impl RemoteFeedBatch {
+    fn push(
+        &mut self,
+        data: Value,
+        ctx: &RemoteFeedIngestContext<'_>,
+        emit_receipt: bool,
+    ) -> Result<(), Error> {
+        self.bytes += serde_json::to_vec(&data).map_or(0, |encoded| encoded.len());
+        self.records.push(data);
+        if self.records.len() >= ctx.batch_size || self.bytes >= REMOTE_FEED_BATCH_BYTES {
+            self.flush(ctx, emit_receipt)?;
+        }
+        Ok(())
+    }
+
+    /// Send buffered records and report each receipt as the server streams it back.
+    fn flush(
+        &mut self,
+        ctx: &RemoteFeedIngestContext<'_>,
+        emit_receipt: bool,
+    ) -> Result<(), Error> {
+        if self.records.is_empty() {
+            return Ok(());
+        }
+        // Retry covers request setup only; the server reads the whole body before appending.
+        let mut receipts = retry_with_config(ctx.retry_config, || {
+            ctx.remote_pool
+                .append_batch(&self.records, ctx.tags, ctx.durability)
+        })?;
+        let base = self.sent;
+        let count = self.records.len() as u64;
+        self.sent += count;
+        self.records.clear();
+        self.bytes = 0;
+
+        let mut received = 0u64;
+        while let Some(receipt) = receipts.next_receipt()? {
+            received += 1;
+            let index = base + receipt.index as u64;
+            match receipt.result {
+                Ok(message) => {
+                    if emit_receipt {
+                        emit_feed_receipt(feed_receipt_from_message(&message), ctx.color_mode);
+                    }
+                }
+                Err(err) if ctx.errors == ErrorPolicyCli::Skip => {
+                    self.rejected += 1;
+                    let failure = IngestFailure {
+                        index,
+                        mode: ctx.input,
+                        message: err.message().unwrap_or("append failed").to_string(),
+                        error_kind: format!("{:?}", err.kind()),
+                        snippet: None,
+                        line: None,
+                    };
+                    ingest_failure_notice(
+                        &failure,
+                        ctx.pool_ref,
+                        ctx.pool_path_label,
+                        ctx.color_mode,
+                    );
+                }
+                Err(err) => {
+                    let message = format!(
+                        "record {index} rejected: {}",
+                        err.message().unwrap_or("append failed")
+                    );
+                    return Err(err.with_message(message));
+                }
+            }
+        }
+        if received < count {
+            return Err(Error::new(ErrorKind::Io)
+                .with_message(format!(
+                    "append receipts ended after {received} of {count} records"
+                ))
+                .with_hint("Earlier records in the batch may be committed; check the pool before re-sending."));
+        }
+        Ok(())
+    }
+}
+
 fn now_ns() -> Result<u64, Error> {
     let duration = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -3555,45 +6619,203 @@ fn format_ts(timestamp_ns: u64) -> Result<String, Error> {
     })
 }
 
-fn feed_receipt_json(seq: u64, timestamp_ns: u64, tags: &[String]) -> Result<Value, Error> {
-    Ok(json!({
+fn feed_receipt_json(
+    seq: u64,
+    timestamp_ns: u64,
+    tags: &[String],
+    flags: u32,
+    control: Option<&str>,
+) -> Result<Value, Error> {
+    let mut receipt = json!({
         "seq": seq,
         "time": format_ts(timestamp_ns)?,
         "meta": {
             "tags": tags,
         },
-    }))
+    });
+    if flags != 0 {
+        receipt["meta"]["flags"] = json!(frame_flag_names(flags));
+    }
+    if let Some(control) = control {
+        receipt["meta"]["control"] = json!(control);
+    }
+    Ok(receipt)
+}
+
+fn annotate_receipt_json(
+    seq: u64,
+    timestamp_ns: u64,
+    tags: &[String],
+    refs: &[u64],
+) -> Result<Value, Error> {
+    let mut receipt = feed_receipt_json(seq, timestamp_ns, tags, 0, None)?;
+    receipt["meta"]["refs"] = json!(refs);
+    Ok(receipt)
 }
 
 fn feed_receipt_from_message(message: &plasmite::api::Message) -> Value {
     json!({
         "seq": message.seq,
         "time": message.time,
-        "meta": {
-            "tags": message.meta.tags,
-        },
+        "meta": message.meta.to_json(),
     })
 }
 
 fn message_to_json(message: &plasmite::api::Message) -> Value {
-    json!({
-        "seq": message.seq,
-        "time": message.time,
-        "meta": {
-            "tags": message.meta.tags,
+    message.to_json()
+}
+
+fn message_from_frame(frame: &FrameRef<'_>) -> Result<Value, Error> {
+    message_from_payload(frame, frame.payload)
+}
+
+/// Decode the message read at `frame` from `pool`, joining chunked payloads. `None` for
+/// continuation chunks and for chunked messages whose first chunks were already overwritten.
+fn pool_message(pool: &Pool, frame: &FrameRef<'_>) -> Result<Option<Value>, Error> {
+    match pool.join_chunks(frame)? {
+        Some(payload) => message_from_payload(frame, &payload).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// `pool_message` for followers; `--strict` validates the whole payload first, and messages
+/// that `--where` field equalities already rule out are skipped (`None`) without decoding.
+fn follow_message(
+    cfg: &FollowConfig,
+    pool: &Pool,
+    frame: &FrameRef<'_>,
+) -> Result<Option<Value>, Error> {
+    if cfg.strict {
+        validate_message_frame(pool, frame)?;
+    }
+    if follow_fields_exclude(cfg, frame) {
+        return Ok(None);
+    }
+    pool_message(pool, frame)
+}
+
+/// Whether a `.data` field read in place from `frame`'s Lite3 envelope proves it fails
+/// `--where`. Control frames (followers must react to them), chunked frames, JSON-codec
+/// payloads, and followers with a control socket are never excluded; neither is a frame whose
+/// fields cannot be read as scalars.
+fn follow_fields_exclude(cfg: &FollowConfig, frame: &FrameRef<'_>) -> bool {
+    if cfg.where_fields.is_empty()
+        || cfg.control.is_some()
+        || frame.flags & (FRAME_FLAG_CONTROL | FRAME_FLAG_CHUNK) != 0
+    {
+        return false;
+    }
+    let Ok((envelope, _)) = unpack_attachment(frame.flags, frame.payload) else {
+        return false;
+    };
+    if payload_codec(envelope) == PoolCodec::Json {
+        return false;
+    }
+    let doc = Lite3DocRef::new(envelope);
+    cfg.where_fields
+        .iter()
+        .any(|(path, expected)| match doc.get_path(path) {
+            Ok(actual) => !scalar_equals(actual.unwrap_or(Lite3Scalar::Null), expected),
+            Err(_) => false,
+        })
+}
+
+/// jq `==` between a scalar read from a Lite3 document and a JSON literal.
+fn scalar_equals(actual: Lite3Scalar<'_>, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Lite3Scalar::Null, Value::Null) => true,
+        (Lite3Scalar::Bool(actual), Value::Bool(expected)) => actual == *expected,
+        (Lite3Scalar::Str(actual), Value::String(expected)) => actual == expected,
+        (Lite3Scalar::I64(actual), Value::Number(expected)) => match expected.as_i64() {
+            Some(expected) => actual == expected,
+            None => expected.as_f64() == Some(actual as f64),
         },
-        "data": message.data,
+        (Lite3Scalar::F64(actual), Value::Number(expected)) => expected.as_f64() == Some(actual),
+        _ => false,
+    }
+}
+
+/// `pool_message` for reads by seq, where a frame holding no whole message is NotFound.
+fn fetched_message(pool: &Pool, frame: &FrameRef<'_>) -> Result<Value, Error> {
+    if let Some(message) = pool_message(pool, frame)? {
+        return Ok(message);
+    }
+    let err = Error::new(ErrorKind::NotFound)
+        .with_message("seq holds part of a chunked message")
+        .with_seq(frame.seq);
+    Err(match chunk_position(frame.flags, frame.payload)? {
+        Some(position) if !position.is_last() => err.with_hint(format!(
+            "Read the whole message at seq {}.",
+            position.message_seq(frame.seq)
+        )),
+        _ => err.with_hint("Earlier chunks of this message were already overwritten."),
     })
 }
 
-fn message_from_frame(frame: &FrameRef<'_>) -> Result<Value, Error> {
-    let (meta, data) = decode_payload(frame.payload)?;
-    Ok(json!({
+/// Read `feed --attach FILE` and reference it from `.data.attachment`.
+fn attach_file(mut data: Value, path: &Path) -> Result<(Value, Vec<u8>), Error> {
+    let bytes = std::fs::read(path).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to read attachment")
+            .with_path(path)
+            .with_source(err)
+    })?;
+    let Some(object) = data.as_object_mut() else {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("--attach needs an object DATA")
+            .with_hint("Wrap the value, e.g. '{\"value\": ...}'."));
+    };
+    if object.contains_key("attachment") {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("DATA already has an attachment field")
+            .with_hint("Rename .data.attachment, or drop --attach."));
+    }
+    let mut reference = attachment_ref(bytes.len());
+    if let Some(name) = path.file_name() {
+        reference[ATTACHMENT_REF_KEY]["name"] = json!(name.to_string_lossy());
+    }
+    object.insert("attachment".to_string(), reference);
+    Ok((data, bytes))
+}
+
+/// Move a fetched message's attachment out of its JSON and into `path` as raw bytes.
+fn save_attachment(message: &mut Value, path: &Path) -> Result<(), Error> {
+    let encoded = message
+        .as_object_mut()
+        .and_then(|object| object.remove("attachment"));
+    let Some(Value::String(encoded)) = encoded else {
+        return Err(Error::new(ErrorKind::NotFound)
+            .with_message("message has no attachment")
+            .with_hint("Drop --attachment-out, or fetch a message fed with --attach."));
+    };
+    std::fs::write(path, base64_decode(&encoded)?).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to write attachment")
+            .with_path(path)
+            .with_source(err)
+    })
+}
+
+fn message_from_payload(frame: &FrameRef<'_>, payload: &[u8]) -> Result<Value, Error> {
+    let (envelope, attachment) = unpack_attachment(frame.flags, payload)?;
+    let (mut meta, data) = decode_payload(envelope)?;
+    let flags = frame.flags & !(FRAME_FLAG_CHUNK | FRAME_FLAG_ATTACHMENT);
+    if flags != 0 {
+        meta["flags"] = json!(frame_flag_names(flags));
+    }
+    if let Some(kind) = control_kind(frame.flags, &data) {
+        meta["control"] = json!(kind);
+    }
+    let mut message = json!({
         "seq": frame.seq,
         "time": format_ts(frame.timestamp_ns)?,
         "meta": meta,
         "data": data,
-    }))
+    });
+    if let Some(attachment) = attachment {
+        message["attachment"] = json!(base64_encode(attachment));
+    }
+    Ok(message)
 }
 
 fn output_value(message: Value, data_only: bool) -> Value {
@@ -3605,6 +6827,10 @@ fn output_value(message: Value, data_only: bool) -> Value {
 }
 
 fn decode_payload(payload: &[u8]) -> Result<(Value, Value), Error> {
+    if payload_codec(payload) == PoolCodec::Json {
+        let (meta, data) = JsonCodec.decode(payload)?;
+        return Ok((json!({ "tags": meta.tags }), data));
+    }
     let doc = Lite3DocRef::new(payload);
     let meta_type = doc
         .type_at_key(0, "meta")
@@ -3637,7 +6863,19 @@ fn decode_payload(payload: &[u8]) -> Result<(Value, Value), Error> {
         })?;
         tags.push(tag);
     }
-    let meta = json!({ "tags": tags });
+    let mut meta = json!({ "tags": tags });
+    // `meta.refs` is optional: only annotations written by `plasmite annotate` carry it.
+    if doc.type_at_key(meta_ofs, "refs").is_ok() {
+        let refs_json = doc
+            .key_offset_at(meta_ofs, "refs")
+            .and_then(|refs_ofs| doc.to_json_at(refs_ofs, false))?;
+        let refs: Vec<u64> = serde_json::from_str(&refs_json).map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("meta.refs must be seq array")
+                .with_source(err)
+        })?;
+        meta["refs"] = json!(refs);
+    }
 
     let data_ofs = doc
         .key_offset("data")
@@ -3663,6 +6901,132 @@ impl DropNotice {
     }
 }
 
+/// Idle wait schedule for local follow (`--poll-min`, `--poll-max`, `--poll-strategy`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FollowPoll {
+    min: Duration,
+    max: Duration,
+    strategy: PollStrategy,
+}
+
+impl Default for FollowPoll {
+    fn default() -> Self {
+        Self {
+            min: Duration::from_millis(1),
+            max: Duration::from_millis(50),
+            strategy: PollStrategy::Exponential,
+        }
+    }
+}
+
+impl FollowPoll {
+    /// Wait used right after a message arrives.
+    fn initial(&self) -> Duration {
+        match self.strategy {
+            PollStrategy::Exponential => self.min,
+            PollStrategy::Fixed => self.max,
+        }
+    }
+
+    /// Wait used after an idle wait of `current`.
+    fn next(&self, current: Duration) -> Duration {
+        match self.strategy {
+            PollStrategy::Exponential => current.saturating_mul(2).clamp(self.min, self.max),
+            PollStrategy::Fixed => self.max,
+        }
+    }
+}
+
+/// Cumulative follow counters for `--stats-interval` notices.
+struct FollowStats {
+    interval: Duration,
+    last_emit: Instant,
+    emitted: u64,
+    bytes: u64,
+    dropped: u64,
+    wakeups: u64,
+    notified_wakeups: u64,
+    poll: Option<Duration>,
+}
+
+impl FollowStats {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_emit: Instant::now(),
+            emitted: 0,
+            bytes: 0,
+            dropped: 0,
+            wakeups: 0,
+            notified_wakeups: 0,
+            poll: None,
+        }
+    }
+
+    fn record_emitted(&mut self, payload_len: usize) {
+        self.emitted += 1;
+        self.bytes += payload_len as u64;
+    }
+
+    /// Count one idle wait of `poll`; `notified` when an append signal ended it early.
+    fn record_wakeup(&mut self, poll: Duration, notified: bool) {
+        self.wakeups += 1;
+        if notified {
+            self.notified_wakeups += 1;
+        }
+        self.poll = Some(poll);
+    }
+
+    /// Emit a `stats` notice once the interval has elapsed; lag is measured against the
+    /// pool's newest seq at that moment.
+    fn maybe_emit(
+        &mut self,
+        pool: &Pool,
+        pool_ref: &str,
+        last_seen_seq: Option<u64>,
+        color_mode: ColorMode,
+    ) -> Result<(), Error> {
+        if self.last_emit.elapsed() < self.interval {
+            return Ok(());
+        }
+        self.last_emit = Instant::now();
+        let Some(time) = notice_time_now() else {
+            return Ok(());
+        };
+        let newest_seq = pool.header_from_mmap()?.newest_seq;
+        let lag = last_seen_seq.map_or(0, |seq| newest_seq.saturating_sub(seq));
+        let mut details = Map::new();
+        details.insert("emitted".to_string(), json!(self.emitted));
+        details.insert("bytes".to_string(), json!(self.bytes));
+        details.insert("lag".to_string(), json!(lag));
+        details.insert("dropped".to_string(), json!(self.dropped));
+        details.insert("wakeups".to_string(), json!(self.wakeups));
+        details.insert("notified_wakeups".to_string(), json!(self.notified_wakeups));
+        if let Some(poll) = self.poll {
+            details.insert("poll_ms".to_string(), json!(poll.as_millis() as u64));
+        }
+        details.insert("last_seen_seq".to_string(), json!(last_seen_seq));
+        details.insert("newest_seq".to_string(), json!(newest_seq));
+        details.insert(
+            "interval_ms".to_string(),
+            json!(self.interval.as_millis() as u64),
+        );
+        let notice = Notice {
+            kind: "stats".to_string(),
+            time,
+            cmd: "follow".to_string(),
+            pool: pool_ref.to_string(),
+            message: format!(
+                "emitted {} messages ({} bytes), lag {lag}, dropped {}, {} wakeups",
+                self.emitted, self.bytes, self.dropped, self.wakeups
+            ),
+            details,
+        };
+        emit_notice(&notice, color_mode);
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 struct FollowConfig {
     tail: u64,
@@ -3673,69 +7037,404 @@ struct FollowConfig {
     since_ns: Option<u64>,
     required_tags: Vec<String>,
     where_predicates: Vec<JqFilter>,
+    /// String values `--where` requires; history scans skip frames whose bloom filter lacks one.
+    where_strings: Vec<String>,
+    /// `.data` field equalities `--where` requires; Lite3 frames failing one are not decoded.
+    where_fields: Vec<(String, Value)>,
     quiet_drops: bool,
     notify: bool,
     color_mode: ColorMode,
     replay_speed: Option<f64>,
+    /// `--until`, `--speed-ramp`, and `--pace-by`; only read when `replay_speed` is set.
+    replay_control: ReplayControl,
     suppress_sender: Option<String>,
     stop: Option<Arc<AtomicBool>>,
+    exec: Option<Arc<Mutex<ExecHook>>>,
+    thread: Option<Arc<Mutex<ThreadView>>>,
+    projection: Option<Arc<Projection>>,
+    /// `--jq`: each message envelope is replaced by the values this program yields.
+    jq: Option<JqFilter>,
+    stats_interval: Option<Duration>,
+    poll: FollowPoll,
+    on_sigpipe: OnSigpipe,
+    show_control: bool,
+    /// `--strict`: validate each message's payload in full before decoding it.
+    strict: bool,
+    /// `duplex --record`: every emitted message is also written as a `recv` transcript line.
+    record: Option<Arc<TranscriptWriter>>,
+    /// `--control-socket`: pause/resume, `--where` swaps, and counters driven by `plasmite ctl`.
+    control: Option<Arc<FollowControl>>,
+}
+
+/// How often a follower paused over its control socket checks for `resume`.
+const FOLLOW_PAUSE_POLL: Duration = Duration::from_millis(50);
+
+/// Flush batched follow output once this many bytes are pending, even mid-drain.
+const FOLLOW_BATCH_BYTES: usize = 64 * 1024;
+
+/// Compact follow output encoded without per-message envelope `Value`s and written once per drain.
+struct FollowBatch {
+    encoder: FrameJsonlEncoder,
+}
+
+impl FollowBatch {
+    /// The fast path covers plain JSONL output; exec hooks, transcripts, threading, projection, `--jq`, pretty/color output,
+    /// `--where`, sender suppression, `--show-control`, and `--tail` waits need the decoded `Value` and use `follow_emit`.
+    /// `--strict` also stays on `follow_message`, which validates each frame before emitting it.
+    fn for_config(cfg: &FollowConfig, codec: PoolCodec, tail_wait: bool) -> Option<Self> {
+        let eligible = cfg.exec.is_none()
+            && cfg.record.is_none()
+            && cfg.control.is_none()
+            && cfg.thread.is_none()
+            && cfg.projection.is_none()
+            && cfg.jq.is_none()
+            && !cfg.pretty
+            && cfg.where_predicates.is_empty()
+            && cfg.suppress_sender.is_none()
+            && !cfg.show_control
+            && !cfg.strict
+            && !tail_wait;
+        eligible.then(|| Self {
+            encoder: FrameJsonlEncoder::new(codec),
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        use std::io::Write as _;
+        let mut out = io::stdout().lock();
+        self.encoder
+            .write_to(&mut out)
+            .and_then(|()| out.flush())
+            .or_else(|err| {
+                if downstream::note_write_error(&err) {
+                    return Ok(());
+                }
+                Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to write to stdout")
+                    .with_source(err))
+            })
+    }
+}
+
+impl Drop for FollowBatch {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+fn follow_emit(cfg: &FollowConfig, message: Value) -> Result<(), Error> {
+    if let Some(record) = &cfg.record {
+        record.record(Direction::Recv, &message)?;
+    }
+    if let Some(control) = &cfg.control {
+        control.record_emitted(message.get("seq").and_then(Value::as_u64));
+    }
+    if let Some(view) = &cfg.thread {
+        let output = output_value(message.clone(), cfg.data_only);
+        view.lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .push(&message, &output);
+        return Ok(());
+    }
+    if let Some(projection) = &cfg.projection {
+        return follow_write_line(&projection.render(&message));
+    }
+    if let Some(program) = &cfg.jq {
+        for value in program.outputs(&message) {
+            follow_output(cfg, value)?;
+        }
+        return Ok(());
+    }
+    if cfg.pretty && cfg.exec.is_none() {
+        if let Some(line) = annotation_line(&message) {
+            return follow_write_line(&line);
+        }
+    }
+    follow_output(cfg, output_value(message, cfg.data_only))
+}
+
+/// Print `value`, or hand it to the `--exec` hook.
+fn follow_output(cfg: &FollowConfig, value: Value) -> Result<(), Error> {
+    match &cfg.exec {
+        Some(hook) => hook
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .run(&value),
+        None => {
+            emit_message(value, cfg.pretty, cfg.color_mode);
+            Ok(())
+        }
+    }
+}
+
+fn follow_write_line(line: &str) -> Result<(), Error> {
+    downstream::write_line(line).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to write to stdout")
+            .with_source(err)
+    })
+}
+
+/// Under `--on-sigpipe exit`, ends follow once stdout's reader has gone away (e.g. `| head -5`),
+/// emitting a `downstream_closed` notice. `probe` also polls an idle stdout for a hangup.
+fn follow_downstream_closed(
+    cfg: &FollowConfig,
+    pool_ref: &str,
+    last_seen_seq: Option<u64>,
+    probe: bool,
+) -> Option<RunOutcome> {
+    if cfg.on_sigpipe != OnSigpipe::Exit {
+        return None;
+    }
+    let closed = if probe {
+        downstream::probe_stdout_closed()
+    } else {
+        downstream::stdout_closed()
+    };
+    if !closed {
+        return None;
+    }
+    if let Some(time) = notice_time_now() {
+        let mut details = Map::new();
+        details.insert("last_seen_seq".to_string(), json!(last_seen_seq));
+        let notice = Notice {
+            kind: "downstream_closed".to_string(),
+            time,
+            cmd: "follow".to_string(),
+            pool: pool_ref.to_string(),
+            message: "stdout closed by reader; stopping".to_string(),
+            details,
+        };
+        emit_notice(&notice, cfg.color_mode);
+    }
+    Some(RunOutcome::ok())
+}
+
+/// Pretty follow renders annotations (messages with `meta.refs`) as one indented line
+/// beneath the message they reference instead of a full JSON block.
+fn annotation_line(message: &Value) -> Option<String> {
+    let refs = message
+        .get("meta")
+        .and_then(|meta| meta.get("refs"))
+        .and_then(Value::as_array)
+        .filter(|refs| !refs.is_empty())?;
+    let targets = refs
+        .iter()
+        .filter_map(Value::as_u64)
+        .map(|seq| format!("#{seq}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    let seq = message.get("seq").and_then(Value::as_u64).unwrap_or(0);
+    let data = message.get("data").cloned().unwrap_or(Value::Null);
+    Some(format!(
+        "  \u{21b3} #{seq} on {targets}: {}",
+        serde_json::to_string(&data).unwrap_or_default()
+    ))
 }
 
 fn matches_required_tags(required_tags: &[String], message: &Value) -> bool {
     if required_tags.is_empty() {
         return true;
     }
-    let Some(tags) = message
-        .get("meta")
-        .and_then(|meta| meta.get("tags"))
-        .and_then(Value::as_array)
-    else {
-        return false;
+    let Some(tags) = message
+        .get("meta")
+        .and_then(|meta| meta.get("tags"))
+        .and_then(Value::as_array)
+    else {
+        return false;
+    };
+    required_tags.iter().all(|required| {
+        tags.iter()
+            .any(|tag| tag.as_str().is_some_and(|value| value == required))
+    })
+}
+
+fn should_suppress_sender(message: &Value, sender: &str) -> bool {
+    message
+        .get("data")
+        .and_then(|data| data.get("from"))
+        .and_then(Value::as_str)
+        .is_some_and(|value| value == sender)
+}
+
+fn duplex_requires_me_when_tty(stdin_is_terminal: bool, me: Option<&str>) -> bool {
+    stdin_is_terminal && me.is_none()
+}
+
+fn parse_duplex_tty_line(me: &str, line: &str) -> Option<Value> {
+    let trimmed = line.trim_end_matches(&['\r', '\n'][..]);
+    if trimmed.trim().is_empty() {
+        return None;
+    }
+    Some(json!({
+        "from": me,
+        "msg": trimmed,
+    }))
+}
+
+fn should_suppress_message(cfg: &FollowConfig, message: &Value) -> bool {
+    (!cfg.show_control && message_control_kind(message).is_some())
+        || cfg
+            .suppress_sender
+            .as_deref()
+            .is_some_and(|sender| should_suppress_sender(message, sender))
+}
+
+fn message_control_kind(message: &Value) -> Option<&str> {
+    message
+        .get("meta")
+        .and_then(|meta| meta.get("control"))
+        .and_then(Value::as_str)
+}
+
+/// Control kind of a frame read live, decoding only control-flagged frames.
+fn frame_control_kind(frame: &FrameRef<'_>) -> Result<Option<String>, Error> {
+    if frame.flags & FRAME_FLAG_CONTROL == 0 {
+        return Ok(None);
+    }
+    let message = message_from_frame(frame)?;
+    Ok(message_control_kind(&message).map(str::to_string))
+}
+
+/// Follows the file behind a pool path so a `rotated` control message can reopen it.
+struct RotationWatch {
+    pending: bool,
+    identity: Option<(u64, u64)>,
+}
+
+impl RotationWatch {
+    fn new(path: &Path) -> Self {
+        Self {
+            pending: false,
+            identity: pool_file_identity(path),
+        }
+    }
+
+    /// After `rotated`, the replacement pool once `path` names a different file.
+    fn poll(&mut self, path: &Path) -> Result<Option<Pool>, Error> {
+        if !self.pending {
+            return Ok(None);
+        }
+        let current = pool_file_identity(path);
+        if current.is_none() || (self.identity.is_some() && current == self.identity) {
+            return Ok(None);
+        }
+        match Pool::open(path) {
+            Ok(pool) => {
+                self.pending = false;
+                self.identity = current;
+                Ok(Some(pool))
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn pool_file_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path)
+        .ok()
+        .map(|meta| (meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn pool_file_identity(path: &Path) -> Option<(u64, u64)> {
+    // No stable file identity: any existing file counts as the replacement.
+    path.exists().then_some((0, 0))
+}
+
+/// Apply a live control message; `Some` ends the command.
+fn react_to_control(
+    cmd: &str,
+    pool_ref: &str,
+    kind: &str,
+    seq: u64,
+    rotation: &mut RotationWatch,
+    color_mode: ColorMode,
+) -> Option<RunOutcome> {
+    let message = match kind {
+        CONTROL_ROTATED => {
+            rotation.pending = true;
+            "pool rotated; reopening once the new file is in place"
+        }
+        CONTROL_DELETED => "pool deleted; stopping",
+        _ => return None,
+    };
+    emit_control_notice(cmd, pool_ref, kind, Some(seq), message, color_mode);
+    (kind == CONTROL_DELETED).then(RunOutcome::ok)
+}
+
+fn emit_control_notice(
+    cmd: &str,
+    pool_ref: &str,
+    kind: &str,
+    seq: Option<u64>,
+    message: &str,
+    color_mode: ColorMode,
+) {
+    let Some(time) = notice_time_now() else {
+        return;
+    };
+    let mut details = Map::new();
+    details.insert("control".to_string(), json!(kind));
+    if let Some(seq) = seq {
+        details.insert("seq".to_string(), json!(seq));
+    }
+    let notice = Notice {
+        kind: "control".to_string(),
+        time,
+        cmd: cmd.to_string(),
+        pool: pool_ref.to_string(),
+        message: message.to_string(),
+        details,
     };
-    required_tags.iter().all(|required| {
-        tags.iter()
-            .any(|tag| tag.as_str().is_some_and(|value| value == required))
-    })
+    emit_notice(&notice, color_mode);
 }
 
-fn should_suppress_sender(message: &Value, sender: &str) -> bool {
-    message
-        .get("data")
-        .and_then(|data| data.get("from"))
-        .and_then(Value::as_str)
-        .is_some_and(|value| value == sender)
+fn follow_should_stop(stop: Option<&Arc<AtomicBool>>) -> bool {
+    stop.is_some_and(|flag| flag.load(Ordering::Acquire))
 }
 
-fn duplex_requires_me_when_tty(stdin_is_terminal: bool, me: Option<&str>) -> bool {
-    stdin_is_terminal && me.is_none()
+/// Whether `frame`'s bloom filter proves it fails `--where`, so it need not be decoded.
+/// Filters swapped in over the control socket are not covered, so followers with one never skip.
+fn follow_bloom_excludes(cfg: &FollowConfig, frame: &FrameRef<'_>) -> bool {
+    cfg.control.is_none()
+        && cfg
+            .where_strings
+            .iter()
+            .any(|value| !bloom_might_contain(frame.bloom, value))
 }
 
-fn parse_duplex_tty_line(me: &str, line: &str) -> Option<Value> {
-    let trimmed = line.trim_end_matches(&['\r', '\n'][..]);
-    if trimmed.trim().is_empty() {
-        return None;
+/// `--where` check that honors filters swapped in over the control socket.
+fn follow_matches_where(cfg: &FollowConfig, message: &Value) -> Result<bool, Error> {
+    match &cfg.control {
+        Some(control) => control.matches(cfg.where_predicates.as_slice(), message),
+        None => matches_all(cfg.where_predicates.as_slice(), message),
     }
-    Some(json!({
-        "from": me,
-        "msg": trimmed,
-    }))
 }
 
-fn should_suppress_message(cfg: &FollowConfig, message: &Value) -> bool {
-    cfg.suppress_sender
-        .as_deref()
-        .is_some_and(|sender| should_suppress_sender(message, sender))
+/// One remote `follow` endpoint; several are tried in priority order on persistent errors.
+struct RemoteFollowTarget {
+    client: RemoteClient,
+    pool: String,
 }
 
-fn follow_should_stop(stop: Option<&Arc<AtomicBool>>) -> bool {
-    stop.is_some_and(|flag| flag.load(Ordering::Acquire))
+/// Consecutive failures on one remote before failing over to the next.
+const REMOTE_FOLLOW_ATTEMPTS: u32 = 3;
+const REMOTE_FOLLOW_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+enum RemoteFollowCycle {
+    Finished(RunOutcome),
+    Drained {
+        emitted: bool,
+    },
+    /// The remote itself failed (connect, info, or stream); a candidate for failover.
+    Failed(Error),
 }
 
-fn follow_remote(
-    client: &RemoteClient,
-    pool: &str,
-    cfg: &FollowConfig,
-) -> Result<RunOutcome, Error> {
+fn follow_remote(targets: &[RemoteFollowTarget], cfg: &FollowConfig) -> Result<RunOutcome, Error> {
     if cfg.replay_speed.is_some() {
         return Err(Error::new(ErrorKind::Usage)
             .with_message("remote follow does not support --replay")
@@ -3757,81 +7456,214 @@ fn follow_remote(
             .with_hint("--quiet-drops only applies to local drop notices."));
     }
 
-    let remote_pool = client.open_pool(&PoolRef::name(pool))?;
-
-    let mut next_since_seq = if cfg.tail > 0 {
-        let info = remote_pool.info()?;
-        match (info.bounds.oldest_seq, info.bounds.newest_seq) {
-            (Some(oldest), Some(newest)) => Some(
-                newest
-                    .saturating_sub(cfg.tail.saturating_sub(1))
-                    .max(oldest),
-            ),
-            _ => None,
-        }
-    } else {
-        None
-    };
-
+    let mut active = 0usize;
+    let mut failures = 0u32;
+    let mut exhausted = 0usize;
+    let mut next_since_seq = None::<u64>;
+    let mut seeded = cfg.tail == 0;
     let mut tail_wait_matches = VecDeque::new();
     loop {
         if follow_should_stop(cfg.stop.as_ref()) {
             return Ok(RunOutcome::ok());
         }
-
-        let mut options = TailOptions::new();
-        options.since_seq = next_since_seq;
-        options.timeout = cfg.timeout;
-        let mut tail = remote_pool.tail(options)?;
-
-        let mut emitted_in_cycle = false;
-        while let Some(message) = tail.next_message()? {
-            if follow_should_stop(cfg.stop.as_ref()) {
-                return Ok(RunOutcome::ok());
-            }
-            next_since_seq = Some(message.seq.saturating_add(1));
-            let value = message_to_json(&message);
-            if should_suppress_message(cfg, &value)
-                || !matches_required_tags(cfg.required_tags.as_slice(), &value)
-                || !matches_all(cfg.where_predicates.as_slice(), &value)?
-            {
-                continue;
+        let since_before = next_since_seq;
+        let cycle = follow_remote_cycle(
+            &targets[active],
+            cfg,
+            &mut next_since_seq,
+            &mut seeded,
+            &mut tail_wait_matches,
+        )?;
+        match cycle {
+            RemoteFollowCycle::Finished(outcome) => return Ok(outcome),
+            RemoteFollowCycle::Drained { emitted } => {
+                failures = 0;
+                exhausted = 0;
+                if cfg.timeout.is_some() && !emitted {
+                    return Ok(RunOutcome::with_code(124));
+                }
             }
-
-            if cfg.one && cfg.tail > 0 {
-                tail_wait_matches.push_back(value);
-                while tail_wait_matches.len() > cfg.tail as usize {
-                    tail_wait_matches.pop_front();
+            RemoteFollowCycle::Failed(err) => {
+                if targets.len() == 1
+                    || matches!(err.kind(), ErrorKind::Usage | ErrorKind::Permission)
+                {
+                    return Err(err);
                 }
-                if tail_wait_matches.len() == cfg.tail as usize {
-                    if let Some(latest) = tail_wait_matches.back() {
-                        emit_message(
-                            output_value(latest.clone(), cfg.data_only),
-                            cfg.pretty,
-                            cfg.color_mode,
-                        );
-                    }
-                    return Ok(RunOutcome::ok());
+                if next_since_seq != since_before {
+                    // The stream made progress before failing; start counting afresh.
+                    failures = 0;
+                    exhausted = 0;
                 }
-                emitted_in_cycle = true;
-                continue;
+                failures += 1;
+                if failures < REMOTE_FOLLOW_ATTEMPTS {
+                    std::thread::sleep(REMOTE_FOLLOW_RETRY_DELAY * failures);
+                    continue;
+                }
+                failures = 0;
+                exhausted += 1;
+                if exhausted >= targets.len() {
+                    return Err(err);
+                }
+                let from = active;
+                active = (active + 1) % targets.len();
+                emit_follow_failover_notice(
+                    &targets[from],
+                    &targets[active],
+                    &err,
+                    next_since_seq,
+                    cfg.color_mode,
+                );
             }
+        }
+    }
+}
 
-            emit_message(
-                output_value(value, cfg.data_only),
-                cfg.pretty,
-                cfg.color_mode,
-            );
-            emitted_in_cycle = true;
-            if cfg.one {
-                return Ok(RunOutcome::ok());
+/// Tail one remote until its stream ends or fails, advancing `next_since_seq` past every seq seen so
+/// a later cycle (on this or another remote) never re-emits a message.
+fn follow_remote_cycle(
+    target: &RemoteFollowTarget,
+    cfg: &FollowConfig,
+    next_since_seq: &mut Option<u64>,
+    seeded: &mut bool,
+    tail_wait_matches: &mut VecDeque<Value>,
+) -> Result<RemoteFollowCycle, Error> {
+    let mut tail = match open_remote_follow_stream(target, cfg, next_since_seq, seeded) {
+        Ok(tail) => tail,
+        Err(err) => return Ok(RemoteFollowCycle::Failed(err)),
+    };
+
+    let mut emitted = false;
+    loop {
+        let message = match tail.next_message() {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(err) => return Ok(RemoteFollowCycle::Failed(err)),
+        };
+        if follow_should_stop(cfg.stop.as_ref()) {
+            return Ok(RemoteFollowCycle::Finished(RunOutcome::ok()));
+        }
+        if next_since_seq.is_some_and(|since| message.seq < since) {
+            continue;
+        }
+        *next_since_seq = Some(message.seq.saturating_add(1));
+        let value = message_to_json(&message);
+        if should_suppress_message(cfg, &value)
+            || !matches_required_tags(cfg.required_tags.as_slice(), &value)
+            || !follow_matches_where(cfg, &value)?
+        {
+            continue;
+        }
+
+        if cfg.one && cfg.tail > 0 {
+            tail_wait_matches.push_back(value);
+            while tail_wait_matches.len() > cfg.tail as usize {
+                tail_wait_matches.pop_front();
+            }
+            if tail_wait_matches.len() == cfg.tail as usize {
+                if let Some(latest) = tail_wait_matches.back() {
+                    follow_emit(cfg, latest.clone())?;
+                }
+                return Ok(RemoteFollowCycle::Finished(RunOutcome::ok()));
             }
+            emitted = true;
+            continue;
+        }
+
+        follow_emit(cfg, value)?;
+        if let Some(outcome) = follow_downstream_closed(cfg, &target.pool, Some(message.seq), false)
+        {
+            return Ok(RemoteFollowCycle::Finished(outcome));
         }
+        emitted = true;
+        if cfg.one {
+            return Ok(RemoteFollowCycle::Finished(RunOutcome::ok()));
+        }
+    }
+    Ok(RemoteFollowCycle::Drained { emitted })
+}
+
+fn open_remote_follow_stream(
+    target: &RemoteFollowTarget,
+    cfg: &FollowConfig,
+    next_since_seq: &mut Option<u64>,
+    seeded: &mut bool,
+) -> Result<RemoteTail, Error> {
+    let remote_pool = target
+        .client
+        .open_pool(&PoolRef::name(target.pool.as_str()))?;
+
+    if !*seeded {
+        let info = remote_pool.info()?;
+        *next_since_seq = match (info.bounds.oldest_seq, info.bounds.newest_seq) {
+            (Some(oldest), Some(newest)) => Some(
+                newest
+                    .saturating_sub(cfg.tail.saturating_sub(1))
+                    .max(oldest),
+            ),
+            _ => None,
+        };
+        *seeded = true;
+    }
+
+    let mut options = TailOptions::new();
+    options.since_seq = *next_since_seq;
+    options.timeout = cfg.timeout;
+    remote_pool.tail(options)
+}
+
+fn remote_follow_ref(target: &RemoteFollowTarget) -> String {
+    format!(
+        "{}/{}",
+        target.client.base_url().as_str().trim_end_matches('/'),
+        target.pool
+    )
+}
+
+fn emit_follow_failover_notice(
+    from: &RemoteFollowTarget,
+    to: &RemoteFollowTarget,
+    err: &Error,
+    next_since_seq: Option<u64>,
+    color_mode: ColorMode,
+) {
+    let Some(time) = notice_time_now() else {
+        return;
+    };
+    let from_ref = remote_follow_ref(from);
+    let to_ref = remote_follow_ref(to);
+    let mut details = Map::new();
+    details.insert("from".to_string(), json!(from_ref));
+    details.insert("to".to_string(), json!(to_ref));
+    details.insert("error".to_string(), err.to_json()["error"].clone());
+    details.insert("since_seq".to_string(), json!(next_since_seq));
+    let notice = Notice {
+        kind: "failover".to_string(),
+        time,
+        cmd: "follow".to_string(),
+        pool: to.pool.clone(),
+        message: format!("failing over from {from_ref} to {to_ref}"),
+        details,
+    };
+    emit_notice(&notice, color_mode);
+}
 
-        if cfg.timeout.is_some() && !emitted_in_cycle {
-            return Ok(RunOutcome::with_code(124));
+/// Starting offset for `--tail N`: without filters every frame counts, so the inline index
+/// can locate seq `newest - N + 1` directly instead of scanning from `tail_off`.
+fn tail_seed_offset(pool: &Pool, cfg: &FollowConfig, tail_off: usize) -> Result<usize, Error> {
+    let unfiltered = cfg.required_tags.is_empty()
+        && cfg.where_predicates.is_empty()
+        && cfg.suppress_sender.is_none()
+        && !pool.is_chunked();
+    let bounds = pool.bounds()?;
+    if let (true, Some(oldest), Some(newest)) = (unfiltered, bounds.oldest_seq, bounds.newest_seq) {
+        let start = newest
+            .saturating_sub(cfg.tail.saturating_sub(1))
+            .max(oldest);
+        if let Some(offset) = pool.locate(start)? {
+            return Ok(offset);
         }
     }
+    Ok(tail_off)
 }
 
 fn follow_pool(
@@ -3841,7 +7673,7 @@ fn follow_pool(
     cfg: FollowConfig,
 ) -> Result<RunOutcome, Error> {
     if cfg.replay_speed.is_some() {
-        return follow_replay(pool, &cfg);
+        return follow_replay(pool, pool_ref, &cfg);
     }
 
     let mut cursor = Cursor::new();
@@ -3862,6 +7694,9 @@ fn follow_pool(
     if notify_enabled && notify_handle.is_none() {
         notify_enabled = false;
     }
+    // Advisory presence for `pool who`; unwritable pool dirs simply go unregistered.
+    let mut presence = register_pool_user(pool_path, PoolRole::Reader, "follow", None).ok();
+    let mut stats = cfg.stats_interval.map(FollowStats::new);
 
     let bump_timeout = |deadline: &mut Option<Instant>| {
         if let Some(duration) = cfg.timeout {
@@ -3881,17 +7716,28 @@ fn follow_pool(
                         return Ok(RunOutcome::ok());
                     }
                     if frame.timestamp_ns >= since_ns {
-                        let message = message_from_frame(&frame)?;
+                        if follow_bloom_excludes(&cfg, &frame) {
+                            last_seen_seq = Some(frame.seq);
+                            continue;
+                        }
+                        let Some(message) = follow_message(&cfg, pool, &frame)? else {
+                            last_seen_seq = Some(frame.seq);
+                            continue;
+                        };
                         if !should_suppress_message(&cfg, &message)
                             && matches_required_tags(cfg.required_tags.as_slice(), &message)
-                            && matches_all(cfg.where_predicates.as_slice(), &message)?
+                            && follow_matches_where(&cfg, &message)?
                         {
-                            emit_message(
-                                output_value(message, cfg.data_only),
-                                cfg.pretty,
-                                cfg.color_mode,
-                            );
+                            follow_emit(&cfg, message)?;
+                            if let Some(outcome) =
+                                follow_downstream_closed(&cfg, pool_ref, Some(frame.seq), false)
+                            {
+                                return Ok(outcome);
+                            }
                             bump_timeout(&mut timeout_deadline);
+                            if let Some(stats) = stats.as_mut() {
+                                stats.record_emitted(frame.payload.len());
+                            }
                             if cfg.one {
                                 return Ok(RunOutcome::ok());
                             }
@@ -3907,7 +7753,7 @@ fn follow_pool(
             }
         }
     } else if cfg.tail > 0 {
-        cursor.seek_to(header.tail_off as usize);
+        cursor.seek_to(tail_seed_offset(pool, &cfg, header.tail_off as usize)?);
         loop {
             if follow_should_stop(cfg.stop.as_ref()) {
                 return Ok(RunOutcome::ok());
@@ -3917,12 +7763,19 @@ fn follow_pool(
                     if follow_should_stop(cfg.stop.as_ref()) {
                         return Ok(RunOutcome::ok());
                     }
-                    let message = message_from_frame(&frame)?;
+                    if follow_bloom_excludes(&cfg, &frame) {
+                        last_seen_seq = Some(frame.seq);
+                        continue;
+                    }
+                    let Some(message) = follow_message(&cfg, pool, &frame)? else {
+                        last_seen_seq = Some(frame.seq);
+                        continue;
+                    };
                     if !should_suppress_message(&cfg, &message)
                         && matches_required_tags(cfg.required_tags.as_slice(), &message)
-                        && matches_all(cfg.where_predicates.as_slice(), &message)?
+                        && follow_matches_where(&cfg, &message)?
                     {
-                        emit.push_back(message);
+                        emit.push_back((message, frame.payload.len()));
                     }
                     last_seen_seq = Some(frame.seq);
                     while emit.len() > cfg.tail as usize {
@@ -3938,23 +7791,23 @@ fn follow_pool(
         }
         if tail_wait {
             if emit.len() >= cfg.tail as usize {
-                if let Some(value) = emit.back() {
-                    emit_message(
-                        output_value(value.clone(), cfg.data_only),
-                        cfg.pretty,
-                        cfg.color_mode,
-                    );
+                if let Some((value, _)) = emit.back() {
+                    follow_emit(&cfg, value.clone())?;
                 }
                 return Ok(RunOutcome::ok());
             }
         } else {
-            for value in emit.drain(..) {
-                emit_message(
-                    output_value(value, cfg.data_only),
-                    cfg.pretty,
-                    cfg.color_mode,
-                );
+            for (value, payload_len) in emit.drain(..) {
+                follow_emit(&cfg, value)?;
+                if let Some(outcome) =
+                    follow_downstream_closed(&cfg, pool_ref, last_seen_seq, false)
+                {
+                    return Ok(outcome);
+                }
                 bump_timeout(&mut timeout_deadline);
+                if let Some(stats) = stats.as_mut() {
+                    stats.record_emitted(payload_len);
+                }
             }
         }
     }
@@ -3963,8 +7816,7 @@ fn follow_pool(
         cursor.seek_to(header.head_off as usize);
     }
 
-    let mut backoff = Duration::from_millis(1);
-    let max_backoff = Duration::from_millis(50);
+    let mut backoff = cfg.poll.initial();
 
     let pool_ref = pool_ref.to_string();
     let pool_path_label = pool_path.display().to_string();
@@ -4033,10 +7885,25 @@ fn follow_pool(
         }
     };
 
+    let mut batch = FollowBatch::for_config(&cfg, pool.codec(), tail_wait);
+    // Control messages only steer live reading; historical ones from the seed phase are inert.
+    let mut rotation = RotationWatch::new(pool_path);
+    let mut reopened: Option<Pool> = None;
     loop {
+        let pool = reopened.as_ref().unwrap_or(pool);
         if follow_should_stop(cfg.stop.as_ref()) {
             return Ok(RunOutcome::ok());
         }
+        if cfg
+            .control
+            .as_ref()
+            .is_some_and(|control| control.is_paused())
+        {
+            // Paused followers hold their cursor and keep the --timeout clock from running out.
+            bump_timeout(&mut timeout_deadline);
+            std::thread::sleep(FOLLOW_PAUSE_POLL);
+            continue;
+        }
         match cursor.next(pool)? {
             CursorResult::Message(frame) => {
                 if follow_should_stop(cfg.stop.as_ref()) {
@@ -4044,91 +7911,196 @@ fn follow_pool(
                 }
                 if let Some(last_seen_seq) = last_seen_seq {
                     if frame.seq > last_seen_seq + 1 {
+                        if let Some(stats) = stats.as_mut() {
+                            stats.dropped += frame.seq - last_seen_seq - 1;
+                        }
                         queue_drop(last_seen_seq, frame.seq, &mut pending_drop);
                         maybe_emit_pending(&mut pending_drop, &mut last_notice_at);
                     }
                 }
-                let message = message_from_frame(&frame)?;
+                let control = frame_control_kind(&frame)?;
+                if let (Some(batch), Some(kind)) = (batch.as_mut(), control.as_deref()) {
+                    // Batched output never shows control messages (`--show-control` disables it).
+                    last_seen_seq = Some(frame.seq);
+                    if let Some(outcome) = react_to_control(
+                        "follow",
+                        &pool_ref,
+                        kind,
+                        frame.seq,
+                        &mut rotation,
+                        cfg.color_mode,
+                    ) {
+                        batch.flush()?;
+                        return Ok(outcome);
+                    }
+                    continue;
+                }
+                // Chunked and attachment messages take the slow path, after flushing the batch.
+                let stored_apart = frame.flags & (FRAME_FLAG_CHUNK | FRAME_FLAG_ATTACHMENT) != 0;
+                if let Some(batch) = batch.as_mut().filter(|_| !stored_apart) {
+                    let required = cfg.required_tags.as_slice();
+                    let emitted = batch.encoder.encode_if(&frame, cfg.data_only, |tags| {
+                        required.iter().all(|tag| tags.contains(tag))
+                    })?;
+                    last_seen_seq = Some(frame.seq);
+                    maybe_emit_pending(&mut pending_drop, &mut last_notice_at);
+                    backoff = cfg.poll.initial();
+                    if let Some(stats) = stats.as_mut() {
+                        if emitted {
+                            stats.record_emitted(frame.payload.len());
+                        }
+                        stats.maybe_emit(pool, &pool_ref, last_seen_seq, cfg.color_mode)?;
+                    }
+                    if emitted {
+                        bump_timeout(&mut timeout_deadline);
+                        if cfg.one {
+                            batch.flush()?;
+                            return Ok(RunOutcome::ok());
+                        }
+                        if batch.encoder.buffered().len() >= FOLLOW_BATCH_BYTES {
+                            batch.flush()?;
+                            if let Some(outcome) =
+                                follow_downstream_closed(&cfg, &pool_ref, last_seen_seq, false)
+                            {
+                                return Ok(outcome);
+                            }
+                        }
+                    }
+                    continue;
+                }
+                if let Some(batch) = batch.as_mut() {
+                    batch.flush()?;
+                }
+                let Some(message) = follow_message(&cfg, pool, &frame)? else {
+                    last_seen_seq = Some(frame.seq);
+                    continue;
+                };
                 if !should_suppress_message(&cfg, &message)
                     && matches_required_tags(cfg.required_tags.as_slice(), &message)
-                    && matches_all(cfg.where_predicates.as_slice(), &message)?
+                    && follow_matches_where(&cfg, &message)?
                 {
                     if tail_wait {
-                        emit.push_back(message);
+                        emit.push_back((message, frame.payload.len()));
                         while emit.len() > cfg.tail as usize {
                             emit.pop_front();
                         }
                         if emit.len() == cfg.tail as usize {
-                            if let Some(value) = emit.back() {
-                                emit_message(
-                                    output_value(value.clone(), cfg.data_only),
-                                    cfg.pretty,
-                                    cfg.color_mode,
-                                );
+                            if let Some((value, _)) = emit.back() {
+                                follow_emit(&cfg, value.clone())?;
                             }
                             return Ok(RunOutcome::ok());
                         }
                     } else {
-                        emit_message(
-                            output_value(message, cfg.data_only),
-                            cfg.pretty,
-                            cfg.color_mode,
-                        );
+                        follow_emit(&cfg, message)?;
+                        if let Some(outcome) =
+                            follow_downstream_closed(&cfg, &pool_ref, Some(frame.seq), false)
+                        {
+                            return Ok(outcome);
+                        }
                         bump_timeout(&mut timeout_deadline);
+                        if let Some(stats) = stats.as_mut() {
+                            stats.record_emitted(frame.payload.len());
+                        }
                         if cfg.one {
                             return Ok(RunOutcome::ok());
                         }
                     }
                 }
                 last_seen_seq = Some(frame.seq);
+                if let Some(kind) = control.as_deref() {
+                    if let Some(outcome) = react_to_control(
+                        "follow",
+                        &pool_ref,
+                        kind,
+                        frame.seq,
+                        &mut rotation,
+                        cfg.color_mode,
+                    ) {
+                        return Ok(outcome);
+                    }
+                }
                 maybe_emit_pending(&mut pending_drop, &mut last_notice_at);
-                backoff = Duration::from_millis(1);
+                backoff = cfg.poll.initial();
+                if let Some(stats) = stats.as_mut() {
+                    stats.maybe_emit(pool, &pool_ref, last_seen_seq, cfg.color_mode)?;
+                }
             }
             CursorResult::WouldBlock => {
+                if let Some(batch) = batch.as_mut() {
+                    batch.flush()?;
+                }
+                if let Some(outcome) =
+                    follow_downstream_closed(&cfg, &pool_ref, last_seen_seq, true)
+                {
+                    return Ok(outcome);
+                }
+                if let Some(next) = rotation.poll(pool_path)? {
+                    // Seqs restart with the new file: read it from the oldest retained frame.
+                    header = next.header_from_mmap()?;
+                    cursor = Cursor::new();
+                    cursor.seek_to(header.tail_off as usize);
+                    last_seen_seq = None;
+                    pending_drop = None;
+                    // The batch was flushed above; the new file may use a different codec.
+                    batch = FollowBatch::for_config(&cfg, next.codec(), tail_wait);
+                    if notify_enabled {
+                        notify_handle = notify::open_for_path(pool_path);
+                        notify_enabled = notify_handle.is_some();
+                    }
+                    reopened = Some(next);
+                    emit_control_notice(
+                        "follow",
+                        &pool_ref,
+                        CONTROL_ROTATED,
+                        None,
+                        "reopened rotated pool",
+                        cfg.color_mode,
+                    );
+                    backoff = cfg.poll.initial();
+                    continue;
+                }
+                if let Some(stats) = stats.as_mut() {
+                    stats.maybe_emit(pool, &pool_ref, last_seen_seq, cfg.color_mode)?;
+                }
+                if let (Some(presence), Some(seq)) = (presence.as_mut(), last_seen_seq) {
+                    presence.update_cursor(seq);
+                }
                 if follow_should_stop(cfg.stop.as_ref()) {
                     return Ok(RunOutcome::ok());
                 }
                 maybe_emit_pending(&mut pending_drop, &mut last_notice_at);
-                if let Some(deadline) = timeout_deadline {
-                    let now = Instant::now();
-                    if now >= deadline {
-                        return Ok(RunOutcome::with_code(124));
-                    }
-                    let remaining = deadline.duration_since(now);
-                    let wait_for = std::cmp::min(backoff, remaining);
-                    if notify_enabled {
-                        match notify_handle
-                            .as_mut()
-                            .map(|handle| handle.wait(wait_for))
-                            .unwrap_or(NotifyWait::Unavailable)
-                        {
-                            NotifyWait::Signaled | NotifyWait::TimedOut => {}
-                            NotifyWait::Unavailable => {
-                                notify_enabled = false;
-                                notify_handle = None;
-                                std::thread::sleep(wait_for);
-                            }
+                let wait_for = match timeout_deadline {
+                    Some(deadline) => {
+                        let now = Instant::now();
+                        if now >= deadline {
+                            return Ok(RunOutcome::with_code(124));
                         }
-                    } else {
-                        std::thread::sleep(wait_for);
+                        std::cmp::min(backoff, deadline.duration_since(now))
                     }
-                } else if notify_enabled {
+                    None => backoff,
+                };
+                let mut notified = false;
+                if notify_enabled {
                     match notify_handle
                         .as_mut()
-                        .map(|handle| handle.wait(backoff))
+                        .map(|handle| handle.wait(wait_for))
                         .unwrap_or(NotifyWait::Unavailable)
                     {
-                        NotifyWait::Signaled | NotifyWait::TimedOut => {}
+                        NotifyWait::Signaled => notified = true,
+                        NotifyWait::TimedOut => {}
                         NotifyWait::Unavailable => {
                             notify_enabled = false;
                             notify_handle = None;
-                            std::thread::sleep(backoff);
+                            std::thread::sleep(wait_for);
                         }
                     }
                 } else {
-                    std::thread::sleep(backoff);
+                    std::thread::sleep(wait_for);
                 }
-                backoff = std::cmp::min(backoff * 2, max_backoff);
+                if let Some(stats) = stats.as_mut() {
+                    stats.record_wakeup(backoff, notified);
+                }
+                backoff = cfg.poll.next(backoff);
             }
             CursorResult::FellBehind => {
                 if follow_should_stop(cfg.stop.as_ref()) {
@@ -4145,7 +8117,80 @@ fn follow_pool(
     }
 }
 
-fn follow_replay(pool: &Pool, cfg: &FollowConfig) -> Result<RunOutcome, Error> {
+/// Print a `duplex --record` transcript, sleeping `offset_ms` deltas scaled by `speed`.
+fn replay_transcript(
+    path: &Path,
+    speed: f64,
+    jsonl: bool,
+    color_mode: ColorMode,
+) -> Result<RunOutcome, Error> {
+    let file = std::fs::File::open(path).map_err(|err| {
+        let kind = if err.kind() == io::ErrorKind::NotFound {
+            ErrorKind::NotFound
+        } else {
+            ErrorKind::Io
+        };
+        Error::new(kind)
+            .with_message("failed to open transcript")
+            .with_path(path)
+            .with_source(err)
+    })?;
+    let theme = active_theme(color_mode.use_color(stdout_is_terminal()));
+    let mut prev_offset_ms = None;
+    for (idx, line) in std::io::BufRead::lines(std::io::BufReader::new(file)).enumerate() {
+        let line = line.map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to read transcript")
+                .with_path(path)
+                .with_source(err)
+        })?;
+        let Some(entry) =
+            parse_transcript_line(&line, idx + 1).map_err(|err| err.with_path(path))?
+        else {
+            continue;
+        };
+        if let Some(prev) = prev_offset_ms {
+            let delta_ms = entry.offset_ms.saturating_sub(prev);
+            if speed > 0.0 && delta_ms > 0 {
+                std::thread::sleep(Duration::from_secs_f64(delta_ms as f64 / 1000.0 / speed));
+            }
+        }
+        prev_offset_ms = Some(entry.offset_ms);
+        let rendered = if jsonl {
+            entry.record.to_string()
+        } else {
+            transcript_line(&entry, theme)
+        };
+        follow_write_line(&rendered)?;
+        if downstream::stdout_closed() {
+            break;
+        }
+    }
+    Ok(RunOutcome::ok())
+}
+
+/// `+1.250s > {"from":"alice","msg":"hi"}`; received lines also show their seq.
+fn transcript_line(entry: &transcript::TranscriptEntry, theme: Option<Theme>) -> String {
+    let (marker, style) = match entry.direction {
+        Direction::Sent => (">", Style::Key),
+        Direction::Recv => ("<", Style::String),
+    };
+    let body = entry.message.get("data").unwrap_or(&entry.message);
+    let seq = match (
+        entry.direction,
+        entry.message.get("seq").and_then(Value::as_u64),
+    ) {
+        (Direction::Recv, Some(seq)) => format!("#{seq} "),
+        _ => String::new(),
+    };
+    format!(
+        "{:>9} {} {seq}{body}",
+        format!("+{:.3}s", entry.offset_ms as f64 / 1000.0),
+        paint(marker, style, theme),
+    )
+}
+
+fn follow_replay(pool: &Pool, pool_ref: &str, cfg: &FollowConfig) -> Result<RunOutcome, Error> {
     let speed = cfg.replay_speed.unwrap_or(0.0);
     let mut cursor = Cursor::new();
     let mut header = pool.header_from_mmap()?;
@@ -4156,10 +8201,16 @@ fn follow_replay(pool: &Pool, cfg: &FollowConfig) -> Result<RunOutcome, Error> {
         loop {
             match cursor.next(pool)? {
                 CursorResult::Message(frame) => {
-                    if frame.timestamp_ns >= since_ns {
-                        let message = message_from_frame(&frame)?;
-                        if matches_required_tags(cfg.required_tags.as_slice(), &message)
-                            && matches_all(cfg.where_predicates.as_slice(), &message)?
+                    if frame.timestamp_ns >= since_ns
+                        && !cfg.replay_control.past_until(frame.seq, frame.timestamp_ns)
+                        && !follow_bloom_excludes(cfg, &frame)
+                    {
+                        let Some(message) = follow_message(cfg, pool, &frame)? else {
+                            continue;
+                        };
+                        if !should_suppress_message(cfg, &message)
+                            && matches_required_tags(cfg.required_tags.as_slice(), &message)
+                            && follow_matches_where(cfg, &message)?
                         {
                             collected.push((frame.timestamp_ns, message));
                         }
@@ -4178,9 +8229,17 @@ fn follow_replay(pool: &Pool, cfg: &FollowConfig) -> Result<RunOutcome, Error> {
         loop {
             match cursor.next(pool)? {
                 CursorResult::Message(frame) => {
-                    let message = message_from_frame(&frame)?;
-                    if matches_required_tags(cfg.required_tags.as_slice(), &message)
-                        && matches_all(cfg.where_predicates.as_slice(), &message)?
+                    if cfg.replay_control.past_until(frame.seq, frame.timestamp_ns)
+                        || follow_bloom_excludes(cfg, &frame)
+                    {
+                        continue;
+                    }
+                    let Some(message) = follow_message(cfg, pool, &frame)? else {
+                        continue;
+                    };
+                    if !should_suppress_message(cfg, &message)
+                        && matches_required_tags(cfg.required_tags.as_slice(), &message)
+                        && follow_matches_where(cfg, &message)?
                     {
                         if cfg.tail > 0 {
                             buffer.push_back((frame.timestamp_ns, message));
@@ -4208,20 +8267,18 @@ fn follow_replay(pool: &Pool, cfg: &FollowConfig) -> Result<RunOutcome, Error> {
         return Ok(RunOutcome::ok());
     }
 
-    let mut prev_ts = collected[0].0;
-    for (i, (ts, message)) in collected.into_iter().enumerate() {
-        if i > 0 && speed > 0.0 {
-            let delta_ns = ts.saturating_sub(prev_ts);
-            let delay_ns = (delta_ns as f64 / speed) as u64;
-            if delay_ns > 0 {
-                std::thread::sleep(Duration::from_nanos(delay_ns));
-            }
+    let times = cfg.replay_control.pacing_times(&collected);
+    let pacer = ReplayPacer::new(speed, cfg.replay_control.ramp, &times);
+    let mut prev_ts = times[0];
+    for ((_, message), ts) in collected.into_iter().zip(times) {
+        let delay = pacer.delay(prev_ts, ts);
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+        follow_emit(cfg, message)?;
+        if let Some(outcome) = follow_downstream_closed(cfg, pool_ref, None, false) {
+            return Ok(outcome);
         }
-        emit_message(
-            output_value(message, cfg.data_only),
-            cfg.pretty,
-            cfg.color_mode,
-        );
         prev_ts = ts;
         if cfg.one {
             return Ok(RunOutcome::ok());
@@ -4234,11 +8291,11 @@ fn follow_replay(pool: &Pool, cfg: &FollowConfig) -> Result<RunOutcome, Error> {
 #[cfg(test)]
 mod tests {
     use super::{
-        Error, ErrorKind, PoolTarget, RetryConfig, build_serve_startup_lines,
-        duplex_requires_me_when_tty, error_text, format_bytes, format_relative_time,
-        format_seq_range, format_timestamp_human, matches_required_tags, parse_duplex_tty_line,
-        parse_duration, parse_size, read_token_file, render_table, resolve_pool_target,
-        retry_with_config, short_display_path,
+        Cli, Command, Error, ErrorKind, FollowPoll, PollStrategy, PoolTarget, RetryConfig,
+        annotation_line, apply_serve_env, build_serve_startup_lines, duplex_requires_me_when_tty,
+        error_text, format_bytes, format_relative_time, format_seq_range, format_timestamp_human,
+        matches_required_tags, parse_duplex_tty_line, parse_duration, parse_size, read_token_file,
+        render_table, resolve_pool_target, retry_with_config, short_display_path,
     };
     use serde_json::json;
     use std::io::Cursor;
@@ -4285,14 +8342,24 @@ mod tests {
             allow_non_loopback: false,
             insecure_no_tls: false,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Default::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         }
     }
 
@@ -4311,6 +8378,41 @@ mod tests {
         assert!(text.contains("Fingerprint: SHA256:AA:BB"));
     }
 
+    #[test]
+    fn apply_serve_env_overrides_flags_and_rejects_bad_values() {
+        use clap::Parser;
+        let cli = Cli::try_parse_from(["plasmite", "serve", "--bind", "127.0.0.1:1", "--from-env"])
+            .expect("parse");
+        let Command::Serve { mut run, .. } = cli.command else {
+            panic!("expected serve");
+        };
+        assert!(run.from_env);
+        let env = |name: &str| match name {
+            "PLASMITE_SERVE_BIND" => Some("0.0.0.0:9700".to_string()),
+            "PLASMITE_SERVE_ACCESS" => Some("read-only".to_string()),
+            "PLASMITE_SERVE_CORS_ORIGINS" => {
+                Some("https://a.example, https://b.example".to_string())
+            }
+            "PLASMITE_SERVE_ALLOW_NON_LOOPBACK" => Some("true".to_string()),
+            "PLASMITE_SERVE_MAX_TAIL_CONCURRENCY" => Some("8".to_string()),
+            "PLASMITE_SERVE_TOKEN" => Some(String::new()),
+            _ => None,
+        };
+        apply_serve_env(&mut run, env).expect("apply");
+        assert_eq!(run.bind, "0.0.0.0:9700");
+        assert!(matches!(run.access, super::AccessModeCli::ReadOnly));
+        assert_eq!(run.cors_origin, ["https://a.example", "https://b.example"]);
+        assert!(run.allow_non_loopback);
+        assert_eq!(run.max_tail_concurrency, 8);
+        assert!(run.token.is_none());
+
+        let err = apply_serve_env(&mut run, |name| {
+            (name == "PLASMITE_SERVE_INSECURE_NO_TLS").then(|| "maybe".to_string())
+        })
+        .expect_err("bad bool");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+
     #[test]
     fn serve_startup_banner_local_mode_stays_compact() {
         let config = test_serve_config();
@@ -4364,6 +8466,26 @@ mod tests {
         assert!(parse_size("3KiB").is_err());
     }
 
+    #[test]
+    fn follow_poll_doubles_within_bounds_or_stays_fixed() {
+        let ms = Duration::from_millis;
+        let exponential = FollowPoll {
+            min: ms(2),
+            max: ms(10),
+            strategy: PollStrategy::Exponential,
+        };
+        assert_eq!(exponential.initial(), ms(2));
+        assert_eq!(exponential.next(ms(2)), ms(4));
+        assert_eq!(exponential.next(ms(8)), ms(10));
+        assert_eq!(exponential.next(ms(10)), ms(10));
+        let fixed = FollowPoll {
+            strategy: PollStrategy::Fixed,
+            ..exponential
+        };
+        assert_eq!(fixed.initial(), ms(10));
+        assert_eq!(fixed.next(ms(10)), ms(10));
+    }
+
     #[test]
     fn parse_duration_accepts_ms_s_m() {
         assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
@@ -4422,6 +8544,21 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn annotation_line_renders_only_messages_with_refs() {
+        let annotation = json!({
+            "seq": 5,
+            "meta": {"tags": [], "refs": [2]},
+            "data": {"emoji": "+1"}
+        });
+        assert_eq!(
+            annotation_line(&annotation).as_deref(),
+            Some("  \u{21b3} #5 on #2: {\"emoji\":\"+1\"}")
+        );
+        let plain = json!({"seq": 2, "meta": {"tags": []}, "data": {"x": 1}});
+        assert!(annotation_line(&plain).is_none());
+    }
+
     #[test]
     fn required_tags_returns_false_on_missing_meta_tags() {
         let message = json!({"data": {"x": 1}});
@@ -4436,6 +8573,16 @@ mod tests {
         assert_eq!(token, "secret-token");
     }
 
+    #[test]
+    fn token_file_sends_first_of_multiple_tokens() {
+        let mut file = NamedTempFile::new().expect("tempfile");
+        std::io::Write::write_all(&mut file, b"# rotation\nnew-token\n\nold-token\n")
+            .expect("write");
+        assert_eq!(read_token_file(file.path()).expect("token"), "new-token");
+        let tokens = super::serve::read_token_list(file.path()).expect("tokens");
+        assert_eq!(tokens, ["new-token", "old-token"]);
+    }
+
     #[test]
     fn token_file_rejects_empty() {
         let mut file = NamedTempFile::new().expect("tempfile");
@@ -11,8 +11,9 @@ use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::api::{
-    Durability, Error, ErrorKind, LocalClient, PoolApiExt, PoolInfo, PoolOptions, PoolRef,
+    Durability, Error, ErrorKind, LocalClient, Pool, PoolApiExt, PoolInfo, PoolOptions, PoolRef,
 };
+use crate::pool_paths::pool_name_for_path;
 
 const JSON_RPC_VERSION: &str = "2.0";
 const MCP_PROTOCOL_VERSION: &str = "2025-11-25";
@@ -24,6 +25,8 @@ const INTERNAL_ERROR_CODE: i32 = -32603;
 const DEFAULT_POOL_SIZE_BYTES: u64 = 1024 * 1024;
 const DEFAULT_READ_COUNT: usize = 20;
 const MAX_READ_COUNT: usize = 200;
+/// Open pools a stdio MCP session keeps between tool calls.
+const POOL_CACHE_CAPACITY: usize = 16;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -393,7 +396,11 @@ pub struct PlasmiteMcpHandler {
 
 impl PlasmiteMcpHandler {
     pub fn new(pool_dir: impl Into<PathBuf>) -> Self {
-        Self::with_client(LocalClient::new().with_pool_dir(pool_dir))
+        Self::with_client(
+            LocalClient::new()
+                .with_pool_dir(pool_dir)
+                .with_pool_cache(POOL_CACHE_CAPACITY),
+        )
     }
 
     pub fn with_client(client: LocalClient) -> Self {
@@ -408,7 +415,7 @@ impl PlasmiteMcpHandler {
         let mut entries = pools
             .into_iter()
             .map(|info| {
-                let name = pool_name_from_path(&info.path);
+                let name = pool_name_from_path(self.client.pool_dir(), &info.path);
                 (name.clone(), pool_info_json_value(&name, &info))
             })
             .collect::<Vec<_>>();
@@ -502,8 +509,9 @@ impl PlasmiteMcpHandler {
         };
 
         let pool_ref = PoolRef::name(pool.clone());
-        let mut opened = match self.client.open_pool(&pool_ref) {
-            Ok(pool) => pool,
+        let append = |opened: &mut Pool| opened.append_json_now(&data, &tags, Durability::Fast);
+        let message = match self.client.with_pool(&pool_ref, append) {
+            Ok(message) => message,
             Err(err) if create && err.kind() == ErrorKind::NotFound => {
                 if let Err(create_err) = self
                     .client
@@ -511,19 +519,14 @@ impl PlasmiteMcpHandler {
                 {
                     return api_error_tool_result("plasmite_feed", create_err);
                 }
-                match self.client.open_pool(&pool_ref) {
-                    Ok(pool) => pool,
-                    Err(open_err) => return api_error_tool_result("plasmite_feed", open_err),
+                match self.client.with_pool(&pool_ref, append) {
+                    Ok(message) => message,
+                    Err(err) => return api_error_tool_result("plasmite_feed", err),
                 }
             }
             Err(err) => return api_error_tool_result("plasmite_feed", err),
         };
 
-        let message = match opened.append_json_now(&data, &tags, Durability::Fast) {
-            Ok(message) => message,
-            Err(err) => return api_error_tool_result("plasmite_feed", err),
-        };
-
         ToolCallResult::success_with_structured(
             format!("Appended message {} to `{pool}`.", message.seq),
             json!({ "message": message_json_value(&message) }),
@@ -540,11 +543,10 @@ impl PlasmiteMcpHandler {
             Err(result) => return invalid_argument_result("plasmite_fetch", "seq", result),
         };
         let pool_ref = PoolRef::name(pool.clone());
-        let opened = match self.client.open_pool(&pool_ref) {
-            Ok(pool) => pool,
-            Err(err) => return api_error_tool_result("plasmite_fetch", err),
-        };
-        let message = match opened.get_message(seq) {
+        let message = match self
+            .client
+            .with_pool(&pool_ref, |opened| opened.get_message(seq))
+        {
             Ok(message) => message,
             Err(err) => return api_error_tool_result("plasmite_fetch", err),
         };
@@ -600,19 +602,15 @@ impl PlasmiteMcpHandler {
         }
 
         let pool_ref = PoolRef::name(pool.clone());
-        let opened = match self.client.open_pool(&pool_ref) {
-            Ok(pool) => pool,
-            Err(err) => return api_error_tool_result("plasmite_read", err),
-        };
-        let info = match opened.info() {
-            Ok(info) => info,
+        let read = self.client.with_pool(&pool_ref, |opened| {
+            let info = opened.info()?;
+            read_messages_for_tool(opened, &info, count, after_seq, since_ns, &tags)
+                .map_err(|err| *err)
+        });
+        let messages = match read {
+            Ok(messages) => messages,
             Err(err) => return api_error_tool_result("plasmite_read", err),
         };
-        let messages =
-            match read_messages_for_tool(&opened, &info, count, after_seq, since_ns, &tags) {
-                Ok(messages) => messages,
-                Err(err) => return api_error_tool_result("plasmite_read", *err),
-            };
         let next_after_seq = messages
             .last()
             .and_then(|message| message.get("seq"))
@@ -750,7 +748,7 @@ impl McpHandler for PlasmiteMcpHandler {
         let mut resources = pools
             .into_iter()
             .map(|info| {
-                let name = pool_name_from_path(&info.path);
+                let name = pool_name_from_path(self.client.pool_dir(), &info.path);
                 McpResource {
                     uri: format!("plasmite:///pools/{name}"),
                     name: name.clone(),
@@ -774,13 +772,14 @@ impl McpHandler for PlasmiteMcpHandler {
         let pool =
             pool_name_from_resource_uri(&request.uri).map_err(JsonRpcError::invalid_params)?;
         let pool_ref = PoolRef::name(pool);
-        let opened = self
+        let messages = self
             .client
-            .open_pool(&pool_ref)
+            .with_pool(&pool_ref, |opened| {
+                let info = opened.info()?;
+                read_messages_for_tool(opened, &info, DEFAULT_READ_COUNT, None, None, &[])
+                    .map_err(|err| *err)
+            })
             .map_err(api_error_jsonrpc)?;
-        let info = opened.info().map_err(api_error_jsonrpc)?;
-        let messages = read_messages_for_tool(&opened, &info, DEFAULT_READ_COUNT, None, None, &[])
-            .map_err(|err| api_error_jsonrpc(*err))?;
         let next_after_seq = messages
             .last()
             .and_then(|message| message.get("seq"))
@@ -1008,7 +1007,10 @@ fn optional_string_array_arg(
     Ok(Some(out))
 }
 
-fn pool_name_from_path(path: &Path) -> String {
+fn pool_name_from_path(pool_dir: &Path, path: &Path) -> String {
+    if let Some(name) = pool_name_for_path(pool_dir, path) {
+        return name;
+    }
     path.file_stem()
         .and_then(|stem| stem.to_str())
         .map(ToString::to_string)
@@ -1061,14 +1063,7 @@ fn pool_bounds_json_value(info: &PoolInfo) -> Value {
 }
 
 fn message_json_value(message: &crate::api::Message) -> Value {
-    json!({
-        "seq": message.seq,
-        "time": message.time.clone(),
-        "meta": {
-            "tags": message.meta.tags.clone(),
-        },
-        "data": message.data.clone(),
-    })
+    message.to_json()
 }
 
 fn read_messages_for_tool(
@@ -1278,10 +1273,11 @@ fn pool_name_from_resource_uri(uri: &str) -> Result<String, String> {
         .path_segments()
         .ok_or_else(|| "resource uri must be in plasmite:///pools/{name} format".to_string())?
         .collect::<Vec<_>>();
-    if segments.len() != 2 || segments[0] != "pools" || segments[1].is_empty() {
+    // Hierarchical pool names keep their slashes: plasmite:///pools/team/events.
+    if segments.len() < 2 || segments[0] != "pools" || segments[1..].contains(&"") {
         return Err("resource uri must be in plasmite:///pools/{name} format".to_string());
     }
-    Ok(segments[1].to_string())
+    Ok(segments[1..].join("/"))
 }
 
 #[cfg(test)]
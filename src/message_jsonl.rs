@@ -0,0 +1,285 @@
+//! Purpose: Allocation-light JSONL encoding of committed frames for high-rate reads.
+//! Exports: `FrameJsonlEncoder`.
+//! Role: Hot-path emitter shared by `plasmite follow` and the bench harness.
+//! Invariants: Lines are byte-identical to `serde_json::to_string` of the CLI message
+//! envelope (sorted keys: `data`, `meta`, `seq`, `time`), or of `data` alone.
+//! Invariants: Buffers are reused across frames; nothing reaches the writer until `write_to`.
+//! Invariants: A frame that fails to decode or is filtered out leaves the buffer unchanged.
+//! Invariants: Payloads decode with the pool's recorded codec, never by sniffing bytes.
+#![allow(clippy::result_large_err)]
+
+use std::io::{self, Write};
+
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::api::control::control_kind;
+use crate::api::{
+    Codec, Error, ErrorKind, FrameRef, JsonCodec, Lite3DocRef, PoolCodec, frame_flag_names, lite3,
+};
+
+/// Reusable encoder that batches frames as JSON Lines in one growable buffer.
+#[derive(Debug)]
+pub struct FrameJsonlEncoder {
+    codec: PoolCodec,
+    buf: Vec<u8>,
+    tags: Vec<String>,
+}
+
+impl FrameJsonlEncoder {
+    /// Encoder for frames of a pool created with `codec` (see `Pool::codec`).
+    pub fn new(codec: PoolCodec) -> Self {
+        Self {
+            codec,
+            buf: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Append one line for `frame`.
+    pub fn encode(&mut self, frame: &FrameRef<'_>, data_only: bool) -> Result<(), Error> {
+        self.encode_if(frame, data_only, |_| true).map(|_| ())
+    }
+
+    /// Append one line for `frame` when `keep(tags)` holds; returns whether a line was added.
+    pub fn encode_if(
+        &mut self,
+        frame: &FrameRef<'_>,
+        data_only: bool,
+        keep: impl FnOnce(&[String]) -> bool,
+    ) -> Result<bool, Error> {
+        let start = self.buf.len();
+        let result = self.encode_line(frame, data_only, keep);
+        if !matches!(result, Ok(true)) {
+            self.buf.truncate(start);
+        }
+        result
+    }
+
+    /// Bytes buffered since the last `write_to`.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Write all buffered lines with a single `write_all` and reset the buffer.
+    pub fn write_to(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let result = out.write_all(&self.buf);
+        self.buf.clear();
+        result
+    }
+
+    fn encode_line(
+        &mut self,
+        frame: &FrameRef<'_>,
+        data_only: bool,
+        keep: impl FnOnce(&[String]) -> bool,
+    ) -> Result<bool, Error> {
+        self.tags.clear();
+        let data = match self.codec {
+            PoolCodec::Json => {
+                let (meta, data) = JsonCodec.decode(frame.payload)?;
+                self.tags.extend(meta.tags);
+                if !keep(&self.tags) {
+                    return Ok(false);
+                }
+                data
+            }
+            PoolCodec::Lite3 => {
+                let doc = Lite3DocRef::new(frame.payload);
+                read_lite3_tags(&doc, &mut self.tags)?;
+                if !keep(&self.tags) {
+                    return Ok(false);
+                }
+                let data_ofs = doc
+                    .key_offset("data")
+                    .map_err(|err| err.with_message("missing data"))?;
+                let data_json = doc.to_json_at(data_ofs, false)?;
+                serde_json::from_str(&data_json).map_err(|err| {
+                    Error::new(ErrorKind::Corrupt)
+                        .with_message("invalid payload json")
+                        .with_source(err)
+                })?
+            }
+        };
+
+        if data_only {
+            serde_json::to_writer(&mut self.buf, &data).map_err(encode_error)?;
+        } else {
+            self.buf.extend_from_slice(b"{\"data\":");
+            serde_json::to_writer(&mut self.buf, &data).map_err(encode_error)?;
+            self.buf.extend_from_slice(b",\"meta\":{");
+            if let Some(kind) = control_kind(frame.flags, &data) {
+                self.buf.extend_from_slice(b"\"control\":");
+                serde_json::to_writer(&mut self.buf, kind).map_err(encode_error)?;
+                self.buf.push(b',');
+            }
+            if frame.flags != 0 {
+                self.buf.extend_from_slice(b"\"flags\":");
+                serde_json::to_writer(&mut self.buf, &frame_flag_names(frame.flags))
+                    .map_err(encode_error)?;
+                self.buf.push(b',');
+            }
+            self.buf.extend_from_slice(b"\"tags\":");
+            serde_json::to_writer(&mut self.buf, &self.tags).map_err(encode_error)?;
+            self.buf.extend_from_slice(b"},\"seq\":");
+            self.buf
+                .extend_from_slice(itoa_u64(frame.seq, &mut [0u8; 20]).as_bytes());
+            self.buf.extend_from_slice(b",\"time\":\"");
+            write_rfc3339(&mut self.buf, frame.timestamp_ns)?;
+            self.buf.extend_from_slice(b"\"}");
+        }
+        self.buf.push(b'\n');
+        Ok(true)
+    }
+}
+
+fn read_lite3_tags(doc: &Lite3DocRef<'_>, tags: &mut Vec<String>) -> Result<(), Error> {
+    let meta_ofs = doc
+        .key_offset("meta")
+        .map_err(|err| err.with_message("missing meta"))?;
+    let tags_ofs = doc
+        .key_offset_at(meta_ofs, "tags")
+        .map_err(|err| err.with_message("missing meta.tags"))?;
+    let count = doc
+        .count_at(tags_ofs)
+        .map_err(|_| Error::new(ErrorKind::Corrupt).with_message("meta.tags must be array"))?;
+    for index in 0..count {
+        let not_strings =
+            || Error::new(ErrorKind::Corrupt).with_message("meta.tags must be string array");
+        if doc
+            .array_item_type(tags_ofs, index)
+            .map_err(|_| not_strings())?
+            != lite3::sys::LITE3_TYPE_STRING
+        {
+            return Err(not_strings());
+        }
+        tags.push(
+            doc.array_string_at(tags_ofs, index)
+                .map_err(|_| not_strings())?,
+        );
+    }
+    Ok(())
+}
+
+fn write_rfc3339(buf: &mut Vec<u8>, timestamp_ns: u64) -> Result<(), Error> {
+    let ts = OffsetDateTime::from_unix_timestamp_nanos(timestamp_ns as i128).map_err(|err| {
+        Error::new(ErrorKind::Internal)
+            .with_message("invalid timestamp")
+            .with_source(err)
+    })?;
+    ts.format_into(buf, &Rfc3339).map_err(|err| {
+        Error::new(ErrorKind::Internal)
+            .with_message("timestamp format failed")
+            .with_source(err)
+    })?;
+    Ok(())
+}
+
+/// Format `value` in decimal into `scratch` without allocating.
+fn itoa_u64(mut value: u64, scratch: &mut [u8; 20]) -> &str {
+    let mut pos = scratch.len();
+    loop {
+        pos -= 1;
+        scratch[pos] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    std::str::from_utf8(&scratch[pos..]).unwrap_or("0")
+}
+
+fn encode_error(err: serde_json::Error) -> Error {
+    Error::new(ErrorKind::Internal)
+        .with_message("json encode failed")
+        .with_source(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameJsonlEncoder, itoa_u64};
+    use crate::api::{Codec, FRAME_FLAG_CONTROL, FrameRef, JsonCodec, Lite3Codec, PoolCodec};
+    use serde_json::json;
+
+    fn frame<'a>(seq: u64, payload: &'a [u8]) -> FrameRef<'a> {
+        FrameRef {
+            seq,
+            timestamp_ns: 1_700_000_000_123_456_789,
+            flags: 0,
+            bloom: 0,
+            payload,
+        }
+    }
+
+    #[test]
+    fn lines_match_value_serialization_for_both_codecs() {
+        let tags = vec!["alpha".to_string(), "b\"q".to_string()];
+        let data = json!({"z": 1, "a": {"nested": [true, null, "x"]}});
+        for codec in [&Lite3Codec as &dyn Codec, &JsonCodec] {
+            let payload = codec.encode(&tags, &data).expect("encode");
+            let mut encoder = FrameJsonlEncoder::new(codec.id());
+            encoder.encode(&frame(42, &payload), false).expect("line");
+            encoder.encode(&frame(43, &payload), true).expect("data");
+
+            let expected = json!({
+                "seq": 42,
+                "time": "2023-11-14T22:13:20.123456789Z",
+                "meta": {"tags": tags},
+                "data": data,
+            });
+            let text = std::str::from_utf8(encoder.buffered()).expect("utf8");
+            let lines = text.lines().collect::<Vec<_>>();
+            assert_eq!(lines[0], serde_json::to_string(&expected).expect("json"));
+            assert_eq!(lines[1], serde_json::to_string(&data).expect("json"));
+
+            let mut flagged = frame(44, &payload);
+            flagged.flags = FRAME_FLAG_CONTROL | 1 << 16;
+            let mut encoder = FrameJsonlEncoder::new(codec.id());
+            encoder.encode(&flagged, false).expect("flagged");
+            let value: serde_json::Value =
+                serde_json::from_slice(encoder.buffered()).expect("json");
+            assert_eq!(value["meta"]["flags"], json!(["control", "user0"]));
+            assert_eq!(
+                std::str::from_utf8(encoder.buffered())
+                    .expect("utf8")
+                    .trim_end(),
+                serde_json::to_string(&value).expect("json")
+            );
+        }
+    }
+
+    #[test]
+    fn filtered_and_failed_frames_leave_buffer_untouched() {
+        let payload = Lite3Codec
+            .encode(&["keep".to_string()], &json!({"x": 1}))
+            .expect("encode");
+        let mut encoder = FrameJsonlEncoder::new(PoolCodec::Lite3);
+        assert!(
+            !encoder
+                .encode_if(&frame(1, &payload), false, |tags| tags.is_empty())
+                .expect("filter")
+        );
+        assert!(encoder.encode(&frame(2, b"{not json"), false).is_err());
+        assert!(encoder.buffered().is_empty());
+        assert!(
+            FrameJsonlEncoder::new(PoolCodec::Json)
+                .encode(&frame(2, &payload), false)
+                .is_err()
+        );
+
+        let mut out = Vec::new();
+        encoder.encode(&frame(3, &payload), true).expect("line");
+        encoder.write_to(&mut out).expect("write");
+        assert_eq!(out, b"{\"x\":1}\n");
+        assert!(encoder.buffered().is_empty());
+    }
+
+    #[test]
+    fn itoa_formats_edges() {
+        assert_eq!(itoa_u64(0, &mut [0; 20]), "0");
+        assert_eq!(itoa_u64(u64::MAX, &mut [0; 20]), u64::MAX.to_string());
+    }
+}
@@ -0,0 +1,317 @@
+//! Purpose: Flatten OTLP/HTTP JSON log and trace exports into plasmite message bodies.
+//! Exports: `OtlpSignal`, `OtlpRecord`, `records_from_export`.
+//! Role: Pure conversion helpers for the `serve --otlp-bind` receiver.
+//! Invariants: One exported log record or span becomes exactly one message.
+//! Invariants: OTLP `AnyValue`/`KeyValue` lists decode to plain JSON values/objects.
+//! Invariants: Unknown fields are ignored; missing fields decode to null.
+use plasmite::api::{Error, ErrorKind};
+use serde_json::{Map, Value, json};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OtlpSignal {
+    Logs,
+    Traces,
+}
+
+impl OtlpSignal {
+    /// Destination pool name for this signal.
+    pub fn pool_name(self) -> &'static str {
+        match self {
+            OtlpSignal::Logs => "otlp-logs",
+            OtlpSignal::Traces => "otlp-traces",
+        }
+    }
+
+    fn resource_key(self) -> &'static str {
+        match self {
+            OtlpSignal::Logs => "resourceLogs",
+            OtlpSignal::Traces => "resourceSpans",
+        }
+    }
+
+    fn scope_key(self) -> &'static str {
+        match self {
+            OtlpSignal::Logs => "scopeLogs",
+            OtlpSignal::Traces => "scopeSpans",
+        }
+    }
+
+    fn item_key(self) -> &'static str {
+        match self {
+            OtlpSignal::Logs => "logRecords",
+            OtlpSignal::Traces => "spans",
+        }
+    }
+
+    fn tag(self) -> &'static str {
+        match self {
+            OtlpSignal::Logs => "log",
+            OtlpSignal::Traces => "span",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtlpRecord {
+    pub data: Value,
+    pub tags: Vec<String>,
+}
+
+/// Convert an `ExportLogsServiceRequest` / `ExportTraceServiceRequest` JSON body.
+pub fn records_from_export(signal: OtlpSignal, body: &Value) -> Result<Vec<OtlpRecord>, Error> {
+    let Some(resources) = body.as_object() else {
+        return Err(Error::new(ErrorKind::Usage).with_message("OTLP export body must be an object"));
+    };
+    let resources = match resources.get(signal.resource_key()) {
+        Some(Value::Array(resources)) => resources.as_slice(),
+        None | Some(Value::Null) => &[],
+        Some(_) => {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("OTLP {} must be an array", signal.resource_key())));
+        }
+    };
+
+    let mut records = Vec::new();
+    for resource in resources {
+        let resource_attrs = attributes(resource.pointer("/resource/attributes"));
+        let service = resource_attrs
+            .get("service.name")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        for scope in array(resource.get(signal.scope_key())) {
+            let scope_json = json!({
+                "name": scope.pointer("/scope/name").cloned().unwrap_or(Value::Null),
+                "version": scope.pointer("/scope/version").cloned().unwrap_or(Value::Null),
+            });
+            for item in array(scope.get(signal.item_key())) {
+                let mut data = match signal {
+                    OtlpSignal::Logs => log_record_json(item),
+                    OtlpSignal::Traces => span_json(item),
+                };
+                data.insert(
+                    "resource".to_string(),
+                    Value::Object(resource_attrs.clone()),
+                );
+                data.insert("scope".to_string(), scope_json.clone());
+
+                let mut tags = vec!["otlp".to_string(), signal.tag().to_string()];
+                if let Some(service) = &service {
+                    tags.push(format!("service:{service}"));
+                }
+                records.push(OtlpRecord {
+                    data: Value::Object(data),
+                    tags,
+                });
+            }
+        }
+    }
+    Ok(records)
+}
+
+fn log_record_json(record: &Value) -> Map<String, Value> {
+    let mut data = Map::new();
+    data.insert("time_unix_nano".to_string(), nanos(record, "timeUnixNano"));
+    data.insert(
+        "observed_time_unix_nano".to_string(),
+        nanos(record, "observedTimeUnixNano"),
+    );
+    data.insert(
+        "severity".to_string(),
+        record.get("severityText").cloned().unwrap_or(Value::Null),
+    );
+    data.insert(
+        "severity_number".to_string(),
+        record.get("severityNumber").cloned().unwrap_or(Value::Null),
+    );
+    data.insert("body".to_string(), any_value(record.get("body")));
+    data.insert(
+        "attributes".to_string(),
+        Value::Object(attributes(record.get("attributes"))),
+    );
+    data.insert("trace_id".to_string(), id(record, "traceId"));
+    data.insert("span_id".to_string(), id(record, "spanId"));
+    data
+}
+
+fn span_json(span: &Value) -> Map<String, Value> {
+    let mut data = Map::new();
+    data.insert(
+        "name".to_string(),
+        span.get("name").cloned().unwrap_or(Value::Null),
+    );
+    data.insert("trace_id".to_string(), id(span, "traceId"));
+    data.insert("span_id".to_string(), id(span, "spanId"));
+    data.insert("parent_span_id".to_string(), id(span, "parentSpanId"));
+    data.insert(
+        "kind".to_string(),
+        span.get("kind").cloned().unwrap_or(Value::Null),
+    );
+    let start = nanos(span, "startTimeUnixNano");
+    let end = nanos(span, "endTimeUnixNano");
+    let duration = match (start.as_u64(), end.as_u64()) {
+        (Some(start), Some(end)) => json!(end.saturating_sub(start)),
+        _ => Value::Null,
+    };
+    data.insert("start_time_unix_nano".to_string(), start);
+    data.insert("end_time_unix_nano".to_string(), end);
+    data.insert("duration_ns".to_string(), duration);
+    data.insert(
+        "attributes".to_string(),
+        Value::Object(attributes(span.get("attributes"))),
+    );
+    data.insert(
+        "status".to_string(),
+        span.get("status").cloned().unwrap_or(Value::Null),
+    );
+    data
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    match value {
+        Some(Value::Array(items)) => items.as_slice(),
+        _ => &[],
+    }
+}
+
+/// OTLP/JSON encodes 64-bit integers as strings; accept both forms.
+fn nanos(value: &Value, key: &str) -> Value {
+    match value.get(key) {
+        Some(Value::String(raw)) => raw.parse::<u64>().map(Value::from).unwrap_or(Value::Null),
+        Some(Value::Number(number)) => number.as_u64().map(Value::from).unwrap_or(Value::Null),
+        _ => Value::Null,
+    }
+}
+
+fn id(value: &Value, key: &str) -> Value {
+    match value.get(key).and_then(Value::as_str) {
+        Some(raw) if !raw.is_empty() => json!(raw),
+        _ => Value::Null,
+    }
+}
+
+fn attributes(value: Option<&Value>) -> Map<String, Value> {
+    let mut out = Map::new();
+    for pair in array(value) {
+        if let Some(key) = pair.get("key").and_then(Value::as_str) {
+            out.insert(key.to_string(), any_value(pair.get("value")));
+        }
+    }
+    out
+}
+
+fn any_value(value: Option<&Value>) -> Value {
+    let Some(Value::Object(value)) = value else {
+        return Value::Null;
+    };
+    if let Some(text) = value.get("stringValue") {
+        return text.clone();
+    }
+    if let Some(flag) = value.get("boolValue") {
+        return flag.clone();
+    }
+    if let Some(int) = value.get("intValue") {
+        return match int {
+            Value::String(raw) => raw
+                .parse::<i64>()
+                .map(Value::from)
+                .unwrap_or_else(|_| int.clone()),
+            other => other.clone(),
+        };
+    }
+    if let Some(double) = value.get("doubleValue") {
+        return double.clone();
+    }
+    if let Some(bytes) = value.get("bytesValue") {
+        return bytes.clone();
+    }
+    if let Some(list) = value.get("arrayValue") {
+        return Value::Array(
+            array(list.get("values"))
+                .iter()
+                .map(|item| any_value(Some(item)))
+                .collect(),
+        );
+    }
+    if let Some(kvlist) = value.get("kvlistValue") {
+        return Value::Object(attributes(kvlist.get("values")));
+    }
+    Value::Null
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OtlpSignal, records_from_export};
+    use serde_json::{Value, json};
+
+    #[test]
+    fn log_export_flattens_resource_scope_and_attributes() {
+        let body = json!({
+            "resourceLogs": [{
+                "resource": {"attributes": [
+                    {"key": "service.name", "value": {"stringValue": "checkout"}}
+                ]},
+                "scopeLogs": [{
+                    "scope": {"name": "app.logger", "version": "1.0"},
+                    "logRecords": [{
+                        "timeUnixNano": "1700000000000000000",
+                        "severityNumber": 17,
+                        "severityText": "ERROR",
+                        "body": {"stringValue": "payment failed"},
+                        "attributes": [
+                            {"key": "retry", "value": {"intValue": "3"}},
+                            {"key": "ids", "value": {"arrayValue": {"values": [
+                                {"stringValue": "a"}, {"boolValue": true}
+                            ]}}}
+                        ],
+                        "traceId": "5b8efff798038103d269b633813fc60c",
+                        "spanId": ""
+                    }]
+                }]
+            }]
+        });
+        let records = records_from_export(OtlpSignal::Logs, &body).expect("records");
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.tags, vec!["otlp", "log", "service:checkout"]);
+        assert_eq!(record.data["body"], "payment failed");
+        assert_eq!(record.data["severity"], "ERROR");
+        assert_eq!(record.data["time_unix_nano"], 1_700_000_000_000_000_000u64);
+        assert_eq!(record.data["attributes"]["retry"], 3);
+        assert_eq!(record.data["attributes"]["ids"], json!(["a", true]));
+        assert_eq!(record.data["resource"]["service.name"], "checkout");
+        assert_eq!(record.data["scope"]["name"], "app.logger");
+        assert_eq!(record.data["span_id"], Value::Null);
+    }
+
+    #[test]
+    fn trace_export_computes_span_duration() {
+        let body = json!({
+            "resourceSpans": [{
+                "scopeSpans": [{
+                    "spans": [{
+                        "traceId": "t1",
+                        "spanId": "s1",
+                        "name": "GET /cart",
+                        "kind": 2,
+                        "startTimeUnixNano": "1000",
+                        "endTimeUnixNano": "4500",
+                        "status": {"code": 1}
+                    }]
+                }]
+            }]
+        });
+        let records = records_from_export(OtlpSignal::Traces, &body).expect("records");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].tags, vec!["otlp", "span"]);
+        assert_eq!(records[0].data["name"], "GET /cart");
+        assert_eq!(records[0].data["duration_ns"], 3500);
+        assert_eq!(records[0].data["parent_span_id"], Value::Null);
+    }
+
+    #[test]
+    fn empty_export_yields_no_records() {
+        let records = records_from_export(OtlpSignal::Logs, &json!({})).expect("records");
+        assert!(records.is_empty());
+        assert!(records_from_export(OtlpSignal::Logs, &json!([])).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! Purpose: Full and incremental pool backups for `plasmite backup` and `plasmite restore`.
+//! Exports: `BackupOutcome`, `RestoreOutcome`, `backup`, `restore`, `plan_restore`.
+//! Role: Owns the backup directory layout and delta format; pool resolution and output stay
+//! in dispatch.
+//! Invariants: A backup directory holds `base.plasmite` (a validated `Pool::snapshot_to`
+//! copy), `delta-<first>-<last>.plsd` files, and `cursor.json` (last backed-up seq).
+//! Invariants: Backup files are written under a temp name and renamed, and the directory
+//! cursor advances only after its delta is in place, so an interrupted backup is redone.
+//! Invariants: Deltas carry raw frame payloads and timestamps, so restores are codec-exact.
+//! Invariants: A restored pool records how far it has applied in a `<pool>.restore.json`
+//! cursor sidecar; re-running restore applies only newer deltas.
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::forward::ForwardCursor;
+use plasmite::api::{Error, ErrorKind, LocalClient, Pool, PoolRef};
+
+const BASE_FILE: &str = "base.plasmite";
+const CURSOR_FILE: &str = "cursor.json";
+const DELTA_MAGIC: &[u8; 8] = b"PLSMDLT1";
+const DELTA_EXTENSION: &str = "plsd";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BackupOutcome {
+    /// First backup into the directory: a full copy of the pool.
+    Base { path: PathBuf, newest: Option<u64> },
+    /// Messages `first..=last` appended since the previous backup. `missed` counts messages
+    /// the ring overwrote before this backup could copy them.
+    Delta {
+        path: PathBuf,
+        first: u64,
+        last: u64,
+        missed: u64,
+    },
+    /// Nothing new since the previous backup.
+    UpToDate { newest: u64 },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RestoreOutcome {
+    /// True when the target pool was created from the base copy by this run.
+    pub created: bool,
+    pub deltas: usize,
+    pub messages: u64,
+    /// Last backed-up seq now reflected in the target.
+    pub through: u64,
+}
+
+/// Back up `pool_path` into `dir`. An empty directory gets a full base copy; afterwards,
+/// `incremental` appends a delta with everything newer than the directory cursor.
+pub fn backup(pool_path: &Path, dir: &Path, incremental: bool) -> Result<BackupOutcome, Error> {
+    let base = dir.join(BASE_FILE);
+    let cursor = ForwardCursor::at(dir.join(CURSOR_FILE));
+    if !base.exists() {
+        fs::create_dir_all(dir).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to create backup directory")
+                .with_path(dir)
+                .with_source(err)
+        })?;
+        LocalClient::new().snapshot_pool(&PoolRef::path(pool_path), &base)?;
+        let newest = Pool::open_read_only(&base)?.bounds()?.newest_seq;
+        cursor.store(newest.unwrap_or(0))?;
+        return Ok(BackupOutcome::Base { path: base, newest });
+    }
+    if !incremental {
+        return Err(Error::new(ErrorKind::AlreadyExists)
+            .with_message("backup directory already holds a base copy")
+            .with_path(dir)
+            .with_hint("Pass --incremental to add a delta, or back up into an empty directory."));
+    }
+
+    let last = cursor.load()?.ok_or_else(|| {
+        Error::new(ErrorKind::Corrupt)
+            .with_message("backup cursor is missing")
+            .with_path(cursor.path())
+            .with_hint("Start a new backup in an empty directory.")
+    })?;
+    let pool = Pool::open_read_only(pool_path)?;
+    let bounds = pool.bounds()?;
+    let newest = bounds.newest_seq.unwrap_or(0);
+    if newest < last {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!(
+                "pool is at seq {newest}, behind the last backup (seq {last})"
+            ))
+            .with_path(pool_path)
+            .with_hint("The pool was recreated; start a new backup in an empty directory."));
+    }
+    if newest == last {
+        return Ok(BackupOutcome::UpToDate { newest });
+    }
+
+    let oldest = bounds.oldest_seq.unwrap_or(newest);
+    let first = (last + 1).max(oldest);
+    let frames = pool.get_range(first, newest)?;
+    let last_written = frames.last().map_or(last, |frame| frame.seq);
+    let path = dir.join(format!(
+        "delta-{first:020}-{last_written:020}.{DELTA_EXTENSION}"
+    ));
+    write_atomically(&path, |out| {
+        out.write_all(DELTA_MAGIC)?;
+        for frame in &frames {
+            out.write_all(&frame.seq.to_le_bytes())?;
+            out.write_all(&frame.timestamp_ns.to_le_bytes())?;
+            out.write_all(&(frame.payload.len() as u32).to_le_bytes())?;
+            out.write_all(frame.payload)?;
+        }
+        Ok(())
+    })?;
+    cursor.store(last_written)?;
+    Ok(BackupOutcome::Delta {
+        path,
+        first,
+        last: last_written,
+        missed: first - (last + 1),
+    })
+}
+
+/// Rebuild `target` from the backup in `dir`: copy the base when `target` does not exist,
+/// then append every delta message newer than the target's restore cursor. The cursor
+/// advances once per delta, so a restore killed mid-delta should be redone into a new pool.
+pub fn restore(dir: &Path, target: &Path) -> Result<RestoreOutcome, Error> {
+    run_restore(dir, target, false)
+}
+
+/// What `restore` would do, after the same checks, without creating or appending to `target`.
+pub fn plan_restore(dir: &Path, target: &Path) -> Result<RestoreOutcome, Error> {
+    run_restore(dir, target, true)
+}
+
+fn run_restore(dir: &Path, target: &Path, dry_run: bool) -> Result<RestoreOutcome, Error> {
+    let base = dir.join(BASE_FILE);
+    if !base.exists() {
+        return Err(Error::new(ErrorKind::NotFound)
+            .with_message("no backup found")
+            .with_path(dir)
+            .with_hint("Create one with `plasmite backup <pool> --to <dir>`."));
+    }
+    let cursor = ForwardCursor::at(restore_cursor_path(target));
+    let created = !target.exists();
+    let mut through = if created {
+        let base_pool = Pool::open_read_only(&base)?;
+        let through = base_pool.bounds()?.newest_seq.unwrap_or(0);
+        if !dry_run {
+            base_pool.snapshot_to(target)?;
+            cursor.store(through)?;
+        }
+        through
+    } else {
+        cursor.load()?.ok_or_else(|| {
+            Error::new(ErrorKind::AlreadyExists)
+                .with_message("target pool exists but was not restored from a backup")
+                .with_path(target)
+                .with_hint("Restore into a new pool name, or delete the existing pool first.")
+        })?
+    };
+
+    let mut pool = if dry_run {
+        None
+    } else {
+        Some(Pool::open(target)?)
+    };
+    let mut outcome = RestoreOutcome {
+        created,
+        deltas: 0,
+        messages: 0,
+        through,
+    };
+    for (last, path) in delta_files(dir)? {
+        if last <= through {
+            continue;
+        }
+        let mut reader = BufReader::new(fs::File::open(&path).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to open backup delta")
+                .with_path(&path)
+                .with_source(err)
+        })?);
+        let corrupt = |err: io::Error| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("truncated or invalid backup delta")
+                .with_path(&path)
+                .with_source(err)
+        };
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(corrupt)?;
+        if &magic != DELTA_MAGIC {
+            return Err(Error::new(ErrorKind::Corrupt)
+                .with_message("not a plasmite backup delta")
+                .with_path(&path));
+        }
+        while let Some((seq, timestamp_ns, payload)) = read_record(&mut reader).map_err(corrupt)? {
+            if seq <= through {
+                continue;
+            }
+            if let Some(pool) = pool.as_mut() {
+                pool.append_with_timestamp(&payload, timestamp_ns)?;
+            }
+            outcome.messages += 1;
+        }
+        through = last;
+        if !dry_run {
+            cursor.store(through)?;
+        }
+        outcome.deltas += 1;
+    }
+    outcome.through = through;
+    Ok(outcome)
+}
+
+fn restore_cursor_path(target: &Path) -> PathBuf {
+    let mut name = target.as_os_str().to_owned();
+    name.push(".restore.json");
+    PathBuf::from(name)
+}
+
+/// Delta files in the directory as `(last seq, path)`, oldest first.
+fn delta_files(dir: &Path) -> Result<Vec<(u64, PathBuf)>, Error> {
+    let entries = fs::read_dir(dir).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to read backup directory")
+            .with_path(dir)
+            .with_source(err)
+    })?;
+    let mut deltas = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(DELTA_EXTENSION) {
+            continue;
+        }
+        let range = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_prefix("delta-"))
+            .and_then(|range| range.split_once('-'))
+            .and_then(|(first, last)| {
+                Some((first.parse::<u64>().ok()?, last.parse::<u64>().ok()?))
+            });
+        if let Some((first, last)) = range {
+            deltas.push((first, last, path));
+        }
+    }
+    deltas.sort();
+    Ok(deltas
+        .into_iter()
+        .map(|(_, last, path)| (last, path))
+        .collect())
+}
+
+fn read_record(reader: &mut impl Read) -> io::Result<Option<(u64, u64, Vec<u8>)>> {
+    let mut seq = [0u8; 8];
+    match reader.read_exact(&mut seq) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    let mut timestamp_ns = [0u8; 8];
+    reader.read_exact(&mut timestamp_ns)?;
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let mut payload = vec![0u8; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut payload)?;
+    Ok(Some((
+        u64::from_le_bytes(seq),
+        u64::from_le_bytes(timestamp_ns),
+        payload,
+    )))
+}
+
+fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<fs::File>) -> io::Result<()>,
+) -> Result<(), Error> {
+    let tmp = path.with_extension(format!("{DELTA_EXTENSION}.tmp"));
+    let io_error = |err: io::Error| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to write backup delta")
+            .with_path(path)
+            .with_source(err)
+    };
+    let result = fs::File::create(&tmp).and_then(|file| {
+        let mut out = BufWriter::new(file);
+        write(&mut out)?;
+        out.into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()
+    });
+    if let Err(err) = result.and_then(|()| fs::rename(&tmp, path)) {
+        let _ = fs::remove_file(&tmp);
+        return Err(io_error(err));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackupOutcome, backup, plan_restore, restore};
+    use plasmite::api::{Pool, PoolOptions};
+
+    #[test]
+    fn incremental_backups_restore_and_catch_up() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let source = temp.path().join("source.plasmite");
+        let dir = temp.path().join("backup");
+        let target = temp.path().join("restored.plasmite");
+        let mut pool = Pool::create(&source, PoolOptions::new(1024 * 1024)).expect("create");
+        pool.append_with_timestamp(b"one", 10).expect("append");
+
+        let base = backup(&source, &dir, true).expect("base");
+        assert!(matches!(
+            base,
+            BackupOutcome::Base {
+                newest: Some(1),
+                ..
+            }
+        ));
+        assert!(backup(&source, &dir, false).is_err());
+        assert_eq!(
+            backup(&source, &dir, true).expect("noop"),
+            BackupOutcome::UpToDate { newest: 1 }
+        );
+
+        pool.append_with_timestamp(b"two", 20).expect("append");
+        pool.append_with_timestamp(b"three", 30).expect("append");
+        let delta = backup(&source, &dir, true).expect("delta");
+        assert!(matches!(
+            delta,
+            BackupOutcome::Delta {
+                first: 2,
+                last: 3,
+                missed: 0,
+                ..
+            }
+        ));
+
+        let plan = plan_restore(&dir, &target).expect("plan");
+        assert!(!target.exists(), "a planned restore writes nothing");
+        let outcome = restore(&dir, &target).expect("restore");
+        assert_eq!(plan, outcome);
+        assert!(outcome.created);
+        assert_eq!(
+            (outcome.deltas, outcome.messages, outcome.through),
+            (1, 2, 3)
+        );
+        let restored = Pool::open_read_only(&target).expect("open");
+        let frame = restored.get(3).expect("get");
+        assert_eq!((frame.payload, frame.timestamp_ns), (&b"three"[..], 30));
+
+        pool.append_with_timestamp(b"four", 40).expect("append");
+        backup(&source, &dir, true).expect("delta");
+        let outcome = restore(&dir, &target).expect("catch up");
+        assert!(!outcome.created);
+        assert_eq!(
+            (outcome.deltas, outcome.messages, outcome.through),
+            (1, 1, 4)
+        );
+        assert_eq!(
+            Pool::open_read_only(&target)
+                .expect("open")
+                .bounds()
+                .expect("bounds")
+                .newest_seq,
+            Some(4)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Purpose: Compare the messages of two sources for `plasmite diff`.
+//! Exports: `DiffEntry`, `PoolDiffReport`, `content_key`, `diff`.
+//! Role: Pure bookkeeping over `(seq, key)` pairs; reading pools and dumps stays in dispatch.
+//! Invariants: Keys are compared as multisets: two copies on one side and one on the other
+//! leave one copy unmatched.
+//! Invariants: Content keys cover `meta.tags` and `data` only, so re-sequenced mirrors match.
+use std::collections::HashMap;
+
+use plasmite::api::fnv1a64;
+use serde_json::{Value, json};
+
+/// One message present on a single side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub seq: u64,
+    pub key: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PoolDiffReport {
+    pub a_count: usize,
+    pub b_count: usize,
+    pub matched: usize,
+    pub only_in_a: Vec<DiffEntry>,
+    pub only_in_b: Vec<DiffEntry>,
+}
+
+impl PoolDiffReport {
+    pub fn is_identical(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty()
+    }
+
+    pub fn to_json(&self) -> Value {
+        let entries = |entries: &[DiffEntry]| {
+            entries
+                .iter()
+                .map(|entry| json!({"seq": entry.seq, "key": entry.key}))
+                .collect::<Vec<_>>()
+        };
+        json!({
+            "identical": self.is_identical(),
+            "matched": self.matched,
+            "a_count": self.a_count,
+            "b_count": self.b_count,
+            "only_in_a": entries(&self.only_in_a),
+            "only_in_b": entries(&self.only_in_b),
+        })
+    }
+}
+
+/// Stable key for a message envelope: FNV-1a of its canonical `{tags, data}` JSON.
+pub fn content_key(message: &Value) -> String {
+    let tags = message
+        .get("meta")
+        .and_then(|meta| meta.get("tags"))
+        .cloned()
+        .unwrap_or_else(|| json!([]));
+    let data = message.get("data").cloned().unwrap_or(Value::Null);
+    // serde_json maps are ordered, so equal documents serialize identically.
+    let canonical = json!({"tags": tags, "data": data}).to_string();
+    format!("{:016x}", fnv1a64(canonical.as_bytes()))
+}
+
+/// Match `a` against `b` by key; unmatched entries keep their side's seq order.
+pub fn diff(a: &[DiffEntry], b: &[DiffEntry]) -> PoolDiffReport {
+    let mut remaining: HashMap<&str, usize> = HashMap::new();
+    for entry in b {
+        *remaining.entry(entry.key.as_str()).or_default() += 1;
+    }
+    let mut only_in_a = Vec::new();
+    let mut matched_in_b: HashMap<&str, usize> = HashMap::new();
+    for entry in a {
+        match remaining.get_mut(entry.key.as_str()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                *matched_in_b.entry(entry.key.as_str()).or_default() += 1;
+            }
+            _ => only_in_a.push(entry.clone()),
+        }
+    }
+    let mut only_in_b = Vec::new();
+    for entry in b {
+        match matched_in_b.get_mut(entry.key.as_str()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => only_in_b.push(entry.clone()),
+        }
+    }
+    PoolDiffReport {
+        a_count: a.len(),
+        b_count: b.len(),
+        matched: a.len() - only_in_a.len(),
+        only_in_a,
+        only_in_b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffEntry, content_key, diff};
+    use serde_json::json;
+
+    fn entries(keys: &[&str]) -> Vec<DiffEntry> {
+        keys.iter()
+            .enumerate()
+            .map(|(index, key)| DiffEntry {
+                seq: index as u64 + 1,
+                key: key.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diff_matches_multisets_and_reports_both_sides() {
+        let report = diff(&entries(&["x", "y", "y", "z"]), &entries(&["y", "z", "w"]));
+        assert_eq!(report.matched, 2);
+        assert_eq!(
+            report.only_in_a,
+            vec![
+                DiffEntry {
+                    seq: 1,
+                    key: "x".to_string()
+                },
+                DiffEntry {
+                    seq: 3,
+                    key: "y".to_string()
+                }
+            ]
+        );
+        assert_eq!(
+            report.only_in_b,
+            vec![DiffEntry {
+                seq: 3,
+                key: "w".to_string()
+            }]
+        );
+        assert!(!report.is_identical());
+    }
+
+    #[test]
+    fn content_key_ignores_seq_and_time() {
+        let a = json!({"seq": 1, "time": "t1", "meta": {"tags": ["a"]}, "data": {"x": 1, "y": 2}});
+        let b = json!({"seq": 9, "time": "t2", "meta": {"tags": ["a"]}, "data": {"y": 2, "x": 1}});
+        assert_eq!(content_key(&a), content_key(&b));
+        let c = json!({"meta": {"tags": []}, "data": {"x": 1, "y": 2}});
+        assert_ne!(content_key(&a), content_key(&c));
+    }
+}
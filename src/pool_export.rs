@@ -0,0 +1,644 @@
+//! Purpose: Write pool messages to JSONL, Parquet, or SQLite files for `plasmite pool export`,
+//! and read SQLite exports back for `plasmite pool import`.
+//! Exports: `ExportFormat`, `ExportRow`, `ExportColumn`, `ImportRow`, `parse_columns`,
+//! `write_jsonl`, `write_parquet`, `write_sqlite`, `read_sqlite`.
+//! Role: Pure formatting over decoded envelopes; reading and appending to pools stay in dispatch.
+//! Invariants: Parquet rows carry `seq`, `time` (UTC nanoseconds), and `tags`, plus either
+//! `data` as a JSON string or one column per `--columns` path.
+//! Invariants: Exploded columns take the narrowest type every non-null value fits (boolean,
+//! int64, double); anything mixed or nested is written as text (strings raw, others JSON).
+//! Invariants: SQLite files hold one `messages(seq, time, tags, data_json)` table keyed by seq
+//! and indexed by time; `time` is UTC nanoseconds and `tags` a JSON array, so an import
+//! re-appends every message with its original timestamp and tags.
+//! Invariants: SQLite exports are written beside `--out` as `<out>.partial` and renamed into
+//! place on success; imports stream rows one at a time in seq order.
+//! Invariants: Parquet and SQLite need the `parquet` and `sqlite` cargo features; without
+//! them export and import fail with a usage error before any file is created.
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde_json::Value;
+
+use plasmite::api::{Error, ErrorKind};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Jsonl,
+    Parquet,
+    Sqlite,
+}
+
+/// One message to export: the decoded envelope plus its frame timestamp.
+#[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+pub struct ExportRow {
+    pub seq: u64,
+    pub timestamp_ns: u64,
+    pub message: Value,
+}
+
+/// One message read back from a SQLite export, in seq order.
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+pub struct ImportRow {
+    /// The message's seq in the exported pool; the importing pool assigns its own.
+    pub seq: u64,
+    pub timestamp_ns: u64,
+    pub tags: Vec<String>,
+    pub data: Value,
+}
+
+/// A `.data` path exploded into its own Parquet column.
+#[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExportColumn {
+    /// Column name: the path as written, without the leading `.data.`.
+    pub name: String,
+    path: Vec<String>,
+}
+
+/// Parse `--columns` such as `level,user.id` (`.data.level` and `.level` also work).
+pub fn parse_columns(spec: &str) -> Result<Vec<ExportColumn>, Error> {
+    let mut columns: Vec<ExportColumn> = Vec::new();
+    for token in spec.split(',').map(str::trim) {
+        let relative = token
+            .strip_prefix(".data.")
+            .or_else(|| token.strip_prefix('.'))
+            .unwrap_or(token);
+        let path = relative.split('.').map(str::to_string).collect::<Vec<_>>();
+        if relative.is_empty() || path.iter().any(String::is_empty) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid --columns entry '{token}'"))
+                .with_hint("Use comma-separated .data paths like `level,user.id`."));
+        }
+        if columns.iter().any(|column| column.name == relative) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("duplicate --columns entry '{token}'")));
+        }
+        columns.push(ExportColumn {
+            name: relative.to_string(),
+            path,
+        });
+    }
+    Ok(columns)
+}
+
+/// One compact envelope per line.
+pub fn write_jsonl(rows: &[ExportRow], out: &mut impl Write) -> std::io::Result<()> {
+    for row in rows {
+        serde_json::to_writer(&mut *out, &row.message)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn write_parquet(
+    rows: &[ExportRow],
+    columns: &[ExportColumn],
+    out: &std::path::Path,
+) -> Result<(), Error> {
+    let _ = (rows, columns);
+    Err(Error::new(ErrorKind::Usage)
+        .with_message("Parquet export is not available in this build")
+        .with_path(out)
+        .with_hint("Rebuild with `--features parquet`, or export JSONL and convert it."))
+}
+
+#[cfg(feature = "parquet")]
+pub use parquet_writer::write_parquet;
+
+#[cfg(not(feature = "sqlite"))]
+fn sqlite_unavailable(path: &std::path::Path) -> Error {
+    Error::new(ErrorKind::Usage)
+        .with_message("SQLite export and import are not available in this build")
+        .with_path(path)
+        .with_hint("Rebuild with `--features sqlite`, or use JSONL.")
+}
+
+#[cfg(not(feature = "sqlite"))]
+pub fn write_sqlite(rows: &[ExportRow], out: &std::path::Path) -> Result<(), Error> {
+    let _ = rows;
+    Err(sqlite_unavailable(out))
+}
+
+#[cfg(not(feature = "sqlite"))]
+pub fn read_sqlite(
+    path: &std::path::Path,
+    on_row: impl FnMut(ImportRow) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let _ = on_row;
+    Err(sqlite_unavailable(path))
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite_bridge::{read_sqlite, write_sqlite};
+
+#[cfg(feature = "sqlite")]
+mod sqlite_bridge {
+    use std::path::{Path, PathBuf};
+
+    use rusqlite::{Connection, OpenFlags, params};
+    use serde_json::Value;
+
+    use super::{ExportRow, ImportRow};
+    use plasmite::api::{Error, ErrorKind};
+
+    const SCHEMA: &str = "CREATE TABLE messages (
+    seq INTEGER PRIMARY KEY,
+    time INTEGER NOT NULL,
+    tags TEXT NOT NULL,
+    data_json TEXT NOT NULL
+);
+CREATE INDEX messages_time ON messages (time);";
+
+    pub fn write_sqlite(rows: &[ExportRow], out: &Path) -> Result<(), Error> {
+        if out.exists() {
+            return Err(Error::new(ErrorKind::AlreadyExists)
+                .with_message("output file already exists")
+                .with_path(out)
+                .with_hint("Remove it or choose another --out."));
+        }
+        let mut partial = out.as_os_str().to_owned();
+        partial.push(".partial");
+        let partial = PathBuf::from(partial);
+        // A leftover from an interrupted export would already hold the table.
+        let _ = std::fs::remove_file(&partial);
+        if let Err(err) = write_rows(rows, &partial) {
+            let _ = std::fs::remove_file(&partial);
+            return Err(err.with_path(out));
+        }
+        std::fs::rename(&partial, out).map_err(|err| {
+            let _ = std::fs::remove_file(&partial);
+            Error::new(ErrorKind::Io)
+                .with_message("failed to move sqlite file into place")
+                .with_path(out)
+                .with_source(err)
+        })
+    }
+
+    fn write_rows(rows: &[ExportRow], path: &Path) -> Result<(), Error> {
+        let write_error = |err: rusqlite::Error| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to write sqlite file")
+                .with_path(path)
+                .with_source(err)
+        };
+        let mut conn = Connection::open(path).map_err(write_error)?;
+        conn.execute_batch(SCHEMA).map_err(write_error)?;
+        let tx = conn.transaction().map_err(write_error)?;
+        {
+            let mut insert = tx
+                .prepare(
+                    "INSERT INTO messages (seq, time, tags, data_json) VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(write_error)?;
+            for row in rows {
+                let tags = row
+                    .message
+                    .get("meta")
+                    .and_then(|meta| meta.get("tags"))
+                    .cloned()
+                    .unwrap_or_else(|| Value::Array(Vec::new()));
+                let data = row.message.get("data").cloned().unwrap_or(Value::Null);
+                insert
+                    .execute(params![
+                        row.seq as i64,
+                        row.timestamp_ns as i64,
+                        tags.to_string(),
+                        data.to_string(),
+                    ])
+                    .map_err(write_error)?;
+            }
+        }
+        tx.commit().map_err(write_error)?;
+        conn.close().map_err(|(_, err)| write_error(err))
+    }
+
+    /// Call `on_row` for each row in seq order, stopping at the first error either side returns.
+    pub fn read_sqlite(
+        path: &Path,
+        mut on_row: impl FnMut(ImportRow) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let read_error = |err: rusqlite::Error| {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("failed to read sqlite export: {err}"))
+                .with_path(path)
+                .with_hint("Pass a database written by `plasmite pool export --format sqlite`.")
+        };
+        if !path.is_file() {
+            return Err(Error::new(ErrorKind::NotFound)
+                .with_message("sqlite file not found")
+                .with_path(path));
+        }
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(read_error)?;
+        let mut select = conn
+            .prepare("SELECT seq, time, tags, data_json FROM messages ORDER BY seq")
+            .map_err(read_error)?;
+        let mut rows = select
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(read_error)?;
+        rows.try_for_each(|row| {
+            let (seq, time, tags, data) = row.map_err(read_error)?;
+            let invalid = |detail: &str| {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid sqlite export row: {detail}"))
+                    .with_path(path)
+                    .with_seq(seq.max(0) as u64)
+            };
+            on_row(ImportRow {
+                seq: u64::try_from(seq).map_err(|_| invalid("negative seq"))?,
+                timestamp_ns: u64::try_from(time).map_err(|_| invalid("negative time"))?,
+                tags: serde_json::from_str(&tags)
+                    .map_err(|_| invalid("tags must be a JSON array of strings"))?,
+                data: serde_json::from_str(&data)
+                    .map_err(|_| invalid("data_json must be valid JSON"))?,
+            })
+        })
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_writer {
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use parquet::basic::{Compression, LogicalType, Repetition, TimeUnit, Type as PhysicalType};
+    use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+    use parquet::errors::ParquetError;
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::{
+        SerializedColumnWriter, SerializedFileWriter, SerializedRowGroupWriter,
+    };
+    use parquet::schema::types::Type;
+    use serde_json::Value;
+
+    use super::{ExportColumn, ExportRow};
+    use plasmite::api::{Error, ErrorKind};
+
+    /// Rows per row group; bounds writer memory on large pools.
+    const ROW_GROUP_ROWS: usize = 65_536;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub(super) enum ColumnKind {
+        Bool,
+        Int,
+        Double,
+        Text,
+    }
+
+    pub(super) fn infer_kind<'a>(values: impl Iterator<Item = Option<&'a Value>>) -> ColumnKind {
+        let mut kind = None;
+        for value in values.flatten() {
+            let next = match value {
+                Value::Null => continue,
+                Value::Bool(_) => ColumnKind::Bool,
+                Value::Number(number) if number.is_i64() => ColumnKind::Int,
+                Value::Number(_) => ColumnKind::Double,
+                _ => ColumnKind::Text,
+            };
+            kind = Some(match (kind, next) {
+                (None, next) => next,
+                (Some(current), next) if current == next => current,
+                (Some(ColumnKind::Int), ColumnKind::Double)
+                | (Some(ColumnKind::Double), ColumnKind::Int) => ColumnKind::Double,
+                _ => ColumnKind::Text,
+            });
+        }
+        kind.unwrap_or(ColumnKind::Text)
+    }
+
+    fn lookup<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+        path.iter().try_fold(value, |value, key| match value {
+            Value::Object(map) => map.get(key),
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => None,
+        })
+    }
+
+    fn data(row: &ExportRow) -> Option<&Value> {
+        row.message.get("data")
+    }
+
+    fn text(value: &Value) -> ByteArray {
+        match value {
+            Value::String(text) => ByteArray::from(text.as_str()),
+            other => ByteArray::from(other.to_string().into_bytes()),
+        }
+    }
+
+    pub fn write_parquet(
+        rows: &[ExportRow],
+        columns: &[ExportColumn],
+        out: &Path,
+    ) -> Result<(), Error> {
+        let write_error = |err: ParquetError| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to write parquet file")
+                .with_path(out)
+                .with_source(err)
+        };
+        let kinds = columns
+            .iter()
+            .map(|column| {
+                infer_kind(
+                    rows.iter()
+                        .map(|row| data(row).and_then(|data| lookup(data, &column.path))),
+                )
+            })
+            .collect::<Vec<_>>();
+        let schema = schema(columns, &kinds).map_err(write_error)?;
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let file = File::create(out).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to create output file")
+                .with_path(out)
+                .with_source(err)
+        })?;
+        let mut writer = SerializedFileWriter::new(file, Arc::new(schema), Arc::new(props))
+            .map_err(write_error)?;
+        for group in rows.chunks(ROW_GROUP_ROWS) {
+            let mut row_group = writer.next_row_group().map_err(write_error)?;
+
+            let mut seq = next_column(&mut row_group).map_err(write_error)?;
+            let values = group.iter().map(|row| row.seq as i64).collect::<Vec<_>>();
+            seq.typed::<Int64Type>()
+                .write_batch(&values, None, None)
+                .map_err(write_error)?;
+            seq.close().map_err(write_error)?;
+
+            let mut time = next_column(&mut row_group).map_err(write_error)?;
+            let values = group
+                .iter()
+                .map(|row| row.timestamp_ns as i64)
+                .collect::<Vec<_>>();
+            time.typed::<Int64Type>()
+                .write_batch(&values, None, None)
+                .map_err(write_error)?;
+            time.close().map_err(write_error)?;
+
+            let mut tags = next_column(&mut row_group).map_err(write_error)?;
+            let (values, def_levels, rep_levels) = tag_levels(group);
+            tags.typed::<ByteArrayType>()
+                .write_batch(&values, Some(&def_levels), Some(&rep_levels))
+                .map_err(write_error)?;
+            tags.close().map_err(write_error)?;
+
+            if columns.is_empty() {
+                let mut column = next_column(&mut row_group).map_err(write_error)?;
+                let cells = group.iter().map(data).collect::<Vec<_>>();
+                write_optional(&mut column, ColumnKind::Text, &cells).map_err(write_error)?;
+                column.close().map_err(write_error)?;
+            }
+            for (spec, kind) in columns.iter().zip(&kinds) {
+                let mut column = next_column(&mut row_group).map_err(write_error)?;
+                let cells = group
+                    .iter()
+                    .map(|row| data(row).and_then(|data| lookup(data, &spec.path)))
+                    .collect::<Vec<_>>();
+                write_optional(&mut column, *kind, &cells).map_err(write_error)?;
+                column.close().map_err(write_error)?;
+            }
+            row_group.close().map_err(write_error)?;
+        }
+        writer.close().map_err(write_error)?;
+        Ok(())
+    }
+
+    fn next_column<'a, W: Write + Send>(
+        row_group: &'a mut SerializedRowGroupWriter<'_, W>,
+    ) -> Result<SerializedColumnWriter<'a>, ParquetError> {
+        row_group
+            .next_column()?
+            .ok_or_else(|| ParquetError::General("schema column missing".to_string()))
+    }
+
+    fn schema(columns: &[ExportColumn], kinds: &[ColumnKind]) -> Result<Type, ParquetError> {
+        let string = |name: &str, repetition| {
+            Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
+                .with_repetition(repetition)
+                .with_logical_type(Some(LogicalType::String))
+                .build()
+        };
+        let mut fields = vec![
+            Type::primitive_type_builder("seq", PhysicalType::INT64)
+                .with_repetition(Repetition::REQUIRED)
+                .build()?,
+            Type::primitive_type_builder("time", PhysicalType::INT64)
+                .with_repetition(Repetition::REQUIRED)
+                .with_logical_type(Some(LogicalType::Timestamp {
+                    is_adjusted_to_u_t_c: true,
+                    unit: TimeUnit::NANOS(Default::default()),
+                }))
+                .build()?,
+            string("tags", Repetition::REPEATED)?,
+        ];
+        if columns.is_empty() {
+            fields.push(string("data", Repetition::OPTIONAL)?);
+        }
+        for (column, kind) in columns.iter().zip(kinds) {
+            fields.push(match kind {
+                ColumnKind::Text => string(&column.name, Repetition::OPTIONAL)?,
+                ColumnKind::Bool | ColumnKind::Int | ColumnKind::Double => {
+                    let physical = match kind {
+                        ColumnKind::Bool => PhysicalType::BOOLEAN,
+                        ColumnKind::Int => PhysicalType::INT64,
+                        _ => PhysicalType::DOUBLE,
+                    };
+                    Type::primitive_type_builder(&column.name, physical)
+                        .with_repetition(Repetition::OPTIONAL)
+                        .build()?
+                }
+            });
+        }
+        Type::group_type_builder("plasmite")
+            .with_fields(fields.into_iter().map(Arc::new).collect())
+            .build()
+    }
+
+    /// Flattened tag values with definition/repetition levels for a top-level repeated column.
+    fn tag_levels(rows: &[ExportRow]) -> (Vec<ByteArray>, Vec<i16>, Vec<i16>) {
+        let (mut values, mut def_levels, mut rep_levels) = (Vec::new(), Vec::new(), Vec::new());
+        for row in rows {
+            let tags = row
+                .message
+                .get("meta")
+                .and_then(|meta| meta.get("tags"))
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            if tags.is_empty() {
+                def_levels.push(0);
+                rep_levels.push(0);
+                continue;
+            }
+            for (index, tag) in tags.iter().enumerate() {
+                values.push(text(tag));
+                def_levels.push(1);
+                rep_levels.push(i16::from(index > 0));
+            }
+        }
+        (values, def_levels, rep_levels)
+    }
+
+    fn write_optional(
+        column: &mut SerializedColumnWriter<'_>,
+        kind: ColumnKind,
+        cells: &[Option<&Value>],
+    ) -> Result<(), ParquetError> {
+        let present = |cell: &&Option<&Value>| !matches!(cell, None | Some(Value::Null));
+        let def_levels = cells
+            .iter()
+            .map(|cell| i16::from(present(&cell)))
+            .collect::<Vec<_>>();
+        let values = cells.iter().filter(present).flatten();
+        match kind {
+            ColumnKind::Bool => {
+                let values = values
+                    .map(|value| value.as_bool().unwrap_or_default())
+                    .collect::<Vec<_>>();
+                column
+                    .typed::<BoolType>()
+                    .write_batch(&values, Some(&def_levels), None)?;
+            }
+            ColumnKind::Int => {
+                let values = values
+                    .map(|value| value.as_i64().unwrap_or_default())
+                    .collect::<Vec<_>>();
+                column
+                    .typed::<Int64Type>()
+                    .write_batch(&values, Some(&def_levels), None)?;
+            }
+            ColumnKind::Double => {
+                let values = values
+                    .map(|value| value.as_f64().unwrap_or_default())
+                    .collect::<Vec<_>>();
+                column
+                    .typed::<DoubleType>()
+                    .write_batch(&values, Some(&def_levels), None)?;
+            }
+            ColumnKind::Text => {
+                let values = values.map(|value| text(value)).collect::<Vec<_>>();
+                column
+                    .typed::<ByteArrayType>()
+                    .write_batch(&values, Some(&def_levels), None)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportRow, parse_columns, write_jsonl};
+    use serde_json::json;
+
+    #[test]
+    fn parses_columns_relative_to_data() {
+        let columns = parse_columns("level, .data.user.id,.host").expect("parse");
+        let names = columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["level", "user.id", "host"]);
+        assert!(parse_columns("level,,host").is_err());
+        assert!(parse_columns("level,.data.level").is_err());
+    }
+
+    #[test]
+    fn writes_one_envelope_per_line() {
+        let rows = [1, 2].map(|seq| ExportRow {
+            seq,
+            timestamp_ns: seq * 1_000,
+            message: json!({"seq": seq, "data": {"n": seq}}),
+        });
+        let mut out = Vec::new();
+        write_jsonl(&rows, &mut out).expect("write");
+        assert_eq!(
+            String::from_utf8(out).expect("utf8"),
+            "{\"data\":{\"n\":1},\"seq\":1}\n{\"data\":{\"n\":2},\"seq\":2}\n"
+        );
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_infers_column_kinds_and_writes_rows() {
+        use super::parquet_writer::{ColumnKind, infer_kind};
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let level = json!("warn");
+        let count = json!(3);
+        let ratio = json!(0.5);
+        assert_eq!(
+            infer_kind([Some(&count), None, Some(&ratio)].into_iter()),
+            ColumnKind::Double
+        );
+        assert_eq!(
+            infer_kind([Some(&count), Some(&level)].into_iter()),
+            ColumnKind::Text
+        );
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("pool.parquet");
+        let rows = [1, 2].map(|seq| ExportRow {
+            seq,
+            timestamp_ns: seq * 1_000,
+            message: json!({"seq": seq, "meta": {"tags": ["a"]}, "data": {"n": seq}}),
+        });
+        let columns = parse_columns("n,missing").expect("columns");
+        super::write_parquet(&rows, &columns, &out).expect("write");
+        let reader =
+            SerializedFileReader::new(std::fs::File::open(&out).expect("open")).expect("reader");
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 2);
+        let names = metadata
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["seq", "time", "tags", "n", "missing"]);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_exports_read_back_with_times_and_tags() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("pool.db");
+        let rows = [3, 4].map(|seq| ExportRow {
+            seq,
+            timestamp_ns: seq * 1_000,
+            message: json!({"seq": seq, "meta": {"tags": ["a"]}, "data": {"n": seq}}),
+        });
+        super::write_sqlite(&rows, &out).expect("write");
+        assert!(
+            super::write_sqlite(&rows, &out).is_err(),
+            "never overwrites"
+        );
+
+        assert!(!dir.path().join("pool.db.partial").exists());
+
+        let mut imported = Vec::new();
+        super::read_sqlite(&out, |row| {
+            imported.push(row);
+            Ok(())
+        })
+        .expect("read");
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].seq, 3);
+        assert_eq!(imported[1].timestamp_ns, 4_000);
+        assert_eq!(imported[1].tags, vec!["a".to_string()]);
+        assert_eq!(imported[1].data, json!({"n": 4}));
+    }
+}
@@ -0,0 +1,154 @@
+//! Purpose: Pick abandoned pools for `plasmite pool gc`.
+//! Exports: `GcReason`, `GcCandidate`, `GcSkipped`, `GcScan`, `scan_pools`, `classify`.
+//! Role: Read-only selection; deletion, confirmation, and output stay in dispatch.
+//! Invariants: A pool's last activity is the later of its newest frame timestamp and the file
+//! mtime, so neither a touched-but-idle file nor a stale mtime on a busy mmap alone decides.
+//! Invariants: Never-written pools are always candidates; pools that cannot be opened are
+//! reported as skipped and never collected.
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use plasmite::api::{Error, Pool};
+use serde_json::{Value, json};
+
+use crate::pool_paths::pool_name_for_path;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GcReason {
+    /// No message was ever appended.
+    Empty,
+    /// Last activity is older than the `--older-than` cutoff.
+    Stale,
+}
+
+impl GcReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GcReason::Empty => "empty",
+            GcReason::Stale => "stale",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GcCandidate {
+    pub name: String,
+    pub path: PathBuf,
+    pub reason: GcReason,
+    pub last_activity_ns: Option<u64>,
+    pub file_size: u64,
+    /// Oldest and newest seq still in the pool; `None` when it holds no messages.
+    pub seqs: Option<(u64, u64)>,
+}
+
+impl GcCandidate {
+    pub fn to_json(&self, last_activity: Option<String>) -> Value {
+        json!({
+            "pool": self.name,
+            "path": self.path.display().to_string(),
+            "reason": self.reason.as_str(),
+            "last_activity": last_activity,
+            "file_size": self.file_size,
+            "oldest_seq": self.seqs.map(|(oldest, _)| oldest),
+            "newest_seq": self.seqs.map(|(_, newest)| newest),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct GcSkipped {
+    pub name: String,
+    pub path: PathBuf,
+    pub error: Error,
+}
+
+#[derive(Debug, Default)]
+pub struct GcScan {
+    pub candidates: Vec<GcCandidate>,
+    pub skipped: Vec<GcSkipped>,
+}
+
+/// `Empty` wins over age; with `empty_only`, written pools are never collected.
+pub fn classify(
+    newest_ts_ns: Option<u64>,
+    mtime_ns: u64,
+    cutoff_ns: u64,
+    empty_only: bool,
+) -> Option<GcReason> {
+    let Some(newest_ts_ns) = newest_ts_ns else {
+        return Some(GcReason::Empty);
+    };
+    if empty_only || newest_ts_ns.max(mtime_ns) >= cutoff_ns {
+        return None;
+    }
+    Some(GcReason::Stale)
+}
+
+pub fn scan_pools(
+    pool_dir: &Path,
+    paths: Vec<PathBuf>,
+    cutoff_ns: u64,
+    empty_only: bool,
+) -> GcScan {
+    let mut scan = GcScan::default();
+    for path in paths {
+        let name =
+            pool_name_for_path(pool_dir, &path).unwrap_or_else(|| path.display().to_string());
+        match inspect(&path) {
+            Ok((seqs, newest_ts_ns, mtime_ns, file_size)) => {
+                if let Some(reason) = classify(newest_ts_ns, mtime_ns, cutoff_ns, empty_only) {
+                    scan.candidates.push(GcCandidate {
+                        name,
+                        path,
+                        reason,
+                        last_activity_ns: newest_ts_ns.map(|ts| ts.max(mtime_ns)),
+                        file_size,
+                        seqs,
+                    });
+                }
+            }
+            Err(error) => scan.skipped.push(GcSkipped { name, path, error }),
+        }
+    }
+    scan
+}
+
+/// `(newest frame timestamp, mtime, file size)` for one pool.
+/// Seq bounds, newest frame timestamp, mtime, and file size of one pool.
+fn inspect(path: &Path) -> Result<(Option<(u64, u64)>, Option<u64>, u64, u64), Error> {
+    let pool = Pool::open_read_only(path)?;
+    let bounds = pool.bounds()?;
+    let seqs = bounds.oldest_seq.zip(bounds.newest_seq);
+    let newest_ts_ns = match bounds.newest_seq {
+        Some(newest) => Some(pool.get(newest)?.timestamp_ns),
+        None => None,
+    };
+    let meta = std::fs::metadata(path).map_err(|err| {
+        Error::new(plasmite::api::ErrorKind::Io)
+            .with_message("failed to stat pool")
+            .with_path(path)
+            .with_source(err)
+    })?;
+    let mtime_ns = meta
+        .modified()
+        .ok()
+        .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |age| age.as_nanos() as u64);
+    Ok((seqs, newest_ts_ns, mtime_ns, meta.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GcReason, classify};
+
+    #[test]
+    fn classify_collects_empty_and_stale_pools_only() {
+        let cutoff = 1_000;
+        assert_eq!(classify(None, 5_000, cutoff, false), Some(GcReason::Empty));
+        assert_eq!(classify(None, 5_000, cutoff, true), Some(GcReason::Empty));
+        assert_eq!(classify(Some(10), 20, cutoff, false), Some(GcReason::Stale));
+        assert_eq!(classify(Some(10), 20, cutoff, true), None);
+        assert_eq!(classify(Some(10), 2_000, cutoff, false), None);
+        assert_eq!(classify(Some(2_000), 20, cutoff, false), None);
+    }
+}
@@ -1,13 +1,19 @@
-//! Purpose: Shared pool-info JSON serializers for CLI and HTTP serving paths.
+//! Purpose: Shared pool-info JSON serializers for CLI, HTTP serving, and C ABI paths.
 //! Exports: `pool_info_json` and `bounds_json`.
 //! Role: Keep pool metadata envelope shape consistent across entry points.
 //! Invariants: Stable key names/order for v0 pool info payloads.
 //! Invariants: Metrics block is emitted only when source metrics exist.
+//! Invariants: `hybrid_timestamps`, `bloom_filter`, and `frozen` are emitted only when the pool
+//! has the flag set.
+//! Invariants: `codec` is emitted only for non-default (non-Lite3) pools.
+//! Invariants: `allocated_bytes` is emitted only when the platform reports it.
+//! Invariants: `max_messages` is emitted only for pools created with a message cap.
+//! Invariants: `max_frame_bytes`/`max_message_bytes` are emitted only for chunked pools.
 
-use plasmite::api::{Bounds, PoolInfo, PoolMetrics};
+use crate::api::{Bounds, PoolCodec, PoolInfo, PoolMetrics};
 use serde_json::{Map, Value, json};
 
-pub(crate) fn bounds_json(bounds: Bounds) -> Value {
+pub fn bounds_json(bounds: Bounds) -> Value {
     let mut map = Map::new();
     if let Some(oldest) = bounds.oldest_seq {
         map.insert("oldest".to_string(), json!(oldest));
@@ -18,7 +24,7 @@ pub(crate) fn bounds_json(bounds: Bounds) -> Value {
     Value::Object(map)
 }
 
-pub(crate) fn pool_info_json(pool_ref: &str, info: &PoolInfo) -> Value {
+pub fn pool_info_json(pool_ref: &str, info: &PoolInfo) -> Value {
     let mut map = Map::new();
     map.insert("name".to_string(), json!(pool_ref));
     map.insert("path".to_string(), json!(info.path.display().to_string()));
@@ -28,10 +34,34 @@ pub(crate) fn pool_info_json(pool_ref: &str, info: &PoolInfo) -> Value {
     map.insert("index_size_bytes".to_string(), json!(info.index_size_bytes));
     map.insert("ring_offset".to_string(), json!(info.ring_offset));
     map.insert("ring_size".to_string(), json!(info.ring_size));
+    if let Some(allocated_bytes) = info.allocated_bytes {
+        map.insert("allocated_bytes".to_string(), json!(allocated_bytes));
+    }
+    if let Some(max_messages) = info.max_messages {
+        map.insert("max_messages".to_string(), json!(max_messages));
+    }
+    if let Some(max_frame_bytes) = info.max_frame_bytes {
+        map.insert("max_frame_bytes".to_string(), json!(max_frame_bytes));
+    }
+    if let Some(max_message_bytes) = info.max_message_bytes {
+        map.insert("max_message_bytes".to_string(), json!(max_message_bytes));
+    }
     map.insert("bounds".to_string(), bounds_json(info.bounds));
     if let Some(metrics) = &info.metrics {
         map.insert("metrics".to_string(), pool_metrics_json(metrics));
     }
+    if info.hybrid_timestamps {
+        map.insert("hybrid_timestamps".to_string(), json!(true));
+    }
+    if info.bloom_filter {
+        map.insert("bloom_filter".to_string(), json!(true));
+    }
+    if info.frozen {
+        map.insert("frozen".to_string(), json!(true));
+    }
+    if info.codec != PoolCodec::Lite3 {
+        map.insert("codec".to_string(), json!(info.codec.as_str()));
+    }
     Value::Object(map)
 }
 
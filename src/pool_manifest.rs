@@ -0,0 +1,316 @@
+//! Purpose: Pool configuration as code for `pool describe --format toml` and `pool apply`.
+//! Exports: `PoolManifest`, `PoolSpec`, `PoolPlan`, `DescribeFormat`.
+//! Role: Reads and writes `[pools.<name>]` tables; plans what `apply` creates, updates, or can
+//! only report as drift.
+//! Invariants: Header settings (size, index, retention, chunking, flags) are fixed at create
+//! time, so `apply` never rewrites them on an existing pool; mismatches are drift.
+//! Invariants: Tags and required fields live in the template sidecar and are updated in place.
+//! Invariants: Keys left out of a table are unmanaged: neither checked nor changed.
+//! Invariants: `describe` output applied unchanged plans no changes and no drift.
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use plasmite::api::{Error, ErrorKind, PoolInfo, PoolOptions};
+
+use super::{parse_frame_limit, parse_size};
+use crate::pool_template::TemplateDefaults;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum DescribeFormat {
+    #[default]
+    Toml,
+    Json,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolManifest {
+    #[serde(default)]
+    pub pools: BTreeMap<String, PoolSpec>,
+}
+
+/// One `[pools.<name>]` table; sizes are strings in `--size` syntax (`64M`).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_capacity: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_messages: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_frame: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid_timestamps: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bloom_filter: Option<bool>,
+    /// Tags `feed` adds to every message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// `.data` keys every fed record must carry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+}
+
+/// A header setting that differs from the spec and cannot be changed in place.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drift {
+    pub field: &'static str,
+    pub desired: Value,
+    pub actual: Value,
+}
+
+/// What `apply` does to one existing pool.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolPlan {
+    /// Sidecar defaults to store, when tags or required fields change.
+    pub defaults: Option<TemplateDefaults>,
+    pub changes: Vec<&'static str>,
+    pub drift: Vec<Drift>,
+}
+
+impl PoolManifest {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            let kind = if err.kind() == std::io::ErrorKind::NotFound {
+                ErrorKind::NotFound
+            } else {
+                ErrorKind::Io
+            };
+            Error::new(kind)
+                .with_message("failed to read pool manifest")
+                .with_path(path)
+                .with_source(err)
+        })?;
+        toml::from_str(&text).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid pool manifest: {}", err.message()))
+                .with_path(path)
+                .with_hint("Each [pools.<name>] table may set size, index_capacity, max_messages, max_frame, max_message, hybrid_timestamps, bloom_filter, tags, and required.")
+        })
+    }
+
+    pub fn render(&self) -> Result<String, Error> {
+        toml::to_string(self).map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to encode pool manifest")
+                .with_source(err)
+        })
+    }
+}
+
+impl PoolSpec {
+    /// Everything `apply` can check about an existing pool.
+    pub fn describe(info: &PoolInfo, defaults: Option<&TemplateDefaults>) -> Self {
+        Self {
+            size: Some(size_label(info.file_size)),
+            index_capacity: Some(info.index_capacity),
+            max_messages: info.max_messages,
+            max_frame: info.max_frame_bytes.map(|bytes| size_label(bytes.into())),
+            max_message: info.max_message_bytes.map(|bytes| size_label(bytes.into())),
+            hybrid_timestamps: Some(info.hybrid_timestamps),
+            bloom_filter: Some(info.bloom_filter),
+            tags: defaults.map(|defaults| defaults.tags.clone()),
+            required: defaults.map(|defaults| defaults.required.clone()),
+        }
+    }
+
+    /// Create options for a pool this spec describes; unset keys take `pool create` defaults.
+    pub fn options(&self, default_size: u64) -> Result<PoolOptions, Error> {
+        let size = self
+            .size
+            .as_deref()
+            .map(parse_size)
+            .transpose()?
+            .unwrap_or(default_size);
+        let mut options = PoolOptions::new(size)
+            .with_hybrid_timestamps(self.hybrid_timestamps.unwrap_or(false))
+            .with_bloom_filter(self.bloom_filter.unwrap_or(false));
+        if let Some(max_messages) = self.max_messages {
+            options = options.with_max_messages(max_messages);
+        }
+        if let Some(max_frame) = self.max_frame.as_deref() {
+            options = options.with_max_frame_bytes(parse_frame_limit("max_frame", max_frame)?);
+        }
+        if let Some(max_message) = self.max_message.as_deref() {
+            if self.max_frame.is_none() {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("max_message requires max_frame")
+                    .with_hint("Set max_frame so messages are chunked, or drop max_message."));
+            }
+            options =
+                options.with_max_message_bytes(parse_frame_limit("max_message", max_message)?);
+        }
+        if let Some(index_capacity) = self.index_capacity {
+            if index_capacity as u64 * 16 > size / 2 {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message("index capacity is too large for pool size")
+                    .with_hint("Reduce index_capacity or increase size (index region must be <= 50% of the pool file)."));
+            }
+            options = options.with_index_capacity(index_capacity);
+        }
+        Ok(options)
+    }
+
+    /// Sidecar defaults for a new pool, when the spec sets tags or required fields.
+    pub fn new_defaults(&self, name: &str) -> Option<TemplateDefaults> {
+        if self.tags.is_none() && self.required.is_none() {
+            return None;
+        }
+        Some(TemplateDefaults {
+            template: name.to_string(),
+            tags: self.tags.clone().unwrap_or_default(),
+            required: self.required.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Compare against an existing pool; `defaults` is its current sidecar, if any.
+    pub fn plan(
+        &self,
+        name: &str,
+        info: &PoolInfo,
+        defaults: Option<&TemplateDefaults>,
+    ) -> Result<PoolPlan, Error> {
+        let mut plan = PoolPlan::default();
+        let mut check = |field, desired: Option<Value>, actual: Value| {
+            if let Some(desired) = desired {
+                if desired != actual {
+                    plan.drift.push(Drift {
+                        field,
+                        desired,
+                        actual,
+                    });
+                }
+            }
+        };
+        let size = self.size.as_deref().map(parse_size).transpose()?;
+        check("size", size.map(|size| json!(size)), json!(info.file_size));
+        check(
+            "index_capacity",
+            self.index_capacity.map(|capacity| json!(capacity)),
+            json!(info.index_capacity),
+        );
+        check(
+            "max_messages",
+            self.max_messages.map(|max| json!(max)),
+            json!(info.max_messages),
+        );
+        let max_frame = self
+            .max_frame
+            .as_deref()
+            .map(|value| parse_frame_limit("max_frame", value))
+            .transpose()?;
+        check(
+            "max_frame",
+            max_frame.map(|bytes| json!(bytes)),
+            json!(info.max_frame_bytes),
+        );
+        let max_message = self
+            .max_message
+            .as_deref()
+            .map(|value| parse_frame_limit("max_message", value))
+            .transpose()?;
+        check(
+            "max_message",
+            max_message.map(|bytes| json!(bytes)),
+            json!(info.max_message_bytes),
+        );
+        check(
+            "hybrid_timestamps",
+            self.hybrid_timestamps.map(|enabled| json!(enabled)),
+            json!(info.hybrid_timestamps),
+        );
+        check(
+            "bloom_filter",
+            self.bloom_filter.map(|enabled| json!(enabled)),
+            json!(info.bloom_filter),
+        );
+
+        let mut next = defaults.cloned().unwrap_or_else(|| TemplateDefaults {
+            template: name.to_string(),
+            ..TemplateDefaults::default()
+        });
+        if let Some(tags) = &self.tags {
+            if *tags != next.tags {
+                next.tags = tags.clone();
+                plan.changes.push("tags");
+            }
+        }
+        if let Some(required) = &self.required {
+            if *required != next.required {
+                next.required = required.clone();
+                plan.changes.push("required");
+            }
+        }
+        if !plan.changes.is_empty() {
+            plan.defaults = Some(next);
+        }
+        Ok(plan)
+    }
+}
+
+/// `64M` when `bytes` is a whole number of K/M/G, else the plain byte count.
+fn size_label(bytes: u64) -> String {
+    for (unit, suffix) in [(1 << 30, "G"), (1 << 20, "M"), (1 << 10, "K")] {
+        if bytes >= unit && bytes.is_multiple_of(unit) {
+            return format!("{}{suffix}", bytes / unit);
+        }
+    }
+    bytes.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PoolManifest, PoolSpec};
+    use crate::pool_template::TemplateDefaults;
+    use plasmite::api::Pool;
+
+    #[test]
+    fn described_pools_apply_cleanly_and_header_changes_are_drift() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("events.plasmite");
+        let spec: PoolSpec = toml::from_str(
+            "size = \"2M\"\nmax_messages = 500\nbloom_filter = true\ntags = [\"svc\"]\n",
+        )
+        .expect("spec");
+        let pool = Pool::create(&path, spec.options(1 << 20).expect("options")).expect("create");
+        let info = pool.info().expect("info");
+        let defaults = spec.new_defaults("events").expect("defaults");
+
+        let described = PoolSpec::describe(&info, Some(&defaults));
+        let mut manifest = PoolManifest::default();
+        manifest.pools.insert("events".to_string(), described);
+        let text = manifest.render().expect("render");
+        assert!(text.contains("[pools.events]"), "{text}");
+        assert!(text.contains("size = \"2M\""), "{text}");
+        let reparsed: PoolManifest = toml::from_str(&text).expect("reparse");
+        let plan = reparsed.pools["events"]
+            .plan("events", &info, Some(&defaults))
+            .expect("plan");
+        assert!(plan.changes.is_empty() && plan.drift.is_empty(), "{plan:?}");
+
+        let changed: PoolSpec =
+            toml::from_str("size = \"4M\"\nrequired = [\"level\"]\n").expect("changed");
+        let plan = changed
+            .plan("events", &info, Some(&defaults))
+            .expect("plan");
+        assert_eq!(plan.changes, vec!["required"]);
+        assert_eq!(plan.drift.len(), 1);
+        assert_eq!(plan.drift[0].field, "size");
+        assert_eq!(
+            plan.defaults,
+            Some(TemplateDefaults {
+                template: "events".to_string(),
+                tags: vec!["svc".to_string()],
+                required: vec!["level".to_string()],
+            })
+        );
+    }
+}
@@ -1,14 +1,23 @@
 //! Purpose: Shared local pool-directory and pool-name path resolution helpers.
-//! Exports: `default_pool_dir` and `resolve_named_pool_path`.
-//! Role: Keep CLI and API-client path semantics aligned from one source.
+//! Exports: `default_pool_dir`, `resolve_named_pool_path`, `is_path_ref`, `pool_name_for_path`,
+//! and `find_pool_files`.
+//! Role: Keep CLI, API-client, and ABI path semantics aligned from one source.
 //! Invariants: Default pool directory remains `~/.plasmite/pools`.
-//! Invariants: Named pool refs must not contain path separators.
+//! Invariants: Names may be hierarchical (`team/service/events`), mapping to subdirectories of
+//! the pool dir. Every segment is non-empty, is not `.`/`..`, does not start with `.`, and has no
+//! `\` or NUL, so a name never escapes the pool dir or reaches `.trash`.
+//! Invariants: A ref containing a separator is a path, not a name, when it is absolute, starts
+//! with `.` or `~`, contains `\`, or ends in `.plasmite`.
 
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+
+const POOL_EXTENSION: &str = "plasmite";
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub(crate) enum PoolNameResolveError {
-    ContainsPathSeparator,
+    EmptySegment,
+    DotSegment,
+    InvalidCharacter,
 }
 
 pub(crate) fn default_pool_dir() -> PathBuf {
@@ -16,15 +25,151 @@ pub(crate) fn default_pool_dir() -> PathBuf {
     PathBuf::from(home).join(".plasmite").join("pools")
 }
 
+/// Whether a local ref names a file path rather than a (possibly hierarchical) pool name.
+pub(crate) fn is_path_ref(input: &str) -> bool {
+    if !input.chars().any(std::path::is_separator) {
+        return false;
+    }
+    Path::new(input).is_absolute()
+        || input.starts_with('.')
+        || input.starts_with('~')
+        || input.contains('\\')
+        || input.ends_with(".plasmite")
+}
+
 pub(crate) fn resolve_named_pool_path(
     name: &str,
     pool_dir: &Path,
 ) -> Result<PathBuf, PoolNameResolveError> {
-    if name.chars().any(std::path::is_separator) {
-        return Err(PoolNameResolveError::ContainsPathSeparator);
+    let mut path = pool_dir.to_path_buf();
+    for segment in name.split('/') {
+        validate_segment(segment)?;
+        path.push(segment);
+    }
+    if !name.ends_with(".plasmite") {
+        path.as_mut_os_string().push(".plasmite");
+    }
+    Ok(path)
+}
+
+fn validate_segment(segment: &str) -> Result<(), PoolNameResolveError> {
+    if segment.is_empty() {
+        return Err(PoolNameResolveError::EmptySegment);
+    }
+    if segment.starts_with('.') {
+        return Err(PoolNameResolveError::DotSegment);
     }
-    if name.ends_with(".plasmite") {
-        return Ok(pool_dir.join(name));
+    if segment.contains(['\\', '\0']) || segment.chars().any(std::path::is_separator) {
+        return Err(PoolNameResolveError::InvalidCharacter);
+    }
+    Ok(())
+}
+
+/// Name (`team/service/events`) of a pool file under `pool_dir`; `None` outside it.
+pub(crate) fn pool_name_for_path(pool_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(pool_dir).ok()?;
+    if relative.extension()? != POOL_EXTENSION {
+        return None;
+    }
+    let segments = relative
+        .with_extension("")
+        .components()
+        .map(|component| match component {
+            Component::Normal(segment) => segment.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(segments.join("/"))
+}
+
+/// Every `*.plasmite` file under `pool_dir`, sorted. Dot-directories (`.trash`) and symlinked
+/// directories are not descended.
+pub(crate) fn find_pool_files(pool_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut pools = Vec::new();
+    let mut pending = vec![pool_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if !hidden && path.extension().is_none_or(|ext| ext != "who") {
+                    pending.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == POOL_EXTENSION) {
+                pools.push(path);
+            }
+        }
+    }
+    pools.sort();
+    Ok(pools)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PoolNameResolveError, find_pool_files, is_path_ref, pool_name_for_path,
+        resolve_named_pool_path,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn hierarchical_names_map_to_subdirectories() {
+        let dir = Path::new("/pools");
+        assert_eq!(
+            resolve_named_pool_path("team/service/events", dir).expect("nested"),
+            Path::new("/pools/team/service/events.plasmite")
+        );
+        assert_eq!(
+            resolve_named_pool_path("v1.2", dir).expect("dotted"),
+            Path::new("/pools/v1.2.plasmite")
+        );
+        for (name, err) in [
+            ("team//events", PoolNameResolveError::EmptySegment),
+            ("team/", PoolNameResolveError::EmptySegment),
+            ("team/../etc", PoolNameResolveError::DotSegment),
+            ("team/.trash/x", PoolNameResolveError::DotSegment),
+            ("team\\x", PoolNameResolveError::InvalidCharacter),
+        ] {
+            assert_eq!(resolve_named_pool_path(name, dir), Err(err), "{name}");
+        }
+        assert_eq!(
+            pool_name_for_path(dir, Path::new("/pools/team/service/events.plasmite")).as_deref(),
+            Some("team/service/events")
+        );
+        assert_eq!(pool_name_for_path(dir, Path::new("/tmp/x.plasmite")), None);
+    }
+
+    #[test]
+    fn path_refs_are_told_apart_from_names() {
+        assert!(!is_path_ref("chat"));
+        assert!(!is_path_ref("team/events"));
+        assert!(is_path_ref("/tmp/chat.plasmite"));
+        assert!(is_path_ref("./chat"));
+        assert!(is_path_ref("../pools/chat"));
+        assert!(is_path_ref("tmp/chat.plasmite"));
+    }
+
+    #[test]
+    fn pool_files_are_found_recursively_outside_hidden_dirs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let nested = dir.path().join("team").join("service");
+        std::fs::create_dir_all(&nested).expect("nested");
+        std::fs::create_dir_all(dir.path().join(".trash")).expect("trash");
+        for path in [
+            dir.path().join("chat.plasmite"),
+            nested.join("events.plasmite"),
+            dir.path().join(".trash").join("old.plasmite"),
+            dir.path().join("notes.txt"),
+        ] {
+            std::fs::write(path, b"").expect("write");
+        }
+        let names = find_pool_files(dir.path())
+            .expect("find")
+            .iter()
+            .filter_map(|path| pool_name_for_path(dir.path(), path))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["chat", "team/service/events"]);
     }
-    Ok(pool_dir.join(format!("{name}.plasmite")))
 }
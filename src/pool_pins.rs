@@ -0,0 +1,238 @@
+//! Purpose: Pin important messages so ring churn and retention never lose them.
+//! Exports: `Pin`, `PinSet`.
+//! Role: Backing store for `plasmite pin`, the `pins` section of `pool info`, and `fetch`.
+//! Invariants: Pinning copies the message forward into the `<pool>.pins.json` sidecar, so a
+//! pinned seq stays fetchable after the ring overwrites (or `max_messages` drops) its frame.
+//! Invariants: Pins are kept sorted by seq; the sidecar is rewritten atomically (unique temp
+//! file + rename) and removed once the last pin is gone.
+//! Invariants: Changes go through `PinSet::update`, which holds the pool append lock across
+//! load and save so concurrent `pin` runs never drop each other's pins.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use plasmite::api::{Error, ErrorKind, Pool, pins_sidecar_path};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Pin {
+    pub seq: u64,
+    /// RFC 3339 time the pin was recorded.
+    pub pinned_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Full message envelope (`seq`, `time`, `meta`, `data`) as of pinning.
+    pub message: Value,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct PinSet {
+    pins: Vec<Pin>,
+}
+
+impl PinSet {
+    /// Pins recorded for `pool_path`; empty when the pool has no sidecar.
+    pub fn load(pool_path: &Path) -> Result<Self, Error> {
+        let path = pins_sidecar_path(pool_path);
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to read pins sidecar")
+                    .with_path(&path)
+                    .with_source(err));
+            }
+        };
+        serde_json::from_str(&raw).map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("invalid pins sidecar")
+                .with_path(&path)
+                .with_hint(
+                    "Fix or delete the file; pinned copies in it cannot be recovered otherwise.",
+                )
+                .with_source(err)
+        })
+    }
+
+    /// Load the pins for `pool`, apply `change`, and save the result, all under the pool's
+    /// append lock. Nothing is written when `change` fails.
+    pub fn update<T>(
+        pool: &Pool,
+        pool_path: &Path,
+        change: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let _lock = pool.append_lock()?;
+        let mut pins = Self::load(pool_path)?;
+        let out = change(&mut pins)?;
+        pins.save(pool_path)?;
+        Ok(out)
+    }
+
+    fn save(&self, pool_path: &Path) -> Result<(), Error> {
+        let path = pins_sidecar_path(pool_path);
+        let io_error = |err: std::io::Error| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to write pins sidecar")
+                .with_path(&path)
+                .with_source(err)
+        };
+        if self.pins.is_empty() {
+            return match fs::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(io_error(err)),
+                _ => Ok(()),
+            };
+        }
+        let body = serde_json::to_string_pretty(self).map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to encode pins sidecar")
+                .with_source(err)
+        })?;
+        let tmp = temp_path(&path);
+        let written = fs::write(&tmp, format!("{body}\n")).and_then(|()| fs::rename(&tmp, &path));
+        if written.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        written.map_err(io_error)
+    }
+
+    /// Record (or refresh) a pin; returns `false` when `seq` was already pinned.
+    pub fn pin(&mut self, pin: Pin) -> bool {
+        match self
+            .pins
+            .binary_search_by_key(&pin.seq, |existing| existing.seq)
+        {
+            Ok(index) => {
+                self.pins[index] = pin;
+                false
+            }
+            Err(index) => {
+                self.pins.insert(index, pin);
+                true
+            }
+        }
+    }
+
+    /// Drop a pin; returns whether `seq` was pinned.
+    pub fn unpin(&mut self, seq: u64) -> bool {
+        match self.pins.binary_search_by_key(&seq, |pin| pin.seq) {
+            Ok(index) => {
+                self.pins.remove(index);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn get(&self, seq: u64) -> Option<&Pin> {
+        self.pins
+            .binary_search_by_key(&seq, |pin| pin.seq)
+            .ok()
+            .map(|index| &self.pins[index])
+    }
+
+    pub fn pins(&self) -> &[Pin] {
+        &self.pins
+    }
+}
+
+/// Per-writer temp name next to the sidecar, so two writers never share a temp file.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut bytes = [0u8; 8];
+    if getrandom::fill(&mut bytes).is_err() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default();
+        bytes = nanos.to_be_bytes();
+    }
+    path.with_extension(format!(
+        "json.{}.{:016x}.tmp",
+        std::process::id(),
+        u64::from_be_bytes(bytes)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pin, PinSet, temp_path};
+    use plasmite::api::{Error, ErrorKind, Pool, PoolOptions};
+    use serde_json::json;
+
+    fn pin(seq: u64) -> Pin {
+        Pin {
+            seq,
+            pinned_at: "2026-01-01T00:00:00Z".to_string(),
+            note: None,
+            message: json!({ "seq": seq, "data": { "n": seq } }),
+        }
+    }
+
+    #[test]
+    fn pins_stay_sorted_and_round_trip_through_the_sidecar() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pool_path = dir.path().join("demo.plasmite");
+        assert!(PinSet::load(&pool_path).expect("empty").pins().is_empty());
+
+        let mut pins = PinSet::default();
+        assert!(pins.pin(pin(9)));
+        assert!(pins.pin(pin(3)));
+        assert!(!pins.pin(pin(9)));
+        pins.save(&pool_path).expect("save");
+
+        let loaded = PinSet::load(&pool_path).expect("load");
+        let seqs = loaded.pins().iter().map(|pin| pin.seq).collect::<Vec<_>>();
+        assert_eq!(seqs, [3, 9]);
+        assert_eq!(loaded.get(3).expect("pinned").message["data"]["n"], 3);
+
+        let mut loaded = loaded;
+        assert!(loaded.unpin(3) && loaded.unpin(9) && !loaded.unpin(9));
+        loaded.save(&pool_path).expect("save empty");
+        assert!(!plasmite::api::pins_sidecar_path(&pool_path).exists());
+    }
+
+    #[test]
+    fn updates_hold_the_append_lock_and_use_unique_temp_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pool_path = dir.path().join("demo.plasmite");
+        let pool = Pool::create(&pool_path, PoolOptions::new(1024 * 1024)).expect("create");
+        let sidecar = plasmite::api::pins_sidecar_path(&pool_path);
+        assert_ne!(temp_path(&sidecar), temp_path(&sidecar));
+
+        let writers = (0..8u64)
+            .map(|seq| {
+                let pool_path = pool_path.clone();
+                std::thread::spawn(move || {
+                    let pool = Pool::open(&pool_path).expect("open");
+                    PinSet::update(&pool, &pool_path, |pins| Ok(pins.pin(pin(seq))))
+                        .expect("update")
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            assert!(writer.join().expect("join"));
+        }
+        let seqs = PinSet::load(&pool_path)
+            .expect("load")
+            .pins()
+            .iter()
+            .map(|pin| pin.seq)
+            .collect::<Vec<_>>();
+        assert_eq!(seqs, (0..8).collect::<Vec<_>>());
+
+        let failed = PinSet::update(&pool, &pool_path, |pins| {
+            pins.unpin(0);
+            Err::<(), _>(Error::new(ErrorKind::Usage))
+        });
+        assert!(failed.is_err());
+        assert_eq!(PinSet::load(&pool_path).expect("load").pins().len(), 8);
+        let leftovers = std::fs::read_dir(dir.path())
+            .expect("read dir")
+            .filter(|entry| {
+                let name = entry.as_ref().expect("entry").file_name();
+                name.to_string_lossy().ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+}
@@ -0,0 +1,217 @@
+//! Purpose: Seal a pool's frames into a hash manifest and verify the pool against it later.
+//! Exports: `SealManifest`, `Verification`, `seal`, `verify`.
+//! Role: Backs `plasmite pool seal` and `plasmite pool verify`; pool resolution, manifest
+//! files, and output stay in dispatch.
+//! Invariants: A leaf is SHA-256 over 0x00, then seq, timestamp, and flags (little-endian),
+//! then the raw payload. Inner nodes hash 0x01 and two children; an odd node moves up as-is.
+//! Invariants: Sealed frames the ring has since overwritten count as evicted, not tampered.
+//! Any retained frame whose leaf changed, or a sealed seq the pool no longer reaches, fails.
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use plasmite::api::{Error, ErrorKind, FrameRef, Pool, hex_decode, hex_encode};
+
+pub const MANIFEST_VERSION: u32 = 1;
+const HASH: &str = "sha256-merkle";
+/// Upper bound on mismatched seqs listed in a verification; the count is always exact.
+const MAX_REPORTED_MISMATCHES: usize = 32;
+
+type Hash = [u8; 32];
+
+/// What `pool seal` writes: one leaf hash per frame from `oldest_seq` to `newest_seq`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SealManifest {
+    pub version: u32,
+    pub pool: String,
+    pub hash: String,
+    pub sealed_at: String,
+    pub oldest_seq: u64,
+    pub newest_seq: u64,
+    pub root: String,
+    pub leaves: Vec<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Verification {
+    /// The manifest's leaves still hash to its root (the manifest itself was not edited).
+    pub root_ok: bool,
+    pub checked: u64,
+    pub evicted: u64,
+    pub missing: u64,
+    pub mismatched: u64,
+    /// The first mismatched seqs, oldest first.
+    pub mismatched_seqs: Vec<u64>,
+}
+
+impl Verification {
+    pub fn intact(&self) -> bool {
+        self.root_ok && self.missing == 0 && self.mismatched == 0
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "intact": self.intact(),
+            "root_ok": self.root_ok,
+            "checked": self.checked,
+            "evicted": self.evicted,
+            "missing": self.missing,
+            "mismatched": self.mismatched,
+            "mismatched_seqs": self.mismatched_seqs,
+        })
+    }
+}
+
+/// Hash every retained frame of `pool`; fails on a pool with no messages.
+pub fn seal(pool: &Pool, name: &str, sealed_at: String) -> Result<SealManifest, Error> {
+    let bounds = pool.bounds()?;
+    let frames = match (bounds.oldest_seq, bounds.newest_seq) {
+        (Some(oldest), Some(newest)) => pool.get_range(oldest, newest)?,
+        _ => Vec::new(),
+    };
+    let (Some(first), Some(last)) = (frames.first(), frames.last()) else {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("pool has no messages to seal")
+            .with_hint("Seal the pool after its first append."));
+    };
+    let (oldest_seq, newest_seq) = (first.seq, last.seq);
+    let leaves = frames.iter().map(leaf_hash).collect::<Vec<_>>();
+    Ok(SealManifest {
+        version: MANIFEST_VERSION,
+        pool: name.to_string(),
+        hash: HASH.to_string(),
+        sealed_at,
+        oldest_seq,
+        newest_seq,
+        root: hex_encode(&merkle_root(&leaves)),
+        leaves: leaves.iter().map(hex).collect(),
+    })
+}
+
+/// Compare the frames of `pool` that `manifest` sealed against their leaf hashes.
+pub fn verify(pool: &Pool, manifest: &SealManifest) -> Result<Verification, Error> {
+    let leaves = manifest_leaves(manifest)?;
+    let mut verification = Verification {
+        root_ok: hex_encode(&merkle_root(&leaves)) == manifest.root,
+        ..Verification::default()
+    };
+    let frames = pool.get_range(manifest.oldest_seq, manifest.newest_seq)?;
+    for frame in &frames {
+        verification.checked += 1;
+        if leaf_hash(frame) != leaves[(frame.seq - manifest.oldest_seq) as usize] {
+            verification.mismatched += 1;
+            if verification.mismatched_seqs.len() < MAX_REPORTED_MISMATCHES {
+                verification.mismatched_seqs.push(frame.seq);
+            }
+        }
+    }
+    // The oldest retained seq only moves forward, so reading it after the scan classifies
+    // every unread seq below it as evicted; unread seqs at or above it are gone for good.
+    let total = leaves.len() as u64;
+    let unread_below = frames
+        .first()
+        .map_or(total, |frame| frame.seq - manifest.oldest_seq);
+    let evictable = pool
+        .bounds()?
+        .oldest_seq
+        .map_or(0, |oldest| oldest.saturating_sub(manifest.oldest_seq));
+    verification.evicted = unread_below.min(evictable);
+    verification.missing = total - verification.checked - verification.evicted;
+    Ok(verification)
+}
+
+fn manifest_leaves(manifest: &SealManifest) -> Result<Vec<Hash>, Error> {
+    let invalid = |detail: &str| {
+        Error::new(ErrorKind::Usage)
+            .with_message(format!("invalid seal manifest: {detail}"))
+            .with_hint("Pass a manifest written by `plasmite pool seal`.")
+    };
+    if manifest.version != MANIFEST_VERSION || manifest.hash != HASH {
+        return Err(invalid(&format!(
+            "unsupported version {} / hash {}",
+            manifest.version, manifest.hash
+        )));
+    }
+    let expected = manifest
+        .newest_seq
+        .checked_sub(manifest.oldest_seq)
+        .map(|span| span + 1);
+    if manifest.oldest_seq == 0 || expected != Some(manifest.leaves.len() as u64) {
+        return Err(invalid("leaf count does not match the sealed seq range"));
+    }
+    manifest
+        .leaves
+        .iter()
+        .map(|leaf| hex_decode::<32>(leaf).ok_or_else(|| invalid("leaves must be 64 hex digits")))
+        .collect()
+}
+
+fn leaf_hash(frame: &FrameRef<'_>) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(frame.seq.to_le_bytes());
+    hasher.update(frame.timestamp_ns.to_le_bytes());
+    hasher.update(frame.flags.to_le_bytes());
+    hasher.update(frame.payload);
+    hasher.finalize().into()
+}
+
+fn merkle_root(leaves: &[Hash]) -> Hash {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hasher = Sha256::new();
+                    hasher.update([1u8]);
+                    hasher.update(left);
+                    hasher.update(right);
+                    hasher.finalize().into()
+                }
+                [odd] => *odd,
+                _ => unreachable!("chunks(2) yields one or two hashes"),
+            })
+            .collect();
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{seal, verify};
+    use plasmite::api::{Durability, Pool, PoolApiExt, PoolOptions};
+    use serde_json::json;
+
+    #[test]
+    fn verify_flags_changed_frames_and_edited_manifests() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("ledger.plasmite");
+        let mut pool = Pool::create(&path, PoolOptions::new(1 << 20)).expect("create");
+        for n in 0..5 {
+            pool.append_json_now(&json!({ "n": n }), &[], Durability::Fast)
+                .expect("append");
+        }
+        let mut manifest = seal(&pool, "ledger", "now".to_string()).expect("seal");
+        assert_eq!((manifest.oldest_seq, manifest.newest_seq), (1, 5));
+
+        pool.append_json_now(&json!({ "n": 5 }), &[], Durability::Fast)
+            .expect("append");
+        let clean = verify(&pool, &manifest).expect("verify");
+        assert!(clean.intact(), "{clean:?}");
+        assert_eq!(clean.checked, 5, "frames after the seal are not covered");
+
+        // A manifest whose leaf was swapped no longer matches its own root or the pool.
+        manifest.leaves[2] = "00".repeat(32);
+        let tampered = verify(&pool, &manifest).expect("verify");
+        assert!(!tampered.root_ok);
+        assert_eq!(tampered.mismatched_seqs, vec![3]);
+
+        manifest.leaves.pop();
+        assert!(
+            verify(&pool, &manifest).is_err(),
+            "leaf count must match the range"
+        );
+    }
+}
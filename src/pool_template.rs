@@ -0,0 +1,229 @@
+//! Purpose: Named `pool create --template` bundles (size, index, retention, tags, required fields).
+//! Exports: `PoolTemplate`, `TemplateDefaults`, `resolve_template`.
+//! Role: Built-in `chat`/`metrics`/`worklog` plus user templates from `~/.plasmite/templates.toml`.
+//! Invariants: User templates override built-ins of the same name; explicit CLI flags override
+//! template values.
+//! Invariants: Tags and required fields persist in the pool's template sidecar so local `feed`
+//! applies them; pools without a sidecar behave exactly as before.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use plasmite::api::{Error, ErrorKind, template_sidecar_path};
+use serde::{Deserialize, Serialize};
+
+use super::parse_size;
+
+/// Env override for the user template file.
+const TEMPLATES_ENV: &str = "PLASMITE_TEMPLATES";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PoolTemplate {
+    pub name: String,
+    pub size: Option<u64>,
+    pub index_capacity: Option<u32>,
+    pub max_messages: Option<u64>,
+    pub dedupe_window: Option<u32>,
+    pub hybrid_timestamps: bool,
+    pub tags: Vec<String>,
+    /// `.data` keys every fed record must carry.
+    pub required: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateSpec {
+    size: Option<String>,
+    index_capacity: Option<u32>,
+    max_messages: Option<u64>,
+    dedupe_window: Option<u32>,
+    #[serde(default)]
+    hybrid_timestamps: bool,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    required: Vec<String>,
+}
+
+/// What a templated pool remembers for `feed`, stored in its sidecar.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateDefaults {
+    pub template: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub required: Vec<String>,
+}
+
+impl PoolTemplate {
+    pub fn defaults(&self) -> TemplateDefaults {
+        TemplateDefaults {
+            template: self.name.clone(),
+            tags: self.tags.clone(),
+            required: self.required.clone(),
+        }
+    }
+}
+
+impl TemplateDefaults {
+    /// Read the sidecar next to `pool_path`; `None` when the pool was not created from a template.
+    pub fn load(pool_path: &Path) -> Result<Option<Self>, Error> {
+        let path = template_sidecar_path(pool_path);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to read pool template sidecar")
+                    .with_path(&path)
+                    .with_source(err));
+            }
+        };
+        serde_json::from_str(&text).map(Some).map_err(|err| {
+            Error::new(ErrorKind::Corrupt)
+                .with_message("invalid pool template sidecar")
+                .with_path(&path)
+                .with_source(err)
+        })
+    }
+
+    pub fn store(&self, pool_path: &Path) -> Result<(), Error> {
+        let path = template_sidecar_path(pool_path);
+        let text = serde_json::to_string_pretty(self).map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to encode pool template sidecar")
+                .with_source(err)
+        })?;
+        std::fs::write(&path, text).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to write pool template sidecar")
+                .with_path(&path)
+                .with_source(err)
+        })
+    }
+}
+
+fn templates_config_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(TEMPLATES_ENV) {
+        return PathBuf::from(path);
+    }
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".plasmite").join("templates.toml")
+}
+
+/// Look up `name` among user templates, then built-ins.
+pub fn resolve_template(name: &str) -> Result<PoolTemplate, Error> {
+    let path = templates_config_path();
+    let mut user = load_user_templates(&path)?;
+    if let Some(template) = user.remove(name) {
+        return Ok(template);
+    }
+    if let Some(template) = builtin_templates().into_iter().find(|t| t.name == name) {
+        return Ok(template);
+    }
+    let mut known = builtin_templates()
+        .into_iter()
+        .map(|template| template.name)
+        .chain(user.into_keys())
+        .collect::<Vec<_>>();
+    known.sort();
+    known.dedup();
+    Err(Error::new(ErrorKind::NotFound)
+        .with_message(format!("unknown pool template '{name}'"))
+        .with_hint(format!(
+            "Available templates: {}. Define more in {}.",
+            known.join(", "),
+            path.display()
+        )))
+}
+
+fn load_user_templates(path: &Path) -> Result<BTreeMap<String, PoolTemplate>, Error> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => {
+            return Err(Error::new(ErrorKind::Io)
+                .with_message("failed to read pool templates")
+                .with_path(path)
+                .with_source(err));
+        }
+    };
+    let specs: BTreeMap<String, TemplateSpec> = toml::from_str(&text).map_err(|err| {
+        Error::new(ErrorKind::Usage)
+            .with_message(format!("invalid pool templates: {}", err.message()))
+            .with_path(path)
+            .with_hint("Each [name] table may set size, index_capacity, max_messages, dedupe_window, hybrid_timestamps, tags, and required.")
+    })?;
+    specs
+        .into_iter()
+        .map(|(name, spec)| {
+            let template = PoolTemplate {
+                name: name.clone(),
+                size: spec.size.as_deref().map(parse_size).transpose()?,
+                index_capacity: spec.index_capacity,
+                max_messages: spec.max_messages,
+                dedupe_window: spec.dedupe_window,
+                hybrid_timestamps: spec.hybrid_timestamps,
+                tags: spec.tags,
+                required: spec.required,
+            };
+            Ok((name, template))
+        })
+        .collect()
+}
+
+fn builtin_templates() -> Vec<PoolTemplate> {
+    vec![
+        PoolTemplate {
+            name: "chat".to_string(),
+            size: Some(4 * 1024 * 1024),
+            tags: vec!["chat".to_string()],
+            required: vec!["from".to_string(), "msg".to_string()],
+            ..PoolTemplate::default()
+        },
+        PoolTemplate {
+            name: "metrics".to_string(),
+            size: Some(64 * 1024 * 1024),
+            index_capacity: Some(65536),
+            max_messages: Some(1_000_000),
+            hybrid_timestamps: true,
+            tags: vec!["metrics".to_string()],
+            required: vec!["name".to_string(), "value".to_string()],
+            ..PoolTemplate::default()
+        },
+        PoolTemplate {
+            name: "worklog".to_string(),
+            size: Some(16 * 1024 * 1024),
+            dedupe_window: Some(16),
+            tags: vec!["worklog".to_string()],
+            required: vec!["msg".to_string()],
+            ..PoolTemplate::default()
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{builtin_templates, load_user_templates};
+
+    #[test]
+    fn user_templates_parse_and_builtins_fit_their_index() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("templates.toml");
+        std::fs::write(
+            &path,
+            "[audit]\nsize = \"8M\"\nmax_messages = 100\ntags = [\"audit\"]\nrequired = [\"actor\"]\n",
+        )
+        .expect("write");
+        let templates = load_user_templates(&path).expect("load");
+        let audit = &templates["audit"];
+        assert_eq!(audit.size, Some(8 * 1024 * 1024));
+        assert_eq!(audit.max_messages, Some(100));
+        assert_eq!(audit.defaults().required, vec!["actor".to_string()]);
+
+        for template in builtin_templates() {
+            let size = template.size.expect("size");
+            let index = template.index_capacity.unwrap_or(0) as u64 * 16;
+            assert!(index <= size / 2, "{}", template.name);
+        }
+    }
+}
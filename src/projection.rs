@@ -0,0 +1,190 @@
+//! Purpose: Project message envelopes down to selected fields for `--fields` output.
+//! Exports: `Projection`, `ProjectionFormat`.
+//! Role: Shared by `follow` and `fetch`; renders compact JSON, CSV, or TSV rows.
+//! Invariants: Field order follows the `--fields` list for CSV/TSV; JSON keeps the envelope
+//! nesting (`.data.msg` projects to `{"data":{"msg":...}}`).
+//! Invariants: Missing fields render as JSON null or an empty cell, never as an error.
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+
+use plasmite::api::{Error, ErrorKind};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProjectionFormat {
+    #[default]
+    Json,
+    Csv,
+    Tsv,
+}
+
+struct Field {
+    label: String,
+    path: Vec<String>,
+}
+
+pub struct Projection {
+    fields: Vec<Field>,
+    format: ProjectionFormat,
+}
+
+impl Projection {
+    /// Parse a comma-separated list such as `seq,time,.data.msg,.meta.tags`.
+    pub fn parse(spec: &str, format: ProjectionFormat) -> Result<Self, Error> {
+        let mut fields = Vec::new();
+        for token in spec.split(',').map(str::trim) {
+            let path = token
+                .strip_prefix('.')
+                .unwrap_or(token)
+                .split('.')
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            if token.is_empty() || path.iter().any(String::is_empty) {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid --fields entry '{token}'"))
+                    .with_hint(
+                        "Use a comma-separated list like `seq,time,.data.msg,.meta.tags`.",
+                    ));
+            }
+            fields.push(Field {
+                label: token.to_string(),
+                path,
+            });
+        }
+        Ok(Self { fields, format })
+    }
+
+    /// The CSV/TSV header line; `None` for JSON output.
+    pub fn header(&self) -> Option<String> {
+        let labels = self.fields.iter().map(|field| field.label.clone());
+        match self.format {
+            ProjectionFormat::Json => None,
+            ProjectionFormat::Csv => Some(
+                labels
+                    .map(|label| csv_cell(&label))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            ProjectionFormat::Tsv => Some(
+                labels
+                    .map(|label| tsv_cell(&label))
+                    .collect::<Vec<_>>()
+                    .join("\t"),
+            ),
+        }
+    }
+
+    /// One output line for `message`.
+    pub fn render(&self, message: &Value) -> String {
+        match self.format {
+            ProjectionFormat::Json => self.project(message).to_string(),
+            ProjectionFormat::Csv => self
+                .cells(message)
+                .map(|cell| csv_cell(&cell))
+                .collect::<Vec<_>>()
+                .join(","),
+            ProjectionFormat::Tsv => self
+                .cells(message)
+                .map(|cell| tsv_cell(&cell))
+                .collect::<Vec<_>>()
+                .join("\t"),
+        }
+    }
+
+    /// The envelope reduced to the selected fields, keeping their nesting.
+    pub fn project(&self, message: &Value) -> Value {
+        let mut root = Map::new();
+        for field in &self.fields {
+            let value = lookup(message, &field.path).cloned().unwrap_or(Value::Null);
+            let (last, parents) = field.path.split_last().expect("non-empty path");
+            let mut target = &mut root;
+            for key in parents {
+                let entry = target
+                    .entry(key.clone())
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if !entry.is_object() {
+                    *entry = Value::Object(Map::new());
+                }
+                target = entry.as_object_mut().expect("object");
+            }
+            target.insert(last.clone(), value);
+        }
+        Value::Object(root)
+    }
+
+    fn cells<'a>(&'a self, message: &'a Value) -> impl Iterator<Item = String> + 'a {
+        self.fields
+            .iter()
+            .map(|field| match lookup(message, &field.path) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(text)) => text.clone(),
+                Some(other) => other.to_string(),
+            })
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => None,
+    })
+}
+
+/// RFC 4180 quoting: only cells containing a separator, quote, or line break are quoted.
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// TSV has no quoting, so tabs and line breaks are escaped the way `jq @tsv` does.
+fn tsv_cell(cell: &str) -> String {
+    cell.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Projection, ProjectionFormat};
+    use serde_json::json;
+
+    #[test]
+    fn projects_json_csv_and_tsv() {
+        let message = json!({
+            "seq": 7,
+            "time": "2026-01-01T00:00:00Z",
+            "meta": {"tags": ["a", "b"]},
+            "data": {"msg": "hi, \"you\"", "items": [10, 20]}
+        });
+        let json = Projection::parse(
+            "seq,.data.msg,.data.items.1,.data.gone",
+            ProjectionFormat::Json,
+        )
+        .expect("parse");
+        assert_eq!(
+            json.project(&message),
+            json!({"seq": 7, "data": {"msg": "hi, \"you\"", "items": {"1": 20}, "gone": null}})
+        );
+
+        let csv =
+            Projection::parse("seq,.data.msg,.meta.tags", ProjectionFormat::Csv).expect("parse");
+        assert_eq!(csv.header().as_deref(), Some("seq,.data.msg,.meta.tags"));
+        assert_eq!(
+            csv.render(&message),
+            "7,\"hi, \"\"you\"\"\",\"[\"\"a\"\",\"\"b\"\"]\""
+        );
+
+        let tsv = Projection::parse("seq, .data.msg", ProjectionFormat::Tsv).expect("parse");
+        assert_eq!(tsv.render(&message), "7\thi, \"you\"");
+    }
+
+    #[test]
+    fn rejects_empty_entries() {
+        assert!(Projection::parse("seq,,time", ProjectionFormat::Json).is_err());
+        assert!(Projection::parse(".data..msg", ProjectionFormat::Json).is_err());
+    }
+}
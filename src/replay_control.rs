@@ -0,0 +1,217 @@
+//! Purpose: Shape `follow --replay` playback: where it stops, how fast it runs, and which clock
+//! it paces by.
+//! Exports: `ReplayControl`, `ReplayUntil`, `SpeedRamp`, `PaceBy`, `ReplayPacer`.
+//! Role: Parsed from `--until`, `--speed-ramp`, and `--pace-by`; `follow_replay` filters with
+//! `ReplayControl::past_until` and sleeps for `ReplayPacer::delay` between messages.
+//! Invariants: `--until` is inclusive and compares frame seqs or frame timestamps, never
+//! `--pace-by` times.
+//! Invariants: The ramp interpolates linearly across the replayed span of pacing times, so the
+//! first gap plays at the start speed and the last at the end speed.
+//! Invariants: A message without a usable `--pace-by` value keeps the previous pacing time,
+//! and pacing times that go backwards never sleep.
+use std::time::Duration;
+
+use serde_json::Value;
+
+use plasmite::api::{Error, ErrorKind};
+
+/// Inclusive replay boundary from `--until`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReplayUntil {
+    Seq(u64),
+    TimeNs(u64),
+}
+
+/// `--speed-ramp FROM..TO`: playback speed at the start and end of the replay.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpeedRamp {
+    from: f64,
+    to: f64,
+}
+
+impl SpeedRamp {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let invalid = || {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid --speed-ramp value: {input}"))
+                .with_hint("Use FROM..TO with positive speeds, e.g. --speed-ramp 1..8.")
+        };
+        let (from, to) = input.trim().split_once("..").ok_or_else(invalid)?;
+        let from: f64 = from.trim().parse().map_err(|_| invalid())?;
+        let to: f64 = to.trim().parse().map_err(|_| invalid())?;
+        if !(from.is_finite() && to.is_finite() && from > 0.0 && to > 0.0) {
+            return Err(invalid());
+        }
+        Ok(Self { from, to })
+    }
+
+    fn speed_at(self, fraction: f64) -> f64 {
+        self.from + (self.to - self.from) * fraction.clamp(0.0, 1.0)
+    }
+}
+
+/// `--pace-by PATH`: read each message's event time from an envelope path such as `.data.ts`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaceBy {
+    path: Vec<String>,
+}
+
+impl PaceBy {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let path = input
+            .trim()
+            .strip_prefix('.')
+            .map(|rest| rest.split('.').map(str::to_string).collect::<Vec<_>>())
+            .filter(|path| !path.iter().any(String::is_empty))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid --pace-by path: {input}"))
+                    .with_hint("Use a dotted envelope path such as .data.ts or .data.event.time.")
+            })?;
+        Ok(Self { path })
+    }
+
+    /// Event time in nanoseconds: RFC 3339 strings, or numbers of Unix seconds.
+    pub fn time_ns(&self, message: &Value) -> Option<u64> {
+        let value = self
+            .path
+            .iter()
+            .try_fold(message, |value, key| value.get(key))?;
+        match value {
+            Value::String(text) => {
+                time::OffsetDateTime::parse(text, &time::format_description::well_known::Rfc3339)
+                    .ok()
+                    .and_then(|ts| u64::try_from(ts.unix_timestamp_nanos()).ok())
+            }
+            Value::Number(number) => number
+                .as_f64()
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .map(|seconds| (seconds * 1e9) as u64),
+            _ => None,
+        }
+    }
+}
+
+/// `--until`, `--speed-ramp`, and `--pace-by` together; all unset means plain `--replay`.
+#[derive(Clone, Debug, Default)]
+pub struct ReplayControl {
+    pub until: Option<ReplayUntil>,
+    pub ramp: Option<SpeedRamp>,
+    pub pace_by: Option<PaceBy>,
+}
+
+impl ReplayControl {
+    /// Whether a frame lies beyond `--until` and must not be replayed.
+    pub fn past_until(&self, seq: u64, timestamp_ns: u64) -> bool {
+        match self.until {
+            Some(ReplayUntil::Seq(until)) => seq > until,
+            Some(ReplayUntil::TimeNs(until)) => timestamp_ns > until,
+            None => false,
+        }
+    }
+
+    /// Pacing times for `collected` (frame timestamp, message) pairs, in order.
+    pub fn pacing_times(&self, collected: &[(u64, Value)]) -> Vec<u64> {
+        let Some(pace_by) = &self.pace_by else {
+            return collected.iter().map(|(ts, _)| *ts).collect();
+        };
+        let mut previous = None;
+        collected
+            .iter()
+            .map(|(frame_ts, message)| {
+                let ts = pace_by.time_ns(message).or(previous).unwrap_or(*frame_ts);
+                previous = Some(ts);
+                ts
+            })
+            .collect()
+    }
+}
+
+/// Converts gaps between pacing times into sleeps at a fixed or ramped speed.
+pub struct ReplayPacer {
+    speed: f64,
+    ramp: Option<SpeedRamp>,
+    start_ns: u64,
+    span_ns: u64,
+}
+
+impl ReplayPacer {
+    pub fn new(speed: f64, ramp: Option<SpeedRamp>, times: &[u64]) -> Self {
+        let start_ns = times.first().copied().unwrap_or(0);
+        let end_ns = times.last().copied().unwrap_or(start_ns);
+        Self {
+            speed,
+            ramp,
+            start_ns,
+            span_ns: end_ns.saturating_sub(start_ns),
+        }
+    }
+
+    /// Sleep before emitting the message at `ts_ns` when the previous one was at `prev_ns`.
+    pub fn delay(&self, prev_ns: u64, ts_ns: u64) -> Duration {
+        let delta_ns = ts_ns.saturating_sub(prev_ns);
+        let speed = match self.ramp {
+            Some(ramp) if self.span_ns > 0 => {
+                let midpoint = prev_ns.saturating_add(delta_ns / 2);
+                let fraction = midpoint.saturating_sub(self.start_ns) as f64 / self.span_ns as f64;
+                ramp.speed_at(fraction)
+            }
+            Some(ramp) => ramp.from,
+            None => self.speed,
+        };
+        if speed <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos((delta_ns as f64 / speed) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaceBy, ReplayControl, ReplayPacer, ReplayUntil, SpeedRamp};
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn ramp_accelerates_across_the_replayed_span() {
+        let ramp = SpeedRamp::parse("1..8").expect("ramp");
+        let pacer = ReplayPacer::new(1.0, Some(ramp), &[0, 1_000, 9_000, 10_000]);
+        assert_eq!(pacer.delay(0, 0), Duration::ZERO);
+        // Midpoint 500 is 5% through the span: speed 1.35.
+        assert_eq!(pacer.delay(0, 1_000), Duration::from_nanos(740));
+        // Midpoint 9_500 is 95% through: speed 7.65.
+        assert_eq!(pacer.delay(9_000, 10_000), Duration::from_nanos(130));
+        assert_eq!(pacer.delay(10_000, 9_000), Duration::ZERO);
+
+        let fixed = ReplayPacer::new(2.0, None, &[0, 1_000]);
+        assert_eq!(fixed.delay(0, 1_000), Duration::from_nanos(500));
+
+        for bad in ["8", "0..8", "1..-2", "a..b", "1..inf"] {
+            assert!(SpeedRamp::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn pace_by_reads_event_times_and_carries_gaps_forward() {
+        let control = ReplayControl {
+            until: Some(ReplayUntil::Seq(2)),
+            ramp: None,
+            pace_by: Some(PaceBy::parse(".data.ts").expect("path")),
+        };
+        let collected = vec![
+            (100, json!({"data": {"ts": "1970-01-01T00:00:01Z"}})),
+            (200, json!({"data": {"ts": 2.5}})),
+            (300, json!({"data": {}})),
+        ];
+        assert_eq!(
+            control.pacing_times(&collected),
+            vec![1_000_000_000, 2_500_000_000, 2_500_000_000]
+        );
+        assert!(!control.past_until(2, u64::MAX));
+        assert!(control.past_until(3, 0));
+
+        for bad in ["data.ts", ".", ".data..ts", ""] {
+            assert!(PaceBy::parse(bad).is_err(), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Purpose: Infer the shape of `.data` across a pool's messages for `plasmite schema`.
+//! Exports: `SchemaInference`, `FieldReport`.
+//! Role: Pure accumulation over JSON values; reading pools stays in dispatch.
+//! Invariants: Paths are jq-style (`.`, `.user.id`, `.items[]`); array elements share one path.
+//! Invariants: Top values are tracked for scalars only, and at most `MAX_TRACKED_VALUES`
+//! distinct values per field are counted, so memory stays bounded on high-cardinality fields.
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{Map, Value, json};
+
+/// Distinct scalar values counted per field before new values are ignored.
+const MAX_TRACKED_VALUES: usize = 1024;
+/// Top values reported per field.
+const TOP_VALUES: usize = 5;
+
+#[derive(Default)]
+struct FieldStats {
+    /// Path of the enclosing object or array; `None` for the root.
+    parent: Option<String>,
+    /// Member name when the parent is an object; `None` for array elements.
+    member: Option<String>,
+    present: usize,
+    /// Times the value here was an object, i.e. how often its members could appear.
+    objects: usize,
+    types: BTreeMap<&'static str, usize>,
+    values: HashMap<String, usize>,
+    values_truncated: bool,
+}
+
+/// Per-field summary, ordered by path.
+#[derive(Debug, PartialEq)]
+pub struct FieldReport {
+    pub path: String,
+    pub present: usize,
+    /// Messages whose enclosing object lacked this member; always 0 for array elements.
+    pub missing: usize,
+    pub types: BTreeMap<&'static str, usize>,
+    pub nullable: bool,
+    /// `(rendered JSON value, count)`, most frequent first.
+    pub top_values: Vec<(String, usize)>,
+}
+
+#[derive(Default)]
+pub struct SchemaInference {
+    sampled: usize,
+    fields: BTreeMap<String, FieldStats>,
+}
+
+impl SchemaInference {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sampled(&self) -> usize {
+        self.sampled
+    }
+
+    /// Account for one message's `.data` value.
+    pub fn observe(&mut self, data: &Value) {
+        self.sampled += 1;
+        self.visit(".".to_string(), None, None, data);
+    }
+
+    fn visit(&mut self, path: String, parent: Option<&str>, member: Option<&str>, value: &Value) {
+        let stats = self
+            .fields
+            .entry(path.clone())
+            .or_insert_with(|| FieldStats {
+                parent: parent.map(str::to_string),
+                member: member.map(str::to_string),
+                ..FieldStats::default()
+            });
+        stats.present += 1;
+        *stats.types.entry(type_name(value)).or_default() += 1;
+        match value {
+            Value::Object(map) => {
+                stats.objects += 1;
+                for (key, child) in map {
+                    let child_path = member_path(&path, key);
+                    self.visit(child_path, Some(&path), Some(key), child);
+                }
+            }
+            Value::Array(items) => {
+                let item_path = item_path(&path);
+                for item in items {
+                    self.visit(item_path.clone(), Some(&path), None, item);
+                }
+            }
+            scalar => {
+                let rendered = scalar.to_string();
+                let tracked = stats.values.len();
+                match stats.values.get_mut(&rendered) {
+                    Some(count) => *count += 1,
+                    None if tracked < MAX_TRACKED_VALUES => {
+                        stats.values.insert(rendered, 1);
+                    }
+                    None => stats.values_truncated = true,
+                }
+            }
+        }
+    }
+
+    pub fn fields(&self) -> Vec<FieldReport> {
+        self.fields
+            .iter()
+            .map(|(path, stats)| {
+                let missing = match (&stats.parent, &stats.member) {
+                    (Some(parent), Some(_)) => {
+                        self.fields[parent].objects.saturating_sub(stats.present)
+                    }
+                    (None, _) => self.sampled.saturating_sub(stats.present),
+                    (Some(_), None) => 0,
+                };
+                let mut top_values = stats
+                    .values
+                    .iter()
+                    .map(|(value, count)| (value.clone(), *count))
+                    .collect::<Vec<_>>();
+                top_values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                top_values.truncate(TOP_VALUES);
+                FieldReport {
+                    path: path.clone(),
+                    present: stats.present,
+                    missing,
+                    types: stats.types.clone(),
+                    nullable: stats.types.contains_key("null"),
+                    top_values,
+                }
+            })
+            .collect()
+    }
+
+    /// A JSON Schema (draft 2020-12) describing every observed shape of `.data`.
+    pub fn json_schema(&self) -> Value {
+        let mut schema = if self.fields.contains_key(".") {
+            self.schema_at(".")
+        } else {
+            json!({})
+        };
+        if let Some(object) = schema.as_object_mut() {
+            object.insert(
+                "$schema".to_string(),
+                json!("https://json-schema.org/draft/2020-12/schema"),
+            );
+        }
+        schema
+    }
+
+    fn schema_at(&self, path: &str) -> Value {
+        let stats = &self.fields[path];
+        let mut schema = Map::new();
+        let types = stats
+            .types
+            .keys()
+            .map(|name| json!(name))
+            .collect::<Vec<_>>();
+        schema.insert(
+            "type".to_string(),
+            match types.as_slice() {
+                [single] => single.clone(),
+                _ => Value::Array(types),
+            },
+        );
+        if stats.types.contains_key("object") {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for (child_path, child) in &self.fields {
+                if child.parent.as_deref() != Some(path) {
+                    continue;
+                }
+                let Some(member) = &child.member else {
+                    continue;
+                };
+                properties.insert(member.clone(), self.schema_at(child_path));
+                if child.present == stats.objects {
+                    required.push(json!(member));
+                }
+            }
+            schema.insert("properties".to_string(), Value::Object(properties));
+            if !required.is_empty() {
+                schema.insert("required".to_string(), Value::Array(required));
+            }
+        }
+        if stats.types.contains_key("array") {
+            let item_path = item_path(path);
+            if self.fields.contains_key(&item_path) {
+                schema.insert("items".to_string(), self.schema_at(&item_path));
+            }
+        }
+        Value::Object(schema)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let fields = self
+            .fields()
+            .into_iter()
+            .map(|field| {
+                let top_values = field
+                    .top_values
+                    .iter()
+                    .map(|(value, count)| {
+                        json!({
+                            "value": serde_json::from_str::<Value>(value).unwrap_or(Value::Null),
+                            "count": count,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "path": field.path,
+                    "present": field.present,
+                    "missing": field.missing,
+                    "types": field.types,
+                    "nullable": field.nullable,
+                    "top_values": top_values,
+                    "top_values_truncated": self.fields[&field.path].values_truncated,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "sampled": self.sampled,
+            "fields": fields,
+            "schema": self.json_schema(),
+        })
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_i64() || number.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn item_path(parent: &str) -> String {
+    if parent == "." {
+        ".[]".to_string()
+    } else {
+        format!("{parent}[]")
+    }
+}
+
+fn member_path(parent: &str, key: &str) -> String {
+    let plain = !key.is_empty()
+        && !key.starts_with(|ch: char| ch.is_ascii_digit())
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    let member = if plain {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    };
+    if parent == "." {
+        format!(".{member}")
+    } else {
+        format!("{parent}.{member}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaInference;
+    use serde_json::json;
+
+    #[test]
+    fn infers_types_optionality_and_top_values() {
+        let mut inference = SchemaInference::new();
+        inference.observe(&json!({"kind": "click", "user": {"id": 1}, "tags": ["a", "b"]}));
+        inference.observe(&json!({"kind": "click", "user": {"id": 2, "vip": true}}));
+        inference.observe(&json!({"kind": "view", "user": null, "weird key": 1.5}));
+
+        let fields = inference.fields();
+        let field = |path: &str| {
+            fields
+                .iter()
+                .find(|field| field.path == path)
+                .unwrap_or_else(|| panic!("missing {path}"))
+        };
+        assert_eq!(inference.sampled(), 3);
+        assert_eq!(field(".kind").missing, 0);
+        assert_eq!(
+            field(".kind").top_values,
+            vec![("\"click\"".to_string(), 2), ("\"view\"".to_string(), 1)]
+        );
+        assert!(field(".user").nullable);
+        assert_eq!(field(".user.vip").missing, 1);
+        assert_eq!(field(".tags").missing, 2);
+        assert_eq!(field(".tags[]").present, 2);
+        assert_eq!(field(".\"weird key\"").types.get("number"), Some(&1));
+
+        let schema = inference.json_schema();
+        assert_eq!(schema["type"], json!("object"));
+        assert_eq!(schema["required"], json!(["kind", "user"]));
+        assert_eq!(
+            schema["properties"]["user"]["type"],
+            json!(["null", "object"])
+        );
+        assert_eq!(
+            schema["properties"]["user"]["properties"]["id"]["type"],
+            json!("integer")
+        );
+        assert_eq!(
+            schema["properties"]["tags"]["items"]["type"],
+            json!("string")
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! Purpose: Classify sequence gaps found while scanning a pool for `plasmite audit-gaps`.
+//! Exports: `GapKind`, `GapSpan`, `SeqAudit`, `SeqAuditReport`.
+//! Role: Pure bookkeeping over observed seqs; frame scanning stays in dispatch.
+//! Invariants: Seqs below the oldest retained seq are `overwritten` (normal ring eviction).
+//! Invariants: Holes inside the retained range, or seqs the header claims but the scan
+//! Invariants: never saw, are `missing` and indicate corruption.
+use serde_json::{Value, json};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GapKind {
+    Overwritten,
+    Missing,
+}
+
+impl GapKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GapKind::Overwritten => "overwritten",
+            GapKind::Missing => "missing",
+        }
+    }
+}
+
+/// Inclusive span of absent seqs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GapSpan {
+    pub kind: GapKind,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl GapSpan {
+    pub fn count(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SeqAudit {
+    first_seen: Option<u64>,
+    last_seen: Option<u64>,
+    scanned: u64,
+    out_of_order: u64,
+    gaps: Vec<GapSpan>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SeqAuditReport {
+    pub first_seq: Option<u64>,
+    pub last_seq: Option<u64>,
+    pub scanned: u64,
+    pub out_of_order: u64,
+    pub gaps: Vec<GapSpan>,
+}
+
+impl SeqAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, seq: u64) {
+        self.scanned += 1;
+        match self.last_seen {
+            None => self.first_seen = Some(seq),
+            Some(last) if seq <= last => {
+                self.out_of_order += 1;
+                return;
+            }
+            Some(last) if seq > last + 1 => self.gaps.push(GapSpan {
+                kind: GapKind::Missing,
+                start: last + 1,
+                end: seq - 1,
+            }),
+            Some(_) => {}
+        }
+        self.last_seen = Some(seq);
+    }
+
+    /// Close the scan against the header bounds captured before scanning.
+    ///
+    /// `window_start` is the first seq the scan was responsible for (1 for a full
+    /// audit); anything below `oldest_seq` in that window was evicted by the ring.
+    pub fn finish(mut self, window_start: u64, oldest_seq: u64, newest_seq: u64) -> SeqAuditReport {
+        let mut leading = Vec::new();
+        if oldest_seq > window_start {
+            leading.push(GapSpan {
+                kind: GapKind::Overwritten,
+                start: window_start,
+                end: oldest_seq - 1,
+            });
+        }
+        let expected_first = oldest_seq.max(window_start);
+        match self.first_seen {
+            Some(first) if first > expected_first => leading.push(GapSpan {
+                kind: GapKind::Missing,
+                start: expected_first,
+                end: first - 1,
+            }),
+            None if oldest_seq != 0 && newest_seq >= expected_first => leading.push(GapSpan {
+                kind: GapKind::Missing,
+                start: expected_first,
+                end: newest_seq,
+            }),
+            _ => {}
+        }
+        if let Some(last) = self.last_seen {
+            if newest_seq > last {
+                self.gaps.push(GapSpan {
+                    kind: GapKind::Missing,
+                    start: last + 1,
+                    end: newest_seq,
+                });
+            }
+        }
+        leading.append(&mut self.gaps);
+        SeqAuditReport {
+            first_seq: self.first_seen,
+            last_seq: self.last_seen,
+            scanned: self.scanned,
+            out_of_order: self.out_of_order,
+            gaps: leading,
+        }
+    }
+}
+
+impl SeqAuditReport {
+    pub fn count(&self, kind: GapKind) -> u64 {
+        self.gaps
+            .iter()
+            .filter(|gap| gap.kind == kind)
+            .map(GapSpan::count)
+            .sum()
+    }
+
+    /// True when the scan found evidence of corruption rather than normal eviction.
+    pub fn has_corruption(&self) -> bool {
+        self.out_of_order > 0 || self.count(GapKind::Missing) > 0
+    }
+
+    pub fn to_json(&self) -> Value {
+        let gaps = self
+            .gaps
+            .iter()
+            .map(|gap| {
+                json!({
+                    "kind": gap.kind.as_str(),
+                    "start": gap.start,
+                    "end": gap.end,
+                    "count": gap.count(),
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "first_seq": self.first_seq,
+            "last_seq": self.last_seq,
+            "scanned": self.scanned,
+            "overwritten_count": self.count(GapKind::Overwritten),
+            "missing_count": self.count(GapKind::Missing),
+            "out_of_order_count": self.out_of_order,
+            "gaps": gaps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GapKind, GapSpan, SeqAudit};
+
+    #[test]
+    fn contiguous_full_pool_has_no_gaps() {
+        let mut audit = SeqAudit::new();
+        for seq in 1..=5 {
+            audit.observe(seq);
+        }
+        let report = audit.finish(1, 1, 5);
+        assert!(report.gaps.is_empty());
+        assert!(!report.has_corruption());
+        assert_eq!(report.scanned, 5);
+    }
+
+    #[test]
+    fn evicted_prefix_is_overwritten_not_corrupt() {
+        let mut audit = SeqAudit::new();
+        for seq in 41..=50 {
+            audit.observe(seq);
+        }
+        let report = audit.finish(1, 41, 50);
+        assert_eq!(
+            report.gaps,
+            vec![GapSpan {
+                kind: GapKind::Overwritten,
+                start: 1,
+                end: 40
+            }]
+        );
+        assert_eq!(report.count(GapKind::Overwritten), 40);
+        assert!(!report.has_corruption());
+    }
+
+    #[test]
+    fn holes_and_unseen_tail_are_missing() {
+        let mut audit = SeqAudit::new();
+        for seq in [3, 4, 7, 8] {
+            audit.observe(seq);
+        }
+        let report = audit.finish(1, 3, 10);
+        assert_eq!(
+            report.gaps,
+            vec![
+                GapSpan {
+                    kind: GapKind::Overwritten,
+                    start: 1,
+                    end: 2
+                },
+                GapSpan {
+                    kind: GapKind::Missing,
+                    start: 5,
+                    end: 6
+                },
+                GapSpan {
+                    kind: GapKind::Missing,
+                    start: 9,
+                    end: 10
+                },
+            ]
+        );
+        assert!(report.has_corruption());
+        assert_eq!(report.to_json()["missing_count"], 4);
+    }
+
+    #[test]
+    fn windowed_audit_skips_eviction_before_window() {
+        let mut audit = SeqAudit::new();
+        for seq in 20..=22 {
+            audit.observe(seq);
+        }
+        let report = audit.finish(20, 5, 22);
+        assert!(report.gaps.is_empty());
+    }
+}
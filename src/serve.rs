@@ -1,20 +1,21 @@
 //! Purpose: Provide the HTTP/JSON remote server for Plasmite.
-//! Exports: `ServeConfig`, `serve`.
+//! Exports: `ServeConfig`, `ServeNamespace`, `serve`.
 //! Role: Axum-based loopback server implementing the remote v0 spec.
 //! Invariants: JSON envelopes match spec/remote/v0/SPEC.md; error kinds remain stable.
 //! Invariants: Loopback-only unless explicitly allowed (v0 policy).
+//! Invariants: Namespaces (`/v0/ns/<ns>/pools/...`) only see `<pool_dir>/<ns>/` and check their own
+//! tokens and access mode; root routes never reach namespaced pools.
 //! Notes: Streaming uses JSONL or framed Lite3; tail is at-least-once and resumable.
 
 use axum::body::Body;
 use axum::extract::{DefaultBodyLimit, Path as AxumPath, Query, RawQuery, State};
 use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
 use axum::response::{IntoResponse, Response};
-use axum::routing::{delete, get, post};
+use axum::routing::{delete, get, post, put};
 use axum::{Json, Router};
 use bytes::Bytes;
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use hyper_util::server::conn::auto::Builder as AutoBuilder;
-use hyper_util::service::TowerToHyperService;
 use rcgen::{Certificate, CertificateParams, SanType};
 use rustls::ServerConfig;
 use rustls::pki_types::pem::{Error as PemError, PemObject};
@@ -34,23 +35,38 @@ use tokio_rustls::TlsAcceptor;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::ReceiverStream;
 use tower_http::cors::{AllowOrigin, CorsLayer};
-use tower_http::trace::TraceLayer;
+use tower_http::trace::{DefaultOnResponse, OnResponse, TraceLayer};
 use tower_service::Service;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 use url::Url;
 
-use crate::pool_info_json::pool_info_json;
+use crate::jq_filter::{JqFilter, compile_filters, matches_all};
+use crate::otlp::{OtlpSignal, records_from_export};
+use crate::pool_paths::pool_name_for_path;
+use crate::serve_policy::{Caller, PoolPolicy, intersect_access};
+use crate::serve_quota::{Quota, QuotaState, QuotaUsage, QuotaViolation};
+use crate::serve_routes::{Route, RouteTable};
+use crate::serve_trace::{OtelConfig, OtlpExportGuard, spawn_exporter};
+use crate::serve_transform::{IngestTransforms, TransformChain};
+use crate::stats_history::StatsRecorder;
 use plasmite::api::{
-    Durability, Error, ErrorKind, LocalClient, PoolApiExt, PoolOptions, PoolRef, TailOptions, lite3,
+    AppendOptions, Deadline, Durability, Error, ErrorKind, LocalClient, PoolApiExt, PoolOptions,
+    PoolRef, TailOptions, TimeNearest, base64_decode, hex_encode, lite3, parse_frame_flags,
 };
 use plasmite::mcp::{
     DispatchOutcome, JsonRpcError as McpJsonRpcError, McpDispatcher, McpHandler, McpResource,
     McpTool, PlasmiteMcpHandler, ResourceReadRequest, ResourceReadResult, ToolCallRequest,
     ToolCallResult,
 };
+use plasmite::pool_info_json::pool_info_json;
 
 const UI_INDEX_HTML: &str = include_str!("../ui/index.html");
 const MCP_PROTOCOL_VERSION: &str = "2025-11-25";
+const OTLP_POOL_SIZE: u64 = 16 * 1024 * 1024;
+/// Open pools kept per pool directory (root and each namespace) between requests.
+const POOL_CACHE_CAPACITY: usize = 64;
 
 #[derive(Clone, Debug)]
 pub struct ServeConfig {
@@ -62,14 +78,112 @@ pub struct ServeConfig {
     pub allow_non_loopback: bool,
     pub insecure_no_tls: bool,
     pub token_file_used: bool,
+    /// Token file to watch; every non-empty, non-`#` line is an accepted token.
+    pub token_file: Option<PathBuf>,
     pub tls_cert: Option<PathBuf>,
     pub tls_key: Option<PathBuf>,
     pub tls_self_signed: bool,
+    /// CA bundle for verifying client certificates; when set, every TLS client must present one.
+    pub tls_client_ca: Option<PathBuf>,
     pub tls_self_signed_material: Option<SelfSignedTlsMaterial>,
     pub tls_fingerprint: Option<String>,
     pub max_body_bytes: u64,
     pub max_tail_timeout_ms: u64,
     pub max_concurrent_tails: usize,
+    pub otlp_bind: Option<SocketAddr>,
+    pub namespaces: Vec<ServeNamespace>,
+    /// Server-wide limits; namespaces inherit any limit they leave unset.
+    pub quota: Quota,
+    /// Open every pool with the read-only open path: no writes, no locks, reads only.
+    pub readonly_snapshot: bool,
+    /// Per-pool rules from `--pool-policy`; when set, callers only see and use granted pools.
+    pub pool_policy: Option<PoolPolicy>,
+    /// Ingest transforms from `--transforms`, applied to JSON appends before they are written.
+    pub transforms: Option<IngestTransforms>,
+    /// `--stats-pool`: record pool metrics into `_stats` this often.
+    pub stats_interval: Option<std::time::Duration>,
+    /// Global `--op-timeout`: bound each pool listing and validation request.
+    pub op_timeout: Option<std::time::Duration>,
+}
+
+/// An isolated group of pools served under `/v0/ns/<name>/pools/...`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServeNamespace {
+    pub name: String,
+    /// Tokens accepted for this namespace only; `None` reuses the server-wide tokens.
+    pub token_file: Option<PathBuf>,
+    /// `None` reuses the server-wide `--access` mode.
+    pub access_mode: Option<AccessMode>,
+    pub quota: Quota,
+    /// Client-certificate identities admitted without a token; any other certificate needs one.
+    pub identities: Vec<String>,
+}
+
+impl ServeNamespace {
+    /// Parse `NAME[,token-file=PATH][,access=MODE][,identity=ID]...[,max-pools=N][,max-bytes=SIZE][,max-append-rate=N]`.
+    pub fn parse(spec: &str) -> Result<Self, Error> {
+        let invalid = |message: String| {
+            Error::new(ErrorKind::Usage)
+                .with_message(message)
+                .with_hint("Use NAME[,token-file=PATH][,access=read-only|write-only|read-write][,identity=ID]...[,max-pools=N][,max-bytes=SIZE][,max-append-rate=N].")
+        };
+        let mut parts = spec.split(',');
+        let name = parts.next().unwrap_or_default().trim().to_string();
+        if !is_valid_namespace_name(&name) {
+            return Err(invalid(format!("invalid namespace name '{name}'")));
+        }
+        let mut namespace = Self {
+            name,
+            token_file: None,
+            access_mode: None,
+            quota: Quota::default(),
+            identities: Vec::new(),
+        };
+        for part in parts {
+            match part.split_once('=') {
+                Some(("token-file", path)) if !path.is_empty() => {
+                    namespace.token_file = Some(PathBuf::from(path));
+                }
+                Some(("access", mode)) => {
+                    namespace.access_mode = Some(match mode {
+                        "read-only" => AccessMode::ReadOnly,
+                        "write-only" => AccessMode::WriteOnly,
+                        "read-write" => AccessMode::ReadWrite,
+                        _ => return Err(invalid(format!("invalid namespace access '{mode}'"))),
+                    });
+                }
+                Some(("identity", identity)) if !identity.is_empty() => {
+                    namespace.identities.push(identity.to_string());
+                }
+                Some(("max-pools", value)) => {
+                    namespace.quota.max_pools =
+                        Some(value.parse().map_err(|_| {
+                            invalid(format!("invalid namespace max-pools '{value}'"))
+                        })?);
+                }
+                Some(("max-bytes", value)) => {
+                    namespace.quota.max_bytes = Some(crate::parse_size(value)?);
+                }
+                Some(("max-append-rate", value)) => {
+                    namespace.quota.max_append_rate =
+                        Some(value.parse().ok().filter(|rate| *rate > 0).ok_or_else(|| {
+                            invalid(format!("invalid namespace max-append-rate '{value}'"))
+                        })?);
+                }
+                _ => return Err(invalid(format!("invalid namespace option '{part}'"))),
+            }
+        }
+        Ok(namespace)
+    }
+}
+
+/// Namespace names become directory names and URL segments, so keep them plain.
+fn is_valid_namespace_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
 }
 
 #[derive(Clone, Debug)]
@@ -119,16 +233,136 @@ pub fn tls_fingerprint_from_cert_path(cert_path: &Path) -> Result<String, Error>
     Ok(format_cert_fingerprint(first.as_ref()))
 }
 
+/// Bearer tokens accepted by the server, reloaded from `--token-file` when it changes.
+/// Reloads never drop auth: an unreadable or empty file keeps the previous set.
+struct TokenStore {
+    tokens: std::sync::RwLock<Vec<String>>,
+    path: Option<PathBuf>,
+}
+
+const TOKEN_RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+impl TokenStore {
+    fn new(token: Option<String>, path: Option<PathBuf>) -> Result<Self, Error> {
+        let tokens = match &path {
+            Some(path) => read_token_list(path)?,
+            None => token.into_iter().collect(),
+        };
+        Ok(Self {
+            tokens: std::sync::RwLock::new(tokens),
+            path,
+        })
+    }
+
+    fn requires_auth(&self) -> bool {
+        self.path.is_some() || !self.read().is_empty()
+    }
+
+    fn accepts(&self, presented: &str) -> bool {
+        self.read().iter().any(|token| token == presented)
+    }
+
+    fn reload(&self) -> Result<usize, Error> {
+        let Some(path) = &self.path else {
+            return Ok(self.read().len());
+        };
+        let tokens = read_token_list(path)?;
+        let count = tokens.len();
+        *self.tokens.write().unwrap_or_else(|err| err.into_inner()) = tokens;
+        Ok(count)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<String>> {
+        self.tokens.read().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Read a token file: one token per line, blank lines and `#` comments ignored.
+/// The first token is the one clients send; the rest stay accepted during rotation.
+pub fn read_token_list(path: &Path) -> Result<Vec<String>, Error> {
+    let raw = std::fs::read_to_string(path).map_err(|err| {
+        Error::new(ErrorKind::Usage)
+            .with_message("failed to read token file")
+            .with_path(path)
+            .with_source(err)
+    })?;
+    let tokens: Vec<String> = raw
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    if tokens.is_empty() {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("token file is empty")
+            .with_path(path));
+    }
+    Ok(tokens)
+}
+
+fn token_file_stamp(path: &Path) -> Option<(Option<std::time::SystemTime>, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok(), meta.len()))
+}
+
+async fn watch_token_file(store: Arc<TokenStore>) {
+    let Some(path) = store.path.clone() else {
+        return;
+    };
+    let mut last = token_file_stamp(&path);
+    loop {
+        tokio::time::sleep(TOKEN_RELOAD_INTERVAL).await;
+        let stamp = token_file_stamp(&path);
+        if stamp == last {
+            continue;
+        }
+        last = stamp;
+        match store.reload() {
+            Ok(count) => tracing::info!(path = %path.display(), count, "reloaded token file"),
+            Err(err) => {
+                tracing::warn!(path = %path.display(), error = %err, "keeping previous tokens")
+            }
+        }
+    }
+}
+
+/// `--stats-pool`: snapshot pool metrics into `_stats` until the server exits.
+async fn record_pool_stats(mut recorder: StatsRecorder, interval: std::time::Duration) {
+    loop {
+        let Ok((next, result)) = tokio::task::spawn_blocking(move || {
+            let result = recorder.tick();
+            (recorder, result)
+        })
+        .await
+        else {
+            return;
+        };
+        recorder = next;
+        if let Err(err) = result {
+            tracing::warn!(error = %err, "failed to record pool stats");
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
 #[derive(Clone)]
 struct AppState {
     client: LocalClient,
-    token: Option<String>,
+    tokens: Arc<TokenStore>,
     access_mode: AccessMode,
     max_tail_timeout_ms: u64,
     tail_semaphore: Arc<Semaphore>,
+    quota: Arc<QuotaState>,
+    policy: Option<Arc<PoolPolicy>>,
+    /// Certificate identities this state admits; `None` admits any verified certificate.
+    identities: Option<Arc<Vec<String>>>,
+    routes: Arc<RouteTable>,
+    /// Transforms for this state's pools, keyed by bare pool name.
+    transforms: Arc<IngestTransforms>,
+    op_timeout: Option<std::time::Duration>,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AccessMode {
     ReadOnly,
     WriteOnly,
@@ -148,7 +382,7 @@ impl AccessMode {
 pub async fn serve(config: ServeConfig) -> Result<(), Error> {
     let cors_allowed_origins = preflight_config(&config)?;
 
-    init_tracing();
+    let _otel_guard = init_tracing(OtelConfig::from_env()?);
 
     let max_body_bytes: usize = config
         .max_body_bytes
@@ -158,12 +392,40 @@ pub async fn serve(config: ServeConfig) -> Result<(), Error> {
     let tls_config = build_tls_config(&config).await?;
     let cors_layer = build_cors_layer(&cors_allowed_origins)?;
 
+    let tokens = Arc::new(TokenStore::new(config.token, config.token_file)?);
+    if tokens.path.is_some() {
+        tokio::spawn(watch_token_file(tokens.clone()));
+    }
+    let client = LocalClient::new()
+        .with_pool_dir(config.pool_dir.clone())
+        .with_read_only(config.readonly_snapshot)
+        .with_pool_cache(POOL_CACHE_CAPACITY)
+        .with_hidden_dirs(
+            config
+                .namespaces
+                .iter()
+                .map(|namespace| namespace.name.clone()),
+        );
+    let quota = Arc::new(QuotaState::new(config.quota));
+    let routes = Arc::new(RouteTable::load(client.clone(), quota.clone())?);
+    if let Some(interval) = config.stats_interval {
+        tokio::spawn(record_pool_stats(
+            StatsRecorder::new(&config.pool_dir),
+            interval,
+        ));
+    }
     let state = Arc::new(AppState {
-        client: LocalClient::new().with_pool_dir(config.pool_dir),
-        token: config.token,
+        client,
+        tokens,
         access_mode: config.access_mode,
         max_tail_timeout_ms: config.max_tail_timeout_ms,
         tail_semaphore: Arc::new(Semaphore::new(config.max_concurrent_tails)),
+        quota,
+        policy: config.pool_policy.map(Arc::new),
+        identities: None,
+        routes,
+        transforms: Arc::new(scoped_transforms(&config.transforms, None)),
+        op_timeout: config.op_timeout,
     });
 
     let mut app = Router::new()
@@ -171,35 +433,184 @@ pub async fn serve(config: ServeConfig) -> Result<(), Error> {
         .route("/mcp", post(mcp_post).get(mcp_get))
         .route("/ui", get(ui_index))
         .route("/ui/pools/:pool", get(ui_pool))
-        .route("/v0/pools", post(create_pool).get(list_pools))
-        .route("/v0/pools/open", post(open_pool))
-        .route("/v0/pools/:pool/info", get(pool_info))
-        .route("/v0/pools/:pool", delete(delete_pool))
-        .route("/v0/pools/:pool/append", post(append_message))
-        .route("/v0/pools/:pool/append_lite3", post(append_lite3))
-        .route("/v0/pools/:pool/messages/:seq", get(get_message))
-        .route("/v0/pools/:pool/messages/:seq/lite3", get(get_lite3))
-        .route("/v0/pools/:pool/tail", get(tail_messages))
-        .route("/v0/pools/:pool/tail_lite3", get(tail_lite3))
+        .merge(pool_api_routes("/v0"))
         .route("/v0/ui/pools", get(list_pools))
         .route("/v0/ui/pools/:pool/info", get(pool_info))
         .route("/v0/ui/pools/:pool/events", get(ui_events))
-        .with_state(state)
+        .with_state(state.clone());
+    for namespace in &config.namespaces {
+        let mut namespace_state = namespace_state(&state, namespace, &config.pool_dir)?;
+        namespace_state.transforms =
+            Arc::new(scoped_transforms(&config.transforms, Some(&namespace.name)));
+        let namespace_state = Arc::new(namespace_state);
+        app = app.merge(
+            pool_api_routes(&format!("/v0/ns/{}", namespace.name)).with_state(namespace_state),
+        );
+    }
+    let mut app = app
         .layer(DefaultBodyLimit::max(max_body_bytes))
-        .layer(TraceLayer::new_for_http());
+        .layer(axum::middleware::map_request(attach_client_identity))
+        .layer(axum::middleware::from_fn(envelope_plain_errors))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(request_span)
+                .on_response(record_response_status),
+        )
+        .layer(axum::middleware::from_fn(assign_request_id));
+
+    let otlp_app = config.otlp_bind.map(|otlp_bind| {
+        let otlp_app = Router::new()
+            .route("/v1/logs", post(otlp_logs))
+            .route("/v1/traces", post(otlp_traces))
+            .with_state(state.clone())
+            .layer(DefaultBodyLimit::max(max_body_bytes))
+            .layer(axum::middleware::map_request(attach_client_identity))
+            .layer(axum::middleware::from_fn(envelope_plain_errors))
+            .layer(
+                TraceLayer::new_for_http()
+                    .make_span_with(request_span)
+                    .on_response(record_response_status),
+            )
+            .layer(axum::middleware::from_fn(assign_request_id));
+        (otlp_bind, otlp_app)
+    });
 
+    if !plasmite::api::chaos::delay().is_zero() {
+        app = app.layer(axum::middleware::from_fn(chaos_delay_reads));
+    }
     if let Some(cors_layer) = cors_layer {
         app = app.layer(cors_layer);
     }
 
-    if let Some(tls_config) = tls_config {
-        return serve_tls(config.bind, app, tls_config).await;
+    let bind = config.bind;
+    let main = async move {
+        if let Some(tls_config) = tls_config {
+            return serve_tls(bind, app, tls_config).await;
+        }
+        serve_plain(bind, app).await
+    };
+    match otlp_app {
+        Some((otlp_bind, otlp_app)) => {
+            tokio::try_join!(main, serve_plain(otlp_bind, otlp_app))?;
+            Ok(())
+        }
+        None => main.await,
+    }
+}
+
+/// The pool API under `prefix` (`/v0` at the root, `/v0/ns/<ns>` for a namespace).
+fn pool_api_routes(prefix: &str) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            &format!("{prefix}/pools"),
+            post(create_pool).get(list_pools),
+        )
+        .route(&format!("{prefix}/pools/open"), post(open_pool))
+        .route(&format!("{prefix}/pools/:pool/info"), get(pool_info))
+        .route(
+            &format!("{prefix}/pools/:pool/validate"),
+            get(validate_pool),
+        )
+        .route(&format!("{prefix}/pools/:pool"), delete(delete_pool))
+        .route(
+            &format!("{prefix}/pools/:pool/append"),
+            post(append_message),
+        )
+        .route(
+            &format!("{prefix}/pools/:pool/append_lite3"),
+            post(append_lite3),
+        )
+        .route(
+            &format!("{prefix}/pools/:pool/append_batch"),
+            post(append_batch),
+        )
+        .route(
+            &format!("{prefix}/pools/:pool/messages/:seq"),
+            get(get_message),
+        )
+        .route(
+            &format!("{prefix}/pools/:pool/messages/:seq/lite3"),
+            get(get_lite3),
+        )
+        .route(&format!("{prefix}/pools/:pool/tail"), get(tail_messages))
+        .route(&format!("{prefix}/pools/:pool/tail_lite3"), get(tail_lite3))
+        .route(&format!("{prefix}/quota"), get(quota_status))
+        .route(&format!("{prefix}/pool_cache"), get(pool_cache_status))
+        .route(
+            &format!("{prefix}/routes"),
+            put(put_route).get(list_routes).delete(delete_route),
+        )
+}
+
+/// Root state narrowed to one namespace: its own pool directory, tokens, access mode, and routes.
+/// The tail concurrency limit stays shared across the whole server; quotas are per namespace.
+/// Client certificates only skip token auth when the namespace lists their identity.
+fn namespace_state(
+    root: &AppState,
+    namespace: &ServeNamespace,
+    pool_dir: &Path,
+) -> Result<AppState, Error> {
+    let dir = pool_dir.join(&namespace.name);
+    let read_only = root.client.is_read_only();
+    if !read_only {
+        std::fs::create_dir_all(&dir).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to create namespace pool directory")
+                .with_path(&dir)
+                .with_source(err)
+        })?;
+    }
+    let tokens = match &namespace.token_file {
+        Some(path) => {
+            let tokens = Arc::new(TokenStore::new(None, Some(path.clone()))?);
+            tokio::spawn(watch_token_file(tokens.clone()));
+            tokens
+        }
+        None => root.tokens.clone(),
+    };
+    let client = LocalClient::new()
+        .with_pool_dir(dir)
+        .with_read_only(read_only)
+        .with_pool_cache(POOL_CACHE_CAPACITY);
+    let quota = Arc::new(QuotaState::new(namespace.quota.or(root.quota.quota())));
+    let routes = Arc::new(RouteTable::load(client.clone(), quota.clone())?);
+    Ok(AppState {
+        client,
+        tokens,
+        access_mode: namespace.access_mode.unwrap_or(root.access_mode),
+        quota,
+        identities: Some(Arc::new(namespace.identities.clone())),
+        routes,
+        ..root.clone()
+    })
+}
+
+impl AppState {
+    /// The shared client bounded by a fresh `--op-timeout` deadline for one request.
+    fn request_client(&self) -> (LocalClient, Deadline) {
+        let deadline = self.op_timeout.map(Deadline::after).unwrap_or_default();
+        (
+            self.client.clone().with_deadline(deadline.clone()),
+            deadline,
+        )
     }
-    serve_plain(config.bind, app).await
+}
+
+fn scoped_transforms(
+    transforms: &Option<IngestTransforms>,
+    namespace: Option<&str>,
+) -> IngestTransforms {
+    transforms
+        .as_ref()
+        .map(|transforms| transforms.scoped(namespace))
+        .unwrap_or_default()
 }
 
 pub fn preflight_config(config: &ServeConfig) -> Result<Vec<String>, Error> {
-    validate_config(config)
+    let cors_allowed_origins = validate_config(config)?;
+    // Malformed `OTEL_*` trace export settings fail `serve check` too, not just `serve`.
+    OtelConfig::from_env()?;
+    Ok(cors_allowed_origins)
 }
 
 fn is_loopback(ip: IpAddr) -> bool {
@@ -230,6 +641,44 @@ fn validate_config(config: &ServeConfig) -> Result<Vec<String>, Error> {
             .with_hint("Use either --tls-self-signed or provide certificate paths; `plasmite serve init` can generate cert/key files."));
     }
 
+    if let Some(otlp_bind) = config.otlp_bind {
+        if !is_loopback(otlp_bind.ip()) && !config.allow_non_loopback {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("non-loopback --otlp-bind requires explicit opt-in")
+                .with_hint("Re-run with --allow-non-loopback or use a loopback OTLP address."));
+        }
+        if otlp_bind == config.bind {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("--otlp-bind must differ from --bind")
+                .with_hint("Use the OTLP default port, e.g. --otlp-bind 127.0.0.1:4318."));
+        }
+        if !config.access_mode.allows_write() {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("--otlp-bind requires write access")
+                .with_hint("Use --access read-write or --access write-only with --otlp-bind."));
+        }
+    }
+
+    if config.readonly_snapshot {
+        let writable_namespace = config.namespaces.iter().any(|namespace| {
+            namespace
+                .access_mode
+                .is_some_and(|mode| mode.allows_write())
+        });
+        if config.access_mode.allows_write() || writable_namespace {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("--readonly-snapshot requires read-only access")
+                .with_hint("Drop --access and namespace access=... overrides; snapshot mode is always read-only."));
+        }
+        if config.otlp_bind.is_some() {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message("--otlp-bind cannot be combined with --readonly-snapshot")
+                .with_hint(
+                    "OTLP ingest appends to pools; serve it from a separate read-write server.",
+                ));
+        }
+    }
+
     if config.max_body_bytes == 0 {
         return Err(Error::new(ErrorKind::Usage)
             .with_message("--max-body-bytes must be greater than zero")
@@ -254,11 +703,64 @@ fn validate_config(config: &ServeConfig) -> Result<Vec<String>, Error> {
             .with_hint("Use a smaller value that fits in memory."));
     }
 
+    if config.tls_client_ca.is_some() && !tls_is_configured(config) {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("--tls-client-ca requires TLS")
+            .with_hint("Add --tls-cert/--tls-key or --tls-self-signed."));
+    }
+
+    let mut namespace_names = HashSet::new();
+    for namespace in &config.namespaces {
+        if !is_valid_namespace_name(&namespace.name) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid namespace name '{}'", namespace.name))
+                .with_hint("Use letters, digits, '-', '_' or '.', not starting with '.'."));
+        }
+        if !namespace_names.insert(namespace.name.as_str()) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!("duplicate namespace '{}'", namespace.name))
+                .with_hint("Declare each --namespace once."));
+        }
+        let writes = namespace
+            .access_mode
+            .unwrap_or(config.access_mode)
+            .allows_write();
+        if !namespace.identities.is_empty() && config.tls_client_ca.is_none() {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!(
+                    "namespace '{}' lists identities without --tls-client-ca",
+                    namespace.name
+                ))
+                .with_hint("Add --tls-client-ca so client certificates are verified."));
+        }
+        let authenticated = namespace.token_file.is_some()
+            || config.token_file_used
+            || !namespace.identities.is_empty();
+        if !is_loopback_bind && writes && !authenticated {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!(
+                    "non-loopback write to namespace '{}' requires a token file",
+                    namespace.name
+                ))
+                .with_hint("Add token-file=PATH or identity=ID to the --namespace value, or use --token-file."));
+        }
+        if !is_loopback_bind && writes && !config.insecure_no_tls && !tls_is_configured(config) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!(
+                    "non-loopback write to namespace '{}' requires TLS",
+                    namespace.name
+                ))
+                .with_hint(
+                    "Use --tls-cert/--tls-key or --tls-self-signed, or --insecure-no-tls.",
+                ));
+        }
+    }
+
     if !is_loopback_bind && config.access_mode.allows_write() {
-        if !config.token_file_used {
+        if !config.token_file_used && config.tls_client_ca.is_none() {
             return Err(Error::new(ErrorKind::Usage)
-                .with_message("non-loopback write requires --token-file")
-                .with_hint("Run `plasmite serve init` and use the generated --token-file for non-loopback write access."));
+                .with_message("non-loopback write requires --token-file or --tls-client-ca")
+                .with_hint("Run `plasmite serve init` and use the generated --token-file, or require client certificates with --tls-client-ca."));
         }
         if !config.insecure_no_tls && !tls_is_configured(config) {
             return Err(Error::new(ErrorKind::Usage)
@@ -355,7 +857,8 @@ fn build_cors_layer(origins: &[String]) -> Result<Option<CorsLayer>, Error> {
     let cors = CorsLayer::new()
         .allow_origin(AllowOrigin::list(allow_origins))
         .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
-        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]);
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+        .expose_headers([header::HeaderName::from_static(REQUEST_ID_HEADER)]);
     Ok(Some(cors))
 }
 
@@ -364,6 +867,7 @@ fn tls_is_configured(config: &ServeConfig) -> bool {
 }
 
 async fn build_tls_config(config: &ServeConfig) -> Result<Option<Arc<ServerConfig>>, Error> {
+    let client_ca = config.tls_client_ca.as_deref();
     if config.tls_self_signed {
         let material = match &config.tls_self_signed_material {
             Some(value) => value.clone(),
@@ -371,19 +875,23 @@ async fn build_tls_config(config: &ServeConfig) -> Result<Option<Arc<ServerConfi
         };
         let certs = vec![CertificateDer::from(material.cert_der)];
         let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(material.key_der));
-        let tls = build_server_config(certs, key)?;
+        let tls = build_server_config(certs, key, client_ca)?;
         return Ok(Some(Arc::new(tls)));
     }
 
     if let (Some(cert), Some(key)) = (&config.tls_cert, &config.tls_key) {
-        let tls = load_tls_config_from_pem(cert, key)?;
+        let tls = load_tls_config_from_pem(cert, key, client_ca)?;
         return Ok(Some(Arc::new(tls)));
     }
 
     Ok(None)
 }
 
-fn load_tls_config_from_pem(cert_path: &Path, key_path: &Path) -> Result<ServerConfig, Error> {
+fn load_tls_config_from_pem(
+    cert_path: &Path,
+    key_path: &Path,
+    client_ca: Option<&Path>,
+) -> Result<ServerConfig, Error> {
     let certs = load_certificates_from_pem(cert_path)?;
     let key_bytes = std::fs::read(key_path).map_err(|err| {
         Error::new(ErrorKind::Io)
@@ -402,7 +910,7 @@ fn load_tls_config_from_pem(cert_path: &Path, key_path: &Path) -> Result<ServerC
             .with_source(err),
     })?;
 
-    build_server_config(certs, key)
+    build_server_config(certs, key, client_ca)
 }
 
 fn load_certificates_from_pem(cert_path: &Path) -> Result<Vec<CertificateDer<'static>>, Error> {
@@ -444,18 +952,192 @@ fn format_cert_fingerprint(cert_der: &[u8]) -> String {
 fn build_server_config(
     certs: Vec<CertificateDer<'static>>,
     key: PrivateKeyDer<'static>,
+    client_ca: Option<&Path>,
 ) -> Result<ServerConfig, Error> {
     let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
-    let mut config = ServerConfig::builder()
-        .with_no_client_auth()
-        .with_single_cert(certs, key)
+    let builder = ServerConfig::builder();
+    let builder = match client_ca {
+        Some(path) => builder.with_client_cert_verifier(build_client_verifier(path)?),
+        None => builder.with_no_client_auth(),
+    };
+    let mut config = builder.with_single_cert(certs, key).map_err(|err| {
+        Error::new(ErrorKind::Usage)
+            .with_message("invalid TLS certificate or key")
+            .with_source(err)
+    })?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+fn build_client_verifier(
+    ca_path: &Path,
+) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>, Error> {
+    let mut roots = rustls::RootCertStore::empty();
+    let (added, _) = roots.add_parsable_certificates(load_certificates_from_pem(ca_path)?);
+    if added == 0 {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("client CA file contains no parsable certificates")
+            .with_path(ca_path));
+    }
+    rustls::server::WebPkiClientVerifier::builder(Arc::new(roots))
+        .build()
         .map_err(|err| {
             Error::new(ErrorKind::Usage)
-                .with_message("invalid TLS certificate or key")
+                .with_message("invalid client CA")
+                .with_path(ca_path)
                 .with_source(err)
-        })?;
-    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
-    Ok(config)
+        })
+}
+
+/// Verified client-certificate identity for a TLS connection (CN, else first DNS/email/URI SAN).
+#[derive(Clone, Debug)]
+struct ClientIdentity(String);
+
+/// Header carrying the verified client identity to handlers; client-sent values are dropped.
+const CLIENT_IDENTITY_HEADER: &str = "x-plasmite-client-identity";
+
+fn client_identity_from_der(cert_der: &[u8]) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(cert_der).ok()?;
+    if let Some(cn) = cert
+        .subject()
+        .iter_common_name()
+        .find_map(|attr| attr.as_str().ok())
+    {
+        return Some(cn.to_string());
+    }
+    let san = cert.subject_alternative_name().ok().flatten()?;
+    san.value.general_names.iter().find_map(|name| match name {
+        x509_parser::extensions::GeneralName::DNSName(value)
+        | x509_parser::extensions::GeneralName::RFC822Name(value)
+        | x509_parser::extensions::GeneralName::URI(value) => Some(value.to_string()),
+        _ => None,
+    })
+}
+
+/// Replace any client-supplied identity header with the one proven by the TLS handshake.
+async fn attach_client_identity(mut request: axum::extract::Request) -> axum::extract::Request {
+    request.headers_mut().remove(CLIENT_IDENTITY_HEADER);
+    let identity = request.extensions().get::<ClientIdentity>().cloned();
+    if let Some(value) = identity.and_then(|identity| HeaderValue::from_str(&identity.0).ok()) {
+        request.headers_mut().insert(CLIENT_IDENTITY_HEADER, value);
+    }
+    request
+}
+
+/// `--chaos-delay-ms` (chaos builds): delay reads here; appends are delayed in the pool layer,
+/// so each request waits once.
+async fn chaos_delay_reads(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    if request.method() == Method::GET {
+        tokio::time::sleep(plasmite::api::chaos::delay()).await;
+    }
+    next.run(request).await
+}
+
+/// Correlation header set on every response (and echoed from the request when valid).
+const REQUEST_ID_HEADER: &str = "x-plasmite-request-id";
+
+/// Reuse a caller-supplied request ID when it is short and header-safe, otherwise mint one.
+async fn assign_request_id(
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(new_request_id);
+    let value = HeaderValue::from_str(&request_id).expect("request id is header-safe");
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, value.clone());
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    response
+}
+
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 64
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.'))
+}
+
+fn new_request_id() -> String {
+    static FALLBACK: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let mut bytes = [0u8; 8];
+    if getrandom::fill(&mut bytes).is_err() {
+        let count = FALLBACK.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        bytes = (count ^ std::process::id() as u64).to_be_bytes();
+    }
+    hex_encode(&bytes)
+}
+
+fn request_span(request: &axum::http::Request<Body>) -> tracing::Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("-");
+    // A W3C `traceparent` from the caller makes exported spans part of the caller's trace.
+    match request
+        .headers()
+        .get("traceparent")
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(traceparent) => tracing::info_span!(
+            "request",
+            method = %request.method(),
+            uri = %request.uri(),
+            request_id,
+            traceparent,
+            status = tracing::field::Empty,
+        ),
+        None => tracing::info_span!(
+            "request",
+            method = %request.method(),
+            uri = %request.uri(),
+            request_id,
+            status = tracing::field::Empty,
+        ),
+    }
+}
+
+fn record_response_status(response: &Response, latency: std::time::Duration, span: &tracing::Span) {
+    span.record("status", response.status().as_u16());
+    DefaultOnResponse::default().on_response(response, latency, span);
+}
+
+/// Run `f` against a (cached) pool handle under a `pool_open` span and then the span `op`
+/// makes, so traces split request latency into opening (or waiting for) the pool and the
+/// operation itself.
+fn with_pool_traced<T>(
+    client: &LocalClient,
+    pool_ref: &PoolRef,
+    op: impl FnOnce() -> tracing::Span,
+    f: impl FnOnce(&mut plasmite::api::Pool) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let name = match pool_ref {
+        PoolRef::Name(name) | PoolRef::Uri(name) => name.clone(),
+        PoolRef::Path(path) => path.display().to_string(),
+    };
+    let open = tracing::info_span!("pool_open", pool = %name).entered();
+    client.with_pool(pool_ref, move |pool| {
+        drop(open);
+        let _op = op().entered();
+        f(pool)
+    })
+}
+
+fn client_identity(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(CLIENT_IDENTITY_HEADER)
+        .and_then(|value| value.to_str().ok())
 }
 
 async fn serve_plain(bind: SocketAddr, app: Router) -> Result<(), Error> {
@@ -541,8 +1223,26 @@ async fn serve_tls(
                         Ok(stream) => stream,
                         Err(_) => return,
                     };
+                    let identity = tls_stream
+                        .get_ref()
+                        .1
+                        .peer_certificates()
+                        .and_then(|certs| certs.first())
+                        .and_then(|cert| client_identity_from_der(cert.as_ref()))
+                        .map(ClientIdentity);
+                    if let Some(identity) = &identity {
+                        tracing::debug!(%peer_addr, identity = %identity.0, "client certificate verified");
+                    }
                     let io = TokioIo::new(tls_stream);
-                    let service = TowerToHyperService::new(service);
+                    let service = hyper::service::service_fn(
+                        move |mut request: axum::http::Request<hyper::body::Incoming>| {
+                            if let Some(identity) = identity.clone() {
+                                request.extensions_mut().insert(identity);
+                            }
+                            let mut service = service.clone();
+                            async move { service.call(request).await }
+                        },
+                    );
                     let _ = builder.serve_connection_with_upgrades(io, service).await;
                 });
             }
@@ -562,12 +1262,21 @@ async fn serve_tls(
     Ok(())
 }
 
-fn init_tracing() {
+/// Install the log subscriber, plus the OTLP span exporter when `OTEL_*` configures one.
+/// The returned guard flushes queued spans when `serve` returns.
+fn init_tracing(otel: Option<OtelConfig>) -> Option<OtlpExportGuard> {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
-        .with_target(false)
-        .try_init();
+    let (otlp_layer, guard) = match otel.map(spawn_exporter) {
+        Some((layer, guard)) => (Some(layer), Some(guard)),
+        None => (None, None),
+    };
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with(otlp_layer)
+        .try_init()
+        .ok()
+        .and(guard)
 }
 
 async fn shutdown_signal() {
@@ -590,15 +1299,28 @@ async fn shutdown_signal() {
 }
 
 fn authorize(headers: &HeaderMap, state: &AppState) -> Result<(), Error> {
-    let Some(token) = state.token.as_ref() else {
+    if let Some(identity) = client_identity(headers) {
+        let admitted = state
+            .identities
+            .as_ref()
+            .is_none_or(|identities| identities.iter().any(|allowed| allowed == identity));
+        if admitted {
+            return Ok(());
+        }
+        if !state.tokens.requires_auth() {
+            return Err(Error::new(ErrorKind::Permission).with_message(format!(
+                "client certificate '{identity}' is not allowed in this namespace"
+            )));
+        }
+    } else if !state.tokens.requires_auth() {
         return Ok(());
-    };
+    }
     let Some(value) = headers.get(axum::http::header::AUTHORIZATION) else {
         return Err(Error::new(ErrorKind::Permission).with_message("missing bearer token"));
     };
     let value = value.to_str().unwrap_or_default();
-    let expected = format!("Bearer {token}");
-    if value != expected {
+    let presented = value.strip_prefix("Bearer ").unwrap_or_default();
+    if presented.is_empty() || !state.tokens.accepts(presented) {
         return Err(Error::new(ErrorKind::Permission).with_message("invalid bearer token"));
     }
     Ok(())
@@ -620,6 +1342,59 @@ fn ensure_write_access(state: &AppState) -> Result<(), Error> {
     }
 }
 
+fn caller(headers: &HeaderMap) -> Caller<'_> {
+    Caller {
+        token: headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer ")),
+        identity: client_identity(headers),
+    }
+}
+
+/// Effective access to one pool: the route's access mode, narrowed by `--pool-policy`.
+fn pool_access(state: &AppState, headers: &HeaderMap, pool: &str) -> Option<AccessMode> {
+    match &state.policy {
+        Some(policy) => intersect_access(state.access_mode, policy.access(caller(headers), pool)),
+        None => Some(state.access_mode),
+    }
+}
+
+fn ensure_pool_read(state: &AppState, headers: &HeaderMap, pool: &str) -> Result<(), Error> {
+    if pool_access(state, headers, pool).is_some_and(AccessMode::allows_read) {
+        Ok(())
+    } else {
+        Err(pool_policy_error(pool, "reads"))
+    }
+}
+
+fn ensure_pool_write(state: &AppState, headers: &HeaderMap, pool: &str) -> Result<(), Error> {
+    if pool_access(state, headers, pool).is_some_and(AccessMode::allows_write) {
+        Ok(())
+    } else {
+        Err(pool_policy_error(pool, "writes"))
+    }
+}
+
+fn pool_policy_error(pool: &str, action: &str) -> Error {
+    Error::new(ErrorKind::Permission)
+        .with_message(format!(
+            "forbidden: pool policy disallows {action} to '{pool}'"
+        ))
+        .with_hint("Ask the server operator to grant this token access in --pool-policy.")
+}
+
+fn permissions_json(access: AccessMode) -> Value {
+    let mut permissions = Vec::new();
+    if access.allows_read() {
+        permissions.push("read");
+    }
+    if access.allows_write() {
+        permissions.push("write");
+    }
+    json!(permissions)
+}
+
 fn access_error(action: &str) -> Error {
     Error::new(ErrorKind::Permission)
         .with_message(format!("forbidden: access mode disallows {action}"))
@@ -642,15 +1417,27 @@ struct AppendRequest {
     data: serde_json::Value,
     tags: Option<Vec<String>>,
     durability: Option<String>,
+    /// Frame flag names (`control`, `user0`, ...).
+    flags: Option<Vec<String>>,
+    /// Base64 attachment bytes; `data` must reference them (see `attachment_ref`).
+    attachment: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct TailQuery {
     since_seq: Option<u64>,
+    /// Consistency token from an append response: hold the stream until this seq exists.
+    min_seq: Option<u64>,
     max: Option<u64>,
     timeout_ms: Option<u64>,
+    max_batch: Option<u64>,
 }
 
+/// Messages coalesced into one streamed chunk when the client does not pass `max_batch`.
+const DEFAULT_TAIL_MAX_BATCH: usize = 256;
+/// Upper bound on `max_batch`, keeping per-chunk memory bounded.
+const MAX_TAIL_MAX_BATCH: usize = 4096;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum TailStreamEncoding {
     Jsonl,
@@ -661,6 +1448,9 @@ enum TailStreamEncoding {
 struct TailRuntime {
     permit: OwnedSemaphorePermit,
     options: TailOptions,
+    max_batch: usize,
+    /// `where` predicates (UI event stream only); empty streams every message.
+    where_exprs: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -668,6 +1458,19 @@ struct AppendLite3Query {
     durability: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct AppendBatchQuery {
+    durability: Option<String>,
+}
+
+/// One JSONL line of an `append_batch` body.
+#[derive(Debug, Deserialize)]
+struct AppendBatchRecord {
+    data: serde_json::Value,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
 async fn healthz() -> Response {
     json_response(json!({ "ok": true }))
 }
@@ -699,7 +1502,22 @@ async fn mcp_post(
         return accepted_response();
     }
 
-    let handler = ServeMcpHandler::new(state.client.clone(), state.access_mode);
+    let access_mode = match &state.policy {
+        Some(policy) => {
+            match intersect_access(state.access_mode, policy.access_to_all(caller(&headers))) {
+                Some(access_mode) => access_mode,
+                None => {
+                    return error_response(
+                        Error::new(ErrorKind::Permission)
+                            .with_message("forbidden: pool policy grants no access to all pools")
+                            .with_hint("MCP can name any pool; grant it with a `*` pool rule in --pool-policy."),
+                    );
+                }
+            }
+        }
+        None => state.access_mode,
+    };
+    let handler = ServeMcpHandler::new(state.client.clone(), access_mode);
     let mut dispatcher = McpDispatcher::new(handler);
     match dispatcher.dispatch_value(payload) {
         DispatchOutcome::NoResponse => accepted_response(),
@@ -883,6 +1701,10 @@ struct ErrorBody {
     seq: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     offset: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    causes: Vec<String>,
 }
 
 async fn create_pool(
@@ -896,11 +1718,17 @@ async fn create_pool(
     if let Err(err) = ensure_write_access(&state) {
         return error_response(err);
     }
-    let pool_ref = match pool_ref_from_request(&payload.pool) {
+    let pool_ref = match pool_ref_from_request(&state, &payload.pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
+    if let Err(err) = ensure_pool_write(&state, &headers, &payload.pool) {
+        return error_response(err);
+    }
     let size_bytes = payload.size_bytes.unwrap_or(1024 * 1024);
+    if let Err(response) = enforce_create_quota(&state, size_bytes) {
+        return response;
+    }
     let result = state
         .client
         .create_pool(&pool_ref, PoolOptions::new(size_bytes));
@@ -921,10 +1749,13 @@ async fn open_pool(
     if let Err(err) = ensure_read_access(&state) {
         return error_response(err);
     }
-    let pool_ref = match pool_ref_from_request(&payload.pool) {
+    let pool_ref = match pool_ref_from_request(&state, &payload.pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
+    if let Err(err) = ensure_pool_read(&state, &headers, &payload.pool) {
+        return error_response(err);
+    }
     match state.client.pool_info(&pool_ref) {
         Ok(info) => json_response(json!({ "pool": pool_info_json(&payload.pool, &info) })),
         Err(err) => error_response(err),
@@ -942,92 +1773,323 @@ async fn pool_info(
     if let Err(err) = ensure_read_access(&state) {
         return error_response(err);
     }
-    let pool_ref = match pool_ref_from_request(&pool) {
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
+    if let Err(err) = ensure_pool_read(&state, &headers, &pool) {
+        return error_response(err);
+    }
     match state.client.pool_info(&pool_ref) {
         Ok(info) => json_response(json!({ "pool": pool_info_json(&pool, &info) })),
         Err(err) => error_response(err),
     }
 }
 
-async fn list_pools(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
-    if let Err(err) = authorize(&headers, &state) {
-        return error_response(err);
-    }
-    if let Err(err) = ensure_read_access(&state) {
-        return error_response(err);
-    }
-    match state.client.list_pools() {
-        Ok(pools) => {
-            let mut out = Vec::new();
-            for info in pools {
-                let name = info
-                    .path
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .unwrap_or_default()
-                    .trim_end_matches(".plasmite")
-                    .to_string();
-                out.push(pool_info_json(&name, &info));
-            }
-            json_response(json!({ "pools": out }))
-        }
-        Err(err) => error_response(err),
-    }
+#[derive(Debug, Deserialize)]
+struct ValidateQuery {
+    depth: Option<String>,
+    max_frames: Option<u64>,
 }
 
-async fn delete_pool(
+/// Payloads a deep `validate` decodes when the client does not pass `max_frames`.
+const DEFAULT_VALIDATE_MAX_FRAMES: u64 = 10_000;
+/// Upper bound on `max_frames`, keeping one request from scanning an arbitrarily large pool.
+const MAX_VALIDATE_MAX_FRAMES: u64 = 1_000_000;
+
+/// Doctor checks for one pool; `depth=deep` also decodes the newest `max_frames` payloads.
+async fn validate_pool(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     AxumPath(pool): AxumPath<String>,
+    Query(query): Query<ValidateQuery>,
 ) -> Response {
     if let Err(err) = authorize(&headers, &state) {
         return error_response(err);
     }
-    if let Err(err) = ensure_write_access(&state) {
+    if let Err(err) = ensure_read_access(&state) {
         return error_response(err);
     }
-    let pool_ref = match pool_ref_from_request(&pool) {
+    let deep = match query.depth.as_deref() {
+        None | Some("quick") => false,
+        Some("deep") => true,
+        Some(other) => {
+            return error_response(
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid depth '{other}'"))
+                    .with_hint("Use depth=quick or depth=deep."),
+            );
+        }
+    };
+    let max_frames = query
+        .max_frames
+        .unwrap_or(DEFAULT_VALIDATE_MAX_FRAMES)
+        .clamp(1, MAX_VALIDATE_MAX_FRAMES);
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
-    match state.client.delete_pool(&pool_ref) {
-        Ok(()) => json_response(json!({ "ok": true })),
-        Err(err) => error_response(err),
+    if let Err(err) = ensure_pool_read(&state, &headers, &pool) {
+        return error_response(err);
+    }
+    let (client, deadline) = state.request_client();
+    // Dropped with this handler when the caller disconnects, stopping the scan it started.
+    let _cancel = deadline.cancel_on_drop();
+    let result = tokio::task::spawn_blocking(move || {
+        if deep {
+            client.validate_pool_deep_bounded(&pool_ref, Some(max_frames))
+        } else {
+            client.validate_pool(&pool_ref)
+        }
+    })
+    .await;
+    match result {
+        Ok(Ok(report)) => json_response(json!({ "report": report.with_pool_ref(pool).to_json() })),
+        Ok(Err(err)) => error_response(err),
+        Err(join_err) => error_response(
+            Error::new(ErrorKind::Internal)
+                .with_message("validation task failed")
+                .with_source(join_err),
+        ),
     }
 }
 
-async fn append_message(
-    State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
-    AxumPath(pool): AxumPath<String>,
-    Json(payload): Json<AppendRequest>,
-) -> Response {
+async fn quota_status(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
     if let Err(err) = authorize(&headers, &state) {
         return error_response(err);
     }
-    if let Err(err) = ensure_write_access(&state) {
-        return error_response(err);
+    match state.client.list_pools() {
+        Ok(pools) => json_response(json!({
+            "quota": state.quota.quota().to_json(),
+            "usage": QuotaUsage::from_pools(&pools).to_json(),
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn pool_cache_status(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    let stats = state.client.pool_cache_stats().unwrap_or_default();
+    json_response(json!({
+        "pool_cache": {
+            "capacity": stats.capacity,
+            "len": stats.len,
+            "hits": stats.hits,
+            "misses": stats.misses,
+            "evictions": stats.evictions,
+            "invalidations": stats.invalidations,
+        }
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct RouteKeyQuery {
+    from_pool: String,
+    to_pool: String,
+}
+
+async fn put_route(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(route): Json<Route>,
+) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_write_access(&state) {
+        return error_response(err);
+    }
+    for pool in [&route.from_pool, &route.to_pool] {
+        if let Err(err) = pool_ref_from_request(&state, pool) {
+            return error_response(err);
+        }
+    }
+    if let Err(err) = ensure_pool_read(&state, &headers, &route.from_pool) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_pool_write(&state, &headers, &route.to_pool) {
+        return error_response(err);
+    }
+    match state.routes.upsert(route) {
+        Ok(route) => json_response(json!({ "route": route })),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn list_routes(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_read_access(&state) {
+        return error_response(err);
+    }
+    json_response(json!({ "routes": state.routes.list() }))
+}
+
+async fn delete_route(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<RouteKeyQuery>,
+) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_write_access(&state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_pool_write(&state, &headers, &query.to_pool) {
+        return error_response(err);
+    }
+    match state.routes.remove(&query.from_pool, &query.to_pool) {
+        Ok(()) => json_response(json!({ "ok": true })),
+        Err(err) => error_response(err),
+    }
+}
+
+/// Reject a pool create that would exceed the pool-count or byte quota.
+/// Concurrent creates can overshoot by the pools racing with this check.
+fn enforce_create_quota(state: &AppState, size_bytes: u64) -> Result<(), Response> {
+    let quota = state.quota.quota();
+    if quota.max_pools.is_none() && quota.max_bytes.is_none() {
+        return Ok(());
+    }
+    let pools = state.client.list_pools().map_err(error_response)?;
+    state
+        .quota
+        .check_create(QuotaUsage::from_pools(&pools), size_bytes)
+        .map_err(quota_violation_response)
+}
+
+fn quota_violation_response(violation: QuotaViolation) -> Response {
+    match violation {
+        QuotaViolation::Rate { error, retry_after } => {
+            let mut response = error_response_with_status(error, StatusCode::TOO_MANY_REQUESTS);
+            let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            if let Ok(value) = HeaderValue::from_str(&seconds.to_string()) {
+                response.headers_mut().insert(header::RETRY_AFTER, value);
+            }
+            response
+        }
+        QuotaViolation::Storage(error) => {
+            error_response_with_status(error, StatusCode::INSUFFICIENT_STORAGE)
+        }
+    }
+}
+
+async fn list_pools(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_read_access(&state) {
+        return error_response(err);
+    }
+    let (client, _) = state.request_client();
+    match client.list_pools() {
+        Ok(pools) => {
+            let mut out = Vec::new();
+            for info in pools {
+                let name =
+                    pool_name_for_path(state.client.pool_dir(), &info.path).unwrap_or_default();
+                // Pools the caller cannot read are left out, not just marked, so a
+                // read-limited token never learns the rest of the inventory.
+                let Some(access) =
+                    pool_access(&state, &headers, &name).filter(|access| access.allows_read())
+                else {
+                    continue;
+                };
+                let mut pool = pool_info_json(&name, &info);
+                pool["permissions"] = permissions_json(access);
+                out.push(pool);
+            }
+            json_response(json!({ "pools": out }))
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+async fn delete_pool(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(pool): AxumPath<String>,
+) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_write_access(&state) {
+        return error_response(err);
+    }
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
+        Ok(pool_ref) => pool_ref,
+        Err(err) => return error_response(err),
+    };
+    if let Err(err) = ensure_pool_write(&state, &headers, &pool) {
+        return error_response(err);
+    }
+    match state.client.delete_pool(&pool_ref) {
+        Ok(()) => json_response(json!({ "ok": true })),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn append_message(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(pool): AxumPath<String>,
+    Json(payload): Json<AppendRequest>,
+) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_write_access(&state) {
+        return error_response(err);
     }
-    let pool_ref = match pool_ref_from_request(&pool) {
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
+    if let Err(err) = ensure_pool_write(&state, &headers, &pool) {
+        return error_response(err);
+    }
+    if let Err(violation) = state.quota.take_appends(1) {
+        return quota_violation_response(violation);
+    }
     let durability = durability_from_str(payload.durability.as_deref());
     let tags = payload.tags.unwrap_or_default();
+    let flags = match parse_frame_flags(payload.flags.as_deref().unwrap_or_default()) {
+        Ok(flags) => flags,
+        Err(err) => return error_response(err),
+    };
+    let attachment = match payload.attachment.as_deref().map(base64_decode).transpose() {
+        Ok(attachment) => attachment,
+        Err(err) => return error_response(err),
+    };
+    let transforms = state.transforms.chain(&pool);
 
-    let result = state
-        .client
-        .open_pool(&pool_ref)
-        .and_then(|mut pool| pool.append_json_now(&payload.data, &tags, durability));
+    let result = crate::now_ns().and_then(|timestamp_ns| {
+        let data = match &transforms {
+            Some(chain) => chain.apply(payload.data, timestamp_ns)?,
+            None => payload.data,
+        };
+        let options = AppendOptions::new(timestamp_ns, durability).with_flags(flags);
+        let append = || tracing::info_span!("append");
+        with_pool_traced(&state.client, &pool_ref, append, |pool| match &attachment {
+            Some(attachment) => pool.append_json_with_attachment(&data, &tags, attachment, options),
+            None => pool.append_json(&data, &tags, options),
+        })
+    });
     match result {
-        Ok(message) => json_response(json!({ "message": message_json(&message) })),
+        Ok(message) => json_response(append_response_json(&message)),
         Err(err) => error_response(err),
     }
 }
 
+/// `{message, min_seq}`; `min_seq` is the read-your-writes token tails accept.
+fn append_response_json(message: &plasmite::api::Message) -> Value {
+    json!({ "message": message_json(message), "min_seq": message.seq })
+}
+
 async fn append_lite3(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
@@ -1056,18 +2118,244 @@ async fn append_lite3(
             Error::new(ErrorKind::Usage).with_message("lite3 payload is required"),
         );
     }
-    let pool_ref = match pool_ref_from_request(&pool) {
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
+    if let Err(err) = ensure_pool_write(&state, &headers, &pool) {
+        return error_response(err);
+    }
+    if state.transforms.chain(&pool).is_some() {
+        return error_response(
+            Error::new(ErrorKind::Usage)
+                .with_message("lite3 appends cannot be transformed")
+                .with_hint(
+                    "This pool has ingest transforms; send JSON to /append or /append_batch.",
+                ),
+        );
+    }
+    if let Err(violation) = state.quota.take_appends(1) {
+        return quota_violation_response(violation);
+    }
     let durability = durability_from_str(query.durability.as_deref());
     let payload = payload.to_vec();
-    let result = state.client.open_pool(&pool_ref).and_then(|mut pool| {
+    let append = || tracing::info_span!("append", bytes = payload.len());
+    let result = with_pool_traced(&state.client, &pool_ref, append, |pool| {
         let seq = pool.append_lite3_now(&payload, durability)?;
         pool.get_message(seq)
     });
     match result {
-        Ok(message) => json_response(json!({ "message": message_json(&message) })),
+        Ok(message) => json_response(append_response_json(&message)),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn append_batch(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    AxumPath(pool): AxumPath<String>,
+    Query(query): Query<AppendBatchQuery>,
+    payload: Bytes,
+) -> Response {
+    if let Err(err) = authorize(&headers, &state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_write_access(&state) {
+        return error_response(err);
+    }
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
+        Ok(pool_ref) => pool_ref,
+        Err(err) => return error_response(err),
+    };
+    if let Err(err) = ensure_pool_write(&state, &headers, &pool) {
+        return error_response(err);
+    }
+    let records = payload
+        .split(|byte| *byte == b'\n')
+        .filter(|line| !line.trim_ascii().is_empty())
+        .count();
+    if let Err(violation) = state.quota.take_appends(records as u64) {
+        return quota_violation_response(violation);
+    }
+    let pool_name = pool;
+    let pool = match tracing::info_span!("pool_open", pool = %pool_name)
+        .in_scope(|| state.client.open_pool(&pool_ref))
+    {
+        Ok(pool) => pool,
+        Err(err) => return error_response(err),
+    };
+    let durability = durability_from_str(query.durability.as_deref());
+    let transforms = state.transforms.chain(&pool_name);
+
+    let (tx, rx) = mpsc::channel::<Bytes>(64);
+    let span = tracing::info_span!("append", records);
+    tokio::task::spawn_blocking(move || {
+        span.in_scope(|| {
+            append_batch_records(pool, &payload, durability, transforms.as_deref(), tx)
+        })
+    });
+    let stream = ReceiverStream::new(rx).map(Ok::<_, std::io::Error>);
+    let mut response = Response::new(Body::from_stream(stream));
+    apply_tail_response_headers(&mut response, TailStreamEncoding::Jsonl);
+    response
+        .headers_mut()
+        .insert("plasmite-version", HeaderValue::from_static("0"));
+    response
+}
+
+/// Append each non-blank JSONL record in order, sending its receipt as soon as it commits.
+/// A failed record yields an `error` receipt; later records are still attempted.
+fn append_batch_records(
+    mut pool: plasmite::api::Pool,
+    payload: &[u8],
+    durability: Durability,
+    transforms: Option<&TransformChain>,
+    tx: mpsc::Sender<Bytes>,
+) {
+    let records = payload
+        .split(|byte| *byte == b'\n')
+        .filter(|line| !line.trim_ascii().is_empty());
+    for (index, line) in records.enumerate() {
+        let result = serde_json::from_slice::<AppendBatchRecord>(line)
+            .map_err(|err| {
+                Error::new(ErrorKind::Usage)
+                    .with_message("invalid append batch record")
+                    .with_hint("Send one {\"data\": ..., \"tags\": [...]} object per line.")
+                    .with_source(err)
+            })
+            .and_then(|record| {
+                let data = match transforms {
+                    Some(chain) => chain.apply(record.data, crate::now_ns()?)?,
+                    None => record.data,
+                };
+                pool.append_json_now(&data, &record.tags, durability)
+            });
+        let receipt = match result {
+            Ok(message) => json!({ "index": index, "message": message_json(&message) }),
+            Err(err) => json!({ "index": index, "error": error_body(&err) }),
+        };
+        let mut line = receipt.to_string().into_bytes();
+        line.push(b'\n');
+        if tx.blocking_send(Bytes::from(line)).is_err() {
+            break;
+        }
+    }
+}
+
+async fn otlp_logs(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    payload: Bytes,
+) -> Response {
+    otlp_export(&state, &headers, OtlpSignal::Logs, &payload)
+}
+
+async fn otlp_traces(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    payload: Bytes,
+) -> Response {
+    otlp_export(&state, &headers, OtlpSignal::Traces, &payload)
+}
+
+fn otlp_export(
+    state: &AppState,
+    headers: &HeaderMap,
+    signal: OtlpSignal,
+    payload: &[u8],
+) -> Response {
+    if let Err(err) = authorize(headers, state) {
+        return error_response(err);
+    }
+    if let Err(err) = ensure_write_access(state) {
+        return error_response(err);
+    }
+    // Only OTLP/HTTP JSON is decoded; protobuf (the usual SDK default) and compressed bodies
+    // are rejected up front.
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if !content_type.starts_with("application/json") {
+        let received = if content_type.is_empty() {
+            "no content type".to_string()
+        } else {
+            format!("'{content_type}'")
+        };
+        return error_response_with_status(
+            Error::new(ErrorKind::Usage)
+                .with_message(format!(
+                    "OTLP receiver accepts OTLP/HTTP JSON (application/json) only, got {received}"
+                ))
+                .with_hint(
+                    "Set OTEL_EXPORTER_OTLP_PROTOCOL=http/json on the exporter; http/protobuf is not supported.",
+                ),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        );
+    }
+    if headers.contains_key(header::CONTENT_ENCODING) {
+        return error_response_with_status(
+            Error::new(ErrorKind::Usage)
+                .with_message("OTLP receiver does not accept compressed bodies")
+                .with_hint(
+                    "Disable exporter compression (e.g. OTEL_EXPORTER_OTLP_COMPRESSION=none).",
+                ),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        );
+    }
+    let body: Value = match serde_json::from_slice(payload) {
+        Ok(body) => body,
+        Err(err) => {
+            return error_response(
+                Error::new(ErrorKind::Usage)
+                    .with_message("invalid OTLP JSON body")
+                    .with_source(err),
+            );
+        }
+    };
+    let records = match records_from_export(signal, &body) {
+        Ok(records) => records,
+        Err(err) => return error_response(err),
+    };
+    if records.is_empty() {
+        return json_response(json!({}));
+    }
+
+    if let Err(violation) = state.quota.take_appends(records.len() as u64) {
+        return quota_violation_response(violation);
+    }
+
+    let pool_ref = PoolRef::name(signal.pool_name());
+    if let Err(err) = ensure_pool_write(state, headers, signal.pool_name()) {
+        return error_response(err);
+    }
+    let append = |pool: &mut plasmite::api::Pool| -> Result<(), Error> {
+        for record in &records {
+            pool.append_json_now(&record.data, &record.tags, Durability::Fast)?;
+        }
+        Ok(())
+    };
+    let span = || tracing::info_span!("append", records = records.len());
+    let result = match with_pool_traced(&state.client, &pool_ref, span, append) {
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            if let Err(response) = enforce_create_quota(state, OTLP_POOL_SIZE) {
+                return response;
+            }
+            match state
+                .client
+                .create_pool(&pool_ref, PoolOptions::new(OTLP_POOL_SIZE))
+            {
+                Ok(_) => {}
+                // Another export may have created the pool concurrently.
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => {}
+                Err(err) => return error_response(err),
+            }
+            with_pool_traced(&state.client, &pool_ref, span, append)
+        }
+        other => other,
+    };
+    match result {
+        Ok(()) => json_response(json!({})),
         Err(err) => error_response(err),
     }
 }
@@ -1083,14 +2371,15 @@ async fn get_message(
     if let Err(err) = ensure_read_access(&state) {
         return error_response(err);
     }
-    let pool_ref = match pool_ref_from_request(&pool) {
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
-    let result = state
-        .client
-        .open_pool(&pool_ref)
-        .and_then(|pool| pool.get_message(seq));
+    if let Err(err) = ensure_pool_read(&state, &headers, &pool) {
+        return error_response(err);
+    }
+    let read = || tracing::info_span!("read", seq);
+    let result = with_pool_traced(&state.client, &pool_ref, read, |pool| pool.get_message(seq));
 
     match result {
         Ok(message) => json_response(json!({ "message": message_json(&message) })),
@@ -1109,11 +2398,15 @@ async fn get_lite3(
     if let Err(err) = ensure_read_access(&state) {
         return error_response(err);
     }
-    let pool_ref = match pool_ref_from_request(&pool) {
+    let pool_ref = match pool_ref_from_request(&state, &pool) {
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
-    let result = state.client.open_pool(&pool_ref).and_then(|pool| {
+    if let Err(err) = ensure_pool_read(&state, &headers, &pool) {
+        return error_response(err);
+    }
+    let read = || tracing::info_span!("read", seq);
+    let result = with_pool_traced(&state.client, &pool_ref, read, |pool| {
         let frame = pool.get_lite3(seq)?;
         let payload = frame.payload.to_vec();
         lite3::validate_bytes(&payload)?;
@@ -1190,13 +2483,54 @@ async fn ui_events(
         Ok(pool_ref) => pool_ref,
         Err(err) => return error_response(err),
     };
-    let runtime = match prepare_tail_runtime(&state, &query, raw_query.as_deref()) {
+    let mut runtime = match prepare_tail_runtime(&state, &query, raw_query.as_deref()) {
         Ok(runtime) => runtime,
         Err(err) => return error_response(err),
     };
+    if let Err(err) = apply_ui_filters(&state, &pool_ref, &mut runtime, raw_query.as_deref()) {
+        return error_response(err);
+    }
     spawn_tail_stream_response(&state, pool_ref, runtime, TailStreamEncoding::Sse)
 }
 
+/// UI-only tail filters: repeated `where` predicates (jq, matched against the message
+/// envelope) and `since` (RFC 3339 or relative like `5m`), which resolves to a starting seq
+/// unless the client already resumes with `since_seq`.
+fn apply_ui_filters(
+    state: &AppState,
+    pool_ref: &PoolRef,
+    runtime: &mut TailRuntime,
+    raw_query: Option<&str>,
+) -> Result<(), Error> {
+    let pairs = raw_query
+        .map(|raw| url::form_urlencoded::parse(raw.as_bytes()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let where_exprs = pairs
+        .iter()
+        .filter(|(key, value)| key == "where" && !value.trim().is_empty())
+        .map(|(_, value)| value.to_string())
+        .collect::<Vec<_>>();
+    compile_filters(&where_exprs)?;
+    runtime.where_exprs = where_exprs;
+
+    let since = pairs
+        .iter()
+        .find(|(key, value)| key == "since" && !value.trim().is_empty());
+    if let (Some((_, since)), None) = (since, runtime.options.since_seq) {
+        let timestamp_ns = crate::parse_since(since, crate::now_ns()?)?;
+        let pool = state.client.open_pool(pool_ref)?;
+        runtime.options.since_seq = match pool.get_at_time(timestamp_ns, TimeNearest::After) {
+            Ok(frame) => Some(frame.seq),
+            // Everything retained is older: start after the newest message.
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                pool.bounds()?.newest_seq.map(|seq| seq + 1)
+            }
+            Err(err) => return Err(err),
+        };
+    }
+    Ok(())
+}
+
 fn tail_pool_ref_from_request(
     state: &Arc<AppState>,
     headers: &HeaderMap,
@@ -1204,7 +2538,9 @@ fn tail_pool_ref_from_request(
 ) -> Result<PoolRef, Error> {
     authorize(headers, state)?;
     ensure_read_access(state)?;
-    pool_ref_from_request(pool)
+    let pool_ref = pool_ref_from_request(state, pool)?;
+    ensure_pool_read(state, headers, pool)?;
+    Ok(pool_ref)
 }
 
 fn precheck_lite3_since_seq(
@@ -1240,15 +2576,34 @@ fn prepare_tail_runtime(
             .with_message("tail timeout exceeds server limit")
             .with_hint(format!("Use timeout_ms <= {}.", state.max_tail_timeout_ms)));
     }
+    let max_batch = parse_tail_max_batch(query.max_batch)?;
     let timeout_ms = query.timeout_ms.unwrap_or(state.max_tail_timeout_ms);
     let options = TailOptions {
         since_seq: query.since_seq,
+        min_seq: query.min_seq,
         max_messages: query.max.map(|value| value as usize),
         tags: parse_tags_from_query(raw_query),
         timeout: Some(Duration::from_millis(timeout_ms)),
         ..TailOptions::default()
     };
-    Ok(TailRuntime { permit, options })
+    Ok(TailRuntime {
+        permit,
+        options,
+        max_batch,
+        where_exprs: Vec::new(),
+    })
+}
+
+fn parse_tail_max_batch(value: Option<u64>) -> Result<usize, Error> {
+    match value {
+        None => Ok(DEFAULT_TAIL_MAX_BATCH),
+        Some(value) if value == 0 || value > MAX_TAIL_MAX_BATCH as u64 => {
+            Err(Error::new(ErrorKind::Usage)
+                .with_message("max_batch out of range")
+                .with_hint(format!("Use 1 <= max_batch <= {MAX_TAIL_MAX_BATCH}.")))
+        }
+        Some(value) => Ok(value as usize),
+    }
 }
 
 fn acquire_tail_permit(state: &Arc<AppState>) -> Result<OwnedSemaphorePermit, Error> {
@@ -1272,13 +2627,22 @@ fn spawn_tail_stream_response(
     encoding: TailStreamEncoding,
 ) -> Response {
     let client = state.client.clone();
-    let TailRuntime { permit, options } = runtime;
+    let TailRuntime {
+        permit,
+        options,
+        max_batch,
+        where_exprs,
+    } = runtime;
     let (tx, rx) = mpsc::channel::<Result<Bytes, Error>>(16);
+    // Tail spans stay under the request's span while the stream runs on a blocking thread.
+    let request_span = tracing::Span::current();
     tokio::task::spawn_blocking(move || {
         let _permit = permit;
-        let result = client
-            .open_pool(&pool_ref)
-            .and_then(|pool| stream_tail_bytes(&pool, options, encoding, tx.clone()));
+        let _request = request_span.enter();
+        let result = compile_filters(&where_exprs).and_then(|filters| {
+            let pool = tracing::info_span!("pool_open").in_scope(|| client.open_pool(&pool_ref))?;
+            stream_tail_bytes(&pool, options, &filters, encoding, max_batch, tx.clone())
+        });
         if let Err(err) = result {
             let _ = tx.blocking_send(Err(err));
         }
@@ -1299,32 +2663,71 @@ fn spawn_tail_stream_response(
     response
 }
 
+/// Stream tail output in chunks: block for the first committed message, then append every
+/// already-committed message (up to `max_batch`) before sending. Each chunk is one flush.
+/// Messages failing `filters` are skipped; a chunk with nothing left is not sent.
 fn stream_tail_bytes(
     pool: &plasmite::api::Pool,
     options: TailOptions,
+    filters: &[JqFilter],
     encoding: TailStreamEncoding,
+    max_batch: usize,
     tx: mpsc::Sender<Result<Bytes, Error>>,
 ) -> Result<(), Error> {
+    let mut chunk = Vec::new();
     match encoding {
         TailStreamEncoding::Jsonl | TailStreamEncoding::Sse => {
             let mut tail = pool.tail(options);
-            while let Some(message) = tail.next_message()? {
-                let encoded = match encoding {
-                    TailStreamEncoding::Jsonl => encode_jsonl_message(&message)?,
-                    TailStreamEncoding::Sse => encode_sse_message(&message)?,
-                    TailStreamEncoding::Lite3 => unreachable!("handled in separate branch"),
+            let wanted = |message: &plasmite::api::Message| {
+                filters.is_empty() || matches_all(filters, &message_json(message)).unwrap_or(false)
+            };
+            loop {
+                let wait = tracing::info_span!("tail_wait").entered();
+                let Some(message) = tail.next_message()? else {
+                    break;
                 };
-                if tx.blocking_send(Ok(encoded)).is_err() {
+                drop(wait);
+                if wanted(&message) {
+                    append_tail_message(&mut chunk, &message, encoding)?;
+                }
+                for _ in 1..max_batch {
+                    let Some(message) = tail.try_next_message()? else {
+                        break;
+                    };
+                    if wanted(&message) {
+                        append_tail_message(&mut chunk, &message, encoding)?;
+                    }
+                }
+                if chunk.is_empty() {
+                    continue;
+                }
+                if tx
+                    .blocking_send(Ok(Bytes::from(std::mem::take(&mut chunk))))
+                    .is_err()
+                {
                     break;
                 }
             }
         }
         TailStreamEncoding::Lite3 => {
             let mut tail = pool.tail_lite3(options);
-            while let Some(frame) = tail.next_frame()? {
-                lite3::validate_bytes(frame.payload)?;
-                let encoded = encode_lite3_stream_frame(&frame)?;
-                if tx.blocking_send(Ok(encoded)).is_err() {
+            loop {
+                let wait = tracing::info_span!("tail_wait").entered();
+                let Some(frame) = tail.next_frame()? else {
+                    break;
+                };
+                drop(wait);
+                append_lite3_frame(&mut chunk, &frame)?;
+                for _ in 1..max_batch {
+                    let Some(frame) = tail.try_next_frame()? else {
+                        break;
+                    };
+                    append_lite3_frame(&mut chunk, &frame)?;
+                }
+                if tx
+                    .blocking_send(Ok(Bytes::from(std::mem::take(&mut chunk))))
+                    .is_err()
+                {
                     break;
                 }
             }
@@ -1333,6 +2736,29 @@ fn stream_tail_bytes(
     Ok(())
 }
 
+fn append_tail_message(
+    chunk: &mut Vec<u8>,
+    message: &plasmite::api::Message,
+    encoding: TailStreamEncoding,
+) -> Result<(), Error> {
+    let encoded = match encoding {
+        TailStreamEncoding::Jsonl => encode_jsonl_message(message)?,
+        TailStreamEncoding::Sse => encode_sse_message(message)?,
+        TailStreamEncoding::Lite3 => unreachable!("lite3 frames use append_lite3_frame"),
+    };
+    chunk.extend_from_slice(&encoded);
+    Ok(())
+}
+
+fn append_lite3_frame(
+    chunk: &mut Vec<u8>,
+    frame: &plasmite::api::FrameRef<'_>,
+) -> Result<(), Error> {
+    lite3::validate_bytes(frame.payload)?;
+    chunk.extend_from_slice(&encode_lite3_stream_frame(frame)?);
+    Ok(())
+}
+
 fn encode_message_payload(message: &plasmite::api::Message) -> Result<Vec<u8>, Error> {
     serde_json::to_vec(&message_json(message)).map_err(|err| {
         Error::new(ErrorKind::Internal)
@@ -1404,7 +2830,11 @@ fn apply_tail_response_headers(response: &mut Response, encoding: TailStreamEnco
     }
 }
 
-fn pool_ref_from_request(pool: &str) -> Result<PoolRef, Error> {
+fn pool_ref_from_request(state: &AppState, pool: &str) -> Result<PoolRef, Error> {
+    // A namespaced pool is absent from the root, not a malformed name.
+    if state.client.hides_name(pool) {
+        return Err(Error::new(ErrorKind::NotFound).with_message("pool not found"));
+    }
     if pool.contains('/') {
         return Err(
             Error::new(ErrorKind::Usage).with_message("pool name must not contain path separators")
@@ -1414,12 +2844,7 @@ fn pool_ref_from_request(pool: &str) -> Result<PoolRef, Error> {
 }
 
 fn message_json(message: &plasmite::api::Message) -> serde_json::Value {
-    json!({
-        "seq": message.seq,
-        "time": message.time.clone(),
-        "meta": { "tags": message.meta.tags.clone() },
-        "data": message.data.clone(),
-    })
+    message.to_json()
 }
 
 fn normalize_tags(raw: Vec<String>) -> Vec<String> {
@@ -1521,6 +2946,66 @@ fn error_body(err: &Error) -> ErrorBody {
         path: err.path().map(|path| path.to_string_lossy().to_string()),
         seq: err.seq(),
         offset: err.offset(),
+        hint: err.hint().map(str::to_string),
+        causes: err.causes(),
+    }
+}
+
+/// Upper bound on framework error text copied into an envelope message.
+const PLAIN_ERROR_BODY_LIMIT: usize = 4096;
+
+/// Rewrap non-JSON error responses (extractor rejections, body limits, unknown routes) in the
+/// standard error envelope so clients see one error shape for every failure.
+async fn envelope_plain_errors(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let response = next.run(request).await;
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !(status.is_client_error() || status.is_server_error()) || is_json {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let text = match axum::body::to_bytes(body, PLAIN_ERROR_BODY_LIMIT).await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).trim().to_string(),
+        Err(_) => String::new(),
+    };
+    let mut err = Error::new(error_kind_for_status(status));
+    err = if text.is_empty() {
+        err.with_message(
+            status
+                .canonical_reason()
+                .unwrap_or("request failed")
+                .to_ascii_lowercase(),
+        )
+    } else {
+        err.with_message(text)
+    };
+    if status == StatusCode::PAYLOAD_TOO_LARGE {
+        err = err.with_hint("Send a smaller body or raise --max-body-bytes on the server.");
+    }
+    let mut wrapped = error_response_with_status(err, status);
+    for (name, value) in parts.headers.iter() {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            wrapped.headers_mut().insert(name.clone(), value.clone());
+        }
+    }
+    wrapped
+}
+
+fn error_kind_for_status(status: StatusCode) -> ErrorKind {
+    match status.as_u16() {
+        401 | 403 => ErrorKind::Permission,
+        404 => ErrorKind::NotFound,
+        409 => ErrorKind::AlreadyExists,
+        423 | 429 | 503 => ErrorKind::Busy,
+        400..=499 => ErrorKind::Usage,
+        _ => ErrorKind::Internal,
     }
 }
 
@@ -1532,9 +3017,13 @@ fn is_access_forbidden(err: &Error) -> bool {
 #[cfg(test)]
 mod tests {
     use super::{
-        AccessMode, ErrorKind, ServeConfig, build_cors_layer, normalize_cors_origins,
-        normalize_tags, parse_tags_from_query, serve, validate_config,
+        AccessMode, AppState, CLIENT_IDENTITY_HEADER, ErrorKind, OtlpSignal, ServeConfig,
+        ServeNamespace, StatusCode, TailStreamEncoding, TokenStore, Value, authorize,
+        build_cors_layer, client_identity_from_der, is_valid_request_id, namespace_state,
+        new_request_id, normalize_cors_origins, normalize_tags, otlp_export, parse_tags_from_query,
+        parse_tail_max_batch, serve, stream_tail_bytes, validate_config,
     };
+    use crate::serve_quota::Quota;
 
     #[tokio::test]
     async fn serve_rejects_non_loopback_bind() {
@@ -1548,19 +3037,120 @@ mod tests {
             allow_non_loopback: false,
             insecure_no_tls: false,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let err = serve(config).await.expect_err("expected usage error");
         assert_eq!(err.kind(), ErrorKind::Usage);
     }
 
+    #[test]
+    fn otlp_bind_requires_loopback_and_write_access() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let mut config = ServeConfig {
+            bind: "127.0.0.1:0".parse().expect("bind"),
+            pool_dir: temp.path().to_path_buf(),
+            token: None,
+            cors_allowed_origins: Vec::new(),
+            access_mode: AccessMode::ReadWrite,
+            allow_non_loopback: false,
+            insecure_no_tls: false,
+            token_file_used: false,
+            token_file: None,
+            tls_cert: None,
+            tls_key: None,
+            tls_self_signed: false,
+            tls_client_ca: None,
+            tls_self_signed_material: None,
+            tls_fingerprint: None,
+            max_body_bytes: 1024 * 1024,
+            max_tail_timeout_ms: 30_000,
+            max_concurrent_tails: 64,
+            otlp_bind: Some("127.0.0.1:4318".parse().expect("otlp bind")),
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
+        };
+        validate_config(&config).expect("loopback otlp bind is valid");
+
+        config.otlp_bind = Some("0.0.0.0:4318".parse().expect("otlp bind"));
+        let err = validate_config(&config).expect_err("non-loopback otlp bind");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+
+        config.otlp_bind = Some("127.0.0.1:4318".parse().expect("otlp bind"));
+        config.access_mode = AccessMode::ReadOnly;
+        let err = validate_config(&config).expect_err("read-only otlp bind");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+
+    #[test]
+    fn readonly_snapshot_requires_read_only_access() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let mut config = ServeConfig {
+            bind: "127.0.0.1:0".parse().expect("bind"),
+            pool_dir: temp.path().to_path_buf(),
+            token: None,
+            cors_allowed_origins: Vec::new(),
+            access_mode: AccessMode::ReadOnly,
+            allow_non_loopback: false,
+            insecure_no_tls: false,
+            token_file_used: false,
+            token_file: None,
+            tls_cert: None,
+            tls_key: None,
+            tls_self_signed: false,
+            tls_client_ca: None,
+            tls_self_signed_material: None,
+            tls_fingerprint: None,
+            max_body_bytes: 1024 * 1024,
+            max_tail_timeout_ms: 30_000,
+            max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: vec![ServeNamespace::parse("team").expect("namespace")],
+            quota: Quota::default(),
+            readonly_snapshot: true,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
+        };
+        validate_config(&config).expect("read-only snapshot is valid");
+
+        config.namespaces = vec![ServeNamespace::parse("team,access=read-write").expect("ns")];
+        let err = validate_config(&config).expect_err("writable namespace");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+
+        config.namespaces = Vec::new();
+        config.access_mode = AccessMode::ReadWrite;
+        let err = validate_config(&config).expect_err("read-write snapshot");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+
+        config.access_mode = AccessMode::ReadOnly;
+        config.otlp_bind = Some("127.0.0.1:4318".parse().expect("otlp bind"));
+        let err = validate_config(&config).expect_err("snapshot with otlp");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+
     #[test]
     fn non_loopback_requires_allow_flag() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -1573,14 +3163,24 @@ mod tests {
             allow_non_loopback: false,
             insecure_no_tls: false,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let err = validate_config(&config).expect_err("expected usage error");
         assert_eq!(err.kind(), ErrorKind::Usage);
@@ -1598,19 +3198,51 @@ mod tests {
             allow_non_loopback: true,
             insecure_no_tls: false,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let origins = validate_config(&config).expect("config ok");
         assert!(origins.is_empty());
     }
 
+    #[test]
+    fn token_store_accepts_all_listed_tokens_and_keeps_them_on_bad_reload() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("tokens");
+        std::fs::write(&path, "new\nold\n").expect("write");
+        let store = TokenStore::new(None, Some(path.clone())).expect("store");
+        assert!(store.requires_auth());
+        assert!(store.accepts("new") && store.accepts("old"));
+        assert!(!store.accepts("other"));
+
+        std::fs::write(&path, "newer\n").expect("rotate");
+        assert_eq!(store.reload().expect("reload"), 1);
+        assert!(store.accepts("newer") && !store.accepts("old"));
+
+        std::fs::write(&path, "\n").expect("truncate");
+        assert!(store.reload().is_err());
+        assert!(store.accepts("newer"));
+
+        let open = TokenStore::new(None, None).expect("open");
+        assert!(!open.requires_auth());
+    }
+
     #[test]
     fn non_loopback_write_requires_token_file() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -1623,19 +3255,268 @@ mod tests {
             allow_non_loopback: true,
             insecure_no_tls: true,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let err = validate_config(&config).expect_err("expected usage error");
         assert_eq!(err.kind(), ErrorKind::Usage);
     }
 
+    #[test]
+    fn namespace_specs_parse_and_non_loopback_writes_need_tokens() {
+        let namespace = ServeNamespace::parse("lab-a,token-file=/etc/lab.tokens,access=read-only")
+            .expect("spec");
+        assert_eq!(namespace.name, "lab-a");
+        assert_eq!(
+            namespace.token_file.as_deref(),
+            Some(std::path::Path::new("/etc/lab.tokens"))
+        );
+        assert_eq!(namespace.access_mode, Some(AccessMode::ReadOnly));
+        let namespace = ServeNamespace::parse("lab-a,identity=alice,identity=bob").expect("spec");
+        assert_eq!(namespace.identities, ["alice", "bob"]);
+        for bad in ["", "../up", "lab,access=admin", "lab,color=red"] {
+            assert!(ServeNamespace::parse(bad).is_err(), "{bad}");
+        }
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let mut config = ServeConfig {
+            bind: "0.0.0.0:0".parse().expect("bind"),
+            pool_dir: temp.path().to_path_buf(),
+            token: None,
+            cors_allowed_origins: Vec::new(),
+            access_mode: AccessMode::ReadOnly,
+            allow_non_loopback: true,
+            insecure_no_tls: true,
+            token_file_used: false,
+            token_file: None,
+            tls_cert: None,
+            tls_key: None,
+            tls_self_signed: false,
+            tls_client_ca: None,
+            tls_self_signed_material: None,
+            tls_fingerprint: None,
+            max_body_bytes: 1024 * 1024,
+            max_tail_timeout_ms: 30_000,
+            max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: vec![ServeNamespace::parse("lab,access=read-write").expect("spec")],
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
+        };
+        let err = validate_config(&config).expect_err("namespace writes need a token");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+
+        config.namespaces =
+            vec![ServeNamespace::parse("lab,access=read-write,token-file=/t").expect("spec")];
+        validate_config(&config).expect("namespace token file authorizes writes");
+
+        let identity = ServeNamespace::parse("lab-b,access=read-write,identity=alice");
+        config.namespaces.push(identity.expect("spec"));
+        let err = validate_config(&config).expect_err("identities need a client CA");
+        assert!(
+            err.message()
+                .unwrap_or_default()
+                .contains("--tls-client-ca")
+        );
+        config.namespaces.pop();
+
+        config.namespaces.push(config.namespaces[0].clone());
+        let err = validate_config(&config).expect_err("duplicate namespace");
+        assert!(err.message().unwrap_or_default().contains("duplicate"));
+    }
+
+    #[test]
+    fn client_ca_requires_tls_and_replaces_token_file_for_remote_writes() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let mut config = ServeConfig {
+            bind: "0.0.0.0:0".parse().expect("bind"),
+            pool_dir: temp.path().to_path_buf(),
+            token: None,
+            cors_allowed_origins: Vec::new(),
+            access_mode: AccessMode::ReadWrite,
+            allow_non_loopback: true,
+            insecure_no_tls: false,
+            token_file_used: false,
+            token_file: None,
+            tls_cert: None,
+            tls_key: None,
+            tls_self_signed: false,
+            tls_client_ca: Some(temp.path().join("clients.pem")),
+            tls_self_signed_material: None,
+            tls_fingerprint: None,
+            max_body_bytes: 1024 * 1024,
+            max_tail_timeout_ms: 30_000,
+            max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
+        };
+        let err = validate_config(&config).expect_err("client CA without TLS");
+        assert!(
+            err.message()
+                .unwrap_or_default()
+                .contains("--tls-client-ca")
+        );
+        config.tls_self_signed = true;
+        validate_config(&config).expect("mTLS satisfies non-loopback write auth");
+    }
+
+    #[test]
+    fn request_ids_are_header_safe_and_reject_oversized_input() {
+        let minted = new_request_id();
+        assert_eq!(minted.len(), 16);
+        assert!(is_valid_request_id(&minted));
+        assert!(is_valid_request_id("trace-42.a_b"));
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id("has space"));
+        assert!(!is_valid_request_id(&"x".repeat(65)));
+    }
+
+    #[test]
+    fn client_identity_prefers_common_name_then_san() {
+        let mut params = rcgen::CertificateParams::new(vec!["svc.internal".to_string()]);
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "ingest-worker");
+        let cert = rcgen::Certificate::from_params(params).expect("cert");
+        let der = cert.serialize_der().expect("der");
+        assert_eq!(
+            client_identity_from_der(&der).as_deref(),
+            Some("ingest-worker")
+        );
+
+        let mut params = rcgen::CertificateParams::new(vec!["svc.internal".to_string()]);
+        params.distinguished_name = rcgen::DistinguishedName::new();
+        let cert = rcgen::Certificate::from_params(params).expect("cert");
+        let der = cert.serialize_der().expect("der");
+        assert_eq!(
+            client_identity_from_der(&der).as_deref(),
+            Some("svc.internal")
+        );
+    }
+
+    #[tokio::test]
+    async fn namespace_client_certificates_do_not_cross_namespaces() {
+        use crate::serve_quota::QuotaState;
+        use crate::serve_routes::RouteTable;
+        use axum::http::{HeaderMap, HeaderValue, header};
+        use plasmite::api::LocalClient;
+        use std::sync::Arc;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let client = LocalClient::new().with_pool_dir(temp.path());
+        let quota = Arc::new(QuotaState::new(Quota::default()));
+        let root = AppState {
+            client: client.clone(),
+            tokens: Arc::new(TokenStore::new(None, None).expect("tokens")),
+            access_mode: AccessMode::ReadWrite,
+            max_tail_timeout_ms: 30_000,
+            tail_semaphore: Arc::new(tokio::sync::Semaphore::new(1)),
+            quota: quota.clone(),
+            policy: None,
+            identities: None,
+            routes: Arc::new(RouteTable::load(client, quota).expect("routes")),
+            transforms: Arc::default(),
+            op_timeout: None,
+        };
+        let b_tokens = temp.path().join("b.tokens");
+        std::fs::write(&b_tokens, "b-secret\n").expect("token file");
+        let lab_a = ServeNamespace::parse("lab-a,identity=alice").expect("spec");
+        let lab_b = ServeNamespace::parse(&format!("lab-b,token-file={}", b_tokens.display()))
+            .expect("spec");
+        let lab_c = ServeNamespace::parse("lab-c").expect("spec");
+        let state_a = namespace_state(&root, &lab_a, temp.path()).expect("lab-a");
+        let state_b = namespace_state(&root, &lab_b, temp.path()).expect("lab-b");
+        let state_c = namespace_state(&root, &lab_c, temp.path()).expect("lab-c");
+
+        let mut alice = HeaderMap::new();
+        alice.insert(CLIENT_IDENTITY_HEADER, HeaderValue::from_static("alice"));
+        authorize(&alice, &root).expect("root admits any verified certificate");
+        authorize(&alice, &state_a).expect("lab-a lists alice");
+        for state in [&state_b, &state_c] {
+            let err = authorize(&alice, state).expect_err("alice is not listed");
+            assert_eq!(err.kind(), ErrorKind::Permission);
+        }
+
+        alice.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer b-secret"),
+        );
+        authorize(&alice, &state_b).expect("a namespace token still works");
+    }
+
+    #[tokio::test]
+    async fn otlp_receiver_rejects_protobuf_exports() {
+        use crate::serve_quota::QuotaState;
+        use crate::serve_routes::RouteTable;
+        use axum::http::{HeaderMap, HeaderValue, header};
+        use plasmite::api::LocalClient;
+        use std::sync::Arc;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let client = LocalClient::new().with_pool_dir(temp.path());
+        let quota = Arc::new(QuotaState::new(Quota::default()));
+        let state = AppState {
+            client: client.clone(),
+            tokens: Arc::new(TokenStore::new(None, None).expect("tokens")),
+            access_mode: AccessMode::ReadWrite,
+            max_tail_timeout_ms: 30_000,
+            tail_semaphore: Arc::new(tokio::sync::Semaphore::new(1)),
+            quota: quota.clone(),
+            policy: None,
+            identities: None,
+            routes: Arc::new(RouteTable::load(client, quota).expect("routes")),
+            transforms: Arc::default(),
+            op_timeout: None,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-protobuf"),
+        );
+        for signal in [OtlpSignal::Logs, OtlpSignal::Traces] {
+            let response = otlp_export(&state, &headers, signal, &[0x0a, 0x00]);
+            assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            let body = axum::body::to_bytes(response.into_body(), 64 * 1024)
+                .await
+                .expect("body");
+            let body: Value = serde_json::from_slice(&body).expect("error json");
+            let message = body["error"]["message"].as_str().expect("message");
+            assert!(message.contains("'application/x-protobuf'"), "{message}");
+            let hint = body["error"]["hint"].as_str().expect("hint");
+            assert!(
+                hint.contains("OTEL_EXPORTER_OTLP_PROTOCOL=http/json"),
+                "{hint}"
+            );
+        }
+        assert!(!temp.path().join("otlp-logs.plasmite").exists());
+    }
+
     #[test]
     fn non_loopback_write_requires_tls_or_insecure() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -1648,14 +3529,24 @@ mod tests {
             allow_non_loopback: true,
             insecure_no_tls: false,
             token_file_used: true,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let err = validate_config(&config).expect_err("expected usage error");
         assert_eq!(err.kind(), ErrorKind::Usage);
@@ -1673,14 +3564,24 @@ mod tests {
             allow_non_loopback: false,
             insecure_no_tls: false,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 0,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let err = validate_config(&config).expect_err("expected usage error");
         assert_eq!(err.kind(), ErrorKind::Usage);
@@ -1714,6 +3615,52 @@ mod tests {
         assert!(parse_tags_from_query(None).is_empty());
     }
 
+    #[test]
+    fn parse_tail_max_batch_defaults_and_bounds() {
+        assert_eq!(parse_tail_max_batch(None).expect("default"), 256);
+        assert_eq!(parse_tail_max_batch(Some(1)).expect("one"), 1);
+        for value in [0, 4097] {
+            let err = parse_tail_max_batch(Some(value)).expect_err("out of range");
+            assert_eq!(err.kind(), ErrorKind::Usage);
+        }
+    }
+
+    #[test]
+    fn stream_tail_bytes_coalesces_committed_messages_per_chunk() {
+        use plasmite::api::{
+            Durability, LocalClient, PoolApiExt, PoolOptions, PoolRef, TailOptions,
+        };
+        use std::time::Duration;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let client = LocalClient::new().with_pool_dir(temp.path());
+        let pool_ref = PoolRef::name("batch");
+        client
+            .create_pool(&pool_ref, PoolOptions::new(1024 * 1024))
+            .expect("create");
+        let mut pool = client.open_pool(&pool_ref).expect("open");
+        for value in 0..5 {
+            pool.append_json_now(&serde_json::json!({ "n": value }), &[], Durability::Fast)
+                .expect("append");
+        }
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let options = TailOptions {
+            since_seq: Some(1),
+            max_messages: Some(5),
+            timeout: Some(Duration::from_millis(50)),
+            ..TailOptions::default()
+        };
+        stream_tail_bytes(&pool, options, &[], TailStreamEncoding::Jsonl, 2, tx).expect("stream");
+        let mut lines_per_chunk = Vec::new();
+        while let Ok(chunk) = rx.try_recv() {
+            let chunk = chunk.expect("chunk");
+            assert!(chunk.ends_with(b"\n"));
+            lines_per_chunk.push(chunk.iter().filter(|byte| **byte == b'\n').count());
+        }
+        assert_eq!(lines_per_chunk, vec![2, 2, 1]);
+    }
+
     #[test]
     fn normalize_cors_origins_dedupes_and_normalizes() {
         let origins = normalize_cors_origins(&[
@@ -1749,14 +3696,24 @@ mod tests {
             allow_non_loopback: false,
             insecure_no_tls: false,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let origins = normalize_cors_origins(&config.cors_allowed_origins).expect("origins");
         let layer = build_cors_layer(&origins).expect("cors layer");
@@ -1778,14 +3735,24 @@ mod tests {
             allow_non_loopback: false,
             insecure_no_tls: false,
             token_file_used: false,
+            token_file: None,
             tls_cert: None,
             tls_key: None,
             tls_self_signed: false,
+            tls_client_ca: None,
             tls_self_signed_material: None,
             tls_fingerprint: None,
             max_body_bytes: 1024 * 1024,
             max_tail_timeout_ms: 30_000,
             max_concurrent_tails: 64,
+            otlp_bind: None,
+            namespaces: Vec::new(),
+            quota: Quota::default(),
+            readonly_snapshot: false,
+            pool_policy: None,
+            transforms: None,
+            stats_interval: None,
+            op_timeout: None,
         };
         let origins = normalize_cors_origins(&config.cors_allowed_origins).expect("origins");
         let cors_layer = build_cors_layer(&origins)
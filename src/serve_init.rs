@@ -12,7 +12,7 @@ use getrandom::fill as fill_random;
 use rcgen::{Certificate, CertificateParams, SanType};
 use sha2::{Digest, Sha256};
 
-use plasmite::api::{Error, ErrorKind};
+use plasmite::api::{Error, ErrorKind, hex_encode};
 
 #[derive(Debug)]
 pub struct ServeInitConfig {
@@ -185,22 +185,6 @@ fn generate_token() -> Result<String, Error> {
     Ok(hex_encode(&bytes))
 }
 
-fn hex_encode(bytes: &[u8]) -> String {
-    let mut out = String::with_capacity(bytes.len() * 2);
-    for byte in bytes {
-        out.push(nibble_hex(byte >> 4));
-        out.push(nibble_hex(byte & 0x0f));
-    }
-    out
-}
-
-fn nibble_hex(nibble: u8) -> char {
-    match nibble {
-        0..=9 => char::from(b'0' + nibble),
-        _ => char::from(b'a' + (nibble - 10)),
-    }
-}
-
 fn generate_self_signed_pem(bind_ip: IpAddr) -> Result<(String, String, Vec<u8>), Error> {
     let mut params = CertificateParams::new(vec!["localhost".to_string()]);
     params
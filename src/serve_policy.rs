@@ -0,0 +1,220 @@
+//! Purpose: Per-pool access rules for `plasmite serve --pool-policy FILE`.
+//! Exports: `PoolPolicy`, `Caller`, `intersect_access`.
+//! Role: Decide what one caller may do with one pool; serve intersects the answer with the
+//! route's `--access` mode and uses it to filter listings and guard pool routes.
+//! Invariants: Rules only grant. A caller no rule matches has no access to the pool.
+//! Invariants: One `PRINCIPAL POOL ACCESS` rule per line; blank lines and `#` comments are
+//! ignored. PRINCIPAL is a bearer token, `cert:NAME` (verified client-certificate identity), or
+//! `*` (any caller that passed authentication). POOL is a name, a prefix ending in `*`, or `*`.
+use std::path::Path;
+
+use plasmite::api::{Error, ErrorKind};
+
+use crate::serve::AccessMode;
+
+/// Who is asking, as proven by `authorize`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Caller<'a> {
+    pub token: Option<&'a str>,
+    pub identity: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Principal {
+    Any,
+    Token(String),
+    Identity(String),
+}
+
+impl Principal {
+    fn matches(&self, caller: Caller<'_>) -> bool {
+        match self {
+            Principal::Any => true,
+            Principal::Token(token) => caller.token == Some(token.as_str()),
+            Principal::Identity(identity) => caller.identity == Some(identity.as_str()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    principal: Principal,
+    pool: String,
+    access: AccessMode,
+}
+
+impl Rule {
+    fn covers(&self, pool: &str) -> bool {
+        match self.pool.strip_suffix('*') {
+            Some(prefix) => pool.starts_with(prefix),
+            None => self.pool == pool,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PoolPolicy {
+    rules: Vec<Rule>,
+}
+
+impl PoolPolicy {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let raw = std::fs::read_to_string(path).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to read pool policy")
+                .with_path(path)
+                .with_source(err)
+        })?;
+        Self::parse(&raw).map_err(|err| err.with_path(path))
+    }
+
+    pub fn parse(raw: &str) -> Result<Self, Error> {
+        let mut rules = Vec::new();
+        for (idx, line) in raw.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: &str| {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid pool policy line {}: {message}", idx + 1))
+                    .with_hint("Use `PRINCIPAL POOL ACCESS`, e.g. `cert:ops team-* read-write` or `* public read-only`.")
+            };
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [principal, pool, access] = fields[..] else {
+                return Err(invalid("expected three fields"));
+            };
+            let principal = match principal {
+                "*" => Principal::Any,
+                _ => match principal.strip_prefix("cert:") {
+                    Some("") => return Err(invalid("empty certificate identity")),
+                    Some(identity) => Principal::Identity(identity.to_string()),
+                    None => Principal::Token(principal.to_string()),
+                },
+            };
+            if pool.contains('/') || pool.trim_end_matches('*').contains('*') {
+                return Err(invalid(
+                    "pool must be a name, a prefix ending in `*`, or `*`",
+                ));
+            }
+            let access = match access {
+                "read-only" => AccessMode::ReadOnly,
+                "write-only" => AccessMode::WriteOnly,
+                "read-write" => AccessMode::ReadWrite,
+                _ => {
+                    return Err(invalid(
+                        "access must be read-only, write-only, or read-write",
+                    ));
+                }
+            };
+            rules.push(Rule {
+                principal,
+                pool: pool.to_string(),
+                access,
+            });
+        }
+        Ok(Self { rules })
+    }
+
+    /// Union of every rule granting `caller` access to `pool`.
+    pub fn access(&self, caller: Caller<'_>, pool: &str) -> Option<AccessMode> {
+        self.union(caller, |rule| rule.covers(pool))
+    }
+
+    /// Access `caller` holds on every pool (rules whose POOL is exactly `*`); used for MCP,
+    /// which can name any pool.
+    pub fn access_to_all(&self, caller: Caller<'_>) -> Option<AccessMode> {
+        self.union(caller, |rule| rule.pool == "*")
+    }
+
+    fn union(&self, caller: Caller<'_>, applies: impl Fn(&Rule) -> bool) -> Option<AccessMode> {
+        let (read, write) = self
+            .rules
+            .iter()
+            .filter(|rule| rule.principal.matches(caller) && applies(rule))
+            .fold((false, false), |(read, write), rule| {
+                let (r, w) = flags(rule.access);
+                (read || r, write || w)
+            });
+        from_flags(read, write)
+    }
+}
+
+/// What both `mode` and `granted` allow; `None` when that is nothing.
+pub fn intersect_access(mode: AccessMode, granted: Option<AccessMode>) -> Option<AccessMode> {
+    let (read, write) = flags(mode);
+    let (granted_read, granted_write) = granted.map_or((false, false), flags);
+    from_flags(read && granted_read, write && granted_write)
+}
+
+fn flags(mode: AccessMode) -> (bool, bool) {
+    match mode {
+        AccessMode::ReadOnly => (true, false),
+        AccessMode::WriteOnly => (false, true),
+        AccessMode::ReadWrite => (true, true),
+    }
+}
+
+fn from_flags(read: bool, write: bool) -> Option<AccessMode> {
+    match (read, write) {
+        (true, true) => Some(AccessMode::ReadWrite),
+        (true, false) => Some(AccessMode::ReadOnly),
+        (false, true) => Some(AccessMode::WriteOnly),
+        (false, false) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Caller, PoolPolicy, intersect_access};
+    use crate::serve::AccessMode;
+
+    #[test]
+    fn rules_grant_per_principal_and_pool_prefix() {
+        let policy = PoolPolicy::parse(
+            "# principal pool access\n\
+             alice team-* read-write\n\
+             cert:ops * read-only\n\
+             * public read-only\n\
+             alice public write-only\n",
+        )
+        .expect("parse");
+        let alice = Caller {
+            token: Some("alice"),
+            identity: None,
+        };
+        let ops = Caller {
+            token: None,
+            identity: Some("ops"),
+        };
+        let anyone = Caller::default();
+
+        assert_eq!(policy.access(alice, "team-a"), Some(AccessMode::ReadWrite));
+        assert_eq!(policy.access(alice, "public"), Some(AccessMode::ReadWrite));
+        assert_eq!(policy.access(alice, "secret"), None);
+        assert_eq!(policy.access(ops, "secret"), Some(AccessMode::ReadOnly));
+        assert_eq!(policy.access(anyone, "public"), Some(AccessMode::ReadOnly));
+        assert_eq!(policy.access(anyone, "team-a"), None);
+        assert_eq!(policy.access_to_all(ops), Some(AccessMode::ReadOnly));
+        assert_eq!(policy.access_to_all(alice), None);
+
+        assert_eq!(
+            intersect_access(AccessMode::ReadOnly, Some(AccessMode::ReadWrite)),
+            Some(AccessMode::ReadOnly)
+        );
+        assert_eq!(
+            intersect_access(AccessMode::WriteOnly, Some(AccessMode::ReadOnly)),
+            None
+        );
+    }
+
+    #[test]
+    fn malformed_rules_name_the_line() {
+        let err = PoolPolicy::parse("alice chat\n").expect_err("fields");
+        assert!(err.message().unwrap_or_default().contains("line 1"));
+        assert!(PoolPolicy::parse("\nalice chat admin\n").is_err());
+        assert!(PoolPolicy::parse("alice a/b read-only\n").is_err());
+        assert!(PoolPolicy::parse("alice a*b read-only\n").is_err());
+        assert!(PoolPolicy::parse("cert: chat read-only\n").is_err());
+    }
+}
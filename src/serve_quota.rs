@@ -0,0 +1,214 @@
+//! Purpose: Enforce per-namespace quotas for `plasmite serve` (pool count, pool bytes, append rate).
+//! Exports: `Quota`, `QuotaState`, `QuotaUsage`, `QuotaViolation`.
+//! Role: Bookkeeping only; serve maps violations to 429/507 responses.
+//! Invariants: Pool files are fixed-size rings, so byte quotas are checked when pools are
+//! created and appends never change usage.
+//! Invariants: The append rate is a token bucket holding at most one second of appends.
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+use plasmite::api::{Error, ErrorKind, PoolInfo};
+
+/// Limits for one namespace; `None` means unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Quota {
+    pub max_pools: Option<u64>,
+    pub max_bytes: Option<u64>,
+    /// Appended messages per second, across every pool in the namespace.
+    pub max_append_rate: Option<u32>,
+}
+
+impl Quota {
+    /// Fill unset limits from `defaults` (namespaces inherit the server-wide quota).
+    pub fn or(self, defaults: Quota) -> Quota {
+        Quota {
+            max_pools: self.max_pools.or(defaults.max_pools),
+            max_bytes: self.max_bytes.or(defaults.max_bytes),
+            max_append_rate: self.max_append_rate.or(defaults.max_append_rate),
+        }
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({
+            "max_pools": self.max_pools,
+            "max_bytes": self.max_bytes,
+            "max_append_rate": self.max_append_rate,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub pools: u64,
+    pub bytes: u64,
+}
+
+impl QuotaUsage {
+    pub fn from_pools(pools: &[PoolInfo]) -> Self {
+        Self {
+            pools: pools.len() as u64,
+            bytes: pools.iter().map(|info| info.file_size).sum(),
+        }
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({ "pools": self.pools, "bytes": self.bytes })
+    }
+}
+
+#[derive(Debug)]
+pub enum QuotaViolation {
+    /// Append rate exceeded; retryable after the given delay.
+    Rate { error: Error, retry_after: Duration },
+    /// Pool count or byte quota exhausted; needs a delete or a larger quota.
+    Storage(Error),
+}
+
+struct Bucket {
+    available: f64,
+    refilled_at: Instant,
+}
+
+pub struct QuotaState {
+    quota: Quota,
+    bucket: Mutex<Bucket>,
+}
+
+impl QuotaState {
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            bucket: Mutex::new(Bucket {
+                available: f64::from(quota.max_append_rate.unwrap_or(0)),
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn quota(&self) -> Quota {
+        self.quota
+    }
+
+    /// Whether a new pool of `size_bytes` fits next to `usage`.
+    pub fn check_create(&self, usage: QuotaUsage, size_bytes: u64) -> Result<(), QuotaViolation> {
+        if let Some(max_pools) = self.quota.max_pools {
+            if usage.pools >= max_pools {
+                return Err(QuotaViolation::Storage(
+                    Error::new(ErrorKind::Usage)
+                        .with_message(format!("pool quota exceeded ({max_pools} pools)"))
+                        .with_hint("Delete an unused pool or ask the operator to raise max-pools."),
+                ));
+            }
+        }
+        if let Some(max_bytes) = self.quota.max_bytes {
+            if usage.bytes.saturating_add(size_bytes) > max_bytes {
+                return Err(QuotaViolation::Storage(
+                    Error::new(ErrorKind::Usage)
+                        .with_message(format!(
+                            "byte quota exceeded ({} of {max_bytes} bytes in use)",
+                            usage.bytes
+                        ))
+                        .with_hint("Create a smaller pool, delete an unused one, or ask the operator to raise max-bytes."),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reserve `count` appends against the rate limit; all or nothing.
+    pub fn take_appends(&self, count: u64) -> Result<(), QuotaViolation> {
+        let Some(rate) = self.quota.max_append_rate else {
+            return Ok(());
+        };
+        let rate = f64::from(rate);
+        let mut bucket = self.bucket.lock().unwrap_or_else(|err| err.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.available = (bucket.available + elapsed * rate).min(rate);
+        bucket.refilled_at = now;
+        let wanted = count as f64;
+        if wanted <= bucket.available {
+            bucket.available -= wanted;
+            return Ok(());
+        }
+        let hint = if wanted > rate {
+            "Split the batch into requests no larger than the per-second rate."
+        } else {
+            "Retry after the Retry-After delay or slow down appends."
+        };
+        let missing = (wanted - bucket.available).min(rate);
+        Err(QuotaViolation::Rate {
+            error: Error::new(ErrorKind::Busy)
+                .with_message(format!("append rate quota exceeded ({rate} messages/s)"))
+                .with_hint(hint),
+            retry_after: Duration::from_secs_f64(missing / rate),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Quota, QuotaState, QuotaUsage, QuotaViolation};
+
+    #[test]
+    fn create_checks_pool_count_and_bytes() {
+        let state = QuotaState::new(Quota {
+            max_pools: Some(2),
+            max_bytes: Some(1000),
+            max_append_rate: None,
+        });
+        let usage = |pools, bytes| QuotaUsage { pools, bytes };
+        assert!(state.check_create(usage(1, 400), 600).is_ok());
+        assert!(matches!(
+            state.check_create(usage(1, 400), 601),
+            Err(QuotaViolation::Storage(_))
+        ));
+        assert!(matches!(
+            state.check_create(usage(2, 0), 1),
+            Err(QuotaViolation::Storage(_))
+        ));
+    }
+
+    #[test]
+    fn append_rate_allows_one_second_burst() {
+        let state = QuotaState::new(Quota {
+            max_append_rate: Some(3),
+            ..Quota::default()
+        });
+        assert!(state.take_appends(2).is_ok());
+        assert!(state.take_appends(1).is_ok());
+        match state.take_appends(1) {
+            Err(QuotaViolation::Rate { retry_after, .. }) => {
+                assert!(retry_after.as_secs_f64() <= 1.0);
+            }
+            other => panic!("expected rate violation, got {other:?}"),
+        }
+        assert!(
+            QuotaState::new(Quota::default())
+                .take_appends(1_000)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn namespace_quota_inherits_unset_limits() {
+        let root = Quota {
+            max_pools: Some(10),
+            max_bytes: Some(1 << 30),
+            max_append_rate: Some(100),
+        };
+        let namespace = Quota {
+            max_pools: Some(2),
+            ..Quota::default()
+        };
+        assert_eq!(
+            namespace.or(root),
+            Quota {
+                max_pools: Some(2),
+                ..root
+            }
+        );
+    }
+}
@@ -0,0 +1,437 @@
+//! Purpose: Serve-managed routes that relay matching appends from one hosted pool to another.
+//! Exports: `Route`, `RouteTable`.
+//! Role: Backs `PUT/GET/DELETE /v0/routes`; one relay thread per route replaces a client-side
+//! `follow | feed` process per subscriber.
+//! Invariants: A route is keyed by `(from_pool, to_pool)`; PUT on an existing pair replaces its
+//! filter and keeps its cursor.
+//! Invariants: Routes persist in `<pool_dir>/.routes.json` and resume from a named cursor
+//! (`route-<hash of to_pool>`) on the source pool, so delivery is at-least-once across restarts.
+//! Invariants: A new route starts after the source's newest message; it never backfills.
+//! Invariants: Routes never form a cycle, so one append cannot relay forever.
+//! Invariants: Relayed appends draw on the namespace append-rate quota and wait when it is spent.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use crate::jq_filter::{JqFilter, compile_filters, matches_all};
+use crate::serve_quota::QuotaState;
+use plasmite::api::{
+    AppendOptions, Durability, Error, ErrorKind, LocalClient, PoolApiExt, PoolRef, TailOptions,
+    hex_encode, load_cursor, save_cursor,
+};
+
+const ROUTES_FILE: &str = ".routes.json";
+const IDLE_POLL: Duration = Duration::from_millis(50);
+const ERROR_BACKOFF: Duration = Duration::from_secs(1);
+/// Relayed messages between cursor saves while the source keeps producing.
+const CURSOR_SAVE_EVERY: u64 = 64;
+
+/// One route as stored and as accepted by `PUT /v0/routes`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Route {
+    pub from_pool: String,
+    pub to_pool: String,
+    /// jq-style predicate over the message envelope; unset relays everything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+impl Route {
+    fn key(&self) -> (String, String) {
+        (self.from_pool.clone(), self.to_pool.clone())
+    }
+
+    /// Cursor on the source pool; hashed so any target name fits the cursor name rules.
+    fn cursor_name(&self) -> String {
+        let digest = Sha256::digest(self.to_pool.as_bytes());
+        format!("route-{}", hex_encode(&digest[..8]))
+    }
+
+    fn filters(&self) -> Result<Vec<JqFilter>, Error> {
+        compile_filters(self.filter.as_slice())
+    }
+}
+
+#[derive(Debug, Default)]
+struct RouteMetrics {
+    relayed: u64,
+    skipped: u64,
+    errors: u64,
+    last_seq: Option<u64>,
+    last_error: Option<String>,
+}
+
+struct RunningRoute {
+    route: Route,
+    metrics: Arc<Mutex<RouteMetrics>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl RunningRoute {
+    fn to_json(&self) -> Value {
+        let metrics = lock(&self.metrics);
+        json!({
+            "from_pool": self.route.from_pool,
+            "to_pool": self.route.to_pool,
+            "filter": self.route.filter,
+            "metrics": {
+                "relayed": metrics.relayed,
+                "skipped": metrics.skipped,
+                "errors": metrics.errors,
+                "last_seq": metrics.last_seq,
+                "last_error": metrics.last_error,
+            },
+        })
+    }
+
+    fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RoutesFile {
+    routes: Vec<Route>,
+}
+
+/// Routes of one pool directory (the root or a namespace) and their relay threads.
+pub struct RouteTable {
+    client: LocalClient,
+    quota: Arc<QuotaState>,
+    routes: Mutex<BTreeMap<(String, String), RunningRoute>>,
+}
+
+impl RouteTable {
+    /// Load persisted routes and start relaying; a read-only server keeps them idle.
+    pub fn load(client: LocalClient, quota: Arc<QuotaState>) -> Result<Self, Error> {
+        let table = Self {
+            client,
+            quota,
+            routes: Mutex::new(BTreeMap::new()),
+        };
+        let path = table.routes_path();
+        let stored = match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str::<RoutesFile>(&raw).map_err(|err| {
+                Error::new(ErrorKind::Corrupt)
+                    .with_message("invalid routes file")
+                    .with_path(&path)
+                    .with_hint("Fix or delete the routes file, then restart the server.")
+                    .with_source(err)
+            })?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => RoutesFile::default(),
+            Err(err) => {
+                return Err(Error::new(ErrorKind::Io)
+                    .with_message("failed to read routes file")
+                    .with_path(&path)
+                    .with_source(err));
+            }
+        };
+        {
+            let mut routes = lock(&table.routes);
+            for route in stored.routes {
+                route.filters()?;
+                let running = table.start(route.clone());
+                routes.insert(route.key(), running);
+            }
+        }
+        Ok(table)
+    }
+
+    pub fn list(&self) -> Vec<Value> {
+        lock(&self.routes)
+            .values()
+            .map(RunningRoute::to_json)
+            .collect()
+    }
+
+    /// Add a route, or replace the filter of an existing one; returns it with its metrics.
+    pub fn upsert(&self, route: Route) -> Result<Value, Error> {
+        if self.client.is_read_only() {
+            return Err(Error::new(ErrorKind::Permission)
+                .with_message("routes cannot be changed on a read-only server"));
+        }
+        route.filters()?;
+        let source = self.client.pool_info(&PoolRef::name(&route.from_pool))?;
+        self.client.pool_info(&PoolRef::name(&route.to_pool))?;
+
+        let mut routes = lock(&self.routes);
+        if creates_cycle(routes.keys(), &route) {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!(
+                    "route {} -> {} would form a cycle",
+                    route.from_pool, route.to_pool
+                ))
+                .with_hint("Routes must not lead back to their source pool; delete a route in the loop first."));
+        }
+        match routes.remove(&route.key()) {
+            Some(mut previous) => previous.stop(),
+            None => {
+                let newest = source.bounds.newest_seq.unwrap_or(0);
+                save_cursor(&source.path, &route.cursor_name(), newest)?;
+            }
+        }
+        let running = self.start(route.clone());
+        let body = running.to_json();
+        routes.insert(route.key(), running);
+        self.persist(&routes)?;
+        Ok(body)
+    }
+
+    /// Stop and forget a route; `NotFound` when no such route exists.
+    pub fn remove(&self, from_pool: &str, to_pool: &str) -> Result<(), Error> {
+        if self.client.is_read_only() {
+            return Err(Error::new(ErrorKind::Permission)
+                .with_message("routes cannot be changed on a read-only server"));
+        }
+        let mut routes = lock(&self.routes);
+        let Some(mut running) = routes.remove(&(from_pool.to_string(), to_pool.to_string())) else {
+            return Err(Error::new(ErrorKind::NotFound)
+                .with_message(format!("no route {from_pool} -> {to_pool}"))
+                .with_hint("List routes with GET /v0/routes."));
+        };
+        running.stop();
+        self.persist(&routes)
+    }
+
+    fn routes_path(&self) -> PathBuf {
+        self.client.pool_dir().join(ROUTES_FILE)
+    }
+
+    fn start(&self, route: Route) -> RunningRoute {
+        let metrics = Arc::new(Mutex::new(RouteMetrics::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = (!self.client.is_read_only()).then(|| {
+            let client = self.client.clone();
+            let quota = self.quota.clone();
+            let route = route.clone();
+            let metrics = metrics.clone();
+            let stop = stop.clone();
+            std::thread::spawn(move || run_route(&client, &quota, &route, &metrics, &stop))
+        });
+        RunningRoute {
+            route,
+            metrics,
+            stop,
+            handle,
+        }
+    }
+
+    fn persist(&self, routes: &BTreeMap<(String, String), RunningRoute>) -> Result<(), Error> {
+        let path = self.routes_path();
+        let file = RoutesFile {
+            routes: routes
+                .values()
+                .map(|running| running.route.clone())
+                .collect(),
+        };
+        let io_error = |err: std::io::Error| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to save routes file")
+                .with_path(&path)
+                .with_source(err)
+        };
+        let body = serde_json::to_string_pretty(&file).map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to encode routes")
+                .with_source(err)
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, format!("{body}\n")).map_err(io_error)?;
+        std::fs::rename(&tmp, &path).map_err(io_error)
+    }
+}
+
+impl Drop for RouteTable {
+    fn drop(&mut self) {
+        for running in lock(&self.routes).values_mut() {
+            running.stop();
+        }
+    }
+}
+
+/// Whether adding `route` lets an append reach its own source pool again.
+fn creates_cycle<'a>(existing: impl Iterator<Item = &'a (String, String)>, route: &Route) -> bool {
+    let edges: Vec<&(String, String)> = existing.collect();
+    let mut stack = vec![route.to_pool.as_str()];
+    let mut seen = Vec::new();
+    while let Some(pool) = stack.pop() {
+        if pool == route.from_pool {
+            return true;
+        }
+        if seen.contains(&pool) {
+            continue;
+        }
+        seen.push(pool);
+        stack.extend(
+            edges
+                .iter()
+                .filter(|(from, _)| from == pool)
+                .map(|(_, to)| to.as_str()),
+        );
+    }
+    false
+}
+
+fn run_route(
+    client: &LocalClient,
+    quota: &QuotaState,
+    route: &Route,
+    metrics: &Mutex<RouteMetrics>,
+    stop: &AtomicBool,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        let Err(err) = relay(client, quota, route, metrics, stop) else {
+            continue;
+        };
+        {
+            let mut metrics = lock(metrics);
+            metrics.errors += 1;
+            metrics.last_error = Some(err.to_string());
+        }
+        sleep_unless_stopped(ERROR_BACKOFF, stop);
+    }
+}
+
+/// Relay from the saved cursor until stopped; errors restart from the last saved cursor.
+fn relay(
+    client: &LocalClient,
+    quota: &QuotaState,
+    route: &Route,
+    metrics: &Mutex<RouteMetrics>,
+    stop: &AtomicBool,
+) -> Result<(), Error> {
+    let filters = route.filters()?;
+    let source = client.open_pool(&PoolRef::name(&route.from_pool))?;
+    let target = PoolRef::name(&route.to_pool);
+    let cursor_name = route.cursor_name();
+    let mut options = TailOptions::new();
+    options.since_seq = load_cursor(source.path(), &cursor_name)?.map(|seq| seq + 1);
+    options.notify = false;
+    let mut tail = source.tail(options);
+
+    let mut unsaved: Option<u64> = None;
+    let mut since_save = 0;
+    let result = loop {
+        if stop.load(Ordering::Relaxed) {
+            break Ok(());
+        }
+        let message = match tail.try_next_message() {
+            Ok(Some(message)) => message,
+            Ok(None) => {
+                if let Some(seq) = unsaved.take() {
+                    save_cursor(source.path(), &cursor_name, seq)?;
+                    since_save = 0;
+                }
+                std::thread::sleep(IDLE_POLL);
+                continue;
+            }
+            Err(err) => break Err(err),
+        };
+        // A filter that errors on a message skips it rather than wedging the route.
+        let matched = match matches_all(&filters, &message.to_json()) {
+            Ok(matched) => matched,
+            Err(err) => {
+                lock(metrics).last_error = Some(err.to_string());
+                false
+            }
+        };
+        if matched {
+            while quota.take_appends(1).is_err() {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                std::thread::sleep(IDLE_POLL);
+            }
+            if stop.load(Ordering::Relaxed) {
+                break Ok(());
+            }
+            let appended = crate::now_ns().and_then(|timestamp_ns| {
+                let options = AppendOptions::new(timestamp_ns, Durability::Fast);
+                client.with_pool(&target, |pool| match &message.attachment {
+                    Some(attachment) => pool.append_json_with_attachment(
+                        &message.data,
+                        &message.meta.tags,
+                        attachment,
+                        options,
+                    ),
+                    None => pool.append_json(&message.data, &message.meta.tags, options),
+                })
+            });
+            if let Err(err) = appended {
+                break Err(err);
+            }
+        }
+        {
+            let mut metrics = lock(metrics);
+            if matched {
+                metrics.relayed += 1;
+            } else {
+                metrics.skipped += 1;
+            }
+            metrics.last_seq = Some(message.seq);
+        }
+        unsaved = Some(message.seq);
+        since_save += 1;
+        if since_save >= CURSOR_SAVE_EVERY {
+            save_cursor(source.path(), &cursor_name, message.seq)?;
+            unsaved = None;
+            since_save = 0;
+        }
+    };
+    if let Some(seq) = unsaved {
+        save_cursor(source.path(), &cursor_name, seq)?;
+    }
+    result
+}
+
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let mut waited = Duration::ZERO;
+    while waited < duration && !stop.load(Ordering::Relaxed) {
+        std::thread::sleep(IDLE_POLL);
+        waited += IDLE_POLL;
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Route, creates_cycle};
+
+    fn route(from: &str, to: &str) -> Route {
+        Route {
+            from_pool: from.to_string(),
+            to_pool: to.to_string(),
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn cycles_are_detected_through_existing_routes() {
+        let existing = [
+            ("a".to_string(), "b".to_string()),
+            ("b".to_string(), "c".to_string()),
+        ];
+        assert!(creates_cycle(existing.iter(), &route("c", "a")));
+        assert!(creates_cycle(existing.iter(), &route("a", "a")));
+        assert!(!creates_cycle(existing.iter(), &route("a", "c")));
+        assert!(!creates_cycle(existing.iter(), &route("c", "d")));
+        assert_ne!(route("a", "b").cursor_name(), route("a", "c").cursor_name());
+    }
+}
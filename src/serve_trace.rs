@@ -0,0 +1,661 @@
+//! Purpose: Export `plasmite serve` tracing spans as OTLP/HTTP JSON, configured by the
+//! standard `OTEL_*` environment variables.
+//! Exports: `OtelConfig`, `OtlpSpanLayer`, `OtlpExportGuard`, `spawn_exporter`.
+//! Role: A `tracing_subscriber` layer beside the log output layer; spans are queued as they
+//! close and one background thread posts them in batches, so handlers never wait on the
+//! collector.
+//! Invariants: Export is off unless an OTLP endpoint variable is set; `OTEL_SDK_DISABLED=true`
+//! and `OTEL_TRACES_EXPORTER=none` turn it off. Only the `http/json` protocol is spoken.
+//! Invariants: A full queue drops spans rather than blocking a handler; a failed post is
+//! logged once per batch and not retried.
+//! Invariants: A root span carrying a valid W3C `traceparent` field continues that trace.
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender};
+use std::time::{Duration, Instant};
+
+use plasmite::api::{Error, ErrorKind, hex_decode, hex_encode};
+use serde_json::{Map, Value, json};
+use tracing::Subscriber;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+const DEFAULT_SERVICE_NAME: &str = "plasmite";
+const DEFAULT_EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+const EXPORT_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_EXPORT_BATCH: usize = 512;
+const QUEUE_CAPACITY: usize = 4096;
+/// Spans whose name is listed here are `SERVER` spans; everything else is `INTERNAL`.
+const SERVER_SPANS: &[&str] = &["request"];
+const SPAN_KIND_INTERNAL: u8 = 1;
+const SPAN_KIND_SERVER: u8 = 2;
+const STATUS_CODE_ERROR: u8 = 2;
+
+/// Where and how to export spans, from `OTEL_*` variables.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtelConfig {
+    pub endpoint: String,
+    pub headers: Vec<(String, String)>,
+    pub timeout: Duration,
+    /// OTLP resource attributes; always includes `service.name`.
+    pub resource: Vec<(String, String)>,
+}
+
+impl OtelConfig {
+    /// Read the process environment; `Ok(None)` when export is not configured.
+    pub fn from_env() -> Result<Option<Self>, Error> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Like `from_env`, with `var` standing in for the environment.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Option<Self>, Error> {
+        // Signal-specific variables win over the generic `OTEL_EXPORTER_OTLP_*` ones.
+        let otlp_var = |suffix: &str| {
+            var(&format!("OTEL_EXPORTER_OTLP_TRACES_{suffix}"))
+                .or_else(|| var(&format!("OTEL_EXPORTER_OTLP_{suffix}")))
+                .filter(|value| !value.trim().is_empty())
+        };
+        if var("OTEL_SDK_DISABLED").is_some_and(|value| value.trim().eq_ignore_ascii_case("true")) {
+            return Ok(None);
+        }
+        if let Some(exporters) = var("OTEL_TRACES_EXPORTER") {
+            let exporters = exporters
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>();
+            if exporters.contains(&"none") {
+                return Ok(None);
+            }
+            if let Some(unsupported) = exporters.iter().find(|name| **name != "otlp") {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message(format!("unsupported OTEL_TRACES_EXPORTER: {unsupported}"))
+                    .with_hint("plasmite serve exports traces with otlp only; use otlp or none."));
+            }
+        }
+        let endpoint = match var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT")
+            .filter(|value| !value.trim().is_empty())
+        {
+            // The per-signal endpoint is used as given; the generic one is a base URL.
+            Some(endpoint) => endpoint.trim().to_string(),
+            None => {
+                match var("OTEL_EXPORTER_OTLP_ENDPOINT").filter(|value| !value.trim().is_empty()) {
+                    Some(base) => format!("{}/v1/traces", base.trim().trim_end_matches('/')),
+                    None => return Ok(None),
+                }
+            }
+        };
+        let parsed = url::Url::parse(&endpoint).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid OTLP traces endpoint: {endpoint}"))
+                .with_hint("Set OTEL_EXPORTER_OTLP_ENDPOINT to a URL like http://127.0.0.1:4318.")
+                .with_source(err)
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(Error::new(ErrorKind::Usage)
+                .with_message(format!(
+                    "unsupported OTLP endpoint scheme: {}",
+                    parsed.scheme()
+                ))
+                .with_hint("Use an http:// or https:// OTLP endpoint."));
+        }
+        if let Some(protocol) = otlp_var("PROTOCOL") {
+            if protocol.trim() != "http/json" {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message(format!(
+                        "unsupported OTEL_EXPORTER_OTLP_PROTOCOL: {protocol}"
+                    ))
+                    .with_hint("plasmite serve exports OTLP/HTTP JSON; set it to http/json."));
+            }
+        }
+        let headers = match otlp_var("HEADERS") {
+            Some(raw) => parse_key_values(&raw, "OTEL_EXPORTER_OTLP_HEADERS")?,
+            None => Vec::new(),
+        };
+        let timeout = match otlp_var("TIMEOUT") {
+            Some(raw) => raw
+                .trim()
+                .parse::<u64>()
+                .map(Duration::from_millis)
+                .map_err(|_| {
+                    Error::new(ErrorKind::Usage)
+                        .with_message(format!("invalid OTEL_EXPORTER_OTLP_TIMEOUT: {raw}"))
+                        .with_hint("Set the export timeout in milliseconds, e.g. 10000.")
+                })?,
+            None => DEFAULT_EXPORT_TIMEOUT,
+        };
+        let mut resource = match var("OTEL_RESOURCE_ATTRIBUTES") {
+            Some(raw) => parse_key_values(&raw, "OTEL_RESOURCE_ATTRIBUTES")?,
+            None => Vec::new(),
+        };
+        let service_name = var("OTEL_SERVICE_NAME")
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| {
+                resource
+                    .iter()
+                    .find(|(key, _)| key == "service.name")
+                    .map(|(_, value)| value.clone())
+            })
+            .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+        resource.retain(|(key, _)| key != "service.name" && key != "service.version");
+        resource.insert(0, ("service.name".to_string(), service_name));
+        resource.insert(
+            1,
+            (
+                "service.version".to_string(),
+                env!("CARGO_PKG_VERSION").to_string(),
+            ),
+        );
+        Ok(Some(Self {
+            endpoint,
+            headers,
+            timeout,
+            resource,
+        }))
+    }
+}
+
+/// Start the exporter thread; the layer feeds it, and the guard flushes it on drop.
+pub fn spawn_exporter(config: OtelConfig) -> (OtlpSpanLayer, OtlpExportGuard) {
+    let (tx, rx) = mpsc::sync_channel(QUEUE_CAPACITY);
+    let timeout = config.timeout;
+    std::thread::Builder::new()
+        .name("plasmite-otlp".to_string())
+        .spawn(move || run_exporter(&config, rx))
+        .expect("spawn OTLP exporter thread");
+    (
+        OtlpSpanLayer::new(tx.clone()),
+        OtlpExportGuard { tx, timeout },
+    )
+}
+
+enum ExportItem {
+    Span(Value),
+    Flush(SyncSender<()>),
+}
+
+/// Flushes queued spans (waiting up to the export timeout) when dropped.
+pub struct OtlpExportGuard {
+    tx: SyncSender<ExportItem>,
+    timeout: Duration,
+}
+
+impl Drop for OtlpExportGuard {
+    fn drop(&mut self) {
+        let (ack_tx, ack_rx) = mpsc::sync_channel(1);
+        if self.tx.send(ExportItem::Flush(ack_tx)).is_ok() {
+            let _ = ack_rx.recv_timeout(self.timeout);
+        }
+    }
+}
+
+fn run_exporter(config: &OtelConfig, rx: Receiver<ExportItem>) {
+    let agent = ureq::AgentBuilder::new().timeout(config.timeout).build();
+    let mut batch = Vec::new();
+    let mut deadline = Instant::now() + EXPORT_INTERVAL;
+    loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(ExportItem::Span(span)) => {
+                batch.push(span);
+                if batch.len() >= MAX_EXPORT_BATCH {
+                    post_batch(&agent, config, &mut batch);
+                }
+            }
+            Ok(ExportItem::Flush(ack)) => {
+                post_batch(&agent, config, &mut batch);
+                let _ = ack.send(());
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                post_batch(&agent, config, &mut batch);
+                deadline = Instant::now() + EXPORT_INTERVAL;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                post_batch(&agent, config, &mut batch);
+                return;
+            }
+        }
+    }
+}
+
+fn post_batch(agent: &ureq::Agent, config: &OtelConfig, batch: &mut Vec<Value>) {
+    if batch.is_empty() {
+        return;
+    }
+    let spans = std::mem::take(batch);
+    let count = spans.len();
+    let body = export_body(&config.resource, spans);
+    let mut request = agent
+        .post(&config.endpoint)
+        .set("Content-Type", "application/json")
+        .set(
+            "User-Agent",
+            concat!("plasmite/", env!("CARGO_PKG_VERSION")),
+        );
+    for (name, value) in &config.headers {
+        request = request.set(name, value);
+    }
+    if let Err(err) = request.send_string(&body.to_string()) {
+        tracing::warn!(endpoint = %config.endpoint, spans = count, error = %err, "dropped OTLP span batch");
+    }
+}
+
+/// An `ExportTraceServiceRequest` (OTLP/HTTP JSON) holding `spans` under one resource.
+fn export_body(resource: &[(String, String)], spans: Vec<Value>) -> Value {
+    let attributes = resource
+        .iter()
+        .map(|(key, value)| key_value(key, json!(value)))
+        .collect::<Vec<_>>();
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": attributes },
+            "scopeSpans": [{
+                "scope": { "name": "plasmite", "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// Queues each closed span as an OTLP JSON span.
+pub struct OtlpSpanLayer {
+    tx: SyncSender<ExportItem>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl OtlpSpanLayer {
+    fn new(tx: SyncSender<ExportItem>) -> Self {
+        Self {
+            tx,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+/// Per-span state kept in the registry's span extensions until the span closes.
+struct SpanRecord {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    start_ns: u64,
+    attributes: Map<String, Value>,
+}
+
+impl<S> Layer<S> for OtlpSpanLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = FieldVisitor::default();
+        attrs.record(&mut fields);
+        let parent = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanRecord>()
+                .map(|record| (record.trace_id, record.span_id))
+        });
+        let remote = fields.traceparent.as_deref().and_then(parse_traceparent);
+        let (trace_id, parent_span_id) = match parent.or(remote) {
+            Some((trace_id, span_id)) => (trace_id, Some(span_id)),
+            None => (random_id(), None),
+        };
+        span.extensions_mut().insert(SpanRecord {
+            trace_id,
+            span_id: random_id(),
+            parent_span_id,
+            start_ns: crate::now_ns().unwrap_or_default(),
+            attributes: fields.attributes,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(record) = extensions.get_mut::<SpanRecord>() else {
+            return;
+        };
+        let mut fields = FieldVisitor {
+            attributes: std::mem::take(&mut record.attributes),
+            traceparent: None,
+        };
+        values.record(&mut fields);
+        record.attributes = fields.attributes;
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(record) = span.extensions_mut().remove::<SpanRecord>() else {
+            return;
+        };
+        let value = span_json(span.name(), record, crate::now_ns().unwrap_or_default());
+        if self.tx.try_send(ExportItem::Span(value)).is_err() {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped.is_power_of_two() {
+                tracing::warn!(dropped, "OTLP span queue is full; dropping spans");
+            }
+        }
+    }
+}
+
+fn span_json(name: &str, record: SpanRecord, end_ns: u64) -> Value {
+    let kind = if SERVER_SPANS.contains(&name) {
+        SPAN_KIND_SERVER
+    } else {
+        SPAN_KIND_INTERNAL
+    };
+    let failed = record
+        .attributes
+        .get("status")
+        .and_then(Value::as_u64)
+        .is_some_and(|status| status >= 500);
+    let mut span = json!({
+        "traceId": hex_encode(&record.trace_id),
+        "spanId": hex_encode(&record.span_id),
+        "name": name,
+        "kind": kind,
+        "startTimeUnixNano": record.start_ns.to_string(),
+        "endTimeUnixNano": end_ns.max(record.start_ns).to_string(),
+        "attributes": record
+            .attributes
+            .into_iter()
+            .map(|(key, value)| key_value(&key, value))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(parent) = record.parent_span_id {
+        span["parentSpanId"] = json!(hex_encode(&parent));
+    }
+    if failed {
+        span["status"] = json!({ "code": STATUS_CODE_ERROR });
+    }
+    span
+}
+
+/// OTLP `KeyValue` for a plain JSON scalar.
+fn key_value(key: &str, value: Value) -> Value {
+    let value = match value {
+        Value::Bool(value) => json!({ "boolValue": value }),
+        Value::Number(number) => match number.as_i64() {
+            // OTLP JSON carries 64-bit integers as strings.
+            Some(int) => json!({ "intValue": int.to_string() }),
+            None => json!({ "doubleValue": number.as_f64() }),
+        },
+        Value::String(text) => json!({ "stringValue": text }),
+        other => json!({ "stringValue": other.to_string() }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    attributes: Map<String, Value>,
+    traceparent: Option<String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "traceparent" {
+            self.traceparent = Some(value.to_string());
+            return;
+        }
+        self.attributes
+            .insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.attributes
+            .insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.attributes
+            .insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.attributes
+            .insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.attributes
+            .insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.attributes
+            .insert(field.name().to_string(), json!(format!("{value:?}")));
+    }
+}
+
+/// Trace and parent span ids of a version-00 W3C `traceparent` header value.
+fn parse_traceparent(value: &str) -> Option<([u8; 16], [u8; 8])> {
+    let mut parts = value.trim().split('-');
+    let (Some("00"), Some(trace_id), Some(span_id), Some(flags), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return None;
+    };
+    if flags.len() != 2 || !flags.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let trace_id = hex_decode::<16>(trace_id)?;
+    let span_id = hex_decode::<8>(span_id)?;
+    if trace_id == [0; 16] || span_id == [0; 8] {
+        return None;
+    }
+    Some((trace_id, span_id))
+}
+
+/// Parse `key=value,key=value` lists (`OTEL_EXPORTER_OTLP_HEADERS`, `OTEL_RESOURCE_ATTRIBUTES`),
+/// percent-decoding values.
+fn parse_key_values(raw: &str, name: &str) -> Result<Vec<(String, String)>, Error> {
+    raw.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let invalid = || {
+                Error::new(ErrorKind::Usage)
+                    .with_message(format!("invalid {name} entry: {entry}"))
+                    .with_hint("Use comma-separated key=value pairs, e.g. key1=value1,key2=value2.")
+            };
+            let (key, value) = entry.split_once('=').ok_or_else(invalid)?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(invalid());
+            }
+            Ok((
+                key.to_string(),
+                percent_decode(value.trim()).ok_or_else(invalid)?,
+            ))
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = text.get(index + 1..index + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            out.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+fn random_id<const N: usize>() -> [u8; N] {
+    static FALLBACK: AtomicU64 = AtomicU64::new(1);
+    let mut bytes = [0u8; N];
+    if getrandom::fill(&mut bytes).is_err() || bytes == [0; N] {
+        // All-zero ids are invalid in OTLP; fall back to a process-unique counter.
+        let count =
+            FALLBACK.fetch_add(1, Ordering::Relaxed) ^ (u64::from(std::process::id()) << 32);
+        for (byte, source) in bytes.iter_mut().rev().zip(count.to_be_bytes().iter().rev()) {
+            *byte = *source;
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportItem, OtelConfig, OtlpSpanLayer, export_body};
+    use std::collections::HashMap;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn config_follows_standard_otel_variables() {
+        let config = |vars: &[(&str, &str)]| {
+            let vars = vars
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>();
+            OtelConfig::from_vars(|name| vars.get(name).cloned())
+        };
+        assert_eq!(config(&[]).expect("empty"), None);
+        assert_eq!(
+            config(&[
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://127.0.0.1:4318"),
+                ("OTEL_SDK_DISABLED", "true"),
+            ])
+            .expect("disabled"),
+            None
+        );
+        assert_eq!(
+            config(&[
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://127.0.0.1:4318"),
+                ("OTEL_TRACES_EXPORTER", "none"),
+            ])
+            .expect("none"),
+            None
+        );
+
+        let full = config(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://127.0.0.1:4318/"),
+            ("OTEL_EXPORTER_OTLP_HEADERS", "x-api-key=a%20b, tenant=blue"),
+            ("OTEL_EXPORTER_OTLP_TRACES_TIMEOUT", "2500"),
+            (
+                "OTEL_RESOURCE_ATTRIBUTES",
+                "service.name=edge,deployment.environment=prod",
+            ),
+        ])
+        .expect("config")
+        .expect("enabled");
+        assert_eq!(full.endpoint, "http://127.0.0.1:4318/v1/traces");
+        assert_eq!(
+            full.headers,
+            vec![
+                ("x-api-key".to_string(), "a b".to_string()),
+                ("tenant".to_string(), "blue".to_string()),
+            ]
+        );
+        assert_eq!(full.timeout, Duration::from_millis(2500));
+        assert_eq!(
+            full.resource[0],
+            ("service.name".to_string(), "edge".to_string())
+        );
+        assert!(
+            full.resource
+                .contains(&("deployment.environment".to_string(), "prod".to_string()))
+        );
+
+        let traces_only = config(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://ignored:4318"),
+            (
+                "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+                "https://collector/custom",
+            ),
+            ("OTEL_SERVICE_NAME", "api"),
+        ])
+        .expect("config")
+        .expect("enabled");
+        assert_eq!(traces_only.endpoint, "https://collector/custom");
+        assert_eq!(traces_only.resource[0].1, "api");
+
+        for bad in [
+            ("OTEL_EXPORTER_OTLP_PROTOCOL", "grpc"),
+            ("OTEL_TRACES_EXPORTER", "zipkin"),
+            ("OTEL_EXPORTER_OTLP_HEADERS", "novalue"),
+            ("OTEL_EXPORTER_OTLP_TIMEOUT", "soon"),
+        ] {
+            assert!(
+                config(&[
+                    ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://127.0.0.1:4318"),
+                    bad
+                ])
+                .is_err(),
+                "{bad:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn closed_spans_become_linked_otlp_spans() {
+        let (tx, rx) = mpsc::sync_channel(16);
+        let subscriber = tracing_subscriber::registry().with(OtlpSpanLayer::new(tx));
+        tracing::subscriber::with_default(subscriber, || {
+            let request = tracing::info_span!(
+                "request",
+                method = "POST",
+                traceparent = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+                status = tracing::field::Empty,
+            );
+            let _entered = request.enter();
+            tracing::info_span!("append", pool = "demo", bytes = 42u64).in_scope(|| {});
+            request.record("status", 503u64);
+        });
+        let spans = rx
+            .try_iter()
+            .map(|item| match item {
+                ExportItem::Span(span) => span,
+                ExportItem::Flush(_) => panic!("unexpected flush"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(spans.len(), 2);
+        let (append, request) = (&spans[0], &spans[1]);
+        assert_eq!(request["name"], "request");
+        assert_eq!(request["kind"], 2);
+        assert_eq!(request["traceId"], "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(request["parentSpanId"], "b7ad6b7169203331");
+        assert_eq!(request["status"]["code"], 2);
+        assert_eq!(append["kind"], 1);
+        assert_eq!(append["traceId"], request["traceId"]);
+        assert_eq!(append["parentSpanId"], request["spanId"]);
+        assert!(
+            append["attributes"]
+                .as_array()
+                .expect("attributes")
+                .contains(&serde_json::json!({ "key": "bytes", "value": { "intValue": "42" } }))
+        );
+
+        let body = export_body(
+            &[("service.name".to_string(), "plasmite".to_string())],
+            spans,
+        );
+        assert_eq!(
+            body["resourceSpans"][0]["scopeSpans"][0]["spans"]
+                .as_array()
+                .map(Vec::len),
+            Some(2)
+        );
+    }
+}
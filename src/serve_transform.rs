@@ -0,0 +1,294 @@
+//! Purpose: Per-pool ingest transforms for `plasmite serve --transforms FILE`.
+//! Exports: `IngestTransforms`, `TransformChain`.
+//! Role: Rewrites `.data` of remote submissions (`/append`, `/append_batch`) before they are
+//! appended, so clients with different field names land in one pool schema.
+//! Invariants: Steps run in file order; each names a dotted path under `.data` (`user.id`).
+//! Invariants: `rename` and `drop` of a missing field do nothing; `server_time` overwrites.
+//! Invariants: Non-object `.data` is appended unchanged.
+//! Invariants: Pools in a namespace are keyed `NS/POOL`; pool names never contain `/`.
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use plasmite::api::{Error, ErrorKind};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransformsFile {
+    #[serde(default)]
+    pools: BTreeMap<String, Vec<StepSpec>>,
+}
+
+/// One `[[pools.<name>]]` table as written in the file.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+enum StepSpec {
+    Rename { from: String, to: String },
+    Drop { field: String },
+    ServerTime { field: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Step {
+    Rename { from: Vec<String>, to: Vec<String> },
+    Drop { field: Vec<String> },
+    ServerTime { field: Vec<String> },
+}
+
+/// The steps for one pool, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransformChain {
+    steps: Vec<Step>,
+}
+
+/// Every chain in a `--transforms` file, keyed by pool (`NS/POOL` inside a namespace).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IngestTransforms {
+    pools: BTreeMap<String, Arc<TransformChain>>,
+}
+
+impl IngestTransforms {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to read transforms file")
+                .with_path(path)
+                .with_source(err)
+        })?;
+        Self::parse(&text).map_err(|err| err.with_path(path))
+    }
+
+    fn parse(text: &str) -> Result<Self, Error> {
+        let file: TransformsFile = toml::from_str(text).map_err(|err| {
+            Error::new(ErrorKind::Usage)
+                .with_message(format!("invalid transforms file: {}", err.message()))
+                .with_hint("Each [[pools.<name>]] table sets op = \"rename\" (from, to), \"drop\" (field), or \"server_time\" (field).")
+        })?;
+        let mut pools = BTreeMap::new();
+        for (pool, specs) in file.pools {
+            let steps = specs
+                .into_iter()
+                .map(|spec| Step::compile(&pool, spec))
+                .collect::<Result<Vec<_>, _>>()?;
+            pools.insert(pool, Arc::new(TransformChain { steps }));
+        }
+        Ok(Self { pools })
+    }
+
+    /// Chains for one pool directory: the root (`None`) or a namespace, keyed by bare pool name.
+    pub fn scoped(&self, namespace: Option<&str>) -> Self {
+        let pools = self
+            .pools
+            .iter()
+            .filter_map(|(key, chain)| {
+                let pool = match (namespace, key.split_once('/')) {
+                    (None, None) => key.as_str(),
+                    (Some(namespace), Some((ns, pool))) if ns == namespace => pool,
+                    _ => return None,
+                };
+                Some((pool.to_string(), chain.clone()))
+            })
+            .collect();
+        Self { pools }
+    }
+
+    pub fn chain(&self, pool: &str) -> Option<Arc<TransformChain>> {
+        self.pools.get(pool).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TransformChain)> {
+        self.pools
+            .iter()
+            .map(|(pool, chain)| (pool.as_str(), chain.as_ref()))
+    }
+
+    /// `{pool: [step, ...]}` with each step spelled as in `serve check` output.
+    pub fn to_json(&self) -> Value {
+        let pools: Map<String, Value> = self
+            .pools
+            .iter()
+            .map(|(pool, chain)| (pool.clone(), json!(chain.describe())))
+            .collect();
+        Value::Object(pools)
+    }
+}
+
+impl TransformChain {
+    /// Rewrite `data` as received at `received_ns`.
+    pub fn apply(&self, mut data: Value, received_ns: u64) -> Result<Value, Error> {
+        let Value::Object(object) = &mut data else {
+            return Ok(data);
+        };
+        for step in &self.steps {
+            match step {
+                Step::Rename { from, to } => {
+                    if let Some(value) = remove_path(object, from) {
+                        insert_path(object, to, value);
+                    }
+                }
+                Step::Drop { field } => {
+                    remove_path(object, field);
+                }
+                Step::ServerTime { field } => {
+                    insert_path(object, field, Value::String(crate::format_ts(received_ns)?));
+                }
+            }
+        }
+        Ok(data)
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        self.steps
+            .iter()
+            .map(|step| match step {
+                Step::Rename { from, to } => {
+                    format!("rename {} -> {}", from.join("."), to.join("."))
+                }
+                Step::Drop { field } => format!("drop {}", field.join(".")),
+                Step::ServerTime { field } => format!("server_time {}", field.join(".")),
+            })
+            .collect()
+    }
+}
+
+impl Step {
+    fn compile(pool: &str, spec: StepSpec) -> Result<Self, Error> {
+        let path = |field: &str| parse_field(pool, field);
+        Ok(match spec {
+            StepSpec::Rename { from, to } => {
+                let (from, to) = (path(&from)?, path(&to)?);
+                if to.starts_with(&from) {
+                    return Err(Error::new(ErrorKind::Usage)
+                        .with_message(format!(
+                            "transform for pool '{pool}' renames {} into itself",
+                            from.join(".")
+                        ))
+                        .with_hint("Rename to a path outside the source field."));
+                }
+                Step::Rename { from, to }
+            }
+            StepSpec::Drop { field } => Step::Drop {
+                field: path(&field)?,
+            },
+            StepSpec::ServerTime { field } => Step::ServerTime {
+                field: path(&field)?,
+            },
+        })
+    }
+}
+
+fn parse_field(pool: &str, field: &str) -> Result<Vec<String>, Error> {
+    let segments: Vec<String> = field.split('.').map(str::to_string).collect();
+    if segments.iter().any(String::is_empty) {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!(
+                "transform for pool '{pool}' has invalid field path '{field}'"
+            ))
+            .with_hint("Use a dotted path under .data without the leading dot, e.g. user.id."));
+    }
+    Ok(segments)
+}
+
+fn remove_path(object: &mut Map<String, Value>, path: &[String]) -> Option<Value> {
+    let (last, parents) = path.split_last()?;
+    let mut current = object;
+    for key in parents {
+        current = current.get_mut(key)?.as_object_mut()?;
+    }
+    current.remove(last)
+}
+
+/// Set `path`, creating objects for missing parents and replacing non-object ones.
+fn insert_path(object: &mut Map<String, Value>, path: &[String], value: Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut current = object;
+    for key in parents {
+        let entry = current
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        current = entry.as_object_mut().expect("object entry");
+    }
+    current.insert(last.clone(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IngestTransforms;
+    use serde_json::json;
+
+    #[test]
+    fn chains_rename_drop_and_stamp_in_order_per_scope() {
+        let transforms = IngestTransforms::parse(
+            r#"
+[[pools.events]]
+op = "rename"
+from = "msg"
+to = "message.text"
+
+[[pools.events]]
+op = "drop"
+field = "debug"
+
+[[pools.events]]
+op = "server_time"
+field = "received_at"
+
+[[pools."team/events"]]
+op = "drop"
+field = "secret"
+"#,
+        )
+        .expect("parse");
+
+        let root = transforms.scoped(None);
+        let chain = root.chain("events").expect("chain");
+        let out = chain
+            .apply(
+                json!({"msg": "hi", "debug": true, "keep": 1}),
+                1_000_000_000,
+            )
+            .expect("apply");
+        assert_eq!(
+            out,
+            json!({
+                "message": {"text": "hi"},
+                "keep": 1,
+                "received_at": "1970-01-01T00:00:01Z",
+            })
+        );
+        assert_eq!(chain.apply(json!([1, 2]), 0).expect("apply"), json!([1, 2]));
+        assert!(root.chain("team/events").is_none());
+
+        let team = transforms.scoped(Some("team"));
+        assert_eq!(
+            team.to_json(),
+            json!({"events": ["drop secret"]}),
+            "namespaced keys lose their prefix"
+        );
+
+        for bad in [
+            "[[pools.x]]\nop = \"drop\"\nfield = \"a..b\"\n",
+            "[[pools.x]]\nop = \"rename\"\nfrom = \"a\"\nto = \"a.b\"\n",
+            "[[pools.x]]\nop = \"upcase\"\nfield = \"a\"\n",
+            "[[pools.x]]\nop = \"drop\"\nfield = \"a\"\nextra = 1\n",
+        ] {
+            assert!(IngestTransforms::parse(bad).is_err(), "{bad}");
+        }
+    }
+}
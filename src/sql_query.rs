@@ -0,0 +1,208 @@
+//! Purpose: Run ad-hoc SQL over pool messages for `plasmite sql` via embedded DuckDB.
+//! Exports: `SqlTable`, `SqlResult`, `table_name`, `ensure_available`, `run_query`.
+//! Role: Loads decoded envelopes into a private in-memory database; reading pools stays in
+//! dispatch.
+//! Invariants: Each pool becomes a table named after the pool with columns
+//! `seq UBIGINT, time TIMESTAMP (UTC), tags VARCHAR[], data JSON`.
+//! Invariants: Queries never see pool files, so nothing a query does can mutate a pool.
+//! Invariants: Needs the `sql` cargo feature; without it the command is a usage error.
+use serde_json::Value;
+
+use crate::pool_export::ExportRow;
+use plasmite::api::{Error, ErrorKind};
+
+/// One pool loaded as a table.
+#[cfg_attr(not(feature = "sql"), allow(dead_code))]
+pub struct SqlTable {
+    pub name: String,
+    pub rows: Vec<ExportRow>,
+}
+
+/// Result columns in query order; each row holds one JSON value per column.
+#[derive(Debug, Default, PartialEq)]
+pub struct SqlResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Table name for a pool ref: the file stem, with anything but ASCII letters, digits, and
+/// `_` replaced by `_` (`logs/app-events.plasmite` -> `app_events`).
+pub fn table_name(pool: &str) -> String {
+    let stem = pool.rsplit(['/', '\\']).next().unwrap_or(pool);
+    let stem = stem.strip_suffix(".plasmite").unwrap_or(stem);
+    let name = stem
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if name.is_empty() || name.starts_with(|ch: char| ch.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+/// Fails fast (before any pool is loaded) when this build has no SQL engine.
+pub fn ensure_available() -> Result<(), Error> {
+    if cfg!(feature = "sql") {
+        return Ok(());
+    }
+    Err(Error::new(ErrorKind::Usage)
+        .with_message("SQL queries are not available in this build")
+        .with_hint(
+            "Rebuild with `--features sql`, or use `pool export --format parquet` with an external engine.",
+        ))
+}
+
+#[cfg(not(feature = "sql"))]
+pub fn run_query(tables: &[SqlTable], query: &str) -> Result<SqlResult, Error> {
+    let _ = (tables, query);
+    ensure_available().map(|()| SqlResult::default())
+}
+
+#[cfg(feature = "sql")]
+pub fn run_query(tables: &[SqlTable], query: &str) -> Result<SqlResult, Error> {
+    use duckdb::{Connection, params};
+
+    let internal = |err: duckdb::Error| {
+        Error::new(ErrorKind::Internal)
+            .with_message("failed to load pool into DuckDB")
+            .with_source(err)
+    };
+    let conn = Connection::open_in_memory().map_err(internal)?;
+    for table in tables {
+        conn.execute_batch(
+            "CREATE TABLE plasmite_load (seq UBIGINT, time_us BIGINT, tags VARCHAR, data VARCHAR)",
+        )
+        .map_err(internal)?;
+        {
+            let mut appender = conn.appender("plasmite_load").map_err(internal)?;
+            for row in &table.rows {
+                let tags = row
+                    .message
+                    .get("meta")
+                    .and_then(|meta| meta.get("tags"))
+                    .cloned()
+                    .unwrap_or_else(|| Value::Array(Vec::new()));
+                let data = row.message.get("data").cloned().unwrap_or(Value::Null);
+                appender
+                    .append_row(params![
+                        row.seq,
+                        (row.timestamp_ns / 1_000) as i64,
+                        tags.to_string(),
+                        data.to_string(),
+                    ])
+                    .map_err(internal)?;
+            }
+            appender.flush().map_err(internal)?;
+        }
+        conn.execute_batch(&format!(
+            "CREATE TABLE {} AS SELECT seq, make_timestamp(time_us) AS time, \
+             from_json(tags, '[\"VARCHAR\"]') AS tags, CAST(data AS JSON) AS data \
+             FROM plasmite_load ORDER BY seq; DROP TABLE plasmite_load;",
+            quote_ident(&table.name)
+        ))
+        .map_err(internal)?;
+    }
+
+    let query = query.trim().trim_end_matches(';');
+    let query_error = |err: duckdb::Error| {
+        Error::new(ErrorKind::Usage)
+            .with_message("SQL query failed")
+            .with_hint(err.to_string())
+    };
+    let columns = {
+        let mut stmt = conn
+            .prepare(&format!("DESCRIBE {query}"))
+            .map_err(query_error)?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(query_error)?;
+        names.collect::<Result<Vec<_>, _>>().map_err(query_error)?
+    };
+    // Each result row comes back as one JSON object, so every DuckDB type (lists, structs,
+    // timestamps, decimals) renders the way DuckDB's own JSON functions render it.
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT CAST(to_json(plasmite_row) AS VARCHAR) FROM ({query}) AS plasmite_row"
+        ))
+        .map_err(query_error)?;
+    let objects = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(query_error)?;
+    let mut rows = Vec::new();
+    for object in objects {
+        let object: Value = serde_json::from_str(&object.map_err(query_error)?).map_err(|err| {
+            Error::new(ErrorKind::Internal)
+                .with_message("failed to decode SQL result row")
+                .with_source(err)
+        })?;
+        rows.push(
+            columns
+                .iter()
+                .map(|column| object.get(column).cloned().unwrap_or(Value::Null))
+                .collect(),
+        );
+    }
+    Ok(SqlResult { columns, rows })
+}
+
+#[cfg(feature = "sql")]
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::table_name;
+
+    #[test]
+    fn table_names_follow_pool_stems() {
+        assert_eq!(table_name("events"), "events");
+        assert_eq!(table_name("logs/app-events.plasmite"), "app_events");
+        assert_eq!(table_name("2026.plasmite"), "_2026");
+    }
+
+    #[cfg(feature = "sql")]
+    #[test]
+    fn queries_loaded_pool_tables() {
+        use super::{SqlTable, run_query};
+        use crate::pool_export::ExportRow;
+        use serde_json::json;
+
+        let rows = (1..=3)
+            .map(|seq| ExportRow {
+                seq,
+                timestamp_ns: seq * 1_000_000_000,
+                message: json!({
+                    "seq": seq,
+                    "meta": {"tags": ["a"]},
+                    "data": {"level": if seq == 2 { "error" } else { "info" }},
+                }),
+            })
+            .collect();
+        let tables = [SqlTable {
+            name: "events".to_string(),
+            rows,
+        }];
+        let result = run_query(
+            &tables,
+            "SELECT data->>'level' AS level, count(*) AS n FROM events GROUP BY 1 ORDER BY 1;",
+        )
+        .expect("query");
+        assert_eq!(result.columns, vec!["level", "n"]);
+        assert_eq!(
+            result.rows,
+            vec![
+                vec![json!("error"), json!(1)],
+                vec![json!("info"), json!(2)]
+            ]
+        );
+        assert!(run_query(&tables, "SELECT nope FROM events").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Purpose: Record periodic pool metric snapshots into the built-in `_stats` pool.
+//! Exports: `StatsRecorder`, `STATS_POOL`.
+//! Role: Shared by `plasmite statsd` and `serve --stats-pool`, so growth trends can be graphed
+//! from `follow _stats` or `sql` instead of an external scraper.
+//! Invariants: Each tick appends one message per pool, tagged `stats`, whose `.data` holds
+//! `pool`, `count`, `used_bytes`, `free_bytes`, `used_percent`, `newest_seq`, and `append_rate`.
+//! Invariants: `append_rate` is messages per second since the previous tick; it is null on a
+//! pool's first tick and after the pool was recreated (its newest seq went backwards).
+//! Invariants: `_stats` is created on first use and never samples itself; pools that fail to
+//! open are skipped for that tick.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use serde_json::{Value, json};
+
+use crate::pool_paths::{find_pool_files, pool_name_for_path};
+use plasmite::api::{Durability, Error, ErrorKind, Pool, PoolApiExt, PoolInfo, PoolOptions};
+
+pub const STATS_POOL: &str = "_stats";
+const STATS_POOL_SIZE: u64 = 8 * 1024 * 1024;
+const STATS_TAG: &str = "stats";
+
+pub struct StatsRecorder {
+    pool_dir: PathBuf,
+    stats_path: PathBuf,
+    /// Newest seq and sample time per pool at the previous tick.
+    previous: HashMap<PathBuf, (u64, Instant)>,
+}
+
+impl StatsRecorder {
+    pub fn new(pool_dir: &Path) -> Self {
+        Self {
+            pool_dir: pool_dir.to_path_buf(),
+            stats_path: pool_dir.join(format!("{STATS_POOL}.plasmite")),
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Sample every pool once and append the snapshots; returns how many were recorded.
+    pub fn tick(&mut self) -> Result<usize, Error> {
+        let paths = find_pool_files(&self.pool_dir).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to read pool directory")
+                .with_path(&self.pool_dir)
+                .with_source(err)
+        })?;
+        let now = Instant::now();
+        let mut previous = HashMap::new();
+        let mut samples = Vec::new();
+        for path in paths {
+            if path == self.stats_path {
+                continue;
+            }
+            let Ok(info) = Pool::open_read_only(&path).and_then(|pool| pool.info()) else {
+                continue;
+            };
+            let newest = info.bounds.newest_seq.unwrap_or(0);
+            let append_rate = match self.previous.get(&path) {
+                Some((prev, at)) if newest >= *prev => {
+                    let seconds = now.duration_since(*at).as_secs_f64();
+                    (seconds > 0.0)
+                        .then(|| ((newest - prev) as f64 / seconds * 100.0).round() / 100.0)
+                }
+                _ => None,
+            };
+            let name = pool_name_for_path(&self.pool_dir, &path)
+                .unwrap_or_else(|| path.display().to_string());
+            samples.push(sample_json(&name, &info, append_rate));
+            previous.insert(path, (newest, now));
+        }
+        self.previous = previous;
+
+        let mut pool = self.open_stats_pool()?;
+        let tags = [STATS_TAG.to_string()];
+        for data in &samples {
+            pool.append_json_now(data, &tags, Durability::Fast)?;
+        }
+        Ok(samples.len())
+    }
+
+    fn open_stats_pool(&self) -> Result<Pool, Error> {
+        match Pool::open(&self.stats_path) {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                match Pool::create(&self.stats_path, PoolOptions::new(STATS_POOL_SIZE)) {
+                    Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                        Pool::open(&self.stats_path)
+                    }
+                    result => result,
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+fn sample_json(name: &str, info: &PoolInfo, append_rate: Option<f64>) -> Value {
+    let (count, used_bytes, free_bytes, used_percent) = match &info.metrics {
+        Some(metrics) => (
+            metrics.message_count,
+            metrics.utilization.used_bytes,
+            metrics.utilization.free_bytes,
+            metrics.utilization.used_percent_hundredths as f64 / 100.0,
+        ),
+        None => (0, 0, info.ring_size, 0.0),
+    };
+    json!({
+        "pool": name,
+        "count": count,
+        "used_bytes": used_bytes,
+        "free_bytes": free_bytes,
+        "used_percent": used_percent,
+        "newest_seq": info.bounds.newest_seq,
+        "append_rate": append_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{STATS_POOL, StatsRecorder};
+    use plasmite::api::{Durability, Pool, PoolApiExt, PoolOptions};
+    use serde_json::json;
+
+    #[test]
+    fn ticks_record_each_pool_and_rate_after_the_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("team")).expect("mkdir");
+        let mut events = Pool::create(
+            dir.path().join("events.plasmite"),
+            PoolOptions::new(1 << 20),
+        )
+        .expect("create");
+        Pool::create(
+            dir.path().join("team/jobs.plasmite"),
+            PoolOptions::new(1 << 20),
+        )
+        .expect("create");
+
+        let mut recorder = StatsRecorder::new(dir.path());
+        assert_eq!(recorder.tick().expect("tick"), 2);
+        for n in 0..3 {
+            events
+                .append_json_now(&json!({ "n": n }), &[], Durability::Fast)
+                .expect("append");
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert_eq!(recorder.tick().expect("tick"), 2, "_stats is not sampled");
+
+        let stats = Pool::open(dir.path().join(format!("{STATS_POOL}.plasmite"))).expect("open");
+        let samples = (1..=4)
+            .map(|seq| stats.get_message(seq).expect("message").data)
+            .collect::<Vec<_>>();
+        assert_eq!(samples[0]["pool"], json!("events"));
+        assert_eq!(samples[1]["pool"], json!("team/jobs"));
+        assert_eq!(samples[0]["append_rate"], json!(null));
+        assert_eq!(samples[2]["count"], json!(3));
+        assert_eq!(samples[2]["newest_seq"], json!(3));
+        assert!(samples[2]["append_rate"].as_f64().expect("rate") > 0.0);
+        assert_eq!(samples[3]["append_rate"], json!(0.0));
+    }
+}
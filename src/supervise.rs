@@ -0,0 +1,300 @@
+//! Purpose: Parse `plasmite run` stack configs and decide child restarts.
+//! Exports: `RunConfig`, `ProcessSpec`, `RestartPolicy`, `RestartPlan`, `load_config`.
+//! Role: Pure config/policy helpers for the supervisor; process spawning stays in dispatch.
+//! Invariants: Children are this executable re-invoked (`tap`/`serve`) with an explicit `--dir`.
+//! Invariants: Restart delays double from `backoff` up to `MAX_BACKOFF`; `max_restarts` counts
+//! consecutive restarts and resets once a child stays up for `STABLE_AFTER`.
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use plasmite::api::{Error, ErrorKind};
+use serde::Deserialize;
+
+use super::{parse_duration, parse_size};
+
+const DEFAULT_CONTROL_POOL: &str = "run";
+const DEFAULT_MAX_RESTARTS: u32 = 5;
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Uptime after which a child's consecutive-restart count resets.
+pub const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunConfig {
+    /// Pool receiving lifecycle events (created if missing).
+    #[serde(default = "default_control_pool")]
+    pub control_pool: String,
+    #[serde(default)]
+    pub pools: Vec<PoolSpec>,
+    #[serde(default)]
+    pub tap: Vec<TapSpec>,
+    pub serve: Option<ServeSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolSpec {
+    pub name: String,
+    pub size: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TapSpec {
+    pub name: String,
+    pub pool: String,
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    pub max_restarts: Option<u32>,
+    pub backoff: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServeSpec {
+    /// Extra `plasmite serve` flags, e.g. `["--bind", "127.0.0.1:9701"]`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    pub max_restarts: Option<u32>,
+    pub backoff: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    Never,
+    #[default]
+    OnFailure,
+    Always,
+}
+
+impl RestartPolicy {
+    pub fn wants_restart(self, success: bool) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => !success,
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RestartPlan {
+    pub policy: RestartPolicy,
+    pub max_restarts: u32,
+    pub backoff: Duration,
+}
+
+impl RestartPlan {
+    /// Delay before restart number `restarts + 1`, or `None` once the budget is spent.
+    pub fn next_delay(&self, restarts: u32) -> Option<Duration> {
+        if restarts >= self.max_restarts {
+            return None;
+        }
+        let factor = 1u32.checked_shl(restarts.min(16)).unwrap_or(u32::MAX);
+        Some(self.backoff.saturating_mul(factor).min(MAX_BACKOFF))
+    }
+}
+
+/// One supervised child: a display name, the argv passed to this executable, and its policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessSpec {
+    pub name: String,
+    pub argv: Vec<String>,
+    pub restart: RestartPlan,
+}
+
+pub fn load_config(path: &Path) -> Result<RunConfig, Error> {
+    let text = std::fs::read_to_string(path).map_err(|err| {
+        let kind = if err.kind() == std::io::ErrorKind::NotFound {
+            ErrorKind::NotFound
+        } else {
+            ErrorKind::Io
+        };
+        Error::new(kind)
+            .with_message("failed to read run config")
+            .with_path(path)
+            .with_source(err)
+    })?;
+    let config: RunConfig = toml::from_str(&text).map_err(|err| {
+        Error::new(ErrorKind::Usage)
+            .with_message(format!("invalid run config: {}", err.message()))
+            .with_path(path)
+            .with_hint("See `plasmite run --help` for the config format.")
+    })?;
+    config.validate()?;
+    Ok(config)
+}
+
+impl RunConfig {
+    fn validate(&self) -> Result<(), Error> {
+        for pool in &self.pools {
+            if let Some(size) = &pool.size {
+                parse_size(size)?;
+            }
+        }
+        let mut names = HashSet::new();
+        for tap in &self.tap {
+            if !names.insert(tap.name.as_str()) || tap.name == "serve" {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message(format!("duplicate process name: {}", tap.name))
+                    .with_hint("Give each [[tap]] a unique name (\"serve\" is reserved)."));
+            }
+            if tap.command.is_empty() {
+                return Err(Error::new(ErrorKind::Usage)
+                    .with_message(format!("tap {} has an empty command", tap.name))
+                    .with_hint("Set command = [\"program\", \"arg\", ...]."));
+            }
+            restart_plan(tap.restart, tap.max_restarts, tap.backoff.as_deref())?;
+        }
+        if let Some(serve) = &self.serve {
+            restart_plan(serve.restart, serve.max_restarts, serve.backoff.as_deref())?;
+        }
+        Ok(())
+    }
+
+    /// Children to supervise, in config order (`serve` last).
+    pub fn processes(&self, pool_dir: &Path) -> Result<Vec<ProcessSpec>, Error> {
+        let dir = pool_dir.display().to_string();
+        let mut processes = Vec::new();
+        for tap in &self.tap {
+            let mut argv = vec!["--dir".to_string(), dir.clone(), "tap".to_string()];
+            argv.push(tap.pool.clone());
+            argv.push("--create".to_string());
+            for tag in &tap.tags {
+                argv.push("--tag".to_string());
+                argv.push(tag.clone());
+            }
+            argv.push("--".to_string());
+            argv.extend(tap.command.iter().cloned());
+            processes.push(ProcessSpec {
+                name: tap.name.clone(),
+                argv,
+                restart: restart_plan(tap.restart, tap.max_restarts, tap.backoff.as_deref())?,
+            });
+        }
+        if let Some(serve) = &self.serve {
+            let mut argv = vec!["--dir".to_string(), dir, "serve".to_string()];
+            argv.extend(serve.args.iter().cloned());
+            processes.push(ProcessSpec {
+                name: "serve".to_string(),
+                argv,
+                restart: restart_plan(serve.restart, serve.max_restarts, serve.backoff.as_deref())?,
+            });
+        }
+        Ok(processes)
+    }
+}
+
+fn restart_plan(
+    policy: RestartPolicy,
+    max_restarts: Option<u32>,
+    backoff: Option<&str>,
+) -> Result<RestartPlan, Error> {
+    Ok(RestartPlan {
+        policy,
+        max_restarts: max_restarts.unwrap_or(DEFAULT_MAX_RESTARTS),
+        backoff: backoff
+            .map(parse_duration)
+            .transpose()?
+            .unwrap_or(DEFAULT_BACKOFF),
+    })
+}
+
+fn default_control_pool() -> String {
+    DEFAULT_CONTROL_POOL.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RestartPolicy, RunConfig};
+    use std::path::Path;
+    use std::time::Duration;
+
+    #[test]
+    fn processes_expand_taps_and_serve() {
+        let config: RunConfig = toml::from_str(
+            r#"
+            [[pools]]
+            name = "events"
+            size = "8M"
+
+            [[tap]]
+            name = "api"
+            pool = "api-logs"
+            command = ["./server", "--port", "8080"]
+            tags = ["api"]
+            restart = "always"
+            backoff = "250ms"
+
+            [serve]
+            args = ["--bind", "127.0.0.1:9701"]
+            "#,
+        )
+        .expect("parse");
+        config.validate().expect("valid");
+        assert_eq!(config.control_pool, "run");
+
+        let processes = config.processes(Path::new("/pools")).expect("processes");
+        assert_eq!(processes[0].name, "api");
+        assert_eq!(
+            processes[0].argv,
+            [
+                "--dir", "/pools", "tap", "api-logs", "--create", "--tag", "api", "--", "./server",
+                "--port", "8080"
+            ]
+        );
+        assert_eq!(processes[0].restart.policy, RestartPolicy::Always);
+        assert_eq!(processes[1].name, "serve");
+        assert_eq!(processes[1].restart.policy, RestartPolicy::OnFailure);
+    }
+
+    #[test]
+    fn restart_delays_double_until_budget_is_spent() {
+        let config: RunConfig = toml::from_str(
+            r#"
+            [[tap]]
+            name = "job"
+            pool = "jobs"
+            command = ["true"]
+            max_restarts = 3
+            backoff = "1s"
+            "#,
+        )
+        .expect("parse");
+        let plan = config.processes(Path::new("/p")).expect("processes")[0].restart;
+        assert_eq!(plan.next_delay(0), Some(Duration::from_secs(1)));
+        assert_eq!(plan.next_delay(2), Some(Duration::from_secs(4)));
+        assert_eq!(plan.next_delay(3), None);
+        assert!(RestartPolicy::OnFailure.wants_restart(false));
+        assert!(!RestartPolicy::OnFailure.wants_restart(true));
+        assert!(!RestartPolicy::Never.wants_restart(false));
+    }
+
+    #[test]
+    fn validate_rejects_duplicate_names_and_unknown_keys() {
+        let config: RunConfig = toml::from_str(
+            r#"
+            [[tap]]
+            name = "a"
+            pool = "p"
+            command = ["x"]
+
+            [[tap]]
+            name = "a"
+            pool = "q"
+            command = ["y"]
+            "#,
+        )
+        .expect("parse");
+        assert!(config.validate().is_err());
+        assert!(toml::from_str::<RunConfig>("unknown = 1").is_err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Purpose: Parse syslog datagrams (RFC 5424 and RFC 3164) into JSON message bodies.
+//! Exports: `SyslogListen`, `MAX_DATAGRAM_BYTES`, `parse_listen`, `parse_datagram`.
+//! Role: Pure parsing helpers for `plasmite syslog`; socket IO stays in command dispatch.
+//! Invariants: Parsing never fails; unparseable headers degrade to `msg`-only records.
+//! Invariants: Output shape is stable: every record carries the same top-level keys.
+//! Invariants: Missing PRI defaults to user.notice (13) per RFC 3164 section 4.3.3.
+use std::net::SocketAddr;
+
+use plasmite::api::{Error, ErrorKind};
+use serde_json::{Map, Value, json};
+
+/// Largest UDP payload; syslog senders may not exceed it.
+pub const MAX_DATAGRAM_BYTES: usize = 64 * 1024;
+
+const DEFAULT_PRI: u8 = 13;
+const NIL: &str = "-";
+
+const SEVERITY_NAMES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+const FACILITY_NAMES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SyslogListen {
+    pub addr: SocketAddr,
+}
+
+/// Parse `udp:<port>` or `udp:<host>:<port>` into a bind address.
+pub fn parse_listen(input: &str) -> Result<SyslogListen, Error> {
+    let Some((scheme, rest)) = input.split_once(':') else {
+        return Err(listen_error(input));
+    };
+    if scheme != "udp" {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message(format!("unsupported syslog transport: {scheme}"))
+            .with_hint("Only UDP is supported in v0 (for example `--listen udp:514`)."));
+    }
+    if let Ok(port) = rest.parse::<u16>() {
+        return Ok(SyslogListen {
+            addr: SocketAddr::from(([0, 0, 0, 0], port)),
+        });
+    }
+    let addr = rest
+        .parse::<SocketAddr>()
+        .map_err(|_| listen_error(input))?;
+    Ok(SyslogListen { addr })
+}
+
+fn listen_error(input: &str) -> Error {
+    Error::new(ErrorKind::Usage)
+        .with_message(format!("invalid --listen value: {input}"))
+        .with_hint("Use udp:<port> or udp:<host>:<port> (for example `udp:5514`).")
+}
+
+/// Parse one datagram into the JSON body appended to the pool.
+pub fn parse_datagram(datagram: &[u8], peer: Option<SocketAddr>) -> Value {
+    let text = String::from_utf8_lossy(datagram);
+    let text = text.trim_end_matches(['\r', '\n', '\0']);
+    let (pri, rest) = split_pri(text);
+    let mut record = if let Some(body) = rest.strip_prefix("1 ") {
+        parse_rfc5424(body)
+    } else {
+        parse_rfc3164(rest)
+    };
+    let pri = pri.unwrap_or(DEFAULT_PRI);
+    let facility = usize::from(pri >> 3);
+    let severity = usize::from(pri & 0x07);
+    record.insert("pri".to_string(), json!(pri));
+    record.insert(
+        "facility".to_string(),
+        json!(FACILITY_NAMES.get(facility).copied().unwrap_or("unknown")),
+    );
+    record.insert("severity".to_string(), json!(SEVERITY_NAMES[severity]));
+    record.insert(
+        "peer".to_string(),
+        peer.map(|addr| json!(addr.to_string()))
+            .unwrap_or(Value::Null),
+    );
+    Value::Object(record)
+}
+
+fn split_pri(text: &str) -> (Option<u8>, &str) {
+    let Some(rest) = text.strip_prefix('<') else {
+        return (None, text);
+    };
+    let Some(end) = rest.find('>') else {
+        return (None, text);
+    };
+    let digits = &rest[..end];
+    if digits.is_empty() || digits.len() > 3 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return (None, text);
+    }
+    match digits.parse::<u8>() {
+        Ok(pri) if pri <= 191 => (Some(pri), &rest[end + 1..]),
+        _ => (None, text),
+    }
+}
+
+fn empty_record(format: &str) -> Map<String, Value> {
+    let mut record = Map::new();
+    record.insert("format".to_string(), json!(format));
+    for key in ["time", "host", "app", "pid", "msgid", "sd", "msg"] {
+        record.insert(key.to_string(), Value::Null);
+    }
+    record
+}
+
+fn nil_or(value: &str) -> Value {
+    if value == NIL {
+        Value::Null
+    } else {
+        json!(value)
+    }
+}
+
+fn parse_rfc5424(body: &str) -> Map<String, Value> {
+    let mut record = empty_record("rfc5424");
+    let mut fields = body.splitn(6, ' ');
+    let (Some(time), Some(host), Some(app), Some(pid), Some(msgid), Some(rest)) = (
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+    ) else {
+        record.insert("msg".to_string(), json!(body));
+        return record;
+    };
+    record.insert("time".to_string(), nil_or(time));
+    record.insert("host".to_string(), nil_or(host));
+    record.insert("app".to_string(), nil_or(app));
+    record.insert("pid".to_string(), nil_or(pid));
+    record.insert("msgid".to_string(), nil_or(msgid));
+
+    let (sd, msg) = match rest.strip_prefix(NIL) {
+        Some(msg) => (Value::Null, msg),
+        None => match parse_structured_data(rest) {
+            Some((sd, msg)) => (sd, msg),
+            None => (Value::Null, rest),
+        },
+    };
+    record.insert("sd".to_string(), sd);
+    let msg = msg.strip_prefix(' ').unwrap_or(msg);
+    let msg = msg.strip_prefix('\u{feff}').unwrap_or(msg);
+    if !msg.is_empty() {
+        record.insert("msg".to_string(), json!(msg));
+    }
+    record
+}
+
+/// Parse `[id k="v" ...]...` into `{id: {k: v}}`, returning the unconsumed tail.
+fn parse_structured_data(input: &str) -> Option<(Value, &str)> {
+    let mut elements = Map::new();
+    let mut rest = input;
+    while let Some(element) = rest.strip_prefix('[') {
+        let id_end = element.find([' ', ']'])?;
+        let id = &element[..id_end];
+        let mut params = Map::new();
+        let mut cursor = &element[id_end..];
+        loop {
+            cursor = cursor.trim_start_matches(' ');
+            if let Some(after) = cursor.strip_prefix(']') {
+                rest = after;
+                break;
+            }
+            let eq = cursor.find('=')?;
+            let name = &cursor[..eq];
+            let quoted = cursor[eq + 1..].strip_prefix('"')?;
+            let (value, after) = take_sd_value(quoted)?;
+            params.insert(name.to_string(), json!(value));
+            cursor = after;
+        }
+        elements.insert(id.to_string(), Value::Object(params));
+    }
+    if elements.is_empty() {
+        return None;
+    }
+    Some((Value::Object(elements), rest))
+}
+
+fn take_sd_value(input: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\' | ']'))) => value.push(escaped),
+                Some((_, other)) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => return None,
+            },
+            '"' => return Some((value, &input[idx + 1..])),
+            other => value.push(other),
+        }
+    }
+    None
+}
+
+fn parse_rfc3164(body: &str) -> Map<String, Value> {
+    let mut record = empty_record("rfc3164");
+    let Some((time, rest)) = split_bsd_timestamp(body) else {
+        record.insert("msg".to_string(), json!(body));
+        return record;
+    };
+    record.insert("time".to_string(), json!(time));
+
+    let (host, rest) = match rest.split_once(' ') {
+        Some((host, rest)) if !host.ends_with(':') && !host.contains('[') => (Some(host), rest),
+        _ => (None, rest),
+    };
+    if let Some(host) = host {
+        record.insert("host".to_string(), json!(host));
+    }
+
+    let (tag, msg) = match rest.split_once(": ") {
+        Some((tag, msg)) if is_bsd_tag(tag) => (Some(tag), msg),
+        _ => match rest.strip_suffix(':') {
+            Some(tag) if is_bsd_tag(tag) => (Some(tag), ""),
+            _ => (None, rest),
+        },
+    };
+    if let Some(tag) = tag {
+        match tag.split_once('[') {
+            Some((app, pid)) => {
+                record.insert("app".to_string(), json!(app));
+                record.insert(
+                    "pid".to_string(),
+                    json!(pid.strip_suffix(']').unwrap_or(pid)),
+                );
+            }
+            None => {
+                record.insert("app".to_string(), json!(tag));
+            }
+        }
+    }
+    if !msg.is_empty() {
+        record.insert("msg".to_string(), json!(msg));
+    }
+    record
+}
+
+/// Split a leading `Mmm dd hh:mm:ss ` timestamp (day may be space-padded).
+fn split_bsd_timestamp(body: &str) -> Option<(&str, &str)> {
+    const LEN: usize = "Mmm dd hh:mm:ss".len();
+    let candidate = body.get(..LEN)?;
+    let bytes = candidate.as_bytes();
+    let month_ok = candidate
+        .get(..3)
+        .is_some_and(|month| MONTHS.contains(&month));
+    let day_ok = bytes[3] == b' '
+        && (bytes[4] == b' ' || bytes[4].is_ascii_digit())
+        && bytes[5].is_ascii_digit();
+    let clock_ok = bytes[6] == b' '
+        && bytes[9] == b':'
+        && bytes[12] == b':'
+        && [7, 8, 10, 11, 13, 14]
+            .iter()
+            .all(|idx| bytes[*idx].is_ascii_digit());
+    if !(month_ok && day_ok && clock_ok) {
+        return None;
+    }
+    let rest = body[LEN..].strip_prefix(' ').unwrap_or(&body[LEN..]);
+    Some((candidate, rest))
+}
+
+fn is_bsd_tag(tag: &str) -> bool {
+    !tag.is_empty() && tag.len() <= 48 && !tag.contains(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_datagram, parse_listen};
+    use plasmite::api::ErrorKind;
+    use serde_json::{Value, json};
+    use std::net::SocketAddr;
+
+    #[test]
+    fn parse_listen_accepts_port_and_host_forms() {
+        let listen = parse_listen("udp:514").expect("port");
+        assert_eq!(listen.addr, "0.0.0.0:514".parse::<SocketAddr>().unwrap());
+        let listen = parse_listen("udp:127.0.0.1:5514").expect("host:port");
+        assert_eq!(listen.addr, "127.0.0.1:5514".parse::<SocketAddr>().unwrap());
+    }
+
+    #[test]
+    fn parse_listen_rejects_other_transports() {
+        let err = parse_listen("tcp:514").expect_err("tcp");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+        let err = parse_listen("514").expect_err("bare port");
+        assert_eq!(err.kind(), ErrorKind::Usage);
+    }
+
+    #[test]
+    fn parses_rfc5424_with_structured_data() {
+        let datagram = br#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="Application"] An application event"#;
+        let value = parse_datagram(datagram, None);
+        assert_eq!(value["format"], "rfc5424");
+        assert_eq!(value["facility"], "local4");
+        assert_eq!(value["severity"], "notice");
+        assert_eq!(value["host"], "mymachine.example.com");
+        assert_eq!(value["app"], "evntslog");
+        assert_eq!(value["pid"], Value::Null);
+        assert_eq!(value["msgid"], "ID47");
+        assert_eq!(
+            value["sd"],
+            json!({"exampleSDID@32473": {"iut": "3", "eventSource": "Application"}})
+        );
+        assert_eq!(value["msg"], "An application event");
+    }
+
+    #[test]
+    fn parses_rfc5424_with_nil_fields() {
+        let value = parse_datagram(b"<34>1 - - - - - -", None);
+        assert_eq!(value["severity"], "crit");
+        assert_eq!(value["facility"], "auth");
+        assert_eq!(value["time"], Value::Null);
+        assert_eq!(value["host"], Value::Null);
+        assert_eq!(value["msg"], Value::Null);
+    }
+
+    #[test]
+    fn parses_rfc3164_with_pid() {
+        let datagram = b"<34>Oct 11 22:14:15 mymachine su[123]: 'su root' failed for lonvick\n";
+        let value = parse_datagram(datagram, Some("10.0.0.1:514".parse().unwrap()));
+        assert_eq!(value["format"], "rfc3164");
+        assert_eq!(value["time"], "Oct 11 22:14:15");
+        assert_eq!(value["host"], "mymachine");
+        assert_eq!(value["app"], "su");
+        assert_eq!(value["pid"], "123");
+        assert_eq!(value["msg"], "'su root' failed for lonvick");
+        assert_eq!(value["peer"], "10.0.0.1:514");
+    }
+
+    #[test]
+    fn unparseable_datagram_keeps_raw_text_as_msg() {
+        let value = parse_datagram(b"hello world", None);
+        assert_eq!(value["pri"], 13);
+        assert_eq!(value["severity"], "notice");
+        assert_eq!(value["host"], Value::Null);
+        assert_eq!(value["msg"], "hello world");
+    }
+}
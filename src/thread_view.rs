@@ -0,0 +1,146 @@
+//! Purpose: Group followed messages by a correlation key for `follow --thread`.
+//! Exports: `ThreadView`, `DEFAULT_THREAD_WINDOW`.
+//! Role: Buffers follow output for a short window, then prints it grouped per thread.
+//! Invariants: Groups print in order of each thread's first buffered message; seq order is
+//! kept within a group. Messages without a key share the `-` group.
+//! Invariants: A ticker thread flushes windows during quiet periods; `finish` flushes the rest.
+use std::io::Write;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::downstream;
+use crate::jq_filter::JqFilter;
+
+/// How long follow output is held back so interleaved threads can be regrouped.
+pub const DEFAULT_THREAD_WINDOW: Duration = Duration::from_millis(500);
+
+const NO_THREAD: &str = "-";
+
+pub struct ThreadView {
+    key: JqFilter,
+    window: Duration,
+    pretty: bool,
+    /// Buffered (thread key, rendered line) pairs in arrival order.
+    pending: Vec<(String, String)>,
+    oldest: Option<Instant>,
+}
+
+impl ThreadView {
+    /// Create a view and its background ticker; the ticker exits once the view is dropped.
+    pub fn start(key: JqFilter, window: Duration, pretty: bool) -> Arc<Mutex<Self>> {
+        let view = Arc::new(Mutex::new(Self {
+            key,
+            window,
+            pretty,
+            pending: Vec::new(),
+            oldest: None,
+        }));
+        let weak = Arc::downgrade(&view);
+        let tick = (window / 2).max(Duration::from_millis(10));
+        std::thread::spawn(move || ticker(weak, tick));
+        view
+    }
+
+    /// Buffer one message. `message` is the full envelope used for the key; `output` is what
+    /// follow would otherwise print (already narrowed by `--data-only`).
+    pub fn push(&mut self, message: &Value, output: &Value) {
+        let key = self
+            .key
+            .key(message)
+            .unwrap_or_else(|| NO_THREAD.to_string());
+        let line = if self.pretty {
+            let seq = message.get("seq").and_then(Value::as_u64).unwrap_or(0);
+            let time = message.get("time").and_then(Value::as_str).unwrap_or("-");
+            let data = message.get("data").unwrap_or(&Value::Null);
+            format!(
+                "  #{seq} {time} {}",
+                serde_json::to_string(data).unwrap_or_default()
+            )
+        } else {
+            serde_json::to_string(output).unwrap_or_default()
+        };
+        self.oldest.get_or_insert_with(Instant::now);
+        self.pending.push((key, line));
+    }
+
+    /// Print buffered messages if the oldest one has waited a full window.
+    fn flush_due(&mut self) {
+        if self
+            .oldest
+            .is_some_and(|oldest| oldest.elapsed() >= self.window)
+        {
+            self.finish();
+        }
+    }
+
+    /// Print everything buffered, grouped by thread.
+    pub fn finish(&mut self) {
+        self.oldest = None;
+        if self.pending.is_empty() {
+            return;
+        }
+        let groups = group_by_thread(std::mem::take(&mut self.pending));
+        if downstream::stdout_closed() {
+            return;
+        }
+        let mut out = std::io::stdout().lock();
+        let written = groups.into_iter().try_for_each(|(key, lines)| {
+            if self.pretty {
+                writeln!(out, "{key}")?;
+            }
+            lines.iter().try_for_each(|line| writeln!(out, "{line}"))
+        });
+        if let Err(err) = written.and_then(|()| out.flush()) {
+            downstream::note_write_error(&err);
+        }
+    }
+}
+
+fn ticker(view: Weak<Mutex<ThreadView>>, tick: Duration) {
+    loop {
+        std::thread::sleep(tick);
+        let Some(view) = view.upgrade() else {
+            return;
+        };
+        view.lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .flush_due();
+    }
+}
+
+fn group_by_thread(pending: Vec<(String, String)>) -> Vec<(String, Vec<String>)> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for (key, line) in pending {
+        match groups.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, lines)) => lines.push(line),
+            None => groups.push((key, vec![line])),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::group_by_thread;
+
+    #[test]
+    fn groups_keep_first_seen_order_and_arrival_order_within() {
+        let pending = vec![
+            ("a".to_string(), "1".to_string()),
+            ("b".to_string(), "2".to_string()),
+            ("a".to_string(), "3".to_string()),
+            ("-".to_string(), "4".to_string()),
+            ("b".to_string(), "5".to_string()),
+        ];
+        let groups = group_by_thread(pending);
+        let keys = groups
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["a", "b", "-"]);
+        assert_eq!(groups[0].1, ["1", "3"]);
+        assert_eq!(groups[1].1, ["2", "5"]);
+    }
+}
@@ -0,0 +1,190 @@
+//! Purpose: Record and replay interactive `duplex` sessions as JSONL transcripts.
+//! Exports: `Direction`, `TranscriptEntry`, `TranscriptWriter`, `parse_transcript_line`.
+//! Role: `duplex --record` writes one line per sent or received message; `replay-transcript`
+//! reads them back with the original spacing.
+//! Invariants: The first line is a `start` record; later lines are `sent`/`recv` records whose
+//! `offset_ms` counts from session start and never decreases.
+//! Invariants: Each record is written with a single `write_all`, so an interrupted session keeps
+//! every line recorded so far. Unknown record types are skipped on replay.
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use plasmite::api::{Error, ErrorKind};
+use serde_json::{Value, json};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Sent,
+    Recv,
+}
+
+impl Direction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Sent => "sent",
+            Direction::Recv => "recv",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscriptEntry {
+    pub direction: Direction,
+    pub offset_ms: u64,
+    /// Message envelope: `seq`/`time`/`meta`/`data` for received lines, `data` (plus `seq` when
+    /// the append reported one) for sent lines.
+    pub message: Value,
+    /// The record exactly as stored, for JSONL passthrough.
+    pub record: Value,
+}
+
+pub struct TranscriptWriter {
+    file: Mutex<File>,
+    started: Instant,
+}
+
+impl TranscriptWriter {
+    /// Create (or truncate) `path` and write the `start` record.
+    pub fn create(path: &Path, pool: &str, me: Option<&str>, time: &str) -> Result<Self, Error> {
+        let file = File::create(path).map_err(|err| {
+            Error::new(ErrorKind::Io)
+                .with_message("failed to create transcript")
+                .with_path(path)
+                .with_source(err)
+        })?;
+        let writer = Self {
+            file: Mutex::new(file),
+            started: Instant::now(),
+        };
+        writer.write_record(&json!({
+            "type": "start",
+            "version": 1,
+            "pool": pool,
+            "me": me,
+            "time": time,
+        }))?;
+        Ok(writer)
+    }
+
+    pub fn record(&self, direction: Direction, message: &Value) -> Result<(), Error> {
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        // Measured under the lock so offsets follow line order across the send/follow threads.
+        let offset_ms = self.started.elapsed().as_millis() as u64;
+        let record = json!({
+            "type": direction.as_str(),
+            "offset_ms": offset_ms,
+            "message": message,
+        });
+        write_line(&mut file, &record)
+    }
+
+    fn write_record(&self, record: &Value) -> Result<(), Error> {
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        write_line(&mut file, record)
+    }
+}
+
+fn write_line(file: &mut File, record: &Value) -> Result<(), Error> {
+    let mut line = serde_json::to_vec(record).map_err(|err| {
+        Error::new(ErrorKind::Internal)
+            .with_message("failed to encode transcript record")
+            .with_source(err)
+    })?;
+    line.push(b'\n');
+    file.write_all(&line).map_err(|err| {
+        Error::new(ErrorKind::Io)
+            .with_message("failed to write transcript")
+            .with_source(err)
+    })
+}
+
+/// Parse one transcript line; `Ok(None)` for blank lines, `start`, and unknown record types.
+pub fn parse_transcript_line(line: &str, line_no: usize) -> Result<Option<TranscriptEntry>, Error> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    let invalid = |message: &str| {
+        Error::new(ErrorKind::Usage)
+            .with_message(format!("invalid transcript line {line_no}: {message}"))
+            .with_hint("Transcripts are the JSONL files written by `plasmite duplex --record`.")
+    };
+    let record: Value = serde_json::from_str(line).map_err(|err| invalid(&err.to_string()))?;
+    let direction = match record.get("type").and_then(Value::as_str) {
+        Some("sent") => Direction::Sent,
+        Some("recv") => Direction::Recv,
+        Some(_) => return Ok(None),
+        None => return Err(invalid("missing \"type\"")),
+    };
+    let offset_ms = record
+        .get("offset_ms")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| invalid("missing \"offset_ms\""))?;
+    let message = record
+        .get("message")
+        .cloned()
+        .ok_or_else(|| invalid("missing \"message\""))?;
+    Ok(Some(TranscriptEntry {
+        direction,
+        offset_ms,
+        message,
+        record,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, TranscriptWriter, parse_transcript_line};
+    use serde_json::json;
+
+    #[test]
+    fn recorded_lines_parse_back_in_order() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+        let writer = TranscriptWriter::create(&path, "chat", Some("alice"), "2026-01-01T00:00:00Z")
+            .expect("create");
+        writer
+            .record(Direction::Sent, &json!({ "data": { "msg": "hi" } }))
+            .expect("sent");
+        writer
+            .record(
+                Direction::Recv,
+                &json!({ "seq": 7, "data": { "msg": "yo" } }),
+            )
+            .expect("recv");
+
+        let raw = std::fs::read_to_string(&path).expect("read");
+        let entries = raw
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| parse_transcript_line(line, idx + 1).expect("parse"))
+            .collect::<Vec<_>>();
+        assert_eq!(raw.lines().count(), 3);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].direction, Direction::Sent);
+        assert_eq!(entries[1].direction, Direction::Recv);
+        assert_eq!(entries[1].message["seq"], 7);
+        assert!(entries[0].offset_ms <= entries[1].offset_ms);
+    }
+
+    #[test]
+    fn malformed_lines_name_the_line_number() {
+        assert!(parse_transcript_line("", 1).expect("blank").is_none());
+        assert!(
+            parse_transcript_line(r#"{"type":"note"}"#, 2)
+                .expect("unknown")
+                .is_none()
+        );
+        let err = parse_transcript_line(r#"{"type":"sent"}"#, 3).expect_err("offset");
+        assert!(err.message().unwrap_or_default().contains("line 3"));
+        assert!(parse_transcript_line("{", 4).is_err());
+    }
+}
@@ -0,0 +1,488 @@
+//! Purpose: `plasmite ui` terminal dashboard (pools, message rates, live tail).
+//! Exports: `run_ui`.
+//! Role: Single-screen operator view instead of juggling pool list/info/follow.
+//! Invariants: Read-only; never appends to or mutates pools.
+//! Invariants: Terminal state is always restored, even when the event loop errors.
+//! Invariants: Tail pane memory is bounded by `TAIL_CAPACITY` lines.
+use std::collections::VecDeque;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use plasmite::api::{Cursor, CursorResult, Error, ErrorKind, Pool};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::pool_paths::pool_name_for_path;
+
+use super::{RunOutcome, list_pool_paths, pool_message};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const TAIL_CAPACITY: usize = 1000;
+const PUMP_BUDGET: usize = 512;
+const PAGE_LINES: usize = 10;
+
+pub(super) fn run_ui(pool_dir: &Path, initial_pool: Option<&str>) -> Result<RunOutcome, Error> {
+    if !io::stdout().is_terminal() {
+        return Err(Error::new(ErrorKind::Usage)
+            .with_message("plasmite ui requires an interactive terminal")
+            .with_hint(
+                "Use `plasmite pool list` or `plasmite follow <pool>` when piping output.",
+            ));
+    }
+    let mut app = App::new(pool_dir.to_path_buf());
+    app.refresh_pools()?;
+    if let Some(name) = initial_pool {
+        app.select_named(name);
+    }
+    app.attach_selected();
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result.map(|()| RunOutcome::ok())
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), Error> {
+    let mut last_refresh = Instant::now();
+    loop {
+        app.tail.pump();
+        terminal
+            .draw(|frame| draw(frame, app))
+            .map_err(terminal_error)?;
+        if event::poll(POLL_INTERVAL).map_err(terminal_error)? {
+            if let Event::Key(key) = event::read().map_err(terminal_error)? {
+                if key.kind == KeyEventKind::Press && app.handle_key(key) == Flow::Quit {
+                    return Ok(());
+                }
+            }
+        }
+        if last_refresh.elapsed() >= REFRESH_INTERVAL {
+            app.refresh_pools()?;
+            last_refresh = Instant::now();
+        }
+    }
+}
+
+fn terminal_error(err: io::Error) -> Error {
+    Error::new(ErrorKind::Io)
+        .with_message("terminal I/O failed")
+        .with_source(err)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Quit,
+}
+
+struct PoolRow {
+    name: String,
+    path: PathBuf,
+    pool: Option<Pool>,
+    message_count: u64,
+    last_sample: Option<(Instant, u64)>,
+    rate: f64,
+    error: Option<String>,
+}
+
+impl PoolRow {
+    fn new(pool_dir: &Path, path: PathBuf) -> Self {
+        let name =
+            pool_name_for_path(pool_dir, &path).unwrap_or_else(|| path.display().to_string());
+        Self {
+            name,
+            path,
+            pool: None,
+            message_count: 0,
+            last_sample: None,
+            rate: 0.0,
+            error: None,
+        }
+    }
+
+    fn sample(&mut self, now: Instant) {
+        if self.pool.is_none() {
+            match Pool::open(&self.path) {
+                Ok(pool) => self.pool = Some(pool),
+                Err(err) => {
+                    self.error = Some(err.display_message());
+                    return;
+                }
+            }
+        }
+        let Some(pool) = &self.pool else {
+            return;
+        };
+        match pool.bounds() {
+            Ok(bounds) => {
+                let newest = bounds.newest_seq.unwrap_or(0);
+                if let Some(prev) = self.last_sample {
+                    self.rate = rate_per_sec(prev, (now, newest));
+                }
+                self.last_sample = Some((now, newest));
+                self.message_count = match (bounds.oldest_seq, bounds.newest_seq) {
+                    (Some(oldest), Some(newest)) => newest.saturating_sub(oldest) + 1,
+                    _ => 0,
+                };
+                self.error = None;
+            }
+            Err(err) => self.error = Some(err.display_message()),
+        }
+    }
+}
+
+/// Messages per second between two `(instant, newest_seq)` samples.
+fn rate_per_sec(prev: (Instant, u64), now: (Instant, u64)) -> f64 {
+    let elapsed = now.0.saturating_duration_since(prev.0).as_secs_f64();
+    if elapsed <= 0.0 {
+        return 0.0;
+    }
+    now.1.saturating_sub(prev.1) as f64 / elapsed
+}
+
+struct TailPane {
+    path: Option<PathBuf>,
+    pool: Option<Pool>,
+    cursor: Cursor,
+    lines: VecDeque<String>,
+}
+
+impl Default for TailPane {
+    fn default() -> Self {
+        Self {
+            path: None,
+            pool: None,
+            cursor: Cursor::new(),
+            lines: VecDeque::new(),
+        }
+    }
+}
+
+impl TailPane {
+    fn attach(&mut self, path: &Path) {
+        if self.path.as_deref() == Some(path) {
+            return;
+        }
+        self.path = Some(path.to_path_buf());
+        self.lines.clear();
+        self.cursor = Cursor::new();
+        self.pool = match Pool::open(path) {
+            Ok(pool) => {
+                if let Ok(header) = pool.header_from_mmap() {
+                    self.cursor.seek_to(header.tail_off as usize);
+                }
+                Some(pool)
+            }
+            Err(err) => {
+                self.push(format!("error: {}", err.display_message()));
+                None
+            }
+        };
+    }
+
+    fn detach(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Read newly committed frames, up to `PUMP_BUDGET` per tick to keep the UI responsive.
+    fn pump(&mut self) {
+        let Self {
+            pool,
+            cursor,
+            lines,
+            ..
+        } = self;
+        let Some(pool) = pool else {
+            return;
+        };
+        for _ in 0..PUMP_BUDGET {
+            let line = match cursor.next(pool) {
+                Ok(CursorResult::Message(frame)) => match pool_message(pool, &frame) {
+                    Ok(Some(message)) => message.to_string(),
+                    Ok(None) => continue,
+                    Err(err) => format!("seq {}: {}", frame.seq, err.display_message()),
+                },
+                Ok(CursorResult::WouldBlock) => return,
+                Ok(CursorResult::FellBehind) => match pool.header_from_mmap() {
+                    Ok(header) => {
+                        cursor.seek_to(header.tail_off as usize);
+                        continue;
+                    }
+                    Err(err) => {
+                        push_bounded(lines, format!("error: {}", err.display_message()));
+                        return;
+                    }
+                },
+                Err(err) => {
+                    push_bounded(lines, format!("error: {}", err.display_message()));
+                    return;
+                }
+            };
+            push_bounded(lines, line);
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        push_bounded(&mut self.lines, line);
+    }
+}
+
+fn push_bounded(lines: &mut VecDeque<String>, line: String) {
+    lines.push_back(line);
+    while lines.len() > TAIL_CAPACITY {
+        lines.pop_front();
+    }
+}
+
+/// Lines to show for a pane `height` rows tall, `scroll` rows up from the newest match.
+fn visible_window<'a>(
+    lines: &'a VecDeque<String>,
+    filter: &str,
+    height: usize,
+    scroll: usize,
+) -> Vec<&'a str> {
+    let matched = lines
+        .iter()
+        .map(String::as_str)
+        .filter(|line| filter.is_empty() || line.contains(filter))
+        .collect::<Vec<_>>();
+    let end = matched.len().saturating_sub(scroll);
+    let start = end.saturating_sub(height);
+    matched[start..end].to_vec()
+}
+
+struct App {
+    pool_dir: PathBuf,
+    pools: Vec<PoolRow>,
+    selected: usize,
+    tail: TailPane,
+    filter: String,
+    editing: Option<String>,
+    scroll: usize,
+}
+
+impl App {
+    fn new(pool_dir: PathBuf) -> Self {
+        Self {
+            pool_dir,
+            pools: Vec::new(),
+            selected: 0,
+            tail: TailPane::default(),
+            filter: String::new(),
+            editing: None,
+            scroll: 0,
+        }
+    }
+
+    fn refresh_pools(&mut self) -> Result<(), Error> {
+        let mut paths = match list_pool_paths(&self.pool_dir) {
+            Ok(paths) => paths,
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        paths.sort();
+        let selected_path = self.selected_path();
+        let mut previous = std::mem::take(&mut self.pools);
+        for path in paths {
+            let row = match previous.iter().position(|row| row.path == path) {
+                Some(idx) => previous.swap_remove(idx),
+                None => PoolRow::new(&self.pool_dir, path),
+            };
+            self.pools.push(row);
+        }
+        let now = Instant::now();
+        for row in &mut self.pools {
+            row.sample(now);
+        }
+        self.selected = selected_path
+            .and_then(|path| self.pools.iter().position(|row| row.path == path))
+            .unwrap_or(0);
+        self.attach_selected();
+        Ok(())
+    }
+
+    fn selected_path(&self) -> Option<PathBuf> {
+        self.pools.get(self.selected).map(|row| row.path.clone())
+    }
+
+    fn select_named(&mut self, name: &str) {
+        if let Some(idx) = self.pools.iter().position(|row| row.name == name) {
+            self.selected = idx;
+        }
+    }
+
+    fn attach_selected(&mut self) {
+        match self.selected_path() {
+            Some(path) => self.tail.attach(&path),
+            None => self.tail.detach(),
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.pools.is_empty() {
+            return;
+        }
+        let last = self.pools.len() - 1;
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        self.scroll = 0;
+        self.attach_selected();
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Flow {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Flow::Quit;
+        }
+        if let Some(input) = &mut self.editing {
+            match key.code {
+                KeyCode::Char(ch) => input.push(ch),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => {
+                    self.filter = std::mem::take(input);
+                    self.editing = None;
+                    self.scroll = 0;
+                }
+                KeyCode::Esc => self.editing = None,
+                _ => {}
+            }
+            return Flow::Continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Flow::Quit,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_add(PAGE_LINES),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(PAGE_LINES),
+            KeyCode::End | KeyCode::Char('G') => self.scroll = 0,
+            KeyCode::Char('/') => self.editing = Some(self.filter.clone()),
+            _ => {}
+        }
+        Flow::Continue
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[0]);
+
+    let name_width = app
+        .pools
+        .iter()
+        .map(|row| row.name.len())
+        .max()
+        .unwrap_or(0);
+    let items = app
+        .pools
+        .iter()
+        .map(|row| {
+            let text = match &row.error {
+                Some(error) => format!("{:<name_width$}  ✗ {error}", row.name),
+                None => format!(
+                    "{:<name_width$}  {:>8} msgs  {:>7.1}/s",
+                    row.name, row.message_count, row.rate
+                ),
+            };
+            ListItem::new(text)
+        })
+        .collect::<Vec<_>>();
+    let pools_title = format!(" pools ({}) ", app.pools.len());
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(pools_title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default();
+    if !app.pools.is_empty() {
+        state.select(Some(app.selected));
+    }
+    frame.render_stateful_widget(list, columns[0], &mut state);
+
+    let tail_height = usize::from(columns[1].height.saturating_sub(2));
+    let visible = visible_window(&app.tail.lines, &app.filter, tail_height, app.scroll);
+    let tail_title = match (app.pools.get(app.selected), app.filter.is_empty()) {
+        (Some(row), true) => format!(" tail: {} ", row.name),
+        (Some(row), false) => format!(" tail: {} (filter: {}) ", row.name, app.filter),
+        (None, _) => " tail ".to_string(),
+    };
+    let tail = Paragraph::new(visible.join("\n"))
+        .block(Block::default().borders(Borders::ALL).title(tail_title));
+    frame.render_widget(tail, columns[1]);
+
+    let status = match &app.editing {
+        Some(input) => format!("filter: {input}▏  (Enter apply, Esc cancel)"),
+        None if app.pools.is_empty() => {
+            format!("No pools in {}  |  q quit", app.pool_dir.display())
+        }
+        None => "↑/↓ select  PgUp/PgDn scroll  End follow  / filter  q quit".to_string(),
+    };
+    frame.render_widget(Paragraph::new(status), rows[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, Flow, TAIL_CAPACITY, TailPane, rate_per_sec, visible_window};
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::collections::VecDeque;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn rate_per_sec_uses_seq_delta_over_elapsed() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(2);
+        assert_eq!(rate_per_sec((start, 10), (later, 30)), 10.0);
+        assert_eq!(rate_per_sec((start, 10), (start, 30)), 0.0);
+        assert_eq!(rate_per_sec((start, 30), (later, 10)), 0.0);
+    }
+
+    #[test]
+    fn visible_window_filters_and_scrolls_from_newest() {
+        let lines = (1..=6)
+            .map(|idx| format!("{{\"n\":{idx},\"odd\":{}}}", idx % 2 == 1))
+            .collect::<VecDeque<_>>();
+        assert_eq!(
+            visible_window(&lines, "", 2, 0),
+            vec![lines[4].as_str(), lines[5].as_str()]
+        );
+        assert_eq!(
+            visible_window(&lines, "", 2, 1),
+            vec![lines[3].as_str(), lines[4].as_str()]
+        );
+        let odd = visible_window(&lines, "\"odd\":true", 10, 0);
+        assert_eq!(odd.len(), 3);
+        assert!(visible_window(&lines, "", 2, 100).is_empty());
+    }
+
+    #[test]
+    fn tail_pane_is_bounded() {
+        let mut pane = TailPane::default();
+        for idx in 0..TAIL_CAPACITY + 5 {
+            pane.push(idx.to_string());
+        }
+        assert_eq!(pane.lines.len(), TAIL_CAPACITY);
+        assert_eq!(pane.lines.front().map(String::as_str), Some("5"));
+    }
+
+    #[test]
+    fn filter_input_applies_on_enter() {
+        let mut app = App::new(PathBuf::from("/nonexistent"));
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(app.handle_key(key(KeyCode::Char('/'))), Flow::Continue);
+        app.handle_key(key(KeyCode::Char('o')));
+        app.handle_key(key(KeyCode::Char('k')));
+        assert!(app.filter.is_empty());
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.filter, "ok");
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Flow::Quit);
+    }
+}
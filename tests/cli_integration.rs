@@ -84,6 +84,15 @@ impl ServeProcess {
         pool_dir: &std::path::Path,
         extra_args: &[&str],
         scheme: &str,
+    ) -> Self {
+        Self::start_with_env(pool_dir, extra_args, scheme, &[])
+    }
+
+    fn start_with_env(
+        pool_dir: &std::path::Path,
+        extra_args: &[&str],
+        scheme: &str,
+        envs: &[(&str, &str)],
     ) -> Self {
         let guard = SERVER_LOCK
             .lock()
@@ -105,6 +114,7 @@ impl ServeProcess {
             if !extra_args.is_empty() {
                 command.args(extra_args);
             }
+            command.envs(envs.iter().copied());
             let mut child = command
                 .stdout(Stdio::null())
                 .stderr(Stdio::piped())
@@ -322,1221 +332,1971 @@ fn duplex_with_no_args_prints_help() {
 }
 
 #[test]
-fn feed_with_no_args_prints_help() {
-    let output = cmd().args(["feed"]).output().expect("feed");
-    assert_eq!(output.status.code(), Some(2));
-    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
-    assert!(stderr.contains("Usage: plasmite feed"));
-}
-
-#[test]
-fn fetch_with_no_args_prints_help() {
-    let output = cmd().args(["fetch"]).output().expect("fetch");
-    assert_eq!(output.status.code(), Some(2));
-    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
-    assert!(stderr.contains("Usage: plasmite fetch"));
-}
-
-#[test]
-fn follow_with_no_args_prints_help() {
-    let output = cmd().args(["follow"]).output().expect("follow");
-    assert_eq!(output.status.code(), Some(2));
-    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
-    assert!(stderr.contains("Usage: plasmite follow"));
-}
-
-#[test]
-fn tap_with_no_args_prints_help() {
-    let output = cmd().args(["tap"]).output().expect("tap");
-    assert_eq!(output.status.code(), Some(2));
-    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
-    assert!(stderr.contains("Usage: plasmite tap"));
-    assert!(stderr.contains("Capture command output into a local pool"));
-}
-
-#[test]
-fn tap_help_renders_examples() {
-    let output = cmd().args(["tap", "--help"]).output().expect("tap help");
-    assert!(output.status.success());
-    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
-    assert!(stdout.contains("plasmite tap build --create -- cargo build"));
-    assert!(stdout.contains("plasmite tap api --create --create-size 64M -- ./server"));
-    assert!(stdout.contains("`--` is required before wrapped command args"));
-}
-
-#[test]
-fn tap_requires_wrapped_command_after_separator() {
-    let output = cmd().args(["tap", "build"]).output().expect("tap");
-    assert_actionable_usage_feedback(
-        &output,
-        "tap requires a wrapped command after `--`",
-        "plasmite tap <pool> -- <command...>",
-    );
-}
-
-#[test]
-fn tap_remote_url_rejected_as_local_only() {
-    let output = cmd()
-        .args(["tap", "http://127.0.0.1:65535/demo", "--", "echo", "hi"])
-        .output()
-        .expect("tap");
-    assert_actionable_usage_feedback(
-        &output,
-        "tap accepts local pool refs only",
-        "Use a local pool name/path",
-    );
-}
-
-#[test]
-fn tap_non_tty_stderr_suppresses_status_lines() {
+fn feed_edit_and_clipboard_sources_append_json() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-    let output = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "demo",
-            "--create",
-            "--",
-            "echo",
-            "hello",
-        ])
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "demo"])
         .output()
-        .expect("tap");
+        .expect("create");
+    assert!(create.status.success());
+
+    // The fake editor keeps the template's comment line to check it is stripped.
+    let editor = temp.path().join("editor.sh");
+    std::fs::write(
+        &editor,
+        "#!/bin/sh\nprintf '// note\\n{\"from\":\"editor\"}\\n' > \"$1\"\n",
+    )
+    .expect("write editor");
+    std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+    let edit = cmd()
+        .args(["--dir", dir, "feed", "demo", "--edit"])
+        .env("EDITOR", &editor)
+        .env_remove("VISUAL")
+        .output()
+        .expect("feed --edit");
     assert!(
-        output.status.success(),
+        edit.status.success(),
         "stderr={}",
-        String::from_utf8_lossy(&output.stderr)
+        String::from_utf8_lossy(&edit.stderr)
     );
     assert_eq!(
-        std::str::from_utf8(&output.stdout).expect("utf8"),
-        "hello\n"
+        fetch_message(&pool_dir, "demo", 1)["data"],
+        json!({"from": "editor"})
     );
-    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
-    assert!(!stderr.contains("tapping"));
-    assert!(!stderr.contains("tapped"));
-}
 
-#[test]
-fn tap_tty_stderr_emits_startup_and_completion_lines() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
-    let output = cmd_tty(&[
-        "--dir",
-        pool_dir.to_str().unwrap(),
-        "tap",
-        "demo",
-        "--create",
-        "--",
-        "echo",
-        "hello",
-    ]);
-    assert!(
-        output.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&output.stderr)
+    let clip = cmd()
+        .args(["--dir", dir, "feed", "demo", "--from-clipboard"])
+        .env(
+            "PLASMITE_CLIPBOARD_CMD",
+            "printf '{\"from\":\"clipboard\"}'",
+        )
+        .output()
+        .expect("feed --from-clipboard");
+    assert!(clip.status.success());
+    assert_eq!(
+        fetch_message(&pool_dir, "demo", 2)["data"],
+        json!({"from": "clipboard"})
     );
-    let text = sanitize_tty_text(&output.stdout);
-    assert!(text.contains("tapping demo <- echo hello"), "output={text}");
-    assert!(text.contains("tapped 1 lines ("), "output={text}");
-    assert!(text.contains("-> demo exit 0"), "output={text}");
+
+    let empty = cmd()
+        .args(["--dir", dir, "feed", "demo", "--edit"])
+        .env("EDITOR", ": >")
+        .env_remove("VISUAL")
+        .output()
+        .expect("feed --edit");
+    assert_eq!(empty.status.code(), Some(2));
 }
 
 #[test]
-fn tap_basic_capture_writes_start_line_and_exit_messages() {
+fn feed_set_enriches_every_record() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "demo"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
 
-    let tap = cmd()
+    let mut feed = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
+            dir,
+            "feed",
             "demo",
-            "--create",
-            "--",
-            "echo",
-            "hello",
+            "--set",
+            ".data.host = env.PLASMITE_TEST_HOST",
+            "--set",
+            ".data.build.version = \"1.2\"",
+            "--set-time",
+            ".data.received_at",
         ])
-        .output()
-        .expect("tap");
-    assert!(
-        tap.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&tap.stderr)
-    );
-
-    let start = fetch_message(&pool_dir, "demo", 1);
-    let line = fetch_message(&pool_dir, "demo", 2);
-    let exit = fetch_message(&pool_dir, "demo", 3);
-
-    assert_eq!(start["data"]["kind"], "start");
-    assert_eq!(start["data"]["cmd"], json!(["echo", "hello"]));
-    assert_eq!(start["meta"]["tags"], json!(["lifecycle"]));
+        .env("PLASMITE_TEST_HOST", "web-1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    feed.stdin
+        .as_mut()
+        .expect("stdin")
+        .write_all(b"{\"n\":1}\n{\"n\":2}\n")
+        .expect("write stdin");
+    let output = feed.wait_with_output().expect("feed output");
+    assert!(output.status.success());
 
-    assert_eq!(line["data"]["kind"], "line");
-    assert_eq!(line["data"]["stream"], "stdout");
-    assert_eq!(line["data"]["line"], "hello");
+    for seq in 1..=2 {
+        let data = &fetch_message(&pool_dir, "demo", seq)["data"];
+        assert_eq!(data["n"], json!(seq));
+        assert_eq!(data["host"], json!("web-1"));
+        assert_eq!(data["build"], json!({"version": "1.2"}));
+        assert!(data["received_at"].as_str().is_some());
+    }
 
-    assert_eq!(exit["data"]["kind"], "exit");
-    assert_eq!(exit["data"]["code"], 0);
-    assert!(exit["data"]["elapsed_ms"].as_u64().is_some());
-    assert_eq!(exit["meta"]["tags"], json!(["lifecycle"]));
+    let invalid = cmd()
+        .args(["--dir", dir, "feed", "demo", "--set", ".data.x", "{}"])
+        .output()
+        .expect("feed");
+    assert_eq!(invalid.status.code(), Some(2));
 }
 
 #[test]
-fn tap_captures_stderr_lines() {
+fn feed_flag_surfaces_in_meta_flags() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "demo"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
 
-    let tap = cmd()
+    let feed = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "errpool",
-            "--create",
-            "--",
-            "sh",
-            "-c",
-            "echo err >&2",
+            dir,
+            "feed",
+            "demo",
+            "--flag",
+            "control",
+            "--flag",
+            "user3",
+            "{\"op\":1}",
         ])
         .output()
-        .expect("tap");
+        .expect("feed");
+    assert!(feed.status.success());
+    let receipt = parse_json(std::str::from_utf8(&feed.stdout).expect("utf8"));
+    assert_eq!(receipt["meta"]["flags"], json!(["control", "user3"]));
+    let plain = cmd()
+        .args(["--dir", dir, "feed", "demo", "{\"op\":2}"])
+        .output()
+        .expect("feed");
+    assert!(plain.status.success());
+
+    assert_eq!(
+        fetch_message(&pool_dir, "demo", 1)["meta"]["flags"],
+        json!(["control", "user3"])
+    );
     assert!(
-        tap.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&tap.stderr)
+        fetch_message(&pool_dir, "demo", 2)["meta"]
+            .get("flags")
+            .is_none()
     );
 
-    let line = fetch_message(&pool_dir, "errpool", 2);
-    assert_eq!(line["data"]["stream"], "stderr");
-    assert_eq!(line["data"]["line"], "err");
-}
-
-#[test]
-fn tap_forwards_exit_code_and_records_exit_lifecycle_code() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
-
-    let tap = cmd()
+    let follow = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "failpool",
-            "--create",
-            "--",
-            "false",
+            dir,
+            "follow",
+            "demo",
+            "--tail",
+            "2",
+            "--jsonl",
+            "--timeout",
+            "200ms",
         ])
         .output()
-        .expect("tap");
-    assert_eq!(tap.status.code(), Some(1));
+        .expect("follow");
+    let lines = parse_json_lines(&follow.stdout);
+    assert_eq!(lines[0]["meta"]["flags"], json!(["control", "user3"]));
+    assert!(lines[1]["meta"].get("flags").is_none());
 
-    let exit = fetch_message(&pool_dir, "failpool", 2);
-    assert_eq!(exit["data"]["kind"], "exit");
-    assert_eq!(exit["data"]["code"], 1);
-    assert!(exit["data"].get("signal").is_none());
+    let invalid = cmd()
+        .args(["--dir", dir, "feed", "demo", "--flag", "sticky", "{}"])
+        .output()
+        .expect("feed");
+    assert_eq!(invalid.status.code(), Some(2));
+}
+
+fn spawn_line_reader<R: Read + Send + 'static>(reader: R) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
 }
 
 #[test]
-fn tap_applies_user_tags_to_lines_and_lifecycle_tag_to_start_exit() {
+fn follow_hides_control_messages_and_stops_on_delete() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let feed = |args: &[&str]| {
+        let output = cmd()
+            .args(["--dir", dir, "feed", "demo"])
+            .args(args)
+            .output()
+            .expect("feed");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "demo"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    feed(&["{\"n\":1}"]);
 
-    let tap = cmd()
+    let mut child = cmd()
+        .args(["--dir", dir, "follow", "demo", "--tail", "1", "--jsonl"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("follow");
+    let lines = spawn_line_reader(child.stdout.take().expect("stdout"));
+    let next = || parse_json(&lines.recv_timeout(Duration::from_secs(5)).expect("line"));
+    assert_eq!(next()["data"], json!({"n": 1}));
+
+    feed(&[
+        "--flag",
+        "control",
+        "{\"control\":\"retention\",\"oldest_seq\":1}",
+    ]);
+    feed(&["{\"n\":2}"]);
+    assert_eq!(next()["data"], json!({"n": 2}));
+
+    let shown = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "tagpool",
-            "--create",
-            "--tag",
-            "ci",
-            "--tag",
-            "build",
-            "--",
-            "echo",
-            "ok",
+            dir,
+            "follow",
+            "demo",
+            "--tail",
+            "3",
+            "--jsonl",
+            "--show-control",
+            "--timeout",
+            "200ms",
         ])
         .output()
-        .expect("tap");
-    assert!(
-        tap.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&tap.stderr)
-    );
-
-    let start = fetch_message(&pool_dir, "tagpool", 1);
-    let line = fetch_message(&pool_dir, "tagpool", 2);
-    let exit = fetch_message(&pool_dir, "tagpool", 3);
-    assert_eq!(start["meta"]["tags"], json!(["lifecycle"]));
-    assert_eq!(line["meta"]["tags"], json!(["ci", "build"]));
-    assert_eq!(exit["meta"]["tags"], json!(["lifecycle"]));
-}
-
-#[test]
-fn tap_quiet_suppresses_passthrough_but_capture_still_works() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+        .expect("follow");
+    let shown = parse_json_lines(&shown.stdout);
+    assert_eq!(shown.len(), 3);
+    assert_eq!(shown[1]["meta"]["control"], json!("retention"));
+    assert_eq!(shown[1]["meta"]["flags"], json!(["control"]));
 
-    let tap = cmd()
+    let invalid = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "quietpool",
-            "--create",
-            "-q",
-            "--",
-            "echo",
-            "hello",
+            dir,
+            "feed",
+            "demo",
+            "--flag",
+            "control",
+            "{\"op\":1}",
         ])
         .output()
-        .expect("tap");
-    assert!(
-        tap.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&tap.stderr)
-    );
-    assert!(
-        tap.stdout.is_empty(),
-        "stdout should be empty in quiet mode"
-    );
+        .expect("feed");
+    assert_eq!(invalid.status.code(), Some(2));
 
-    let line = fetch_message(&pool_dir, "quietpool", 2);
-    assert_eq!(line["data"]["line"], "hello");
+    let delete = cmd()
+        .args(["--dir", dir, "pool", "delete", "--force", "demo"])
+        .output()
+        .expect("delete");
+    assert!(delete.status.success());
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let status = loop {
+        if let Some(status) = child.try_wait().expect("try_wait") {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            panic!("follow did not stop after the pool was deleted");
+        }
+        sleep(Duration::from_millis(20));
+    };
+    assert_eq!(status.code(), Some(0));
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .expect("stderr")
+        .read_to_string(&mut stderr)
+        .expect("read stderr");
+    let notice = parse_notice_json(stderr.lines().last().expect("notice"));
+    assert_eq!(notice["notice"]["kind"], json!("control"));
+    assert_eq!(notice["notice"]["details"]["control"], json!("deleted"));
 }
 
 #[test]
-fn tap_multiline_capture_preserves_line_order() {
+fn follow_reopens_pool_after_rotated_control_message() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    for (pool, data) in [("demo", "{\"gen\":1}"), ("next", "{\"gen\":2}")] {
+        let create = cmd()
+            .args(["--dir", dir, "pool", "create", pool])
+            .output()
+            .expect("create");
+        assert!(create.status.success());
+        let feed = cmd()
+            .args(["--dir", dir, "feed", pool, data])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let tap = cmd()
+    let mut child = cmd()
+        .args(["--dir", dir, "follow", "demo", "--tail", "1", "--jsonl"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("follow");
+    let lines = spawn_line_reader(child.stdout.take().expect("stdout"));
+    let next = || parse_json(&lines.recv_timeout(Duration::from_secs(5)).expect("line"));
+    assert_eq!(next()["data"], json!({"gen": 1}));
+
+    let rotated = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "mpool",
-            "--create",
-            "--",
-            "sh",
-            "-c",
-            "echo a; echo b; echo c",
+            dir,
+            "feed",
+            "demo",
+            "--flag",
+            "control",
+            "{\"control\":\"rotated\"}",
         ])
         .output()
-        .expect("tap");
-    assert!(
-        tap.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&tap.stderr)
-    );
+        .expect("feed");
+    assert!(rotated.status.success());
+    std::fs::rename(
+        pool_dir.join("next.plasmite"),
+        pool_dir.join("demo.plasmite"),
+    )
+    .expect("rotate");
 
-    let l1 = fetch_message(&pool_dir, "mpool", 2);
-    let l2 = fetch_message(&pool_dir, "mpool", 3);
-    let l3 = fetch_message(&pool_dir, "mpool", 4);
-    let exit = fetch_message(&pool_dir, "mpool", 5);
-    assert_eq!(l1["data"]["line"], "a");
-    assert_eq!(l2["data"]["line"], "b");
-    assert_eq!(l3["data"]["line"], "c");
-    assert_eq!(exit["data"]["kind"], "exit");
+    let reopened = next();
+    let _ = child.kill();
+    let _ = child.wait();
+    assert_eq!(reopened["data"], json!({"gen": 2}));
+    assert_eq!(reopened["seq"], json!(1));
 }
 
 #[test]
-fn tap_captures_unterminated_final_line() {
+fn feed_with_no_args_prints_help() {
+    let output = cmd().args(["feed"]).output().expect("feed");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
+    assert!(stderr.contains("Usage: plasmite feed"));
+}
+
+#[test]
+fn follow_thread_groups_interleaved_messages_by_key() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "agents"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for rid in ["a", "b", "a", "b"] {
+        let feed = cmd()
+            .args([
+                "--dir",
+                dir,
+                "feed",
+                "agents",
+                &format!("{{\"rid\":\"{rid}\"}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let tap = cmd()
+    let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "nolf",
-            "--create",
-            "--",
-            "python3",
-            "-c",
-            "import sys; sys.stdout.write('tail-without-newline')",
+            dir,
+            "follow",
+            "agents",
+            "--tail",
+            "4",
+            "--thread",
+            ".data.rid",
+            "--jsonl",
+            "--timeout",
+            "1s",
         ])
         .output()
-        .expect("tap");
-    assert!(
-        tap.status.success(),
+        .expect("follow");
+    let seqs = parse_json_lines(&output.stdout)
+        .iter()
+        .map(|line| line["seq"].as_u64().expect("seq"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        seqs,
+        [1, 3, 2, 4],
         "stderr={}",
-        String::from_utf8_lossy(&tap.stderr)
+        String::from_utf8_lossy(&output.stderr)
     );
-
-    let line = fetch_message(&pool_dir, "nolf", 2);
-    assert_eq!(line["data"]["line"], "tail-without-newline");
 }
 
 #[test]
-fn tap_preserves_long_line_without_truncation() {
+fn schema_reports_field_types_and_top_values() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for data in [
+        r#"{"kind":"click","user":{"id":1}}"#,
+        r#"{"kind":"click","user":null}"#,
+        r#"{"kind":"view","extra":[1,2]}"#,
+    ] {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "events", data])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let tap = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "longline",
-            "--create",
-            "--",
-            "python3",
-            "-c",
-            "print('x' * 65536)",
-        ])
+    let output = cmd()
+        .args(["--dir", dir, "schema", "events", "--json"])
         .output()
-        .expect("tap");
+        .expect("schema");
     assert!(
-        tap.status.success(),
+        output.status.success(),
         "stderr={}",
-        String::from_utf8_lossy(&tap.stderr)
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(report["sampled"], json!(3));
+    let field = |path: &str| {
+        report["fields"]
+            .as_array()
+            .expect("fields")
+            .iter()
+            .find(|field| field["path"] == json!(path))
+            .cloned()
+            .unwrap_or_else(|| panic!("missing field {path}"))
+    };
+    assert_eq!(
+        field(".kind")["top_values"][0],
+        json!({"value": "click", "count": 2})
     );
+    assert_eq!(field(".user")["nullable"], json!(true));
+    assert_eq!(field(".user")["missing"], json!(1));
+    assert_eq!(field(".extra[]")["types"], json!({"integer": 2}));
+    assert_eq!(report["schema"]["required"], json!(["kind"]));
 
-    let line = fetch_message(&pool_dir, "longline", 2);
-    let captured = line["data"]["line"].as_str().expect("line string");
-    assert_eq!(captured.len(), 65536);
-    assert!(captured.chars().all(|ch| ch == 'x'));
+    let output = cmd()
+        .args(["--dir", dir, "schema", "events", "--sample", "1", "--json"])
+        .output()
+        .expect("schema sample");
+    assert!(output.status.success());
+    let report = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(report["sampled"], json!(1));
 }
 
 #[test]
-fn tap_missing_wrapped_executable_is_actionable_nonzero_error() {
+fn diff_reports_messages_missing_from_either_side() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    for (pool, ids) in [("primary", &[1, 2, 3][..]), ("mirror", &[1, 3][..])] {
+        let create = cmd()
+            .args(["--dir", dir, "pool", "create", pool])
+            .output()
+            .expect("create");
+        assert!(create.status.success());
+        for id in ids {
+            let feed = cmd()
+                .args(["--dir", dir, "feed", pool, &format!("{{\"id\":{id}}}")])
+                .output()
+                .expect("feed");
+            assert!(feed.status.success());
+        }
+    }
 
+    let output = cmd()
+        .args(["--dir", dir, "diff", "primary", "mirror", "--json"])
+        .output()
+        .expect("diff");
+    assert_eq!(output.status.code(), Some(1));
+    let report = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(report["matched"], json!(2));
+    assert_eq!(report["only_in_a"].as_array().map(Vec::len), Some(1));
+    assert_eq!(report["only_in_a"][0]["seq"], json!(2));
+    assert_eq!(report["only_in_b"], json!([]));
+
+    let dump = temp.path().join("dump.jsonl");
+    std::fs::write(&dump, "{\"id\":3}\n{\"id\":1}\n").expect("write dump");
     let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "missingcmd",
-            "--create",
-            "--",
-            "nonexistent-command-xyz",
+            dir,
+            "diff",
+            "mirror",
+            dump.to_str().unwrap(),
+            "--key",
+            ".data.id",
+            "--json",
         ])
         .output()
-        .expect("tap");
-    assert_actionable_usage_feedback(
-        &output,
-        "wrapped command not found",
-        "Check PATH or use an absolute executable path",
+        .expect("diff dump");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
     );
+    let report = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(report["identical"], json!(true));
+    assert_eq!(report["key"], json!(".data.id"));
 }
 
 #[test]
-fn tap_missing_pool_without_create_has_create_hint() {
+fn fields_project_follow_and_fetch_output() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "logs"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for msg in ["boot", "ready, set"] {
+        let feed = cmd()
+            .args([
+                "--dir",
+                dir,
+                "feed",
+                "logs",
+                "--tag",
+                "app",
+                &format!("{{\"msg\":\"{msg}\",\"n\":1}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
     let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "tap",
-            "missingpool",
-            "--",
-            "echo",
-            "x",
+            dir,
+            "follow",
+            "logs",
+            "--tail",
+            "2",
+            "--fields",
+            "seq,.data.msg",
+            "--timeout",
+            "1s",
         ])
         .output()
-        .expect("tap");
-    assert_eq!(output.status.code(), Some(3));
-    let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(
-        hint.contains("--create"),
-        "expected --create hint in '{hint}'"
+        .expect("follow");
+    assert_eq!(
+        parse_json_lines(&output.stdout),
+        vec![
+            json!({"seq": 1, "data": {"msg": "boot"}}),
+            json!({"seq": 2, "data": {"msg": "ready, set"}}),
+        ],
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
     );
-}
 
-#[test]
-fn tap_empty_command_after_separator_is_usage_error() {
     let output = cmd()
-        .args(["tap", "demo", "--create", "--"])
+        .args([
+            "--dir",
+            dir,
+            "follow",
+            "logs",
+            "--tail",
+            "2",
+            "--fields",
+            "seq,.data.msg,.meta.tags",
+            "--output",
+            "csv",
+            "--timeout",
+            "1s",
+        ])
         .output()
-        .expect("tap");
-    assert_actionable_usage_feedback(
-        &output,
-        "tap requires a wrapped command after `--`",
-        "plasmite tap <pool> -- <command...>",
+        .expect("follow csv");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "seq,.data.msg,.meta.tags\n1,boot,\"[\"\"app\"\"]\"\n2,\"ready, set\",\"[\"\"app\"\"]\"\n"
     );
-}
 
-#[test]
-fn pool_create_with_no_args_prints_help() {
     let output = cmd()
-        .args(["pool", "create"])
+        .args([
+            "--dir",
+            dir,
+            "fetch",
+            "logs",
+            "2",
+            "--fields",
+            "seq,.data.n",
+            "--output",
+            "tsv",
+        ])
         .output()
-        .expect("pool create");
-    assert_eq!(output.status.code(), Some(2));
-    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
-    assert!(stderr.contains("Usage: plasmite pool create"));
+        .expect("fetch tsv");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "seq\t.data.n\n2\t1\n"
+    );
 }
 
 #[test]
-fn create_feed_fetch_follow_flow() {
+fn jq_programs_transform_follow_and_fetch_output() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    for data in [r#"{"msg":"boot","items":[1,2]}"#, r#"{"items":[3]}"#] {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "logs", "--create", data])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let create = cmd()
+    let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "--json",
-            "testpool",
+            dir,
+            "follow",
+            "logs",
+            "--tail",
+            "2",
+            "--jq",
+            "{seq, msg: (.data.msg? // null), first: .data.items[0]}",
+            "--timeout",
+            "1s",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-    let create_json = parse_json(std::str::from_utf8(&create.stdout).expect("utf8"));
-    let created = create_json
-        .get("created")
-        .and_then(|value| value.as_array())
-        .expect("created array")
-        .first()
-        .expect("first");
-    assert_eq!(created.get("name").unwrap().as_str().unwrap(), "testpool");
-    assert!(
-        created
-            .get("path")
-            .unwrap()
-            .as_str()
-            .unwrap()
-            .ends_with("testpool.plasmite")
+        .expect("follow");
+    assert_eq!(
+        parse_json_lines(&output.stdout),
+        vec![
+            json!({"seq": 1, "msg": "boot", "first": 1}),
+            json!({"seq": 2, "msg": null, "first": 3}),
+        ],
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
     );
-    assert!(created.get("bounds").unwrap().get("oldest").is_none());
 
-    let feed_out = cmd()
+    let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "testpool",
-            "{\"x\":1}",
-            "--tag",
-            "ping",
+            dir,
+            "follow",
+            "logs",
+            "--tail",
+            "2",
+            "--jq",
+            ".data.msg",
+            "--timeout",
+            "1s",
         ])
         .output()
-        .expect("feed");
-    assert!(feed_out.status.success());
-    let feed_json = parse_json(std::str::from_utf8(&feed_out.stdout).expect("utf8"));
-    let seq = feed_json.get("seq").unwrap().as_u64().unwrap();
-    assert!(feed_json.get("time").is_some());
-    assert_eq!(feed_json.get("meta").unwrap()["tags"][0], "ping");
-    assert!(feed_json.get("data").is_none());
+        .expect("follow");
+    assert_eq!(
+        parse_json_lines(&output.stdout),
+        vec![json!("boot")],
+        "a missing field drops that message's output"
+    );
 
-    let get = cmd()
+    let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "fetch",
-            "testpool",
-            &seq.to_string(),
+            "logs",
+            "1",
+            "--jq",
+            ".data.items[] * 10",
         ])
         .output()
         .expect("fetch");
-    assert!(get.status.success());
-    let get_json = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
-    assert_eq!(get_json.get("seq").unwrap().as_u64().unwrap(), seq);
-    assert_eq!(get_json.get("data").unwrap()["x"], 1);
+    assert!(output.status.success());
+    assert_eq!(parse_json_lines(&output.stdout), vec![json!(10), json!(20)]);
 
-    let mut follower = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "testpool",
-            "--tail",
-            "1",
-            "--jsonl",
-        ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let follower_json = parse_json(line.trim());
-    assert_eq!(follower_json.get("seq").unwrap().as_u64().unwrap(), seq);
-    let _ = follower.kill();
-    let _ = follower.wait();
+    let output = cmd()
+        .args(["--dir", dir, "fetch", "logs", "1", "--jq", "{seq"])
+        .output()
+        .expect("fetch bad program");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(parse_error_json(&output.stderr)["error"]["kind"], "Usage");
 }
 
 #[test]
-fn pool_create_defaults_to_table_output() {
+fn fetch_at_resolves_nearest_message_by_time() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for x in 1..=2 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "events", &format!("{{\"x\":{x}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+    let second = fetch_message(&pool_dir, "events", 2);
+    let second_time = second["time"].as_str().expect("time").to_string();
+
+    let fetch_at = |at: &str, nearest: &str| {
+        cmd()
+            .args([
+                "--dir",
+                dir,
+                "fetch",
+                "events",
+                "--at",
+                at,
+                "--nearest",
+                nearest,
+            ])
+            .output()
+            .expect("fetch --at")
+    };
+    let exact = fetch_at(&second_time, "before");
+    assert!(
+        exact.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&exact.stderr)
+    );
+    assert_eq!(
+        parse_json(std::str::from_utf8(&exact.stdout).expect("utf8"))["seq"],
+        json!(2)
+    );
+
+    let earliest = fetch_at("2000-01-01T00:00:00Z", "after");
+    assert!(earliest.status.success());
+    assert_eq!(
+        parse_json(std::str::from_utf8(&earliest.stdout).expect("utf8"))["seq"],
+        json!(1)
+    );
+
+    let none = fetch_at("2000-01-01T00:00:00Z", "before");
+    assert_eq!(none.status.code(), Some(3));
+}
 
+#[test]
+fn annotate_links_message_and_rejects_missing_seq() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
     let create = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
-        ])
+        .args(["--dir", dir, "pool", "create", "chat"])
         .output()
         .expect("create");
     assert!(create.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "chat", "{\"msg\":\"ship it\"}"])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
 
-    let stdout = std::str::from_utf8(&create.stdout).expect("utf8");
-    assert!(stdout.contains("NAME"));
-    assert!(stdout.contains("SIZE"));
-    assert!(stdout.contains("INDEX"));
-    assert!(stdout.contains("PATH"));
-    assert!(stdout.contains("demo"));
+    let annotate = cmd()
+        .args(["--dir", dir, "annotate", "chat", "1", "{\"emoji\":\"+1\"}"])
+        .output()
+        .expect("annotate");
+    assert!(
+        annotate.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&annotate.stderr)
+    );
+    let receipt = parse_json(std::str::from_utf8(&annotate.stdout).expect("utf8").trim());
+    assert_eq!(receipt["seq"], json!(2));
+    assert_eq!(receipt["meta"]["refs"], json!([1]));
+
+    let annotation = fetch_message(&pool_dir, "chat", 2);
+    assert_eq!(annotation["meta"]["refs"], json!([1]));
+    assert_eq!(annotation["data"]["emoji"], json!("+1"));
+    let original = fetch_message(&pool_dir, "chat", 1);
+    assert!(original["meta"].get("refs").is_none());
+
+    let missing = cmd()
+        .args(["--dir", dir, "annotate", "chat", "99", "{\"emoji\":\"+1\"}"])
+        .output()
+        .expect("annotate missing");
+    assert_eq!(missing.status.code(), Some(3));
 }
 
 #[test]
-fn pool_info_json_includes_metrics() {
+fn pin_keeps_message_fetchable_after_retention_drops_it() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
     let create = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "pool",
             "create",
-            "metrics",
+            "--max-messages",
+            "2",
+            "incidents",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
-
-    let feed_one = cmd()
+    let feed = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "feed",
-            "metrics",
-            "{\"x\":1}",
+            "incidents",
+            "{\"decision\":\"rollback\"}",
         ])
         .output()
         .expect("feed");
-    assert!(feed_one.status.success());
+    assert!(feed.status.success());
 
-    let feed_two = cmd()
+    let pin = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "metrics",
-            "{\"x\":2}",
+            dir,
+            "pin",
+            "incidents",
+            "1",
+            "--note",
+            "root cause",
+            "--json",
         ])
         .output()
-        .expect("feed");
-    assert!(feed_two.status.success());
+        .expect("pin");
+    assert!(
+        pin.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&pin.stderr)
+    );
+    let receipt = parse_json(std::str::from_utf8(&pin.stdout).expect("utf8"));
+    assert_eq!(receipt["pinned"], json!([1]));
+
+    for id in 2..=4 {
+        let feed = cmd()
+            .args([
+                "--dir",
+                dir,
+                "feed",
+                "incidents",
+                &format!("{{\"id\":{id}}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let pinned = fetch_message(&pool_dir, "incidents", 1);
+    assert_eq!(pinned["data"]["decision"], json!("rollback"));
 
     let info = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "info",
-            "metrics",
-            "--json",
-        ])
+        .args(["--dir", dir, "pool", "info", "incidents", "--json"])
         .output()
         .expect("info");
     assert!(info.status.success());
-    let info_json = parse_json(std::str::from_utf8(&info.stdout).expect("utf8"));
-    assert!(info_json["index_capacity"].as_u64().unwrap() > 0);
-    assert!(info_json["index_size_bytes"].as_u64().unwrap() > 0);
-    let metrics = info_json.get("metrics").expect("metrics");
-    assert_eq!(metrics["message_count"], 2);
-    assert_eq!(metrics["seq_span"], 2);
-    assert!(metrics["utilization"]["used_bytes"].as_u64().unwrap() > 0);
-    assert!(metrics["utilization"]["free_bytes"].as_u64().unwrap() > 0);
-    assert!(metrics["utilization"]["used_percent"].is_number());
-    assert!(metrics["age"]["oldest_time"].is_string());
-    assert!(metrics["age"]["newest_time"].is_string());
-    assert!(metrics["age"]["oldest_age_ms"].is_number());
-    assert!(metrics["age"]["newest_age_ms"].is_number());
+    let value = parse_json(std::str::from_utf8(&info.stdout).expect("utf8"));
+    assert_eq!(value["bounds"]["oldest"], json!(3));
+    assert_eq!(value["pins"][0]["seq"], json!(1));
+    assert_eq!(value["pins"][0]["note"], json!("root cause"));
+
+    let list = cmd()
+        .args(["--dir", dir, "pin", "incidents", "--json"])
+        .output()
+        .expect("list");
+    assert!(list.status.success());
+    let listed = parse_json(std::str::from_utf8(&list.stdout).expect("utf8"));
+    assert_eq!(listed["pins"][0]["retained"], json!(false));
+
+    let unpin = cmd()
+        .args(["--dir", dir, "pin", "incidents", "1", "--remove"])
+        .output()
+        .expect("unpin");
+    assert!(unpin.status.success());
+    let gone = cmd()
+        .args(["--dir", dir, "fetch", "incidents", "1"])
+        .output()
+        .expect("fetch");
+    assert_eq!(gone.status.code(), Some(3));
+
+    let missing = cmd()
+        .args(["--dir", dir, "pin", "incidents", "1"])
+        .output()
+        .expect("pin missing");
+    assert_eq!(missing.status.code(), Some(3));
 }
 
 #[test]
-fn pool_create_supports_explicit_and_zero_index_capacity() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+fn fetch_with_no_args_prints_help() {
+    let output = cmd().args(["fetch"]).output().expect("fetch");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
+    assert!(stderr.contains("Usage: plasmite fetch"));
+}
 
-    let create_explicit = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "--size",
-            "1M",
-            "--index-capacity",
-            "1024",
-            "indexed",
-        ])
+#[test]
+fn follow_with_no_args_prints_help() {
+    let output = cmd().args(["follow"]).output().expect("follow");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
+    assert!(stderr.contains("Usage: plasmite follow"));
+}
+
+#[test]
+fn tap_with_no_args_prints_help() {
+    let output = cmd().args(["tap"]).output().expect("tap");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
+    assert!(stderr.contains("Usage: plasmite tap"));
+    assert!(stderr.contains("Capture command output into a local pool"));
+}
+
+#[test]
+fn tap_help_renders_examples() {
+    let output = cmd().args(["tap", "--help"]).output().expect("tap help");
+    assert!(output.status.success());
+    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
+    assert!(stdout.contains("plasmite tap build --create -- cargo build"));
+    assert!(stdout.contains("plasmite tap api --create --create-size 64M -- ./server"));
+    assert!(stdout.contains("`--` is required before wrapped command args"));
+}
+
+#[test]
+fn tap_requires_wrapped_command_after_separator() {
+    let output = cmd().args(["tap", "build"]).output().expect("tap");
+    assert_actionable_usage_feedback(
+        &output,
+        "tap requires a wrapped command after `--`",
+        "plasmite tap <pool> -- <command...>",
+    );
+}
+
+#[test]
+fn tap_remote_url_rejected_as_local_only() {
+    let output = cmd()
+        .args(["tap", "http://127.0.0.1:65535/demo", "--", "echo", "hi"])
         .output()
-        .expect("create");
-    assert!(create_explicit.status.success());
+        .expect("tap");
+    assert_actionable_usage_feedback(
+        &output,
+        "tap accepts local pool refs only",
+        "Use a local pool name/path",
+    );
+}
 
-    let info_explicit = cmd()
+#[test]
+fn tap_non_tty_stderr_suppresses_status_lines() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "info",
-            "indexed",
-            "--json",
+            "tap",
+            "demo",
+            "--create",
+            "--",
+            "echo",
+            "hello",
         ])
         .output()
-        .expect("info");
-    assert!(info_explicit.status.success());
-    let json_explicit = parse_json(std::str::from_utf8(&info_explicit.stdout).expect("utf8"));
-    assert_eq!(json_explicit["index_capacity"], json!(1024));
-    assert_eq!(json_explicit["index_size_bytes"], json!(1024 * 16));
+        .expect("tap");
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        std::str::from_utf8(&output.stdout).expect("utf8"),
+        "hello\n"
+    );
+    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
+    assert!(!stderr.contains("tapping"));
+    assert!(!stderr.contains("tapped"));
+}
 
-    let create_scan_only = cmd()
+#[test]
+fn tap_tty_stderr_emits_startup_and_completion_lines() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let output = cmd_tty(&[
+        "--dir",
+        pool_dir.to_str().unwrap(),
+        "tap",
+        "demo",
+        "--create",
+        "--",
+        "echo",
+        "hello",
+    ]);
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let text = sanitize_tty_text(&output.stdout);
+    assert!(text.contains("tapping demo <- echo hello"), "output={text}");
+    assert!(text.contains("tapped 1 lines ("), "output={text}");
+    assert!(text.contains("-> demo exit 0"), "output={text}");
+}
+
+#[test]
+fn tap_basic_capture_writes_start_line_and_exit_messages() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "--size",
-            "1M",
-            "--index-capacity",
-            "0",
-            "scanonly",
+            "tap",
+            "demo",
+            "--create",
+            "--",
+            "echo",
+            "hello",
         ])
         .output()
-        .expect("create");
-    assert!(create_scan_only.status.success());
+        .expect("tap");
+    assert!(
+        tap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&tap.stderr)
+    );
 
-    let info_scan_only = cmd()
+    let start = fetch_message(&pool_dir, "demo", 1);
+    let line = fetch_message(&pool_dir, "demo", 2);
+    let exit = fetch_message(&pool_dir, "demo", 3);
+
+    assert_eq!(start["data"]["kind"], "start");
+    assert_eq!(start["data"]["cmd"], json!(["echo", "hello"]));
+    assert_eq!(start["meta"]["tags"], json!(["lifecycle"]));
+
+    assert_eq!(line["data"]["kind"], "line");
+    assert_eq!(line["data"]["stream"], "stdout");
+    assert_eq!(line["data"]["line"], "hello");
+
+    assert_eq!(exit["data"]["kind"], "exit");
+    assert_eq!(exit["data"]["code"], 0);
+    assert!(exit["data"]["elapsed_ms"].as_u64().is_some());
+    assert_eq!(exit["meta"]["tags"], json!(["lifecycle"]));
+}
+
+#[test]
+fn tap_captures_stderr_lines() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "info",
-            "scanonly",
-            "--json",
+            "tap",
+            "errpool",
+            "--create",
+            "--",
+            "sh",
+            "-c",
+            "echo err >&2",
         ])
         .output()
-        .expect("info");
-    assert!(info_scan_only.status.success());
-    let json_scan_only = parse_json(std::str::from_utf8(&info_scan_only.stdout).expect("utf8"));
-    assert_eq!(json_scan_only["index_capacity"], json!(0));
-    assert_eq!(json_scan_only["index_size_bytes"], json!(0));
-    assert_eq!(json_scan_only["ring_offset"], json!(4096));
+        .expect("tap");
+    assert!(
+        tap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&tap.stderr)
+    );
+
+    let line = fetch_message(&pool_dir, "errpool", 2);
+    assert_eq!(line["data"]["stream"], "stderr");
+    assert_eq!(line["data"]["line"], "err");
 }
 
 #[test]
-fn pool_create_rejects_oversized_index_capacity() {
+fn tap_forwards_exit_code_and_records_exit_lifecycle_code() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let create = cmd()
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "--size",
-            "64K",
-            "--index-capacity",
-            "5000",
-            "too-big",
+            "tap",
+            "failpool",
+            "--create",
+            "--",
+            "false",
         ])
         .output()
-        .expect("create");
-    assert!(!create.status.success());
+        .expect("tap");
+    assert_eq!(tap.status.code(), Some(1));
 
-    let err = parse_error_json(&create.stderr);
-    assert_eq!(
-        err.get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Usage")
-    );
-    let message = err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(message.contains("index capacity"));
+    let exit = fetch_message(&pool_dir, "failpool", 2);
+    assert_eq!(exit["data"]["kind"], "exit");
+    assert_eq!(exit["data"]["code"], 1);
+    assert!(exit["data"].get("signal").is_none());
+    assert!(exit["data"]["rusage"]["max_rss_kb"].is_u64());
 }
 
 #[test]
-fn pool_info_default_is_human_readable() {
+fn tap_restart_on_failure_records_attempts_until_give_up() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
-    let create = cmd()
+    let tap = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "pretty",
+            dir,
+            "tap",
+            "svc",
+            "--create",
+            "--restart",
+            "on-failure",
+            "--max-restarts",
+            "2",
+            "--backoff",
+            "10ms",
+            "--",
+            "sh",
+            "-c",
+            "echo up; exit 3",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("tap");
+    assert_eq!(tap.status.code(), Some(3));
 
-    let emit_out = cmd()
+    let follow = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "pretty",
-            "{\"x\":1}",
+            dir,
+            "follow",
+            "svc",
+            "--tail",
+            "50",
+            "--jsonl",
+            "--tag",
+            "lifecycle",
+            "--timeout",
+            "200ms",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("follow");
+    let events = parse_json_lines(&follow.stdout)
+        .into_iter()
+        .map(|message| {
+            let data = &message["data"];
+            format!(
+                "{}:{}",
+                data["kind"].as_str().expect("kind"),
+                data["attempt"]
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            "start:1",
+            "exit:1",
+            "restart:2",
+            "start:2",
+            "exit:2",
+            "restart:3",
+            "start:3",
+            "exit:3",
+            "give_up:3",
+        ]
+    );
 
-    let info = cmd()
+    let ok = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "info",
-            "pretty",
+            dir,
+            "tap",
+            "once",
+            "--create",
+            "--restart",
+            "on-failure",
+            "--",
+            "true",
         ])
         .output()
-        .expect("info");
-    assert!(info.status.success());
-    let stdout = std::str::from_utf8(&info.stdout).expect("utf8");
-    assert!(stdout.contains("Pool: pretty"));
-    assert!(stdout.contains("Path: "));
-    assert!(stdout.contains("Bounds: "));
-    assert!(stdout.contains("Utilization: "));
-    assert!(stdout.contains("Oldest: "));
-    assert!(stdout.contains("Newest: "));
+        .expect("tap");
+    assert_eq!(ok.status.code(), Some(0));
+    let exit = fetch_message(&pool_dir, "once", 2);
+    assert_eq!(exit["data"]["kind"], "exit");
+    assert_eq!(exit["data"]["attempt"], 1);
 }
 
+#[cfg(unix)]
 #[test]
-fn pool_info_tty_is_compact_and_hides_ring_offset() {
+fn tap_records_signal_core_dump_and_rusage_for_crashed_command() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let create = cmd()
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "pretty",
+            "tap",
+            "crashpool",
+            "--create",
+            "--",
+            "sh",
+            "-c",
+            "ulimit -c 0; kill -SEGV $$",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("tap");
+    assert_eq!(tap.status.code(), Some(128 + 11));
 
-    let info = cmd_tty(&[
-        "--color",
-        "never",
-        "--dir",
-        pool_dir.to_str().unwrap(),
-        "pool",
-        "info",
-        "pretty",
-    ]);
-    assert!(info.status.success());
-    let stdout = sanitize_tty_text(&info.stdout);
-    assert!(stdout.contains("pretty"));
-    assert!(stdout.contains("path:      pretty.plasmite"));
-    assert!(stdout.contains("messages:  empty"));
-    assert!(stdout.contains("oldest:    —"));
-    assert!(stdout.contains("newest:    —"));
-    assert!(stdout.contains("index:     4096 slots (64K)"));
-    assert!(stdout.contains("ring:      956K"));
-    assert!(!stdout.contains("offset"));
+    let exit = fetch_message(&pool_dir, "crashpool", 2);
+    assert_eq!(exit["data"]["kind"], "exit");
+    assert_eq!(exit["data"]["signal"], "SIGSEGV");
+    assert_eq!(exit["data"]["core_dumped"], false);
+    assert!(exit["data"].get("code").is_none());
+    let rusage = &exit["data"]["rusage"];
+    for field in ["max_rss_kb", "user_cpu_ms", "sys_cpu_ms"] {
+        assert!(rusage[field].is_u64(), "missing rusage.{field}: {rusage}");
+    }
 }
 
 #[test]
-fn pool_info_missing_does_not_emit_path_or_causes() {
+fn tap_applies_user_tags_to_lines_and_lifecycle_tag_to_start_exit() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let output = cmd()
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "info",
-            "channel",
+            "tap",
+            "tagpool",
+            "--create",
+            "--tag",
+            "ci",
+            "--tag",
+            "build",
+            "--",
+            "echo",
+            "ok",
         ])
         .output()
-        .expect("pool info");
-    assert_eq!(output.status.code(), Some(3));
-    let err = parse_error_json(&output.stderr);
-    let error = err
-        .get("error")
-        .and_then(|value| value.as_object())
-        .expect("error");
-    assert_eq!(
-        error.get("kind").and_then(|value| value.as_str()),
-        Some("NotFound")
-    );
-    assert_eq!(
-        error.get("message").and_then(|value| value.as_str()),
-        Some("not found")
-    );
-    assert!(
-        error.get("path").is_none(),
-        "path should not be emitted for missing pool name"
-    );
+        .expect("tap");
     assert!(
-        error.get("causes").is_none(),
-        "causes should not be emitted for missing pool name"
+        tap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&tap.stderr)
     );
+
+    let start = fetch_message(&pool_dir, "tagpool", 1);
+    let line = fetch_message(&pool_dir, "tagpool", 2);
+    let exit = fetch_message(&pool_dir, "tagpool", 3);
+    assert_eq!(start["meta"]["tags"], json!(["lifecycle"]));
+    assert_eq!(line["meta"]["tags"], json!(["ci", "build"]));
+    assert_eq!(exit["meta"]["tags"], json!(["lifecycle"]));
 }
 
 #[test]
-fn readme_quickstart_flow() {
+fn tap_quiet_suppresses_passthrough_but_capture_still_works() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let create = cmd()
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
-            "--size",
-            "128K",
+            "tap",
+            "quietpool",
+            "--create",
+            "-q",
+            "--",
+            "echo",
+            "hello",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("tap");
+    assert!(
+        tap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&tap.stderr)
+    );
+    assert!(
+        tap.stdout.is_empty(),
+        "stdout should be empty in quiet mode"
+    );
 
-    let feed_out = cmd()
+    let line = fetch_message(&pool_dir, "quietpool", 2);
+    assert_eq!(line["data"]["line"], "hello");
+}
+
+#[test]
+fn tap_multiline_capture_preserves_line_order() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
-            "--tag",
-            "ping",
+            "tap",
+            "mpool",
+            "--create",
+            "--",
+            "sh",
+            "-c",
+            "echo a; echo b; echo c",
         ])
         .output()
-        .expect("feed");
-    assert!(feed_out.status.success());
-    let feed_json = parse_json(std::str::from_utf8(&feed_out.stdout).expect("utf8"));
-    let seq = feed_json.get("seq").unwrap().as_u64().unwrap();
+        .expect("tap");
+    assert!(
+        tap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&tap.stderr)
+    );
 
-    let get = cmd()
+    let l1 = fetch_message(&pool_dir, "mpool", 2);
+    let l2 = fetch_message(&pool_dir, "mpool", 3);
+    let l3 = fetch_message(&pool_dir, "mpool", 4);
+    let exit = fetch_message(&pool_dir, "mpool", 5);
+    assert_eq!(l1["data"]["line"], "a");
+    assert_eq!(l2["data"]["line"], "b");
+    assert_eq!(l3["data"]["line"], "c");
+    assert_eq!(exit["data"]["kind"], "exit");
+}
+
+#[test]
+fn tap_captures_unterminated_final_line() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "fetch",
-            "demo",
-            &seq.to_string(),
+            "tap",
+            "nolf",
+            "--create",
+            "--",
+            "python3",
+            "-c",
+            "import sys; sys.stdout.write('tail-without-newline')",
         ])
         .output()
-        .expect("fetch");
-    assert!(get.status.success());
-    let get_json = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
-    assert_eq!(get_json.get("seq").unwrap().as_u64().unwrap(), seq);
+        .expect("tap");
+    assert!(
+        tap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&tap.stderr)
+    );
 
-    let mut follower = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--jsonl",
-        ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let follower_json = parse_json(line.trim());
-    assert_eq!(follower_json.get("seq").unwrap().as_u64().unwrap(), seq);
-    let _ = follower.kill();
-    let _ = follower.wait();
+    let line = fetch_message(&pool_dir, "nolf", 2);
+    assert_eq!(line["data"]["line"], "tail-without-newline");
 }
 
 #[test]
-fn follow_emits_new_messages() {
+fn tap_preserves_long_line_without_truncation() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let create = cmd()
+    let tap = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
+            "tap",
+            "longline",
+            "--create",
+            "--",
+            "python3",
+            "-c",
+            "print('x' * 65536)",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-
-    let mut follower = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--jsonl",
-            "--one",
-        ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
+        .expect("tap");
+    assert!(
+        tap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&tap.stderr)
+    );
 
-    let stdout = follower.stdout.take().expect("stdout");
-    let (line_tx, line_rx) = mpsc::channel();
-    thread::spawn(move || {
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
-        let _ = reader.read_line(&mut line);
-        let _ = line_tx.send(line);
-    });
+    let line = fetch_message(&pool_dir, "longline", 2);
+    let captured = line["data"]["line"].as_str().expect("line string");
+    assert_eq!(captured.len(), 65536);
+    assert!(captured.chars().all(|ch| ch == 'x'));
+}
 
-    let emit_out = cmd()
+#[test]
+fn tap_missing_wrapped_executable_is_actionable_nonzero_error() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":42}",
+            "tap",
+            "missingcmd",
+            "--create",
+            "--",
+            "nonexistent-command-xyz",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
-
-    let line = line_rx
-        .recv_timeout(Duration::from_secs(2))
-        .expect("follow output");
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 42);
-    let status = follower.wait().expect("follow wait");
-    assert!(status.success(), "follow status={status:?}");
+        .expect("tap");
+    assert_actionable_usage_feedback(
+        &output,
+        "wrapped command not found",
+        "Check PATH or use an absolute executable path",
+    );
 }
 
 #[test]
-fn follow_one_exits_after_first_match() {
+fn tap_missing_pool_without_create_has_create_hint() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let create = cmd()
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
+            "tap",
+            "missingpool",
+            "--",
+            "echo",
+            "x",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("tap");
+    assert_eq!(output.status.code(), Some(3));
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(
+        hint.contains("--create"),
+        "expected --create hint in '{hint}'"
+    );
+}
 
-    let mut follower = cmd()
+#[test]
+fn tap_empty_command_after_separator_is_usage_error() {
+    let output = cmd()
+        .args(["tap", "demo", "--create", "--"])
+        .output()
+        .expect("tap");
+    assert_actionable_usage_feedback(
+        &output,
+        "tap requires a wrapped command after `--`",
+        "plasmite tap <pool> -- <command...>",
+    );
+}
+
+#[test]
+fn syslog_rejects_non_udp_listen() {
+    let output = cmd()
+        .args(["syslog", "--listen", "tcp:5514", "--pool", "demo"])
+        .output()
+        .expect("syslog");
+    assert_actionable_usage_feedback(
+        &output,
+        "unsupported syslog transport: tcp",
+        "Only UDP is supported",
+    );
+}
+
+#[test]
+fn syslog_appends_parsed_datagrams() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let port = pick_port().expect("port");
+    let listen = format!("udp:127.0.0.1:{port}");
+
+    let mut child = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--jsonl",
-            "--tail",
-            "1",
-            "--one",
+            "syslog",
+            "--listen",
+            &listen,
+            "--pool",
+            "logs",
+            "--create",
+            "--tag",
+            "lab",
         ])
-        .stdout(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
         .spawn()
-        .expect("follow");
+        .expect("spawn syslog");
+
+    let sender = std::net::UdpSocket::bind("127.0.0.1:0").expect("sender");
+    let datagram = b"<11>Oct 11 22:14:15 labhost cron[42]: job failed";
+    let deadline = Instant::now() + Duration::from_secs(8);
+    let message = loop {
+        sender
+            .send_to(datagram, ("127.0.0.1", port))
+            .expect("send datagram");
+        let fetch = cmd()
+            .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "logs", "1"])
+            .output()
+            .expect("fetch");
+        if fetch.status.success() {
+            break parse_json(std::str::from_utf8(&fetch.stdout).expect("utf8"));
+        }
+        assert!(Instant::now() < deadline, "syslog did not append in time");
+        sleep(Duration::from_millis(50));
+    };
+    let _ = child.kill();
+    let _ = child.wait();
 
-    thread::sleep(Duration::from_millis(50));
+    assert_eq!(message["meta"]["tags"], json!(["lab"]));
+    assert_eq!(message["data"]["format"], "rfc3164");
+    assert_eq!(message["data"]["facility"], "user");
+    assert_eq!(message["data"]["severity"], "err");
+    assert_eq!(message["data"]["host"], "labhost");
+    assert_eq!(message["data"]["app"], "cron");
+    assert_eq!(message["data"]["pid"], "42");
+    assert_eq!(message["data"]["msg"], "job failed");
+}
 
-    let emit_out = cmd()
+#[test]
+fn journal_copies_export_entries_with_unit_and_priority_tags() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let args_log = temp.path().join("args.txt");
+    let fake = temp.path().join("fake-journalctl");
+    std::fs::write(
+        &fake,
+        format!(
+            "#!/bin/sh\necho \"$@\" > '{}'\nprintf '__REALTIME_TIMESTAMP=1700000000000000\\n_SYSTEMD_UNIT=web.service\\nPRIORITY=4\\nMESSAGE=disk almost full\\n\\n'\n",
+            args_log.display()
+        ),
+    )
+    .expect("write fake journalctl");
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
+            "journal",
+            "--pool",
+            "svc",
+            "--create",
+            "--unit",
+            "web.service",
+            "--journalctl",
+            fake.to_str().unwrap(),
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("journal");
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
 
-    let stdout = follower.stdout.take().expect("stdout");
-    let (line_tx, line_rx) = mpsc::channel();
-    thread::spawn(move || {
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
-        let _ = reader.read_line(&mut line);
-        let _ = line_tx.send(line);
-    });
+    let args = std::fs::read_to_string(&args_log).expect("args");
+    assert!(args.contains("--output=export"), "args={args}");
+    assert!(args.contains("--unit=web.service"), "args={args}");
+    assert!(!args.contains("--follow"), "args={args}");
 
-    let line = line_rx
-        .recv_timeout(Duration::from_secs(2))
-        .expect("follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 1);
+    let message = fetch_message(&pool_dir, "svc", 1);
+    assert_eq!(
+        message["meta"]["tags"],
+        json!(["unit:web.service", "priority:warning"])
+    );
+    assert_eq!(message["data"]["msg"], "disk almost full");
+    assert_eq!(message["data"]["fields"]["PRIORITY"], "4");
+    assert!(
+        message["time"]
+            .as_str()
+            .expect("time")
+            .starts_with("2023-11-14T22:13:20")
+    );
+}
 
-    // Send a second message after the first is observed so `--one` ordering
-    // is deterministic without relying on fixed startup sleeps.
-    let emit_out = cmd()
+#[test]
+fn journal_missing_journalctl_is_actionable() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":2}",
+            "journal",
+            "--pool",
+            "svc",
+            "--create",
+            "--journalctl",
+            "/nonexistent/journalctl",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("journal");
+    assert_actionable_usage_feedback(&output, "journalctl not found", "--journalctl <path>");
+}
 
-    let (exit_tx, exit_rx) = mpsc::channel();
+/// Minimal HTTP/1.1 sink: answers every POST with `status` and reports bodies.
+fn spawn_webhook_sink(status: u16) -> (String, mpsc::Receiver<Value>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind sink");
+    let url = format!("http://{}/hook", listener.local_addr().expect("addr"));
+    let (tx, rx) = mpsc::channel();
     thread::spawn(move || {
-        let status = follower.wait().expect("wait");
-        let _ = exit_tx.send(status);
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { return };
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+                let mut writer = stream;
+                loop {
+                    let mut content_length = 0usize;
+                    let mut line = String::new();
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let trimmed = line.trim_end();
+                        if trimmed.is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = trimmed.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap_or(0);
+                            }
+                        }
+                    }
+                    let mut body = vec![0u8; content_length];
+                    if reader.read_exact(&mut body).is_err() {
+                        return;
+                    }
+                    if let Ok(value) = serde_json::from_slice(&body) {
+                        let _ = tx.send(value);
+                    }
+                    let response = format!("HTTP/1.1 {status} X\r\nContent-Length: 0\r\n\r\n");
+                    if writer.write_all(response.as_bytes()).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
     });
-    let status = exit_rx
-        .recv_timeout(Duration::from_secs(2))
-        .expect("follow exit");
-    assert!(status.success());
+    (url, rx)
 }
 
 #[test]
-fn follow_tail_one_emits_nth_match() {
+fn forward_delivers_batches_and_resumes_from_cursor() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let cursor_file = temp.path().join("cursor.json");
+    std::fs::write(&cursor_file, "{\"seq\":0}\n").expect("seed cursor");
+    let (url, bodies) = spawn_webhook_sink(200);
 
-    let create = cmd()
+    let feed = |data: &str| {
+        let output = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "events",
+                "--create",
+                data,
+            ])
+            .output()
+            .expect("feed");
+        assert!(output.status.success());
+    };
+    let forward = || {
+        cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "forward",
+                "events",
+                "--to",
+                &url,
+                "--batch",
+                "2",
+                "--where",
+                ".data.level == \"error\"",
+                "--cursor-file",
+                cursor_file.to_str().unwrap(),
+                "--drain",
+            ])
+            .output()
+            .expect("forward")
+    };
+
+    feed(r#"{"level":"error","n":1}"#);
+    feed(r#"{"level":"info","n":2}"#);
+    feed(r#"{"level":"error","n":3}"#);
+    let output = forward();
+    assert!(output.status.success(), "{output:?}");
+    let body = bodies.recv_timeout(Duration::from_secs(5)).expect("batch");
+    let batch = body.as_array().expect("array body");
+    assert_eq!(batch.len(), 2);
+    assert_eq!(batch[0]["data"]["n"], 1);
+    assert_eq!(batch[1]["data"]["n"], 3);
+    let cursor = parse_json(&std::fs::read_to_string(&cursor_file).expect("cursor"));
+    assert_eq!(cursor["seq"], 3);
+
+    feed(r#"{"level":"error","n":4}"#);
+    let output = forward();
+    assert!(output.status.success(), "{output:?}");
+    let body = bodies.recv_timeout(Duration::from_secs(5)).expect("batch");
+    assert_eq!(body[0]["data"]["n"], 4);
+    assert_eq!(body.as_array().map(Vec::len), Some(1));
+    assert!(bodies.try_recv().is_err());
+}
+
+#[test]
+fn hookd_runs_registered_hooks_and_retries_failures() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let out = temp.path().join("hook.out");
+    let (url, bodies) = spawn_webhook_sink(200);
+    let run = |args: &[&str]| cmd().args(["--dir", dir]).args(args).output().expect("run");
+    let feed = |data: &str| {
+        let output = run(&["feed", "events", "--create", data]);
+        assert!(output.status.success());
+    };
+
+    feed(r#"{"level":"error","n":0}"#);
+    let exec = format!("cat >> '{}'", out.display());
+    let added = run(&[
+        "hook",
+        "add",
+        "events",
+        "--exec",
+        &exec,
+        "--where",
+        ".data.level == \"error\"",
+        "--json",
+    ]);
+    assert!(added.status.success(), "{added:?}");
+    let exec_id = parse_json(std::str::from_utf8(&added.stdout).expect("utf8"))["hook"]["id"]
+        .as_str()
+        .expect("id")
+        .to_string();
+    assert!(
+        run(&["hook", "add", "events", "--post", &url])
+            .status
+            .success()
+    );
+    assert!(
+        run(&["hook", "add", "events", "--exec", "exit 3"])
+            .status
+            .success()
+    );
+
+    feed(r#"{"level":"error","n":1}"#);
+    feed(r#"{"level":"info","n":2}"#);
+    let hookd = run(&["hookd", "--once"]);
+    assert_eq!(hookd.status.code(), Some(1), "{hookd:?}");
+    assert!(String::from_utf8_lossy(&hookd.stderr).contains("hook_failed"));
+
+    // Only messages after `hook add` fire, filtered by --where.
+    let lines = std::fs::read_to_string(&out).expect("hook output");
+    let seen = lines
+        .lines()
+        .map(|line| parse_json(line)["data"]["n"].as_i64().expect("n"))
+        .collect::<Vec<_>>();
+    assert_eq!(seen, [1]);
+    let first = bodies.recv_timeout(Duration::from_secs(5)).expect("post");
+    let second = bodies.recv_timeout(Duration::from_secs(5)).expect("post");
+    assert_eq!(
+        (first["data"]["n"].clone(), second["data"]["n"].clone()),
+        (json!(1), json!(2))
+    );
+
+    let list = run(&["hook", "list", "--json"]);
+    let hooks = parse_json(std::str::from_utf8(&list.stdout).expect("utf8"));
+    let cursors = hooks["hooks"]
+        .as_array()
+        .expect("hooks")
+        .iter()
+        .map(|hook| hook["cursor_seq"].as_u64().expect("cursor"))
+        .collect::<Vec<_>>();
+    // The failing hook stays on the message it could not handle.
+    assert_eq!(cursors, [3, 3, 1]);
+
+    assert!(run(&["hook", "remove", &exec_id]).status.success());
+    let missing = run(&["hook", "remove", &exec_id]);
+    assert_eq!(missing.status.code(), Some(3));
+    let list = run(&["hook", "list", "--json"]);
+    let hooks = parse_json(std::str::from_utf8(&list.stdout).expect("utf8"));
+    assert_eq!(hooks["hooks"].as_array().map(Vec::len), Some(2));
+}
+
+#[test]
+fn forward_client_error_is_not_retried_and_keeps_cursor() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let cursor_file = temp.path().join("cursor.json");
+    std::fs::write(&cursor_file, "{\"seq\":0}\n").expect("seed cursor");
+    let (url, bodies) = spawn_webhook_sink(400);
+
+    let feed = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
+            "feed",
+            "events",
+            "--create",
+            "{\"n\":1}",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("feed");
+    assert!(feed.status.success());
 
-    let mut follower = cmd()
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "2",
-            "--jsonl",
-            "--one",
+            "forward",
+            "events",
+            "--to",
+            &url,
+            "--cursor-file",
+            cursor_file.to_str().unwrap(),
+            "--drain",
         ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-
-    thread::sleep(Duration::from_millis(50));
+        .output()
+        .expect("forward");
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    assert!(
+        err["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("HTTP 400")
+    );
+    assert_eq!(
+        bodies.recv_timeout(Duration::from_secs(5)).expect("body")["data"]["n"],
+        1
+    );
+    assert!(bodies.try_recv().is_err());
+    let cursor = parse_json(&std::fs::read_to_string(&cursor_file).expect("cursor"));
+    assert_eq!(cursor["seq"], 0);
+}
 
-    let emit_out = cmd()
+#[test]
+fn pool_create_with_no_args_prints_help() {
+    let output = cmd()
+        .args(["pool", "create"])
+        .output()
+        .expect("pool create");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = std::str::from_utf8(&output.stderr).expect("utf8");
+    assert!(stderr.contains("Usage: plasmite pool create"));
+}
+
+#[test]
+fn create_feed_fetch_follow_flow() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
+            "pool",
+            "create",
+            "--json",
+            "testpool",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("create");
+    assert!(create.status.success());
+    let create_json = parse_json(std::str::from_utf8(&create.stdout).expect("utf8"));
+    let created = create_json
+        .get("created")
+        .and_then(|value| value.as_array())
+        .expect("created array")
+        .first()
+        .expect("first");
+    assert_eq!(created.get("name").unwrap().as_str().unwrap(), "testpool");
+    assert!(
+        created
+            .get("path")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .ends_with("testpool.plasmite")
+    );
+    assert!(created.get("bounds").unwrap().get("oldest").is_none());
 
-    let emit_out = cmd()
+    let feed_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
-            "demo",
-            "{\"x\":2}",
+            "testpool",
+            "{\"x\":1}",
+            "--tag",
+            "ping",
         ])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
-
-    let stdout = follower.stdout.take().expect("stdout");
-    let (line_tx, line_rx) = mpsc::channel();
-    thread::spawn(move || {
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
-        let _ = reader.read_line(&mut line);
-        let _ = line_tx.send(line);
-    });
-
-    let line = line_rx
-        .recv_timeout(Duration::from_secs(2))
-        .expect("follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 2);
-
-    let (exit_tx, exit_rx) = mpsc::channel();
-    thread::spawn(move || {
-        let status = follower.wait().expect("wait");
-        let _ = exit_tx.send(status);
-    });
-    let status = exit_rx
-        .recv_timeout(Duration::from_secs(2))
-        .expect("follow exit");
-    assert!(status.success());
-}
-
-#[test]
-fn follow_timeout_exits_when_no_output() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    assert!(feed_out.status.success());
+    let feed_json = parse_json(std::str::from_utf8(&feed_out.stdout).expect("utf8"));
+    let seq = feed_json.get("seq").unwrap().as_u64().unwrap();
+    assert!(feed_json.get("time").is_some());
+    assert_eq!(feed_json.get("meta").unwrap()["tags"][0], "ping");
+    assert!(feed_json.get("data").is_none());
 
-    let create = cmd()
+    let get = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
+            "fetch",
+            "testpool",
+            &seq.to_string(),
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("fetch");
+    assert!(get.status.success());
+    let get_json = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
+    assert_eq!(get_json.get("seq").unwrap().as_u64().unwrap(), seq);
+    assert_eq!(get_json.get("data").unwrap()["x"], 1);
 
-    let output = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "follow",
-            "demo",
+            "testpool",
+            "--tail",
+            "1",
             "--jsonl",
-            "--timeout",
-            "500ms",
         ])
-        .output()
+        .stdout(Stdio::piped())
+        .spawn()
         .expect("follow");
-    assert_eq!(output.status.code().unwrap(), 124);
-    assert!(output.stdout.is_empty());
-    assert!(output.stderr.is_empty());
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let follower_json = parse_json(line.trim());
+    assert_eq!(follower_json.get("seq").unwrap().as_u64().unwrap(), seq);
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn follow_timeout_on_tty_prints_message() {
+fn pool_create_defaults_to_table_output() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -1552,24 +2312,16 @@ fn follow_timeout_on_tty_prints_message() {
         .expect("create");
     assert!(create.status.success());
 
-    let output = cmd_tty(&[
-        "--color",
-        "never",
-        "--dir",
-        pool_dir.to_str().unwrap(),
-        "follow",
-        "demo",
-        "--one",
-        "--timeout",
-        "200ms",
-    ]);
-    assert_eq!(output.status.code(), Some(124));
-    let text = sanitize_tty_text(&output.stdout);
-    assert!(text.contains("No messages received (timed out after 200ms)."));
+    let stdout = std::str::from_utf8(&create.stdout).expect("utf8");
+    assert!(stdout.contains("NAME"));
+    assert!(stdout.contains("SIZE"));
+    assert!(stdout.contains("INDEX"));
+    assert!(stdout.contains("PATH"));
+    assert!(stdout.contains("demo"));
 }
 
 #[test]
-fn follow_timeout_with_one_exits_on_message() {
+fn pool_info_json_includes_metrics() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -1579,91 +2331,65 @@ fn follow_timeout_with_one_exits_on_message() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "demo",
+            "metrics",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let pool_dir_str = pool_dir.to_str().unwrap().to_string();
-    thread::spawn(move || {
-        thread::sleep(Duration::from_millis(50));
-        let _ = cmd()
-            .args(["--dir", &pool_dir_str, "feed", "demo", "{\"x\":1}"])
-            .output();
-    });
-
-    let output = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--jsonl",
-            "--one",
-            "--timeout",
-            "5s",
-        ])
-        .output()
-        .expect("follow");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 1);
-    assert_eq!(lines[0].get("data").unwrap()["x"], 1);
-}
-
-#[test]
-fn follow_data_only_jsonl_emits_payload() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
-
-    let create = cmd()
+    let feed_one = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
+            "feed",
+            "metrics",
+            "{\"x\":1}",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("feed");
+    assert!(feed_one.status.success());
 
-    let emit_out = cmd()
+    let feed_two = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
-            "demo",
-            "{\"x\":1}",
+            "metrics",
+            "{\"x\":2}",
         ])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
+    assert!(feed_two.status.success());
 
-    let output = cmd()
+    let info = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--jsonl",
-            "--data-only",
-            "--one",
+            "pool",
+            "info",
+            "metrics",
+            "--json",
         ])
         .output()
-        .expect("follow");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 1);
-    assert_eq!(lines[0].get("x").unwrap().as_i64().unwrap(), 1);
-    assert!(lines[0].get("data").is_none());
+        .expect("info");
+    assert!(info.status.success());
+    let info_json = parse_json(std::str::from_utf8(&info.stdout).expect("utf8"));
+    assert!(info_json["index_capacity"].as_u64().unwrap() > 0);
+    assert!(info_json["index_size_bytes"].as_u64().unwrap() > 0);
+    let metrics = info_json.get("metrics").expect("metrics");
+    assert_eq!(metrics["message_count"], 2);
+    assert_eq!(metrics["seq_span"], 2);
+    assert!(metrics["utilization"]["used_bytes"].as_u64().unwrap() > 0);
+    assert!(metrics["utilization"]["free_bytes"].as_u64().unwrap() > 0);
+    assert!(metrics["utilization"]["used_percent"].is_number());
+    assert!(metrics["age"]["oldest_time"].is_string());
+    assert!(metrics["age"]["newest_time"].is_string());
+    assert!(metrics["age"]["oldest_age_ms"].is_number());
+    assert!(metrics["age"]["newest_age_ms"].is_number());
 }
 
 #[test]
-fn follow_data_only_where_filters_envelope() {
+fn pool_create_hybrid_timestamps_is_reported_in_info() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -1673,443 +2399,656 @@ fn follow_data_only_where_filters_envelope() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "demo",
+            "--hybrid-timestamps",
+            "hybrid",
+            "plain",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let emit_out = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
-            "--tag",
-            "drop",
-        ])
-        .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
-
-    let emit_out = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":2}",
-            "--tag",
-            "keep",
-        ])
-        .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+    for (name, expected) in [("hybrid", json!(true)), ("plain", Value::Null)] {
+        let info = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "pool",
+                "info",
+                name,
+                "--json",
+            ])
+            .output()
+            .expect("info");
+        assert!(info.status.success());
+        let value = parse_json(std::str::from_utf8(&info.stdout).expect("utf8"));
+        assert_eq!(value["hybrid_timestamps"], expected);
+    }
 
-    let output = cmd()
+    let doctor = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--jsonl",
-            "--data-only",
-            "--one",
-            "--where",
-            r#".meta.tags[]? == "keep""#,
+            "doctor",
+            "hybrid",
+            "--json",
         ])
         .output()
-        .expect("follow");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 1);
-    assert_eq!(lines[0].get("x").unwrap().as_i64().unwrap(), 2);
-    assert!(lines[0].get("data").is_none());
+        .expect("doctor");
+    assert!(doctor.status.success());
 }
 
 #[test]
-fn follow_data_only_pretty_emits_payload() {
+fn pool_create_max_messages_keeps_newest_messages() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
     let create = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "pool",
             "create",
-            "demo",
+            "--max-messages",
+            "2",
+            "recent",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
+    for id in 1..=3 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "recent", &format!("{{\"id\":{id}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let emit_out = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":3}",
-        ])
+    let info = cmd()
+        .args(["--dir", dir, "pool", "info", "recent", "--json"])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("info");
+    assert!(info.status.success());
+    let value = parse_json(std::str::from_utf8(&info.stdout).expect("utf8"));
+    assert_eq!(value["max_messages"], json!(2));
+    assert_eq!(value["bounds"]["oldest"], json!(2));
+    assert_eq!(value["bounds"]["newest"], json!(3));
 
-    let output = cmd()
+    let zero = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--format",
-            "pretty",
-            "--data-only",
-            "--one",
+            dir,
+            "pool",
+            "create",
+            "--max-messages",
+            "0",
+            "none",
         ])
         .output()
-        .expect("follow");
-    assert!(output.status.success());
-    let value = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
-    assert_eq!(value.get("x").unwrap().as_i64().unwrap(), 3);
-    assert!(value.get("data").is_none());
+        .expect("create");
+    assert_eq!(zero.status.code(), Some(2));
 }
 
 #[test]
-fn follow_where_filters_messages() {
+fn pool_create_max_frame_chunks_large_messages_and_reads_them_whole() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
     let create = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "pool",
             "create",
-            "demo",
+            "--max-frame",
+            "256",
+            "--max-message",
+            "4K",
+            "big",
+            "--json",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
+    let created = parse_json(std::str::from_utf8(&create.stdout).expect("utf8"));
+    assert_eq!(created["created"][0]["max_frame_bytes"], json!(256));
+    assert_eq!(created["created"][0]["max_message_bytes"], json!(4096));
 
-    let emit_out = cmd()
+    let text = "x".repeat(1500);
+    let feed = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "feed",
-            "demo",
-            "{\"x\":1}",
-            "--tag",
-            "drop",
+            "big",
+            &json!({ "text": text }).to_string(),
         ])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
-
-    let emit_out = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":2}",
-            "--tag",
-            "keep",
-        ])
+    assert!(feed.status.success());
+    let seq = parse_json(std::str::from_utf8(&feed.stdout).expect("utf8"))["seq"]
+        .as_u64()
+        .expect("seq");
+    assert!(seq > 1, "large message should span several frames");
+    let small = cmd()
+        .args(["--dir", dir, "feed", "big", "{\"text\":\"small\"}"])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
+    assert!(small.status.success());
 
-    let mut follower = cmd()
+    let fetched = fetch_message(&pool_dir, "big", seq);
+    assert_eq!(fetched["data"]["text"], json!(text));
+    assert!(fetched["meta"].get("flags").is_none());
+    let continuation = cmd()
+        .args(["--dir", dir, "fetch", "big", "1"])
+        .output()
+        .expect("fetch");
+    assert_eq!(continuation.status.code(), Some(3));
+
+    let follow = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "follow",
-            "demo",
-            "--tail",
-            "10",
+            "big",
+            "--since",
+            "1h",
             "--jsonl",
-            "--where",
-            r#".meta.tags[]? == "keep""#,
+            "--timeout",
+            "200ms",
         ])
-        .stdout(Stdio::piped())
-        .spawn()
+        .output()
         .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 2);
-    let _ = follower.kill();
-    let _ = follower.wait();
+    let lines = parse_json_lines(&follow.stdout);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["seq"], json!(seq));
+    assert_eq!(lines[0]["data"]["text"], json!(text));
+    assert_eq!(lines[1]["data"]["text"], json!("small"));
+
+    let too_big = cmd()
+        .args([
+            "--dir",
+            dir,
+            "feed",
+            "big",
+            &json!({ "text": "y".repeat(5000) }).to_string(),
+        ])
+        .output()
+        .expect("feed");
+    assert_eq!(too_big.status.code(), Some(2));
 }
 
 #[test]
-fn follow_tag_filters_messages() {
+fn feed_attach_stores_bytes_and_fetch_writes_them_back() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-
+    let dir = pool_dir.to_str().unwrap();
     let create = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
-        ])
+        .args(["--dir", dir, "pool", "create", "files"])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let emit_out = cmd()
+    let bytes = (0..=255u8).cycle().take(700).collect::<Vec<_>>();
+    let input = temp.path().join("blob.bin");
+    std::fs::write(&input, &bytes).expect("write blob");
+    let feed = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "feed",
-            "demo",
-            "{\"x\":1}",
-            "--tag",
-            "drop",
+            "files",
+            "--attach",
+            input.to_str().unwrap(),
+            "{\"kind\":\"upload\"}",
         ])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
+    assert!(
+        feed.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&feed.stderr)
+    );
 
-    let emit_out = cmd()
+    let fetched = fetch_message(&pool_dir, "files", 1);
+    assert_eq!(fetched["data"]["kind"], json!("upload"));
+    assert_eq!(
+        fetched["data"]["attachment"]["$attachment"],
+        json!({ "bytes": 700, "name": "blob.bin" })
+    );
+    assert!(fetched["attachment"].is_string());
+    assert!(fetched["meta"].get("flags").is_none());
+
+    let output = temp.path().join("out.bin");
+    let saved = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":2}",
-            "--tag",
-            "keep",
+            dir,
+            "fetch",
+            "files",
+            "1",
+            "--attachment-out",
+            output.to_str().unwrap(),
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("fetch");
+    assert!(saved.status.success());
+    let message = parse_json(std::str::from_utf8(&saved.stdout).expect("utf8"));
+    assert!(message.get("attachment").is_none());
+    assert_eq!(std::fs::read(&output).expect("read out"), bytes);
 
-    let mut follower = cmd()
+    let plain = cmd()
+        .args(["--dir", dir, "feed", "files", "{\"kind\":\"note\"}"])
+        .output()
+        .expect("feed");
+    assert!(plain.status.success());
+    let missing = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "10",
-            "--jsonl",
-            "--tag",
-            "keep",
+            dir,
+            "fetch",
+            "files",
+            "2",
+            "--attachment-out",
+            output.to_str().unwrap(),
         ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 2);
-    let _ = follower.kill();
-    let _ = follower.wait();
+        .output()
+        .expect("fetch");
+    assert_eq!(missing.status.code(), Some(3));
 }
 
 #[test]
-fn follow_tag_and_where_compose_with_and() {
+fn bloom_filter_pools_answer_where_scans_like_plain_pools() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-
+    let dir = pool_dir.to_str().unwrap();
     let create = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "pool",
             "create",
-            "demo",
+            "--bloom-filter",
+            "audit",
+            "--json",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
+    let created = parse_json(std::str::from_utf8(&create.stdout).expect("utf8"));
+    assert_eq!(created["created"][0]["bloom_filter"], json!(true));
 
-    let events = [
-        (r#"{"service":"billing","level":"warn"}"#, "keep"),
-        (r#"{"service":"billing","level":"error"}"#, "keep"),
-        (r#"{"service":"payments","level":"error"}"#, "keep"),
-    ];
-    for (payload, tag) in events {
-        let emit_out = cmd()
+    for level in ["info", "error", "warn", "error", "info"] {
+        let feed = cmd()
             .args([
                 "--dir",
-                pool_dir.to_str().unwrap(),
+                dir,
                 "feed",
-                "demo",
-                payload,
-                "--tag",
-                tag,
+                "audit",
+                &json!({ "level": level, "host": "web-1" }).to_string(),
             ])
             .output()
             .expect("feed");
-        assert!(emit_out.status.success());
+        assert!(feed.status.success());
     }
 
-    let mut follower = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "10",
-            "--jsonl",
-            "--tag",
-            "keep",
-            "--where",
-            r#".data.level == "error""#,
-            "--where",
-            r#".data.service == "billing""#,
-        ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value["data"]["service"], "billing");
-    assert_eq!(value["data"]["level"], "error");
-    let _ = follower.kill();
-    let _ = follower.wait();
+    let follow = |where_expr: &str| {
+        let output = cmd()
+            .args([
+                "--dir",
+                dir,
+                "follow",
+                "audit",
+                "--since",
+                "1h",
+                "--where",
+                where_expr,
+                "--jsonl",
+                "--timeout",
+                "200ms",
+            ])
+            .output()
+            .expect("follow");
+        parse_json_lines(&output.stdout)
+            .iter()
+            .map(|line| line["seq"].as_u64().expect("seq"))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(follow(".data.level == \"error\""), vec![2, 4]);
+    assert_eq!(
+        follow(".data.level == \"error\" and .data.host == \"web-1\""),
+        vec![2, 4]
+    );
+    assert_eq!(follow(".data.level == \"debug\""), Vec::<u64>::new());
+    assert_eq!(
+        follow(".data.level == \"warn\" or .data.level == \"error\""),
+        vec![2, 3, 4]
+    );
 }
 
 #[test]
-fn follow_where_multiple_predicates_and() {
+fn where_field_equalities_match_like_full_decodes() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "metrics"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    for data in [
+        json!({ "user": { "id": 7, "name": "ada" }, "score": 1.5, "ok": true }),
+        json!({ "user": { "id": 8, "name": "bob" }, "score": 2, "ok": false }),
+        json!({ "user": { "id": 7.0, "name": "ada" }, "score": 1.5 }),
+        json!({ "user": "7", "ok": null }),
+        json!({ "score": [1.5] }),
+    ] {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "metrics", &data.to_string()])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let follow = |where_expr: &str| {
+        let output = cmd()
+            .args([
+                "--dir",
+                dir,
+                "follow",
+                "metrics",
+                "--tail",
+                "10",
+                "--where",
+                where_expr,
+                "--jsonl",
+                "--timeout",
+                "200ms",
+            ])
+            .output()
+            .expect("follow");
+        parse_json_lines(&output.stdout)
+            .iter()
+            .map(|line| line["seq"].as_u64().expect("seq"))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(follow(".data.user.id == 7"), vec![1, 3]);
+    assert_eq!(
+        follow(".data.user.name == \"ada\" and .data.score == 1.5"),
+        vec![1, 3]
+    );
+    assert_eq!(follow(".data.score == 2.0"), vec![2]);
+    assert_eq!(follow(".data.ok == null"), vec![3, 4, 5]);
+    assert_eq!(follow(".data.ok == false"), vec![2]);
+    assert_eq!(follow(".data.user == \"7\""), vec![4]);
+    assert_eq!(follow(".data.user.id == 9"), Vec::<u64>::new());
+}
+
+#[test]
+fn pool_create_template_applies_settings_tags_and_required_fields() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let templates = temp.path().join("templates.toml");
+    std::fs::write(
+        &templates,
+        "[audit]\nsize = \"2M\"\nmax_messages = 10\ntags = [\"audit\"]\nrequired = [\"actor\"]\n",
+    )
+    .expect("write templates");
 
     let create = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "pool",
             "create",
-            "demo",
+            "--template",
+            "chat",
+            "room",
+            "--json",
         ])
+        .env("PLASMITE_TEMPLATES", &templates)
         .output()
         .expect("create");
     assert!(create.status.success());
+    let created = parse_json(std::str::from_utf8(&create.stdout).expect("utf8"));
+    assert_eq!(created["created"][0]["template"], json!("chat"));
+    assert_eq!(created["created"][0]["file_size"], json!(4 * 1024 * 1024));
 
-    let emit_out = cmd()
+    let missing = cmd()
+        .args(["--dir", dir, "feed", "room", r#"{"msg":"hi"}"#])
+        .output()
+        .expect("feed");
+    assert_eq!(missing.status.code(), Some(2));
+    let feed = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "feed",
-            "demo",
-            "{\"level\":1}",
+            "room",
             "--tag",
-            "alpha",
+            "urgent",
+            r#"{"from":"ann","msg":"hi"}"#,
         ])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
+    assert!(feed.status.success());
+    assert_eq!(
+        fetch_message(&pool_dir, "room", 1)["meta"]["tags"],
+        json!(["chat", "urgent"])
+    );
 
-    let emit_out = cmd()
+    let audit = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"level\":2}",
-            "--tag",
-            "alpha",
+            dir,
+            "pool",
+            "create",
+            "--template",
+            "audit",
+            "trail",
+            "--json",
         ])
+        .env("PLASMITE_TEMPLATES", &templates)
+        .output()
+        .expect("create");
+    assert!(audit.status.success());
+    let audit = parse_json(std::str::from_utf8(&audit.stdout).expect("utf8"));
+    assert_eq!(audit["created"][0]["file_size"], json!(2 * 1024 * 1024));
+    assert_eq!(audit["created"][0]["max_messages"], json!(10));
+
+    let unknown = cmd()
+        .args(["--dir", dir, "pool", "create", "--template", "nope", "x"])
+        .env("PLASMITE_TEMPLATES", &templates)
+        .output()
+        .expect("create");
+    assert_eq!(unknown.status.code(), Some(3));
+}
+
+#[test]
+fn pool_apply_creates_updates_and_reports_drift_from_describe_output() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let manifest = temp.path().join("pools.toml");
+    std::fs::write(
+        &manifest,
+        "[pools.events]\nsize = \"2M\"\nmax_messages = 50\ntags = [\"events\"]\nrequired = [\"level\"]\n",
+    )
+    .expect("write manifest");
+
+    let apply = |extra: &[&str]| {
+        let mut args = vec!["--dir", dir, "pool", "apply", manifest.to_str().unwrap()];
+        args.extend_from_slice(extra);
+        args.push("--json");
+        cmd().args(&args).output().expect("apply")
+    };
+    let dry = apply(&["--dry-run"]);
+    assert!(dry.status.success());
+    let dry = parse_json(std::str::from_utf8(&dry.stdout).expect("utf8"));
+    assert_eq!(dry["pools"][0]["action"], json!("created"));
+    assert!(!pool_dir.join("events.plasmite").exists());
+
+    let created = apply(&[]);
+    assert!(created.status.success());
+    let missing = cmd()
+        .args(["--dir", dir, "feed", "events", r#"{"msg":"hi"}"#])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
+    assert_eq!(missing.status.code(), Some(2));
+
+    let describe = cmd()
+        .args(["--dir", dir, "pool", "describe", "events"])
+        .output()
+        .expect("describe");
+    assert!(describe.status.success());
+    let described = String::from_utf8(describe.stdout).expect("utf8");
+    assert!(described.starts_with("[pools.events]"), "{described}");
+    assert!(described.contains("max_messages = 50"), "{described}");
+    std::fs::write(&manifest, &described).expect("write described");
+    let unchanged = apply(&[]);
+    assert!(unchanged.status.success());
+    let unchanged = parse_json(std::str::from_utf8(&unchanged.stdout).expect("utf8"));
+    assert_eq!(unchanged["pools"][0]["action"], json!("unchanged"));
+
+    std::fs::write(&manifest, "[pools.events]\nsize = \"4M\"\nrequired = []\n")
+        .expect("write drifted manifest");
+    let drifted = apply(&[]);
+    assert_eq!(drifted.status.code(), Some(1));
+    let drifted = parse_json(std::str::from_utf8(&drifted.stdout).expect("utf8"));
+    assert_eq!(drifted["pools"][0]["action"], json!("updated"));
+    assert_eq!(drifted["pools"][0]["changes"], json!(["required"]));
+    assert_eq!(drifted["pools"][0]["drift"][0]["field"], json!("size"));
+    assert_eq!(
+        drifted["pools"][0]["drift"][0]["actual"],
+        json!(2 * 1024 * 1024)
+    );
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "events", r#"{"msg":"hi"}"#])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+    assert_eq!(
+        fetch_message(&pool_dir, "events", 1)["meta"]["tags"],
+        json!(["events"])
+    );
 
-    let mut follower = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "10",
-            "--jsonl",
-            "--where",
-            r#".meta.tags[]? == "alpha""#,
-            "--where",
-            ".data.level >= 2",
-        ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["level"], 2);
-    let _ = follower.kill();
-    let _ = follower.wait();
+    std::fs::write(&manifest, "[pools.events]\nsize = \"lots\"\n").expect("write");
+    let invalid = apply(&[]);
+    assert_eq!(invalid.status.code(), Some(2));
 }
 
 #[test]
-fn follow_where_invalid_expression_is_usage_error() {
+fn pool_create_supports_explicit_and_zero_index_capacity() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let create = cmd()
+    let create_explicit = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "demo",
+            "--size",
+            "1M",
+            "--index-capacity",
+            "1024",
+            "indexed",
         ])
         .output()
         .expect("create");
-    assert!(create.status.success());
+    assert!(create_explicit.status.success());
 
-    let emit_out = cmd()
+    let info_explicit = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
+            "pool",
+            "info",
+            "indexed",
+            "--json",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("info");
+    assert!(info_explicit.status.success());
+    let json_explicit = parse_json(std::str::from_utf8(&info_explicit.stdout).expect("utf8"));
+    assert_eq!(json_explicit["index_capacity"], json!(1024));
+    assert_eq!(json_explicit["index_size_bytes"], json!(1024 * 16));
 
-    let follower = cmd()
+    let create_scan_only = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--jsonl",
-            "--where",
-            "not valid jq",
+            "pool",
+            "create",
+            "--size",
+            "1M",
+            "--index-capacity",
+            "0",
+            "scanonly",
         ])
         .output()
-        .expect("follow");
-    assert_eq!(follower.status.code().unwrap(), 2);
-    let err = parse_error_json(&follower.stderr);
-    let inner = err
+        .expect("create");
+    assert!(create_scan_only.status.success());
+
+    let info_scan_only = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "info",
+            "scanonly",
+            "--json",
+        ])
+        .output()
+        .expect("info");
+    assert!(info_scan_only.status.success());
+    let json_scan_only = parse_json(std::str::from_utf8(&info_scan_only.stdout).expect("utf8"));
+    assert_eq!(json_scan_only["index_capacity"], json!(0));
+    assert_eq!(json_scan_only["index_size_bytes"], json!(0));
+    assert_eq!(json_scan_only["ring_offset"], json!(4096));
+}
+
+#[test]
+fn pool_create_rejects_oversized_index_capacity() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "--size",
+            "64K",
+            "--index-capacity",
+            "5000",
+            "too-big",
+        ])
+        .output()
+        .expect("create");
+    assert!(!create.status.success());
+
+    let err = parse_error_json(&create.stderr);
+    assert_eq!(
+        err.get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Usage")
+    );
+    let message = err
         .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(message.contains("index capacity"));
 }
 
 #[test]
-fn follow_where_non_boolean_expression_is_usage_error() {
+fn pool_info_default_is_human_readable() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2119,7 +3058,7 @@ fn follow_where_non_boolean_expression_is_usage_error() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "demo",
+            "pretty",
         ])
         .output()
         .expect("create");
@@ -2130,38 +3069,35 @@ fn follow_where_non_boolean_expression_is_usage_error() {
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
-            "demo",
+            "pretty",
             "{\"x\":1}",
         ])
         .output()
         .expect("feed");
     assert!(emit_out.status.success());
 
-    let follower = cmd()
+    let info = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--jsonl",
-            "--where",
-            ".data",
+            "pool",
+            "info",
+            "pretty",
         ])
         .output()
-        .expect("follow");
-    assert_eq!(follower.status.code().unwrap(), 2);
-    let err = parse_error_json(&follower.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
+        .expect("info");
+    assert!(info.status.success());
+    let stdout = std::str::from_utf8(&info.stdout).expect("utf8");
+    assert!(stdout.contains("Pool: pretty"));
+    assert!(stdout.contains("Path: "));
+    assert!(stdout.contains("Bounds: "));
+    assert!(stdout.contains("Utilization: "));
+    assert!(stdout.contains("Oldest: "));
+    assert!(stdout.contains("Newest: "));
 }
 
 #[test]
-fn follow_where_with_since_emits_matches() {
+fn pool_info_tty_is_compact_and_hides_ring_offset() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2171,62 +3107,224 @@ fn follow_where_with_since_emits_matches() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "demo",
+            "pretty",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let emit_out = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
+    let info = cmd_tty(&[
+        "--color",
+        "never",
+        "--dir",
+        pool_dir.to_str().unwrap(),
+        "pool",
+        "info",
+        "pretty",
+    ]);
+    assert!(info.status.success());
+    let stdout = sanitize_tty_text(&info.stdout);
+    assert!(stdout.contains("pretty"));
+    assert!(stdout.contains("path:      pretty.plasmite"));
+    assert!(stdout.contains("messages:  empty"));
+    assert!(stdout.contains("oldest:    —"));
+    assert!(stdout.contains("newest:    —"));
+    assert!(stdout.contains("index:     4096 slots (64K)"));
+    assert!(stdout.contains("ring:      956K"));
+    assert!(!stdout.contains("offset"));
+}
+
+#[test]
+fn output_flag_overrides_tty_detection() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "forced"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let machine = cmd_tty(&[
+        "--color", "never", "--dir", dir, "--output", "jsonl", "pool", "info", "forced",
+    ]);
+    assert!(machine.status.success());
+    let stdout = sanitize_tty_text(&machine.stdout);
+    let lines = stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1, "expected one compact JSON line: {stdout}");
+    let info = parse_json(lines[0].trim());
+    assert_eq!(info["name"], "forced");
+
+    let human = cmd()
+        .args(["--dir", dir, "pool", "info", "forced", "--output", "pretty"])
+        .output()
+        .expect("info");
+    assert!(human.status.success());
+    let stdout = std::str::from_utf8(&human.stdout).expect("utf8");
+    assert!(stdout.contains("path:      forced.plasmite"));
+
+    let listed = cmd()
+        .args(["--dir", dir, "--output", "json", "pool", "list"])
+        .output()
+        .expect("list");
+    assert!(listed.status.success());
+    let value = parse_json(std::str::from_utf8(&listed.stdout).expect("utf8"));
+    assert_eq!(value["pools"][0]["name"], "forced");
+}
+
+#[test]
+fn lang_selects_catalog_for_human_text_only() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("pool dir");
+    let dir = pool_dir.to_str().unwrap();
+
+    let spanish = cmd()
+        .env("LC_ALL", "es_ES.UTF-8")
+        .args(["--dir", dir, "--output", "pretty", "pool", "list"])
+        .output()
+        .expect("list");
+    assert!(spanish.status.success());
+    let stdout = std::str::from_utf8(&spanish.stdout).expect("utf8");
+    assert!(stdout.contains("No se encontraron pools en"), "{stdout}");
+
+    let english = cmd()
+        .env("LC_ALL", "es_ES.UTF-8")
+        .args([
+            "--dir", dir, "--lang", "en", "--output", "pretty", "pool", "list",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("list");
+    let stdout = std::str::from_utf8(&english.stdout).expect("utf8");
+    assert!(stdout.contains("No pools found in"), "{stdout}");
 
-    let emit_out = cmd()
+    let machine = cmd()
         .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":5}",
+            "--dir", dir, "--lang", "es", "--output", "json", "pool", "list",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .expect("list");
+    assert!(machine.status.success());
+    let value = parse_json(std::str::from_utf8(&machine.stdout).expect("utf8"));
+    assert_eq!(value["pools"], json!([]));
+}
 
-    let mut follower = cmd()
+#[test]
+fn help_json_describes_command_tree() {
+    let output = cmd().arg("--help-json").output().expect("help-json");
+    assert!(output.status.success());
+    let schema = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(schema["path"], json!(["plasmite"]));
+    let subcommands = schema["command"]["subcommands"]
+        .as_array()
+        .expect("subcommands");
+    assert!(subcommands.iter().any(|sub| sub["name"] == "feed"));
+    let globals = schema["command"]["args"].as_array().expect("args");
+    let output_arg = globals
+        .iter()
+        .find(|arg| arg["long"] == "--output")
+        .expect("--output arg");
+    assert_eq!(output_arg["type"], "enum");
+    assert_eq!(output_arg["default"], json!(["auto"]));
+
+    let scoped = cmd()
+        .args(["pool", "create", "--help-json"])
+        .output()
+        .expect("help-json");
+    assert!(scoped.status.success());
+    let schema = parse_json(std::str::from_utf8(&scoped.stdout).expect("utf8"));
+    assert_eq!(schema["path"], json!(["plasmite", "pool", "create"]));
+    assert_eq!(schema["command"]["name"], "create");
+}
+
+#[test]
+fn color_env_and_theme_control_json_styling() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "themed"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let info = |envs: &[(&str, &str)], extra: &[&str]| {
+        let mut command = cmd();
+        command
+            .env_remove("NO_COLOR")
+            .env_remove("CLICOLOR_FORCE")
+            .env_remove("PLASMITE_THEME")
+            .args(["--dir", dir])
+            .args(extra)
+            .args(["pool", "info", "themed", "--json"]);
+        for (key, value) in envs {
+            command.env(key, value);
+        }
+        let output = command.output().expect("info");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).expect("utf8")
+    };
+
+    let forced = info(&[("CLICOLOR_FORCE", "1")], &[]);
+    assert!(forced.contains("\u{1b}[36m\"name\"\u{1b}[0m"));
+
+    let suppressed = info(&[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")], &[]);
+    assert!(!suppressed.contains('\u{1b}'));
+
+    let light = info(&[("CLICOLOR_FORCE", "1")], &["--theme", "light"]);
+    assert!(light.contains("\u{1b}[34m\"name\"\u{1b}[0m"));
+
+    let mono_env = info(&[("PLASMITE_THEME", "mono")], &["--color", "always"]);
+    assert!(mono_env.contains("\u{1b}[1m\"name\"\u{1b}[0m"));
+    assert!(mono_env.contains(": \"themed\""));
+}
+
+#[test]
+fn pool_info_missing_does_not_emit_path_or_causes() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--since",
-            "1h",
-            "--jsonl",
-            "--where",
-            ".data.x == 5",
+            "pool",
+            "info",
+            "channel",
         ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 5);
-    let _ = follower.kill();
-    let _ = follower.wait();
+        .output()
+        .expect("pool info");
+    assert_eq!(output.status.code(), Some(3));
+    let err = parse_error_json(&output.stderr);
+    let error = err
+        .get("error")
+        .and_then(|value| value.as_object())
+        .expect("error");
+    assert_eq!(
+        error.get("kind").and_then(|value| value.as_str()),
+        Some("NotFound")
+    );
+    assert_eq!(
+        error.get("message").and_then(|value| value.as_str()),
+        Some("not found")
+    );
+    assert!(
+        error.get("path").is_none(),
+        "path should not be emitted for missing pool name"
+    );
+    assert!(
+        error.get("causes").is_none(),
+        "causes should not be emitted for missing pool name"
+    );
 }
 
 #[test]
-fn follow_where_with_format_pretty_emits_matches() {
+fn readme_quickstart_flow() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2237,22 +3335,42 @@ fn follow_where_with_format_pretty_emits_matches() {
             "pool",
             "create",
             "demo",
+            "--size",
+            "128K",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let emit_out = cmd()
+    let feed_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
             "demo",
             "{\"x\":1}",
+            "--tag",
+            "ping",
         ])
         .output()
         .expect("feed");
-    assert!(emit_out.status.success());
+    assert!(feed_out.status.success());
+    let feed_json = parse_json(std::str::from_utf8(&feed_out.stdout).expect("utf8"));
+    let seq = feed_json.get("seq").unwrap().as_u64().unwrap();
+
+    let get = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "fetch",
+            "demo",
+            &seq.to_string(),
+        ])
+        .output()
+        .expect("fetch");
+    assert!(get.status.success());
+    let get_json = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
+    assert_eq!(get_json.get("seq").unwrap().as_u64().unwrap(), seq);
 
     let mut follower = cmd()
         .args([
@@ -2262,24 +3380,22 @@ fn follow_where_with_format_pretty_emits_matches() {
             "demo",
             "--tail",
             "1",
-            "--format",
-            "pretty",
-            "--where",
-            ".data.x == 1",
+            "--jsonl",
         ])
         .stdout(Stdio::piped())
         .spawn()
         .expect("follow");
     let stdout = follower.stdout.take().expect("stdout");
-    let reader = BufReader::new(stdout);
-    let value = read_json_value(reader);
-    assert_eq!(value.get("data").unwrap()["x"], 1);
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let follower_json = parse_json(line.trim());
+    assert_eq!(follower_json.get("seq").unwrap().as_u64().unwrap(), seq);
     let _ = follower.kill();
     let _ = follower.wait();
 }
 
 #[test]
-fn follow_where_with_quiet_drops_suppresses_notice() {
+fn follow_emits_new_messages() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2295,18 +3411,6 @@ fn follow_where_with_quiet_drops_suppresses_notice() {
         .expect("create");
     assert!(create.status.success());
 
-    let emit_out = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
-        ])
-        .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
-
     let mut follower = cmd()
         .args([
             "--dir",
@@ -2316,29 +3420,45 @@ fn follow_where_with_quiet_drops_suppresses_notice() {
             "--tail",
             "1",
             "--jsonl",
-            "--where",
-            ".data.x == 1",
-            "--quiet-drops",
+            "--one",
         ])
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
         .spawn()
         .expect("follow");
+
     let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    let (line_tx, line_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        let _ = reader.read_line(&mut line);
+        let _ = line_tx.send(line);
+    });
+
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":42}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+
+    let line = line_rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("follow output");
     assert!(!line.is_empty(), "expected a line from follow output");
     let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 1);
-    let _ = follower.kill();
-    let output = follower.wait_with_output().expect("wait");
-    assert!(
-        output.stderr.is_empty(),
-        "expected no drop notices on stderr"
-    );
+    assert_eq!(value.get("data").unwrap()["x"], 42);
+    let status = follower.wait().expect("follow wait");
+    assert!(status.success(), "follow status={status:?}");
 }
 
 #[test]
-fn follow_where_multiple_predicates_with_since() {
+fn follow_one_exits_after_first_match() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2354,19 +3474,22 @@ fn follow_where_multiple_predicates_with_since() {
         .expect("create");
     assert!(create.status.success());
 
-    let emit_out = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
+            "follow",
             "demo",
-            "{\"x\":1}",
-            "--tag",
-            "alpha",
+            "--jsonl",
+            "--tail",
+            "1",
+            "--one",
         ])
-        .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow");
+
+    thread::sleep(Duration::from_millis(50));
 
     let emit_out = cmd()
         .args([
@@ -2374,42 +3497,54 @@ fn follow_where_multiple_predicates_with_since() {
             pool_dir.to_str().unwrap(),
             "feed",
             "demo",
-            "{\"x\":2}",
-            "--tag",
-            "alpha",
+            "{\"x\":1}",
         ])
         .output()
         .expect("feed");
     assert!(emit_out.status.success());
 
-    let mut follower = cmd()
+    let stdout = follower.stdout.take().expect("stdout");
+    let (line_tx, line_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        let _ = reader.read_line(&mut line);
+        let _ = line_tx.send(line);
+    });
+
+    let line = line_rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["x"], 1);
+
+    // Send a second message after the first is observed so `--one` ordering
+    // is deterministic without relying on fixed startup sleeps.
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
+            "feed",
             "demo",
-            "--since",
-            "1h",
-            "--jsonl",
-            "--where",
-            r#".meta.tags[]? == "alpha""#,
-            "--where",
-            ".data.x == 2",
+            "{\"x\":2}",
         ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").unwrap()["x"], 2);
-    let _ = follower.kill();
-    let _ = follower.wait();
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+
+    let (exit_tx, exit_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let status = follower.wait().expect("wait");
+        let _ = exit_tx.send(status);
+    });
+    let status = exit_rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("follow exit");
+    assert!(status.success());
 }
 
 #[test]
-fn not_found_exit_code() {
+fn follow_tail_one_emits_nth_match() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2419,71 +3554,81 @@ fn not_found_exit_code() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "testpool",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let get = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "fetch",
-            "testpool",
-            "999",
+            "follow",
+            "demo",
+            "--tail",
+            "2",
+            "--jsonl",
+            "--one",
         ])
-        .output()
-        .expect("fetch");
-    assert_eq!(get.status.code().unwrap(), 3);
-    let err = parse_error_json(&get.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(
-        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
-        "NotFound"
-    );
-    assert_eq!(inner.get("seq").and_then(|v| v.as_u64()).unwrap(), 999);
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("pool info") || hint.contains("follow"));
-}
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow");
 
-#[test]
-fn usage_exit_code() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    thread::sleep(Duration::from_millis(50));
 
-    let create = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "testpool",
+            "feed",
+            "demo",
+            "{\"x\":1}",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("feed");
+    assert!(emit_out.status.success());
 
     let emit_out = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "testpool"])
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":2}",
+        ])
         .output()
         .expect("feed");
-    assert_eq!(emit_out.status.code().unwrap(), 2);
-    let err = parse_error_json(&emit_out.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("--file") || hint.contains("pipe JSON"));
+    assert!(emit_out.status.success());
+
+    let stdout = follower.stdout.take().expect("stdout");
+    let (line_tx, line_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        let _ = reader.read_line(&mut line);
+        let _ = line_tx.send(line);
+    });
+
+    let line = line_rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["x"], 2);
+
+    let (exit_tx, exit_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let status = follower.wait().expect("wait");
+        let _ = exit_tx.send(status);
+    });
+    let status = exit_rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("follow exit");
+    assert!(status.success());
 }
 
 #[test]
-fn emit_emits_json_by_default() {
+fn follow_timeout_exits_when_no_output() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2493,240 +3638,402 @@ fn emit_emits_json_by_default() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "testpool",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let emit_out = cmd()
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "testpool",
-            "{\"x\":1}",
+            "follow",
+            "demo",
+            "--jsonl",
+            "--timeout",
+            "500ms",
         ])
         .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
-    let value = parse_json(std::str::from_utf8(&emit_out.stdout).expect("utf8"));
-    assert!(value.get("seq").is_some());
-    assert!(value.get("time").is_some());
+        .expect("follow");
+    assert_eq!(output.status.code().unwrap(), 124);
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty());
 }
 
 #[test]
-fn emit_short_file_flag_reads_single_json_file() {
+fn follow_stats_interval_reports_emitted_and_lag() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
     let create = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
-        ])
+        .args(["--dir", dir, "pool", "create", "demo"])
         .output()
         .expect("create");
     assert!(create.status.success());
+    for n in 1..=3 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "demo", &format!("{{\"n\":{n}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let input_file = temp.path().join("one.json");
-    std::fs::write(&input_file, b"{\"x\":1}\n").expect("write input");
-
-    let emit_out = cmd()
+    let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
+            dir,
+            "follow",
             "demo",
-            "-f",
-            input_file.to_str().unwrap(),
+            "--tail",
+            "2",
+            "--jsonl",
+            "--stats-interval",
+            "100ms",
+            "--timeout",
+            "500ms",
         ])
         .output()
-        .expect("feed");
-    assert!(
-        emit_out.status.success(),
-        "{}",
-        String::from_utf8_lossy(&emit_out.stderr)
-    );
-    let receipts = parse_json_lines(&emit_out.stdout);
-    assert_eq!(receipts.len(), 1);
-
-    let get = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "1"])
+        .expect("follow");
+    assert_eq!(output.status.code(), Some(124));
+    assert_eq!(parse_json_lines(&output.stdout).len(), 2);
+    let notices = parse_json_lines(&output.stderr);
+    let stats = notices
+        .iter()
+        .rev()
+        .find(|value| value["notice"]["kind"] == json!("stats"))
+        .expect("stats notice");
+    let details = &stats["notice"]["details"];
+    assert_eq!(details["emitted"], json!(2));
+    assert_eq!(details["lag"], json!(0));
+    assert_eq!(details["dropped"], json!(0));
+    assert!(details["bytes"].as_u64().expect("bytes") > 0);
+
+    let zero = cmd()
+        .args(["--dir", dir, "follow", "demo", "--stats-interval", "0s"])
         .output()
-        .expect("fetch");
-    assert!(get.status.success());
-    let value = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
-    assert_eq!(value["data"]["x"], 1);
+        .expect("follow");
+    assert_eq!(zero.status.code(), Some(2));
 }
 
 #[test]
-fn emit_file_jsonl_ingests_multiple_records() {
+fn follow_poll_flags_shape_idle_waits_and_report_wakeups() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-
+    let dir = pool_dir.to_str().unwrap();
     let create = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
-        ])
+        .args(["--dir", dir, "pool", "create", "idle"])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let input_file = temp.path().join("events.jsonl");
-    std::fs::write(&input_file, b"{\"x\":1}\n{\"x\":2}\n").expect("write input");
+    let output = cmd()
+        .args([
+            "--dir",
+            dir,
+            "follow",
+            "idle",
+            "--jsonl",
+            "--no-notify",
+            "--poll-strategy",
+            "fixed",
+            "--poll-max",
+            "20ms",
+            "--stats-interval",
+            "100ms",
+            "--timeout",
+            "400ms",
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(output.status.code(), Some(124));
+    let notices = parse_json_lines(&output.stderr);
+    let stats = notices
+        .iter()
+        .rev()
+        .find(|value| value["notice"]["kind"] == json!("stats"))
+        .expect("stats notice");
+    let details = &stats["notice"]["details"];
+    assert_eq!(details["poll_ms"], json!(20));
+    assert_eq!(details["notified_wakeups"], json!(0));
+    let wakeups = details["wakeups"].as_u64().expect("wakeups");
+    assert!((3..=25).contains(&wakeups), "wakeups={wakeups}");
 
-    let emit_out = cmd()
+    let inverted = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "--file",
-            input_file.to_str().unwrap(),
+            dir,
+            "follow",
+            "idle",
+            "--poll-min",
+            "100ms",
+            "--poll-max",
+            "10ms",
         ])
         .output()
-        .expect("feed");
-    assert!(
-        emit_out.status.success(),
-        "{}",
-        String::from_utf8_lossy(&emit_out.stderr)
-    );
-    let receipts = parse_json_lines(&emit_out.stdout);
-    assert_eq!(receipts.len(), 2);
-    assert_eq!(receipts[0]["seq"], 1);
-    assert_eq!(receipts[1]["seq"], 2);
+        .expect("follow");
+    assert_eq!(inverted.status.code(), Some(2));
+}
 
-    let get_one = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "1"])
+#[test]
+fn strict_reads_pass_valid_pools_and_stay_local() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "checked", "--create", "{\"n\":1}"])
         .output()
-        .expect("fetch one");
-    assert!(get_one.status.success());
-    let first = parse_json(std::str::from_utf8(&get_one.stdout).expect("utf8"));
-    assert_eq!(first["data"]["x"], 1);
+        .expect("feed");
+    assert!(feed.status.success(), "{feed:?}");
 
-    let get_two = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "2"])
+    let fetch = cmd()
+        .args(["--dir", dir, "fetch", "checked", "1", "--strict"])
         .output()
-        .expect("fetch two");
-    assert!(get_two.status.success());
-    let second = parse_json(std::str::from_utf8(&get_two.stdout).expect("utf8"));
-    assert_eq!(second["data"]["x"], 2);
+        .expect("fetch");
+    assert!(fetch.status.success(), "{fetch:?}");
+    let message: Value = serde_json::from_slice(&fetch.stdout).expect("json");
+    assert_eq!(message["data"], json!({"n": 1}));
+
+    let follow = cmd()
+        .args([
+            "--dir", dir, "follow", "checked", "--tail", "1", "--one", "--jsonl", "--strict",
+        ])
+        .output()
+        .expect("follow");
+    assert!(follow.status.success(), "{follow:?}");
+    assert_eq!(parse_json_lines(&follow.stdout)[0]["data"], json!({"n": 1}));
+
+    let remote = cmd()
+        .args(["follow", "http://127.0.0.1:9/checked", "--strict"])
+        .output()
+        .expect("remote follow");
+    assert_eq!(remote.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&remote.stderr).contains("--strict"));
 }
 
 #[test]
-fn emit_file_auto_handles_multiline_json() {
+fn strict_follow_rejects_a_malformed_live_frame() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "checked", "--create", "{\"n\":1}"])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success(), "{feed:?}");
 
-    let create = cmd()
+    // Scribble over the start of the Lite3 payload; frame headers carry no read-time checksum.
+    let explain = cmd()
+        .args(["--dir", dir, "explain", "checked", "--seq", "1", "--json"])
+        .output()
+        .expect("explain");
+    assert!(explain.status.success(), "{explain:?}");
+    let report = parse_json(std::str::from_utf8(&explain.stdout).expect("utf8"));
+    let header_len = report["header_hex"]
+        .as_str()
+        .expect("hex")
+        .split(' ')
+        .count();
+    let payload_at = report["file_offset"].as_u64().expect("file_offset") as usize + header_len;
+    let path = pool_dir.join("checked.plasmite");
+    let mut bytes = std::fs::read(&path).expect("read pool");
+    bytes[payload_at..payload_at + 8].fill(0xff);
+    std::fs::write(&path, bytes).expect("write pool");
+
+    // No `--one`, so the follower takes the batched JSONL path that `--strict` must bypass.
+    let follow = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
+            dir,
+            "follow",
+            "checked",
+            "--tail",
+            "1",
+            "--jsonl",
+            "--strict",
+            "--timeout",
+            "2s",
         ])
         .output()
+        .expect("follow");
+    assert_eq!(follow.status.code(), Some(7), "{follow:?}");
+    let err = parse_error_json(&follow.stderr);
+    assert_eq!(err["error"]["kind"], "Corrupt");
+    assert!(
+        err["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("strict validation"),
+        "{err}"
+    );
+    assert!(
+        follow.stdout.is_empty(),
+        "nothing is emitted before the check"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn ctl_pauses_and_refilters_a_running_follower() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let socket = temp.path().join("f.sock");
+    let socket_arg = socket.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "demo"])
+        .output()
         .expect("create");
     assert!(create.status.success());
+    let feed = |level: &str, n: u64| {
+        let data = json!({ "level": level, "n": n }).to_string();
+        let output = cmd()
+            .args(["--dir", dir, "feed", "demo", &data])
+            .output()
+            .expect("feed");
+        assert!(output.status.success());
+    };
+    let ctl = |args: &[&str]| {
+        cmd()
+            .args(["ctl", socket_arg])
+            .args(args)
+            .output()
+            .expect("ctl")
+    };
 
-    let input_file = temp.path().join("pretty.json");
-    std::fs::write(&input_file, b"{\n  \"x\": 1,\n  \"y\": 2\n}\n").expect("write input");
-
-    let emit_out = cmd()
+    let mut follow = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
+            dir,
+            "follow",
             "demo",
-            "--file",
-            input_file.to_str().unwrap(),
+            "--jsonl",
+            "--where",
+            ".data.level == \"info\"",
+            "--control-socket",
+            socket_arg,
         ])
-        .output()
-        .expect("feed");
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("follow");
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !socket.exists() {
+        assert!(Instant::now() < deadline, "control socket never appeared");
+        sleep(Duration::from_millis(20));
+    }
+    let (tx, rx) = mpsc::channel();
+    let stdout = follow.stdout.take().expect("stdout");
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if tx.send(parse_json(&line)).is_err() {
+                break;
+            }
+        }
+    });
+    let next = || {
+        rx.recv_timeout(Duration::from_secs(5))
+            .expect("follow line")
+    };
+
+    feed("info", 1);
+    assert_eq!(next()["data"]["n"], json!(1));
+
+    let swap = ctl(&["where", ".data.level == \"error\""]);
     assert!(
-        emit_out.status.success(),
-        "{}",
-        String::from_utf8_lossy(&emit_out.stderr)
+        swap.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&swap.stderr)
     );
-    let receipts = parse_json_lines(&emit_out.stdout);
-    assert_eq!(receipts.len(), 1);
-    assert_eq!(receipts[0]["seq"], 1);
+    feed("info", 2);
+    feed("error", 3);
+    assert_eq!(next()["data"]["n"], json!(3));
 
-    let get = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "1"])
-        .output()
-        .expect("fetch");
-    assert!(get.status.success());
-    let value = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
-    assert_eq!(value["data"]["x"], 1);
-    assert_eq!(value["data"]["y"], 2);
+    assert!(ctl(&["pause"]).status.success());
+    feed("error", 4);
+    sleep(Duration::from_millis(300));
+    let stats = ctl(&["stats", "--json"]);
+    assert!(stats.status.success());
+    let stats = parse_json(std::str::from_utf8(&stats.stdout).expect("utf8"));
+    assert_eq!(stats["paused"], json!(true));
+    assert_eq!(stats["emitted"], json!(2));
+    assert_eq!(stats["last_seq"], json!(3));
+    assert_eq!(stats["where"], json!([".data.level == \"error\""]));
+    assert!(rx.try_recv().is_err(), "paused follower emitted");
+
+    assert!(ctl(&["resume"]).status.success());
+    assert_eq!(next()["data"]["n"], json!(4));
+
+    let bad = ctl(&["where", "("]);
+    assert_eq!(bad.status.code(), Some(2));
+
+    let _ = follow.kill();
+    let _ = follow.wait();
+    let gone = ctl(&["stats"]);
+    assert!(!gone.status.success());
 }
 
 #[test]
-fn emit_retries_when_pool_is_busy() {
+fn follow_exits_cleanly_when_stdout_reader_closes() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
     let create = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "busy",
-        ])
+        .args(["--dir", dir, "pool", "create", "demo"])
         .output()
         .expect("create");
     assert!(create.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "demo", "{\"n\":1}"])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
 
-    let pool_path = pool_dir.join("busy.plasmite");
-    let file = File::open(&pool_path).expect("open pool");
-    file.try_lock_exclusive().expect("try lock");
-
-    let (tx, rx) = mpsc::channel();
-    let pool_dir_str = pool_dir.to_str().unwrap().to_string();
-    thread::spawn(move || {
-        let output = cmd()
-            .args([
-                "--dir",
-                &pool_dir_str,
-                "feed",
-                "busy",
-                "{\"x\":1}",
-                "--retry",
-                "5",
-                "--retry-delay",
-                "50ms",
-            ])
-            .output()
-            .expect("feed");
-        let _ = tx.send(output);
-    });
+    let mut child = cmd()
+        .args(["--dir", dir, "follow", "demo", "--tail", "1", "--jsonl"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("follow");
+    // Reading one line and dropping the reader mimics `follow | head -1`.
+    let line = read_line_with_timeout(child.stdout.take().expect("stdout"), Duration::from_secs(5));
+    assert_eq!(parse_json(line.trim())["data"], json!({"n": 1}));
 
-    thread::sleep(Duration::from_millis(150));
-    fs2::FileExt::unlock(&file).expect("unlock");
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "demo", "{\"n\":2}"])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
 
-    let output = rx.recv_timeout(Duration::from_secs(2)).expect("output");
-    assert!(
-        output.status.success(),
-        "feed failed: {}",
-        String::from_utf8_lossy(&output.stderr)
-    );
-    let value = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
-    assert!(value.get("seq").is_some());
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let status = loop {
+        if let Some(status) = child.try_wait().expect("try_wait") {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            panic!("follow did not exit after stdout closed");
+        }
+        sleep(Duration::from_millis(20));
+    };
+    assert_eq!(status.code(), Some(0));
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .expect("stderr")
+        .read_to_string(&mut stderr)
+        .expect("read stderr");
+    let notice = parse_notice_json(stderr.lines().last().expect("notice"));
+    assert_eq!(notice["notice"]["kind"], json!("downstream_closed"));
 }
 
 #[test]
-fn color_always_colorizes_pretty_stdout() {
+fn follow_timeout_on_tty_prints_message() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2742,26 +4049,24 @@ fn color_always_colorizes_pretty_stdout() {
         .expect("create");
     assert!(create.status.success());
 
-    let info = cmd()
-        .args([
-            "--color",
-            "always",
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "info",
-            "demo",
-            "--json",
-        ])
-        .output()
-        .expect("info");
-    assert!(info.status.success());
-    let stdout = String::from_utf8_lossy(&info.stdout);
-    assert!(stdout.contains("\u{1b}[36m\"name\"\u{1b}[0m"));
+    let output = cmd_tty(&[
+        "--color",
+        "never",
+        "--dir",
+        pool_dir.to_str().unwrap(),
+        "follow",
+        "demo",
+        "--one",
+        "--timeout",
+        "200ms",
+    ]);
+    assert_eq!(output.status.code(), Some(124));
+    let text = sanitize_tty_text(&output.stdout);
+    assert!(text.contains("No messages received (timed out after 200ms)."));
 }
 
 #[test]
-fn color_never_does_not_emit_ansi() {
+fn follow_timeout_with_one_exits_on_message() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2777,26 +4082,35 @@ fn color_never_does_not_emit_ansi() {
         .expect("create");
     assert!(create.status.success());
 
-    let info = cmd()
+    let pool_dir_str = pool_dir.to_str().unwrap().to_string();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        let _ = cmd()
+            .args(["--dir", &pool_dir_str, "feed", "demo", "{\"x\":1}"])
+            .output();
+    });
+
+    let output = cmd()
         .args([
-            "--color",
-            "never",
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "info",
+            "follow",
             "demo",
-            "--json",
+            "--jsonl",
+            "--one",
+            "--timeout",
+            "5s",
         ])
         .output()
-        .expect("info");
-    assert!(info.status.success());
-    let stdout = String::from_utf8_lossy(&info.stdout);
-    assert!(!stdout.contains("\u{1b}["));
+        .expect("follow");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].get("data").unwrap()["x"], 1);
 }
 
 #[test]
-fn color_always_does_not_color_jsonl() {
+fn follow_data_only_jsonl_emits_payload() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2824,34 +4138,29 @@ fn color_always_does_not_color_jsonl() {
         .expect("feed");
     assert!(emit_out.status.success());
 
-    let mut follower = cmd()
+    let output = cmd()
         .args([
-            "--color",
-            "always",
             "--dir",
             pool_dir.to_str().unwrap(),
             "follow",
             "demo",
             "--tail",
             "1",
-            "--format",
-            "jsonl",
+            "--jsonl",
+            "--data-only",
+            "--one",
         ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow jsonl");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let line = line.trim_end();
-    assert!(!line.contains("\u{1b}["));
-    let _ = parse_json(line);
-    let _ = follower.kill();
-    let _ = follower.wait();
+        .output()
+        .expect("follow");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].get("x").unwrap().as_i64().unwrap(), 1);
+    assert!(lines[0].get("data").is_none());
 }
 
 #[test]
-fn emit_auto_handles_pretty_json() {
+fn follow_data_only_where_filters_envelope() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2867,102 +4176,59 @@ fn emit_auto_handles_pretty_json() {
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo"])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":1}",
+            "--tag",
+            "drop",
+        ])
+        .output()
         .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin
-            .write_all(b"{\n  \"x\": 1,\n  \"y\": 2\n}\n")
-            .expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 1);
-    assert!(lines[0].get("seq").is_some());
-    assert!(lines[0].get("data").is_none());
-}
-
-#[test]
-fn emit_auto_handles_event_stream() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    assert!(emit_out.status.success());
 
-    let create = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
+            "feed",
             "demo",
+            "{\"x\":2}",
+            "--tag",
+            "keep",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-
-    let mut emit_out = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo"])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
         .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin
-            .write_all(b"data: {\"x\":1}\n\ndata: {\"x\":2}\n\n")
-            .expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 2);
-    assert!(lines[1].get("seq").is_some());
-    assert!(lines[1].get("data").is_none());
-}
-
-#[test]
-fn emit_auto_detects_json_seq() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    assert!(emit_out.status.success());
 
-    let create = cmd()
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
+            "follow",
             "demo",
+            "--tail",
+            "1",
+            "--jsonl",
+            "--data-only",
+            "--one",
+            "--where",
+            r#".meta.tags[]? == "keep""#,
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-
-    let mut emit_out = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo"])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin
-            .write_all(b"\x1e{\"x\":1}\x1e{\"x\":2}")
-            .expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
+        .expect("follow");
     assert!(output.status.success());
     let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 2);
-    assert!(lines[0].get("seq").is_some());
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].get("x").unwrap().as_i64().unwrap(), 2);
     assert!(lines[0].get("data").is_none());
 }
 
 #[test]
-fn emit_auto_skip_reports_oversize() {
+fn follow_data_only_pretty_emits_payload() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -2978,82 +4244,41 @@ fn emit_auto_skip_reports_oversize() {
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
             "demo",
-            "-e",
-            "skip",
+            "{\"x\":3}",
         ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
+        .output()
         .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        let big = "x".repeat(1024 * 1024 + 1);
-        let line = format!("{{\"big\":\"{big}\"}}\n");
-        stdin.write_all(line.as_bytes()).expect("write stdin");
-        stdin.write_all(b"{\"ok\":1}\n").expect("write ok");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert_eq!(output.status.code().unwrap(), 1);
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 1);
-    let notices = parse_json_lines(&output.stderr);
-    let oversize = notices.iter().find(|value| {
-        value
-            .get("notice")
-            .and_then(|v| v.get("details"))
-            .and_then(|v| v.get("error_kind"))
-            .and_then(|v| v.as_str())
-            == Some("Oversize")
-    });
-    assert!(oversize.is_some());
-}
-
-#[test]
-fn feed_file_tty_emits_human_receipts() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
-    let input_file = temp.path().join("events.jsonl");
-    std::fs::write(&input_file, "{\"x\":1}\n{\"x\":2}\n").expect("write input");
+    assert!(emit_out.status.success());
 
-    let create = cmd()
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
+            "follow",
             "demo",
+            "--tail",
+            "1",
+            "--format",
+            "pretty",
+            "--data-only",
+            "--one",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-
-    let output = cmd_tty(&[
-        "--color",
-        "never",
-        "--dir",
-        pool_dir.to_str().unwrap(),
-        "feed",
-        "demo",
-        "--file",
-        input_file.to_str().unwrap(),
-        "--in",
-        "jsonl",
-    ]);
+        .expect("follow");
     assert!(output.status.success());
-    let text = sanitize_tty_text(&output.stdout);
-    assert_eq!(text.matches("fed seq=").count(), 2);
-    assert!(!text.contains("\"seq\":"));
+    let value = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(value.get("x").unwrap().as_i64().unwrap(), 3);
+    assert!(value.get("data").is_none());
 }
 
 #[test]
-fn emit_seq_mode_parses_rs_records() {
+fn follow_where_filters_messages() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3069,35 +4294,60 @@ fn emit_seq_mode_parses_rs_records() {
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
             "demo",
-            "-i",
-            "seq",
+            "{\"x\":1}",
+            "--tag",
+            "drop",
+        ])
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":2}",
+            "--tag",
+            "keep",
+        ])
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+
+    let mut follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--tail",
+            "10",
+            "--jsonl",
+            "--where",
+            r#".meta.tags[]? == "keep""#,
         ])
-        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .spawn()
-        .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin
-            .write_all(b"\x1e{\"x\":1}\x1e{\"x\":2}")
-            .expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 2);
-    assert!(lines[0].get("seq").is_some());
-    assert!(lines[0].get("data").is_none());
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["x"], 2);
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn emit_errors_skip_emits_notices_and_nonzero() {
+fn follow_tag_filters_messages() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3113,112 +4363,60 @@ fn emit_errors_skip_emits_notices_and_nonzero() {
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
             "demo",
-            "-i",
-            "jsonl",
-            "-e",
-            "skip",
+            "{\"x\":1}",
+            "--tag",
+            "drop",
         ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
+        .output()
         .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin
-            .write_all(b"{\"x\":1}\nnot-json\n{\"x\":2}\n")
-            .expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert_eq!(output.status.code().unwrap(), 1);
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 2);
-
-    let notices = parse_json_lines(&output.stderr);
-    assert!(notices.len() >= 2);
-    let first = notices[0]
-        .get("notice")
-        .and_then(|v| v.as_object())
-        .expect("notice");
-    assert_eq!(
-        first.get("kind").and_then(|v| v.as_str()),
-        Some("ingest_skip")
-    );
-    let summary = notices
-        .iter()
-        .find(|value| {
-            value
-                .get("notice")
-                .and_then(|v| v.get("kind"))
-                .and_then(|v| v.as_str())
-                == Some("ingest_summary")
-        })
-        .expect("summary");
-    assert!(summary.get("notice").is_some());
-}
-
-#[test]
-fn emit_errors_skip_reports_oversize() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    assert!(emit_out.status.success());
 
-    let create = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
+            "feed",
             "demo",
+            "{\"x\":2}",
+            "--tag",
+            "keep",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("feed");
+    assert!(emit_out.status.success());
 
-    let mut emit_out = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
+            "follow",
             "demo",
-            "-i",
-            "jsonl",
-            "-e",
-            "skip",
+            "--tail",
+            "10",
+            "--jsonl",
+            "--tag",
+            "keep",
         ])
-        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
         .spawn()
-        .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        let big = "x".repeat(1024 * 1024 + 1);
-        let line = format!("{{\"big\":\"{big}\"}}\n");
-        stdin.write_all(line.as_bytes()).expect("write stdin");
-        stdin.write_all(b"{\"ok\":1}\n").expect("write ok");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert_eq!(output.status.code().unwrap(), 1);
-    let notices = parse_json_lines(&output.stderr);
-    let oversize = notices.iter().find(|value| {
-        value
-            .get("notice")
-            .and_then(|v| v.get("details"))
-            .and_then(|v| v.get("error_kind"))
-            .and_then(|v| v.as_str())
-            == Some("Oversize")
-    });
-    assert!(oversize.is_some());
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["x"], 2);
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn emit_in_json_accepts_pretty_json() {
+fn follow_tag_and_where_compose_with_and() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3234,35 +4432,58 @@ fn emit_in_json_accepts_pretty_json() {
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
+    let events = [
+        (r#"{"service":"billing","level":"warn"}"#, "keep"),
+        (r#"{"service":"billing","level":"error"}"#, "keep"),
+        (r#"{"service":"payments","level":"error"}"#, "keep"),
+    ];
+    for (payload, tag) in events {
+        let emit_out = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "demo",
+                payload,
+                "--tag",
+                tag,
+            ])
+            .output()
+            .expect("feed");
+        assert!(emit_out.status.success());
+    }
+
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
+            "follow",
             "demo",
-            "-i",
-            "json",
+            "--tail",
+            "10",
+            "--jsonl",
+            "--tag",
+            "keep",
+            "--where",
+            r#".data.level == "error""#,
+            "--where",
+            r#".data.service == "billing""#,
         ])
-        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .spawn()
-        .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin
-            .write_all(b"{\n  \"x\": 1,\n  \"y\": 2\n}\n")
-            .expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 1);
-    assert!(lines[0].get("seq").is_some());
-    assert!(lines[0].get("data").is_none());
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value["data"]["service"], "billing");
+    assert_eq!(value["data"]["level"], "error");
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn emit_in_json_errors_skip_returns_nonzero() {
+fn follow_where_multiple_predicates_and() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3278,81 +4499,62 @@ fn emit_in_json_errors_skip_returns_nonzero() {
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
             "demo",
-            "-i",
-            "json",
-            "-e",
-            "skip",
+            "{\"level\":1}",
+            "--tag",
+            "alpha",
         ])
-        .stdin(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
+        .output()
         .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin.write_all(b"{\"x\":1").expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert_eq!(output.status.code().unwrap(), 1);
-    let notices = parse_json_lines(&output.stderr);
-    assert!(notices.iter().any(|value| {
-        value
-            .get("notice")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str())
-            == Some("ingest_skip")
-    }));
-}
-
-#[test]
-fn emit_event_stream_flushes_trailing_event() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    assert!(emit_out.status.success());
 
-    let create = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
+            "feed",
             "demo",
+            "{\"level\":2}",
+            "--tag",
+            "alpha",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("feed");
+    assert!(emit_out.status.success());
 
-    let mut emit_out = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
+            "follow",
             "demo",
-            "-i",
-            "auto",
+            "--tail",
+            "10",
+            "--jsonl",
+            "--where",
+            r#".meta.tags[]? == "alpha""#,
+            "--where",
+            ".data.level >= 2",
         ])
-        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .spawn()
-        .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin.write_all(b"data: {\"x\":1}\n").expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 1);
-    assert!(lines[0].get("seq").is_some());
-    assert!(lines[0].get("data").is_none());
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["level"], 2);
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn emit_jq_mode_rejects_skip() {
+fn follow_where_invalid_expression_is_usage_error() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3368,37 +4570,43 @@ fn emit_jq_mode_rejects_skip() {
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
             "demo",
-            "-i",
-            "jq",
-            "-e",
-            "skip",
+            "{\"x\":1}",
         ])
-        .stdin(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
+        .output()
         .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin.write_all(b"{\"x\":1}\n{\"x\":2}\n").expect("write");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert_eq!(output.status.code().unwrap(), 2);
-    let err = parse_error_json(&output.stderr);
+    assert!(emit_out.status.success());
+
+    let follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--tail",
+            "1",
+            "--jsonl",
+            "--where",
+            "not valid jq",
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(follower.status.code().unwrap(), 2);
+    let err = parse_error_json(&follower.stderr);
     let inner = err
         .get("error")
         .and_then(|v| v.as_object())
         .expect("error object");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
 }
 
 #[test]
-fn pool_list_lists_pools_sorted_by_name() {
+fn follow_where_non_boolean_expression_is_usage_error() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3408,40 +4616,51 @@ fn pool_list_lists_pools_sorted_by_name() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "beta",
-            "alpha",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let list = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "list",
-            "--json",
+            "feed",
+            "demo",
+            "{\"x\":1}",
         ])
         .output()
-        .expect("list");
-    assert!(list.status.success());
+        .expect("feed");
+    assert!(emit_out.status.success());
 
-    let value = parse_json(std::str::from_utf8(&list.stdout).expect("utf8"));
-    let pools = value
-        .get("pools")
-        .and_then(|v| v.as_array())
-        .expect("pools array");
-    assert_eq!(pools.len(), 2);
-    assert_eq!(pools[0].get("name").and_then(|v| v.as_str()), Some("alpha"));
-    assert_eq!(pools[1].get("name").and_then(|v| v.as_str()), Some("beta"));
+    let follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--tail",
+            "1",
+            "--jsonl",
+            "--where",
+            ".data",
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(follower.status.code().unwrap(), 2);
+    let err = parse_error_json(&follower.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
 }
 
 #[test]
-fn pool_list_defaults_to_table_output() {
+fn follow_where_with_since_emits_matches() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-    std::fs::create_dir_all(&pool_dir).expect("mkdir");
 
     let create = cmd()
         .args([
@@ -3449,87 +4668,161 @@ fn pool_list_defaults_to_table_output() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "beta",
-            "alpha",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    std::fs::write(pool_dir.join("bad.plasmite"), b"NOPE").expect("write bad");
-
-    let list = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "list"])
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":1}",
+        ])
         .output()
-        .expect("list");
-    assert!(list.status.success());
-
-    let stdout = std::str::from_utf8(&list.stdout).expect("utf8");
-    assert!(stdout.contains("NAME"));
-    assert!(stdout.contains("STATUS"));
-    assert!(stdout.contains("DETAIL"));
-    assert!(stdout.contains("alpha"));
-    assert!(stdout.contains("beta"));
-    assert!(stdout.contains("bad"));
-    assert!(stdout.contains("ERR"));
-}
-
-#[test]
-fn follow_since_future_exits_empty() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+        .expect("feed");
+    assert!(emit_out.status.success());
 
-    let create = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
+            "feed",
             "demo",
+            "{\"x\":5}",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
+        .expect("feed");
+    assert!(emit_out.status.success());
 
-    let follower = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "follow",
             "demo",
             "--since",
-            "2999-01-01T00:00:00Z",
+            "1h",
+            "--jsonl",
+            "--where",
+            ".data.x == 5",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["x"], 5);
+    let _ = follower.kill();
+    let _ = follower.wait();
+}
+
+#[test]
+fn follow_exec_runs_hook_per_match_with_json_stdin_and_placeholder() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let stdin_log = temp.path().join("stdin.jsonl");
+    let arg_log = temp.path().join("args.txt");
+
+    for data in ["{\"alert\":false,\"n\":1}", "{\"alert\":true,\"n\":2}"] {
+        let feed = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "alerts",
+                "--create",
+                data,
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let hook = format!(
+        "cat >> {} && printf '%s\\n' {{}} >> {}",
+        stdin_log.display(),
+        arg_log.display()
+    );
+    let output = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "alerts",
+            "--since",
+            "1h",
+            "--where",
+            ".data.alert == true",
+            "--one",
+            "--data-only",
+            "--exec",
+            &hook,
         ])
         .output()
         .expect("follow");
-    assert!(follower.status.success());
-    assert!(follower.stdout.is_empty());
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty());
+
+    let stdin_lines = std::fs::read_to_string(&stdin_log).expect("stdin log");
+    assert_eq!(
+        parse_json(stdin_lines.trim()),
+        json!({"alert": true, "n": 2})
+    );
+    let arg_lines = std::fs::read_to_string(&arg_log).expect("arg log");
+    assert_eq!(parse_json(arg_lines.trim()), json!({"alert": true, "n": 2}));
 }
 
 #[test]
-fn follow_since_future_missing_pool_reports_not_found() {
+fn follow_exec_errors_stop_fails_on_hook_exit() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let feed = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "jobs",
+            "--create",
+            "{\"n\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
 
-    let follower = cmd()
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "follow",
-            "missing",
+            "jobs",
             "--since",
-            "2999-01-01T00:00:00Z",
+            "1h",
+            "--one",
+            "--exec",
+            "exit 3",
+            "--exec-errors",
+            "stop",
         ])
         .output()
         .expect("follow");
-    assert_eq!(follower.status.code(), Some(3));
-    let err = parse_error_json(&follower.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    assert!(
+        err["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("--exec command failed")
+    );
 }
 
 #[test]
-fn follow_format_jsonl_matches_jsonl_alias() {
+fn follow_where_with_format_pretty_emits_matches() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3557,7 +4850,7 @@ fn follow_format_jsonl_matches_jsonl_alias() {
         .expect("feed");
     assert!(emit_out.status.success());
 
-    let mut fmt = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
@@ -3566,45 +4859,23 @@ fn follow_format_jsonl_matches_jsonl_alias() {
             "--tail",
             "1",
             "--format",
-            "jsonl",
-        ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow format");
-    let fmt_stdout = fmt.stdout.take().expect("stdout");
-    let fmt_line = read_line_with_timeout(fmt_stdout, Duration::from_secs(2));
-    assert!(!fmt_line.is_empty(), "expected a line from follow output");
-    let fmt_line = fmt_line.trim_end();
-    assert!(!fmt_line.contains('\n'));
-    let _ = parse_json(fmt_line);
-    let _ = fmt.kill();
-    let _ = fmt.wait();
-
-    let mut alias = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "1",
-            "--jsonl",
+            "pretty",
+            "--where",
+            ".data.x == 1",
         ])
         .stdout(Stdio::piped())
         .spawn()
-        .expect("follow jsonl");
-    let alias_stdout = alias.stdout.take().expect("stdout");
-    let alias_line = read_line_with_timeout(alias_stdout, Duration::from_secs(2));
-    assert!(!alias_line.is_empty(), "expected a line from follow output");
-    let alias_line = alias_line.trim_end();
-    assert!(!alias_line.contains('\n'));
-    let _ = parse_json(alias_line);
-    let _ = alias.kill();
-    let _ = alias.wait();
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let reader = BufReader::new(stdout);
+    let value = read_json_value(reader);
+    assert_eq!(value.get("data").unwrap()["x"], 1);
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn follow_emits_drop_notice_on_stderr() {
+fn follow_where_with_quiet_drops_suppresses_notice() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3614,106 +4885,56 @@ fn follow_emits_drop_notice_on_stderr() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "--size",
-            "1M",
             "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let mut follower = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
+            "feed",
+            "demo",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+
+    let mut follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
             "demo",
+            "--tail",
+            "1",
             "--jsonl",
+            "--where",
+            ".data.x == 1",
+            "--quiet-drops",
         ])
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .expect("follow");
-
     let stdout = follower.stdout.take().expect("stdout");
-    let stderr = follower.stderr.take().expect("stderr");
-
-    thread::spawn(move || {
-        let mut reader = BufReader::new(stdout);
-        let mut line = String::new();
-        loop {
-            line.clear();
-            if reader.read_line(&mut line).unwrap_or(0) == 0 {
-                break;
-            }
-            thread::sleep(Duration::from_millis(500));
-        }
-    });
-
-    let (tx, rx) = mpsc::channel();
-    thread::spawn(move || {
-        let mut reader = BufReader::new(stderr);
-        let mut line = String::new();
-        loop {
-            line.clear();
-            let read = reader.read_line(&mut line).unwrap_or(0);
-            if read == 0 {
-                break;
-            }
-            if !line.trim().is_empty() {
-                let _ = tx.send(line.clone());
-                break;
-            }
-        }
-    });
-
-    for i in 0..200u64 {
-        let payload = "a".repeat(8192);
-        let emit_out = cmd()
-            .args([
-                "--dir",
-                pool_dir.to_str().unwrap(),
-                "feed",
-                "demo",
-                &format!("{{\"x\":{i},\"pad\":\"{payload}\"}}"),
-            ])
-            .output()
-            .expect("feed");
-        if !emit_out.status.success() {
-            let stderr = String::from_utf8_lossy(&emit_out.stderr);
-            panic!("feed failed at {i}: {stderr}");
-        }
-    }
-
-    let notice_line = rx
-        .recv_timeout(Duration::from_secs(15))
-        .expect("drop notice");
-    let notice_json = parse_notice_json(&notice_line);
-    let notice = notice_json
-        .get("notice")
-        .and_then(|v| v.as_object())
-        .expect("notice object");
-    assert_eq!(notice.get("kind").and_then(|v| v.as_str()), Some("drop"));
-    assert_eq!(notice.get("cmd").and_then(|v| v.as_str()), Some("follow"));
-    assert_eq!(notice.get("pool").and_then(|v| v.as_str()), Some("demo"));
-    let details = notice
-        .get("details")
-        .and_then(|v| v.as_object())
-        .expect("details");
-    let dropped = details
-        .get("dropped_count")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0);
-    assert!(dropped > 0);
-    assert!(details.get("last_seen_seq").is_some());
-    assert!(details.get("next_seen_seq").is_some());
-
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["x"], 1);
     let _ = follower.kill();
-    let _ = follower.wait();
+    let output = follower.wait_with_output().expect("wait");
+    assert!(
+        output.stderr.is_empty(),
+        "expected no drop notices on stderr"
+    );
 }
 
 #[test]
-fn follow_rejects_conflicting_output_flags() {
+fn follow_where_multiple_predicates_with_since() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3729,78 +4950,62 @@ fn follow_rejects_conflicting_output_flags() {
         .expect("create");
     assert!(create.status.success());
 
-    let follower = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
+            "feed",
             "demo",
-            "--tail",
-            "1",
-            "--jsonl",
-            "--format",
-            "jsonl",
+            "{\"x\":1}",
+            "--tag",
+            "alpha",
         ])
         .output()
-        .expect("follow");
-    assert_eq!(follower.status.code().unwrap(), 2);
-    let err = parse_error_json(&follower.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("--format jsonl") || hint.contains("--jsonl"));
-}
-
-#[test]
-fn emit_create_flag_creates_missing_pool() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+        .expect("feed");
+    assert!(emit_out.status.success());
 
     let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
-            "autopool",
-            "{\"x\":1}",
-            "--create",
+            "demo",
+            "{\"x\":2}",
+            "--tag",
+            "alpha",
         ])
         .output()
         .expect("feed");
     assert!(emit_out.status.success());
-    let value = parse_json(std::str::from_utf8(&emit_out.stdout).expect("utf8"));
-    assert!(value.get("seq").is_some());
-
-    let pool_path = pool_dir.join("autopool.plasmite");
-    assert!(pool_path.exists());
-}
-
-#[test]
-fn follow_create_flag_creates_missing_pool() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
 
-    let follower = cmd()
+    let mut follower = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "follow",
-            "autopool",
-            "--create",
-            "--timeout",
-            "20ms",
+            "demo",
+            "--since",
+            "1h",
+            "--jsonl",
+            "--where",
+            r#".meta.tags[]? == "alpha""#,
+            "--where",
+            ".data.x == 2",
         ])
-        .output()
+        .stdout(Stdio::piped())
+        .spawn()
         .expect("follow");
-    assert_eq!(follower.status.code(), Some(124));
-    assert!(pool_dir.join("autopool.plasmite").exists());
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").unwrap()["x"], 2);
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn pool_delete_removes_pool_file() {
+fn not_found_exit_code() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3810,45 +5015,39 @@ fn pool_delete_removes_pool_file() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "deleteme",
+            "testpool",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let delete = cmd()
+    let get = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "delete",
-            "--json",
-            "deleteme",
+            "fetch",
+            "testpool",
+            "999",
         ])
         .output()
-        .expect("delete");
-    assert!(delete.status.success());
-    let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
-    let deleted = output
-        .get("deleted")
-        .and_then(|v| v.as_array())
-        .expect("deleted array");
-    assert_eq!(deleted.len(), 1);
+        .expect("fetch");
+    assert_eq!(get.status.code().unwrap(), 3);
+    let err = parse_error_json(&get.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
     assert_eq!(
-        deleted[0].get("pool").and_then(|v| v.as_str()),
-        Some("deleteme")
+        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
+        "NotFound"
     );
-    let failed = output
-        .get("failed")
-        .and_then(|v| v.as_array())
-        .expect("failed array");
-    assert!(failed.is_empty());
-    let pool_path = pool_dir.join("deleteme.plasmite");
-    assert!(!pool_path.exists());
+    assert_eq!(inner.get("seq").and_then(|v| v.as_u64()).unwrap(), 999);
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("pool info") || hint.contains("follow"));
 }
 
 #[test]
-fn pool_delete_multiple_best_effort_mixed_results() {
+fn usage_exit_code() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -3858,319 +5057,307 @@ fn pool_delete_multiple_best_effort_mixed_results() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "a",
-            "b",
+            "testpool",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let delete = cmd()
+    let emit_out = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "testpool"])
+        .output()
+        .expect("feed");
+    assert_eq!(emit_out.status.code().unwrap(), 2);
+    let err = parse_error_json(&emit_out.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("--file") || hint.contains("pipe JSON"));
+}
+
+#[test]
+fn emit_emits_json_by_default() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "pool",
-            "delete",
-            "--json",
-            "a",
-            "missing",
-            "b",
+            "create",
+            "testpool",
         ])
         .output()
-        .expect("delete");
-    assert_eq!(delete.status.code(), Some(3));
-
-    let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
-    let deleted = output
-        .get("deleted")
-        .and_then(|v| v.as_array())
-        .expect("deleted array");
-    assert_eq!(deleted.len(), 2);
-    let deleted_names = deleted
-        .iter()
-        .filter_map(|entry| entry.get("pool").and_then(|v| v.as_str()))
-        .collect::<Vec<_>>();
-    assert!(deleted_names.contains(&"a"));
-    assert!(deleted_names.contains(&"b"));
-
-    let failed = output
-        .get("failed")
-        .and_then(|v| v.as_array())
-        .expect("failed array");
-    assert_eq!(failed.len(), 1);
-    assert_eq!(
-        failed[0].get("pool").and_then(|v| v.as_str()),
-        Some("missing")
-    );
-    let error = failed[0]
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(error.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+        .expect("create");
+    assert!(create.status.success());
 
-    assert!(!pool_dir.join("a.plasmite").exists());
-    assert!(!pool_dir.join("b.plasmite").exists());
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "testpool",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+    let value = parse_json(std::str::from_utf8(&emit_out.stdout).expect("utf8"));
+    assert!(value.get("seq").is_some());
+    assert!(value.get("time").is_some());
 }
 
 #[test]
-fn pool_delete_multiple_with_invalid_ref_continues() {
+fn emit_short_file_flag_reads_single_json_file() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
     let create = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "create", "ok"])
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let delete = cmd()
+    let input_file = temp.path().join("one.json");
+    std::fs::write(&input_file, b"{\"x\":1}\n").expect("write input");
+
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "delete",
-            "--json",
-            "ok",
-            "http://127.0.0.1:9700/demo",
+            "feed",
+            "demo",
+            "-f",
+            input_file.to_str().unwrap(),
         ])
         .output()
-        .expect("delete");
-    assert_eq!(delete.status.code(), Some(2));
-
-    let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
-    let deleted = output
-        .get("deleted")
-        .and_then(|v| v.as_array())
-        .expect("deleted array");
-    assert_eq!(deleted.len(), 1);
-    assert_eq!(deleted[0].get("pool").and_then(|v| v.as_str()), Some("ok"));
-
-    let failed = output
-        .get("failed")
-        .and_then(|v| v.as_array())
-        .expect("failed array");
-    assert_eq!(failed.len(), 1);
-    assert_eq!(
-        failed[0].get("pool").and_then(|v| v.as_str()),
-        Some("http://127.0.0.1:9700/demo")
+        .expect("feed");
+    assert!(
+        emit_out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&emit_out.stderr)
     );
-    let error = failed[0]
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(error.get("kind").and_then(|v| v.as_str()), Some("Usage"));
-    assert!(!pool_dir.join("ok.plasmite").exists());
+    let receipts = parse_json_lines(&emit_out.stdout);
+    assert_eq!(receipts.len(), 1);
+
+    let get = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "1"])
+        .output()
+        .expect("fetch");
+    assert!(get.status.success());
+    let value = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
+    assert_eq!(value["data"]["x"], 1);
 }
 
 #[test]
-fn pool_delete_defaults_to_table_output() {
+fn emit_file_jsonl_ingests_multiple_records() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
     let create = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "create", "ok"])
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let delete = cmd()
+    let input_file = temp.path().join("events.jsonl");
+    std::fs::write(&input_file, b"{\"x\":1}\n{\"x\":2}\n").expect("write input");
+
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "delete",
-            "ok",
-            "missing",
+            "feed",
+            "demo",
+            "--file",
+            input_file.to_str().unwrap(),
         ])
         .output()
-        .expect("delete");
-    assert_eq!(delete.status.code(), Some(3));
+        .expect("feed");
+    assert!(
+        emit_out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&emit_out.stderr)
+    );
+    let receipts = parse_json_lines(&emit_out.stdout);
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts[0]["seq"], 1);
+    assert_eq!(receipts[1]["seq"], 2);
 
-    let stdout = std::str::from_utf8(&delete.stdout).expect("utf8");
-    assert!(stdout.contains("NAME"));
-    assert!(stdout.contains("STATUS"));
-    assert!(stdout.contains("PATH"));
-    assert!(stdout.contains("DETAIL"));
-    assert!(stdout.contains("ok"));
-    assert!(stdout.contains("missing"));
-    assert!(stdout.contains("OK"));
-    assert!(stdout.contains("ERR"));
+    let get_one = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "1"])
+        .output()
+        .expect("fetch one");
+    assert!(get_one.status.success());
+    let first = parse_json(std::str::from_utf8(&get_one.stdout).expect("utf8"));
+    assert_eq!(first["data"]["x"], 1);
+
+    let get_two = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "2"])
+        .output()
+        .expect("fetch two");
+    assert!(get_two.status.success());
+    let second = parse_json(std::str::from_utf8(&get_two.stdout).expect("utf8"));
+    assert_eq!(second["data"]["x"], 2);
 }
 
 #[test]
-fn errors_are_json_on_non_tty_stderr() {
+fn emit_file_auto_handles_multiline_json() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let follower = cmd()
+    let create = cmd()
         .args([
-            "--color",
-            "always",
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "missing",
+            "pool",
+            "create",
+            "demo",
         ])
         .output()
-        .expect("follow");
-    assert_eq!(follower.status.code().unwrap(), 3);
+        .expect("create");
+    assert!(create.status.success());
 
-    let err = parse_error_json(&follower.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(
-        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
-        "NotFound"
-    );
-    assert!(
-        !inner
-            .get("message")
-            .and_then(|v| v.as_str())
-            .unwrap()
-            .is_empty()
-    );
+    let input_file = temp.path().join("pretty.json");
+    std::fs::write(&input_file, b"{\n  \"x\": 1,\n  \"y\": 2\n}\n").expect("write input");
+
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "--file",
+            input_file.to_str().unwrap(),
+        ])
+        .output()
+        .expect("feed");
     assert!(
-        inner
-            .get("path")
-            .and_then(|v| v.as_str())
-            .unwrap()
-            .ends_with("missing.plasmite")
+        emit_out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&emit_out.stderr)
     );
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("--create") || hint.contains("exact command"));
+    let receipts = parse_json_lines(&emit_out.stdout);
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0]["seq"], 1);
+
+    let get = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "fetch", "demo", "1"])
+        .output()
+        .expect("fetch");
+    assert!(get.status.success());
+    let value = parse_json(std::str::from_utf8(&get.stdout).expect("utf8"));
+    assert_eq!(value["data"]["x"], 1);
+    assert_eq!(value["data"]["y"], 2);
 }
 
 #[test]
-fn clap_errors_are_concise_in_json() {
+fn emit_retries_when_pool_is_busy() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let bad = cmd()
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--definitely-not-a-flag",
+            "pool",
+            "create",
+            "busy",
         ])
         .output()
-        .expect("follow");
-    assert_eq!(bad.status.code().unwrap(), 2);
+        .expect("create");
+    assert!(create.status.success());
 
-    let err = parse_error_json(&bad.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
-    let message = inner.get("message").and_then(|v| v.as_str()).unwrap();
-    assert!(!message.contains('\n'));
-    assert!(!message.contains("Usage:"));
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("--help"));
-}
+    let pool_path = pool_dir.join("busy.plasmite");
+    let file = File::open(&pool_path).expect("open pool");
+    file.try_lock_exclusive().expect("try lock");
 
-#[test]
-fn misuse_feedback_matrix_is_actionable_across_command_families() {
-    let cases: [(&[&str], &str, &str); 5] = [
-        (
-            &["feed", "demo", "{\"x\":1}", "--retry-delay", "1s"],
-            "--retry-delay requires --retry",
-            "Add --retry",
-        ),
-        (
-            &["doctor", "demo", "--all"],
-            "--all cannot be combined",
-            "Use --all by itself",
-        ),
-        (
-            &["serve", "--bind", "nope", "check"],
-            "invalid bind address",
-            "host:port",
-        ),
-        (&["completion", "nope"], "invalid value", "plasmite --help"),
-        (
-            &["version", "extra"],
-            "unexpected argument",
-            "version --help",
-        ),
-    ];
+    let (tx, rx) = mpsc::channel();
+    let pool_dir_str = pool_dir.to_str().unwrap().to_string();
+    thread::spawn(move || {
+        let output = cmd()
+            .args([
+                "--dir",
+                &pool_dir_str,
+                "feed",
+                "busy",
+                "{\"x\":1}",
+                "--retry",
+                "5",
+                "--retry-delay",
+                "50ms",
+            ])
+            .output()
+            .expect("feed");
+        let _ = tx.send(output);
+    });
 
-    for (args, expected_message_fragment, expected_hint_fragment) in cases {
-        let output = cmd().args(args).output().expect("command");
-        assert_actionable_usage_feedback(
-            &output,
-            expected_message_fragment,
-            expected_hint_fragment,
-        );
-    }
-}
+    thread::sleep(Duration::from_millis(150));
+    fs2::FileExt::unlock(&file).expect("unlock");
 
-#[test]
-fn follow_missing_pool_has_actionable_hint() {
-    // This assertion currently checks the fallback exact command hint text for missing pools.
-    let output = cmd().args(["follow", "-n", "1"]).output().expect("follow");
-    assert_eq!(output.status.code().unwrap(), 2);
-    let err = parse_error_json(&output.stderr);
-    let hint = err
-        .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("pool ref"));
-    assert!(hint.contains("plasmite follow chat -n 1"));
+    let output = rx.recv_timeout(Duration::from_secs(2)).expect("output");
+    assert!(
+        output.status.success(),
+        "feed failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert!(value.get("seq").is_some());
 }
 
 #[test]
-fn emit_missing_pool_hint_suggests_create() {
+fn color_always_colorizes_pretty_stdout() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let output = cmd()
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "missing",
-            "{\"x\":1}",
+            "pool",
+            "create",
+            "demo",
         ])
         .output()
-        .expect("feed");
-    assert_eq!(output.status.code(), Some(3));
-
-    let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("--create"));
-    assert!(hint.contains("exact command"));
-    assert!(hint.contains("plasmite feed missing --create"));
-}
-
-#[test]
-fn follow_missing_pool_hint_suggests_create() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+        .expect("create");
+    assert!(create.status.success());
 
-    let output = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "follow", "missing"])
+    let info = cmd()
+        .args([
+            "--color",
+            "always",
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "info",
+            "demo",
+            "--json",
+        ])
         .output()
-        .expect("follow");
-    assert_eq!(output.status.code(), Some(3));
-
-    let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("--create"));
-    assert!(hint.contains("exact command"));
-    assert!(hint.contains("plasmite follow missing --create"));
+        .expect("info");
+    assert!(info.status.success());
+    let stdout = String::from_utf8_lossy(&info.stdout);
+    assert!(stdout.contains("\u{1b}[36m\"name\"\u{1b}[0m"));
 }
 
 #[test]
-fn already_exists_has_hint_and_path() {
+fn color_never_does_not_emit_ansi() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4180,55 +5367,34 @@ fn already_exists_has_hint_and_path() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "testpool",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let again = cmd()
+    let info = cmd()
         .args([
+            "--color",
+            "never",
             "--dir",
             pool_dir.to_str().unwrap(),
             "pool",
-            "create",
-            "testpool",
+            "info",
+            "demo",
+            "--json",
         ])
         .output()
-        .expect("create again");
-    assert_eq!(again.status.code().unwrap(), 4);
-    let err = parse_error_json(&again.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(
-        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
-        "AlreadyExists"
-    );
-    assert!(
-        inner
-            .get("path")
-            .and_then(|v| v.as_str())
-            .unwrap()
-            .ends_with("testpool.plasmite")
-    );
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("different name") || hint.contains("remove"));
+        .expect("info");
+    assert!(info.status.success());
+    let stdout = String::from_utf8_lossy(&info.stdout);
+    assert!(!stdout.contains("\u{1b}["));
 }
 
 #[test]
-fn permission_error_has_hint_and_causes() {
+fn color_always_does_not_color_jsonl() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("readonly");
-    std::fs::create_dir_all(&pool_dir).expect("mkdir");
-
-    let mut perms = std::fs::metadata(&pool_dir)
-        .expect("metadata")
-        .permissions();
-    let original_mode = perms.mode();
-    perms.set_readonly(true);
-    std::fs::set_permissions(&pool_dir, perms).expect("set perms");
+    let pool_dir = temp.path().join("pools");
 
     let create = cmd()
         .args([
@@ -4236,117 +5402,91 @@ fn permission_error_has_hint_and_causes() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "testpool",
+            "demo",
         ])
         .output()
         .expect("create");
-    assert_eq!(create.status.code().unwrap(), 6);
-    let err = parse_error_json(&create.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert!(
-        !inner
-            .get("hint")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .is_empty()
-    );
-    let empty = Vec::new();
-    let causes = inner
-        .get("causes")
-        .and_then(|v| v.as_array())
-        .unwrap_or(&empty);
-    if causes.is_empty() {
-        eprintln!(
-            "warning: Permission/Io error had no causes; stderr={}",
-            String::from_utf8_lossy(&create.stderr)
-        );
-    }
-
-    let mut perms = std::fs::metadata(&pool_dir)
-        .expect("metadata")
-        .permissions();
-    perms.set_mode(original_mode);
-    std::fs::set_permissions(&pool_dir, perms).expect("unset perms");
-}
-
-#[test]
-fn permission_denied_matrix_for_write_paths() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("readonly-pools");
-    std::fs::create_dir_all(&pool_dir).expect("mkdir");
+    assert!(create.status.success());
 
-    let create = cmd()
+    let emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "base",
+            "feed",
+            "demo",
+            "{\"x\":1}",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-
-    let mut perms = std::fs::metadata(&pool_dir)
-        .expect("metadata")
-        .permissions();
-    let original_mode = perms.mode();
-    perms.set_readonly(true);
-    std::fs::set_permissions(&pool_dir, perms).expect("set perms");
+        .expect("feed");
+    assert!(emit_out.status.success());
 
-    let output_create = cmd()
+    let mut follower = cmd()
         .args([
+            "--color",
+            "always",
             "--dir",
             pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "other",
-        ])
-        .output()
-        .expect("create denied");
-    assert!(
-        matches!(output_create.status.code(), Some(6) | Some(8)),
-        "unexpected create status: {:?}",
-        output_create.status.code()
-    );
-    let output_json = parse_error_json(&output_create.stderr);
-    let inner_create = output_json
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert!(
-        matches!(
-            inner_create.get("kind").and_then(|v| v.as_str()),
-            Some("Permission" | "Io")
-        ),
-        "unexpected create kind: {:?}",
-        inner_create.get("kind")
-    );
+            "follow",
+            "demo",
+            "--tail",
+            "1",
+            "--format",
+            "jsonl",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow jsonl");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let line = line.trim_end();
+    assert!(!line.contains("\u{1b}["));
+    let _ = parse_json(line);
+    let _ = follower.kill();
+    let _ = follower.wait();
+}
 
-    let output_delete = cmd()
+#[test]
+fn emit_auto_handles_pretty_json() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "pool",
-            "delete",
-            "base",
+            "create",
+            "demo",
         ])
         .output()
-        .expect("delete denied");
-    assert_eq!(output_delete.status.code(), Some(8));
+        .expect("create");
+    assert!(create.status.success());
 
-    let restore = std::fs::Permissions::from_mode(original_mode);
-    std::fs::set_permissions(&pool_dir, restore).expect("unset perms");
+    let mut emit_out = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin
+            .write_all(b"{\n  \"x\": 1,\n  \"y\": 2\n}\n")
+            .expect("write stdin");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].get("seq").is_some());
+    assert!(lines[0].get("data").is_none());
 }
 
 #[test]
-fn truncated_pool_file_variants_are_rejected() {
+fn emit_auto_handles_event_stream() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-    std::fs::create_dir_all(&pool_dir).expect("mkdir");
 
     let create = cmd()
         .args([
@@ -4354,83 +5494,71 @@ fn truncated_pool_file_variants_are_rejected() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "probe",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let pool_path = pool_dir.join("probe.plasmite");
-    let valid = std::fs::read(&pool_path).expect("read valid pool");
-
-    let mut cases = Vec::new();
-    cases.push(("empty", Vec::new()));
-    cases.push(("one-byte", valid[..1].to_vec()));
-    cases.push(("half", valid[..valid.len() / 2].to_vec()));
-    let mut truncated = valid.clone();
-    truncated.truncate(valid.len().saturating_sub(128.min(valid.len())));
-    cases.push(("truncated-end", truncated));
-    cases.push(("zero-header", vec![0; 4096]));
-
-    for (case_name, bytes) in cases {
-        std::fs::write(&pool_path, &bytes).expect("write mutated pool");
-        let output = cmd()
-            .args(["--dir", pool_dir.to_str().unwrap(), "pool", "info", "probe"])
-            .output()
-            .expect("info");
-        assert_ne!(
-            output.status.code(),
-            Some(0),
-            "{case_name} unexpectedly succeeded"
-        );
-        assert!(
-            !output.stderr.is_empty(),
-            "{case_name} should return structured error stderr"
-        );
-        let error_json = parse_error_json(&output.stderr);
-        let inner = error_json
-            .get("error")
-            .and_then(|v| v.as_object())
-            .expect("error object");
-        assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Corrupt"));
+    let mut emit_out = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin
+            .write_all(b"data: {\"x\":1}\n\ndata: {\"x\":2}\n\n")
+            .expect("write stdin");
     }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].get("seq").is_some());
+    assert!(lines[1].get("data").is_none());
 }
 
 #[test]
-fn corrupt_pool_has_hint_and_path() {
+fn emit_auto_detects_json_seq() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-    std::fs::create_dir_all(&pool_dir).expect("mkdir");
-    let pool_path = pool_dir.join("bad.plasmite");
-    std::fs::write(&pool_path, b"NOPE").expect("write");
 
-    let info = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "info", "bad"])
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
         .output()
-        .expect("info");
-    assert_eq!(info.status.code().unwrap(), 7);
-    let err = parse_error_json(&info.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(
-        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
-        "Corrupt"
-    );
-    assert!(
-        inner
-            .get("path")
-            .and_then(|v| v.as_str())
-            .unwrap()
-            .ends_with("bad.plasmite")
-    );
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("Recreate") || hint.contains("recreate"));
+        .expect("create");
+    assert!(create.status.success());
+
+    let mut emit_out = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin
+            .write_all(b"\x1e{\"x\":1}\x1e{\"x\":2}")
+            .expect("write stdin");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].get("seq").is_some());
+    assert!(lines[0].get("data").is_none());
 }
 
 #[test]
-fn doctor_reports_ok_as_json() {
+fn emit_auto_skip_reports_oversize() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4440,120 +5568,139 @@ fn doctor_reports_ok_as_json() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "doctorpool",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let doctor = cmd()
+    let mut emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "doctor",
-            "doctorpool",
-            "--json",
+            "feed",
+            "demo",
+            "-e",
+            "skip",
         ])
-        .output()
-        .expect("doctor");
-    assert!(doctor.status.success());
-    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
-    let reports = output
-        .get("reports")
-        .and_then(|v| v.as_array())
-        .expect("reports array");
-    assert_eq!(reports.len(), 1);
-    let report = reports[0].as_object().expect("report object");
-    assert_eq!(report.get("status").and_then(|v| v.as_str()), Some("ok"));
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        let big = "x".repeat(1024 * 1024 + 1);
+        let line = format!("{{\"big\":\"{big}\"}}\n");
+        stdin.write_all(line.as_bytes()).expect("write stdin");
+        stdin.write_all(b"{\"ok\":1}\n").expect("write ok");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert_eq!(output.status.code().unwrap(), 1);
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 1);
+    let notices = parse_json_lines(&output.stderr);
+    let oversize = notices.iter().find(|value| {
+        value
+            .get("notice")
+            .and_then(|v| v.get("details"))
+            .and_then(|v| v.get("error_kind"))
+            .and_then(|v| v.as_str())
+            == Some("Oversize")
+    });
+    assert!(oversize.is_some());
 }
 
 #[test]
-fn doctor_reports_corrupt_and_exit_code() {
+fn feed_file_tty_emits_human_receipts() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-    std::fs::create_dir_all(&pool_dir).expect("mkdir");
-    let pool_path = pool_dir.join("bad.plasmite");
-    std::fs::write(&pool_path, b"NOPE").expect("write");
+    let input_file = temp.path().join("events.jsonl");
+    std::fs::write(&input_file, "{\"x\":1}\n{\"x\":2}\n").expect("write input");
 
-    let doctor = cmd()
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "doctor",
-            "bad",
-            "--json",
+            "pool",
+            "create",
+            "demo",
         ])
         .output()
-        .expect("doctor");
-    assert_eq!(doctor.status.code().unwrap(), 7);
-    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
-    let reports = output
-        .get("reports")
-        .and_then(|v| v.as_array())
-        .expect("reports array");
-    let report = reports[0].as_object().expect("report object");
-    assert_eq!(
-        report.get("status").and_then(|v| v.as_str()),
-        Some("corrupt")
-    );
+        .expect("create");
+    assert!(create.status.success());
+
+    let output = cmd_tty(&[
+        "--color",
+        "never",
+        "--dir",
+        pool_dir.to_str().unwrap(),
+        "feed",
+        "demo",
+        "--file",
+        input_file.to_str().unwrap(),
+        "--in",
+        "jsonl",
+    ]);
+    assert!(output.status.success());
+    let text = sanitize_tty_text(&output.stdout);
+    assert_eq!(text.matches("fed seq=").count(), 2);
+    assert!(!text.contains("\"seq\":"));
 }
 
 #[test]
-fn doctor_all_reports_mixed_ok_and_corrupt() {
+fn feed_csv_converts_rows_to_objects() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-
+    let dir = pool_dir.to_str().unwrap();
     let create = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "create", "ok"])
+        .args(["--dir", dir, "pool", "create", "orders"])
         .output()
         .expect("create");
     assert!(create.status.success());
+    let csv = temp.path().join("orders.csv");
+    std::fs::write(
+        &csv,
+        "id,customer,paid\n1,\"Acme, Inc\",true\n2,Globex,false\n",
+    )
+    .expect("write");
 
-    std::fs::create_dir_all(&pool_dir).expect("mkdir");
-    let pool_path = pool_dir.join("bad.plasmite");
-    std::fs::write(&pool_path, b"NOPE").expect("write");
-
-    let doctor = cmd()
+    let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "doctor",
-            "--all",
-            "--json",
+            dir,
+            "feed",
+            "orders",
+            "--in",
+            "csv",
+            "--file",
+            csv.to_str().unwrap(),
         ])
         .output()
-        .expect("doctor");
-    assert_eq!(doctor.status.code().unwrap(), 7);
-    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
-    let reports = output
-        .get("reports")
-        .and_then(|v| v.as_array())
-        .expect("reports array");
-    let statuses = reports
-        .iter()
-        .filter_map(|report| report.get("status").and_then(|v| v.as_str()))
-        .collect::<Vec<_>>();
-    assert!(statuses.contains(&"ok"));
-    assert!(statuses.contains(&"corrupt"));
-}
-
-#[test]
-fn doctor_requires_pool_or_all() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+        .expect("feed csv");
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fetch_message(&pool_dir, "orders", 1)["data"],
+        json!({"id": 1, "customer": "Acme, Inc", "paid": true})
+    );
+    assert_eq!(
+        fetch_message(&pool_dir, "orders", 2)["data"],
+        json!({"id": 2, "customer": "Globex", "paid": false})
+    );
 
-    let doctor = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "doctor"])
+    let output = cmd()
+        .args(["--dir", dir, "feed", "orders", "--csv-header", "no", "{}"])
         .output()
-        .expect("doctor");
-    assert_eq!(doctor.status.code(), Some(2));
-    let stderr = std::str::from_utf8(&doctor.stderr).expect("utf8");
-    assert!(stderr.contains("Usage: plasmite doctor"));
-    assert!(stderr.contains("Diagnose pool health"));
+        .expect("feed");
+    assert_eq!(output.status.code(), Some(2));
 }
 
 #[test]
-fn doctor_defaults_to_human_output() {
+fn emit_seq_mode_parses_rs_records() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4563,23 +5710,41 @@ fn doctor_defaults_to_human_output() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "doctorpool",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let doctor = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "doctor", "doctorpool"])
-        .output()
-        .expect("doctor");
-    assert!(doctor.status.success());
-    let stdout = std::str::from_utf8(&doctor.stdout).expect("utf8");
-    assert!(stdout.contains("OK: doctorpool"));
+    let mut emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "-i",
+            "seq",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin
+            .write_all(b"\x1e{\"x\":1}\x1e{\"x\":2}")
+            .expect("write stdin");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].get("seq").is_some());
+    assert!(lines[0].get("data").is_none());
 }
 
 #[test]
-fn doctor_tty_reports_count_and_seq_range_for_healthy_pool() {
+fn emit_errors_skip_emits_notices_and_nonzero() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4589,43 +5754,64 @@ fn doctor_tty_reports_count_and_seq_range_for_healthy_pool() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "doctorpool",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    for idx in 1..=4 {
-        let feed = cmd()
-            .args([
-                "--dir",
-                pool_dir.to_str().unwrap(),
-                "feed",
-                "doctorpool",
-                &format!("{{\"x\":{idx}}}"),
-            ])
-            .output()
-            .expect("feed");
-        assert!(feed.status.success());
+    let mut emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "-i",
+            "jsonl",
+            "-e",
+            "skip",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin
+            .write_all(b"{\"x\":1}\nnot-json\n{\"x\":2}\n")
+            .expect("write stdin");
     }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert_eq!(output.status.code().unwrap(), 1);
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 2);
 
-    let doctor = cmd_tty(&[
-        "--color",
-        "never",
-        "--dir",
-        pool_dir.to_str().unwrap(),
-        "doctor",
-        "doctorpool",
-    ]);
-    assert!(doctor.status.success());
-    let stdout = sanitize_tty_text(&doctor.stdout);
-    assert!(stdout.contains("doctorpool: healthy"));
-    assert!(stdout.contains("messages:  4 (seq 1..4)"));
-    assert!(stdout.contains("checked:   header, index, ring — 0 issues"));
+    let notices = parse_json_lines(&output.stderr);
+    assert!(notices.len() >= 2);
+    let first = notices[0]
+        .get("notice")
+        .and_then(|v| v.as_object())
+        .expect("notice");
+    assert_eq!(
+        first.get("kind").and_then(|v| v.as_str()),
+        Some("ingest_skip")
+    );
+    let summary = notices
+        .iter()
+        .find(|value| {
+            value
+                .get("notice")
+                .and_then(|v| v.get("kind"))
+                .and_then(|v| v.as_str())
+                == Some("ingest_summary")
+        })
+        .expect("summary");
+    assert!(summary.get("notice").is_some());
 }
 
 #[test]
-fn doctor_all_tty_uses_pool_names_and_message_counts() {
+fn emit_errors_skip_reports_oversize() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4635,93 +5821,51 @@ fn doctor_all_tty_uses_pool_names_and_message_counts() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "tour-main",
-            "tour-aux",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    for idx in 1..=4 {
-        let feed = cmd()
-            .args([
-                "--dir",
-                pool_dir.to_str().unwrap(),
-                "feed",
-                "tour-main",
-                &format!("{{\"x\":{idx}}}"),
-            ])
-            .output()
-            .expect("feed");
-        assert!(feed.status.success());
-    }
-
-    let doctor = cmd_tty(&[
-        "--color",
-        "never",
-        "--dir",
-        pool_dir.to_str().unwrap(),
-        "doctor",
-        "--all",
-    ]);
-    assert!(doctor.status.success());
-    let stdout = sanitize_tty_text(&doctor.stdout);
-    assert!(stdout.contains("All 2 pools healthy."));
-    assert!(stdout.contains("tour-main"));
-    assert!(stdout.contains("4 messages"));
-    assert!(stdout.contains("tour-aux"));
-    assert!(stdout.contains("0 messages"));
-    assert!(stdout.contains("0 issues"));
-    assert!(!stdout.contains(".plasmite"));
-}
-
-#[test]
-fn doctor_rejects_pool_with_all() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
-
-    let doctor = cmd()
+    let mut emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "doctor",
-            "foo",
-            "--all",
+            "feed",
+            "demo",
+            "-i",
+            "jsonl",
+            "-e",
+            "skip",
         ])
-        .output()
-        .expect("doctor");
-    assert!(!doctor.status.success());
-    let err = parse_error_json(&doctor.stderr);
-    let inner = err
-        .get("error")
-        .and_then(|v| v.as_object())
-        .expect("error object");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
-    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(message.contains("--all cannot be combined"));
-}
-
-#[test]
-fn doctor_missing_pool_reports_not_found() {
-    let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
-
-    let doctor = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "doctor", "missing"])
-        .output()
-        .expect("doctor");
-    assert!(!doctor.status.success());
-    let err = parse_error_json(&doctor.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "NotFound");
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        let big = "x".repeat(1024 * 1024 + 1);
+        let line = format!("{{\"big\":\"{big}\"}}\n");
+        stdin.write_all(line.as_bytes()).expect("write stdin");
+        stdin.write_all(b"{\"ok\":1}\n").expect("write ok");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert_eq!(output.status.code().unwrap(), 1);
+    let notices = parse_json_lines(&output.stderr);
+    let oversize = notices.iter().find(|value| {
+        value
+            .get("notice")
+            .and_then(|v| v.get("details"))
+            .and_then(|v| v.get("error_kind"))
+            .and_then(|v| v.as_str())
+            == Some("Oversize")
+    });
+    assert!(oversize.is_some());
 }
 
 #[test]
-fn emit_remote_url_happy_path_appends_message() {
+fn emit_in_json_accepts_pretty_json() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4737,147 +5881,83 @@ fn emit_remote_url_happy_path_appends_message() {
         .expect("create");
     assert!(create.status.success());
 
-    let server = ServeProcess::start(&pool_dir);
-    let pool_url = format!("{}/demo", server.base_url);
-    let emit_out = cmd()
+    let mut emit_out = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "feed",
-            &pool_url,
-            "{\"x\":1}",
-            "--tag",
-            "ping",
-        ])
-        .output()
-        .expect("feed");
-    assert!(emit_out.status.success());
-    let value = parse_json(std::str::from_utf8(&emit_out.stdout).expect("utf8"));
-    assert_eq!(value.get("seq").and_then(|v| v.as_u64()), Some(1));
-    assert!(value.get("data").is_none());
-    assert_eq!(
-        value.get("meta").and_then(|v| v.get("tags")),
-        Some(&json!(["ping"]))
-    );
-}
-
-#[test]
-fn emit_remote_url_rejects_api_shaped_path() {
-    let output = cmd()
-        .args([
-            "feed",
-            "http://localhost:9170/v0/pools/demo/append",
-            "{\"x\":1}",
-        ])
-        .output()
-        .expect("feed");
-    assert!(!output.status.success());
-    let err = parse_error_json(&output.stderr);
-    assert_eq!(
-        err.get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Usage")
-    );
-}
-
-#[test]
-fn emit_remote_url_rejects_trailing_slash() {
-    let output = cmd()
-        .args(["feed", "http://localhost:9170/demo/", "{\"x\":1}"])
-        .output()
-        .expect("feed");
-    assert!(!output.status.success());
-    let err = parse_error_json(&output.stderr);
-    assert_eq!(
-        err.get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Usage")
-    );
-}
-
-#[test]
-fn emit_remote_url_rejects_create_flag() {
-    let output = cmd()
-        .args([
-            "feed",
-            "http://localhost:9170/demo",
-            "--create",
-            "{\"x\":1}",
-        ])
-        .output()
-        .expect("feed");
-    assert!(!output.status.success());
-    let err = parse_error_json(&output.stderr);
-    assert_eq!(
-        err.get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Usage")
-    );
-    let message = err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(message.contains("does not support --create"));
-    let hint = err
-        .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("server-side"));
-}
-
-#[test]
-fn emit_remote_create_rejected() {
-    let output = cmd()
-        .args([
-            "feed",
-            "http://localhost:9170/demo",
-            "--create",
-            "{\"x\":1}",
+            "demo",
+            "-i",
+            "json",
         ])
-        .output()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
         .expect("feed");
-    assert_eq!(output.status.code(), Some(2));
-
-    let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
-    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(message.contains("does not support --create"));
-    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(hint.contains("server-side"));
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin
+            .write_all(b"{\n  \"x\": 1,\n  \"y\": 2\n}\n")
+            .expect("write stdin");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].get("seq").is_some());
+    assert!(lines[0].get("data").is_none());
 }
 
 #[test]
-fn follow_remote_url_rejects_create_flag() {
+fn emit_in_json_errors_skip_returns_nonzero() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let output = cmd()
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "http://127.0.0.1:65535/demo",
-            "--create",
+            "pool",
+            "create",
+            "demo",
         ])
         .output()
-        .expect("follow");
-    assert_eq!(output.status.code(), Some(2));
+        .expect("create");
+    assert!(create.status.success());
 
-    let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
-    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(message.contains("does not support --create"));
+    let mut emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "-i",
+            "json",
+            "-e",
+            "skip",
+        ])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin.write_all(b"{\"x\":1").expect("write stdin");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert_eq!(output.status.code().unwrap(), 1);
+    let notices = parse_json_lines(&output.stderr);
+    assert!(notices.iter().any(|value| {
+        value
+            .get("notice")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str())
+            == Some("ingest_skip")
+    }));
 }
 
 #[test]
-fn emit_remote_url_auth_errors_propagate() {
+fn emit_event_stream_flushes_trailing_event() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4893,24 +5973,33 @@ fn emit_remote_url_auth_errors_propagate() {
         .expect("create");
     assert!(create.status.success());
 
-    let server = ServeProcess::start_with_args(&pool_dir, &["--token", "secret-token"]);
-    let pool_url = format!("{}/demo", server.base_url);
-    let output = cmd()
-        .args(["feed", &pool_url, "{\"x\":1}"])
-        .output()
+    let mut emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "-i",
+            "auto",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
         .expect("feed");
-    assert!(!output.status.success());
-    let err = parse_error_json(&output.stderr);
-    assert_eq!(
-        err.get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Permission")
-    );
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin.write_all(b"data: {\"x\":1}\n").expect("write stdin");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].get("seq").is_some());
+    assert!(lines[0].get("data").is_none());
 }
 
 #[test]
-fn emit_remote_url_accepts_token_and_token_file_flags() {
+fn emit_jq_mode_rejects_skip() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -4926,36 +6015,39 @@ fn emit_remote_url_accepts_token_and_token_file_flags() {
         .expect("create");
     assert!(create.status.success());
 
-    let server = ServeProcess::start_with_args(&pool_dir, &["--token", "secret-token"]);
-    let pool_url = format!("{}/demo", server.base_url);
-
-    let with_token = cmd()
-        .args(["feed", &pool_url, "{\"x\":1}", "--token", "secret-token"])
-        .output()
-        .expect("feed");
-    assert!(with_token.status.success());
-
-    let token_file = temp.path().join("token.txt");
-    std::fs::write(&token_file, "secret-token\n").expect("write token file");
-    let with_token_file = cmd()
+    let mut emit_out = cmd()
         .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
             "feed",
-            &pool_url,
-            "{\"x\":2}",
-            "--token-file",
-            token_file.to_str().unwrap(),
+            "demo",
+            "-i",
+            "jq",
+            "-e",
+            "skip",
         ])
-        .output()
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .expect("feed");
-    assert!(with_token_file.status.success());
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin.write_all(b"{\"x\":1}\n{\"x\":2}\n").expect("write");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert_eq!(output.status.code().unwrap(), 2);
+    let err = parse_error_json(&output.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
 }
 
 #[test]
-fn emit_and_follow_local_reject_remote_auth_tls_flags() {
+fn pool_list_lists_pools_sorted_by_name() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-    let cert_path = temp.path().join("dev-cert.pem");
-    std::fs::write(&cert_path, "not-a-real-cert\n").expect("write cert");
 
     let create = cmd()
         .args([
@@ -4963,227 +6055,204 @@ fn emit_and_follow_local_reject_remote_auth_tls_flags() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "demo",
+            "beta",
+            "alpha",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let feed = cmd()
+    let list = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
-            "--token",
-            "devtoken",
+            "pool",
+            "list",
+            "--json",
         ])
         .output()
-        .expect("feed");
-    assert_eq!(feed.status.code(), Some(2));
-    let feed_err = parse_error_json(&feed.stderr);
-    assert_eq!(
-        feed_err
-            .get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Usage")
-    );
+        .expect("list");
+    assert!(list.status.success());
 
-    let follow = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "follow",
-            "demo",
-            "--tail",
-            "0",
-            "--timeout",
-            "100ms",
-            "--tls-ca",
-            cert_path.to_str().unwrap(),
-        ])
-        .output()
-        .expect("follow");
-    assert_eq!(follow.status.code(), Some(2));
-    let follow_err = parse_error_json(&follow.stderr);
-    assert_eq!(
-        follow_err
-            .get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Usage")
-    );
+    let value = parse_json(std::str::from_utf8(&list.stdout).expect("utf8"));
+    let pools = value
+        .get("pools")
+        .and_then(|v| v.as_array())
+        .expect("pools array");
+    assert_eq!(pools.len(), 2);
+    assert_eq!(pools[0].get("name").and_then(|v| v.as_str()), Some("alpha"));
+    assert_eq!(pools[1].get("name").and_then(|v| v.as_str()), Some("beta"));
 }
 
 #[test]
-fn follow_remote_tls_ca_and_skip_verify_work() {
+fn hierarchical_pool_names_map_to_subdirectories_and_list_as_tree() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
     let create = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "pool",
             "create",
-            "demo",
+            "team/service/events",
+            "chat",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
+    assert!(pool_dir.join("team/service/events.plasmite").is_file());
 
     let feed = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
-        ])
+        .args(["--dir", dir, "feed", "team/service/events", "{\"x\":1}"])
         .output()
         .expect("feed");
     assert!(feed.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "team/other", "--create", "{\"x\":2}"])
+        .output()
+        .expect("feed --create");
+    assert!(feed.status.success());
+    assert!(pool_dir.join("team/other.plasmite").is_file());
 
-    let mut params = CertificateParams::new(vec!["localhost".to_string()]);
-    params
-        .subject_alt_names
-        .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
-    params
-        .subject_alt_names
-        .push(SanType::IpAddress(IpAddr::V6(Ipv6Addr::LOCALHOST)));
-    let cert = Certificate::from_params(params).expect("cert");
-    let cert_pem = cert.serialize_pem().expect("cert pem");
-    let key_pem = cert.serialize_private_key_pem();
-    let cert_path = temp.path().join("cert.pem");
-    let key_path = temp.path().join("key.pem");
-    std::fs::write(&cert_path, cert_pem).expect("write cert");
-    std::fs::write(&key_path, key_pem).expect("write key");
-
-    let server = ServeProcess::start_with_args_and_scheme(
-        &pool_dir,
-        &[
-            "--tls-cert",
-            cert_path.to_str().unwrap(),
-            "--tls-key",
-            key_path.to_str().unwrap(),
-        ],
-        "https",
-    );
-    let pool_url = format!("{}/demo", server.base_url);
+    let list = cmd()
+        .args(["--dir", dir, "pool", "list", "--json"])
+        .output()
+        .expect("list");
+    let value = parse_json(std::str::from_utf8(&list.stdout).expect("utf8"));
+    let names = value["pools"]
+        .as_array()
+        .expect("pools")
+        .iter()
+        .map(|pool| pool["name"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["chat", "team/other", "team/service/events"]);
 
-    let trusted = cmd()
-        .args([
-            "follow",
-            &pool_url,
-            "--tail",
-            "1",
-            "--one",
-            "--jsonl",
-            "--timeout",
-            "2s",
-            "--tls-ca",
-            cert_path.to_str().unwrap(),
-        ])
+    let table = cmd()
+        .args(["--dir", dir, "--output", "pretty", "pool", "list"])
         .output()
-        .expect("follow");
-    assert!(
-        trusted.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&trusted.stderr)
+        .expect("list table");
+    let stdout = String::from_utf8_lossy(&table.stdout);
+    let first_cells = stdout
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let cells = line.trim_start();
+            let indent = line.len() - cells.len();
+            format!(
+                "{}{}",
+                " ".repeat(indent),
+                cells.split("  ").next().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        first_cells,
+        ["chat", "team/", "  other", "  service/", "    events"]
     );
 
-    let skipped = cmd()
-        .args([
-            "follow",
-            &pool_url,
-            "--tail",
-            "1",
-            "--one",
-            "--jsonl",
-            "--timeout",
-            "2s",
-            "--tls-skip-verify",
-        ])
+    let escape = cmd()
+        .args(["--dir", dir, "pool", "create", "team/../../escape"])
         .output()
-        .expect("follow");
-    assert!(
-        skipped.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&skipped.stderr)
-    );
+        .expect("create escape");
+    assert_eq!(escape.status.code(), Some(2));
 }
 
 #[test]
-fn follow_remote_url_happy_path_reads_recent_messages() {
+fn pool_gc_collects_empty_pools_and_keeps_written_ones() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
 
     let create = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
-        ])
+        .args(["--dir", dir, "pool", "create", "empty", "team/idle", "busy"])
         .output()
         .expect("create");
     assert!(create.status.success());
-
-    let first = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":1}",
-        ])
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "busy", "{\"x\":1}"])
         .output()
         .expect("feed");
-    assert!(first.status.success());
-    let second = cmd()
+    assert!(feed.status.success());
+
+    let dry_run = cmd()
+        .args(["--dir", dir, "pool", "gc", "--dry-run", "--json"])
+        .output()
+        .expect("gc dry run");
+    assert!(dry_run.status.success());
+    let value = parse_json(std::str::from_utf8(&dry_run.stdout).expect("utf8"));
+    let candidates = value["candidates"]
+        .as_array()
+        .expect("candidates")
+        .iter()
+        .map(|candidate| {
+            (
+                candidate["pool"].as_str().unwrap_or_default().to_string(),
+                candidate["reason"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        candidates,
+        [
+            ("empty".to_string(), "empty".to_string()),
+            ("team/idle".to_string(), "empty".to_string()),
+        ]
+    );
+    assert!(value["candidates"][0]["oldest_seq"].is_null());
+    assert_eq!(value["deleted"].as_array().map(Vec::len), Some(0));
+    assert!(pool_dir.join("empty.plasmite").is_file());
+
+    let unconfirmed = cmd()
+        .args(["--dir", dir, "pool", "gc"])
+        .output()
+        .expect("gc without --yes");
+    assert_eq!(unconfirmed.status.code(), Some(2));
+    assert!(pool_dir.join("empty.plasmite").is_file());
+
+    let gc = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "demo",
-            "{\"x\":2}",
+            dir,
+            "pool",
+            "gc",
+            "--empty-only",
+            "--yes",
+            "--json",
         ])
         .output()
-        .expect("feed");
-    assert!(second.status.success());
+        .expect("gc");
+    assert!(gc.status.success());
+    let value = parse_json(std::str::from_utf8(&gc.stdout).expect("utf8"));
+    assert_eq!(value["deleted"].as_array().map(Vec::len), Some(2));
+    assert!(!pool_dir.join("empty.plasmite").exists());
+    assert!(!pool_dir.join("team/idle.plasmite").exists());
+    assert!(pool_dir.join("busy.plasmite").is_file());
 
-    let server = ServeProcess::start(&pool_dir);
-    let pool_url = format!("{}/demo", server.base_url);
-    let follower = cmd()
+    let stale = cmd()
         .args([
-            "follow",
-            &pool_url,
-            "--tail",
-            "1",
-            "--one",
-            "--jsonl",
-            "--timeout",
-            "2s",
+            "--dir",
+            dir,
+            "pool",
+            "gc",
+            "--older-than",
+            "0s",
+            "--dry-run",
+            "--json",
         ])
         .output()
-        .expect("follow");
-    assert!(
-        follower.status.success(),
-        "stderr={}",
-        String::from_utf8_lossy(&follower.stderr)
-    );
-    let value = parse_json(std::str::from_utf8(&follower.stdout).expect("utf8").trim());
-    assert_eq!(value.get("seq").and_then(|v| v.as_u64()), Some(2));
-    assert_eq!(value.get("data").and_then(|v| v.get("x")), Some(&json!(2)));
+        .expect("gc stale");
+    let value = parse_json(std::str::from_utf8(&stale.stdout).expect("utf8"));
+    assert_eq!(value["candidates"][0]["pool"], "busy");
+    assert_eq!(value["candidates"][0]["reason"], "stale");
 }
 
 #[test]
-fn follow_remote_url_supports_tag_filter() {
+fn pool_list_defaults_to_table_output() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("mkdir");
 
     let create = cmd()
         .args([
@@ -5191,123 +6260,33 @@ fn follow_remote_url_supports_tag_filter() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "demo",
-        ])
+            "beta",
+            "alpha",
+        ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    for (payload, tag) in [("{\"x\":1}", "drop"), ("{\"x\":2}", "keep")] {
-        let emit_out = cmd()
-            .args([
-                "--dir",
-                pool_dir.to_str().unwrap(),
-                "feed",
-                "demo",
-                payload,
-                "--tag",
-                tag,
-            ])
-            .output()
-            .expect("feed");
-        assert!(emit_out.status.success());
-    }
-
-    let server = ServeProcess::start(&pool_dir);
-    let pool_url = format!("{}/demo", server.base_url);
-    let mut follower = cmd()
-        .args([
-            "follow", &pool_url, "--tail", "10", "--jsonl", "--tag", "keep",
-        ])
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    assert!(!line.is_empty(), "expected a line from follow output");
-    let value = parse_json(line.trim());
-    assert_eq!(value.get("data").and_then(|v| v.get("x")), Some(&json!(2)));
-    let _ = follower.kill();
-    let _ = follower.wait();
-}
-
-#[test]
-fn follow_remote_url_rejects_api_shaped_path() {
-    let output = cmd()
-        .args([
-            "follow",
-            "http://localhost:9170/v0/pools/demo/tail",
-            "--jsonl",
-        ])
-        .output()
-        .expect("follow");
-    assert!(!output.status.success());
-    let err = parse_error_json(&output.stderr);
-    assert_eq!(
-        err.get("error")
-            .and_then(|v| v.get("kind"))
-            .and_then(|v| v.as_str()),
-        Some("Usage")
-    );
-}
-
-#[test]
-fn follow_remote_url_rejects_since_and_replay() {
-    let pool_url = "http://localhost:9170/demo";
-
-    let since = cmd()
-        .args(["follow", pool_url, "--since", "5m"])
-        .output()
-        .expect("follow");
-    assert!(!since.status.success());
-    let since_err = parse_error_json(&since.stderr);
-    let since_message = since_err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(since_message.contains("does not support --since"));
+    std::fs::write(pool_dir.join("bad.plasmite"), b"NOPE").expect("write bad");
 
-    let replay = cmd()
-        .args(["follow", pool_url, "--tail", "5", "--replay", "1"])
+    let list = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "list"])
         .output()
-        .expect("follow");
-    assert!(!replay.status.success());
-    let replay_err = parse_error_json(&replay.stderr);
-    let replay_message = replay_err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(replay_message.contains("does not support --replay"));
-}
-
-#[test]
-fn follow_remote_url_rejects_future_since() {
-    let pool_url = "http://localhost:9170/demo";
+        .expect("list");
+    assert!(list.status.success());
 
-    let since = cmd()
-        .args(["follow", pool_url, "--since", "2999-01-01T00:00:00Z"])
-        .output()
-        .expect("follow");
-    assert_eq!(since.status.code(), Some(2));
-    let since_err = parse_error_json(&since.stderr);
-    let since_kind = since_err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(since_kind, "Usage");
-    let since_message = since_err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(since_message.contains("does not support --since"));
+    let stdout = std::str::from_utf8(&list.stdout).expect("utf8");
+    assert!(stdout.contains("NAME"));
+    assert!(stdout.contains("STATUS"));
+    assert!(stdout.contains("DETAIL"));
+    assert!(stdout.contains("alpha"));
+    assert!(stdout.contains("beta"));
+    assert!(stdout.contains("bad"));
+    assert!(stdout.contains("ERR"));
 }
 
 #[test]
-fn follow_remote_url_timeout_returns_124() {
+fn follow_since_future_exits_empty() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -5323,17 +6302,45 @@ fn follow_remote_url_timeout_returns_124() {
         .expect("create");
     assert!(create.status.success());
 
-    let server = ServeProcess::start(&pool_dir);
-    let pool_url = format!("{}/demo", server.base_url);
     let follower = cmd()
-        .args(["follow", &pool_url, "--jsonl", "--timeout", "150ms"])
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--since",
+            "2999-01-01T00:00:00Z",
+        ])
         .output()
         .expect("follow");
-    assert_eq!(follower.status.code(), Some(124));
+    assert!(follower.status.success());
+    assert!(follower.stdout.is_empty());
 }
 
 #[test]
-fn emit_streams_json_values_from_stdin() {
+fn follow_since_future_missing_pool_reports_not_found() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "missing",
+            "--since",
+            "2999-01-01T00:00:00Z",
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(follower.status.code(), Some(3));
+    let err = parse_error_json(&follower.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+}
+
+#[test]
+fn follow_format_jsonl_matches_jsonl_alias() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -5343,64 +6350,72 @@ fn emit_streams_json_values_from_stdin() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "testpool",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let mut emit_out = cmd()
-        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "testpool"])
-        .stdin(Stdio::piped())
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+
+    let mut fmt = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--tail",
+            "1",
+            "--format",
+            "jsonl",
+        ])
         .stdout(Stdio::piped())
         .spawn()
-        .expect("feed");
-    {
-        let stdin = emit_out.stdin.as_mut().expect("stdin");
-        stdin
-            .write_all(b"{\"x\":1}\n{\"x\":2}")
-            .expect("write stdin");
-    }
-    let output = emit_out.wait_with_output().expect("feed output");
-    assert!(output.status.success());
-    let lines = parse_json_lines(&output.stdout);
-    assert_eq!(lines.len(), 2);
-    assert!(lines[0].get("seq").is_some());
-    assert!(lines[1].get("seq").is_some());
-    assert!(lines[0].get("data").is_none());
-    assert!(lines[1].get("data").is_none());
+        .expect("follow format");
+    let fmt_stdout = fmt.stdout.take().expect("stdout");
+    let fmt_line = read_line_with_timeout(fmt_stdout, Duration::from_secs(2));
+    assert!(!fmt_line.is_empty(), "expected a line from follow output");
+    let fmt_line = fmt_line.trim_end();
+    assert!(!fmt_line.contains('\n'));
+    let _ = parse_json(fmt_line);
+    let _ = fmt.kill();
+    let _ = fmt.wait();
 
-    let mut follower = cmd()
+    let mut alias = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "follow",
-            "testpool",
+            "demo",
             "--tail",
-            "2",
+            "1",
             "--jsonl",
         ])
         .stdout(Stdio::piped())
         .spawn()
-        .expect("follow");
-    let stdout = follower.stdout.take().expect("stdout");
-    let mut reader = BufReader::new(stdout);
-    let mut follower_lines = Vec::new();
-    for _ in 0..2 {
-        let mut line = String::new();
-        let read = reader.read_line(&mut line).expect("read line");
-        assert!(read > 0, "expected a line from follow output");
-        follower_lines.push(parse_json(line.trim()));
-    }
-    let _ = follower.kill();
-    let _ = follower.wait();
-    assert_eq!(follower_lines.len(), 2);
-    assert_eq!(follower_lines[0].get("data").unwrap()["x"], 1);
-    assert_eq!(follower_lines[1].get("data").unwrap()["x"], 2);
+        .expect("follow jsonl");
+    let alias_stdout = alias.stdout.take().expect("stdout");
+    let alias_line = read_line_with_timeout(alias_stdout, Duration::from_secs(2));
+    assert!(!alias_line.is_empty(), "expected a line from follow output");
+    let alias_line = alias_line.trim_end();
+    assert!(!alias_line.contains('\n'));
+    let _ = parse_json(alias_line);
+    let _ = alias.kill();
+    let _ = alias.wait();
 }
 
 #[test]
-fn duplex_non_tty_echoes_followed_messages_without_self_echo() {
+fn audit_gaps_reports_overwritten_prefix_without_corruption() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
@@ -5410,810 +6425,4845 @@ fn duplex_non_tty_echoes_followed_messages_without_self_echo() {
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "chat",
+            "--size",
+            "1M",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
 
-    let seed_out = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "feed",
-            "chat",
-            "{\"from\":\"bob\",\"msg\":\"seed\"}",
-        ])
-        .output()
-        .expect("seed feed");
-    assert!(seed_out.status.success());
-
-    let mut duplex = cmd()
-        .args([
-            "--dir",
-            pool_dir.to_str().unwrap(),
-            "duplex",
-            "chat",
-            "--create",
-            "--me",
-            "alice",
-            "--tail",
-            "1",
-            "--jsonl",
-        ])
+    let mut feeder = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo"])
         .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
+        .stdout(Stdio::null())
         .spawn()
-        .expect("duplex");
-    let stdout = duplex.stdout.take().expect("duplex stdout");
-    let (line_tx, line_rx) = mpsc::channel::<String>();
-    thread::spawn(move || {
-        let mut reader = BufReader::new(stdout);
-        loop {
-            let mut line = String::new();
-            let read = reader.read_line(&mut line).unwrap_or(0);
-            if read == 0 {
-                break;
-            }
-            if line_tx.send(line).is_err() {
-                break;
-            }
-        }
-    });
-    let first_line = line_rx
-        .recv_timeout(Duration::from_secs(2))
-        .expect("expected seed line");
-    let first_value = parse_json(first_line.trim());
-    assert_eq!(first_value.get("data").unwrap()["from"], "bob");
+        .expect("feed");
     {
-        let stdin = duplex.stdin.as_mut().expect("duplex stdin");
-        stdin
-            .write_all(b"{\"from\":\"alice\",\"msg\":\"reply\"}\n")
-            .expect("write stdin");
+        let mut stdin = feeder.stdin.take().expect("stdin");
+        let payload = "a".repeat(8192);
+        for i in 0..200u64 {
+            writeln!(stdin, "{{\"x\":{i},\"pad\":\"{payload}\"}}").expect("write");
+        }
     }
-    assert!(
-        line_rx.recv_timeout(Duration::from_millis(500)).is_err(),
-        "expected self-suppression to avoid echoing alice message"
-    );
-    let _ = duplex.stdin.take();
-    let status = duplex.wait().expect("duplex wait");
-    assert_eq!(status.code(), Some(0), "unexpected duplex exit code");
+    assert!(feeder.wait().expect("wait").success());
 
-    let follow_out = cmd()
+    let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "follow",
-            "chat",
-            "--tail",
-            "2",
-            "--jsonl",
-            "--timeout",
-            "150ms",
+            "audit-gaps",
+            "demo",
+            "--json",
         ])
         .output()
-        .expect("follow");
-    assert!(
-        follow_out.status.code() == Some(0) || follow_out.status.code() == Some(124),
-        "unexpected follow exit code: {:?}",
-        follow_out.status.code()
+        .expect("audit-gaps");
+    assert!(output.status.success(), "{output:?}");
+    let report = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(report["missing_count"], 0);
+    assert_eq!(report["newest_seq"], 200);
+    let oldest = report["oldest_seq"].as_u64().expect("oldest");
+    assert!(oldest > 1);
+    assert_eq!(report["overwritten_count"], oldest - 1);
+    assert_eq!(report["scanned"], 200 - oldest + 1);
+    assert_eq!(
+        report["gaps"],
+        json!([{"kind": "overwritten", "start": 1, "end": oldest - 1, "count": oldest - 1}])
     );
-    let follow_lines = parse_json_lines(&follow_out.stdout);
-    assert_eq!(follow_lines.len(), 2);
-    assert_eq!(follow_lines[0].get("data").unwrap()["from"], "bob");
-    assert_eq!(follow_lines[1].get("data").unwrap()["from"], "alice");
 }
 
 #[test]
-fn duplex_remote_url_rejects_create_flag() {
+fn explain_dumps_frame_header_checksums_and_index() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "demo"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for id in 1..=3 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "demo", &format!("{{\"id\":{id}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
     let output = cmd()
+        .args(["--dir", dir, "explain", "demo", "--seq", "2", "--json"])
+        .output()
+        .expect("explain");
+    assert!(output.status.success(), "{output:?}");
+    let report = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(report["seq"], 2);
+    assert_eq!(report["on_boundary"], true);
+    assert_eq!(report["checksums"]["commit_marker_ok"], true);
+    assert!(report["checksums"]["computed_crc32c"].is_u64());
+    assert!(
+        report["header_hex"]
+            .as_str()
+            .expect("hex")
+            .starts_with("46 52 4d 31")
+    );
+    assert_eq!(report["fields"][0]["name"], "magic");
+    assert_eq!(report["neighbors"]["prev_offset"], 0);
+    let statuses = report["index"]
+        .as_array()
+        .expect("index")
+        .iter()
+        .map(|probe| probe["status"].as_str().expect("status").to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(statuses, ["ok", "ok", "ok"]);
+
+    let offset = report["offset"].as_u64().expect("offset") + 8;
+    let inside = cmd()
         .args([
-            "duplex",
-            "http://127.0.0.1:65535/chat",
-            "--create",
-            "--me",
-            "alice",
+            "--dir",
+            dir,
+            "explain",
+            "demo",
+            "--offset",
+            &offset.to_string(),
         ])
         .output()
-        .expect("duplex");
-    assert_eq!(output.status.code(), Some(2));
-    let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
-    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(message.contains("does not support --create"));
+        .expect("explain offset");
+    assert!(inside.status.success(), "{inside:?}");
+    let text = String::from_utf8_lossy(&inside.stdout);
+    assert!(text.contains("not a walked frame boundary"), "{text}");
+    assert!(text.contains("expected FRM1"), "{text}");
+
+    let missing = cmd()
+        .args(["--dir", dir, "explain", "demo", "--seq", "99"])
+        .output()
+        .expect("explain missing");
+    assert_eq!(missing.status.code(), Some(3));
 }
 
 #[test]
-fn duplex_remote_url_rejects_since_even_when_future() {
+fn audit_gaps_missing_pool_is_not_found() {
+    let temp = tempfile::tempdir().expect("tempdir");
     let output = cmd()
-        .args([
-            "duplex",
-            "http://127.0.0.1:65535/chat",
-            "--since",
-            "2999-01-01T00:00:00Z",
-        ])
+        .args(["--dir", temp.path().to_str().unwrap(), "audit-gaps", "nope"])
         .output()
-        .expect("duplex");
-    assert_eq!(output.status.code(), Some(2));
+        .expect("audit-gaps");
+    assert!(!output.status.success());
     let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
-    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(message.contains("does not support --since"));
+    assert_eq!(err["error"]["kind"], "NotFound");
 }
 
 #[test]
-fn duplex_since_future_missing_pool_reports_not_found() {
+fn activity_buckets_recent_messages() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for i in 0..3 {
+        let feed = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "demo",
+                &format!("{{\"i\":{i}}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
     let output = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "duplex",
-            "missing",
+            "activity",
+            "demo",
+            "--bucket",
+            "1h",
             "--since",
-            "2999-01-01T00:00:00Z",
+            "3h",
+            "--json",
         ])
         .output()
-        .expect("duplex");
-    assert_eq!(output.status.code(), Some(3));
-    let err = parse_error_json(&output.stderr);
-    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
-    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+        .expect("activity");
+    assert!(output.status.success(), "{output:?}");
+    let report = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    assert_eq!(report["total"], 3);
+    assert_eq!(report["bucket_ns"], 3_600_000_000_000u64);
+    let buckets = report["buckets"].as_array().expect("buckets");
+    assert!((3..=4).contains(&buckets.len()));
+    assert_eq!(buckets.last().expect("last")["count"], 3);
+    assert_eq!(report["first_seq"], 1);
+
+    let bad = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "activity",
+            "demo",
+            "--bucket",
+            "1s",
+            "--since",
+            "30d",
+        ])
+        .output()
+        .expect("activity");
+    assert_eq!(bad.status.code(), Some(2));
 }
 
 #[test]
-fn duplex_remote_happy_path_sends_and_reads() {
+fn bridge_streams_to_fifo_across_reader_churn() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
-
+    let fifo = temp.path().join("events.pipe");
     let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
             "pool",
             "create",
-            "chat",
+            "demo",
         ])
         .output()
         .expect("create");
     assert!(create.status.success());
+    let feed = |value: &str| {
+        let out = cmd()
+            .args(["--dir", pool_dir.to_str().unwrap(), "feed", "demo", value])
+            .output()
+            .expect("feed");
+        assert!(out.status.success());
+    };
 
-    let seed_out = cmd()
+    let mut bridge = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "feed",
-            "chat",
-            "{\"from\":\"bob\",\"msg\":\"seed\"}",
-        ])
-        .output()
-        .expect("seed feed");
-    assert!(seed_out.status.success());
-
-    let server = ServeProcess::start(&pool_dir);
-    let pool_url = format!("{}/chat", server.base_url);
-    let mut duplex = cmd()
-        .args([
-            "duplex", &pool_url, "--me", "alice", "--tail", "1", "--jsonl",
+            "bridge",
+            "demo",
+            "--fifo",
+            fifo.to_str().unwrap(),
         ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()
-        .expect("duplex");
-    let stdout = duplex.stdout.take().expect("duplex stdout");
-    let first_line = read_line_with_timeout(stdout, Duration::from_secs(2));
-    let first_value = parse_json(first_line.trim());
-    assert_eq!(first_value.get("data").unwrap()["from"], "bob");
+        .expect("bridge");
+    let (notice_tx, notice_rx) = mpsc::channel();
+    let stderr = bridge.stderr.take().expect("stderr");
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            let _ = notice_tx.send(line);
+        }
+    });
+    let wait_notice = |kind: &str| loop {
+        let line = notice_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("notice");
+        if parse_notice_json(&line)["notice"]["kind"] == kind {
+            break;
+        }
+    };
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !fifo.exists() {
+        assert!(Instant::now() < deadline, "fifo was not created");
+        sleep(Duration::from_millis(20));
+    }
 
-    {
-        let stdin = duplex.stdin.as_mut().expect("duplex stdin");
-        stdin
-            .write_all(b"{\"from\":\"alice\",\"msg\":\"remote-reply\"}\n")
-            .expect("write stdin");
+    for round in 1..=2 {
+        let reader = File::open(&fifo).expect("open fifo");
+        wait_notice("reader_connected");
+        feed(&format!("{{\"round\":{round}}}"));
+        let line = read_line_with_timeout(reader, Duration::from_secs(5));
+        let message = parse_json(line.trim());
+        assert_eq!(message["data"]["round"], round);
+        feed("{\"unread\":true}");
+        wait_notice("reader_disconnected");
     }
-    let _ = duplex.stdin.take();
-    let status = duplex.wait().expect("duplex wait");
-    assert_eq!(status.code(), Some(0), "unexpected duplex exit code");
 
-    let follow_out = cmd()
-        .args([
-            "follow",
-            &pool_url,
-            "--tail",
-            "2",
-            "--jsonl",
-            "--timeout",
-            "200ms",
-        ])
-        .output()
-        .expect("follow");
-    assert!(
-        follow_out.status.code() == Some(0) || follow_out.status.code() == Some(124),
-        "unexpected follow exit code: {:?}",
-        follow_out.status.code()
-    );
-    let follow_lines = parse_json_lines(&follow_out.stdout);
-    assert_eq!(follow_lines.len(), 2);
-    assert_eq!(follow_lines[0].get("data").unwrap()["from"], "bob");
-    assert_eq!(follow_lines[1].get("data").unwrap()["from"], "alice");
+    let status = Command::new("kill")
+        .args(["-TERM", &bridge.id().to_string()])
+        .status()
+        .expect("kill");
+    assert!(status.success());
+    assert!(bridge.wait().expect("wait").success());
+    assert!(!fifo.exists());
 }
 
 #[test]
-fn serve_rejects_invalid_bind() {
+fn follow_emits_drop_notice_on_stderr() {
     let temp = tempfile::tempdir().expect("tempdir");
     let pool_dir = temp.path().join("pools");
 
-    let serve = cmd()
+    let create = cmd()
         .args([
             "--dir",
             pool_dir.to_str().unwrap(),
-            "serve",
-            "--bind",
-            "nope",
+            "pool",
+            "create",
+            "--size",
+            "1M",
+            "demo",
         ])
         .output()
-        .expect("serve");
-    assert!(!serve.status.success());
-    let err = parse_error_json(&serve.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
+        .expect("create");
+    assert!(create.status.success());
+
+    let mut follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--jsonl",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("follow");
+
+    let stdout = follower.stdout.take().expect("stdout");
+    let stderr = follower.stderr.take().expect("stderr");
+
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+    });
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stderr);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line).unwrap_or(0);
+            if read == 0 {
+                break;
+            }
+            if !line.trim().is_empty() {
+                let _ = tx.send(line.clone());
+                break;
+            }
+        }
+    });
+
+    for i in 0..200u64 {
+        let payload = "a".repeat(8192);
+        let emit_out = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "demo",
+                &format!("{{\"x\":{i},\"pad\":\"{payload}\"}}"),
+            ])
+            .output()
+            .expect("feed");
+        if !emit_out.status.success() {
+            let stderr = String::from_utf8_lossy(&emit_out.stderr);
+            panic!("feed failed at {i}: {stderr}");
+        }
+    }
+
+    let notice_line = rx
+        .recv_timeout(Duration::from_secs(15))
+        .expect("drop notice");
+    let notice_json = parse_notice_json(&notice_line);
+    let notice = notice_json
+        .get("notice")
+        .and_then(|v| v.as_object())
+        .expect("notice object");
+    assert_eq!(notice.get("kind").and_then(|v| v.as_str()), Some("drop"));
+    assert_eq!(notice.get("cmd").and_then(|v| v.as_str()), Some("follow"));
+    assert_eq!(notice.get("pool").and_then(|v| v.as_str()), Some("demo"));
+    let details = notice
+        .get("details")
+        .and_then(|v| v.as_object())
+        .expect("details");
+    let dropped = details
+        .get("dropped_count")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    assert!(dropped > 0);
+    assert!(details.get("last_seen_seq").is_some());
+    assert!(details.get("next_seen_seq").is_some());
+
+    let _ = follower.kill();
+    let _ = follower.wait();
 }
 
 #[test]
-fn serve_init_help_is_available() {
-    let output = cmd()
-        .args(["serve", "init", "--help"])
+fn follow_rejects_conflicting_output_flags() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
         .output()
-        .expect("serve init help");
-    assert!(output.status.success());
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("Generate token + TLS artifacts"));
-}
+        .expect("create");
+    assert!(create.status.success());
 
-#[test]
-fn serve_check_outputs_resolved_config() {
-    let output = cmd()
-        .args(["serve", "check", "--json"])
+    let follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--tail",
+            "1",
+            "--jsonl",
+            "--format",
+            "jsonl",
+        ])
         .output()
-        .expect("serve check");
-    assert!(output.status.success());
-    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
-    let payload = parse_json(stdout);
-    let check = payload.get("check").expect("check");
-    let status = check.get("status").and_then(|v| v.as_str()).unwrap_or("");
-    assert_eq!(status, "valid");
-    let base_url = check.get("base_url").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(base_url.contains("127.0.0.1:9700"));
-    let mcp = check.get("mcp").and_then(|v| v.as_str()).unwrap_or("");
-    assert!(mcp.ends_with("/mcp"));
+        .expect("follow");
+    assert_eq!(follower.status.code().unwrap(), 2);
+    let err = parse_error_json(&follower.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("--format jsonl") || hint.contains("--jsonl"));
 }
 
 #[test]
-fn serve_check_human_uses_readable_limits_and_fingerprint() {
+fn emit_create_flag_creates_missing_pool() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let mut params = CertificateParams::new(vec!["localhost".to_string()]);
-    params
-        .subject_alt_names
-        .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
-    params
-        .subject_alt_names
-        .push(SanType::IpAddress(IpAddr::V6(Ipv6Addr::LOCALHOST)));
-    let cert = Certificate::from_params(params).expect("cert");
-    let cert_pem = cert.serialize_pem().expect("cert pem");
-    let key_pem = cert.serialize_private_key_pem();
-    let cert_path = temp.path().join("cert.pem");
-    let key_path = temp.path().join("key.pem");
-    std::fs::write(&cert_path, cert_pem).expect("write cert");
-    std::fs::write(&key_path, key_pem).expect("write key");
+    let pool_dir = temp.path().join("pools");
 
-    let output = cmd()
+    let emit_out = cmd()
         .args([
-            "serve",
-            "--tls-cert",
-            cert_path.to_str().unwrap(),
-            "--tls-key",
-            key_path.to_str().unwrap(),
-            "check",
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "autopool",
+            "{\"x\":1}",
+            "--create",
         ])
         .output()
-        .expect("serve check");
-    assert!(output.status.success());
-    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
-    assert!(stdout.contains("Configuration valid."));
-    assert!(stdout.contains("MCP:    https://127.0.0.1:9700/mcp"));
-    assert!(stdout.contains("Limits: body 1M, timeout 30s, concurrency 64"));
-    assert!(stdout.contains("Fingerprint: SHA256:"));
+        .expect("feed");
+    assert!(emit_out.status.success());
+    let value = parse_json(std::str::from_utf8(&emit_out.stdout).expect("utf8"));
+    assert!(value.get("seq").is_some());
+
+    let pool_path = pool_dir.join("autopool.plasmite");
+    assert!(pool_path.exists());
 }
 
 #[test]
-fn serve_check_json_includes_tls_fingerprint() {
+fn follow_create_flag_creates_missing_pool() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let mut params = CertificateParams::new(vec!["localhost".to_string()]);
-    params
-        .subject_alt_names
-        .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
-    params
-        .subject_alt_names
-        .push(SanType::IpAddress(IpAddr::V6(Ipv6Addr::LOCALHOST)));
-    let cert = Certificate::from_params(params).expect("cert");
-    let cert_pem = cert.serialize_pem().expect("cert pem");
-    let key_pem = cert.serialize_private_key_pem();
-    let cert_path = temp.path().join("cert.pem");
-    let key_path = temp.path().join("key.pem");
-    std::fs::write(&cert_path, cert_pem).expect("write cert");
-    std::fs::write(&key_path, key_pem).expect("write key");
+    let pool_dir = temp.path().join("pools");
 
-    let output = cmd()
+    let follower = cmd()
         .args([
-            "serve",
-            "--tls-cert",
-            cert_path.to_str().unwrap(),
-            "--tls-key",
-            key_path.to_str().unwrap(),
-            "check",
-            "--json",
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "autopool",
+            "--create",
+            "--timeout",
+            "20ms",
         ])
         .output()
-        .expect("serve check");
-    assert!(output.status.success());
-    let payload = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
-    let fingerprint = payload
-        .get("check")
-        .and_then(|v| v.get("tls_fingerprint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(fingerprint.starts_with("SHA256:"));
+        .expect("follow");
+    assert_eq!(follower.status.code(), Some(124));
+    assert!(pool_dir.join("autopool.plasmite").exists());
 }
 
 #[test]
-fn serve_check_defaults_to_human_output() {
-    let output = cmd()
-        .args(["serve", "check"])
-        .output()
-        .expect("serve check");
-    assert!(output.status.success());
-    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
-    assert!(stdout.contains("Configuration valid."));
-}
+fn pool_delete_removes_pool_file() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
 
-#[test]
-fn serve_check_rejects_invalid_config() {
-    let output = cmd()
-        .args(["serve", "--bind", "0.0.0.0:0", "check"])
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "deleteme",
+        ])
         .output()
-        .expect("serve check");
-    assert!(!output.status.success());
-    let err = parse_error_json(&output.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
+        .expect("create");
+    assert!(create.status.success());
+
+    let delete = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "delete",
+            "--json",
+            "deleteme",
+        ])
+        .output()
+        .expect("delete");
+    assert!(delete.status.success());
+    let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
+    let deleted = output
+        .get("deleted")
+        .and_then(|v| v.as_array())
+        .expect("deleted array");
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(
+        deleted[0].get("pool").and_then(|v| v.as_str()),
+        Some("deleteme")
+    );
+    let failed = output
+        .get("failed")
+        .and_then(|v| v.as_array())
+        .expect("failed array");
+    assert!(failed.is_empty());
+    let pool_path = pool_dir.join("deleteme.plasmite");
+    assert!(!pool_path.exists());
 }
 
 #[test]
-fn serve_init_writes_artifacts_and_next_commands() {
+fn pool_delete_multiple_best_effort_mixed_results() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let out_dir = temp.path().join("serve-init");
-    let output = cmd()
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
         .args([
-            "serve",
-            "init",
-            "--output-dir",
-            out_dir.to_str().unwrap(),
-            "--bind",
-            "0.0.0.0:9700",
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "a",
+            "b",
         ])
         .output()
-        .expect("serve init");
-    assert!(output.status.success());
-
-    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
-    let payload = parse_json(stdout);
-    let artifacts = payload
-        .get("init")
-        .and_then(|v| v.get("artifact_paths"))
-        .expect("artifact_paths");
-    let token_file = artifacts
-        .get("token_file")
-        .and_then(|v| v.as_str())
-        .expect("token_file");
-    let tls_cert = artifacts
-        .get("tls_cert")
-        .and_then(|v| v.as_str())
-        .expect("tls_cert");
-    let tls_key = artifacts
-        .get("tls_key")
-        .and_then(|v| v.as_str())
-        .expect("tls_key");
-    assert!(Path::new(token_file).exists());
-    assert!(Path::new(tls_cert).exists());
-    assert!(Path::new(tls_key).exists());
+        .expect("create");
+    assert!(create.status.success());
 
-    let token = std::fs::read_to_string(token_file).expect("read token");
-    assert!(!token.trim().is_empty());
-    assert!(
-        !stdout.contains(token.trim()),
-        "token value should not be echoed to stdout"
-    );
+    let delete = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "delete",
+            "--json",
+            "a",
+            "missing",
+            "b",
+        ])
+        .output()
+        .expect("delete");
+    assert_eq!(delete.status.code(), Some(3));
 
-    let server_commands = payload
-        .get("init")
-        .and_then(|v| v.get("server_commands"))
+    let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
+    let deleted = output
+        .get("deleted")
         .and_then(|v| v.as_array())
-        .expect("server_commands");
-    assert!(server_commands.iter().any(|v| {
-        v.as_str()
-            .unwrap_or("")
-            .contains("plasmite serve --bind 0.0.0.0:9700 --allow-non-loopback")
-    }));
-    let client_commands = payload
-        .get("init")
-        .and_then(|v| v.get("client_commands"))
+        .expect("deleted array");
+    assert_eq!(deleted.len(), 2);
+    let deleted_names = deleted
+        .iter()
+        .filter_map(|entry| entry.get("pool").and_then(|v| v.as_str()))
+        .collect::<Vec<_>>();
+    assert!(deleted_names.contains(&"a"));
+    assert!(deleted_names.contains(&"b"));
+
+    let failed = output
+        .get("failed")
         .and_then(|v| v.as_array())
-        .expect("client_commands");
-    assert!(
-        client_commands
-            .iter()
-            .any(|v| v.as_str().unwrap_or("").contains("plasmite feed")),
-        "expected plasmite feed client command"
+        .expect("failed array");
+    assert_eq!(failed.len(), 1);
+    assert_eq!(
+        failed[0].get("pool").and_then(|v| v.as_str()),
+        Some("missing")
     );
-    let tls_fingerprint = payload
-        .get("init")
-        .and_then(|v| v.get("tls_fingerprint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(tls_fingerprint.starts_with("SHA256:"));
+    let error = failed[0]
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(error.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+
+    assert!(!pool_dir.join("a.plasmite").exists());
+    assert!(!pool_dir.join("b.plasmite").exists());
 }
 
 #[test]
-fn serve_init_requires_force_for_existing_artifacts() {
+fn pool_delete_multiple_with_invalid_ref_continues() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let out_dir = temp.path().join("serve-init");
+    let pool_dir = temp.path().join("pools");
 
-    let first = cmd()
-        .args(["serve", "init", "--output-dir", out_dir.to_str().unwrap()])
+    let create = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "create", "ok"])
         .output()
-        .expect("first serve init");
-    assert!(first.status.success());
+        .expect("create");
+    assert!(create.status.success());
 
-    let second = cmd()
-        .args(["serve", "init", "--output-dir", out_dir.to_str().unwrap()])
+    let delete = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "delete",
+            "--json",
+            "ok",
+            "http://127.0.0.1:9700/demo",
+        ])
         .output()
-        .expect("second serve init");
-    assert!(!second.status.success());
-    let err = parse_error_json(&second.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "AlreadyExists");
-    let hint = err
+        .expect("delete");
+    assert_eq!(delete.status.code(), Some(2));
+
+    let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
+    let deleted = output
+        .get("deleted")
+        .and_then(|v| v.as_array())
+        .expect("deleted array");
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].get("pool").and_then(|v| v.as_str()), Some("ok"));
+
+    let failed = output
+        .get("failed")
+        .and_then(|v| v.as_array())
+        .expect("failed array");
+    assert_eq!(failed.len(), 1);
+    assert_eq!(
+        failed[0].get("pool").and_then(|v| v.as_str()),
+        Some("http://127.0.0.1:9700/demo")
+    );
+    let error = failed[0]
         .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("--force"));
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(error.get("kind").and_then(|v| v.as_str()), Some("Usage"));
+    assert!(!pool_dir.join("ok.plasmite").exists());
 }
 
 #[test]
-fn serve_init_tty_reports_created_and_overwritten() {
+fn pool_delete_defaults_to_table_output() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let out_dir = temp.path().join("serve-init");
+    let pool_dir = temp.path().join("pools");
 
-    let first = cmd_tty(&[
-        "--color",
-        "never",
-        "serve",
-        "init",
-        "--output-dir",
-        out_dir.to_str().unwrap(),
-        "--bind",
-        "0.0.0.0:9700",
-    ]);
-    assert!(first.status.success());
-    let first_text = sanitize_tty_text(&first.stdout);
-    assert!(first_text.contains("Secure serving initialized."));
-    assert!(first_text.contains("Output directory:"));
-    assert!(first_text.contains("Files created:"));
-    assert!(first_text.contains("    pls serve \\"));
-    assert!(first_text.contains("      --bind 0.0.0.0:9700 \\"));
-    assert!(first_text.contains("      --allow-non-loopback \\"));
-    assert!(first_text.contains("      --token-file "));
-    assert!(first_text.contains("      --tls-cert "));
-    assert!(first_text.contains("      --tls-key "));
-    assert!(!first_text.contains("THIS-HOST"));
-    let feed_line = first_text
-        .lines()
-        .find(|line| line.contains("pls feed https://"))
-        .expect("feed line");
-    assert!(feed_line.contains(":9700/demo \\"));
-    let follow_line = first_text
-        .lines()
-        .find(|line| line.contains("pls follow https://"))
-        .expect("follow line");
-    assert!(follow_line.contains(":9700/demo \\"));
+    let create = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "create", "ok"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
 
-    let second = cmd_tty(&[
-        "--color",
-        "never",
-        "serve",
-        "init",
-        "--output-dir",
-        out_dir.to_str().unwrap(),
-        "--bind",
-        "0.0.0.0:9700",
-        "--force",
-    ]);
-    assert!(second.status.success());
-    let second_text = sanitize_tty_text(&second.stdout);
-    assert!(second_text.contains("Secure serving re-initialized."));
-    assert!(second_text.contains("Files overwritten:"));
+    let delete = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "delete",
+            "ok",
+            "missing",
+        ])
+        .output()
+        .expect("delete");
+    assert_eq!(delete.status.code(), Some(3));
+
+    let stdout = std::str::from_utf8(&delete.stdout).expect("utf8");
+    assert!(stdout.contains("NAME"));
+    assert!(stdout.contains("STATUS"));
+    assert!(stdout.contains("PATH"));
+    assert!(stdout.contains("DETAIL"));
+    assert!(stdout.contains("ok"));
+    assert!(stdout.contains("missing"));
+    assert!(stdout.contains("OK"));
+    assert!(stdout.contains("ERR"));
 }
 
 #[test]
-fn serve_rejects_non_loopback_without_allow() {
-    let serve = cmd()
-        .args(["serve", "--bind", "0.0.0.0:0"])
+fn pool_delete_trash_and_restore_round_trip() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "keep"])
         .output()
-        .expect("serve");
-    assert!(!serve.status.success());
-    let err = parse_error_json(&serve.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
-    let hint = err
-        .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("--allow-non-loopback"));
+        .expect("create");
+    assert!(create.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "keep", r#"{"n":1}"#])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+
+    let delete = cmd()
+        .args(["--dir", dir, "pool", "delete", "--trash", "--json", "keep"])
+        .output()
+        .expect("delete");
+    assert!(delete.status.success());
+    let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
+    let trash = output["deleted"][0]["trash"].as_str().expect("trash path");
+    assert!(std::path::Path::new(trash).exists());
+    assert!(!pool_dir.join("keep.plasmite").exists());
+
+    let restore = cmd()
+        .args(["--dir", dir, "pool", "restore", "--json", "keep"])
+        .output()
+        .expect("restore");
+    assert!(restore.status.success());
+    assert!(pool_dir.join("keep.plasmite").exists());
+    let fetch = cmd()
+        .args(["--dir", dir, "fetch", "keep", "1"])
+        .output()
+        .expect("fetch");
+    assert!(fetch.status.success());
+
+    let again = cmd()
+        .args(["--dir", dir, "pool", "restore", "keep"])
+        .output()
+        .expect("restore again");
+    assert_eq!(again.status.code(), Some(3));
 }
 
 #[test]
-fn serve_non_loopback_write_requires_token_file() {
-    let serve = cmd()
+fn pool_delete_trash_twice_keeps_both_copies() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let mut trashed = Vec::new();
+    for n in 1..=2 {
+        let create = cmd()
+            .args(["--dir", dir, "pool", "create", "keep"])
+            .output()
+            .expect("create");
+        assert!(create.status.success());
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "keep", &format!(r#"{{"n":{n}}}"#)])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+        let delete = cmd()
+            .args(["--dir", dir, "pool", "delete", "--trash", "--json", "keep"])
+            .output()
+            .expect("delete");
+        assert!(delete.status.success());
+        let output = parse_json(std::str::from_utf8(&delete.stdout).expect("utf8"));
+        trashed.push(
+            output["deleted"][0]["trash"]
+                .as_str()
+                .expect("trash")
+                .to_string(),
+        );
+    }
+    assert_ne!(trashed[0], trashed[1]);
+    assert!(
+        trashed
+            .iter()
+            .all(|path| std::path::Path::new(path).exists())
+    );
+
+    // Restores take the newest trashed copy first, then the older one.
+    for expected in [2, 1] {
+        let restore = cmd()
+            .args(["--dir", dir, "pool", "restore", "keep"])
+            .output()
+            .expect("restore");
+        assert!(restore.status.success());
+        let fetch = cmd()
+            .args(["--dir", dir, "fetch", "keep", "1"])
+            .output()
+            .expect("fetch");
+        assert!(fetch.status.success());
+        let message = parse_json(std::str::from_utf8(&fetch.stdout).expect("utf8"));
+        assert_eq!(message["data"]["n"], json!(expected));
+        let delete = cmd()
+            .args(["--dir", dir, "pool", "delete", "keep"])
+            .output()
+            .expect("delete");
+        assert!(delete.status.success());
+    }
+}
+
+#[test]
+fn pool_delete_dry_run_lists_files_and_seqs_without_deleting() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "keep"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for n in 0..3 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "keep", &format!("{{\"n\":{n}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let preview = cmd()
         .args([
-            "serve",
-            "--bind",
-            "0.0.0.0:0",
-            "--allow-non-loopback",
-            "--access",
-            "write-only",
-            "--insecure-no-tls",
+            "--dir",
+            dir,
+            "pool",
+            "delete",
+            "--dry-run",
+            "--json",
+            "keep",
+            "missing",
         ])
         .output()
-        .expect("serve");
-    assert!(!serve.status.success());
-    let err = parse_error_json(&serve.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
-    let message = err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(message.contains("--token-file"));
-    let hint = err
-        .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("serve init"));
+        .expect("delete dry run");
+    assert_eq!(
+        preview.status.code(),
+        Some(3),
+        "exits as the real delete would"
+    );
+    let output = parse_json(std::str::from_utf8(&preview.stdout).expect("utf8"));
+    assert_eq!(output["dry_run"], true);
+    let pool = &output["pools"][0];
+    assert_eq!(pool["pool"], "keep");
+    assert_eq!(pool["oldest_seq"], 1);
+    assert_eq!(pool["newest_seq"], 3);
+    let files = pool["files"].as_array().expect("files");
+    assert!(files.iter().any(|file| {
+        file["path"]
+            .as_str()
+            .unwrap_or_default()
+            .ends_with("keep.plasmite")
+    }));
+    let total = files
+        .iter()
+        .map(|file| file["bytes"].as_u64().expect("bytes"))
+        .sum::<u64>();
+    assert_eq!(pool["bytes"].as_u64(), Some(total));
+    assert_eq!(output["failed"][0]["pool"], "missing");
+    assert!(
+        pool_dir.join("keep.plasmite").is_file(),
+        "nothing is deleted"
+    );
 }
 
 #[test]
-fn serve_non_loopback_write_requires_tls_or_insecure() {
+fn pool_seal_and_verify_detect_edited_manifests() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let token_path = temp.path().join("token.txt");
-    std::fs::write(&token_path, "secret").expect("write token");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let manifest = temp.path().join("seal.json");
+    let manifest_arg = manifest.to_str().unwrap();
 
-    let serve = cmd()
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "ledger", "empty"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let empty = cmd()
+        .args(["--dir", dir, "pool", "seal", "empty"])
+        .output()
+        .expect("seal empty");
+    assert_eq!(empty.status.code(), Some(2));
+    for n in 0..4 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "ledger", &format!("{{\"n\":{n}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let seal = cmd()
         .args([
-            "serve",
-            "--bind",
-            "0.0.0.0:0",
-            "--allow-non-loopback",
-            "--access",
-            "write-only",
-            "--token-file",
-            token_path.to_str().unwrap(),
+            "--dir",
+            dir,
+            "pool",
+            "seal",
+            "ledger",
+            "--out",
+            manifest_arg,
+            "--json",
         ])
         .output()
-        .expect("serve");
-    assert!(!serve.status.success());
-    let err = parse_error_json(&serve.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
-    let message = err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(message.contains("TLS"));
-    let hint = err
-        .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("serve init"));
+        .expect("seal");
+    assert!(seal.status.success());
+    let sealed = parse_json(std::str::from_utf8(&seal.stdout).expect("utf8"));
+    assert_eq!(sealed["seal"]["newest_seq"], 4);
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "ledger", r#"{"n":4}"#])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+
+    let verify = |expected: i32| {
+        let output = cmd()
+            .args([
+                "--dir",
+                dir,
+                "pool",
+                "verify",
+                "ledger",
+                "--manifest",
+                manifest_arg,
+                "--json",
+            ])
+            .output()
+            .expect("verify");
+        assert_eq!(output.status.code(), Some(expected));
+        parse_json(std::str::from_utf8(&output.stdout).expect("utf8"))["verify"].clone()
+    };
+    let clean = verify(0);
+    assert_eq!(clean["intact"], true);
+    assert_eq!(clean["checked"], 4);
+
+    let mut edited: Value =
+        serde_json::from_str(&std::fs::read_to_string(&manifest).expect("read")).expect("json");
+    edited["leaves"][1] = json!("00".repeat(32));
+    std::fs::write(&manifest, edited.to_string()).expect("write");
+    let tampered = verify(7);
+    assert_eq!(tampered["intact"], false);
+    assert_eq!(tampered["root_ok"], false);
+    assert_eq!(tampered["mismatched_seqs"], json!([2]));
 }
 
 #[test]
-fn serve_rejects_token_and_token_file_combination_with_init_hint() {
+fn pool_snapshot_writes_validated_copy() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let token_path = temp.path().join("token.txt");
-    std::fs::write(&token_path, "secret").expect("write token");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let out = temp.path().join("snap.plasmite");
+    let out_str = out.to_str().unwrap();
 
-    let serve = cmd()
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "live"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for n in 1..=2 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "live", &format!("{{\"n\":{n}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let snapshot = cmd()
         .args([
-            "serve",
-            "--token",
-            "dev-token",
-            "--token-file",
-            token_path.to_str().unwrap(),
+            "--dir", dir, "pool", "snapshot", "live", "--out", out_str, "--json",
         ])
         .output()
-        .expect("serve");
-    assert!(!serve.status.success());
-    let err = parse_error_json(&serve.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
-    let hint = err
+        .expect("snapshot");
+    assert!(snapshot.status.success());
+    let value = parse_json(std::str::from_utf8(&snapshot.stdout).expect("utf8"));
+    assert_eq!(value["snapshot"]["bounds"]["newest"], json!(2));
+    assert!(!temp.path().join("snap.plasmite.partial").exists());
+
+    let doctor = cmd()
+        .args(["doctor", out_str, "--json"])
+        .output()
+        .expect("doctor");
+    assert!(doctor.status.success());
+    let fetch = cmd().args(["fetch", out_str, "2"]).output().expect("fetch");
+    assert!(fetch.status.success());
+
+    let again = cmd()
+        .args(["--dir", dir, "pool", "snapshot", "live", "--out", out_str])
+        .output()
+        .expect("snapshot again");
+    assert_eq!(again.status.code(), Some(4));
+}
+
+#[test]
+fn pool_freeze_refuses_local_and_served_appends_until_unfrozen() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let feed = || {
+        cmd()
+            .args(["--dir", dir, "feed", "events", "{\"n\":1}"])
+            .output()
+            .expect("feed")
+    };
+    assert!(feed().status.success());
+
+    let freeze = cmd()
+        .args(["--dir", dir, "pool", "freeze", "events", "--json"])
+        .output()
+        .expect("freeze");
+    assert!(freeze.status.success());
+    let value = parse_json(std::str::from_utf8(&freeze.stdout).expect("utf8"));
+    assert_eq!(value["frozen"], json!(true));
+    assert_eq!(value["changed"], json!(true));
+
+    let refused = feed();
+    assert_eq!(refused.status.code(), Some(5));
+    let err = parse_error_json(&refused.stderr);
+    assert_eq!(err["error"]["kind"], "Busy");
+    assert!(
+        err["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("frozen")
+    );
+    let info = cmd()
+        .args(["--dir", dir, "pool", "info", "events", "--json"])
+        .output()
+        .expect("info");
+    let info = parse_json(std::str::from_utf8(&info.stdout).expect("utf8"));
+    assert_eq!(info["frozen"], json!(true));
+    assert_eq!(info["bounds"]["newest"], json!(1));
+
+    let server = ServeProcess::start(&pool_dir);
+    match ureq::post(&format!("{}/v0/pools/events/append", server.base_url))
+        .set("Content-Type", "application/json")
+        .send_string(&json!({"data": {"n": 2}}).to_string())
+    {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 423),
+        other => panic!("expected 423, got {other:?}"),
+    }
+    drop(server);
+
+    let unfreeze = cmd()
+        .args(["--dir", dir, "pool", "unfreeze", "events", "--json"])
+        .output()
+        .expect("unfreeze");
+    assert!(unfreeze.status.success());
+    let value = parse_json(std::str::from_utf8(&unfreeze.stdout).expect("utf8"));
+    assert_eq!(value["frozen"], json!(false));
+    assert!(feed().status.success());
+}
+
+#[cfg(not(feature = "chaos"))]
+#[test]
+fn chaos_flags_are_rejected_without_the_chaos_feature() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let output = cmd()
+        .args([
+            "--dir",
+            temp.path().to_str().unwrap(),
+            "pool",
+            "list",
+            "--chaos-drop-appends",
+            "0.5",
+        ])
+        .output()
+        .expect("pool list");
+    assert_eq!(output.status.code(), Some(2));
+    let err = parse_error_json(&output.stderr);
+    assert!(
+        err["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("chaos")
+    );
+}
+
+#[cfg(feature = "chaos")]
+#[test]
+fn chaos_flags_fail_seeded_appends_and_add_latency() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let dropped = cmd()
+        .args([
+            "--dir",
+            dir,
+            "--chaos-drop-appends",
+            "1",
+            "feed",
+            "events",
+            "{\"n\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert_eq!(dropped.status.code(), Some(5));
+    let err = parse_error_json(&dropped.stderr);
+    assert_eq!(err["error"]["kind"], "Busy");
+
+    let started = Instant::now();
+    let delayed = cmd()
+        .args([
+            "--dir",
+            dir,
+            "--chaos-delay-ms",
+            "200",
+            "feed",
+            "events",
+            "{\"n\":2}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(delayed.status.success());
+    assert!(started.elapsed() >= Duration::from_millis(200));
+
+    let bad = cmd()
+        .args(["--dir", dir, "--chaos-drop-appends", "1.5", "pool", "list"])
+        .output()
+        .expect("pool list");
+    assert_eq!(bad.status.code(), Some(2));
+}
+
+#[test]
+fn statsd_and_serve_stats_pool_record_pool_snapshots() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    for n in 1..=2 {
+        let feed = cmd()
+            .args([
+                "--dir",
+                dir,
+                "feed",
+                "events",
+                "--create",
+                &format!("{{\"n\":{n}}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let once = cmd()
+        .args(["--dir", dir, "statsd", "--once"])
+        .output()
+        .expect("statsd");
+    assert!(once.status.success());
+    let snapshot = fetch_message(&pool_dir, "_stats", 1);
+    assert_eq!(snapshot["meta"]["tags"], json!(["stats"]));
+    assert_eq!(snapshot["data"]["pool"], json!("events"));
+    assert_eq!(snapshot["data"]["count"], json!(2));
+    assert_eq!(snapshot["data"]["newest_seq"], json!(2));
+    assert_eq!(snapshot["data"]["append_rate"], json!(null));
+
+    let server =
+        ServeProcess::start_with_args(&pool_dir, &["--stats-pool", "--stats-interval", "100ms"]);
+    let start = Instant::now();
+    loop {
+        let info = cmd()
+            .args(["--dir", dir, "pool", "info", "_stats", "--json"])
+            .output()
+            .expect("info");
+        let info = parse_json(std::str::from_utf8(&info.stdout).expect("utf8"));
+        if info["bounds"]["newest"].as_u64().unwrap_or(0) >= 3 {
+            break;
+        }
+        assert!(start.elapsed() < Duration::from_secs(10), "{info}");
+        sleep(Duration::from_millis(50));
+    }
+    drop(server);
+    let snapshot = fetch_message(&pool_dir, "_stats", 3);
+    assert_eq!(snapshot["data"]["pool"], json!("events"));
+    assert!(snapshot["data"]["append_rate"].is_number());
+}
+
+#[test]
+fn backup_incremental_and_restore_round_trip() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let backup_dir = temp.path().join("backup");
+    let backup_str = backup_dir.to_str().unwrap();
+    let feed = |n: u64| {
+        let output = cmd()
+            .args(["--dir", dir, "feed", "events", &format!("{{\"n\":{n}}}")])
+            .output()
+            .expect("feed");
+        assert!(output.status.success());
+    };
+    let backup = |extra: &[&str]| {
+        let mut args = vec![
+            "--dir", dir, "backup", "events", "--to", backup_str, "--json",
+        ];
+        args.extend_from_slice(extra);
+        let output = cmd().args(&args).output().expect("backup");
+        assert!(output.status.success(), "{output:?}");
+        parse_json(std::str::from_utf8(&output.stdout).expect("utf8"))
+    };
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    feed(1);
+    assert_eq!(backup(&[])["backup"]["kind"], json!("base"));
+    feed(2);
+    feed(3);
+    let delta = backup(&["--incremental"]);
+    assert_eq!(delta["backup"]["kind"], json!("delta"));
+    assert_eq!(delta["backup"]["first"], json!(2));
+    assert_eq!(delta["backup"]["last"], json!(3));
+
+    let refused = cmd()
+        .args(["--dir", dir, "backup", "events", "--to", backup_str])
+        .output()
+        .expect("backup");
+    assert_eq!(refused.status.code(), Some(4));
+
+    let restore = cmd()
+        .args([
+            "--dir", dir, "restore", backup_str, "--to", "copy", "--json",
+        ])
+        .output()
+        .expect("restore");
+    assert!(restore.status.success());
+    let value = parse_json(std::str::from_utf8(&restore.stdout).expect("utf8"));
+    assert_eq!(value["created"], json!(true));
+    assert_eq!(value["through"], json!(3));
+
+    feed(4);
+    backup(&["--incremental"]);
+    let catch_up = cmd()
+        .args([
+            "--dir", dir, "restore", backup_str, "--to", "copy", "--json",
+        ])
+        .output()
+        .expect("restore");
+    assert!(catch_up.status.success());
+    let value = parse_json(std::str::from_utf8(&catch_up.stdout).expect("utf8"));
+    assert_eq!(value["created"], json!(false));
+    assert_eq!(value["messages"], json!(1));
+
+    let fetch = cmd()
+        .args(["--dir", dir, "fetch", "copy", "4"])
+        .output()
+        .expect("fetch");
+    assert!(fetch.status.success());
+    let message = parse_json(std::str::from_utf8(&fetch.stdout).expect("utf8"));
+    assert_eq!(message["data"]["n"], json!(4));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn pool_delete_refuses_pool_open_by_another_process_unless_forced() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "busy"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let mut follower = cmd()
+        .args(["--dir", dir, "follow", "busy", "--tail", "1", "--jsonl"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow");
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "busy", r#"{"n":1}"#])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "follower should have the pool open");
+
+    let refused = cmd()
+        .args(["--dir", dir, "pool", "delete", "--json", "busy"])
+        .output()
+        .expect("delete");
+    assert_eq!(refused.status.code(), Some(5));
+    let output = parse_json(std::str::from_utf8(&refused.stdout).expect("utf8"));
+    assert_eq!(output["failed"][0]["error"]["kind"], "Busy");
+    let message = output["failed"][0]["error"]["message"]
+        .as_str()
+        .expect("message");
+    assert!(message.contains(&format!("pid {}", follower.id())));
+    assert!(pool_dir.join("busy.plasmite").exists());
+
+    let forced = cmd()
+        .args(["--dir", dir, "pool", "delete", "--force", "busy"])
+        .output()
+        .expect("forced delete");
+    let _ = follower.kill();
+    let _ = follower.wait();
+    assert!(forced.status.success());
+    assert!(!pool_dir.join("busy.plasmite").exists());
+}
+
+#[test]
+fn pool_who_lists_registered_followers_with_cursor() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "watched"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "watched", r#"{"n":1}"#])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+
+    let mut follower = cmd()
+        .args(["--dir", dir, "follow", "watched", "--tail", "1", "--jsonl"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected follow output");
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let handle = loop {
+        let who = cmd()
+            .args(["--dir", dir, "pool", "who", "--json", "watched"])
+            .output()
+            .expect("who");
+        assert!(who.status.success());
+        let output = parse_json(std::str::from_utf8(&who.stdout).expect("utf8"));
+        let found = output["handles"].as_array().and_then(|handles| {
+            handles
+                .iter()
+                .find(|h| h["pid"] == follower.id() && h["cursor"] == 1)
+                .cloned()
+        });
+        if let Some(handle) = found {
+            break handle;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "follower never registered: {output}"
+        );
+        thread::sleep(Duration::from_millis(100));
+    };
+    assert_eq!(handle["role"], "reader");
+    assert_eq!(handle["command"], "follow");
+    assert!(handle["started"].as_str().is_some());
+
+    let _ = follower.kill();
+    let _ = follower.wait();
+    let who = cmd()
+        .args(["--dir", dir, "pool", "who", "--json", "watched"])
+        .output()
+        .expect("who after exit");
+    let output = parse_json(std::str::from_utf8(&who.stdout).expect("utf8"));
+    assert_eq!(output["handles"].as_array().map(Vec::len), Some(0));
+}
+
+#[test]
+fn errors_are_json_on_non_tty_stderr() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let follower = cmd()
+        .args([
+            "--color",
+            "always",
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "missing",
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(follower.status.code().unwrap(), 3);
+
+    let err = parse_error_json(&follower.stderr);
+    let inner = err
         .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("serve init"));
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(
+        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
+        "NotFound"
+    );
+    assert!(
+        !inner
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        inner
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap()
+            .ends_with("missing.plasmite")
+    );
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("--create") || hint.contains("exact command"));
+}
+
+#[test]
+fn clap_errors_are_concise_in_json() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let bad = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--definitely-not-a-flag",
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(bad.status.code().unwrap(), 2);
+
+    let err = parse_error_json(&bad.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()).unwrap(), "Usage");
+    let message = inner.get("message").and_then(|v| v.as_str()).unwrap();
+    assert!(!message.contains('\n'));
+    assert!(!message.contains("Usage:"));
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("--help"));
+}
+
+#[test]
+fn misuse_feedback_matrix_is_actionable_across_command_families() {
+    let cases: [(&[&str], &str, &str); 5] = [
+        (
+            &["feed", "demo", "{\"x\":1}", "--retry-delay", "1s"],
+            "--retry-delay requires --retry",
+            "Add --retry",
+        ),
+        (
+            &["doctor", "demo", "--all"],
+            "--all cannot be combined",
+            "Use --all by itself",
+        ),
+        (
+            &["serve", "--bind", "nope", "check"],
+            "invalid bind address",
+            "host:port",
+        ),
+        (&["completion", "nope"], "invalid value", "plasmite --help"),
+        (
+            &["version", "extra"],
+            "unexpected argument",
+            "version --help",
+        ),
+    ];
+
+    for (args, expected_message_fragment, expected_hint_fragment) in cases {
+        let output = cmd().args(args).output().expect("command");
+        assert_actionable_usage_feedback(
+            &output,
+            expected_message_fragment,
+            expected_hint_fragment,
+        );
+    }
+}
+
+#[test]
+fn follow_missing_pool_has_actionable_hint() {
+    // This assertion currently checks the fallback exact command hint text for missing pools.
+    let output = cmd().args(["follow", "-n", "1"]).output().expect("follow");
+    assert_eq!(output.status.code().unwrap(), 2);
+    let err = parse_error_json(&output.stderr);
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("pool ref"));
+    assert!(hint.contains("plasmite follow chat -n 1"));
+}
+
+#[test]
+fn emit_missing_pool_hint_suggests_create() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let output = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "missing",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert_eq!(output.status.code(), Some(3));
+
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("--create"));
+    assert!(hint.contains("exact command"));
+    assert!(hint.contains("plasmite feed missing --create"));
+}
+
+#[test]
+fn follow_missing_pool_hint_suggests_create() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let output = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "follow", "missing"])
+        .output()
+        .expect("follow");
+    assert_eq!(output.status.code(), Some(3));
+
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("--create"));
+    assert!(hint.contains("exact command"));
+    assert!(hint.contains("plasmite follow missing --create"));
+}
+
+#[test]
+fn already_exists_has_hint_and_path() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "testpool",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let again = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "testpool",
+        ])
+        .output()
+        .expect("create again");
+    assert_eq!(again.status.code().unwrap(), 4);
+    let err = parse_error_json(&again.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(
+        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
+        "AlreadyExists"
+    );
+    assert!(
+        inner
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap()
+            .ends_with("testpool.plasmite")
+    );
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("different name") || hint.contains("remove"));
+}
+
+#[test]
+fn permission_error_has_hint_and_causes() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("readonly");
+    std::fs::create_dir_all(&pool_dir).expect("mkdir");
+
+    let mut perms = std::fs::metadata(&pool_dir)
+        .expect("metadata")
+        .permissions();
+    let original_mode = perms.mode();
+    perms.set_readonly(true);
+    std::fs::set_permissions(&pool_dir, perms).expect("set perms");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "testpool",
+        ])
+        .output()
+        .expect("create");
+    assert_eq!(create.status.code().unwrap(), 6);
+    let err = parse_error_json(&create.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert!(
+        !inner
+            .get("hint")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .is_empty()
+    );
+    let empty = Vec::new();
+    let causes = inner
+        .get("causes")
+        .and_then(|v| v.as_array())
+        .unwrap_or(&empty);
+    if causes.is_empty() {
+        eprintln!(
+            "warning: Permission/Io error had no causes; stderr={}",
+            String::from_utf8_lossy(&create.stderr)
+        );
+    }
+
+    let mut perms = std::fs::metadata(&pool_dir)
+        .expect("metadata")
+        .permissions();
+    perms.set_mode(original_mode);
+    std::fs::set_permissions(&pool_dir, perms).expect("unset perms");
+}
+
+#[test]
+fn permission_denied_matrix_for_write_paths() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("readonly-pools");
+    std::fs::create_dir_all(&pool_dir).expect("mkdir");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "base",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let mut perms = std::fs::metadata(&pool_dir)
+        .expect("metadata")
+        .permissions();
+    let original_mode = perms.mode();
+    perms.set_readonly(true);
+    std::fs::set_permissions(&pool_dir, perms).expect("set perms");
+
+    let output_create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "other",
+        ])
+        .output()
+        .expect("create denied");
+    assert!(
+        matches!(output_create.status.code(), Some(6) | Some(8)),
+        "unexpected create status: {:?}",
+        output_create.status.code()
+    );
+    let output_json = parse_error_json(&output_create.stderr);
+    let inner_create = output_json
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert!(
+        matches!(
+            inner_create.get("kind").and_then(|v| v.as_str()),
+            Some("Permission" | "Io")
+        ),
+        "unexpected create kind: {:?}",
+        inner_create.get("kind")
+    );
+
+    let output_delete = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "delete",
+            "base",
+        ])
+        .output()
+        .expect("delete denied");
+    assert_eq!(output_delete.status.code(), Some(8));
+
+    let restore = std::fs::Permissions::from_mode(original_mode);
+    std::fs::set_permissions(&pool_dir, restore).expect("unset perms");
+}
+
+#[test]
+fn truncated_pool_file_variants_are_rejected() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("mkdir");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "probe",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let pool_path = pool_dir.join("probe.plasmite");
+    let valid = std::fs::read(&pool_path).expect("read valid pool");
+
+    let mut cases = Vec::new();
+    cases.push(("empty", Vec::new()));
+    cases.push(("one-byte", valid[..1].to_vec()));
+    cases.push(("half", valid[..valid.len() / 2].to_vec()));
+    let mut truncated = valid.clone();
+    truncated.truncate(valid.len().saturating_sub(128.min(valid.len())));
+    cases.push(("truncated-end", truncated));
+    cases.push(("zero-header", vec![0; 4096]));
+
+    for (case_name, bytes) in cases {
+        std::fs::write(&pool_path, &bytes).expect("write mutated pool");
+        let output = cmd()
+            .args(["--dir", pool_dir.to_str().unwrap(), "pool", "info", "probe"])
+            .output()
+            .expect("info");
+        assert_ne!(
+            output.status.code(),
+            Some(0),
+            "{case_name} unexpectedly succeeded"
+        );
+        assert!(
+            !output.stderr.is_empty(),
+            "{case_name} should return structured error stderr"
+        );
+        let error_json = parse_error_json(&output.stderr);
+        let inner = error_json
+            .get("error")
+            .and_then(|v| v.as_object())
+            .expect("error object");
+        assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Corrupt"));
+    }
+}
+
+#[test]
+fn corrupt_pool_has_hint_and_path() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("mkdir");
+    let pool_path = pool_dir.join("bad.plasmite");
+    std::fs::write(&pool_path, b"NOPE").expect("write");
+
+    let info = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "info", "bad"])
+        .output()
+        .expect("info");
+    assert_eq!(info.status.code().unwrap(), 7);
+    let err = parse_error_json(&info.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(
+        inner.get("kind").and_then(|v| v.as_str()).unwrap(),
+        "Corrupt"
+    );
+    assert!(
+        inner
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap()
+            .ends_with("bad.plasmite")
+    );
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("Recreate") || hint.contains("recreate"));
+}
+
+#[test]
+fn doctor_reports_ok_as_json() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "doctorpool",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let doctor = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "doctor",
+            "doctorpool",
+            "--json",
+        ])
+        .output()
+        .expect("doctor");
+    assert!(doctor.status.success());
+    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
+    let reports = output
+        .get("reports")
+        .and_then(|v| v.as_array())
+        .expect("reports array");
+    assert_eq!(reports.len(), 1);
+    let report = reports[0].as_object().expect("report object");
+    assert_eq!(report.get("status").and_then(|v| v.as_str()), Some("ok"));
+}
+
+#[test]
+fn doctor_repair_is_a_no_op_on_a_healthy_journaled_pool() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args([
+            "--dir",
+            dir,
+            "pool",
+            "create",
+            "journaled",
+            "--intent-journal",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "journaled", "{\"n\":1}"])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+
+    let doctor = cmd()
+        .args(["--dir", dir, "doctor", "journaled", "--repair", "--json"])
+        .output()
+        .expect("doctor");
+    assert!(doctor.status.success());
+    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
+    let report = &output["reports"][0];
+    assert_eq!(report["status"], "ok");
+    assert_eq!(report["last_good_seq"], 1);
+
+    let remote = cmd()
+        .args(["doctor", "http://127.0.0.1:9/journaled", "--repair"])
+        .output()
+        .expect("doctor");
+    assert_eq!(remote.status.code(), Some(2));
+}
+
+#[test]
+fn doctor_reports_corrupt_and_exit_code() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("mkdir");
+    let pool_path = pool_dir.join("bad.plasmite");
+    std::fs::write(&pool_path, b"NOPE").expect("write");
+
+    let doctor = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "doctor",
+            "bad",
+            "--json",
+        ])
+        .output()
+        .expect("doctor");
+    assert_eq!(doctor.status.code().unwrap(), 7);
+    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
+    let reports = output
+        .get("reports")
+        .and_then(|v| v.as_array())
+        .expect("reports array");
+    let report = reports[0].as_object().expect("report object");
+    assert_eq!(
+        report.get("status").and_then(|v| v.as_str()),
+        Some("corrupt")
+    );
+}
+
+#[test]
+fn doctor_all_reports_mixed_ok_and_corrupt() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "create", "ok"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    std::fs::create_dir_all(&pool_dir).expect("mkdir");
+    let pool_path = pool_dir.join("bad.plasmite");
+    std::fs::write(&pool_path, b"NOPE").expect("write");
+
+    let doctor = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "doctor",
+            "--all",
+            "--json",
+        ])
+        .output()
+        .expect("doctor");
+    assert_eq!(doctor.status.code().unwrap(), 7);
+    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
+    let reports = output
+        .get("reports")
+        .and_then(|v| v.as_array())
+        .expect("reports array");
+    let statuses = reports
+        .iter()
+        .filter_map(|report| report.get("status").and_then(|v| v.as_str()))
+        .collect::<Vec<_>>();
+    assert!(statuses.contains(&"ok"));
+    assert!(statuses.contains(&"corrupt"));
+}
+
+#[test]
+fn doctor_requires_pool_or_all() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let doctor = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "doctor"])
+        .output()
+        .expect("doctor");
+    assert_eq!(doctor.status.code(), Some(2));
+    let stderr = std::str::from_utf8(&doctor.stderr).expect("utf8");
+    assert!(stderr.contains("Usage: plasmite doctor"));
+    assert!(stderr.contains("Diagnose pool health"));
+}
+
+#[test]
+fn doctor_defaults_to_human_output() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "doctorpool",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let doctor = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "doctor", "doctorpool"])
+        .output()
+        .expect("doctor");
+    assert!(doctor.status.success());
+    let stdout = std::str::from_utf8(&doctor.stdout).expect("utf8");
+    assert!(stdout.contains("OK: doctorpool"));
+}
+
+#[test]
+fn doctor_tty_reports_count_and_seq_range_for_healthy_pool() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "doctorpool",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    for idx in 1..=4 {
+        let feed = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "doctorpool",
+                &format!("{{\"x\":{idx}}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let doctor = cmd_tty(&[
+        "--color",
+        "never",
+        "--dir",
+        pool_dir.to_str().unwrap(),
+        "doctor",
+        "doctorpool",
+    ]);
+    assert!(doctor.status.success());
+    let stdout = sanitize_tty_text(&doctor.stdout);
+    assert!(stdout.contains("doctorpool: healthy"));
+    assert!(stdout.contains("messages:  4 (seq 1..4)"));
+    assert!(stdout.contains("checked:   header, index, ring — 0 issues"));
+}
+
+#[test]
+fn doctor_all_tty_uses_pool_names_and_message_counts() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "tour-main",
+            "tour-aux",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    for idx in 1..=4 {
+        let feed = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "tour-main",
+                &format!("{{\"x\":{idx}}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let doctor = cmd_tty(&[
+        "--color",
+        "never",
+        "--dir",
+        pool_dir.to_str().unwrap(),
+        "doctor",
+        "--all",
+    ]);
+    assert!(doctor.status.success());
+    let stdout = sanitize_tty_text(&doctor.stdout);
+    assert!(stdout.contains("All 2 pools healthy."));
+    assert!(stdout.contains("tour-main"));
+    assert!(stdout.contains("4 messages"));
+    assert!(stdout.contains("tour-aux"));
+    assert!(stdout.contains("0 messages"));
+    assert!(stdout.contains("0 issues"));
+    assert!(!stdout.contains(".plasmite"));
+}
+
+#[test]
+fn doctor_rejects_pool_with_all() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let doctor = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "doctor",
+            "foo",
+            "--all",
+        ])
+        .output()
+        .expect("doctor");
+    assert!(!doctor.status.success());
+    let err = parse_error_json(&doctor.stderr);
+    let inner = err
+        .get("error")
+        .and_then(|v| v.as_object())
+        .expect("error object");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
+    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(message.contains("--all cannot be combined"));
+}
+
+#[test]
+fn doctor_missing_pool_reports_not_found() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let doctor = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "doctor", "missing"])
+        .output()
+        .expect("doctor");
+    assert!(!doctor.status.success());
+    let err = parse_error_json(&doctor.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "NotFound");
+}
+
+#[test]
+fn op_timeout_stops_deep_doctor_scans_with_busy() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "big", "--size", "16M"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let mut feed = cmd()
+        .args(["--dir", dir, "feed", "big"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = feed.stdin.as_mut().expect("stdin");
+        let lines = (0..50_000)
+            .map(|n| format!("{{\"n\":{n}}}\n"))
+            .collect::<String>();
+        stdin.write_all(lines.as_bytes()).expect("write stdin");
+    }
+    assert!(feed.wait().expect("feed").success());
+
+    let doctor = cmd()
+        .args([
+            "--dir",
+            dir,
+            "--op-timeout",
+            "1ms",
+            "doctor",
+            "big",
+            "--deep",
+            "--max-frames",
+            "1000000",
+        ])
+        .output()
+        .expect("doctor");
+    assert_eq!(doctor.status.code(), Some(5));
+    let err = parse_error_json(&doctor.stderr);
+    assert_eq!(err["error"]["kind"], "Busy");
+    assert!(
+        err["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("timed out")
+    );
+
+    let zero = cmd()
+        .args(["--dir", dir, "--op-timeout", "0s", "pool", "list"])
+        .output()
+        .expect("pool list");
+    assert_eq!(zero.status.code(), Some(2));
+    let list = cmd()
+        .args([
+            "--dir",
+            dir,
+            "--op-timeout",
+            "30s",
+            "pool",
+            "list",
+            "--json",
+        ])
+        .output()
+        .expect("pool list");
+    assert!(list.status.success());
+}
+
+#[test]
+fn doctor_remote_url_validates_over_serve() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let feed = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+    let pool_url = format!("{}/demo", server.base_url);
+    let doctor = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "doctor",
+            &pool_url,
+            "--deep",
+            "--max-frames",
+            "10",
+            "--json",
+        ])
+        .output()
+        .expect("doctor");
+    assert!(
+        doctor.status.success(),
+        "{}",
+        String::from_utf8_lossy(&doctor.stderr)
+    );
+    let output = parse_json(std::str::from_utf8(&doctor.stdout).expect("utf8"));
+    let report = &output["reports"][0];
+    assert_eq!(report["status"], json!("ok"));
+    assert_eq!(report["pool_ref"], json!(pool_url));
+
+    let missing = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "doctor",
+            &format!("{}/absent", server.base_url),
+        ])
+        .output()
+        .expect("doctor missing");
+    assert_eq!(missing.status.code(), Some(3));
+}
+
+#[test]
+fn emit_remote_url_happy_path_appends_message() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+    let pool_url = format!("{}/demo", server.base_url);
+    let emit_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            &pool_url,
+            "{\"x\":1}",
+            "--tag",
+            "ping",
+        ])
+        .output()
+        .expect("feed");
+    assert!(emit_out.status.success());
+    let value = parse_json(std::str::from_utf8(&emit_out.stdout).expect("utf8"));
+    assert_eq!(value.get("seq").and_then(|v| v.as_u64()), Some(1));
+    assert!(value.get("data").is_none());
+    assert_eq!(
+        value.get("meta").and_then(|v| v.get("tags")),
+        Some(&json!(["ping"]))
+    );
+}
+
+#[test]
+fn emit_remote_url_rejects_api_shaped_path() {
+    let output = cmd()
+        .args([
+            "feed",
+            "http://localhost:9170/v0/pools/demo/append",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    assert_eq!(
+        err.get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Usage")
+    );
+}
+
+#[test]
+fn emit_remote_url_rejects_trailing_slash() {
+    let output = cmd()
+        .args(["feed", "http://localhost:9170/demo/", "{\"x\":1}"])
+        .output()
+        .expect("feed");
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    assert_eq!(
+        err.get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Usage")
+    );
+}
+
+#[test]
+fn emit_remote_url_rejects_create_flag() {
+    let output = cmd()
+        .args([
+            "feed",
+            "http://localhost:9170/demo",
+            "--create",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    assert_eq!(
+        err.get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Usage")
+    );
+    let message = err
+        .get("error")
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(message.contains("does not support --create"));
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("server-side"));
+}
+
+#[test]
+fn emit_remote_create_rejected() {
+    let output = cmd()
+        .args([
+            "feed",
+            "http://localhost:9170/demo",
+            "--create",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert_eq!(output.status.code(), Some(2));
+
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
+    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(message.contains("does not support --create"));
+    let hint = inner.get("hint").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(hint.contains("server-side"));
+}
+
+#[test]
+fn follow_remote_url_rejects_create_flag() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let output = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "http://127.0.0.1:65535/demo",
+            "--create",
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(output.status.code(), Some(2));
+
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
+    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(message.contains("does not support --create"));
+}
+
+#[test]
+fn emit_remote_url_auth_errors_propagate() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let server = ServeProcess::start_with_args(&pool_dir, &["--token", "secret-token"]);
+    let pool_url = format!("{}/demo", server.base_url);
+    let output = cmd()
+        .args(["feed", &pool_url, "{\"x\":1}"])
+        .output()
+        .expect("feed");
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    assert_eq!(
+        err.get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Permission")
+    );
+}
+
+#[test]
+fn emit_remote_url_accepts_token_and_token_file_flags() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let server = ServeProcess::start_with_args(&pool_dir, &["--token", "secret-token"]);
+    let pool_url = format!("{}/demo", server.base_url);
+
+    let with_token = cmd()
+        .args(["feed", &pool_url, "{\"x\":1}", "--token", "secret-token"])
+        .output()
+        .expect("feed");
+    assert!(with_token.status.success());
+
+    let token_file = temp.path().join("token.txt");
+    std::fs::write(&token_file, "secret-token\n").expect("write token file");
+    let with_token_file = cmd()
+        .args([
+            "feed",
+            &pool_url,
+            "{\"x\":2}",
+            "--token-file",
+            token_file.to_str().unwrap(),
+        ])
+        .output()
+        .expect("feed");
+    assert!(with_token_file.status.success());
+}
+
+#[test]
+fn emit_and_follow_local_reject_remote_auth_tls_flags() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let cert_path = temp.path().join("dev-cert.pem");
+    std::fs::write(&cert_path, "not-a-real-cert\n").expect("write cert");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let feed = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":1}",
+            "--token",
+            "devtoken",
+        ])
+        .output()
+        .expect("feed");
+    assert_eq!(feed.status.code(), Some(2));
+    let feed_err = parse_error_json(&feed.stderr);
+    assert_eq!(
+        feed_err
+            .get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Usage")
+    );
+
+    let follow = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "demo",
+            "--tail",
+            "0",
+            "--timeout",
+            "100ms",
+            "--tls-ca",
+            cert_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("follow");
+    assert_eq!(follow.status.code(), Some(2));
+    let follow_err = parse_error_json(&follow.stderr);
+    assert_eq!(
+        follow_err
+            .get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Usage")
+    );
+}
+
+#[test]
+fn follow_remote_tls_ca_and_skip_verify_work() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let feed = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+
+    let mut params = CertificateParams::new(vec!["localhost".to_string()]);
+    params
+        .subject_alt_names
+        .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    params
+        .subject_alt_names
+        .push(SanType::IpAddress(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    let cert = Certificate::from_params(params).expect("cert");
+    let cert_pem = cert.serialize_pem().expect("cert pem");
+    let key_pem = cert.serialize_private_key_pem();
+    let cert_path = temp.path().join("cert.pem");
+    let key_path = temp.path().join("key.pem");
+    std::fs::write(&cert_path, cert_pem).expect("write cert");
+    std::fs::write(&key_path, key_pem).expect("write key");
+
+    let server = ServeProcess::start_with_args_and_scheme(
+        &pool_dir,
+        &[
+            "--tls-cert",
+            cert_path.to_str().unwrap(),
+            "--tls-key",
+            key_path.to_str().unwrap(),
+        ],
+        "https",
+    );
+    let pool_url = format!("{}/demo", server.base_url);
+
+    let trusted = cmd()
+        .args([
+            "follow",
+            &pool_url,
+            "--tail",
+            "1",
+            "--one",
+            "--jsonl",
+            "--timeout",
+            "2s",
+            "--tls-ca",
+            cert_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("follow");
+    assert!(
+        trusted.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&trusted.stderr)
+    );
+
+    let skipped = cmd()
+        .args([
+            "follow",
+            &pool_url,
+            "--tail",
+            "1",
+            "--one",
+            "--jsonl",
+            "--timeout",
+            "2s",
+            "--tls-skip-verify",
+        ])
+        .output()
+        .expect("follow");
+    assert!(
+        skipped.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&skipped.stderr)
+    );
+}
+
+#[test]
+fn follow_remote_url_happy_path_reads_recent_messages() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let first = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":1}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(first.status.success());
+    let second = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "demo",
+            "{\"x\":2}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(second.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+    let pool_url = format!("{}/demo", server.base_url);
+    let follower = cmd()
+        .args([
+            "follow",
+            &pool_url,
+            "--tail",
+            "1",
+            "--one",
+            "--jsonl",
+            "--timeout",
+            "2s",
+        ])
+        .output()
+        .expect("follow");
+    assert!(
+        follower.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&follower.stderr)
+    );
+    let value = parse_json(std::str::from_utf8(&follower.stdout).expect("utf8").trim());
+    assert_eq!(value.get("seq").and_then(|v| v.as_u64()), Some(2));
+    assert_eq!(value.get("data").and_then(|v| v.get("x")), Some(&json!(2)));
+}
+
+#[test]
+fn follow_remote_fails_over_to_next_remote() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for x in 1..=2 {
+        let feed = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "demo",
+                &format!("{{\"x\":{x}}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let server = ServeProcess::start(&pool_dir);
+    let dead_url = format!("http://127.0.0.1:{}/demo", pick_port().expect("port"));
+    let live_url = format!("{}/demo", server.base_url);
+    let follower = cmd()
+        .args([
+            "follow",
+            "--remote",
+            &dead_url,
+            "--remote",
+            &live_url,
+            "--tail",
+            "1",
+            "--one",
+            "--jsonl",
+            "--timeout",
+            "2s",
+        ])
+        .output()
+        .expect("follow");
+    assert!(
+        follower.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&follower.stderr)
+    );
+    let value = parse_json(std::str::from_utf8(&follower.stdout).expect("utf8").trim());
+    assert_eq!(value.get("seq").and_then(|v| v.as_u64()), Some(2));
+    let stderr = String::from_utf8_lossy(&follower.stderr);
+    assert!(stderr.contains("failover"), "stderr={stderr}");
+}
+
+#[test]
+fn follow_remote_url_supports_tag_filter() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    for (payload, tag) in [("{\"x\":1}", "drop"), ("{\"x\":2}", "keep")] {
+        let emit_out = cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "demo",
+                payload,
+                "--tag",
+                tag,
+            ])
+            .output()
+            .expect("feed");
+        assert!(emit_out.status.success());
+    }
+
+    let server = ServeProcess::start(&pool_dir);
+    let pool_url = format!("{}/demo", server.base_url);
+    let mut follower = cmd()
+        .args([
+            "follow", &pool_url, "--tail", "10", "--jsonl", "--tag", "keep",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    assert!(!line.is_empty(), "expected a line from follow output");
+    let value = parse_json(line.trim());
+    assert_eq!(value.get("data").and_then(|v| v.get("x")), Some(&json!(2)));
+    let _ = follower.kill();
+    let _ = follower.wait();
+}
+
+#[test]
+fn follow_remote_url_rejects_api_shaped_path() {
+    let output = cmd()
+        .args([
+            "follow",
+            "http://localhost:9170/v0/pools/demo/tail",
+            "--jsonl",
+        ])
+        .output()
+        .expect("follow");
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    assert_eq!(
+        err.get("error")
+            .and_then(|v| v.get("kind"))
+            .and_then(|v| v.as_str()),
+        Some("Usage")
+    );
+}
+
+#[test]
+fn follow_remote_url_rejects_since_and_replay() {
+    let pool_url = "http://localhost:9170/demo";
+
+    let since = cmd()
+        .args(["follow", pool_url, "--since", "5m"])
+        .output()
+        .expect("follow");
+    assert!(!since.status.success());
+    let since_err = parse_error_json(&since.stderr);
+    let since_message = since_err
+        .get("error")
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(since_message.contains("does not support --since"));
+
+    let replay = cmd()
+        .args(["follow", pool_url, "--tail", "5", "--replay", "1"])
+        .output()
+        .expect("follow");
+    assert!(!replay.status.success());
+    let replay_err = parse_error_json(&replay.stderr);
+    let replay_message = replay_err
+        .get("error")
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(replay_message.contains("does not support --replay"));
+}
+
+#[test]
+fn follow_remote_url_rejects_future_since() {
+    let pool_url = "http://localhost:9170/demo";
+
+    let since = cmd()
+        .args(["follow", pool_url, "--since", "2999-01-01T00:00:00Z"])
+        .output()
+        .expect("follow");
+    assert_eq!(since.status.code(), Some(2));
+    let since_err = parse_error_json(&since.stderr);
+    let since_kind = since_err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(since_kind, "Usage");
+    let since_message = since_err
+        .get("error")
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(since_message.contains("does not support --since"));
+}
+
+#[test]
+fn follow_remote_url_timeout_returns_124() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+    let pool_url = format!("{}/demo", server.base_url);
+    let follower = cmd()
+        .args(["follow", &pool_url, "--jsonl", "--timeout", "150ms"])
+        .output()
+        .expect("follow");
+    assert_eq!(follower.status.code(), Some(124));
+}
+
+#[test]
+fn emit_streams_json_values_from_stdin() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "testpool",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let mut emit_out = cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "feed", "testpool"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("feed");
+    {
+        let stdin = emit_out.stdin.as_mut().expect("stdin");
+        stdin
+            .write_all(b"{\"x\":1}\n{\"x\":2}")
+            .expect("write stdin");
+    }
+    let output = emit_out.wait_with_output().expect("feed output");
+    assert!(output.status.success());
+    let lines = parse_json_lines(&output.stdout);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].get("seq").is_some());
+    assert!(lines[1].get("seq").is_some());
+    assert!(lines[0].get("data").is_none());
+    assert!(lines[1].get("data").is_none());
+
+    let mut follower = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "testpool",
+            "--tail",
+            "2",
+            "--jsonl",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("follow");
+    let stdout = follower.stdout.take().expect("stdout");
+    let mut reader = BufReader::new(stdout);
+    let mut follower_lines = Vec::new();
+    for _ in 0..2 {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line).expect("read line");
+        assert!(read > 0, "expected a line from follow output");
+        follower_lines.push(parse_json(line.trim()));
+    }
+    let _ = follower.kill();
+    let _ = follower.wait();
+    assert_eq!(follower_lines.len(), 2);
+    assert_eq!(follower_lines[0].get("data").unwrap()["x"], 1);
+    assert_eq!(follower_lines[1].get("data").unwrap()["x"], 2);
+}
+
+#[test]
+fn duplex_non_tty_echoes_followed_messages_without_self_echo() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "chat",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let seed_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "chat",
+            "{\"from\":\"bob\",\"msg\":\"seed\"}",
+        ])
+        .output()
+        .expect("seed feed");
+    assert!(seed_out.status.success());
+
+    let mut duplex = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "duplex",
+            "chat",
+            "--create",
+            "--me",
+            "alice",
+            "--tail",
+            "1",
+            "--jsonl",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("duplex");
+    let stdout = duplex.stdout.take().expect("duplex stdout");
+    let (line_tx, line_rx) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        loop {
+            let mut line = String::new();
+            let read = reader.read_line(&mut line).unwrap_or(0);
+            if read == 0 {
+                break;
+            }
+            if line_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let first_line = line_rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("expected seed line");
+    let first_value = parse_json(first_line.trim());
+    assert_eq!(first_value.get("data").unwrap()["from"], "bob");
+    {
+        let stdin = duplex.stdin.as_mut().expect("duplex stdin");
+        stdin
+            .write_all(b"{\"from\":\"alice\",\"msg\":\"reply\"}\n")
+            .expect("write stdin");
+    }
+    assert!(
+        line_rx.recv_timeout(Duration::from_millis(500)).is_err(),
+        "expected self-suppression to avoid echoing alice message"
+    );
+    let _ = duplex.stdin.take();
+    let status = duplex.wait().expect("duplex wait");
+    assert_eq!(status.code(), Some(0), "unexpected duplex exit code");
+
+    let follow_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "chat",
+            "--tail",
+            "2",
+            "--jsonl",
+            "--timeout",
+            "150ms",
+        ])
+        .output()
+        .expect("follow");
+    assert!(
+        follow_out.status.code() == Some(0) || follow_out.status.code() == Some(124),
+        "unexpected follow exit code: {:?}",
+        follow_out.status.code()
+    );
+    let follow_lines = parse_json_lines(&follow_out.stdout);
+    assert_eq!(follow_lines.len(), 2);
+    assert_eq!(follow_lines[0].get("data").unwrap()["from"], "bob");
+    assert_eq!(follow_lines[1].get("data").unwrap()["from"], "alice");
+}
+
+#[test]
+fn duplex_record_writes_transcript_that_replays() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let transcript = temp.path().join("session.jsonl");
+
+    let seed_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "chat",
+            "--create",
+            "{\"from\":\"bob\",\"msg\":\"seed\"}",
+        ])
+        .output()
+        .expect("seed feed");
+    assert!(seed_out.status.success());
+
+    let mut duplex = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "duplex",
+            "chat",
+            "--me",
+            "alice",
+            "--tail",
+            "1",
+            "--jsonl",
+            "--record",
+            transcript.to_str().unwrap(),
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("duplex");
+    let mut reader = BufReader::new(duplex.stdout.take().expect("duplex stdout"));
+    let mut first_line = String::new();
+    reader.read_line(&mut first_line).expect("seed line");
+    assert_eq!(parse_json(first_line.trim())["data"]["from"], "bob");
+    duplex
+        .stdin
+        .as_mut()
+        .expect("duplex stdin")
+        .write_all(b"{\"from\":\"alice\",\"msg\":\"reply\"}\n")
+        .expect("write stdin");
+    let _ = duplex.stdin.take();
+    let status = duplex.wait().expect("duplex wait");
+    assert_eq!(status.code(), Some(0), "unexpected duplex exit code");
+
+    let raw = std::fs::read_to_string(&transcript).expect("transcript");
+    let records = raw.lines().map(parse_json).collect::<Vec<_>>();
+    assert_eq!(records[0]["type"], "start");
+    assert_eq!(records[0]["pool"], "chat");
+    let recv = records
+        .iter()
+        .find(|record| record["type"] == "recv")
+        .expect("recv record");
+    assert_eq!(recv["message"]["data"]["msg"], "seed");
+    let sent = records
+        .iter()
+        .find(|record| record["type"] == "sent")
+        .expect("sent record");
+    assert_eq!(sent["message"]["data"]["msg"], "reply");
+    assert_eq!(sent["message"]["seq"], 2);
+
+    let replay = cmd()
+        .args([
+            "replay-transcript",
+            transcript.to_str().unwrap(),
+            "--speed",
+            "0",
+            "--jsonl",
+        ])
+        .output()
+        .expect("replay-transcript");
+    assert!(replay.status.success());
+    let replayed = parse_json_lines(&replay.stdout);
+    assert_eq!(replayed.len(), records.len() - 1);
+    assert!(replayed.iter().all(|record| record["type"] != "start"));
+
+    let pretty = cmd()
+        .args([
+            "replay-transcript",
+            transcript.to_str().unwrap(),
+            "--speed",
+            "0",
+        ])
+        .output()
+        .expect("replay-transcript pretty");
+    let stdout = String::from_utf8_lossy(&pretty.stdout);
+    assert!(stdout.contains("< #1 {\"from\":\"bob\",\"msg\":\"seed\"}"));
+    assert!(stdout.contains("> {\"from\":\"alice\",\"msg\":\"reply\"}"));
+}
+
+#[test]
+fn duplex_remote_url_rejects_create_flag() {
+    let output = cmd()
+        .args([
+            "duplex",
+            "http://127.0.0.1:65535/chat",
+            "--create",
+            "--me",
+            "alice",
+        ])
+        .output()
+        .expect("duplex");
+    assert_eq!(output.status.code(), Some(2));
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
+    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(message.contains("does not support --create"));
+}
+
+#[test]
+fn duplex_remote_url_rejects_since_even_when_future() {
+    let output = cmd()
+        .args([
+            "duplex",
+            "http://127.0.0.1:65535/chat",
+            "--since",
+            "2999-01-01T00:00:00Z",
+        ])
+        .output()
+        .expect("duplex");
+    assert_eq!(output.status.code(), Some(2));
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("Usage"));
+    let message = inner.get("message").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(message.contains("does not support --since"));
+}
+
+#[test]
+fn duplex_since_future_missing_pool_reports_not_found() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let output = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "duplex",
+            "missing",
+            "--since",
+            "2999-01-01T00:00:00Z",
+        ])
+        .output()
+        .expect("duplex");
+    assert_eq!(output.status.code(), Some(3));
+    let err = parse_error_json(&output.stderr);
+    let inner = err.get("error").and_then(|v| v.as_object()).expect("error");
+    assert_eq!(inner.get("kind").and_then(|v| v.as_str()), Some("NotFound"));
+}
+
+#[test]
+fn duplex_remote_happy_path_sends_and_reads() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "chat",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let seed_out = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "feed",
+            "chat",
+            "{\"from\":\"bob\",\"msg\":\"seed\"}",
+        ])
+        .output()
+        .expect("seed feed");
+    assert!(seed_out.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+    let pool_url = format!("{}/chat", server.base_url);
+    let mut duplex = cmd()
+        .args([
+            "duplex", &pool_url, "--me", "alice", "--tail", "1", "--jsonl",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("duplex");
+    let stdout = duplex.stdout.take().expect("duplex stdout");
+    let first_line = read_line_with_timeout(stdout, Duration::from_secs(2));
+    let first_value = parse_json(first_line.trim());
+    assert_eq!(first_value.get("data").unwrap()["from"], "bob");
+
+    {
+        let stdin = duplex.stdin.as_mut().expect("duplex stdin");
+        stdin
+            .write_all(b"{\"from\":\"alice\",\"msg\":\"remote-reply\"}\n")
+            .expect("write stdin");
+    }
+    let _ = duplex.stdin.take();
+    let status = duplex.wait().expect("duplex wait");
+    assert_eq!(status.code(), Some(0), "unexpected duplex exit code");
+
+    let follow_out = cmd()
+        .args([
+            "follow",
+            &pool_url,
+            "--tail",
+            "2",
+            "--jsonl",
+            "--timeout",
+            "200ms",
+        ])
+        .output()
+        .expect("follow");
+    assert!(
+        follow_out.status.code() == Some(0) || follow_out.status.code() == Some(124),
+        "unexpected follow exit code: {:?}",
+        follow_out.status.code()
+    );
+    let follow_lines = parse_json_lines(&follow_out.stdout);
+    assert_eq!(follow_lines.len(), 2);
+    assert_eq!(follow_lines[0].get("data").unwrap()["from"], "bob");
+    assert_eq!(follow_lines[1].get("data").unwrap()["from"], "alice");
+}
+
+#[test]
+fn serve_rejects_invalid_bind() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let serve = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "serve",
+            "--bind",
+            "nope",
+        ])
+        .output()
+        .expect("serve");
+    assert!(!serve.status.success());
+    let err = parse_error_json(&serve.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+}
+
+#[test]
+fn serve_init_help_is_available() {
+    let output = cmd()
+        .args(["serve", "init", "--help"])
+        .output()
+        .expect("serve init help");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Generate token + TLS artifacts"));
+}
+
+#[test]
+fn serve_check_outputs_resolved_config() {
+    let output = cmd()
+        .args(["serve", "check", "--json"])
+        .output()
+        .expect("serve check");
+    assert!(output.status.success());
+    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
+    let payload = parse_json(stdout);
+    let check = payload.get("check").expect("check");
+    let status = check.get("status").and_then(|v| v.as_str()).unwrap_or("");
+    assert_eq!(status, "valid");
+    let base_url = check.get("base_url").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(base_url.contains("127.0.0.1:9700"));
+    let mcp = check.get("mcp").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(mcp.ends_with("/mcp"));
+}
+
+#[test]
+fn serve_check_human_uses_readable_limits_and_fingerprint() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let mut params = CertificateParams::new(vec!["localhost".to_string()]);
+    params
+        .subject_alt_names
+        .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    params
+        .subject_alt_names
+        .push(SanType::IpAddress(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    let cert = Certificate::from_params(params).expect("cert");
+    let cert_pem = cert.serialize_pem().expect("cert pem");
+    let key_pem = cert.serialize_private_key_pem();
+    let cert_path = temp.path().join("cert.pem");
+    let key_path = temp.path().join("key.pem");
+    std::fs::write(&cert_path, cert_pem).expect("write cert");
+    std::fs::write(&key_path, key_pem).expect("write key");
+
+    let output = cmd()
+        .args([
+            "serve",
+            "--tls-cert",
+            cert_path.to_str().unwrap(),
+            "--tls-key",
+            key_path.to_str().unwrap(),
+            "check",
+        ])
+        .output()
+        .expect("serve check");
+    assert!(output.status.success());
+    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
+    assert!(stdout.contains("Configuration valid."));
+    assert!(stdout.contains("MCP:    https://127.0.0.1:9700/mcp"));
+    assert!(stdout.contains("Limits: body 1M, timeout 30s, concurrency 64"));
+    assert!(stdout.contains("Fingerprint: SHA256:"));
+}
+
+#[test]
+fn serve_check_json_includes_tls_fingerprint() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let mut params = CertificateParams::new(vec!["localhost".to_string()]);
+    params
+        .subject_alt_names
+        .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    params
+        .subject_alt_names
+        .push(SanType::IpAddress(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    let cert = Certificate::from_params(params).expect("cert");
+    let cert_pem = cert.serialize_pem().expect("cert pem");
+    let key_pem = cert.serialize_private_key_pem();
+    let cert_path = temp.path().join("cert.pem");
+    let key_path = temp.path().join("key.pem");
+    std::fs::write(&cert_path, cert_pem).expect("write cert");
+    std::fs::write(&key_path, key_pem).expect("write key");
+
+    let output = cmd()
+        .args([
+            "serve",
+            "--tls-cert",
+            cert_path.to_str().unwrap(),
+            "--tls-key",
+            key_path.to_str().unwrap(),
+            "check",
+            "--json",
+        ])
+        .output()
+        .expect("serve check");
+    assert!(output.status.success());
+    let payload = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    let fingerprint = payload
+        .get("check")
+        .and_then(|v| v.get("tls_fingerprint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(fingerprint.starts_with("SHA256:"));
+}
+
+#[test]
+fn serve_check_defaults_to_human_output() {
+    let output = cmd()
+        .args(["serve", "check"])
+        .output()
+        .expect("serve check");
+    assert!(output.status.success());
+    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
+    assert!(stdout.contains("Configuration valid."));
+}
+
+#[test]
+fn serve_check_from_env_applies_environment_config() {
+    let output = cmd()
+        .args(["serve", "check", "--from-env", "--json"])
+        .env("PLASMITE_SERVE_BIND", "127.0.0.1:9911")
+        .env("PLASMITE_SERVE_ACCESS", "read-only")
+        .output()
+        .expect("serve check");
+    assert!(output.status.success());
+    let payload = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+    let check = payload.get("check").expect("check");
+    let base_url = check.get("base_url").and_then(|v| v.as_str()).unwrap_or("");
+    assert!(base_url.contains("127.0.0.1:9911"));
+
+    let output = cmd()
+        .args(["serve", "check", "--from-env"])
+        .env("PLASMITE_SERVE_MAX_BODY_BYTES", "lots")
+        .output()
+        .expect("serve check");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("PLASMITE_SERVE_MAX_BODY_BYTES"));
+}
+
+#[test]
+fn serve_check_rejects_invalid_config() {
+    let output = cmd()
+        .args(["serve", "--bind", "0.0.0.0:0", "check"])
+        .output()
+        .expect("serve check");
+    assert!(!output.status.success());
+    let err = parse_error_json(&output.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+}
+
+#[test]
+fn serve_init_writes_artifacts_and_next_commands() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let out_dir = temp.path().join("serve-init");
+    let output = cmd()
+        .args([
+            "serve",
+            "init",
+            "--output-dir",
+            out_dir.to_str().unwrap(),
+            "--bind",
+            "0.0.0.0:9700",
+        ])
+        .output()
+        .expect("serve init");
+    assert!(output.status.success());
+
+    let stdout = std::str::from_utf8(&output.stdout).expect("utf8");
+    let payload = parse_json(stdout);
+    let artifacts = payload
+        .get("init")
+        .and_then(|v| v.get("artifact_paths"))
+        .expect("artifact_paths");
+    let token_file = artifacts
+        .get("token_file")
+        .and_then(|v| v.as_str())
+        .expect("token_file");
+    let tls_cert = artifacts
+        .get("tls_cert")
+        .and_then(|v| v.as_str())
+        .expect("tls_cert");
+    let tls_key = artifacts
+        .get("tls_key")
+        .and_then(|v| v.as_str())
+        .expect("tls_key");
+    assert!(Path::new(token_file).exists());
+    assert!(Path::new(tls_cert).exists());
+    assert!(Path::new(tls_key).exists());
+
+    let token = std::fs::read_to_string(token_file).expect("read token");
+    assert!(!token.trim().is_empty());
+    assert!(
+        !stdout.contains(token.trim()),
+        "token value should not be echoed to stdout"
+    );
+
+    let server_commands = payload
+        .get("init")
+        .and_then(|v| v.get("server_commands"))
+        .and_then(|v| v.as_array())
+        .expect("server_commands");
+    assert!(server_commands.iter().any(|v| {
+        v.as_str()
+            .unwrap_or("")
+            .contains("plasmite serve --bind 0.0.0.0:9700 --allow-non-loopback")
+    }));
+    let client_commands = payload
+        .get("init")
+        .and_then(|v| v.get("client_commands"))
+        .and_then(|v| v.as_array())
+        .expect("client_commands");
+    assert!(
+        client_commands
+            .iter()
+            .any(|v| v.as_str().unwrap_or("").contains("plasmite feed")),
+        "expected plasmite feed client command"
+    );
+    let tls_fingerprint = payload
+        .get("init")
+        .and_then(|v| v.get("tls_fingerprint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(tls_fingerprint.starts_with("SHA256:"));
+}
+
+#[test]
+fn serve_init_requires_force_for_existing_artifacts() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let out_dir = temp.path().join("serve-init");
+
+    let first = cmd()
+        .args(["serve", "init", "--output-dir", out_dir.to_str().unwrap()])
+        .output()
+        .expect("first serve init");
+    assert!(first.status.success());
+
+    let second = cmd()
+        .args(["serve", "init", "--output-dir", out_dir.to_str().unwrap()])
+        .output()
+        .expect("second serve init");
+    assert!(!second.status.success());
+    let err = parse_error_json(&second.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "AlreadyExists");
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("--force"));
+}
+
+#[test]
+fn serve_init_tty_reports_created_and_overwritten() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let out_dir = temp.path().join("serve-init");
+
+    let first = cmd_tty(&[
+        "--color",
+        "never",
+        "serve",
+        "init",
+        "--output-dir",
+        out_dir.to_str().unwrap(),
+        "--bind",
+        "0.0.0.0:9700",
+    ]);
+    assert!(first.status.success());
+    let first_text = sanitize_tty_text(&first.stdout);
+    assert!(first_text.contains("Secure serving initialized."));
+    assert!(first_text.contains("Output directory:"));
+    assert!(first_text.contains("Files created:"));
+    assert!(first_text.contains("    pls serve \\"));
+    assert!(first_text.contains("      --bind 0.0.0.0:9700 \\"));
+    assert!(first_text.contains("      --allow-non-loopback \\"));
+    assert!(first_text.contains("      --token-file "));
+    assert!(first_text.contains("      --tls-cert "));
+    assert!(first_text.contains("      --tls-key "));
+    assert!(!first_text.contains("THIS-HOST"));
+    let feed_line = first_text
+        .lines()
+        .find(|line| line.contains("pls feed https://"))
+        .expect("feed line");
+    assert!(feed_line.contains(":9700/demo \\"));
+    let follow_line = first_text
+        .lines()
+        .find(|line| line.contains("pls follow https://"))
+        .expect("follow line");
+    assert!(follow_line.contains(":9700/demo \\"));
+
+    let second = cmd_tty(&[
+        "--color",
+        "never",
+        "serve",
+        "init",
+        "--output-dir",
+        out_dir.to_str().unwrap(),
+        "--bind",
+        "0.0.0.0:9700",
+        "--force",
+    ]);
+    assert!(second.status.success());
+    let second_text = sanitize_tty_text(&second.stdout);
+    assert!(second_text.contains("Secure serving re-initialized."));
+    assert!(second_text.contains("Files overwritten:"));
+}
+
+#[test]
+fn serve_rejects_non_loopback_without_allow() {
+    let serve = cmd()
+        .args(["serve", "--bind", "0.0.0.0:0"])
+        .output()
+        .expect("serve");
+    assert!(!serve.status.success());
+    let err = parse_error_json(&serve.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("--allow-non-loopback"));
+}
+
+#[test]
+fn serve_non_loopback_write_requires_token_file() {
+    let serve = cmd()
+        .args([
+            "serve",
+            "--bind",
+            "0.0.0.0:0",
+            "--allow-non-loopback",
+            "--access",
+            "write-only",
+            "--insecure-no-tls",
+        ])
+        .output()
+        .expect("serve");
+    assert!(!serve.status.success());
+    let err = parse_error_json(&serve.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+    let message = err
+        .get("error")
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(message.contains("--token-file"));
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("serve init"));
+}
+
+#[test]
+fn serve_non_loopback_write_requires_tls_or_insecure() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let token_path = temp.path().join("token.txt");
+    std::fs::write(&token_path, "secret").expect("write token");
+
+    let serve = cmd()
+        .args([
+            "serve",
+            "--bind",
+            "0.0.0.0:0",
+            "--allow-non-loopback",
+            "--access",
+            "write-only",
+            "--token-file",
+            token_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("serve");
+    assert!(!serve.status.success());
+    let err = parse_error_json(&serve.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+    let message = err
+        .get("error")
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(message.contains("TLS"));
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("serve init"));
+}
+
+#[test]
+fn serve_rejects_token_and_token_file_combination_with_init_hint() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let token_path = temp.path().join("token.txt");
+    std::fs::write(&token_path, "secret").expect("write token");
+
+    let serve = cmd()
+        .args([
+            "serve",
+            "--token",
+            "dev-token",
+            "--token-file",
+            token_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("serve");
+    assert!(!serve.status.success());
+    let err = parse_error_json(&serve.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("serve init"));
+}
+
+#[test]
+fn serve_rejects_conflicting_tls_flags_with_init_hint() {
+    let serve = cmd()
+        .args([
+            "serve",
+            "--tls-self-signed",
+            "--tls-cert",
+            "cert.pem",
+            "--tls-key",
+            "key.pem",
+        ])
+        .output()
+        .expect("serve");
+    assert!(!serve.status.success());
+    let err = parse_error_json(&serve.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+    let hint = err
+        .get("error")
+        .and_then(|v| v.get("hint"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(hint.contains("serve init"));
+}
+
+#[test]
+fn serve_rejects_wildcard_cors_origin() {
+    let serve = cmd()
+        .args(["serve", "--bind", "127.0.0.1:0", "--cors-origin", "*"])
+        .output()
+        .expect("serve");
+    assert!(!serve.status.success());
+    let err = parse_error_json(&serve.stderr);
+    let kind = err
+        .get("error")
+        .and_then(|v| v.get("kind"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert_eq!(kind, "Usage");
+    let message = err
+        .get("error")
+        .and_then(|v| v.get("message"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    assert!(message.contains("wildcard"));
+}
+
+#[test]
+fn serve_responses_include_version_header() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+
+    let list_url = format!("{}/v0/pools", server.base_url);
+    let list = ureq::get(&list_url).call().expect("list");
+    assert_eq!(list.header("plasmite-version"), Some("0"));
+
+    let tail_url = format!("{}/v0/pools/demo/tail?timeout_ms=10", server.base_url);
+    let tail = ureq::get(&tail_url).call().expect("tail");
+    assert_eq!(tail.header("plasmite-version"), Some("0"));
+
+    let health_url = format!("{}/healthz", server.base_url);
+    let health = ureq::get(&health_url).call().expect("healthz");
+    assert_eq!(health.header("plasmite-version"), Some("0"));
+    let body: serde_json::Value =
+        serde_json::from_str(&health.into_string().expect("body")).expect("healthz json");
+    assert_eq!(body.get("ok").and_then(|v| v.as_bool()), Some(true));
+}
+
+#[test]
+fn serve_rejects_oversized_body() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    let create = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "pool",
+            "create",
+            "demo",
+        ])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let server = ServeProcess::start_with_args(&pool_dir, &["--max-body-bytes", "64"]);
+    let append_url = format!("{}/v0/pools/demo/append", server.base_url);
+    let payload = json!({
+        "data": { "big": "x".repeat(256) },
+        "tags": ["oversized"],
+        "durability": "fast"
+    })
+    .to_string();
+
+    match ureq::post(&append_url)
+        .set("Content-Type", "application/json")
+        .send_string(&payload)
+    {
+        Ok(_) => panic!("expected 413 for oversized body"),
+        Err(ureq::Error::Status(code, _resp)) => {
+            assert_eq!(code, 413);
+        }
+        Err(err) => panic!("request failed: {err:?}"),
+    }
+}
+
+#[test]
+fn serve_namespaces_isolate_pools_and_tokens() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("pool dir");
+    let lab_tokens = temp.path().join("lab.tokens");
+    std::fs::write(&lab_tokens, "lab-token\n").expect("write tokens");
+    let namespace = format!("lab,token-file={}", lab_tokens.display());
+    let server = ServeProcess::start_with_args(
+        &pool_dir,
+        &[
+            "--namespace",
+            &namespace,
+            "--namespace",
+            "guest,access=read-only",
+        ],
+    );
+
+    let create_url = format!("{}/v0/ns/lab/pools", server.base_url);
+    let body = json!({"pool": "runs"}).to_string();
+    match ureq::post(&create_url)
+        .set("Content-Type", "application/json")
+        .send_string(&body)
+    {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 401),
+        other => panic!("expected 401 without the namespace token, got {other:?}"),
+    }
+    ureq::post(&create_url)
+        .set("Content-Type", "application/json")
+        .set("Authorization", "Bearer lab-token")
+        .send_string(&body)
+        .expect("create in namespace");
+    assert!(pool_dir.join("lab").join("runs.plasmite").exists());
+
+    // The root namespace does not see namespaced pools.
+    let root_list: Value = ureq::get(&format!("{}/v0/pools", server.base_url))
+        .call()
+        .expect("root list")
+        .into_json()
+        .expect("json");
+    assert_eq!(root_list["pools"], json!([]));
+
+    match ureq::post(&format!("{}/v0/ns/guest/pools", server.base_url))
+        .set("Content-Type", "application/json")
+        .send_string(&body)
+    {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 403),
+        other => panic!("expected 403 for read-only namespace, got {other:?}"),
+    }
+    match ureq::get(&format!("{}/v0/ns/missing/pools", server.base_url)).call() {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 404),
+        other => panic!("expected 404 for unknown namespace, got {other:?}"),
+    }
+}
+
+#[test]
+fn serve_root_routes_never_reach_namespaced_pools() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("pool dir");
+    let root_tokens = temp.path().join("root.tokens");
+    std::fs::write(&root_tokens, "root-token\n").expect("write tokens");
+    let lab_tokens = temp.path().join("lab.tokens");
+    std::fs::write(&lab_tokens, "lab-token\n").expect("write tokens");
+    let namespace = format!("lab,token-file={}", lab_tokens.display());
+    let server = ServeProcess::start_with_args(
+        &pool_dir,
+        &[
+            "--token-file",
+            root_tokens.to_str().unwrap(),
+            "--namespace",
+            &namespace,
+        ],
+    );
+
+    for (prefix, token) in [("/v0/ns/lab", "lab-token"), ("/v0", "root-token")] {
+        ureq::post(&format!("{}{prefix}/pools", server.base_url))
+            .set("Content-Type", "application/json")
+            .set("Authorization", &format!("Bearer {token}"))
+            .send_string(&json!({"pool": "runs"}).to_string())
+            .expect("create");
+    }
+
+    let root_list: Value = ureq::get(&format!("{}/v0/pools", server.base_url))
+        .set("Authorization", "Bearer root-token")
+        .call()
+        .expect("root list")
+        .into_json()
+        .expect("json");
+    let names = root_list["pools"]
+        .as_array()
+        .expect("pools")
+        .iter()
+        .map(|pool| pool["name"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![json!("runs")],
+        "lab/runs stays out of the root list"
+    );
+
+    for (method, path) in [
+        ("GET", "/v0/pools/lab%2Fruns/info"),
+        ("GET", "/v0/pools/lab%2Fruns/messages/1"),
+        ("POST", "/v0/pools/lab%2Fruns/append"),
+    ] {
+        let result = ureq::request(method, &format!("{}{path}", server.base_url))
+            .set("Content-Type", "application/json")
+            .set("Authorization", "Bearer root-token")
+            .send_string(&json!({"data": {"x": 1}}).to_string());
+        match result {
+            Err(ureq::Error::Status(code, _)) => {
+                assert!(matches!(code, 403 | 404), "{method} {path}: {code}")
+            }
+            other => panic!("{method} {path}: expected 403/404, got {other:?}"),
+        }
+    }
+    match ureq::post(&format!("{}/v0/pools/open", server.base_url))
+        .set("Content-Type", "application/json")
+        .set("Authorization", "Bearer root-token")
+        .send_string(&json!({"pool": "lab/runs"}).to_string())
+    {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 404),
+        other => panic!("expected 404 opening lab/runs from the root, got {other:?}"),
+    }
+}
+
+#[test]
+fn serve_pool_policy_filters_listing_and_guards_pools() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("pool dir");
+    let tokens = temp.path().join("tokens");
+    std::fs::write(&tokens, "ops-token\nviewer-token\n").expect("write tokens");
+    let policy = temp.path().join("policy");
+    std::fs::write(
+        &policy,
+        "# principal pool access\nops-token * read-write\nviewer-token public read-only\n",
+    )
+    .expect("write policy");
+    let server = ServeProcess::start_with_args(
+        &pool_dir,
+        &[
+            "--token-file",
+            tokens.to_str().unwrap(),
+            "--pool-policy",
+            policy.to_str().unwrap(),
+        ],
+    );
+
+    for pool in ["public", "secret"] {
+        ureq::post(&format!("{}/v0/pools", server.base_url))
+            .set("Content-Type", "application/json")
+            .set("Authorization", "Bearer ops-token")
+            .send_string(&json!({ "pool": pool }).to_string())
+            .expect("create");
+    }
+
+    let list = |token: &str| -> Vec<(String, Value)> {
+        let body: Value = ureq::get(&format!("{}/v0/pools", server.base_url))
+            .set("Authorization", &format!("Bearer {token}"))
+            .call()
+            .expect("list")
+            .into_json()
+            .expect("json");
+        body["pools"]
+            .as_array()
+            .expect("pools")
+            .iter()
+            .map(|pool| {
+                (
+                    pool["name"].as_str().unwrap_or_default().to_string(),
+                    pool["permissions"].clone(),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(
+        list("ops-token"),
+        [
+            ("public".to_string(), json!(["read", "write"])),
+            ("secret".to_string(), json!(["read", "write"])),
+        ]
+    );
+    assert_eq!(
+        list("viewer-token"),
+        [("public".to_string(), json!(["read"]))]
+    );
+
+    match ureq::get(&format!("{}/v0/pools/secret/info", server.base_url))
+        .set("Authorization", "Bearer viewer-token")
+        .call()
+    {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 403),
+        other => panic!("expected 403 reading an ungranted pool, got {other:?}"),
+    }
+    match ureq::post(&format!("{}/v0/pools/public/append", server.base_url))
+        .set("Content-Type", "application/json")
+        .set("Authorization", "Bearer viewer-token")
+        .send_string(&json!({ "data": { "x": 1 } }).to_string())
+    {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 403),
+        other => panic!("expected 403 writing a read-only pool, got {other:?}"),
+    }
+}
+
+#[test]
+fn serve_quotas_limit_pools_and_append_rate() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    std::fs::create_dir_all(&pool_dir).expect("pool dir");
+    let server = ServeProcess::start_with_args(
+        &pool_dir,
+        &["--namespace", "lab,max-pools=1", "--max-append-rate", "1"],
+    );
+    let base = format!("{}/v0/ns/lab", server.base_url);
+    let create = |pool: &str| {
+        ureq::post(&format!("{base}/pools"))
+            .set("Content-Type", "application/json")
+            .send_string(&json!({"pool": pool, "size_bytes": 65536}).to_string())
+    };
+    create("first").expect("first pool fits");
+    match create("second") {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 507),
+        other => panic!("expected 507 past max-pools, got {other:?}"),
+    }
+
+    // A two-record batch can never fit a one-per-second bucket.
+    match ureq::post(&format!("{base}/pools/first/append_batch"))
+        .send_string("{\"data\":{\"n\":1}}\n{\"data\":{\"n\":2}}\n")
+    {
+        Err(ureq::Error::Status(code, resp)) => {
+            assert_eq!(code, 429);
+            assert!(resp.header("retry-after").is_some());
+        }
+        other => panic!("expected 429 past max-append-rate, got {other:?}"),
+    }
+
+    let status: Value = ureq::get(&format!("{base}/quota"))
+        .call()
+        .expect("quota")
+        .into_json()
+        .expect("json");
+    assert_eq!(
+        status["quota"],
+        json!({"max_pools": 1, "max_bytes": null, "max_append_rate": 1})
+    );
+    assert_eq!(status["usage"]["pools"], json!(1));
+}
+
+#[test]
+fn serve_routes_relay_matching_appends_and_report_metrics() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    for pool in ["events", "alerts"] {
+        let create = cmd()
+            .args(["--dir", dir, "pool", "create", pool])
+            .output()
+            .expect("create");
+        assert!(create.status.success());
+    }
+    let before = cmd()
+        .args([
+            "--dir",
+            dir,
+            "feed",
+            "events",
+            "{\"level\":\"error\",\"n\":0}",
+        ])
+        .output()
+        .expect("feed");
+    assert!(before.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+    let routes = format!("{}/v0/routes", server.base_url);
+    let put = |body: Value| {
+        ureq::put(&routes)
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string())
+    };
+    let created: Value = put(json!({
+        "from_pool": "events",
+        "to_pool": "alerts",
+        "filter": ".data.level == \"error\"",
+    }))
+    .expect("put route")
+    .into_json()
+    .expect("json");
+    assert_eq!(created["route"]["to_pool"], json!("alerts"));
+    match put(json!({"from_pool": "alerts", "to_pool": "events"})) {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 400),
+        other => panic!("expected cycle rejection, got {other:?}"),
+    }
+
+    let append = format!("{}/v0/pools/events/append", server.base_url);
+    for (n, level) in [(1, "info"), (2, "error"), (3, "error")] {
+        ureq::post(&append)
+            .set("Content-Type", "application/json")
+            .send_string(&json!({"data": {"level": level, "n": n}, "tags": ["svc"]}).to_string())
+            .expect("append");
+    }
+
+    let start = Instant::now();
+    let listed = loop {
+        let listed: Value = ureq::get(&routes)
+            .call()
+            .expect("list")
+            .into_json()
+            .expect("json");
+        if listed["routes"][0]["metrics"]["relayed"] == json!(2) {
+            break listed;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "route did not relay: {listed}"
+        );
+        sleep(Duration::from_millis(20));
+    };
+    assert_eq!(listed["routes"][0]["metrics"]["skipped"], json!(1));
+    assert_eq!(listed["routes"][0]["metrics"]["errors"], json!(0));
+
+    let relayed: Value = ureq::get(&format!(
+        "{}/v0/pools/alerts/tail?since_seq=1&max=2&timeout_ms=1000",
+        server.base_url
+    ))
+    .call()
+    .expect("tail")
+    .into_string()
+    .expect("body")
+    .lines()
+    .map(|line| serde_json::from_str::<Value>(line).expect("line")["data"]["n"].clone())
+    .collect();
+    assert_eq!(relayed, json!([2, 3]));
+    assert!(pool_dir.join(".routes.json").exists());
+
+    ureq::delete(&format!("{routes}?from_pool=events&to_pool=alerts"))
+        .call()
+        .expect("delete route");
+    let listed: Value = ureq::get(&routes)
+        .call()
+        .expect("list")
+        .into_json()
+        .expect("json");
+    assert_eq!(listed["routes"], json!([]));
+}
+
+#[test]
+fn serve_transforms_rewrite_appends_and_check_dry_runs_samples() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let transforms = temp.path().join("transforms.toml");
+    std::fs::write(
+        &transforms,
+        "[[pools.events]]\nop = \"rename\"\nfrom = \"msg\"\nto = \"message\"\n\n\
+         [[pools.events]]\nop = \"drop\"\nfield = \"secret\"\n\n\
+         [[pools.events]]\nop = \"server_time\"\nfield = \"received_at\"\n",
+    )
+    .expect("write transforms");
+    let transforms = transforms.to_str().unwrap();
+
+    let check = cmd()
+        .args([
+            "serve",
+            "--transforms",
+            transforms,
+            "check",
+            "--json",
+            "--sample",
+            "events={\"msg\":\"hi\",\"secret\":1}",
+        ])
+        .output()
+        .expect("serve check");
+    assert!(check.status.success(), "{check:?}");
+    let report = parse_json(std::str::from_utf8(&check.stdout).expect("utf8"));
+    let report = &report["check"]["transforms"];
+    assert_eq!(
+        report["pools"]["events"],
+        json!([
+            "rename msg -> message",
+            "drop secret",
+            "server_time received_at"
+        ])
+    );
+    assert_eq!(report["samples"][0]["output"]["message"], json!("hi"));
+    assert!(report["samples"][0]["output"].get("secret").is_none());
+
+    let unknown = cmd()
+        .args([
+            "serve",
+            "--transforms",
+            transforms,
+            "check",
+            "--sample",
+            "other={}",
+        ])
+        .output()
+        .expect("serve check");
+    assert!(!unknown.status.success());
+
+    let server = ServeProcess::start_with_args(&pool_dir, &["--transforms", transforms]);
+    let appended: Value = ureq::post(&format!("{}/v0/pools/events/append", server.base_url))
+        .set("Content-Type", "application/json")
+        .send_string(&json!({"data": {"msg": "hello", "secret": "x"}}).to_string())
+        .expect("append")
+        .into_json()
+        .expect("json");
+    let data = &appended["message"]["data"];
+    assert_eq!(data["message"], json!("hello"));
+    assert!(
+        data.get("msg").is_none() && data.get("secret").is_none(),
+        "{data}"
+    );
+    assert!(
+        data["received_at"]
+            .as_str()
+            .is_some_and(|ts| ts.ends_with('Z'))
+    );
+}
+
+#[test]
+fn serve_reuses_open_pools_and_reopens_replaced_files() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "cached"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    let server = ServeProcess::start(&pool_dir);
+    let base = format!("{}/v0/pools/cached", server.base_url);
+    let append = |n: u64| {
+        ureq::post(&format!("{base}/append"))
+            .set("Content-Type", "application/json")
+            .send_string(&json!({"data": {"n": n}}).to_string())
+            .expect("append")
+    };
+    append(1);
+    append(2);
+    let stats: Value = ureq::get(&format!("{}/v0/pool_cache", server.base_url))
+        .call()
+        .expect("stats")
+        .into_json()
+        .expect("json");
+    assert_eq!(stats["pool_cache"]["misses"], json!(1));
+    assert_eq!(stats["pool_cache"]["hits"], json!(1));
+    assert_eq!(stats["pool_cache"]["len"], json!(1));
+
+    // Replace the pool behind the server's back; the cached mapping must not be reused.
+    let delete = cmd()
+        .args(["--dir", dir, "pool", "delete", "cached", "--force"])
+        .output()
+        .expect("delete");
+    assert!(delete.status.success(), "{delete:?}");
+    let recreate = cmd()
+        .args(["--dir", dir, "pool", "create", "cached"])
+        .output()
+        .expect("recreate");
+    assert!(recreate.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "cached", "{\"fresh\":true}"])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+    let message: Value = ureq::get(&format!("{base}/messages/1"))
+        .call()
+        .expect("get")
+        .into_json()
+        .expect("json");
+    assert_eq!(message["message"]["data"], json!({"fresh": true}));
+    let stats: Value = ureq::get(&format!("{}/v0/pool_cache", server.base_url))
+        .call()
+        .expect("stats")
+        .into_json()
+        .expect("json");
+    assert_eq!(stats["pool_cache"]["invalidations"], json!(1));
+}
+
+#[test]
+fn serve_exports_request_spans_to_the_otel_endpoint() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "traced"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+
+    // A minimal OTLP/HTTP collector: one export per connection, bodies handed to the test.
+    let collector = TcpListener::bind("127.0.0.1:0").expect("collector");
+    let endpoint = format!("http://{}", collector.local_addr().expect("addr"));
+    let (tx, rx) = mpsc::channel::<(String, Value)>();
+    thread::spawn(move || {
+        for stream in collector.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+            let mut request_line = String::new();
+            let mut content_length = 0usize;
+            let mut line = String::new();
+            reader.read_line(&mut request_line).expect("request line");
+            loop {
+                line.clear();
+                reader.read_line(&mut line).expect("header");
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().expect("length");
+                    }
+                }
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).expect("body");
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}");
+            let body = serde_json::from_slice(&body).expect("export json");
+            if tx.send((request_line, body)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let server = ServeProcess::start_with_env(
+        &pool_dir,
+        &[],
+        "http",
+        &[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", &endpoint),
+            ("OTEL_SERVICE_NAME", "edge-serve"),
+        ],
+    );
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    ureq::post(&format!("{}/v0/pools/traced/append", server.base_url))
+        .set("Content-Type", "application/json")
+        .set("traceparent", &format!("00-{trace_id}-00f067aa0ba902b7-01"))
+        .send_string(&json!({"data": {"n": 1}}).to_string())
+        .expect("append");
+
+    let mut names = std::collections::BTreeSet::new();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !["request", "pool_open", "append"]
+        .iter()
+        .all(|name| names.contains(*name))
+    {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let (request_line, body) = rx.recv_timeout(remaining).expect("span export");
+        assert!(
+            request_line.starts_with("POST /v1/traces "),
+            "{request_line}"
+        );
+        let resource = &body["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0],
+            json!({"key": "service.name", "value": {"stringValue": "edge-serve"}})
+        );
+        for span in resource["scopeSpans"][0]["spans"]
+            .as_array()
+            .expect("spans")
+        {
+            if span["traceId"] == json!(trace_id) {
+                names.insert(span["name"].as_str().expect("name").to_string());
+            }
+        }
+    }
+}
+
+#[test]
+fn serve_readonly_snapshot_reads_without_touching_pools() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+    let dir = pool_dir.to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "snap"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    let feed = cmd()
+        .args(["--dir", dir, "feed", "snap", "{\"n\":1}"])
+        .output()
+        .expect("feed");
+    assert!(feed.status.success());
+    let pool_path = pool_dir.join("snap.plasmite");
+    let before = std::fs::read(&pool_path).expect("read pool");
+
+    let server = ServeProcess::start_with_args(&pool_dir, &["--readonly-snapshot"]);
+    let base = format!("{}/v0/pools", server.base_url);
+    let message: Value = ureq::get(&format!("{base}/snap/messages/1"))
+        .call()
+        .expect("get")
+        .into_json()
+        .expect("json");
+    assert_eq!(message["message"]["data"], json!({"n": 1}));
+
+    match ureq::post(&format!("{base}/snap/append"))
+        .set("Content-Type", "application/json")
+        .send_string(&json!({"data": {"n": 2}}).to_string())
+    {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 403),
+        other => panic!("expected 403 for snapshot append, got {other:?}"),
+    }
+    match ureq::delete(&format!("{base}/snap")).call() {
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 403),
+        other => panic!("expected 403 for snapshot delete, got {other:?}"),
+    }
+    assert_eq!(std::fs::read(&pool_path).expect("read pool"), before);
 }
 
 #[test]
-fn serve_rejects_conflicting_tls_flags_with_init_hint() {
-    let serve = cmd()
+fn pool_export_writes_jsonl_and_gates_parquet() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path().to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
+        .output()
+        .expect("create");
+    assert!(create.status.success());
+    for n in 1..=2 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "events", &format!("{{\"n\":{n}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let export = cmd()
+        .args(["--dir", dir, "pool", "export", "events"])
+        .output()
+        .expect("export");
+    assert!(export.status.success());
+    let lines = parse_json_lines(&export.stdout);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["data"], json!({"n": 2}));
+
+    let columns = cmd()
+        .args(["--dir", dir, "pool", "export", "events", "--columns", "n"])
+        .output()
+        .expect("export columns");
+    assert_eq!(columns.status.code(), Some(2));
+
+    let out = temp.path().join("events.parquet");
+    let parquet = cmd()
         .args([
-            "serve",
-            "--tls-self-signed",
-            "--tls-cert",
-            "cert.pem",
-            "--tls-key",
-            "key.pem",
+            "--dir",
+            dir,
+            "pool",
+            "export",
+            "events",
+            "--format",
+            "parquet",
+            "--out",
+            out.to_str().unwrap(),
         ])
         .output()
-        .expect("serve");
-    assert!(!serve.status.success());
-    let err = parse_error_json(&serve.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
-    let hint = err
-        .get("error")
-        .and_then(|v| v.get("hint"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(hint.contains("serve init"));
+        .expect("export parquet");
+    if cfg!(feature = "parquet") {
+        assert!(parquet.status.success());
+        assert!(
+            std::fs::read(&out)
+                .expect("parquet file")
+                .starts_with(b"PAR1")
+        );
+    } else {
+        assert_eq!(parquet.status.code(), Some(2));
+        assert!(!out.exists());
+    }
 }
 
+#[cfg(feature = "parquet")]
 #[test]
-fn serve_rejects_wildcard_cors_origin() {
-    let serve = cmd()
-        .args(["serve", "--bind", "127.0.0.1:0", "--cors-origin", "*"])
+fn pool_export_parquet_writes_requested_columns() {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path().to_str().unwrap();
+    let create = cmd()
+        .args(["--dir", dir, "pool", "create", "events"])
         .output()
-        .expect("serve");
-    assert!(!serve.status.success());
-    let err = parse_error_json(&serve.stderr);
-    let kind = err
-        .get("error")
-        .and_then(|v| v.get("kind"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert_eq!(kind, "Usage");
-    let message = err
-        .get("error")
-        .and_then(|v| v.get("message"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    assert!(message.contains("wildcard"));
+        .expect("create");
+    assert!(create.status.success());
+    for n in 1..=3 {
+        let feed = cmd()
+            .args(["--dir", dir, "feed", "events", &format!("{{\"n\":{n}}}")])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
+
+    let out = temp.path().join("events.parquet");
+    let export = cmd()
+        .args([
+            "--dir",
+            dir,
+            "pool",
+            "export",
+            "events",
+            "--format",
+            "parquet",
+            "--columns",
+            "n",
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .output()
+        .expect("export parquet");
+    assert!(export.status.success());
+
+    let reader =
+        SerializedFileReader::new(std::fs::File::open(&out).expect("open")).expect("reader");
+    let metadata = reader.metadata().file_metadata();
+    assert_eq!(metadata.num_rows(), 3);
+    let names = metadata
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| column.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["seq", "time", "tags", "n"]);
 }
 
 #[test]
-fn serve_responses_include_version_header() {
+fn pool_export_sqlite_round_trips_or_reports_missing_feature() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    let dir = temp.path().to_str().unwrap();
+    for pool in ["events", "restored"] {
+        let create = cmd()
+            .args(["--dir", dir, "pool", "create", pool])
+            .output()
+            .expect("create");
+        assert!(create.status.success());
+    }
+    for n in 1..=2 {
+        let feed = cmd()
+            .args([
+                "--dir",
+                dir,
+                "feed",
+                "events",
+                "--tag",
+                "audit",
+                &format!("{{\"n\":{n}}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let create = cmd()
+    let out = temp.path().join("events.db");
+    let export = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
+            dir,
             "pool",
-            "create",
-            "demo",
+            "export",
+            "events",
+            "--format",
+            "sqlite",
+            "--out",
+            out.to_str().unwrap(),
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-
-    let server = ServeProcess::start(&pool_dir);
+        .expect("export sqlite");
+    if !cfg!(feature = "sqlite") {
+        assert_eq!(export.status.code(), Some(2));
+        assert!(!out.exists());
+        return;
+    }
+    assert!(export.status.success());
+    assert!(!temp.path().join("events.db.partial").exists());
 
-    let list_url = format!("{}/v0/pools", server.base_url);
-    let list = ureq::get(&list_url).call().expect("list");
-    assert_eq!(list.header("plasmite-version"), Some("0"));
+    let dry_run = cmd()
+        .args([
+            "--dir",
+            dir,
+            "pool",
+            "import",
+            "restored",
+            "--from",
+            out.to_str().unwrap(),
+            "--dry-run",
+            "--json",
+        ])
+        .output()
+        .expect("import dry run");
+    assert!(dry_run.status.success());
+    let report = parse_json(std::str::from_utf8(&dry_run.stdout).expect("utf8"));
+    assert_eq!(report["dry_run"], json!(true));
+    assert_eq!(report["import"]["rows"], json!(2));
+    assert_eq!(report["import"]["oldest_seq"], json!(1));
+    assert_eq!(report["import"]["newest_seq"], json!(2));
+    let fetch = cmd()
+        .args(["--dir", dir, "fetch", "restored", "1"])
+        .output()
+        .expect("fetch");
+    assert_eq!(fetch.status.code(), Some(3), "dry run appends nothing");
 
-    let tail_url = format!("{}/v0/pools/demo/tail?timeout_ms=10", server.base_url);
-    let tail = ureq::get(&tail_url).call().expect("tail");
-    assert_eq!(tail.header("plasmite-version"), Some("0"));
+    let import = cmd()
+        .args([
+            "--dir",
+            dir,
+            "pool",
+            "import",
+            "restored",
+            "--from",
+            out.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("import");
+    assert!(import.status.success());
+    let report = parse_json(std::str::from_utf8(&import.stdout).expect("utf8"));
+    assert_eq!(report["import"]["imported"], json!(2));
+    assert_eq!(report["import"]["last_seq"], json!(2));
 
-    let health_url = format!("{}/healthz", server.base_url);
-    let health = ureq::get(&health_url).call().expect("healthz");
-    assert_eq!(health.header("plasmite-version"), Some("0"));
-    let body: serde_json::Value =
-        serde_json::from_str(&health.into_string().expect("body")).expect("healthz json");
-    assert_eq!(body.get("ok").and_then(|v| v.as_bool()), Some(true));
+    let fetch = cmd()
+        .args(["--dir", dir, "fetch", "restored", "2"])
+        .output()
+        .expect("fetch");
+    assert!(fetch.status.success());
+    let message = parse_json(std::str::from_utf8(&fetch.stdout).expect("utf8"));
+    assert_eq!(message["data"], json!({"n": 2}));
+    assert_eq!(message["meta"]["tags"], json!(["audit"]));
 }
 
 #[test]
-fn serve_rejects_oversized_body() {
+fn sql_queries_pool_tables_or_reports_missing_feature() {
     let temp = tempfile::tempdir().expect("tempdir");
-    let pool_dir = temp.path().join("pools");
+    let dir = temp.path().to_str().unwrap();
+    for level in ["info", "error", "info"] {
+        let feed = cmd()
+            .args([
+                "--dir",
+                dir,
+                "feed",
+                "events",
+                "--create",
+                &format!("{{\"level\":\"{level}\"}}"),
+            ])
+            .output()
+            .expect("feed");
+        assert!(feed.status.success());
+    }
 
-    let create = cmd()
+    let output = cmd()
         .args([
             "--dir",
-            pool_dir.to_str().unwrap(),
-            "pool",
-            "create",
-            "demo",
+            dir,
+            "sql",
+            "SELECT data->>'level' AS level, count(*) AS n FROM events GROUP BY 1 ORDER BY 1",
+            "--pool",
+            "events",
+            "--json",
         ])
         .output()
-        .expect("create");
-    assert!(create.status.success());
-
-    let server = ServeProcess::start_with_args(&pool_dir, &["--max-body-bytes", "64"]);
-    let append_url = format!("{}/v0/pools/demo/append", server.base_url);
-    let payload = json!({
-        "data": { "big": "x".repeat(256) },
-        "tags": ["oversized"],
-        "durability": "fast"
-    })
-    .to_string();
+        .expect("sql");
+    if cfg!(feature = "sql") {
+        assert!(output.status.success());
+        let value = parse_json(std::str::from_utf8(&output.stdout).expect("utf8"));
+        assert_eq!(value["columns"], json!(["level", "n"]));
+        assert_eq!(value["rows"], json!([["error", 1], ["info", 2]]));
+    } else {
+        assert_eq!(output.status.code(), Some(2));
+    }
+}
 
-    match ureq::post(&append_url)
-        .set("Content-Type", "application/json")
-        .send_string(&payload)
-    {
-        Ok(_) => panic!("expected 413 for oversized body"),
-        Err(ureq::Error::Status(code, _resp)) => {
-            assert_eq!(code, 413);
+#[cfg(feature = "sql")]
+#[test]
+fn sql_joins_pools_and_reports_query_errors() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path().to_str().unwrap();
+    for (pool, messages) in [
+        ("a", [r#"{"n":1}"#, r#"{"n":2}"#]),
+        ("b", [r#"{"label":"x"}"#, r#"{"label":"y"}"#]),
+    ] {
+        let create = cmd()
+            .args(["--dir", dir, "pool", "create", pool])
+            .output()
+            .expect("create");
+        assert!(create.status.success());
+        for message in messages {
+            let feed = cmd()
+                .args(["--dir", dir, "feed", pool, message])
+                .output()
+                .expect("feed");
+            assert!(feed.status.success());
         }
-        Err(err) => panic!("request failed: {err:?}"),
     }
+
+    let join = cmd()
+        .args([
+            "--dir",
+            dir,
+            "sql",
+            "SELECT seq, a.data->>'n' AS n, b.data->>'label' AS label FROM a JOIN b USING (seq) ORDER BY seq",
+            "--pool",
+            "a",
+            "--pool",
+            "b",
+            "--json",
+        ])
+        .output()
+        .expect("sql join");
+    assert!(join.status.success());
+    let value = parse_json(std::str::from_utf8(&join.stdout).expect("utf8"));
+    assert_eq!(value["columns"], json!(["seq", "n", "label"]));
+    assert_eq!(value["rows"], json!([[1, "1", "x"], [2, "2", "y"]]));
+
+    let bad = cmd()
+        .args(["--dir", dir, "sql", "SELECT nope FROM a", "--pool", "a"])
+        .output()
+        .expect("sql error");
+    assert_eq!(bad.status.code(), Some(2));
 }
 
 #[test]
@@ -6485,6 +11535,90 @@ fn follow_replay_tail_limits_messages() {
     assert_eq!(messages[1]["data"]["i"], 5);
 }
 
+#[test]
+fn follow_replay_stops_at_until_and_paces_by_event_time() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let pool_dir = temp.path().join("pools");
+
+    cmd()
+        .args(["--dir", pool_dir.to_str().unwrap(), "pool", "create", "rpu"])
+        .output()
+        .expect("create");
+    for (i, ts) in [(1, "10.0"), (2, "10.3"), (3, "10.3"), (4, "99.0")] {
+        cmd()
+            .args([
+                "--dir",
+                pool_dir.to_str().unwrap(),
+                "feed",
+                "rpu",
+                &format!("{{\"i\":{i},\"ts\":{ts}}}"),
+            ])
+            .output()
+            .expect("feed");
+    }
+
+    let start = Instant::now();
+    let output = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "rpu",
+            "--tail",
+            "100",
+            "--replay",
+            "1",
+            "--until",
+            "3",
+            "--pace-by",
+            ".data.ts",
+            "--jsonl",
+        ])
+        .output()
+        .expect("follow --replay --until");
+    let elapsed = start.elapsed();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let messages = parse_json_lines(&output.stdout);
+    let seen: Vec<_> = messages.iter().map(|m| m["data"]["i"].clone()).collect();
+    assert_eq!(seen, vec![json!(1), json!(2), json!(3)]);
+    assert!(
+        elapsed >= Duration::from_millis(250),
+        "pacing by .data.ts should wait ~300ms, took {elapsed:?}"
+    );
+    assert!(
+        elapsed < Duration::from_secs(20),
+        "seq 4 is past --until and must not be paced, took {elapsed:?}"
+    );
+
+    let bad = cmd()
+        .args([
+            "--dir",
+            pool_dir.to_str().unwrap(),
+            "follow",
+            "rpu",
+            "--tail",
+            "1",
+            "--replay",
+            "1",
+            "--speed-ramp",
+            "0..8",
+        ])
+        .output()
+        .expect("follow --speed-ramp");
+    assert_eq!(bad.status.code(), Some(2));
+    let err = parse_error_json(&bad.stderr);
+    assert!(
+        err["error"]["message"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("--speed-ramp")
+    );
+}
+
 #[test]
 fn follow_replay_respects_speed_timing() {
     let temp = tempfile::tempdir().expect("tempdir");
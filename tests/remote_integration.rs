@@ -31,19 +31,19 @@ struct TestServer {
 
 impl TestServer {
     fn start(pool_dir: &std::path::Path) -> TestResult<Self> {
-        Self::start_with_options(pool_dir, None, None, &[])
+        Self::start_with_options(pool_dir, None, None, &[], &[])
     }
 
     fn start_with_token(pool_dir: &std::path::Path, token: Option<&str>) -> TestResult<Self> {
-        Self::start_with_options(pool_dir, token, None, &[])
+        Self::start_with_options(pool_dir, token, None, &[], &[])
     }
 
     fn start_with_access(pool_dir: &std::path::Path, access: &str) -> TestResult<Self> {
-        Self::start_with_options(pool_dir, None, Some(access), &[])
+        Self::start_with_options(pool_dir, None, Some(access), &[], &[])
     }
 
     fn start_with_cors(pool_dir: &std::path::Path, cors_origins: &[&str]) -> TestResult<Self> {
-        Self::start_with_options(pool_dir, None, None, cors_origins)
+        Self::start_with_options(pool_dir, None, None, cors_origins, &[])
     }
 
     fn start_with_options(
@@ -51,6 +51,7 @@ impl TestServer {
         token: Option<&str>,
         access: Option<&str>,
         cors_origins: &[&str],
+        extra_args: &[&str],
     ) -> TestResult<Self> {
         let guard = SERVER_LOCK
             .lock()
@@ -79,6 +80,7 @@ impl TestServer {
             for origin in cors_origins {
                 command.arg("--cors-origin").arg(origin);
             }
+            command.args(extra_args);
             let mut child = command.spawn()?;
 
             match wait_for_server(&mut child, bind.parse()?) {
@@ -249,6 +251,48 @@ fn remote_tail_streams_in_order() -> TestResult<()> {
     Ok(())
 }
 
+#[test]
+fn remote_tail_min_seq_waits_for_the_append_token() -> TestResult<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let server = TestServer::start(temp_dir.path())?;
+    let client = server.client()?;
+    let pool_ref = PoolRef::name("ryw");
+    client.create_pool(&pool_ref, PoolOptions::new(1024 * 1024))?;
+
+    let url = format!("{}/v0/pools/ryw/append", server.base_url);
+    let receipt: Value = ureq::post(&url)
+        .send_json(json!({"data": {"n": 1}}))?
+        .into_json()?;
+    assert_eq!(receipt["min_seq"], json!(1));
+    assert_eq!(receipt["min_seq"], receipt["message"]["seq"]);
+
+    let pool = client.open_pool(&pool_ref)?;
+    let options = |min_seq, timeout_ms| TailOptions {
+        since_seq: Some(1),
+        min_seq: Some(min_seq),
+        timeout: Some(Duration::from_millis(timeout_ms)),
+        ..TailOptions::default()
+    };
+    let mut tail = pool.tail(options(3, 100))?;
+    let err = tail.next_message().expect_err("seq 3 never arrives");
+    assert_eq!(err.kind(), ErrorKind::Busy);
+
+    let writer = {
+        let pool = client.open_pool(&pool_ref)?;
+        std::thread::spawn(move || {
+            sleep(Duration::from_millis(100));
+            pool.append_json_now(&json!({"n": 2}), &[], Durability::Fast)
+        })
+    };
+    let mut tail = pool.tail(options(2, 5_000))?;
+    let started = Instant::now();
+    assert_eq!(tail.next_message()?.expect("first").seq, 1);
+    assert!(started.elapsed() >= Duration::from_millis(50));
+    assert_eq!(tail.next_message()?.expect("second").seq, 2);
+    writer.join().expect("writer")?;
+    Ok(())
+}
+
 #[test]
 fn remote_tail_reconnects_with_stable_since_seq_without_duplicates() -> TestResult<()> {
     let temp_dir = tempfile::tempdir()?;
@@ -405,6 +449,44 @@ fn remote_tail_cancel_under_active_writes_is_prompt() -> TestResult<()> {
     Ok(())
 }
 
+#[test]
+fn remote_append_batch_streams_per_record_receipts() -> TestResult<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let server = TestServer::start(temp_dir.path())?;
+    let client = server.client()?;
+    let pool_ref = PoolRef::name("imports");
+    client.create_pool(&pool_ref, PoolOptions::new(64 * 1024))?;
+    let pool = client.open_pool(&pool_ref)?;
+
+    let records = vec![
+        json!({"n": 1}),
+        json!({"blob": "x".repeat(256 * 1024)}),
+        json!({"n": 3}),
+    ];
+    let tags = vec!["import".to_string()];
+    let mut receipts = pool.append_batch(&records, &tags, Durability::Fast)?;
+    let mut results = Vec::new();
+    while let Some(receipt) = receipts.next_receipt()? {
+        results.push(receipt);
+    }
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results.iter().map(|r| r.index).collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    let first = results[0].result.as_ref().expect("first record appended");
+    assert_eq!(first.data, json!({"n": 1}));
+    assert_eq!(first.meta.tags, tags);
+    assert!(results[1].result.is_err(), "oversized record is rejected");
+    let third = results[2]
+        .result
+        .as_ref()
+        .expect("later records still appended");
+    assert!(third.seq > first.seq);
+    assert_eq!(pool.get_message(third.seq)?.data, json!({"n": 3}));
+    Ok(())
+}
+
 #[test]
 fn remote_errors_propagate_kind() -> TestResult<()> {
     let temp_dir = tempfile::tempdir()?;
@@ -415,6 +497,49 @@ fn remote_errors_propagate_kind() -> TestResult<()> {
         Err(err) => err,
     };
     assert_eq!(err.kind(), ErrorKind::NotFound);
+    let request_id = err.request_id().expect("request id on remote error");
+    assert!(!request_id.is_empty());
+
+    let response = ureq::get(&format!("{}/v0/pools", server.base_url))
+        .set("x-plasmite-request-id", "trace-42")
+        .call()?;
+    assert_eq!(response.header("x-plasmite-request-id"), Some("trace-42"));
+    Ok(())
+}
+
+#[test]
+fn remote_framework_errors_use_error_envelope() -> TestResult<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let server = TestServer::start(temp_dir.path())?;
+    let client = server.client()?;
+    client.create_pool(&PoolRef::name("alpha"), PoolOptions::new(1024 * 1024))?;
+
+    let url = format!("{}/v0/pools/alpha/append", server.base_url);
+    let (code, body) = match ureq::post(&url)
+        .set("Content-Type", "application/json")
+        .send_string("{not json")
+    {
+        Ok(_) => return Err("expected malformed body rejection".into()),
+        Err(ureq::Error::Status(code, resp)) => (code, resp.into_string()?),
+        Err(err) => return Err(err.into()),
+    };
+    assert_eq!(code, 400);
+    let payload: Value = serde_json::from_str(&body)?;
+    assert_eq!(payload["error"]["kind"], "Usage");
+    assert!(
+        payload["error"]["message"]
+            .as_str()
+            .is_some_and(|m| !m.is_empty())
+    );
+
+    let missing = format!("{}/v0/no-such-route", server.base_url);
+    match ureq::get(&missing).call() {
+        Err(ureq::Error::Status(404, resp)) => {
+            let payload: Value = serde_json::from_str(&resp.into_string()?)?;
+            assert_eq!(payload["error"]["kind"], "NotFound");
+        }
+        other => return Err(format!("expected 404 envelope, got {:?}", other.is_ok()).into()),
+    }
     Ok(())
 }
 
@@ -744,6 +869,48 @@ fn remote_ui_events_stream_sends_sse_and_requires_auth() -> TestResult<()> {
     Ok(())
 }
 
+#[test]
+fn remote_ui_events_apply_where_and_since_filters() -> TestResult<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let server = TestServer::start(temp_dir.path())?;
+    let client = server.client()?;
+    let pool_ref = PoolRef::name("ui-filters");
+
+    client.create_pool(&pool_ref, PoolOptions::new(1024 * 1024))?;
+    let pool = client.open_pool(&pool_ref)?;
+    for n in 1..=3 {
+        pool.append_json_now(&json!({"n": n}), &[], Durability::Fast)?;
+    }
+
+    let events = format!("{}/v0/ui/pools/ui-filters/events", server.base_url);
+    let body = ureq::get(&events)
+        .query("since_seq", "1")
+        .query("max", "3")
+        .query("where", ".data.n >= 2")
+        .call()
+        .expect("filtered sse request")
+        .into_string()?;
+    assert!(!body.contains("\"seq\":1,"));
+    assert!(body.contains("\"seq\":2"));
+    assert!(body.contains("\"seq\":3"));
+
+    // `since` resolves to the first retained message newer than the cutoff.
+    let body = ureq::get(&events)
+        .query("since", "1h")
+        .query("max", "1")
+        .call()
+        .expect("since sse request")
+        .into_string()?;
+    assert!(body.contains("\"seq\":1"));
+
+    match ureq::get(&events).query("where", ".data.n >=").call() {
+        Ok(_) => return Err("expected invalid where filter to fail".into()),
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 400),
+        Err(err) => return Err(err.into()),
+    }
+    Ok(())
+}
+
 #[test]
 fn remote_ui_routes_emit_cors_headers_for_allowed_origin() -> TestResult<()> {
     let temp_dir = tempfile::tempdir()?;
@@ -1281,6 +1448,53 @@ fn remote_mcp_access_mode_restricts_tools() -> TestResult<()> {
     Ok(())
 }
 
+#[test]
+fn remote_otlp_receiver_appends_logs_and_rejects_protobuf() -> TestResult<()> {
+    let temp = tempfile::tempdir()?;
+    let pool_dir = temp.path();
+    let otlp_bind = format!("127.0.0.1:{}", pick_port()?);
+    let _server =
+        TestServer::start_with_options(pool_dir, None, None, &[], &["--otlp-bind", &otlp_bind])?;
+    let logs_url = format!("http://{otlp_bind}/v1/logs");
+
+    let export = json!({
+        "resourceLogs": [{
+            "resource": {"attributes": [
+                {"key": "service.name", "value": {"stringValue": "checkout"}}
+            ]},
+            "scopeLogs": [{
+                "logRecords": [{
+                    "timeUnixNano": "1700000000000000000",
+                    "severityText": "WARN",
+                    "body": {"stringValue": "slow request"}
+                }]
+            }]
+        }]
+    });
+    let resp = ureq::post(&logs_url)
+        .set("Content-Type", "application/json")
+        .send_string(&export.to_string())?;
+    assert_eq!(resp.status(), 200);
+
+    let pool = LocalClient::new()
+        .with_pool_dir(pool_dir)
+        .open_pool(&PoolRef::name("otlp-logs"))?;
+    let message = pool.get_message(1)?;
+    assert_eq!(message.data["body"], json!("slow request"));
+    assert_eq!(message.data["severity"], json!("WARN"));
+    assert_eq!(message.meta.tags, vec!["otlp", "log", "service:checkout"]);
+
+    match ureq::post(&logs_url)
+        .set("Content-Type", "application/x-protobuf")
+        .send_bytes(&[0x0a, 0x00])
+    {
+        Ok(resp) => panic!("expected 415, got {}", resp.status()),
+        Err(ureq::Error::Status(code, _)) => assert_eq!(code, 415),
+        Err(err) => return Err(err.into()),
+    }
+    Ok(())
+}
+
 fn pick_port() -> TestResult<u16> {
     let listener = TcpListener::bind("127.0.0.1:0")?;
     let port = listener.local_addr()?.port();